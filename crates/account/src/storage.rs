@@ -121,3 +121,22 @@ where
     }
     Ok(())
 }
+
+/// Replace the public keys and the threshold of an established account,
+/// without updating its validity predicate
+pub fn update_account_keys<S>(
+    storage: &mut S,
+    data: &UpdateAccountKeys,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    if !matches!(data.addr, Address::Established(_)) {
+        return Err(namada_storage::Error::new_const(
+            "Only the keys of an established account can be updated",
+        ));
+    }
+    data.validate().map_err(namada_storage::Error::new_const)?;
+    clear_public_keys(storage, &data.addr)?;
+    init_account_storage(storage, &data.addr, &data.public_keys, data.threshold)
+}
@@ -1,5 +1,6 @@
 use namada_core::types::address::Address;
 use namada_core::types::key::common;
+use namada_core::types::key_space::{ModuleKeySpace, PrefixSeg};
 use namada_core::types::storage::{self, DbKeySeg};
 use namada_macros::StorageKeys;
 use namada_storage::collections::lazy_map::LazyMap;
@@ -13,6 +14,18 @@ struct Keys {
     protocol_public_keys: &'static str,
 }
 
+/// The key prefixes reserved by accounts
+pub fn key_space() -> ModuleKeySpace {
+    Keys::ALL
+        .iter()
+        .fold(ModuleKeySpace::new("account"), |key_space, key| {
+            key_space.reserve(
+                [PrefixSeg::AnyAddress, PrefixSeg::Str(key)],
+                "Public keys and signature thresholds of accounts",
+            )
+        })
+}
+
 /// Obtain a storage key for user's public key.
 pub fn pks_key_prefix(owner: &Address) -> storage::Key {
     storage::Key {
@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+
 use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use namada_core::types::address::Address;
 use namada_core::types::hash::Hash;
@@ -50,6 +52,52 @@ pub struct UpdateAccount {
     pub threshold: Option<u8>,
 }
 
+/// A tx data type to replace the public keys and the signature threshold of an
+/// established account, without updating its validity predicate
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct UpdateAccountKeys {
+    /// An address of the account
+    pub addr: Address,
+    /// The new public keys of the account
+    pub public_keys: Vec<common::PublicKey>,
+    /// The new account signature threshold
+    pub threshold: u8,
+}
+
+impl UpdateAccountKeys {
+    /// Check that the public keys are unique and can be indexed, and that the
+    /// threshold can be met by them
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.public_keys.is_empty() {
+            return Err("The account must have at least one public key");
+        }
+        if self.public_keys.len() > u8::MAX as usize {
+            return Err("The account cannot have more than 255 public keys");
+        }
+        if self.threshold == 0
+            || self.threshold as usize > self.public_keys.len()
+        {
+            return Err(
+                "The threshold must be between 1 and the number of public keys"
+            );
+        }
+        let unique_keys: BTreeSet<_> = self.public_keys.iter().collect();
+        if unique_keys.len() != self.public_keys.len() {
+            return Err("The public keys of the account must be unique");
+        }
+        Ok(())
+    }
+}
+
 #[cfg(any(test, feature = "testing"))]
 /// Tests and strategies for accounts
 pub mod tests {
@@ -96,4 +144,83 @@ pub mod tests {
             }
         }
     }
+
+    prop_compose! {
+        /// Generate an arbitrary valid update of the keys of an account
+        pub fn arb_update_account_keys()(
+            public_keys in collection::btree_set(arb_common_pk(), 1..10),
+        )(
+            addr in arb_non_internal_address(),
+            threshold in 1..=public_keys.len() as u8,
+            public_keys in Just(public_keys.into_iter().collect()),
+        ) -> UpdateAccountKeys {
+            UpdateAccountKeys {
+                addr,
+                public_keys,
+                threshold,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_update_account_keys {
+    use namada_core::types::address::testing::established_address_1;
+    use namada_core::types::key::testing::{keypair_1, keypair_2};
+    use namada_core::types::key::RefTo;
+    use proptest::prelude::*;
+
+    use super::tests::arb_update_account_keys;
+    use super::*;
+
+    proptest! {
+        /// Check that any generated update of the keys of an account is
+        /// valid, and that it becomes invalid once its threshold can't be met
+        /// or one of its keys is duplicated
+        #[test]
+        fn test_validate_arb_update_account_keys(
+            update in arb_update_account_keys(),
+        ) {
+            assert!(update.validate().is_ok());
+
+            let mut no_threshold = update.clone();
+            no_threshold.threshold = 0;
+            assert!(no_threshold.validate().is_err());
+
+            let mut unmet_threshold = update.clone();
+            unmet_threshold.threshold = update.public_keys.len() as u8 + 1;
+            assert!(unmet_threshold.validate().is_err());
+
+            let mut duplicate_key = update.clone();
+            duplicate_key.public_keys.push(update.public_keys[0].clone());
+            assert!(duplicate_key.validate().is_err());
+        }
+    }
+
+    #[test]
+    fn test_validate_update_account_keys() {
+        let pk_1 = keypair_1().ref_to();
+        let pk_2 = keypair_2().ref_to();
+        let update = |public_keys: Vec<common::PublicKey>, threshold| {
+            UpdateAccountKeys {
+                addr: established_address_1(),
+                public_keys,
+                threshold,
+            }
+        };
+
+        assert!(update(vec![pk_1.clone(), pk_2.clone()], 2)
+            .validate()
+            .is_ok());
+        assert!(update(vec![pk_1.clone(), pk_2.clone()], 1)
+            .validate()
+            .is_ok());
+        // no keys
+        assert!(update(vec![], 0).validate().is_err());
+        // a threshold that can't be met
+        assert!(update(vec![pk_1.clone(), pk_2], 3).validate().is_err());
+        assert!(update(vec![pk_1.clone()], 0).validate().is_err());
+        // duplicate keys
+        assert!(update(vec![pk_1.clone(), pk_1], 1).validate().is_err());
+    }
 }
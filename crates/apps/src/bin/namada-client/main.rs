@@ -13,10 +13,14 @@ async fn main() -> Result<()> {
     let _log_guard = logging::init_from_env_or(LevelFilter::INFO)?;
 
     // run the CLI
-    CliApi::handle_client_command::<HttpClient, _>(
+    let result = CliApi::handle_client_command::<HttpClient, _>(
         None,
         cli::namada_client_cli()?,
         CliIo,
     )
-    .await
+    .await;
+    if let Err(report) = result {
+        cli::exit::exit_with_failure(report)
+    }
+    Ok(())
 }
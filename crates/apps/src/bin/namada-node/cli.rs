@@ -1,19 +1,26 @@
 //! Namada node CLI.
 
 use eyre::{Context, Result};
+use namada::tendermint_rpc::HttpClient;
 use namada::types::time::{DateTimeUtc, Utc};
+use namada_apps::cli::api::CliIo;
 use namada_apps::cli::{self, cmds};
+use namada_apps::client::utils::take_config_address;
 use namada_apps::config::ValidatorLocalConfig;
+use namada_apps::node::faucet::{self, FaucetConfig};
 use namada_apps::node::ledger;
 
-pub fn main() -> Result<()> {
-    let (cmd, mut ctx) = cli::namada_node_cli()?;
+pub fn main(cmd: cmds::NamadaNode, mut ctx: cli::Context) -> Result<()> {
     match cmd {
         cmds::NamadaNode::Ledger(sub) => match sub {
             cmds::Ledger::Run(cmds::LedgerRun(args)) => {
-                let chain_ctx = ctx.take_chain_or_exit();
+                let mut chain_ctx = ctx.take_chain_or_exit();
                 let wasm_dir = chain_ctx.wasm_dir();
                 sleep_until(args.start_time);
+                if args.read_only {
+                    tracing::info!("Running the node in read-only mode");
+                    chain_ctx.config.ledger.set_read_only(args.trusted_peer);
+                }
                 ledger::run(chain_ctx.config.ledger, wasm_dir);
             }
             cmds::Ledger::RunUntil(cmds::LedgerRunUntil(args)) => {
@@ -76,6 +83,70 @@ pub fn main() -> Result<()> {
                 std::fs::write(config_path, updated_config).unwrap();
             }
         },
+        cmds::NamadaNode::Faucet(cmds::Faucet(mut args)) => {
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            let config = FaucetConfig {
+                listen_address: args.listen_address,
+                source: chain_ctx.get(&args.source),
+                token: chain_ctx.get(&args.token),
+                amount: args.amount,
+                quota: args.quota,
+                quota_period: args.quota_period.0,
+                difficulty: args.difficulty,
+                data_dir: chain_ctx.config.ledger.chain_dir().join("faucet"),
+            };
+            let client =
+                HttpClient::new(take_config_address(&mut args.ledger_address))
+                    .wrap_err("Failed to connect to the ledger node")?;
+            let namada = ctx.to_sdk(client, CliIo);
+            faucet::run(namada, config).wrap_err("The faucet failed")?;
+        }
+        cmds::NamadaNode::Db(cmds::Db::Query(cmds::DbQuery(args))) => {
+            let chain_ctx = ctx.take_chain_or_exit();
+            ledger::query_db(chain_ctx.config.ledger, args)
+                .wrap_err("Failed to query the DB")?;
+        }
+        cmds::NamadaNode::Db(cmds::Db::Diff(cmds::DbDiff(args))) => {
+            let chain_ctx = ctx.take_chain_or_exit();
+            ledger::diff_db(chain_ctx.config.ledger, args)
+                .wrap_err("Failed to diff the DB")?;
+        }
+        cmds::NamadaNode::EthereumOracle(cmds::EthereumOracle::Status(_)) => {
+            let chain_ctx = ctx.take_chain_or_exit();
+            ledger::ethereum_oracle_status(chain_ctx.config.ledger).wrap_err(
+                "Failed to read the stats of the Ethereum endpoints",
+            )?;
+        }
+        cmds::NamadaNode::Utils(cmds::NodeUtils::CalibrateGas(
+            cmds::CalibrateGas(args),
+        )) => {
+            ledger::calibrate_gas(args)
+                .wrap_err("Failed to calibrate the gas costs")?;
+        }
+        cmds::NamadaNode::Utils(cmds::NodeUtils::DumpKeyspace(
+            cmds::DumpKeyspace,
+        )) => {
+            ledger::dump_keyspace()
+                .wrap_err("Failed to build the storage key space")?;
+        }
+        cmds::NamadaNode::Utils(cmds::NodeUtils::GenerateSentryConfig(
+            cmds::GenerateSentryConfig(args),
+        )) => {
+            ledger::generate_sentry_config(args)
+                .wrap_err("Failed to generate the sentry configs")?;
+        }
+        cmds::NamadaNode::Debug(cmds::NodeDebug::MempoolRejections(
+            cmds::DebugMempoolRejections(args),
+        )) => {
+            ledger::mempool_rejections(args)?;
+        }
+        cmds::NamadaNode::Debug(cmds::NodeDebug::VoteTally(
+            cmds::DebugVoteTally(args),
+        )) => {
+            let chain_ctx = ctx.take_chain_or_exit();
+            ledger::vote_tally(chain_ctx.config.ledger, args)
+                .wrap_err("Failed to validate the vote tally")?;
+        }
     }
     Ok(())
 }
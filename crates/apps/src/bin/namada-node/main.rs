@@ -8,9 +8,17 @@ fn main() -> Result<()> {
     // init error reporting
     color_eyre::install()?;
 
+    // parse the CLI and load the config
+    let (cmd, ctx) = namada_apps::cli::namada_node_cli()?;
+
     // init logging
-    let _log_guard = logging::init_from_env_or(LevelFilter::INFO)?;
+    let tracing_config =
+        ctx.chain.as_ref().map(|chain| &chain.config.ledger.tracing);
+    let _log_guards = logging::init_from_env_or_with_tracing(
+        LevelFilter::INFO,
+        tracing_config,
+    )?;
 
     // run the CLI
-    cli::main()
+    cli::main(cmd, ctx)
 }
@@ -45,6 +45,7 @@ fn handle_command(cmd: cli::cmds::Namada, raw_sub_cmd: String) -> Result<()> {
         }
         cli::cmds::Namada::Client(_)
         | cli::cmds::Namada::TxCustom(_)
+        | cli::cmds::Namada::TxBuild(_)
         | cli::cmds::Namada::TxTransfer(_)
         | cli::cmds::Namada::TxIbcTransfer(_)
         | cli::cmds::Namada::TxUpdateAccount(_)
@@ -143,10 +144,11 @@ mod imp {
             .status()
             .wrap_err_with(|| eyre!("Could not execute command {}", program))?;
 
-        if exit.success() {
-            Ok(())
-        } else {
-            Err(eyre!("Command {} failed.", program))
+        match exit.code() {
+            Some(0) => Ok(()),
+            // Forward the exit code of the sub-command
+            Some(code) => std::process::exit(code),
+            None => Err(eyre!("Command {} failed.", program)),
         }
     }
 }
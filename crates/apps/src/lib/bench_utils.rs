@@ -4,9 +4,8 @@
 use std::fs::{File, OpenOptions};
 use std::io::{Read, Write};
 use std::ops::{Deref, DerefMut};
-use std::path::PathBuf;
 use std::str::FromStr;
-use std::sync::Once;
+use std::sync::{Arc, Once};
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use borsh_ext::BorshSerializeExt;
@@ -54,7 +53,8 @@ use namada::ledger::gas::TxGasMeter;
 use namada::ledger::ibc::storage::{channel_key, connection_key};
 use namada::ledger::native_vp::ibc::get_dummy_header;
 use namada::ledger::queries::{
-    Client, EncodedResponseQuery, RequestCtx, RequestQuery, Router, RPC,
+    is_dry_run_tx, Client, EncodedResponseQuery, RequestCtx, RequestQuery,
+    Router, RPC,
 };
 use namada::state::StorageRead;
 use namada::tendermint_rpc::{self};
@@ -626,17 +626,8 @@ pub struct BenchShieldedUtils {
 #[cfg_attr(feature = "async-send", async_trait::async_trait)]
 #[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
 impl ShieldedUtils for BenchShieldedUtils {
-    fn local_tx_prover(&self) -> LocalTxProver {
-        if let Ok(params_dir) = std::env::var(masp::ENV_VAR_MASP_PARAMS_DIR) {
-            let params_dir = PathBuf::from(params_dir);
-            let spend_path = params_dir.join(masp::SPEND_NAME);
-            let convert_path = params_dir.join(masp::CONVERT_NAME);
-            let output_path = params_dir.join(masp::OUTPUT_NAME);
-            LocalTxProver::new(&spend_path, &output_path, &convert_path)
-        } else {
-            LocalTxProver::with_default_location()
-                .expect("unable to load MASP Parameters")
-        }
+    fn local_tx_prover(&self) -> Arc<LocalTxProver> {
+        masp::load_local_tx_prover()
     }
 
     /// Try to load the last saved shielded context from the given context
@@ -720,12 +711,13 @@ impl Client for BenchShell {
         let ctx = RequestCtx {
             wl_storage: &self.wl_storage,
             event_log: self.event_log(),
+            mempool_rejections: self.mempool_rejections(),
             vp_wasm_cache: self.vp_wasm_cache.read_only(),
             tx_wasm_cache: self.tx_wasm_cache.read_only(),
             storage_read_past_height_limit: None,
         };
 
-        if request.path == "/shell/dry_run_tx" {
+        if is_dry_run_tx(&request) {
             dry_run_tx(ctx, &request)
         } else {
             RPC.handle(ctx, &request)
@@ -878,6 +870,9 @@ impl Default for BenchShieldedCtx {
             chain_id: Some(shell.inner.chain_id.clone()),
             base_dir,
             wasm_dir: Some(WASM_DIR.into()),
+            no_progress: true,
+            profile: None,
+            proving_threads: None,
         })
         .unwrap();
 
@@ -960,6 +955,7 @@ impl BenchShieldedCtx {
         async_runtime
             .block_on(self.shielded.fetch(
                 &self.shell,
+                &StdIo,
                 &[spending_key.into()],
                 &[],
             ))
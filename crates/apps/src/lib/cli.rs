@@ -8,7 +8,9 @@
 
 pub mod api;
 pub mod client;
+pub mod completions;
 pub mod context;
+pub mod exit;
 pub mod relayer;
 mod utils;
 pub mod wallet;
@@ -21,6 +23,7 @@ pub use utils::{safe_exit, Cmd};
 
 pub use self::context::Context;
 use crate::cli::api::CliIo;
+use crate::config::profiles::Profiles;
 
 include!("../../version.rs");
 
@@ -57,6 +60,7 @@ pub mod cmds {
 
         // Inlined commands from the client.
         TxCustom(TxCustom),
+        TxBuild(TxBuild),
         TxTransfer(TxTransfer),
         TxIbcTransfer(TxIbcTransfer),
         TxUpdateAccount(TxUpdateAccount),
@@ -91,6 +95,7 @@ pub mod cmds {
             let wallet = SubCmd::parse(matches).map(Self::Wallet);
             let ledger = SubCmd::parse(matches).map(Self::Ledger);
             let tx_custom = SubCmd::parse(matches).map(Self::TxCustom);
+            let tx_build = SubCmd::parse(matches).map(Self::TxBuild);
             let tx_transfer = SubCmd::parse(matches).map(Self::TxTransfer);
             let tx_ibc_transfer =
                 SubCmd::parse(matches).map(Self::TxIbcTransfer);
@@ -107,6 +112,7 @@ pub mod cmds {
                 .or(wallet)
                 .or(ledger)
                 .or(tx_custom)
+                .or(tx_build)
                 .or(tx_transfer)
                 .or(tx_ibc_transfer)
                 .or(tx_update_account)
@@ -123,17 +129,40 @@ pub mod cmds {
     pub enum NamadaNode {
         Ledger(Ledger),
         Config(Config),
+        Faucet(Faucet),
+        Db(Db),
+        EthereumOracle(EthereumOracle),
+        Utils(NodeUtils),
+        Debug(NodeDebug),
     }
 
     impl Cmd for NamadaNode {
         fn add_sub(app: App) -> App {
-            app.subcommand(Ledger::def()).subcommand(Config::def())
+            app.subcommand(Ledger::def())
+                .subcommand(Config::def())
+                .subcommand(Faucet::def())
+                .subcommand(Db::def())
+                .subcommand(EthereumOracle::def())
+                .subcommand(NodeUtils::def())
+                .subcommand(NodeDebug::def())
         }
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             let ledger = SubCmd::parse(matches).map(Self::Ledger);
             let config = SubCmd::parse(matches).map(Self::Config);
-            ledger.or(config)
+            let faucet = SubCmd::parse(matches).map(Self::Faucet);
+            let db = SubCmd::parse(matches).map(Self::Db);
+            let ethereum_oracle =
+                SubCmd::parse(matches).map(Self::EthereumOracle);
+            let utils = SubCmd::parse(matches).map(Self::Utils);
+            let debug = SubCmd::parse(matches).map(Self::Debug);
+            ledger
+                .or(config)
+                .or(faucet)
+                .or(db)
+                .or(ethereum_oracle)
+                .or(utils)
+                .or(debug)
         }
     }
     impl SubCmd for NamadaNode {
@@ -216,12 +245,16 @@ pub mod cmds {
                 .subcommand(TxCustom::def().display_order(1))
                 .subcommand(TxTransfer::def().display_order(1))
                 .subcommand(TxIbcTransfer::def().display_order(1))
+                .subcommand(TxVestingTransfer::def().display_order(1))
+                .subcommand(TxSchedule::def().display_order(1))
                 .subcommand(TxUpdateAccount::def().display_order(1))
+                .subcommand(TxUpdateAccountKeys::def().display_order(1))
                 .subcommand(TxInitAccount::def().display_order(1))
                 .subcommand(TxRevealPk::def().display_order(1))
                 // Governance transactions
                 .subcommand(TxInitProposal::def().display_order(1))
                 .subcommand(TxVoteProposal::def().display_order(1))
+                .subcommand(TxUpdateVoteProxy::def().display_order(1))
                 // PoS transactions
                 .subcommand(TxBecomeValidator::def().display_order(2))
                 .subcommand(TxInitValidator::def().display_order(2))
@@ -233,11 +266,14 @@ pub mod cmds {
                 .subcommand(Withdraw::def().display_order(2))
                 .subcommand(Redelegate::def().display_order(2))
                 .subcommand(ClaimRewards::def().display_order(2))
+                .subcommand(SetAutoCompound::def().display_order(2))
                 .subcommand(TxCommissionRateChange::def().display_order(2))
                 .subcommand(TxChangeConsensusKey::def().display_order(2))
                 .subcommand(TxMetadataChange::def().display_order(2))
                 // Ethereum bridge transactions
                 .subcommand(AddToEthBridgePool::def().display_order(3))
+                .subcommand(TxBridgePauseVote::def().display_order(3))
+                .subcommand(TxConvertNuts::def().display_order(3))
                 // PGF transactions
                 .subcommand(TxUpdateStewardCommission::def().display_order(4))
                 .subcommand(TxResignSteward::def().display_order(4))
@@ -248,37 +284,57 @@ pub mod cmds {
                 .subcommand(QueryConversions::def().display_order(5))
                 .subcommand(QueryMaspRewardTokens::def().display_order(5))
                 .subcommand(QueryBlock::def().display_order(5))
+                .subcommand(QueryChainTiming::def().display_order(5))
                 .subcommand(QueryBalance::def().display_order(5))
+                .subcommand(QueryBalances::def().display_order(5))
+                .subcommand(QueryVesting::def().display_order(5))
+                .subcommand(QueryIbcDenom::def().display_order(5))
                 .subcommand(QueryBonds::def().display_order(5))
                 .subcommand(QueryBondedStake::def().display_order(5))
                 .subcommand(QuerySlashes::def().display_order(5))
                 .subcommand(QueryDelegations::def().display_order(5))
+                .subcommand(QueryStakingOverview::def().display_order(5))
                 .subcommand(QueryFindValidator::def().display_order(5))
+                .subcommand(QueryProposer::def().display_order(5))
+                .subcommand(QueryValidatorSet::def().display_order(5))
+                .subcommand(QueryBridgePoolFees::def().display_order(5))
+                .subcommand(QueryTransferStatus::def().display_order(5))
+                .subcommand(QueryNutBalances::def().display_order(5))
                 .subcommand(QueryResult::def().display_order(5))
                 .subcommand(QueryRawBytes::def().display_order(5))
+                .subcommand(QueryStorageValue::def().display_order(5))
                 .subcommand(QueryProposal::def().display_order(5))
                 .subcommand(QueryProposalVotes::def().display_order(5))
                 .subcommand(QueryProposalResult::def().display_order(5))
                 .subcommand(QueryProtocolParameters::def().display_order(5))
                 .subcommand(QueryPgf::def().display_order(5))
+                .subcommand(QueryFeeReport::def().display_order(5))
                 .subcommand(QueryValidatorState::def().display_order(5))
                 .subcommand(QueryCommissionRate::def().display_order(5))
                 .subcommand(QueryRewards::def().display_order(5))
                 .subcommand(QueryMetaData::def().display_order(5))
                 // Actions
                 .subcommand(SignTx::def().display_order(6))
+                .subcommand(BroadcastSignedTx::def().display_order(6))
                 .subcommand(GenIbcShieldedTransafer::def().display_order(6))
                 // Utils
+                .subcommand(Profile::def().display_order(7))
                 .subcommand(Utils::def().display_order(7))
         }
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             use NamadaClientWithContext::*;
             let tx_custom = Self::parse_with_ctx(matches, TxCustom);
+            let tx_build = Self::parse_with_ctx(matches, TxBuild);
             let tx_transfer = Self::parse_with_ctx(matches, TxTransfer);
             let tx_ibc_transfer = Self::parse_with_ctx(matches, TxIbcTransfer);
+            let tx_vesting_transfer =
+                Self::parse_with_ctx(matches, TxVestingTransfer);
+            let tx_schedule = Self::parse_with_ctx(matches, TxSchedule);
             let tx_update_account =
                 Self::parse_with_ctx(matches, TxUpdateAccount);
+            let tx_update_account_keys =
+                Self::parse_with_ctx(matches, TxUpdateAccountKeys);
             let tx_init_account = Self::parse_with_ctx(matches, TxInitAccount);
             let tx_become_validator =
                 Self::parse_with_ctx(matches, TxBecomeValidator);
@@ -295,6 +351,8 @@ pub mod cmds {
                 Self::parse_with_ctx(matches, TxInitProposal);
             let tx_vote_proposal =
                 Self::parse_with_ctx(matches, TxVoteProposal);
+            let tx_update_vote_proxy =
+                Self::parse_with_ctx(matches, TxUpdateVoteProxy);
             let tx_update_steward_commission =
                 Self::parse_with_ctx(matches, TxUpdateStewardCommission);
             let tx_resign_steward =
@@ -310,6 +368,8 @@ pub mod cmds {
             let withdraw = Self::parse_with_ctx(matches, Withdraw);
             let redelegate = Self::parse_with_ctx(matches, Redelegate);
             let claim_rewards = Self::parse_with_ctx(matches, ClaimRewards);
+            let set_auto_compound =
+                Self::parse_with_ctx(matches, SetAutoCompound);
             let query_epoch = Self::parse_with_ctx(matches, QueryEpoch);
             let query_account = Self::parse_with_ctx(matches, QueryAccount);
             let query_transfers = Self::parse_with_ctx(matches, QueryTransfers);
@@ -318,7 +378,12 @@ pub mod cmds {
             let query_masp_reward_tokens =
                 Self::parse_with_ctx(matches, QueryMaspRewardTokens);
             let query_block = Self::parse_with_ctx(matches, QueryBlock);
+            let query_chain_timing =
+                Self::parse_with_ctx(matches, QueryChainTiming);
             let query_balance = Self::parse_with_ctx(matches, QueryBalance);
+            let query_balances = Self::parse_with_ctx(matches, QueryBalances);
+            let query_vesting = Self::parse_with_ctx(matches, QueryVesting);
+            let query_ibc_denom = Self::parse_with_ctx(matches, QueryIbcDenom);
             let query_bonds = Self::parse_with_ctx(matches, QueryBonds);
             let query_bonded_stake =
                 Self::parse_with_ctx(matches, QueryBondedStake);
@@ -326,10 +391,23 @@ pub mod cmds {
             let query_rewards = Self::parse_with_ctx(matches, QueryRewards);
             let query_delegations =
                 Self::parse_with_ctx(matches, QueryDelegations);
+            let query_staking_overview =
+                Self::parse_with_ctx(matches, QueryStakingOverview);
             let query_find_validator =
                 Self::parse_with_ctx(matches, QueryFindValidator);
+            let query_proposer = Self::parse_with_ctx(matches, QueryProposer);
+            let query_validator_set =
+                Self::parse_with_ctx(matches, QueryValidatorSet);
+            let query_bridge_pool_fees =
+                Self::parse_with_ctx(matches, QueryBridgePoolFees);
+            let query_transfer_status =
+                Self::parse_with_ctx(matches, QueryTransferStatus);
+            let query_nut_balances =
+                Self::parse_with_ctx(matches, QueryNutBalances);
             let query_result = Self::parse_with_ctx(matches, QueryResult);
             let query_raw_bytes = Self::parse_with_ctx(matches, QueryRawBytes);
+            let query_storage_value =
+                Self::parse_with_ctx(matches, QueryStorageValue);
             let query_proposal = Self::parse_with_ctx(matches, QueryProposal);
             let query_proposal_votes =
                 Self::parse_with_ctx(matches, QueryProposalVotes);
@@ -338,6 +416,8 @@ pub mod cmds {
             let query_protocol_parameters =
                 Self::parse_with_ctx(matches, QueryProtocolParameters);
             let query_pgf = Self::parse_with_ctx(matches, QueryPgf);
+            let query_fee_report =
+                Self::parse_with_ctx(matches, QueryFeeReport);
             let query_validator_state =
                 Self::parse_with_ctx(matches, QueryValidatorState);
             let query_commission =
@@ -345,18 +425,30 @@ pub mod cmds {
             let query_metadata = Self::parse_with_ctx(matches, QueryMetaData);
             let add_to_eth_bridge_pool =
                 Self::parse_with_ctx(matches, AddToEthBridgePool);
+            let tx_bridge_pause_vote =
+                Self::parse_with_ctx(matches, TxBridgePauseVote);
+            let tx_convert_nuts = Self::parse_with_ctx(matches, TxConvertNuts);
             let sign_tx = Self::parse_with_ctx(matches, SignTx);
+            let broadcast_signed_tx =
+                Self::parse_with_ctx(matches, BroadcastSignedTx);
             let gen_ibc_shielded =
                 Self::parse_with_ctx(matches, GenIbcShieldedTransafer);
+            let profile = SubCmd::parse(matches)
+                .map(|profile| Self::WithoutContext(Utils::Profile(profile)));
             let utils = SubCmd::parse(matches).map(Self::WithoutContext);
             tx_custom
+                .or(tx_build)
                 .or(tx_transfer)
                 .or(tx_ibc_transfer)
+                .or(tx_vesting_transfer)
+                .or(tx_schedule)
                 .or(tx_update_account)
+                .or(tx_update_account_keys)
                 .or(tx_init_account)
                 .or(tx_reveal_pk)
                 .or(tx_init_proposal)
                 .or(tx_vote_proposal)
+                .or(tx_update_vote_proxy)
                 .or(tx_become_validator)
                 .or(tx_init_validator)
                 .or(tx_commission_rate_change)
@@ -370,7 +462,10 @@ pub mod cmds {
                 .or(withdraw)
                 .or(redelegate)
                 .or(claim_rewards)
+                .or(set_auto_compound)
                 .or(add_to_eth_bridge_pool)
+                .or(tx_bridge_pause_vote)
+                .or(tx_convert_nuts)
                 .or(tx_update_steward_commission)
                 .or(tx_resign_steward)
                 .or(query_epoch)
@@ -378,26 +473,40 @@ pub mod cmds {
                 .or(query_conversions)
                 .or(query_masp_reward_tokens)
                 .or(query_block)
+                .or(query_chain_timing)
                 .or(query_balance)
+                .or(query_balances)
+                .or(query_vesting)
+                .or(query_ibc_denom)
                 .or(query_bonds)
                 .or(query_bonded_stake)
                 .or(query_slashes)
                 .or(query_rewards)
                 .or(query_delegations)
+                .or(query_staking_overview)
                 .or(query_find_validator)
+                .or(query_proposer)
+                .or(query_validator_set)
+                .or(query_bridge_pool_fees)
+                .or(query_transfer_status)
+                .or(query_nut_balances)
                 .or(query_result)
                 .or(query_raw_bytes)
+                .or(query_storage_value)
                 .or(query_proposal)
                 .or(query_proposal_votes)
                 .or(query_proposal_result)
                 .or(query_protocol_parameters)
                 .or(query_pgf)
+                .or(query_fee_report)
                 .or(query_validator_state)
                 .or(query_commission)
                 .or(query_metadata)
                 .or(query_account)
                 .or(sign_tx)
+                .or(broadcast_signed_tx)
                 .or(gen_ibc_shielded)
+                .or(profile)
                 .or(utils)
         }
     }
@@ -436,10 +545,14 @@ pub mod cmds {
     pub enum NamadaClientWithContext {
         // Ledger cmds
         TxCustom(TxCustom),
+        TxBuild(TxBuild),
         TxTransfer(TxTransfer),
         TxIbcTransfer(TxIbcTransfer),
+        TxVestingTransfer(TxVestingTransfer),
+        TxSchedule(TxSchedule),
         QueryResult(QueryResult),
         TxUpdateAccount(TxUpdateAccount),
+        TxUpdateAccountKeys(TxUpdateAccountKeys),
         TxInitAccount(TxInitAccount),
         TxBecomeValidator(TxBecomeValidator),
         TxInitValidator(TxInitValidator),
@@ -451,13 +564,17 @@ pub mod cmds {
         TxReactivateValidator(TxReactivateValidator),
         TxInitProposal(TxInitProposal),
         TxVoteProposal(TxVoteProposal),
+        TxUpdateVoteProxy(TxUpdateVoteProxy),
         TxRevealPk(TxRevealPk),
         Bond(Bond),
         Unbond(Unbond),
         Withdraw(Withdraw),
         ClaimRewards(ClaimRewards),
+        SetAutoCompound(SetAutoCompound),
         Redelegate(Redelegate),
         AddToEthBridgePool(AddToEthBridgePool),
+        TxBridgePauseVote(TxBridgePauseVote),
+        TxConvertNuts(TxConvertNuts),
         TxUpdateStewardCommission(TxUpdateStewardCommission),
         TxResignSteward(TxResignSteward),
         QueryEpoch(QueryEpoch),
@@ -466,23 +583,36 @@ pub mod cmds {
         QueryConversions(QueryConversions),
         QueryMaspRewardTokens(QueryMaspRewardTokens),
         QueryBlock(QueryBlock),
+        QueryChainTiming(QueryChainTiming),
         QueryBalance(QueryBalance),
+        QueryBalances(QueryBalances),
+        QueryVesting(QueryVesting),
+        QueryIbcDenom(QueryIbcDenom),
         QueryBonds(QueryBonds),
         QueryBondedStake(QueryBondedStake),
         QueryCommissionRate(QueryCommissionRate),
         QueryMetaData(QueryMetaData),
         QuerySlashes(QuerySlashes),
         QueryDelegations(QueryDelegations),
+        QueryStakingOverview(QueryStakingOverview),
         QueryFindValidator(QueryFindValidator),
+        QueryProposer(QueryProposer),
+        QueryValidatorSet(QueryValidatorSet),
+        QueryBridgePoolFees(QueryBridgePoolFees),
+        QueryTransferStatus(QueryTransferStatus),
+        QueryNutBalances(QueryNutBalances),
         QueryRawBytes(QueryRawBytes),
+        QueryStorageValue(QueryStorageValue),
         QueryProposal(QueryProposal),
         QueryProposalVotes(QueryProposalVotes),
         QueryProposalResult(QueryProposalResult),
         QueryProtocolParameters(QueryProtocolParameters),
         QueryPgf(QueryPgf),
+        QueryFeeReport(QueryFeeReport),
         QueryValidatorState(QueryValidatorState),
         QueryRewards(QueryRewards),
         SignTx(SignTx),
+        BroadcastSignedTx(BroadcastSignedTx),
         GenIbcShieldedTransafer(GenIbcShieldedTransafer),
     }
 
@@ -507,6 +637,14 @@ pub mod cmds {
         KeyAddrAdd(WalletAddKeyAddress),
         /// Key / address remove
         KeyAddrRemove(WalletRemoveKeyAddress),
+        /// Address book import / export
+        AddrBook(WalletAddressBook),
+        /// Offline tx signing
+        SignTx(WalletSignTx),
+        /// Signing daemon
+        Daemon(WalletDaemon),
+        /// Wallet password verification and keychain storage
+        Unlock(WalletUnlock),
     }
 
     impl Cmd for NamadaWallet {
@@ -520,6 +658,10 @@ pub mod cmds {
                 .subcommand(WalletImportKey::def())
                 .subcommand(WalletAddKeyAddress::def())
                 .subcommand(WalletRemoveKeyAddress::def())
+                .subcommand(WalletAddressBook::def())
+                .subcommand(WalletSignTx::def())
+                .subcommand(WalletDaemon::def())
+                .subcommand(WalletUnlock::def())
         }
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
@@ -533,6 +675,10 @@ pub mod cmds {
             let key_addr_add = SubCmd::parse(matches).map(Self::KeyAddrAdd);
             let key_addr_remove =
                 SubCmd::parse(matches).map(Self::KeyAddrRemove);
+            let addr_book = SubCmd::parse(matches).map(Self::AddrBook);
+            let sign_tx = SubCmd::parse(matches).map(Self::SignTx);
+            let daemon = SubCmd::parse(matches).map(Self::Daemon);
+            let unlock = SubCmd::parse(matches).map(Self::Unlock);
             gen.or(derive)
                 .or(pay_addr_gen)
                 .or(key_addr_list)
@@ -541,6 +687,10 @@ pub mod cmds {
                 .or(import)
                 .or(key_addr_add)
                 .or(key_addr_remove)
+                .or(addr_book)
+                .or(sign_tx)
+                .or(daemon)
+                .or(unlock)
         }
     }
 
@@ -770,6 +920,184 @@ pub mod cmds {
         }
     }
 
+    /// Address book sub-commands
+    #[derive(Clone, Debug)]
+    pub enum WalletAddressBook {
+        Import(WalletImportAddressBook),
+        Export(WalletExportAddressBook),
+        Grind(WalletGrindAddress),
+    }
+
+    impl SubCmd for WalletAddressBook {
+        const CMD: &'static str = "address";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).and_then(|matches| {
+                let import = SubCmd::parse(matches).map(Self::Import);
+                let export = SubCmd::parse(matches).map(Self::Export);
+                let grind = SubCmd::parse(matches).map(Self::Grind);
+                import.or(export).or(grind)
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Address book sub-commands.")
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .subcommand(WalletImportAddressBook::def())
+                .subcommand(WalletExportAddressBook::def())
+                .subcommand(WalletGrindAddress::def())
+        }
+    }
+
+    /// Import addresses into the wallet from a file
+    #[derive(Clone, Debug)]
+    pub struct WalletImportAddressBook(pub args::AddressBookImport);
+
+    impl SubCmd for WalletImportAddressBook {
+        const CMD: &'static str = "import";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::AddressBookImport::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Imports aliased transparent addresses from an address \
+                     book file.",
+                )
+                .add_args::<args::AddressBookImport>()
+        }
+    }
+
+    /// Export the wallet's addresses to a file
+    #[derive(Clone, Debug)]
+    pub struct WalletExportAddressBook(pub args::AddressBookExport);
+
+    impl SubCmd for WalletExportAddressBook {
+        const CMD: &'static str = "export";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::AddressBookExport::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Exports the wallet's aliased transparent addresses to an \
+                     address book file.",
+                )
+                .add_args::<args::AddressBookExport>()
+        }
+    }
+
+    /// Grind a key for a genesis established account with a vanity address
+    #[derive(Clone, Debug)]
+    pub struct WalletGrindAddress(pub args::AddressGrind);
+
+    impl SubCmd for WalletGrindAddress {
+        const CMD: &'static str = "grind";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::AddressGrind::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Generates keys until the genesis established account \
+                     with the key has an address starting with the given \
+                     pattern, and stores the key and the address in the \
+                     wallet.",
+                )
+                .add_args::<args::AddressGrind>()
+        }
+    }
+
+    /// Sign a transaction with the keys from the wallet, without a node
+    #[derive(Clone, Debug)]
+    pub struct WalletSignTx(pub args::OfflineSignTx);
+
+    impl SubCmd for WalletSignTx {
+        const CMD: &'static str = "sign-tx";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::OfflineSignTx::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Sign a transaction dumped with `--offline-sign` using \
+                     the keys from the wallet. This doesn't need a connection \
+                     to a node.",
+                )
+                .add_args::<args::OfflineSignTx>()
+        }
+    }
+
+    /// Serve the keys of the wallet to other tools over a Unix socket
+    #[derive(Clone, Debug)]
+    pub struct WalletDaemon(pub args::WalletDaemon);
+
+    impl SubCmd for WalletDaemon {
+        const CMD: &'static str = "daemon";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::WalletDaemon::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Run a daemon that lists the keys of the wallet and signs \
+                     with them on requests received over a Unix socket. The \
+                     keys are decrypted once for the whole session, so that \
+                     the client and other tools can sign with \
+                     `--wallet-daemon <socket-path>` without decrypting the \
+                     wallet.",
+                )
+                .add_args::<args::WalletDaemon>()
+        }
+    }
+
+    /// Verify the wallet password and save it in the keychain of the OS
+    #[derive(Clone, Debug)]
+    pub struct WalletUnlock(pub args::WalletUnlock);
+
+    impl SubCmd for WalletUnlock {
+        const CMD: &'static str = "unlock";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::WalletUnlock::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Verify the decryption password of the wallet. With \
+                     `--save`, the password is saved in the keychain of the \
+                     OS and used to decrypt the wallet without prompting for \
+                     it. Requires a client built with the `keychain` feature.",
+                )
+                .add_args::<args::WalletUnlock>()
+        }
+    }
+
     /// Generate a payment address from a viewing key or payment address
     #[derive(Clone, Debug)]
     pub struct WalletGenPaymentAddress(pub args::PayAddressGen<args::CliTypes>);
@@ -818,6 +1146,8 @@ pub mod cmds {
                     // The `run` command is the default if no sub-command given
                     .or(Some(Self::Run(LedgerRun(args::LedgerRun {
                         start_time: None,
+                        read_only: false,
+                        trusted_peer: None,
                     }))))
             })
         }
@@ -963,204 +1293,570 @@ pub mod cmds {
     }
 
     #[derive(Clone, Debug)]
-    pub struct ConfigGen;
+    pub struct Faucet(pub args::Faucet);
 
-    impl SubCmd for ConfigGen {
-        const CMD: &'static str = "gen";
+    impl SubCmd for Faucet {
+        const CMD: &'static str = "faucet";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches.subcommand_matches(Self::CMD).map(|_matches| Self)
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::Faucet::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Generate the default configuration file.")
+                .about(
+                    "Run an HTTP faucet dispensing tokens to the addresses \
+                     that solve a proof-of-work challenge, within a quota per \
+                     address.",
+                )
+                .add_args::<args::Faucet>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct LocalConfig(pub args::UpdateLocalConfig);
+    pub enum Db {
+        Query(DbQuery),
+        Diff(DbDiff),
+    }
 
-    impl SubCmd for LocalConfig {
-        const CMD: &'static str = "update-local-config";
+    impl SubCmd for Db {
+        const CMD: &'static str = "db";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::UpdateLocalConfig::parse(matches)))
+            matches.subcommand_matches(Self::CMD).and_then(|matches| {
+                let query = SubCmd::parse(matches).map(Self::Query);
+                let diff = SubCmd::parse(matches).map(Self::Diff);
+                query.or(diff)
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Update the validator's local configuration.")
-                .add_args::<args::UpdateLocalConfig>()
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .about(
+                    "Sub-commands operating on the DB of a stopped ledger \
+                     node.",
+                )
+                .subcommand(DbQuery::def())
+                .subcommand(DbDiff::def())
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryResult(pub args::QueryResult<args::CliTypes>);
+    pub struct DbQuery(pub args::DbQuery);
 
-    impl SubCmd for QueryResult {
-        const CMD: &'static str = "tx-result";
+    impl SubCmd for DbQuery {
+        const CMD: &'static str = "query";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| QueryResult(args::QueryResult::parse(matches)))
+                .map(|matches| Self(args::DbQuery::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query the result of a transaction.")
-                .add_args::<args::QueryResult<args::CliTypes>>()
+                .about(
+                    "Query the latest state in the DB of a stopped ledger \
+                     node and print the matching keys with their decoded \
+                     values.",
+                )
+                .add_args::<args::DbQuery>()
         }
     }
 
-    #[derive(Debug, Clone)]
-    pub struct QueryProposalVotes(pub args::QueryProposalVotes<args::CliTypes>);
+    #[derive(Clone, Debug)]
+    pub struct DbDiff(pub args::DbDiff);
 
-    impl SubCmd for QueryProposalVotes {
-        const CMD: &'static str = "query-proposal-votes";
+    impl SubCmd for DbDiff {
+        const CMD: &'static str = "diff";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryProposalVotes(args::QueryProposalVotes::parse(matches))
-            })
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::DbDiff::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query votes for the proposal.")
-                .add_args::<args::QueryProposalVotes<args::CliTypes>>()
+                .about(
+                    "Print the keys added, removed or changed between two \
+                     heights in the DB of a stopped ledger node, with their \
+                     decoded values.",
+                )
+                .add_args::<args::DbDiff>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryProposal(pub args::QueryProposal<args::CliTypes>);
+    pub enum EthereumOracle {
+        Status(EthereumOracleStatus),
+    }
 
-    impl SubCmd for QueryProposal {
-        const CMD: &'static str = "query-proposal";
+    impl SubCmd for EthereumOracle {
+        const CMD: &'static str = "ethereum-oracle";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryProposal(args::QueryProposal::parse(matches))
-            })
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .and_then(|matches| SubCmd::parse(matches).map(Self::Status))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query proposals.")
-                .add_args::<args::QueryProposal<args::CliTypes>>()
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .about("Sub-commands for the Ethereum oracle of the node.")
+                .subcommand(EthereumOracleStatus::def())
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryProposalResult(
-        pub args::QueryProposalResult<args::CliTypes>,
-    );
+    pub struct EthereumOracleStatus;
 
-    impl SubCmd for QueryProposalResult {
-        const CMD: &'static str = "query-proposal-result";
+    impl SubCmd for EthereumOracleStatus {
+        const CMD: &'static str = "status";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryProposalResult(args::QueryProposalResult::parse(matches))
-            })
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|_matches| Self)
         }
 
         fn def() -> App {
-            App::new(Self::CMD)
-                .about("Query proposals result.")
-                .add_args::<args::QueryProposalResult<args::CliTypes>>()
+            App::new(Self::CMD).about(
+                "Show the stats of the requests of the oracle to its Ethereum \
+                 endpoints, persisted by the node, from the most preferred \
+                 endpoint.",
+            )
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryProtocolParameters(
-        pub args::QueryProtocolParameters<args::CliTypes>,
-    );
+    pub enum NodeDebug {
+        MempoolRejections(DebugMempoolRejections),
+        VoteTally(DebugVoteTally),
+    }
 
-    impl SubCmd for QueryProtocolParameters {
-        const CMD: &'static str = "query-protocol-parameters";
+    impl SubCmd for NodeDebug {
+        const CMD: &'static str = "debug";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
-            matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryProtocolParameters(args::QueryProtocolParameters::parse(
-                    matches,
-                ))
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).and_then(|matches| {
+                let mempool_rejections =
+                    SubCmd::parse(matches).map(Self::MempoolRejections);
+                let vote_tally = SubCmd::parse(matches).map(Self::VoteTally);
+                mempool_rejections.or(vote_tally)
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query protocol parameters.")
-                .add_args::<args::QueryProtocolParameters<args::CliTypes>>()
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .about("Sub-commands to debug a ledger node.")
+                .subcommand(DebugMempoolRejections::def())
+                .subcommand(DebugVoteTally::def())
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryPgf(pub args::QueryPgf<args::CliTypes>);
+    pub struct DebugMempoolRejections(pub args::DebugMempoolRejections);
 
-    impl SubCmd for QueryPgf {
-        const CMD: &'static str = "query-pgf";
+    impl SubCmd for DebugMempoolRejections {
+        const CMD: &'static str = "mempool-rejections";
 
-        fn parse(matches: &ArgMatches) -> Option<Self>
-        where
-            Self: Sized,
-        {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| QueryPgf(args::QueryPgf::parse(matches)))
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::DebugMempoolRejections::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query pgf stewards and continuous funding.")
-                .add_args::<args::QueryPgf<args::CliTypes>>()
+                .about(
+                    "Print the txs recently rejected by the mempool \
+                     validation of the node as JSON, with the result codes \
+                     and reasons of the rejections.",
+                )
+                .add_args::<args::DebugMempoolRejections>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxCustom(pub args::TxCustom<args::CliTypes>);
+    pub struct DebugVoteTally(pub args::DebugVoteTally);
 
-    impl SubCmd for TxCustom {
-        const CMD: &'static str = "tx";
+    impl SubCmd for DebugVoteTally {
+        const CMD: &'static str = "vote-tally";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| TxCustom(args::TxCustom::parse(matches)))
+                .map(|matches| Self(args::DebugVoteTally::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Send a transaction with custom WASM code.")
-                .add_args::<args::TxCustom<args::CliTypes>>()
+                .about(
+                    "Check that all the keys of the vote tally of an Ethereum \
+                     event or a validator set update exist and decode in the \
+                     DB of a stopped ledger node.",
+                )
+                .add_args::<args::DebugVoteTally>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct TxTransfer(pub args::TxTransfer<crate::cli::args::CliTypes>);
+    pub enum NodeUtils {
+        CalibrateGas(CalibrateGas),
+        DumpKeyspace(DumpKeyspace),
+        GenerateSentryConfig(GenerateSentryConfig),
+    }
 
-    impl SubCmd for TxTransfer {
-        const CMD: &'static str = "transfer";
+    impl SubCmd for NodeUtils {
+        const CMD: &'static str = "utils";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| TxTransfer(args::TxTransfer::parse(matches)))
+            matches.subcommand_matches(Self::CMD).and_then(|matches| {
+                let calibrate_gas =
+                    SubCmd::parse(matches).map(Self::CalibrateGas);
+                let dump_keyspace =
+                    SubCmd::parse(matches).map(Self::DumpKeyspace);
+                let generate_sentry_config =
+                    SubCmd::parse(matches).map(Self::GenerateSentryConfig);
+                calibrate_gas.or(dump_keyspace).or(generate_sentry_config)
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+                .about("Utilities for the node operators.")
+                .subcommand(CalibrateGas::def())
+                .subcommand(DumpKeyspace::def())
+                .subcommand(GenerateSentryConfig::def())
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct CalibrateGas(pub args::CalibrateGas);
+
+    impl SubCmd for CalibrateGas {
+        const CMD: &'static str = "calibrate-gas";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::CalibrateGas::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Run a suite of micro-WASMs on this machine, compare \
+                     their time to the gas they're charged and suggest the \
+                     gas costs that match the time, to support the decisions \
+                     on the gas parameters.",
+                )
+                .add_args::<args::CalibrateGas>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct DumpKeyspace;
+
+    impl SubCmd for DumpKeyspace {
+        const CMD: &'static str = "dump-keyspace";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|_matches| Self)
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD).about(
+                "Print the storage key prefixes reserved by each of the \
+                 protocol modules.",
+            )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct GenerateSentryConfig(pub args::GenerateSentryConfig);
+
+    impl SubCmd for GenerateSentryConfig {
+        const CMD: &'static str = "generate-sentry-config";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::GenerateSentryConfig::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Generate the matching sentry configs of a validator and \
+                     of its sentry nodes, to be added to their Namada configs.",
+                )
+                .add_args::<args::GenerateSentryConfig>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ConfigGen;
+
+    impl SubCmd for ConfigGen {
+        const CMD: &'static str = "gen";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|_matches| Self)
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Generate the default configuration file.")
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct LocalConfig(pub args::UpdateLocalConfig);
+
+    impl SubCmd for LocalConfig {
+        const CMD: &'static str = "update-local-config";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::UpdateLocalConfig::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Update the validator's local configuration.")
+                .add_args::<args::UpdateLocalConfig>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryResult(pub args::QueryResult<args::CliTypes>);
+
+    impl SubCmd for QueryResult {
+        const CMD: &'static str = "tx-result";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryResult(args::QueryResult::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Query the result of a transaction.")
+                .add_args::<args::QueryResult<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct QueryProposalVotes(pub args::QueryProposalVotes<args::CliTypes>);
+
+    impl SubCmd for QueryProposalVotes {
+        const CMD: &'static str = "query-proposal-votes";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryProposalVotes(args::QueryProposalVotes::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Query votes for the proposal.")
+                .add_args::<args::QueryProposalVotes<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryProposal(pub args::QueryProposal<args::CliTypes>);
+
+    impl SubCmd for QueryProposal {
+        const CMD: &'static str = "query-proposal";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryProposal(args::QueryProposal::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Query proposals.")
+                .add_args::<args::QueryProposal<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryProposalResult(
+        pub args::QueryProposalResult<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryProposalResult {
+        const CMD: &'static str = "query-proposal-result";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryProposalResult(args::QueryProposalResult::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Query proposals result.")
+                .add_args::<args::QueryProposalResult<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryProtocolParameters(
+        pub args::QueryProtocolParameters<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryProtocolParameters {
+        const CMD: &'static str = "query-protocol-parameters";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryProtocolParameters(args::QueryProtocolParameters::parse(
+                    matches,
+                ))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Query protocol parameters.")
+                .add_args::<args::QueryProtocolParameters<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryPgf(pub args::QueryPgf<args::CliTypes>);
+
+    impl SubCmd for QueryPgf {
+        const CMD: &'static str = "query-pgf";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryPgf(args::QueryPgf::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Query pgf stewards and continuous funding.")
+                .add_args::<args::QueryPgf<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryFeeReport(pub args::QueryFeeReport<args::CliTypes>);
+
+    impl SubCmd for QueryFeeReport {
+        const CMD: &'static str = "fee-report";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryFeeReport(args::QueryFeeReport::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the fees collected and distributed, the tokens \
+                     burned and the PGF treasury inflows and outflows in an \
+                     epoch.",
+                )
+                .add_args::<args::QueryFeeReport<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxCustom(pub args::TxCustom<args::CliTypes>);
+
+    impl SubCmd for TxCustom {
+        const CMD: &'static str = "tx";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .filter(|matches| {
+                    matches.subcommand_matches(TxBuild::CMD).is_none()
+                })
+                .map(|matches| TxCustom(args::TxCustom::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Send a transaction with custom WASM code.")
+                .add_args::<args::TxCustom<args::CliTypes>>()
+                .subcommand(TxBuild::def())
+                .subcommand_negates_reqs(true)
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxBuild(pub args::TxBuild<args::CliTypes>);
+
+    impl SubCmd for TxBuild {
+        const CMD: &'static str = "build";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(TxCustom::CMD)
+                .and_then(|matches| matches.subcommand_matches(Self::CMD))
+                .map(|matches| TxBuild(args::TxBuild::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Build a transaction step by step and save it unsigned \
+                     for the offline signing flow.",
+                )
+                .add_args::<args::TxBuild<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxTransfer(pub args::TxTransfer<crate::cli::args::CliTypes>);
+
+    impl SubCmd for TxTransfer {
+        const CMD: &'static str = "transfer";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| TxTransfer(args::TxTransfer::parse(matches)))
         }
 
         fn def() -> App {
@@ -1170,6 +1866,55 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxVestingTransfer(pub args::TxVestingTransfer<args::CliTypes>);
+
+    impl SubCmd for TxVestingTransfer {
+        const CMD: &'static str = "vesting-transfer";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxVestingTransfer(args::TxVestingTransfer::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Send a signed transfer transaction whose amount is \
+                     locked in the target's balance until it vests. The \
+                     locked tokens can be bonded, but not transferred. The \
+                     target must also sign the transaction to accept the \
+                     vesting schedule, e.g. with `--dump-tx` and offline \
+                     signing.",
+                )
+                .add_args::<args::TxVestingTransfer<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxSchedule(pub args::TxSchedule<args::CliTypes>);
+
+    impl SubCmd for TxSchedule {
+        const CMD: &'static str = "schedule-tx";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| TxSchedule(args::TxSchedule::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Schedule a transaction for execution by the protocol at \
+                     the start of a future epoch. The fee of the scheduled \
+                     transaction is paid upfront by its owner.",
+                )
+                .add_args::<args::TxSchedule<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxIbcTransfer(pub args::TxIbcTransfer<args::CliTypes>);
 
@@ -1211,6 +1956,31 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxUpdateAccountKeys(
+        pub args::TxUpdateAccountKeys<args::CliTypes>,
+    );
+
+    impl SubCmd for TxUpdateAccountKeys {
+        const CMD: &'static str = "update-account-keys";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxUpdateAccountKeys(args::TxUpdateAccountKeys::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Send a signed transaction to replace the public keys and \
+                     the signature threshold of an established account, \
+                     without updating its validity predicate.",
+                )
+                .add_args::<args::TxUpdateAccountKeys<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxInitAccount(pub args::TxInitAccount<args::CliTypes>);
 
@@ -1423,6 +2193,28 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct SetAutoCompound(pub args::SetAutoCompound<args::CliTypes>);
+
+    impl SubCmd for SetAutoCompound {
+        const CMD: &'static str = "auto-compound";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                SetAutoCompound(args::SetAutoCompound::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Enable or disable the automatic restaking of the rewards \
+                     of a bond at every epoch.",
+                )
+                .add_args::<args::SetAutoCompound<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct Redelegate(pub args::Redelegate<args::CliTypes>);
 
@@ -1499,68 +2291,153 @@ pub mod cmds {
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query currently applicable conversions.")
-                .add_args::<args::QueryConversions<args::CliTypes>>()
+                .about("Query currently applicable conversions.")
+                .add_args::<args::QueryConversions<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryMaspRewardTokens(pub args::Query<args::CliTypes>);
+
+    impl SubCmd for QueryMaspRewardTokens {
+        const CMD: &'static str = "masp-reward-tokens";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryMaspRewardTokens(args::Query::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the tokens which can earn MASP rewards while \
+                     shielded.",
+                )
+                .add_args::<args::Query<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryBlock(pub args::QueryBlock<args::CliTypes>);
+
+    impl SubCmd for QueryBlock {
+        const CMD: &'static str = "block";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryBlock(args::QueryBlock::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query a committed block and the gas used by its \
+                     transactions.",
+                )
+                .add_args::<args::QueryBlock<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryChainTiming(pub args::Query<args::CliTypes>);
+
+    impl SubCmd for QueryChainTiming {
+        const CMD: &'static str = "chain-timing";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryChainTiming(args::Query::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the statistics of the last block times and the \
+                     minimum start of the next epoch.",
+                )
+                .add_args::<args::Query<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryBalance(pub args::QueryBalance<args::CliTypes>);
+
+    impl SubCmd for QueryBalance {
+        const CMD: &'static str = "balance";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| QueryBalance(args::QueryBalance::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Query balance(s) of tokens.")
+                .add_args::<args::QueryBalance<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryMaspRewardTokens(pub args::Query<args::CliTypes>);
+    pub struct QueryBalances(pub args::QueryBalances<args::CliTypes>);
 
-    impl SubCmd for QueryMaspRewardTokens {
-        const CMD: &'static str = "masp-reward-tokens";
+    impl SubCmd for QueryBalances {
+        const CMD: &'static str = "balances";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches.subcommand_matches(Self::CMD).map(|matches| {
-                QueryMaspRewardTokens(args::Query::parse(matches))
+                QueryBalances(args::QueryBalances::parse(matches))
             })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
                 .about(
-                    "Query the tokens which can earn MASP rewards while \
-                     shielded.",
+                    "Query the balances of many owners in many tokens in a \
+                     single query.",
                 )
-                .add_args::<args::Query<args::CliTypes>>()
+                .add_args::<args::QueryBalances<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryBlock(pub args::Query<args::CliTypes>);
+    pub struct QueryVesting(pub args::QueryVesting<args::CliTypes>);
 
-    impl SubCmd for QueryBlock {
-        const CMD: &'static str = "block";
+    impl SubCmd for QueryVesting {
+        const CMD: &'static str = "vesting";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| QueryBlock(args::Query::parse(matches)))
+                .map(|matches| QueryVesting(args::QueryVesting::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query the last committed block.")
-                .add_args::<args::Query<args::CliTypes>>()
+                .about("Query the vested and locked amounts of a balance.")
+                .add_args::<args::QueryVesting<args::CliTypes>>()
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct QueryBalance(pub args::QueryBalance<args::CliTypes>);
+    pub struct QueryIbcDenom(pub args::QueryIbcDenom<args::CliTypes>);
 
-    impl SubCmd for QueryBalance {
-        const CMD: &'static str = "balance";
+    impl SubCmd for QueryIbcDenom {
+        const CMD: &'static str = "ibc-denom";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
-            matches
-                .subcommand_matches(Self::CMD)
-                .map(|matches| QueryBalance(args::QueryBalance::parse(matches)))
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryIbcDenom(args::QueryIbcDenom::parse(matches))
+            })
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Query balance(s) of tokens.")
-                .add_args::<args::QueryBalance<args::CliTypes>>()
+                .about("Query the full trace path of an IBC denom by its hash.")
+                .add_args::<args::QueryIbcDenom<args::CliTypes>>()
         }
     }
 
@@ -1621,6 +2498,28 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct BroadcastSignedTx(pub args::BroadcastSignedTx<args::CliTypes>);
+
+    impl SubCmd for BroadcastSignedTx {
+        const CMD: &'static str = "broadcast-signed";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                BroadcastSignedTx(args::BroadcastSignedTx::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Submit a transaction signed offline with `namadaw \
+                     sign-tx`.",
+                )
+                .add_args::<args::BroadcastSignedTx<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryValidatorState(
         pub args::QueryValidatorState<args::CliTypes>,
@@ -1767,6 +2666,34 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryStakingOverview(
+        pub args::QueryStakingOverview<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryStakingOverview {
+        const CMD: &'static str = "staking-overview";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryStakingOverview(args::QueryStakingOverview::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query all the bonds, unbonds, pending rewards and \
+                     applied slashes of the given owner address and the \
+                     states of its validators.",
+                )
+                .add_args::<args::QueryStakingOverview<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryFindValidator(pub args::QueryFindValidator<args::CliTypes>);
 
@@ -1792,6 +2719,128 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryProposer(pub args::QueryProposer<args::CliTypes>);
+
+    impl SubCmd for QueryProposer {
+        const CMD: &'static str = "proposer";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryProposer(args::QueryProposer::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the proposers of committed blocks and the expected \
+                     proposers of upcoming blocks.",
+                )
+                .add_args::<args::QueryProposer<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryBridgePoolFees(pub args::BridgePoolFees<args::CliTypes>);
+
+    impl SubCmd for QueryBridgePoolFees {
+        const CMD: &'static str = "bridge-pool-fees";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::BridgePoolFees::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Suggest a gas fee for a transfer in the Ethereum Bridge \
+                     pool, based on recently relayed transfers and Ethereum \
+                     gas prices.",
+                )
+                .add_args::<args::BridgePoolFees<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryTransferStatus(
+        pub args::QueryTransferStatus<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryTransferStatus {
+        const CMD: &'static str = "query-transfer-status";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::QueryTransferStatus::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the status of a transfer to Ethereum: pending in \
+                     the Bridge pool, signed in a batch, relayed, confirmed \
+                     on Ethereum or expired.",
+                )
+                .add_args::<args::QueryTransferStatus<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryNutBalances(pub args::QueryNutBalances<args::CliTypes>);
+
+    impl SubCmd for QueryNutBalances {
+        const CMD: &'static str = "query-nut-balances";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::QueryNutBalances::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the balances of the NUTs held by an owner, which \
+                     can be converted into wrapped ERC20s once the token caps \
+                     of their assets are raised.",
+                )
+                .add_args::<args::QueryNutBalances<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryValidatorSet(pub args::QueryValidatorSet<args::CliTypes>);
+
+    impl SubCmd for QueryValidatorSet {
+        const CMD: &'static str = "validator-set";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryValidatorSet(args::QueryValidatorSet::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the consensus validator set of an epoch, with the \
+                     voting powers and keys of its members, optionally with a \
+                     Merkle proof of it.",
+                )
+                .add_args::<args::QueryValidatorSet<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryRawBytes(pub args::QueryRawBytes<args::CliTypes>);
 
@@ -1811,6 +2860,28 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryStorageValue(pub args::QueryStorageValue<args::CliTypes>);
+
+    impl SubCmd for QueryStorageValue {
+        const CMD: &'static str = "query-storage-value";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryStorageValue(args::QueryStorageValue::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the value of a given storage key and decode it to \
+                     JSON",
+                )
+                .add_args::<args::QueryStorageValue<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxInitProposal(pub args::InitProposal<args::CliTypes>);
 
@@ -1978,6 +3049,31 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxUpdateVoteProxy(pub args::UpdateVoteProxy<args::CliTypes>);
+
+    impl SubCmd for TxUpdateVoteProxy {
+        const CMD: &'static str = "update-vote-proxy";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxUpdateVoteProxy(args::UpdateVoteProxy::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Set or remove the address that votes on governance \
+                     proposals with the bonded stake of a delegator.",
+                )
+                .add_args::<args::UpdateVoteProxy<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxRevealPk(pub args::RevealPk<args::CliTypes>);
 
@@ -2058,6 +3154,7 @@ pub mod cmds {
         JoinNetwork(JoinNetwork),
         FetchWasms(FetchWasms),
         ValidateWasm(ValidateWasm),
+        VerifyWasm(VerifyWasm),
         InitNetwork(InitNetwork),
         DeriveGenesisAddresses(DeriveGenesisAddresses),
         GenesisBond(GenesisBond),
@@ -2067,8 +3164,13 @@ pub mod cmds {
         DefaultBaseDir(DefaultBaseDir),
         EpochSleep(EpochSleep),
         ValidateGenesisTemplates(ValidateGenesisTemplates),
+        ValidateGenesis(ValidateGenesis),
+        ValidateGenesisValidator(ValidateGenesisValidator),
         TestGenesis(TestGenesis),
         SignGenesisTxs(SignGenesisTxs),
+        Completions(Completions),
+        /// Only available as the top-level `profile` command of the client
+        Profile(Profile),
     }
 
     impl SubCmd for Utils {
@@ -2081,6 +3183,7 @@ pub mod cmds {
                 let fetch_wasms = SubCmd::parse(matches).map(Self::FetchWasms);
                 let validate_wasm =
                     SubCmd::parse(matches).map(Self::ValidateWasm);
+                let verify_wasm = SubCmd::parse(matches).map(Self::VerifyWasm);
                 let init_network =
                     SubCmd::parse(matches).map(Self::InitNetwork);
                 let derive_addresses =
@@ -2098,13 +3201,19 @@ pub mod cmds {
                 let epoch_sleep = SubCmd::parse(matches).map(Self::EpochSleep);
                 let validate_genesis_templates =
                     SubCmd::parse(matches).map(Self::ValidateGenesisTemplates);
+                let validate_genesis =
+                    SubCmd::parse(matches).map(Self::ValidateGenesis);
+                let validate_genesis_validator =
+                    SubCmd::parse(matches).map(Self::ValidateGenesisValidator);
                 let genesis_tx =
                     SubCmd::parse(matches).map(Self::SignGenesisTxs);
                 let test_genesis =
                     SubCmd::parse(matches).map(Self::TestGenesis);
+                let completions = SubCmd::parse(matches).map(Self::Completions);
                 join_network
                     .or(fetch_wasms)
                     .or(validate_wasm)
+                    .or(verify_wasm)
                     .or(init_network)
                     .or(derive_addresses)
                     .or(genesis_bond)
@@ -2114,8 +3223,11 @@ pub mod cmds {
                     .or(default_base_dir)
                     .or(epoch_sleep)
                     .or(validate_genesis_templates)
+                    .or(validate_genesis)
+                    .or(validate_genesis_validator)
                     .or(test_genesis)
                     .or(genesis_tx)
+                    .or(completions)
             })
         }
 
@@ -2125,6 +3237,7 @@ pub mod cmds {
                 .subcommand(JoinNetwork::def())
                 .subcommand(FetchWasms::def())
                 .subcommand(ValidateWasm::def())
+                .subcommand(VerifyWasm::def())
                 .subcommand(InitNetwork::def())
                 .subcommand(DeriveGenesisAddresses::def())
                 .subcommand(GenesisBond::def())
@@ -2134,8 +3247,11 @@ pub mod cmds {
                 .subcommand(DefaultBaseDir::def())
                 .subcommand(EpochSleep::def())
                 .subcommand(ValidateGenesisTemplates::def())
+                .subcommand(ValidateGenesis::def())
+                .subcommand(ValidateGenesisValidator::def())
                 .subcommand(TestGenesis::def())
                 .subcommand(SignGenesisTxs::def())
+                .subcommand(Completions::def())
                 .subcommand_required(true)
                 .arg_required_else_help(true)
         }
@@ -2201,6 +3317,30 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct VerifyWasm(pub args::VerifyWasm);
+
+    impl SubCmd for VerifyWasm {
+        const CMD: &'static str = "verify-wasm";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::VerifyWasm::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Check that the provided wasm code matches the given \
+                     on-chain hash. Optionally, rebuild the wasm code from \
+                     source with the pinned toolchain in a docker container \
+                     and check that the reproduced code matches too.",
+                )
+                .add_args::<args::VerifyWasm>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct InitNetwork(pub args::InitNetwork);
 
@@ -2299,7 +3439,9 @@ pub mod cmds {
                     "Initialize genesis validator's address, consensus key \
                      and validator account key and use it in the ledger's \
                      node. Appends validator creation and self-bond txs to a \
-                     .toml file containing an established account tx.",
+                     .toml file containing an established account tx. With \
+                     `--sign`, the txs are signed and validated, so that the \
+                     file can be sent to the coordinator as is.",
                 )
                 .add_args::<args::InitGenesisValidator>()
         }
@@ -2324,6 +3466,52 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct ValidateGenesis(pub args::ValidateGenesis);
+
+    impl SubCmd for ValidateGenesis {
+        const CMD: &'static str = "validate-genesis";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::ValidateGenesis::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Validate genesis templates with all the checks done by \
+                     `init-network` and print the chain ID and genesis hash \
+                     derived from them.",
+                )
+                .add_args::<args::ValidateGenesis>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ValidateGenesisValidator(pub args::ValidateGenesisValidator);
+
+    impl SubCmd for ValidateGenesisValidator {
+        const CMD: &'static str = "validate-genesis-validator";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::ValidateGenesisValidator::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Validate the signed genesis txs of a validator produced \
+                     by `init-genesis-validator --sign`, before they're sent \
+                     to the coordinator of the genesis ceremony.",
+                )
+                .add_args::<args::ValidateGenesisValidator>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TestGenesis(pub args::TestGenesis);
 
@@ -2477,22 +3665,69 @@ pub mod cmds {
     }
 
     #[derive(Clone, Debug)]
-    pub struct AddToEthBridgePool(pub args::EthereumBridgePool<args::CliTypes>);
+    pub struct AddToEthBridgePool(pub args::EthereumBridgePool<args::CliTypes>);
+
+    impl SubCmd for AddToEthBridgePool {
+        const CMD: &'static str = "add-erc20-transfer";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::EthereumBridgePool::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Add a new transfer to the Ethereum Bridge pool.")
+                .arg_required_else_help(true)
+                .add_args::<args::EthereumBridgePool<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxBridgePauseVote(pub args::TxBridgePauseVote<args::CliTypes>);
+
+    impl SubCmd for TxBridgePauseVote {
+        const CMD: &'static str = "bridge-pause-vote";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::TxBridgePauseVote::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Send a signed transaction for a validator to vote to \
+                     pause the transfers over the Ethereum bridge. The bridge \
+                     is paused once more than 2/3 of the voting power voted \
+                     in the same epoch, and can only be resumed by governance.",
+                )
+                .add_args::<args::TxBridgePauseVote<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxConvertNuts(pub args::TxConvertNuts<args::CliTypes>);
 
-    impl SubCmd for AddToEthBridgePool {
-        const CMD: &'static str = "add-erc20-transfer";
+    impl SubCmd for TxConvertNuts {
+        const CMD: &'static str = "convert-nuts";
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             matches
                 .subcommand_matches(Self::CMD)
-                .map(|matches| Self(args::EthereumBridgePool::parse(matches)))
+                .map(|matches| Self(args::TxConvertNuts::parse(matches)))
         }
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Add a new transfer to the Ethereum Bridge pool.")
-                .arg_required_else_help(true)
-                .add_args::<args::EthereumBridgePool<args::CliTypes>>()
+                .about(
+                    "Send a signed transaction to convert NUTs into the \
+                     wrapped ERC20s of the same asset. The supply of wrapped \
+                     ERC20s must stay within the token cap of the asset.",
+                )
+                .add_args::<args::TxConvertNuts<args::CliTypes>>()
         }
     }
 
@@ -2816,6 +4051,172 @@ pub mod cmds {
                 .add_args::<args::DefaultBaseDir>()
         }
     }
+
+    /// Client profiles management
+    #[derive(Clone, Debug)]
+    pub enum Profile {
+        Set(ProfileSet),
+        Remove(ProfileRemove),
+        Use(ProfileUse),
+        List(ProfileList),
+        Show(ProfileShow),
+    }
+
+    impl SubCmd for Profile {
+        const CMD: &'static str = "profile";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).and_then(|matches| {
+                let set = SubCmd::parse(matches).map(Self::Set);
+                let remove = SubCmd::parse(matches).map(Self::Remove);
+                let use_profile = SubCmd::parse(matches).map(Self::Use);
+                let list = SubCmd::parse(matches).map(Self::List);
+                let show = SubCmd::parse(matches).map(Self::Show);
+                set.or(remove).or(use_profile).or(list).or(show)
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Manage the client profiles. A profile stores the node \
+                     address, chain ID, signing key and fee settings that are \
+                     used as the defaults of the client commands. It is \
+                     selected with the `--profile` argument or the \
+                     `NAMADA_PROFILE` environment variable, or else with \
+                     `profile use`.",
+                )
+                .subcommand(ProfileSet::def().display_order(1))
+                .subcommand(ProfileRemove::def().display_order(1))
+                .subcommand(ProfileUse::def().display_order(1))
+                .subcommand(ProfileList::def().display_order(1))
+                .subcommand(ProfileShow::def().display_order(1))
+                .subcommand_required(true)
+                .arg_required_else_help(true)
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ProfileSet(pub args::ProfileSet);
+
+    impl SubCmd for ProfileSet {
+        const CMD: &'static str = "set";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::ProfileSet::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Create a profile or update the given settings of an \
+                     existing one.",
+                )
+                .add_args::<args::ProfileSet>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ProfileRemove(pub args::ProfileName);
+
+    impl SubCmd for ProfileRemove {
+        const CMD: &'static str = "remove";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::ProfileName::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Remove a profile.")
+                .add_args::<args::ProfileName>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ProfileUse(pub args::ProfileName);
+
+    impl SubCmd for ProfileUse {
+        const CMD: &'static str = "use";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::ProfileName::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Use the given profile when none is selected with \
+                     `--profile` or the `NAMADA_PROFILE` environment variable.",
+                )
+                .add_args::<args::ProfileName>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ProfileList;
+
+    impl SubCmd for ProfileList {
+        const CMD: &'static str = "list";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|_matches| Self)
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("List the profiles, marking the one in use.")
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ProfileShow(pub args::ProfileShow);
+
+    impl SubCmd for ProfileShow {
+        const CMD: &'static str = "show";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::ProfileShow::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about("Show the settings of a profile.")
+                .add_args::<args::ProfileShow>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct Completions(pub args::Completions);
+
+    impl SubCmd for Completions {
+        const CMD: &'static str = "completions";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::Completions::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Print the shell completion script of the client. The \
+                     wallet aliases and token names are completed from a \
+                     cache in the base directory that is updated by every \
+                     command that loads the wallet.",
+                )
+                .add_args::<args::Completions>()
+        }
+    }
 }
 
 pub mod args {
@@ -2832,6 +4233,7 @@ pub mod args {
     use namada::types::chain::{ChainId, ChainIdPrefix};
     use namada::types::dec::Dec;
     use namada::types::ethereum_events::EthAddress;
+    use namada::types::hash::Hash;
     use namada::types::keccak::KeccakHash;
     use namada::types::key::*;
     use namada::types::masp::PaymentAddress;
@@ -2840,28 +4242,41 @@ pub mod args {
     use namada::types::token;
     use namada::types::token::NATIVE_MAX_DECIMAL_PLACES;
     pub use namada_sdk::args::*;
+    use namada_sdk::storage_value::StorageValueType;
     pub use namada_sdk::tx::{
-        TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
-        TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-        TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
+        TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_PAUSE_VOTE_WASM,
+        TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
+        TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+        TX_CLAIM_REWARDS_WASM, TX_CONVERT_NUTS_WASM,
         TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
         TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-        TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
-        TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-        TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
-        VP_USER_WASM,
+        TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_SCHEDULE_WASM,
+        TX_SET_AUTO_COMPOUND_WASM, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+        TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_KEYS_WASM,
+        TX_UPDATE_ACCOUNT_WASM, TX_UPDATE_STEWARD_COMMISSION,
+        TX_UPDATE_VOTE_PROXY, TX_VESTING_TRANSFER_WASM, TX_VOTE_PROPOSAL,
+        TX_WITHDRAW_WASM, VP_USER_WASM,
     };
+    use namada_sdk::wallet::address_book::{
+        AddressBookFormat, ConflictStrategy,
+    };
+    use namada_sdk::wallet::daemon::ConfirmPolicy;
 
+    use super::completions::Shell;
     use super::context::*;
     use super::utils::*;
     use super::{ArgGroup, ArgMatches};
     use crate::client::utils::PRE_GENESIS_DIR;
     use crate::config::genesis::GenesisAddress;
     use crate::config::{self, Action, ActionAtHeight};
-    use crate::facade::tendermint::Timeout;
+    use crate::facade::tendermint::{Hash as TmHash, Timeout};
     use crate::facade::tendermint_config::net::Address as TendermintAddress;
+    use crate::node::ledger::db_query::{self, OutputFormat};
 
     pub const ADDRESS: Arg<WalletAddress> = arg("address");
+    pub const ADDRESS_BOOK_FORMAT: ArgDefault<AddressBookFormat> =
+        arg_default("format", DefaultFn(AddressBookFormat::default));
+    pub const ADDRESS_PATTERN: Arg<String> = arg("prefix");
     pub const ALIAS_OPT: ArgOpt<String> = ALIAS.opt();
     pub const ALIAS: Arg<String> = arg("alias");
     pub const ALIAS_FORCE: ArgFlag = flag("alias-force");
@@ -2869,6 +4284,7 @@ pub mod args {
     pub const ALLOW_DUPLICATE_IP: ArgFlag = flag("allow-duplicate-ip");
     pub const AMOUNT: Arg<token::DenominatedAmount> = arg("amount");
     pub const ARCHIVE_DIR: ArgOpt<PathBuf> = arg_opt("archive-dir");
+    pub const AUTO_RESUBMIT_OPT: ArgOpt<u8> = arg_opt("auto-resubmit");
     pub const AVATAR_OPT: ArgOpt<String> = arg_opt("avatar");
     pub const BALANCE_OWNER: ArgOpt<WalletBalanceOwner> = arg_opt("owner");
     pub const BASE_DIR: ArgDefault<PathBuf> = arg_default(
@@ -2880,6 +4296,7 @@ pub mod args {
     );
     pub const BLOCK_HEIGHT: Arg<BlockHeight> = arg("block-height");
     pub const BLOCK_HEIGHT_OPT: ArgOpt<BlockHeight> = arg_opt("height");
+    pub const BRIDGE_POOL_FEE: ArgOpt<BridgePoolFee> = arg_opt("fee");
     pub const BRIDGE_POOL_GAS_AMOUNT: ArgDefault<token::DenominatedAmount> =
         arg_default(
             "pool-gas-amount",
@@ -2911,15 +4328,23 @@ pub mod args {
         "consensus-timeout-commit",
         DefaultFn(|| Timeout::from_str("1s").unwrap()),
     );
+    pub const CONVERSION_RATE: Arg<f64> = arg("conversion-rate");
+    pub const CONVERSION_RATE_OPT: ArgOpt<f64> = CONVERSION_RATE.opt();
     pub const CONVERSION_TABLE: Arg<PathBuf> = arg("conversion-table");
+    pub const CONFIRM_POLICY: ArgDefault<ConfirmPolicy> =
+        arg_default("confirm", DefaultFn(ConfirmPolicy::default));
+    pub const COUNT: ArgDefault<u64> = arg_default("count", DefaultFn(|| 1));
     pub const DAEMON_MODE: ArgFlag = flag("daemon");
     pub const DAEMON_MODE_RETRY_DUR: ArgOpt<Duration> = arg_opt("retry-sleep");
     pub const DAEMON_MODE_SUCCESS_DUR: ArgOpt<Duration> =
         arg_opt("success-sleep");
     pub const DATA_PATH_OPT: ArgOpt<PathBuf> = arg_opt("data-path");
     pub const DATA_PATH: Arg<PathBuf> = arg("data-path");
+    pub const DB_QUERY: Arg<db_query::Query> = arg("query");
     pub const DECRYPT: ArgFlag = flag("decrypt");
+    pub const DELEGATOR: Arg<WalletAddress> = arg("delegator");
     pub const DESCRIPTION_OPT: ArgOpt<String> = arg_opt("description");
+    pub const DISABLE: ArgFlag = flag("disable");
     pub const DISPOSABLE_SIGNING_KEY: ArgFlag = flag("disposable-gas-payer");
     pub const DESTINATION_VALIDATOR: Arg<WalletAddress> =
         arg("destination-validator");
@@ -2933,29 +4358,57 @@ pub mod args {
     pub const EPOCH: ArgOpt<Epoch> = arg_opt("epoch");
     pub const ERC20: Arg<EthAddress> = arg("erc20");
     pub const ETH_CONFIRMATIONS: Arg<u64> = arg("confirmations");
+    pub const ETH_EVENT_HASH: ArgOpt<Hash> = arg_opt("event-hash");
     pub const ETH_GAS: ArgOpt<u64> = arg_opt("eth-gas");
     pub const ETH_GAS_PRICE: ArgOpt<u64> = arg_opt("eth-gas-price");
     pub const ETH_ADDRESS: Arg<EthAddress> = arg("ethereum-address");
     pub const ETH_ADDRESS_OPT: ArgOpt<EthAddress> = ETH_ADDRESS.opt();
+    pub const ETH_BLOCKS: ArgDefault<u64> =
+        arg_default("eth-blocks", DefaultFn(|| 10));
     pub const ETH_RPC_ENDPOINT: ArgDefault<String> = arg_default(
         "eth-rpc-endpoint",
         DefaultFn(|| "http://localhost:8545".into()),
     );
     pub const ETH_SYNC: ArgFlag = flag("sync");
     pub const EXPIRATION_OPT: ArgOpt<DateTimeUtc> = arg_opt("expiration");
+    pub const EXPIRATION_HEIGHT_OPT: ArgOpt<BlockHeight> =
+        arg_opt("expiration-height");
     pub const EMAIL: Arg<String> = arg("email");
     pub const EMAIL_OPT: ArgOpt<String> = EMAIL.opt();
+    pub const END_EPOCH: Arg<Epoch> = arg("end-epoch");
     pub const FEE_UNSHIELD_SPENDING_KEY: ArgOpt<WalletTransferSource> =
         arg_opt("gas-spending-key");
     pub const FEE_AMOUNT_OPT: ArgOpt<token::DenominatedAmount> =
         arg_opt("gas-price");
     pub const FEE_PAYER_OPT: ArgOpt<WalletPublicKey> = arg_opt("gas-payer");
+    pub const FAUCET_LISTEN_ADDRESS: ArgDefault<SocketAddr> = arg_default(
+        "listen-address",
+        DefaultFn(|| SocketAddr::from_str("127.0.0.1:5000").unwrap()),
+    );
+    pub const FAUCET_QUOTA: Arg<token::DenominatedAmount> = arg("quota");
+    pub const FAUCET_QUOTA_PERIOD: ArgDefault<Duration> = arg_default(
+        "quota-period",
+        DefaultFn(|| Duration::from_str("24h").unwrap()),
+    );
     pub const FILE_PATH: Arg<String> = arg("file");
     pub const FORCE: ArgFlag = flag("force");
-    pub const GAS_LIMIT: ArgDefault<GasLimit> =
-        arg_default("gas-limit", DefaultFn(|| GasLimit::from(25_000)));
+    pub const FORGET: ArgFlag = flag("forget");
+    pub const GAS_LIMIT: ArgDefault<GasLimitArg> = arg_default(
+        "gas-limit",
+        DefaultFn(|| GasLimitArg::Limit(default_gas_limit())),
+    );
+    pub const GAS_LIMIT_OPT: ArgOpt<GasLimit> = arg_opt("gas-limit");
     pub const FEE_TOKEN: ArgDefaultFromCtx<WalletAddress> =
-        arg_default_from_ctx("gas-token", DefaultFn(|| "NAM".parse().unwrap()));
+        arg_default_from_ctx(
+            "gas-token",
+            DefaultFn(|| {
+                let profile = config::profiles::selected();
+                profile
+                    .and_then(|p| p.gas_token.clone())
+                    .unwrap_or_else(|| "NAM".to_string())
+            }),
+        );
+    pub const FEE_TOKEN_OPT: ArgOpt<String> = arg_opt("gas-token");
     pub const FEE_PAYER: Arg<WalletAddress> = arg("fee-payer");
     pub const FEE_AMOUNT: ArgDefault<token::DenominatedAmount> = arg_default(
         "fee-amount",
@@ -2975,48 +4428,76 @@ pub mod args {
         arg("validator");
     pub const HALT_ACTION: ArgFlag = flag("halt");
     pub const HASH_LIST: Arg<String> = arg("hash-list");
+    pub const IBC_DENOM_HASH: Arg<String> = arg("hash");
     pub const HD_WALLET_DERIVATION_PATH: ArgDefault<String> =
         arg_default("hd-path", DefaultFn(|| "default".to_string()));
+    pub const HEIGHT_A: Arg<BlockHeight> = arg("height-a");
+    pub const HEIGHT_B: Arg<BlockHeight> = arg("height-b");
     pub const HISTORIC: ArgFlag = flag("historic");
     pub const IBC_TRANSFER_MEMO_PATH: ArgOpt<PathBuf> = arg_opt("memo-path");
     pub const INPUT_OPT: ArgOpt<PathBuf> = arg_opt("input");
+    pub const INTERACTIVE: ArgFlag = flag("interactive");
+    pub const ITERATIONS: ArgDefault<u64> =
+        arg_default("iterations", DefaultFn(|| 10_000));
     pub const LEDGER_ADDRESS_ABOUT: &str =
         "Address of a ledger node as \"{scheme}://{host}:{port}\". If the \
          scheme is not supplied, it is assumed to be TCP.";
     pub const LEDGER_ADDRESS_DEFAULT: ArgDefault<TendermintAddress> =
         LEDGER_ADDRESS.default(DefaultFn(|| {
-            let raw = "127.0.0.1:26657";
-            TendermintAddress::from_str(raw).unwrap()
+            let profile = config::profiles::selected();
+            profile.and_then(|p| p.node.clone()).unwrap_or_else(|| {
+                let raw = "127.0.0.1:26657";
+                TendermintAddress::from_str(raw).unwrap()
+            })
         }));
     pub const LEDGER_ADDRESS: Arg<TendermintAddress> = arg("node");
+    pub const LEDGER_ADDRESS_OPT: ArgOpt<TendermintAddress> =
+        LEDGER_ADDRESS.opt();
     pub const LIST_FIND_ADDRESSES_ONLY: ArgFlag = flag("addr");
     pub const LIST_FIND_KEYS_ONLY: ArgFlag = flag("keys");
     pub const LOCALHOST: ArgFlag = flag("localhost");
     pub const MAX_COMMISSION_RATE_CHANGE: Arg<Dec> =
         arg("max-commission-rate-change");
     pub const MAX_ETH_GAS: ArgOpt<u64> = arg_opt("max_eth-gas");
+    pub const MAX_ITERATIONS: ArgOpt<u64> = arg_opt("max-iterations");
     pub const MEMO_OPT: ArgOpt<String> = arg_opt("memo");
     pub const MODE: ArgOpt<String> = arg_opt("mode");
     pub const NET_ADDRESS: Arg<SocketAddr> = arg("net-address");
     pub const NAMADA_START_TIME: ArgOpt<DateTimeUtc> = arg_opt("time");
     pub const NO_CONVERSIONS: ArgFlag = flag("no-conversions");
+    pub const NO_PROGRESS: ArgFlag = flag("no-progress");
     pub const NUT: ArgFlag = flag("nut");
     pub const OUT_FILE_PATH_OPT: ArgOpt<PathBuf> = arg_opt("out-file-path");
     pub const OUTPUT: ArgOpt<PathBuf> = arg_opt("output");
+    pub const OUTPUT_FORMAT: ArgDefault<OutputFormat> =
+        arg_default("format", DefaultFn(OutputFormat::default));
+    pub const OFFLINE_SIGN: ArgFlag = flag("offline-sign");
+    pub const ON_CONFLICT: ArgDefault<ConflictStrategy> =
+        arg_default("on-conflict", DefaultFn(ConflictStrategy::default));
     pub const OUTPUT_FOLDER_PATH: ArgOpt<PathBuf> =
         arg_opt("output-folder-path");
     pub const OWNER: Arg<WalletAddress> = arg("owner");
     pub const OWNER_OPT: ArgOpt<WalletAddress> = OWNER.opt();
+    pub const OWNERS: ArgMulti<WalletAddress, GlobPlus> = arg_multi("owners");
     pub const PATH: Arg<PathBuf> = arg("path");
     pub const PIN: ArgFlag = flag("pin");
+    pub const POW_DIFFICULTY: ArgDefault<u8> =
+        arg_default("difficulty", DefaultFn(|| 20));
     pub const PORT_ID: ArgDefault<PortId> = arg_default(
         "port-id",
         DefaultFn(|| PortId::from_str("transfer").unwrap()),
     );
     pub const PRE_GENESIS: ArgFlag = flag("pre-genesis");
+    pub const PROFILE: ArgOpt<String> = arg_opt("profile");
+    pub const PROFILE_NAME: Arg<String> = arg("name");
+    pub const PROFILE_NAME_OPT: ArgOpt<String> = PROFILE_NAME.opt();
+    pub const PROFILE_SIGNING_KEY: ArgOpt<String> = arg_opt("signing-key");
     pub const PROPOSAL_ETH: ArgFlag = flag("eth");
+    pub const PROVING_THREADS: ArgOpt<usize> = arg_opt("proving-threads");
     pub const PROPOSAL_PGF_STEWARD: ArgFlag = flag("pgf-stewards");
     pub const PROPOSAL_PGF_FUNDING: ArgFlag = flag("pgf-funding");
+    pub const PROPOSAL_IBC_CLIENT_RECOVERY: ArgFlag =
+        flag("ibc-client-recovery");
     pub const PROPOSAL_OFFLINE: ArgFlag = flag("offline");
     pub const PROTOCOL_KEY: ArgOpt<WalletPublicKey> = arg_opt("protocol-key");
     pub const PRE_GENESIS_PATH: ArgOpt<PathBuf> = arg_opt("pre-genesis-path");
@@ -3028,6 +4509,7 @@ pub mod args {
     pub const PROPOSAL_VOTE_PGF_OPT: ArgOpt<String> = arg_opt("pgf");
     pub const PROPOSAL_VOTE_ETH_OPT: ArgOpt<String> = arg_opt("eth");
     pub const PROPOSAL_VOTE: Arg<String> = arg("vote");
+    pub const PROXY_OPT: ArgOpt<WalletAddress> = arg_opt("proxy");
     pub const RAW_ADDRESS: Arg<Address> = arg("address");
     pub const RAW_ADDRESS_ESTABLISHED: Arg<EstablishedAddress> = arg("address");
     pub const RAW_ADDRESS_OPT: ArgOpt<Address> = RAW_ADDRESS.opt();
@@ -3041,42 +4523,65 @@ pub mod args {
     pub const RAW_PUBLIC_KEY_HASH: Arg<String> = arg("public-key-hash");
     pub const RAW_PUBLIC_KEY_HASH_OPT: ArgOpt<String> =
         RAW_PUBLIC_KEY_HASH.opt();
+    pub const READ_ONLY: ArgFlag = flag("read-only");
     pub const RECEIVER: Arg<String> = arg("receiver");
     pub const RELAYER: Arg<Address> = arg("relayer");
+    pub const RELAYER_API_ADDRESS: ArgOpt<SocketAddr> = arg_opt("api-address");
+    pub const RUNS: ArgDefault<u64> = arg_default("runs", DefaultFn(|| 5));
     pub const SAFE_MODE: ArgFlag = flag("safe-mode");
+    pub const SAVE: ArgFlag = flag("save");
+    pub const SCHEDULE_EPOCH: Arg<Epoch> = arg("at-epoch");
+    pub const SCHEDULED_GAS_LIMIT: Arg<GasLimit> = arg("scheduled-gas-limit");
     pub const SCHEME: ArgDefault<SchemeType> =
         arg_default("scheme", DefaultFn(|| SchemeType::Ed25519));
     pub const SELF_BOND_AMOUNT: Arg<token::DenominatedAmount> =
         arg("self-bond-amount");
     pub const SENDER: Arg<String> = arg("sender");
+    pub const SHELL: Arg<Shell> = arg("shell");
     pub const SHIELDED: ArgFlag = flag("shielded");
+    pub const SIGN: ArgFlag = flag("sign");
     pub const SIGNER: ArgOpt<WalletAddress> = arg_opt("signer");
     pub const SIGNING_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
         arg_multi("signing-keys");
     pub const SIGNATURES: ArgMulti<PathBuf, GlobStar> = arg_multi("signatures");
+    pub const SOCKET_PATH: Arg<PathBuf> = arg("socket-path");
+    pub const SENTRY_P2P_ADDRESSES: ArgMulti<TendermintAddress, GlobPlus> =
+        arg_multi("sentries");
     pub const SOURCE: Arg<WalletAddress> = arg("source");
     pub const SOURCE_OPT: ArgOpt<WalletAddress> = SOURCE.opt();
+    pub const START_EPOCH: Arg<Epoch> = arg("start-epoch");
     pub const STEWARD: Arg<WalletAddress> = arg("steward");
     pub const SOURCE_VALIDATOR: Arg<WalletAddress> = arg("source-validator");
     pub const STORAGE_KEY: Arg<storage::Key> = arg("storage-key");
+    pub const STORAGE_KEY_PREFIX_OPT: ArgOpt<storage::Key> = arg_opt("prefix");
     pub const SUSPEND_ACTION: ArgFlag = flag("suspend");
+    pub const TARGET: Arg<WalletAddress> = arg("target");
     pub const TEMPLATES_PATH: Arg<PathBuf> = arg("templates-path");
     pub const TIMEOUT_HEIGHT: ArgOpt<u64> = arg_opt("timeout-height");
     pub const TIMEOUT_SEC_OFFSET: ArgOpt<u64> = arg_opt("timeout-sec-offset");
     pub const TM_ADDRESS: ArgOpt<String> = arg_opt("tm-address");
     pub const TOKEN_OPT: ArgOpt<WalletAddress> = TOKEN.opt();
     pub const TOKEN: Arg<WalletAddress> = arg("token");
+    pub const TOKENS: ArgMulti<WalletAddress, GlobPlus> = arg_multi("tokens");
     pub const TOKEN_STR: Arg<String> = arg("token");
+    pub const TRANSFER_HASH: Arg<KeccakHash> = arg("hash");
     pub const TRANSFER_SOURCE: Arg<WalletTransferSource> = arg("source");
     pub const TRANSFER_TARGET: Arg<WalletTransferTarget> = arg("target");
     pub const TRANSPARENT: ArgFlag = flag("transparent");
+    pub const TRUST_NODE: ArgDefault<bool> =
+        arg_default("trust-node", DefaultFn(|| true));
+    pub const TRUSTED_HASH: ArgOpt<TmHash> = arg_opt("trusted-hash");
+    pub const TRUSTED_HEIGHT: ArgOpt<BlockHeight> = arg_opt("trusted-height");
+    pub const TRUSTED_PEER: ArgOpt<TendermintAddress> = arg_opt("trusted-peer");
     pub const TX_HASH: Arg<String> = arg("tx-hash");
     pub const THRESHOLD: ArgOpt<u8> = arg_opt("threshold");
+    pub const TIME_LIMIT: ArgOpt<u64> = arg_opt("time-limit");
     pub const UNSAFE_DONT_ENCRYPT: ArgFlag = flag("unsafe-dont-encrypt");
     pub const UNSAFE_SHOW_SECRET: ArgFlag = flag("unsafe-show-secret");
     pub const USE_DEVICE: ArgFlag = flag("use-device");
     pub const VALIDATOR: Arg<WalletAddress> = arg("validator");
     pub const VALIDATOR_OPT: ArgOpt<WalletAddress> = VALIDATOR.opt();
+    pub const VALIDATOR_P2P_ADDRESS: Arg<TendermintAddress> = arg("validator");
     pub const VALIDATOR_ACCOUNT_KEY: ArgOpt<WalletPublicKey> =
         arg_opt("account-key");
     pub const VALIDATOR_ACCOUNT_KEYS: ArgMulti<WalletPublicKey, GlobStar> =
@@ -3090,13 +4595,18 @@ pub mod args {
     pub const VALIDATOR_ETH_HOT_KEY: ArgOpt<WalletPublicKey> =
         arg_opt("eth-hot-key");
     pub const VALUE: Arg<String> = arg("value");
+    pub const VALUE_TYPE: ArgOpt<StorageValueType> = arg_opt("type");
     pub const VOTER_OPT: ArgOpt<WalletAddress> = arg_opt("voter");
     pub const VIEWING_KEY: Arg<WalletViewingKey> = arg("key");
     pub const VP: ArgOpt<String> = arg_opt("vp");
     pub const WALLET_ALIAS_FORCE: ArgFlag = flag("wallet-alias-force");
+    pub const WALLET_DAEMON: ArgOpt<PathBuf> = arg_opt("wallet-daemon");
     pub const WASM_CHECKSUMS_PATH: Arg<PathBuf> = arg("wasm-checksums-path");
+    pub const WASM_CODE_HASH: Arg<Hash> = arg("hash");
     pub const WASM_DIR: ArgOpt<PathBuf> = arg_opt("wasm-dir");
+    pub const WASM_SOURCE_DIR: ArgOpt<PathBuf> = arg_opt("source-dir");
     pub const WEBSITE_OPT: ArgOpt<String> = arg_opt("website");
+    pub const WITH_PROOF: ArgFlag = flag("with-proof");
     pub const TX_PATH: Arg<PathBuf> = arg("tx-path");
     pub const TX_PATH_OPT: ArgOpt<PathBuf> = TX_PATH.opt();
 
@@ -3107,6 +4617,9 @@ pub mod args {
         pub chain_id: Option<ChainId>,
         pub base_dir: PathBuf,
         pub wasm_dir: Option<PathBuf>,
+        pub no_progress: bool,
+        pub profile: Option<String>,
+        pub proving_threads: Option<usize>,
     }
 
     impl Global {
@@ -3116,11 +4629,17 @@ pub mod args {
             let chain_id = CHAIN_ID_OPT.parse(matches);
             let base_dir = BASE_DIR.parse(matches);
             let wasm_dir = WASM_DIR.parse(matches);
+            let no_progress = NO_PROGRESS.parse(matches);
+            let profile = PROFILE.parse(matches);
+            let proving_threads = PROVING_THREADS.parse(matches);
             Global {
                 is_pre_genesis,
                 chain_id,
                 base_dir,
                 wasm_dir,
+                no_progress,
+                profile,
+                proving_threads,
             }
         }
 
@@ -3149,18 +4668,70 @@ pub mod args {
                         .def()
                         .help("Dispatch pre-genesis specific logic."),
                 )
+                .arg(NO_PROGRESS.def().help(
+                    "Don't show the progress of long-running operations. The \
+                     progress is only shown when the standard error is a \
+                     terminal.",
+                ))
+                .arg(PROFILE.def().help(
+                    "The client profile whose node, chain ID, signing key and \
+                     fee settings are used as the defaults of the command. \
+                     This value can also be set via `NAMADA_PROFILE` \
+                     environment variable, but the argument takes precedence, \
+                     if specified. Defaults to the profile selected with \
+                     `profile use`, if any.",
+                ))
+                .arg(PROVING_THREADS.def().help(
+                    "The number of threads used to generate the MASP proofs \
+                     of shielded transfers. Defaults to the number of CPUs.",
+                ))
+        }
+    }
+
+    /// The value of the `--gas-limit` argument
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum GasLimitArg {
+        /// Estimate the gas limit by simulating the tx
+        Auto,
+        /// The given gas limit
+        Limit(GasLimit),
+    }
+
+    impl FromStr for GasLimitArg {
+        type Err = std::num::ParseIntError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            if s.eq_ignore_ascii_case("auto") {
+                Ok(Self::Auto)
+            } else {
+                GasLimit::from_str(s).map(Self::Limit)
+            }
         }
     }
 
+    /// The gas limit of the selected profile, if any, or else the default one
+    fn default_gas_limit() -> GasLimit {
+        let profile = config::profiles::selected();
+        GasLimit::from(profile.and_then(|p| p.gas_limit).unwrap_or(25_000))
+    }
+
     #[derive(Clone, Debug)]
     pub struct LedgerRun {
         pub start_time: Option<DateTimeUtc>,
+        pub read_only: bool,
+        pub trusted_peer: Option<TendermintAddress>,
     }
 
     impl Args for LedgerRun {
         fn parse(matches: &ArgMatches) -> Self {
             let start_time = NAMADA_START_TIME.parse(matches);
-            Self { start_time }
+            let read_only = READ_ONLY.parse(matches);
+            let trusted_peer = TRUSTED_PEER.parse(matches);
+            Self {
+                start_time,
+                read_only,
+                trusted_peer,
+            }
         }
 
         fn def(app: App) -> App {
@@ -3172,6 +4743,22 @@ pub mod args {
                  equivalent:\n2023-01-20T12:12:12Z\n2023-01-20 \
                  12:12:12Z\n2023-  01-20T12:  12:12Z",
             ))
+            .arg(READ_ONLY.def().help(
+                "Run a node that only serves queries. The node follows the \
+                 chain as a full node, never signs anything with the \
+                 validator key and doesn't run the Ethereum oracle.",
+            ))
+            .arg(
+                TRUSTED_PEER
+                    .def()
+                    .help(
+                        "The address of a peer to sync the blocks from in \
+                         read-only mode, in the format \
+                         tcp://{peer_id}@{ip}:{port}. When set, the node \
+                         doesn't connect to any other peers.",
+                    )
+                    .requires(READ_ONLY.name),
+            )
         }
     }
 
@@ -3225,36 +4812,322 @@ pub mod args {
         pub historic: bool,
     }
 
-    impl Args for LedgerDumpDb {
-        fn parse(matches: &ArgMatches) -> Self {
-            let block_height = BLOCK_HEIGHT_OPT.parse(matches);
-            let out_file_path = OUT_FILE_PATH_OPT
-                .parse(matches)
-                .unwrap_or_else(|| PathBuf::from("db_dump".to_string()));
-            let historic = HISTORIC.parse(matches);
+    impl Args for LedgerDumpDb {
+        fn parse(matches: &ArgMatches) -> Self {
+            let block_height = BLOCK_HEIGHT_OPT.parse(matches);
+            let out_file_path = OUT_FILE_PATH_OPT
+                .parse(matches)
+                .unwrap_or_else(|| PathBuf::from("db_dump".to_string()));
+            let historic = HISTORIC.parse(matches);
+
+            Self {
+                block_height,
+                out_file_path,
+                historic,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(BLOCK_HEIGHT_OPT.def().help(
+                "The block height to dump. Defaults to latest committed
+                block.",
+            ))
+            .arg(OUT_FILE_PATH_OPT.def().help(
+                "Path for the output file (omitting file extension). Defaults \
+                 to \"db_dump.{block_height}.toml\" in the current working \
+                 directory.",
+            ))
+            .arg(
+                HISTORIC
+                    .def()
+                    .help("If provided, dump also the diff of the last height"),
+            )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct DebugMempoolRejections {
+        pub ledger_address: TendermintAddress,
+    }
+
+    impl Args for DebugMempoolRejections {
+        fn parse(matches: &ArgMatches) -> Self {
+            let ledger_address = LEDGER_ADDRESS_DEFAULT.parse(matches);
+            Self { ledger_address }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(LEDGER_ADDRESS_DEFAULT.def().help(LEDGER_ADDRESS_ABOUT))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct DebugVoteTally {
+        pub event_hash: Option<Hash>,
+        pub epoch: Option<Epoch>,
+    }
+
+    impl Args for DebugVoteTally {
+        fn parse(matches: &ArgMatches) -> Self {
+            let event_hash = ETH_EVENT_HASH.parse(matches);
+            let epoch = EPOCH.parse(matches);
+            Self { event_hash, epoch }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                ETH_EVENT_HASH.def().help(
+                    "The hash of the Ethereum event whose tally to check.",
+                ),
+            )
+            .arg(EPOCH.def().help(
+                "The epoch of the validator set update whose tally to check.",
+            ))
+            .group(
+                ArgGroup::new("tally")
+                    .args([ETH_EVENT_HASH.name, EPOCH.name])
+                    .required(true),
+            )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct DbQuery {
+        pub query: db_query::Query,
+        pub value_type: Option<StorageValueType>,
+        pub output_format: OutputFormat,
+    }
+
+    impl Args for DbQuery {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = DB_QUERY.parse(matches);
+            let value_type = VALUE_TYPE.parse(matches);
+            let output_format = OUTPUT_FORMAT.parse(matches);
+            Self {
+                query,
+                value_type,
+                output_format,
+            }
+        }
+
+        fn def(app: App) -> App {
+            let types: Vec<_> =
+                StorageValueType::ALL.iter().map(|ty| ty.name()).collect();
+            app.arg(DB_QUERY.def().help(
+                "The query, as \"<prefix> [WHERE <condition> [AND \
+                 <condition>]*] [LIMIT <n>]\". The prefix is a storage key, \
+                 or \"*\" for all the keys. A condition compares a field \
+                 (\"key\", \"type\", \"size\", \"value\" or \"value.<path>\") \
+                 with a literal using one of \"=\", \"!=\", \"<\", \"<=\", \
+                 \">\", \">=\" or \"LIKE\". For example: \"* WHERE type = \
+                 amount AND value > 1000 LIMIT 10\".",
+            ))
+            .arg(VALUE_TYPE.def().help(format!(
+                "The type to decode all the values as. When not given, the \
+                 type of each value is found from its storage key if it \
+                 matches a known pattern. One of: {}.",
+                types.join(", ")
+            )))
+            .arg(OUTPUT_FORMAT.def().help(
+                "The format of the results, either \"csv\" or \"json\". \
+                 Defaults to \"csv\".",
+            ))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct DbDiff {
+        pub height_a: BlockHeight,
+        pub height_b: BlockHeight,
+        pub prefix: Option<storage::Key>,
+        pub value_type: Option<StorageValueType>,
+        pub output_format: OutputFormat,
+    }
+
+    impl Args for DbDiff {
+        fn parse(matches: &ArgMatches) -> Self {
+            let height_a = HEIGHT_A.parse(matches);
+            let height_b = HEIGHT_B.parse(matches);
+            let prefix = STORAGE_KEY_PREFIX_OPT.parse(matches);
+            let value_type = VALUE_TYPE.parse(matches);
+            let output_format = OUTPUT_FORMAT.parse(matches);
+            Self {
+                height_a,
+                height_b,
+                prefix,
+                value_type,
+                output_format,
+            }
+        }
+
+        fn def(app: App) -> App {
+            let types: Vec<_> =
+                StorageValueType::ALL.iter().map(|ty| ty.name()).collect();
+            app.arg(HEIGHT_A.def().help("The first block height to compare."))
+                .arg(HEIGHT_B.def().help("The second block height to compare."))
+                .arg(STORAGE_KEY_PREFIX_OPT.def().help(
+                    "Only compare the keys with the given storage key prefix.",
+                ))
+                .arg(VALUE_TYPE.def().help(format!(
+                    "The type to decode all the values as. When not given, \
+                     the type of each value is found from its storage key if \
+                     it matches a known pattern. One of: {}.",
+                    types.join(", ")
+                )))
+                .arg(OUTPUT_FORMAT.def().help(
+                    "The format of the results, either \"csv\" or \"json\". \
+                     Defaults to \"csv\".",
+                ))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct CalibrateGas {
+        /// The number of iterations of every benchmark
+        pub iterations: u64,
+        /// The number of runs of every benchmark
+        pub runs: u64,
+        /// The file to write the suggested costs to
+        pub out_file_path: PathBuf,
+    }
+
+    impl Args for CalibrateGas {
+        fn parse(matches: &ArgMatches) -> Self {
+            let iterations = ITERATIONS.parse(matches);
+            let runs = RUNS.parse(matches);
+            let out_file_path = OUT_FILE_PATH_OPT
+                .parse(matches)
+                .unwrap_or_else(|| PathBuf::from("gas_costs.toml"));
+            Self {
+                iterations,
+                runs,
+                out_file_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(ITERATIONS.def().help(
+                "The number of iterations of the operation of every \
+                 benchmark. The memory benchmark is limited by the max memory \
+                 of a tx. Defaults to 10000.",
+            ))
+            .arg(RUNS.def().help(
+                "The number of times every benchmark is run, of which the \
+                 median time is taken. Defaults to 5.",
+            ))
+            .arg(OUT_FILE_PATH_OPT.def().help(
+                "Path of the TOML file to write the suggested costs to. \
+                 Defaults to \"gas_costs.toml\" in the current working \
+                 directory.",
+            ))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct GenerateSentryConfig {
+        /// The P2P address of the validator
+        pub validator: TendermintAddress,
+        /// The P2P addresses of the sentry nodes
+        pub sentries: Vec<TendermintAddress>,
+        /// The directory to write the configs to
+        pub output_folder: Option<PathBuf>,
+    }
 
+    impl Args for GenerateSentryConfig {
+        fn parse(matches: &ArgMatches) -> Self {
+            let validator = VALIDATOR_P2P_ADDRESS.parse(matches);
+            let sentries = SENTRY_P2P_ADDRESSES.parse(matches);
+            let output_folder = OUTPUT_FOLDER_PATH.parse(matches);
             Self {
-                block_height,
-                out_file_path,
-                historic,
+                validator,
+                sentries,
+                output_folder,
             }
         }
 
         fn def(app: App) -> App {
-            app.arg(BLOCK_HEIGHT_OPT.def().help(
-                "The block height to dump. Defaults to latest committed
-                block.",
+            app.arg(VALIDATOR_P2P_ADDRESS.def().help(
+                "The P2P address of the validator node, including its node \
+                 ID, e.g. \"tcp://<node-id>@10.0.0.1:26656\".",
             ))
-            .arg(OUT_FILE_PATH_OPT.def().help(
-                "Path for the output file (omitting file extension). Defaults \
-                 to \"db_dump.{block_height}.toml\" in the current working \
-                 directory.",
+            .arg(SENTRY_P2P_ADDRESSES.def().help(
+                "Comma separated P2P addresses of the sentry nodes, including \
+                 their node IDs.",
             ))
-            .arg(
-                HISTORIC
-                    .def()
-                    .help("If provided, dump also the diff of the last height"),
-            )
+            .arg(OUTPUT_FOLDER_PATH.def().help(
+                "The directory to write the configs to, as \"validator.toml\" \
+                 and \"sentry-<n>.toml\". When not set, they are printed.",
+            ))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct Faucet {
+        /// The address of the ledger node to submit transfers to
+        pub ledger_address: TendermintAddress,
+        /// The address to serve the faucet on
+        pub listen_address: SocketAddr,
+        /// The account to transfer the tokens from
+        pub source: WalletAddress,
+        /// The dispensed token
+        pub token: WalletAddress,
+        /// The amount transferred per request
+        pub amount: token::DenominatedAmount,
+        /// The max amount transferred to an address per quota period
+        pub quota: token::DenominatedAmount,
+        /// The duration of a quota period
+        pub quota_period: Duration,
+        /// The proof-of-work difficulty, in leading zero bits
+        pub difficulty: u8,
+    }
+
+    impl Args for Faucet {
+        fn parse(matches: &ArgMatches) -> Self {
+            let ledger_address = LEDGER_ADDRESS_DEFAULT.parse(matches);
+            let listen_address = FAUCET_LISTEN_ADDRESS.parse(matches);
+            let source = SOURCE.parse(matches);
+            let token = TOKEN.parse(matches);
+            let amount = AMOUNT.parse(matches);
+            let quota = FAUCET_QUOTA.parse(matches);
+            let quota_period = FAUCET_QUOTA_PERIOD.parse(matches);
+            let difficulty = POW_DIFFICULTY.parse(matches);
+            Self {
+                ledger_address,
+                listen_address,
+                source,
+                token,
+                amount,
+                quota,
+                quota_period,
+                difficulty,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(LEDGER_ADDRESS_DEFAULT.def().help(LEDGER_ADDRESS_ABOUT))
+                .arg(
+                    FAUCET_LISTEN_ADDRESS
+                        .def()
+                        .help("The address to serve the faucet on."),
+                )
+                .arg(SOURCE.def().help(
+                    "The account to transfer the tokens from. Its signing \
+                     keys must be in the wallet and unencrypted.",
+                ))
+                .arg(TOKEN.def().help("The token to dispense."))
+                .arg(AMOUNT.def().help("The amount transferred per request."))
+                .arg(FAUCET_QUOTA.def().help(
+                    "The max amount transferred to the same address per quota \
+                     period.",
+                ))
+                .arg(FAUCET_QUOTA_PERIOD.def().help(
+                    "The duration of a quota period, e.g. \"24h\". Defaults \
+                     to 24 hours.",
+                ))
+                .arg(POW_DIFFICULTY.def().help(
+                    "The number of leading zero bits required in the hash of \
+                     a proof-of-work solution. Defaults to 20.",
+                ))
         }
     }
 
@@ -3340,6 +5213,9 @@ pub mod args {
                     .fee_payer
                     .map(|fee_payer| chain_ctx.get(&fee_payer)),
                 fee_token: chain_ctx.get(&self.fee_token),
+                fee_estimate: self
+                    .fee_estimate
+                    .map(|estimate| estimate.to_sdk_ctxless()),
                 code_path: self.code_path,
             }
         }
@@ -3352,8 +5228,19 @@ pub mod args {
             let recipient = BRIDGE_POOL_TARGET.parse(matches);
             let sender = SOURCE.parse(matches);
             let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
-            let fee_amount =
-                InputAmount::Unvalidated(BRIDGE_POOL_GAS_AMOUNT.parse(matches));
+            let fee = BRIDGE_POOL_FEE.parse(matches);
+            let fee_amount = InputAmount::Unvalidated(match fee {
+                Some(BridgePoolFee::Amount(amount)) => amount,
+                _ => BRIDGE_POOL_GAS_AMOUNT.parse(matches),
+            });
+            let fee_estimate =
+                matches!(fee, Some(BridgePoolFee::Auto)).then(|| {
+                    BridgePoolFeeEstimate {
+                        eth_rpc_endpoint: ETH_RPC_ENDPOINT.parse(matches),
+                        conversion_rate: CONVERSION_RATE.parse(matches),
+                        blocks: ETH_BLOCKS.parse(matches),
+                    }
+                });
             let fee_payer = BRIDGE_POOL_GAS_PAYER.parse(matches);
             let fee_token = BRIDGE_POOL_GAS_TOKEN.parse(matches);
             let code_path = PathBuf::from(TX_BRIDGE_POOL_WASM);
@@ -3367,6 +5254,7 @@ pub mod args {
                 fee_amount,
                 fee_payer,
                 fee_token,
+                fee_estimate,
                 code_path,
                 nut,
             }
@@ -3396,6 +5284,17 @@ pub mod args {
                     "The amount of gas you wish to pay to have this transfer \
                      relayed to Ethereum.",
                 ))
+                .arg(
+                    BRIDGE_POOL_FEE
+                        .def()
+                        .help(
+                            "The amount of gas you wish to pay to have this \
+                             transfer relayed to Ethereum, or `auto` to pay a \
+                             fee estimated from recent relays and Ethereum \
+                             gas prices.",
+                        )
+                        .conflicts_with(BRIDGE_POOL_GAS_AMOUNT.name),
+                )
                 .arg(BRIDGE_POOL_GAS_PAYER.def().help(
                     "The Namada address of the account paying the gas. By \
                      default, it is the same as the source.",
@@ -3404,6 +5303,25 @@ pub mod args {
                     "The token for paying the Bridge pool gas fees. Defaults \
                      to NAM.",
                 ))
+                .arg(
+                    ETH_RPC_ENDPOINT.def().help(
+                        "The Ethereum RPC endpoint, used with `--fee auto`.",
+                    ),
+                )
+                .arg(
+                    CONVERSION_RATE_OPT
+                        .def()
+                        .help(
+                            "The conversion rate from the gas token to gwei, \
+                             required with `--fee auto`.",
+                        )
+                        .required_if_eq(BRIDGE_POOL_FEE.name, "auto"),
+                )
+                .arg(ETH_BLOCKS.def().help(
+                    "The number of Ethereum blocks within which the transfer \
+                     should be relayed, used with `--fee auto`. Defaults to \
+                     10.",
+                ))
                 .arg(NUT.def().help(
                     "Add Non Usable Tokens (NUTs) to the Bridge pool. These \
                      are usually obtained from invalid transfers to Namada.",
@@ -3411,6 +5329,123 @@ pub mod args {
         }
     }
 
+    impl CliToSdkCtxless<BridgePoolFeeEstimate<SdkTypes>>
+        for BridgePoolFeeEstimate<CliTypes>
+    {
+        fn to_sdk_ctxless(self) -> BridgePoolFeeEstimate<SdkTypes> {
+            BridgePoolFeeEstimate::<SdkTypes> {
+                eth_rpc_endpoint: (),
+                conversion_rate: self.conversion_rate,
+                blocks: self.blocks,
+            }
+        }
+    }
+
+    impl Args for BridgePoolFeeEstimate<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let eth_rpc_endpoint = ETH_RPC_ENDPOINT.parse(matches);
+            let conversion_rate = CONVERSION_RATE.parse(matches);
+            let blocks = ETH_BLOCKS.parse(matches);
+            Self {
+                eth_rpc_endpoint,
+                conversion_rate,
+                blocks,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(ETH_RPC_ENDPOINT.def().help("The Ethereum RPC endpoint."))
+                .arg(
+                    CONVERSION_RATE.def().help(
+                        "The conversion rate from the gas token to gwei.",
+                    ),
+                )
+                .arg(ETH_BLOCKS.def().help(
+                    "The number of Ethereum blocks within which the transfer \
+                     should be relayed. Defaults to 10.",
+                ))
+        }
+    }
+
+    impl CliToSdk<BridgePoolFees<SdkTypes>> for BridgePoolFees<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> BridgePoolFees<SdkTypes> {
+            BridgePoolFees::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                fee_token: ctx.borrow_chain_or_exit().get(&self.fee_token),
+                estimate: self.estimate.to_sdk_ctxless(),
+            }
+        }
+    }
+
+    impl Args for BridgePoolFees<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let fee_token = BRIDGE_POOL_GAS_TOKEN.parse(matches);
+            let estimate = BridgePoolFeeEstimate::parse(matches);
+            Self {
+                query,
+                fee_token,
+                estimate,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(BRIDGE_POOL_GAS_TOKEN.def().help(
+                    "The token for paying the Bridge pool gas fees. Defaults \
+                     to NAM.",
+                ))
+                .add_args::<BridgePoolFeeEstimate<CliTypes>>()
+        }
+    }
+
+    impl CliToSdk<QueryTransferStatus<SdkTypes>> for QueryTransferStatus<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryTransferStatus<SdkTypes> {
+            QueryTransferStatus::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                hash: self.hash,
+            }
+        }
+    }
+
+    impl Args for QueryTransferStatus<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let hash = TRANSFER_HASH.parse(matches);
+            Self { query, hash }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                TRANSFER_HASH
+                    .def()
+                    .help("The keccak hash of the transfer to Ethereum."),
+            )
+        }
+    }
+
+    impl CliToSdk<QueryNutBalances<SdkTypes>> for QueryNutBalances<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryNutBalances<SdkTypes> {
+            QueryNutBalances::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+            }
+        }
+    }
+
+    impl Args for QueryNutBalances<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owner = OWNER.parse(matches);
+            Self { query, owner }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(OWNER.def().help("The owner of the NUTs."))
+        }
+    }
+
     impl CliToSdk<RecommendBatch<SdkTypes>> for RecommendBatch<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> RecommendBatch<SdkTypes> {
             let chain_ctx = ctx.borrow_chain_or_exit();
@@ -3722,6 +5757,7 @@ pub mod args {
                 retry_dur: self.retry_dur,
                 success_dur: self.success_dur,
                 safe_mode: self.safe_mode,
+                api_addr: self.api_addr,
             }
         }
     }
@@ -3742,6 +5778,7 @@ pub mod args {
                 DAEMON_MODE_RETRY_DUR.parse(matches).map(|dur| dur.0);
             let success_dur =
                 DAEMON_MODE_SUCCESS_DUR.parse(matches).map(|dur| dur.0);
+            let api_addr = RELAYER_API_ADDRESS.parse(matches);
             Self {
                 sync,
                 daemon,
@@ -3755,6 +5792,7 @@ pub mod args {
                 retry_dur,
                 success_dur,
                 safe_mode,
+                api_addr,
             }
         }
 
@@ -3776,6 +5814,18 @@ pub mod args {
                     "The amount of time to sleep between successful daemon \
                      mode relays.",
                 ))
+                .arg(
+                    RELAYER_API_ADDRESS
+                        .def()
+                        .help(
+                            "The local address of a REST API to serve in \
+                             daemon mode, to manage the relayer while it \
+                             runs. The requests must be authorized with the \
+                             token set in the NAMADA_RELAYER_API_TOKEN env \
+                             var.",
+                        )
+                        .requires(DAEMON_MODE.name),
+                )
                 .arg(ETH_ADDRESS_OPT.def().help(
                     "The address of the Ethereum wallet to pay the gas fees. \
                      If unset, the default wallet is used.",
@@ -3823,6 +5873,31 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<TxBuild<SdkTypes>> for TxBuild<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxBuild<SdkTypes> {
+            TxBuild::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                interactive: self.interactive,
+            }
+        }
+    }
+
+    impl Args for TxBuild<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let interactive = INTERACTIVE.parse(matches);
+            Self { tx, interactive }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>().arg(INTERACTIVE.def().help(
+                "Prompt for the code, data, extra sections and signers of the \
+                 transaction, preview it and estimate its gas before saving \
+                 it unsigned.",
+            ))
+        }
+    }
+
     impl Args for TxCustom<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let tx = Tx::parse(matches);
@@ -3874,53 +5949,183 @@ pub mod args {
         }
     }
 
-    impl CliToSdk<TxTransfer<SdkTypes>> for TxTransfer<CliTypes> {
-        fn to_sdk(self, ctx: &mut Context) -> TxTransfer<SdkTypes> {
-            let tx = self.tx.to_sdk(ctx);
-            let chain_ctx = ctx.borrow_mut_chain_or_exit();
-            TxTransfer::<SdkTypes> {
-                tx,
-                source: chain_ctx.get_cached(&self.source),
-                target: chain_ctx.get(&self.target),
-                token: chain_ctx.get(&self.token),
-                amount: self.amount,
-                native_token: chain_ctx.native_token.clone(),
-                tx_code_path: self.tx_code_path.to_path_buf(),
+    impl CliToSdk<TxTransfer<SdkTypes>> for TxTransfer<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxTransfer<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            TxTransfer::<SdkTypes> {
+                tx,
+                source: chain_ctx.get_cached(&self.source),
+                target: chain_ctx.get(&self.target),
+                token: chain_ctx.get(&self.token),
+                amount: self.amount,
+                native_token: chain_ctx.native_token.clone(),
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for TxTransfer<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let source = TRANSFER_SOURCE.parse(matches);
+            let target = TRANSFER_TARGET.parse(matches);
+            let token = TOKEN.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let tx_code_path = PathBuf::from(TX_TRANSFER_WASM);
+            Self {
+                tx,
+                source,
+                target,
+                token,
+                amount,
+                tx_code_path,
+                native_token: (),
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(TRANSFER_SOURCE.def().help(
+                    "The source account address. The source's key may be used \
+                     to produce the signature.",
+                ))
+                .arg(TRANSFER_TARGET.def().help(
+                    "The target account address. The target's key may be used \
+                     to produce the signature.",
+                ))
+                .arg(TOKEN.def().help("The transfer token."))
+                .arg(AMOUNT.def().help("The amount to transfer in decimal."))
+        }
+    }
+
+    impl CliToSdk<TxVestingTransfer<SdkTypes>> for TxVestingTransfer<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxVestingTransfer<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            TxVestingTransfer::<SdkTypes> {
+                tx,
+                source: chain_ctx.get(&self.source),
+                target: chain_ctx.get(&self.target),
+                token: chain_ctx.get(&self.token),
+                amount: self.amount,
+                start: self.start,
+                end: self.end,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for TxVestingTransfer<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let source = SOURCE.parse(matches);
+            let target = TARGET.parse(matches);
+            let token = TOKEN.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let start = START_EPOCH.parse(matches);
+            let end = END_EPOCH.parse(matches);
+            let tx_code_path = PathBuf::from(TX_VESTING_TRANSFER_WASM);
+            Self {
+                tx,
+                source,
+                target,
+                token,
+                amount,
+                start,
+                end,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(SOURCE.def().help(
+                    "The source account address. The source's key is used to \
+                     produce the signature.",
+                ))
+                .arg(TARGET.def().help(
+                    "The target account address whose received tokens are \
+                     locked.",
+                ))
+                .arg(TOKEN.def().help("The transfer token."))
+                .arg(AMOUNT.def().help("The amount to transfer in decimal."))
+                .arg(START_EPOCH.def().help(
+                    "The epoch at which the locked tokens start to be \
+                     released.",
+                ))
+                .arg(END_EPOCH.def().help(
+                    "The epoch at which the tokens are fully released. The \
+                     tokens are released linearly from the start epoch. Use \
+                     the start epoch for a release of all the tokens at once.",
+                ))
+        }
+    }
+
+    impl CliToSdk<TxSchedule<SdkTypes>> for TxSchedule<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxSchedule<SdkTypes> {
+            TxSchedule::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+                epoch: self.epoch,
+                gas_limit: self.gas_limit,
+                code_path: self.code_path,
+                data: self.data.map(|data_path| {
+                    std::fs::read(data_path)
+                        .expect("Expected a file at given path")
+                }),
+                tx_code_path: self.tx_code_path,
             }
         }
     }
 
-    impl Args for TxTransfer<CliTypes> {
+    impl Args for TxSchedule<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let tx = Tx::parse(matches);
-            let source = TRANSFER_SOURCE.parse(matches);
-            let target = TRANSFER_TARGET.parse(matches);
-            let token = TOKEN.parse(matches);
-            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
-            let tx_code_path = PathBuf::from(TX_TRANSFER_WASM);
+            let owner = OWNER.parse(matches);
+            let epoch = SCHEDULE_EPOCH.parse(matches);
+            let gas_limit = SCHEDULED_GAS_LIMIT.parse(matches);
+            let code_path = CODE_PATH.parse(matches);
+            let data = DATA_PATH_OPT.parse(matches);
+            let tx_code_path = PathBuf::from(TX_SCHEDULE_WASM);
             Self {
                 tx,
-                source,
-                target,
-                token,
-                amount,
+                owner,
+                epoch,
+                gas_limit,
+                code_path,
+                data,
                 tx_code_path,
-                native_token: (),
             }
         }
 
         fn def(app: App) -> App {
             app.add_args::<Tx<CliTypes>>()
-                .arg(TRANSFER_SOURCE.def().help(
-                    "The source account address. The source's key may be used \
-                     to produce the signature.",
+                .arg(OWNER.def().help(
+                    "The address authorizing the scheduled transaction and \
+                     paying its fee upfront. Its keys are used to sign the \
+                     scheduled transaction.",
                 ))
-                .arg(TRANSFER_TARGET.def().help(
-                    "The target account address. The target's key may be used \
-                     to produce the signature.",
+                .arg(
+                    SCHEDULE_EPOCH
+                        .def()
+                        .help("The epoch at whose start the tx is executed."),
+                )
+                .arg(SCHEDULED_GAS_LIMIT.def().help(
+                    "The gas limit of the scheduled transaction. The fee paid \
+                     upfront is this limit times the minimum gas price of the \
+                     native token.",
+                ))
+                .arg(
+                    CODE_PATH.def().help(
+                        "The path to the scheduled transaction's WASM code.",
+                    ),
+                )
+                .arg(DATA_PATH_OPT.def().help(
+                    "The data file at this path containing arbitrary bytes \
+                     will be passed to the scheduled transaction code when \
+                     it's executed.",
                 ))
-                .arg(TOKEN.def().help("The transfer token."))
-                .arg(AMOUNT.def().help("The amount to transfer in decimal."))
         }
     }
 
@@ -4627,6 +6832,7 @@ pub mod args {
                 is_offline: self.is_offline,
                 is_pgf_stewards: self.is_pgf_stewards,
                 is_pgf_funding: self.is_pgf_funding,
+                is_ibc_client_recovery: self.is_ibc_client_recovery,
                 native_token: ctx.borrow_chain_or_exit().native_token.clone(),
                 tx_code_path: self.tx_code_path,
             }
@@ -4640,6 +6846,8 @@ pub mod args {
             let is_offline = PROPOSAL_OFFLINE.parse(matches);
             let is_pgf_stewards = PROPOSAL_PGF_STEWARD.parse(matches);
             let is_pgf_funding = PROPOSAL_PGF_FUNDING.parse(matches);
+            let is_ibc_client_recovery =
+                PROPOSAL_IBC_CLIENT_RECOVERY.parse(matches);
             let tx_code_path = PathBuf::from(TX_INIT_PROPOSAL);
 
             Self {
@@ -4650,6 +6858,7 @@ pub mod args {
                 is_offline,
                 is_pgf_stewards,
                 is_pgf_funding,
+                is_ibc_client_recovery,
             }
         }
 
@@ -4669,6 +6878,7 @@ pub mod args {
                             PROPOSAL_PGF_FUNDING.name,
                             PROPOSAL_PGF_STEWARD.name,
                             PROPOSAL_ETH.name,
+                            PROPOSAL_IBC_CLIENT_RECOVERY.name,
                         ]),
                 )
                 .arg(
@@ -4678,6 +6888,7 @@ pub mod args {
                         .conflicts_with_all([
                             PROPOSAL_PGF_FUNDING.name,
                             PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_IBC_CLIENT_RECOVERY.name,
                         ]),
                 )
                 .arg(
@@ -4690,6 +6901,7 @@ pub mod args {
                         .conflicts_with_all([
                             PROPOSAL_ETH.name,
                             PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_IBC_CLIENT_RECOVERY.name,
                         ]),
                 )
                 .arg(
@@ -4702,6 +6914,19 @@ pub mod args {
                         .conflicts_with_all([
                             PROPOSAL_ETH.name,
                             PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_IBC_CLIENT_RECOVERY.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_IBC_CLIENT_RECOVERY
+                        .def()
+                        .help(
+                            "Flag if the proposal is of type                              ibc-client-recovery. Used to substitute an                              expired or frozen IBC client.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_ETH.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
                         ]),
                 )
         }
@@ -4781,6 +7006,46 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<UpdateVoteProxy<SdkTypes>> for UpdateVoteProxy<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> UpdateVoteProxy<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            UpdateVoteProxy::<SdkTypes> {
+                tx,
+                delegator: chain_ctx.get(&self.delegator),
+                proxy: self.proxy.map(|x| chain_ctx.get(&x)),
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for UpdateVoteProxy<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let delegator = DELEGATOR.parse(matches);
+            let proxy = PROXY_OPT.parse(matches);
+            let tx_code_path = PathBuf::from(TX_UPDATE_VOTE_PROXY);
+            Self {
+                tx,
+                delegator,
+                proxy,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(DELEGATOR.def().help(
+                    "The delegator whose bonded stake the proxy votes with.",
+                ))
+                .arg(PROXY_OPT.def().help(
+                    "The address that votes on behalf of the delegator on \
+                     every proposal that the delegator doesn't vote on \
+                     directly. If not given, the current proxy is removed.",
+                ))
+        }
+    }
+
     impl CliToSdk<RevealPk<SdkTypes>> for RevealPk<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> RevealPk<SdkTypes> {
             let tx = self.tx.to_sdk(ctx);
@@ -4976,6 +7241,30 @@ pub mod args {
         }
     }
 
+    impl Args for QueryFeeReport<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let epoch = EPOCH.parse(matches);
+            Self { query, epoch }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(EPOCH.def().help(
+                "The epoch of the report. Defaults to the last committed \
+                 epoch.",
+            ))
+        }
+    }
+
+    impl CliToSdk<QueryFeeReport<SdkTypes>> for QueryFeeReport<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryFeeReport<SdkTypes> {
+            QueryFeeReport::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                epoch: self.epoch,
+            }
+        }
+    }
+
     impl CliToSdk<Withdraw<SdkTypes>> for Withdraw<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> Withdraw<SdkTypes> {
             let tx = self.tx.to_sdk(ctx);
@@ -5051,6 +7340,50 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<SetAutoCompound<SdkTypes>> for SetAutoCompound<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> SetAutoCompound<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            SetAutoCompound::<SdkTypes> {
+                tx,
+                validator: chain_ctx.get(&self.validator),
+                source: self.source.map(|x| chain_ctx.get(&x)),
+                enabled: self.enabled,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for SetAutoCompound<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let validator = VALIDATOR.parse(matches);
+            let source = SOURCE_OPT.parse(matches);
+            let enabled = !DISABLE.parse(matches);
+            let tx_code_path = PathBuf::from(TX_SET_AUTO_COMPOUND_WASM);
+            Self {
+                tx,
+                validator,
+                source,
+                enabled,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(VALIDATOR.def().help("Validator address."))
+                .arg(SOURCE_OPT.def().help(
+                    "Source address of the bond. For self-bonds, the \
+                     validator is also the source.",
+                ))
+                .arg(DISABLE.def().help(
+                    "Stop restaking the rewards of the bond. By default, \
+                     auto-compounding is enabled.",
+                ))
+        }
+    }
+
     impl CliToSdk<QueryConversions<SdkTypes>> for QueryConversions<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> QueryConversions<SdkTypes> {
             QueryConversions::<SdkTypes> {
@@ -5061,56 +7394,208 @@ pub mod args {
         }
     }
 
-    impl Args for QueryConversions<CliTypes> {
+    impl Args for QueryConversions<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let token = TOKEN_OPT.parse(matches);
+            let epoch = EPOCH.parse(matches);
+            Self {
+                query,
+                epoch,
+                token,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(
+                    EPOCH
+                        .def()
+                        .help("The epoch for which to query conversions."),
+                )
+                .arg(
+                    TOKEN_OPT.def().help(
+                        "The token address for which to query conversions.",
+                    ),
+                )
+        }
+    }
+
+    impl CliToSdk<TxUpdateAccountKeys<SdkTypes>> for TxUpdateAccountKeys<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxUpdateAccountKeys<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            TxUpdateAccountKeys::<SdkTypes> {
+                tx,
+                tx_code_path: self.tx_code_path,
+                addr: chain_ctx.get(&self.addr),
+                public_keys: self
+                    .public_keys
+                    .iter()
+                    .map(|pk| chain_ctx.get(pk))
+                    .collect(),
+                threshold: self.threshold,
+            }
+        }
+    }
+
+    impl Args for TxUpdateAccountKeys<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let addr = ADDRESS.parse(matches);
+            let tx_code_path = PathBuf::from(TX_UPDATE_ACCOUNT_KEYS_WASM);
+            let public_keys = PUBLIC_KEYS.parse(matches);
+            let threshold = THRESHOLD.parse(matches);
+            Self {
+                tx,
+                tx_code_path,
+                addr,
+                public_keys,
+                threshold,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(ADDRESS.def().help(
+                    "The established account's address. The change must be \
+                     signed by enough of its current keys to meet its current \
+                     threshold.",
+                ))
+                .arg(
+                    PUBLIC_KEYS
+                        .def()
+                        .help(
+                            "The list of public keys that replaces the \
+                             current keys of the account.",
+                        )
+                        .required(true),
+                )
+                .arg(THRESHOLD.def().help(
+                    "The new minimum number of signatures to be provided for \
+                     authorization. Must not be more than the number of \
+                     public keys. Defaults to 1 when a single key is given.",
+                ))
+        }
+    }
+
+    impl CliToSdk<QueryAccount<SdkTypes>> for QueryAccount<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryAccount<SdkTypes> {
+            QueryAccount::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+            }
+        }
+    }
+
+    impl Args for QueryAccount<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owner = OWNER.parse(matches);
+            Self { query, owner }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                OWNER
+                    .def()
+                    .help("The substorage space address to query.")
+                    .visible_alias("address")
+                    .required(true),
+            )
+        }
+    }
+
+    impl CliToSdk<QueryBalances<SdkTypes>> for QueryBalances<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryBalances<SdkTypes> {
+            let query = self.query.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            QueryBalances::<SdkTypes> {
+                query,
+                owners: self.owners.iter().map(|x| chain_ctx.get(x)).collect(),
+                tokens: self.tokens.iter().map(|x| chain_ctx.get(x)).collect(),
+            }
+        }
+    }
+
+    impl Args for QueryBalances<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owners = OWNERS.parse(matches);
+            let tokens = TOKENS.parse(matches);
+            Self {
+                query,
+                owners,
+                tokens,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(OWNERS.def().help(
+                    "Comma separated list of the addresses whose balances to \
+                     query.",
+                ))
+                .arg(TOKENS.def().help(
+                    "Comma separated list of the tokens whose balances to \
+                     query.",
+                ))
+        }
+    }
+
+    impl CliToSdk<QueryVesting<SdkTypes>> for QueryVesting<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryVesting<SdkTypes> {
+            let query = self.query.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_mut_chain_or_exit();
+            QueryVesting::<SdkTypes> {
+                query,
+                owner: chain_ctx.get(&self.owner),
+                token: chain_ctx.get(&self.token),
+            }
+        }
+    }
+
+    impl Args for QueryVesting<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
-            let token = TOKEN_OPT.parse(matches);
-            let epoch = EPOCH.parse(matches);
+            let owner = OWNER.parse(matches);
+            let token = TOKEN.parse(matches);
             Self {
                 query,
-                epoch,
+                owner,
                 token,
             }
         }
 
         fn def(app: App) -> App {
             app.add_args::<Query<CliTypes>>()
-                .arg(
-                    EPOCH
-                        .def()
-                        .help("The epoch for which to query conversions."),
-                )
-                .arg(
-                    TOKEN_OPT.def().help(
-                        "The token address for which to query conversions.",
-                    ),
-                )
+                .arg(OWNER.def().help("The owner of the vesting balance."))
+                .arg(TOKEN.def().help("The token of the vesting balance."))
         }
     }
 
-    impl CliToSdk<QueryAccount<SdkTypes>> for QueryAccount<CliTypes> {
-        fn to_sdk(self, ctx: &mut Context) -> QueryAccount<SdkTypes> {
-            QueryAccount::<SdkTypes> {
+    impl CliToSdk<QueryIbcDenom<SdkTypes>> for QueryIbcDenom<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryIbcDenom<SdkTypes> {
+            QueryIbcDenom::<SdkTypes> {
                 query: self.query.to_sdk(ctx),
-                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+                hash: self.hash,
             }
         }
     }
 
-    impl Args for QueryAccount<CliTypes> {
+    impl Args for QueryIbcDenom<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
-            let owner = OWNER.parse(matches);
-            Self { query, owner }
+            let hash = IBC_DENOM_HASH.parse(matches);
+            Self { query, hash }
         }
 
         fn def(app: App) -> App {
-            app.add_args::<Query<CliTypes>>().arg(
-                OWNER
-                    .def()
-                    .help("The substorage space address to query.")
-                    .required(true),
-            )
+            app.add_args::<Query<CliTypes>>()
+                .arg(IBC_DENOM_HASH.def().help(
+                    "The hash of the IBC denom, or the address of the IBC \
+                     token.",
+                ))
         }
     }
 
@@ -5204,6 +7689,7 @@ pub mod args {
                 query,
                 owner: self.owner.map(|x| chain_ctx.get(&x)),
                 validator: self.validator.map(|x| chain_ctx.get(&x)),
+                epoch: self.epoch,
             }
         }
     }
@@ -5213,10 +7699,12 @@ pub mod args {
             let query = Query::parse(matches);
             let owner = OWNER_OPT.parse(matches);
             let validator = VALIDATOR_OPT.parse(matches);
+            let epoch = EPOCH.parse(matches);
             Self {
                 query,
                 owner,
                 validator,
+                epoch,
             }
         }
 
@@ -5232,6 +7720,10 @@ pub mod args {
                         .def()
                         .help("The validator's address whose bonds to query."),
                 )
+                .arg(EPOCH.def().help(
+                    "The epoch at which to query the bonds (corresponding to \
+                     the last committed block, if not specified).",
+                ))
         }
     }
 
@@ -5532,6 +8024,77 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<TxBridgePauseVote<SdkTypes>> for TxBridgePauseVote<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxBridgePauseVote<SdkTypes> {
+            TxBridgePauseVote::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                validator: ctx.borrow_chain_or_exit().get(&self.validator),
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for TxBridgePauseVote<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let validator = VALIDATOR.parse(matches);
+            let tx_code_path = PathBuf::from(TX_BRIDGE_PAUSE_VOTE_WASM);
+            Self {
+                tx,
+                validator,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>().arg(
+                VALIDATOR
+                    .def()
+                    .help("The address of the validator casting the vote."),
+            )
+        }
+    }
+
+    impl CliToSdk<TxConvertNuts<SdkTypes>> for TxConvertNuts<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> TxConvertNuts<SdkTypes> {
+            TxConvertNuts::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+                asset: self.asset,
+                amount: self.amount,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for TxConvertNuts<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let owner = OWNER.parse(matches);
+            let asset = ERC20.parse(matches);
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let tx_code_path = PathBuf::from(TX_CONVERT_NUTS_WASM);
+            Self {
+                tx,
+                owner,
+                asset,
+                amount,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(OWNER.def().help("The owner of the NUTs to convert."))
+                .arg(
+                    ERC20
+                        .def()
+                        .help("The Ethereum address of the ERC20 token."),
+                )
+                .arg(AMOUNT.def().help("The amount of NUTs to convert."))
+        }
+    }
+
     impl CliToSdk<TxReactivateValidator<SdkTypes>>
         for TxReactivateValidator<CliTypes>
     {
@@ -5598,6 +8161,34 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<BroadcastSignedTx<SdkTypes>> for BroadcastSignedTx<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> BroadcastSignedTx<SdkTypes> {
+            BroadcastSignedTx::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                tx_data: std::fs::read(self.tx_data).expect(""),
+            }
+        }
+    }
+
+    impl Args for BroadcastSignedTx<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let tx_path = TX_PATH.parse(matches);
+            Self {
+                tx,
+                tx_data: tx_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>().arg(
+                TX_PATH
+                    .def()
+                    .help("The path to the tx file with the signed tx."),
+            )
+        }
+    }
+
     impl CliToSdk<GenIbcShieldedTransafer<SdkTypes>>
         for GenIbcShieldedTransafer<CliTypes>
     {
@@ -5723,148 +8314,343 @@ pub mod args {
         fn to_sdk(self, ctx: &mut Context) -> QuerySlashes<SdkTypes> {
             QuerySlashes::<SdkTypes> {
                 query: self.query.to_sdk(ctx),
-                validator: self
-                    .validator
+                validator: self
+                    .validator
+                    .map(|x| ctx.borrow_chain_or_exit().get(&x)),
+            }
+        }
+    }
+
+    impl Args for QuerySlashes<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let validator = VALIDATOR_OPT.parse(matches);
+            Self { query, validator }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                VALIDATOR_OPT
+                    .def()
+                    .help("The validator's address whose slashes to query."),
+            )
+        }
+    }
+
+    impl CliToSdk<QueryRewards<SdkTypes>> for QueryRewards<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryRewards<SdkTypes> {
+            QueryRewards::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                validator: ctx.borrow_chain_or_exit().get(&self.validator),
+                source: self.source.map(|x| ctx.borrow_chain_or_exit().get(&x)),
+            }
+        }
+    }
+
+    impl Args for QueryRewards<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let source = SOURCE_OPT.parse(matches);
+            let validator = VALIDATOR.parse(matches);
+            Self {
+                query,
+                source,
+                validator,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(SOURCE_OPT.def().help(
+                    "Source address for the rewards query. For self-bonds, \
+                     this arg does not need to be supplied.",
+                ))
+                .arg(
+                    VALIDATOR
+                        .def()
+                        .help("Validator address for the rewards query."),
+                )
+        }
+    }
+
+    impl Args for QueryDelegations<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owner = OWNER.parse(matches);
+            Self { query, owner }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                OWNER.def().help(
+                    "The address of the owner of the delegations to find.",
+                ),
+            )
+        }
+    }
+
+    impl CliToSdk<QueryDelegations<SdkTypes>> for QueryDelegations<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryDelegations<SdkTypes> {
+            QueryDelegations::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+            }
+        }
+    }
+
+    impl Args for QueryStakingOverview<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let owner = OWNER.parse(matches);
+            Self { query, owner }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>().arg(
+                OWNER
+                    .def()
+                    .help("The address of the owner of the bonds to query."),
+            )
+        }
+    }
+
+    impl CliToSdk<QueryStakingOverview<SdkTypes>>
+        for QueryStakingOverview<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> QueryStakingOverview<SdkTypes> {
+            QueryStakingOverview::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+            }
+        }
+    }
+
+    impl Args for QueryFindValidator<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+            let tm_addr = TM_ADDRESS.parse(matches);
+            let validator_addr = VALIDATOR_OPT.parse(matches);
+            Self {
+                query,
+                tm_addr,
+                validator_addr,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(
+                    TM_ADDRESS
+                        .def()
+                        .help("The address of the validator in Tendermint."),
+                )
+                .arg(
+                    VALIDATOR_OPT
+                        .def()
+                        .help("The native address of the validator."),
+                )
+        }
+    }
+
+    impl CliToSdk<QueryFindValidator<SdkTypes>> for QueryFindValidator<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryFindValidator<SdkTypes> {
+            QueryFindValidator::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                tm_addr: self.tm_addr,
+                validator_addr: self
+                    .validator_addr
                     .map(|x| ctx.borrow_chain_or_exit().get(&x)),
             }
         }
     }
 
-    impl Args for QuerySlashes<CliTypes> {
+    impl Args for QueryBlock<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
-            let validator = VALIDATOR_OPT.parse(matches);
-            Self { query, validator }
+            let height = BLOCK_HEIGHT_OPT.parse(matches);
+            Self { query, height }
         }
 
         fn def(app: App) -> App {
-            app.add_args::<Query<CliTypes>>().arg(
-                VALIDATOR_OPT
-                    .def()
-                    .help("The validator's address whose slashes to query."),
-            )
+            app.add_args::<Query<CliTypes>>()
+                .arg(BLOCK_HEIGHT_OPT.def().help(
+                    "The height of the block to query. Defaults to the last \
+                     committed block.",
+                ))
         }
     }
 
-    impl CliToSdk<QueryRewards<SdkTypes>> for QueryRewards<CliTypes> {
-        fn to_sdk(self, ctx: &mut Context) -> QueryRewards<SdkTypes> {
-            QueryRewards::<SdkTypes> {
+    impl CliToSdk<QueryBlock<SdkTypes>> for QueryBlock<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryBlock<SdkTypes> {
+            QueryBlock::<SdkTypes> {
                 query: self.query.to_sdk(ctx),
-                validator: ctx.borrow_chain_or_exit().get(&self.validator),
-                source: self.source.map(|x| ctx.borrow_chain_or_exit().get(&x)),
+                height: self.height,
             }
         }
     }
 
-    impl Args for QueryRewards<CliTypes> {
+    impl Args for QueryProposer<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
-            let source = SOURCE_OPT.parse(matches);
-            let validator = VALIDATOR.parse(matches);
+            let height = BLOCK_HEIGHT_OPT.parse(matches);
+            let count = COUNT.parse(matches);
             Self {
                 query,
-                source,
-                validator,
+                height,
+                count,
             }
         }
 
         fn def(app: App) -> App {
             app.add_args::<Query<CliTypes>>()
-                .arg(SOURCE_OPT.def().help(
-                    "Source address for the rewards query. For self-bonds, \
-                     this arg does not need to be supplied.",
+                .arg(BLOCK_HEIGHT_OPT.def().help(
+                    "The first block height to query. Defaults to the height \
+                     of the next block.",
                 ))
                 .arg(
-                    VALIDATOR
+                    COUNT
                         .def()
-                        .help("Validator address for the rewards query."),
+                        .help("The number of consecutive heights to query."),
                 )
         }
     }
 
-    impl Args for QueryDelegations<CliTypes> {
+    impl CliToSdk<QueryProposer<SdkTypes>> for QueryProposer<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryProposer<SdkTypes> {
+            QueryProposer::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                height: self.height,
+                count: self.count,
+            }
+        }
+    }
+
+    impl Args for QueryValidatorSet<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
-            let owner = OWNER.parse(matches);
-            Self { query, owner }
+            let epoch = EPOCH.parse(matches);
+            let with_proof = WITH_PROOF.parse(matches);
+            Self {
+                query,
+                epoch,
+                with_proof,
+            }
         }
 
         fn def(app: App) -> App {
-            app.add_args::<Query<CliTypes>>().arg(
-                OWNER.def().help(
-                    "The address of the owner of the delegations to find.",
-                ),
-            )
+            app.add_args::<Query<CliTypes>>()
+                .arg(EPOCH.def().help(
+                    "The epoch of the validator set (corresponding to the \
+                     last committed block, if not specified).",
+                ))
+                .arg(WITH_PROOF.def().help(
+                    "Also query a Merkle proof of the validator set against \
+                     the state of the last committed block.",
+                ))
         }
     }
 
-    impl CliToSdk<QueryDelegations<SdkTypes>> for QueryDelegations<CliTypes> {
-        fn to_sdk(self, ctx: &mut Context) -> QueryDelegations<SdkTypes> {
-            QueryDelegations::<SdkTypes> {
+    impl CliToSdk<QueryValidatorSet<SdkTypes>> for QueryValidatorSet<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryValidatorSet<SdkTypes> {
+            QueryValidatorSet::<SdkTypes> {
                 query: self.query.to_sdk(ctx),
-                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+                epoch: self.epoch,
+                with_proof: self.with_proof,
             }
         }
     }
 
-    impl Args for QueryFindValidator<CliTypes> {
+    impl CliToSdk<QueryRawBytes<SdkTypes>> for QueryRawBytes<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryRawBytes<SdkTypes> {
+            QueryRawBytes::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+                storage_key: self.storage_key,
+                trust_node: self.trust_node,
+                trusted_height: self.trusted_height,
+                trusted_hash: self.trusted_hash,
+            }
+        }
+    }
+
+    impl Args for QueryRawBytes<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
+            let storage_key = STORAGE_KEY.parse(matches);
             let query = Query::parse(matches);
-            let tm_addr = TM_ADDRESS.parse(matches);
-            let validator_addr = VALIDATOR_OPT.parse(matches);
+            let trust_node = TRUST_NODE.parse(matches);
+            let trusted_height = TRUSTED_HEIGHT.parse(matches);
+            let trusted_hash = TRUSTED_HASH.parse(matches);
             Self {
+                storage_key,
                 query,
-                tm_addr,
-                validator_addr,
+                trust_node,
+                trusted_height,
+                trusted_hash,
             }
         }
 
         fn def(app: App) -> App {
             app.add_args::<Query<CliTypes>>()
+                .arg(STORAGE_KEY.def().help("Storage key"))
+                .arg(TRUST_NODE.def().help(
+                    "Whether to accept the result from the node as is. When \
+                     set to `false`, the result is verified against a header \
+                     checked by the built-in light client and refused if the \
+                     verification fails. Defaults to `true`.",
+                ))
                 .arg(
-                    TM_ADDRESS
+                    TRUSTED_HEIGHT
                         .def()
-                        .help("The address of the validator in Tendermint."),
+                        .help(
+                            "Height of a trusted header to initialize the \
+                             light client from.",
+                        )
+                        .requires(TRUSTED_HASH.name),
                 )
                 .arg(
-                    VALIDATOR_OPT
+                    TRUSTED_HASH
                         .def()
-                        .help("The native address of the validator."),
+                        .help(
+                            "Hash of the trusted header at the given \
+                             `--trusted-height`.",
+                        )
+                        .requires(TRUSTED_HEIGHT.name),
                 )
         }
     }
 
-    impl CliToSdk<QueryFindValidator<SdkTypes>> for QueryFindValidator<CliTypes> {
-        fn to_sdk(self, ctx: &mut Context) -> QueryFindValidator<SdkTypes> {
-            QueryFindValidator::<SdkTypes> {
-                query: self.query.to_sdk(ctx),
-                tm_addr: self.tm_addr,
-                validator_addr: self
-                    .validator_addr
-                    .map(|x| ctx.borrow_chain_or_exit().get(&x)),
-            }
-        }
-    }
-
-    impl CliToSdk<QueryRawBytes<SdkTypes>> for QueryRawBytes<CliTypes> {
-        fn to_sdk(self, ctx: &mut Context) -> QueryRawBytes<SdkTypes> {
-            QueryRawBytes::<SdkTypes> {
+    impl CliToSdk<QueryStorageValue<SdkTypes>> for QueryStorageValue<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> QueryStorageValue<SdkTypes> {
+            QueryStorageValue::<SdkTypes> {
                 query: self.query.to_sdk(ctx),
                 storage_key: self.storage_key,
+                value_type: self.value_type,
             }
         }
     }
 
-    impl Args for QueryRawBytes<CliTypes> {
+    impl Args for QueryStorageValue<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let storage_key = STORAGE_KEY.parse(matches);
             let query = Query::parse(matches);
-            Self { storage_key, query }
+            let value_type = VALUE_TYPE.parse(matches);
+            Self {
+                storage_key,
+                query,
+                value_type,
+            }
         }
 
         fn def(app: App) -> App {
+            let types: Vec<_> =
+                StorageValueType::ALL.iter().map(|ty| ty.name()).collect();
             app.add_args::<Query<CliTypes>>()
                 .arg(STORAGE_KEY.def().help("Storage key"))
+                .arg(VALUE_TYPE.def().help(format!(
+                    "The type to decode the value as. When not given, the \
+                     type is found from the storage key if it matches a known \
+                     pattern. One of: {}.",
+                    types.join(", ")
+                )))
         }
     }
 
@@ -5894,6 +8680,7 @@ pub mod args {
                 dry_run: self.dry_run,
                 dry_run_wrapper: self.dry_run_wrapper,
                 dump_tx: self.dump_tx,
+                offline_sign: self.offline_sign,
                 output_folder: self.output_folder,
                 force: self.force,
                 broadcast_only: self.broadcast_only,
@@ -5906,6 +8693,7 @@ pub mod args {
                     .fee_unshield
                     .map(|ref fee_unshield| ctx.get_cached(fee_unshield)),
                 gas_limit: self.gas_limit,
+                estimate_gas_limit: self.estimate_gas_limit,
                 signing_keys: self
                     .signing_keys
                     .iter()
@@ -5920,12 +8708,15 @@ pub mod args {
                 tx_reveal_code_path: self.tx_reveal_code_path,
                 password: self.password,
                 expiration: self.expiration,
+                expiration_height: self.expiration_height,
+                auto_resubmit: self.auto_resubmit,
                 chain_id: self
                     .chain_id
                     .or_else(|| Some(ctx.config.ledger.chain_id.clone())),
                 wrapper_fee_payer: self.wrapper_fee_payer.map(|x| ctx.get(&x)),
                 memo: self.memo,
                 use_device: self.use_device,
+                wallet_daemon: self.wallet_daemon,
             }
         }
     }
@@ -5948,6 +8739,11 @@ pub mod args {
                     .conflicts_with(DRY_RUN_TX.name),
             )
             .arg(DUMP_TX.def().help("Dump transaction bytes to a file."))
+            .arg(OFFLINE_SIGN.def().help(
+                "Dump the unsigned transaction together with the data needed \
+                 to sign it, to be signed with `namadaw sign-tx` on an \
+                 offline machine.",
+            ))
             .arg(FORCE.def().help(
                 "Submit the transaction even if it doesn't pass client checks.",
             ))
@@ -5979,7 +8775,10 @@ pub mod args {
             ))
             .arg(GAS_LIMIT.def().help(
                 "The multiplier of the gas limit resolution defining the \
-                 maximum amount of gas needed to run transaction.",
+                 maximum amount of gas needed to run transaction. Use `auto` \
+                 to set it from the gas used by a simulation of the signed \
+                 transaction on the node, with a safety margin. The \
+                 transaction is then signed twice.",
             ))
             .arg(WALLET_ALIAS_FORCE.def().help(
                 "Override the alias without confirmation if it already exists.",
@@ -5990,6 +8789,28 @@ pub mod args {
                  equivalent:\n2012-12-12T12:12:12Z\n2012-12-12 \
                  12:12:12Z\n2012-  12-12T12:  12:12Z",
             ))
+            .arg(EXPIRATION_HEIGHT_OPT.def().help(
+                "The last block height at which the transaction can be \
+                 included, after which the tx won't be accepted anymore.",
+            ))
+            .arg(
+                AUTO_RESUBMIT_OPT
+                    .def()
+                    .help(
+                        "The maximum number of times to resubmit the \
+                         transaction if it expires or is evicted from the \
+                         mempool before it's accepted. Each time, it's signed \
+                         again with a fresh timestamp, the same lifetime and \
+                         a 10% higher gas price. Requires an expiration or an \
+                         expiration height.",
+                    )
+                    .conflicts_with_all([
+                        BROADCAST_ONLY.name,
+                        DRY_RUN_TX.name,
+                        DRY_RUN_WRAPPER_TX.name,
+                        SIGNATURES.name,
+                    ]),
+            )
             .arg(
                 DISPOSABLE_SIGNING_KEY
                     .def()
@@ -6039,6 +8860,16 @@ pub mod args {
                 "Use an attached hardware wallet device to sign the \
                  transaction.",
             ))
+            .arg(
+                WALLET_DAEMON
+                    .def()
+                    .help(
+                        "The Unix socket of a wallet daemon (`namadaw \
+                         daemon`) to sign the transaction with the keys that \
+                         are not in the wallet.",
+                    )
+                    .conflicts_with(USE_DEVICE.name),
+            )
             .arg(
                 MEMO_OPT
                     .def()
@@ -6049,22 +8880,40 @@ pub mod args {
         fn parse(matches: &ArgMatches) -> Self {
             let dry_run = DRY_RUN_TX.parse(matches);
             let dry_run_wrapper = DRY_RUN_WRAPPER_TX.parse(matches);
-            let dump_tx = DUMP_TX.parse(matches);
+            let offline_sign = OFFLINE_SIGN.parse(matches);
+            let dump_tx = DUMP_TX.parse(matches) || offline_sign;
             let force = FORCE.parse(matches);
             let broadcast_only = BROADCAST_ONLY.parse(matches);
             let ledger_address = LEDGER_ADDRESS_DEFAULT.parse(matches);
             let initialized_account_alias = ALIAS_OPT.parse(matches);
-            let fee_amount =
-                FEE_AMOUNT_OPT.parse(matches).map(InputAmount::Unvalidated);
+            let profile = config::profiles::selected();
+            let fee_amount = FEE_AMOUNT_OPT
+                .parse(matches)
+                .or_else(|| {
+                    let gas_price = profile?.gas_price.as_ref()?;
+                    token::DenominatedAmount::from_str(gas_price).ok()
+                })
+                .map(InputAmount::Unvalidated);
             let fee_token = FEE_TOKEN.parse(matches);
             let fee_unshield = FEE_UNSHIELD_SPENDING_KEY.parse(matches);
             let _wallet_alias_force = WALLET_ALIAS_FORCE.parse(matches);
-            let gas_limit = GAS_LIMIT.parse(matches);
+            let (gas_limit, estimate_gas_limit) = match GAS_LIMIT.parse(matches)
+            {
+                GasLimitArg::Auto => (default_gas_limit(), true),
+                GasLimitArg::Limit(gas_limit) => (gas_limit, false),
+            };
             let wallet_alias_force = WALLET_ALIAS_FORCE.parse(matches);
             let expiration = EXPIRATION_OPT.parse(matches);
+            let expiration_height = EXPIRATION_HEIGHT_OPT.parse(matches);
+            let auto_resubmit = AUTO_RESUBMIT_OPT.parse(matches);
             let disposable_signing_key = DISPOSABLE_SIGNING_KEY.parse(matches);
-            let signing_keys = SIGNING_KEYS.parse(matches);
+            let mut signing_keys = SIGNING_KEYS.parse(matches);
             let signatures = SIGNATURES.parse(matches);
+            if signing_keys.is_empty() && signatures.is_empty() {
+                if let Some(signer) = profile.and_then(|p| p.signer.as_ref()) {
+                    signing_keys.push(FromContext::new(signer.clone()));
+                }
+            }
             let tx_reveal_code_path = PathBuf::from(TX_REVEAL_PK);
             let chain_id = CHAIN_ID_OPT.parse(matches);
             let password = None;
@@ -6072,10 +8921,12 @@ pub mod args {
             let wrapper_fee_payer = FEE_PAYER_OPT.parse(matches);
             let output_folder = OUTPUT_FOLDER_PATH.parse(matches);
             let use_device = USE_DEVICE.parse(matches);
+            let wallet_daemon = WALLET_DAEMON.parse(matches);
             Self {
                 dry_run,
                 dry_run_wrapper,
                 dump_tx,
+                offline_sign,
                 force,
                 broadcast_only,
                 ledger_address,
@@ -6085,7 +8936,10 @@ pub mod args {
                 fee_token,
                 fee_unshield,
                 gas_limit,
+                estimate_gas_limit,
                 expiration,
+                expiration_height,
+                auto_resubmit,
                 disposable_signing_key,
                 signing_keys,
                 signatures,
@@ -6096,6 +8950,7 @@ pub mod args {
                 output_folder,
                 memo,
                 use_device,
+                wallet_daemon,
             }
         }
     }
@@ -6379,114 +9234,300 @@ pub mod args {
 
         fn def(app: App) -> App {
             app.arg(
-                ALIAS_OPT
+                ALIAS_OPT
+                    .def()
+                    .help("An alias associated with the keys / addresses."),
+            )
+            .arg(
+                RAW_ADDRESS_OPT.def().help(
+                    "The bech32m encoded string of a transparent address.",
+                ),
+            )
+            .arg(
+                RAW_PUBLIC_KEY_OPT.def().help(
+                    "A public key associated with the transparent keypair.",
+                ),
+            )
+            .arg(RAW_PUBLIC_KEY_HASH_OPT.def().help(
+                "A public key hash associated with the transparent keypair.",
+            ))
+            .arg(RAW_PAYMENT_ADDRESS_OPT.def().help(
+                "The bech32m encoded string of a shielded payment address.",
+            ))
+            .group(
+                ArgGroup::new("addr_find_args")
+                    .args([
+                        ALIAS_OPT.name,
+                        RAW_ADDRESS_OPT.name,
+                        RAW_PUBLIC_KEY_OPT.name,
+                        RAW_PUBLIC_KEY_HASH_OPT.name,
+                        RAW_PAYMENT_ADDRESS_OPT.name,
+                    ])
+                    .required(true),
+            )
+            .arg(LIST_FIND_KEYS_ONLY.def().help("Find keys only."))
+            .arg(LIST_FIND_ADDRESSES_ONLY.def().help("Find addresses only."))
+            .group(ArgGroup::new("only_group").args([
+                LIST_FIND_KEYS_ONLY.name,
+                LIST_FIND_ADDRESSES_ONLY.name,
+            ]))
+            .arg(PRE_GENESIS.def().help(
+                "Use pre-genesis wallet, instead of for the current chain, if \
+                 any.",
+            ))
+            .arg(DECRYPT.def().help("Decrypt keys that are encrypted."))
+            .arg(
+                UNSAFE_SHOW_SECRET
+                    .def()
+                    .help("UNSAFE: Print the secret / spending key."),
+            )
+        }
+    }
+
+    impl Args for KeyAddressAdd {
+        fn parse(matches: &ArgMatches) -> Self {
+            let alias = ALIAS.parse(matches);
+            let alias_force = ALIAS_FORCE.parse(matches);
+            let value = VALUE.parse(matches);
+            let unsafe_dont_encrypt = UNSAFE_DONT_ENCRYPT.parse(matches);
+            Self {
+                alias,
+                alias_force,
+                value,
+                unsafe_dont_encrypt,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                ALIAS
+                    .def()
+                    .help("An alias to be associated with the new entry."),
+            )
+            .arg(ALIAS_FORCE.def().help(
+                "Override the alias without confirmation if it already exists.",
+            ))
+            .arg(VALUE.def().help(
+                "Any value of the following:\n- transparent pool secret \
+                 key\n- transparent pool public key\n- Ethereum-style \
+                 secp256k1 secret key, hex encoded\n- transparent pool \
+                 address\n- shielded pool spending key\n- shielded pool \
+                 viewing key\n- shielded pool payment address ",
+            ))
+            .arg(UNSAFE_DONT_ENCRYPT.def().help(
+                "UNSAFE: Do not encrypt the added keys. Do not use this for \
+                 keys used in a live network.",
+            ))
+        }
+    }
+
+    impl Args for KeyAddressRemove {
+        fn parse(matches: &ArgMatches) -> Self {
+            let alias = ALIAS.parse(matches);
+            let do_it = DO_IT.parse(matches);
+            Self { alias, do_it }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(ALIAS.def().help("An alias to be removed."))
+                .arg(DO_IT.def().help("Confirm alias removal.").required(true))
+        }
+    }
+
+    impl Args for KeyExport {
+        fn parse(matches: &ArgMatches) -> Self {
+            let alias = ALIAS.parse(matches);
+            Self { alias }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                ALIAS.def().help("The alias of the key you wish to export."),
+            )
+        }
+    }
+
+    impl Args for AddressBookExport {
+        fn parse(matches: &ArgMatches) -> Self {
+            let format = ADDRESS_BOOK_FORMAT.parse(matches);
+            let output = OUTPUT.parse(matches);
+            Self { format, output }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(ADDRESS_BOOK_FORMAT.def().help(
+                "The format of the address book, one of \"json\" (default), \
+                 \"csv\" or \"keplr\".",
+            ))
+            .arg(OUTPUT.def().help(
+                "Save the address book to a file. When not supplied, it will \
+                 be printed to stdout instead.",
+            ))
+        }
+    }
+
+    impl Args for AddressBookImport {
+        fn parse(matches: &ArgMatches) -> Self {
+            let file_path = PathBuf::from(FILE_PATH.parse(matches));
+            let format = ADDRESS_BOOK_FORMAT.parse(matches);
+            let on_conflict = ON_CONFLICT.parse(matches);
+            let dry_run = DRY_RUN_TX.parse(matches);
+            Self {
+                file_path,
+                format,
+                on_conflict,
+                dry_run,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                FILE_PATH
                     .def()
-                    .help("An alias associated with the keys / addresses."),
-            )
-            .arg(
-                RAW_ADDRESS_OPT.def().help(
-                    "The bech32m encoded string of a transparent address.",
-                ),
-            )
-            .arg(
-                RAW_PUBLIC_KEY_OPT.def().help(
-                    "A public key associated with the transparent keypair.",
-                ),
+                    .help("Path to the address book file to import."),
             )
-            .arg(RAW_PUBLIC_KEY_HASH_OPT.def().help(
-                "A public key hash associated with the transparent keypair.",
-            ))
-            .arg(RAW_PAYMENT_ADDRESS_OPT.def().help(
-                "The bech32m encoded string of a shielded payment address.",
+            .arg(ADDRESS_BOOK_FORMAT.def().help(
+                "The format of the address book, one of \"json\" (default), \
+                 \"csv\" or \"keplr\".",
             ))
-            .group(
-                ArgGroup::new("addr_find_args")
-                    .args([
-                        ALIAS_OPT.name,
-                        RAW_ADDRESS_OPT.name,
-                        RAW_PUBLIC_KEY_OPT.name,
-                        RAW_PUBLIC_KEY_HASH_OPT.name,
-                        RAW_PAYMENT_ADDRESS_OPT.name,
-                    ])
-                    .required(true),
-            )
-            .arg(LIST_FIND_KEYS_ONLY.def().help("Find keys only."))
-            .arg(LIST_FIND_ADDRESSES_ONLY.def().help("Find addresses only."))
-            .group(ArgGroup::new("only_group").args([
-                LIST_FIND_KEYS_ONLY.name,
-                LIST_FIND_ADDRESSES_ONLY.name,
-            ]))
-            .arg(PRE_GENESIS.def().help(
-                "Use pre-genesis wallet, instead of for the current chain, if \
-                 any.",
+            .arg(ON_CONFLICT.def().help(
+                "How to resolve an entry whose alias or address is already in \
+                 the wallet, one of \"skip\" (default), \"overwrite\" or \
+                 \"rename\".",
             ))
-            .arg(DECRYPT.def().help("Decrypt keys that are encrypted."))
             .arg(
-                UNSAFE_SHOW_SECRET
+                DRY_RUN_TX
                     .def()
-                    .help("UNSAFE: Print the secret / spending key."),
+                    .help("Preview the changes without modifying the wallet."),
             )
         }
     }
 
-    impl Args for KeyAddressAdd {
+    impl Args for AddressGrind {
         fn parse(matches: &ArgMatches) -> Self {
+            use crate::config::genesis::utils::VP_USER;
+            let prefix = ADDRESS_PATTERN.parse(matches);
+            let scheme = SCHEME.parse(matches);
             let alias = ALIAS.parse(matches);
             let alias_force = ALIAS_FORCE.parse(matches);
-            let value = VALUE.parse(matches);
             let unsafe_dont_encrypt = UNSAFE_DONT_ENCRYPT.parse(matches);
+            let vp = VP.parse(matches).unwrap_or_else(|| VP_USER.to_string());
+            let max_iterations = MAX_ITERATIONS.parse(matches);
+            let time_limit = TIME_LIMIT
+                .parse(matches)
+                .map(std::time::Duration::from_secs);
             Self {
+                prefix,
+                scheme,
                 alias,
                 alias_force,
-                value,
                 unsafe_dont_encrypt,
+                vp,
+                max_iterations,
+                time_limit,
             }
         }
 
         fn def(app: App) -> App {
-            app.arg(
-                ALIAS
-                    .def()
-                    .help("An alias to be associated with the new entry."),
-            )
+            app.arg(ADDRESS_PATTERN.def().help(
+                "The pattern of bech32 characters that the address must start \
+                 with. It's matched after the first two characters of the \
+                 address following the `tnam1` prefix, which are set by the \
+                 address kind. Every character takes about 32 times longer to \
+                 find.",
+            ))
+            .arg(SCHEME.def().help(
+                "The type of key that should be generated. Argument must be \
+                 either ed25519 or secp256k1. If none provided, the default \
+                 key scheme is ed25519.",
+            ))
+            .arg(ALIAS.def().help("The key and address alias."))
             .arg(ALIAS_FORCE.def().help(
                 "Override the alias without confirmation if it already exists.",
             ))
-            .arg(VALUE.def().help(
-                "Any value of the following:\n- transparent pool secret \
-                 key\n- transparent pool public key\n- transparent pool \
-                 address\n- shielded pool spending key\n- shielded pool \
-                 viewing key\n- shielded pool payment address ",
-            ))
             .arg(UNSAFE_DONT_ENCRYPT.def().help(
-                "UNSAFE: Do not encrypt the added keys. Do not use this for \
-                 keys used in a live network.",
+                "UNSAFE: Do not encrypt the keypair. Do not use this for keys \
+                 used in a live network.",
+            ))
+            .arg(VP.def().help(
+                "The validity predicate of the genesis established account. \
+                 Defaults to `vp_user`.",
+            ))
+            .arg(
+                MAX_ITERATIONS.def().help(
+                    "The maximum number of keys to try before giving up.",
+                ),
+            )
+            .arg(TIME_LIMIT.def().help(
+                "The maximum number of seconds to grind for before giving up.",
             ))
         }
     }
 
-    impl Args for KeyAddressRemove {
+    impl Args for OfflineSignTx {
         fn parse(matches: &ArgMatches) -> Self {
-            let alias = ALIAS.parse(matches);
-            let do_it = DO_IT.parse(matches);
-            Self { alias, do_it }
+            let tx_path = TX_PATH.parse(matches);
+            let output_folder = OUTPUT_FOLDER_PATH.parse(matches);
+            Self {
+                tx_path,
+                output_folder,
+            }
         }
 
         fn def(app: App) -> App {
-            app.arg(ALIAS.def().help("An alias to be removed."))
-                .arg(DO_IT.def().help("Confirm alias removal.").required(true))
+            app.arg(TX_PATH.def().help(
+                "The path to the file with the unsigned tx and its signing \
+                 data.",
+            ))
+            .arg(OUTPUT_FOLDER_PATH.def().help(
+                "The output folder path where the signed tx will be stored.",
+            ))
         }
     }
 
-    impl Args for KeyExport {
+    impl Args for WalletDaemon {
         fn parse(matches: &ArgMatches) -> Self {
-            let alias = ALIAS.parse(matches);
-            Self { alias }
+            let socket_path = SOCKET_PATH.parse(matches);
+            let confirm = CONFIRM_POLICY.parse(matches);
+            Self {
+                socket_path,
+                confirm,
+            }
         }
 
         fn def(app: App) -> App {
             app.arg(
-                ALIAS.def().help("The alias of the key you wish to export."),
+                SOCKET_PATH
+                    .def()
+                    .help("The path of the Unix socket to listen on."),
+            )
+            .arg(CONFIRM_POLICY.def().help(
+                "When to ask for a confirmation of the signing requests: \
+                 `always` for every request, `once` for the first request \
+                 with each key or `never`. Defaults to `always`.",
+            ))
+        }
+    }
+
+    impl Args for WalletUnlock {
+        fn parse(matches: &ArgMatches) -> Self {
+            let save = SAVE.parse(matches);
+            let forget = FORGET.parse(matches);
+            Self { save, forget }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(SAVE.def().help(
+                "Save the password in the keychain of the OS once it's \
+                 verified.",
+            ))
+            .arg(
+                FORGET
+                    .def()
+                    .help(
+                        "Remove the password saved in the keychain of the OS.",
+                    )
+                    .conflicts_with(SAVE.name),
             )
         }
     }
@@ -6594,6 +9635,117 @@ pub mod args {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct ProfileSet {
+        pub name: String,
+        pub node: Option<TendermintAddress>,
+        pub chain_id: Option<ChainId>,
+        pub signing_key: Option<String>,
+        pub gas_token: Option<String>,
+        pub gas_price: Option<token::DenominatedAmount>,
+        pub gas_limit: Option<GasLimit>,
+    }
+
+    impl Args for ProfileSet {
+        fn parse(matches: &ArgMatches) -> Self {
+            let name = PROFILE_NAME.parse(matches);
+            let node = LEDGER_ADDRESS_OPT.parse(matches);
+            let chain_id = CHAIN_ID_OPT.parse(matches);
+            let signing_key = PROFILE_SIGNING_KEY.parse(matches);
+            let gas_token = FEE_TOKEN_OPT.parse(matches);
+            let gas_price = FEE_AMOUNT_OPT.parse(matches);
+            let gas_limit = GAS_LIMIT_OPT.parse(matches);
+            Self {
+                name,
+                node,
+                chain_id,
+                signing_key,
+                gas_token,
+                gas_price,
+                gas_limit,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(PROFILE_NAME.def().help("The name of the profile."))
+                .arg(
+                    LEDGER_ADDRESS_OPT
+                        .def()
+                        .help("Address of the node to connect to."),
+                )
+                .arg(CHAIN_ID_OPT.def().help("The chain ID."))
+                .arg(PROFILE_SIGNING_KEY.def().help(
+                    "The alias or public key of the wallet key that signs the \
+                     transactions when `--signing-keys` isn't given.",
+                ))
+                .arg(FEE_TOKEN_OPT.def().help("The token for paying the gas."))
+                .arg(
+                    FEE_AMOUNT_OPT
+                        .def()
+                        .help("The amount being paid, per gas unit."),
+                )
+                .arg(
+                    GAS_LIMIT_OPT
+                        .def()
+                        .help("The gas limit of the transactions."),
+                )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ProfileName {
+        pub name: String,
+    }
+
+    impl Args for ProfileName {
+        fn parse(matches: &ArgMatches) -> Self {
+            let name = PROFILE_NAME.parse(matches);
+            Self { name }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(PROFILE_NAME.def().help("The name of the profile."))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ProfileShow {
+        pub name: Option<String>,
+    }
+
+    impl Args for ProfileShow {
+        fn parse(matches: &ArgMatches) -> Self {
+            let name = PROFILE_NAME_OPT.parse(matches);
+            Self { name }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(PROFILE_NAME_OPT.def().help(
+                "The name of the profile. Defaults to the profile in use.",
+            ))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct Completions {
+        pub shell: Shell,
+    }
+
+    impl Args for Completions {
+        fn parse(matches: &ArgMatches) -> Self {
+            let shell = SHELL.parse(matches);
+            Self { shell }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                SHELL
+                    .def()
+                    .help("The shell to complete: bash, zsh or fish."),
+            )
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct FetchWasms {
         pub chain_id: ChainId,
@@ -6630,6 +9782,42 @@ pub mod args {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct VerifyWasm {
+        pub code_path: PathBuf,
+        pub hash: Hash,
+        pub source_dir: Option<PathBuf>,
+    }
+
+    impl Args for VerifyWasm {
+        fn parse(matches: &ArgMatches) -> Self {
+            let code_path = CODE_PATH.parse(matches);
+            let hash = WASM_CODE_HASH.parse(matches);
+            let source_dir = WASM_SOURCE_DIR.parse(matches);
+            Self {
+                code_path,
+                hash,
+                source_dir,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                CODE_PATH.def().help("The path to the wasm file to verify."),
+            )
+            .arg(WASM_CODE_HASH.def().help(
+                "The expected SHA-256 hash of the wasm code, e.g. the code \
+                 hash referenced by a governance proposal.",
+            ))
+            .arg(WASM_SOURCE_DIR.def().help(
+                "The path to a Namada source directory. When given, the wasm \
+                 code is rebuilt from this source with the pinned toolchain \
+                 in a docker container and the artifact with the same name as \
+                 the code path must also match the expected hash.",
+            ))
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct InitNetwork {
         pub templates_path: PathBuf,
@@ -6810,6 +9998,8 @@ pub mod args {
         pub avatar: Option<String>,
         pub address: EstablishedAddress,
         pub tx_path: PathBuf,
+        pub sign: bool,
+        pub use_device: bool,
     }
 
     impl Args for InitGenesisValidator {
@@ -6830,6 +10020,8 @@ pub mod args {
             let avatar = AVATAR_OPT.parse(matches);
             let address = RAW_ADDRESS_ESTABLISHED.parse(matches);
             let tx_path = PATH.parse(matches);
+            let sign = SIGN.parse(matches);
+            let use_device = USE_DEVICE.parse(matches);
             Self {
                 alias,
                 net_address,
@@ -6845,6 +10037,8 @@ pub mod args {
                 avatar,
                 tx_path,
                 address,
+                sign,
+                use_device,
             }
         }
 
@@ -6903,6 +10097,40 @@ pub mod args {
                 .arg(AVATAR_OPT.def().help(
                     "The validator's avatar. This is an optional parameter.",
                 ))
+                .arg(SIGN.def().help(
+                    "Sign the validator account and bond txs with the keys of \
+                     the established account from the pre-genesis wallet and \
+                     with the new validator keys.",
+                ))
+                .arg(
+                    USE_DEVICE
+                        .def()
+                        .help(
+                            "Sign the txs with the keys of the established \
+                             account stored on the connected hardware wallet.",
+                        )
+                        .requires(SIGN.name),
+                )
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct ValidateGenesisValidator {
+        /// Path of the signed txs TOML
+        pub path: PathBuf,
+    }
+
+    impl Args for ValidateGenesisValidator {
+        fn parse(matches: &ArgMatches) -> Self {
+            let path = PATH.parse(matches);
+            Self { path }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(PATH.def().help(
+                "Path to the TOML file with the signed genesis txs of the \
+                 validator.",
+            ))
         }
     }
 
@@ -6926,6 +10154,51 @@ pub mod args {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct ValidateGenesis {
+        pub templates_path: PathBuf,
+        pub chain_id_prefix: ChainIdPrefix,
+        pub genesis_time: DateTimeUtc,
+        pub consensus_timeout_commit: Timeout,
+    }
+
+    impl Args for ValidateGenesis {
+        fn parse(matches: &ArgMatches) -> Self {
+            let templates_path = TEMPLATES_PATH.parse(matches);
+            let chain_id_prefix = CHAIN_ID_PREFIX.parse(matches);
+            let genesis_time = GENESIS_TIME.parse(matches);
+            let consensus_timeout_commit =
+                CONSENSUS_TIMEOUT_COMMIT.parse(matches);
+            Self {
+                templates_path,
+                chain_id_prefix,
+                genesis_time,
+                consensus_timeout_commit,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.arg(
+                TEMPLATES_PATH
+                    .def()
+                    .help("Path to the directory with genesis templates."),
+            )
+            .arg(
+                CHAIN_ID_PREFIX.def().help(
+                    "The chain ID prefix used to initialize the network.",
+                ),
+            )
+            .arg(GENESIS_TIME.def().help(
+                "The start time of the network used to initialize the \
+                 network, in RFC 3339 and ISO 8601 format.",
+            ))
+            .arg(CONSENSUS_TIMEOUT_COMMIT.def().help(
+                "The Tendermint consensus timeout_commit configuration used \
+                 to initialize the network.",
+            ))
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TestGenesis {
         /// Templates dir
@@ -7025,9 +10298,18 @@ pub enum NamadaClient {
 pub fn namada_client_cli() -> Result<NamadaClient> {
     let app = namada_client_app();
     let matches = app.clone().get_matches();
+    // The profile has to be selected before the command is parsed, as it
+    // provides the defaults of its arguments
+    let mut global_args = args::Global::parse(&matches);
+    select_profile(&mut global_args);
     match Cmd::parse(&matches) {
         Some(cmd) => {
-            let global_args = args::Global::parse(&matches);
+            if global_args.no_progress {
+                api::disable_progress();
+            }
+            if let Some(num_threads) = global_args.proving_threads {
+                namada_sdk::masp::set_proving_threads(num_threads)?;
+            }
             match cmd {
                 cmds::NamadaClient::WithContext(sub_cmd) => {
                     let context = Context::new::<CliIo>(global_args)?;
@@ -7046,6 +10328,34 @@ pub fn namada_client_cli() -> Result<NamadaClient> {
     }
 }
 
+/// Select the client profile given with `--profile` or the `NAMADA_PROFILE`
+/// env var, or else the one in use, if any. Its chain ID is used when none is
+/// given explicitly.
+fn select_profile(global_args: &mut args::Global) {
+    let name = global_args
+        .profile
+        .clone()
+        .or_else(|| std::env::var(context::ENV_VAR_PROFILE).ok());
+    let profiles =
+        Profiles::read(&global_args.base_dir).unwrap_or_else(|err| {
+            eprintln!("Failed to read the client profiles: {err}");
+            safe_exit(1)
+        });
+    match profiles.select(name.as_deref()) {
+        Ok(Some(profile)) => {
+            if global_args.chain_id.is_none() {
+                global_args.chain_id = profile.chain_id.clone();
+            }
+            crate::config::profiles::select(profile.clone());
+        }
+        Ok(None) => {}
+        Err(err) => {
+            eprintln!("{err}");
+            safe_exit(1)
+        }
+    }
+}
+
 pub fn namada_wallet_cli() -> Result<(cmds::NamadaWallet, Context)> {
     let app = namada_wallet_app();
     cmds::NamadaWallet::parse_or_print_help(app)
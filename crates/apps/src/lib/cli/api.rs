@@ -1,5 +1,10 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use indicatif::{ProgressBar, ProgressStyle};
 use namada::tendermint_rpc::HttpClient;
-use namada::types::io::Io;
+use namada::types::io::{Io, Progress};
 use namada_sdk::error::Error;
 use namada_sdk::queries::Client;
 use namada_sdk::rpc::wait_until_node_is_synched;
@@ -31,9 +36,66 @@ impl CliClient for HttpClient {
     }
 }
 
+/// Set when the progress of long-running operations shouldn't be shown
+static PROGRESS_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Don't show the progress of long-running operations
+pub fn disable_progress() {
+    PROGRESS_DISABLED.store(true, Ordering::Relaxed);
+}
+
 pub struct CliIo;
 
 #[async_trait::async_trait(?Send)]
-impl Io for CliIo {}
+impl Io for CliIo {
+    fn progress(
+        &self,
+        message: impl AsRef<str>,
+        len: Option<u64>,
+    ) -> Box<dyn Progress> {
+        // The progress is drawn on stderr, so that it never ends up in the
+        // redirected output of a command
+        if PROGRESS_DISABLED.load(Ordering::Relaxed)
+            || !std::io::stderr().is_terminal()
+        {
+            return Box::new(());
+        }
+        let bar = match len {
+            Some(len) => ProgressBar::new(len).with_style(
+                ProgressStyle::with_template(
+                    "{spinner} {msg} [{bar:40}] {pos}/{len} (ETA {eta})",
+                )
+                .unwrap()
+                .progress_chars("=> "),
+            ),
+            None => ProgressBar::new_spinner().with_style(
+                ProgressStyle::with_template("{spinner} {msg} ({elapsed})")
+                    .unwrap(),
+            ),
+        };
+        bar.set_message(message.as_ref().to_string());
+        bar.enable_steady_tick(Duration::from_millis(100));
+        Box::new(CliProgress(bar))
+    }
+}
+
+/// The progress of a long-running operation, drawn on stderr
+struct CliProgress(ProgressBar);
+
+impl Progress for CliProgress {
+    fn inc(&self, steps: u64) {
+        self.0.inc(steps);
+    }
+
+    fn set_length(&self, len: u64) {
+        self.0.set_length(len);
+    }
+}
+
+impl Drop for CliProgress {
+    fn drop(&mut self) {
+        self.0.finish_and_clear();
+    }
+}
 
 pub struct CliApi;
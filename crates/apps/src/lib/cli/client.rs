@@ -1,11 +1,13 @@
 use color_eyre::eyre::Result;
 use namada::types::io::Io;
+use namada_sdk::eth_bridge::bridge_pool;
 use namada_sdk::{Namada, NamadaImpl};
 
 use crate::cli;
 use crate::cli::api::{CliApi, CliClient};
 use crate::cli::args::CliToSdk;
 use crate::cli::cmds::*;
+use crate::cli::utils::get_eth_rpc_provider;
 use crate::client::{rpc, tx, utils};
 
 impl CliApi {
@@ -48,6 +50,17 @@ impl CliApi {
                             )
                         }
                     }
+                    Sub::TxBuild(TxBuild(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_build(&namada, args).await?;
+                    }
                     Sub::TxTransfer(TxTransfer(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -70,6 +83,28 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::submit_ibc_transfer(&namada, args).await?;
                     }
+                    Sub::TxVestingTransfer(TxVestingTransfer(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_vesting_transfer(&namada, args).await?;
+                    }
+                    Sub::TxSchedule(TxSchedule(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_schedule_tx(&namada, args).await?;
+                    }
                     Sub::TxUpdateAccount(TxUpdateAccount(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -81,6 +116,17 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::submit_update_account(&namada, args).await?;
                     }
+                    Sub::TxUpdateAccountKeys(TxUpdateAccountKeys(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_update_account_keys(&namada, args).await?;
+                    }
                     Sub::TxInitAccount(TxInitAccount(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -176,6 +222,17 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::submit_vote_proposal(&namada, args).await?;
                     }
+                    Sub::TxUpdateVoteProxy(TxUpdateVoteProxy(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_update_vote_proxy(&namada, args).await?;
+                    }
                     Sub::TxRevealPk(TxRevealPk(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -231,6 +288,17 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::submit_claim_rewards(&namada, args).await?;
                     }
+                    Sub::SetAutoCompound(SetAutoCompound(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_set_auto_compound(&namada, args).await?;
+                    }
                     Sub::Redelegate(Redelegate(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -307,10 +375,42 @@ impl CliApi {
                             )
                         });
                         client.wait_until_node_is_synced(&io).await?;
-                        let args = args.to_sdk(&mut ctx);
+                        let eth_client =
+                            args.fee_estimate.as_ref().map(|estimate| {
+                                get_eth_rpc_provider(&estimate.eth_rpc_endpoint)
+                            });
+                        let mut args = args.to_sdk(&mut ctx);
                         let namada = ctx.to_sdk(client, io);
+                        if let Some(eth_client) = eth_client {
+                            args = bridge_pool::estimate_bridge_pool_fee(
+                                eth_client, &namada, args,
+                            )
+                            .await?;
+                        }
                         tx::submit_bridge_pool_tx(&namada, args).await?;
                     }
+                    Sub::TxBridgePauseVote(TxBridgePauseVote(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_bridge_pause_vote(&namada, args).await?;
+                    }
+                    Sub::TxConvertNuts(TxConvertNuts(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_convert_nuts(&namada, args).await?;
+                    }
                     Sub::TxUnjailValidator(TxUnjailValidator(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -427,12 +527,23 @@ impl CliApi {
                         rpc::query_masp_reward_tokens(&namada).await;
                     }
                     Sub::QueryBlock(QueryBlock(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_block(&namada, args).await;
+                    }
+                    Sub::QueryChainTiming(QueryChainTiming(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(&mut args.ledger_address)
                         });
                         client.wait_until_node_is_synced(&io).await?;
                         let namada = ctx.to_sdk(client, io);
-                        rpc::query_block(&namada).await;
+                        rpc::query_chain_timing(&namada).await;
                     }
                     Sub::QueryBalance(QueryBalance(mut args)) => {
                         let client = client.unwrap_or_else(|| {
@@ -445,6 +556,39 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_balance(&namada, args).await;
                     }
+                    Sub::QueryBalances(QueryBalances(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_balances(&namada, args).await;
+                    }
+                    Sub::QueryVesting(QueryVesting(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_vesting(&namada, args).await;
+                    }
+                    Sub::QueryIbcDenom(QueryIbcDenom(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_ibc_denom(&namada, args).await;
+                    }
                     Sub::QueryBonds(QueryBonds(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -525,6 +669,24 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_delegations(&namada, args).await;
                     }
+                    Sub::QueryStakingOverview(QueryStakingOverview(
+                        mut args,
+                    )) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_staking_overview(&namada, args)
+                            .await
+                            .expect(
+                                "expected successful query of the staking \
+                                 overview",
+                            );
+                    }
                     Sub::QueryFindValidator(QueryFindValidator(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -536,6 +698,66 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_find_validator(&namada, args).await;
                     }
+                    Sub::QueryBridgePoolFees(QueryBridgePoolFees(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let eth_client = get_eth_rpc_provider(
+                            &args.estimate.eth_rpc_endpoint,
+                        );
+                        let args = args.to_sdk(&mut ctx);
+                        bridge_pool::query_bridge_pool_fees(
+                            eth_client, &client, &io, args,
+                        )
+                        .await?;
+                    }
+                    Sub::QueryTransferStatus(QueryTransferStatus(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        bridge_pool::query_transfer_status(&client, &io, args)
+                            .await?;
+                    }
+                    Sub::QueryNutBalances(QueryNutBalances(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        bridge_pool::query_nut_balances(&client, &io, args)
+                            .await?;
+                    }
+                    Sub::QueryProposer(QueryProposer(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_proposer(&namada, args).await;
+                    }
+                    Sub::QueryValidatorSet(QueryValidatorSet(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_validator_set(&namada, args).await;
+                    }
                     Sub::QueryResult(QueryResult(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -554,9 +776,25 @@ impl CliApi {
                             )
                         });
                         client.wait_until_node_is_synced(&io).await?;
+                        let chain_dir = ctx
+                            .borrow_chain_or_exit()
+                            .config
+                            .ledger
+                            .chain_dir();
                         let args = args.to_sdk(&mut ctx);
                         let namada = ctx.to_sdk(client, io);
-                        rpc::query_raw_bytes(&namada, args).await;
+                        rpc::query_raw_bytes(&namada, args, &chain_dir).await;
+                    }
+                    Sub::QueryStorageValue(QueryStorageValue(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_storage_value_json(&namada, args).await;
                     }
                     Sub::QueryProposal(QueryProposal(mut args)) => {
                         let client = client.unwrap_or_else(|| {
@@ -615,6 +853,17 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         rpc::query_pgf(&namada, args).await;
                     }
+                    Sub::QueryFeeReport(QueryFeeReport(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.query.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        rpc::query_fee_report(&namada, args).await;
+                    }
                     Sub::QueryAccount(QueryAccount(mut args)) => {
                         let client = client.unwrap_or_else(|| {
                             C::from_tendermint_address(
@@ -637,6 +886,17 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         tx::sign_tx(&namada, args).await?;
                     }
+                    Sub::BroadcastSignedTx(BroadcastSignedTx(mut args)) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_signed_tx(&namada, args).await?;
+                    }
                     Sub::GenIbcShieldedTransafer(GenIbcShieldedTransafer(
                         mut args,
                     )) => {
@@ -663,6 +923,7 @@ impl CliApi {
                 Utils::ValidateWasm(ValidateWasm(args)) => {
                     utils::validate_wasm(args)
                 }
+                Utils::VerifyWasm(VerifyWasm(args)) => utils::verify_wasm(args),
                 Utils::InitNetwork(InitNetwork(args)) => {
                     utils::init_network(global_args, args)
                 }
@@ -676,7 +937,7 @@ impl CliApi {
                     InitGenesisEstablishedAccount(args),
                 ) => utils::init_genesis_established_account(global_args, args),
                 Utils::InitGenesisValidator(InitGenesisValidator(args)) => {
-                    utils::init_genesis_validator(global_args, args)
+                    utils::init_genesis_validator(global_args, args).await
                 }
                 Utils::PkToTmAddress(PkToTmAddress(args)) => {
                     utils::pk_to_tm_address(global_args, args)
@@ -698,12 +959,38 @@ impl CliApi {
                 Utils::ValidateGenesisTemplates(ValidateGenesisTemplates(
                     args,
                 )) => utils::validate_genesis_templates(global_args, args),
+                Utils::ValidateGenesis(ValidateGenesis(args)) => {
+                    utils::validate_genesis(global_args, args)
+                }
+                Utils::ValidateGenesisValidator(ValidateGenesisValidator(
+                    args,
+                )) => utils::validate_genesis_validator(global_args, args),
                 Utils::TestGenesis(TestGenesis(args)) => {
                     utils::test_genesis(args)
                 }
                 Utils::SignGenesisTxs(SignGenesisTxs(args)) => {
                     utils::sign_genesis_tx(global_args, args).await
                 }
+                Utils::Completions(Completions(args)) => {
+                    utils::completions(global_args, args)
+                }
+                Utils::Profile(profile) => match profile {
+                    Profile::Set(ProfileSet(args)) => {
+                        utils::profile_set(global_args, args)
+                    }
+                    Profile::Remove(ProfileRemove(args)) => {
+                        utils::profile_remove(global_args, args)
+                    }
+                    Profile::Use(ProfileUse(args)) => {
+                        utils::profile_use(global_args, args)
+                    }
+                    Profile::List(ProfileList) => {
+                        utils::profile_list(global_args)
+                    }
+                    Profile::Show(ProfileShow(args)) => {
+                        utils::profile_show(global_args, args)
+                    }
+                },
             },
         }
         Ok(())
@@ -0,0 +1,312 @@
+//! Shell completions of the client commands.
+//!
+//! The completion scripts are generated from the definitions of the commands.
+//! The values of the arguments that take wallet aliases or token names can't
+//! be known when a script is generated, so they are completed from a cache in
+//! the base directory instead. The cache is updated with the aliases of the
+//! wallet of the current chain whenever a command loads it, which keeps the
+//! completion itself from having to run the client.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use namada_sdk::wallet::Wallet;
+
+use super::namada_client_app;
+
+/// The name of the client binary the completions are generated for
+const BIN_NAME: &str = "namadac";
+/// The directory of the completions cache in the base directory
+const CACHE_DIR: &str = "completions";
+/// The cache file of the wallet aliases
+const ALIASES_FILE: &str = "aliases";
+/// The cache file of the token names
+const TOKENS_FILE: &str = "tokens";
+
+/// The arguments whose values are completed with token names
+const TOKEN_ARGS: &[&str] = &["token", "tokens", "gas-token", "pool-gas-token"];
+
+/// The arguments whose values are completed with wallet aliases
+const ALIAS_ARGS: &[&str] = &[
+    "account-key",
+    "account-keys",
+    "address",
+    "consensus-key",
+    "destination-validator",
+    "eth-cold-key",
+    "eth-hot-key",
+    "fee-payer",
+    "gas-payer",
+    "gas-spending-key",
+    "key",
+    "owner",
+    "owners",
+    "pool-gas-payer",
+    "protocol-key",
+    "public-key",
+    "public-keys",
+    "signer",
+    "signing-keys",
+    "source",
+    "source-validator",
+    "steward",
+    "target",
+    "validator",
+    "voter",
+];
+
+/// The shells that completion scripts can be generated for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_lowercase().as_str() {
+            "bash" => Ok(Self::Bash),
+            "zsh" => Ok(Self::Zsh),
+            "fish" => Ok(Self::Fish),
+            _ => Err(format!(
+                "Unsupported shell {input}, expected one of bash, zsh or fish"
+            )),
+        }
+    }
+}
+
+/// Write the completion script of the client for the given shell. The
+/// script reads the cached aliases from the given base directory, unless
+/// it's overridden by `NAMADA_BASE_DIR` when completing.
+pub fn write_script(
+    shell: Shell,
+    base_dir: &Path,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    let mut app = namada_client_app();
+    let base_dir = base_dir.to_string_lossy();
+    match shell {
+        Shell::Bash => {
+            clap_complete::generate(
+                clap_complete::Shell::Bash,
+                &mut app,
+                BIN_NAME,
+                out,
+            );
+            write_bash_dynamic(&quote_posix(&base_dir), out)
+        }
+        Shell::Zsh => {
+            clap_complete::generate(
+                clap_complete::Shell::Zsh,
+                &mut app,
+                BIN_NAME,
+                out,
+            );
+            write_zsh_dynamic(&quote_posix(&base_dir), out)
+        }
+        Shell::Fish => {
+            clap_complete::generate(
+                clap_complete::Shell::Fish,
+                &mut app,
+                BIN_NAME,
+                out,
+            );
+            write_fish_dynamic(&quote_fish(&base_dir), out)
+        }
+    }
+}
+
+/// Update the cached wallet aliases and token names used by the completion
+/// scripts. The files are only written when their content changes.
+pub fn update_cache<U>(base_dir: &Path, wallet: &Wallet<U>) -> io::Result<()> {
+    let cache_dir = base_dir.join(CACHE_DIR);
+    fs::create_dir_all(&cache_dir)?;
+
+    let aliases: BTreeSet<String> = wallet
+        .get_addresses()
+        .into_keys()
+        .chain(wallet.get_public_keys().into_keys())
+        .chain(wallet.get_secret_keys().into_keys())
+        .chain(wallet.get_payment_addrs().into_keys())
+        .chain(wallet.get_viewing_keys().into_keys())
+        .chain(wallet.get_spending_keys().into_keys())
+        .collect();
+    write_cache_file(&cache_dir.join(ALIASES_FILE), aliases)?;
+
+    let tokens: BTreeSet<String> =
+        wallet.tokens_with_aliases().into_keys().collect();
+    write_cache_file(&cache_dir.join(TOKENS_FILE), tokens)
+}
+
+/// Write the values to a cache file one per line, if they've changed
+fn write_cache_file(path: &Path, values: BTreeSet<String>) -> io::Result<()> {
+    let content: String = values
+        .into_iter()
+        .map(|value| format!("{value}\n"))
+        .collect();
+    if fs::read_to_string(path).ok().as_deref() == Some(content.as_str()) {
+        return Ok(());
+    }
+    fs::write(path, content)
+}
+
+fn write_bash_dynamic(base_dir: &str, out: &mut impl Write) -> io::Result<()> {
+    let token_args = case_pattern(TOKEN_ARGS);
+    let alias_args = case_pattern(ALIAS_ARGS);
+    write!(
+        out,
+        r#"
+__{BIN_NAME}_cached() {{
+    local base_dir={base_dir}
+    cat "${{NAMADA_BASE_DIR:-$base_dir}}/{CACHE_DIR}/$1" 2>/dev/null
+}}
+
+_{BIN_NAME}_dynamic() {{
+    local cur prev
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    case "${{prev}}" in
+        {token_args})
+            COMPREPLY=($(compgen -W "$(__{BIN_NAME}_cached {TOKENS_FILE})" -- "${{cur}}"))
+            return 0
+            ;;
+        {alias_args})
+            COMPREPLY=($(compgen -W "$(__{BIN_NAME}_cached {ALIASES_FILE})" -- "${{cur}}"))
+            return 0
+            ;;
+    esac
+    _{BIN_NAME} "$@"
+}}
+
+complete -F _{BIN_NAME}_dynamic -o bashdefault -o default {BIN_NAME}
+"#
+    )
+}
+
+fn write_zsh_dynamic(base_dir: &str, out: &mut impl Write) -> io::Result<()> {
+    let token_args = case_pattern(TOKEN_ARGS);
+    let alias_args = case_pattern(ALIAS_ARGS);
+    write!(
+        out,
+        r#"
+__{BIN_NAME}_cached() {{
+    local base_dir={base_dir}
+    cat "${{NAMADA_BASE_DIR:-$base_dir}}/{CACHE_DIR}/$1" 2>/dev/null
+}}
+
+_{BIN_NAME}_dynamic() {{
+    case "${{words[CURRENT-1]}}" in
+        {token_args})
+            compadd -- ${{(f)"$(__{BIN_NAME}_cached {TOKENS_FILE})"}}
+            ;;
+        {alias_args})
+            compadd -- ${{(f)"$(__{BIN_NAME}_cached {ALIASES_FILE})"}}
+            ;;
+        *)
+            _{BIN_NAME} "$@"
+            ;;
+    esac
+}}
+
+compdef _{BIN_NAME}_dynamic {BIN_NAME}
+"#
+    )
+}
+
+fn write_fish_dynamic(base_dir: &str, out: &mut impl Write) -> io::Result<()> {
+    write!(
+        out,
+        r#"
+function __{BIN_NAME}_cached
+    set -l base_dir {base_dir}
+    set -q NAMADA_BASE_DIR; and set base_dir $NAMADA_BASE_DIR
+    cat "$base_dir/{CACHE_DIR}/$argv[1]" 2>/dev/null
+end
+
+"#
+    )?;
+    for (args, file) in [(TOKEN_ARGS, TOKENS_FILE), (ALIAS_ARGS, ALIASES_FILE)]
+    {
+        for arg in args {
+            writeln!(
+                out,
+                "complete -c {BIN_NAME} -l {arg} -x -a '(__{BIN_NAME}_cached \
+                 {file})'"
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Match any of the arguments as long options in a shell `case`
+fn case_pattern(args: &[&str]) -> String {
+    args.iter()
+        .map(|arg| format!("--{arg}"))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// Quote a string for bash and zsh
+fn quote_posix(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Quote a string for fish
+fn quote_fish(s: &str) -> String {
+    format!("'{}'", s.replace('\\', r"\\").replace('\'', r"\'"))
+}
+
+#[cfg(test)]
+mod test_completions {
+    use super::*;
+
+    #[test]
+    fn test_write_script() {
+        let base_dir = Path::new("/home/it's/.namada");
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let mut out = vec![];
+            write_script(shell, base_dir, &mut out).unwrap();
+            let script = String::from_utf8(out).unwrap();
+            assert!(script.contains("transfer"), "{shell:?}");
+            assert!(script.contains("__namadac_cached"), "{shell:?}");
+            match shell {
+                Shell::Bash | Shell::Zsh => {
+                    assert!(script.contains(r"'/home/it'\''s/.namada'"))
+                }
+                Shell::Fish => {
+                    assert!(script.contains(r"'/home/it\'s/.namada'"));
+                    assert!(script.contains(
+                        "complete -c namadac -l token -x -a \
+                         '(__namadac_cached tokens)'"
+                    ));
+                }
+            }
+        }
+        assert!("powershell".parse::<Shell>().is_err());
+    }
+
+    #[test]
+    fn test_update_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut wallet = crate::wallet::load_or_new(dir.path());
+        let address = namada::types::address::nam();
+        wallet.insert_address("alice", address.clone(), false);
+        wallet.add_vp_type_to_address(
+            namada_sdk::wallet::AddressVpType::Token,
+            address,
+        );
+        update_cache(dir.path(), &wallet).unwrap();
+
+        let read = |file: &str| {
+            fs::read_to_string(dir.path().join(CACHE_DIR).join(file)).unwrap()
+        };
+        assert_eq!(read(ALIASES_FILE), "alice\n");
+        assert_eq!(read(TOKENS_FILE), "alice\n");
+    }
+}
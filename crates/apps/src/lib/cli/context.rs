@@ -20,7 +20,7 @@ use namada_sdk::wallet::Wallet;
 use namada_sdk::{Namada, NamadaImpl};
 
 use super::args;
-use crate::cli::utils;
+use crate::cli::{completions, utils};
 use crate::config::global::GlobalConfig;
 use crate::config::{genesis, Config};
 use crate::wallet::CliWalletUtils;
@@ -32,6 +32,9 @@ pub const ENV_VAR_WASM_DIR: &str = "NAMADA_WASM_DIR";
 /// Env. var to read the Namada chain id from
 pub const ENV_VAR_CHAIN_ID: &str = "NAMADA_CHAIN_ID";
 
+/// Env. var to read the client profile from
+pub const ENV_VAR_PROFILE: &str = "NAMADA_PROFILE";
+
 /// A raw address (bech32m encoding) or an alias of an address that may be found
 /// in the wallet
 pub type WalletAddress = FromContext<Address>;
@@ -121,6 +124,10 @@ impl Context {
                         chain_dir.to_string_lossy()
                     );
                 };
+                // The shell completions are best-effort, so a failure to
+                // cache the aliases mustn't fail the command
+                let _ =
+                    completions::update_cache(&global_args.base_dir, &wallet);
 
                 // If the WASM dir specified, put it in the config
                 match global_args.wasm_dir.as_ref() {
@@ -0,0 +1,151 @@
+//! Process exit codes of the client.
+//!
+//! Every failure of the client falls in one of the [`FailureClass`]es, each of
+//! which exits the process with a distinct code. Before exiting, a JSON object
+//! describing the failure is printed as the last line on stderr, so that
+//! scripts can branch on the failure type without parsing the human-readable
+//! output.
+
+use std::io::Write;
+
+use color_eyre::eyre::Report;
+use namada_sdk::error::{Error, QueryError, TxSubmitError};
+use namada_sdk::wallet::{FindKeyError, LoadStoreError};
+use serde::Serialize;
+
+/// The class of a client failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureClass {
+    /// Any failure that doesn't fall in the other classes
+    Other,
+    /// The node couldn't be reached
+    Connection,
+    /// The transaction was rejected by the mempool, in `CheckTx`
+    Rejected,
+    /// The transaction was included in a block, but it is invalid
+    Invalid,
+    /// Timed out waiting for the node or for the transaction
+    Timeout,
+    /// The wallet couldn't be loaded or the keys couldn't be found in it
+    Wallet,
+}
+
+impl FailureClass {
+    /// The process exit code of the failure class
+    pub const fn exit_code(self) -> i32 {
+        match self {
+            Self::Other => 1,
+            Self::Connection => 2,
+            Self::Rejected => 3,
+            Self::Invalid => 4,
+            Self::Timeout => 5,
+            Self::Wallet => 6,
+        }
+    }
+
+    /// Classify the failure from the chain of errors in the report
+    pub fn of(report: &Report) -> Self {
+        report
+            .chain()
+            .find_map(|error| {
+                if let Some(error) = error.downcast_ref::<Error>() {
+                    Self::of_sdk_error(error)
+                } else if error.is::<FindKeyError>()
+                    || error.is::<LoadStoreError>()
+                {
+                    Some(Self::Wallet)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(Self::Other)
+    }
+
+    fn of_sdk_error(error: &Error) -> Option<Self> {
+        match error {
+            Error::Tx(TxSubmitError::TxBroadcast(_))
+            | Error::Query(QueryError::Connection(_)) => Some(Self::Connection),
+            Error::Tx(TxSubmitError::CheckTxRejected(_)) => {
+                Some(Self::Rejected)
+            }
+            Error::Tx(TxSubmitError::InvalidOnChain(_, _)) => {
+                Some(Self::Invalid)
+            }
+            Error::Tx(
+                TxSubmitError::AcceptTimeout | TxSubmitError::AppliedTimeout,
+            )
+            | Error::Query(QueryError::CatchingUp) => Some(Self::Timeout),
+            Error::Wallet(_) => Some(Self::Wallet),
+            _ => None,
+        }
+    }
+}
+
+/// The machine-readable description of a client failure
+#[derive(Debug, Serialize)]
+struct Failure {
+    class: FailureClass,
+    exit_code: i32,
+    message: String,
+}
+
+/// Report the failure on stderr, followed by a single line JSON object
+/// describing it, and exit the process with the code of its class.
+pub fn exit_with_failure(report: Report) -> ! {
+    let class = FailureClass::of(&report);
+    let failure = Failure {
+        class,
+        exit_code: class.exit_code(),
+        message: report.to_string(),
+    };
+    eprintln!("Error: {report:?}");
+    eprintln!("{}", serde_json::json!({ "error": failure }));
+
+    let _ = std::io::stdout().lock().flush();
+    let _ = std::io::stderr().lock().flush();
+    std::process::exit(class.exit_code())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use color_eyre::eyre::eyre;
+
+    use super::*;
+
+    #[test]
+    fn test_distinct_exit_codes() {
+        let classes = [
+            FailureClass::Other,
+            FailureClass::Connection,
+            FailureClass::Rejected,
+            FailureClass::Invalid,
+            FailureClass::Timeout,
+            FailureClass::Wallet,
+        ];
+        let codes: HashSet<_> =
+            classes.iter().map(|class| class.exit_code()).collect();
+        assert_eq!(codes.len(), classes.len());
+        assert!(!codes.contains(&0));
+    }
+
+    #[test]
+    fn test_failure_class_of_report() {
+        let report = Report::from(Error::Tx(TxSubmitError::CheckTxRejected(
+            "{}".to_string(),
+        )));
+        assert_eq!(FailureClass::of(&report), FailureClass::Rejected);
+
+        let report = Report::from(Error::Tx(TxSubmitError::AppliedTimeout))
+            .wrap_err("Submitting the transaction failed");
+        assert_eq!(FailureClass::of(&report), FailureClass::Timeout);
+
+        let report = Report::from(Error::Wallet("No key".to_string()));
+        assert_eq!(FailureClass::of(&report), FailureClass::Wallet);
+
+        let report = eyre!("Something else");
+        assert_eq!(FailureClass::of(&report), FailureClass::Other);
+    }
+}
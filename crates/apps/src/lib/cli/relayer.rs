@@ -7,6 +7,7 @@ use crate::cli::api::{CliApi, CliClient};
 use crate::cli::args::{CliToSdk, CliToSdkCtxless};
 use crate::cli::cmds::*;
 use crate::cli::utils::get_eth_rpc_client;
+use crate::client::relayer_api;
 
 impl CliApi {
     pub async fn handle_relayer_command<C>(
@@ -150,10 +151,21 @@ impl CliApi {
                     let eth_client =
                         get_eth_rpc_client(&args.eth_rpc_endpoint).await;
                     let args = args.to_sdk_ctxless();
-                    validator_set::relay_validator_set_update(
-                        eth_client, &client, &io, args,
-                    )
-                    .await?;
+                    if let Some(laddr) = args.api_addr {
+                        relayer_api::relay_validator_set_update(
+                            laddr, eth_client, &client, &io, args,
+                        )
+                        .await?;
+                    } else {
+                        validator_set::relay_validator_set_update(
+                            eth_client,
+                            &client,
+                            &io,
+                            args,
+                            &Default::default(),
+                        )
+                        .await?;
+                    }
                 }
             },
         }
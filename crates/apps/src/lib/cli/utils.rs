@@ -15,7 +15,7 @@ use namada::eth_bridge::ethers::signers::{Signer, Wallet};
 
 use super::args;
 use super::context::Context;
-use crate::cli::api::CliIo;
+use crate::cli::api::{self, CliIo};
 use crate::cli::context::FromContext;
 
 /// Environment variable where Ethereum relayer private
@@ -45,6 +45,12 @@ pub trait Cmd: Sized {
         match Self::parse(&matches) {
             Some(cmd) => {
                 let global_args = args::Global::parse(&matches);
+                if global_args.no_progress {
+                    api::disable_progress();
+                }
+                if let Some(num_threads) = global_args.proving_threads {
+                    namada_sdk::masp::set_proving_threads(num_threads)?;
+                }
                 let context = Context::new::<CliIo>(global_args)?;
                 Ok((cmd, context))
             }
@@ -441,6 +447,14 @@ fn get_eth_signer_from_env(chain_id: u64) -> Option<impl Signer> {
     Some(wallet)
 }
 
+/// Return an Ethereum RPC client without a signer, for read-only queries.
+pub fn get_eth_rpc_provider(url: &str) -> Arc<Provider<Http>> {
+    Arc::new(
+        Provider::<Http>::try_from(url)
+            .expect("Failed to instantiate Ethereum RPC client"),
+    )
+}
+
 /// Return an Ethereum RPC client.
 pub async fn get_eth_rpc_client(url: &str) -> Arc<impl Middleware> {
     let client = Provider::<Http>::try_from(url)
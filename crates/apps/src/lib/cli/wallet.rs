@@ -3,6 +3,7 @@
 use std::fs::File;
 use std::io::{self, Write};
 use std::str::FromStr;
+use std::time::Instant;
 
 use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
@@ -13,10 +14,13 @@ use ledger_transport_hid::hidapi::HidApi;
 use ledger_transport_hid::TransportNativeHID;
 use masp_primitives::zip32::ExtendedFullViewingKey;
 use namada::types::address::{Address, DecodeError};
+use namada::types::ethereum_events::EthAddress;
 use namada::types::io::Io;
 use namada::types::key::*;
 use namada::types::masp::{ExtendedSpendingKey, MaspValue, PaymentAddress};
 use namada_sdk::masp::find_valid_diversifier;
+use namada_sdk::signing::OfflineTx;
+use namada_sdk::wallet::address_book::{self, ImportAction};
 use namada_sdk::wallet::{
     DecryptionError, DerivationPath, DerivationPathError, FindKeyError, Wallet,
 };
@@ -28,6 +32,7 @@ use crate::cli::api::CliApi;
 use crate::cli::args::CliToSdk;
 use crate::cli::{args, cmds, Context};
 use crate::client::utils::PRE_GENESIS_DIR;
+use crate::config::genesis::transactions;
 use crate::wallet::{
     self, read_and_confirm_encryption_password, CliWalletUtils,
 };
@@ -63,6 +68,24 @@ impl CliApi {
             cmds::NamadaWallet::KeyAddrRemove(
                 cmds::WalletRemoveKeyAddress(args),
             ) => key_address_remove(ctx, io, args),
+            cmds::NamadaWallet::AddrBook(cmds::WalletAddressBook::Import(
+                cmds::WalletImportAddressBook(args),
+            )) => address_book_import(ctx, io, args),
+            cmds::NamadaWallet::AddrBook(cmds::WalletAddressBook::Export(
+                cmds::WalletExportAddressBook(args),
+            )) => address_book_export(ctx, io, args),
+            cmds::NamadaWallet::AddrBook(cmds::WalletAddressBook::Grind(
+                cmds::WalletGrindAddress(args),
+            )) => address_grind(ctx, io, args),
+            cmds::NamadaWallet::SignTx(cmds::WalletSignTx(args)) => {
+                offline_sign_tx(ctx, io, args)
+            }
+            cmds::NamadaWallet::Daemon(cmds::WalletDaemon(args)) => {
+                wallet_daemon(ctx, io, args).await
+            }
+            cmds::NamadaWallet::Unlock(cmds::WalletUnlock(args)) => {
+                wallet_unlock(ctx, io, args)
+            }
             cmds::NamadaWallet::PayAddrGen(cmds::WalletGenPaymentAddress(
                 args,
             )) => {
@@ -614,12 +637,15 @@ impl FromStr for TransparentValue {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // Try to decode this value first as a secret key, then as a public key,
-        // then as an address
+        // then as an Ethereum-style secp256k1 secret key, then as an address
         common::SecretKey::from_str(s)
             .map(Self::TranspSecretKey)
             .or_else(|_| {
                 common::PublicKey::from_str(s).map(Self::TranspPublicKey)
             })
+            .or_else(|_| {
+                common::SecretKey::from_eth_str(s).map(Self::TranspSecretKey)
+            })
             .or_else(|_| Address::from_str(s).map(Self::TranspAddress))
     }
 }
@@ -861,7 +887,7 @@ fn transparent_key_address_find_by_alias(
             found = true;
             display_line!(io, &mut w_lock; "Found transparent keys:").unwrap();
             let encrypted = match wallet.is_encrypted_secret_key(&alias) {
-                None => "external",
+                None => "watch-only",
                 Some(res) if res => "encrypted",
                 _ => "not encrypted",
             };
@@ -881,6 +907,10 @@ fn transparent_key_address_find_by_alias(
                 public_key
             )
             .unwrap();
+            if let Ok(eth_address) = EthAddress::try_from(&public_key) {
+                display_line!(io, &mut w_lock; "    Ethereum address: {}", eth_address)
+                    .unwrap();
+            }
             if decrypt {
                 // Check if alias is also a secret key. Decrypt and print it if
                 // requested.
@@ -1004,7 +1034,7 @@ fn transparent_keys_list(
         for (alias, public_key) in known_public_keys {
             let stored_keypair = known_secret_keys.get(&alias);
             let encrypted = match stored_keypair {
-                None => "external",
+                None => "watch-only",
                 Some((stored_keypair, _pkh))
                     if stored_keypair.is_encrypted() =>
                 {
@@ -1022,6 +1052,10 @@ fn transparent_keys_list(
                 .unwrap();
             display_line!(io, &mut w_lock; "    Public key: {}", public_key)
                 .unwrap();
+            if let Ok(eth_address) = EthAddress::try_from(&public_key) {
+                display_line!(io, &mut w_lock; "    Ethereum address: {}", eth_address)
+                    .unwrap();
+            }
             // A subset of public keys will have corresponding secret keys.
             // Print those too if they are available and requested.
             if let Some((stored_keypair, _pkh)) = stored_keypair {
@@ -1201,7 +1235,7 @@ fn transparent_public_key_add(
         .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
     display_line!(
         io,
-        "Successfully added a public key with alias: \"{}\"",
+        "Successfully added a watch-only public key with alias: \"{}\"",
         alias
     );
 }
@@ -1233,6 +1267,353 @@ fn transparent_address_add(
     );
 }
 
+/// Export the transparent addresses of the wallet to an address book.
+fn address_book_export(
+    ctx: Context,
+    io: &impl Io,
+    args::AddressBookExport { format, output }: args::AddressBookExport,
+) {
+    let wallet = load_wallet(ctx);
+    let entries = address_book::read_entries(wallet.store());
+    let data = address_book::encode(&entries, format);
+    match output {
+        Some(path) => {
+            std::fs::write(&path, data).unwrap_or_else(|err| {
+                edisplay_line!(io, "{}", err);
+                cli::safe_exit(1)
+            });
+            display_line!(
+                io,
+                "Exported {} addresses to file {}",
+                entries.len(),
+                path.to_string_lossy()
+            );
+        }
+        None => display_line!(io, "{}", data),
+    }
+}
+
+/// Import transparent addresses from an address book into the wallet.
+fn address_book_import(
+    ctx: Context,
+    io: &impl Io,
+    args::AddressBookImport {
+        file_path,
+        format,
+        on_conflict,
+        dry_run,
+    }: args::AddressBookImport,
+) {
+    let data = std::fs::read_to_string(file_path).unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        display_line!(io, "No changes are persisted. Exiting.");
+        cli::safe_exit(1)
+    });
+    let entries = address_book::decode(&data, format).unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        display_line!(io, "No changes are persisted. Exiting.");
+        cli::safe_exit(1)
+    });
+    let mut wallet = load_wallet(ctx);
+    let actions =
+        address_book::plan_import(wallet.store(), entries, on_conflict);
+    for action in &actions {
+        display_line!(io, "  {}", action);
+    }
+    if dry_run {
+        display_line!(io, "Dry run, no changes are persisted.");
+        return;
+    }
+
+    let mut imported = 0_usize;
+    for action in actions {
+        let inserted = match action {
+            ImportAction::Add(entry) => {
+                wallet.insert_address(entry.alias, entry.address, false)
+            }
+            ImportAction::Overwrite(entry) => {
+                wallet.insert_address(entry.alias, entry.address, true)
+            }
+            ImportAction::Rename { entry, new_alias } => {
+                wallet.insert_address(new_alias, entry.address, false)
+            }
+            ImportAction::Skip { .. } => continue,
+        };
+        if inserted.is_some() {
+            imported += 1;
+        }
+    }
+    wallet
+        .save()
+        .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
+    display_line!(io, "Successfully imported {} addresses.", imported);
+}
+
+/// Grind keys for a genesis established account with a vanity address, then
+/// store the key and the address in the wallet under the same alias.
+fn address_grind(
+    ctx: Context,
+    io: &impl Io,
+    args::AddressGrind {
+        prefix,
+        scheme,
+        alias,
+        alias_force,
+        unsafe_dont_encrypt,
+        vp,
+        max_iterations,
+        time_limit,
+    }: args::AddressGrind,
+) {
+    if let Err(err) = transactions::validate_vanity_pattern(&prefix) {
+        edisplay_line!(io, "{}", err);
+        cli::safe_exit(1)
+    }
+    let expected_iterations = 32_f64.powi(prefix.len() as i32);
+    display_line!(
+        io,
+        "Grinding for an address with the pattern \"{}\", expecting about {} \
+         attempts...",
+        prefix,
+        expected_iterations
+    );
+    let deadline = time_limit.map(|limit| Instant::now() + limit);
+    let account = transactions::grind_established_account(
+        &vp,
+        scheme,
+        &prefix,
+        max_iterations,
+        deadline,
+        &mut OsRng,
+    )
+    .unwrap_or_else(|iterations| {
+        edisplay_line!(
+            io,
+            "No matching address found after {} attempts.",
+            iterations
+        );
+        cli::safe_exit(1)
+    });
+    display_line!(
+        io,
+        "Found address {} after {} attempts.",
+        account.address,
+        account.iterations
+    );
+
+    let alias = alias.to_lowercase();
+    let mut wallet = load_wallet(ctx);
+    let encryption_password =
+        read_and_confirm_encryption_password(unsafe_dont_encrypt);
+    let alias = wallet
+        .insert_keypair(
+            alias,
+            alias_force,
+            account.secret_key,
+            encryption_password,
+            Some(account.address),
+            None,
+        )
+        .unwrap_or_else(|err| {
+            edisplay_line!(io, "{}", err);
+            display_line!(io, "No changes are persisted. Exiting.");
+            cli::safe_exit(1)
+        });
+    wallet
+        .save()
+        .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
+    display_line!(
+        io,
+        "Successfully added a key and an address with alias: \"{}\". The \
+         address is only derived by a genesis established account with this \
+         key as its only public key and the `{}` VP, e.g. with `namadac utils \
+         init-genesis-established-account --aliases {} --vp {}`.",
+        alias,
+        vp,
+        alias,
+        vp
+    );
+}
+
+/// Sign a transaction dumped with `--offline-sign` with the keys from the
+/// wallet. If the wallet also has the key of the fee payer, the signed tx is
+/// written out ready for broadcasting, otherwise the partially signed tx is
+/// written out for the holders of the remaining keys.
+fn offline_sign_tx(
+    ctx: Context,
+    io: &impl Io,
+    args::OfflineSignTx {
+        tx_path,
+        output_folder,
+    }: args::OfflineSignTx,
+) {
+    let mut wallet = load_wallet(ctx);
+    let mut offline_tx: OfflineTx = File::open(&tx_path)
+        .map_err(|err| err.to_string())
+        .and_then(|file| {
+            serde_json::from_reader(file).map_err(|err| err.to_string())
+        })
+        .unwrap_or_else(|err| {
+            edisplay_line!(
+                io,
+                "Couldn't read the unsigned transaction from {}: {}",
+                tx_path.display(),
+                err
+            );
+            cli::safe_exit(1)
+        });
+    let complete = offline_tx.sign(&mut wallet, None).unwrap_or_else(|err| {
+        edisplay_line!(io, "{}", err);
+        cli::safe_exit(1)
+    });
+
+    let tx_id = offline_tx.tx.header_hash();
+    let (filename, contents) = if complete {
+        (
+            format!("{}.tx", tx_id),
+            serde_json::to_string_pretty(&offline_tx.tx.serialize()),
+        )
+    } else {
+        (
+            format!("{}.offline.tx", tx_id),
+            serde_json::to_string_pretty(&offline_tx),
+        )
+    };
+    let contents = contents.expect("Should be able to serialize the tx.");
+    let output_path = match output_folder {
+        Some(path) => path.join(filename),
+        None => filename.into(),
+    };
+    std::fs::write(&output_path, contents).unwrap_or_else(|err| {
+        edisplay_line!(
+            io,
+            "Couldn't write the transaction to {}: {}",
+            output_path.display(),
+            err
+        );
+        cli::safe_exit(1)
+    });
+    if complete {
+        display_line!(
+            io,
+            "Signed transaction serialized to {}. Submit it with `namadac \
+             broadcast-signed`.",
+            output_path.display()
+        );
+    } else if offline_tx.has_pending_signatures() {
+        display_line!(
+            io,
+            "The transaction still has to be signed by {} more key(s). \
+             Partially signed transaction serialized to {}.",
+            offline_tx.signing_data.public_keys.len(),
+            output_path.display()
+        );
+    } else {
+        display_line!(
+            io,
+            "The wallet doesn't have the key of the fee payer {}. Partially \
+             signed transaction serialized to {}.",
+            offline_tx.signing_data.fee_payer,
+            output_path.display()
+        );
+    }
+}
+
+/// Run the wallet daemon until it's interrupted
+async fn wallet_daemon(
+    ctx: Context,
+    io: &impl Io,
+    args::WalletDaemon {
+        socket_path,
+        confirm,
+    }: args::WalletDaemon,
+) {
+    let wallet = load_wallet(ctx);
+    #[cfg(unix)]
+    if let Err(err) =
+        wallet::daemon::run(wallet, &socket_path, confirm, io).await
+    {
+        edisplay_line!(io, "The wallet daemon failed: {}", err);
+        cli::safe_exit(1)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (wallet, socket_path, confirm);
+        edisplay_line!(io, "The wallet daemon is only supported on Unix.");
+        cli::safe_exit(1)
+    }
+}
+
+/// Verify the wallet password against the encrypted keys of the wallet and
+/// save it in the keychain or remove it from the keychain
+fn wallet_unlock(
+    ctx: Context,
+    io: &impl Io,
+    args::WalletUnlock { save, forget }: args::WalletUnlock,
+) {
+    if forget {
+        match wallet::keychain::delete_password() {
+            Ok(true) => display_line!(
+                io,
+                "The wallet password was removed from the keychain."
+            ),
+            Ok(false) => {
+                display_line!(
+                    io,
+                    "No wallet password is saved in the keychain."
+                )
+            }
+            Err(err) => {
+                edisplay_line!(io, "{}", err);
+                cli::safe_exit(1)
+            }
+        }
+        return;
+    }
+    let wallet = load_wallet(ctx);
+    let encrypted_keys: Vec<_> = wallet
+        .get_secret_keys()
+        .into_values()
+        .map(|(stored_key, _)| stored_key)
+        .filter(|stored_key| stored_key.is_encrypted())
+        .collect();
+    if encrypted_keys.is_empty() {
+        edisplay_line!(io, "The wallet doesn't have any encrypted keys.");
+        cli::safe_exit(1)
+    }
+    // The password is always prompted for, so that a wrong password saved in
+    // the keychain can be replaced
+    let password = wallet::read_decryption_password_tty();
+    let decrypted = encrypted_keys.iter().all(|stored_key| {
+        stored_key
+            .get::<CliWalletUtils>(true, Some(password.clone()))
+            .is_ok()
+    });
+    if !decrypted {
+        edisplay_line!(
+            io,
+            "The password doesn't decrypt all the encrypted keys of the \
+             wallet."
+        );
+        cli::safe_exit(1)
+    }
+    if !save {
+        display_line!(io, "The password decrypts the wallet.");
+        return;
+    }
+    match wallet::keychain::save_password(&password) {
+        Ok(()) => display_line!(
+            io,
+            "The wallet password was saved in the keychain. It will be used \
+             instead of prompting for the password."
+        ),
+        Err(err) => {
+            edisplay_line!(io, "{}", err);
+            cli::safe_exit(1)
+        }
+    }
+}
+
 /// Load wallet for chain when `ctx.chain.is_some()` or pre-genesis wallet when
 /// `ctx.global_args.is_pre_genesis`.
 fn load_wallet(ctx: Context) -> Wallet<CliWalletUtils> {
@@ -1,3 +1,4 @@
 pub mod rpc;
+pub mod relayer_api;
 pub mod tx;
 pub mod utils;
@@ -0,0 +1,449 @@
+//! A REST API to manage a validator set update relayer running in daemon
+//! mode, without restarting it with new flags.
+//!
+//! The API is served on the local address given with `--api-address`. Each
+//! request must be authorized with the token set in the
+//! [`API_TOKEN_ENV_VAR`] env var, as a bearer token of its `Authorization`
+//! header. The endpoints are:
+//!
+//! - `GET /status`: whether the relays of the daemon are paused
+//! - `POST /pause` and `POST /resume`: pause and resume the relays of the
+//!   daemon
+//! - `GET /bridge_pool`: the pending transfers of the Bridge pool, by hash
+//! - `POST /relay`: relay a proof of a batch of transfers of the Bridge pool,
+//!   given as `{"transfers": [<hash>, ..], "relayer": <address>}`
+//! - `GET /eth_account`: the balance and nonce of the Ethereum account that
+//!   pays the gas of the relays
+//!
+//! The responses are JSON objects, with an `error` field when a request
+//! failed. The requests that query the ledger or Ethereum are processed on
+//! the task of the daemon, which owns the clients.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use namada::eth_bridge::ethers::providers::Middleware;
+use namada::ledger::queries::RPC;
+use namada::types::address::Address;
+use namada::types::eth_bridge_pool::PendingTransfer;
+use namada::types::io::Io;
+use namada::types::keccak::KeccakHash;
+use namada_sdk::error::Error;
+use namada_sdk::eth_bridge::bridge_pool;
+use namada_sdk::eth_bridge::validator_set::{self, RelayerControl};
+use namada_sdk::queries::Client;
+use namada_sdk::{args, MaybeSync};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::sync::{mpsc, oneshot};
+use warp::http::{Method, StatusCode};
+use warp::Filter;
+
+/// The env var with the token that authorizes the requests to the API
+pub const API_TOKEN_ENV_VAR: &str = "NAMADA_RELAYER_API_TOKEN";
+
+/// The number of requests waiting to be processed by the daemon
+const REQUESTS_CAPACITY: usize = 16;
+
+/// A request processed on the task of the daemon
+#[derive(Debug)]
+enum Command {
+    /// Query the pending transfers of the Bridge pool
+    BridgePool,
+    /// Relay a proof of a batch of transfers
+    Relay(RelayBatch),
+    /// Query the Ethereum account of the relayer
+    EthAccount,
+}
+
+/// A batch of transfers of the Bridge pool to relay
+#[derive(Debug, Deserialize)]
+struct RelayBatch {
+    /// The hashes of the transfers
+    transfers: Vec<KeccakHash>,
+    /// The Namada address that receives the fees of the transfers
+    relayer: Address,
+}
+
+/// A request sent to the task of the daemon, with the channel of its
+/// response
+#[derive(Debug)]
+pub struct Request {
+    command: Command,
+    response: oneshot::Sender<Result<Value, String>>,
+}
+
+/// The state of the API server
+#[derive(Debug, Clone)]
+struct Api {
+    token: Arc<str>,
+    control: RelayerControl,
+    requests: mpsc::Sender<Request>,
+}
+
+impl Api {
+    /// Handle a request to the API, returning the status and JSON body of
+    /// the response
+    async fn handle(
+        &self,
+        method: &Method,
+        path: &str,
+        authorization: Option<&str>,
+        body: &[u8],
+    ) -> (StatusCode, Value) {
+        if !is_authorized(authorization, &self.token) {
+            return error(StatusCode::UNAUTHORIZED, "Unauthorized");
+        }
+        match (method.as_str(), path) {
+            ("GET", "/status") => (
+                StatusCode::OK,
+                json!({ "paused": self.control.is_paused() }),
+            ),
+            ("POST", "/pause") => {
+                self.control.pause();
+                tracing::info!("The relayer was paused through the API");
+                (StatusCode::OK, json!({ "paused": true }))
+            }
+            ("POST", "/resume") => {
+                self.control.resume();
+                tracing::info!("The relayer was resumed through the API");
+                (StatusCode::OK, json!({ "paused": false }))
+            }
+            ("GET", "/bridge_pool") => self.forward(Command::BridgePool).await,
+            ("POST", "/relay") => match serde_json::from_slice(body) {
+                Ok(batch) => self.forward(Command::Relay(batch)).await,
+                Err(err) => error(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid batch of transfers: {err}"),
+                ),
+            },
+            ("GET", "/eth_account") => self.forward(Command::EthAccount).await,
+            _ => error(StatusCode::NOT_FOUND, "Not found"),
+        }
+    }
+
+    /// Forward a command to the task of the daemon and wait for its result
+    async fn forward(&self, command: Command) -> (StatusCode, Value) {
+        let (response, result) = oneshot::channel();
+        if self
+            .requests
+            .send(Request { command, response })
+            .await
+            .is_err()
+        {
+            return error(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "The relayer has stopped",
+            );
+        }
+        match result.await {
+            Ok(Ok(value)) => (StatusCode::OK, value),
+            Ok(Err(err)) => error(StatusCode::INTERNAL_SERVER_ERROR, err),
+            Err(_) => error(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "The relayer has stopped",
+            ),
+        }
+    }
+}
+
+/// The response of a failed request
+fn error(status: StatusCode, msg: impl ToString) -> (StatusCode, Value) {
+    (status, json!({ "error": msg.to_string() }))
+}
+
+/// Check that an `Authorization` header has the given bearer token. The
+/// tokens are compared in constant time.
+fn is_authorized(authorization: Option<&str>, token: &str) -> bool {
+    let Some(given) =
+        authorization.and_then(|value| value.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    !token.is_empty()
+        && given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Relay validator set updates in daemon mode, while serving the API on the
+/// given address
+pub async fn relay_validator_set_update<E>(
+    laddr: SocketAddr,
+    eth_client: Arc<E>,
+    client: &(impl Client + Sync),
+    io: &(impl Io + MaybeSync),
+    args: args::ValidatorSetUpdateRelay,
+) -> Result<(), Error>
+where
+    E: Middleware,
+    E::Error: std::fmt::Debug + std::fmt::Display,
+{
+    let token = std::env::var(API_TOKEN_ENV_VAR)
+        .ok()
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| {
+            Error::Other(format!(
+                "The {API_TOKEN_ENV_VAR} env var must be set to serve the \
+                 relayer API"
+            ))
+        })?;
+    let control = RelayerControl::default();
+    let (requests, receiver) = mpsc::channel(REQUESTS_CAPACITY);
+    let api = Api {
+        token: token.into(),
+        control: control.clone(),
+        requests,
+    };
+    let (abort_send, abort_recv) = oneshot::channel();
+    tokio::spawn(serve(laddr, api, abort_recv));
+
+    let relayer = validator_set::relay_validator_set_update(
+        Arc::clone(&eth_client),
+        client,
+        io,
+        args.clone(),
+        &control,
+    );
+    let processor = process_requests(receiver, eth_client, client, io, &args);
+    futures::pin_mut!(relayer, processor);
+    let result = match futures::future::select(relayer, processor).await {
+        futures::future::Either::Left((result, _)) => result,
+        // The server has stopped, but the relayer keeps running
+        futures::future::Either::Right(((), relayer)) => relayer.await,
+    };
+    let _ = abort_send.send(());
+    result
+}
+
+/// Serve the API on the given address until the abort signal is received
+async fn serve(laddr: SocketAddr, api: Api, abort_recv: oneshot::Receiver<()>) {
+    let routes = warp::method()
+        .and(warp::path::full())
+        .and(warp::header::optional::<String>("authorization"))
+        .and(warp::body::bytes())
+        .and_then(
+            move |method: Method,
+                  path: warp::path::FullPath,
+                  auth: Option<String>,
+                  body: Bytes| {
+                let api = api.clone();
+                async move {
+                    let (status, value) = api
+                        .handle(&method, path.as_str(), auth.as_deref(), &body)
+                        .await;
+                    Ok::<_, warp::Rejection>(warp::reply::with_status(
+                        warp::reply::json(&value),
+                        status,
+                    ))
+                }
+            },
+        );
+    let server = warp::serve(routes).try_bind_with_graceful_shutdown(
+        laddr,
+        async move {
+            let _ = abort_recv.await;
+        },
+    );
+    match server {
+        Ok((laddr, server)) => {
+            tracing::info!(?laddr, "Serving the relayer API");
+            server.await;
+        }
+        Err(err) => {
+            tracing::error!(?laddr, "Failed to serve the relayer API: {err}");
+        }
+    }
+}
+
+/// Process the requests forwarded by the API server, until it stops
+async fn process_requests<E>(
+    mut receiver: mpsc::Receiver<Request>,
+    eth_client: Arc<E>,
+    client: &(impl Client + Sync),
+    io: &(impl Io + MaybeSync),
+    args: &args::ValidatorSetUpdateRelay,
+) where
+    E: Middleware,
+    E::Error: std::fmt::Debug + std::fmt::Display,
+{
+    while let Some(Request { command, response }) = receiver.recv().await {
+        let result = match command {
+            Command::BridgePool => query_bridge_pool(client).await,
+            Command::Relay(batch) => {
+                relay_batch(Arc::clone(&eth_client), client, io, args, batch)
+                    .await
+            }
+            Command::EthAccount => query_eth_account(&*eth_client, args).await,
+        };
+        let _ = response.send(result);
+    }
+}
+
+/// Query the pending transfers of the Bridge pool, by their hashes
+async fn query_bridge_pool(
+    client: &(impl Client + Sync),
+) -> Result<Value, String> {
+    let pool: Vec<PendingTransfer> = RPC
+        .shell()
+        .eth_bridge()
+        .read_ethereum_bridge_pool(client)
+        .await
+        .map_err(|err| err.to_string())?;
+    let pool: HashMap<String, PendingTransfer> = pool
+        .into_iter()
+        .map(|transfer| (transfer.keccak256().to_string(), transfer))
+        .collect();
+    serde_json::to_value(pool).map_err(|err| err.to_string())
+}
+
+/// Relay a proof of a batch of transfers, paying the gas as configured for
+/// the daemon
+async fn relay_batch<E>(
+    eth_client: Arc<E>,
+    client: &(impl Client + Sync),
+    io: &(impl Io + MaybeSync),
+    args: &args::ValidatorSetUpdateRelay,
+    batch: RelayBatch,
+) -> Result<Value, String>
+where
+    E: Middleware,
+    E::Error: std::fmt::Debug + std::fmt::Display,
+{
+    tracing::info!(
+        transfers = ?batch.transfers,
+        "Relaying a batch of transfers requested through the API"
+    );
+    let relay_args = args::RelayBridgePoolProof {
+        query: args.query.clone(),
+        transfers: batch.transfers,
+        relayer: batch.relayer,
+        confirmations: args.confirmations,
+        eth_rpc_endpoint: (),
+        gas: args.gas,
+        gas_price: args.gas_price,
+        eth_addr: args.eth_addr,
+        sync: false,
+        safe_mode: false,
+    };
+    bridge_pool::relay_bridge_pool_proof(eth_client, client, io, relay_args)
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(json!({ "relayed": true }))
+}
+
+/// Query the balance and nonce of the Ethereum account that pays the gas of
+/// the relays
+async fn query_eth_account<E>(
+    eth_client: &E,
+    args: &args::ValidatorSetUpdateRelay,
+) -> Result<Value, String>
+where
+    E: Middleware,
+    E::Error: std::fmt::Debug + std::fmt::Display,
+{
+    let address = args
+        .eth_addr
+        .map(Into::into)
+        .or_else(|| eth_client.default_sender())
+        .ok_or_else(|| "The Ethereum account is unknown".to_string())?;
+    let (balance, nonce) = futures::try_join!(
+        eth_client.get_balance(address, None),
+        eth_client.get_transaction_count(address, None),
+    )
+    .map_err(|err| err.to_string())?;
+    Ok(json!({
+        "address": address,
+        "balance": balance.to_string(),
+        "nonce": nonce.to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod test_relayer_api {
+    use super::*;
+
+    fn api() -> (Api, mpsc::Receiver<Request>) {
+        let (requests, receiver) = mpsc::channel(1);
+        let api = Api {
+            token: "secret".into(),
+            control: RelayerControl::default(),
+            requests,
+        };
+        (api, receiver)
+    }
+
+    #[test]
+    fn test_is_authorized() {
+        assert!(is_authorized(Some("Bearer secret"), "secret"));
+        assert!(!is_authorized(Some("Bearer secreT"), "secret"));
+        assert!(!is_authorized(Some("Bearer secret2"), "secret"));
+        assert!(!is_authorized(Some("secret"), "secret"));
+        assert!(!is_authorized(None, "secret"));
+        assert!(!is_authorized(Some("Bearer "), ""));
+    }
+
+    /// Test pausing and resuming the relayer and the errors of the requests
+    /// that don't reach the daemon
+    #[tokio::test]
+    async fn test_handle() {
+        let (api, receiver) = api();
+        let auth = Some("Bearer secret");
+
+        let (status, _) = api.handle(&Method::POST, "/pause", None, &[]).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert!(!api.control.is_paused());
+
+        let (status, body) =
+            api.handle(&Method::POST, "/pause", auth, &[]).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!({ "paused": true }));
+        assert!(api.control.is_paused());
+
+        let (_, body) = api.handle(&Method::GET, "/status", auth, &[]).await;
+        assert_eq!(body, json!({ "paused": true }));
+
+        let (_, body) = api.handle(&Method::POST, "/resume", auth, &[]).await;
+        assert_eq!(body, json!({ "paused": false }));
+        assert!(!api.control.is_paused());
+
+        let (status, _) = api.handle(&Method::GET, "/pause", auth, &[]).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, _) = api
+            .handle(&Method::POST, "/relay", auth, br#"{"transfers": 1}"#)
+            .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        drop(receiver);
+        let (status, _) =
+            api.handle(&Method::GET, "/bridge_pool", auth, &[]).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    /// Test that the requests are forwarded to the daemon with their
+    /// responses
+    #[tokio::test]
+    async fn test_forward() {
+        let (api, mut receiver) = api();
+        let daemon = tokio::spawn(async move {
+            let request = receiver.recv().await.unwrap();
+            assert!(matches!(request.command, Command::EthAccount));
+            request
+                .response
+                .send(Err("No account".to_string()))
+                .unwrap();
+        });
+        let (status, body) = api
+            .handle(&Method::GET, "/eth_account", Some("Bearer secret"), &[])
+            .await;
+        daemon.await.unwrap();
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(body, json!({ "error": "No account" }));
+    }
+}
@@ -5,6 +5,7 @@ use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs::{self, read_dir};
 use std::io;
 use std::iter::Iterator;
+use std::path::Path;
 use std::str::FromStr;
 
 use borsh::BorshDeserialize;
@@ -31,14 +32,20 @@ use namada::governance::utils::{
 };
 use namada::ledger::events::Event;
 use namada::ledger::ibc::storage::{
-    ibc_denom_key, ibc_denom_key_prefix, is_ibc_denom_key,
+    ibc_denom_key, ibc_denom_key_prefix, ibc_trace_key_prefix,
+    is_ibc_denom_key, is_ibc_trace_key,
 };
 use namada::ledger::parameters::{storage as param_storage, EpochDuration};
 use namada::ledger::pos::types::{CommissionPair, Slash};
 use namada::ledger::pos::PosParams;
 use namada::ledger::queries::RPC;
-use namada::proof_of_stake::types::{ValidatorState, WeightedValidator};
+use namada::proof_of_stake::storage_key::validator_set_history_key;
+use namada::proof_of_stake::types::{
+    DeactivationReason, HistoricalValidator, RewardsRate, ValidatorState,
+    WeightedValidator,
+};
 use namada::types::address::{Address, InternalAddress, MASP};
+use namada::types::dec::Dec;
 use namada::types::hash::Hash;
 use namada::types::ibc::{is_ibc_denom, IbcTokenHash};
 use namada::types::io::Io;
@@ -47,6 +54,7 @@ use namada::types::masp::{BalanceOwner, ExtendedViewingKey, PaymentAddress};
 use namada::types::storage::{
     BlockHeight, BlockResults, Epoch, IndexedTx, Key, KeySeg,
 };
+use namada::types::time::DurationNanos;
 use namada::types::token::{Change, MaspDenom};
 use namada::{state as storage, token};
 use namada_sdk::error::{
@@ -59,7 +67,10 @@ use namada_sdk::rpc::{
 };
 use namada_sdk::tx::{display_inner_resp, display_wrapper_resp_and_get_result};
 use namada_sdk::wallet::AddressVpType;
-use namada_sdk::{display, display_line, edisplay_line, error, prompt, Namada};
+use namada_sdk::{
+    display, display_line, edisplay_line, error, light_client, prompt,
+    storage_value, Namada,
+};
 use tokio::time::Instant;
 
 use crate::cli::{self, args};
@@ -88,24 +99,102 @@ pub async fn query_and_print_epoch(context: &impl Namada) -> Epoch {
 }
 
 /// Query the last committed block
-pub async fn query_block(context: &impl Namada) {
-    let block = namada_sdk::rpc::query_block(context.client())
+pub async fn query_block(context: &impl Namada, args: args::QueryBlock) {
+    let block = match args.height {
+        Some(height) => {
+            namada_sdk::rpc::query_block_at_height(context.client(), height)
+                .await
+                .unwrap()
+        }
+        None => namada_sdk::rpc::query_block(context.client())
+            .await
+            .unwrap(),
+    };
+    let Some(block) = block else {
+        match args.height {
+            Some(height) => display_line!(
+                context.io(),
+                "No block has been committed at height {} yet.",
+                height
+            ),
+            None => {
+                display_line!(context.io(), "No block has been committed yet.")
+            }
+        }
+        return;
+    };
+    let gas =
+        namada_sdk::rpc::query_block_gas(context.client(), Some(block.height))
+            .await
+            .unwrap();
+    let label = if args.height.is_some() {
+        "Block"
+    } else {
+        "Last committed block"
+    };
+    display_line!(
+        context.io(),
+        "{} ID: {}, height: {}, time: {}",
+        label,
+        block.hash,
+        block.height,
+        block.time
+    );
+    let utilization = if gas.max_block_gas == 0 {
+        0.0
+    } else {
+        gas.gas_used as f64 * 100.0 / gas.max_block_gas as f64
+    };
+    display_line!(
+        context.io(),
+        "Gas used: {} / {} ({:.2}%)",
+        gas.gas_used,
+        gas.max_block_gas,
+        utilization
+    );
+}
+
+/// Query the statistics of the last block times and the minimum start of the
+/// next epoch
+pub async fn query_chain_timing(context: &impl Namada) {
+    let timing = namada_sdk::rpc::query_chain_timing(context.client())
         .await
         .unwrap();
-    match block {
-        Some(block) => {
+    let secs = |duration: DurationNanos| {
+        format!("{:.3}s", std::time::Duration::from(duration).as_secs_f64())
+    };
+    match timing.block_times {
+        Some(stats) => {
             display_line!(
                 context.io(),
-                "Last committed block ID: {}, height: {}, time: {}",
-                block.hash,
-                block.height,
-                block.time
+                "Times of the last {} blocks:",
+                stats.num_blocks
             );
+            display_line!(
+                context.io(),
+                "  Average block time: {}",
+                secs(stats.average_block_time)
+            );
+            display_line!(
+                context.io(),
+                "  Median block time: {}",
+                secs(stats.median_block_time)
+            );
+            display_line!(context.io(), "  Median time: {}", stats.median_time);
         }
         None => {
-            display_line!(context.io(), "No block has been committed yet.");
+            display_line!(
+                context.io(),
+                "No block times have been recorded yet."
+            );
         }
     }
+    display_line!(
+        context.io(),
+        "The next epoch can start from height {} and time {}",
+        timing.next_epoch_min_start_height,
+        timing.next_epoch_min_start_time
+    );
 }
 
 /// Query the results of the last committed block
@@ -135,6 +224,7 @@ pub async fn query_transfers(
     let transfers = shielded
         .query_tx_deltas(
             context.client(),
+            context.io(),
             &query_owner,
             &query_token,
             &wallet.get_viewing_keys(),
@@ -257,10 +347,103 @@ pub async fn query_transfers(
     }
 }
 
+/// The file in the chain directory holding the light client's trusted state
+const LIGHT_CLIENT_STATE_FILE: &str = "light-client-state.json";
+
 /// Query the raw bytes of given storage key
 pub async fn query_raw_bytes<N: Namada>(
     context: &N,
     args: args::QueryRawBytes,
+    chain_dir: &Path,
+) {
+    let data = if args.trust_node {
+        let response = unwrap_client_response::<N::Client, _>(
+            RPC.shell()
+                .storage_value(
+                    context.client(),
+                    None,
+                    None,
+                    false,
+                    &args.storage_key,
+                )
+                .await,
+        );
+        (!response.data.is_empty()).then_some(response.data)
+    } else {
+        query_verified_raw_bytes(context, &args, chain_dir).await
+    };
+    if let Some(data) = data {
+        display_line!(context.io(), "Found data: 0x{}", HEXLOWER.encode(&data));
+    } else {
+        display_line!(
+            context.io(),
+            "No data found for key {}",
+            args.storage_key
+        );
+    }
+}
+
+/// Query the raw bytes of given storage key and verify them with the light
+/// client. Exits the client if the result cannot be verified.
+async fn query_verified_raw_bytes<N: Namada>(
+    context: &N,
+    args: &args::QueryRawBytes,
+    chain_dir: &Path,
+) -> Option<Vec<u8>> {
+    let state_path = chain_dir.join(LIGHT_CLIENT_STATE_FILE);
+    let trusted = match (args.trusted_height, args.trusted_hash) {
+        (Some(height), Some(hash)) => {
+            light_client::bootstrap(context.client(), height, hash)
+                .await
+                .unwrap_or_else(|err| {
+                    edisplay_line!(
+                        context.io(),
+                        "Failed to initialize the light client: {err}"
+                    );
+                    cli::safe_exit(1)
+                })
+        }
+        _ => fs::read(&state_path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_else(|| {
+                edisplay_line!(
+                    context.io(),
+                    "No trusted header found at {}. Initialize the light \
+                     client with `--trusted-height` and `--trusted-hash`.",
+                    state_path.to_string_lossy()
+                );
+                cli::safe_exit(1)
+            }),
+    };
+    let (data, verified) = light_client::query_verified_storage_value_bytes(
+        context.client(),
+        &trusted,
+        &args.storage_key,
+        &light_client::default_options(),
+    )
+    .await
+    .unwrap_or_else(|err| {
+        edisplay_line!(context.io(), "Refusing the query result: {err}");
+        cli::safe_exit(1)
+    });
+    // Advance the trusted state to the newly verified header
+    let state = serde_json::to_vec_pretty(&verified)
+        .expect("Serializing a light block shouldn't fail");
+    if let Err(err) = fs::write(&state_path, state) {
+        edisplay_line!(
+            context.io(),
+            "Failed to save the light client state to {}: {err}",
+            state_path.to_string_lossy()
+        );
+    }
+    data
+}
+
+/// Query the value of given storage key and print it decoded to JSON
+pub async fn query_storage_value_json<N: Namada>(
+    context: &N,
+    args: args::QueryStorageValue,
 ) {
     let response = unwrap_client_response::<N::Client, _>(
         RPC.shell()
@@ -273,18 +456,50 @@ pub async fn query_raw_bytes<N: Namada>(
             )
             .await,
     );
-    if !response.data.is_empty() {
-        display_line!(
-            context.io(),
-            "Found data: 0x{}",
-            HEXLOWER.encode(&response.data)
-        );
-    } else {
+    if response.data.is_empty() {
         display_line!(
             context.io(),
             "No data found for key {}",
             args.storage_key
         );
+        return;
+    }
+    let Some(value_type) = args
+        .value_type
+        .or_else(|| storage_value::value_type_of(&args.storage_key))
+    else {
+        edisplay_line!(
+            context.io(),
+            "Unknown value type for key {}, use `--type` to decode it. Found \
+             data: 0x{}",
+            args.storage_key,
+            HEXLOWER.encode(&response.data)
+        );
+        cli::safe_exit(1)
+    };
+    match value_type.decode(&response.data) {
+        Ok(value) => {
+            let json = serde_json::json!({
+                "type": value_type.name(),
+                "value": value,
+            });
+            display_line!(
+                context.io(),
+                "{}",
+                serde_json::to_string_pretty(&json)
+                    .expect("Serializing JSON shouldn't fail")
+            );
+        }
+        Err(err) => {
+            edisplay_line!(
+                context.io(),
+                "Failed to decode the value of key {} as {value_type}: {err}. \
+                 Found data: 0x{}",
+                args.storage_key,
+                HEXLOWER.encode(&response.data)
+            );
+            cli::safe_exit(1)
+        }
     }
 }
 
@@ -313,6 +528,81 @@ pub async fn query_balance(context: &impl Namada, args: args::QueryBalance) {
     };
 }
 
+/// Query the balances of many owners in many tokens in a single query
+pub async fn query_balances(context: &impl Namada, args: args::QueryBalances) {
+    let tokens: BTreeSet<Address> = args.tokens.into_iter().collect();
+    let owners: BTreeSet<Address> = args.owners.into_iter().collect();
+    let balances =
+        match rpc::get_token_balances(context.client(), tokens, owners).await {
+            Ok(balances) => balances,
+            Err(err) => {
+                edisplay_line!(context.io(), "Error querying balances: {err}");
+                cli::safe_exit(1)
+            }
+        };
+    let wallet = context.wallet().await;
+    let token_aliases: BTreeMap<&Address, String> = balances
+        .keys()
+        .map(|(token, _owner)| (token, wallet.lookup_alias(token)))
+        .collect();
+    drop(wallet);
+    for ((token, owner), balance) in &balances {
+        let balance = context.format_amount(token, *balance).await;
+        display_line!(
+            context.io(),
+            "{owner} {}: {balance}",
+            token_aliases[token]
+        );
+    }
+}
+
+/// Query the vested and locked amounts of a balance with a vesting schedule
+pub async fn query_vesting(context: &impl Namada, args: args::QueryVesting) {
+    let schedule = match rpc::get_vesting_schedule(
+        context.client(),
+        &args.token,
+        &args.owner,
+    )
+    .await
+    {
+        Ok(Some(schedule)) => schedule,
+        Ok(None) => {
+            display_line!(
+                context.io(),
+                "No vesting schedule found for the {} balance of {}",
+                args.token,
+                args.owner
+            );
+            return;
+        }
+        Err(err) => {
+            edisplay_line!(
+                context.io(),
+                "Error querying the vesting schedule: {err}"
+            );
+            cli::safe_exit(1)
+        }
+    };
+    let epoch = query_epoch(context.client()).await.unwrap();
+    let total = context.format_amount(&args.token, schedule.amount).await;
+    let vested = context
+        .format_amount(&args.token, schedule.vested_amount(epoch))
+        .await;
+    let locked = context
+        .format_amount(&args.token, schedule.locked_amount(epoch))
+        .await;
+    display_line!(
+        context.io(),
+        "Vesting schedule of {} releasing {} from epoch {} to epoch {}",
+        args.owner,
+        total,
+        schedule.start,
+        schedule.end
+    );
+    display_line!(context.io(), "Vested in epoch {}: {}", epoch, vested);
+    display_line!(context.io(), "Locked in epoch {}: {}", epoch, locked);
+}
+
 /// Query token balance(s)
 pub async fn query_transparent_balance(
     context: &impl Namada,
@@ -639,19 +929,66 @@ async fn print_balances(
     }
 }
 
+/// Query the full trace path of an IBC denom by its hash
+pub async fn query_ibc_denom(context: &impl Namada, args: args::QueryIbcDenom) {
+    let hash = match Address::decode(&args.hash) {
+        Ok(Address::Internal(InternalAddress::IbcToken(hash))) => hash,
+        _ => match args.hash.parse::<IbcTokenHash>() {
+            Ok(hash) => hash,
+            Err(_) => {
+                edisplay_line!(
+                    context.io(),
+                    "Invalid IBC denom hash or IBC token address: {}",
+                    args.hash
+                );
+                cli::safe_exit(1)
+            }
+        },
+    };
+    match rpc::query_ibc_trace(context.client(), hash.to_string()).await {
+        Ok(Some(ibc_denom)) => {
+            display_line!(context.io(), "{}: {}", hash, ibc_denom)
+        }
+        Ok(None) => display_line!(
+            context.io(),
+            "No IBC denom found for the hash {}",
+            hash
+        ),
+        Err(err) => {
+            edisplay_line!(context.io(), "Error querying the IBC denom: {err}");
+            cli::safe_exit(1)
+        }
+    }
+}
+
 async fn lookup_token_alias(
     context: &impl Namada,
     token: &Address,
     owner: &Address,
 ) -> String {
     if let Address::Internal(InternalAddress::IbcToken(trace_hash)) = token {
-        let ibc_denom_key =
-            ibc_denom_key(owner.to_string(), trace_hash.to_string());
-        match query_storage_value::<_, String>(context.client(), &ibc_denom_key)
-            .await
+        let ibc_denom = match rpc::query_ibc_trace(
+            context.client(),
+            trace_hash.to_string(),
+        )
+        .await
         {
-            Ok(ibc_denom) => get_ibc_denom_alias(context, ibc_denom).await,
-            Err(_) => token.to_string(),
+            Ok(Some(ibc_denom)) => Some(ibc_denom),
+            // Fall back to the denom stored for the owner
+            _ => {
+                let ibc_denom_key =
+                    ibc_denom_key(owner.to_string(), trace_hash.to_string());
+                query_storage_value::<_, String>(
+                    context.client(),
+                    &ibc_denom_key,
+                )
+                .await
+                .ok()
+            }
+        };
+        match ibc_denom {
+            Some(ibc_denom) => get_ibc_denom_alias(context, ibc_denom).await,
+            None => token.to_string(),
         }
     } else {
         context.wallet().await.lookup_alias(token)
@@ -715,6 +1052,23 @@ async fn query_tokens(
             }
         }
     }
+    // The IBC denoms by their hashes regardless of the owners
+    if base_token.is_none() {
+        let prefix = ibc_trace_key_prefix();
+        let ibc_denoms = query_storage_prefix::<String>(context, &prefix).await;
+        for (key, ibc_denom) in ibc_denoms.into_iter().flatten() {
+            if let Some(hash) = is_ibc_trace_key(&key) {
+                let ibc_denom_alias =
+                    get_ibc_denom_alias(context, ibc_denom).await;
+                let hash: IbcTokenHash = hash.parse().expect(
+                    "Parsing an IBC token hash from storage shouldn't fail",
+                );
+                let ibc_token =
+                    Address::Internal(InternalAddress::IbcToken(hash));
+                tokens.insert(ibc_denom_alias, ibc_token);
+            }
+        }
+    }
     tokens
 }
 
@@ -853,7 +1207,10 @@ pub async fn query_shielded_balance(
             .iter()
             .map(|fvk| ExtendedFullViewingKey::from(*fvk).fvk.vk)
             .collect();
-        shielded.fetch(context.client(), &[], &fvks).await.unwrap();
+        shielded
+            .fetch(context.client(), context.io(), &[], &fvks)
+            .await
+            .unwrap();
         // Save the update state so that future fetches can be short-circuited
         let _ = shielded.save().await;
     }
@@ -1247,10 +1604,15 @@ pub async fn query_proposal_result(
         )
         .await;
 
+        let tally_params = query_governance_parameters(context.client())
+            .await
+            .tally_params
+            .default;
         let proposal_result = compute_proposal_result(
             proposal_votes,
             total_voting_power,
             TallyType::TwoThirds,
+            tally_params,
         );
 
         display_line!(
@@ -1268,11 +1630,18 @@ pub async fn query_account(context: &impl Namada, args: args::QueryAccount) {
         .await
         .unwrap();
     if let Some(account) = account {
+        let public_keys: BTreeMap<u8, common::PublicKey> =
+            account.public_keys_map.idx_to_pk.into_iter().collect();
         display_line!(context.io(), "Address: {}", account.address);
-        display_line!(context.io(), "Threshold: {}", account.threshold);
+        display_line!(
+            context.io(),
+            "Threshold: {} of {} keys",
+            account.threshold,
+            public_keys.len()
+        );
         display_line!(context.io(), "Public keys:");
-        for (public_key, _) in account.public_keys_map.pk_to_idx {
-            display_line!(context.io(), "- {}", public_key);
+        for (index, public_key) in public_keys {
+            display_line!(context.io(), "- #{index}: {public_key}");
         }
     } else {
         display_line!(context.io(), "No account exists for {}", args.owner);
@@ -1330,6 +1699,69 @@ pub async fn query_pgf(context: &impl Namada, _args: args::QueryPgf) {
     }
 }
 
+/// Query the fees collected and distributed, the tokens burned and the PGF
+/// treasury flows of every token in an epoch
+pub async fn query_fee_report(
+    context: &impl Namada,
+    args: args::QueryFeeReport,
+) {
+    let epoch = match args.epoch {
+        Some(epoch) => epoch,
+        None => query_epoch(context.client()).await.unwrap(),
+    };
+    let flows = rpc::query_epoch_flows(context.client(), epoch)
+        .await
+        .unwrap();
+
+    display_line!(context.io(), "Fee report for epoch {epoch}:");
+    if flows.is_empty() {
+        display_line!(context.io(), "{:2}No flows in this epoch.", "");
+        return;
+    }
+    for (token, flows) in flows {
+        let format_amount = |amount| {
+            rpc::format_denominated_amount(
+                context.client(),
+                context.io(),
+                &token,
+                amount,
+            )
+        };
+        let alias = context.wallet().await.lookup_alias(&token);
+        display_line!(context.io(), "{:2}{alias}:", "");
+        display_line!(
+            context.io(),
+            "{:4}Fees collected: {}",
+            "",
+            format_amount(flows.fees_collected).await
+        );
+        display_line!(
+            context.io(),
+            "{:4}Fees distributed to block proposers: {}",
+            "",
+            format_amount(flows.fees_distributed).await
+        );
+        display_line!(
+            context.io(),
+            "{:4}Burned: {}",
+            "",
+            format_amount(flows.burned).await
+        );
+        display_line!(
+            context.io(),
+            "{:4}PGF treasury inflow: {}",
+            "",
+            format_amount(flows.treasury_inflow).await
+        );
+        display_line!(
+            context.io(),
+            "{:4}PGF treasury outflow: {}",
+            "",
+            format_amount(flows.treasury_outflow).await
+        );
+    }
+}
+
 pub async fn query_protocol_parameters(
     context: &impl Namada,
     _args: args::QueryProtocolParameters,
@@ -1373,6 +1805,27 @@ pub async fn query_protocol_parameters(
         "",
         governance_parameters.min_proposal_grace_epochs
     );
+    display_line!(
+        context.io(),
+        "{:4}Spam proposal fund to PGF: {}",
+        "",
+        governance_parameters.spam_proposal_fund_to_pgf
+    );
+    let tally_params = &governance_parameters.tally_params;
+    for (proposal_type, params) in [
+        ("Default", tally_params.default),
+        ("PGF steward", tally_params.pgf_steward),
+        ("PGF funding", tally_params.pgf_payment),
+        ("IBC client recovery", tally_params.ibc_client_recovery),
+    ] {
+        display_line!(
+            context.io(),
+            "{:4}{} proposals tally: {}",
+            "",
+            proposal_type,
+            params
+        );
+    }
 
     let pgf_parameters = query_pgf_parameters(context.client()).await;
     display_line!(context.io(), "Public Goods Funding Parameters\n");
@@ -1436,12 +1889,57 @@ pub async fn query_protocol_parameters(
         tx_whitelist
     );
 
+    let key = param_storage::get_max_tx_memo_bytes_key();
+    let max_tx_memo_bytes: u32 = query_storage_value(context.client(), &key)
+        .await
+        .expect("Parameter should be defined.");
+    display_line!(
+        context.io(),
+        "{:4}Max tx memo bytes: {:?}",
+        "",
+        max_tx_memo_bytes
+    );
+
+    let key = param_storage::get_max_tx_sections_key();
+    let max_tx_sections: u32 = query_storage_value(context.client(), &key)
+        .await
+        .expect("Parameter should be defined.");
+    display_line!(
+        context.io(),
+        "{:4}Max tx sections: {:?}",
+        "",
+        max_tx_sections
+    );
+
+    let key = param_storage::get_max_tx_code_bytes_key();
+    let max_tx_code_bytes: u32 = query_storage_value(context.client(), &key)
+        .await
+        .expect("Parameter should be defined.");
+    display_line!(
+        context.io(),
+        "{:4}Max tx code bytes: {:?}",
+        "",
+        max_tx_code_bytes
+    );
+
     let key = param_storage::get_max_block_gas_key();
     let max_block_gas: u64 = query_storage_value(context.client(), &key)
         .await
         .expect("Parameter should be defined.");
     display_line!(context.io(), "{:4}Max block gas: {:?}", "", max_block_gas);
 
+    let key = param_storage::get_max_scheduled_txs_gas_key();
+    let max_scheduled_txs_gas: u64 =
+        query_storage_value(context.client(), &key)
+            .await
+            .expect("Parameter should be defined.");
+    display_line!(
+        context.io(),
+        "{:4}Max scheduled txs gas per epoch: {:?}",
+        "",
+        max_scheduled_txs_gas
+    );
+
     let key = param_storage::get_fee_unshielding_gas_limit_key();
     let fee_unshielding_gas_limit: u64 =
         query_storage_value(context.client(), &key)
@@ -1502,12 +2000,24 @@ pub async fn query_protocol_parameters(
         "",
         pos_params.light_client_attack_min_slash_rate
     );
+    display_line!(
+        context.io(),
+        "{:4}Slash evidence bounty rate: {}",
+        "",
+        pos_params.slash_evidence_bounty_rate
+    );
     display_line!(
         context.io(),
         "{:4}Max. validator slots: {}",
         "",
         pos_params.max_validator_slots
     );
+    display_line!(
+        context.io(),
+        "{:4}Min. validator self-bond: {}",
+        "",
+        pos_params.min_self_bond.to_string_native()
+    );
     display_line!(
         context.io(),
         "{:4}Pipeline length: {}",
@@ -1520,6 +2030,12 @@ pub async fn query_protocol_parameters(
         "",
         pos_params.unbonding_len
     );
+    display_line!(
+        context.io(),
+        "{:4}Stake history length: {}",
+        "",
+        pos_params.stake_history_len
+    );
     display_line!(
         context.io(),
         "{:4}Votes per token: {}",
@@ -1648,7 +2164,7 @@ pub async fn query_bonds(
     context: &impl Namada,
     args: args::QueryBonds,
 ) -> std::io::Result<()> {
-    let epoch = query_and_print_epoch(context).await;
+    let current_epoch = query_and_print_epoch(context).await;
 
     let source = args.owner;
     let validator = args.validator;
@@ -1656,14 +2172,29 @@ pub async fn query_bonds(
     let stdout = io::stdout();
     let mut w = stdout.lock();
 
-    let bonds_and_unbonds = enriched_bonds_and_unbonds(
-        context.client(),
-        epoch,
-        &source,
-        &validator,
-    )
-    .await
+    let progress = context.io().progress("Enumerating the bonds", None);
+    let bonds_and_unbonds = match args.epoch {
+        Some(epoch) if epoch < current_epoch => {
+            rpc::enriched_bonds_and_unbonds_at(
+                context.client(),
+                epoch,
+                &source,
+                &validator,
+            )
+            .await
+        }
+        _ => {
+            enriched_bonds_and_unbonds(
+                context.client(),
+                current_epoch,
+                &source,
+                &validator,
+            )
+            .await
+        }
+    }
     .unwrap();
+    drop(progress);
 
     for (bond_id, details) in &bonds_and_unbonds.data {
         let bond_type = if bond_id.source == bond_id.validator {
@@ -1773,7 +2304,22 @@ pub async fn query_bonded_stake<N: Namada>(
     args: args::QueryBondedStake,
 ) {
     let epoch = match args.epoch {
-        Some(epoch) => epoch,
+        Some(epoch) => {
+            let current_epoch = query_epoch(context.client()).await.unwrap();
+            let pos_params = query_pos_parameters(context.client()).await;
+            let history_len = pos_params.stake_history_epoch_offset();
+            if epoch + history_len < current_epoch {
+                edisplay_line!(
+                    context.io(),
+                    "The bonded stake at epoch {epoch} is no longer \
+                     available. The stake is only kept for the last \
+                     {history_len} epochs before the current epoch \
+                     {current_epoch}."
+                );
+                return;
+            }
+            epoch
+        }
         None => query_and_print_epoch(context).await,
     };
 
@@ -1916,6 +2462,17 @@ pub async fn query_rewards<C: namada::ledger::queries::Client + Sync>(
     )
 }
 
+/// Query the estimated rewards rate of the bond
+pub async fn query_rewards_rate<C: namada::ledger::queries::Client + Sync>(
+    client: &C,
+    source: &Option<Address>,
+    validator: &Address,
+) -> RewardsRate {
+    unwrap_client_response::<C, RewardsRate>(
+        RPC.vp().pos().rewards_rate(client, validator, source).await,
+    )
+}
+
 /// Query a validator's state information
 pub async fn query_and_print_validator_state(
     context: &impl Namada,
@@ -1946,7 +2503,31 @@ pub async fn query_and_print_validator_state(
                 )
             }
             ValidatorState::Inactive => {
-                display_line!(context.io(), "Validator {validator} is inactive")
+                let reason =
+                    unwrap_client_response::<_, Option<DeactivationReason>>(
+                        RPC.vp()
+                            .pos()
+                            .validator_deactivation_reason(
+                                context.client(),
+                                &validator,
+                            )
+                            .await,
+                    );
+                match reason {
+                    Some(DeactivationReason::SelfBondBelowMinimum) => {
+                        display_line!(
+                            context.io(),
+                            "Validator {validator} is inactive because its \
+                             self-bond is below the minimum self-bond. It \
+                             will be reactivated once its self-bond is topped \
+                             up."
+                        )
+                    }
+                    None => display_line!(
+                        context.io(),
+                        "Validator {validator} is inactive"
+                    ),
+                }
             }
             ValidatorState::Jailed => {
                 display_line!(context.io(), "Validator {validator} is jailed")
@@ -2242,6 +2823,20 @@ pub async fn query_and_print_rewards<N: Namada>(
         "Current rewards available for claim: {} NAM",
         rewards.to_string_native()
     );
+
+    let rate = query_rewards_rate(context.client(), &source, &validator).await;
+    display_line!(
+        context.io(),
+        "Estimated annual rewards rate: {} APR, {} APY with auto-compounding \
+         (auto-compounding is {})",
+        rate.apr,
+        rate.apy,
+        if rate.auto_compound {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
 }
 
 pub async fn query_delegations<N: Namada>(
@@ -2265,6 +2860,149 @@ pub async fn query_delegations<N: Namada>(
     }
 }
 
+/// Query the bonds, unbonds, pending rewards and applied slashes of an owner
+/// and the states of its validators, in a single query
+pub async fn query_staking_overview<N: Namada>(
+    context: &N,
+    args: args::QueryStakingOverview,
+) -> std::io::Result<()> {
+    let owner = args.owner;
+    let overview =
+        match rpc::query_staking_overview(context.client(), &owner).await {
+            Ok(overview) => overview,
+            Err(err) => {
+                edisplay_line!(
+                    context.io(),
+                    "Error querying the staking overview: {err}"
+                );
+                cli::safe_exit(1)
+            }
+        };
+
+    let stdout = io::stdout();
+    let mut w = stdout.lock();
+
+    display_line!(context.io(), &mut w; "Last committed epoch: {}", overview.epoch)?;
+    if overview.validator_states.is_empty() {
+        display_line!(context.io(), &mut w; "No bonds found for {owner}")?;
+        return Ok(());
+    }
+
+    display_line!(context.io(), &mut w; "Validators:")?;
+    for (validator, state) in &overview.validator_states {
+        let state = match state {
+            Some(ValidatorState::Consensus) => "consensus",
+            Some(ValidatorState::BelowCapacity) => "below-capacity",
+            Some(ValidatorState::BelowThreshold) => "below-threshold",
+            Some(ValidatorState::Inactive) => "inactive",
+            Some(ValidatorState::Jailed) => "jailed",
+            None => "not a validator",
+        };
+        display_line!(context.io(), &mut w; "  {validator}: {state}")?;
+    }
+    display_line!(context.io(), &mut w; "")?;
+
+    for (bond_id, details) in &overview.bonds_and_unbonds.data {
+        if bond_id.source == bond_id.validator {
+            display_line!(context.io(), &mut w; "Self-bonds:")?;
+        } else {
+            display_line!(context.io(), &mut w; "Delegations to {}:", bond_id.validator)?;
+        }
+        for bond in &details.data.bonds {
+            display_line!(
+                context.io(),
+                &mut w;
+                "  Remaining active bond from epoch {}: Δ {}",
+                bond.start,
+                bond.amount.to_string_native()
+            )?;
+        }
+        for unbond in &details.data.unbonds {
+            display_line!(
+                context.io(),
+                &mut w;
+                "  Unbonded, withdrawable from epoch {} (active from {}): Δ {}",
+                unbond.withdraw,
+                unbond.start,
+                unbond.amount.to_string_native()
+            )?;
+        }
+        for slash in &details.data.slashes {
+            display_line!(
+                context.io(),
+                &mut w;
+                "  Slashed for an infraction in epoch {}, block height {}, \
+                 type {}, rate {}",
+                slash.epoch,
+                slash.block_height,
+                slash.r#type,
+                slash.rate
+            )?;
+        }
+        display_line!(
+            context.io(),
+            &mut w;
+            "Active (slashed) bonds total: {}",
+            details.bonds_total_active().to_string_native()
+        )?;
+        display_line!(
+            context.io(),
+            &mut w;
+            "Unbonded total: {}",
+            details.unbonds_total.to_string_native()
+        )?;
+        display_line!(
+            context.io(),
+            &mut w;
+            "Withdrawable total: {}",
+            details.total_withdrawable.to_string_native()
+        )?;
+        display_line!(context.io(), &mut w; "")?;
+    }
+
+    let mut rewards_total = token::Amount::zero();
+    if !overview.rewards.is_empty() {
+        display_line!(context.io(), &mut w; "Rewards available to claim:")?;
+        for (validator, rewards) in &overview.rewards {
+            display_line!(
+                context.io(),
+                &mut w;
+                "  From {validator}: {}",
+                rewards.to_string_native()
+            )?;
+            rewards_total += *rewards;
+        }
+        display_line!(context.io(), &mut w; "")?;
+    }
+
+    let totals = &overview.bonds_and_unbonds;
+    display_line!(
+        context.io(),
+        &mut w;
+        "All bonds total active: {}",
+        totals.bonds_total_active().to_string_native()
+    )?;
+    display_line!(
+        context.io(),
+        &mut w;
+        "All unbonds total active: {}",
+        totals.unbonds_total_active().to_string_native()
+    )?;
+    display_line!(
+        context.io(),
+        &mut w;
+        "All unbonds total withdrawable: {}",
+        totals.total_withdrawable.to_string_native()
+    )?;
+    display_line!(
+        context.io(),
+        &mut w;
+        "All rewards total: {}",
+        rewards_total.to_string_native()
+    )?;
+    Ok(())
+}
+
 pub async fn query_find_validator<N: Namada>(
     context: &N,
     args: args::QueryFindValidator,
@@ -2333,6 +3071,133 @@ pub async fn query_find_validator<N: Namada>(
     }
 }
 
+/// Query the consensus validator set of an epoch from the history of the
+/// validator sets, optionally with a Merkle proof of it
+pub async fn query_validator_set<N: Namada>(
+    context: &N,
+    args: args::QueryValidatorSet,
+) {
+    let epoch = match args.epoch {
+        Some(epoch) => epoch,
+        None => query_and_print_epoch(context).await,
+    };
+    // Pin the height, so that the proof is checked against the right state
+    let height = namada_sdk::rpc::query_block(context.client())
+        .await
+        .unwrap_or_else(|err| {
+            edisplay_line!(
+                context.io(),
+                "Failed to query the last block: {err}"
+            );
+            cli::safe_exit(1)
+        })
+        .map(|block| block.height);
+    let key = validator_set_history_key(epoch);
+    let (bytes, proof) = query_storage_value_bytes(
+        context.client(),
+        &key,
+        height,
+        args.with_proof,
+    )
+    .await;
+    let Some(bytes) = bytes else {
+        edisplay_line!(
+            context.io(),
+            "No validator set found in the history for epoch {epoch}, it may \
+             have been pruned"
+        );
+        cli::safe_exit(1)
+    };
+    let validators = Vec::<HistoricalValidator>::try_from_slice(&bytes)
+        .unwrap_or_else(|err| {
+            edisplay_line!(
+                context.io(),
+                "Failed to decode the validator set of epoch {epoch}: {err}"
+            );
+            cli::safe_exit(1)
+        });
+
+    display_line!(context.io(), "Consensus validator set of epoch {epoch}:");
+    for validator in &validators {
+        display_line!(
+            context.io(),
+            "  {}: bonded stake {}, voting power {}",
+            validator.address,
+            validator.bonded_stake.to_string_native(),
+            validator.voting_power
+        );
+        display_line!(
+            context.io(),
+            "    consensus key: {}",
+            validator.consensus_key
+        );
+        if let Some(key) = &validator.eth_hot_key {
+            display_line!(context.io(), "    Ethereum hot key: {key}");
+        }
+        if let Some(key) = &validator.eth_cold_key {
+            display_line!(context.io(), "    Ethereum cold key: {key}");
+        }
+    }
+
+    if args.with_proof {
+        let Some(proof) = proof else {
+            edisplay_line!(context.io(), "The node didn't return a proof");
+            cli::safe_exit(1)
+        };
+        display_line!(
+            context.io(),
+            "Merkle proof of the storage key {key} and value {} in the state \
+             at height {}:",
+            HEXLOWER.encode(&bytes),
+            height.unwrap_or_default()
+        );
+        for op in proof.ops {
+            display_line!(
+                context.io(),
+                "  {}: key {}, data {}",
+                op.field_type,
+                HEXLOWER.encode(&op.key),
+                HEXLOWER.encode(&op.data)
+            );
+        }
+    }
+}
+
+/// Query the proposers of committed blocks and the expected proposers of
+/// upcoming blocks
+pub async fn query_proposer<N: Namada>(context: &N, args: args::QueryProposer) {
+    let proposers = match namada_sdk::proposer::query_proposers(
+        context.client(),
+        args.height,
+        args.count,
+    )
+    .await
+    {
+        Ok(proposers) => proposers,
+        Err(err) => {
+            edisplay_line!(context.io(), "Failed to query proposers: {err}");
+            cli::safe_exit(1)
+        }
+    };
+    for proposer in proposers {
+        let kind = if proposer.predicted {
+            "expected proposer"
+        } else {
+            "proposer"
+        };
+        let validator = proposer
+            .address
+            .map(|address| address.to_string())
+            .unwrap_or_else(|| "unknown validator".to_string());
+        display_line!(
+            context.io(),
+            "Height {}: {kind} {validator} (Tendermint address {})",
+            proposer.height,
+            proposer.tm_address
+        );
+    }
+}
+
 /// Get account's public key stored in its storage sub-space
 pub async fn get_public_key<C: namada::ledger::queries::Client + Sync>(
     client: &C,
@@ -2475,8 +3340,42 @@ pub async fn query_masp_reward_tokens(context: &impl Namada) {
         .await
         .expect("The tokens that may earn MASP rewards should be defined");
     display_line!(context.io(), "The following tokens may ear MASP rewards:");
-    for (alias, address) in tokens {
-        display_line!(context.io(), "{}: {}", alias, address);
+    for (alias, address) in &tokens {
+        let max_reward_rate_key =
+            token::storage_key::masp_max_reward_rate_key(address);
+        match query_storage_value::<_, Dec>(
+            context.client(),
+            &max_reward_rate_key,
+        )
+        .await
+        {
+            Ok(rate) => display_line!(
+                context.io(),
+                "{}: {} (max reward rate {})",
+                alias,
+                address,
+                rate
+            ),
+            Err(_) => display_line!(context.io(), "{}: {}", alias, address),
+        }
+    }
+
+    // The reward tokens added by governance are only converted from the next
+    // epoch
+    let reward_tokens_key = token::storage_key::masp_reward_tokens_key();
+    let reward_tokens: BTreeMap<String, Address> =
+        query_storage_value(context.client(), &reward_tokens_key)
+            .await
+            .unwrap_or_default();
+    for (alias, address) in reward_tokens {
+        if !tokens.values().any(|token| *token == address) {
+            display_line!(
+                context.io(),
+                "{}: {} (earning MASP rewards from the next epoch)",
+                alias,
+                address
+            );
+        }
     }
 }
 
@@ -2562,7 +3461,28 @@ pub async fn query_tx_response<C: namada::ledger::queries::Client + Sync>(
 /// Lookup the results of applying the specified transaction to the
 /// blockchain.
 pub async fn query_result(context: &impl Namada, args: args::QueryResult) {
-    // First try looking up application event pertaining to given hash.
+    // Find the block that included the tx from the node's index of tx hashes
+    if let Ok(tx_hash) = Hash::from_str(&args.tx_hash) {
+        if let Ok(Some(indexed)) =
+            namada_sdk::rpc::query_indexed_tx_events(context.client(), &tx_hash)
+                .await
+        {
+            match indexed.kind {
+                namada_sdk::rpc::TxHashKind::Inner => {
+                    display_inner_resp(context, &indexed.response);
+                }
+                namada_sdk::rpc::TxHashKind::Wrapper => {
+                    display_wrapper_resp_and_get_result(
+                        context,
+                        &indexed.response,
+                    );
+                }
+            }
+            return;
+        }
+    }
+
+    // Otherwise, try looking up application event pertaining to given hash.
     let inner_resp = query_tx_response(
         context.client(),
         namada_sdk::rpc::TxEventQuery::Applied(&args.tx_hash),
@@ -1,6 +1,8 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::fs::File;
 use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
 
 use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
@@ -11,21 +13,28 @@ use namada::governance::cli::offline::{
     OfflineProposal, OfflineSignedProposal, OfflineVote,
 };
 use namada::governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, IbcClientRecoveryProposal, PgfFundingProposal,
+    PgfStewardProposal,
 };
 use namada::governance::ProposalVote;
 use namada::ibc::apps::transfer::types::Memo;
 use namada::state::EPOCH_SWITCH_BLOCKS_DELAY;
 use namada::tx::data::pos::{BecomeValidator, ConsensusKeyChange};
-use namada::tx::{CompressedSignature, Section, Signer, Tx};
+use namada::tx::data::wrapper::GasLimit;
+use namada::tx::data::TxType;
+use namada::tx::{CompressedSignature, Section, Signature, Signer, Tx};
 use namada::types::address::{Address, ImplicitAddress};
 use namada::types::dec::Dec;
+use namada::types::hash::Hash;
 use namada::types::io::Io;
 use namada::types::key::{self, *};
+use namada::types::storage::BlockHeight;
 use namada_sdk::rpc::{InnerTxResult, TxBroadcastData, TxResponse};
 use namada_sdk::wallet::alias::validator_consensus_key;
 use namada_sdk::wallet::{Wallet, WalletIo};
-use namada_sdk::{display_line, edisplay_line, error, signing, tx, Namada};
+use namada_sdk::{
+    display_line, edisplay_line, error, prompt, signing, tx, Namada,
+};
 use rand::rngs::OsRng;
 use tokio::sync::RwLock;
 
@@ -155,6 +164,69 @@ pub async fn with_hardware_wallet<'a, U: WalletIo + Clone>(
     Ok(tx)
 }
 
+/// Sign the requested parts of the transaction with the given key of the
+/// wallet daemon listening on the given socket
+pub async fn with_wallet_daemon(
+    mut tx: Tx,
+    pubkey: common::PublicKey,
+    parts: HashSet<signing::Signable>,
+    socket_path: &Path,
+) -> Result<Tx, error::Error> {
+    #[cfg(unix)]
+    {
+        tx.protocol_filter();
+        // Sign the raw header if that is requested
+        if parts.contains(&signing::Signable::RawHeader) {
+            let section = sign_with_wallet_daemon(
+                socket_path,
+                &pubkey,
+                vec![tx.raw_header_hash()],
+            )
+            .await?;
+            tx.add_section(section);
+        }
+        // Sign the fee header if that is requested. This commits to all the
+        // sections, including the signature of the raw header.
+        if parts.contains(&signing::Signable::FeeHeader) {
+            let section =
+                sign_with_wallet_daemon(socket_path, &pubkey, tx.sechashes())
+                    .await?;
+            tx.add_section(section);
+        }
+        Ok(tx)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (tx, pubkey, parts, socket_path);
+        Err(error::Error::Other(
+            "The wallet daemon is only supported on Unix.".to_string(),
+        ))
+    }
+}
+
+/// Get a signature section of the given targets, signed with the given key of
+/// the wallet daemon listening on the given socket
+#[cfg(unix)]
+async fn sign_with_wallet_daemon(
+    socket_path: &Path,
+    pubkey: &common::PublicKey,
+    targets: Vec<Hash>,
+) -> Result<Section, error::Error> {
+    let mut section = Signature {
+        targets,
+        signer: Signer::PubKeys(vec![pubkey.clone()]),
+        signatures: BTreeMap::new(),
+    };
+    let signature = namada_sdk::wallet::daemon::sign(
+        socket_path,
+        pubkey,
+        section.get_raw_hash(),
+    )
+    .await?;
+    section.signatures.insert(0, signature);
+    Ok(Section::Signature(section))
+}
+
 // Sign the given transaction using a hardware wallet as a backup
 pub async fn sign<N: Namada>(
     context: &N,
@@ -187,6 +259,17 @@ pub async fn sign<N: Namada>(
                 with_hw_data,
             )
             .await?;
+    } else if let Some(socket_path) = &args.wallet_daemon {
+        // Sign with the keys of the wallet daemon as backup
+        context
+            .sign(
+                tx,
+                args,
+                signing_data,
+                with_wallet_daemon,
+                socket_path.as_path(),
+            )
+            .await?;
     } else {
         // Otherwise sign without a backup procedure
         context
@@ -196,6 +279,127 @@ pub async fn sign<N: Namada>(
     Ok(())
 }
 
+/// Sign and submit a transaction. With `--auto-resubmit`, a transaction that
+/// expires or is evicted from the mempool before it's accepted is renewed
+/// with a fresh timestamp and a higher fee, signed again and resubmitted, up
+/// to the given number of times.
+pub async fn sign_and_submit<N: Namada>(
+    namada: &N,
+    mut tx: Tx,
+    args: &args::Tx,
+    signing_data: SigningTxData,
+) -> Result<ProcessTxResponse, error::Error> {
+    let max_resubmissions = args.auto_resubmit.unwrap_or_default();
+    if max_resubmissions == 0 || args.dry_run || args.dry_run_wrapper {
+        sign(namada, &mut tx, args, signing_data).await?;
+        return namada.submit(tx, args).await;
+    }
+    // Without an expiration, we can't tell when the tx can no longer be
+    // included, and resubmitting it could apply it twice
+    if tx.header.expiration.is_none() && tx.header.expiration_height.is_none() {
+        return Err(error::Error::Other(
+            "Resubmitting an expired transaction requires an expiration or an \
+             expiration height"
+                .to_string(),
+        ));
+    }
+    // The number of blocks in which the tx can be included, kept for the
+    // resubmitted txs
+    let height_lifetime = match tx.header.expiration_height {
+        Some(expiration_height) => {
+            let last_height = last_block_height(namada).await?;
+            Some(expiration_height.0.saturating_sub(last_height.0))
+        }
+        None => None,
+    };
+
+    let mut resubmissions = 0;
+    loop {
+        sign(namada, &mut tx, args, signing_data.clone()).await?;
+        let result = namada.submit(tx.clone(), args).await;
+        let accept_timeout = matches!(
+            result,
+            Err(error::Error::Tx(error::TxSubmitError::AcceptTimeout))
+        );
+        if !accept_timeout || resubmissions == max_resubmissions {
+            return result;
+        }
+
+        let reason = match tx::wait_for_pending_tx(namada, &tx).await? {
+            tx::PendingTxOutcome::Expired => {
+                "The transaction expired before it was accepted".to_string()
+            }
+            tx::PendingTxOutcome::Evicted(log) => format!(
+                "The transaction was evicted from the mempool before it was \
+                 accepted: {log}"
+            ),
+            tx::PendingTxOutcome::Accepted => {
+                // The tx was accepted after all
+                let resp = tx::wait_for_tx_result(
+                    namada,
+                    &tx.header_hash().to_string(),
+                    &tx.raw_header_hash().to_string(),
+                )
+                .await?;
+                if let InnerTxResult::Success(result) = resp.inner_tx_result() {
+                    tx::save_initialized_accounts(
+                        namada,
+                        args,
+                        result.initialized_accounts.clone(),
+                    )
+                    .await;
+                }
+                return Ok(ProcessTxResponse::Applied(resp));
+            }
+        };
+
+        let expiration_height = match height_lifetime {
+            Some(lifetime) => {
+                let last_height = last_block_height(namada).await?;
+                Some(BlockHeight(last_height.0 + lifetime))
+            }
+            None => None,
+        };
+        tx::renew_expired_tx(&mut tx, expiration_height)?;
+        resubmissions += 1;
+        let gas_price = tx
+            .header
+            .wrapper()
+            .map(|wrapper| wrapper.fee.amount_per_gas_unit.to_string())
+            .unwrap_or_default();
+        display_line!(
+            namada.io(),
+            "{reason}. Resubmitting it with a gas price of {gas_price} \
+             (attempt {resubmissions} of {max_resubmissions})."
+        );
+    }
+}
+
+/// Query the height of the last committed block
+async fn last_block_height(
+    namada: &impl Namada,
+) -> Result<BlockHeight, error::Error> {
+    Ok(namada_sdk::rpc::query_block(namada.client())
+        .await?
+        .map(|last_block| last_block.height)
+        .unwrap_or_default())
+}
+
+// Dump the given transaction, together with its signing data if it's to be
+// signed offline
+fn dump_tx<IO: Io>(
+    io: &IO,
+    args: &args::Tx,
+    tx: Tx,
+    signing_data: SigningTxData,
+) {
+    if args.offline_sign {
+        tx::dump_offline_tx(io, args, tx, signing_data);
+    } else {
+        tx::dump_tx(io, args, tx);
+    }
+}
+
 // Build a transaction to reveal the signer of the given transaction.
 pub async fn submit_reveal_aux(
     context: &impl Namada,
@@ -220,12 +424,12 @@ pub async fn submit_reveal_aux(
                 "Submitting a tx to reveal the public key for address \
                  {address}..."
             );
-            let (mut tx, signing_data) =
+            let (tx, signing_data) =
                 tx::build_reveal_pk(context, &args, &public_key).await?;
 
-            sign(context, &mut tx, &args, signing_data).await?;
-
-            context.submit(tx, &args).await?;
+            sign_and_submit(context, tx, &args, signing_data)
+                .await?
+                .into_valid()?;
         }
     }
 
@@ -237,16 +441,16 @@ pub async fn submit_bridge_pool_tx<N: Namada>(
     args: args::EthereumBridgePool,
 ) -> Result<(), error::Error> {
     let tx_args = args.tx.clone();
-    let (mut tx, signing_data) = args.clone().build(namada).await?;
+    let (tx, signing_data) = args.clone().build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
         submit_reveal_aux(namada, tx_args.clone(), &args.sender).await?;
 
-        sign(namada, &mut tx, &tx_args, signing_data).await?;
-
-        namada.submit(tx, &tx_args).await?;
+        sign_and_submit(namada, tx, &tx_args, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -261,19 +465,260 @@ where
 {
     submit_reveal_aux(namada, args.tx.clone(), &args.owner).await?;
 
-    let (mut tx, signing_data) = args.build(namada).await?;
+    let (tx, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
+    }
+
+    Ok(())
+}
+
+/// Build a transaction by prompting for its code, data, extra sections and
+/// signers. The transaction is previewed and its gas is estimated with a
+/// dry-run before it's saved unsigned, to be signed with `namadaw sign-tx`
+/// and submitted with `namadac broadcast-signed`.
+pub async fn submit_build<N: Namada>(
+    namada: &N,
+    args: args::TxBuild,
+) -> Result<(), error::Error> {
+    if !args.interactive {
+        return Err(error::Error::Other(
+            "Only the interactive mode is supported, use `--interactive`."
+                .to_string(),
+        ));
+    }
+    let io = namada.io();
+    let mut tx_args = args.tx;
 
-        namada.submit(tx, &args.tx).await?;
+    let (code_path, code_hash) = loop {
+        let code_path = prompt!(
+            io,
+            "Name of the transaction's WASM code (e.g. tx_transfer.wasm): "
+        )
+        .await;
+        let code_path = code_path.trim();
+        match query_wasm_code_hash(namada, code_path).await {
+            Ok(code_hash) => break (code_path.to_string(), code_hash),
+            // The missing code is reported by the query
+            Err(error::Error::Query(error::QueryError::Wasm(_))) => {}
+            Err(err) => return Err(err),
+        }
+    };
+
+    let data = loop {
+        let path =
+            prompt!(io, "Path to the data file (empty for none): ").await;
+        let path = path.trim();
+        if path.is_empty() {
+            break None;
+        }
+        match std::fs::read(path) {
+            Ok(data) => break Some(data),
+            Err(err) => edisplay_line!(io, "Couldn't read {path}: {err}"),
+        }
+    };
+
+    let mut extra_sections = vec![];
+    loop {
+        let path =
+            prompt!(io, "Path to an extra section (empty to finish): ").await;
+        let path = path.trim();
+        if path.is_empty() {
+            break;
+        }
+        let section = match std::fs::read(path) {
+            Ok(section) => section,
+            Err(err) => {
+                edisplay_line!(io, "Couldn't read {path}: {err}");
+                continue;
+            }
+        };
+        let tag =
+            prompt!(io, "Tag of the extra section (empty for none): ").await;
+        let tag = Some(tag.trim().to_string()).filter(|tag| !tag.is_empty());
+        extra_sections.push((section, tag));
+    }
+
+    if tx_args.memo.is_none() {
+        let memo = prompt!(io, "Memo (empty for none): ").await;
+        let memo = memo.trim();
+        if !memo.is_empty() {
+            tx_args.memo = Some(memo.as_bytes().to_vec());
+        }
     }
 
+    let owner = loop {
+        let owner = prompt!(
+            io,
+            "Address or alias of the account sending the transaction: "
+        )
+        .await;
+        let owner = owner.trim();
+        let address = match Address::decode(owner) {
+            Ok(address) => Some(address),
+            Err(_) => namada
+                .wallet()
+                .await
+                .find_address(owner)
+                .map(|address| address.into_owned()),
+        };
+        match address {
+            Some(address) => break address,
+            None => edisplay_line!(io, "Unknown address or alias {owner}"),
+        }
+    };
+
+    if tx_args.signing_keys.is_empty() {
+        'signers: loop {
+            let signers = prompt!(
+                io,
+                "Public keys or aliases of the signers, comma separated \
+                 (empty for the keys of the account): "
+            )
+            .await;
+            let mut signing_keys = vec![];
+            for signer in signers.split(',').map(str::trim) {
+                if signer.is_empty() {
+                    continue;
+                }
+                let key = match common::PublicKey::from_str(signer) {
+                    Ok(key) => Some(key),
+                    Err(_) => {
+                        namada.wallet().await.find_public_key(signer).ok()
+                    }
+                };
+                match key {
+                    Some(key) => signing_keys.push(key),
+                    None => {
+                        edisplay_line!(
+                            io,
+                            "Unknown public key or alias {signer}"
+                        );
+                        continue 'signers;
+                    }
+                }
+            }
+            tx_args.signing_keys = signing_keys;
+            break;
+        }
+    }
+
+    let signing_data =
+        aux_signing_data(namada, &tx_args, Some(owner.clone()), Some(owner))
+            .await?;
+
+    let chain_id = tx_args.chain_id.clone().unwrap();
+    let mut tx = Tx::new(chain_id, tx_args.expiration);
+    if let Some(memo) = &tx_args.memo {
+        tx.add_memo(memo);
+    }
+    tx.add_code_from_hash(code_hash, Some(code_path));
+    if let Some(data) = data {
+        tx.add_serialized_data(data);
+    }
+    for (section, tag) in extra_sections {
+        tx.add_extra_section(section, tag);
+    }
+
+    // Estimate the gas of the inner tx by simulating it
+    let estimate =
+        match namada_sdk::rpc::dry_run_tx(namada, tx.to_bytes()).await {
+            Ok(result) => GasLimit::from_str(&result.gas_used.to_string()).ok(),
+            Err(err) => {
+                edisplay_line!(io, "Couldn't estimate the gas: {err}");
+                None
+            }
+        };
+    let default_gas_limit = estimate.unwrap_or(tx_args.gas_limit);
+    tx_args.gas_limit = loop {
+        let gas_limit = prompt!(
+            io,
+            "Gas limit (empty for {}): ",
+            u64::from(default_gas_limit)
+        )
+        .await;
+        let gas_limit = gas_limit.trim();
+        if gas_limit.is_empty() {
+            break default_gas_limit;
+        }
+        match GasLimit::from_str(gas_limit) {
+            Ok(gas_limit) => break gas_limit,
+            Err(err) => edisplay_line!(io, "Invalid gas limit: {err}"),
+        }
+    };
+
+    tx::prepare_tx(
+        namada,
+        &tx_args,
+        &mut tx,
+        signing_data.fee_payer.clone(),
+        None,
+    )
+    .await?;
+
+    display_tx_preview(io, &tx, &signing_data);
+    let confirmation = prompt!(io, "Save the transaction? [y/N]: ").await;
+    if !matches!(confirmation.trim(), "y" | "Y" | "yes") {
+        display_line!(io, "The transaction was not saved.");
+        return Ok(());
+    }
+    tx::dump_offline_tx(io, &tx_args, tx, signing_data);
+
     Ok(())
 }
 
+/// Display the decoded structure of a transaction built interactively
+fn display_tx_preview<IO: Io>(io: &IO, tx: &Tx, signing_data: &SigningTxData) {
+    display_line!(io, "Transaction {}:", tx.header_hash());
+    display_line!(io, "  Chain ID: {}", tx.header.chain_id);
+    if let Some(expiration) = &tx.header.expiration {
+        display_line!(io, "  Expiration: {expiration}");
+    }
+    if let Some(expiration_height) = tx.header.expiration_height {
+        display_line!(io, "  Expiration height: {expiration_height}");
+    }
+    if let TxType::Wrapper(wrapper) = &tx.header.tx_type {
+        display_line!(
+            io,
+            "  Fee: {} {} per gas unit, gas limit {}",
+            wrapper.fee.amount_per_gas_unit,
+            wrapper.fee.token,
+            u64::from(wrapper.gas_limit)
+        );
+        display_line!(io, "  Fee payer: {}", wrapper.pk);
+    }
+    display_line!(io, "  Sections:");
+    for section in &tx.sections {
+        let (kind, tag) = match section {
+            Section::Data(_) => ("data", None),
+            Section::ExtraData(code) => ("extra data", code.tag.as_ref()),
+            Section::Code(code) => ("code", code.tag.as_ref()),
+            Section::Signature(_) => ("signature", None),
+            Section::Ciphertext(_) => ("ciphertext", None),
+            Section::MaspTx(_) => ("MASP tx", None),
+            Section::MaspBuilder(_) => ("MASP builder", None),
+            Section::Header(_) => ("header", None),
+        };
+        match tag {
+            Some(tag) => display_line!(
+                io,
+                "    {kind} {} (tag {tag})",
+                section.get_hash()
+            ),
+            None => display_line!(io, "    {kind} {}", section.get_hash()),
+        }
+    }
+    display_line!(io, "  Signers (threshold {}):", signing_data.threshold);
+    for public_key in &signing_data.public_keys {
+        display_line!(io, "    {public_key}");
+    }
+}
+
 pub async fn submit_update_account<N: Namada>(
     namada: &N,
     args: args::TxUpdateAccount,
@@ -281,14 +726,34 @@ pub async fn submit_update_account<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx, signing_data) = args.build(namada).await?;
+    let (tx, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
+    }
+
+    Ok(())
+}
 
-        namada.submit(tx, &args.tx).await?;
+pub async fn submit_update_account_keys<N: Namada>(
+    namada: &N,
+    args: args::TxUpdateAccountKeys,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
+    } else {
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -301,14 +766,14 @@ pub async fn submit_init_account<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx, signing_data) = tx::build_init_account(namada, &args).await?;
+    let (tx, signing_data) = tx::build_init_account(namada, &args).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
-
-        let response = namada.submit(tx, &args.tx).await?;
+        let response = sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
         if let Some(result) = response.is_applied_and_valid() {
             return Ok(result.initialized_accounts.first().cloned());
         }
@@ -425,10 +890,11 @@ pub async fn submit_change_consensus_key(
     .await?;
 
     if tx_args.dump_tx {
-        tx::dump_tx(namada.io(), &tx_args, tx);
+        dump_tx(namada.io(), &tx_args, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &tx_args, signing_data).await?;
-        let resp = namada.submit(tx, &tx_args).await?;
+        let resp = sign_and_submit(namada, tx, &tx_args, signing_data)
+            .await?
+            .into_valid()?;
 
         if !tx_args.dry_run {
             if resp.is_applied_and_valid().is_some() {
@@ -751,10 +1217,11 @@ pub async fn submit_become_validator(
     .await?;
 
     if tx_args.dump_tx {
-        tx::dump_tx(namada.io(), &tx_args, tx);
+        dump_tx(namada.io(), &tx_args, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &tx_args, signing_data).await?;
-        let resp = namada.submit(tx, &tx_args).await?;
+        let resp = sign_and_submit(namada, tx, &tx_args, signing_data)
+            .await?
+            .into_valid()?;
 
         if !tx_args.dry_run {
             if resp.is_applied_and_valid().is_some() {
@@ -920,16 +1387,14 @@ pub async fn submit_transfer(
         )
         .await?;
 
-        let (mut tx, signing_data, tx_epoch) =
-            args.clone().build(namada).await?;
+        let (tx, signing_data, tx_epoch) = args.clone().build(namada).await?;
 
         if args.tx.dump_tx {
-            tx::dump_tx(namada.io(), &args.tx, tx);
+            dump_tx(namada.io(), &args.tx, tx, signing_data);
             break;
         } else {
-            sign(namada, &mut tx, &args.tx, signing_data).await?;
-
-            let result = namada.submit(tx, &args.tx).await?;
+            let result =
+                sign_and_submit(namada, tx, &args.tx, signing_data).await?;
 
             match result {
                 ProcessTxResponse::Applied(resp) if
@@ -951,7 +1416,10 @@ pub async fn submit_transfer(
                 },
                 // Otherwise either the transaction was successful or it will not
                 // benefit from resubmission
-                _ => break,
+                result => {
+                    result.into_valid()?;
+                    break;
+                }
             }
         }
     }
@@ -959,6 +1427,42 @@ pub async fn submit_transfer(
     Ok(())
 }
 
+pub async fn submit_vesting_transfer(
+    namada: &impl Namada,
+    args: args::TxVestingTransfer,
+) -> Result<(), error::Error> {
+    submit_reveal_aux(namada, args.tx.clone(), &args.source).await?;
+    let (tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
+    } else {
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_schedule_tx(
+    namada: &impl Namada,
+    args: args::TxSchedule,
+) -> Result<(), error::Error> {
+    submit_reveal_aux(namada, args.tx.clone(), &args.owner).await?;
+    let (tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
+    } else {
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_ibc_transfer<N: Namada>(
     namada: &N,
     args: args::TxIbcTransfer,
@@ -972,14 +1476,14 @@ where
         &args.source.effective_address(),
     )
     .await?;
-    let (mut tx, signing_data, _) = args.build(namada).await?;
+    let (tx, signing_data, _) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
-
-        namada.submit(tx, &args.tx).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
     // NOTE that the tx could fail when its submission epoch doesn't match
     // construction epoch
@@ -997,7 +1501,7 @@ where
     let current_epoch = rpc::query_and_print_epoch(namada).await;
     let governance_parameters =
         rpc::query_governance_parameters(namada.client()).await;
-    let (mut tx_builder, signing_data) = if args.is_offline {
+    let (tx_builder, signing_data) = if args.is_offline {
         let proposal = OfflineProposal::try_from(args.proposal_data.as_ref())
             .map_err(|e| {
                 error::TxSubmitError::FailedGovernaneProposalDeserialize(
@@ -1089,6 +1593,35 @@ where
             .await?;
 
         tx::build_pgf_stewards_proposal(namada, &args, proposal).await?
+    } else if args.is_ibc_client_recovery {
+        let proposal =
+            IbcClientRecoveryProposal::try_from(args.proposal_data.as_ref())
+                .map_err(|e| {
+                    error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                        e.to_string(),
+                    )
+                })?;
+        let author_balance = rpc::get_token_balance(
+            namada.client(),
+            &namada.native_token(),
+            &proposal.proposal.author,
+        )
+        .await;
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                args.tx.force,
+            )
+            .map_err(|e| {
+                error::TxSubmitError::InvalidProposal(e.to_string())
+            })?;
+
+        submit_reveal_aux(namada, args.tx.clone(), &proposal.proposal.author)
+            .await?;
+
+        tx::build_ibc_client_recovery_proposal(namada, &args, proposal).await?
     } else {
         let proposal = DefaultProposal::try_from(args.proposal_data.as_ref())
             .map_err(|e| {
@@ -1120,11 +1653,11 @@ where
     };
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx_builder);
+        dump_tx(namada.io(), &args.tx, tx_builder, signing_data);
     } else {
-        sign(namada, &mut tx_builder, &args.tx, signing_data).await?;
-
-        namada.submit(tx_builder, &args.tx).await?;
+        sign_and_submit(namada, tx_builder, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -1137,7 +1670,7 @@ pub async fn submit_vote_proposal<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx_builder, signing_data) = if args.is_offline {
+    let (tx_builder, signing_data) = if args.is_offline {
         let default_signer = Some(args.voter.clone());
         let signing_data = aux_signing_data(
             namada,
@@ -1202,11 +1735,11 @@ where
     };
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx_builder);
+        dump_tx(namada.io(), &args.tx, tx_builder, signing_data);
     } else {
-        sign(namada, &mut tx_builder, &args.tx, signing_data).await?;
-
-        namada.submit(tx_builder, &args.tx).await?;
+        sign_and_submit(namada, tx_builder, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -1293,6 +1826,41 @@ where
     Ok(())
 }
 
+/// Submit a transaction that was signed offline with `namadaw sign-tx`.
+pub async fn submit_signed_tx<N: Namada>(
+    namada: &N,
+    args: args::BroadcastSignedTx,
+) -> Result<(), error::Error> {
+    let tx = if let Ok(transaction) = Tx::deserialize(args.tx_data.as_ref()) {
+        transaction
+    } else {
+        edisplay_line!(namada.io(), "Couldn't decode the transaction.");
+        safe_exit(1)
+    };
+    namada.submit(tx, &args.tx).await?.into_valid()?;
+    Ok(())
+}
+
+pub async fn submit_update_vote_proxy<N: Namada>(
+    namada: &N,
+    args: args::UpdateVoteProxy,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
+    } else {
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_reveal_pk<N: Namada>(
     namada: &N,
     args: args::RevealPk,
@@ -1315,14 +1883,14 @@ where
     let default_address = args.source.clone().unwrap_or(args.validator.clone());
     submit_reveal_aux(namada, args.tx.clone(), &default_address).await?;
 
-    let (mut tx, signing_data) = args.build(namada).await?;
+    let (tx, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
-
-        namada.submit(tx, &args.tx).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -1335,14 +1903,14 @@ pub async fn submit_unbond<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx, signing_data, latest_withdrawal_pre) =
-        args.build(namada).await?;
+    let (tx, signing_data, latest_withdrawal_pre) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
-        let resp = namada.submit(tx, &args.tx).await?;
+        let resp = sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
 
         if !args.tx.dry_run && resp.is_applied_and_valid().is_some() {
             tx::query_unbonds(namada, args.clone(), latest_withdrawal_pre)
@@ -1360,14 +1928,14 @@ pub async fn submit_withdraw<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx, signing_data) = args.build(namada).await?;
+    let (tx, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
-
-        namada.submit(tx, &args.tx).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -1380,14 +1948,34 @@ pub async fn submit_claim_rewards<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx, signing_data) = args.build(namada).await?;
+    let (tx, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
+    }
+
+    Ok(())
+}
 
-        namada.submit(tx, &args.tx).await?;
+pub async fn submit_set_auto_compound<N: Namada>(
+    namada: &N,
+    args: args::SetAutoCompound,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
+    } else {
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -1400,14 +1988,14 @@ pub async fn submit_redelegate<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx, signing_data) = args.build(namada).await?;
+    let (tx, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
-
-        namada.submit(tx, &args.tx).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -1420,14 +2008,14 @@ pub async fn submit_validator_commission_change<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx, signing_data) = args.build(namada).await?;
+    let (tx, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
-
-        namada.submit(tx, &args.tx).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -1440,14 +2028,14 @@ pub async fn submit_validator_metadata_change<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx, signing_data) = args.build(namada).await?;
+    let (tx, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
-
-        namada.submit(tx, &args.tx).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -1460,14 +2048,14 @@ pub async fn submit_unjail_validator<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx, signing_data) = args.build(namada).await?;
+    let (tx, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
-
-        namada.submit(tx, &args.tx).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -1480,14 +2068,54 @@ pub async fn submit_deactivate_validator<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx, signing_data) = args.build(namada).await?;
+    let (tx, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_bridge_pause_vote<N: Namada>(
+    namada: &N,
+    args: args::TxBridgePauseVote,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
+    } else {
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_convert_nuts<N: Namada>(
+    namada: &N,
+    args: args::TxConvertNuts,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (tx, signing_data) = args.build(namada).await?;
 
-        namada.submit(tx, &args.tx).await?;
+    if args.tx.dump_tx {
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
+    } else {
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -1500,14 +2128,14 @@ pub async fn submit_reactivate_validator<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx, signing_data) = args.build(namada).await?;
+    let (tx, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
-
-        namada.submit(tx, &args.tx).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -1520,14 +2148,14 @@ pub async fn submit_update_steward_commission<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx, signing_data) = args.build(namada).await?;
+    let (tx, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
-
-        namada.submit(tx, &args.tx).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
@@ -1540,14 +2168,14 @@ pub async fn submit_resign_steward<N: Namada>(
 where
     <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
 {
-    let (mut tx, signing_data) = args.build(namada).await?;
+    let (tx, signing_data) = args.build(namada).await?;
 
     if args.tx.dump_tx {
-        tx::dump_tx(namada.io(), &args.tx, tx);
+        dump_tx(namada.io(), &args.tx, tx, signing_data);
     } else {
-        sign(namada, &mut tx, &args.tx, signing_data).await?;
-
-        namada.submit(tx, &args.tx).await?;
+        sign_and_submit(namada, tx, &args.tx, signing_data)
+            .await?
+            .into_valid()?;
     }
 
     Ok(())
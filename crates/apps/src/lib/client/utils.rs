@@ -1,7 +1,9 @@
+use std::collections::BTreeMap;
 use std::env;
 use std::fs::{self, File, OpenOptions};
 use std::io::Write;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::str::FromStr;
 
 use borsh_ext::BorshSerializeExt;
@@ -12,6 +14,7 @@ use flate2::Compression;
 use itertools::Either;
 use namada::types::chain::ChainId;
 use namada::types::dec::Dec;
+use namada::types::hash::Hash;
 use namada::types::key::*;
 use namada::types::string_encoding::StringEncoded;
 use namada::types::token;
@@ -19,18 +22,20 @@ use namada::types::uint::Uint;
 use namada::vm::validate_untrusted_wasm;
 use namada_sdk::wallet::{alias, Wallet};
 use prost::bytes::Bytes;
+use rand::rngs::OsRng;
 use serde_json::json;
 use sha2::{Digest, Sha256};
 use tokio::sync::RwLock;
 
-use crate::cli::args;
 use crate::cli::args::TestGenesis;
-use crate::cli::context::ENV_VAR_WASM_DIR;
+use crate::cli::context::{ENV_VAR_PROFILE, ENV_VAR_WASM_DIR};
+use crate::cli::{args, completions};
 use crate::config::genesis::chain::DeriveEstablishedAddress;
 use crate::config::genesis::transactions::{
     sign_delegation_bond_tx, sign_validator_account_tx, UnsignedTransactions,
 };
 use crate::config::global::GlobalConfig;
+use crate::config::profiles::{Profile, Profiles};
 use crate::config::{
     self, genesis, get_default_namada_folder, Config, TendermintMode,
 };
@@ -369,6 +374,149 @@ pub fn validate_wasm(args::ValidateWasm { code_path }: args::ValidateWasm) {
     }
 }
 
+/// Docker image tag used for the deterministic wasm builds
+const WASM_BUILD_IMAGE: &str = "namada-wasm";
+
+/// Check that the given wasm code matches the expected hash and, if a source
+/// directory is given, that the code rebuilt from it matches the hash too.
+pub fn verify_wasm(
+    args::VerifyWasm {
+        code_path,
+        hash,
+        source_dir,
+    }: args::VerifyWasm,
+) {
+    let code = fs::read(&code_path).unwrap_or_else(|err| {
+        eprintln!(
+            "Failed to read the wasm file {}: {err}",
+            code_path.display()
+        );
+        safe_exit(1)
+    });
+    let code_hash = Hash::sha256(&code);
+    let mut verified = code_hash == hash;
+    if verified {
+        println!("Wasm code {} matches hash {hash}", code_path.display());
+    } else {
+        eprintln!(
+            "Wasm code {} has hash {code_hash}, expected {hash}",
+            code_path.display()
+        );
+    }
+
+    if let Some(source_dir) = source_dir {
+        let rebuilt_hash = rebuild_wasm(&source_dir, &code_path);
+        if rebuilt_hash == hash {
+            println!(
+                "Wasm code rebuilt from {} matches hash {hash}",
+                source_dir.display()
+            );
+        } else {
+            eprintln!(
+                "Wasm code rebuilt from {} has hash {rebuilt_hash}, expected \
+                 {hash}",
+                source_dir.display()
+            );
+            verified = false;
+        }
+    }
+
+    if !verified {
+        safe_exit(1)
+    }
+}
+
+/// Rebuild the wasm code from the given source directory with the pinned
+/// toolchain inside a docker container and return the hash of the artifact
+/// that has the same name as the given wasm file.
+fn rebuild_wasm(source_dir: &Path, code_path: &Path) -> Hash {
+    let source_dir = fs::canonicalize(source_dir).unwrap_or_else(|err| {
+        eprintln!(
+            "Failed to find the source directory {}: {err}",
+            source_dir.display()
+        );
+        safe_exit(1)
+    });
+    // The wasm file may be named with or without its checksum, e.g.
+    // `tx_bond.wasm` or `tx_bond.<hash>.wasm`
+    let wasm_name = code_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.split('.').next())
+        .unwrap_or_else(|| {
+            eprintln!("Invalid wasm file name {}", code_path.display());
+            safe_exit(1)
+        });
+
+    println!("Building the {WASM_BUILD_IMAGE} docker image...");
+    let dockerfile = source_dir.join("docker/namada-wasm/Dockerfile");
+    let dockerfile = File::open(&dockerfile).unwrap_or_else(|err| {
+        eprintln!("Failed to open {}: {err}", dockerfile.display());
+        safe_exit(1)
+    });
+    run_docker(
+        Command::new("docker")
+            .args(["build", "-t", WASM_BUILD_IMAGE, "-"])
+            .stdin(Stdio::from(dockerfile)),
+    );
+
+    println!("Rebuilding the wasm code in {}...", source_dir.display());
+    let volume = format!("{}:/__w/namada/namada", source_dir.display());
+    run_docker(Command::new("docker").args([
+        "run",
+        "--rm",
+        "-v",
+        &volume,
+        WASM_BUILD_IMAGE,
+        "make",
+        "build-wasm-scripts",
+    ]));
+
+    let checksums_path = source_dir.join("wasm/checksums.json");
+    let checksums: BTreeMap<String, String> = fs::read(&checksums_path)
+        .ok()
+        .and_then(|checksums| serde_json::from_slice(&checksums).ok())
+        .unwrap_or_else(|| {
+            eprintln!(
+                "Failed to read the wasm checksums from {}",
+                checksums_path.display()
+            );
+            safe_exit(1)
+        });
+    let wasm_file = format!("{wasm_name}.wasm");
+    let artifact = checksums.get(&wasm_file).unwrap_or_else(|| {
+        eprintln!(
+            "No wasm named {wasm_file} was built from {}",
+            source_dir.display()
+        );
+        safe_exit(1)
+    });
+    let artifact_path = source_dir.join("wasm").join(artifact);
+    let code = fs::read(&artifact_path).unwrap_or_else(|err| {
+        eprintln!(
+            "Failed to read the rebuilt wasm file {}: {err}",
+            artifact_path.display()
+        );
+        safe_exit(1)
+    });
+    Hash::sha256(code)
+}
+
+/// Run a docker command, exiting if it cannot be run or if it fails.
+fn run_docker(cmd: &mut Command) {
+    match cmd.status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            eprintln!("Docker command failed with {status}");
+            safe_exit(1)
+        }
+        Err(err) => {
+            eprintln!("Failed to run docker: {err}");
+            safe_exit(1)
+        }
+    }
+}
+
 /// Length of a Tendermint Node ID in bytes
 const TENDERMINT_NODE_ID_LENGTH: usize = 20;
 
@@ -416,34 +564,9 @@ pub fn init_network(
             safe_exit(1)
         });
 
-    // In addition to standard templates validation, check that there is at
-    // least one validator account.
-    if !templates.transactions.has_at_least_one_validator() {
-        eprintln!("No validator genesis transaction found, aborting.");
-        safe_exit(1)
-    }
-
-    // Also check that at least one validator account has positive voting power.
-    let tm_votes_per_token = templates.parameters.pos_params.tm_votes_per_token;
-    if !templates
-        .transactions
-        .has_validator_with_positive_voting_power(tm_votes_per_token)
-    {
-        let min_stake = token::Amount::from_uint(
-            if tm_votes_per_token > Dec::from(1) {
-                Uint::one()
-            } else {
-                (Dec::from(1) / tm_votes_per_token).ceil().abs()
-            },
-            token::NATIVE_MAX_DECIMAL_PLACES,
-        )
-        .unwrap();
-        eprintln!(
-            "No validator with positive voting power, aborting. The minimum \
-             staked tokens amount required to run the network is {}, because \
-             there are {tm_votes_per_token} votes per NAMNAM tokens.",
-            min_stake.to_string_native(),
-        );
+    // In addition to standard templates validation, check the validators
+    if !validate_genesis_validators(&templates) {
+        eprintln!("Invalid genesis validators, aborting.");
         safe_exit(1)
     }
 
@@ -628,6 +751,150 @@ pub fn default_base_dir(
     );
 }
 
+/// Print the shell completion script of the client.
+pub fn completions(global_args: args::Global, args: args::Completions) {
+    let mut stdout = std::io::stdout();
+    completions::write_script(args.shell, &global_args.base_dir, &mut stdout)
+        .unwrap_or_else(|err| {
+            eprintln!("Failed to write the completion script: {err}");
+            safe_exit(1)
+        });
+}
+
+fn read_profiles(base_dir: &Path) -> Profiles {
+    Profiles::read(base_dir).unwrap_or_else(|err| {
+        eprintln!("Failed to read the client profiles: {err}");
+        safe_exit(1)
+    })
+}
+
+fn write_profiles(profiles: &Profiles, base_dir: &Path) {
+    profiles.write(base_dir).unwrap_or_else(|err| {
+        eprintln!("Failed to write the client profiles: {err}");
+        safe_exit(1)
+    })
+}
+
+/// Create a client profile or update the given settings of an existing one.
+pub fn profile_set(global_args: args::Global, args: args::ProfileSet) {
+    let mut profiles = read_profiles(&global_args.base_dir);
+    let is_new = !profiles.profiles.contains_key(&args.name);
+    let profile = profiles.profiles.entry(args.name.clone()).or_default();
+    if let Some(node) = args.node {
+        profile.node = Some(node);
+    }
+    if let Some(chain_id) = args.chain_id {
+        profile.chain_id = Some(chain_id);
+    }
+    if let Some(signing_key) = args.signing_key {
+        profile.signer = Some(signing_key);
+    }
+    if let Some(gas_token) = args.gas_token {
+        profile.gas_token = Some(gas_token);
+    }
+    if let Some(gas_price) = args.gas_price {
+        profile.gas_price = Some(gas_price.to_string());
+    }
+    if let Some(gas_limit) = args.gas_limit {
+        profile.gas_limit = Some(gas_limit.into());
+    }
+    write_profiles(&profiles, &global_args.base_dir);
+    if is_new {
+        println!("Created profile \"{}\".", args.name);
+    } else {
+        println!("Updated profile \"{}\".", args.name);
+    }
+}
+
+/// Remove a client profile.
+pub fn profile_remove(global_args: args::Global, args: args::ProfileName) {
+    let mut profiles = read_profiles(&global_args.base_dir);
+    if profiles.profiles.remove(&args.name).is_none() {
+        eprintln!("No profile named \"{}\" is configured.", args.name);
+        safe_exit(1)
+    }
+    if profiles.active.as_ref() == Some(&args.name) {
+        profiles.active = None;
+    }
+    write_profiles(&profiles, &global_args.base_dir);
+    println!("Removed profile \"{}\".", args.name);
+}
+
+/// Use the given client profile when none is selected explicitly.
+pub fn profile_use(global_args: args::Global, args: args::ProfileName) {
+    let mut profiles = read_profiles(&global_args.base_dir);
+    if !profiles.profiles.contains_key(&args.name) {
+        eprintln!("No profile named \"{}\" is configured.", args.name);
+        safe_exit(1)
+    }
+    profiles.active = Some(args.name.clone());
+    write_profiles(&profiles, &global_args.base_dir);
+    println!("Using profile \"{}\".", args.name);
+}
+
+/// List the client profiles, marking the one in use.
+pub fn profile_list(global_args: args::Global) {
+    let profiles = read_profiles(&global_args.base_dir);
+    if profiles.profiles.is_empty() {
+        println!("No profiles configured.");
+        return;
+    }
+    for name in profiles.profiles.keys() {
+        if profiles.active.as_ref() == Some(name) {
+            println!("* {name}");
+        } else {
+            println!("  {name}");
+        }
+    }
+}
+
+/// Show the settings of a client profile, or of the one in use.
+pub fn profile_show(global_args: args::Global, args: args::ProfileShow) {
+    let profiles = read_profiles(&global_args.base_dir);
+    let name = args
+        .name
+        .or(global_args.profile)
+        .or_else(|| env::var(ENV_VAR_PROFILE).ok())
+        .or_else(|| profiles.active.clone())
+        .unwrap_or_else(|| {
+            eprintln!("No profile is in use.");
+            safe_exit(1)
+        });
+    let Profile {
+        node,
+        chain_id,
+        signer,
+        gas_token,
+        gas_price,
+        gas_limit,
+    } = profiles.profiles.get(&name).unwrap_or_else(|| {
+        eprintln!("No profile named \"{name}\" is configured.");
+        safe_exit(1)
+    });
+    let unset = || "<unset>".to_string();
+    println!("Profile \"{name}\":");
+    println!(
+        "  Node: {}",
+        node.as_ref().map(ToString::to_string).unwrap_or_else(unset)
+    );
+    println!(
+        "  Chain ID: {}",
+        chain_id
+            .as_ref()
+            .map(ToString::to_string)
+            .unwrap_or_else(unset)
+    );
+    println!("  Signing key: {}", signer.clone().unwrap_or_else(unset));
+    println!("  Gas token: {}", gas_token.clone().unwrap_or_else(unset));
+    println!("  Gas price: {}", gas_price.clone().unwrap_or_else(unset));
+    println!(
+        "  Gas limit: {}",
+        gas_limit
+            .map(|limit| limit.to_string())
+            .unwrap_or_else(unset)
+    );
+}
+
 /// Derive and print all established addresses from the provided
 /// genesis txs toml file.
 pub fn derive_genesis_addresses(
@@ -792,7 +1059,7 @@ pub fn genesis_bond(args: args::GenesisBond) {
 
 /// Initialize genesis validator's address, consensus key and validator account
 /// key into a special "pre-genesis" wallet.
-pub fn init_genesis_validator(
+pub async fn init_genesis_validator(
     global_args: args::Global,
     args::InitGenesisValidator {
         alias,
@@ -809,6 +1076,8 @@ pub fn init_genesis_validator(
         avatar,
         tx_path,
         address,
+        sign,
+        use_device,
     }: args::InitGenesisValidator,
 ) {
     let contents = fs::read_to_string(&tx_path).unwrap_or_else(|err| {
@@ -861,6 +1130,7 @@ pub fn init_genesis_validator(
         key_scheme,
         unsafe_dont_encrypt,
         &pre_genesis_dir,
+        &mut OsRng,
     )
     .unwrap_or_else(|err| {
         eprintln!(
@@ -904,7 +1174,28 @@ pub fn init_genesis_validator(
         .unwrap()
         .append(&mut prev_txs.bond.unwrap_or_default());
 
-    let genesis_part = toml::to_string(&transactions).unwrap();
+    let genesis_part = if sign {
+        // Sign the txs with the keys of the established account from the
+        // pre-genesis wallet and with the new validator keys, so that the
+        // file can be merged by the coordinator as is
+        let (wallet, _wallet_file) =
+            load_pre_genesis_wallet_or_exit(&global_args.base_dir);
+        let wallet_lock = RwLock::new(wallet);
+        let signed = genesis::transactions::sign_txs(
+            transactions,
+            &wallet_lock,
+            Some(&validator_wallet),
+            use_device,
+        )
+        .await;
+        if !genesis::transactions::validate_validator_bundle(&signed) {
+            eprintln!("The signed genesis txs of the validator are invalid.");
+            safe_exit(1)
+        }
+        toml::to_string(&signed).unwrap()
+    } else {
+        toml::to_string(&transactions).unwrap()
+    };
     fs::write(&toml_path, genesis_part).unwrap_or_else(|err| {
         eprintln!(
             "Couldn't write pre-genesis transactions file to {toml_path_str}. \
@@ -1023,6 +1314,98 @@ pub fn validate_genesis_templates(
     }
 }
 
+/// Validate the signed genesis txs of a validator, as produced by
+/// `init-genesis-validator --sign`. Exits process if invalid.
+pub fn validate_genesis_validator(
+    _global_args: args::Global,
+    args::ValidateGenesisValidator { path }: args::ValidateGenesisValidator,
+) {
+    let transactions = genesis::templates::read_transactions(&path)
+        .unwrap_or_else(|err| {
+            eprintln!(
+                "Unable to parse the signed txs TOML from path {}. Failed \
+                 with {err}.",
+                path.to_string_lossy()
+            );
+            safe_exit(1)
+        });
+    if !genesis::transactions::validate_validator_bundle(&transactions) {
+        eprintln!("Invalid validator genesis txs.");
+        safe_exit(1)
+    }
+    println!("The validator genesis txs are valid.");
+}
+
+/// Validate genesis templates with all the checks done by `init-network` and
+/// print the chain ID and the genesis hash that would be derived from them,
+/// so that validators can verify they hold identical genesis files. Exits
+/// process if invalid.
+pub fn validate_genesis(
+    _global_args: args::Global,
+    args::ValidateGenesis {
+        templates_path,
+        chain_id_prefix,
+        genesis_time,
+        consensus_timeout_commit,
+    }: args::ValidateGenesis,
+) {
+    let aliases_are_valid = genesis::templates::lint_aliases(&templates_path);
+    let Some(templates) =
+        genesis::templates::load_and_validate(&templates_path)
+    else {
+        eprintln!("Invalid templates.");
+        safe_exit(1)
+    };
+    if !aliases_are_valid || !validate_genesis_validators(&templates) {
+        eprintln!("Invalid templates.");
+        safe_exit(1)
+    }
+
+    let genesis = genesis::chain::finalize(
+        templates,
+        chain_id_prefix,
+        genesis_time,
+        consensus_timeout_commit,
+    );
+    println!("Chain ID: {}", genesis.metadata.chain_id);
+    println!("Genesis hash: {}", genesis.genesis_hash());
+}
+
+/// Check that the validated templates contain at least one validator account
+/// and that at least one validator has positive voting power.
+fn validate_genesis_validators(
+    templates: &genesis::templates::All<genesis::templates::Validated>,
+) -> bool {
+    if !templates.transactions.has_at_least_one_validator() {
+        eprintln!("No validator genesis transaction found.");
+        return false;
+    }
+
+    let tm_votes_per_token = templates.parameters.pos_params.tm_votes_per_token;
+    if !templates
+        .transactions
+        .has_validator_with_positive_voting_power(tm_votes_per_token)
+    {
+        let min_stake = token::Amount::from_uint(
+            if tm_votes_per_token > Dec::from(1) {
+                Uint::one()
+            } else {
+                (Dec::from(1) / tm_votes_per_token).ceil().abs()
+            },
+            token::NATIVE_MAX_DECIMAL_PLACES,
+        )
+        .unwrap();
+        eprintln!(
+            "No validator with positive voting power. The minimum staked \
+             tokens amount required to run the network is {}, because there \
+             are {tm_votes_per_token} votes per NAMNAM tokens.",
+            min_stake.to_string_native(),
+        );
+        return false;
+    }
+    true
+}
+
 async fn append_signature_to_signed_toml(
     input_txs: &Path,
     wallet: &RwLock<Wallet<CliWalletUtils>>,
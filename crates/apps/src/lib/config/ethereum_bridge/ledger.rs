@@ -33,6 +33,12 @@ pub struct Config {
     /// The Ethereum JSON-RPC endpoint that the Ethereum event oracle will use
     /// to listen for events from the Ethereum bridge smart contracts
     pub oracle_rpc_endpoint: String,
+    /// More Ethereum JSON-RPC endpoints that the oracle can use. Every
+    /// request goes to the healthiest endpoint first, based on the stats of
+    /// the past requests to each endpoint, and then to the others if it
+    /// fails.
+    #[serde(default)]
+    pub oracle_rpc_fallback_endpoints: Vec<String>,
     /// The size of bounded channel between the Ethereum oracle and main
     /// ledger subprocesses. This is the number of Ethereum events that
     /// can be held in the channel. The default is 1000.
@@ -44,6 +50,7 @@ impl Default for Config {
         Self {
             mode: Mode::RemoteEndpoint,
             oracle_rpc_endpoint: DEFAULT_ORACLE_RPC_ENDPOINT.to_owned(),
+            oracle_rpc_fallback_endpoints: vec![],
             channel_buffer_size: ORACLE_CHANNEL_BUFFER_SIZE,
         }
     }
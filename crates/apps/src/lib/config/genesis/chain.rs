@@ -108,6 +108,12 @@ impl Finalized {
         })
     }
 
+    /// Hash of the finalized genesis. Validators that hold identical genesis
+    /// files will get the same hash.
+    pub fn genesis_hash(&self) -> Hash {
+        Hash::sha256(self.serialize_to_vec())
+    }
+
     /// Find the address of the configured native token
     pub fn get_native_token(&self) -> &Address {
         let alias = &self.parameters.parameters.native_token;
@@ -271,8 +277,12 @@ impl Finalized {
             fee_unshielding_gas_limit,
             fee_unshielding_descriptions_limit,
             max_block_gas,
+            max_scheduled_txs_gas,
             minimum_gas_price,
             max_tx_bytes,
+            max_tx_memo_bytes,
+            max_tx_sections,
+            max_tx_code_bytes,
             ..
         } = self.parameters.parameters.clone();
 
@@ -304,6 +314,9 @@ impl Finalized {
 
         namada::ledger::parameters::Parameters {
             max_tx_bytes,
+            max_tx_memo_bytes,
+            max_tx_sections,
+            max_tx_code_bytes,
             epoch_duration,
             max_expected_time_per_block,
             vp_whitelist,
@@ -317,6 +330,7 @@ impl Finalized {
             fee_unshielding_gas_limit,
             fee_unshielding_descriptions_limit,
             max_block_gas,
+            max_scheduled_txs_gas,
             minimum_gas_price: minimum_gas_price
                 .iter()
                 .map(|(token, amt)| {
@@ -343,8 +357,11 @@ impl Finalized {
             target_staked_ratio,
             duplicate_vote_min_slash_rate,
             light_client_attack_min_slash_rate,
+            slash_evidence_bounty_rate,
             cubic_slashing_window_length,
             validator_stake_threshold,
+            min_self_bond,
+            stake_history_len,
             liveness_window_check,
             liveness_threshold,
             rewards_gain_p,
@@ -363,8 +380,11 @@ impl Finalized {
                 target_staked_ratio,
                 duplicate_vote_min_slash_rate,
                 light_client_attack_min_slash_rate,
+                slash_evidence_bounty_rate,
                 cubic_slashing_window_length,
                 validator_stake_threshold,
+                min_self_bond,
+                stake_history_len,
                 liveness_window_check,
                 liveness_threshold,
                 rewards_gain_p,
@@ -384,6 +404,8 @@ impl Finalized {
             max_proposal_period,
             max_proposal_content_size,
             min_proposal_grace_epochs,
+            spam_proposal_fund_to_pgf,
+            tally_params,
         } = self.parameters.gov_params.clone();
         namada::governance::parameters::GovernanceParameters {
             min_proposal_fund: Amount::native_whole(min_proposal_fund),
@@ -392,6 +414,8 @@ impl Finalized {
             max_proposal_content_size,
             min_proposal_grace_epochs,
             min_proposal_voting_period,
+            spam_proposal_fund_to_pgf,
+            tally_params,
         }
     }
 
@@ -833,4 +857,40 @@ mod test {
 
         pretty_assertions::assert_eq!(finalized_0, finalized_1);
     }
+
+    /// Test that the genesis hash is deterministic and that it changes with
+    /// the genesis metadata.
+    #[test]
+    fn test_genesis_hash() {
+        // Load the localnet templates
+        let templates_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("genesis/localnet");
+        let templates = templates::load_and_validate(&templates_dir).unwrap();
+
+        let chain_id_prefix: ChainIdPrefix =
+            FromStr::from_str("test-prefix").unwrap();
+
+        let consensus_timeout_commit =
+            crate::facade::tendermint::Timeout::from_str("1s").unwrap();
+
+        let finalize_at = |genesis_time: &str| {
+            finalize(
+                templates.clone(),
+                chain_id_prefix.clone(),
+                DateTimeUtc::from_str(genesis_time).unwrap(),
+                consensus_timeout_commit,
+            )
+        };
+
+        let finalized_0 = finalize_at("2021-12-31T00:00:00Z");
+        let finalized_1 = finalize_at("2021-12-31T00:00:00Z");
+        let finalized_2 = finalize_at("2022-01-01T00:00:00Z");
+
+        assert_eq!(finalized_0.genesis_hash(), finalized_1.genesis_hash());
+        assert_ne!(finalized_0.genesis_hash(), finalized_2.genesis_hash());
+    }
 }
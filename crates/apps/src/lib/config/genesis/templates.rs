@@ -8,9 +8,11 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use namada::eth_bridge::storage::parameters::{
     Contracts, Erc20WhitelistEntry, MinimumConfirmations,
 };
+use namada::governance::parameters::ProposalTallyParameters;
 use namada::types::address::Address;
 use namada::types::chain::ProposalBytes;
 use namada::types::dec::Dec;
+use namada::types::storage::Epoch;
 use namada::types::token::{
     Amount, DenominatedAmount, Denomination, NATIVE_MAX_DECIMAL_PLACES,
 };
@@ -71,6 +73,9 @@ pub fn read_transactions(
 )]
 pub struct UndenominatedBalances {
     pub token: BTreeMap<Alias, RawTokenBalances>,
+    /// Vesting schedules locking some of the balances
+    #[serde(default)]
+    pub vesting: BTreeMap<Alias, RawTokenVesting>,
 }
 
 impl UndenominatedBalances {
@@ -82,19 +87,23 @@ impl UndenominatedBalances {
     ) -> eyre::Result<DenominatedBalances> {
         let mut balances = DenominatedBalances {
             token: BTreeMap::new(),
+            vesting: BTreeMap::new(),
         };
-        for (alias, bals) in self.token {
-            let denom = tokens
+        let get_denom = |alias: &Alias| {
+            tokens
                 .token
-                .get(&alias)
+                .get(alias)
+                .map(|config| config.denom)
                 .ok_or_else(|| {
                     eyre::eyre!(
                         "A balance of token {} was found, but this token was \
                          not found in the `tokens.toml` file",
                         alias
                     )
-                })?
-                .denom;
+                })
+        };
+        for (alias, bals) in self.token {
+            let denom = get_denom(&alias)?;
             let mut denominated_bals = BTreeMap::new();
             for (addr, bal) in bals.0.into_iter() {
                 let denominated = bal.increase_precision(denom)?;
@@ -104,6 +113,24 @@ impl UndenominatedBalances {
                 .token
                 .insert(alias, TokenBalances(denominated_bals));
         }
+        for (alias, schedules) in self.vesting {
+            let denom = get_denom(&alias)?;
+            let mut denominated_schedules = BTreeMap::new();
+            for (addr, schedule) in schedules.0.into_iter() {
+                let amount = schedule.amount.increase_precision(denom)?;
+                denominated_schedules.insert(
+                    addr,
+                    token::VestingSchedule {
+                        amount: amount.amount(),
+                        start: schedule.start,
+                        end: schedule.end,
+                    },
+                );
+            }
+            balances
+                .vesting
+                .insert(alias, TokenVesting(denominated_schedules));
+        }
         Ok(balances)
     }
 }
@@ -121,6 +148,9 @@ impl UndenominatedBalances {
 )]
 pub struct DenominatedBalances {
     pub token: BTreeMap<Alias, TokenBalances>,
+    /// Vesting schedules locking some of the balances
+    #[serde(default)]
+    pub vesting: BTreeMap<Alias, TokenVesting>,
 }
 
 /// Genesis balances for a given token
@@ -153,6 +183,52 @@ pub struct TokenBalances(
     pub BTreeMap<GenesisAddress, token::DenominatedAmount>,
 );
 
+/// A genesis vesting schedule with an amount that has yet to be denominated
+#[derive(
+    Clone,
+    Debug,
+    Deserialize,
+    Serialize,
+    BorshDeserialize,
+    BorshSerialize,
+    PartialEq,
+    Eq,
+)]
+pub struct RawVestingSchedule {
+    /// The amount of the owner's balance that is locked
+    pub amount: token::DenominatedAmount,
+    /// The epoch at which the release of the tokens starts
+    pub start: Epoch,
+    /// The epoch at which the tokens are fully vested
+    pub end: Epoch,
+}
+
+/// Genesis vesting schedules for a given token
+#[derive(
+    Clone,
+    Debug,
+    Deserialize,
+    Serialize,
+    BorshDeserialize,
+    BorshSerialize,
+    PartialEq,
+    Eq,
+)]
+pub struct RawTokenVesting(pub BTreeMap<GenesisAddress, RawVestingSchedule>);
+
+/// Genesis vesting schedules for a given token
+#[derive(
+    Clone,
+    Debug,
+    Deserialize,
+    Serialize,
+    BorshDeserialize,
+    BorshSerialize,
+    PartialEq,
+    Eq,
+)]
+pub struct TokenVesting(pub BTreeMap<GenesisAddress, token::VestingSchedule>);
+
 /// Genesis validity predicates
 #[derive(
     Clone,
@@ -244,6 +320,12 @@ pub struct ChainParams<T: TemplateValidation> {
     /// Max payload size, in bytes, for a tx decided through
     /// the consensus protocol.
     pub max_tx_bytes: u32,
+    /// Max size, in bytes, of the memo attached to a tx.
+    pub max_tx_memo_bytes: u32,
+    /// Max number of sections in a tx.
+    pub max_tx_sections: u32,
+    /// Max size, in bytes, of a WASM code embedded in a tx.
+    pub max_tx_code_bytes: u32,
     /// Name of the native token - this must one of the tokens from
     /// `tokens.toml` file
     pub native_token: Alias,
@@ -280,6 +362,8 @@ pub struct ChainParams<T: TemplateValidation> {
     pub max_signatures_per_transaction: u8,
     /// Max gas for block
     pub max_block_gas: u64,
+    /// Max total gas limit of the txs scheduled to run at an epoch
+    pub max_scheduled_txs_gas: u64,
     /// Fee unshielding gas limit
     pub fee_unshielding_gas_limit: u64,
     /// Fee unshielding descriptions limit
@@ -295,6 +379,9 @@ impl ChainParams<Unvalidated> {
     ) -> eyre::Result<ChainParams<Validated>> {
         let ChainParams {
             max_tx_bytes,
+            max_tx_memo_bytes,
+            max_tx_sections,
+            max_tx_code_bytes,
             native_token,
             min_num_of_blocks,
             max_expected_time_per_block,
@@ -305,6 +392,7 @@ impl ChainParams<Unvalidated> {
             epochs_per_year,
             max_signatures_per_transaction,
             max_block_gas,
+            max_scheduled_txs_gas,
             fee_unshielding_gas_limit,
             fee_unshielding_descriptions_limit,
             minimum_gas_price,
@@ -340,6 +428,9 @@ impl ChainParams<Unvalidated> {
 
         Ok(ChainParams {
             max_tx_bytes,
+            max_tx_memo_bytes,
+            max_tx_sections,
+            max_tx_code_bytes,
             native_token,
             min_num_of_blocks,
             max_expected_time_per_block,
@@ -350,6 +441,7 @@ impl ChainParams<Unvalidated> {
             epochs_per_year,
             max_signatures_per_transaction,
             max_block_gas,
+            max_scheduled_txs_gas,
             fee_unshielding_gas_limit,
             fee_unshielding_descriptions_limit,
             minimum_gas_price: min_gas_prices,
@@ -390,12 +482,21 @@ pub struct PosParams {
     /// Portion of a validator's stake that should be slashed on a
     /// light client attack.
     pub light_client_attack_min_slash_rate: Dec,
+    /// Portion of the slashed tokens paid to the submitter of the evidence
+    /// of a misbehavior.
+    pub slash_evidence_bounty_rate: Dec,
     /// Number of epochs above and below (separately) the current epoch to
     /// consider when doing cubic slashing
     pub cubic_slashing_window_length: u64,
     /// The minimum amount of bonded tokens that a validator needs to be in
     /// either the `consensus` or `below_capacity` validator sets
     pub validator_stake_threshold: token::Amount,
+    /// The minimum amount of tokens that a validator must have bonded to
+    /// itself to stay active
+    pub min_self_bond: token::Amount,
+    /// The minimum number of past epochs for which the validator sets and
+    /// stakes are kept in storage for historical queries
+    pub stake_history_len: u64,
     /// The length, in blocks, of the sliding window for consensus validators
     /// inactivity verification
     pub liveness_window_check: u64,
@@ -431,6 +532,13 @@ pub struct GovernanceParams {
     pub max_proposal_content_size: u64,
     /// Minimum number of epoch between end and grace epoch
     pub min_proposal_grace_epochs: u64,
+    /// Send the funds of the rejected proposals that didn't reach the quorum
+    /// to the PGF account instead of burning them
+    #[serde(default)]
+    pub spam_proposal_fund_to_pgf: bool,
+    /// The tally parameters of each type of proposal
+    #[serde(default)]
+    pub tally_params: ProposalTallyParameters,
 }
 
 #[derive(
@@ -784,6 +892,40 @@ pub fn load_and_validate(templates_dir: &Path) -> Option<All<Validated>> {
     }
 }
 
+/// Check the tokens and balances templates in the given directory for token
+/// aliases that only differ in case. Aliases are case-insensitive, so one of
+/// the entries would otherwise silently replace the other when the templates
+/// are loaded. Returns `false` when some duplicates are found.
+pub fn lint_aliases(templates_dir: &Path) -> bool {
+    let mut is_valid = true;
+    for (file_name, which_file) in [
+        (TOKENS_FILE_NAME, "Tokens"),
+        (BALANCES_FILE_NAME, "Balances"),
+    ] {
+        // Missing or malformed files are reported by `load_and_validate`
+        let Ok(contents) = read_toml::<toml::Value>(
+            &templates_dir.join(file_name),
+            which_file,
+        ) else {
+            continue;
+        };
+        let Some(tokens) = contents.get("token").and_then(|t| t.as_table())
+        else {
+            continue;
+        };
+        let mut aliases: BTreeMap<Alias, &str> = BTreeMap::new();
+        for alias in tokens.keys() {
+            if let Some(other) = aliases.insert(Alias::from(alias), alias) {
+                eprintln!(
+                    "{which_file} file is NOT valid. Token aliases                      \"{other}\" and \"{alias}\" are duplicates, because                      aliases are case-insensitive."
+                );
+                is_valid = false;
+            }
+        }
+    }
+    is_valid
+}
+
 pub fn validate_vps(vps: &ValidityPredicates) -> bool {
     let mut is_valid = true;
     vps.wasm.iter().for_each(|(name, config)| {
@@ -918,6 +1060,30 @@ pub fn validate_balances(
             is_valid = false;
         }
     });
+    balances.vesting.iter().for_each(|(token, schedules)| {
+        // Every vesting schedule must lock a part of a genesis balance
+        for (owner, schedule) in &schedules.0 {
+            let balance = balances
+                .token
+                .get(token)
+                .and_then(|balances| balances.get(owner))
+                .unwrap_or_default();
+            if schedule.amount > balance {
+                is_valid = false;
+                eprintln!(
+                    "The vesting schedule of token {token} of {owner} locks \
+                     more than its balance."
+                );
+            }
+            if schedule.start > schedule.end {
+                is_valid = false;
+                eprintln!(
+                    "The vesting schedule of token {token} of {owner} starts \
+                     after its end."
+                );
+            }
+        }
+    });
     is_valid
 }
 
@@ -963,6 +1129,32 @@ mod tests {
         );
     }
 
+    /// Test that token aliases that only differ in case are reported.
+    #[test]
+    fn test_lint_aliases() {
+        let test_dir = tempdir().unwrap();
+        let path = test_dir.path().join(TOKENS_FILE_NAME);
+        let token = |alias: &str| {
+            format!(
+                r#"
+		[token.{alias}]
+		denom = 6
+		[token.{alias}.parameters]
+		max_reward_rate = "0.1"
+		kd_gain_nom = "0.1"
+		kp_gain_nom = "0.1"
+		locked_ratio_target = "0.1"
+	    "#
+            )
+        };
+
+        fs::write(&path, [token("btc"), token("eth")].concat()).unwrap();
+        assert!(lint_aliases(test_dir.path()));
+
+        fs::write(&path, [token("btc"), token("BTC")].concat()).unwrap();
+        assert!(!lint_aliases(test_dir.path()));
+    }
+
     #[test]
     fn test_read_balances() {
         let test_dir = tempdir().unwrap();
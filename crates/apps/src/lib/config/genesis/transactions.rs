@@ -3,6 +3,7 @@
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt::Debug;
 use std::net::SocketAddr;
+use std::time::Instant;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use borsh_ext::BorshSerializeExt;
@@ -21,7 +22,7 @@ use namada::types::address::{nam, Address, EstablishedAddress};
 use namada::types::chain::ChainId;
 use namada::types::dec::Dec;
 use namada::types::key::{
-    common, ed25519, RefTo, SerializeWithBorsh, SigScheme,
+    common, ed25519, RefTo, SchemeType, SerializeWithBorsh, SigScheme,
 };
 use namada::types::string_encoding::StringEncoded;
 use namada::types::time::DateTimeUtc;
@@ -32,11 +33,14 @@ use namada_sdk::signing::{sign_tx, SigningTxData};
 use namada_sdk::tx::{TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM};
 use namada_sdk::wallet::alias::Alias;
 use namada_sdk::wallet::pre_genesis::ValidatorWallet;
-use namada_sdk::wallet::Wallet;
+use namada_sdk::wallet::{gen_secret_key, Wallet};
+use rand_core::{CryptoRng, RngCore};
 use serde::{Deserialize, Serialize};
 use tokio::sync::RwLock;
 
-use super::templates::{DenominatedBalances, Parameters, ValidityPredicates};
+use super::templates::{
+    DenominatedBalances, Parameters, ValidityPredicates, WasmVpConfig,
+};
 use crate::config::genesis::chain::DeriveEstablishedAddress;
 use crate::config::genesis::templates::{
     TemplateValidation, Unvalidated, Validated,
@@ -68,6 +72,7 @@ fn get_tx_args(use_device: bool) -> TxArgs {
         dry_run: false,
         dry_run_wrapper: false,
         dump_tx: false,
+        offline_sign: false,
         output_folder: None,
         force: false,
         broadcast_only: false,
@@ -79,7 +84,10 @@ fn get_tx_args(use_device: bool) -> TxArgs {
         fee_token: nam(),
         fee_unshield: None,
         gas_limit: Default::default(),
+        estimate_gas_limit: false,
         expiration: None,
+        expiration_height: None,
+        auto_resubmit: None,
         disposable_signing_key: false,
         chain_id: None,
         signing_keys: vec![],
@@ -88,6 +96,7 @@ fn get_tx_args(use_device: bool) -> TxArgs {
         password: None,
         memo: None,
         use_device,
+        wallet_daemon: None,
     }
 }
 
@@ -242,6 +251,89 @@ pub fn init_established_account(
     (address, txs)
 }
 
+/// The characters of the data part of bech32m encoded addresses
+const BECH32_CHARSET: &str = "qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The number of leading characters of the data part of an encoded
+/// established address that are (partially) fixed by the address kind. A
+/// vanity pattern is matched right after them.
+pub const ESTABLISHED_ADDRESS_FIXED_CHARS: usize = 2;
+
+/// A key whose genesis established account has a vanity address
+#[derive(Clone, Debug)]
+pub struct VanityAccount {
+    /// The secret key of the account
+    pub secret_key: common::SecretKey,
+    /// The derived address of the account
+    pub address: Address,
+    /// The number of keys generated to find it
+    pub iterations: u64,
+}
+
+/// Check that a vanity address pattern can be matched, i.e. that it's not
+/// empty and only uses bech32 characters.
+pub fn validate_vanity_pattern(pattern: &str) -> Result<(), String> {
+    if pattern.is_empty() {
+        return Err("The address pattern must not be empty".to_string());
+    }
+    match pattern.chars().find(|c| !BECH32_CHARSET.contains(*c)) {
+        Some(c) => Err(format!(
+            "The address pattern contains the character '{c}', which is not \
+             valid in addresses. Valid characters are \"{BECH32_CHARSET}\"."
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Check if the encoding of an established address has the vanity pattern
+/// right after the characters fixed by the address kind.
+pub fn has_vanity_pattern(address: &Address, pattern: &str) -> bool {
+    let encoded = address.encode();
+    encoded
+        .rsplit_once('1')
+        .and_then(|(_hrp, data)| data.get(ESTABLISHED_ADDRESS_FIXED_CHARS..))
+        .map(|data| data.starts_with(pattern))
+        .unwrap_or_default()
+}
+
+/// Generate keys until the genesis established account with the given VP
+/// and the key as its only public key has an address with the vanity
+/// pattern. Established addresses are derived from the account's data, so
+/// its key is the only free input. Gives up once either of the limits is
+/// reached, returning the number of keys that were tried.
+pub fn grind_established_account(
+    vp: &str,
+    scheme: SchemeType,
+    pattern: &str,
+    max_iterations: Option<u64>,
+    deadline: Option<Instant>,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<VanityAccount, u64> {
+    let mut iterations = 0_u64;
+    loop {
+        if max_iterations.map_or(false, |max| iterations >= max)
+            || deadline.map_or(false, |deadline| Instant::now() >= deadline)
+        {
+            return Err(iterations);
+        }
+        iterations += 1;
+        let secret_key = gen_secret_key(scheme, rng);
+        let address = EstablishedAccountTx {
+            vp: vp.to_string(),
+            threshold: 1,
+            public_keys: vec![StringEncoded::new(secret_key.ref_to())],
+        }
+        .derive_address();
+        if has_vanity_pattern(&address, pattern) {
+            return Ok(VanityAccount {
+                secret_key,
+                address,
+                iterations,
+            });
+        }
+    }
+}
+
 /// Create a [`BondTx`] for a genesis validator.
 pub fn init_bond(
     source: GenesisAddress,
@@ -1129,32 +1221,7 @@ fn validate_bond(
     parameters: &Parameters<Validated>,
 ) -> Option<BondTx<Validated>> {
     // Check signature
-    let mut is_valid = {
-        let source = &tx.data.source;
-        let maybe_threshold = match source {
-            GenesisAddress::EstablishedAddress(address) => {
-                // Try to find the source's PK in either established_accounts or
-                // validator_accounts
-                let established_addr = Address::Established(address.clone());
-                established_accounts.get(&established_addr).map(|(_, t)| *t)
-            }
-            GenesisAddress::PublicKey(_) => Some(1),
-        };
-        if let Some(threshold) = maybe_threshold {
-            if let Err(err) = tx.verify_sig(threshold) {
-                eprintln!("Invalid bond tx signature: {err}");
-                false
-            } else {
-                true
-            }
-        } else {
-            eprintln!(
-                "Invalid bond tx. Couldn't verify bond's signature, because \
-                 the source accounts \"{source}\" public key cannot be found."
-            );
-            false
-        }
-    };
+    let mut is_valid = validate_bond_signature(&tx, established_accounts);
 
     // Make sure the native token amount is denominated correctly
     let validated_bond = tx.data.denominate().ok()?;
@@ -1230,6 +1297,102 @@ fn validate_bond(
     is_valid.then_some(validated_bond)
 }
 
+fn validate_bond_signature(
+    tx: &SignedBondTx<Unvalidated>,
+    established_accounts: &BTreeMap<Address, (Vec<common::PublicKey>, u8)>,
+) -> bool {
+    let source = &tx.data.source;
+    let maybe_threshold = match source {
+        GenesisAddress::EstablishedAddress(address) => {
+            // Try to find the source's PK in either established_accounts or
+            // validator_accounts
+            let established_addr = Address::Established(address.clone());
+            established_accounts.get(&established_addr).map(|(_, t)| *t)
+        }
+        GenesisAddress::PublicKey(_) => Some(1),
+    };
+    if let Some(threshold) = maybe_threshold {
+        if let Err(err) = tx.verify_sig(threshold) {
+            eprintln!("Invalid bond tx signature: {err}");
+            false
+        } else {
+            true
+        }
+    } else {
+        eprintln!(
+            "Invalid bond tx. Couldn't verify bond's signature, because the \
+             source accounts \"{source}\" public key cannot be found."
+        );
+        false
+    }
+}
+
+/// Validate the signed txs of a single genesis validator, as produced by
+/// `init-genesis-validator --sign`, before they're merged with the txs of the
+/// other validators. Unlike [`validate`], this doesn't need the other genesis
+/// templates: the accounts can only use the user VP, and the bonds are only
+/// checked to be signed by their source and to target a validator of the
+/// bundle, as the genesis balances are not known.
+pub fn validate_validator_bundle(
+    transactions: &Transactions<Unvalidated>,
+) -> bool {
+    let vps = ValidityPredicates {
+        wasm: BTreeMap::from([(
+            utils::VP_USER.to_string(),
+            WasmVpConfig {
+                filename: format!("{}.wasm", utils::VP_USER),
+            },
+        )]),
+    };
+    let mut is_valid = true;
+    let mut all_used_addresses: BTreeSet<Address> = BTreeSet::default();
+    let mut established_accounts: BTreeMap<
+        Address,
+        (Vec<common::PublicKey>, u8),
+    > = BTreeMap::default();
+    let mut validator_accounts = BTreeSet::new();
+
+    for tx in transactions.established_account.iter().flatten() {
+        if !validate_established_account(
+            tx,
+            Some(&vps),
+            &mut all_used_addresses,
+            &mut established_accounts,
+        ) {
+            is_valid = false;
+        }
+    }
+    for tx in transactions.validator_account.iter().flatten() {
+        if !validate_validator_account(
+            tx,
+            Some(&vps),
+            &all_used_addresses,
+            &established_accounts,
+            &mut validator_accounts,
+        ) {
+            is_valid = false;
+        }
+    }
+    if validator_accounts.is_empty() {
+        eprintln!("No validator account tx found.");
+        is_valid = false;
+    }
+    for tx in transactions.bond.iter().flatten() {
+        if !validate_bond_signature(tx, &established_accounts) {
+            is_valid = false;
+        }
+        if !validator_accounts.contains(&tx.data.validator) {
+            eprintln!(
+                "Invalid bond tx. The target validator \"{}\" account not \
+                 found.",
+                tx.data.validator
+            );
+            is_valid = false;
+        }
+    }
+    is_valid
+}
+
 #[derive(Clone, Debug)]
 pub struct TokenBalancesForValidation {
     /// Accumulator for tokens transferred to accounts
@@ -1535,3 +1698,146 @@ impl From<&ValidatorAccountTx<SignedPk>> for UnsignedValidatorAccountTx {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rand_core::OsRng;
+
+    use super::*;
+
+    /// Test the validation of vanity address patterns
+    #[test]
+    fn test_validate_vanity_pattern() {
+        assert!(validate_vanity_pattern("n4m4d4").is_ok());
+        assert!(validate_vanity_pattern("").is_err());
+        // '1', 'b', 'i' and 'o' are not in the bech32 charset
+        for pattern in ["n1", "bad", "io", "ABC"] {
+            assert!(validate_vanity_pattern(pattern).is_err());
+        }
+    }
+
+    /// Test that the grinded key derives the vanity address of the genesis
+    /// established account it's used in
+    #[test]
+    fn test_grind_established_account() {
+        let account = grind_established_account(
+            "vp_user",
+            SchemeType::Ed25519,
+            "q",
+            Some(10_000),
+            None,
+            &mut OsRng,
+        )
+        .expect("A single character pattern should be found");
+        assert!(account.iterations >= 1);
+        assert!(has_vanity_pattern(&account.address, "q"));
+        let (address, _) = init_established_account(
+            "vp_user".to_string(),
+            vec![StringEncoded::new(account.secret_key.ref_to())],
+            1,
+        );
+        assert_eq!(address, account.address);
+
+        // the limits are respected
+        assert_eq!(
+            grind_established_account(
+                "vp_user",
+                SchemeType::Ed25519,
+                "qqqqqqqqqqqq",
+                Some(10),
+                None,
+                &mut OsRng,
+            )
+            .unwrap_err(),
+            10
+        );
+        assert!(grind_established_account(
+            "vp_user",
+            SchemeType::Ed25519,
+            "qqqqqqqqqqqq",
+            None,
+            Some(Instant::now()),
+            &mut OsRng,
+        )
+        .is_err());
+    }
+
+    /// Test that the signed txs of a genesis validator are valid on their own
+    /// and that a tampered bundle isn't
+    #[tokio::test]
+    async fn test_validate_validator_bundle() {
+        let base_dir = tempfile::tempdir().unwrap();
+        let validator_wallet = crate::wallet::pre_genesis::gen_and_store(
+            SchemeType::Ed25519,
+            true,
+            base_dir.path(),
+            &mut OsRng,
+        )
+        .unwrap();
+        let mut wallet = CliWalletUtils::new(base_dir.path().to_path_buf());
+        let account_key = gen_secret_key(SchemeType::Ed25519, &mut OsRng);
+        wallet
+            .insert_keypair(
+                "validator-key".to_string(),
+                true,
+                account_key.clone(),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let (address, mut txs) = init_established_account(
+            utils::VP_USER.to_string(),
+            vec![StringEncoded::new(account_key.ref_to())],
+            1,
+        );
+        let Address::Established(address) = address else {
+            unreachable!("Genesis accounts have established addresses")
+        };
+        let (_, validator_txs) = init_validator(
+            GenesisValidatorData {
+                address,
+                commission_rate: Dec::new(5, 2).unwrap(),
+                max_commission_rate_change: Dec::new(1, 2).unwrap(),
+                net_address: "127.0.0.1:26656".parse().unwrap(),
+                self_bond_amount: DenominatedAmount::new(
+                    token::Amount::native_whole(100),
+                    NATIVE_MAX_DECIMAL_PLACES.into(),
+                ),
+                email: "null@null.net".to_string(),
+                description: None,
+                website: None,
+                discord_handle: None,
+                avatar: None,
+            },
+            &validator_wallet,
+        );
+        txs.validator_account = validator_txs.validator_account;
+        txs.bond = validator_txs.bond;
+
+        let signed =
+            sign_txs(txs, &RwLock::new(wallet), Some(&validator_wallet), false)
+                .await;
+        assert!(validate_validator_bundle(&signed));
+
+        // A change of the signed validator account is detected
+        let mut tampered = signed.clone();
+        tampered.validator_account.as_mut().unwrap()[0]
+            .data
+            .commission_rate = Dec::new(10, 2).unwrap();
+        assert!(!validate_validator_bundle(&tampered));
+
+        // The bonds must target the validator of the bundle
+        let mut tampered = signed.clone();
+        tampered.bond.as_mut().unwrap()[0].data.validator =
+            namada::types::address::testing::established_address_1();
+        assert!(!validate_validator_bundle(&tampered));
+
+        // The bundle must contain a validator account
+        let mut tampered = signed;
+        tampered.validator_account = None;
+        tampered.bond = None;
+        assert!(!validate_validator_bundle(&tampered));
+    }
+}
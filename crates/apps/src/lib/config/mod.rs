@@ -3,11 +3,13 @@
 pub mod ethereum_bridge;
 pub mod genesis;
 pub mod global;
+pub mod profiles;
 pub mod utils;
 
 use std::collections::HashMap;
 use std::fs::{create_dir_all, File};
 use std::io::Write;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 
 use directories::ProjectDirs;
@@ -18,6 +20,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::cli;
+use crate::facade::tendermint::node::Id as TendermintNodeId;
+use crate::facade::tendermint_config::net::Address as TendermintAddress;
 use crate::facade::tendermint_config::{
     TendermintConfig, TxIndexConfig, TxIndexer,
 };
@@ -35,6 +39,15 @@ pub const FILENAME: &str = "config.toml";
 pub const COMETBFT_DIR: &str = "cometbft";
 /// Chain-specific Namada DB. Nested in chain dirs.
 pub const DB_DIR: &str = "db";
+/// The CometBFT private validator key used in read-only mode, relative to the
+/// CometBFT dir. It's generated by CometBFT and never registered as a
+/// validator key.
+pub const READ_ONLY_PRIV_VALIDATOR_KEY_FILE: &str =
+    "config/read_only_priv_validator_key.json";
+/// The CometBFT private validator state used in read-only mode, relative to
+/// the CometBFT dir.
+pub const READ_ONLY_PRIV_VALIDATOR_STATE_FILE: &str =
+    "data/read_only_priv_validator_state.json";
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Config {
@@ -93,6 +106,145 @@ pub struct Ledger {
     pub shell: Shell,
     pub cometbft: TendermintConfig,
     pub ethereum_bridge: ethereum_bridge::ledger::Config,
+    pub tracing: Tracing,
+    pub health: Health,
+    pub rosetta: Rosetta,
+    pub rpc_proxy: RpcProxy,
+    pub mempool_feed: MempoolFeed,
+    pub sentry: Sentry,
+}
+
+/// Export of the node's tracing spans
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Tracing {
+    /// The OTLP gRPC endpoint to export spans to, e.g.
+    /// `http://127.0.0.1:4317`. When not set, spans are not exported.
+    pub otlp_endpoint: Option<String>,
+    /// The service name attached to the exported spans. When not set,
+    /// defaults to `namada`.
+    pub service_name: Option<String>,
+}
+
+/// HTTP endpoints of the node's health and readiness checks
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Health {
+    /// The address to serve `/health` and `/ready` on, e.g.
+    /// `127.0.0.1:26670`. When not set, the endpoints are not served.
+    pub laddr: Option<SocketAddr>,
+    /// The number of blocks the ledger can be behind the latest block of
+    /// CometBFT to be ready. When not set, defaults to 2.
+    pub max_blocks_behind: Option<u64>,
+}
+
+/// HTTP endpoints of the Rosetta API, only served when the node is built
+/// with the `rosetta` feature
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Rosetta {
+    /// The address to serve the Rosetta API on, e.g. `127.0.0.1:8080`. When
+    /// not set, the API is not served.
+    pub laddr: Option<SocketAddr>,
+}
+
+/// A caching proxy in front of the CometBFT RPC, that serves the immutable
+/// responses, such as past blocks and tx results, from an LRU cache
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RpcProxy {
+    /// The address to serve the proxy on, e.g. `127.0.0.1:26667`. When not
+    /// set, the proxy is not served.
+    pub laddr: Option<SocketAddr>,
+    /// The maximum size of the cached responses in bytes. When not set,
+    /// defaults to 64 MiB.
+    pub cache_bytes: Option<usize>,
+}
+
+/// A websocket feed of the wrapper txs accepted into the node's mempool
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MempoolFeed {
+    /// The address to serve the feed on, e.g. `127.0.0.1:26668`. When not
+    /// set, the feed is not served.
+    pub laddr: Option<SocketAddr>,
+    /// The maximum number of txs sent to each subscriber per second. When not
+    /// set, defaults to 100.
+    pub max_txs_per_sec: Option<u32>,
+}
+
+/// The P2P setup of a sentry node deployment, in which a validator only
+/// connects to its sentry nodes, which connect to the rest of the network
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Sentry {
+    /// Peers to keep persistent connections to regardless of the peer
+    /// limits and whose addresses are never gossiped, e.g. the sentries of a
+    /// validator or the validator of a sentry. The addresses must include
+    /// the peer IDs.
+    pub private_peers: Vec<TendermintAddress>,
+    /// IDs of other peers to connect to regardless of the peer limits
+    pub unconditional_peer_ids: Vec<TendermintNodeId>,
+    /// When enabled, the peer exchange is disabled so that the node's
+    /// address isn't gossiped and it only connects to its persistent peers,
+    /// as a validator behind sentries should
+    pub hide_address: bool,
+}
+
+impl Sentry {
+    /// The matching setups of a validator, which only connects to its
+    /// sentries and hides its address, and of each of its sentries, which
+    /// keep its address private
+    pub fn for_validator(
+        validator: &TendermintAddress,
+        sentries: &[TendermintAddress],
+    ) -> (Self, Vec<Self>) {
+        let validator_setup = Self {
+            private_peers: sentries.to_vec(),
+            unconditional_peer_ids: vec![],
+            hide_address: true,
+        };
+        let sentry_setup = Self {
+            private_peers: vec![validator.clone()],
+            unconditional_peer_ids: vec![],
+            hide_address: false,
+        };
+        (validator_setup, vec![sentry_setup; sentries.len()])
+    }
+
+    /// Apply the sentry setup on top of the P2P config of CometBFT
+    pub fn apply(&self, config: &mut TendermintConfig) {
+        let p2p = &mut config.p2p;
+        for peer in &self.private_peers {
+            if !p2p.persistent_peers.contains(peer) {
+                p2p.persistent_peers.push(peer.clone());
+            }
+            match peer {
+                TendermintAddress::Tcp {
+                    peer_id: Some(peer_id),
+                    ..
+                } => {
+                    push_unique(&mut p2p.private_peer_ids, *peer_id);
+                    push_unique(&mut p2p.unconditional_peer_ids, *peer_id);
+                }
+                _ => tracing::warn!(
+                    "The private peer {peer} has no peer ID, so its address \
+                     may be gossiped"
+                ),
+            }
+        }
+        for peer_id in &self.unconditional_peer_ids {
+            push_unique(&mut p2p.unconditional_peer_ids, *peer_id);
+        }
+        if !self.private_peers.is_empty() {
+            // The sentries and their validator are commonly on a private
+            // network
+            p2p.addr_book_strict = false;
+        }
+        if self.hide_address {
+            p2p.pex = false;
+        }
+    }
+}
+
+fn push_unique<T: PartialEq>(items: &mut Vec<T>, item: T) {
+    if !items.contains(&item) {
+        items.push(item);
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -111,6 +263,49 @@ pub struct Shell {
     /// When set, will limit the how many block heights in the past can the
     /// storage be queried for reading values.
     pub storage_read_past_height_limit: Option<u64>,
+    /// When enabled, the decrypted txs at the start of a block are
+    /// pre-executed in parallel before the block is applied. A tx that read
+    /// or wrote a key written by a tx before it in the block is applied
+    /// again serially. Disabled when not set.
+    pub parallel_tx_execution: Option<bool>,
+    /// When enabled, the storage values that the VPs of a tx are likely to
+    /// read are prefetched from the DB while the tx is executing. Disabled
+    /// when not set.
+    pub prefetch_vp_reads: Option<bool>,
+    /// When enabled, the RPC queries are served concurrently from a
+    /// read-only snapshot of the last committed state, instead of being
+    /// queued with the consensus requests of the shell. Disabled when not
+    /// set.
+    pub query_snapshots: Option<bool>,
+    /// When set, caps the wall-clock time of the tx and VP wasm runs in
+    /// CheckTx, dry-runs and queries, in milliseconds. A run that exceeds it
+    /// is stopped as if it ran out of gas. The wasm runs of block execution
+    /// are never capped, as their results must not depend on the speed of
+    /// the node. Disabled when not set.
+    pub wasm_execution_cap_ms: Option<u64>,
+    /// The wasm operators, named as the variants of `wasmparser::Operator`
+    /// (e.g. `MemoryGrow`), that the wasm code submitted in txs must not
+    /// use. Such txs are rejected from the mempool. None are denied when not
+    /// set.
+    pub denied_wasm_operators: Option<Vec<String>>,
+    /// The maximum number of events retained by the in-node event log.
+    /// When not set, defaults to 50000 events.
+    pub event_log_max_events: Option<usize>,
+    /// When set, the event log only retains the events of this number of
+    /// last blocks.
+    pub event_log_max_blocks: Option<u64>,
+    /// When set, limits the approximate size of the events retained by the
+    /// event log, in bytes.
+    pub event_log_max_bytes: Option<u64>,
+    /// The version of CometBFT's mempool, either "v0" for the FIFO mempool
+    /// or "v1" for the prioritized mempool, which orders the txs by their
+    /// CheckTx priority and evicts the lowest priority txs when it's full.
+    /// When not set, defaults to "v1", so that protocol txs can't be crowded
+    /// out by wrapper txs.
+    pub cometbft_mempool_version: Option<String>,
+    /// The maximum number of txs held by CometBFT's mempool. When not set,
+    /// defaults to 4000 txs.
+    pub cometbft_mempool_size: Option<usize>,
     /// Use the [`Ledger::db_dir()`] method to read the value.
     db_dir: PathBuf,
     /// Use the [`Ledger::cometbft_dir()`] method to read the value.
@@ -143,6 +338,16 @@ impl Ledger {
                 tx_wasm_compilation_cache_bytes: None,
                 // Default corresponds to 1 hour of past blocks at 1 block/sec
                 storage_read_past_height_limit: Some(3600),
+                parallel_tx_execution: None,
+                prefetch_vp_reads: None,
+                query_snapshots: None,
+                wasm_execution_cap_ms: None,
+                denied_wasm_operators: None,
+                event_log_max_events: None,
+                event_log_max_blocks: None,
+                event_log_max_bytes: None,
+                cometbft_mempool_version: None,
+                cometbft_mempool_size: None,
                 db_dir: DB_DIR.into(),
                 cometbft_dir: COMETBFT_DIR.into(),
                 action_at_height: None,
@@ -150,6 +355,40 @@ impl Ledger {
             },
             cometbft: tendermint_config,
             ethereum_bridge: ethereum_bridge::ledger::Config::default(),
+            tracing: Tracing::default(),
+            health: Health::default(),
+            rosetta: Rosetta::default(),
+            rpc_proxy: RpcProxy::default(),
+            mempool_feed: MempoolFeed::default(),
+            sentry: Sentry::default(),
+        }
+    }
+
+    /// Configure the node to only follow the chain and serve queries. The node
+    /// runs as a full node, CometBFT uses a throwaway private validator key
+    /// instead of the validator's key, if any, and the Ethereum oracle is
+    /// turned off. When a trusted peer is given, the node only syncs the
+    /// blocks from it.
+    pub fn set_read_only(&mut self, trusted_peer: Option<TendermintAddress>) {
+        self.shell.tendermint_mode = TendermintMode::Full;
+        self.ethereum_bridge.mode = ethereum_bridge::ledger::Mode::Off;
+        self.cometbft.priv_validator_key_file =
+            Some(READ_ONLY_PRIV_VALIDATOR_KEY_FILE.into());
+        self.cometbft.priv_validator_state_file =
+            READ_ONLY_PRIV_VALIDATOR_STATE_FILE.into();
+        self.cometbft.priv_validator_laddr = None;
+        if let Some(peer) = trusted_peer {
+            let p2p = &mut self.cometbft.p2p;
+            p2p.persistent_peers = vec![peer.clone()];
+            p2p.seeds = vec![];
+            p2p.pex = false;
+            if let TendermintAddress::Tcp {
+                peer_id: Some(peer_id),
+                ..
+            } = peer
+            {
+                push_unique(&mut p2p.unconditional_peer_ids, peer_id);
+            }
         }
     }
 
@@ -649,7 +888,9 @@ dial_timeout = "3s"
 # Mempool version to use:
 #   1) "v0" - (default) FIFO mempool.
 #   2) "v1" - prioritized mempool (deprecated; will be removed in the next release).
-version = "v0"
+# Overridden by the `cometbft_mempool_version` of the ledger's shell config,
+# which defaults to the prioritized mempool.
+version = "v1"
 
 recheck = true
 broadcast = true
@@ -845,11 +1086,84 @@ namespace = "cometbft"
 
 #[cfg(test)]
 mod tests {
-    use super::DEFAULT_COMETBFT_CONFIG;
+    use std::path::PathBuf;
+
+    use namada::types::chain::ChainId;
+
+    use super::{
+        ethereum_bridge, Ledger, Sentry, TendermintMode,
+        DEFAULT_COMETBFT_CONFIG, READ_ONLY_PRIV_VALIDATOR_KEY_FILE,
+    };
+    use crate::facade::tendermint_config::net::Address as TendermintAddress;
     use crate::facade::tendermint_config::TendermintConfig;
 
     #[test]
     fn test_default_cometbft_config() {
         assert!(TendermintConfig::parse_toml(DEFAULT_COMETBFT_CONFIG).is_ok());
     }
+
+    #[test]
+    fn test_read_only_setup() {
+        let peer: TendermintAddress =
+            "tcp://0000000000000000000000000000000000000001@10.0.0.1:26656"
+                .parse()
+                .unwrap();
+        let mut config = Ledger::new(
+            "base",
+            ChainId("chain".to_owned()),
+            TendermintMode::Validator,
+        );
+        config.ethereum_bridge.mode =
+            ethereum_bridge::ledger::Mode::RemoteEndpoint;
+        config.set_read_only(Some(peer.clone()));
+
+        assert_eq!(config.shell.tendermint_mode, TendermintMode::Full);
+        assert!(matches!(
+            config.ethereum_bridge.mode,
+            ethereum_bridge::ledger::Mode::Off
+        ));
+        assert_eq!(
+            config.cometbft.priv_validator_key_file,
+            Some(PathBuf::from(READ_ONLY_PRIV_VALIDATOR_KEY_FILE))
+        );
+        assert_eq!(config.cometbft.p2p.persistent_peers, vec![peer]);
+        assert_eq!(config.cometbft.p2p.unconditional_peer_ids.len(), 1);
+        assert!(!config.cometbft.p2p.pex);
+    }
+
+    #[test]
+    fn test_sentry_setup() {
+        let validator: TendermintAddress =
+            "tcp://0000000000000000000000000000000000000001@10.0.0.1:26656"
+                .parse()
+                .unwrap();
+        let sentries: Vec<TendermintAddress> = [
+            "tcp://0000000000000000000000000000000000000002@10.0.0.2:26656",
+            "tcp://0000000000000000000000000000000000000003@10.0.0.3:26656",
+        ]
+        .iter()
+        .map(|addr| addr.parse().unwrap())
+        .collect();
+        let (validator_setup, sentry_setups) =
+            Sentry::for_validator(&validator, &sentries);
+        assert_eq!(sentry_setups.len(), 2);
+
+        let mut config =
+            TendermintConfig::parse_toml(DEFAULT_COMETBFT_CONFIG).unwrap();
+        validator_setup.apply(&mut config);
+        // Applying the setup again doesn't duplicate the peers
+        validator_setup.apply(&mut config);
+        assert_eq!(config.p2p.persistent_peers, sentries);
+        assert_eq!(config.p2p.private_peer_ids.len(), 2);
+        assert_eq!(config.p2p.unconditional_peer_ids.len(), 2);
+        assert!(!config.p2p.pex);
+
+        let mut config =
+            TendermintConfig::parse_toml(DEFAULT_COMETBFT_CONFIG).unwrap();
+        sentry_setups[0].apply(&mut config);
+        assert_eq!(config.p2p.persistent_peers, vec![validator]);
+        assert_eq!(config.p2p.private_peer_ids.len(), 1);
+        assert!(config.p2p.pex);
+        assert!(!config.p2p.addr_book_strict);
+    }
 }
@@ -0,0 +1,183 @@
+//! Client profiles
+//!
+//! A profile is a named set of defaults for the client commands (the node
+//! address, the chain ID, the default signer and the fee settings), so that
+//! they don't have to be passed on every command when working with multiple
+//! networks. The profiles are stored in the base directory, together with the
+//! name of the profile that is used when none is selected explicitly.
+
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use namada::types::chain::ChainId;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::facade::tendermint_config::net::Address as TendermintAddress;
+
+pub const FILENAME: &str = "profiles.toml";
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Error while reading profiles: {0}")]
+    ReadError(config::ConfigError),
+    #[error("Error while deserializing profiles: {0}")]
+    DeserializationError(config::ConfigError),
+    #[error("Error while writing profiles: {0}")]
+    WriteError(std::io::Error),
+    #[error("Error while serializing to toml: {0}")]
+    TomlError(toml::ser::Error),
+    #[error("No profile named \"{0}\" is configured")]
+    UnknownProfile(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Defaults of the client commands. Any argument given explicitly on the
+/// command line takes precedence over the profile.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Address of the node to connect to
+    pub node: Option<TendermintAddress>,
+    /// The chain ID
+    pub chain_id: Option<ChainId>,
+    /// Alias or public key of the default signing key of transactions
+    pub signer: Option<String>,
+    /// The token for paying the gas
+    pub gas_token: Option<String>,
+    /// The amount paid per gas unit
+    pub gas_price: Option<String>,
+    /// The gas limit of transactions
+    pub gas_limit: Option<u64>,
+}
+
+/// The profiles stored in the base directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Profiles {
+    /// The profile used when none is selected with `--profile` or the
+    /// `NAMADA_PROFILE` env var
+    pub active: Option<String>,
+    /// The profiles by name
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl Profiles {
+    /// Try to read the profiles from a file. Returns no profiles if the file
+    /// doesn't exist.
+    pub fn read(base_dir: impl AsRef<Path>) -> Result<Self> {
+        let file_path = Self::file_path(base_dir.as_ref());
+        let file_name = file_path.to_str().expect("Expected UTF-8 file path");
+        let mut config = config::Config::new();
+        if file_path.exists() {
+            config
+                .merge(config::File::with_name(file_name))
+                .map_err(Error::ReadError)?;
+        }
+        config.try_into().map_err(Error::DeserializationError)
+    }
+
+    /// Write the profiles to a file.
+    pub fn write(&self, base_dir: impl AsRef<Path>) -> Result<()> {
+        let file_path = Self::file_path(base_dir.as_ref());
+        let file_dir = file_path.parent().unwrap();
+        create_dir_all(file_dir).map_err(Error::WriteError)?;
+        let mut file = File::create(file_path).map_err(Error::WriteError)?;
+        let toml = toml::ser::to_string(&self).map_err(|err| {
+            if let toml::ser::Error::ValueAfterTable = err {
+                tracing::error!("{}", super::VALUE_AFTER_TABLE_ERROR_MSG);
+            }
+            Error::TomlError(err)
+        })?;
+        file.write_all(toml.as_bytes()).map_err(Error::WriteError)
+    }
+
+    /// Get the file path to the profiles
+    pub fn file_path(base_dir: impl AsRef<Path>) -> PathBuf {
+        base_dir.as_ref().join(FILENAME)
+    }
+
+    /// Find the profile to use: the one with the given name, if any, or else
+    /// the active one. Errors if the given name isn't configured.
+    pub fn select(&self, name: Option<&str>) -> Result<Option<&Profile>> {
+        match name {
+            Some(name) => self
+                .profiles
+                .get(name)
+                .map(Some)
+                .ok_or_else(|| Error::UnknownProfile(name.to_string())),
+            None => Ok(self
+                .active
+                .as_ref()
+                .and_then(|name| self.profiles.get(name))),
+        }
+    }
+}
+
+/// The profile selected for this process
+static SELECTED: OnceLock<Profile> = OnceLock::new();
+
+/// Select the profile whose defaults are used by the commands of this
+/// process. Only the first selection has an effect.
+pub fn select(profile: Profile) {
+    let _ = SELECTED.set(profile);
+}
+
+/// The profile selected for this process, if any
+pub fn selected() -> Option<&'static Profile> {
+    SELECTED.get()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_profiles_roundtrip() {
+        let base_dir = tempfile::tempdir().unwrap();
+        let profile = Profile {
+            node: Some(
+                TendermintAddress::from_str("tcp://127.0.0.1:26657").unwrap(),
+            ),
+            chain_id: Some(ChainId("test-chain.abc".to_string())),
+            signer: Some("albert-key".to_string()),
+            gas_token: Some("NAM".to_string()),
+            gas_price: Some("0.01".to_string()),
+            gas_limit: Some(30_000),
+        };
+        let mut profiles = Profiles::default();
+        profiles
+            .profiles
+            .insert("local".to_string(), profile.clone());
+        profiles
+            .profiles
+            .insert("empty".to_string(), Profile::default());
+        profiles.active = Some("local".to_string());
+        profiles.write(base_dir.path()).unwrap();
+
+        let read = Profiles::read(base_dir.path()).unwrap();
+        assert_eq!(read.active.as_deref(), Some("local"));
+        assert_eq!(read.select(None).unwrap(), Some(&profile));
+        assert_eq!(
+            read.select(Some("empty")).unwrap(),
+            Some(&Profile::default())
+        );
+        assert!(matches!(
+            read.select(Some("missing")),
+            Err(Error::UnknownProfile(_))
+        ));
+    }
+
+    #[test]
+    fn test_no_profiles_file() {
+        let base_dir = tempfile::tempdir().unwrap();
+        let profiles = Profiles::read(base_dir.path()).unwrap();
+        assert!(profiles.profiles.is_empty());
+        assert_eq!(profiles.select(None).unwrap(), None);
+    }
+}
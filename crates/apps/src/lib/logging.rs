@@ -1,12 +1,23 @@
 //! A module for anything related to logging
-use std::env;
+use std::collections::VecDeque;
+use std::sync::{Mutex, PoisonError};
+use std::{env, fmt};
 
 use color_eyre::eyre::Result;
 use eyre::WrapErr;
+use namada::types::time::DateTimeUtc;
+use once_cell::sync::Lazy;
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::trace::Tracer;
+use opentelemetry_sdk::Resource;
+use tracing::field::{Field, Visit};
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_log::LogTracer;
 use tracing_subscriber::filter::{Directive, EnvFilter};
 use tracing_subscriber::fmt::Subscriber;
+use tracing_subscriber::layer::{self, Layer, SubscriberExt};
+
+use crate::config;
 
 pub const ENV_KEY: &str = "NAMADA_LOG";
 
@@ -21,6 +32,14 @@ const ROLLING_ENV_KEY: &str = "NAMADA_LOG_ROLLING";
 
 const LOG_FILE_NAME_PREFIX: &str = "namada.log";
 
+const DEFAULT_SERVICE_NAME: &str = "namada";
+
+// Number of the most recent log events kept in memory for crash reports
+const RECENT_EVENTS_CAPACITY: usize = 200;
+
+static RECENT_EVENTS: Lazy<Mutex<VecDeque<String>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(RECENT_EVENTS_CAPACITY)));
+
 #[derive(Clone, Debug)]
 enum Fmt {
     Full,
@@ -48,6 +67,92 @@ pub fn init_from_env_or(
     Ok(guard)
 }
 
+/// Like [`init_from_env_or`], but additionally exports tracing spans via OTLP
+/// when it is enabled in the given config.
+///
+/// Important: The returned guards must be assigned to a binding that is not
+/// _, as _ will result in them being dropped immediately.
+pub fn init_from_env_or_with_tracing(
+    default: impl Into<Directive>,
+    config: Option<&config::Tracing>,
+) -> Result<Guards> {
+    let filter = filter_from_env_or(default);
+    let otlp = match config {
+        Some(config::Tracing {
+            otlp_endpoint: Some(endpoint),
+            service_name,
+        }) => Some(OtlpExporter::new(
+            endpoint,
+            service_name.as_deref().unwrap_or(DEFAULT_SERVICE_NAME),
+        )?),
+        _ => None,
+    };
+    let log = set_subscriber_with_tracer(
+        filter,
+        otlp.as_ref().map(|otlp| otlp.tracer.clone()),
+    )?;
+    init_log_tracer()?;
+    Ok(Guards {
+        _log: log,
+        _otlp: otlp,
+    })
+}
+
+/// Guards that handle flushing of remaining logs and spans on termination.
+pub struct Guards {
+    _log: Option<WorkerGuard>,
+    _otlp: Option<OtlpExporter>,
+}
+
+/// Exports spans to an OTLP endpoint in batches from a dedicated runtime.
+struct OtlpExporter {
+    tracer: Tracer,
+    // Must be kept alive until the exporter is shut down
+    _runtime: tokio::runtime::Runtime,
+}
+
+impl OtlpExporter {
+    fn new(endpoint: &str, service_name: &str) -> Result<Self> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("otlp-exporter")
+            .enable_all()
+            .build()
+            .wrap_err("Failed to start the OTLP exporter runtime")?;
+        let tracer = {
+            let _guard = runtime.enter();
+            opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .tonic()
+                        .with_endpoint(endpoint),
+                )
+                .with_trace_config(
+                    opentelemetry_sdk::trace::config().with_resource(
+                        Resource::new([KeyValue::new(
+                            "service.name",
+                            service_name.to_owned(),
+                        )]),
+                    ),
+                )
+                .install_batch(opentelemetry_sdk::runtime::Tokio)
+                .wrap_err("Failed to install the OTLP span exporter")?
+        };
+        Ok(Self {
+            tracer,
+            _runtime: runtime,
+        })
+    }
+}
+
+impl Drop for OtlpExporter {
+    fn drop(&mut self) {
+        // Export the remaining spans
+        opentelemetry::global::shutdown_tracer_provider();
+    }
+}
+
 pub fn filter_from_env_or(default: impl Into<Directive>) -> EnvFilter {
     env::var(ENV_KEY)
         .map(EnvFilter::new)
@@ -59,6 +164,15 @@ pub fn init_log_tracer() -> Result<()> {
 }
 
 pub fn set_subscriber(filter: EnvFilter) -> Result<Option<WorkerGuard>> {
+    set_subscriber_with_tracer(filter, None)
+}
+
+/// Set the global subscriber. When a tracer is given, the spans are also
+/// exported with it.
+fn set_subscriber_with_tracer(
+    filter: EnvFilter,
+    tracer: Option<Tracer>,
+) -> Result<Option<WorkerGuard>> {
     let with_color = if let Ok(val) = env::var(COLOR_ENV_KEY) {
         val.to_ascii_lowercase() != "false"
     } else {
@@ -84,7 +198,13 @@ pub fn set_subscriber(filter: EnvFilter) -> Result<Option<WorkerGuard>> {
     macro_rules! finish {
         ($($builder:tt)*) => {
             {
-                let my_collector = $($builder)*.finish();
+                let otel_layer = tracer.clone().map(|tracer| {
+                    tracing_opentelemetry::layer().with_tracer(tracer)
+                });
+                let my_collector = $($builder)*
+                    .finish()
+                    .with(otel_layer)
+                    .with(RecentEvents);
                 tracing::subscriber::set_global_default(my_collector)
                     .wrap_err("Failed to set log subscriber")
             }
@@ -123,6 +243,59 @@ pub fn set_subscriber(filter: EnvFilter) -> Result<Option<WorkerGuard>> {
     }
 }
 
+/// Returns the most recent log events that passed the log filter, from the
+/// oldest one.
+pub fn recent_events() -> Vec<String> {
+    RECENT_EVENTS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .cloned()
+        .collect()
+}
+
+/// A layer that keeps the most recent log events in memory.
+struct RecentEvents;
+
+impl<S: tracing::Subscriber> Layer<S> for RecentEvents {
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _ctx: layer::Context<'_, S>,
+    ) {
+        let metadata = event.metadata();
+        let mut line = format!(
+            "{} {} {}:",
+            DateTimeUtc::now().to_rfc3339(),
+            metadata.level(),
+            metadata.target()
+        );
+        event.record(&mut FieldsVisitor(&mut line));
+
+        let mut events =
+            RECENT_EVENTS.lock().unwrap_or_else(PoisonError::into_inner);
+        if events.len() == RECENT_EVENTS_CAPACITY {
+            events.pop_front();
+        }
+        events.push_back(line);
+    }
+}
+
+/// Appends the fields of an event to a line.
+struct FieldsVisitor<'a>(&'a mut String);
+
+impl Visit for FieldsVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        use std::fmt::Write;
+
+        let _ = if field.name() == "message" {
+            write!(self.0, " {value:?}")
+        } else {
+            write!(self.0, " {}={value:?}", field.name())
+        };
+    }
+}
+
 enum RollingFreq {
     Never,
     Minutely,
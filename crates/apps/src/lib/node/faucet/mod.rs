@@ -0,0 +1,323 @@
+//! A faucet dispensing testnet tokens over HTTP.
+//!
+//! The faucet serves two endpoints:
+//!
+//! - `GET /challenge` returns a fresh proof-of-work challenge and its
+//!   difficulty, see [`pow`].
+//! - `POST /transfer` takes a JSON body with the `target` address, the hex
+//!   encoded `challenge` and the `nonce` solving it. If the solution is valid
+//!   and the target address hasn't exhausted its quota (see [`quota`]), a
+//!   transfer from the faucet's account is submitted and the hash of the
+//!   applied tx is returned.
+//!
+//! The transfers are submitted one at a time by a single task that owns the
+//! faucet's wallet.
+
+pub mod pow;
+pub mod quota;
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use data_encoding::HEXLOWER;
+use eyre::{eyre, Context, Result};
+use namada::token;
+use namada::types::address::Address;
+use namada::types::masp::{TransferSource, TransferTarget};
+use namada::types::time::DateTimeUtc;
+use namada_sdk::args::InputAmount;
+use namada_sdk::{error, rpc, signing, Namada};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot};
+use warp::http::StatusCode;
+use warp::reply::{Json, WithStatus};
+use warp::Filter;
+
+use self::pow::{Challenge, Challenges};
+use self::quota::Quotas;
+use crate::client::tx::submit_reveal_aux;
+
+/// The time after which an issued challenge is no longer accepted
+const CHALLENGE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// The max number of transfers waiting to be submitted
+const TRANSFERS_QUEUE_SIZE: usize = 100;
+
+/// The max size of the body of a transfer request
+const MAX_REQUEST_BYTES: u64 = 4 * 1024;
+
+/// The configuration of a faucet
+#[derive(Debug, Clone)]
+pub struct FaucetConfig {
+    /// The address to serve the HTTP endpoints on
+    pub listen_address: SocketAddr,
+    /// The account that the tokens are transferred from
+    pub source: Address,
+    /// The dispensed token
+    pub token: Address,
+    /// The amount transferred per request
+    pub amount: token::DenominatedAmount,
+    /// The max amount transferred to an address per quota period
+    pub quota: token::DenominatedAmount,
+    /// The duration of a quota period
+    pub quota_period: Duration,
+    /// The number of leading zero bits required in the proof-of-work
+    pub difficulty: u8,
+    /// The directory with the faucet's storage
+    pub data_dir: PathBuf,
+}
+
+#[derive(Serialize)]
+struct ChallengeResponse {
+    challenge: String,
+    difficulty: u8,
+}
+
+#[derive(Deserialize)]
+struct TransferRequest {
+    target: Address,
+    challenge: String,
+    nonce: u64,
+}
+
+#[derive(Serialize)]
+struct TransferResponse {
+    tx_hash: String,
+}
+
+#[derive(Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// A transfer waiting to be submitted, with the channel to reply the tx hash
+/// or the error to
+struct PendingTransfer {
+    target: Address,
+    reply: oneshot::Sender<std::result::Result<String, String>>,
+}
+
+/// The state shared by the HTTP handlers
+struct State {
+    difficulty: u8,
+    amount: token::Amount,
+    challenges: Mutex<Challenges>,
+    quotas: Mutex<Quotas>,
+    transfers: mpsc::Sender<PendingTransfer>,
+}
+
+/// Run the faucet until the process is terminated.
+pub fn run<N: Namada>(namada: N, config: FaucetConfig) -> Result<()> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .wrap_err("Failed to start the faucet's runtime")?;
+    runtime.block_on(serve(&namada, config))
+}
+
+async fn serve<N: Namada>(namada: &N, config: FaucetConfig) -> Result<()> {
+    let validate = |amount| {
+        rpc::validate_amount(
+            namada,
+            InputAmount::Unvalidated(amount),
+            &config.token,
+            false,
+        )
+    };
+    let amount = validate(config.amount)
+        .await
+        .wrap_err("Invalid faucet amount")?;
+    let quota = validate(config.quota)
+        .await
+        .wrap_err("Invalid faucet quota")?;
+    if quota.amount() < amount.amount() {
+        return Err(eyre!(
+            "The quota {quota} is less than the amount per request {amount}"
+        ));
+    }
+
+    std::fs::create_dir_all(&config.data_dir)
+        .wrap_err("Failed to create the faucet's directory")?;
+    let quotas = Quotas::open(
+        config.data_dir.join("quotas.json"),
+        quota.amount(),
+        config.quota_period,
+    )?;
+
+    submit_reveal_aux(namada, namada.tx_builder(), &config.source)
+        .await
+        .wrap_err("Failed to reveal the public key of the faucet account")?;
+
+    let (transfers, receiver) = mpsc::channel(TRANSFERS_QUEUE_SIZE);
+    let state = Arc::new(State {
+        difficulty: config.difficulty,
+        amount: amount.amount(),
+        challenges: Mutex::new(Challenges::new(CHALLENGE_TTL)),
+        quotas: Mutex::new(quotas),
+        transfers,
+    });
+
+    let listen_address = config.listen_address;
+    tracing::info!(?listen_address, "Starting the faucet");
+    tokio::spawn(warp::serve(routes(state)).run(listen_address));
+
+    process_transfers(namada, &config, amount, receiver).await;
+    Ok(())
+}
+
+fn routes(
+    state: Arc<State>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_state = warp::any().map(move || state.clone());
+    let challenge = warp::get()
+        .and(warp::path("challenge"))
+        .and(warp::path::end())
+        .and(with_state.clone())
+        .map(|state: Arc<State>| {
+            let challenge = state.challenges.lock().unwrap().issue();
+            warp::reply::json(&ChallengeResponse {
+                challenge: HEXLOWER.encode(&challenge),
+                difficulty: state.difficulty,
+            })
+        });
+    let transfer = warp::post()
+        .and(warp::path("transfer"))
+        .and(warp::path::end())
+        .and(warp::body::content_length_limit(MAX_REQUEST_BYTES))
+        .and(warp::body::json())
+        .and(with_state)
+        .then(|request: TransferRequest, state: Arc<State>| async move {
+            match request_transfer(request, &state).await {
+                Ok(tx_hash) => reply(
+                    warp::reply::json(&TransferResponse { tx_hash }),
+                    StatusCode::OK,
+                ),
+                Err((status, error)) => {
+                    reply(warp::reply::json(&ErrorResponse { error }), status)
+                }
+            }
+        });
+    challenge.or(transfer)
+}
+
+fn reply(json: Json, status: StatusCode) -> WithStatus<Json> {
+    warp::reply::with_status(json, status)
+}
+
+/// Check the proof-of-work and the quota of a transfer request and queue the
+/// transfer. Returns the hash of the applied tx.
+async fn request_transfer(
+    request: TransferRequest,
+    state: &State,
+) -> std::result::Result<String, (StatusCode, String)> {
+    let challenge: Challenge = HEXLOWER
+        .decode(request.challenge.to_lowercase().as_bytes())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| {
+            (StatusCode::BAD_REQUEST, "Invalid challenge".to_string())
+        })?;
+    if !pow::is_solution(&challenge, request.nonce, state.difficulty) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "The nonce doesn't solve the challenge".to_string(),
+        ));
+    }
+    let issued = state.challenges.lock().unwrap().consume(&challenge);
+    if !issued {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "Unknown or expired challenge".to_string(),
+        ));
+    }
+
+    // Reserve the amount in the quota before the transfer is submitted, so
+    // that concurrent requests can't exceed it
+    let withdrawn = state.quotas.lock().unwrap().withdraw(
+        &request.target,
+        state.amount,
+        DateTimeUtc::now(),
+    );
+    withdrawn.map_err(|err| match err {
+        quota::Error::Exceeded { .. } => {
+            (StatusCode::TOO_MANY_REQUESTS, err.to_string())
+        }
+        _ => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    })?;
+
+    let (reply, result) = oneshot::channel();
+    let pending = PendingTransfer {
+        target: request.target.clone(),
+        reply,
+    };
+    let result = match state.transfers.send(pending).await {
+        Ok(()) => result
+            .await
+            .unwrap_or_else(|_| Err("The faucet is shutting down".to_string())),
+        Err(_) => Err("The faucet is shutting down".to_string()),
+    };
+    if result.is_err() {
+        let refunded = state
+            .quotas
+            .lock()
+            .unwrap()
+            .refund(&request.target, state.amount);
+        if let Err(err) = refunded {
+            tracing::error!("Failed to refund a faucet withdrawal: {err}");
+        }
+    }
+    result.map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err))
+}
+
+/// Submit the queued transfers one by one.
+async fn process_transfers<N: Namada>(
+    namada: &N,
+    config: &FaucetConfig,
+    amount: token::DenominatedAmount,
+    mut receiver: mpsc::Receiver<PendingTransfer>,
+) {
+    while let Some(PendingTransfer { target, reply }) = receiver.recv().await {
+        let result = submit_transfer(namada, config, amount, target.clone())
+            .await
+            .map_err(|err| err.to_string());
+        match &result {
+            Ok(tx_hash) => {
+                tracing::info!(%target, %tx_hash, "Faucet transfer applied")
+            }
+            Err(err) => {
+                tracing::warn!(%target, "Faucet transfer failed: {err}")
+            }
+        }
+        // The requester may have disconnected in the meantime
+        let _ = reply.send(result);
+    }
+}
+
+async fn submit_transfer<N: Namada>(
+    namada: &N,
+    config: &FaucetConfig,
+    amount: token::DenominatedAmount,
+    target: Address,
+) -> std::result::Result<String, error::Error> {
+    let mut args = namada.new_transfer(
+        TransferSource::Address(config.source.clone()),
+        TransferTarget::Address(target),
+        config.token.clone(),
+        InputAmount::Validated(amount),
+    );
+    let (mut tx, signing_data, _) = args.build(namada).await?;
+    namada
+        .sign(&mut tx, &args.tx, signing_data, signing::default_sign, ())
+        .await?;
+    let tx_hash = tx.raw_header_hash().to_string();
+    let response = namada.submit(tx, &args.tx).await?;
+    if response.is_applied_and_valid().is_some() {
+        Ok(tx_hash)
+    } else {
+        Err(error::Error::Other(format!(
+            "The transfer {tx_hash} was not applied"
+        )))
+    }
+}
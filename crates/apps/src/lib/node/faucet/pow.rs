@@ -0,0 +1,120 @@
+//! Proof-of-work challenges of the faucet.
+//!
+//! A client first requests a random challenge and then has to find a nonce
+//! such that the SHA-256 hash of the challenge followed by the little-endian
+//! bytes of the nonce starts with at least `difficulty` zero bits. Each
+//! challenge can be used at most once and expires after a while.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+/// The size of a challenge in bytes
+pub const CHALLENGE_LEN: usize = 32;
+
+/// A random challenge issued to a client
+pub type Challenge = [u8; CHALLENGE_LEN];
+
+/// Check if the given nonce solves the challenge with the given difficulty.
+pub fn is_solution(challenge: &Challenge, nonce: u64, difficulty: u8) -> bool {
+    let hash = Sha256::new()
+        .chain(challenge)
+        .chain(nonce.to_le_bytes())
+        .finalize();
+    leading_zero_bits(&hash) >= u32::from(difficulty)
+}
+
+/// Find the smallest nonce that solves the challenge. This is what clients
+/// have to compute before requesting a transfer.
+pub fn solve(challenge: &Challenge, difficulty: u8) -> u64 {
+    (0..=u64::MAX)
+        .find(|nonce| is_solution(challenge, *nonce, difficulty))
+        .expect("A solution must exist for any difficulty below 256")
+}
+
+fn leading_zero_bits(bytes: &[u8]) -> u32 {
+    let mut zeros = 0;
+    for byte in bytes {
+        zeros += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    zeros
+}
+
+/// The challenges issued to clients that haven't been used yet
+#[derive(Debug)]
+pub struct Challenges {
+    /// The time after which an issued challenge is no longer accepted
+    ttl: Duration,
+    /// The time of issuance of each pending challenge
+    issued: HashMap<Challenge, Instant>,
+}
+
+impl Challenges {
+    /// Create an empty set of challenges that expire after `ttl`.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            issued: HashMap::new(),
+        }
+    }
+
+    /// Issue a new random challenge. The expired challenges are dropped.
+    pub fn issue(&mut self) -> Challenge {
+        let now = Instant::now();
+        let ttl = self.ttl;
+        self.issued
+            .retain(|_, issued_at| now.duration_since(*issued_at) < ttl);
+        let mut challenge = [0; CHALLENGE_LEN];
+        rand::thread_rng().fill_bytes(&mut challenge);
+        self.issued.insert(challenge, now);
+        challenge
+    }
+
+    /// Remove the given challenge. Returns `true` iff it was issued and it
+    /// hasn't expired yet.
+    pub fn consume(&mut self, challenge: &Challenge) -> bool {
+        self.issued
+            .remove(challenge)
+            .map(|issued_at| issued_at.elapsed() < self.ttl)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test_pow {
+    use super::*;
+
+    #[test]
+    fn test_solve_challenge() {
+        let mut challenges = Challenges::new(Duration::from_secs(60));
+        let challenge = challenges.issue();
+        let difficulty = 8;
+        let nonce = solve(&challenge, difficulty);
+        assert!(is_solution(&challenge, nonce, difficulty));
+
+        // A challenge can only be used once
+        assert!(challenges.consume(&challenge));
+        assert!(!challenges.consume(&challenge));
+        // Unknown challenges are rejected
+        assert!(!challenges.consume(&[0; CHALLENGE_LEN]));
+    }
+
+    #[test]
+    fn test_leading_zero_bits() {
+        assert_eq!(leading_zero_bits(&[0xff]), 0);
+        assert_eq!(leading_zero_bits(&[0, 0x10, 0]), 11);
+        assert_eq!(leading_zero_bits(&[0, 0]), 16);
+    }
+
+    #[test]
+    fn test_expired_challenge() {
+        let mut challenges = Challenges::new(Duration::ZERO);
+        let challenge = challenges.issue();
+        assert!(!challenges.consume(&challenge));
+    }
+}
@@ -0,0 +1,189 @@
+//! Per-address withdrawal quotas of the faucet.
+//!
+//! Every address may withdraw up to a limit per period. The withdrawals are
+//! persisted in a JSON file in the faucet's directory, so that restarting the
+//! faucet doesn't reset them.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use namada::token;
+use namada::types::address::Address;
+use namada::types::time::DateTimeUtc;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(
+        "The quota of {limit} per period is exceeded, the address has already \
+         withdrawn {withdrawn}"
+    )]
+    Exceeded {
+        limit: token::Amount,
+        withdrawn: token::Amount,
+    },
+    #[error("Failed to access the quotas file: {0}")]
+    Io(std::io::Error),
+    #[error("Failed to decode the quotas file: {0}")]
+    Decoding(serde_json::Error),
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// The withdrawals of an address in its current period
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Withdrawals {
+    /// The start of the period
+    period_start: DateTimeUtc,
+    /// The total amount withdrawn since the start of the period
+    amount: token::Amount,
+}
+
+/// The quotas of all the addresses that withdrew from the faucet
+#[derive(Debug)]
+pub struct Quotas {
+    /// The file where the withdrawals are persisted
+    path: PathBuf,
+    /// The max amount that can be withdrawn per period
+    limit: token::Amount,
+    /// The duration of a period
+    period: Duration,
+    withdrawals: HashMap<Address, Withdrawals>,
+}
+
+impl Quotas {
+    /// Load the quotas from the given file, if it exists.
+    pub fn open(
+        path: PathBuf,
+        limit: token::Amount,
+        period: Duration,
+    ) -> Result<Self> {
+        let withdrawals = if path.exists() {
+            let bytes = fs::read(&path).map_err(Error::Io)?;
+            serde_json::from_slice(&bytes).map_err(Error::Decoding)?
+        } else {
+            HashMap::new()
+        };
+        Ok(Self {
+            path,
+            limit,
+            period,
+            withdrawals,
+        })
+    }
+
+    /// Record a withdrawal of `amount` by `owner` at time `now`, if it fits
+    /// in the remaining quota of the address.
+    pub fn withdraw(
+        &mut self,
+        owner: &Address,
+        amount: token::Amount,
+        now: DateTimeUtc,
+    ) -> Result<()> {
+        let period = self.period;
+        let withdrawals =
+            self.withdrawals.entry(owner.clone()).or_insert_with(|| {
+                Withdrawals {
+                    period_start: now,
+                    amount: token::Amount::zero(),
+                }
+            });
+        let elapsed = now
+            .0
+            .signed_duration_since(withdrawals.period_start.0)
+            .to_std()
+            .unwrap_or_default();
+        if elapsed >= period {
+            *withdrawals = Withdrawals {
+                period_start: now,
+                amount: token::Amount::zero(),
+            };
+        }
+        let withdrawn = withdrawals.amount;
+        match withdrawn.checked_add(amount) {
+            Some(total) if total <= self.limit => {
+                withdrawals.amount = total;
+            }
+            _ => {
+                return Err(Error::Exceeded {
+                    limit: self.limit,
+                    withdrawn,
+                });
+            }
+        }
+        self.save()
+    }
+
+    /// Revert a withdrawal recorded with [`Quotas::withdraw`] whose transfer
+    /// failed.
+    pub fn refund(
+        &mut self,
+        owner: &Address,
+        amount: token::Amount,
+    ) -> Result<()> {
+        if let Some(withdrawals) = self.withdrawals.get_mut(owner) {
+            withdrawals.amount =
+                withdrawals.amount.checked_sub(amount).unwrap_or_default();
+        }
+        self.save()
+    }
+
+    /// Write the withdrawals to the quotas file.
+    fn save(&self) -> Result<()> {
+        let bytes = serde_json::to_vec(&self.withdrawals)
+            .expect("Serializing the withdrawals shouldn't fail");
+        // Write to a temporary file first, so that the file is never left
+        // half-written
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, bytes).map_err(Error::Io)?;
+        fs::rename(&tmp_path, &self.path).map_err(Error::Io)
+    }
+}
+
+#[cfg(test)]
+mod test_quota {
+    use namada::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada::types::time::Duration as ChronoDuration;
+
+    use super::*;
+
+    #[test]
+    fn test_quotas() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("quotas.json");
+        let limit = token::Amount::native_whole(100);
+        let period = Duration::from_secs(60 * 60);
+        let mut quotas = Quotas::open(path.clone(), limit, period).unwrap();
+        let owner = established_address_1();
+        let now = DateTimeUtc::now();
+
+        let amount = token::Amount::native_whole(60);
+        quotas.withdraw(&owner, amount, now).unwrap();
+        // Exceeds the quota
+        assert!(matches!(
+            quotas.withdraw(&owner, amount, now),
+            Err(Error::Exceeded { .. })
+        ));
+        // Other addresses have their own quota
+        quotas
+            .withdraw(&established_address_2(), amount, now)
+            .unwrap();
+
+        // The withdrawals persist
+        let mut quotas = Quotas::open(path, limit, period).unwrap();
+        assert!(quotas.withdraw(&owner, amount, now).is_err());
+
+        // A refund frees the quota
+        quotas.refund(&owner, amount).unwrap();
+        quotas.withdraw(&owner, amount, now).unwrap();
+
+        // The quota is reset in the next period
+        let later = DateTimeUtc(now.0 + ChronoDuration::hours(1));
+        quotas.withdraw(&owner, amount, later).unwrap();
+    }
+}
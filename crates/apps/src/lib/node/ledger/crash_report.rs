@@ -0,0 +1,166 @@
+//! Crash reports written when the node panics, to give operators the context
+//! of the panic in a single file.
+
+use std::fmt::{self, Display};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+
+use namada::types::hash::Hash;
+use namada::types::storage::BlockHeight;
+use namada::types::time::DateTimeUtc;
+use once_cell::sync::Lazy;
+
+use crate::logging;
+
+/// The sub-directory of the chain dir in which the crash reports are written.
+pub const CRASH_REPORTS_DIR: &str = "crash_reports";
+
+static LAST_PROCESSED: Lazy<Mutex<LastProcessed>> =
+    Lazy::new(|| Mutex::new(LastProcessed::default()));
+
+/// The last block and tx processed by the node.
+#[derive(Clone, Debug, Default)]
+struct LastProcessed {
+    height: Option<BlockHeight>,
+    tx_hash: Option<Hash>,
+}
+
+/// Record the height of the block that is being finalized.
+pub fn set_block_height(height: BlockHeight) {
+    let mut last = LAST_PROCESSED
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    last.height = Some(height);
+    last.tx_hash = None;
+}
+
+/// Record the hash of the tx that is being finalized.
+pub fn set_tx_hash(tx_hash: Hash) {
+    LAST_PROCESSED
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .tx_hash = Some(tx_hash);
+}
+
+/// Install a panic hook that writes a crash report into the given directory
+/// and prints its path, after running the previously installed hook.
+pub fn install_panic_hook(reports_dir: PathBuf) {
+    let prev_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        prev_hook(info);
+        // Don't block on the lock in case the panic happened while holding it
+        let last_processed = LAST_PROCESSED
+            .try_lock()
+            .map(|last| last.clone())
+            .unwrap_or_default();
+        let report = CrashReport::new(info.to_string(), last_processed);
+        match report.write(&reports_dir) {
+            Ok(path) => eprintln!(
+                "The node panicked. A crash report was written to {}",
+                path.to_string_lossy()
+            ),
+            Err(err) => {
+                eprintln!(
+                    "The node panicked. Failed to write a crash report: {err}"
+                )
+            }
+        }
+    }));
+}
+
+/// The context of a panic.
+struct CrashReport {
+    time: DateTimeUtc,
+    thread: String,
+    panic: String,
+    last_processed: LastProcessed,
+    backtrace: String,
+    recent_events: Vec<String>,
+}
+
+impl CrashReport {
+    /// Capture the context of a panic from the panicking thread.
+    fn new(panic: String, last_processed: LastProcessed) -> Self {
+        Self {
+            time: DateTimeUtc::now(),
+            thread: std::thread::current()
+                .name()
+                .unwrap_or("<unnamed>")
+                .to_string(),
+            panic,
+            last_processed,
+            backtrace: std::backtrace::Backtrace::force_capture().to_string(),
+            recent_events: logging::recent_events(),
+        }
+    }
+
+    /// Write the report into a new timestamped file in the given directory
+    /// and return its path.
+    fn write(&self, reports_dir: &Path) -> std::io::Result<PathBuf> {
+        fs::create_dir_all(reports_dir)?;
+        let file_name =
+            format!("crash-{}.txt", self.time.0.format("%Y%m%dT%H%M%S%.6fZ"));
+        let path = reports_dir.join(file_name);
+        fs::write(&path, self.to_string())?;
+        Ok(path)
+    }
+}
+
+impl Display for CrashReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let or_none =
+            |value: Option<String>| value.unwrap_or_else(|| "none".to_string());
+        writeln!(f, "Namada node crash report")?;
+        writeln!(f, "Version: {}", env!("CARGO_PKG_VERSION"))?;
+        writeln!(f, "Time: {}", self.time.to_rfc3339())?;
+        writeln!(f, "Thread: {}", self.thread)?;
+        writeln!(f, "Panic: {}", self.panic)?;
+        writeln!(
+            f,
+            "Last block height: {}",
+            or_none(self.last_processed.height.map(|h| h.to_string()))
+        )?;
+        writeln!(
+            f,
+            "Last tx hash: {}",
+            or_none(self.last_processed.tx_hash.map(|h| h.to_string()))
+        )?;
+        writeln!(f)?;
+        writeln!(f, "Backtrace:")?;
+        writeln!(f, "{}", self.backtrace)?;
+        writeln!(f, "Recent log events:")?;
+        for event in &self.recent_events {
+            writeln!(f, "{event}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Test that a crash report is written with the last processed block and
+    /// tx.
+    #[test]
+    fn test_write_crash_report() {
+        let reports_dir = tempfile::tempdir().unwrap();
+        let tx_hash = Hash::sha256(b"tx");
+        let last_processed = LastProcessed {
+            height: Some(BlockHeight(42)),
+            tx_hash: Some(tx_hash),
+        };
+
+        let report =
+            CrashReport::new("panicked at 'boom'".to_string(), last_processed);
+        let path = report.write(reports_dir.path()).unwrap();
+        assert!(path.starts_with(reports_dir.path()));
+
+        let contents = fs::read_to_string(path).unwrap();
+        assert!(contents.contains("Panic: panicked at 'boom'"));
+        assert!(contents.contains("Last block height: 42"));
+        assert!(contents.contains(&format!("Last tx hash: {tx_hash}")));
+        assert!(contents.contains("Backtrace:"));
+    }
+}
@@ -0,0 +1,226 @@
+//! The difference of the state in the DB of a stopped node between two
+//! heights.
+//!
+//! The diff is found by walking the old and new values stored for every
+//! height after the first one, up to the second one. It lists the keys that
+//! were added, removed or changed, ordered by the storage keys, such that the
+//! diffs of the DBs of two validators can be compared to find the keys where
+//! their states diverged.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+
+use namada::state::DBIter;
+use namada::types::storage::{BlockHeight, Key};
+use namada_sdk::storage_value::StorageValueType;
+use serde_json::json;
+
+use super::db_query::{csv_field, value_to_string, OutputFormat, Row};
+
+/// The change of the value of a key between two heights
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    /// The key didn't exist at the first height
+    Added(Vec<u8>),
+    /// The key doesn't exist at the second height
+    Removed(Vec<u8>),
+    /// The key has a different value at the second height
+    Changed { old: Vec<u8>, new: Vec<u8> },
+}
+
+impl Change {
+    /// The name of the kind of the change
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Change::Added(_) => "added",
+            Change::Removed(_) => "removed",
+            Change::Changed { .. } => "changed",
+        }
+    }
+}
+
+/// Find the changes of the keys with the given prefix from the state at the
+/// `from` height to the state at the `to` height, which must not be lower.
+/// The keys whose value ended up the same as at the `from` height are
+/// omitted.
+pub fn diff<D>(
+    db: &D,
+    from: BlockHeight,
+    to: BlockHeight,
+    prefix: &Key,
+) -> BTreeMap<String, Change>
+where
+    D: for<'iter> DBIter<'iter>,
+{
+    // The values of the keys at the `from` height and at the last height
+    // walked so far, `None` when the key doesn't exist
+    let mut values: BTreeMap<String, (Option<Vec<u8>>, Option<Vec<u8>>)> =
+        BTreeMap::new();
+    for height in (from.0 + 1)..=to.0 {
+        let height = BlockHeight(height);
+        let mut new_values: HashMap<String, Vec<u8>> = db
+            .iter_new_diffs(height, Some(prefix))
+            .map(|(key, value, _gas)| (key, value))
+            .collect();
+        // A key with an old value was either written or deleted
+        for (key, old_value, _gas) in db.iter_old_diffs(height, Some(prefix)) {
+            let new_value = new_values.remove(&key);
+            values
+                .entry(key)
+                .and_modify(|(_, last)| *last = new_value.clone())
+                .or_insert((Some(old_value), new_value));
+        }
+        // The remaining keys were written without a previous value
+        for (key, new_value) in new_values {
+            values
+                .entry(key)
+                .and_modify(|(_, last)| *last = Some(new_value.clone()))
+                .or_insert((None, Some(new_value)));
+        }
+    }
+    values
+        .into_iter()
+        .filter_map(|(key, values)| {
+            let change = match values {
+                (None, Some(new)) => Change::Added(new),
+                (Some(old), None) => Change::Removed(old),
+                (Some(old), Some(new)) if old != new => {
+                    Change::Changed { old, new }
+                }
+                _ => return None,
+            };
+            Some((key, change))
+        })
+        .collect()
+}
+
+/// Write the changes with their values decoded with the given type or the
+/// type found from their keys.
+pub fn write_diff(
+    changes: &BTreeMap<String, Change>,
+    value_type: Option<StorageValueType>,
+    format: OutputFormat,
+    mut out: impl Write,
+) -> io::Result<()> {
+    let decode = |key: &str, bytes: &[u8]| {
+        Row::decode(key.to_string(), bytes, value_type)
+    };
+    let rows = changes.iter().map(|(key, change)| {
+        let (old, new) = match change {
+            Change::Added(new) => (None, Some(decode(key, new))),
+            Change::Removed(old) => (Some(decode(key, old)), None),
+            Change::Changed { old, new } => {
+                (Some(decode(key, old)), Some(decode(key, new)))
+            }
+        };
+        (key, change.kind(), old, new)
+    });
+    match format {
+        OutputFormat::Csv => {
+            writeln!(out, "change,key,type,old,new")?;
+            for (key, kind, old, new) in rows {
+                let type_name =
+                    new.as_ref().or(old.as_ref()).map(Row::type_name);
+                let value = |row: Option<Row>| {
+                    row.map(|row| csv_field(&value_to_string(&row.value)))
+                        .unwrap_or_default()
+                };
+                writeln!(
+                    out,
+                    "{kind},{},{},{},{}",
+                    csv_field(key),
+                    type_name.unwrap_or_default(),
+                    value(old),
+                    value(new)
+                )?;
+            }
+        }
+        OutputFormat::Json => {
+            write!(out, "[")?;
+            for (ix, (key, kind, old, new)) in rows.enumerate() {
+                let separator = if ix == 0 { "" } else { "," };
+                let type_name =
+                    new.as_ref().or(old.as_ref()).map(Row::type_name);
+                let object = json!({
+                    "change": kind,
+                    "key": key,
+                    "type": type_name,
+                    "old": old.map(|row| row.value),
+                    "new": new.map(|row| row.value),
+                });
+                write!(out, "{separator}\n  {object}")?;
+            }
+            writeln!(out, "\n]")?;
+        }
+    }
+    out.flush()
+}
+
+#[cfg(test)]
+mod test_db_diff {
+    use namada::state::mockdb::MockDB;
+    use namada::state::DB;
+
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let mut db = MockDB::default();
+        let key = |key: &str| Key::parse(key).unwrap();
+        db.write_subspace_val(BlockHeight(1), &key("a/changed"), [1])
+            .unwrap();
+        db.write_subspace_val(BlockHeight(1), &key("a/removed"), [2])
+            .unwrap();
+        db.write_subspace_val(BlockHeight(1), &key("a/reverted"), [3])
+            .unwrap();
+        db.write_subspace_val(BlockHeight(2), &key("a/changed"), [4])
+            .unwrap();
+        db.write_subspace_val(BlockHeight(2), &key("a/added"), [5])
+            .unwrap();
+        db.write_subspace_val(BlockHeight(2), &key("a/reverted"), [6])
+            .unwrap();
+        db.write_subspace_val(BlockHeight(2), &key("b/added"), [7])
+            .unwrap();
+        db.delete_subspace_val(BlockHeight(3), &key("a/removed"))
+            .unwrap();
+        db.write_subspace_val(BlockHeight(3), &key("a/changed"), [8])
+            .unwrap();
+        db.write_subspace_val(BlockHeight(3), &key("a/reverted"), [3])
+            .unwrap();
+
+        let changes = diff(&db, BlockHeight(1), BlockHeight(3), &key("a"));
+        let expected = BTreeMap::from([
+            ("a/added".to_string(), Change::Added(vec![5])),
+            (
+                "a/changed".to_string(),
+                Change::Changed {
+                    old: vec![1],
+                    new: vec![8],
+                },
+            ),
+            ("a/removed".to_string(), Change::Removed(vec![2])),
+        ]);
+        assert_eq!(changes, expected);
+
+        let changes =
+            diff(&db, BlockHeight(2), BlockHeight(3), &Key::default());
+        assert_eq!(changes.len(), 3);
+        assert_eq!(
+            changes.get("a/reverted"),
+            Some(&Change::Changed {
+                old: vec![6],
+                new: vec![3]
+            })
+        );
+        assert!(diff(&db, BlockHeight(3), BlockHeight(3), &key("a")).is_empty());
+
+        let mut out = vec![];
+        write_diff(&changes, None, OutputFormat::Csv, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(
+            out,
+            "change,key,type,old,new\nchanged,a/changed,bytes,04,08\nchanged,\
+             a/reverted,bytes,06,03\nremoved,a/removed,bytes,02,\n"
+        );
+    }
+}
@@ -0,0 +1,735 @@
+//! An SQL-like language to query the state in the DB of a stopped node.
+//!
+//! A query has the form
+//!
+//! ```text
+//! <prefix> [WHERE <condition> [AND <condition>]*] [LIMIT <n>]
+//! ```
+//!
+//! The `prefix` is a storage key whose sub-keys are iterated, or `*` to
+//! iterate the whole subspace. Each value is decoded with the type given
+//! explicitly or else with the type found from its key (see
+//! [`value_type_of`]). The values that can't be decoded are hex encoded and
+//! have the type `bytes`.
+//!
+//! A condition compares a field of the rows with a literal:
+//!
+//! - the fields are `key`, `type`, `size` (the length of the raw value in
+//!   bytes), `value` and `value.<path>`, which selects a nested field of a
+//!   decoded value by a dot-separated path of object fields or array indices;
+//! - the operators are `=`, `!=`, `<`, `<=`, `>`, `>=` and `LIKE`, whose
+//!   pattern matches any sequence of characters with `%` and any single
+//!   character with `_`;
+//! - the literals are bare words or quoted with `"` or `'`.
+//!
+//! Two decimal numbers are compared by their value, anything else is compared
+//! as strings. The keywords are case-insensitive. For example:
+//!
+//! ```text
+//! * WHERE type = amount AND value >= 1000000 LIMIT 10
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use data_encoding::HEXLOWER;
+use namada::types::storage::{self, Key};
+use namada_sdk::storage_value::{value_type_of, StorageValueType};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+/// The type of the values that couldn't be decoded
+pub const BYTES_TYPE: &str = "bytes";
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Invalid query: {0}")]
+    Parse(String),
+    #[error("Invalid key prefix: {0}")]
+    Prefix(storage::Error),
+    #[error("Failed to write the query results: {0}")]
+    Io(io::Error),
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// A parsed query
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Query {
+    /// The prefix of the iterated keys, `None` to iterate all of them
+    pub prefix: Option<Key>,
+    /// The conditions that all the returned rows satisfy
+    pub conditions: Vec<Condition>,
+    /// The max number of returned rows
+    pub limit: Option<usize>,
+}
+
+/// A comparison of a field of the rows with a literal
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Condition {
+    pub field: Field,
+    pub op: Operator,
+    pub literal: String,
+}
+
+/// A field of the rows
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Field {
+    /// The storage key
+    Key,
+    /// The name of the type of the value
+    Type,
+    /// The length of the raw value in bytes
+    Size,
+    /// The value, or the nested field of it at the given path
+    Value(Vec<String>),
+}
+
+/// A comparison operator
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Like,
+}
+
+/// The format of the query results
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Comma-separated values, with a header line
+    #[default]
+    Csv,
+    /// A JSON array of objects
+    Json,
+}
+
+/// A storage key with its decoded value
+#[derive(Clone, Debug)]
+pub struct Row {
+    pub key: String,
+    /// The type the value was decoded as, `None` if it's hex encoded
+    pub value_type: Option<StorageValueType>,
+    /// The length of the raw value in bytes
+    pub size: usize,
+    pub value: Value,
+}
+
+impl Row {
+    /// Decode a raw value with the given type or the type found from its key
+    pub fn decode(
+        key: String,
+        bytes: &[u8],
+        value_type: Option<StorageValueType>,
+    ) -> Self {
+        let value_type = value_type.or_else(|| {
+            Key::parse(&key).ok().and_then(|key| value_type_of(&key))
+        });
+        let decoded = value_type
+            .and_then(|ty| ty.decode(bytes).ok().map(|value| (ty, value)));
+        let (value_type, value) = match decoded {
+            Some((ty, value)) => (Some(ty), value),
+            None => (None, Value::String(HEXLOWER.encode(bytes))),
+        };
+        Self {
+            key,
+            value_type,
+            size: bytes.len(),
+            value,
+        }
+    }
+
+    /// The name of the type of the value
+    pub fn type_name(&self) -> &'static str {
+        self.value_type
+            .as_ref()
+            .map(StorageValueType::name)
+            .unwrap_or(BYTES_TYPE)
+    }
+
+    /// The given field as a string, if the row has it
+    fn field(&self, field: &Field) -> Option<String> {
+        match field {
+            Field::Key => Some(self.key.clone()),
+            Field::Type => Some(self.type_name().to_string()),
+            Field::Size => Some(self.size.to_string()),
+            Field::Value(path) => path
+                .iter()
+                .try_fold(&self.value, |value, segment| match value {
+                    Value::Object(fields) => fields.get(segment),
+                    Value::Array(items) => segment
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|ix| items.get(ix)),
+                    _ => None,
+                })
+                .map(value_to_string),
+        }
+    }
+}
+
+impl Query {
+    /// Check if the row satisfies all the conditions of the query
+    pub fn matches(&self, row: &Row) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(row))
+    }
+
+    /// Run the query over the key-value pairs of the prefix iterator and
+    /// write the matching rows. Returns the number of written rows.
+    pub fn run(
+        &self,
+        pairs: impl Iterator<Item = (String, Vec<u8>)>,
+        value_type: Option<StorageValueType>,
+        format: OutputFormat,
+        mut out: impl Write,
+    ) -> Result<usize> {
+        let rows = pairs
+            .map(|(key, bytes)| Row::decode(key, &bytes, value_type))
+            .filter(|row| self.matches(row))
+            .take(self.limit.unwrap_or(usize::MAX));
+        let mut count = 0;
+        match format {
+            OutputFormat::Csv => {
+                writeln!(out, "key,type,value").map_err(Error::Io)?;
+                for row in rows {
+                    writeln!(
+                        out,
+                        "{},{},{}",
+                        csv_field(&row.key),
+                        row.type_name(),
+                        csv_field(&value_to_string(&row.value))
+                    )
+                    .map_err(Error::Io)?;
+                    count += 1;
+                }
+            }
+            OutputFormat::Json => {
+                write!(out, "[").map_err(Error::Io)?;
+                for row in rows {
+                    let separator = if count == 0 { "" } else { "," };
+                    let value_type = row.type_name();
+                    let object = json!({
+                        "key": row.key,
+                        "type": value_type,
+                        "value": row.value,
+                    });
+                    write!(out, "{separator}\n  {object}")
+                        .map_err(Error::Io)?;
+                    count += 1;
+                }
+                writeln!(out, "\n]").map_err(Error::Io)?;
+            }
+        }
+        out.flush().map_err(Error::Io)?;
+        Ok(count)
+    }
+}
+
+impl Condition {
+    /// Check if the row satisfies the condition. A row without the field
+    /// never does.
+    pub fn matches(&self, row: &Row) -> bool {
+        let value = match row.field(&self.field) {
+            Some(value) => value,
+            None => return false,
+        };
+        let ordering = compare(&value, &self.literal);
+        match self.op {
+            Operator::Eq => ordering == Ordering::Equal,
+            Operator::Ne => ordering != Ordering::Equal,
+            Operator::Lt => ordering == Ordering::Less,
+            Operator::Le => ordering != Ordering::Greater,
+            Operator::Gt => ordering == Ordering::Greater,
+            Operator::Ge => ordering != Ordering::Less,
+            Operator::Like => is_like(&value, &self.literal),
+        }
+    }
+}
+
+/// The string of a JSON value, without quotes for strings
+pub(crate) fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Quote a CSV field if needed
+pub(crate) fn csv_field(field: &str) -> String {
+    if field.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Compare two strings by their value if both are decimal numbers, or else
+/// lexicographically
+fn compare(lhs: &str, rhs: &str) -> Ordering {
+    match (Decimal::parse(lhs), Decimal::parse(rhs)) {
+        (Some(lhs), Some(rhs)) => lhs.cmp(&rhs),
+        _ => lhs.cmp(rhs),
+    }
+}
+
+/// A decimal number of arbitrary size and precision, for comparisons only
+#[derive(Debug, PartialEq, Eq)]
+struct Decimal {
+    negative: bool,
+    /// The digits of the integer part, without leading zeros
+    integer: String,
+    /// The digits of the fractional part, without trailing zeros
+    fraction: String,
+}
+
+impl Decimal {
+    fn parse(string: &str) -> Option<Self> {
+        let (negative, digits) = match string.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, string.strip_prefix('+').unwrap_or(string)),
+        };
+        let (integer, fraction) =
+            digits.split_once('.').unwrap_or((digits, ""));
+        let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if (integer.is_empty() && fraction.is_empty())
+            || !is_digits(integer)
+            || !is_digits(fraction)
+        {
+            return None;
+        }
+        let integer = integer.trim_start_matches('0').to_string();
+        let fraction = fraction.trim_end_matches('0').to_string();
+        // There's no negative zero
+        let negative = negative && !(integer.is_empty() && fraction.is_empty());
+        Some(Self {
+            negative,
+            integer,
+            fraction,
+        })
+    }
+
+    fn cmp_magnitude(&self, other: &Self) -> Ordering {
+        self.integer
+            .len()
+            .cmp(&other.integer.len())
+            .then_with(|| self.integer.cmp(&other.integer))
+            .then_with(|| self.fraction.cmp(&other.fraction))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.cmp_magnitude(other),
+            (true, true) => self.cmp_magnitude(other).reverse(),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Match a string against a `LIKE` pattern
+fn is_like(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+    let (mut v, mut p) = (0, 0);
+    // The positions in the pattern after the last `%` and in the value where
+    // its match ends
+    let mut backtrack = None;
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('%') => {
+                p += 1;
+                backtrack = Some((p, v));
+            }
+            Some(c) if *c == '_' || *c == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                // Extend the match of the last `%` by one character
+                Some((after_wildcard, matched_until)) => {
+                    p = after_wildcard;
+                    v = matched_until + 1;
+                    backtrack = Some((after_wildcard, v));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '%')
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(Operator),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {}
+            '"' | '\'' => {
+                let mut literal = String::new();
+                loop {
+                    match chars.next() {
+                        Some(next) if next == c => break,
+                        Some(next) => literal.push(next),
+                        None => {
+                            return Err(Error::Parse(format!(
+                                "Unterminated quoted literal \"{literal}\""
+                            )));
+                        }
+                    }
+                }
+                tokens.push(Token::Quoted(literal));
+            }
+            '=' | '!' | '<' | '>' => {
+                let with_eq = chars.next_if_eq(&'=').is_some();
+                let op = match (c, with_eq) {
+                    ('=', _) => Operator::Eq,
+                    ('!', true) => Operator::Ne,
+                    ('<', false) => Operator::Lt,
+                    ('<', true) => Operator::Le,
+                    ('>', false) => Operator::Gt,
+                    ('>', true) => Operator::Ge,
+                    _ => {
+                        return Err(Error::Parse(format!(
+                            "Unexpected character '{c}'"
+                        )));
+                    }
+                };
+                tokens.push(Token::Op(op));
+            }
+            c => {
+                let mut word = c.to_string();
+                while let Some(next) = chars.next_if(|next| {
+                    !next.is_whitespace() && !"\"'=!<>".contains(*next)
+                }) {
+                    word.push(next);
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.pos >= self.tokens.len()
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(
+            self.tokens.get(self.pos),
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword)
+        )
+    }
+
+    /// Consume the next token if it's the given keyword
+    fn next_if_keyword(&mut self, keyword: &str) -> bool {
+        let is_keyword = self.is_keyword(keyword);
+        if is_keyword {
+            self.pos += 1;
+        }
+        is_keyword
+    }
+
+    fn literal(&mut self, expected: &str) -> Result<String> {
+        match self.next() {
+            Some(Token::Word(literal) | Token::Quoted(literal)) => Ok(literal),
+            token => Err(unexpected(expected, token)),
+        }
+    }
+
+    fn condition(&mut self) -> Result<Condition> {
+        let field = self.literal("a field")?.parse()?;
+        let op = match self.next() {
+            Some(Token::Op(op)) => op,
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case("LIKE") => {
+                Operator::Like
+            }
+            token => return Err(unexpected("an operator", token)),
+        };
+        let literal = self.literal("a value")?;
+        Ok(Condition { field, op, literal })
+    }
+}
+
+fn unexpected(expected: &str, found: Option<Token>) -> Error {
+    match found {
+        Some(token) => {
+            Error::Parse(format!("Expected {expected}, found \"{token}\""))
+        }
+        None => Error::Parse(format!(
+            "Expected {expected}, found the end of the query"
+        )),
+    }
+}
+
+impl FromStr for Query {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            pos: 0,
+        };
+        let prefix = if parser.is_at_end()
+            || parser.is_keyword("WHERE")
+            || parser.is_keyword("LIMIT")
+        {
+            None
+        } else {
+            match parser.literal("a key prefix")?.as_str() {
+                "*" => None,
+                prefix => Some(Key::parse(prefix).map_err(Error::Prefix)?),
+            }
+        };
+        let mut conditions = vec![];
+        if parser.next_if_keyword("WHERE") {
+            conditions.push(parser.condition()?);
+            while parser.next_if_keyword("AND") {
+                conditions.push(parser.condition()?);
+            }
+        }
+        let limit = if parser.next_if_keyword("LIMIT") {
+            let limit = parser.literal("a limit")?;
+            let limit = limit.parse().map_err(|_| {
+                Error::Parse(format!("Invalid limit \"{limit}\""))
+            })?;
+            Some(limit)
+        } else {
+            None
+        };
+        match parser.next() {
+            None => Ok(Self {
+                prefix,
+                conditions,
+                limit,
+            }),
+            token => Err(unexpected("the end of the query", token)),
+        }
+    }
+}
+
+impl FromStr for Field {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "key" => Ok(Self::Key),
+            "type" => Ok(Self::Type),
+            "size" => Ok(Self::Size),
+            "value" => Ok(Self::Value(vec![])),
+            _ => match s.get(..6) {
+                Some(head) if head.eq_ignore_ascii_case("value.") => Ok(
+                    Self::Value(s[6..].split('.').map(String::from).collect()),
+                ),
+                _ => Err(Error::Parse(format!("Unknown field \"{s}\""))),
+            },
+        }
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unknown output format \"{s}\"")),
+        }
+    }
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let op = match self {
+            Self::Eq => "=",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Like => "LIKE",
+        };
+        write!(f, "{op}")
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Word(word) | Self::Quoted(word) => write!(f, "{word}"),
+            Self::Op(op) => write!(f, "{op}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_db_query {
+    use borsh_ext::BorshSerializeExt;
+    use namada::token;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_query() {
+        let query: Query = "'some/prefix' where value.total >= 10 AND key \
+                            LIKE %a_ limit 5"
+            .parse()
+            .unwrap();
+        assert_eq!(query.prefix, Some(Key::parse("some/prefix").unwrap()));
+        assert_eq!(
+            query.conditions,
+            vec![
+                Condition {
+                    field: Field::Value(vec!["total".to_string()]),
+                    op: Operator::Ge,
+                    literal: "10".to_string(),
+                },
+                Condition {
+                    field: Field::Key,
+                    op: Operator::Like,
+                    literal: "%a_".to_string(),
+                },
+            ]
+        );
+        assert_eq!(query.limit, Some(5));
+
+        let query: Query = "* WHERE type!=\"u64\"".parse().unwrap();
+        assert_eq!(query.prefix, None);
+        assert_eq!(query.conditions[0].op, Operator::Ne);
+        assert_eq!(query.conditions[0].literal, "u64");
+        assert_eq!(query.limit, None);
+
+        let query: Query = "LIMIT 1".parse().unwrap();
+        assert_eq!(query.prefix, None);
+        assert_eq!(query.limit, Some(1));
+
+        for invalid in [
+            "* WHERE",
+            "* WHERE value",
+            "* WHERE value =",
+            "* WHERE nope = 1",
+            "* WHERE value ! 1",
+            "* WHERE key = 'open",
+            "* LIMIT -1",
+            "* LIMIT 1 extra",
+        ] {
+            assert!(invalid.parse::<Query>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(compare("10", "9"), Ordering::Greater);
+        assert_eq!(compare("010.50", "10.5"), Ordering::Equal);
+        assert_eq!(compare("-2", "-10"), Ordering::Greater);
+        assert_eq!(compare("-0", "0"), Ordering::Equal);
+        assert_eq!(compare("0.05", "0.5"), Ordering::Less);
+        assert_eq!(
+            compare(
+                "340282366920938463463374607431768211456",
+                "340282366920938463463374607431768211455"
+            ),
+            Ordering::Greater
+        );
+        // Not numbers
+        assert_eq!(compare("10", "9a"), Ordering::Less);
+        assert_eq!(compare(".", "-"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_like() {
+        assert!(is_like("abc", "abc"));
+        assert!(is_like("abc", "a_c"));
+        assert!(is_like("abc", "%"));
+        assert!(is_like("", "%"));
+        assert!(is_like("#tnam1/balance/tnam2", "%/balance/%"));
+        assert!(is_like("aXbXc", "%X_"));
+        assert!(!is_like("abc", "ab"));
+        assert!(!is_like("abc", "_"));
+        assert!(!is_like("abc", "%d%"));
+    }
+
+    #[test]
+    fn test_run_query() {
+        let pairs = vec![
+            (
+                "a/small".to_string(),
+                token::Amount::from(1).serialize_to_vec(),
+            ),
+            (
+                "a/big".to_string(),
+                token::Amount::from(1_000).serialize_to_vec(),
+            ),
+            ("b/raw".to_string(), vec![0xab, 0xcd]),
+        ];
+        let query: Query =
+            "* WHERE type = amount AND value > 100".parse().unwrap();
+
+        let mut out = vec![];
+        let count = query
+            .run(
+                pairs.clone().into_iter(),
+                Some(StorageValueType::Amount),
+                OutputFormat::Csv,
+                &mut out,
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "key,type,value\na/big,amount,1000\n"
+        );
+
+        // The raw value doesn't decode as an amount
+        let query: Query = "* WHERE type = bytes".parse().unwrap();
+        let mut out = vec![];
+        query
+            .run(
+                pairs.into_iter(),
+                Some(StorageValueType::Amount),
+                OutputFormat::Json,
+                &mut out,
+            )
+            .unwrap();
+        let rows: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            rows,
+            json!([{ "key": "b/raw", "type": "bytes", "value": "abcd" }])
+        );
+    }
+}
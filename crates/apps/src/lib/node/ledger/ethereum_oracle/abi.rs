@@ -0,0 +1,39 @@
+//! Versioned decoding of the bridge contract events.
+//!
+//! The events of a bridge contract are decoded with the ABI of its version,
+//! as stored on chain. During the migration window of a bridge contract
+//! upgrade, the oracle decodes the events of both the current and the next
+//! contract, each with the ABI of its own version.
+
+use ethbridge_events::{event_codecs, DynEventCodec};
+use namada::eth_bridge::ContractVersion;
+
+/// Get the codecs of the events emitted by the given version of the bridge
+/// contract, or `None` if the oracle can't decode them.
+pub fn bridge_event_codecs(
+    version: ContractVersion,
+) -> Option<Vec<DynEventCodec>> {
+    match u64::from(version) {
+        // The ABI of the `ethbridge-rs` bindings in use
+        1 => Some(event_codecs().into_iter().collect()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU64;
+
+    use super::*;
+
+    /// Test that the codecs are selected by the contract version.
+    #[test]
+    fn test_bridge_event_codecs_by_version() {
+        let codecs = bridge_event_codecs(ContractVersion::default())
+            .expect("Test failed");
+        assert_eq!(codecs.len(), event_codecs().into_iter().count());
+
+        let unknown = ContractVersion::from(NonZeroU64::new(2).unwrap());
+        assert!(bridge_event_codecs(unknown).is_none());
+    }
+}
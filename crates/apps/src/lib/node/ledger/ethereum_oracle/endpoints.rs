@@ -0,0 +1,425 @@
+//! Failover between the Ethereum JSON-RPC endpoints of the oracle.
+//!
+//! The oracle keeps the stats of the requests it makes to each endpoint and
+//! sends every request to the healthiest endpoint first, falling back to the
+//! others in order when it fails. An endpoint is healthier than another when
+//! it failed fewer times in a row, or else when it responds faster. The
+//! stats are persisted in the chain directory, so they survive restarts of
+//! the node and can be inspected with `namadan ethereum-oracle status`.
+
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use async_trait::async_trait;
+use ethabi::Address;
+use namada::types::control_flow::time::{Duration, Instant};
+use namada::types::ethereum_structs;
+use namada_sdk::eth_bridge::SyncStatus;
+use serde::{Deserialize, Serialize};
+
+use super::{Error, RpcClient};
+
+/// The file of the endpoints' stats in the chain directory
+pub const STATS_FILE: &str = "ethereum_oracle_endpoints.json";
+
+/// The minimum time between two writes of the stats to their file
+const PERSIST_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The weight of the previous average in the moving average of the latency,
+/// out of [`LATENCY_WEIGHTS`]
+const LATENCY_HISTORY_WEIGHT: u64 = 7;
+const LATENCY_WEIGHTS: u64 = 8;
+
+/// The stats of the requests made to an endpoint
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EndpointStats {
+    /// The number of successful requests
+    pub successes: u64,
+    /// The number of failed requests
+    pub failures: u64,
+    /// The number of requests that failed since the last successful one
+    pub consecutive_failures: u64,
+    /// The moving average of the latency of the successful requests
+    pub avg_latency_ms: u64,
+}
+
+impl EndpointStats {
+    fn record_success(&mut self, latency: Duration) {
+        let latency_ms = u64::try_from(latency.as_millis()).unwrap_or(u64::MAX);
+        self.avg_latency_ms = if self.successes == 0 {
+            latency_ms
+        } else {
+            self.avg_latency_ms
+                .saturating_mul(LATENCY_HISTORY_WEIGHT)
+                .saturating_add(latency_ms)
+                / LATENCY_WEIGHTS
+        };
+        self.successes = self.successes.saturating_add(1);
+        self.consecutive_failures = 0;
+    }
+
+    fn record_failure(&mut self) {
+        self.failures = self.failures.saturating_add(1);
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+    }
+
+    /// The rank of the endpoint, where the lower ranks are healthier
+    pub fn rank(&self) -> (u64, u64) {
+        (self.consecutive_failures, self.avg_latency_ms)
+    }
+}
+
+/// The stats of the endpoints, by their URL
+pub type Stats = BTreeMap<String, EndpointStats>;
+
+/// Read the persisted stats of the endpoints. Returns no stats if the file
+/// doesn't exist yet.
+pub fn read_stats(path: &Path) -> io::Result<Stats> {
+    match fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Stats::new()),
+        Err(err) => Err(err),
+    }
+}
+
+fn write_stats(path: &Path, stats: &Stats) -> io::Result<()> {
+    let json = serde_json::to_vec_pretty(stats)
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+    fs::write(path, json)
+}
+
+/// Sort the endpoints from the healthiest. The endpoints without stats come
+/// first, so that they get tried. The ties keep the given order.
+pub fn rank_endpoints<'a>(
+    urls: impl IntoIterator<Item = &'a String>,
+    stats: &Stats,
+) -> Vec<&'a String> {
+    let mut urls: Vec<&String> = urls.into_iter().collect();
+    urls.sort_by_key(|url| stats.get(*url).map(EndpointStats::rank));
+    urls
+}
+
+/// A client of several Ethereum endpoints, that sends every request to the
+/// healthiest endpoint first
+pub struct Endpoints<C> {
+    /// The clients of the endpoints with their URL, in the configured order
+    clients: Vec<(String, C)>,
+    stats: RefCell<Stats>,
+    /// Where the stats are persisted, if anywhere
+    stats_path: Option<PathBuf>,
+    last_persisted: Cell<Instant>,
+}
+
+impl<C: RpcClient> Endpoints<C> {
+    /// Create the clients of the given endpoints, with the stats persisted
+    /// in the given file, if any.
+    ///
+    /// Panics if there are no endpoints.
+    pub fn new(urls: Vec<String>, stats_path: Option<PathBuf>) -> Self {
+        assert!(!urls.is_empty(), "The oracle needs an Ethereum endpoint");
+        let mut stats = match stats_path.as_deref().map(read_stats) {
+            Some(Ok(stats)) => stats,
+            Some(Err(err)) => {
+                tracing::warn!(
+                    %err,
+                    "Couldn't read the stats of the Ethereum endpoints, \
+                     starting afresh"
+                );
+                Stats::new()
+            }
+            None => Stats::new(),
+        };
+        // Forget the endpoints that are no longer configured
+        stats.retain(|url, _| urls.contains(url));
+        let clients = urls
+            .into_iter()
+            .map(|url| {
+                let client = C::new_client(&url);
+                (url, client)
+            })
+            .collect();
+        Self {
+            clients,
+            stats: RefCell::new(stats),
+            stats_path,
+            last_persisted: Cell::new(Instant::now()),
+        }
+    }
+
+    /// The indices of the clients, ranked like [`rank_endpoints`]
+    fn ranked(&self) -> Vec<usize> {
+        let stats = self.stats.borrow();
+        let mut indices: Vec<usize> = (0..self.clients.len()).collect();
+        indices.sort_by_key(|ix| {
+            stats.get(&self.clients[*ix].0).map(EndpointStats::rank)
+        });
+        indices
+    }
+
+    fn record(&self, ix: usize, latency: Option<Duration>) {
+        let url = &self.clients[ix].0;
+        {
+            let mut stats = self.stats.borrow_mut();
+            let stats = stats.entry(url.clone()).or_default();
+            match latency {
+                Some(latency) => stats.record_success(latency),
+                None => {
+                    stats.record_failure();
+                    tracing::warn!(
+                        url,
+                        consecutive_failures = stats.consecutive_failures,
+                        "Request to an Ethereum endpoint failed"
+                    );
+                }
+            }
+        }
+        if self.last_persisted.get().elapsed() >= PERSIST_INTERVAL {
+            self.persist();
+        }
+    }
+
+    fn persist(&self) {
+        if let Some(path) = &self.stats_path {
+            if let Err(err) = write_stats(path, &self.stats.borrow()) {
+                tracing::warn!(
+                    %err,
+                    "Couldn't persist the stats of the Ethereum endpoints"
+                );
+            }
+        }
+        self.last_persisted.set(Instant::now());
+    }
+}
+
+impl<C> Drop for Endpoints<C> {
+    fn drop(&mut self) {
+        if let Some(path) = &self.stats_path {
+            let _ = write_stats(path, &self.stats.borrow());
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl<C: RpcClient> RpcClient for Endpoints<C> {
+    type Log = C::Log;
+
+    fn new_client(rpc_url: &str) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(vec![rpc_url.to_owned()], None)
+    }
+
+    async fn check_events_in_block(
+        &self,
+        block: ethereum_structs::BlockHeight,
+        address: Address,
+        abi_signature: &str,
+    ) -> Result<Vec<Self::Log>, Error> {
+        let mut last_error = None;
+        for ix in self.ranked() {
+            let start = Instant::now();
+            match self.clients[ix]
+                .1
+                .check_events_in_block(block.clone(), address, abi_signature)
+                .await
+            {
+                Ok(logs) => {
+                    self.record(ix, Some(start.elapsed()));
+                    return Ok(logs);
+                }
+                Err(error) => {
+                    self.record(ix, None);
+                    last_error = Some(error);
+                }
+            }
+        }
+        Err(last_error.expect("There's at least one endpoint"))
+    }
+
+    async fn syncing(
+        &self,
+        last_processed_block: Option<&ethereum_structs::BlockHeight>,
+        backoff: Duration,
+        deadline: Instant,
+    ) -> Result<SyncStatus, Error> {
+        let ranked = self.ranked();
+        let mut last_error = None;
+        for (tried, ix) in ranked.iter().copied().enumerate() {
+            // Share the time left between the endpoints left to try
+            let start = Instant::now();
+            let endpoints_left = (ranked.len() - tried) as u32;
+            let endpoint_deadline = start
+                + deadline.saturating_duration_since(start) / endpoints_left;
+            match self.clients[ix]
+                .1
+                .syncing(last_processed_block, backoff, endpoint_deadline)
+                .await
+            {
+                // The endpoint responded, but it's not ahead of the oracle
+                result @ (Ok(_) | Err(Error::FallenBehind)) => {
+                    self.record(ix, Some(start.elapsed()));
+                    return result;
+                }
+                Err(error) => {
+                    self.record(ix, None);
+                    last_error = Some(error);
+                }
+            }
+        }
+        Err(last_error.expect("There's at least one endpoint"))
+    }
+
+    #[inline]
+    fn may_recover(&self, error: &Error) -> bool {
+        self.clients[0].1.may_recover(error)
+    }
+}
+
+#[cfg(test)]
+mod test_endpoints {
+    use num256::Uint256;
+
+    use super::*;
+
+    /// A client of an endpoint that fails if its URL says so
+    struct TestClient {
+        fails: bool,
+    }
+
+    #[async_trait(?Send)]
+    impl RpcClient for TestClient {
+        type Log = ethabi::RawLog;
+
+        fn new_client(rpc_url: &str) -> Self
+        where
+            Self: Sized,
+        {
+            Self {
+                fails: rpc_url.starts_with("failing"),
+            }
+        }
+
+        async fn check_events_in_block(
+            &self,
+            _: ethereum_structs::BlockHeight,
+            address: Address,
+            abi_signature: &str,
+        ) -> Result<Vec<Self::Log>, Error> {
+            if self.fails {
+                Err(Error::CheckEvents(
+                    abi_signature.into(),
+                    address,
+                    "failing endpoint".into(),
+                ))
+            } else {
+                Ok(vec![])
+            }
+        }
+
+        async fn syncing(
+            &self,
+            _: Option<&ethereum_structs::BlockHeight>,
+            _: Duration,
+            _: Instant,
+        ) -> Result<SyncStatus, Error> {
+            if self.fails {
+                Err(Error::Timeout)
+            } else {
+                Ok(SyncStatus::AtHeight(Uint256::from(1u32)))
+            }
+        }
+
+        fn may_recover(&self, _: &Error) -> bool {
+            true
+        }
+    }
+
+    async fn check_events(endpoints: &Endpoints<TestClient>) {
+        endpoints
+            .check_events_in_block(1u64.into(), Address::zero(), "")
+            .await
+            .unwrap();
+    }
+
+    /// Test that the requests fail over to the healthy endpoints and then
+    /// prefer them
+    #[tokio::test]
+    async fn test_failover() {
+        let urls = vec!["failing".to_string(), "healthy".to_string()];
+        let endpoints = Endpoints::<TestClient>::new(urls.clone(), None);
+        check_events(&endpoints).await;
+        {
+            let stats = endpoints.stats.borrow();
+            assert_eq!(stats["failing"].failures, 1);
+            assert_eq!(stats["healthy"].successes, 1);
+            assert_eq!(rank_endpoints(&urls, &stats), vec![&urls[1], &urls[0]]);
+        }
+
+        // The healthy endpoint is now tried first
+        check_events(&endpoints).await;
+        let deadline = Instant::now() + Duration::from_secs(1);
+        endpoints
+            .syncing(None, Duration::from_millis(1), deadline)
+            .await
+            .unwrap();
+        let stats = endpoints.stats.borrow();
+        assert_eq!(stats["failing"].failures, 1);
+        assert_eq!(stats["healthy"].successes, 3);
+    }
+
+    /// Test that all the endpoints are tried before failing
+    #[tokio::test]
+    async fn test_all_endpoints_failing() {
+        let urls = vec!["failing-1".to_string(), "failing-2".to_string()];
+        let endpoints = Endpoints::<TestClient>::new(urls, None);
+        let deadline = Instant::now() + Duration::from_secs(1);
+        let result = endpoints
+            .syncing(None, Duration::from_millis(1), deadline)
+            .await;
+        assert!(matches!(result, Err(Error::Timeout)));
+        let stats = endpoints.stats.borrow();
+        assert_eq!(stats["failing-1"].consecutive_failures, 1);
+        assert_eq!(stats["failing-2"].consecutive_failures, 1);
+    }
+
+    /// Test that the stats are persisted and loaded again
+    #[tokio::test]
+    async fn test_persisted_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(STATS_FILE);
+        let urls = vec!["failing".to_string(), "healthy".to_string()];
+        let endpoints = Endpoints::<TestClient>::new(urls, Some(path.clone()));
+        check_events(&endpoints).await;
+        drop(endpoints);
+
+        let stats = read_stats(&path).unwrap();
+        assert_eq!(stats["failing"].failures, 1);
+        assert_eq!(stats["healthy"].successes, 1);
+
+        // The stats of the endpoints no longer configured are forgotten
+        let endpoints = Endpoints::<TestClient>::new(
+            vec!["healthy".to_string()],
+            Some(path),
+        );
+        assert_eq!(endpoints.stats.borrow().len(), 1);
+        assert_eq!(endpoints.stats.borrow()["healthy"], stats["healthy"]);
+    }
+
+    #[test]
+    fn test_latency_average() {
+        let mut stats = EndpointStats::default();
+        stats.record_success(Duration::from_millis(80));
+        assert_eq!(stats.avg_latency_ms, 80);
+        stats.record_success(Duration::from_millis(160));
+        assert_eq!(stats.avg_latency_ms, 90);
+        stats.record_failure();
+        stats.record_failure();
+        assert_eq!(stats.rank(), (2, 90));
+        stats.record_success(Duration::from_millis(90));
+        assert_eq!(stats.rank(), (0, 90));
+    }
+}
@@ -1,20 +1,24 @@
+pub mod abi;
 pub mod control;
+pub mod endpoints;
 pub mod events;
 pub mod test_tools;
 
 use std::ops::ControlFlow;
+use std::path::PathBuf;
 
 use async_trait::async_trait;
 use ethabi::Address;
-use ethbridge_events::{event_codecs, EventKind};
 use itertools::Either;
 use namada::core::hints;
 use namada::eth_bridge::ethers;
 use namada::eth_bridge::ethers::providers::{Http, Middleware, Provider};
 use namada::eth_bridge::oracle::config::Config;
 use namada::types::control_flow::time::{Constant, Duration, Instant, Sleep};
-use namada::types::ethereum_events::EthereumEvent;
+use namada::types::ethereum_events::{EthereumEvent, Uint};
 use namada::types::ethereum_structs;
+use namada::types::hash::Hash;
+use namada::types::keccak::KeccakHash;
 use namada_sdk::eth_bridge::{eth_syncing_status_timeout, SyncStatus};
 use num256::Uint256;
 use thiserror::Error;
@@ -22,6 +26,7 @@ use tokio::sync::mpsc::error::TryRecvError;
 use tokio::sync::mpsc::Sender as BoundedSender;
 use tokio::task::LocalSet;
 
+use self::endpoints::Endpoints;
 use self::events::PendingEvent;
 use super::abortable::AbortableSpawner;
 use crate::node::ledger::oracle::control::Command;
@@ -46,6 +51,15 @@ pub enum Error {
     MoreConfirmations,
     #[error("The Ethereum oracle timed out")]
     Timeout,
+    #[error(
+        "Can't decode the events of version {0} of the bridge contract at {1}"
+    )]
+    UnsupportedAbi(u64, Address),
+    #[error(
+        "Couldn't fetch the receipts root of Ethereum block {0} with the RPC \
+         endpoint: {1}"
+    )]
+    ReceiptsRoot(u64, String),
 }
 
 /// Convert values to [`ethabi`] Ethereum event logs.
@@ -91,6 +105,13 @@ pub trait RpcClient {
         abi_signature: &str,
     ) -> Result<Vec<Self::Log>, Error>;
 
+    /// Query the root of the receipts trie of a block, against which
+    /// the receipts of the events it emitted can be proven.
+    async fn receipts_root(
+        &self,
+        block: ethereum_structs::BlockHeight,
+    ) -> Result<KeccakHash, Error>;
+
     /// Check if the fullnode we are connected to is syncing or is up
     /// to date with the Ethereum (an return the block height).
     ///
@@ -127,12 +148,7 @@ impl RpcClient for Provider<Http> {
         contract_address: Address,
         abi_signature: &str,
     ) -> Result<Vec<Self::Log>, Error> {
-        let height = {
-            let n: Uint256 = block.into();
-            let n: u64 =
-                n.0.try_into().expect("Ethereum block number overflow");
-            n
-        };
+        let height = block_number(block);
         self.get_logs(
             &ethers::types::Filter::new()
                 .from_block(height)
@@ -150,6 +166,20 @@ impl RpcClient for Provider<Http> {
         })
     }
 
+    async fn receipts_root(
+        &self,
+        block: ethereum_structs::BlockHeight,
+    ) -> Result<KeccakHash, Error> {
+        let height = block_number(block);
+        self.get_block(height)
+            .await
+            .map_err(|error| Error::ReceiptsRoot(height, error.to_string()))?
+            .map(|block| KeccakHash(block.receipts_root.0))
+            .ok_or_else(|| {
+                Error::ReceiptsRoot(height, "Block not found".into())
+            })
+    }
+
     async fn syncing(
         &self,
         last_processed_block: Option<&ethereum_structs::BlockHeight>,
@@ -175,11 +205,22 @@ impl RpcClient for Provider<Http> {
     fn may_recover(&self, error: &Error) -> bool {
         !matches!(
             error,
-            Error::Timeout | Error::Channel(_, _) | Error::CheckEvents(_, _, _)
+            Error::Timeout
+                | Error::Channel(_, _)
+                | Error::CheckEvents(_, _, _)
+                | Error::UnsupportedAbi(_, _)
+                | Error::ReceiptsRoot(_, _)
         )
     }
 }
 
+/// Convert an Ethereum block height to the block number
+/// expected by the RPC endpoint.
+fn block_number(block: ethereum_structs::BlockHeight) -> u64 {
+    let n: Uint256 = block.into();
+    n.0.try_into().expect("Ethereum block number overflow")
+}
+
 /// A client that can talk to geth and parse
 /// and relay events relevant to Namada to the
 /// ledger process
@@ -279,24 +320,28 @@ async fn await_initial_configuration(
 }
 
 /// Set up an Oracle and run the process where the Oracle
-/// processes and forwards Ethereum events to the ledger
+/// processes and forwards Ethereum events to the ledger. The Oracle fails
+/// over between the given endpoints, whose stats are persisted in the given
+/// file.
 pub fn run_oracle<C: RpcClient>(
-    url: impl AsRef<str>,
+    urls: Vec<String>,
+    stats_path: PathBuf,
     sender: BoundedSender<EthereumEvent>,
     control: control::Receiver,
     last_processed_block: last_processed_block::Sender,
     spawner: &mut AbortableSpawner,
 ) -> tokio::task::JoinHandle<()> {
-    let url = url.as_ref().to_owned();
     let blocking_handle = tokio::task::spawn_blocking(move || {
         let rt = tokio::runtime::Handle::current();
         rt.block_on(async move {
             LocalSet::new()
                 .run_until(async move {
-                    tracing::info!(?url, "Ethereum event oracle is starting");
+                    tracing::info!(?urls, "Ethereum event oracle is starting");
 
-                    let oracle = Oracle::<C>::new(
-                        Either::Right(&url),
+                    let client =
+                        Endpoints::<C>::new(urls.clone(), Some(stats_path));
+                    let oracle = Oracle::new(
+                        Either::Left(client),
                         sender,
                         last_processed_block,
                         DEFAULT_BACKOFF,
@@ -306,7 +351,7 @@ pub fn run_oracle<C: RpcClient>(
                     run_oracle_aux(oracle).await;
 
                     tracing::info!(
-                        ?url,
+                        ?urls,
                         "Ethereum event oracle is no longer running"
                     );
                 })
@@ -466,6 +511,8 @@ async fn process_events_in_block<C: RpcClient>(
 ) -> Result<(), Error> {
     let mut queue: Vec<PendingEvent> = vec![];
     let pending = &mut queue;
+    // the hashes of the events of this block that were sent to the shell
+    let mut sent: Vec<Hash> = vec![];
     // update the latest block height
 
     let last_processed_block_ref = oracle.last_processed_block.borrow();
@@ -499,82 +546,120 @@ async fn process_events_in_block<C: RpcClient>(
     );
     // check for events in Ethereum blocks that have reached the minimum number
     // of confirmations
-    for codec in event_codecs() {
-        let sig = codec.event_signature();
-        let addr: Address = match codec.kind() {
-            EventKind::Bridge => config.bridge_contract.into(),
-        };
-        tracing::debug!(
-            ?block_to_process,
-            ?addr,
-            ?sig,
-            "Checking for bridge events"
-        );
-        // fetch the events for matching the given signature
-        let mut events = {
-            let logs = oracle
-                .client
-                .check_events_in_block(block_to_process.clone(), addr, &sig)
-                .await?;
-            if !logs.is_empty() {
-                tracing::info!(
-                    ?block_to_process,
-                    ?addr,
-                    ?sig,
-                    n_events = logs.len(),
-                    "Found bridge events in Ethereum block"
-                )
+    for contract in config.bridge_contracts() {
+        let addr: Address = contract.address.into();
+        // decode the events with the ABI of the contract's version
+        let Some(codecs) = abi::bridge_event_codecs(contract.version) else {
+            let version = u64::from(contract.version);
+            if contract.address == config.bridge_contract {
+                return Err(Error::UnsupportedAbi(version, addr));
             }
-            logs.into_iter()
-                .map(IntoEthAbiLog::into_ethabi_log)
-                .filter_map(|log| {
-                    match PendingEvent::decode(
-                        codec,
-                        block_to_process.clone().into(),
-                        &log,
-                        u64::from(config.min_confirmations).into(),
-                    ) {
-                        Ok(event) => Some(event),
-                        Err(error) => {
-                            tracing::error!(
-                                ?error,
-                                ?block_to_process,
-                                ?addr,
-                                ?sig,
-                                "Couldn't decode event: {:#?}",
-                                log
-                            );
-                            None
-                        }
-                    }
-                })
-                .collect()
-        };
-        pending.append(&mut events);
-        if !pending.is_empty() {
-            tracing::info!(
+            // an unknown ABI of the next contract must not halt the
+            // relaying of the events of the current contract
+            tracing::warn!(
                 ?block_to_process,
                 ?addr,
-                ?sig,
-                pending = pending.len(),
-                "There are Ethereum events pending"
+                version,
+                "Skipping the events of the next bridge contract, its ABI \
+                 version is unsupported"
             );
-        }
-        let confirmed = process_queue(&latest_block, pending);
-        if !confirmed.is_empty() {
-            tracing::info!(
+            continue;
+        };
+        for codec in codecs {
+            let sig = codec.event_signature();
+            tracing::debug!(
                 ?block_to_process,
                 ?addr,
                 ?sig,
-                pending = pending.len(),
-                confirmed = confirmed.len(),
-                min_confirmations = ?config.min_confirmations,
-                "Some events that have reached the minimum number of \
-                 confirmations and will be sent onwards"
+                "Checking for bridge events"
             );
+            // fetch the events for matching the given signature
+            let mut events = {
+                let logs = oracle
+                    .client
+                    .check_events_in_block(block_to_process.clone(), addr, &sig)
+                    .await?;
+                if !logs.is_empty() {
+                    tracing::info!(
+                        ?block_to_process,
+                        ?addr,
+                        ?sig,
+                        n_events = logs.len(),
+                        "Found bridge events in Ethereum block"
+                    )
+                }
+                logs.into_iter()
+                    .map(IntoEthAbiLog::into_ethabi_log)
+                    .filter_map(|log| {
+                        match PendingEvent::decode(
+                            codec,
+                            block_to_process.clone().into(),
+                            &log,
+                            u64::from(config.min_confirmations).into(),
+                        ) {
+                            Ok(event) => Some(event),
+                            Err(error) => {
+                                tracing::error!(
+                                    ?error,
+                                    ?block_to_process,
+                                    ?addr,
+                                    ?sig,
+                                    "Couldn't decode event: {:#?}",
+                                    log
+                                );
+                                None
+                            }
+                        }
+                    })
+                    .collect()
+            };
+            pending.append(&mut events);
+            if !pending.is_empty() {
+                tracing::info!(
+                    ?block_to_process,
+                    ?addr,
+                    ?sig,
+                    pending = pending.len(),
+                    "There are Ethereum events pending"
+                );
+            }
+            let confirmed = process_queue(&latest_block, pending);
+            if !confirmed.is_empty() {
+                tracing::info!(
+                    ?block_to_process,
+                    ?addr,
+                    ?sig,
+                    pending = pending.len(),
+                    confirmed = confirmed.len(),
+                    min_confirmations = ?config.min_confirmations,
+                    "Some events that have reached the minimum number of \
+                     confirmations and will be sent onwards"
+                );
+            }
+            sent.extend(confirmed.iter().filter_map(|event| event.hash().ok()));
+            if !oracle.send(confirmed).await {
+                return Err(Error::Channel(sig.into(), addr));
+            }
         }
-        if !oracle.send(confirmed).await {
-            return Err(Error::Channel(sig.into(), addr));
+    }
+    // vote on the receipts root of the block, such that the receipts
+    // of its events can later be proven against the ledger's storage
+    if !sent.is_empty() {
+        sent.sort();
+        let receipts_root = oracle
+            .client
+            .receipts_root(block_to_process.clone())
+            .await?;
+        let event = EthereumEvent::ReceiptsRoot {
+            block_height: Uint::from(block_number(block_to_process.clone())),
+            receipts_root,
+            events: sent,
+        };
+        if !oracle.send(vec![event]).await {
+            return Err(Error::Channel(
+                "receipts root".into(),
+                config.bridge_contract.into(),
+            ));
         }
     }
     Ok(())
@@ -622,6 +707,9 @@ mod test_oracle {
 
     use ethbridge_bridge_events::{TransferToChainFilter, TransferToErcFilter};
     use namada::eth_bridge::ethers::types::H160;
+    use namada::eth_bridge::storage::parameters::{
+        ContractVersion, UpgradeableContract,
+    };
     use namada::eth_bridge::structs::Erc20Transfer;
     use namada::types::address::testing::gen_established_address;
     use namada::types::ethereum_events::{EthAddress, TransferToEthereum};
@@ -632,7 +720,8 @@ mod test_oracle {
     use super::*;
     use crate::node::ledger::ethereum_oracle::test_tools::event_log::GetLog;
     use crate::node::ledger::ethereum_oracle::test_tools::mock_web3_client::{
-        event_signature, TestCmd, TestOracle, Web3Client, Web3Controller,
+        event_signature, mock_receipts_root, TestCmd, TestOracle, Web3Client,
+        Web3Controller,
     };
 
     /// The data returned from setting up a test
@@ -692,6 +781,29 @@ mod test_oracle {
         }
     }
 
+    /// Check that the next event sent by the oracle is the receipts root of
+    /// the Ethereum block at the given height, covering the given events
+    async fn expect_receipts_root(
+        eth_recv: &mut tokio::sync::mpsc::Receiver<EthereumEvent>,
+        height: u64,
+        events: &[EthereumEvent],
+    ) {
+        let mut hashes: Vec<Hash> = events
+            .iter()
+            .map(|event| event.hash().expect("Test failed"))
+            .collect();
+        hashes.sort();
+        let event = eth_recv.recv().await.expect("Test failed");
+        assert_eq!(
+            event,
+            EthereumEvent::ReceiptsRoot {
+                block_height: height.into(),
+                receipts_root: mock_receipts_root(&height.into()),
+                events: hashes,
+            }
+        );
+    }
+
     /// Test that if the fullnode stops, the oracle
     /// shuts down, even if the web3 client is unresponsive
     #[tokio::test]
@@ -911,12 +1023,14 @@ mod test_oracle {
         controller.apply_cmd(TestCmd::NewHeight(Uint256::from(200u32)));
         // check the correct event is received
         let event = eth_recv.recv().await.expect("Test failed");
-        if let EthereumEvent::TransfersToNamada { nonce, transfers } = event {
-            assert_eq!(nonce, 0.into());
+        if let EthereumEvent::TransfersToNamada { nonce, transfers } = &event {
+            assert_eq!(*nonce, 0.into());
             assert!(transfers.is_empty());
         } else {
             panic!("Test failed, {:?}", event);
         }
+        // followed by the receipts root of its block
+        expect_receipts_root(&mut eth_recv, 100, &[event]).await;
 
         // check no other events are received
         let mut time = std::time::Duration::from_secs(1);
@@ -933,12 +1047,10 @@ mod test_oracle {
         controller.apply_cmd(TestCmd::NewHeight(Uint256::from(250u32)));
         // check correct event is received
         let event = eth_recv.recv().await.expect("Test failed");
-        if let EthereumEvent::TransfersToEthereum { mut transfers, .. } = event
-        {
+        if let EthereumEvent::TransfersToEthereum { transfers, .. } = &event {
             assert_eq!(transfers.len(), 1);
-            let transfer = transfers.remove(0);
             assert_eq!(
-                transfer,
+                transfers[0],
                 TransferToEthereum {
                     amount: Default::default(),
                     asset: EthAddress([0; 20]),
@@ -949,6 +1061,7 @@ mod test_oracle {
         } else {
             panic!("Test failed");
         }
+        expect_receipts_root(&mut eth_recv, 125, &[event]).await;
 
         drop(eth_recv);
         oracle.await.expect("Test failed");
@@ -1186,4 +1299,148 @@ mod test_oracle {
         drop(eth_recv);
         oracle.await.expect("Test failed");
     }
+
+    /// Send a [`TransferToChainFilter`] event with the given nonce, emitted
+    /// by the contract at `addr`
+    fn new_contract_event(
+        controller: &Web3Controller,
+        addr: EthAddress,
+        nonce: u64,
+        height: u32,
+    ) -> tokio::sync::oneshot::Receiver<()> {
+        let log = TransferToChainFilter {
+            nonce: nonce.into(),
+            transfers: vec![],
+            confirmations: 100.into(),
+        }
+        .get_log();
+        let (sender, seen) = channel();
+        controller.apply_cmd(TestCmd::NewContractEvent {
+            addr: addr.into(),
+            event_type: event_signature::<TransferToChainFilter>(),
+            log,
+            height,
+            seen: sender,
+        });
+        seen
+    }
+
+    /// Test that the events of both the current and the next bridge contract
+    /// are relayed during the migration window of a bridge contract upgrade
+    #[tokio::test]
+    async fn test_next_bridge_contract_events() {
+        let TestPackage {
+            oracle,
+            mut eth_recv,
+            controller,
+            blocks_processed_recv: _processed,
+            mut control_sender,
+        } = setup();
+        let min_confirmations = 100;
+        let current = EthAddress([1; 20]);
+        let next = EthAddress([2; 20]);
+        let config = Config {
+            min_confirmations: NonZeroU64::try_from(min_confirmations)
+                .expect("Test wasn't set up correctly"),
+            bridge_contract: current,
+            next_bridge_contract: Some(UpgradeableContract {
+                address: next,
+                version: ContractVersion::default(),
+            }),
+            ..Config::default()
+        };
+        let oracle =
+            start_with_default_config(oracle, &mut control_sender, config)
+                .await;
+        controller.apply_cmd(TestCmd::NewHeight(min_confirmations.into()));
+
+        let seen_current = new_contract_event(&controller, current, 0, 100);
+        let seen_next = new_contract_event(&controller, next, 1, 100);
+        controller.apply_cmd(TestCmd::NewHeight(Uint256::from(200u32)));
+        seen_current.await.expect("Test failed");
+        seen_next.await.expect("Test failed");
+
+        let mut events = vec![];
+        let mut nonces = vec![];
+        for _ in 0..2 {
+            let event = eth_recv.recv().await.expect("Test failed");
+            match &event {
+                EthereumEvent::TransfersToNamada { nonce, .. } => {
+                    nonces.push(*nonce)
+                }
+                event => panic!("Test failed, {:?}", event),
+            }
+            events.push(event);
+        }
+        nonces.sort();
+        assert_eq!(nonces, vec![0.into(), 1.into()]);
+        // a single receipts root covers the events of both contracts
+        expect_receipts_root(&mut eth_recv, 100, &events).await;
+
+        drop(eth_recv);
+        oracle.await.expect("Test failed");
+    }
+
+    /// Test that a next bridge contract with an unsupported ABI version is
+    /// skipped, while the events of the current contract are still relayed
+    #[tokio::test]
+    async fn test_next_bridge_contract_unsupported_abi() {
+        let TestPackage {
+            oracle,
+            mut eth_recv,
+            controller,
+            blocks_processed_recv: _processed,
+            mut control_sender,
+        } = setup();
+        let min_confirmations = 100;
+        let current = EthAddress([1; 20]);
+        let next = EthAddress([2; 20]);
+        let config = Config {
+            min_confirmations: NonZeroU64::try_from(min_confirmations)
+                .expect("Test wasn't set up correctly"),
+            bridge_contract: current,
+            next_bridge_contract: Some(UpgradeableContract {
+                address: next,
+                version: ContractVersion::from(
+                    NonZeroU64::new(u64::MAX).unwrap(),
+                ),
+            }),
+            ..Config::default()
+        };
+        let oracle =
+            start_with_default_config(oracle, &mut control_sender, config)
+                .await;
+        controller.apply_cmd(TestCmd::NewHeight(min_confirmations.into()));
+
+        let mut seen_next = new_contract_event(&controller, next, 0, 100);
+        let seen_current = new_contract_event(&controller, current, 1, 100);
+        controller.apply_cmd(TestCmd::NewHeight(Uint256::from(200u32)));
+        seen_current.await.expect("Test failed");
+        let event = eth_recv.recv().await.expect("Test failed");
+        if let EthereumEvent::TransfersToNamada { nonce, .. } = &event {
+            assert_eq!(*nonce, 1.into());
+        } else {
+            panic!("Test failed, {:?}", event);
+        }
+        expect_receipts_root(&mut eth_recv, 100, &[event]).await;
+
+        // the oracle keeps relaying the events of the current contract
+        let seen_current = new_contract_event(&controller, current, 2, 250);
+        controller.apply_cmd(TestCmd::NewHeight(Uint256::from(350u32)));
+        seen_current.await.expect("Test failed");
+        let event = eth_recv.recv().await.expect("Test failed");
+        if let EthereumEvent::TransfersToNamada { nonce, .. } = &event {
+            assert_eq!(*nonce, 2.into());
+        } else {
+            panic!("Test failed, {:?}", event);
+        }
+        expect_receipts_root(&mut eth_recv, 250, &[event]).await;
+
+        // the events of the next contract are never fetched
+        assert!(seen_next.try_recv().is_err());
+        assert!(eth_recv.try_recv().is_err());
+
+        drop(eth_recv);
+        oracle.await.expect("Test failed");
+    }
 }
@@ -69,6 +69,7 @@ pub mod mock_web3_client {
     use ethbridge_events::EventCodec;
     use namada::types::control_flow::time::{Duration, Instant};
     use namada::types::ethereum_structs::BlockHeight;
+    use namada::types::keccak::{keccak_hash, KeccakHash};
     use num256::Uint256;
     use tokio::sync::mpsc::{
         unbounded_channel, UnboundedReceiver, UnboundedSender,
@@ -93,8 +94,25 @@ pub mod mock_web3_client {
             height: u32,
             seen: Sender<()>,
         },
+        /// An event emitted by the contract at the given address only
+        NewContractEvent {
+            addr: Address,
+            event_type: MockEventType,
+            log: ethabi::RawLog,
+            height: u32,
+            seen: Sender<()>,
+        },
     }
 
+    /// A mock event, optionally restricted to the contract that emitted it
+    type MockEvent = (
+        Option<Address>,
+        MockEventType,
+        ethabi::RawLog,
+        u32,
+        Sender<()>,
+    );
+
     /// The type of events supported
     pub type MockEventType = Cow<'static, str>;
 
@@ -120,7 +138,14 @@ pub mod mock_web3_client {
                     log,
                     height,
                     seen,
-                } => oracle.events.push((ty, log, height, seen)),
+                } => oracle.events.push((None, ty, log, height, seen)),
+                TestCmd::NewContractEvent {
+                    addr,
+                    event_type: ty,
+                    log,
+                    height,
+                    seen,
+                } => oracle.events.push((Some(addr), ty, log, height, seen)),
             }
         }
     }
@@ -139,7 +164,7 @@ pub mod mock_web3_client {
     pub struct Web3ClientInner {
         active: bool,
         latest_block_height: Uint256,
-        events: Vec<(MockEventType, ethabi::RawLog, u32, Sender<()>)>,
+        events: Vec<MockEvent>,
         blocks_processed: UnboundedSender<Uint256>,
         last_block_processed: Option<Uint256>,
     }
@@ -171,13 +196,20 @@ pub mod mock_web3_client {
                 let mut logs = vec![];
                 let mut events = vec![];
                 std::mem::swap(&mut client.events, &mut events);
-                for (event_ty, log, height, seen) in events.into_iter() {
-                    if event_ty == ty && block_to_check >= Uint256::from(height)
+                for (event_addr, event_ty, log, height, seen) in
+                    events.into_iter()
+                {
+                    if event_ty == ty
+                        && event_addr
+                            .map_or(true, |event_addr| event_addr == addr)
+                        && block_to_check >= Uint256::from(height)
                     {
                         seen.send(()).unwrap();
                         logs.push(log);
                     } else {
-                        client.events.push((event_ty, log, height, seen));
+                        client
+                            .events
+                            .push((event_addr, event_ty, log, height, seen));
                     }
                 }
                 if client.last_block_processed.as_ref() < Some(&block_to_check)
@@ -199,6 +231,13 @@ pub mod mock_web3_client {
             }
         }
 
+        async fn receipts_root(
+            &self,
+            block: BlockHeight,
+        ) -> Result<KeccakHash, Error> {
+            Ok(mock_receipts_root(&block))
+        }
+
         async fn syncing(
             &self,
             _: Option<&BlockHeight>,
@@ -239,6 +278,11 @@ pub mod mock_web3_client {
         }
     }
 
+    /// The receipts root the mock client reports for the given block.
+    pub fn mock_receipts_root(block: &BlockHeight) -> KeccakHash {
+        keccak_hash(block.to_string())
+    }
+
     /// Get the signature of the given Ethereum event.
     pub fn event_signature<C>() -> Cow<'static, str>
     where
@@ -0,0 +1,476 @@
+//! Calibration of the gas costs on the local machine.
+//!
+//! A suite of micro-WASMs, each exercising one kind of operation in a loop,
+//! is run as txs on a temporary DB. Every benchmark runs once with no
+//! iterations and once with the requested number of iterations, so that the
+//! costs of compiling, loading and instantiating its code cancel out and only
+//! the time and the gas of the operation itself remain.
+//!
+//! The time per gas unit of every benchmark is compared to the median over
+//! all the benchmarks that are charged gas. The suggested value of a cost
+//! parameter is the one that brings the time per gas unit of its benchmark to
+//! the median. As the wasm instructions aren't charged gas, their suggested
+//! cost is derived from the time of the instructions in the arithmetic
+//! benchmark instead.
+//!
+//! The results depend on the machine and on the state of its caches, so they
+//! are meant to support the decisions on the gas parameters, rather than to
+//! be applied as they are.
+
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use namada::gas::{
+    GasMetering, TxGasMeter, MEMORY_ACCESS_GAS_PER_BYTE,
+    STORAGE_ACCESS_GAS_PER_BYTE, STORAGE_WRITE_GAS_PER_BYTE,
+    WASM_MEMORY_PAGE_GAS,
+};
+use namada::state::write_log::WriteLog;
+use namada::tx::data::TxType;
+use namada::tx::{Code, Tx};
+use namada::types::address;
+use namada::types::chain::ChainId;
+use namada::types::storage::{Key, TxIndex};
+use namada::vm::wasm::memory::{TX_MEMORY_INIT_PAGES, TX_MEMORY_MAX_PAGES};
+use namada::vm::wasm::{run, TxCache, VpCache};
+use namada::vm::WasmCacheRwAccess;
+use serde::Serialize;
+use thiserror::Error;
+
+use super::storage::PersistentStorage;
+
+/// The size of the compilation caches of the benchmarks, in bytes
+const CACHE_SIZE: usize = 50 * 1024 * 1024;
+/// The key the number of iterations is read from by the benchmarks
+const ITERATIONS_KEY: &str = "calibration/iterations";
+/// The key that's read and written by the storage benchmarks
+const VALUE_KEY: &str = "calibration/value";
+/// The size of the value read and written by the benchmarks, in bytes
+const VALUE_LEN: usize = 1024;
+/// The offset in the memory of the benchmarks of the keys they access. The
+/// tx is written at the start of the memory, so the data is placed far from
+/// it.
+const DATA_OFFSET: u64 = 4 * 1024 * 1024;
+/// The offset in the memory of the buffer of the values read by the
+/// benchmarks
+const BUFFER_OFFSET: u64 = DATA_OFFSET + 1024;
+/// The number of times the arithmetic benchmark repeats its operation in
+/// every iteration
+const ARITHMETIC_UNROLL: usize = 8;
+/// The number of wasm instructions in an iteration of the arithmetic
+/// benchmark: 6 for each operation and 9 for the loop
+const ARITHMETIC_INSTRUCTIONS: u64 = 6 * ARITHMETIC_UNROLL as u64 + 9;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Failed to build the wasm of the benchmark {0}: {1}")]
+    Wat(&'static str, wat::Error),
+    #[error("The benchmark {0} failed: {1}")]
+    Run(&'static str, run::Error),
+    #[error("Failed to set up the storage of the benchmarks: {0}")]
+    Storage(String),
+    #[error("Failed to write the suggested costs: {0}")]
+    Output(String),
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// The cost parameters that are calibrated
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct CostTable {
+    pub wasm_instruction_gas: u64,
+    pub wasm_memory_page_gas: u64,
+    pub memory_access_gas_per_byte: u64,
+    pub storage_access_gas_per_byte: u64,
+    pub storage_write_gas_per_byte: u64,
+}
+
+impl CostTable {
+    /// The costs currently charged by the protocol
+    pub fn current() -> Self {
+        Self {
+            wasm_instruction_gas: 0,
+            wasm_memory_page_gas: WASM_MEMORY_PAGE_GAS as u64,
+            memory_access_gas_per_byte: MEMORY_ACCESS_GAS_PER_BYTE,
+            storage_access_gas_per_byte: STORAGE_ACCESS_GAS_PER_BYTE,
+            storage_write_gas_per_byte: STORAGE_WRITE_GAS_PER_BYTE,
+        }
+    }
+
+    /// The names and the values of the costs
+    pub fn entries(&self) -> impl Iterator<Item = (&'static str, u64)> + '_ {
+        Param::ALL
+            .into_iter()
+            .map(|param| (param.name(), self.get(param)))
+    }
+
+    fn get(&self, param: Param) -> u64 {
+        match param {
+            Param::WasmInstruction => self.wasm_instruction_gas,
+            Param::WasmMemoryPage => self.wasm_memory_page_gas,
+            Param::MemoryAccess => self.memory_access_gas_per_byte,
+            Param::StorageAccess => self.storage_access_gas_per_byte,
+            Param::StorageWrite => self.storage_write_gas_per_byte,
+        }
+    }
+
+    fn get_mut(&mut self, param: Param) -> &mut u64 {
+        match param {
+            Param::WasmInstruction => &mut self.wasm_instruction_gas,
+            Param::WasmMemoryPage => &mut self.wasm_memory_page_gas,
+            Param::MemoryAccess => &mut self.memory_access_gas_per_byte,
+            Param::StorageAccess => &mut self.storage_access_gas_per_byte,
+            Param::StorageWrite => &mut self.storage_write_gas_per_byte,
+        }
+    }
+}
+
+/// A cost parameter calibrated by a benchmark
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Param {
+    WasmInstruction,
+    WasmMemoryPage,
+    MemoryAccess,
+    StorageAccess,
+    StorageWrite,
+}
+
+impl Param {
+    const ALL: [Param; 5] = [
+        Param::WasmInstruction,
+        Param::WasmMemoryPage,
+        Param::MemoryAccess,
+        Param::StorageAccess,
+        Param::StorageWrite,
+    ];
+
+    /// The name of the parameter in the cost table
+    fn name(self) -> &'static str {
+        match self {
+            Param::WasmInstruction => "wasm_instruction_gas",
+            Param::WasmMemoryPage => "wasm_memory_page_gas",
+            Param::MemoryAccess => "memory_access_gas_per_byte",
+            Param::StorageAccess => "storage_access_gas_per_byte",
+            Param::StorageWrite => "storage_write_gas_per_byte",
+        }
+    }
+}
+
+/// A micro-WASM benchmark
+struct Benchmark {
+    name: &'static str,
+    param: Param,
+    /// The body of the loop, with the locals `$i`, `$n` and `$x` in scope
+    body: String,
+    /// The max number of iterations, if limited
+    max_iterations: Option<u64>,
+}
+
+/// The suite of benchmarks
+fn benchmarks() -> Vec<Benchmark> {
+    let key_ptr = DATA_OFFSET + ITERATIONS_KEY.len() as u64;
+    let key_len = VALUE_KEY.len();
+    let read_value = format!(
+        "(drop (call $read (i64.const {key_ptr}) (i64.const {key_len})))
+         (call $result_buffer (i64.const {BUFFER_OFFSET}))"
+    );
+    vec![
+        Benchmark {
+            name: "arithmetic",
+            param: Param::WasmInstruction,
+            body: "(local.set $x (i64.add (i64.mul (local.get $x) (i64.const \
+                   6364136223846793005)) (i64.const 1442695040888963407)))"
+                .repeat(ARITHMETIC_UNROLL),
+            max_iterations: None,
+        },
+        Benchmark {
+            name: "memory_grow",
+            param: Param::WasmMemoryPage,
+            body: "(drop (memory.grow (i32.const 1)))".to_string(),
+            max_iterations: Some(
+                (TX_MEMORY_MAX_PAGES - TX_MEMORY_INIT_PAGES) as u64,
+            ),
+        },
+        Benchmark {
+            name: "memory_access",
+            param: Param::MemoryAccess,
+            body: read_value.clone(),
+            max_iterations: None,
+        },
+        Benchmark {
+            name: "storage_read",
+            param: Param::StorageAccess,
+            body: read_value,
+            max_iterations: None,
+        },
+        Benchmark {
+            name: "storage_write",
+            param: Param::StorageWrite,
+            body: format!(
+                "(call $write (i64.const {key_ptr}) (i64.const {key_len}) \
+                 (i64.const {BUFFER_OFFSET}) (i64.const {VALUE_LEN}))"
+            ),
+            max_iterations: None,
+        },
+    ]
+}
+
+impl Benchmark {
+    /// The wasm code of the benchmark. It reads the number of iterations from
+    /// storage, so that the same code runs with any number of iterations.
+    fn wasm(&self) -> Result<Vec<u8>> {
+        let body = &self.body;
+        let iterations_key_len = ITERATIONS_KEY.len();
+        let wat = format!(
+            r#"(module
+  (import "env" "memory" (memory {TX_MEMORY_INIT_PAGES}))
+  (import "env" "namada_tx_read" (func $read (param i64 i64) (result i64)))
+  (import "env" "namada_tx_result_buffer" (func $result_buffer (param i64)))
+  (import "env" "namada_tx_write"
+    (func $write (param i64 i64 i64 i64)))
+  (data (i32.const {DATA_OFFSET}) "{ITERATIONS_KEY}{VALUE_KEY}")
+  (func (export "_apply_tx") (param i64 i64)
+    (local $i i64) (local $n i64) (local $x i64)
+    (drop (call $read (i64.const {DATA_OFFSET}) (i64.const {iterations_key_len})))
+    (call $result_buffer (i64.const {BUFFER_OFFSET}))
+    (local.set $n (i64.load (i32.const {BUFFER_OFFSET})))
+    (block $done
+      (loop $loop
+        (br_if $done (i64.ge_u (local.get $i) (local.get $n)))
+        {body}
+        (local.set $i (i64.add (local.get $i) (i64.const 1)))
+        (br $loop)))))"#
+        );
+        wat::parse_str(wat).map_err(|err| Error::Wat(self.name, err))
+    }
+}
+
+/// The result of a benchmark, per iteration
+#[derive(Clone, Debug, Serialize)]
+pub struct BenchmarkResult {
+    pub name: &'static str,
+    pub iterations: u64,
+    /// The median time of an iteration, in nanoseconds
+    pub time_ns: f64,
+    /// The gas charged for an iteration
+    pub gas: f64,
+    /// The time per gas unit, if any gas was charged
+    pub ns_per_gas: Option<f64>,
+}
+
+/// The report of a calibration
+#[derive(Clone, Debug, Serialize)]
+pub struct Report {
+    /// The median time per gas unit of the benchmarks that are charged gas
+    pub ns_per_gas: f64,
+    pub current: CostTable,
+    pub suggested: CostTable,
+    pub benchmarks: Vec<BenchmarkResult>,
+}
+
+impl Report {
+    /// Write the suggested costs as a TOML file, together with the results
+    /// they're derived from
+    pub fn write_toml(&self, path: &Path) -> Result<()> {
+        let toml = toml::to_string(self)
+            .map_err(|err| Error::Output(err.to_string()))?;
+        std::fs::write(path, toml).map_err(|err| Error::Output(err.to_string()))
+    }
+}
+
+/// The environment the benchmarks run in
+struct Env {
+    storage: PersistentStorage,
+    write_log: WriteLog,
+    vp_cache: VpCache<WasmCacheRwAccess>,
+    tx_cache: TxCache<WasmCacheRwAccess>,
+}
+
+impl Env {
+    fn new(dir: &Path) -> Result<Self> {
+        let mut storage = PersistentStorage::open(
+            dir.join("db"),
+            ChainId::default(),
+            address::nam(),
+            None,
+            None,
+        );
+        let value_key = Key::parse(VALUE_KEY)
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        // The storage benchmark reads the value from the DB, while the memory
+        // benchmark reads it from the write log
+        storage
+            .write(&value_key, vec![1; VALUE_LEN])
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        Ok(Self {
+            storage,
+            write_log: WriteLog::default(),
+            vp_cache: VpCache::new(dir.join("vp_wasm_cache"), CACHE_SIZE),
+            tx_cache: TxCache::new(dir.join("tx_wasm_cache"), CACHE_SIZE),
+        })
+    }
+
+    /// Run the code with the given number of iterations, returning its time
+    /// and the gas it was charged
+    fn run(
+        &mut self,
+        benchmark: &Benchmark,
+        code: &[u8],
+        iterations: u64,
+    ) -> Result<(Duration, u64)> {
+        let storage_err = |err: namada::state::write_log::Error| {
+            Error::Storage(err.to_string())
+        };
+        let value_key = Key::parse(VALUE_KEY)
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        if benchmark.param == Param::MemoryAccess {
+            self.write_log
+                .write(&value_key, vec![1; VALUE_LEN])
+                .map_err(storage_err)?;
+        }
+        let iterations_key = Key::parse(ITERATIONS_KEY)
+            .map_err(|err| Error::Storage(err.to_string()))?;
+        self.write_log
+            .write(&iterations_key, iterations.to_le_bytes().to_vec())
+            .map_err(storage_err)?;
+
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.set_code(Code::new(code.to_vec(), None));
+        let mut gas_meter = TxGasMeter::new_from_sub_limit(u64::MAX.into());
+        let start = Instant::now();
+        let result = run::tx(
+            &self.storage,
+            &mut self.write_log,
+            &mut gas_meter,
+            &TxIndex::default(),
+            &tx,
+            &mut self.vp_cache,
+            &mut self.tx_cache,
+        );
+        let elapsed = start.elapsed();
+        self.write_log.drop_tx();
+        result.map_err(|err| Error::Run(benchmark.name, err))?;
+        Ok((elapsed, gas_meter.get_tx_consumed_gas().into()))
+    }
+
+    /// Run the benchmark the given number of times, returning the median
+    /// time and the gas it was charged
+    fn measure(
+        &mut self,
+        benchmark: &Benchmark,
+        code: &[u8],
+        iterations: u64,
+        runs: u64,
+    ) -> Result<(Duration, u64)> {
+        let mut times = Vec::with_capacity(runs as usize);
+        let mut gas = 0;
+        for _ in 0..runs.max(1) {
+            let (time, run_gas) = self.run(benchmark, code, iterations)?;
+            times.push(time);
+            gas = run_gas;
+        }
+        times.sort();
+        Ok((times[times.len() / 2], gas))
+    }
+}
+
+/// Run the benchmarks with the given number of iterations, each the given
+/// number of times, in a temporary directory
+pub fn calibrate(iterations: u64, runs: u64) -> Result<Report> {
+    let dir =
+        tempfile::tempdir().map_err(|err| Error::Storage(err.to_string()))?;
+    let mut env = Env::new(dir.path())?;
+
+    let suite = benchmarks();
+    let mut results = Vec::with_capacity(suite.len());
+    for benchmark in &suite {
+        let iterations = benchmark
+            .max_iterations
+            .map_or(iterations, |max| iterations.min(max))
+            .max(1);
+        let code = benchmark.wasm()?;
+        // Warm up the compilation cache
+        env.run(benchmark, &code, 0)?;
+        let (base_time, base_gas) = env.measure(benchmark, &code, 0, runs)?;
+        let (time, gas) = env.measure(benchmark, &code, iterations, runs)?;
+
+        let time_ns = time.saturating_sub(base_time).as_nanos() as f64
+            / iterations as f64;
+        let gas = gas.saturating_sub(base_gas) as f64 / iterations as f64;
+        results.push(BenchmarkResult {
+            name: benchmark.name,
+            iterations,
+            time_ns,
+            gas,
+            ns_per_gas: (gas > 0.0).then_some(time_ns / gas),
+        });
+    }
+
+    let ns_per_gas = median(results.iter().filter_map(|res| res.ns_per_gas));
+    let current = CostTable::current();
+    let mut suggested = current;
+    for (benchmark, result) in suite.iter().zip(&results) {
+        let value = match result.ns_per_gas {
+            Some(bench_ns_per_gas) => {
+                current.get(benchmark.param) as f64 * bench_ns_per_gas
+                    / ns_per_gas
+            }
+            None if benchmark.param == Param::WasmInstruction => {
+                result.time_ns / ARITHMETIC_INSTRUCTIONS as f64 / ns_per_gas
+            }
+            None => continue,
+        };
+        if value.is_finite() {
+            *suggested.get_mut(benchmark.param) = value.round() as u64;
+        }
+    }
+
+    Ok(Report {
+        ns_per_gas,
+        current,
+        suggested,
+        benchmarks: results,
+    })
+}
+
+/// The median of the values, or zero if there are none
+fn median(values: impl Iterator<Item = f64>) -> f64 {
+    let mut values: Vec<f64> = values.collect();
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    values[values.len() / 2]
+}
+
+#[cfg(test)]
+mod test_gas_calibration {
+    use super::*;
+
+    #[test]
+    fn test_calibrate() {
+        let report = calibrate(10, 1).unwrap();
+        let charged = |name: &str| {
+            report
+                .benchmarks
+                .iter()
+                .find(|res| res.name == name)
+                .unwrap()
+                .gas
+        };
+        // The wasm instructions aren't charged
+        assert_eq!(charged("arithmetic"), 0.0);
+        assert_eq!(charged("memory_grow"), WASM_MEMORY_PAGE_GAS as f64);
+        assert!(charged("memory_access") > 0.0);
+        assert!(charged("storage_read") > charged("memory_access"));
+        assert!(charged("storage_write") > 0.0);
+        assert_eq!(report.current, CostTable::current());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("gas_costs.toml");
+        report.write_toml(&path).unwrap();
+        let written: toml::Value =
+            toml::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert!(written["suggested"]["storage_write_gas_per_byte"].is_integer());
+    }
+}
@@ -0,0 +1,279 @@
+//! HTTP endpoints for the health and readiness checks of the node, that
+//! orchestrators and load balancers can use to gate the traffic to it.
+//!
+//! - `/health` responds with `200 OK` when the node is running and its CometBFT
+//!   node responds to RPC requests.
+//! - `/ready` additionally requires that CometBFT isn't catching up, that the
+//!   state of the ledger is within the configured number of blocks of the
+//!   latest block of CometBFT and, if the Ethereum bridge is active and the
+//!   node runs an oracle, that the oracle is still processing Ethereum blocks.
+//!
+//! Failed checks respond with `503 Service Unavailable`. Both endpoints
+//! respond with a JSON body with the result of each check.
+
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use namada::eth_bridge::storage::active_key;
+use namada::types::storage::Epoch;
+use namada_sdk::eth_bridge::{EthBridgeEnabled, EthBridgeStatus};
+use serde::Serialize;
+use warp::http::StatusCode;
+use warp::Filter;
+
+use super::ethereum_oracle::last_processed_block;
+use crate::facade::tendermint_rpc::{Client, HttpClient};
+
+/// The number of blocks the ledger can be behind the latest block of
+/// CometBFT to be ready, when not configured
+pub const DEFAULT_MAX_BLOCKS_BEHIND: u64 = 2;
+
+/// How long the oracle can go without processing an Ethereum block before
+/// it's considered disconnected from its Ethereum node
+const ORACLE_IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// The shared state of the endpoints
+struct State {
+    client: HttpClient,
+    max_blocks_behind: u64,
+    oracle: Option<Mutex<OracleProgress>>,
+}
+
+/// The progress of the Ethereum oracle, seen through the blocks it processes
+struct OracleProgress {
+    last_processed_block: last_processed_block::Receiver,
+    last_progress: Instant,
+}
+
+impl OracleProgress {
+    /// Check if the oracle has processed a block recently. The oracle is
+    /// disconnected if it stopped running.
+    fn is_connected(&mut self, now: Instant) -> bool {
+        match self.last_processed_block.has_changed() {
+            Ok(true) => {
+                self.last_processed_block.borrow_and_update();
+                self.last_progress = now;
+                true
+            }
+            Ok(false) => {
+                now.duration_since(self.last_progress) <= ORACLE_IDLE_TIMEOUT
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+/// The result of the health checks
+#[derive(Debug, Serialize)]
+struct Health {
+    tendermint_connected: bool,
+}
+
+/// The result of the readiness checks
+#[derive(Debug, Default, Serialize)]
+struct Readiness {
+    tendermint_connected: bool,
+    catching_up: bool,
+    latest_block_height: Option<u64>,
+    ledger_block_height: Option<u64>,
+    /// Only checked when the node runs an oracle
+    bridge_active: Option<bool>,
+    /// Only checked when the bridge is active
+    oracle_connected: Option<bool>,
+}
+
+impl Readiness {
+    fn is_ready(&self, max_blocks_behind: u64) -> bool {
+        let caught_up =
+            match (self.latest_block_height, self.ledger_block_height) {
+                (Some(latest), Some(ledger)) => {
+                    latest.saturating_sub(ledger) <= max_blocks_behind
+                }
+                _ => false,
+            };
+        self.tendermint_connected
+            && !self.catching_up
+            && caught_up
+            && self.oracle_connected.unwrap_or(true)
+    }
+}
+
+/// Serve the endpoints on the given address until the abort signal is
+/// received. The oracle's receiver is only given when the node runs an
+/// oracle.
+pub async fn serve(
+    laddr: SocketAddr,
+    rpc_address: SocketAddr,
+    max_blocks_behind: u64,
+    oracle: Option<last_processed_block::Receiver>,
+    abort_recv: tokio::sync::oneshot::Receiver<()>,
+) {
+    let client = HttpClient::new(format!("http://{}", rpc_address).as_str())
+        .expect("Should be able to create the CometBFT RPC client");
+    let state = Arc::new(State {
+        client,
+        max_blocks_behind,
+        oracle: oracle.map(|last_processed_block| {
+            Mutex::new(OracleProgress {
+                last_processed_block,
+                last_progress: Instant::now(),
+            })
+        }),
+    });
+    let server = warp::serve(routes(state)).try_bind_with_graceful_shutdown(
+        laddr,
+        async move {
+            let _ = abort_recv.await;
+        },
+    );
+    match server {
+        Ok((laddr, server)) => {
+            tracing::info!(?laddr, "Serving the health and readiness checks");
+            server.await;
+        }
+        Err(err) => {
+            tracing::error!(
+                ?laddr,
+                "Failed to serve the health and readiness checks: {err}"
+            );
+        }
+    }
+}
+
+fn routes(
+    state: Arc<State>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_state = warp::any().map(move || state.clone());
+    let health = warp::get()
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .and(with_state.clone())
+        .then(|state: Arc<State>| async move {
+            let health = Health {
+                tendermint_connected: state.client.status().await.is_ok(),
+            };
+            let status = if health.tendermint_connected {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            };
+            warp::reply::with_status(warp::reply::json(&health), status)
+        });
+    let ready = warp::get()
+        .and(warp::path("ready"))
+        .and(warp::path::end())
+        .and(with_state)
+        .then(|state: Arc<State>| async move {
+            let readiness = check_readiness(&state).await;
+            let status = if readiness.is_ready(state.max_blocks_behind) {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            };
+            warp::reply::with_status(warp::reply::json(&readiness), status)
+        });
+    health.or(ready)
+}
+
+async fn check_readiness(state: &State) -> Readiness {
+    let mut readiness = Readiness::default();
+    let status = match state.client.status().await {
+        Ok(status) => status,
+        Err(_) => return readiness,
+    };
+    readiness.tendermint_connected = true;
+    readiness.catching_up = status.sync_info.catching_up;
+    readiness.latest_block_height =
+        Some(status.sync_info.latest_block_height.value());
+    readiness.ledger_block_height = state
+        .client
+        .abci_info()
+        .await
+        .ok()
+        .map(|info| info.last_block_height.value());
+
+    if let Some(oracle) = &state.oracle {
+        let bridge_active = is_bridge_active(&state.client).await;
+        readiness.bridge_active = bridge_active;
+        if bridge_active.unwrap_or_default() {
+            let mut oracle = oracle.lock().unwrap();
+            readiness.oracle_connected =
+                Some(oracle.is_connected(Instant::now()));
+        }
+    }
+    readiness
+}
+
+/// Query if the Ethereum bridge is active in the current epoch
+async fn is_bridge_active(client: &HttpClient) -> Option<bool> {
+    let status: EthBridgeStatus =
+        namada_sdk::rpc::query_storage_value(client, &active_key())
+            .await
+            .ok()?;
+    Some(match status {
+        EthBridgeStatus::Disabled => false,
+        EthBridgeStatus::Enabled(EthBridgeEnabled::AtGenesis) => true,
+        EthBridgeStatus::Enabled(EthBridgeEnabled::AtEpoch(epoch)) => {
+            let current: Epoch =
+                namada_sdk::rpc::query_epoch(client).await.ok()?;
+            current >= epoch
+        }
+    })
+}
+
+#[cfg(test)]
+mod test_health {
+    use super::*;
+
+    #[test]
+    fn test_readiness() {
+        let ready = Readiness {
+            tendermint_connected: true,
+            catching_up: false,
+            latest_block_height: Some(10),
+            ledger_block_height: Some(8),
+            bridge_active: None,
+            oracle_connected: None,
+        };
+        assert!(ready.is_ready(2));
+        assert!(!ready.is_ready(1));
+        assert!(!Readiness {
+            catching_up: true,
+            ..ready
+        }
+        .is_ready(2));
+        assert!(!Readiness {
+            ledger_block_height: None,
+            ..ready
+        }
+        .is_ready(2));
+        assert!(!Readiness {
+            bridge_active: Some(true),
+            oracle_connected: Some(false),
+            ..ready
+        }
+        .is_ready(2));
+        assert!(!Readiness::default().is_ready(2));
+    }
+
+    #[test]
+    fn test_oracle_progress() {
+        let (sender, receiver) = last_processed_block::channel();
+        let start = Instant::now();
+        let mut progress = OracleProgress {
+            last_processed_block: receiver,
+            last_progress: start,
+        };
+        assert!(progress.is_connected(start));
+        let idle = start + ORACLE_IDLE_TIMEOUT + Duration::from_secs(1);
+        assert!(!progress.is_connected(idle));
+
+        sender.send_replace(Some(1u64.into()));
+        assert!(progress.is_connected(idle));
+        assert!(progress.is_connected(idle + ORACLE_IDLE_TIMEOUT));
+
+        drop(sender);
+        assert!(!progress.is_connected(idle));
+    }
+}
@@ -0,0 +1,222 @@
+//! A websocket feed of the wrapper txs accepted into the local mempool, so
+//! that block explorers and other watchers can show the pending activity.
+//!
+//! The shell announces every new wrapper tx that passes the mempool
+//! validation. Each websocket subscriber of `/pending_txs` receives them as
+//! JSON messages, at most at the configured rate. The txs exceeding the rate
+//! of a subscriber, or that it couldn't keep up with, are dropped and the
+//! subscriber is told how many were dropped.
+
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use futures::{SinkExt, StreamExt};
+use namada::tx::data::WrapperTx;
+use namada::tx::Tx;
+use namada::types::address::Address;
+use serde::Serialize;
+use tokio::sync::broadcast;
+use warp::ws::{Message, WebSocket};
+use warp::Filter;
+
+/// The maximum number of txs sent to a subscriber per second, when not
+/// configured
+pub const DEFAULT_MAX_TXS_PER_SEC: u32 = 100;
+
+/// The number of announced txs buffered for the subscribers
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// The sending side of the feed, used by the shell to announce the txs
+pub type Sender = broadcast::Sender<PendingTx>;
+
+/// A wrapper tx accepted into the mempool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PendingTx {
+    /// The hash of the wrapper tx, in hex
+    pub hash: String,
+    /// The token in which the fee is paid
+    pub fee_token: Address,
+    /// The fee paid per gas unit
+    pub fee_amount_per_gas_unit: String,
+    /// The gas limit of the tx
+    pub gas_limit: u64,
+    /// The address of the fee payer
+    pub fee_payer: Address,
+    /// The name of the tx's wasm code, when the code is one of the allowed
+    /// txs of the chain
+    pub kind: Option<String>,
+}
+
+impl PendingTx {
+    /// Describe a wrapper tx with the given kind
+    pub fn new(tx: &Tx, wrapper: &WrapperTx, kind: Option<String>) -> Self {
+        Self {
+            hash: tx.header_hash().to_string(),
+            fee_token: wrapper.fee.token.clone(),
+            fee_amount_per_gas_unit: wrapper
+                .fee
+                .amount_per_gas_unit
+                .to_string(),
+            gas_limit: u64::from(wrapper.gas_limit),
+            fee_payer: wrapper.fee_payer(),
+            kind,
+        }
+    }
+}
+
+/// A message of the feed sent to a subscriber
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum FeedMessage<'a> {
+    /// A tx accepted into the mempool
+    PendingTx(&'a PendingTx),
+    /// The number of txs not sent to the subscriber since the previous
+    /// message
+    Dropped { count: u64 },
+}
+
+/// Limits the number of txs sent to a subscriber per second
+#[derive(Debug)]
+struct RateLimit {
+    max_per_sec: u32,
+    window_start: Instant,
+    sent_in_window: u32,
+}
+
+impl RateLimit {
+    fn new(max_per_sec: u32, now: Instant) -> Self {
+        Self {
+            max_per_sec,
+            window_start: now,
+            sent_in_window: 0,
+        }
+    }
+
+    /// Check if another tx can be sent at the given time and if so, count it
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        if now.duration_since(self.window_start) >= Duration::from_secs(1) {
+            self.window_start = now;
+            self.sent_in_window = 0;
+        }
+        if self.sent_in_window < self.max_per_sec {
+            self.sent_in_window += 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Make the channel of the feed
+pub fn channel() -> Sender {
+    broadcast::channel(CHANNEL_CAPACITY).0
+}
+
+/// Serve the feed on the given address until the abort signal is received
+pub async fn serve(
+    laddr: SocketAddr,
+    sender: Sender,
+    max_txs_per_sec: u32,
+    abort_recv: tokio::sync::oneshot::Receiver<()>,
+) {
+    let routes = warp::path("pending_txs")
+        .and(warp::path::end())
+        .and(warp::ws())
+        .map(move |ws: warp::ws::Ws| {
+            let receiver = sender.subscribe();
+            ws.on_upgrade(move |socket| {
+                subscriber(socket, receiver, max_txs_per_sec)
+            })
+        });
+    let server = warp::serve(routes).try_bind_with_graceful_shutdown(
+        laddr,
+        async move {
+            let _ = abort_recv.await;
+        },
+    );
+    match server {
+        Ok((laddr, server)) => {
+            tracing::info!(?laddr, "Serving the mempool feed");
+            server.await;
+        }
+        Err(err) => {
+            tracing::error!(?laddr, "Failed to serve the mempool feed: {err}");
+        }
+    }
+}
+
+/// Send the announced txs to a subscriber until it disconnects
+async fn subscriber(
+    socket: WebSocket,
+    mut receiver: broadcast::Receiver<PendingTx>,
+    max_txs_per_sec: u32,
+) {
+    let (mut socket_send, mut socket_recv) = socket.split();
+    let mut rate_limit = RateLimit::new(max_txs_per_sec, Instant::now());
+    let mut dropped = 0_u64;
+    loop {
+        let tx = tokio::select! {
+            tx = receiver.recv() => tx,
+            // The messages of the subscriber are ignored, until it
+            // disconnects
+            msg = socket_recv.next() => match msg {
+                Some(Ok(_)) => continue,
+                Some(Err(_)) | None => break,
+            },
+        };
+        let tx = match tx {
+            Ok(tx) => tx,
+            Err(broadcast::error::RecvError::Lagged(count)) => {
+                dropped += count;
+                continue;
+            }
+            Err(broadcast::error::RecvError::Closed) => break,
+        };
+        if !rate_limit.try_acquire(Instant::now()) {
+            dropped += 1;
+            continue;
+        }
+        let mut messages = Vec::with_capacity(2);
+        if dropped > 0 {
+            messages.push(FeedMessage::Dropped { count: dropped });
+            dropped = 0;
+        }
+        messages.push(FeedMessage::PendingTx(&tx));
+        for message in messages {
+            let json = serde_json::to_string(&message)
+                .expect("The feed message must be serializable");
+            if socket_send.send(Message::text(json)).await.is_err() {
+                return;
+            }
+        }
+    }
+    let _ = socket_send.close().await;
+}
+
+#[cfg(test)]
+mod test_mempool_feed {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit() {
+        let start = Instant::now();
+        let mut rate_limit = RateLimit::new(2, start);
+        assert!(rate_limit.try_acquire(start));
+        assert!(rate_limit.try_acquire(start + Duration::from_millis(10)));
+        assert!(!rate_limit.try_acquire(start + Duration::from_millis(20)));
+        assert!(!rate_limit.try_acquire(start + Duration::from_millis(999)));
+
+        // A new window starts after a second
+        let next = start + Duration::from_secs(1);
+        assert!(rate_limit.try_acquire(next));
+        assert!(rate_limit.try_acquire(next));
+        assert!(!rate_limit.try_acquire(next));
+    }
+
+    #[test]
+    fn test_feed_message() {
+        let json =
+            serde_json::to_value(FeedMessage::Dropped { count: 3 }).unwrap();
+        assert_eq!(json, serde_json::json!({"type": "dropped", "count": 3}));
+    }
+}
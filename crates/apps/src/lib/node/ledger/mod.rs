@@ -1,6 +1,16 @@
 mod abortable;
 mod broadcaster;
+pub mod crash_report;
+pub mod db_diff;
+pub mod db_query;
 pub mod ethereum_oracle;
+pub mod gas_calibration;
+mod health;
+mod mempool_feed;
+mod query_snapshot;
+#[cfg(feature = "rosetta")]
+mod rosetta;
+mod rpc_proxy;
 pub mod shell;
 pub mod shims;
 pub mod storage;
@@ -16,6 +26,7 @@ use byte_unit::Byte;
 use futures::future::TryFutureExt;
 use namada::eth_bridge::ethers::providers::{Http, Provider};
 use namada::governance::storage::keys as governance_storage;
+use namada::ledger::key_space;
 use namada::types::storage::Key;
 use namada::types::time::{DateTimeUtc, Utc};
 use namada_sdk::tendermint::abci::request::CheckTxKind;
@@ -145,7 +156,10 @@ impl Shell {
                     CheckTxKind::Recheck => MempoolTxType::RecheckTransaction,
                 };
                 let r#type = mempool_tx_type;
-                Ok(Response::CheckTx(self.mempool_validate(&tx.tx, r#type)))
+                let response = self.mempool_validate(&tx.tx, r#type.clone());
+                self.log_mempool_rejection(&tx.tx, &response);
+                self.announce_pending_tx(&tx.tx, r#type, &response);
+                Ok(Response::CheckTx(response))
             }
             Request::ListSnapshots => {
                 Ok(Response::ListSnapshots(Default::default()))
@@ -165,6 +179,21 @@ impl Shell {
 
 /// Run the ledger with an async runtime
 pub fn run(config: config::Ledger, wasm_dir: PathBuf) {
+    crash_report::install_panic_hook(
+        config.chain_dir().join(crash_report::CRASH_REPORTS_DIR),
+    );
+
+    // Refuse to run if the storage keys of different modules could collide
+    if let Err(err) = key_space::key_space() {
+        panic!("Invalid storage key space: {err}");
+    }
+
+    // Roll back the last blocks down to the last verified height instead of
+    // failing to load them if their data got corrupted on disk
+    if let Err(err) = shell::recover_corrupted_state(&config) {
+        panic!("Failed to recover the storage: {err}");
+    }
+
     let logical_cores = num_cpus::get();
     tracing::info!("Available logical cores: {}", logical_cores);
 
@@ -224,6 +253,284 @@ pub fn dump_db(
     db.dump_block(out_file_path, historic, block_height);
 }
 
+/// Query the state in Namada ledger node's DB and print the matching values
+pub fn query_db(
+    config: config::Ledger,
+    args::DbQuery {
+        query,
+        value_type,
+        output_format,
+    }: args::DbQuery,
+) -> Result<(), db_query::Error> {
+    use namada::state::DBIter;
+
+    let chain_id = config.chain_id;
+    let db_path = config.shell.db_dir(&chain_id);
+
+    let db = storage::PersistentDB::open(db_path, None);
+    let pairs = db
+        .iter_prefix(query.prefix.as_ref())
+        .map(|(key, value, _gas)| (key, value));
+    let count =
+        query.run(pairs, value_type, output_format, std::io::stdout())?;
+    // Printed to stderr to keep the results clean for redirection
+    eprintln!("Found {count} matching values.");
+    Ok(())
+}
+
+/// Print the difference of the state in Namada ledger node's DB between two
+/// heights
+pub fn diff_db(
+    config: config::Ledger,
+    args::DbDiff {
+        height_a,
+        height_b,
+        prefix,
+        value_type,
+        output_format,
+    }: args::DbDiff,
+) -> eyre::Result<()> {
+    use namada::state::DB;
+
+    let chain_id = config.chain_id;
+    let db_path = config.shell.db_dir(&chain_id);
+
+    let db = storage::PersistentDB::open(db_path, None);
+    let last_height = db
+        .read_last_block()
+        .map_err(|err| eyre::eyre!("Failed to read the last block: {err}"))?
+        .map(|block| block.height)
+        .unwrap_or_default();
+    let (from, to) = if height_a <= height_b {
+        (height_a, height_b)
+    } else {
+        (height_b, height_a)
+    };
+    if to > last_height {
+        eyre::bail!(
+            "The height {to} is above the last committed height {last_height}"
+        );
+    }
+    let prefix = prefix.unwrap_or_default();
+    let changes = db_diff::diff(&db, from, to, &prefix);
+    db_diff::write_diff(
+        &changes,
+        value_type,
+        output_format,
+        std::io::stdout(),
+    )?;
+    // Printed to stderr to keep the results clean for redirection
+    eprintln!(
+        "Found {} changed keys from height {from} to {to}.",
+        changes.len()
+    );
+    Ok(())
+}
+
+/// Print the txs recently rejected by the mempool validation of a running
+/// node as JSON, from the most recent
+pub fn mempool_rejections(
+    mut args: args::DebugMempoolRejections,
+) -> eyre::Result<()> {
+    use eyre::WrapErr;
+    use namada::tendermint_rpc::HttpClient;
+
+    let client = HttpClient::new(crate::client::utils::take_config_address(
+        &mut args.ledger_address,
+    ))
+    .wrap_err("Failed to connect to the ledger node")?;
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .wrap_err("Failed to start the runtime")?;
+    let rejections = runtime
+        .block_on(namada_sdk::rpc::query_mempool_rejections(&client))
+        .wrap_err("Failed to query the mempool rejections")?;
+    println!("{}", serde_json::to_string_pretty(&rejections)?);
+    Ok(())
+}
+
+/// Check that all the keys of a vote tally in Namada ledger node's DB exist
+/// and decode
+pub fn vote_tally(
+    config: config::Ledger,
+    args::DebugVoteTally { event_hash, epoch }: args::DebugVoteTally,
+) -> eyre::Result<()> {
+    use eyre::WrapErr;
+    use namada::eth_bridge::protocol::transactions::votes::validate_tally_storage;
+    use namada::eth_bridge::storage::vote_tallies;
+    use namada::state::write_log::WriteLog;
+    use namada::state::WlStorage;
+
+    let chain_id = config.chain_id;
+    let db_path = config.shell.db_dir(&chain_id);
+    let chain_dir = config.shell.base_dir.join(chain_id.as_str());
+    let native_token =
+        config::genesis::chain::Finalized::read_toml_files(&chain_dir)
+            .wrap_err("Failed to read the genesis files")?
+            .get_native_token()
+            .clone();
+
+    let mut storage = storage::PersistentStorage::open(
+        db_path,
+        chain_id,
+        native_token,
+        None,
+        None,
+    );
+    storage.load_last_state().map_err(|err| {
+        eyre::eyre!("Failed to load the last state from the DB: {err}")
+    })?;
+    let wl_storage = WlStorage {
+        storage,
+        write_log: WriteLog::default(),
+    };
+    match (event_hash, epoch) {
+        (Some(hash), _) => validate_tally_storage(
+            &wl_storage,
+            &vote_tallies::Keys::from(&hash),
+        )?,
+        (None, Some(epoch)) => validate_tally_storage(
+            &wl_storage,
+            &vote_tallies::Keys::from(&epoch),
+        )?,
+        (None, None) => {
+            eyre::bail!("Either an event hash or an epoch must be given")
+        }
+    }
+    println!("The vote tally is valid.");
+    Ok(())
+}
+
+/// Print the stats of the Ethereum endpoints of the oracle, from the most
+/// preferred one
+pub fn ethereum_oracle_status(config: config::Ledger) -> std::io::Result<()> {
+    let stats_path = config.chain_dir().join(oracle::endpoints::STATS_FILE);
+    let stats = oracle::endpoints::read_stats(&stats_path)?;
+    let urls: Vec<String> =
+        std::iter::once(config.ethereum_bridge.oracle_rpc_endpoint)
+            .chain(config.ethereum_bridge.oracle_rpc_fallback_endpoints)
+            .collect();
+    println!("Ethereum endpoints of the oracle, from the most preferred:");
+    for url in oracle::endpoints::rank_endpoints(&urls, &stats) {
+        match stats.get(url) {
+            Some(stats) => println!(
+                "  {url}: {} successful and {} failed requests ({} in a row), \
+                 average latency {} ms",
+                stats.successes,
+                stats.failures,
+                stats.consecutive_failures,
+                stats.avg_latency_ms
+            ),
+            None => println!("  {url}: no requests yet"),
+        }
+    }
+    Ok(())
+}
+
+/// Calibrate the gas costs on the local machine, print the report and write
+/// the suggested costs to a file
+pub fn calibrate_gas(
+    args::CalibrateGas {
+        iterations,
+        runs,
+        out_file_path,
+    }: args::CalibrateGas,
+) -> Result<(), gas_calibration::Error> {
+    let report = gas_calibration::calibrate(iterations, runs)?;
+    println!("Benchmark results per iteration:");
+    for result in &report.benchmarks {
+        print!(
+            "  {}: {:.1} ns, {:.1} gas",
+            result.name, result.time_ns, result.gas
+        );
+        match result.ns_per_gas {
+            Some(ns_per_gas) => println!(" ({ns_per_gas:.4} ns per gas)"),
+            None => println!(" (not charged)"),
+        }
+    }
+    println!("Median time per gas: {:.4} ns", report.ns_per_gas);
+    println!("Suggested costs (current -> suggested):");
+    for ((param, current), (_, suggested)) in
+        report.current.entries().zip(report.suggested.entries())
+    {
+        println!("  {param}: {current} -> {suggested}");
+    }
+    report.write_toml(&out_file_path)?;
+    println!("Wrote the suggested costs to {}", out_file_path.display());
+    Ok(())
+}
+
+/// Generate the matching sentry configs of a validator and of its sentries
+/// and write them to the output folder, or print them
+pub fn generate_sentry_config(
+    args::GenerateSentryConfig {
+        validator,
+        sentries,
+        output_folder,
+    }: args::GenerateSentryConfig,
+) -> std::io::Result<()> {
+    /// The part of the Namada config with the sentry setup
+    #[derive(serde::Serialize)]
+    struct SentryConfig {
+        ledger: SentryLedgerConfig,
+    }
+
+    #[derive(serde::Serialize)]
+    struct SentryLedgerConfig {
+        sentry: config::Sentry,
+    }
+
+    for addr in std::iter::once(&validator).chain(&sentries) {
+        if !matches!(
+            addr,
+            crate::facade::tendermint_config::net::Address::Tcp {
+                peer_id: Some(_),
+                ..
+            }
+        ) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("The P2P address {addr} must include a node ID"),
+            ));
+        }
+    }
+
+    let (validator_setup, sentry_setups) =
+        config::Sentry::for_validator(&validator, &sentries);
+    let configs =
+        std::iter::once(("validator.toml".to_string(), validator_setup)).chain(
+            sentry_setups
+                .into_iter()
+                .enumerate()
+                .map(|(ix, setup)| (format!("sentry-{ix}.toml"), setup)),
+        );
+    for (file_name, sentry) in configs {
+        let config = toml::to_string(&SentryConfig {
+            ledger: SentryLedgerConfig { sentry },
+        })
+        .map_err(|err| {
+            std::io::Error::new(std::io::ErrorKind::Other, err.to_string())
+        })?;
+        match &output_folder {
+            Some(output_folder) => {
+                std::fs::create_dir_all(output_folder)?;
+                let path = output_folder.join(&file_name);
+                std::fs::write(&path, config)?;
+                println!("Wrote {}", path.display());
+            }
+            None => println!("# {file_name}\n{config}"),
+        }
+    }
+    Ok(())
+}
+
+/// Print the storage key prefixes reserved by the protocol modules
+pub fn dump_keyspace() -> Result<(), key_space::Error> {
+    print!("{}", key_space::key_space()?);
+    Ok(())
+}
+
 /// Roll Namada state back to the previous height
 pub fn rollback(config: config::Ledger) -> Result<(), shell::Error> {
     shell::rollback(config)
@@ -239,6 +546,9 @@ pub fn rollback(config: config::Ledger) -> Result<(), shell::Error> {
 ///   - An Ethereum full node.
 ///   - An oracle, to receive events from the Ethereum full node, and forward
 ///     them to the ledger.
+///   - The HTTP endpoints of the health and readiness checks, if configured.
+///   - The Rosetta API, if configured and enabled with the `rosetta` feature.
+///   - The caching proxy of the CometBFT RPC, if configured.
 ///
 /// All must be alive for correct functioning.
 async fn run_aux(config: config::Ledger, wasm_dir: PathBuf) {
@@ -266,6 +576,20 @@ async fn run_aux(config: config::Ledger, wasm_dir: PathBuf) {
             }
         };
 
+    // Serve the health and readiness checks if configured
+    let health =
+        maybe_start_health_checks(&mut spawner, &config, &eth_oracle_channels);
+
+    // Serve the Rosetta API if configured
+    let rosetta = maybe_start_rosetta(&mut spawner, &config);
+
+    // Serve the CometBFT RPC proxy if configured
+    let rpc_proxy = maybe_start_rpc_proxy(&mut spawner, &config);
+
+    // Serve the feed of the txs accepted into the mempool if configured
+    let (mempool_feed, mempool_feed_sender) =
+        maybe_start_mempool_feed(&mut spawner, &config);
+
     tracing::info!("Loading MASP verifying keys.");
     let _ = namada_sdk::masp::preload_verifying_keys();
     tracing::info!("Done loading MASP verifying keys.");
@@ -278,16 +602,26 @@ async fn run_aux(config: config::Ledger, wasm_dir: PathBuf) {
         wasm_dir,
         setup_data,
         config,
+        mempool_feed_sender,
     );
 
     // Wait for interrupt signal or abort message
     let aborted = spawner.wait_for_abort().await.child_terminated();
 
     // Wait for all managed tasks to finish.
-    let res = tokio::try_join!(tendermint_node, abci, eth_oracle, broadcaster);
+    let res = tokio::try_join!(
+        tendermint_node,
+        abci,
+        eth_oracle,
+        broadcaster,
+        health,
+        rosetta,
+        rpc_proxy,
+        mempool_feed
+    );
 
     match res {
-        Ok((tendermint_res, abci_res, _, _)) => {
+        Ok((tendermint_res, abci_res, _, _, _, _, _, _)) => {
             // we ignore errors on user-initiated shutdown
             if aborted {
                 if let Err(err) = tendermint_res {
@@ -425,6 +759,7 @@ fn start_abci_broadcaster_shell(
     wasm_dir: PathBuf,
     setup_data: RunAuxSetup,
     config: config::Ledger,
+    mempool_feed: Option<mempool_feed::Sender>,
 ) -> (
     task::JoinHandle<shell::Result<()>>,
     task::JoinHandle<()>,
@@ -485,6 +820,7 @@ fn start_abci_broadcaster_shell(
         &db_cache,
         vp_wasm_compilation_cache,
         tx_wasm_compilation_cache,
+        mempool_feed,
     );
 
     // Channel for signalling shut down to ABCI server
@@ -637,6 +973,185 @@ fn start_tendermint(
         })
 }
 
+/// Launches a new task serving the health and readiness checks of the node
+/// into the asynchronous runtime, if they're configured.
+fn maybe_start_health_checks(
+    spawner: &mut AbortableSpawner,
+    config: &config::Ledger,
+    eth_oracle: &Option<EthereumOracleChannels>,
+) -> task::JoinHandle<()> {
+    let laddr = match config.health.laddr {
+        Some(laddr) => laddr,
+        None => return spawn_dummy_task(()),
+    };
+    let rpc_address =
+        convert_tm_addr_to_socket_addr(&config.cometbft.rpc.laddr);
+    let max_blocks_behind = config
+        .health
+        .max_blocks_behind
+        .unwrap_or(health::DEFAULT_MAX_BLOCKS_BEHIND);
+    // The self-hosted events endpoint doesn't report the blocks it processes
+    let oracle = eth_oracle
+        .as_ref()
+        .filter(|_| {
+            matches!(
+                config.ethereum_bridge.mode,
+                ethereum_bridge::ledger::Mode::RemoteEndpoint
+            )
+        })
+        .map(EthereumOracleChannels::last_processed_block);
+
+    // Channel for signalling shut down to the health checks server
+    let (health_abort_send, health_abort_recv) =
+        tokio::sync::oneshot::channel::<()>();
+
+    spawner
+        .spawn_abortable("Health checks", move |aborter| async move {
+            health::serve(
+                laddr,
+                rpc_address,
+                max_blocks_behind,
+                oracle,
+                health_abort_recv,
+            )
+            .await;
+            tracing::info!("Health checks are no longer served.");
+
+            drop(aborter);
+        })
+        .with_cleanup(async move {
+            let _ = health_abort_send.send(());
+        })
+}
+
+/// Launches a new task serving the caching proxy of the CometBFT RPC into the
+/// asynchronous runtime, if it's configured.
+fn maybe_start_rpc_proxy(
+    spawner: &mut AbortableSpawner,
+    config: &config::Ledger,
+) -> task::JoinHandle<()> {
+    let laddr = match config.rpc_proxy.laddr {
+        Some(laddr) => laddr,
+        None => return spawn_dummy_task(()),
+    };
+    let rpc_address =
+        convert_tm_addr_to_socket_addr(&config.cometbft.rpc.laddr);
+    let cache_bytes = config
+        .rpc_proxy
+        .cache_bytes
+        .unwrap_or(rpc_proxy::DEFAULT_CACHE_BYTES);
+    if cache_bytes == 0 {
+        tracing::warn!(
+            "The CometBFT RPC proxy is configured with an empty cache. The \
+             proxy will not be served."
+        );
+        return spawn_dummy_task(());
+    }
+
+    // Channel for signalling shut down to the RPC proxy server
+    let (proxy_abort_send, proxy_abort_recv) =
+        tokio::sync::oneshot::channel::<()>();
+
+    spawner
+        .spawn_abortable("CometBFT RPC proxy", move |aborter| async move {
+            rpc_proxy::serve(laddr, rpc_address, cache_bytes, proxy_abort_recv)
+                .await;
+            tracing::info!("The CometBFT RPC proxy is no longer served.");
+
+            drop(aborter);
+        })
+        .with_cleanup(async move {
+            let _ = proxy_abort_send.send(());
+        })
+}
+
+/// Launches a new task serving the feed of the txs accepted into the mempool
+/// into the asynchronous runtime, if it's configured. The sender of the feed
+/// is returned for the shell to announce the txs.
+fn maybe_start_mempool_feed(
+    spawner: &mut AbortableSpawner,
+    config: &config::Ledger,
+) -> (task::JoinHandle<()>, Option<mempool_feed::Sender>) {
+    let laddr = match config.mempool_feed.laddr {
+        Some(laddr) => laddr,
+        None => return (spawn_dummy_task(()), None),
+    };
+    let max_txs_per_sec = config
+        .mempool_feed
+        .max_txs_per_sec
+        .unwrap_or(mempool_feed::DEFAULT_MAX_TXS_PER_SEC);
+    let sender = mempool_feed::channel();
+    let feed_sender = sender.clone();
+
+    // Channel for signalling shut down to the mempool feed server
+    let (feed_abort_send, feed_abort_recv) =
+        tokio::sync::oneshot::channel::<()>();
+
+    let handle = spawner
+        .spawn_abortable("Mempool feed", move |aborter| async move {
+            mempool_feed::serve(
+                laddr,
+                feed_sender,
+                max_txs_per_sec,
+                feed_abort_recv,
+            )
+            .await;
+            tracing::info!("The mempool feed is no longer served.");
+
+            drop(aborter);
+        })
+        .with_cleanup(async move {
+            let _ = feed_abort_send.send(());
+        });
+    (handle, Some(sender))
+}
+
+/// Launches a new task serving the Rosetta API into the asynchronous runtime,
+/// if it's configured.
+#[cfg(feature = "rosetta")]
+fn maybe_start_rosetta(
+    spawner: &mut AbortableSpawner,
+    config: &config::Ledger,
+) -> task::JoinHandle<()> {
+    let laddr = match config.rosetta.laddr {
+        Some(laddr) => laddr,
+        None => return spawn_dummy_task(()),
+    };
+    let rpc_address =
+        convert_tm_addr_to_socket_addr(&config.cometbft.rpc.laddr);
+    let chain_id = config.chain_id.clone();
+
+    // Channel for signalling shut down to the Rosetta API server
+    let (rosetta_abort_send, rosetta_abort_recv) =
+        tokio::sync::oneshot::channel::<()>();
+
+    spawner
+        .spawn_abortable("Rosetta API", move |aborter| async move {
+            rosetta::serve(laddr, rpc_address, chain_id, rosetta_abort_recv)
+                .await;
+            tracing::info!("The Rosetta API is no longer served.");
+
+            drop(aborter);
+        })
+        .with_cleanup(async move {
+            let _ = rosetta_abort_send.send(());
+        })
+}
+
+/// The Rosetta API can't be served without the `rosetta` feature.
+#[cfg(not(feature = "rosetta"))]
+fn maybe_start_rosetta(
+    _spawner: &mut AbortableSpawner,
+    config: &config::Ledger,
+) -> task::JoinHandle<()> {
+    if config.rosetta.laddr.is_some() {
+        tracing::warn!(
+            "The Rosetta API is configured, but the node was built without              the `rosetta` feature. The API will not be served."
+        );
+    }
+    spawn_dummy_task(())
+}
+
 /// Represents a [`tokio::task`] in which an Ethereum oracle may be running, and
 /// if so, channels for communicating with it.
 enum EthereumOracleTask {
@@ -677,8 +1192,17 @@ async fn maybe_start_ethereum_oracle(
 
     match config.ethereum_bridge.mode {
         ethereum_bridge::ledger::Mode::RemoteEndpoint => {
+            let urls = std::iter::once(ethereum_url)
+                .chain(
+                    config
+                        .ethereum_bridge
+                        .oracle_rpc_fallback_endpoints
+                        .clone(),
+                )
+                .collect();
             let handle = oracle::run_oracle::<Provider<Http>>(
-                ethereum_url,
+                urls,
+                config.chain_dir().join(oracle::endpoints::STATS_FILE),
                 eth_sender,
                 control_receiver,
                 last_processed_block_sender,
@@ -0,0 +1,292 @@
+//! A read-only snapshot of the last committed state, used to serve the RPC
+//! queries concurrently with the consensus requests handled by the shell.
+//!
+//! The snapshot is backed by a secondary instance of the shell's RocksDB,
+//! which only sees the writes of the shell up to its last refresh. The shell
+//! requests a refresh after every block commit, which is applied in a
+//! separate thread: it catches up with the DB writes and reloads the
+//! committed Merkle tree. The queries are served from the snapshot without
+//! ever waiting for the shell, so heavy query traffic can't delay consensus.
+//! The queries that read the event log or the mempool rejections, which are
+//! only held in the shell's memory, must still be served by the shell.
+
+use std::path::Path;
+use std::sync::{mpsc, Arc, RwLock};
+use std::time::Duration;
+
+use namada::ledger::events::log::EventLog;
+use namada::ledger::events::mempool::MempoolRejections;
+use namada::ledger::queries::RequestCtx;
+use namada::state::write_log::WriteLog;
+use namada::state::{Sha256Hasher, State, StorageError, WlStorage};
+use namada::types::address::Address;
+use namada::types::chain::ChainId;
+use namada::vm::wasm::{TxCache, VpCache};
+use namada::vm::WasmCacheRoAccess;
+
+use super::shell::queries::query_with_ctx;
+use super::shell::{Error, Result};
+use super::storage::{self, PersistentDB};
+use crate::facade::tendermint::v0_37::abci::{request, response};
+
+/// The name of the directory, in the chain directory, of the info logs of
+/// the secondary DB instance
+pub const QUERY_SNAPSHOT_DIR: &str = "query_snapshot";
+
+/// A read-only snapshot of the last committed state. It can be cloned to
+/// serve queries from multiple threads.
+#[derive(Debug, Clone)]
+pub struct QuerySnapshot {
+    inner: Arc<RwLock<Snapshot>>,
+}
+
+#[derive(Debug)]
+struct Snapshot {
+    /// The storage with the secondary DB instance and an empty write log
+    wl_storage: WlStorage<PersistentDB, Sha256Hasher>,
+    /// Always empty, the queries reading the event log go to the shell
+    event_log: EventLog,
+    /// Always empty, the queries reading the rejections go to the shell
+    mempool_rejections: MempoolRejections,
+    /// VP WASM compilation cache, shared with the shell
+    vp_wasm_cache: VpCache<WasmCacheRoAccess>,
+    /// Tx WASM compilation cache, shared with the shell
+    tx_wasm_cache: TxCache<WasmCacheRoAccess>,
+    /// Taken from config `storage_read_past_height_limit`
+    storage_read_past_height_limit: Option<u64>,
+    /// Taken from config `wasm_execution_cap_ms`
+    wasm_execution_cap: Option<Duration>,
+}
+
+impl QuerySnapshot {
+    /// Open a snapshot of the DB at `db_path`, which must have been opened by
+    /// the shell already, at its last committed block.
+    pub fn open(
+        db_path: impl AsRef<Path>,
+        secondary_path: impl AsRef<Path>,
+        chain_id: ChainId,
+        native_token: Address,
+        vp_wasm_cache: VpCache<WasmCacheRoAccess>,
+        tx_wasm_cache: TxCache<WasmCacheRoAccess>,
+        storage_read_past_height_limit: Option<u64>,
+        wasm_execution_cap: Option<Duration>,
+    ) -> Result<Self> {
+        let db = storage::open_secondary(db_path, secondary_path)
+            .map_err(|e| Error::Storage(StorageError::new(e)))?;
+        let mut storage = State::with_db(
+            db,
+            chain_id,
+            native_token,
+            storage_read_past_height_limit,
+        );
+        storage
+            .load_last_state()
+            .map_err(|e| Error::Storage(StorageError::new(e)))?;
+        let snapshot = Snapshot {
+            wl_storage: WlStorage {
+                storage,
+                write_log: WriteLog::default(),
+            },
+            event_log: EventLog::default(),
+            mempool_rejections: MempoolRejections::default(),
+            vp_wasm_cache,
+            tx_wasm_cache,
+            storage_read_past_height_limit,
+            wasm_execution_cap,
+        };
+        Ok(Self {
+            inner: Arc::new(RwLock::new(snapshot)),
+        })
+    }
+
+    /// Catch up with the last block committed by the shell. The queries
+    /// being served are completed on the previous state first.
+    pub fn refresh(&self) -> Result<()> {
+        let mut snapshot = self.inner.write().unwrap();
+        let storage = &mut snapshot.wl_storage.storage;
+        storage
+            .db
+            .catch_up_with_primary()
+            .map_err(|e| Error::Storage(StorageError::new(e)))?;
+        storage.read_cache.clear();
+        storage.decoded_cache.clear();
+        storage
+            .load_last_state()
+            .map_err(|e| Error::Storage(StorageError::new(e)))
+    }
+
+    /// Spawn a thread that refreshes the snapshot whenever it's notified on
+    /// the returned channel. The notifications received while refreshing are
+    /// applied with a single refresh. The thread stops when the channel is
+    /// dropped.
+    pub fn spawn_refresher(&self) -> mpsc::Sender<()> {
+        let (refresh_send, refresh_recv) = mpsc::channel::<()>();
+        let snapshot = self.clone();
+        std::thread::Builder::new()
+            .name("query-snapshot".to_string())
+            .spawn(move || {
+                while refresh_recv.recv().is_ok() {
+                    while refresh_recv.try_recv().is_ok() {}
+                    if let Err(err) = snapshot.refresh() {
+                        tracing::error!(
+                            "Failed to refresh the query snapshot: {err}"
+                        );
+                    }
+                }
+            })
+            .expect("Spawning the query snapshot thread should not fail");
+        refresh_send
+    }
+
+    /// Serve the query from the snapshot, as [`Shell::query`] would
+    ///
+    /// [`Shell::query`]: super::shell::Shell::query
+    pub fn query(&self, query: request::Query) -> response::Query {
+        let snapshot = self.inner.read().unwrap();
+        let ctx = RequestCtx {
+            wl_storage: &snapshot.wl_storage,
+            event_log: &snapshot.event_log,
+            mempool_rejections: &snapshot.mempool_rejections,
+            vp_wasm_cache: snapshot.vp_wasm_cache.clone(),
+            tx_wasm_cache: snapshot.tx_wasm_cache.clone(),
+            storage_read_past_height_limit: snapshot
+                .storage_read_past_height_limit,
+        };
+        query_with_ctx(ctx, &query, snapshot.wasm_execution_cap)
+    }
+}
+
+#[cfg(test)]
+mod test_query_snapshot {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, Instant};
+
+    use namada::ledger::queries::RPC;
+    use namada::state::StorageWrite;
+    use namada::types::address;
+    use namada::types::storage::{BlockHash, BlockHeight, Key};
+    use namada::vm::wasm::compilation_cache::common::testing;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::facade::tendermint::block;
+
+    const NUM_BLOCKS: u64 = 50;
+    const NUM_QUERY_THREADS: usize = 4;
+
+    /// Query the value of the counter from the snapshot, if it was committed
+    fn query_counter(snapshot: &QuerySnapshot, key: &Key) -> Option<u64> {
+        let resp = snapshot.query(request::Query {
+            data: Default::default(),
+            path: RPC.shell().storage_value_path(key),
+            height: block::Height::from(0_u32),
+            prove: false,
+        });
+        if resp.code.is_err() {
+            return None;
+        }
+        Some(namada::types::decode(resp.value).expect("decoding failed"))
+    }
+
+    /// Test that the queries served from a snapshot, while the blocks are
+    /// being committed to the DB, only ever see the values of whole
+    /// committed blocks, and that the snapshot catches up with the last
+    /// committed block when it's refreshed.
+    #[test]
+    fn test_queries_during_block_commits() {
+        let base_dir = TempDir::new().unwrap();
+        let db_path = base_dir.path().join("db");
+        let secondary_path = base_dir.path().join(QUERY_SNAPSHOT_DIR);
+        let storage = State::<PersistentDB, Sha256Hasher>::open(
+            &db_path,
+            ChainId::default(),
+            address::nam(),
+            None,
+            None,
+        );
+        let mut wl_storage = WlStorage::new(WriteLog::default(), storage);
+        let (vp_wasm_cache, _vp_dir) = testing::cache();
+        let (tx_wasm_cache, _tx_dir) = testing::cache();
+        let snapshot = QuerySnapshot::open(
+            &db_path,
+            &secondary_path,
+            ChainId::default(),
+            address::nam(),
+            vp_wasm_cache.read_only(),
+            tx_wasm_cache.read_only(),
+            None,
+            None,
+        )
+        .expect("Opening the query snapshot failed");
+        let key = Key::parse("counter").unwrap();
+        let key_copy = Key::parse("counter_copy").unwrap();
+
+        // Nothing is committed yet
+        assert_eq!(query_counter(&snapshot, &key), None);
+
+        let refresh = snapshot.spawn_refresher();
+        let done = Arc::new(AtomicBool::new(false));
+        let query_threads: Vec<_> = (0..NUM_QUERY_THREADS)
+            .map(|_| {
+                let snapshot = snapshot.clone();
+                let done = done.clone();
+                let key = key.clone();
+                let key_copy = key_copy.clone();
+                std::thread::spawn(move || {
+                    let mut last_seen = 0;
+                    let mut num_queries = 0_u64;
+                    while !done.load(Ordering::Relaxed) {
+                        // The snapshot never goes back to a previous block
+                        for key in [&key, &key_copy] {
+                            if let Some(counter) = query_counter(&snapshot, key)
+                            {
+                                assert!(counter >= last_seen);
+                                last_seen = counter;
+                            }
+                        }
+                        num_queries += 1;
+                    }
+                    num_queries
+                })
+            })
+            .collect();
+
+        // Commit the blocks while the snapshot is being queried and
+        // refreshed
+        for height in 1..=NUM_BLOCKS {
+            wl_storage
+                .storage
+                .begin_block(BlockHash::default(), BlockHeight(height))
+                .expect("begin_block failed");
+            wl_storage.write(&key, height).unwrap();
+            wl_storage.write(&key_copy, height).unwrap();
+            wl_storage.commit_block().expect("commit failed");
+            refresh.send(()).unwrap();
+        }
+
+        // The refresher catches up with the last committed block
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while query_counter(&snapshot, &key) != Some(NUM_BLOCKS) {
+            assert!(
+                Instant::now() < deadline,
+                "The snapshot didn't catch up with the last block"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        done.store(true, Ordering::Relaxed);
+        for thread in query_threads {
+            assert!(thread.join().expect("a query thread panicked") > 0);
+        }
+        assert_eq!(query_counter(&snapshot, &key_copy), Some(NUM_BLOCKS));
+        assert_eq!(
+            snapshot
+                .inner
+                .read()
+                .unwrap()
+                .wl_storage
+                .storage
+                .get_last_block_height(),
+            BlockHeight(NUM_BLOCKS)
+        );
+    }
+}
@@ -0,0 +1,406 @@
+//! The endpoints of the Construction API
+
+use std::collections::BTreeMap;
+
+use borsh::BorshDeserialize;
+use data_encoding::HEXLOWER_PERMISSIVE;
+use namada::parameters::storage as parameter_storage;
+use namada::tx::Tx;
+use namada::types::address::Address;
+use namada::types::hash::Hash;
+use namada::types::key::{common, ed25519};
+use namada::types::storage::Key;
+use namada::types::token;
+use namada_sdk::rpc;
+use namada_sdk::tx::TX_TRANSFER_WASM;
+use serde::{Deserialize, Serialize};
+
+use super::types::*;
+use super::{
+    amount, currency, currency_token, native_currency, node_error,
+    parse_address, transfer_operations, ApiError, State, OP_TRANSFER,
+};
+use crate::facade::tendermint_rpc::Client;
+
+/// The curve of the supported public keys
+const CURVE_EDWARDS25519: &str = "edwards25519";
+
+/// The gas limit of transfers, as defaulted by the client
+const TRANSFER_GAS_LIMIT: u64 = 25_000;
+
+/// The options of a transfer, from its operations
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct TransferOptions {
+    source: Address,
+    target: Address,
+    currency: Currency,
+    value: token::Amount,
+}
+
+/// The metadata needed to build a transfer
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct TransferMetadata {
+    chain_id: String,
+    epoch: u64,
+    transfer_code_hash: String,
+    token: Address,
+    token_decimals: u32,
+    fee_token: Address,
+    gas_price: token::Amount,
+    gas_limit: u64,
+}
+
+pub(super) async fn derive(
+    state: &State,
+    request: ConstructionDeriveRequest,
+) -> Result<ConstructionDeriveResponse, ApiError> {
+    state.check_network(&request.network_identifier)?;
+    let public_key = parse_public_key(&request.public_key)?;
+    Ok(ConstructionDeriveResponse {
+        account_identifier: AccountIdentifier {
+            address: Address::from(&public_key).to_string(),
+        },
+    })
+}
+
+pub(super) async fn preprocess(
+    state: &State,
+    request: ConstructionPreprocessRequest,
+) -> Result<ConstructionPreprocessResponse, ApiError> {
+    state.check_network(&request.network_identifier)?;
+    let options = transfer_options(&request.operations)?;
+    Ok(ConstructionPreprocessResponse {
+        required_public_keys: vec![AccountIdentifier {
+            address: options.source.to_string(),
+        }],
+        options: serde_json::to_value(options)
+            .expect("Serializing the options shouldn't fail"),
+    })
+}
+
+pub(super) async fn metadata(
+    state: &State,
+    request: ConstructionMetadataRequest,
+) -> Result<ConstructionMetadataResponse, ApiError> {
+    state.check_network(&request.network_identifier)?;
+    let options: TransferOptions = serde_json::from_value(request.options)
+        .map_err(|err| ApiError::InvalidRequest(err.to_string()))?;
+
+    // The source pays the fee and signs the transfer with the key of its
+    // implicit account, which must be revealed to verify the signature
+    if !matches!(options.source, Address::Implicit(_)) {
+        return Err(ApiError::UnsupportedOperations(
+            "The source must be an implicit account".to_string(),
+        ));
+    }
+    let revealed = rpc::is_public_key_revealed(&state.client, &options.source)
+        .await
+        .map_err(node_error)?;
+    if !revealed {
+        return Err(ApiError::PublicKeyNotRevealed(options.source.to_string()));
+    }
+
+    let native_token = rpc::query_native_token(&state.client)
+        .await
+        .map_err(node_error)?;
+    let token = currency_token(&options.currency, &native_token)?;
+    let token_currency = currency(state, &native_token, &token).await?;
+    let epoch = rpc::query_epoch(&state.client).await.map_err(node_error)?;
+    let transfer_code_hash = query_transfer_code_hash(&state.client)
+        .await?
+        .ok_or_else(|| node_error("The transfer code is not in storage"))?;
+    let gas_price = rpc::query_storage_value::<
+        _,
+        BTreeMap<Address, token::Amount>,
+    >(&state.client, &parameter_storage::get_gas_cost_key())
+    .await
+    .map_err(node_error)?
+    .get(&native_token)
+    .copied()
+    .ok_or_else(|| node_error("No gas price for the native token"))?;
+
+    let fee = gas_price
+        .checked_mul(token::Amount::from_u64(TRANSFER_GAS_LIMIT))
+        .ok_or_else(|| node_error("The fee overflows"))?;
+    let metadata = TransferMetadata {
+        chain_id: state.network.network.clone(),
+        epoch: epoch.0,
+        transfer_code_hash: transfer_code_hash.to_string(),
+        token,
+        token_decimals: token_currency.decimals,
+        fee_token: native_token.clone(),
+        gas_price,
+        gas_limit: TRANSFER_GAS_LIMIT,
+    };
+    Ok(ConstructionMetadataResponse {
+        metadata: serde_json::to_value(metadata)
+            .expect("Serializing the metadata shouldn't fail"),
+        suggested_fee: vec![amount(
+            fee,
+            &native_currency(&native_token),
+            false,
+        )],
+    })
+}
+
+pub(super) async fn parse(
+    state: &State,
+    request: ConstructionParseRequest,
+) -> Result<ConstructionParseResponse, ApiError> {
+    state.check_network(&request.network_identifier)?;
+    let tx = decode_tx(&request.transaction)?;
+    let transfer_code_hash = query_transfer_code_hash(&state.client).await?;
+    let transfer = super::data::transfer_of(&tx, transfer_code_hash)
+        .ok_or_else(|| {
+            ApiError::UnsupportedOperations(
+                "The transaction is not a transfer".to_string(),
+            )
+        })?;
+    let native_token = rpc::query_native_token(&state.client)
+        .await
+        .map_err(node_error)?;
+    let currency = currency(state, &native_token, &transfer.token).await?;
+    let value = transfer
+        .amount
+        .scale(currency.decimals as u8)
+        .map_err(|err| ApiError::InvalidRequest(err.to_string()))?;
+
+    let mut signers = vec![];
+    if request.signed {
+        signers.push(transfer.source.clone());
+        if let Some(wrapper) = tx.header().wrapper() {
+            let fee_payer = wrapper.fee_payer();
+            if fee_payer != transfer.source {
+                signers.push(fee_payer);
+            }
+        }
+    }
+    Ok(ConstructionParseResponse {
+        operations: transfer_operations(
+            0,
+            &transfer.source,
+            &transfer.target,
+            value,
+            &currency,
+            None,
+        )
+        .to_vec(),
+        account_identifier_signers: signers
+            .into_iter()
+            .map(|address| AccountIdentifier {
+                address: address.to_string(),
+            })
+            .collect(),
+    })
+}
+
+pub(super) async fn hash(
+    state: &State,
+    request: ConstructionSubmitRequest,
+) -> Result<TransactionIdentifierResponse, ApiError> {
+    state.check_network(&request.network_identifier)?;
+    let tx = decode_tx(&request.signed_transaction)?;
+    Ok(TransactionIdentifierResponse {
+        transaction_identifier: transaction_identifier(&tx),
+    })
+}
+
+pub(super) async fn submit(
+    state: &State,
+    request: ConstructionSubmitRequest,
+) -> Result<TransactionIdentifierResponse, ApiError> {
+    state.check_network(&request.network_identifier)?;
+    let tx = decode_tx(&request.signed_transaction)?;
+    let response = state
+        .client
+        .broadcast_tx_sync(tx.to_bytes())
+        .await
+        .map_err(node_error)?;
+    if response.code.is_err() {
+        return Err(ApiError::Rejected(response.log));
+    }
+    Ok(TransactionIdentifierResponse {
+        transaction_identifier: transaction_identifier(&tx),
+    })
+}
+
+/// A transaction is identified by the hash of its inner transaction, which
+/// identifies the transfer in the blocks
+fn transaction_identifier(tx: &Tx) -> TransactionIdentifier {
+    TransactionIdentifier {
+        hash: tx.raw_header_hash().to_string(),
+    }
+}
+
+fn decode_tx(hex: &str) -> Result<Tx, ApiError> {
+    let bytes = HEXLOWER_PERMISSIVE.decode(hex.as_bytes()).map_err(|err| {
+        ApiError::InvalidRequest(format!("Invalid transaction hex: {err}"))
+    })?;
+    Tx::try_from(bytes.as_slice()).map_err(|err| {
+        ApiError::InvalidRequest(format!("Invalid transaction: {err}"))
+    })
+}
+
+async fn query_transfer_code_hash(
+    client: &crate::facade::tendermint_rpc::HttpClient,
+) -> Result<Option<Hash>, ApiError> {
+    let (bytes, _) = rpc::query_storage_value_bytes(
+        client,
+        &Key::wasm_hash(TX_TRANSFER_WASM),
+        None,
+        false,
+    )
+    .await
+    .map_err(node_error)?;
+    Ok(bytes.and_then(|bytes| Hash::try_from(&bytes[..]).ok()))
+}
+
+fn parse_public_key(
+    public_key: &PublicKey,
+) -> Result<common::PublicKey, ApiError> {
+    if public_key.curve_type != CURVE_EDWARDS25519 {
+        return Err(ApiError::InvalidRequest(format!(
+            "Unsupported curve type {}",
+            public_key.curve_type
+        )));
+    }
+    HEXLOWER_PERMISSIVE
+        .decode(public_key.hex_bytes.as_bytes())
+        .ok()
+        .and_then(|bytes| ed25519::PublicKey::try_from_slice(&bytes).ok())
+        .map(common::PublicKey::Ed25519)
+        .ok_or_else(|| {
+            ApiError::InvalidRequest("Invalid public key".to_string())
+        })
+}
+
+/// Get the options of a transfer from its debit and credit operations
+fn transfer_options(
+    operations: &[Operation],
+) -> Result<TransferOptions, ApiError> {
+    let unsupported =
+        |msg: &str| Err(ApiError::UnsupportedOperations(msg.to_string()));
+    let [first, second] = operations else {
+        return unsupported("A transfer has exactly two operations");
+    };
+    if first.kind != OP_TRANSFER || second.kind != OP_TRANSFER {
+        return unsupported("Only transfer operations are supported");
+    }
+    let (Some(first_amount), Some(second_amount)) =
+        (&first.amount, &second.amount)
+    else {
+        return unsupported("The operations must have amounts");
+    };
+    if first_amount.currency != second_amount.currency {
+        return unsupported("The operations must have the same currency");
+    }
+    let (debit, credit) = if first_amount.value.starts_with('-') {
+        (first, second)
+    } else {
+        (second, first)
+    };
+    let (Some(debit_amount), Some(credit_amount)) =
+        (&debit.amount, &credit.amount)
+    else {
+        return unsupported("The operations must have amounts");
+    };
+    let value = parse_value(&credit_amount.value)?;
+    match debit_amount.value.strip_prefix('-') {
+        Some(debit_value)
+            if parse_value(debit_value)? == value && !value.is_zero() => {}
+        _ => {
+            return unsupported(
+                "The debit and credit must be of the same non-zero value",
+            );
+        }
+    }
+    let (Some(source), Some(target)) = (&debit.account, &credit.account) else {
+        return unsupported("The operations must have accounts");
+    };
+    Ok(TransferOptions {
+        source: parse_address(&source.address)?,
+        target: parse_address(&target.address)?,
+        currency: credit_amount.currency.clone(),
+        value,
+    })
+}
+
+fn parse_value(value: &str) -> Result<token::Amount, ApiError> {
+    token::Amount::from_str(value, 0u8).map_err(|err| {
+        ApiError::InvalidRequest(format!("Invalid amount {value}: {err}"))
+    })
+}
+
+#[cfg(test)]
+mod test_construction {
+    use namada::types::address::nam;
+    use namada::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada::types::key::RefTo;
+
+    use super::super::OP_FEE;
+    use super::*;
+
+    #[test]
+    fn test_transfer_options() {
+        let currency = native_currency(&nam());
+        let source = established_address_1();
+        let target = established_address_2();
+        let value = token::Amount::from_u64(100);
+        let operations =
+            transfer_operations(0, &source, &target, value, &currency, None);
+        let expected = TransferOptions {
+            source,
+            target,
+            currency,
+            value,
+        };
+        assert_eq!(transfer_options(&operations).unwrap(), expected);
+
+        // The order of the operations doesn't matter
+        let [debit, credit] = operations.clone();
+        assert_eq!(
+            transfer_options(&[credit.clone(), debit.clone()]).unwrap(),
+            expected
+        );
+
+        // Mismatched values are rejected
+        let mut mismatched = credit.clone();
+        mismatched.amount.as_mut().unwrap().value = "99".to_string();
+        let result = transfer_options(&[debit.clone(), mismatched]);
+        assert!(result.is_err());
+
+        // Other operation types are rejected
+        let mut fee = debit;
+        fee.kind = OP_FEE.to_string();
+        let result = transfer_options(&[fee, credit]);
+        assert!(result.is_err());
+
+        let result = transfer_options(&operations[..1]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_public_key() {
+        let public_key: common::PublicKey =
+            namada::types::key::testing::keypair_1().ref_to();
+        let common::PublicKey::Ed25519(ed25519_key) = &public_key else {
+            panic!("The test key should be an ed25519 key");
+        };
+        let hex_bytes =
+            data_encoding::HEXLOWER.encode(ed25519_key.0.as_bytes());
+        let parsed = parse_public_key(&PublicKey {
+            hex_bytes: hex_bytes.clone(),
+            curve_type: CURVE_EDWARDS25519.to_string(),
+        })
+        .unwrap();
+        assert_eq!(parsed, public_key);
+
+        let result = parse_public_key(&PublicKey {
+            hex_bytes,
+            curve_type: "secp256k1".to_string(),
+        });
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,402 @@
+//! The endpoints of the Data API
+
+use std::str::FromStr;
+
+use borsh::BorshDeserialize;
+use namada::token::storage_key::balance_key;
+use namada::tx::data::{DecryptedTx, ResultCode, TxType};
+use namada::tx::{Section, Tx};
+use namada::types::address::Address;
+use namada::types::hash::Hash;
+use namada::types::storage::{BlockHeight, Key};
+use namada::types::token::{self, Transfer};
+use namada_sdk::rpc;
+use namada_sdk::tx::TX_TRANSFER_WASM;
+
+use super::types::*;
+use super::{
+    amount, currency, currency_token, native_currency, node_error,
+    transfer_operations, ApiError, State, OP_FEE, OP_TRANSFER, STATUS_FAILURE,
+    STATUS_SUCCESS,
+};
+use crate::cli::namada_version;
+use crate::facade::tendermint::abci::Event;
+use crate::facade::tendermint::block::{self, Height};
+use crate::facade::tendermint_rpc::Client;
+
+pub(super) async fn network_list(
+    state: &State,
+    _request: MetadataRequest,
+) -> Result<NetworkListResponse, ApiError> {
+    Ok(NetworkListResponse {
+        network_identifiers: vec![state.network.clone()],
+    })
+}
+
+pub(super) async fn network_status(
+    state: &State,
+    request: NetworkRequest,
+) -> Result<NetworkStatusResponse, ApiError> {
+    state.check_network(&request.network_identifier)?;
+    let status = state.client.status().await.map_err(node_error)?;
+    let (current_block_identifier, current_block) =
+        fetch_block(state, &PartialBlockIdentifier::default()).await?;
+    let peers = state
+        .client
+        .net_info()
+        .await
+        .map_err(node_error)?
+        .peers
+        .into_iter()
+        .map(|peer| Peer {
+            peer_id: peer.node_info.id.to_string(),
+        })
+        .collect();
+    Ok(NetworkStatusResponse {
+        current_block_timestamp: timestamp(&current_block),
+        sync_status: SyncStatus {
+            current_index: current_block_identifier.index,
+            target_index: status.sync_info.latest_block_height.value(),
+            synced: !status.sync_info.catching_up,
+        },
+        current_block_identifier,
+        // The earliest block of the node is the genesis block, unless the
+        // node was started from a snapshot
+        genesis_block_identifier: BlockIdentifier {
+            index: status.sync_info.earliest_block_height.value(),
+            hash: status.sync_info.earliest_block_hash.to_string(),
+        },
+        peers,
+    })
+}
+
+pub(super) async fn network_options(
+    state: &State,
+    request: NetworkRequest,
+) -> Result<NetworkOptionsResponse, ApiError> {
+    state.check_network(&request.network_identifier)?;
+    Ok(NetworkOptionsResponse {
+        version: Version {
+            rosetta_version: ROSETTA_VERSION.to_string(),
+            node_version: namada_version().to_string(),
+        },
+        allow: Allow {
+            operation_statuses: vec![
+                OperationStatus {
+                    status: STATUS_SUCCESS.to_string(),
+                    successful: true,
+                },
+                OperationStatus {
+                    status: STATUS_FAILURE.to_string(),
+                    successful: false,
+                },
+            ],
+            operation_types: vec![OP_TRANSFER.to_string(), OP_FEE.to_string()],
+            errors: ApiError::all().iter().map(ApiError::description).collect(),
+            // Limited by the node's `storage_read_past_height_limit`
+            historical_balance_lookup: true,
+            call_methods: vec![],
+            balance_exemptions: vec![],
+            mempool_coins: false,
+        },
+    })
+}
+
+pub(super) async fn account_balance(
+    state: &State,
+    request: AccountBalanceRequest,
+) -> Result<AccountBalanceResponse, ApiError> {
+    state.check_network(&request.network_identifier)?;
+    let owner = super::parse_address(&request.account_identifier.address)?;
+    let (block_identifier, _) =
+        fetch_block(state, &request.block_identifier.unwrap_or_default())
+            .await?;
+    let native_token = rpc::query_native_token(&state.client)
+        .await
+        .map_err(node_error)?;
+    let currencies = request
+        .currencies
+        .unwrap_or_else(|| vec![native_currency(&native_token)]);
+
+    let height = BlockHeight(block_identifier.index);
+    let mut balances = Vec::with_capacity(currencies.len());
+    for requested in currencies {
+        let token = currency_token(&requested, &native_token)?;
+        let currency = currency(state, &native_token, &token).await?;
+        let balance = read_balance(state, &token, &owner, height).await?;
+        balances.push(amount(balance, &currency, false));
+    }
+    Ok(AccountBalanceResponse {
+        block_identifier,
+        balances,
+    })
+}
+
+pub(super) async fn block(
+    state: &State,
+    request: BlockRequest,
+) -> Result<BlockResponse, ApiError> {
+    state.check_network(&request.network_identifier)?;
+    let (block_identifier, block) =
+        fetch_block(state, &request.block_identifier).await?;
+    let transactions = block_transactions(state, &block).await?;
+    Ok(BlockResponse {
+        block: Block {
+            parent_block_identifier: parent_block_identifier(
+                &block_identifier,
+                &block,
+            ),
+            timestamp: timestamp(&block),
+            block_identifier,
+            transactions,
+        },
+    })
+}
+
+pub(super) async fn block_transaction(
+    state: &State,
+    request: BlockTransactionRequest,
+) -> Result<BlockTransactionResponse, ApiError> {
+    state.check_network(&request.network_identifier)?;
+    let (_, block) = fetch_block(
+        state,
+        &PartialBlockIdentifier {
+            index: Some(request.block_identifier.index),
+            hash: Some(request.block_identifier.hash),
+        },
+    )
+    .await?;
+    block_transactions(state, &block)
+        .await?
+        .into_iter()
+        .find(|tx| tx.transaction_identifier == request.transaction_identifier)
+        .map(|transaction| BlockTransactionResponse { transaction })
+        .ok_or(ApiError::TransactionNotFound)
+}
+
+/// Read the balance of a token of an owner at the given height
+async fn read_balance(
+    state: &State,
+    token: &Address,
+    owner: &Address,
+    height: BlockHeight,
+) -> Result<token::Amount, ApiError> {
+    let (value, _) = rpc::query_storage_value_bytes(
+        &state.client,
+        &balance_key(token, owner),
+        Some(height),
+        false,
+    )
+    .await
+    .map_err(node_error)?;
+    value
+        .map(|bytes| token::Amount::try_from_slice(&bytes))
+        .transpose()
+        .map_err(node_error)
+        .map(Option::unwrap_or_default)
+}
+
+/// Fetch a committed block by its index or hash, or the last committed block
+/// when neither is given
+async fn fetch_block(
+    state: &State,
+    identifier: &PartialBlockIdentifier,
+) -> Result<(BlockIdentifier, block::Block), ApiError> {
+    let last_committed = rpc::query_block(&state.client)
+        .await
+        .map_err(node_error)?
+        .ok_or(ApiError::BlockNotFound)?
+        .height
+        .0;
+    let (block_id, block) = match (identifier.index, &identifier.hash) {
+        (Some(index), _) => {
+            if index > last_committed {
+                return Err(ApiError::BlockNotFound);
+            }
+            let height = Height::try_from(index).map_err(|err| {
+                ApiError::InvalidRequest(format!("Invalid index: {err}"))
+            })?;
+            let response =
+                state.client.block(height).await.map_err(node_error)?;
+            (response.block_id, response.block)
+        }
+        (None, Some(hash)) => {
+            let hash =
+                crate::facade::tendermint::Hash::from_str(&hash.to_uppercase())
+                    .map_err(|err| {
+                        ApiError::InvalidRequest(format!("Invalid hash: {err}"))
+                    })?;
+            let response =
+                state.client.block_by_hash(hash).await.map_err(node_error)?;
+            let block = response.block.ok_or(ApiError::BlockNotFound)?;
+            if block.header.height.value() > last_committed {
+                return Err(ApiError::BlockNotFound);
+            }
+            (response.block_id, block)
+        }
+        (None, None) => {
+            let height =
+                Height::try_from(last_committed).map_err(node_error)?;
+            let response =
+                state.client.block(height).await.map_err(node_error)?;
+            (response.block_id, response.block)
+        }
+    };
+    let block_identifier = BlockIdentifier {
+        index: block.header.height.value(),
+        hash: block_id.hash.to_string(),
+    };
+    // When both the index and hash are given, they must match
+    match &identifier.hash {
+        Some(hash) if !hash.eq_ignore_ascii_case(&block_identifier.hash) => {
+            Err(ApiError::BlockNotFound)
+        }
+        _ => Ok((block_identifier, block)),
+    }
+}
+
+/// The parent of the first block is itself
+fn parent_block_identifier(
+    block_identifier: &BlockIdentifier,
+    block: &block::Block,
+) -> BlockIdentifier {
+    match &block.header.last_block_id {
+        Some(parent) => BlockIdentifier {
+            index: block_identifier.index - 1,
+            hash: parent.hash.to_string(),
+        },
+        None => block_identifier.clone(),
+    }
+}
+
+/// The time of a block in milliseconds since the Unix epoch
+fn timestamp(block: &block::Block) -> i64 {
+    (block.header.time.unix_timestamp_nanos() / 1_000_000) as i64
+}
+
+/// The transactions of a block with the operations they perform
+async fn block_transactions(
+    state: &State,
+    block: &block::Block,
+) -> Result<Vec<Transaction>, ApiError> {
+    let events = state
+        .client
+        .block_results(block.header.height)
+        .await
+        .map_err(node_error)?
+        .end_block_events
+        .unwrap_or_default();
+    let native_token = rpc::query_native_token(&state.client)
+        .await
+        .map_err(node_error)?;
+    let transfer_code_hash = rpc::query_storage_value_bytes(
+        &state.client,
+        &Key::wasm_hash(TX_TRANSFER_WASM),
+        None,
+        false,
+    )
+    .await
+    .map_err(node_error)?
+    .0
+    .and_then(|bytes| Hash::try_from(&bytes[..]).ok());
+
+    let mut transactions = vec![];
+    for tx_bytes in &block.data {
+        let Ok(tx) = Tx::try_from(tx_bytes.as_slice()) else {
+            continue;
+        };
+        let transaction = match tx.header().tx_type {
+            TxType::Wrapper(wrapper) => {
+                let hash = tx.header_hash().to_string();
+                let status = tx_status(&events, "accepted", &hash);
+                let currency =
+                    currency(state, &native_token, &wrapper.fee.token).await?;
+                let fee = wrapper
+                    .get_tx_fee()
+                    .ok()
+                    .and_then(|fee| fee.scale(currency.decimals as u8).ok())
+                    .unwrap_or_default();
+                Transaction {
+                    transaction_identifier: TransactionIdentifier { hash },
+                    operations: vec![Operation {
+                        operation_identifier: OperationIdentifier { index: 0 },
+                        related_operations: None,
+                        kind: OP_FEE.to_string(),
+                        status: Some(status.to_string()),
+                        account: Some(AccountIdentifier {
+                            address: wrapper.fee_payer().to_string(),
+                        }),
+                        amount: Some(amount(fee, &currency, true)),
+                    }],
+                }
+            }
+            TxType::Decrypted(DecryptedTx::Decrypted) => {
+                let Some(transfer) = transfer_of(&tx, transfer_code_hash)
+                else {
+                    continue;
+                };
+                let hash = tx.raw_header_hash().to_string();
+                let status = tx_status(&events, "applied", &hash);
+                let currency =
+                    currency(state, &native_token, &transfer.token).await?;
+                let value = transfer
+                    .amount
+                    .scale(currency.decimals as u8)
+                    .map_err(node_error)?;
+                Transaction {
+                    transaction_identifier: TransactionIdentifier { hash },
+                    operations: transfer_operations(
+                        0,
+                        &transfer.source,
+                        &transfer.target,
+                        value,
+                        &currency,
+                        Some(status),
+                    )
+                    .to_vec(),
+                }
+            }
+            _ => continue,
+        };
+        transactions.push(transaction);
+    }
+    Ok(transactions)
+}
+
+/// Decode the transfer of a transaction, if it runs the transfer code
+pub(super) fn transfer_of(
+    tx: &Tx,
+    transfer_code_hash: Option<Hash>,
+) -> Option<Transfer> {
+    let code_hash = match tx.get_section(tx.code_sechash()).as_deref() {
+        Some(Section::Code(code)) => code.code.hash(),
+        _ => return None,
+    };
+    if Some(code_hash) != transfer_code_hash {
+        return None;
+    }
+    Transfer::try_from_slice(&tx.data()?).ok()
+}
+
+/// The status of a transaction from its event of the given kind
+fn tx_status(events: &[Event], kind: &str, hash: &str) -> &'static str {
+    let event = events.iter().find(|event| {
+        event.kind == kind
+            && event
+                .attributes
+                .iter()
+                .any(|attr| attr.key == "hash" && attr.value == hash)
+    });
+    let code = event.and_then(|event| {
+        event
+            .attributes
+            .iter()
+            .find(|attr| attr.key == "code")
+            .and_then(|attr| ResultCode::from_str(&attr.value).ok())
+    });
+    if code == Some(ResultCode::Ok) {
+        STATUS_SUCCESS
+    } else {
+        STATUS_FAILURE
+    }
+}
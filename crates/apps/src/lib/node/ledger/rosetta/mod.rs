@@ -0,0 +1,478 @@
+//! A server of the [Rosetta API](https://www.rosetta-api.org/) for exchange
+//! integrations, enabled with the `rosetta` feature.
+//!
+//! The Data API serves the balances of accounts and the transfers in the
+//! blocks of the chain. A block's transactions only include the operations
+//! that move balances of transparent accounts:
+//!
+//! - `fee`, the fee debited from the fee payer of a wrapper transaction.
+//! - `transfer`, a debit from the source and a credit to the target of a
+//!   transfer transaction, once it's applied.
+//!
+//! The Construction API derives the implicit addresses of `ed25519` public
+//! keys and prepares the metadata of transfers, whose fee is paid by their
+//! source in the native token. It can also parse, hash and submit signed
+//! transactions. The `/construction/payloads` and `/construction/combine`
+//! endpoints aren't supported, as a wrapper's signature covers the signature
+//! of its inner transaction, so that both can't be requested in a single
+//! round. Transactions are signed with the SDK or a hardware wallet instead.
+//!
+//! All the endpoints query the node's own RPC, so the server runs alongside
+//! the ledger.
+
+mod construction;
+mod data;
+mod types;
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use namada::ledger::queries::RPC;
+use namada::types::address::Address;
+use namada::types::chain::ChainId;
+use namada::types::token;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use thiserror::Error;
+use warp::http::StatusCode;
+use warp::Filter;
+
+use self::types::{
+    Amount, Currency, NetworkIdentifier, Operation, OperationIdentifier,
+};
+use crate::facade::tendermint_rpc::HttpClient;
+
+/// The blockchain identifier of the networks
+const BLOCKCHAIN: &str = "namada";
+
+/// The symbol of the native token's currency
+const NATIVE_SYMBOL: &str = "NAM";
+
+/// The maximum size of a request's body
+const MAX_REQUEST_BYTES: u64 = 1024 * 1024;
+
+/// The operation types
+const OP_TRANSFER: &str = "transfer";
+const OP_FEE: &str = "fee";
+
+/// The operation statuses
+const STATUS_SUCCESS: &str = "success";
+const STATUS_FAILURE: &str = "failure";
+
+/// The shared state of the endpoints
+struct State {
+    client: HttpClient,
+    network: NetworkIdentifier,
+}
+
+impl State {
+    /// Check that a request is for the network of the node
+    fn check_network(
+        &self,
+        network: &NetworkIdentifier,
+    ) -> Result<(), ApiError> {
+        if *network == self.network {
+            Ok(())
+        } else {
+            Err(ApiError::UnsupportedNetwork)
+        }
+    }
+}
+
+/// The errors of the endpoints
+#[derive(Debug, Error)]
+enum ApiError {
+    #[error("The network is not supported by this node")]
+    UnsupportedNetwork,
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("The node is unavailable: {0}")]
+    Node(String),
+    #[error("The block was not found")]
+    BlockNotFound,
+    #[error("The transaction was not found in the block")]
+    TransactionNotFound,
+    #[error("Unsupported operations: {0}")]
+    UnsupportedOperations(String),
+    #[error("The public key of {0} must be revealed first")]
+    PublicKeyNotRevealed(String),
+    #[error("The transaction was rejected: {0}")]
+    Rejected(String),
+    #[error("Unsupported endpoint: {0}")]
+    Unsupported(&'static str),
+}
+
+impl ApiError {
+    /// All the errors, with placeholder details, listed in the network
+    /// options
+    fn all() -> Vec<Self> {
+        vec![
+            Self::UnsupportedNetwork,
+            Self::InvalidRequest(String::new()),
+            Self::Node(String::new()),
+            Self::BlockNotFound,
+            Self::TransactionNotFound,
+            Self::UnsupportedOperations(String::new()),
+            Self::PublicKeyNotRevealed(String::new()),
+            Self::Rejected(String::new()),
+            Self::Unsupported(""),
+        ]
+    }
+
+    fn code(&self) -> u32 {
+        match self {
+            Self::UnsupportedNetwork => 1,
+            Self::InvalidRequest(_) => 2,
+            Self::Node(_) => 3,
+            Self::BlockNotFound => 4,
+            Self::TransactionNotFound => 5,
+            Self::UnsupportedOperations(_) => 6,
+            Self::PublicKeyNotRevealed(_) => 7,
+            Self::Rejected(_) => 8,
+            Self::Unsupported(_) => 9,
+        }
+    }
+
+    /// The error with its description only, as listed in the network
+    /// options
+    fn description(&self) -> types::Error {
+        let message = match self {
+            Self::UnsupportedNetwork => "Unsupported network",
+            Self::InvalidRequest(_) => "Invalid request",
+            Self::Node(_) => "Node unavailable",
+            Self::BlockNotFound => "Block not found",
+            Self::TransactionNotFound => "Transaction not found",
+            Self::UnsupportedOperations(_) => "Unsupported operations",
+            Self::PublicKeyNotRevealed(_) => "Public key not revealed",
+            Self::Rejected(_) => "Transaction rejected",
+            Self::Unsupported(_) => "Unsupported endpoint",
+        };
+        types::Error {
+            code: self.code(),
+            message: message.to_string(),
+            retriable: self.is_retriable(),
+            details: None,
+        }
+    }
+
+    /// Errors of the node or for blocks that may not be committed yet may
+    /// be retried
+    fn is_retriable(&self) -> bool {
+        matches!(self, Self::Node(_) | Self::BlockNotFound)
+    }
+
+    fn to_error(&self) -> types::Error {
+        types::Error {
+            details: Some(json!({ "error": self.to_string() })),
+            ..self.description()
+        }
+    }
+}
+
+/// Map an error of a query to the node
+fn node_error(err: impl ToString) -> ApiError {
+    ApiError::Node(err.to_string())
+}
+
+/// Serve the endpoints on the given address until the abort signal is
+/// received
+pub async fn serve(
+    laddr: SocketAddr,
+    rpc_address: SocketAddr,
+    chain_id: ChainId,
+    abort_recv: tokio::sync::oneshot::Receiver<()>,
+) {
+    let client = HttpClient::new(format!("http://{}", rpc_address).as_str())
+        .expect("Should be able to create the CometBFT RPC client");
+    let state = Arc::new(State {
+        client,
+        network: NetworkIdentifier {
+            blockchain: BLOCKCHAIN.to_string(),
+            network: chain_id.to_string(),
+        },
+    });
+    let server = warp::serve(routes(state)).try_bind_with_graceful_shutdown(
+        laddr,
+        async move {
+            let _ = abort_recv.await;
+        },
+    );
+    match server {
+        Ok((laddr, server)) => {
+            tracing::info!(?laddr, "Serving the Rosetta API");
+            server.await;
+        }
+        Err(err) => {
+            tracing::error!(?laddr, "Failed to serve the Rosetta API: {err}");
+        }
+    }
+}
+
+fn routes(
+    state: Arc<State>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_state = warp::any().map(move || state.clone());
+    warp::post()
+        .and(warp::path::full())
+        .and(warp::body::content_length_limit(MAX_REQUEST_BYTES))
+        .and(warp::body::json())
+        .and(with_state)
+        .then(
+            |path: warp::path::FullPath,
+             body: Value,
+             state: Arc<State>| async move {
+                match dispatch(&state, path.as_str(), body).await {
+                    Ok(Some(response)) => warp::reply::with_status(
+                        warp::reply::json(&response),
+                        StatusCode::OK,
+                    ),
+                    Ok(None) => warp::reply::with_status(
+                        warp::reply::json(&json!({})),
+                        StatusCode::NOT_FOUND,
+                    ),
+                    // The Rosetta API responds to all errors with a 500
+                    Err(err) => warp::reply::with_status(
+                        warp::reply::json(&err.to_error()),
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                    ),
+                }
+            },
+        )
+}
+
+/// Handle a request to the endpoint at the given path. Returns `None` for
+/// unknown endpoints.
+async fn dispatch(
+    state: &State,
+    path: &str,
+    body: Value,
+) -> Result<Option<Value>, ApiError> {
+    let response = match path {
+        "/network/list" => reply(data::network_list(state, parse(body)?).await),
+        "/network/status" => {
+            reply(data::network_status(state, parse(body)?).await)
+        }
+        "/network/options" => {
+            reply(data::network_options(state, parse(body)?).await)
+        }
+        "/account/balance" => {
+            reply(data::account_balance(state, parse(body)?).await)
+        }
+        "/block" => reply(data::block(state, parse(body)?).await),
+        "/block/transaction" => {
+            reply(data::block_transaction(state, parse(body)?).await)
+        }
+        "/construction/derive" => {
+            reply(construction::derive(state, parse(body)?).await)
+        }
+        "/construction/preprocess" => {
+            reply(construction::preprocess(state, parse(body)?).await)
+        }
+        "/construction/metadata" => {
+            reply(construction::metadata(state, parse(body)?).await)
+        }
+        "/construction/parse" => {
+            reply(construction::parse(state, parse(body)?).await)
+        }
+        "/construction/hash" => {
+            reply(construction::hash(state, parse(body)?).await)
+        }
+        "/construction/submit" => {
+            reply(construction::submit(state, parse(body)?).await)
+        }
+        "/construction/payloads" | "/construction/combine" => {
+            Err(ApiError::Unsupported(
+                "the wrapper and inner transaction signatures can't be \
+                 requested in a single round",
+            ))
+        }
+        _ => return Ok(None),
+    };
+    response.map(Some)
+}
+
+fn parse<T: DeserializeOwned>(body: Value) -> Result<T, ApiError> {
+    serde_json::from_value(body)
+        .map_err(|err| ApiError::InvalidRequest(err.to_string()))
+}
+
+fn reply<T: Serialize>(
+    response: Result<T, ApiError>,
+) -> Result<Value, ApiError> {
+    response.map(|response| {
+        serde_json::to_value(response)
+            .expect("Serializing a response shouldn't fail")
+    })
+}
+
+/// Get the currency of a token
+async fn currency(
+    state: &State,
+    native_token: &Address,
+    token: &Address,
+) -> Result<Currency, ApiError> {
+    if token == native_token {
+        return Ok(native_currency(native_token));
+    }
+    let denom = RPC
+        .vp()
+        .token()
+        .denomination(&state.client, token)
+        .await
+        .map_err(node_error)?
+        .ok_or_else(|| {
+            ApiError::InvalidRequest(format!("Unknown token {token}"))
+        })?;
+    Ok(Currency {
+        symbol: token.to_string(),
+        decimals: denom.0.into(),
+        metadata: Some(json!({ "address": token })),
+    })
+}
+
+fn native_currency(native_token: &Address) -> Currency {
+    Currency {
+        symbol: NATIVE_SYMBOL.to_string(),
+        decimals: token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        metadata: Some(json!({ "address": native_token })),
+    }
+}
+
+/// Get the token of a currency, from the address in its metadata or else its
+/// symbol, which is the token's address for all but the native token
+fn currency_token(
+    currency: &Currency,
+    native_token: &Address,
+) -> Result<Address, ApiError> {
+    let address = currency
+        .metadata
+        .as_ref()
+        .and_then(|metadata| metadata.get("address"))
+        .and_then(Value::as_str);
+    match address {
+        Some(address) => parse_address(address),
+        None if currency.symbol == NATIVE_SYMBOL => Ok(native_token.clone()),
+        None => parse_address(&currency.symbol),
+    }
+}
+
+fn parse_address(address: &str) -> Result<Address, ApiError> {
+    Address::decode(address).map_err(|err| {
+        ApiError::InvalidRequest(format!("Invalid address {address}: {err}"))
+    })
+}
+
+/// Make an amount of the currency, negative for debits
+fn amount(value: token::Amount, currency: &Currency, debit: bool) -> Amount {
+    Amount {
+        value: if debit && !value.is_zero() {
+            format!("-{value}")
+        } else {
+            value.to_string()
+        },
+        currency: currency.clone(),
+    }
+}
+
+/// The debit from the source and the credit to the target of a transfer, as
+/// operations numbered from the given index
+fn transfer_operations(
+    first_index: u64,
+    source: &Address,
+    target: &Address,
+    value: token::Amount,
+    currency: &Currency,
+    status: Option<&str>,
+) -> [Operation; 2] {
+    let debit = OperationIdentifier { index: first_index };
+    let credit = OperationIdentifier {
+        index: first_index + 1,
+    };
+    [
+        Operation {
+            operation_identifier: debit.clone(),
+            related_operations: None,
+            kind: OP_TRANSFER.to_string(),
+            status: status.map(str::to_string),
+            account: Some(types::AccountIdentifier {
+                address: source.to_string(),
+            }),
+            amount: Some(amount(value, currency, true)),
+        },
+        Operation {
+            operation_identifier: credit,
+            related_operations: Some(vec![debit]),
+            kind: OP_TRANSFER.to_string(),
+            status: status.map(str::to_string),
+            account: Some(types::AccountIdentifier {
+                address: target.to_string(),
+            }),
+            amount: Some(amount(value, currency, false)),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod test_rosetta {
+    use namada::types::address::nam;
+    use namada::types::address::testing::established_address_1;
+
+    use super::*;
+
+    #[test]
+    fn test_currency_token() {
+        let native_token = nam();
+        let currency = native_currency(&native_token);
+        assert_eq!(currency_token(&currency, &native_token).unwrap(), nam());
+
+        let token = established_address_1();
+        let currency = Currency {
+            symbol: token.to_string(),
+            decimals: 0,
+            metadata: None,
+        };
+        assert_eq!(currency_token(&currency, &native_token).unwrap(), token);
+
+        let currency = Currency {
+            symbol: "BTC".to_string(),
+            decimals: 8,
+            metadata: None,
+        };
+        let result = currency_token(&currency, &native_token);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_transfer_operations() {
+        let native_token = nam();
+        let currency = native_currency(&native_token);
+        let source = established_address_1();
+        let value = token::Amount::from_u64(1_000_000);
+        let [debit, credit] = transfer_operations(
+            2,
+            &source,
+            &native_token,
+            value,
+            &currency,
+            Some(STATUS_SUCCESS),
+        );
+        assert_eq!(debit.operation_identifier.index, 2);
+        assert_eq!(debit.amount.unwrap().value, "-1000000");
+        assert_eq!(credit.operation_identifier.index, 3);
+        assert_eq!(
+            credit.related_operations,
+            Some(vec![debit.operation_identifier])
+        );
+        assert_eq!(credit.amount.unwrap().value, "1000000");
+    }
+
+    #[test]
+    fn test_error_codes_are_distinct() {
+        let errors = ApiError::all();
+        let mut codes: Vec<_> = errors.iter().map(ApiError::code).collect();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+    }
+}
@@ -0,0 +1,326 @@
+//! A caching proxy in front of the node's CometBFT RPC, that reduces the load
+//! of query-heavy clients, such as block explorers, on CometBFT.
+//!
+//! JSON-RPC requests are forwarded to CometBFT. The successful responses of
+//! the requests whose results can't change anymore, i.e. the blocks, block
+//! results and headers at a given height and the txs by hash, are kept in an
+//! LRU cache and the same requests are then served from it without reaching
+//! CometBFT. All the other requests, including batches and requests in the
+//! URI form (`GET /block?height=1`), are forwarded as they are. Websocket
+//! subscriptions aren't proxied.
+
+use std::collections::hash_map::RandomState;
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+
+use bytes::Bytes;
+use clru::{CLruCache, CLruCacheConfig, WeightScale};
+use serde::Deserialize;
+use serde_json::value::RawValue;
+use serde_json::Value;
+use warp::http::header::CONTENT_TYPE;
+use warp::http::{Response, StatusCode};
+use warp::Filter;
+
+/// The maximum size of the cached responses in bytes, when not configured
+pub const DEFAULT_CACHE_BYTES: usize = 64 * 1024 * 1024;
+
+/// The maximum size of a request's body
+const MAX_REQUEST_BYTES: u64 = 1024 * 1024;
+
+/// The methods whose successful responses are immutable, provided that the
+/// request has the given param, and can be cached. Without it, the requests
+/// for blocks are for the latest block.
+const CACHEABLE_METHODS: [(&str, &str); 4] = [
+    ("block", "height"),
+    ("block_results", "height"),
+    ("header", "height"),
+    ("tx", "hash"),
+];
+
+/// The cached results of requests, identified by their method and params
+type ResponseCache =
+    CLruCache<String, Arc<RawValue>, RandomState, ResponseCacheScale>;
+
+/// Weighs the cached results by their size, so that the cache's capacity
+/// limits the memory they use
+#[derive(Debug)]
+struct ResponseCacheScale;
+
+impl WeightScale<String, Arc<RawValue>> for ResponseCacheScale {
+    fn weight(&self, key: &String, value: &Arc<RawValue>) -> usize {
+        key.len() + value.get().len()
+    }
+}
+
+/// The shared state of the proxy
+struct State {
+    client: reqwest::Client,
+    /// The URL of the CometBFT RPC
+    upstream: String,
+    cache: Mutex<ResponseCache>,
+}
+
+/// A JSON-RPC request, of which only the parts that identify a cacheable
+/// request are parsed
+#[derive(Debug, Deserialize)]
+struct JsonRpcRequest {
+    id: Box<RawValue>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+/// A JSON-RPC response, of which only the result of a successful response is
+/// parsed
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    result: Option<Box<RawValue>>,
+}
+
+/// Serve the proxy on the given address until the abort signal is received.
+///
+/// # Panics
+/// The `cache_bytes` must be non-zero.
+pub async fn serve(
+    laddr: SocketAddr,
+    rpc_address: SocketAddr,
+    cache_bytes: usize,
+    abort_recv: tokio::sync::oneshot::Receiver<()>,
+) {
+    let cache = CLruCache::with_config(
+        CLruCacheConfig::new(NonZeroUsize::new(cache_bytes).unwrap())
+            .with_scale(ResponseCacheScale),
+    );
+    let state = Arc::new(State {
+        client: reqwest::Client::new(),
+        upstream: format!("http://{}", rpc_address),
+        cache: Mutex::new(cache),
+    });
+    let server = warp::serve(routes(state)).try_bind_with_graceful_shutdown(
+        laddr,
+        async move {
+            let _ = abort_recv.await;
+        },
+    );
+    match server {
+        Ok((laddr, server)) => {
+            tracing::info!(?laddr, "Serving the CometBFT RPC proxy");
+            server.await;
+        }
+        Err(err) => {
+            tracing::error!(
+                ?laddr,
+                "Failed to serve the CometBFT RPC proxy: {err}"
+            );
+        }
+    }
+}
+
+fn routes(
+    state: Arc<State>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let with_state = warp::any().map(move || state.clone());
+    let json_rpc = warp::post()
+        .and(warp::path::end())
+        .and(warp::body::content_length_limit(MAX_REQUEST_BYTES))
+        .and(warp::body::bytes())
+        .and(with_state.clone())
+        .then(|body: Bytes, state: Arc<State>| async move {
+            json_rpc(&state, body).await
+        });
+    let uri = warp::get()
+        .and(warp::path::full())
+        .and(
+            warp::query::raw()
+                .or(warp::any().map(String::new))
+                .unify(),
+        )
+        .and(with_state)
+        .then(
+            |path: warp::path::FullPath, query: String, state: Arc<State>| {
+                async move { uri_request(&state, path.as_str(), &query).await }
+            },
+        );
+    json_rpc.or(uri)
+}
+
+/// Serve a JSON-RPC request from the cache or forward it to CometBFT
+async fn json_rpc(state: &State, body: Bytes) -> Response<Bytes> {
+    let cacheable = cacheable_request(&body);
+    if let Some((key, id)) = &cacheable {
+        let cached = state.cache.lock().unwrap().get(key).cloned();
+        if let Some(result) = cached {
+            return json_response(StatusCode::OK, rpc_response(id, &result));
+        }
+    }
+
+    let response = state
+        .client
+        .post(&state.upstream)
+        .header(CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await;
+    let (response, body) = forward_response(response).await;
+    if let (Some((key, _id)), Some(body)) = (cacheable, body) {
+        if let Ok(JsonRpcResponse {
+            result: Some(result),
+        }) = serde_json::from_slice(&body)
+        {
+            // A result too large for the cache isn't cached
+            let _ = state
+                .cache
+                .lock()
+                .unwrap()
+                .put_with_weight(key, Arc::from(result));
+        }
+    }
+    response
+}
+
+/// Forward a request in the URI form to CometBFT
+async fn uri_request(
+    state: &State,
+    path: &str,
+    query: &str,
+) -> Response<Bytes> {
+    let mut url = format!("{}{}", state.upstream, path);
+    if !query.is_empty() {
+        url = format!("{url}?{query}");
+    }
+    let response = state.client.get(url).send().await;
+    forward_response(response).await.0
+}
+
+/// Convert the response of CometBFT to a response of the proxy. The body is
+/// also returned when the request succeeded.
+async fn forward_response(
+    response: reqwest::Result<reqwest::Response>,
+) -> (Response<Bytes>, Option<Bytes>) {
+    let result = match response {
+        Ok(response) => {
+            let status = StatusCode::from_u16(response.status().as_u16())
+                .unwrap_or(StatusCode::BAD_GATEWAY);
+            response.bytes().await.map(|body| (status, body))
+        }
+        Err(err) => Err(err),
+    };
+    match result {
+        Ok((status, body)) => (
+            json_response(status, body.clone()),
+            status.is_success().then_some(body),
+        ),
+        Err(err) => {
+            tracing::debug!("The CometBFT RPC request failed: {err}");
+            let body = Bytes::from(format!("CometBFT RPC error: {err}"));
+            let response = Response::builder()
+                .status(StatusCode::BAD_GATEWAY)
+                .body(body)
+                .expect("The response must be valid");
+            (response, None)
+        }
+    }
+}
+
+fn json_response(status: StatusCode, body: Bytes) -> Response<Bytes> {
+    Response::builder()
+        .status(status)
+        .header(CONTENT_TYPE, "application/json")
+        .body(body)
+        .expect("The response must be valid")
+}
+
+/// Find the cache key and the ID of a single JSON-RPC request whose result
+/// can be cached
+fn cacheable_request(body: &[u8]) -> Option<(String, Box<RawValue>)> {
+    let JsonRpcRequest { id, method, params } =
+        serde_json::from_slice(body).ok()?;
+    let (_, param) = CACHEABLE_METHODS
+        .iter()
+        .find(|(cacheable, _)| *cacheable == method)?;
+    match params.get(param) {
+        None | Some(Value::Null) => None,
+        Some(_) => Some((format!("{method}:{params}"), id)),
+    }
+}
+
+/// Make the body of a JSON-RPC response with a result
+fn rpc_response(id: &RawValue, result: &RawValue) -> Bytes {
+    Bytes::from(format!(
+        r#"{{"jsonrpc":"2.0","id":{},"result":{}}}"#,
+        id.get(),
+        result.get()
+    ))
+}
+
+#[cfg(test)]
+mod test_rpc_proxy {
+    use super::*;
+
+    #[test]
+    fn test_cacheable_request() {
+        let (key, id) = cacheable_request(
+            br#"{"jsonrpc":"2.0","id":7,"method":"block","params":{"height":"5"}}"#,
+        )
+        .unwrap();
+        assert_eq!(key, r#"block:{"height":"5"}"#);
+        assert_eq!(id.get(), "7");
+
+        // The same request with a different ID has the same key
+        let (other_key, _) = cacheable_request(
+            br#"{"jsonrpc":"2.0","id":"a","method":"block","params":{"height":"5"}}"#,
+        )
+        .unwrap();
+        assert_eq!(key, other_key);
+
+        assert!(
+            cacheable_request(
+                br#"{"jsonrpc":"2.0","id":1,"method":"tx","params":{"hash":"AA","prove":false}}"#
+            )
+            .is_some()
+        );
+        // The latest block
+        assert!(cacheable_request(
+            br#"{"jsonrpc":"2.0","id":1,"method":"block","params":{}}"#
+        )
+        .is_none());
+        assert!(
+            cacheable_request(
+                br#"{"jsonrpc":"2.0","id":1,"method":"block","params":{"height":null}}"#
+            )
+            .is_none()
+        );
+        // Mutable results
+        assert!(cacheable_request(
+            br#"{"jsonrpc":"2.0","id":1,"method":"status","params":{}}"#
+        )
+        .is_none());
+        // Batches
+        assert!(
+            cacheable_request(
+                br#"[{"jsonrpc":"2.0","id":1,"method":"block","params":{"height":"5"}}]"#
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn test_rpc_response() {
+        let id = RawValue::from_string(r#""req""#.to_string()).unwrap();
+        let result =
+            RawValue::from_string(r#"{"block":{"height":"5"}}"#.to_string())
+                .unwrap();
+        let body = rpc_response(&id, &result);
+        let response: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(
+            response,
+            serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": "req",
+                "result": {"block": {"height": "5"}},
+            })
+        );
+    }
+}
@@ -0,0 +1,778 @@
+//! The updates applied by the modules when a new epoch begins, run as an
+//! ordered pipeline of hooks.
+//!
+//! Each module registers its [`EpochHook`]s in [`EpochHooks::load`] for one
+//! of the [`EpochStage`]s of `FinalizeBlock`. A hook may name the hooks of
+//! the same stage that it must run after. The order of a stage is resolved
+//! once, when the shell is created: the hooks run after their dependencies
+//! and otherwise in the order of their registration, so that the order is
+//! deterministic, as required for consensus.
+//!
+//! The time taken by each hook is logged and kept until the next epoch.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use namada::governance::pgf::inflation as pgf_inflation;
+use namada::ledger::events::{EventLevel, EventType};
+use namada::ledger::pos::namada_proof_of_stake;
+use namada::proof_of_stake::parameters::PosParams;
+use namada::proof_of_stake::types::{
+    DeactivationReason, ReactivationReason, ValidatorState,
+};
+use namada::state::wl_storage::WriteLogAndStorage;
+use namada::token::conversion::update_allowed_conversions;
+use namada::types::storage::Epoch;
+
+use super::governance::execute_governance_proposals;
+use super::*;
+
+/// The points of `FinalizeBlock` at which the epoch hooks run, in the order
+/// of the execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EpochStage {
+    /// At the beginning of the block, before the votes and the evidence of
+    /// the block are processed
+    BeginBlock,
+    /// After the slashes from the evidence of the block are recorded
+    AfterEvidence,
+    /// After the liveness of the validators is recorded
+    AfterLiveness,
+}
+
+/// The state of the block passed to the epoch hooks
+pub struct EpochContext<'a> {
+    /// The height of the first block of the new epoch
+    pub height: BlockHeight,
+    /// The new epoch
+    pub epoch: Epoch,
+    /// The PoS parameters of the new epoch
+    pub pos_params: &'a PosParams,
+    /// The response of the block, to which the hooks add their events
+    pub response: &'a mut shim::response::FinalizeBlock,
+}
+
+/// The function of an epoch hook
+pub type EpochHookFn<D, H> =
+    fn(&mut Shell<D, H>, &mut EpochContext<'_>) -> Result<()>;
+
+/// An update of a module applied at the beginning of an epoch
+pub struct EpochHook<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// The name of the hook, unique across the stages
+    pub name: &'static str,
+    /// The stage at which the hook runs
+    pub stage: EpochStage,
+    /// The names of the hooks of the same stage that must run before this one
+    pub after: &'static [&'static str],
+    /// The update
+    pub run: EpochHookFn<D, H>,
+}
+
+impl<D, H> Clone for EpochHook<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name,
+            stage: self.stage,
+            after: self.after,
+            run: self.run,
+        }
+    }
+}
+
+/// The time taken by an epoch hook
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookTiming {
+    /// The stage at which the hook ran
+    pub stage: EpochStage,
+    /// The name of the hook
+    pub name: &'static str,
+    /// The time taken by the hook
+    pub elapsed: Duration,
+}
+
+/// Errors of the ordering of the epoch hooks
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum OrderError {
+    #[error("The epoch hook {0} is registered more than once")]
+    Duplicate(&'static str),
+    #[error(
+        "The epoch hook {hook} must run after {dependency}, which is not a \
+         hook of the same stage"
+    )]
+    UnknownDependency {
+        hook: &'static str,
+        dependency: &'static str,
+    },
+    #[error("The epoch hooks {0:?} depend on each other in a cycle")]
+    Cycle(Vec<&'static str>),
+}
+
+/// The epoch hooks of the modules, in the resolved order of each stage
+pub struct EpochHooks<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    stages: BTreeMap<EpochStage, Vec<EpochHook<D, H>>>,
+    last_timings: Vec<HookTiming>,
+}
+
+impl<D, H> EpochHooks<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Order the given hooks, listed in the order of their registration
+    pub fn new(
+        hooks: Vec<EpochHook<D, H>>,
+    ) -> std::result::Result<Self, OrderError> {
+        let mut names = HashSet::new();
+        if let Some(hook) = hooks.iter().find(|hook| !names.insert(hook.name)) {
+            return Err(OrderError::Duplicate(hook.name));
+        }
+        let mut registered: BTreeMap<EpochStage, Vec<EpochHook<D, H>>> =
+            BTreeMap::new();
+        for hook in hooks {
+            registered.entry(hook.stage).or_default().push(hook);
+        }
+        let mut stages = BTreeMap::new();
+        for (stage, hooks) in registered {
+            let deps: Vec<_> =
+                hooks.iter().map(|hook| (hook.name, hook.after)).collect();
+            let order = resolve_order(&deps)?;
+            let ordered =
+                order.into_iter().map(|ix| hooks[ix].clone()).collect();
+            stages.insert(stage, ordered);
+        }
+        Ok(Self {
+            stages,
+            last_timings: vec![],
+        })
+    }
+
+    /// Load the epoch hooks of the modules
+    pub fn load() -> Self {
+        let hooks = vec![
+            // MASP rewards
+            EpochHook {
+                name: "masp_conversions",
+                stage: EpochStage::BeginBlock,
+                after: &[],
+                run: masp_conversions,
+            },
+            // Governance
+            EpochHook {
+                name: "governance_proposals",
+                stage: EpochStage::BeginBlock,
+                after: &[],
+                run: governance_proposals,
+            },
+            // PoS
+            EpochHook {
+                name: "pos_validator_sets",
+                stage: EpochStage::BeginBlock,
+                after: &["governance_proposals"],
+                run: pos_validator_sets,
+            },
+            EpochHook {
+                name: "pos_total_consensus_stake",
+                stage: EpochStage::BeginBlock,
+                after: &["pos_validator_sets"],
+                run: pos_total_consensus_stake,
+            },
+            EpochHook {
+                name: "pos_validator_set_history",
+                stage: EpochStage::BeginBlock,
+                after: &["pos_validator_sets"],
+                run: pos_validator_set_history,
+            },
+            EpochHook {
+                name: "pos_slashes",
+                stage: EpochStage::AfterEvidence,
+                after: &[],
+                run: pos_slashes,
+            },
+            EpochHook {
+                name: "pos_inflation",
+                stage: EpochStage::AfterEvidence,
+                // Slashes may affect the rewards of the last epoch
+                after: &["pos_slashes"],
+                run: pos_inflation,
+            },
+            EpochHook {
+                name: "pos_self_bond_updates",
+                stage: EpochStage::AfterEvidence,
+                after: &["pos_inflation", "pgf_inflation"],
+                run: pos_self_bond_updates,
+            },
+            EpochHook {
+                name: "pos_liveness_pruning",
+                stage: EpochStage::AfterLiveness,
+                after: &[],
+                run: pos_liveness_pruning,
+            },
+            // Ethereum bridge
+            EpochHook {
+                name: "eth_bridge_voting_power_snapshot",
+                stage: EpochStage::BeginBlock,
+                // The snapshot is taken from the stake of the new consensus
+                // validator set
+                after: &["pos_total_consensus_stake"],
+                run: eth_bridge_voting_power_snapshot,
+            },
+            // PGF
+            EpochHook {
+                name: "pgf_inflation",
+                stage: EpochStage::AfterEvidence,
+                after: &["pos_inflation"],
+                run: pgf_inflation,
+            },
+        ];
+        Self::new(hooks).expect("The epoch hooks must have a valid order")
+    }
+
+    /// The names of the hooks of the given stage, in the order they run
+    pub fn order(&self, stage: EpochStage) -> Vec<&'static str> {
+        self.stages
+            .get(&stage)
+            .map(|hooks| hooks.iter().map(|hook| hook.name).collect())
+            .unwrap_or_default()
+    }
+
+    /// The time taken by the hooks run in the last new epoch, in the order
+    /// they ran
+    pub fn last_timings(&self) -> &[HookTiming] {
+        &self.last_timings
+    }
+}
+
+impl<D, H> std::fmt::Debug for EpochHooks<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(
+                self.stages.keys().map(|stage| (stage, self.order(*stage))),
+            )
+            .finish()
+    }
+}
+
+/// Resolve the order of the hooks given by their names and dependencies, in
+/// the order of their registration. Returns the indices of the hooks in the
+/// order they must run. Of the hooks whose dependencies have run, the one
+/// registered first runs next.
+fn resolve_order(
+    hooks: &[(&'static str, &'static [&'static str])],
+) -> std::result::Result<Vec<usize>, OrderError> {
+    let mut dependents = vec![vec![]; hooks.len()];
+    let mut pending_deps = vec![0_usize; hooks.len()];
+    for (ix, &(hook, after)) in hooks.iter().enumerate() {
+        for &dependency in after {
+            let dep_ix = hooks
+                .iter()
+                .position(|(name, _)| *name == dependency)
+                .ok_or(OrderError::UnknownDependency { hook, dependency })?;
+            dependents[dep_ix].push(ix);
+            pending_deps[ix] += 1;
+        }
+    }
+    let mut order = Vec::with_capacity(hooks.len());
+    let mut done = vec![false; hooks.len()];
+    while let Some(next) =
+        (0..hooks.len()).find(|ix| !done[*ix] && pending_deps[*ix] == 0)
+    {
+        done[next] = true;
+        order.push(next);
+        for dependent in &dependents[next] {
+            pending_deps[*dependent] -= 1;
+        }
+    }
+    if order.len() < hooks.len() {
+        let cycle = (0..hooks.len())
+            .filter(|ix| !done[*ix])
+            .map(|ix| hooks[ix].0)
+            .collect();
+        return Err(OrderError::Cycle(cycle));
+    }
+    Ok(order)
+}
+
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Run the epoch hooks of the given stage in their order, recording the
+    /// time taken by each of them
+    pub(super) fn run_epoch_hooks(
+        &mut self,
+        stage: EpochStage,
+        height: BlockHeight,
+        epoch: Epoch,
+        pos_params: &PosParams,
+        response: &mut shim::response::FinalizeBlock,
+    ) -> Result<()> {
+        if stage == EpochStage::BeginBlock {
+            self.epoch_hooks.last_timings.clear();
+        }
+        let hooks = self
+            .epoch_hooks
+            .stages
+            .get(&stage)
+            .cloned()
+            .unwrap_or_default();
+        let mut ctx = EpochContext {
+            height,
+            epoch,
+            pos_params,
+            response,
+        };
+        for hook in hooks {
+            let start = Instant::now();
+            (hook.run)(self, &mut ctx)?;
+            let elapsed = start.elapsed();
+            tracing::debug!(
+                ?stage,
+                hook = hook.name,
+                ?elapsed,
+                "Ran the epoch hook"
+            );
+            self.epoch_hooks.last_timings.push(HookTiming {
+                stage,
+                name: hook.name,
+                elapsed,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Update the allowed conversions of the MASP, which distribute the rewards
+/// of the shielded tokens
+fn masp_conversions<D, H>(
+    shell: &mut Shell<D, H>,
+    _ctx: &mut EpochContext<'_>,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    update_allowed_conversions(&mut shell.wl_storage)?;
+    Ok(())
+}
+
+/// Execute the governance proposals that ended in the last epoch
+fn governance_proposals<D, H>(
+    shell: &mut Shell<D, H>,
+    ctx: &mut EpochContext<'_>,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    execute_governance_proposals(shell, ctx.response)?;
+    Ok(())
+}
+
+/// Copy the new_epoch + pipeline_len - 1 validator set into new_epoch +
+/// pipeline_len
+fn pos_validator_sets<D, H>(
+    shell: &mut Shell<D, H>,
+    ctx: &mut EpochContext<'_>,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    namada_proof_of_stake::validator_set_update::copy_validator_sets_and_positions(
+        &mut shell.wl_storage,
+        ctx.pos_params,
+        ctx.epoch,
+        ctx.epoch + ctx.pos_params.pipeline_len,
+    )?;
+    Ok(())
+}
+
+/// Compute the total stake of the consensus validator set and record it in
+/// storage
+fn pos_total_consensus_stake<D, H>(
+    shell: &mut Shell<D, H>,
+    ctx: &mut EpochContext<'_>,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    namada_proof_of_stake::compute_and_store_total_consensus_stake(
+        &mut shell.wl_storage,
+        ctx.epoch,
+    )?;
+    Ok(())
+}
+
+/// Record the consensus validator set of the new epoch in its history
+fn pos_validator_set_history<D, H>(
+    shell: &mut Shell<D, H>,
+    ctx: &mut EpochContext<'_>,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    namada_proof_of_stake::storage::write_validator_set_history(
+        &mut shell.wl_storage,
+        ctx.pos_params,
+        ctx.epoch,
+    )?;
+    Ok(())
+}
+
+/// Process the slashes queued for the new epoch
+fn pos_slashes<D, H>(
+    shell: &mut Shell<D, H>,
+    _ctx: &mut EpochContext<'_>,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    shell.process_slashes();
+    Ok(())
+}
+
+/// Calculate the new inflation rate, mint the new tokens to the PoS account,
+/// then update the reward products of the validators with respect to the last
+/// epoch. Then restake the rewards of the next batch of bonds that opted into
+/// auto-compounding, skipping the ones that fail.
+fn pos_inflation<D, H>(
+    shell: &mut Shell<D, H>,
+    ctx: &mut EpochContext<'_>,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let last_epoch = ctx.epoch.prev();
+
+    // Get the number of blocks in the last epoch
+    let first_block_of_last_epoch = shell
+        .wl_storage
+        .storage
+        .block
+        .pred_epochs
+        .first_block_heights[last_epoch.0 as usize]
+        .0;
+    let num_blocks_in_last_epoch =
+        shell.wl_storage.storage.block.height.0 - first_block_of_last_epoch;
+
+    namada_proof_of_stake::rewards::apply_inflation(
+        &mut shell.wl_storage,
+        last_epoch,
+        num_blocks_in_last_epoch,
+    )?;
+    for bond_id in namada_proof_of_stake::rewards::next_auto_compound_bonds(
+        &mut shell.wl_storage,
+    )? {
+        // Restake on a fork of the storage, so that a bond that fails to be
+        // restaked neither leaves partial changes behind nor halts the chain
+        let mut fork = shell.wl_storage.fork();
+        match namada_proof_of_stake::rewards::compound_bond_rewards(
+            &mut fork, &bond_id, ctx.epoch,
+        ) {
+            Ok(()) => {
+                let write_log = fork.into_write_log();
+                shell.wl_storage.merge(write_log);
+            }
+            Err(err) => tracing::error!(
+                "Failed to restake the rewards of bond {bond_id}: {err}"
+            ),
+        }
+    }
+    Ok(())
+}
+
+/// Deactivate the validators whose self-bond fell below the minimum and
+/// reactivate the ones that topped it up
+fn pos_self_bond_updates<D, H>(
+    shell: &mut Shell<D, H>,
+    ctx: &mut EpochContext<'_>,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let updates = namada_proof_of_stake::update_validators_below_min_self_bond(
+        &mut shell.wl_storage,
+        ctx.pos_params,
+        ctx.epoch,
+    )?;
+    let pipeline_epoch = ctx.epoch + ctx.pos_params.pipeline_len;
+    for (validator, state) in updates {
+        let reason = if state == ValidatorState::Inactive {
+            DeactivationReason::SelfBondBelowMinimum.to_string()
+        } else {
+            ReactivationReason::SelfBondToppedUp.to_string()
+        };
+        let mut event = Event {
+            event_type: EventType::ValidatorStateChange,
+            level: EventLevel::Block,
+            attributes: Default::default(),
+        };
+        event["validator"] = validator.to_string();
+        event["state"] = state.to_string();
+        event["reason"] = reason;
+        event["epoch"] = pipeline_epoch.to_string();
+        event["height"] = ctx.height.to_string();
+        ctx.response.events.push(event);
+    }
+    Ok(())
+}
+
+/// Prune the liveness data of the validators that are no longer in the
+/// consensus set
+fn pos_liveness_pruning<D, H>(
+    shell: &mut Shell<D, H>,
+    ctx: &mut EpochContext<'_>,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    namada_proof_of_stake::prune_liveness_data(
+        &mut shell.wl_storage,
+        ctx.epoch,
+    )?;
+    Ok(())
+}
+
+/// Snapshot the total stake to evaluate the tallies of the votes cast in the
+/// new epoch
+fn eth_bridge_voting_power_snapshot<D, H>(
+    shell: &mut Shell<D, H>,
+    ctx: &mut EpochContext<'_>,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    namada::eth_bridge::protocol::transactions::votes::snapshot_total_voting_power(
+        &mut shell.wl_storage,
+        ctx.epoch,
+    )?;
+    Ok(())
+}
+
+/// Apply the PGF inflation and emit the events of the resulting IBC
+/// transfers
+fn pgf_inflation<D, H>(
+    shell: &mut Shell<D, H>,
+    ctx: &mut EpochContext<'_>,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    pgf_inflation::apply_inflation(
+        &mut shell.wl_storage,
+        namada::ibc::transfer_over_ibc,
+    )?;
+    for ibc_event in shell.wl_storage.write_log_mut().take_ibc_events() {
+        let mut event = Event::from(ibc_event.clone());
+        // Add the height for IBC event query
+        let height = shell.wl_storage.storage.get_last_block_height() + 1;
+        event["height"] = height.to_string();
+        ctx.response.events.push(event);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_epoch_hooks {
+    use namada::proof_of_stake::storage::read_pos_params;
+
+    use super::*;
+    use crate::node::ledger::shell::test_utils::{self, TestShell};
+
+    type TestHook =
+        EpochHook<namada::state::mockdb::MockDB, namada::state::Sha256Hasher>;
+
+    fn noop<D, H>(
+        _shell: &mut Shell<D, H>,
+        _ctx: &mut EpochContext<'_>,
+    ) -> Result<()>
+    where
+        D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+        H: StorageHasher + Sync + 'static,
+    {
+        Ok(())
+    }
+
+    fn hook(
+        name: &'static str,
+        stage: EpochStage,
+        after: &'static [&'static str],
+    ) -> TestHook {
+        EpochHook {
+            name,
+            stage,
+            after,
+            run: noop,
+        }
+    }
+
+    /// Test that the hooks of the modules run in the order of the sequence
+    /// of updates of a new epoch
+    #[test]
+    fn test_default_order() {
+        let (shell, _recv, _, _) = test_utils::setup();
+        let hooks = &shell.epoch_hooks;
+        assert_eq!(
+            hooks.order(EpochStage::BeginBlock),
+            vec![
+                "masp_conversions",
+                "governance_proposals",
+                "pos_validator_sets",
+                "pos_total_consensus_stake",
+                "pos_validator_set_history",
+                "eth_bridge_voting_power_snapshot",
+            ]
+        );
+        assert_eq!(
+            hooks.order(EpochStage::AfterEvidence),
+            vec![
+                "pos_slashes",
+                "pos_inflation",
+                "pgf_inflation",
+                "pos_self_bond_updates",
+            ]
+        );
+        assert_eq!(
+            hooks.order(EpochStage::AfterLiveness),
+            vec!["pos_liveness_pruning"]
+        );
+    }
+
+    /// Test that the hooks run after their dependencies and otherwise in the
+    /// order of their registration
+    #[test]
+    fn test_resolve_order() {
+        assert_eq!(resolve_order(&[]), Ok(vec![]));
+        assert_eq!(
+            resolve_order(&[("a", &[]), ("b", &[]), ("c", &[])]),
+            Ok(vec![0, 1, 2])
+        );
+        assert_eq!(
+            resolve_order(&[("a", &["c"]), ("b", &[]), ("c", &["b"])]),
+            Ok(vec![1, 2, 0])
+        );
+        // A hook that became ready runs before the later registered ones
+        assert_eq!(
+            resolve_order(&[("a", &["b"]), ("b", &[]), ("c", &[])]),
+            Ok(vec![1, 0, 2])
+        );
+    }
+
+    /// Test the errors of invalid orderings
+    #[test]
+    fn test_order_errors() {
+        assert_eq!(
+            resolve_order(&[("a", &["b"]), ("b", &["a"]), ("c", &[])]),
+            Err(OrderError::Cycle(vec!["a", "b"]))
+        );
+        assert_eq!(
+            resolve_order(&[("a", &["a"])]),
+            Err(OrderError::Cycle(vec!["a"]))
+        );
+        assert_eq!(
+            resolve_order(&[("a", &["z"])]),
+            Err(OrderError::UnknownDependency {
+                hook: "a",
+                dependency: "z"
+            })
+        );
+
+        // Dependencies are only resolved within a stage
+        let err = EpochHooks::new(vec![
+            hook("a", EpochStage::BeginBlock, &[]),
+            hook("b", EpochStage::AfterEvidence, &["a"]),
+        ])
+        .unwrap_err();
+        assert_eq!(
+            err,
+            OrderError::UnknownDependency {
+                hook: "b",
+                dependency: "a"
+            }
+        );
+
+        let err = EpochHooks::new(vec![
+            hook("a", EpochStage::BeginBlock, &[]),
+            hook("a", EpochStage::AfterLiveness, &[]),
+        ])
+        .unwrap_err();
+        assert_eq!(err, OrderError::Duplicate("a"));
+    }
+
+    /// Test that the time taken by each hook is recorded in the order they
+    /// ran and reset at the beginning of the next epoch
+    #[test]
+    fn test_hook_timings() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        shell.epoch_hooks = EpochHooks::new(vec![
+            hook("second", EpochStage::BeginBlock, &["first"]),
+            hook("first", EpochStage::BeginBlock, &[]),
+            hook("last", EpochStage::AfterLiveness, &[]),
+        ])
+        .unwrap();
+
+        let run_stages = |shell: &mut TestShell| {
+            let pos_params = read_pos_params(&shell.wl_storage).unwrap();
+            let mut response = shim::response::FinalizeBlock::default();
+            for stage in [
+                EpochStage::BeginBlock,
+                EpochStage::AfterEvidence,
+                EpochStage::AfterLiveness,
+            ] {
+                shell
+                    .run_epoch_hooks(
+                        stage,
+                        BlockHeight(1),
+                        Epoch(1),
+                        &pos_params,
+                        &mut response,
+                    )
+                    .unwrap();
+            }
+        };
+
+        run_stages(&mut shell);
+        let ran = |shell: &TestShell| {
+            shell
+                .epoch_hooks
+                .last_timings()
+                .iter()
+                .map(|timing| (timing.stage, timing.name))
+                .collect::<Vec<_>>()
+        };
+        let expected = vec![
+            (EpochStage::BeginBlock, "first"),
+            (EpochStage::BeginBlock, "second"),
+            (EpochStage::AfterLiveness, "last"),
+        ];
+        assert_eq!(ran(&shell), expected);
+
+        run_stages(&mut shell);
+        assert_eq!(ran(&shell), expected);
+    }
+}
@@ -3,30 +3,31 @@
 use data_encoding::HEXUPPER;
 use masp_primitives::merkle_tree::CommitmentTree;
 use masp_primitives::sapling::Node;
-use namada::governance::pgf::inflation as pgf_inflation;
 use namada::ledger::events::EventType;
-use namada::ledger::gas::{GasMetering, TxGasMeter};
+use namada::ledger::gas::{Gas, GasMetering, TxGasMeter};
 use namada::ledger::pos::namada_proof_of_stake;
 use namada::ledger::protocol::{self, WrapperArgs};
 use namada::proof_of_stake::storage::{
     find_validator_by_raw_hash, read_last_block_proposer_address,
     write_last_block_proposer_address,
 };
-use namada::state::wl_storage::WriteLogAndStorage;
 use namada::state::write_log::StorageModification;
 use namada::state::{
     ResultExt, StorageRead, StorageWrite, EPOCH_SWITCH_BLOCKS_DELAY,
 };
-use namada::token::conversion::update_allowed_conversions;
 use namada::tx::data::protocol::ProtocolTxType;
+use namada::types::hash::Hash;
 use namada::types::key::tm_raw_hash_to_string;
 use namada::types::storage::{BlockHash, BlockResults, Epoch, Header};
 use namada::vote_ext::ethereum_events::MultiSignedEthEvent;
 use namada::vote_ext::ethereum_tx_data_variants;
 
-use super::governance::execute_governance_proposals;
+use super::epoch_hooks::EpochStage;
+use super::scheduled_txs::execute_scheduled_txs;
+use super::scheduler::PreExecutedTx;
 use super::*;
 use crate::facade::tendermint::abci::types::{Misbehavior, VoteInfo};
+use crate::node::ledger::crash_report;
 use crate::node::ledger::shell::stats::InternalStats;
 
 impl<D, H> Shell<D, H>
@@ -63,6 +64,9 @@ where
         // Begin the new block and check if a new epoch has begun
         let (height, new_epoch) =
             self.update_state(req.header, req.hash, req.byzantine_validators);
+        let _span =
+            tracing::info_span!("finalize_block", height = height.0).entered();
+        crash_report::set_block_height(height);
 
         let (current_epoch, _gas) = self.wl_storage.storage.get_current_epoch();
         let update_for_tendermint = matches!(
@@ -88,28 +92,31 @@ where
                 .expect("Failed tx hashes finalization")
         }
 
-        let pos_params =
-            namada_proof_of_stake::storage::read_pos_params(&self.wl_storage)?;
-
         if new_epoch {
-            update_allowed_conversions(&mut self.wl_storage)?;
-
-            execute_governance_proposals(self, &mut response)?;
-
-            // Copy the new_epoch + pipeline_len - 1 validator set into
-            // new_epoch + pipeline_len
-            namada_proof_of_stake::validator_set_update::copy_validator_sets_and_positions(
+            // Apply the parameter changes scheduled for this epoch before
+            // anything else reads the parameters
+            let applied = namada::parameters::apply_scheduled_changes(
                 &mut self.wl_storage,
-                &pos_params,
                 current_epoch,
-                current_epoch + pos_params.pipeline_len,
             )?;
+            for key in applied {
+                tracing::info!(
+                    "Applied a scheduled parameter change to {key} in epoch \
+                     {current_epoch}."
+                );
+            }
+        }
 
-            // Compute the total stake of the consensus validator set and record
-            // it in storage
-            namada_proof_of_stake::compute_and_store_total_consensus_stake(
-                &mut self.wl_storage,
+        let pos_params =
+            namada_proof_of_stake::storage::read_pos_params(&self.wl_storage)?;
+
+        if new_epoch {
+            self.run_epoch_hooks(
+                EpochStage::BeginBlock,
+                height,
                 current_epoch,
+                &pos_params,
+                &mut response,
             )?;
         }
 
@@ -134,10 +141,13 @@ where
         // Invariant: This has to be applied after
         // `copy_validator_sets_and_positions` if we're starting a new epoch
         if new_epoch {
-            // Invariant: Process slashes before inflation as they may affect
-            // the rewards in the current epoch.
-            self.process_slashes();
-            self.apply_inflation(current_epoch, &mut response)?;
+            self.run_epoch_hooks(
+                EpochStage::AfterEvidence,
+                height,
+                current_epoch,
+                &pos_params,
+                &mut response,
+            )?;
         }
 
         // Consensus set liveness check
@@ -174,11 +184,12 @@ where
         )?;
 
         if new_epoch {
-            // Prune liveness data from validators that are no longer in the
-            // consensus set
-            namada_proof_of_stake::prune_liveness_data(
-                &mut self.wl_storage,
+            self.run_epoch_hooks(
+                EpochStage::AfterLiveness,
+                height,
                 current_epoch,
+                &pos_params,
+                &mut response,
             )?;
         }
 
@@ -195,9 +206,24 @@ where
                 )
         };
 
+        if new_epoch {
+            // Apply the txs scheduled for the new epoch before the txs of
+            // the block
+            execute_scheduled_txs(
+                self,
+                &mut response,
+                height,
+                current_epoch,
+                &native_block_proposer_address,
+            )?;
+        }
+
         // Tracks the accepted transactions
         self.wl_storage.storage.block.results = BlockResults::default();
+        // Tracks the gas used by the txs of the block
+        let mut block_gas_used = Gas::default();
         let mut changed_keys = BTreeSet::new();
+        let mut pre_executed = self.pre_execute_txs(&req.txs);
         for (tx_index, processed_tx) in req.txs.iter().enumerate() {
             let tx = if let Ok(tx) = Tx::try_from(processed_tx.tx.as_ref()) {
                 tx
@@ -209,6 +235,13 @@ where
                 );
                 continue;
             };
+            let _tx_span = tracing::info_span!(
+                "tx",
+                index = tx_index,
+                hash = %tx.header_hash(),
+            )
+            .entered();
+            crash_report::set_tx_hash(tx.header_hash());
             // If [`process_proposal`] rejected a Tx due to invalid signature,
             // emit an event here and move on to next tx.
             if ResultCode::from_u32(processed_tx.result.code).unwrap()
@@ -344,7 +377,8 @@ where
                     ProtocolTxType::BridgePoolVext
                     | ProtocolTxType::BridgePool
                     | ProtocolTxType::ValSetUpdateVext
-                    | ProtocolTxType::ValidatorSetUpdate => (
+                    | ProtocolTxType::ValidatorSetUpdate
+                    | ProtocolTxType::DuplicateVextEvidence => (
                         Event::new_tx_event(&tx, height.0),
                         None,
                         TxGasMeter::new_from_sub_limit(0.into()),
@@ -408,22 +442,35 @@ where
                 },
             };
 
-            match protocol::dispatch_tx(
-                tx,
-                processed_tx.tx.as_ref(),
-                TxIndex(
-                    tx_index
-                        .try_into()
-                        .expect("transaction index out of bounds"),
+            // The tx is consumed by its application, keep a copy for the hooks
+            let hooked_tx = (!self.tx_hooks.is_empty()).then(|| tx.clone());
+            let dispatch_result = match pre_executed.take(tx_index) {
+                Some(PreExecutedTx {
+                    result,
+                    gas_meter,
+                    write_log,
+                    ..
+                }) => {
+                    tx_gas_meter = gas_meter;
+                    self.wl_storage.write_log.restore_tx(write_log);
+                    result
+                }
+                None => protocol::dispatch_tx(
+                    tx,
+                    processed_tx.tx.as_ref(),
+                    TxIndex(
+                        tx_index
+                            .try_into()
+                            .expect("transaction index out of bounds"),
+                    ),
+                    &mut tx_gas_meter,
+                    &mut self.wl_storage,
+                    &mut self.vp_wasm_cache,
+                    &mut self.tx_wasm_cache,
+                    wrapper_args.as_mut(),
                 ),
-                &mut tx_gas_meter,
-                &mut self.wl_storage,
-                &mut self.vp_wasm_cache,
-                &mut self.tx_wasm_cache,
-                wrapper_args.as_mut(),
-            )
-            .map_err(Error::TxApply)
-            {
+            };
+            match dispatch_result.map_err(Error::TxApply) {
                 Ok(ref mut result) => {
                     if result.is_accepted() {
                         if let EventType::Accepted = tx_event.event_type {
@@ -465,6 +512,7 @@ where
                                 self.commit_inner_tx_hash(wrapper);
                             }
                         }
+                        pre_executed.record_writes(&self.wl_storage.write_log);
                         self.wl_storage.commit_tx();
                         if !tx_event.contains_key("code") {
                             tx_event["code"] = ResultCode::Ok.into();
@@ -485,6 +533,7 @@ where
                                     // Add the IBC event besides the tx_event
                                     let mut event = Event::from(ibc_event);
                                     event["height"] = height.to_string();
+                                    event["hash"] = tx_event["hash"].clone();
                                     event
                                 })
                                 // eth bridge events
@@ -516,6 +565,9 @@ where
                         self.wl_storage.drop_tx();
                         tx_event["code"] = ResultCode::InvalidTx.into();
                     }
+                    block_gas_used = block_gas_used
+                        .checked_add(result.gas_used)
+                        .expect("The gas used by a block can't overflow");
                     tx_event["gas_used"] = result.gas_used.to_string();
                     tx_event["info"] = "Check inner_tx for result.".to_string();
                     tx_event["inner_tx"] = result.to_string();
@@ -561,8 +613,11 @@ where
                     stats.increment_errored_txs();
                     self.wl_storage.drop_tx();
 
-                    tx_event["gas_used"] =
-                        tx_gas_meter.get_tx_consumed_gas().to_string();
+                    let gas_used = tx_gas_meter.get_tx_consumed_gas();
+                    block_gas_used = block_gas_used
+                        .checked_add(gas_used)
+                        .expect("The gas used by a block can't overflow");
+                    tx_event["gas_used"] = gas_used.to_string();
                     tx_event["info"] = msg.to_string();
                     if let EventType::Accepted = tx_event.event_type {
                         // If wrapper, invalid tx error code
@@ -581,6 +636,9 @@ where
                     }
                 }
             }
+            if let Some(tx) = hooked_tx {
+                self.tx_hooks.on_finalized_tx(&tx, height, &tx_event);
+            }
             response.events.push(tx_event);
         }
 
@@ -609,6 +667,17 @@ where
             self.wl_storage.write(&anchor_key, ())?;
         }
 
+        // Pause the Ethereum bridge if enough validators voted for it
+        changed_keys.extend(
+            namada::eth_bridge::protocol::emergency_pause::tally_votes(
+                &mut self.wl_storage,
+            )
+            .expect(
+                "Tallying the votes to pause the Ethereum bridge shouldn't \
+                 fail",
+            ),
+        );
+
         if update_for_tendermint {
             self.update_epoch(&mut response);
             // send the latest oracle configs. These may have changed due to
@@ -620,8 +689,27 @@ where
             &mut self.wl_storage,
             native_block_proposer_address,
         )?;
+        namada::ledger::parameters::record_block_gas_used(
+            &mut self.wl_storage,
+            block_gas_used.get_whole_gas_units(),
+        )?;
 
-        self.event_log_mut().log_events(response.events.clone());
+        // Index the heights of the txs by their hashes
+        for event in &response.events {
+            if matches!(
+                event.event_type,
+                EventType::Accepted | EventType::Applied
+            ) {
+                if let Some(hash) =
+                    event.get("hash").and_then(|hash| hash.parse::<Hash>().ok())
+                {
+                    self.wl_storage.write_log.index_tx_height(hash);
+                }
+            }
+        }
+
+        self.event_log_mut()
+            .log_events(height, response.events.clone());
         tracing::debug!("End finalize_block {height} of epoch {current_epoch}");
 
         Ok(response)
@@ -653,6 +741,11 @@ where
 
         self.byzantine_validators = byzantine_validators;
 
+        namada::ledger::parameters::record_block_time(
+            &mut self.wl_storage,
+            header_time,
+        )
+        .expect("Must be able to record the block time");
         let new_epoch = self
             .wl_storage
             .update_epoch(height, header_time)
@@ -679,51 +772,6 @@ where
             .expect("Must be able to update validator set");
     }
 
-    /// Calculate the new inflation rate, mint the new tokens to the PoS
-    /// account, then update the reward products of the validators. This is
-    /// executed while finalizing the first block of a new epoch and is applied
-    /// with respect to the previous epoch.
-    fn apply_inflation(
-        &mut self,
-        current_epoch: Epoch,
-        response: &mut shim::response::FinalizeBlock,
-    ) -> Result<()> {
-        let last_epoch = current_epoch.prev();
-
-        // Get the number of blocks in the last epoch
-        let first_block_of_last_epoch = self
-            .wl_storage
-            .storage
-            .block
-            .pred_epochs
-            .first_block_heights[last_epoch.0 as usize]
-            .0;
-        let num_blocks_in_last_epoch =
-            self.wl_storage.storage.block.height.0 - first_block_of_last_epoch;
-
-        // PoS inflation
-        namada_proof_of_stake::rewards::apply_inflation(
-            &mut self.wl_storage,
-            last_epoch,
-            num_blocks_in_last_epoch,
-        )?;
-
-        // Pgf inflation
-        pgf_inflation::apply_inflation(
-            &mut self.wl_storage,
-            namada::ibc::transfer_over_ibc,
-        )?;
-        for ibc_event in self.wl_storage.write_log_mut().take_ibc_events() {
-            let mut event = Event::from(ibc_event.clone());
-            // Add the height for IBC event query
-            let height = self.wl_storage.storage.get_last_block_height() + 1;
-            event["height"] = height.to_string();
-            response.events.push(event);
-        }
-
-        Ok(())
-    }
-
     // Process the proposer and votes in the block to assign their PoS rewards.
     fn log_block_rewards(
         &mut self,
@@ -1605,6 +1653,77 @@ mod test_finalize_block {
         });
     }
 
+    /// Test that an epoch only ends once both its minimum number of blocks and
+    /// its minimum duration have passed, so that neither fast blocks nor a
+    /// chain halt can end it early.
+    #[test]
+    fn test_epoch_min_blocks_and_min_duration() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+
+        let epoch_duration = EpochDuration {
+            min_num_of_blocks: 5,
+            min_duration: DurationSecs(100),
+        };
+        namada::ledger::parameters::update_epoch_parameter(
+            &mut shell.wl_storage,
+            &epoch_duration,
+        )
+        .unwrap();
+        let start_time = DateTimeUtc::now();
+        shell.wl_storage.storage.next_epoch_min_start_height =
+            shell.wl_storage.get_current_decision_height() + 5;
+        shell.wl_storage.storage.next_epoch_min_start_time =
+            start_time + DurationSecs(100);
+        let epoch = shell.wl_storage.storage.block.epoch;
+
+        let next_block = |shell: &mut TestShell, time: DateTimeUtc| {
+            let mut req = FinalizeBlock::default();
+            req.header.time = time;
+            shell.finalize_block(req).expect("Test failed");
+            shell.commit();
+        };
+
+        // Fast blocks go past the minimum number of blocks well before the
+        // minimum duration has passed
+        for secs in 1..=10 {
+            next_block(&mut shell, start_time + DurationSecs(secs));
+            assert_eq!(shell.wl_storage.storage.block.epoch, epoch);
+            assert!(
+                shell.wl_storage.storage.update_epoch_blocks_delay.is_none()
+            );
+        }
+
+        // After a halt, the minimum duration has passed but the epoch must
+        // still last for its minimum number of blocks
+        let min_start_height =
+            shell.wl_storage.get_current_decision_height() + 3;
+        shell.wl_storage.storage.next_epoch_min_start_height = min_start_height;
+        let halt_end = start_time + DurationSecs(1000);
+        for secs in 0..3 {
+            next_block(&mut shell, halt_end + DurationSecs(secs));
+            assert_eq!(shell.wl_storage.storage.block.epoch, epoch);
+            assert!(
+                shell.wl_storage.storage.update_epoch_blocks_delay.is_none()
+            );
+        }
+
+        // Once both are satisfied, the new epoch is enqueued
+        assert_eq!(
+            shell.wl_storage.get_current_decision_height(),
+            min_start_height
+        );
+        next_block(&mut shell, halt_end + DurationSecs(3));
+        assert_eq!(shell.wl_storage.storage.block.epoch, epoch);
+        assert_eq!(
+            shell.wl_storage.storage.update_epoch_blocks_delay,
+            Some(EPOCH_SWITCH_BLOCKS_DELAY)
+        );
+        for secs in 4..4 + EPOCH_SWITCH_BLOCKS_DELAY {
+            next_block(&mut shell, halt_end + DurationSecs(secs.into()));
+        }
+        assert_eq!(shell.wl_storage.storage.block.epoch, epoch.next());
+    }
+
     /// Test that the finalize block handler never commits changes directly to
     /// the DB.
     #[test]
@@ -2344,6 +2463,122 @@ mod test_finalize_block {
         assert_eq!(query_rewards, token::Amount::zero());
     }
 
+    /// A unit test for the automatic restaking of PoS rewards at the start of
+    /// a new epoch
+    #[test]
+    fn test_auto_compound_rewards() {
+        let (mut shell, _recv, _, _) = setup_with_cfg(SetupCfg {
+            last_height: 0,
+            num_validators: 1,
+            ..Default::default()
+        });
+
+        let mut validator_set: BTreeSet<WeightedValidator> =
+            read_consensus_validator_set_addresses_with_stake(
+                &shell.wl_storage,
+                Epoch::default(),
+            )
+            .unwrap()
+            .into_iter()
+            .collect();
+        let params = read_pos_params(&shell.wl_storage).unwrap();
+        let validator = validator_set.pop_first().unwrap();
+
+        let ck = validator_consensus_key_handle(&validator.address)
+            .get(&shell.wl_storage, Epoch::default(), &params)
+            .unwrap()
+            .unwrap();
+        let hash_string = tm_consensus_key_raw_hash(&ck);
+        let pkh1 = HEXUPPER.decode(hash_string.as_bytes()).unwrap();
+        let votes = vec![VoteInfo {
+            validator: Validator {
+                address: pkh1.clone().try_into().unwrap(),
+                power: (u128::try_from(validator.bonded_stake).unwrap() as u64)
+                    .try_into()
+                    .unwrap(),
+            },
+            sig_info: tendermint::abci::types::BlockSignatureInfo::LegacySigned,
+        }];
+
+        let bond_id = BondId {
+            source: validator.address.clone(),
+            validator: validator.address.clone(),
+        };
+
+        namada_proof_of_stake::set_auto_compound(
+            &mut shell.wl_storage,
+            None,
+            &validator.address,
+            true,
+        )
+        .unwrap();
+        assert!(
+            namada_proof_of_stake::storage::is_auto_compound_enabled(
+                &shell.wl_storage,
+                &validator.address,
+                &validator.address,
+            )
+            .unwrap()
+        );
+
+        next_block_for_inflation(&mut shell, pkh1.clone(), vec![], None);
+        let (current_epoch, inflation) =
+            advance_epoch(&mut shell, &pkh1, &votes, None);
+        assert!(!inflation.is_zero());
+
+        // The rewards have already been claimed and restaked
+        let query_rewards = namada_proof_of_stake::query_reward_tokens(
+            &shell.wl_storage,
+            None,
+            &validator.address,
+            current_epoch,
+        )
+        .unwrap();
+        assert_eq!(query_rewards, token::Amount::zero());
+
+        let pipeline_bond = namada_proof_of_stake::bond_amount(
+            &shell.wl_storage,
+            &bond_id,
+            current_epoch + params.pipeline_len,
+        )
+        .unwrap();
+        let restaked = pipeline_bond - validator.bonded_stake;
+        let diff = inflation - restaked;
+        assert!(diff <= token::Amount::from(1_u64));
+
+        // With compounding, the effective yield is higher than the nominal
+        // rate
+        let rate = namada_proof_of_stake::rewards::estimate_rewards_rate(
+            &shell.wl_storage,
+            None,
+            &validator.address,
+            current_epoch,
+        )
+        .unwrap();
+        assert!(rate.auto_compound);
+        assert!(rate.apr > Dec::zero());
+        assert!(rate.apy > rate.apr);
+
+        // Once disabled, the rewards are left to be claimed
+        namada_proof_of_stake::set_auto_compound(
+            &mut shell.wl_storage,
+            None,
+            &validator.address,
+            false,
+        )
+        .unwrap();
+        let (current_epoch, _inflation) =
+            advance_epoch(&mut shell, &pkh1, &votes, None);
+        let query_rewards = namada_proof_of_stake::query_reward_tokens(
+            &shell.wl_storage,
+            None,
+            &validator.address,
+            current_epoch,
+        )
+        .unwrap();
+        assert!(!query_rewards.is_zero());
+    }
+
     /// A unit test for PoS inflationary rewards claiming
     #[test]
     fn test_claim_validator_commissions() {
@@ -2905,6 +3140,80 @@ mod test_finalize_block {
         );
     }
 
+    /// Test that the heights of the txs are indexed by their hashes on commit
+    #[test]
+    fn test_tx_heights_indexed() {
+        let (mut shell, _, _, _) = setup();
+
+        let (wrapper_tx, processed_tx) =
+            mk_wrapper_tx(&shell, &crate::wallet::defaults::albert_keypair());
+        let wrapper_hash = wrapper_tx.header_hash();
+
+        let event = shell
+            .finalize_block(FinalizeBlock {
+                txs: vec![processed_tx],
+                ..Default::default()
+            })
+            .expect("Test failed")
+            .remove(0);
+        let height: BlockHeight = event["height"].parse().unwrap();
+
+        // The index is only written on commit
+        assert_eq!(
+            shell
+                .shell
+                .wl_storage
+                .storage
+                .read_tx_height(&wrapper_hash)
+                .unwrap(),
+            None
+        );
+        shell.commit();
+        assert_eq!(
+            shell
+                .shell
+                .wl_storage
+                .storage
+                .read_tx_height(&wrapper_hash)
+                .unwrap(),
+            Some(height)
+        );
+    }
+
+    /// Test that the gas used by the txs of a block is recorded
+    #[test]
+    fn test_block_gas_used_recorded() {
+        let (mut shell, _, _, _) = setup();
+
+        let (_, processed_tx) =
+            mk_wrapper_tx(&shell, &crate::wallet::defaults::albert_keypair());
+        let event = shell
+            .finalize_block(FinalizeBlock {
+                txs: vec![processed_tx],
+                ..Default::default()
+            })
+            .expect("Test failed")
+            .remove(0);
+        let tx_gas_used: u64 = event["gas_used"].parse().unwrap();
+        assert!(tx_gas_used > 0);
+        assert_eq!(
+            namada::ledger::parameters::read_block_gas_used(&shell.wl_storage)
+                .unwrap(),
+            tx_gas_used
+        );
+
+        // A block without txs uses no gas
+        shell.commit();
+        shell
+            .finalize_block(FinalizeBlock::default())
+            .expect("Test failed");
+        assert_eq!(
+            namada::ledger::parameters::read_block_gas_used(&shell.wl_storage)
+                .unwrap(),
+            0
+        );
+    }
+
     /// Test that a decrypted tx that has already been applied in the same block
     /// doesn't get reapplied
     #[test]
@@ -5144,4 +5453,288 @@ mod test_finalize_block {
             control_receiver.recv().await.expect("Test failed");
         assert_eq!(u64::from(cmd.min_confirmations), 42);
     }
+
+    /// Run the parameters VP on the given changed keys.
+    fn validate_params_changes(
+        shell: &TestShell,
+        tx: &Tx,
+        keys_changed: BTreeSet<namada::types::storage::Key>,
+    ) -> bool {
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let verifiers = BTreeSet::default();
+        let ctx = namada::ledger::native_vp::Ctx::new(
+            shell.mode.get_validator_address().expect("Test failed"),
+            &shell.wl_storage.storage,
+            &shell.wl_storage.write_log,
+            tx,
+            &TxIndex(0),
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            shell.vp_wasm_cache.clone(),
+        );
+        let parameters = ParametersVp { ctx };
+        parameters
+            .validate_tx(tx, &keys_changed, &verifiers)
+            .expect("Test failed")
+    }
+
+    /// Test that a parameter change scheduled by governance is only accepted
+    /// for a future epoch and that it's applied on the epoch transition.
+    #[test]
+    fn test_scheduled_param_changes() {
+        let (mut shell, _broadcaster, _, _control_receiver) =
+            setup_at_height(3u64);
+        let proposal_execution_key = get_proposal_execution_key(0);
+        shell
+            .wl_storage
+            .write(&proposal_execution_key, 0u64)
+            .expect("Test failed.");
+        let mut tx = Tx::new(shell.chain_id.clone(), None);
+        tx.add_code_from_hash(Hash::default(), None).add_data(0u64);
+
+        let current_epoch = shell.wl_storage.storage.block.epoch;
+        let max_tx_bytes_key =
+            namada::parameters::storage::get_max_tx_bytes_key();
+        let new_max_tx_bytes = 1024_u32;
+        let old_max_tx_bytes: u32 =
+            shell.wl_storage.read(&max_tx_bytes_key).unwrap().unwrap();
+        assert_ne!(old_max_tx_bytes, new_max_tx_bytes);
+
+        // A change scheduled for the current epoch is rejected
+        namada::parameters::schedule_change(
+            &mut shell.wl_storage,
+            current_epoch,
+            max_tx_bytes_key.clone(),
+            new_max_tx_bytes.serialize_to_vec(),
+        )
+        .expect("Test failed");
+        let current_key =
+            namada::parameters::storage::get_pending_changes_key(current_epoch);
+        assert!(!validate_params_changes(
+            &shell,
+            &tx,
+            BTreeSet::from([current_key.clone()]),
+        ));
+        shell.wl_storage.delete(&current_key).expect("Test failed");
+
+        // A change scheduled for the next epoch is accepted
+        namada::parameters::schedule_change(
+            &mut shell.wl_storage,
+            current_epoch.next(),
+            max_tx_bytes_key.clone(),
+            new_max_tx_bytes.serialize_to_vec(),
+        )
+        .expect("Test failed");
+        let pending_key = namada::parameters::storage::get_pending_changes_key(
+            current_epoch.next(),
+        );
+        assert!(validate_params_changes(
+            &shell,
+            &tx,
+            BTreeSet::from([pending_key.clone()]),
+        ));
+
+        // The change isn't applied before the epoch transition
+        let max_tx_bytes: u32 =
+            shell.wl_storage.read(&max_tx_bytes_key).unwrap().unwrap();
+        assert_eq!(max_tx_bytes, old_max_tx_bytes);
+
+        // we advance forward to the next epoch
+        let mut req = FinalizeBlock::default();
+        req.header.time = namada::types::time::DateTimeUtc::now();
+        let current_decision_height = shell.get_current_decision_height();
+        if let Some(b) = shell.wl_storage.storage.last_block.as_mut() {
+            b.height = current_decision_height + 11;
+        }
+        shell.finalize_block(req).expect("Test failed");
+        shell.commit();
+        assert_eq!(shell.wl_storage.storage.block.epoch, current_epoch.next());
+
+        let max_tx_bytes: u32 =
+            shell.wl_storage.read(&max_tx_bytes_key).unwrap().unwrap();
+        assert_eq!(max_tx_bytes, new_max_tx_bytes);
+        assert!(!shell.wl_storage.has_key(&pending_key).unwrap());
+    }
+
+    /// Test that the results of `FinalizeBlock` don't depend on the
+    /// wall-clock cap on wasm execution, which is only enforced in CheckTx
+    /// and queries. With a zero cap, any enforced wasm run would be stopped
+    /// at its first gas charge.
+    #[test]
+    fn test_finalize_block_ignores_wasm_execution_cap() {
+        let keypair = gen_keypair();
+        let key = Key::parse("wasm_execution_cap").expect("Test failed");
+        let results: Vec<_> = [None, Some(std::time::Duration::ZERO)]
+            .into_iter()
+            .map(|cap| {
+                let (mut shell, _, _, _) = setup();
+                shell.wasm_execution_cap = cap;
+
+                let mut wrapper =
+                    Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                        Fee {
+                            amount_per_gas_unit: DenominatedAmount::native(
+                                0.into(),
+                            ),
+                            token: shell
+                                .wl_storage
+                                .storage
+                                .native_token
+                                .clone(),
+                        },
+                        keypair.ref_to(),
+                        Epoch(0),
+                        GAS_LIMIT_MULTIPLIER.into(),
+                        None,
+                    ))));
+                wrapper.header.chain_id = shell.chain_id.clone();
+                wrapper.set_code(Code::new(
+                    TestWasms::TxWriteStorageKey.read_bytes(),
+                    None,
+                ));
+                wrapper.set_data(Data::new(
+                    borsh::to_vec(&TxWriteData {
+                        key: key.clone(),
+                        value: "value".as_bytes().to_owned(),
+                    })
+                    .unwrap(),
+                ));
+                wrapper.add_section(Section::Signature(Signature::new(
+                    wrapper.sechashes(),
+                    [(0, keypair.clone())].into_iter().collect(),
+                    None,
+                )));
+                shell.enqueue_tx(wrapper.clone(), GAS_LIMIT_MULTIPLIER.into());
+                let mut inner = wrapper;
+                inner.update_header(TxType::Decrypted(DecryptedTx::Decrypted));
+
+                let event = shell
+                    .finalize_block(FinalizeBlock {
+                        txs: vec![ProcessedTx {
+                            tx: inner.to_bytes().into(),
+                            result: TxResult {
+                                code: ResultCode::Ok.into(),
+                                info: "".into(),
+                            },
+                        }],
+                        ..Default::default()
+                    })
+                    .expect("Test failed")
+                    .remove(0);
+                let code =
+                    event.attributes.get("code").expect("Test failed").clone();
+                let gas_used = event
+                    .attributes
+                    .get("gas_used")
+                    .expect("Test failed")
+                    .clone();
+                let value = shell.wl_storage.read_bytes(&key).unwrap();
+                (code, gas_used, value)
+            })
+            .collect();
+
+        assert_eq!(results[0].0, String::from(ResultCode::Ok));
+        assert_eq!(results[0].2, Some("value".as_bytes().to_owned()));
+        assert_eq!(results[0], results[1]);
+    }
+
+    /// Test that applying a block with its decrypted txs pre-executed in
+    /// parallel gives the same results and app hash as applying it serially.
+    /// The second tx reads the key written by the first one, so its
+    /// pre-executed result, in which the key is missing, must be discarded.
+    #[test]
+    fn test_parallel_tx_execution_matches_serial() {
+        let keypair = gen_keypair();
+        let key_a = Key::parse("parallel_a").expect("Test failed");
+        let key_b = Key::parse("parallel_b").expect("Test failed");
+        // A borsh encoded value, as read by the tx reading a key
+        let value = borsh::to_vec(&vec![1_u8]).unwrap();
+        let write_tx = |key: &Key| {
+            (
+                TestWasms::TxWriteStorageKey,
+                borsh::to_vec(&TxWriteData {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .unwrap(),
+            )
+        };
+        let wrappers: Vec<Tx> = [
+            write_tx(&key_a),
+            (TestWasms::TxReadStorageKey, borsh::to_vec(&key_a).unwrap()),
+            write_tx(&key_b),
+        ]
+        .into_iter()
+        .map(|(code, data)| {
+            let mut wrapper =
+                Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                    Fee {
+                        amount_per_gas_unit: DenominatedAmount::native(
+                            0.into(),
+                        ),
+                        token: namada::types::address::nam(),
+                    },
+                    keypair.ref_to(),
+                    Epoch(0),
+                    GAS_LIMIT_MULTIPLIER.into(),
+                    None,
+                ))));
+            wrapper.set_code(Code::new(code.read_bytes(), None));
+            wrapper.set_data(Data::new(data));
+            wrapper
+        })
+        .collect();
+
+        let results: Vec<_> = [false, true]
+            .into_iter()
+            .map(|parallel_tx_execution| {
+                let (mut shell, _, _, _) = setup();
+                shell.parallel_tx_execution = parallel_tx_execution;
+
+                let mut txs = vec![];
+                for wrapper in &wrappers {
+                    let mut wrapper = wrapper.clone();
+                    wrapper.header.chain_id = shell.chain_id.clone();
+                    wrapper.add_section(Section::Signature(Signature::new(
+                        wrapper.sechashes(),
+                        [(0, keypair.clone())].into_iter().collect(),
+                        None,
+                    )));
+                    shell.enqueue_tx(
+                        wrapper.clone(),
+                        GAS_LIMIT_MULTIPLIER.into(),
+                    );
+                    let mut inner = wrapper;
+                    inner.update_header(TxType::Decrypted(
+                        DecryptedTx::Decrypted,
+                    ));
+                    txs.push(ProcessedTx {
+                        tx: inner.to_bytes().into(),
+                        result: TxResult {
+                            code: ResultCode::Ok.into(),
+                            info: "".into(),
+                        },
+                    });
+                }
+
+                let codes: Vec<_> = shell
+                    .finalize_block(FinalizeBlock {
+                        txs,
+                        ..Default::default()
+                    })
+                    .expect("Test failed")
+                    .into_iter()
+                    .filter_map(|event| event.attributes.get("code").cloned())
+                    .collect();
+                let app_hash = shell.commit().data;
+                (codes, app_hash)
+            })
+            .collect();
+
+        assert_eq!(results[0].0, vec![String::from(ResultCode::Ok); 3]);
+        assert_eq!(results[0], results[1]);
+    }
 }
@@ -5,18 +5,21 @@ use namada::governance::pgf::storage::steward::StewardDetail;
 use namada::governance::pgf::{storage as pgf, ADDRESS};
 use namada::governance::storage::keys as gov_storage;
 use namada::governance::storage::proposal::{
-    AddRemove, PGFAction, PGFTarget, ProposalType, StoragePgfFunding,
+    AddRemove, DepositState, IbcClientRecovery, PGFAction, PGFTarget,
+    ProposalType, StoragePgfFunding,
 };
+use namada::governance::storage::vote::ProposalVote;
 use namada::governance::utils::{
-    compute_proposal_result, ProposalVotes, TallyResult, TallyType, TallyVote,
-    VotePower,
+    compute_proposal_result, ProposalResult, ProposalVotes, TallyResult,
+    TallyType, TallyVote, VotePower,
 };
 use namada::governance::{storage as gov_api, ADDRESS as gov_address};
 use namada::ledger::governance::utils::ProposalEvent;
 use namada::ledger::pos::BondId;
-use namada::ledger::protocol;
+use namada::ledger::protocol::{self, ShellParams};
 use namada::proof_of_stake::bond_amount;
 use namada::proof_of_stake::parameters::PosParams;
+use namada::proof_of_stake::queries::find_delegations;
 use namada::proof_of_stake::storage::read_total_stake;
 use namada::state::{DBIter, StorageHasher, StorageWrite, DB};
 use namada::tx::{Code, Data};
@@ -66,20 +69,27 @@ where
             read_total_stake(&shell.wl_storage, &params, proposal_end_epoch)?;
 
         let tally_type = TallyType::from(proposal_type.clone(), is_steward);
+        let tally_params = gov_api::get_parameters(&shell.wl_storage)?
+            .tally_params
+            .get(&proposal_type);
         let votes = compute_proposal_votes(
             &shell.wl_storage,
             &params,
             id,
             proposal_end_epoch,
         )?;
-        let proposal_result =
-            compute_proposal_result(votes, total_voting_power, tally_type);
+        let proposal_result = compute_proposal_result(
+            votes,
+            total_voting_power,
+            tally_type,
+            tally_params,
+        );
         let proposal_result_key = gov_storage::get_proposal_result_key(id);
         shell
             .wl_storage
             .write(&proposal_result_key, proposal_result)?;
 
-        let transfer_address = match proposal_result.result {
+        match proposal_result.result {
             TallyResult::Passed => {
                 let proposal_event = match proposal_type {
                     ProposalType::Default(_) => {
@@ -152,25 +162,39 @@ where
                         ProposalEvent::pgf_payments_proposal_event(id, result)
                             .into()
                     }
+                    ProposalType::IbcClientRecovery(recovery) => {
+                        let result = execute_ibc_client_recovery_proposal(
+                            &mut shell.wl_storage,
+                            recovery,
+                            id,
+                        );
+                        tracing::info!(
+                            "Governance proposal (ibc client recovery) {} has \
+                             been executed ({}) and passed.",
+                            id,
+                            result
+                        );
+
+                        ProposalEvent::ibc_client_recovery_proposal_event(
+                            id, result,
+                        )
+                        .into()
+                    }
                 };
                 response.events.push(proposal_event);
                 proposals_result.passed.push(id);
-
-                let proposal_author_key = gov_storage::get_author_key(id);
-                shell.wl_storage.read::<Address>(&proposal_author_key)?
             }
             TallyResult::Rejected => {
                 if let ProposalType::PGFPayment(_) = proposal_type {
-                    if proposal_result.two_thirds_nay_over_two_thirds_total() {
+                    if proposal_result.is_vetoed() {
                         pgf::remove_steward(
                             &mut shell.wl_storage,
                             &proposal_author,
                         )?;
 
                         tracing::info!(
-                            "Governance proposal {} was rejected with 2/3 of \
-                             nay votes over 2/3 of the total voting power. If \
-                             {} is a steward, it's being removed from the \
+                            "Governance proposal {} was vetoed by nay votes. \
+                             If {} is a steward, it's being removed from the \
                              stewards set.",
                             id,
                             proposal_author
@@ -186,33 +210,78 @@ where
                     "Governance proposal {} has been executed and rejected.",
                     id
                 );
-
-                None
             }
         };
 
-        let native_token = shell.wl_storage.storage.native_token.clone();
-        if let Some(address) = transfer_address {
-            token::transfer(
-                &mut shell.wl_storage,
-                &native_token,
-                &gov_address,
-                &address,
-                funds,
-            )?;
-        } else {
-            token::burn(
-                &mut shell.wl_storage,
-                &native_token,
-                &gov_address,
-                funds,
-            )?;
-        }
+        let deposit_state = settle_proposal_funds(
+            &mut shell.wl_storage,
+            &proposal_result,
+            &proposal_author,
+            funds,
+        )?;
+        tracing::info!(
+            "The funds of governance proposal {} have been {}.",
+            id,
+            deposit_state
+        );
+        let deposit_state_key = gov_storage::get_deposit_state_key(id);
+        shell.wl_storage.write(&deposit_state_key, deposit_state)?;
     }
 
     Ok(proposals_result)
 }
 
+/// Refund the funds locked by a proposal to its author if it passed or
+/// reached the quorum. Otherwise, the funds are burned or sent to the PGF
+/// account, depending on the governance parameters.
+fn settle_proposal_funds<S>(
+    storage: &mut S,
+    proposal_result: &ProposalResult,
+    proposal_author: &Address,
+    funds: token::Amount,
+) -> namada::state::StorageResult<DepositState>
+where
+    S: StorageRead + StorageWrite,
+{
+    let native_token = storage.get_native_token()?;
+    if matches!(proposal_result.result, TallyResult::Passed)
+        || proposal_result.reached_quorum()
+    {
+        token::transfer(
+            storage,
+            &native_token,
+            &gov_address,
+            proposal_author,
+            funds,
+        )?;
+        return Ok(DepositState::Refunded);
+    }
+    let params = gov_api::get_parameters(storage)?;
+    if params.spam_proposal_fund_to_pgf {
+        token::transfer(storage, &native_token, &gov_address, &ADDRESS, funds)?;
+        token::record_epoch_flows(
+            storage,
+            &native_token,
+            token::TokenFlows {
+                treasury_inflow: funds,
+                ..Default::default()
+            },
+        )?;
+        Ok(DepositState::SentToPgf)
+    } else {
+        token::burn(storage, &native_token, &gov_address, funds)?;
+        token::record_epoch_flows(
+            storage,
+            &native_token,
+            token::TokenFlows {
+                burned: funds,
+                ..Default::default()
+            },
+        )?;
+        Ok(DepositState::Burned)
+    }
+}
+
 fn compute_proposal_votes<S>(
     storage: &S,
     params: &PosParams,
@@ -232,8 +301,10 @@ where
         Address,
         HashMap<Address, VotePower>,
     > = HashMap::default();
+    let mut direct_votes: HashMap<Address, ProposalVote> = HashMap::default();
 
     for vote in votes {
+        direct_votes.insert(vote.delegator.clone(), vote.data.clone());
         if vote.is_validator() {
             let validator = vote.validator.clone();
             let vote_data = vote.data.clone();
@@ -263,6 +334,31 @@ where
         }
     }
 
+    // The delegators that didn't vote are represented by the vote of their
+    // proxy, if it voted. A direct vote takes precedence over the vote of the
+    // proxy, so the stake of a delegator is never counted twice.
+    for (proxy, proxy_vote) in &direct_votes {
+        for delegator in gov_api::get_proxied_delegators(storage, proxy)? {
+            if direct_votes.contains_key(&delegator) {
+                continue;
+            }
+            let validators = find_delegations(storage, &delegator, &epoch)?;
+            for validator in validators.into_keys() {
+                let bond_id = BondId {
+                    source: delegator.clone(),
+                    validator: validator.clone(),
+                };
+                let delegator_stake =
+                    bond_amount(storage, &bond_id, epoch).unwrap_or_default();
+                delegator_voting_power
+                    .entry(delegator.clone())
+                    .or_default()
+                    .insert(validator, delegator_stake);
+            }
+            delegators_vote.insert(delegator, proxy_vote.clone().into());
+        }
+    }
+
     Ok(ProposalVotes {
         validators_vote,
         validator_voting_power,
@@ -289,33 +385,33 @@ where
         tx.set_data(Data::new(encode(&id)));
         tx.set_code(Code::new(code, None));
 
-        let tx_result = protocol::dispatch_tx(
+        // Simulate the proposal code on a fork of the storage, whose changes
+        // are only merged back if the code is accepted
+        let mut fork = shell.wl_storage.fork();
+        let tx_result = protocol::apply_wasm_tx(
             tx,
-            &[], /*  this is used to compute the fee
-                  * based on the code size. We dont
-                  * need it here. */
-            TxIndex::default(),
-            &mut TxGasMeter::new_from_sub_limit(u64::MAX.into()), /* No gas limit for governance proposal */
-            &mut shell.wl_storage,
-            &mut shell.vp_wasm_cache,
-            &mut shell.tx_wasm_cache,
-            None,
+            &TxIndex::default(),
+            ShellParams::new(
+                // No gas limit for governance proposal
+                &mut TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+                &mut fork,
+                &mut shell.vp_wasm_cache,
+                &mut shell.tx_wasm_cache,
+            ),
         );
+        let write_log = fork.into_write_log();
         shell
             .wl_storage
             .storage
             .delete(&pending_execution_key)
             .expect("Should be able to delete the storage.");
         match tx_result {
-            Ok(tx_result) => {
-                if tx_result.is_accepted() {
-                    shell.wl_storage.commit_tx();
-                    Ok(true)
-                } else {
-                    Ok(false)
-                }
+            Ok(tx_result) if tx_result.is_accepted() => {
+                shell.wl_storage.merge(write_log);
+                shell.wl_storage.commit_tx();
+                Ok(true)
             }
-            Err(_) => {
+            _ => {
                 shell.wl_storage.drop_tx();
                 Ok(false)
             }
@@ -407,12 +503,23 @@ where
                     }
                 };
                 match result {
-                    Ok(()) => tracing::info!(
-                        "Execute RetroPgf from proposal id {}: sent {} to {}.",
-                        proposal_id,
-                        target.amount().to_string_native(),
-                        target.target()
-                    ),
+                    Ok(()) => {
+                        token::record_epoch_flows(
+                            storage,
+                            token,
+                            token::TokenFlows {
+                                treasury_outflow: target.amount(),
+                                ..Default::default()
+                            },
+                        )?;
+                        tracing::info!(
+                            "Execute RetroPgf from proposal id {}: sent {} to \
+                             {}.",
+                            proposal_id,
+                            target.amount().to_string_native(),
+                            target.target()
+                        )
+                    }
                     Err(e) => tracing::warn!(
                         "Error in RetroPgf transfer from proposal id {}, \
                          amount {} to {}: {}",
@@ -428,3 +535,375 @@ where
 
     Ok(true)
 }
+
+fn execute_ibc_client_recovery_proposal<D, H>(
+    storage: &mut WlStorage<D, H>,
+    recovery: IbcClientRecovery,
+    proposal_id: u64,
+) -> bool
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    match ibc::recover_client(
+        storage,
+        &recovery.subject_client_id,
+        &recovery.substitute_client_id,
+    ) {
+        Ok(()) => {
+            tracing::info!(
+                "Execute IbcClientRecovery from proposal id {}: substituted \
+                 {} with {}.",
+                proposal_id,
+                recovery.subject_client_id,
+                recovery.substitute_client_id
+            );
+            true
+        }
+        Err(e) => {
+            tracing::warn!(
+                "Error in IbcClientRecovery from proposal id {}, subject {} \
+                 and substitute {}: {}",
+                proposal_id,
+                recovery.subject_client_id,
+                recovery.substitute_client_id,
+                e
+            );
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_governance {
+    use std::collections::BTreeSet;
+
+    use namada::governance::parameters::{
+        GovernanceParameters, ProposalTallyParameters,
+    };
+    use namada::governance::{VoteProposalData, VoteProxyData};
+    use namada::proof_of_stake::storage::{
+        read_consensus_validator_set_addresses_with_stake, read_pos_params,
+    };
+    use namada::state::testing::TestWlStorage;
+    use namada::types::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+        established_address_4,
+    };
+    use namada::types::dec::Dec;
+
+    use super::*;
+    use crate::node::ledger::shell::test_utils::{setup_with_cfg, SetupCfg};
+
+    fn proposal_result(yay: u64, nay: u64) -> ProposalResult {
+        let tally_type = TallyType::OneHalfOverOneThird;
+        let tally_params = ProposalTallyParameters::default().pgf_steward;
+        let total_voting_power = token::Amount::native_whole(100);
+        let total_yay_power = token::Amount::native_whole(yay);
+        let total_nay_power = token::Amount::native_whole(nay);
+        ProposalResult {
+            result: TallyResult::new(
+                &tally_type,
+                &tally_params,
+                total_yay_power,
+                total_nay_power,
+                token::Amount::zero(),
+                total_voting_power,
+            ),
+            tally_type,
+            tally_params,
+            total_voting_power,
+            total_yay_power,
+            total_nay_power,
+            total_abstain_power: token::Amount::zero(),
+        }
+    }
+
+    /// Test that the funds of a proposal are refunded if it passed or reached
+    /// the quorum, and otherwise burned or sent to PGF
+    #[test]
+    fn test_settle_proposal_funds() {
+        let author = established_address_1();
+        let funds = token::Amount::native_whole(500);
+        for (spam_proposal_fund_to_pgf, yay, nay, expected) in [
+            (false, 50, 0, DepositState::Refunded),
+            (false, 10, 30, DepositState::Refunded),
+            (false, 5, 5, DepositState::Burned),
+            (true, 5, 5, DepositState::SentToPgf),
+        ] {
+            let mut wl_storage = TestWlStorage::default();
+            GovernanceParameters {
+                spam_proposal_fund_to_pgf,
+                ..Default::default()
+            }
+            .init_storage(&mut wl_storage)
+            .unwrap();
+            let native_token = wl_storage.storage.native_token.clone();
+            token::credit_tokens(
+                &mut wl_storage,
+                &native_token,
+                &gov_address,
+                funds,
+            )
+            .unwrap();
+
+            let result = proposal_result(yay, nay);
+            let state =
+                settle_proposal_funds(&mut wl_storage, &result, &author, funds)
+                    .unwrap();
+            assert_eq!(state, expected);
+
+            let balance = |owner: &Address| {
+                token::read_balance(&wl_storage, &native_token, owner).unwrap()
+            };
+            assert!(balance(&gov_address).is_zero());
+            let (author_balance, pgf_balance) = match expected {
+                DepositState::Refunded => (funds, token::Amount::zero()),
+                DepositState::SentToPgf => (token::Amount::zero(), funds),
+                _ => (token::Amount::zero(), token::Amount::zero()),
+            };
+            assert_eq!(balance(&author), author_balance);
+            assert_eq!(balance(&ADDRESS), pgf_balance);
+
+            let epoch = wl_storage.storage.block.epoch;
+            let flows =
+                token::read_epoch_flows(&wl_storage, &native_token, epoch)
+                    .unwrap();
+            let expected_flows = match expected {
+                DepositState::SentToPgf => token::TokenFlows {
+                    treasury_inflow: funds,
+                    ..Default::default()
+                },
+                DepositState::Burned => token::TokenFlows {
+                    burned: funds,
+                    ..Default::default()
+                },
+                _ => token::TokenFlows::default(),
+            };
+            assert_eq!(flows, expected_flows);
+        }
+    }
+
+    /// Test that the tally parameters are selected by the type of proposal
+    #[test]
+    fn test_tally_params_by_proposal_type() {
+        let total = token::Amount::native_whole(100);
+        let yay = token::Amount::native_whole(60);
+        let nay = token::Amount::native_whole(40);
+        let tally = |tally_type: TallyType,
+                     params: &ProposalTallyParameters| {
+            let proposal_type = match tally_type {
+                TallyType::TwoThirds => ProposalType::Default(None),
+                _ => ProposalType::PGFSteward(Default::default()),
+            };
+            TallyResult::new(
+                &tally_type,
+                &params.get(&proposal_type),
+                yay,
+                nay,
+                token::Amount::zero(),
+                total,
+            )
+        };
+
+        // With the default parameters, 60% of yay votes pass a PGF steward
+        // proposal but not a default proposal
+        let mut params = ProposalTallyParameters::default();
+        assert!(matches!(
+            tally(TallyType::TwoThirds, &params),
+            TallyResult::Rejected
+        ));
+        assert!(matches!(
+            tally(TallyType::OneHalfOverOneThird, &params),
+            TallyResult::Passed
+        ));
+
+        // Raising the threshold of the PGF steward proposals only affects them
+        params.pgf_steward.threshold = Dec::new(7, 1).unwrap();
+        params.default.threshold = Dec::one() / 2;
+        assert!(matches!(
+            tally(TallyType::TwoThirds, &params),
+            TallyResult::Passed
+        ));
+        assert!(matches!(
+            tally(TallyType::OneHalfOverOneThird, &params),
+            TallyResult::Rejected
+        ));
+        assert!(params.is_valid());
+        params.default.veto = Dec::two();
+        assert!(!params.is_valid());
+    }
+
+    /// Test that a vote proxy votes with the stake of the delegators that
+    /// didn't vote directly, without counting any stake twice
+    #[test]
+    fn test_vote_proxy_tally() {
+        let (mut shell, _recv, _, _) = setup_with_cfg(SetupCfg {
+            last_height: 0,
+            num_validators: 1,
+            ..Default::default()
+        });
+        let validator = read_consensus_validator_set_addresses_with_stake(
+            &shell.wl_storage,
+            Epoch::default(),
+        )
+        .unwrap()
+        .into_iter()
+        .next()
+        .unwrap()
+        .address;
+        let params = read_pos_params(&shell.wl_storage).unwrap();
+        let current_epoch = shell.wl_storage.storage.block.epoch;
+        let tally_epoch = current_epoch + params.pipeline_len;
+
+        // The first delegator leaves it to the proxy, the second one votes
+        // directly and the proxy itself has no bonds
+        let proxied = established_address_1();
+        let direct = established_address_2();
+        let independent = established_address_3();
+        let proxy = established_address_4();
+        let staking_token = shell.wl_storage.storage.native_token.clone();
+        let bond_amount = token::Amount::native_whole(100);
+        for delegator in [&proxied, &direct, &independent] {
+            token::credit_tokens(
+                &mut shell.wl_storage,
+                &staking_token,
+                delegator,
+                bond_amount,
+            )
+            .unwrap();
+            namada::proof_of_stake::bond_tokens(
+                &mut shell.wl_storage,
+                Some(delegator),
+                &validator,
+                bond_amount,
+                current_epoch,
+                None,
+            )
+            .unwrap();
+        }
+        for delegator in [&proxied, &direct] {
+            gov_api::update_vote_proxy(
+                &mut shell.wl_storage,
+                VoteProxyData {
+                    delegator: delegator.clone(),
+                    proxy: Some(proxy.clone()),
+                },
+            )
+            .unwrap();
+        }
+
+        let proposal_id = 0;
+        let vote = |voter: &Address, vote| VoteProposalData {
+            id: proposal_id,
+            vote,
+            voter: voter.clone(),
+            delegations: vec![validator.clone()],
+        };
+        for data in [
+            vote(&validator, ProposalVote::Yay),
+            vote(&proxy, ProposalVote::Nay),
+            vote(&direct, ProposalVote::Yay),
+        ] {
+            gov_api::vote_proposal(&mut shell.wl_storage, data).unwrap();
+        }
+
+        let votes = compute_proposal_votes(
+            &shell.wl_storage,
+            &params,
+            proposal_id,
+            tally_epoch,
+        )
+        .unwrap();
+        assert!(votes.delegators_vote[&proxied].is_nay());
+        assert!(votes.delegators_vote[&direct].is_yay());
+        assert!(!votes.delegators_vote.contains_key(&independent));
+        assert_eq!(
+            votes.delegator_voting_power[&proxied][&validator],
+            bond_amount
+        );
+
+        // Only the stake of the proxied delegator goes against the vote of
+        // the validator
+        let total_voting_power =
+            read_total_stake(&shell.wl_storage, &params, tally_epoch).unwrap();
+        let result = compute_proposal_result(
+            votes,
+            total_voting_power,
+            TallyType::OneHalfOverOneThird,
+            ProposalTallyParameters::default().pgf_steward,
+        );
+        assert_eq!(result.total_nay_power, bond_amount);
+        assert_eq!(result.total_yay_power, total_voting_power - bond_amount);
+
+        // Removing the proxy gives the stake back to the validator
+        gov_api::update_vote_proxy(
+            &mut shell.wl_storage,
+            VoteProxyData {
+                delegator: proxied.clone(),
+                proxy: None,
+            },
+        )
+        .unwrap();
+        let votes = compute_proposal_votes(
+            &shell.wl_storage,
+            &params,
+            proposal_id,
+            tally_epoch,
+        )
+        .unwrap();
+        assert!(!votes.delegators_vote.contains_key(&proxied));
+    }
+
+    /// Test that the delegators of a vote proxy are indexed by the proxy
+    #[test]
+    fn test_vote_proxy_index() {
+        let mut wl_storage = TestWlStorage::default();
+        let delegator = established_address_1();
+        let first_proxy = established_address_2();
+        let second_proxy = established_address_3();
+        let set_proxy = |wl_storage: &mut TestWlStorage, proxy| {
+            gov_api::update_vote_proxy(
+                wl_storage,
+                VoteProxyData {
+                    delegator: delegator.clone(),
+                    proxy,
+                },
+            )
+            .unwrap();
+        };
+
+        set_proxy(&mut wl_storage, Some(first_proxy.clone()));
+        assert!(gov_api::is_vote_proxy(&wl_storage, &first_proxy).unwrap());
+        assert_eq!(
+            gov_api::get_proxied_delegators(&wl_storage, &first_proxy).unwrap(),
+            BTreeSet::from([delegator.clone()])
+        );
+
+        // Changing the proxy moves the delegator to the new proxy
+        set_proxy(&mut wl_storage, Some(second_proxy.clone()));
+        assert!(!gov_api::is_vote_proxy(&wl_storage, &first_proxy).unwrap());
+        assert!(gov_api::is_vote_proxy(&wl_storage, &second_proxy).unwrap());
+
+        // Removing the proxy removes the delegator from the index
+        set_proxy(&mut wl_storage, None);
+        assert!(!gov_api::is_vote_proxy(&wl_storage, &second_proxy).unwrap());
+        assert!(
+            gov_api::get_vote_proxy(&wl_storage, &delegator)
+                .unwrap()
+                .is_none()
+        );
+
+        // A delegator cannot be its own proxy
+        assert!(
+            gov_api::update_vote_proxy(
+                &mut wl_storage,
+                VoteProxyData {
+                    delegator: delegator.clone(),
+                    proxy: Some(delegator.clone()),
+                },
+            )
+            .is_err()
+        );
+    }
+}
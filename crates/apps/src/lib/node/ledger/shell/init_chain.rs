@@ -10,7 +10,7 @@ use namada::ledger::parameters::Parameters;
 use namada::ledger::{ibc, pos};
 use namada::proof_of_stake::BecomeValidator;
 use namada::state::{DBIter, StorageHasher, StorageWrite, DB};
-use namada::token::{credit_tokens, write_denom};
+use namada::token::{credit_tokens, write_denom, write_vesting_schedule};
 use namada::types::address::Address;
 use namada::types::hash::Hash as CodeHash;
 use namada::types::time::{DateTimeUtc, TimeZone, Utc};
@@ -23,7 +23,9 @@ use crate::config::genesis::chain::{
     FinalizedEstablishedAccountTx, FinalizedTokenConfig,
     FinalizedValidatorAccountTx,
 };
-use crate::config::genesis::templates::{TokenBalances, TokenConfig};
+use crate::config::genesis::templates::{
+    TokenBalances, TokenConfig, TokenVesting,
+};
 use crate::config::genesis::transactions::{
     BondTx, EstablishedAccountTx, Signed as SignedTx, ValidatorAccountTx,
 };
@@ -262,6 +264,12 @@ where
             current_epoch,
         )
         .expect("Could not compute total consensus stake at genesis");
+        pos::namada_proof_of_stake::storage::write_validator_set_history(
+            &mut self.wl_storage,
+            &pos_params,
+            current_epoch,
+        )
+        .expect("Could not record the genesis validator set in its history");
         // This has to be done after `apply_genesis_txs_validator_account`
         pos::namada_proof_of_stake::copy_genesis_validator_sets(
             &mut self.wl_storage,
@@ -269,6 +277,11 @@ where
             current_epoch,
         )
         .expect("Must be able to copy PoS genesis validator sets");
+        namada::eth_bridge::protocol::transactions::votes::snapshot_total_voting_power(
+            &mut self.wl_storage,
+            current_epoch,
+        )
+        .expect("Must be able to snapshot the total voting power at genesis");
 
         ibc::init_genesis_storage(&mut self.wl_storage);
         ControlFlow::Continue(())
@@ -448,6 +461,12 @@ where
                     .insert(alias, address.clone());
             }
         }
+        // the reward tokens can later be extended by governance
+        let reward_tokens =
+            self.wl_storage.storage.conversion_state.tokens.clone();
+        self.wl_storage
+            .write(&token::storage_key::masp_reward_tokens_key(), reward_tokens)
+            .unwrap();
     }
 
     /// Init genesis token balances
@@ -502,6 +521,28 @@ where
                     total_token_balance,
                 )
                 .unwrap();
+
+            if let Some(TokenVesting(schedules)) =
+                genesis.balances.vesting.get(token_alias)
+            {
+                for (owner, schedule) in schedules {
+                    tracing::info!(
+                        "Locking {} {} tokens of {} from epoch {} to {}",
+                        schedule.amount.to_string_native(),
+                        token_alias,
+                        owner,
+                        schedule.start,
+                        schedule.end,
+                    );
+                    write_vesting_schedule(
+                        &mut self.wl_storage,
+                        token_address,
+                        &owner.address(),
+                        *schedule,
+                    )
+                    .expect("Couldn't write a vesting schedule");
+                }
+            }
         }
         self.proceed_with(())
     }
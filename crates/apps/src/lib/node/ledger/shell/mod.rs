@@ -6,6 +6,7 @@
 //! (unless we can simply overwrite them in the next block).
 //! More info in <https://github.com/anoma/namada/issues/362>.
 pub mod block_alloc;
+pub mod epoch_hooks;
 mod finalize_block;
 mod governance;
 mod init_chain;
@@ -14,10 +15,13 @@ use namada_sdk::tx::data::GasLimit;
 pub mod prepare_proposal;
 pub mod process_proposal;
 pub(super) mod queries;
+mod scheduled_txs;
+mod scheduler;
 mod stats;
 #[cfg(any(test, feature = "testing"))]
 #[allow(dead_code)]
 pub mod testing;
+pub mod tx_hooks;
 pub mod utils;
 mod vote_extensions;
 
@@ -31,11 +35,14 @@ use std::rc::Rc;
 use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
 use masp_primitives::transaction::Transaction;
+use namada::account::{InitAccount, UpdateAccount};
 use namada::core::hints;
 use namada::ethereum_bridge::protocol::validation::bridge_pool_roots::validate_bp_roots_vext;
 use namada::ethereum_bridge::protocol::validation::ethereum_events::validate_eth_events_vext;
+use namada::ethereum_bridge::protocol::validation::evidence::validate_duplicate_vext_evidence;
 use namada::ethereum_bridge::protocol::validation::validator_set_update::validate_valset_upd_vext;
-use namada::ledger::events::log::EventLog;
+use namada::ledger::events::log::{self as event_log, EventLog};
+use namada::ledger::events::mempool::{MempoolRejection, MempoolRejections};
 use namada::ledger::events::Event;
 use namada::ledger::gas::{Gas, TxGasMeter};
 use namada::ledger::pos::into_tm_voting_power;
@@ -47,7 +54,10 @@ use namada::ledger::protocol::{
     get_transfer_hash_from_storage, ShellParams,
 };
 use namada::ledger::{parameters, pos, protocol};
-use namada::parameters::validate_tx_bytes;
+use namada::parameters::{
+    validate_tx_bytes, validate_tx_code_bytes, validate_tx_memo_bytes,
+    validate_tx_sections,
+};
 use namada::proof_of_stake::slashing::{process_slashes, slash};
 use namada::proof_of_stake::storage::read_pos_params;
 use namada::proof_of_stake::{self};
@@ -61,22 +71,28 @@ use namada::state::{
 use namada::token;
 pub use namada::tx::data::ResultCode;
 use namada::tx::data::{DecryptedTx, TxType, WrapperTx, WrapperTxErr};
-use namada::tx::{Section, Tx};
+use namada::tx::{Commitment, Section, Tx};
 use namada::types::address;
 use namada::types::address::Address;
 use namada::types::chain::ChainId;
 use namada::types::ethereum_events::EthereumEvent;
+use namada::types::hash::Hash;
 use namada::types::key::*;
 use namada::types::storage::{BlockHeight, Key, TxIndex};
 use namada::types::time::DateTimeUtc;
+use namada::types::uint::Uint;
+use namada::vm::wasm::validation::{self as wasm_validation, WasmCodeKind};
 use namada::vm::wasm::{TxCache, VpCache};
 use namada::vm::{WasmCacheAccess, WasmCacheRwAccess};
 use namada::vote_ext::EthereumTxData;
 use namada_sdk::eth_bridge::{EthBridgeQueries, EthereumOracleConfig};
 use namada_sdk::tendermint::AppHash;
+use namada_sdk::tx::{TX_INIT_ACCOUNT_WASM, TX_UPDATE_ACCOUNT_WASM};
 use thiserror::Error;
 use tokio::sync::mpsc::{Receiver, UnboundedSender};
 
+use self::epoch_hooks::EpochHooks;
+use self::tx_hooks::TxHooks;
 use super::ethereum_oracle::{self as oracle, last_processed_block};
 use crate::config::{self, genesis, TendermintMode, ValidatorLocalConfig};
 use crate::facade::tendermint::abci::types::{Misbehavior, MisbehaviorKind};
@@ -85,7 +101,7 @@ use crate::facade::tendermint::{self, validator};
 use crate::facade::tendermint_proto::v0_37::crypto::public_key;
 use crate::node::ledger::shims::abcipp_shim_types::shim;
 use crate::node::ledger::shims::abcipp_shim_types::shim::response::TxResult;
-use crate::node::ledger::{storage, tendermint_node};
+use crate::node::ledger::{mempool_feed, storage, tendermint_node};
 use crate::wallet::{ValidatorData, ValidatorKeys};
 
 fn key_to_tendermint(
@@ -129,6 +145,27 @@ pub enum Error {
     Storage(#[from] namada::state::StorageError),
     #[error("Transaction replay attempt: {0}")]
     ReplayAttempt(String),
+    #[error("The storage is corrupted: {0}")]
+    CorruptedStorage(String),
+}
+
+/// A tx exceeding one of the limits set by the protocol parameters
+#[derive(Error, Debug)]
+pub enum TxLimitError {
+    #[error("Tx has too many sections: {0}")]
+    TooManySections(usize),
+    #[error("Tx carries a wasm code too large: {0} bytes")]
+    CodeTooLarge(usize),
+}
+
+impl TxLimitError {
+    /// The result code of a tx rejected for exceeding the limit
+    pub fn result_code(&self) -> ResultCode {
+        match self {
+            Self::TooManySections(_) => ResultCode::TooManySections,
+            Self::CodeTooLarge(_) => ResultCode::CodeTooLarge,
+        }
+    }
 }
 
 impl From<Error> for TxResult {
@@ -170,6 +207,59 @@ pub fn rollback(config: config::Ledger) -> Result<()> {
         .map_err(|e| Error::Storage(namada::state::StorageError::new(e)))
 }
 
+/// Check the integrity of the last committed block. While its data is
+/// corrupted, the state is rolled back one block at a time, down to the last
+/// verified height, instead of failing to load the corrupted block.
+pub fn recover_corrupted_state(config: &config::Ledger) -> Result<()> {
+    let db_path = config.shell.db_dir(&config.chain_id);
+    if !db_path.exists() {
+        return Ok(());
+    }
+    let check_integrity = || {
+        storage::PersistentDB::open(&db_path, None)
+            .check_integrity()
+            .map_err(|e| Error::Storage(namada::state::StorageError::new(e)))
+    };
+    // The height of the last corrupted block that was rolled back
+    let mut rolled_back: Option<BlockHeight> = None;
+    let integrity = loop {
+        let (height, reason) = match check_integrity()? {
+            storage::BlockIntegrity::Corrupted { height, reason } => {
+                (height, reason)
+            }
+            integrity => break integrity,
+        };
+        if rolled_back.is_some_and(|rolled_back| height >= rolled_back) {
+            return Err(Error::CorruptedStorage(format!(
+                "The block at height {height} is corrupted ({reason}), but it \
+                 could not be rolled back"
+            )));
+        }
+        tracing::error!(
+            "The block at height {height} is corrupted: {reason}. Rolling \
+             back to the previous height."
+        );
+        rollback(config.clone())?;
+        rolled_back = Some(height);
+    };
+    match (rolled_back, integrity) {
+        (None, storage::BlockIntegrity::Unchecked(height)) => {
+            tracing::info!(
+                "The block at height {height} has no checksums, skipping the \
+                 storage integrity check"
+            );
+        }
+        (Some(height), integrity) => {
+            tracing::info!(
+                "Recovered the storage from the corrupted block at height \
+                 {height}: {integrity:?}"
+            );
+        }
+        (None, _) => {}
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 #[allow(dead_code, clippy::large_enum_variant)]
 pub(super) enum ShellMode {
@@ -357,10 +447,29 @@ where
     /// limit the how many block heights in the past can the storage be
     /// queried for reading values.
     storage_read_past_height_limit: Option<u64>,
+    /// Taken from config `wasm_execution_cap_ms`. The wall-clock cap on the
+    /// wasm runs of CheckTx and queries.
+    pub(super) wasm_execution_cap: Option<std::time::Duration>,
+    /// Taken from config `parallel_tx_execution`. When set, the decrypted
+    /// txs at the start of a block are pre-executed in parallel in
+    /// `FinalizeBlock`.
+    parallel_tx_execution: bool,
+    /// Taken from config `denied_wasm_operators`. The wasm operators that the
+    /// wasm code submitted in txs must not use.
+    denied_wasm_operators: Vec<String>,
     /// Proposal execution tracking
     pub proposal_data: HashSet<u64>,
     /// Log of events emitted by `FinalizeBlock` ABCI calls.
     event_log: EventLog,
+    /// Log of txs rejected by `CheckTx` ABCI calls.
+    mempool_rejections: MempoolRejections,
+    /// Hooks invoked with the wrapper txs checked for the mempool and blocks
+    /// and with the txs applied in `FinalizeBlock`.
+    tx_hooks: TxHooks,
+    /// Feed of the wrapper txs accepted by `CheckTx` calls, if it's served.
+    mempool_feed: Option<mempool_feed::Sender>,
+    /// Hooks of the modules run in `FinalizeBlock` when a new epoch begins.
+    epoch_hooks: EpochHooks<D, H>,
 }
 
 /// Channels for communicating with an Ethereum oracle.
@@ -383,6 +492,11 @@ impl EthereumOracleChannels {
             last_processed_block_receiver,
         }
     }
+
+    /// Get a receiver of the most recently processed block of the oracle
+    pub fn last_processed_block(&self) -> last_processed_block::Receiver {
+        self.last_processed_block_receiver.clone()
+    }
 }
 
 impl<D, H> Shell<D, H>
@@ -408,6 +522,28 @@ where
         let mode = config.shell.tendermint_mode;
         let storage_read_past_height_limit =
             config.shell.storage_read_past_height_limit;
+        let parallel_tx_execution =
+            config.shell.parallel_tx_execution.unwrap_or_default();
+        let denied_wasm_operators =
+            config.shell.denied_wasm_operators.unwrap_or_default();
+        let prefetch_vp_reads =
+            config.shell.prefetch_vp_reads.unwrap_or_default();
+        let default_event_log_params = event_log::Params::default();
+        let event_log_params = event_log::Params {
+            max_log_events: config
+                .shell
+                .event_log_max_events
+                .unwrap_or(default_event_log_params.max_log_events),
+            max_log_blocks: config.shell.event_log_max_blocks,
+            max_log_bytes: config
+                .shell
+                .event_log_max_bytes
+                .map(|bytes| bytes as usize),
+        };
+        let wasm_execution_cap = config
+            .shell
+            .wasm_execution_cap_ms
+            .map(std::time::Duration::from_millis);
         if !Path::new(&base_dir).is_dir() {
             std::fs::create_dir(&base_dir)
                 .expect("Creating directory for Namada should not fail");
@@ -438,6 +574,7 @@ where
                 tracing::error!("Cannot load the last state from the DB {}", e);
             })
             .expect("PersistentStorage cannot be initialized");
+        storage.read_cache.set_enabled(prefetch_vp_reads);
         let vp_wasm_cache_dir =
             base_dir.join(chain_id.as_str()).join("vp_wasm_cache");
         let tx_wasm_cache_dir =
@@ -526,9 +663,15 @@ where
                 tx_wasm_compilation_cache as usize,
             ),
             storage_read_past_height_limit,
+            wasm_execution_cap,
+            parallel_tx_execution,
+            denied_wasm_operators,
             proposal_data: HashSet::new(),
-            // TODO: config event log params
-            event_log: EventLog::default(),
+            event_log: EventLog::new(event_log_params),
+            mempool_rejections: MempoolRejections::default(),
+            tx_hooks: TxHooks::load(),
+            mempool_feed: None,
+            epoch_hooks: EpochHooks::load(),
         };
         shell.update_eth_oracle(&Default::default());
         shell
@@ -546,6 +689,17 @@ where
         &mut self.event_log
     }
 
+    /// Announce the wrapper txs accepted into the mempool on the given feed.
+    pub fn set_mempool_feed(&mut self, sender: mempool_feed::Sender) {
+        self.mempool_feed = Some(sender);
+    }
+
+    /// Return a reference to the [`MempoolRejections`].
+    #[inline]
+    pub fn mempool_rejections(&self) -> &MempoolRejections {
+        &self.mempool_rejections
+    }
+
     /// Iterate over the wrapper txs in order
     #[allow(dead_code)]
     fn iter_tx_queue(&mut self) -> impl Iterator<Item = &TxInQueue> {
@@ -962,6 +1116,8 @@ where
             let config = namada::eth_bridge::oracle::config::Config {
                 min_confirmations: config.min_confirmations.into(),
                 bridge_contract: config.contracts.bridge.address,
+                bridge_contract_version: config.contracts.bridge.version,
+                next_bridge_contract: config.next_bridge,
                 start_block,
                 active,
             };
@@ -990,6 +1146,169 @@ where
         }
     }
 
+    /// Log the tx in the [`MempoolRejections`] if it got rejected by
+    /// [`Self::mempool_validate`].
+    pub fn log_mempool_rejection(
+        &mut self,
+        tx_bytes: &[u8],
+        response: &response::CheckTx,
+    ) {
+        if response.code.is_ok() {
+            return;
+        }
+        self.mempool_rejections.log_rejection(MempoolRejection {
+            tx_hash: Hash::sha256(tx_bytes),
+            code: response.code.value(),
+            log: response.log.clone(),
+            height: self.wl_storage.storage.get_last_block_height(),
+            time: DateTimeUtc::now(),
+        });
+    }
+
+    /// Announce the tx on the mempool feed, if it's a new wrapper tx that got
+    /// accepted into the mempool by [`Shell::mempool_validate`].
+    pub fn announce_pending_tx(
+        &self,
+        tx_bytes: &[u8],
+        r#type: MempoolTxType,
+        response: &response::CheckTx,
+    ) {
+        let sender = match &self.mempool_feed {
+            Some(sender) => sender,
+            None => return,
+        };
+        if !response.code.is_ok()
+            || matches!(r#type, MempoolTxType::RecheckTransaction)
+            || sender.receiver_count() == 0
+        {
+            return;
+        }
+        let Ok(tx) = Tx::try_from(tx_bytes) else {
+            return;
+        };
+        let TxType::Wrapper(wrapper) = tx.header().tx_type else {
+            return;
+        };
+        let kind = self
+            .allowed_tx_code_tag(&tx)
+            .map(|tag| tag.trim_end_matches(".wasm").to_string());
+        let _ = sender.send(mempool_feed::PendingTx::new(&tx, &wrapper, kind));
+    }
+
+    /// Get the tag of the code of a tx, if it's the allowed wasm of the same
+    /// name. Matching the allowed code's hash prevents spoofing the kind of
+    /// a tx with the code's tag.
+    fn allowed_tx_code_tag(&self, tx: &Tx) -> Option<String> {
+        let section = tx.get_section(tx.code_sechash())?;
+        let Section::Code(code) = section.as_ref() else {
+            return None;
+        };
+        let tag = code.tag.as_ref()?;
+        let allowed_hash: Option<Hash> =
+            self.wl_storage.read(&Key::wasm_hash(tag)).ok().flatten();
+        (allowed_hash == Some(code.code.hash())).then(|| tag.clone())
+    }
+
+    /// Get the hash of the extra data section holding the VP code of an
+    /// account that a tx inits or updates, if any
+    fn submitted_vp_sechash(&self, tx: &Tx) -> Option<Hash> {
+        let tag = self.allowed_tx_code_tag(tx)?;
+        let data = tx.data()?;
+        match tag.as_str() {
+            TX_INIT_ACCOUNT_WASM => InitAccount::try_from_slice(&data)
+                .ok()
+                .map(|data| data.vp_code_hash),
+            TX_UPDATE_ACCOUNT_WASM => {
+                UpdateAccount::try_from_slice(&data).ok()?.vp_code_hash
+            }
+            _ => None,
+        }
+    }
+
+    /// Check the wasm code carried by the sections of a submitted tx against
+    /// the role that it plays in the tx. The VP code of an account that the
+    /// tx inits or updates is checked as VP code, also when the tx only
+    /// references it by its hash, in which case it's read from storage. The
+    /// tx code and any other wasm code attached as extra data (e.g. the code
+    /// of a governance proposal) are checked as tx code. Bytes that are not a
+    /// wasm module are not checked here.
+    fn check_submitted_wasm(
+        &self,
+        tx: &Tx,
+    ) -> std::result::Result<(), wasm_validation::Error> {
+        let vp_sechash = self.submitted_vp_sechash(tx);
+        for section in &tx.sections {
+            let (code, kind) = match section {
+                Section::ExtraData(code)
+                    if Some(section.get_hash()) == vp_sechash =>
+                {
+                    let code = match &code.code {
+                        Commitment::Id(code) => Some(code.clone()),
+                        Commitment::Hash(hash) => self
+                            .wl_storage
+                            .read_bytes(&Key::wasm_code(hash))
+                            .expect(
+                                "Failed to read the wasm code from storage",
+                            ),
+                    };
+                    (code, WasmCodeKind::Vp)
+                }
+                Section::Code(code) | Section::ExtraData(code) => {
+                    (code.code.id(), WasmCodeKind::Tx)
+                }
+                _ => continue,
+            };
+            if let Some(code) =
+                code.filter(|code| wasm_validation::is_wasm(code))
+            {
+                wasm_validation::check_submitted_wasm(
+                    &code,
+                    kind,
+                    &self.denied_wasm_operators,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the number of sections of a tx and the size of the wasm code
+    /// embedded in its sections against the protocol parameters. Code
+    /// referenced only by its hash doesn't count towards the limit.
+    fn check_tx_limits(
+        &self,
+        tx: &Tx,
+    ) -> std::result::Result<(), TxLimitError> {
+        if !validate_tx_sections(&self.wl_storage, tx.sections.len())
+            .expect("Failed to get max tx sections param from storage")
+        {
+            return Err(TxLimitError::TooManySections(tx.sections.len()));
+        }
+        let code_size = tx
+            .sections
+            .iter()
+            .filter_map(|section| match section {
+                Section::Code(code) | Section::ExtraData(code) => {
+                    match &code.code {
+                        Commitment::Id(code)
+                            if wasm_validation::is_wasm(code) =>
+                        {
+                            Some(code.len())
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .max()
+            .unwrap_or_default();
+        if !validate_tx_code_bytes(&self.wl_storage, code_size)
+            .expect("Failed to get max tx code bytes param from storage")
+        {
+            return Err(TxLimitError::CodeTooLarge(code_size));
+        }
+        Ok(())
+    }
+
     /// Validate a transaction request. On success, the transaction will
     /// included in the mempool and propagated to peers, otherwise it will be
     /// rejected.
@@ -1001,6 +1320,8 @@ where
         use namada::tx::data::protocol::ProtocolTxType;
         use namada::vote_ext::ethereum_tx_data_variants;
 
+        let _enforced_cap =
+            namada::vm::deadline::enforce(self.wasm_execution_cap);
         let mut response = response::CheckTx::default();
 
         const VALID_MSG: &str = "Mempool validation passed";
@@ -1028,6 +1349,13 @@ where
             }
         };
 
+        // Tx sections and embedded code size
+        if let Err(err) = self.check_tx_limits(&tx) {
+            response.code = err.result_code().into();
+            response.log = format!("{INVALID_MSG}: {err}");
+            return response;
+        }
+
         // Tx chain id
         if tx.header.chain_id != self.chain_id {
             response.code = ResultCode::InvalidChainId.into();
@@ -1056,6 +1384,20 @@ where
                 return response;
             }
         }
+        let next_block_height = self
+            .wl_storage
+            .storage
+            .get_last_block_height()
+            .next_height();
+        if tx.header.is_expired_at_height(next_block_height) {
+            response.code = ResultCode::ExpiredTx.into();
+            response.log = format!(
+                "{INVALID_MSG}: Tx expired at height {}, next block height: \
+                 {next_block_height}",
+                tx.header.expiration_height.unwrap_or_default(),
+            );
+            return response;
+        }
 
         // Tx signature check
         let tx_type = match tx.validate_tx() {
@@ -1105,6 +1447,7 @@ where
                         );
                     } else {
                         response.log = String::from(VALID_MSG);
+                        response.priority = PROTOCOL_TX_PRIORITY;
                     }
                 }
                 ProtocolTxType::BridgePoolVext => {
@@ -1127,6 +1470,7 @@ where
                         );
                     } else {
                         response.log = String::from(VALID_MSG);
+                        response.priority = PROTOCOL_TX_PRIORITY;
                     }
                 }
                 ProtocolTxType::ValSetUpdateVext => {
@@ -1159,7 +1503,30 @@ where
                         response.log = String::from(VALID_MSG);
                         // validator set update votes should be decided
                         // as soon as possible
-                        response.priority = i64::MAX;
+                        response.priority = PROTOCOL_TX_PRIORITY;
+                    }
+                }
+                ProtocolTxType::DuplicateVextEvidence => {
+                    let evidence = try_vote_extension!(
+                        "duplicate vote extensions evidence",
+                        response,
+                        ethereum_tx_data_variants::DuplicateVextEvidence::try_from(
+                            &tx
+                        ),
+                    );
+                    if let Err(err) = validate_duplicate_vext_evidence(
+                        &self.wl_storage,
+                        &evidence,
+                        self.wl_storage.storage.get_last_block_height(),
+                    ) {
+                        response.code = ResultCode::InvalidVoteExtension.into();
+                        response.log = format!(
+                            "{INVALID_MSG}: Invalid duplicate vote extensions \
+                             evidence: {err}",
+                        );
+                    } else {
+                        response.log = String::from(VALID_MSG);
+                        response.priority = PROTOCOL_TX_PRIORITY;
                     }
                 }
                 _ => {
@@ -1194,6 +1561,32 @@ where
                     return response;
                 }
 
+                // Tx memo size
+                let memo_size = tx.memo().map(|memo| memo.len()).unwrap_or(0);
+                if !validate_tx_memo_bytes(&self.wl_storage, memo_size).expect(
+                    "Failed to get max tx memo bytes param from storage",
+                ) {
+                    response.code = ResultCode::TooLarge.into();
+                    response.log = format!("{INVALID_MSG}: Tx memo too large");
+                    return response;
+                }
+
+                // Tx hooks
+                if let Err(reason) =
+                    self.tx_hooks.check_wrapper_tx(&tx, next_block_height)
+                {
+                    response.code = ResultCode::InvalidTx.into();
+                    response.log = format!("{INVALID_MSG}: {reason}");
+                    return response;
+                }
+
+                // Submitted wasm code
+                if let Err(err) = self.check_submitted_wasm(&tx) {
+                    response.code = ResultCode::InvalidTx.into();
+                    response.log = format!("{INVALID_MSG}: {err}");
+                    return response;
+                }
+
                 // Replay protection check
                 let inner_tx_hash = tx.raw_header_hash();
                 if self
@@ -1235,7 +1628,7 @@ where
                 if let Err(e) = mempool_fee_check(
                     &wrapper,
                     get_fee_unshielding_transaction(&tx, &wrapper),
-                    &mut TempWlStorage::new(&self.wl_storage.storage),
+                    &mut self.wl_storage.fork(),
                     &mut self.vp_wasm_cache.clone(),
                     &mut self.tx_wasm_cache.clone(),
                 ) {
@@ -1243,6 +1636,10 @@ where
                     response.log = format!("{INVALID_MSG}: {e}");
                     return response;
                 }
+
+                // Wrappers paying more than the minimum gas price are
+                // prioritized over the rest
+                response.priority = self.mempool_wrapper_priority(&wrapper);
             }
             TxType::Raw => {
                 response.code = ResultCode::InvalidTx.into();
@@ -1265,6 +1662,29 @@ where
         response
     }
 
+    /// Compute the mempool priority of a wrapper tx that passed the fee
+    /// check, from the gas price it pays.
+    fn mempool_wrapper_priority(&self, wrapper: &WrapperTx) -> i64 {
+        let minimum_gas_price = namada::ledger::parameters::read_gas_cost(
+            &self.wl_storage,
+            &wrapper.fee.token,
+        )
+        .ok()
+        .flatten();
+        let amount_per_gas_unit = token::denom_to_amount(
+            wrapper.fee.amount_per_gas_unit,
+            &wrapper.fee.token,
+            &self.wl_storage,
+        )
+        .ok();
+        match (amount_per_gas_unit, minimum_gas_price) {
+            (Some(amount_per_gas_unit), Some(minimum_gas_price)) => {
+                wrapper_tx_priority(amount_per_gas_unit, minimum_gas_price)
+            }
+            _ => 0,
+        }
+    }
+
     fn get_abci_validator_updates<F, V>(
         &self,
         is_genesis: bool,
@@ -1373,6 +1793,43 @@ where
         .map_err(|e| Error::ReplayAttempt(e.to_string()))
 }
 
+/// The mempool priority of protocol txs. CometBFT's priority mempool orders
+/// txs by priority and evicts the lowest priority ones when it's full, so
+/// protocol txs must not be crowded out by wrapper txs.
+pub const PROTOCOL_TX_PRIORITY: i64 = i64::MAX;
+
+/// The highest mempool priority of wrapper txs, right below the priority of
+/// protocol txs
+pub const MAX_WRAPPER_TX_PRIORITY: i64 = PROTOCOL_TX_PRIORITY - 1;
+
+/// Compute the mempool priority of a wrapper tx as the premium of its gas
+/// price over the minimum gas price of its fee token, in percent. Wrappers
+/// paying the minimum gas price get the lowest priority.
+pub fn wrapper_tx_priority(
+    amount_per_gas_unit: token::Amount,
+    minimum_gas_price: token::Amount,
+) -> i64 {
+    let amount_per_gas_unit = amount_per_gas_unit.raw_amount();
+    let minimum_gas_price = minimum_gas_price.raw_amount();
+    if amount_per_gas_unit <= minimum_gas_price {
+        return 0;
+    }
+    if minimum_gas_price.is_zero() {
+        return MAX_WRAPPER_TX_PRIORITY;
+    }
+    let premium = (amount_per_gas_unit - minimum_gas_price)
+        .checked_mul(Uint::from_u64(100))
+        .map(|premium| premium / minimum_gas_price);
+    match premium {
+        Some(premium)
+            if premium < Uint::from_u64(MAX_WRAPPER_TX_PRIORITY as u64) =>
+        {
+            premium.as_u64() as i64
+        }
+        _ => MAX_WRAPPER_TX_PRIORITY,
+    }
+}
+
 // Perform the fee check in mempool
 fn mempool_fee_check<D, H, CA>(
     wrapper: &WrapperTx,
@@ -2118,6 +2575,9 @@ mod test_utils {
         // initialize parameter storage
         let params = Parameters {
             max_tx_bytes: 1024 * 1024,
+            max_tx_memo_bytes: 256,
+            max_tx_sections: 64,
+            max_tx_code_bytes: 600_000,
             epoch_duration: EpochDuration {
                 min_num_of_blocks: 1,
                 min_duration: DurationSecs(3600),
@@ -2125,6 +2585,7 @@ mod test_utils {
             max_expected_time_per_block: DurationSecs(3600),
             max_proposal_bytes: Default::default(),
             max_block_gas: 100,
+            max_scheduled_txs_gas: 100,
             vp_whitelist: vec![],
             tx_whitelist: vec![],
             implicit_vp_code_hash: Default::default(),
@@ -2826,6 +3287,36 @@ mod shell_tests {
         )
     }
 
+    /// Check that only the txs rejected by the mempool validation are logged
+    /// in the mempool rejections
+    #[test]
+    fn test_log_mempool_rejection() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+
+        let keypair = super::test_utils::gen_keypair();
+
+        let wrong_chain_id = ChainId("Wrong chain id".to_string());
+        let mut tx = Tx::new(wrong_chain_id, None);
+        tx.add_code("wasm_code".as_bytes().to_owned(), None)
+            .add_data("transaction data".as_bytes().to_owned())
+            .sign_wrapper(keypair);
+        let tx_bytes = tx.to_bytes();
+
+        let result =
+            shell.mempool_validate(&tx_bytes, MempoolTxType::NewTransaction);
+        shell.log_mempool_rejection(&tx_bytes, &result);
+
+        let accepted = response::CheckTx::default();
+        shell.log_mempool_rejection(b"accepted", &accepted);
+
+        let rejections: Vec<_> =
+            shell.mempool_rejections().iter().cloned().collect();
+        assert_eq!(rejections.len(), 1);
+        assert_eq!(rejections[0].tx_hash, Hash::sha256(&tx_bytes));
+        assert_eq!(rejections[0].code, ResultCode::InvalidChainId.to_u32());
+        assert_eq!(rejections[0].log, result.log);
+    }
+
     /// Check that an expired transaction gets rejected
     #[test]
     fn test_expired_tx() {
@@ -2915,6 +3406,144 @@ mod shell_tests {
         assert_eq!(result.code, ResultCode::TxGasLimit.into());
     }
 
+    /// Check that a tx carrying wasm code that uses floating-point types is
+    /// rejected
+    #[test]
+    fn test_invalid_submitted_wasm() {
+        let (shell, _recv, _, _) = test_utils::setup();
+        let keypair = super::test_utils::gen_keypair();
+
+        let mut wrapper =
+            Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(100.into()),
+                    token: shell.wl_storage.storage.native_token.clone(),
+                },
+                keypair.ref_to(),
+                Epoch(0),
+                GAS_LIMIT_MULTIPLIER.into(),
+                None,
+            ))));
+        wrapper.header.chain_id = shell.chain_id.clone();
+        let code = wat::parse_str(
+            r#"
+            (module
+                (func $apply_tx (param i64 i64) (result i64)
+                    f64.const 1.5
+                    drop
+                    i64.const 0
+                )
+                (export "_apply_tx" (func $apply_tx))
+            )
+            "#,
+        )
+        .unwrap();
+        wrapper.set_code(Code::new(code, None));
+        wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
+        wrapper.add_section(Section::Signature(Signature::new(
+            wrapper.sechashes(),
+            [(0, keypair)].into_iter().collect(),
+            None,
+        )));
+
+        let result = shell.mempool_validate(
+            wrapper.to_bytes().as_ref(),
+            MempoolTxType::NewTransaction,
+        );
+        assert_eq!(result.code, ResultCode::InvalidTx.into());
+        assert!(result.log.contains("floating-point"), "{}", result.log);
+    }
+
+    /// Check that the VP code of an account updated by a tx is checked as VP
+    /// code, also when it's only referenced by its hash
+    #[test]
+    fn test_invalid_submitted_vp_code_by_hash() {
+        use namada::state::StorageWrite;
+
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        let keypair = super::test_utils::gen_keypair();
+
+        // Allow a tx code under the name of the update account tx
+        let tx_code = wat::parse_str(
+            r#"
+            (module
+                (func $apply_tx (param i64 i64) (result i64)
+                    i64.const 0
+                )
+                (export "_apply_tx" (func $apply_tx))
+            )
+            "#,
+        )
+        .unwrap();
+        shell
+            .wl_storage
+            .write(
+                &Key::wasm_hash(TX_UPDATE_ACCOUNT_WASM),
+                Hash::sha256(&tx_code),
+            )
+            .unwrap();
+        // Store a "VP" that imports a tx host function
+        let vp_code = wat::parse_str(
+            r#"
+            (module
+                (import "env" "namada_tx_write"
+                    (func $write (param i64 i64 i64 i64)))
+                (func $validate_tx
+                    (param i64 i64 i64 i64 i64 i64 i64 i64) (result i64)
+                    i64.const 1
+                )
+                (export "_validate_tx" (func $validate_tx))
+            )
+            "#,
+        )
+        .unwrap();
+        let vp_hash = Hash::sha256(&vp_code);
+        shell
+            .wl_storage
+            .write_bytes(&Key::wasm_code(&vp_hash), vp_code)
+            .unwrap();
+
+        let mut wrapper =
+            Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(100.into()),
+                    token: shell.wl_storage.storage.native_token.clone(),
+                },
+                keypair.ref_to(),
+                Epoch(0),
+                GAS_LIMIT_MULTIPLIER.into(),
+                None,
+            ))));
+        wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.set_code(Code::new(
+            tx_code,
+            Some(TX_UPDATE_ACCOUNT_WASM.to_string()),
+        ));
+        let vp_code_hash = wrapper
+            .add_extra_section_from_hash(vp_hash, Some("vp.wasm".to_string()));
+        wrapper.set_data(Data::new(
+            UpdateAccount {
+                addr: address::testing::established_address_1(),
+                vp_code_hash: Some(vp_code_hash),
+                public_keys: vec![],
+                threshold: None,
+            }
+            .serialize_to_vec(),
+        ));
+        wrapper.add_section(Section::Signature(Signature::new(
+            wrapper.sechashes(),
+            [(0, keypair)].into_iter().collect(),
+            None,
+        )));
+
+        let result = shell.mempool_validate(
+            wrapper.to_bytes().as_ref(),
+            MempoolTxType::NewTransaction,
+        );
+        assert_eq!(result.code, ResultCode::InvalidTx.into());
+        assert!(result.log.contains("VP host functions"), "{}", result.log);
+    }
+
     // Check that a wrapper using a non-whitelisted token for fee payment is
     // rejected
     #[test]
@@ -3118,4 +3747,242 @@ mod shell_tests {
         );
         assert_eq!(result.code, ResultCode::TooLarge.into());
     }
+
+    /// Test max tx memo bytes parameter in CheckTx
+    #[test]
+    fn test_max_tx_memo_bytes_check_tx() {
+        let (shell, _recv, _, _) = test_utils::setup();
+
+        let max_tx_memo_bytes: u32 = {
+            let key = parameters::storage::get_max_tx_memo_bytes_key();
+            shell
+                .wl_storage
+                .read(&key)
+                .expect("Failed to read from storage")
+                .expect("Max tx memo bytes should have been written to storage")
+        };
+
+        let new_tx = |memo_size: u32| {
+            let keypair = super::test_utils::gen_keypair();
+            let mut wrapper =
+                Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                    Fee {
+                        amount_per_gas_unit: DenominatedAmount::native(
+                            100.into(),
+                        ),
+                        token: shell.wl_storage.storage.native_token.clone(),
+                    },
+                    keypair.ref_to(),
+                    Epoch(0),
+                    GAS_LIMIT_MULTIPLIER.into(),
+                    None,
+                ))));
+            wrapper.header.chain_id = shell.chain_id.clone();
+            wrapper
+                .set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+            wrapper
+                .set_data(Data::new("transaction data".as_bytes().to_owned()));
+            wrapper.add_memo(&vec![b'm'; memo_size as usize]);
+            wrapper.add_section(Section::Signature(Signature::new(
+                wrapper.sechashes(),
+                [(0, keypair)].into_iter().collect(),
+                None,
+            )));
+            wrapper
+        };
+
+        // a memo within the limit is accepted
+        let result = shell.mempool_validate(
+            new_tx(max_tx_memo_bytes).to_bytes().as_ref(),
+            MempoolTxType::NewTransaction,
+        );
+        assert!(result.code != ResultCode::TooLarge.into());
+
+        // max tx memo bytes + 1, on the other hand, is not
+        let result = shell.mempool_validate(
+            new_tx(max_tx_memo_bytes + 1).to_bytes().as_ref(),
+            MempoolTxType::NewTransaction,
+        );
+        assert_eq!(result.code, ResultCode::TooLarge.into());
+    }
+
+    /// Test max tx sections and max tx code bytes parameters in CheckTx
+    #[test]
+    fn test_max_tx_limits_check_tx() {
+        let (shell, _recv, _, _) = test_utils::setup();
+
+        let read_param = |key: Key| -> u32 {
+            shell
+                .wl_storage
+                .read(&key)
+                .expect("Failed to read from storage")
+                .expect("The param should have been written to storage")
+        };
+        let max_tx_sections =
+            read_param(parameters::storage::get_max_tx_sections_key());
+        let max_tx_code_bytes =
+            read_param(parameters::storage::get_max_tx_code_bytes_key());
+
+        let new_tx = |code: Vec<u8>, extra_sections: usize| {
+            let keypair = super::test_utils::gen_keypair();
+            let mut wrapper =
+                Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                    Fee {
+                        amount_per_gas_unit: DenominatedAmount::native(
+                            100.into(),
+                        ),
+                        token: shell.wl_storage.storage.native_token.clone(),
+                    },
+                    keypair.ref_to(),
+                    Epoch(0),
+                    GAS_LIMIT_MULTIPLIER.into(),
+                    None,
+                ))));
+            wrapper.header.chain_id = shell.chain_id.clone();
+            wrapper.set_code(Code::new(code, None));
+            wrapper
+                .set_data(Data::new("transaction data".as_bytes().to_owned()));
+            for ix in 0..extra_sections {
+                wrapper.add_section(Section::ExtraData(Code::new(
+                    ix.to_le_bytes().to_vec(),
+                    None,
+                )));
+            }
+            wrapper.add_section(Section::Signature(Signature::new(
+                wrapper.sechashes(),
+                [(0, keypair)].into_iter().collect(),
+                None,
+            )));
+            wrapper
+        };
+        let wasm_code = |size: u32| {
+            let mut code = b"\0asm".to_vec();
+            code.resize(size as usize, 0);
+            code
+        };
+        // the code, data and signature sections
+        let base_sections = 3;
+
+        // the max number of sections is accepted
+        let tx = new_tx(
+            "wasm_code".as_bytes().to_owned(),
+            max_tx_sections as usize - base_sections,
+        );
+        let result = shell.mempool_validate(
+            tx.to_bytes().as_ref(),
+            MempoolTxType::NewTransaction,
+        );
+        assert!(result.code != ResultCode::TooManySections.into());
+
+        // max tx sections + 1, on the other hand, is not
+        let tx = new_tx(
+            "wasm_code".as_bytes().to_owned(),
+            max_tx_sections as usize - base_sections + 1,
+        );
+        let result = shell.mempool_validate(
+            tx.to_bytes().as_ref(),
+            MempoolTxType::NewTransaction,
+        );
+        assert_eq!(result.code, ResultCode::TooManySections.into());
+
+        // a wasm code within the limit is accepted
+        let result = shell.mempool_validate(
+            new_tx(wasm_code(max_tx_code_bytes), 0).to_bytes().as_ref(),
+            MempoolTxType::NewTransaction,
+        );
+        assert!(result.code != ResultCode::CodeTooLarge.into());
+
+        // max tx code bytes + 1, on the other hand, is not
+        let result = shell.mempool_validate(
+            new_tx(wasm_code(max_tx_code_bytes + 1), 0)
+                .to_bytes()
+                .as_ref(),
+            MempoolTxType::NewTransaction,
+        );
+        assert_eq!(result.code, ResultCode::CodeTooLarge.into());
+    }
+
+    /// Test the mempool priority of wrapper txs from their gas price
+    #[test]
+    fn test_wrapper_tx_priority() {
+        let minimum = token::Amount::from_u64(10);
+        assert_eq!(wrapper_tx_priority(minimum, minimum), 0);
+        assert_eq!(wrapper_tx_priority(token::Amount::from_u64(5), minimum), 0);
+        assert_eq!(
+            wrapper_tx_priority(token::Amount::from_u64(15), minimum),
+            50
+        );
+        assert_eq!(
+            wrapper_tx_priority(token::Amount::from_u64(100), minimum),
+            900
+        );
+        assert_eq!(
+            wrapper_tx_priority(
+                token::Amount::from_u64(1),
+                token::Amount::zero()
+            ),
+            MAX_WRAPPER_TX_PRIORITY
+        );
+        assert_eq!(
+            wrapper_tx_priority(
+                token::Amount::max(),
+                token::Amount::from_u64(1)
+            ),
+            MAX_WRAPPER_TX_PRIORITY
+        );
+    }
+
+    /// Test that CheckTx gives protocol txs a higher priority than wrappers
+    /// and high fee wrappers a higher priority than low fee ones. The txs are
+    /// ordered by CometBFT's prioritized mempool.
+    #[test]
+    fn test_check_tx_priorities() {
+        const LAST_HEIGHT: BlockHeight = BlockHeight(3);
+
+        let (shell, _recv, _, _) = test_utils::setup_at_height(LAST_HEIGHT);
+
+        // a valid protocol tx
+        let (protocol_key, _) = wallet::defaults::validator_keys();
+        let ext = ethereum_events::Vext {
+            validator_addr: wallet::defaults::validator_address(),
+            block_height: LAST_HEIGHT,
+            ethereum_events: vec![EthereumEvent::TransfersToNamada {
+                nonce: 0u64.into(),
+                transfers: vec![],
+            }],
+        }
+        .sign(&protocol_key);
+        let tx = EthereumTxData::EthEventsVext(ext.into())
+            .sign(&protocol_key, shell.chain_id.clone())
+            .to_bytes();
+        let rsp = shell.mempool_validate(&tx, Default::default());
+        assert_eq!(rsp.code, ResultCode::Ok.into());
+        assert_eq!(rsp.priority, PROTOCOL_TX_PRIORITY);
+
+        // wrappers paying the minimum gas price and ten times as much
+        let native_token = shell.wl_storage.storage.native_token.clone();
+        let minimum_gas_price =
+            parameters::read_gas_cost(&shell.wl_storage, &native_token)
+                .expect("Test failed")
+                .expect("Test failed");
+        let wrapper_priority = |amount_per_gas_unit: token::Amount| {
+            let wrapper = WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(
+                        amount_per_gas_unit,
+                    ),
+                    token: native_token.clone(),
+                },
+                crate::wallet::defaults::albert_keypair().ref_to(),
+                Epoch(0),
+                GAS_LIMIT_MULTIPLIER.into(),
+                None,
+            );
+            shell.mempool_wrapper_priority(&wrapper)
+        };
+        let low_fee = wrapper_priority(minimum_gas_price);
+        let high_fee = wrapper_priority(minimum_gas_price * 10);
+        assert!(low_fee < high_fee);
+        assert!(high_fee < PROTOCOL_TX_PRIORITY);
+    }
 }
@@ -24,6 +24,7 @@ use super::block_alloc::{AllocFailure, BlockAllocator, BlockResources};
 use crate::config::ValidatorLocalConfig;
 use crate::facade::tendermint_proto::google::protobuf::Timestamp;
 use crate::facade::tendermint_proto::v0_37::abci::RequestPrepareProposal;
+use crate::node::ledger::shell::tx_hooks::TxHooks;
 use crate::node::ledger::shell::ShellMode;
 use crate::node::ledger::shims::abcipp_shim_types::shim::{response, TxBytes};
 
@@ -44,6 +45,9 @@ where
         &self,
         req: RequestPrepareProposal,
     ) -> response::PrepareProposal {
+        let _span =
+            tracing::info_span!("prepare_proposal", height = req.height)
+                .entered();
         let txs = if let ShellMode::Validator {
             ref local_config, ..
         } = self.mode
@@ -148,7 +152,7 @@ where
         let txs = txs
             .iter()
             .filter_map(|tx_bytes| {
-                match validate_wrapper_bytes(tx_bytes, block_time, block_proposer, proposer_local_config, &mut temp_wl_storage, &mut vp_wasm_cache, &mut tx_wasm_cache, ) {
+                match validate_wrapper_bytes(tx_bytes, block_time, block_proposer, proposer_local_config, &self.tx_hooks, &mut temp_wl_storage, &mut vp_wasm_cache, &mut tx_wasm_cache, ) {
                     Ok(gas) => {
                         temp_wl_storage.write_log.commit_tx();
                         Some((tx_bytes.to_owned(), gas))
@@ -323,6 +327,7 @@ fn validate_wrapper_bytes<D, H, CA>(
     block_time: Option<DateTimeUtc>,
     block_proposer: &Address,
     proposer_local_config: Option<&ValidatorLocalConfig>,
+    tx_hooks: &TxHooks,
     temp_wl_storage: &mut TempWlStorage<D, H>,
     vp_wasm_cache: &mut VpCache<CA>,
     tx_wasm_cache: &mut TxCache<CA>,
@@ -344,6 +349,13 @@ where
             return Err(());
         }
     }
+    let block_height = temp_wl_storage
+        .storage
+        .get_last_block_height()
+        .next_height();
+    if tx.header().is_expired_at_height(block_height) {
+        return Err(());
+    }
 
     tx.validate_tx().map_err(|_| ())?;
     if let TxType::Wrapper(wrapper) = tx.header().tx_type {
@@ -351,6 +363,10 @@ where
         let mut tx_gas_meter = TxGasMeter::new(wrapper.gas_limit);
         tx_gas_meter.add_wrapper_gas(tx_bytes).map_err(|_| ())?;
 
+        tx_hooks
+            .check_wrapper_tx(&tx, block_height)
+            .map_err(|_| ())?;
+
         super::replay_protection_checks(&tx, temp_wl_storage)
             .map_err(|_| ())?;
 
@@ -5,11 +5,12 @@ use data_encoding::HEXUPPER;
 use namada::core::hints;
 use namada::ethereum_bridge::protocol::validation::bridge_pool_roots::validate_bp_roots_vext;
 use namada::ethereum_bridge::protocol::validation::ethereum_events::validate_eth_events_vext;
+use namada::ethereum_bridge::protocol::validation::evidence::validate_duplicate_vext_evidence;
 use namada::ethereum_bridge::protocol::validation::validator_set_update::validate_valset_upd_vext;
 use namada::ledger::pos::PosQueries;
 use namada::ledger::protocol::get_fee_unshielding_transaction;
 use namada::ledger::storage::tx_queue::TxInQueue;
-use namada::parameters::validate_tx_bytes;
+use namada::parameters::{validate_tx_bytes, validate_tx_memo_bytes};
 use namada::proof_of_stake::storage::find_validator_by_raw_hash;
 use namada::state::{TempWlStorage, WlStorage};
 use namada::tx::data::protocol::ProtocolTxType;
@@ -84,6 +85,9 @@ where
         &self,
         req: RequestProcessProposal,
     ) -> (ProcessProposal, Vec<TxResult>) {
+        let _span =
+            tracing::info_span!("process_proposal", height = req.height)
+                .entered();
         tracing::info!(
             proposer = ?HEXUPPER.encode(&req.proposer_address),
             height = req.height,
@@ -304,6 +308,11 @@ where
             Ok(tx) => tx,
             Err(tx_result) => return tx_result,
         };
+        let block_height = self
+            .wl_storage
+            .storage
+            .get_last_block_height()
+            .next_height();
 
         if let Err(err) = tx.validate_tx() {
             return TxResult {
@@ -311,6 +320,14 @@ where
                 info: err.to_string(),
             };
         }
+
+        // Tx sections and embedded code size
+        if let Err(err) = self.check_tx_limits(&tx) {
+            return TxResult {
+                code: err.result_code().into(),
+                info: err.to_string(),
+            };
+        }
         match tx.header().tx_type {
             // If it is a raw transaction, we do no further validation
             TxType::Raw => TxResult {
@@ -344,6 +361,16 @@ where
                         };
                     }
                 }
+                if tx.header.is_expired_at_height(block_height) {
+                    return TxResult {
+                        code: ResultCode::ExpiredTx.into(),
+                        info: format!(
+                            "Tx expired at height {}, block height: {}",
+                            tx.header.expiration_height.unwrap_or_default(),
+                            block_height
+                        ),
+                    };
+                }
                 match protocol_tx.tx {
                     ProtocolTxType::EthEventsVext => {
                         ethereum_tx_data_variants::EthEventsVext::try_from(&tx)
@@ -437,6 +464,34 @@ where
                             }
                         })
                     }
+                    ProtocolTxType::DuplicateVextEvidence => {
+                        ethereum_tx_data_variants::DuplicateVextEvidence::try_from(
+                            &tx,
+                        )
+                        .map_err(|err| err.to_string())
+                        .and_then(|evidence| {
+                            validate_duplicate_vext_evidence(
+                                &self.wl_storage,
+                                &evidence,
+                                self.wl_storage.storage.get_last_block_height(),
+                            )
+                            .map(|_| TxResult {
+                                code: ResultCode::Ok.into(),
+                                info: "Process Proposal accepted this \
+                                       transaction"
+                                    .into(),
+                            })
+                            .map_err(|err| err.to_string())
+                        })
+                        .unwrap_or_else(|err| TxResult {
+                            code: ResultCode::InvalidVoteExtension.into(),
+                            info: format!(
+                                "Process proposal rejected this proposal \
+                                 because one of the included duplicate vote \
+                                 extensions evidence was invalid: {err}"
+                            ),
+                        })
+                    }
                     ProtocolTxType::EthereumEvents
                     | ProtocolTxType::BridgePool
                     | ProtocolTxType::ValidatorSetUpdate => TxResult {
@@ -488,6 +543,23 @@ where
                                         ),
                                     }
                                 }
+                                _ if tx
+                                    .header
+                                    .is_expired_at_height(block_height) =>
+                                {
+                                    TxResult {
+                                        code: ResultCode::ExpiredDecryptedTx
+                                            .into(),
+                                        info: format!(
+                                            "Tx expired at height {}, block \
+                                             height: {}",
+                                            tx.header
+                                                .expiration_height
+                                                .unwrap_or_default(),
+                                            block_height
+                                        ),
+                                    }
+                                }
                                 _ => TxResult {
                                     code: ResultCode::Ok.into(),
                                     info: "Process Proposal accepted this \
@@ -553,6 +625,17 @@ where
                     };
                 }
 
+                // Memo size check
+                let memo_size = tx.memo().map(|memo| memo.len()).unwrap_or(0);
+                if !validate_tx_memo_bytes(&self.wl_storage, memo_size).expect(
+                    "Failed to get max tx memo bytes param from storage",
+                ) {
+                    return TxResult {
+                        code: ResultCode::TooLarge.into(),
+                        info: "Tx memo too large".into(),
+                    };
+                }
+
                 // ChainId check
                 if tx_chain_id != self.chain_id {
                     return TxResult {
@@ -577,6 +660,26 @@ where
                         };
                     }
                 }
+                if tx.header.is_expired_at_height(block_height) {
+                    return TxResult {
+                        code: ResultCode::ExpiredTx.into(),
+                        info: format!(
+                            "Tx expired at height {}, block height: {}",
+                            tx.header.expiration_height.unwrap_or_default(),
+                            block_height
+                        ),
+                    };
+                }
+
+                // Tx hooks
+                if let Err(info) =
+                    self.tx_hooks.check_wrapper_tx(&tx, block_height)
+                {
+                    return TxResult {
+                        code: ResultCode::InvalidTx.into(),
+                        info,
+                    };
+                }
 
                 // Replay protection checks
                 if let Err(e) =
@@ -688,6 +791,7 @@ mod test_process_proposal {
         self, deactivate_bridge, gen_keypair, get_bp_bytes_to_sign,
         ProcessProposal, TestError, TestShell,
     };
+    use crate::node::ledger::shell::tx_hooks::TxHook;
     use crate::node::ledger::shims::abcipp_shim_types::shim::request::ProcessedTx;
     use crate::wallet;
 
@@ -1727,6 +1831,112 @@ mod test_process_proposal {
         }
     }
 
+    /// Test that a wrapper transaction rejected by a tx hook causes a block
+    /// rejection
+    #[test]
+    fn test_wrapper_rejected_by_tx_hook() {
+        struct RejectAll;
+
+        impl TxHook for RejectAll {
+            fn name(&self) -> &'static str {
+                "reject-all"
+            }
+
+            fn check_wrapper_tx(
+                &self,
+                _tx: &Tx,
+                _height: BlockHeight,
+            ) -> Result<(), String> {
+                Err("no txs allowed".to_string())
+            }
+        }
+
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        shell.tx_hooks.push(Box::new(RejectAll));
+        let keypair = crate::wallet::defaults::daewon_keypair();
+
+        let mut wrapper =
+            Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(1.into()),
+                    token: shell.wl_storage.storage.native_token.clone(),
+                },
+                keypair.ref_to(),
+                Epoch(0),
+                GAS_LIMIT_MULTIPLIER.into(),
+                None,
+            ))));
+        wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+        wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
+        wrapper.add_section(Section::Signature(Signature::new(
+            wrapper.sechashes(),
+            [(0, keypair)].into_iter().collect(),
+            None,
+        )));
+
+        // Run validation
+        let request = ProcessProposal {
+            txs: vec![wrapper.to_bytes()],
+        };
+        match shell.process_proposal(request) {
+            Ok(_) => panic!("Test failed"),
+            Err(TestError::RejectProposal(response)) => {
+                assert_eq!(
+                    response[0].result.code,
+                    u32::from(ResultCode::InvalidTx)
+                );
+                assert_eq!(
+                    response[0].result.info,
+                    "Rejected by the reject-all tx hook: no txs allowed"
+                );
+            }
+        }
+    }
+
+    /// Test that a wrapper transaction expired by block height causes a block
+    /// rejection
+    #[test]
+    fn test_expired_wrapper_at_height() {
+        let (shell, _recv, _, _) = test_utils::setup_at_height(3);
+        let keypair = crate::wallet::defaults::daewon_keypair();
+
+        let mut wrapper =
+            Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                Fee {
+                    amount_per_gas_unit: DenominatedAmount::native(1.into()),
+                    token: shell.wl_storage.storage.native_token.clone(),
+                },
+                keypair.ref_to(),
+                Epoch(0),
+                GAS_LIMIT_MULTIPLIER.into(),
+                None,
+            ))));
+        wrapper.header.chain_id = shell.chain_id.clone();
+        wrapper.header.expiration_height = Some(BlockHeight(3));
+        wrapper.set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+        wrapper.set_data(Data::new("transaction data".as_bytes().to_owned()));
+        wrapper.add_section(Section::Signature(Signature::new(
+            wrapper.sechashes(),
+            [(0, keypair)].into_iter().collect(),
+            None,
+        )));
+
+        // Run validation
+        let request = ProcessProposal {
+            txs: vec![wrapper.to_bytes()],
+        };
+        match shell.process_proposal(request) {
+            Ok(_) => panic!("Test failed"),
+            Err(TestError::RejectProposal(response)) => {
+                assert_eq!(
+                    response[0].result.code,
+                    u32::from(ResultCode::ExpiredTx)
+                );
+            }
+        }
+    }
+
     /// Test that an expired decrypted transaction is marked as rejected but
     /// still allows the block to be accepted
     #[test]
@@ -2173,6 +2383,83 @@ mod test_process_proposal {
         }
     }
 
+    /// Test max tx sections parameter in ProcessProposal
+    #[test]
+    fn test_max_tx_sections_process_proposal() {
+        use namada::ledger::parameters::storage::get_max_tx_sections_key;
+        let (shell, _recv, _, _) = test_utils::setup_at_height(3u64);
+
+        let max_tx_sections: u32 = {
+            let key = get_max_tx_sections_key();
+            shell
+                .wl_storage
+                .read(&key)
+                .expect("Failed to read from storage")
+                .expect("Max tx sections should have been written to storage")
+        };
+
+        let new_tx = |num_sections: u32| {
+            let keypair = super::test_utils::gen_keypair();
+            let mut wrapper =
+                Tx::from_type(TxType::Wrapper(Box::new(WrapperTx::new(
+                    Fee {
+                        amount_per_gas_unit: DenominatedAmount::native(
+                            100.into(),
+                        ),
+                        token: shell.wl_storage.storage.native_token.clone(),
+                    },
+                    keypair.ref_to(),
+                    Epoch(0),
+                    GAS_LIMIT_MULTIPLIER.into(),
+                    None,
+                ))));
+            wrapper.header.chain_id = shell.chain_id.clone();
+            wrapper
+                .set_code(Code::new("wasm_code".as_bytes().to_owned(), None));
+            wrapper
+                .set_data(Data::new("transaction data".as_bytes().to_owned()));
+            // leave room for the code, data and signature sections
+            for ix in 3..num_sections {
+                wrapper.add_section(Section::ExtraData(Code::new(
+                    ix.to_le_bytes().to_vec(),
+                    None,
+                )));
+            }
+            wrapper.add_section(Section::Signature(Signature::new(
+                wrapper.sechashes(),
+                [(0, keypair)].into_iter().collect(),
+                None,
+            )));
+            wrapper
+        };
+
+        let request = ProcessProposal {
+            txs: vec![new_tx(max_tx_sections + 1).to_bytes()],
+        };
+        match shell.process_proposal(request) {
+            Ok(_) => panic!("Test failed"),
+            Err(TestError::RejectProposal(response)) => {
+                assert_eq!(
+                    response[0].result.code,
+                    u32::from(ResultCode::TooManySections)
+                );
+            }
+        }
+
+        let request = ProcessProposal {
+            txs: vec![new_tx(max_tx_sections).to_bytes()],
+        };
+        match shell.process_proposal(request) {
+            Ok(_) => panic!("Test failed"),
+            Err(TestError::RejectProposal(response)) => {
+                assert!(
+                    response[0].result.code
+                        != u32::from(ResultCode::TooManySections)
+                );
+            }
+        }
+    }
+
     /// Test that Ethereum events with outdated nonces are
     /// not validated by `ProcessProposal`.
     #[test]
@@ -21,30 +21,13 @@ where
         let ctx = RequestCtx {
             wl_storage: &self.wl_storage,
             event_log: self.event_log(),
+            mempool_rejections: self.mempool_rejections(),
             vp_wasm_cache: self.vp_wasm_cache.read_only(),
             tx_wasm_cache: self.tx_wasm_cache.read_only(),
             storage_read_past_height_limit: self.storage_read_past_height_limit,
         };
 
-        // Invoke the root RPC handler - returns borsh-encoded data on success
-        let result = if query.path == "/shell/dry_run_tx" {
-            dry_run_tx(ctx, &query)
-        } else {
-            namada::ledger::queries::handle_path(ctx, &query)
-        };
-        match result {
-            Ok(ResponseQuery { data, info, proof }) => response::Query {
-                value: data.into(),
-                info,
-                proof: proof.map(Into::into),
-                ..Default::default()
-            },
-            Err(err) => response::Query {
-                code: 1.into(),
-                info: format!("RPC error: {}", err),
-                ..Default::default()
-            },
-        }
+        query_with_ctx(ctx, &query, self.wasm_execution_cap)
     }
 
     /// Simple helper function for the ledger to get balances
@@ -61,6 +44,42 @@ where
     }
 }
 
+/// Forward the query to the right query method with the given context and
+/// return the result. This is shared by the shell and the read-only snapshots
+/// of its state that may serve the queries concurrently. The wasm runs of the
+/// query are capped by `wasm_execution_cap`, if any.
+pub fn query_with_ctx<D, H, CA>(
+    ctx: RequestCtx<'_, D, H, VpCache<CA>, TxCache<CA>>,
+    query: &request::Query,
+    wasm_execution_cap: Option<std::time::Duration>,
+) -> response::Query
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+    CA: WasmCacheAccess + Sync + 'static,
+{
+    let _enforced_cap = namada::vm::deadline::enforce(wasm_execution_cap);
+    // Invoke the root RPC handler - returns borsh-encoded data on success
+    let result = if namada::ledger::queries::is_dry_run_tx(query) {
+        dry_run_tx(ctx, query)
+    } else {
+        namada::ledger::queries::handle_path(ctx, query)
+    };
+    match result {
+        Ok(ResponseQuery { data, info, proof }) => response::Query {
+            value: data.into(),
+            info,
+            proof: proof.map(Into::into),
+            ..Default::default()
+        },
+        Err(err) => response::Query {
+            code: 1.into(),
+            info: format!("RPC error: {}", err),
+            ..Default::default()
+        },
+    }
+}
+
 // NOTE: we are testing `namada::ledger::queries_ext`,
 // which is not possible from `namada` since we do not have
 // access to the `Shell` there
@@ -0,0 +1,288 @@
+//! Execution of the txs scheduled for an epoch.
+//!
+//! A tx scheduled with the `tx_schedule` tx is stored with its prepaid fee
+//! until the start of its epoch, when it's applied by the protocol like a
+//! decrypted tx with the gas limit it was scheduled with. The result of each
+//! scheduled tx is emitted as an applied tx event, with the epoch for which it
+//! was scheduled in its `scheduled_epoch` attribute.
+//!
+//! The total gas limit of the txs applied at the start of an epoch is capped
+//! by the `max_scheduled_txs_gas` parameter. The txs over the cap stay queued
+//! and are applied first at the start of the next epoch.
+
+use namada::ledger::protocol::{self, ShellParams};
+use namada::state::wl_storage::WriteLogAndStorage;
+use namada::state::{DBIter, StorageHasher, StorageRead, StorageWrite, DB};
+use namada::token;
+use namada::tx::data::scheduled::{self, ScheduledTx};
+use namada::types::address::Address;
+use namada::types::storage::{BlockHeight, Epoch};
+
+use super::*;
+
+/// Apply the txs scheduled for the current or any earlier epoch, up to the
+/// `max_scheduled_txs_gas` parameter, and remove them from the queue. Their
+/// prepaid fees are paid to the block proposer. Must be called on a new epoch.
+pub fn execute_scheduled_txs<D, H>(
+    shell: &mut Shell<D, H>,
+    response: &mut shim::response::FinalizeBlock,
+    height: BlockHeight,
+    current_epoch: Epoch,
+    block_proposer: &Address,
+) -> Result<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    let max_gas =
+        namada::parameters::get_max_scheduled_txs_gas(&shell.wl_storage)?;
+    let prefix = scheduled::scheduled_txs_prefix();
+    let mut due = Vec::new();
+    let mut total_gas = 0_u64;
+    for entry in
+        namada::state::iter_prefix::<ScheduledTx>(&shell.wl_storage, &prefix)?
+    {
+        let (key, scheduled) = entry?;
+        match scheduled::is_scheduled_tx_key(&key) {
+            Some((epoch, _)) if epoch <= current_epoch => {
+                // A tx over the cap is always applied if it's the first one,
+                // so that it can't block the queue if the cap is lowered
+                total_gas =
+                    total_gas.saturating_add(scheduled.gas_limit.into());
+                if total_gas > max_gas && !due.is_empty() {
+                    break;
+                }
+                due.push((key, scheduled))
+            }
+            // The keys are ordered by epoch
+            Some(_) => break,
+            None => {}
+        }
+    }
+
+    let native_token = shell.wl_storage.get_native_token()?;
+    for (key, scheduled) in due {
+        let hash = scheduled.hash();
+        shell.wl_storage.delete(&key)?;
+        token::transfer(
+            &mut shell.wl_storage,
+            &native_token,
+            &scheduled::ADDRESS,
+            block_proposer,
+            scheduled.fee,
+        )?;
+        shell.wl_storage.commit_tx();
+
+        let mut tx = scheduled.tx;
+        tx.update_header(TxType::Decrypted(DecryptedTx::Decrypted));
+        let mut tx_event = Event::new_tx_event(&tx, height.0);
+        tx_event["scheduled_epoch"] = scheduled.epoch.to_string();
+
+        if shell
+            .wl_storage
+            .has_replay_protection_entry(&hash)
+            .expect("Error while checking the replay protection storage")
+        {
+            tracing::info!(
+                "The tx {hash} scheduled for epoch {} has already been \
+                 applied.",
+                scheduled.epoch
+            );
+            tx_event["code"] = ResultCode::ReplayTx.into();
+            tx_event["info"] = "The tx has already been applied".to_string();
+            tx_event["gas_used"] = "0".into();
+            response.events.push(tx_event);
+            continue;
+        }
+
+        // Apply the tx on a fork of the storage, whose changes are only
+        // merged back if the tx is accepted
+        let mut tx_gas_meter = TxGasMeter::new(scheduled.gas_limit);
+        let mut fork = shell.wl_storage.fork();
+        let tx_result = protocol::apply_wasm_tx(
+            tx,
+            &TxIndex::default(),
+            ShellParams::new(
+                &mut tx_gas_meter,
+                &mut fork,
+                &mut shell.vp_wasm_cache,
+                &mut shell.tx_wasm_cache,
+            ),
+        );
+        let write_log = fork.into_write_log();
+        match tx_result {
+            Ok(result) => {
+                if result.is_accepted() {
+                    shell.wl_storage.merge(write_log);
+                    shell.wl_storage.commit_tx();
+                    tx_event["code"] = ResultCode::Ok.into();
+                } else {
+                    shell.wl_storage.drop_tx();
+                    tx_event["code"] = ResultCode::InvalidTx.into();
+                }
+                tx_event["gas_used"] = result.gas_used.to_string();
+                tx_event["info"] = "Check inner_tx for result.".to_string();
+                tx_event["inner_tx"] = result.to_string();
+            }
+            Err(msg) => {
+                shell.wl_storage.drop_tx();
+                tx_event["code"] = ResultCode::WasmRuntimeError.into();
+                tx_event["gas_used"] =
+                    tx_gas_meter.get_tx_consumed_gas().to_string();
+                tx_event["info"] = msg.to_string();
+            }
+        }
+        tracing::info!(
+            "The tx {hash} scheduled for epoch {} has been applied with code \
+             {}.",
+            scheduled.epoch,
+            tx_event["code"]
+        );
+        // A scheduled tx is only applied once, whatever its result
+        shell
+            .wl_storage
+            .write_tx_hash(hash)
+            .expect("Error while writing tx hash to storage");
+        response.events.push(tx_event);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test_scheduled_txs {
+    use namada::tx::{Code, Data};
+    use namada::types::address::testing::established_address_1;
+
+    use super::*;
+    use crate::node::ledger::shell::test_utils::{
+        self, top_level_directory, TestShell,
+    };
+    use crate::wallet::defaults;
+
+    const GAS_LIMIT: u64 = 5_000_000;
+
+    /// Schedule a test tx for the epoch and return its key and hash
+    fn schedule_tx(
+        shell: &mut TestShell,
+        wasm: &str,
+        epoch: Epoch,
+        nonce: u8,
+    ) -> (Key, Hash) {
+        let mut wasm_path = top_level_directory();
+        wasm_path.push(format!("wasm_for_tests/{wasm}"));
+        let tx_code = std::fs::read(wasm_path)
+            .expect("Expected a file at given code path");
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.header.chain_id = shell.chain_id.clone();
+        tx.set_code(Code::new(tx_code, None));
+        tx.set_data(Data::new(vec![nonce]));
+        let scheduled = ScheduledTx {
+            epoch,
+            payer: defaults::albert_address(),
+            gas_limit: GAS_LIMIT.into(),
+            fee: token::Amount::native_whole(1),
+            tx,
+        };
+        let hash = scheduled.hash();
+        let key = scheduled::scheduled_tx_key(epoch, &hash);
+        let native_token = shell.wl_storage.storage.native_token.clone();
+        token::credit_tokens(
+            &mut shell.wl_storage,
+            &native_token,
+            &scheduled::ADDRESS,
+            scheduled.fee,
+        )
+        .unwrap();
+        shell.wl_storage.write(&key, scheduled).unwrap();
+        shell.wl_storage.commit_tx();
+        (key, hash)
+    }
+
+    /// Execute the txs scheduled up to the epoch and return their events
+    fn execute(shell: &mut TestShell, epoch: Epoch) -> Vec<Event> {
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_scheduled_txs(
+            &mut shell.shell,
+            &mut response,
+            BlockHeight(1),
+            epoch,
+            &established_address_1(),
+        )
+        .unwrap();
+        response.events
+    }
+
+    /// Test that a due tx is applied and removed from the queue, its fee is
+    /// paid to the block proposer and the txs scheduled for a later epoch
+    /// stay queued.
+    #[test]
+    fn test_execute_scheduled_tx() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        let (key, hash) = schedule_tx(&mut shell, "tx_no_op.wasm", Epoch(1), 0);
+        let (later_key, _) =
+            schedule_tx(&mut shell, "tx_no_op.wasm", Epoch(2), 0);
+
+        let events = execute(&mut shell, Epoch(1));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["code"], String::from(ResultCode::Ok));
+        assert_eq!(events[0]["scheduled_epoch"], "1");
+        assert!(!shell.wl_storage.has_key(&key).unwrap());
+        assert!(shell.wl_storage.has_key(&later_key).unwrap());
+        assert!(shell.wl_storage.has_replay_protection_entry(&hash).unwrap());
+
+        let native_token = shell.wl_storage.storage.native_token.clone();
+        let proposer_balance = token::read_balance(
+            &shell.wl_storage,
+            &native_token,
+            &established_address_1(),
+        )
+        .unwrap();
+        assert_eq!(proposer_balance, token::Amount::native_whole(1));
+    }
+
+    /// Test that a failing tx is removed from the queue and can't be replayed.
+    #[test]
+    fn test_execute_failing_scheduled_tx() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        let (key, hash) = schedule_tx(&mut shell, "tx_fail.wasm", Epoch(1), 0);
+
+        let events = execute(&mut shell, Epoch(1));
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0]["code"],
+            String::from(ResultCode::WasmRuntimeError)
+        );
+        assert!(!shell.wl_storage.has_key(&key).unwrap());
+        assert!(shell.wl_storage.has_replay_protection_entry(&hash).unwrap());
+    }
+
+    /// Test that the txs over the `max_scheduled_txs_gas` parameter are
+    /// deferred to the next epoch.
+    #[test]
+    fn test_scheduled_txs_gas_cap() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        shell
+            .wl_storage
+            .write(
+                &namada::parameters::storage::get_max_scheduled_txs_gas_key(),
+                2 * GAS_LIMIT,
+            )
+            .unwrap();
+        for nonce in 0..3 {
+            schedule_tx(&mut shell, "tx_no_op.wasm", Epoch(1), nonce);
+        }
+
+        let events = execute(&mut shell, Epoch(1));
+        assert_eq!(events.len(), 2);
+        let events = execute(&mut shell, Epoch(2));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["scheduled_epoch"], "1");
+        let prefix = scheduled::scheduled_txs_prefix();
+        assert!(
+            namada::state::iter_prefix_bytes(&shell.wl_storage, &prefix)
+                .unwrap()
+                .next()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,254 @@
+//! Optimistic scheduling of the decrypted transactions of a block.
+//!
+//! Before `FinalizeBlock` applies the transactions of a block one by one, the
+//! decrypted transactions at the start of the block are pre-executed in
+//! parallel on top of the state at that point, recording the keys read by
+//! each of them and their VPs. The serial loop then uses these results
+//! instead of applying the transactions again, unless a transaction applied
+//! before wrote any key that the pre-executed one read or wrote, in which
+//! case it is applied serially on the up to date state.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use namada::ledger::gas::TxGasMeter;
+use namada::ledger::protocol::{self, ShellParams};
+use namada::state::write_log::{ReadSet, TxWriteLog, WriteLog};
+use namada::state::{DBIter, StorageHasher, TempWlStorage, DB};
+use namada::tx::data::{DecryptedTx, ResultCode, TxResult, TxType};
+use namada::tx::Tx;
+use namada::types::storage::{Key, TxIndex};
+use rayon::prelude::*;
+
+use super::Shell;
+use crate::node::ledger::shims::abcipp_shim_types::shim;
+
+/// A transaction applied on a copy of the write log at the start of the
+/// block
+pub struct PreExecutedTx {
+    /// The result of applying the transaction
+    pub result: protocol::Result<TxResult>,
+    /// The gas meter of the transaction
+    pub gas_meter: TxGasMeter,
+    /// The storage modifications of the transaction
+    pub write_log: TxWriteLog,
+    /// The keys read by the transaction and its VPs
+    pub read_set: ReadSet,
+}
+
+impl PreExecutedTx {
+    /// Check if the transaction read or wrote any of the given keys
+    fn conflicts_with(&self, written: &BTreeSet<Key>) -> bool {
+        written.iter().any(|key| self.read_set.contains(key))
+            || self.write_log.keys().any(|key| written.contains(key))
+    }
+}
+
+/// The transactions of a block that were pre-executed in parallel, by index
+/// in the block
+#[derive(Default)]
+pub struct PreExecutedTxs {
+    /// The keys written by the txs applied so far in the block
+    written: BTreeSet<Key>,
+    /// The results of the pre-executed txs
+    results: HashMap<usize, PreExecutedTx>,
+}
+
+impl PreExecutedTxs {
+    /// Take the result of the tx at the given index, if it was pre-executed
+    /// and none of the keys it read or wrote were since written by the txs
+    /// applied before it. Otherwise, the tx must be applied serially.
+    pub fn take(&mut self, tx_index: usize) -> Option<PreExecutedTx> {
+        let pre_executed = self.results.remove(&tx_index)?;
+        if pre_executed.conflicts_with(&self.written) {
+            tracing::debug!(
+                "Tx at index {tx_index} depends on the writes of a previous                  tx, applying it serially"
+            );
+            return None;
+        }
+        Some(pre_executed)
+    }
+
+    /// Record the keys written by the tx about to be committed from the
+    /// given write log, to check the pre-executed txs after it against them
+    pub fn record_writes(&mut self, write_log: &WriteLog) {
+        if !self.results.is_empty() {
+            self.written.extend(write_log.get_keys_with_precommit());
+        }
+    }
+}
+
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Pre-execute in parallel the decrypted txs at the start of the block.
+    /// Nothing is pre-executed unless enabled in the config.
+    pub(super) fn pre_execute_txs(
+        &self,
+        txs: &[shim::request::ProcessedTx],
+    ) -> PreExecutedTxs {
+        let mut pre_executed = PreExecutedTxs::default();
+        if !self.parallel_tx_execution {
+            return pre_executed;
+        }
+
+        // Find the txs that can be pre-executed. This must mirror the
+        // handling of the txs in `finalize_block`, which pops a wrapper from
+        // the queue for each decrypted tx.
+        let mut tx_hashes = HashSet::new();
+        let mut queue_index = 0;
+        let mut scheduled = vec![];
+        for (tx_index, processed_tx) in txs.iter().enumerate() {
+            let Ok(tx) = Tx::try_from(processed_tx.tx.as_ref()) else {
+                break;
+            };
+            let code = ResultCode::from_u32(processed_tx.result.code);
+            if code == Some(ResultCode::InvalidSig) || tx.validate_tx().is_err()
+            {
+                break;
+            }
+            let TxType::Decrypted(inner) = tx.header().tx_type else {
+                break;
+            };
+            let Some(tx_in_queue) =
+                self.wl_storage.storage.tx_queue.get(queue_index)
+            else {
+                break;
+            };
+            queue_index += 1;
+            // Rejected and undecryptable txs are not applied
+            if code != Some(ResultCode::Ok)
+                || matches!(inner, DecryptedTx::Undecryptable)
+            {
+                continue;
+            }
+            // The replay protection of a repeated tx depends on the outcome
+            // of its first occurrence
+            if !tx_hashes.insert(tx.raw_header_hash()) {
+                break;
+            }
+            scheduled.push((tx_index, tx, tx_in_queue.gas));
+        }
+        if scheduled.is_empty() {
+            return pre_executed;
+        }
+
+        let _span =
+            tracing::info_span!("pre_execute_txs", scheduled = scheduled.len())
+                .entered();
+        tracing::debug!(
+            "Pre-executing {} of {} txs in parallel",
+            scheduled.len(),
+            txs.len()
+        );
+        let write_log = &self.wl_storage.write_log;
+        let storage = &self.wl_storage.storage;
+        let vp_wasm_cache = &self.vp_wasm_cache;
+        let tx_wasm_cache = &self.tx_wasm_cache;
+        let results: Vec<_> = scheduled
+            .into_par_iter()
+            .filter_map(|(tx_index, tx, gas)| {
+                let mut wl_storage = TempWlStorage {
+                    write_log: write_log.clone(),
+                    storage,
+                };
+                wl_storage.write_log.record_reads();
+                let mut gas_meter = TxGasMeter::new_from_sub_limit(gas);
+                let mut vp_wasm_cache = vp_wasm_cache.clone();
+                let mut tx_wasm_cache = tx_wasm_cache.clone();
+                let result = protocol::apply_wasm_tx(
+                    tx,
+                    &TxIndex(
+                        tx_index
+                            .try_into()
+                            .expect("transaction index out of bounds"),
+                    ),
+                    ShellParams::new(
+                        &mut gas_meter,
+                        &mut wl_storage,
+                        &mut vp_wasm_cache,
+                        &mut tx_wasm_cache,
+                    ),
+                );
+                let read_set = wl_storage.write_log.take_read_set();
+                let write_log = wl_storage.write_log.take_tx();
+                // The addresses of new accounts depend on the accounts
+                // initialized before in the block
+                if write_log.has_initialized_accounts() {
+                    return None;
+                }
+                Some((
+                    tx_index,
+                    PreExecutedTx {
+                        result,
+                        gas_meter,
+                        write_log,
+                        read_set,
+                    },
+                ))
+            })
+            .collect();
+        pre_executed.results.extend(results);
+        pre_executed
+    }
+}
+
+#[cfg(test)]
+mod test_scheduler {
+    use namada::types::address::nam;
+    use namada::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+
+    use super::*;
+
+    fn pre_executed_tx(read_set: ReadSet) -> PreExecutedTx {
+        PreExecutedTx {
+            result: Ok(TxResult::default()),
+            gas_meter: TxGasMeter::new_from_sub_limit(0.into()),
+            write_log: TxWriteLog::default(),
+            read_set,
+        }
+    }
+
+    /// Test that a pre-executed tx that read a key written by a previous tx
+    /// is applied serially.
+    #[test]
+    fn test_conflicting_read_discards_result() {
+        let key =
+            |owner| namada::token::storage_key::balance_key(&nam(), &owner);
+        let mut pre_executed = PreExecutedTxs::default();
+        pre_executed.results.insert(
+            1,
+            pre_executed_tx(ReadSet {
+                keys: BTreeSet::from([key(established_address_1())]),
+                prefixes: BTreeSet::new(),
+            }),
+        );
+        pre_executed.results.insert(
+            2,
+            pre_executed_tx(ReadSet {
+                keys: BTreeSet::new(),
+                prefixes: BTreeSet::from([key(established_address_2())]),
+            }),
+        );
+        pre_executed
+            .results
+            .insert(3, pre_executed_tx(ReadSet::default()));
+
+        // The tx at index 0 writes the keys read by the txs at index 1 and 2
+        let mut write_log = WriteLog::default();
+        write_log
+            .write(&key(established_address_1()), vec![])
+            .unwrap();
+        write_log
+            .write(&key(established_address_2()), vec![])
+            .unwrap();
+        pre_executed.record_writes(&write_log);
+
+        assert!(pre_executed.take(1).is_none());
+        assert!(pre_executed.take(2).is_none());
+        assert!(pre_executed.take(3).is_some());
+    }
+}
@@ -22,6 +22,9 @@ pub fn run(
             chain_id: Some(locked.chain_id.clone()),
             base_dir: locked.base_dir.clone(),
             wasm_dir: Some(locked.wasm_dir.clone()),
+            no_progress: true,
+            profile: None,
+            proving_threads: None,
         }
     };
     let ctx = Context::new::<TestingIo>(global.clone())?;
@@ -14,7 +14,7 @@ use namada::eth_bridge::oracle::config::Config as OracleConfig;
 use namada::ledger::dry_run_tx;
 use namada::ledger::events::log::dumb_queries;
 use namada::ledger::queries::{
-    EncodedResponseQuery, RequestCtx, RequestQuery, Router, RPC,
+    is_dry_run_tx, EncodedResponseQuery, RequestCtx, RequestQuery, Router, RPC,
 };
 use namada::proof_of_stake::pos_queries::PosQueries;
 use namada::proof_of_stake::storage::{
@@ -692,11 +692,12 @@ impl<'a> Client for &'a MockNode {
         let ctx = RequestCtx {
             wl_storage: &borrowed.wl_storage,
             event_log: borrowed.event_log(),
+            mempool_rejections: borrowed.mempool_rejections(),
             vp_wasm_cache: borrowed.vp_wasm_cache.read_only(),
             tx_wasm_cache: borrowed.tx_wasm_cache.read_only(),
             storage_read_past_height_limit: None,
         };
-        if request.path == "/shell/dry_run_tx" {
+        if is_dry_run_tx(&request) {
             dry_run_tx(ctx, &request)
         } else {
             rpc.handle(ctx, &request)
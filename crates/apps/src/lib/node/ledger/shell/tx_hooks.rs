@@ -0,0 +1,116 @@
+//! Hooks for chains built on this codebase to add their own validation and
+//! indexing of transactions, without forking the shell.
+//!
+//! A [`TxHook`] is invoked with the decoded transactions:
+//!
+//! - [`TxHook::check_wrapper_tx`] when a wrapper transaction is validated for
+//!   the mempool in `CheckTx` and for a block in `PrepareProposal` and
+//!   `ProcessProposal`. Rejected transactions are removed from the mempool,
+//!   left out of the proposals, and make the proposals that include them
+//!   invalid.
+//! - [`TxHook::on_finalized_tx`] when a transaction is applied in
+//!   `FinalizeBlock`, with its event.
+//!
+//! Since rejections affect consensus, the checks must be deterministic and
+//! all the validators of a chain must run the same hooks.
+//!
+//! The hooks are loaded statically: a chain adds its implementations to
+//! [`TxHooks::load`], each enabled by a feature flag of this crate.
+
+use namada::ledger::events::Event;
+use namada::tx::Tx;
+use namada::types::storage::BlockHeight;
+
+/// A hook invoked with the transactions processed by the shell
+pub trait TxHook: Send + Sync {
+    /// The name of the hook, used in logs and in the reasons of rejections
+    fn name(&self) -> &'static str;
+
+    /// Check a wrapper transaction to be included in the block at the given
+    /// height. Returns the reason of a rejection as an error.
+    fn check_wrapper_tx(
+        &self,
+        _tx: &Tx,
+        _height: BlockHeight,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Observe a transaction applied in the block at the given height, with
+    /// the event that reports its result
+    fn on_finalized_tx(&self, _tx: &Tx, _height: BlockHeight, _event: &Event) {}
+}
+
+/// The hooks run by the shell, in order
+#[derive(Default)]
+pub struct TxHooks(Vec<Box<dyn TxHook>>);
+
+impl TxHooks {
+    /// Load the hooks enabled by the feature flags of the build
+    pub fn load() -> Self {
+        #[allow(unused_mut)]
+        let mut hooks = Self::default();
+        #[cfg(feature = "tx-hook-log")]
+        hooks.push(Box::new(LogTxHook));
+        hooks
+    }
+
+    /// Add a hook, run after the existing ones
+    pub fn push(&mut self, hook: Box<dyn TxHook>) {
+        self.0.push(hook);
+    }
+
+    /// Check if there are no hooks
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Check a wrapper transaction with all the hooks, until one of them
+    /// rejects it
+    pub fn check_wrapper_tx(
+        &self,
+        tx: &Tx,
+        height: BlockHeight,
+    ) -> Result<(), String> {
+        self.0.iter().try_for_each(|hook| {
+            hook.check_wrapper_tx(tx, height).map_err(|reason| {
+                format!("Rejected by the {} tx hook: {reason}", hook.name())
+            })
+        })
+    }
+
+    /// Pass an applied transaction to all the hooks
+    pub fn on_finalized_tx(&self, tx: &Tx, height: BlockHeight, event: &Event) {
+        for hook in &self.0 {
+            hook.on_finalized_tx(tx, height, event);
+        }
+    }
+}
+
+impl std::fmt::Debug for TxHooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.0.iter().map(|hook| hook.name()))
+            .finish()
+    }
+}
+
+/// A hook that logs the hash and result code of the applied transactions
+#[cfg(feature = "tx-hook-log")]
+struct LogTxHook;
+
+#[cfg(feature = "tx-hook-log")]
+impl TxHook for LogTxHook {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    fn on_finalized_tx(&self, _tx: &Tx, height: BlockHeight, event: &Event) {
+        tracing::info!(
+            %height,
+            hash = event.get("hash").map(String::as_str).unwrap_or_default(),
+            code = event.get("code").map(String::as_str).unwrap_or_default(),
+            "Finalized tx"
+        );
+    }
+}
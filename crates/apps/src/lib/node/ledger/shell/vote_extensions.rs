@@ -162,6 +162,16 @@ where
                         .valset_upd_seen(ext.data.signing_epoch.next()))
                     .then(|| tx_bytes.clone())
                 }
+                EthereumTxData::DuplicateVextEvidence(evidence) => {
+                    // only propose evidence that can still be slashed
+                    validate_duplicate_vext_evidence(
+                        &self.wl_storage,
+                        &evidence,
+                        self.wl_storage.storage.get_last_block_height(),
+                    )
+                    .is_ok()
+                    .then(|| tx_bytes.clone())
+                }
                 _ => None,
             }
         })
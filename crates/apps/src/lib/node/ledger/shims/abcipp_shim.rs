@@ -5,6 +5,7 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use futures::future::FutureExt;
+use namada::ledger::queries::reads_shell_logs;
 use namada::proof_of_stake::storage::find_validator_by_raw_hash;
 use namada::tx::data::hash_tx;
 use namada::tx::Tx;
@@ -25,6 +26,8 @@ use crate::facade::tendermint::v0_37::abci::{
 };
 use crate::facade::tendermint_proto::v0_37::abci::ResponseDeliverTx;
 use crate::facade::tower_abci::BoxError;
+use crate::node::ledger::mempool_feed;
+use crate::node::ledger::query_snapshot::{QuerySnapshot, QUERY_SNAPSHOT_DIR};
 use crate::node::ledger::shell::{EthereumOracleChannels, Shell};
 
 /// The shim wraps the shell, which implements ABCI++.
@@ -39,6 +42,9 @@ pub struct AbcippShim {
         Req,
         tokio::sync::oneshot::Sender<Result<Resp, BoxError>>,
     )>,
+    /// Notifies the refresher of the query snapshot of the block commits, if
+    /// the queries are served from a snapshot
+    query_snapshot_refresh: Option<std::sync::mpsc::Sender<()>>,
 }
 
 impl AbcippShim {
@@ -53,6 +59,7 @@ impl AbcippShim {
         db_cache: &rocksdb::Cache,
         vp_wasm_compilation_cache: u64,
         tx_wasm_compilation_cache: u64,
+        mempool_feed: Option<mempool_feed::Sender>,
     ) -> (Self, AbciService, broadcast::Sender<()>) {
         // We can use an unbounded channel here, because tower-abci limits the
         // the number of requests that can come in
@@ -60,23 +67,54 @@ impl AbcippShim {
         let (shell_send, shell_recv) = std::sync::mpsc::channel();
         let (server_shutdown, _) = broadcast::channel::<()>(1);
         let action_at_height = config.shell.action_at_height.clone();
+        let query_snapshot_config =
+            config.shell.query_snapshots.unwrap_or_default().then(|| {
+                (
+                    config.db_dir(),
+                    config.chain_dir().join(QUERY_SNAPSHOT_DIR),
+                    config.shell.storage_read_past_height_limit,
+                )
+            });
+        let mut service = Shell::new(
+            config,
+            wasm_dir,
+            broadcast_sender,
+            eth_oracle,
+            Some(db_cache),
+            vp_wasm_compilation_cache,
+            tx_wasm_compilation_cache,
+        );
+        if let Some(sender) = mempool_feed {
+            service.set_mempool_feed(sender);
+        }
+        let query_snapshot = query_snapshot_config.map(
+            |(db_path, secondary_path, storage_read_past_height_limit)| {
+                QuerySnapshot::open(
+                    db_path,
+                    secondary_path,
+                    service.chain_id.clone(),
+                    service.wl_storage.storage.native_token.clone(),
+                    service.vp_wasm_cache.read_only(),
+                    service.tx_wasm_cache.read_only(),
+                    storage_read_past_height_limit,
+                    service.wasm_execution_cap,
+                )
+                .expect("Opening the query snapshot should not fail")
+            },
+        );
+        let query_snapshot_refresh =
+            query_snapshot.as_ref().map(QuerySnapshot::spawn_refresher);
         (
             Self {
-                service: Shell::new(
-                    config,
-                    wasm_dir,
-                    broadcast_sender,
-                    eth_oracle,
-                    Some(db_cache),
-                    vp_wasm_compilation_cache,
-                    tx_wasm_compilation_cache,
-                ),
+                service,
                 begin_block_request: None,
                 delivered_txs: vec![],
                 shell_recv,
+                query_snapshot_refresh,
             },
             AbciService {
                 shell_send,
+                query_snapshot,
                 shutdown: server_shutdown.clone(),
                 action_at_height,
                 suspended: false,
@@ -96,6 +134,7 @@ impl AbcippShim {
     /// [`AbciService`].
     pub fn run(mut self) {
         while let Ok((req, resp_sender)) = self.shell_recv.recv() {
+            let is_commit = matches!(req, Req::Commit);
             let resp = match req {
                 Req::ProcessProposal(proposal) => self
                     .service
@@ -186,6 +225,12 @@ impl AbcippShim {
             if resp_sender.send(resp).is_err() {
                 tracing::info!("ABCI response channel is closed")
             }
+            if is_commit {
+                if let Some(refresh) = self.query_snapshot_refresh.as_ref() {
+                    // The refresher only stops if it panicked
+                    _ = refresh.send(());
+                }
+            }
         }
     }
 }
@@ -210,6 +255,9 @@ pub struct AbciService {
         Req,
         tokio::sync::oneshot::Sender<Result<Resp, BoxError>>,
     )>,
+    /// A snapshot of the last committed state to serve the queries from,
+    /// instead of forwarding them to the shell, when enabled
+    query_snapshot: Option<QuerySnapshot>,
     /// Indicates if the consensus connection is suspended.
     suspended: bool,
     /// This resolves the non-completing futures returned to tower-abci
@@ -310,6 +358,20 @@ impl AbciService {
         .boxed()
     }
 
+    /// Serve the query from the snapshot of the last committed state in a
+    /// blocking task, concurrently with the requests handled by the shell.
+    fn query_snapshot(
+        snapshot: QuerySnapshot,
+        query: request::Query,
+    ) -> <Self as Service<Req>>::Future {
+        tokio::task::spawn_blocking(move || snapshot.query(query))
+            .map(|result| match result {
+                Ok(resp) => Ok(Resp::Query(resp)),
+                Err(err) => Err(err.into()),
+            })
+            .boxed()
+    }
+
     /// Given the type of request, determine if we need to check
     /// to possibly take an action.
     fn get_action(&self, req: &Req) -> Option<CheckAction> {
@@ -354,6 +416,13 @@ impl Service<Req> for AbciService {
     }
 
     fn call(&mut self, req: Req) -> Self::Future {
+        if let (Req::Query(query), Some(snapshot)) =
+            (&req, &self.query_snapshot)
+        {
+            if !reads_shell_logs(query) {
+                return Self::query_snapshot(snapshot.clone(), query.clone());
+            }
+        }
         let action = self.get_action(&req);
         if let Some(action) = action {
             let (suspended, fut) = Self::maybe_take_action(
@@ -10,6 +10,7 @@ use arse_merkle_tree::traits::Hasher;
 use arse_merkle_tree::H256;
 use blake2b_rs::{Blake2b, Blake2bBuilder};
 use namada::state::{State, StorageHasher};
+pub use rocksdb::{open_secondary, BlockIntegrity};
 
 #[derive(Default)]
 pub struct PersistentStorageHasher(Blake2bHasher);
@@ -149,6 +150,9 @@ mod tests {
         // initialize parameter storage
         let params = Parameters {
             max_tx_bytes: 1024 * 1024,
+            max_tx_memo_bytes: 256,
+            max_tx_sections: 64,
+            max_tx_code_bytes: 600_000,
             epoch_duration: EpochDuration {
                 min_num_of_blocks: 1,
                 min_duration: DurationSecs(3600),
@@ -156,6 +160,7 @@ mod tests {
             max_expected_time_per_block: DurationSecs(3600),
             max_proposal_bytes: Default::default(),
             max_block_gas: 100,
+            max_scheduled_txs_gas: 100,
             vp_whitelist: vec![],
             tx_whitelist: vec![],
             implicit_vp_code_hash: Default::default(),
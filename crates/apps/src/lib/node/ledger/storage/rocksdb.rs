@@ -25,6 +25,8 @@
 //!   - `old/{dyn}`: value from predecessor block height
 //! - `block`: block state
 //!   - `results/{h}`: block results at height `h`
+//!   - `tx_heights/{hash}`: height of the block that included the tx with the
+//!     given hash
 //!   - `h`: for each block at height `h`:
 //!     - `tree`: merkle tree
 //!       - `root`: root hash
@@ -34,10 +36,13 @@
 //!     - `epoch`: block epoch
 //!     - `address_gen`: established address generator
 //!     - `header`: block's header
+//!     - `checksums`: checksums of the block's values and of the `subspace` and
+//!       `diffs` values written at `h`
 //! - `replay_protection`: hashes of processed tx
 //!     - `all`: the hashes included up to the last block
 //!     - `last`: the hashes included in the last block
 
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{BufWriter, Write};
 use std::path::Path;
@@ -56,9 +61,10 @@ use namada::state::merkle_tree::{base_tree_key_prefix, subtree_key_prefix};
 use namada::state::types::PrefixIterator;
 use namada::state::{
     BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, DbError as Error,
-    DbResult as Result, MerkleTreeStoresRead, StoreType, DB,
+    DbResult as Result, MerkleTreeStoresRead, StoreType, ValueChunk, DB,
 };
 use namada::types;
+use namada::types::hash::Hash;
 use namada::types::storage::{
     BlockHeight, BlockResults, Epoch, EthEventsQueue, Header, Key, KeySeg,
     KEY_SEGMENT_SEPARATOR,
@@ -89,11 +95,40 @@ const REPLAY_PROTECTION_CF: &str = "replay_protection";
 
 /// RocksDB handle
 #[derive(Debug)]
-pub struct RocksDB(rocksdb::DB);
+pub struct RocksDB(
+    rocksdb::DB,
+    /// Whether this is a read-only secondary instance following the primary
+    /// instance that writes to the DB
+    bool,
+);
 
-/// DB Handle for batch writes.
+/// DB Handle for batch writes, along with the checksums of the `subspace`
+/// and `diffs` values written in the batch.
 #[derive(Default)]
-pub struct RocksDBWriteBatch(WriteBatch);
+pub struct RocksDBWriteBatch(WriteBatch, BlockChecksums);
+
+/// The checksums of the values written for a block, by their column family
+/// and key. The checksum of a deleted key is `None`.
+type BlockChecksums = BTreeMap<(String, String), Option<Hash>>;
+
+/// The outcome of the integrity check of the last committed block
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockIntegrity {
+    /// No block has been committed yet
+    Empty,
+    /// The block's values match their checksums
+    Verified(BlockHeight),
+    /// The block was committed without checksums
+    Unchecked(BlockHeight),
+    /// The block's values are missing, unreadable or don't match their
+    /// checksums
+    Corrupted {
+        /// The height of the block
+        height: BlockHeight,
+        /// The reason for which the block is considered corrupted
+        reason: String,
+    },
+}
 
 /// Open RocksDB for the DB
 pub fn open(
@@ -189,17 +224,55 @@ pub fn open(
     ));
 
     rocksdb::DB::open_cf_descriptors(&db_opts, path, cfs)
-        .map(RocksDB)
+        .map(|db| RocksDB(db, false))
         .map_err(|e| Error::DBError(e.into_string()))
 }
 
+/// Open a read-only secondary instance of the RocksDB at the given path. The
+/// secondary instance keeps its own info logs in `secondary_path` and it only
+/// sees the writes of the primary instance up to its last
+/// [`RocksDB::catch_up_with_primary`].
+pub fn open_secondary(
+    primary_path: impl AsRef<Path>,
+    secondary_path: impl AsRef<Path>,
+) -> Result<RocksDB> {
+    let mut db_opts = Options::default();
+    // Secondary instances must keep all the files open
+    db_opts.set_max_open_files(-1);
+    rocksdb::DB::open_cf_as_secondary(
+        &db_opts,
+        primary_path.as_ref(),
+        secondary_path.as_ref(),
+        [
+            SUBSPACE_CF,
+            DIFFS_CF,
+            STATE_CF,
+            BLOCK_CF,
+            REPLAY_PROTECTION_CF,
+        ],
+    )
+    .map(|db| RocksDB(db, true))
+    .map_err(|e| Error::DBError(e.into_string()))
+}
+
 impl Drop for RocksDB {
     fn drop(&mut self) {
-        self.flush(true).expect("flush failed");
+        // A secondary instance has nothing to flush
+        if !self.1 {
+            self.flush(true).expect("flush failed");
+        }
     }
 }
 
 impl RocksDB {
+    /// Catch up a secondary instance with the writes of the primary instance.
+    /// The writes of a batch are either all visible after this or none are.
+    pub fn catch_up_with_primary(&self) -> Result<()> {
+        self.0
+            .try_catch_up_with_primary()
+            .map_err(|e| Error::DBError(e.into_string()))
+    }
+
     fn get_column_family(&self, cf_name: &str) -> Result<&ColumnFamily> {
         self.0
             .cf_handle(cf_name)
@@ -261,6 +334,12 @@ impl RocksDB {
                 .map_err(Error::KeyError)?
                 .join(key)
                 .to_string();
+            record_checksum(
+                &mut batch.1,
+                DIFFS_CF,
+                old_val_key.clone(),
+                Some(old_value),
+            );
             batch.0.put_cf(cf, old_val_key, old_value);
         }
 
@@ -270,6 +349,12 @@ impl RocksDB {
                 .map_err(Error::KeyError)?
                 .join(key)
                 .to_string();
+            record_checksum(
+                &mut batch.1,
+                DIFFS_CF,
+                new_val_key.clone(),
+                Some(new_value),
+            );
             batch.0.put_cf(cf, new_val_key, new_value);
         }
         Ok(())
@@ -438,18 +523,20 @@ impl RocksDB {
         &mut self,
         tendermint_block_height: BlockHeight,
     ) -> Result<()> {
-        let last_block = self.read_last_block()?.ok_or(Error::DBError(
+        // Only the height and epochs of the last block are read, so that a
+        // block whose other data is corrupted can be rolled back
+        let last_height = self.read_last_height()?.ok_or(Error::DBError(
             "Missing last block in storage".to_string(),
         ))?;
         tracing::info!(
             "Namada last block height: {}, Tendermint last block height: {}",
-            last_block.height,
+            last_height,
             tendermint_block_height
         );
 
         // If the block height to which tendermint rolled back matches the
         // Namada height, there's no need to rollback
-        if tendermint_block_height == last_block.height {
+        if tendermint_block_height == last_height {
             tracing::info!(
                 "Namada height already matches the rollback Tendermint \
                  height, no need to rollback."
@@ -458,8 +545,7 @@ impl RocksDB {
         }
 
         let mut batch = WriteBatch::default();
-        let previous_height =
-            BlockHeight::from(u64::from(last_block.height) - 1);
+        let previous_height = BlockHeight::from(u64::from(last_height) - 1);
 
         let state_cf = self.get_column_family(STATE_CF)?;
         // Revert the non-height-prepended metadata storage keys which get
@@ -488,8 +574,8 @@ impl RocksDB {
         }
 
         // Revert conversion state if the epoch had been changed
-        if last_block.pred_epochs.get_epoch(previous_height)
-            != Some(last_block.epoch)
+        if self.read_block_epoch(previous_height)?
+            != self.read_block_epoch(last_height)?
         {
             let previous_key = "pred/conversion_state".to_string();
             let previous_value = self
@@ -503,7 +589,7 @@ impl RocksDB {
         // Delete block results for the last block
         let block_cf = self.get_column_family(BLOCK_CF)?;
         tracing::info!("Removing last block results");
-        batch.delete_cf(block_cf, format!("results/{}", last_block.height));
+        batch.delete_cf(block_cf, format!("results/{}", last_height));
 
         // Delete the tx hashes included in the last block
         let reprot_cf = self.get_column_family(REPLAY_PROTECTION_CF)?;
@@ -523,7 +609,7 @@ impl RocksDB {
                 match self.read_subspace_val_with_height(
                     &Key::from(key.to_db_key()),
                     previous_height,
-                    last_block.height,
+                    last_height,
                 )? {
                     Some(previous_value) => batch.lock().unwrap().put_cf(
                         subspace_cf,
@@ -540,7 +626,7 @@ impl RocksDB {
         // Look for diffs in this block to find what has been deleted
         let diff_new_key_prefix = Key {
             segments: vec![
-                last_block.height.to_db_key(),
+                last_height.to_db_key(),
                 "new".to_string().to_db_key(),
             ],
         };
@@ -548,7 +634,7 @@ impl RocksDB {
             let mut batch_guard = batch.lock().unwrap();
             let subspace_cf = self.get_column_family(SUBSPACE_CF)?;
             for (key, val, _) in
-                iter_diffs_prefix(self, last_block.height, None, true)
+                iter_diffs_prefix(self, last_height, None, true)
             {
                 let key = Key::parse(key).unwrap();
                 let diff_new_key = diff_new_key_prefix.join(&key);
@@ -562,7 +648,7 @@ impl RocksDB {
 
         tracing::info!("Deleting keys prepended with the last height");
         let mut batch = batch.into_inner().unwrap();
-        let prefix = last_block.height.to_string();
+        let prefix = last_height.to_string();
         let mut delete_keys = |cf: &ColumnFamily| {
             let read_opts = make_iter_read_opts(Some(prefix.clone()));
             let iter = self.0.iterator_cf_opt(
@@ -587,6 +673,119 @@ impl RocksDB {
         tracing::info!("Flushing restored state to disk");
         self.exec_batch(batch)
     }
+
+    /// Check that the values written for the last committed block in the
+    /// `block`, `subspace` and `diffs` column families match the checksums
+    /// recorded with them. Errors reported by RocksDB when reading these
+    /// values, which is how it reports the corruption of its files, are
+    /// considered as the block's corruption.
+    pub fn check_integrity(&self) -> Result<BlockIntegrity> {
+        let height = match self.read_last_height()? {
+            Some(height) => height,
+            None => return Ok(BlockIntegrity::Empty),
+        };
+        let corrupted =
+            |reason: String| Ok(BlockIntegrity::Corrupted { height, reason });
+        let block_cf = self.get_column_family(BLOCK_CF)?;
+        let checksums_key = Key::from(height.to_db_key())
+            .push(&"checksums".to_owned())
+            .map_err(Error::KeyError)?
+            .to_string();
+        let checksums: BlockChecksums =
+            match self.0.get_cf(block_cf, &checksums_key) {
+                Ok(Some(bytes)) => match types::decode(bytes) {
+                    Ok(checksums) => checksums,
+                    Err(e) => {
+                        return corrupted(format!(
+                            "Cannot decode the block checksums: {e}"
+                        ));
+                    }
+                },
+                Ok(None) => return Ok(BlockIntegrity::Unchecked(height)),
+                Err(e) => {
+                    return corrupted(format!(
+                        "Cannot read the block checksums: {e}"
+                    ));
+                }
+            };
+        for ((cf_name, key), checksum) in checksums {
+            let cf = self.get_column_family(&cf_name)?;
+            match (self.0.get_cf(cf, &key), checksum) {
+                (Ok(Some(bytes)), Some(checksum))
+                    if Hash::sha256(&bytes) == checksum => {}
+                (Ok(None), None) => {}
+                (Ok(Some(_)), Some(_)) => {
+                    return corrupted(format!(
+                        "The value of key {key} in {cf_name} doesn't match \
+                         its checksum"
+                    ));
+                }
+                (Ok(Some(_)), None) => {
+                    return corrupted(format!(
+                        "The deleted key {key} in {cf_name} is present"
+                    ));
+                }
+                (Ok(None), Some(_)) => {
+                    return corrupted(format!(
+                        "The key {key} in {cf_name} is missing"
+                    ));
+                }
+                (Err(e), _) => {
+                    return corrupted(format!(
+                        "Cannot read key {key} in {cf_name}: {e}"
+                    ));
+                }
+            }
+        }
+        Ok(BlockIntegrity::Verified(height))
+    }
+
+    /// Read the height of the last committed block, if any
+    fn read_last_height(&self) -> Result<Option<BlockHeight>> {
+        let state_cf = self.get_column_family(STATE_CF)?;
+        self.0
+            .get_cf(state_cf, "height")
+            .map_err(|e| Error::DBError(e.into_string()))?
+            .map(|bytes| types::decode(bytes).map_err(Error::CodingError))
+            .transpose()
+    }
+
+    /// Read the epoch of the block at the given height, if any
+    fn read_block_epoch(&self, height: BlockHeight) -> Result<Option<Epoch>> {
+        let block_cf = self.get_column_family(BLOCK_CF)?;
+        let key = Key::from(height.to_db_key())
+            .push(&"epoch".to_owned())
+            .map_err(Error::KeyError)?;
+        self.0
+            .get_cf(block_cf, key.to_string())
+            .map_err(|e| Error::DBError(e.into_string()))?
+            .map(|bytes| types::decode(bytes).map_err(Error::CodingError))
+            .transpose()
+    }
+}
+
+/// Add a value of a block to the `block` column family in the batch and
+/// record its checksum
+fn put_block_value(
+    batch: &mut WriteBatch,
+    block_cf: &ColumnFamily,
+    checksums: &mut BlockChecksums,
+    key: String,
+    value: Vec<u8>,
+) {
+    record_checksum(checksums, BLOCK_CF, key.clone(), Some(&value));
+    batch.put_cf(block_cf, key, value);
+}
+
+/// Record the checksum of a value written to the given column family, or of
+/// its deletion
+fn record_checksum(
+    checksums: &mut BlockChecksums,
+    cf_name: &str,
+    key: String,
+    value: Option<&[u8]>,
+) {
+    checksums.insert((cf_name.to_owned(), key), value.map(Hash::sha256));
 }
 
 impl DB for RocksDB {
@@ -803,6 +1002,9 @@ impl DB for RocksDB {
                             types::decode(bytes).map_err(Error::CodingError)?,
                         );
                     }
+                    "checksums" => {
+                        // the checksums are only used by the integrity check
+                    }
                     _ => unknown_key_error(path)?,
                 },
                 None => unknown_key_error(path)?,
@@ -991,6 +1193,8 @@ impl DB for RocksDB {
 
         let block_cf = self.get_column_family(BLOCK_CF)?;
         let prefix_key = Key::from(height.to_db_key());
+        // The checksums of the subspace and diffs values written in the batch
+        let mut checksums = std::mem::take(&mut batch.1);
         // Merkle tree
         {
             for st in StoreType::iter() {
@@ -1002,14 +1206,18 @@ impl DB for RocksDB {
                     };
                     let root_key =
                         key_prefix.clone().with_segment("root".to_owned());
-                    batch.0.put_cf(
+                    put_block_value(
+                        &mut batch.0,
                         block_cf,
+                        &mut checksums,
                         root_key.to_string(),
                         types::encode(merkle_tree_stores.root(st)),
                     );
                     let store_key = key_prefix.with_segment("store".to_owned());
-                    batch.0.put_cf(
+                    put_block_value(
+                        &mut batch.0,
                         block_cf,
+                        &mut checksums,
                         store_key.to_string(),
                         merkle_tree_stores.store(st).encode(),
                     );
@@ -1022,9 +1230,13 @@ impl DB for RocksDB {
                 let key = prefix_key
                     .push(&"header".to_owned())
                     .map_err(Error::KeyError)?;
-                batch
-                    .0
-                    .put_cf(block_cf, key.to_string(), h.serialize_to_vec());
+                put_block_value(
+                    &mut batch.0,
+                    block_cf,
+                    &mut checksums,
+                    key.to_string(),
+                    h.serialize_to_vec(),
+                );
             }
         }
         // Block hash
@@ -1032,42 +1244,60 @@ impl DB for RocksDB {
             let key = prefix_key
                 .push(&"hash".to_owned())
                 .map_err(Error::KeyError)?;
-            batch
-                .0
-                .put_cf(block_cf, key.to_string(), types::encode(&hash));
+            put_block_value(
+                &mut batch.0,
+                block_cf,
+                &mut checksums,
+                key.to_string(),
+                types::encode(&hash),
+            );
         }
         // Block time
         {
             let key = prefix_key
                 .push(&"time".to_owned())
                 .map_err(Error::KeyError)?;
-            batch
-                .0
-                .put_cf(block_cf, key.to_string(), types::encode(&time));
+            put_block_value(
+                &mut batch.0,
+                block_cf,
+                &mut checksums,
+                key.to_string(),
+                types::encode(&time),
+            );
         }
         // Block epoch
         {
             let key = prefix_key
                 .push(&"epoch".to_owned())
                 .map_err(Error::KeyError)?;
-            batch
-                .0
-                .put_cf(block_cf, key.to_string(), types::encode(&epoch));
+            put_block_value(
+                &mut batch.0,
+                block_cf,
+                &mut checksums,
+                key.to_string(),
+                types::encode(&epoch),
+            );
         }
         // Block results
         {
             let results_path = format!("results/{}", height.raw());
-            batch
-                .0
-                .put_cf(block_cf, results_path, types::encode(&results));
+            put_block_value(
+                &mut batch.0,
+                block_cf,
+                &mut checksums,
+                results_path,
+                types::encode(&results),
+            );
         }
         // Predecessor block epochs
         {
             let key = prefix_key
                 .push(&"pred_epochs".to_owned())
                 .map_err(Error::KeyError)?;
-            batch.0.put_cf(
+            put_block_value(
+                &mut batch.0,
                 block_cf,
+                &mut checksums,
                 key.to_string(),
                 types::encode(&pred_epochs),
             );
@@ -1077,12 +1307,25 @@ impl DB for RocksDB {
             let key = prefix_key
                 .push(&"address_gen".to_owned())
                 .map_err(Error::KeyError)?;
-            batch.0.put_cf(
+            put_block_value(
+                &mut batch.0,
                 block_cf,
+                &mut checksums,
                 key.to_string(),
                 types::encode(&address_gen),
             );
         }
+        // Checksums of the block's values for the integrity check on start
+        {
+            let key = prefix_key
+                .push(&"checksums".to_owned())
+                .map_err(Error::KeyError)?;
+            batch.0.put_cf(
+                block_cf,
+                key.to_string(),
+                types::encode(&checksums),
+            );
+        }
 
         // Block height
         batch.0.put_cf(state_cf, "height", types::encode(&height));
@@ -1179,6 +1422,23 @@ impl DB for RocksDB {
         Ok(false)
     }
 
+    fn read_tx_height(
+        &self,
+        hash: &namada::types::hash::Hash,
+    ) -> Result<Option<BlockHeight>> {
+        let block_cf = self.get_column_family(BLOCK_CF)?;
+        match self
+            .0
+            .get_cf(block_cf, format!("tx_heights/{hash}"))
+            .map_err(|e| Error::DBError(e.into_string()))?
+        {
+            Some(bytes) => {
+                Ok(Some(types::decode(bytes).map_err(Error::CodingError)?))
+            }
+            None => Ok(None),
+        }
+    }
+
     fn read_subspace_val(&self, key: &Key) -> Result<Option<Vec<u8>>> {
         let subspace_cf = self.get_column_family(SUBSPACE_CF)?;
         self.0
@@ -1186,6 +1446,21 @@ impl DB for RocksDB {
             .map_err(|e| Error::DBError(e.into_string()))
     }
 
+    fn read_subspace_val_chunk(
+        &self,
+        key: &Key,
+        offset: u64,
+        len: u64,
+    ) -> Result<Option<ValueChunk>> {
+        let subspace_cf = self.get_column_family(SUBSPACE_CF)?;
+        // The pinned slice avoids copying the whole value out of the DB
+        let value = self
+            .0
+            .get_pinned_cf(subspace_cf, key.to_string())
+            .map_err(|e| Error::DBError(e.into_string()))?;
+        Ok(value.map(|value| ValueChunk::new(&value, offset, len)))
+    }
+
     fn read_subspace_val_with_height(
         &self,
         key: &Key,
@@ -1393,6 +1668,12 @@ impl DB for RocksDB {
         };
 
         // Write the new key-val
+        record_checksum(
+            &mut batch.1,
+            SUBSPACE_CF,
+            key.to_string(),
+            Some(value),
+        );
         batch.0.put_cf(subspace_cf, key.to_string(), value);
 
         Ok(size_diff)
@@ -1428,6 +1709,7 @@ impl DB for RocksDB {
         };
 
         // Delete the key-val
+        record_checksum(&mut batch.1, SUBSPACE_CF, key.to_string(), None);
         batch.0.delete_cf(subspace_cf, key.to_string());
 
         Ok(prev_len)
@@ -1496,6 +1778,21 @@ impl DB for RocksDB {
 
         Ok(())
     }
+
+    fn write_tx_height(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        hash: &namada::types::hash::Hash,
+        height: BlockHeight,
+    ) -> Result<()> {
+        let block_cf = self.get_column_family(BLOCK_CF)?;
+        batch.0.put_cf(
+            block_cf,
+            format!("tx_heights/{hash}"),
+            types::encode(&height),
+        );
+        Ok(())
+    }
 }
 
 impl<'iter> DBIter<'iter> for RocksDB {
@@ -2004,6 +2301,11 @@ mod test {
         .unwrap();
         db.exec_batch(batch.0).unwrap();
 
+        assert_eq!(
+            db.check_integrity().unwrap(),
+            BlockIntegrity::Verified(height_1)
+        );
+
         // Check that the values are as expected from second block
         let added = db.read_subspace_val(&add_key).unwrap();
         assert_eq!(added, Some(add_val));
@@ -2022,6 +2324,12 @@ mod test {
         assert_eq!(overwritten, Some(to_overwrite_val));
         let deleted = db.read_subspace_val(&delete_key).unwrap();
         assert_eq!(deleted, Some(to_delete_val));
+        // Check that the restored state matches the checksums of the first
+        // block
+        assert_eq!(
+            db.check_integrity().unwrap(),
+            BlockIntegrity::Verified(height_0)
+        );
         // Check the conversion state
         let state_cf = db.get_column_family(STATE_CF).unwrap();
         let conversion_state =
@@ -2031,6 +2339,77 @@ mod test {
         assert_eq!(conversion_state, types::encode(&conversion_state_0));
     }
 
+    /// Test that the corruption of the last block's values or of the state it
+    /// wrote is detected and that the block can be rolled back to the
+    /// previous intact block.
+    #[test]
+    fn test_check_integrity() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+        assert_eq!(db.check_integrity().unwrap(), BlockIntegrity::Empty);
+
+        let key = Key::parse("key").unwrap();
+        let mut pred_epochs = Epochs::default();
+        let conversion_state = ConversionState::default();
+        for (height, epoch) in [(BlockHeight(100), 1), (BlockHeight(101), 2)] {
+            let mut batch = RocksDB::batch();
+            pred_epochs.new_epoch(height);
+            db.batch_write_subspace_val(
+                &mut batch,
+                height,
+                &key,
+                [epoch as u8],
+            )
+            .unwrap();
+            add_block_to_batch(
+                &db,
+                &mut batch,
+                height,
+                Epoch(epoch),
+                pred_epochs.clone(),
+                &conversion_state,
+            )
+            .unwrap();
+            db.exec_batch(batch.0).unwrap();
+            assert_eq!(
+                db.check_integrity().unwrap(),
+                BlockIntegrity::Verified(height)
+            );
+        }
+
+        // Corrupt the hash of the last block
+        let block_cf = db.get_column_family(BLOCK_CF).unwrap();
+        let hash_key = Key::from(BlockHeight(101).to_db_key())
+            .push(&"hash".to_owned())
+            .unwrap();
+        db.0.put_cf(block_cf, hash_key.to_string(), [0_u8; 3])
+            .unwrap();
+        match db.check_integrity().unwrap() {
+            BlockIntegrity::Corrupted { height, .. } => {
+                assert_eq!(height, BlockHeight(101))
+            }
+            integrity => panic!("Unexpected integrity {integrity:?}"),
+        }
+
+        // The corrupted block can be rolled back
+        db.rollback(BlockHeight(100)).unwrap();
+        assert_eq!(
+            db.check_integrity().unwrap(),
+            BlockIntegrity::Verified(BlockHeight(100))
+        );
+        assert_eq!(db.read_subspace_val(&key).unwrap(), Some(vec![1]));
+
+        // Corrupt the subspace value written by the block
+        let subspace_cf = db.get_column_family(SUBSPACE_CF).unwrap();
+        db.0.put_cf(subspace_cf, key.to_string(), [3_u8]).unwrap();
+        match db.check_integrity().unwrap() {
+            BlockIntegrity::Corrupted { height, .. } => {
+                assert_eq!(height, BlockHeight(100))
+            }
+            integrity => panic!("Unexpected integrity {integrity:?}"),
+        }
+    }
+
     /// A test helper to write a block
     fn add_block_to_batch(
         db: &RocksDB,
@@ -118,7 +118,9 @@ async fn initalize_config(
 
     write_tm_genesis(&home_dir, chain_id, genesis_time).await?;
 
-    update_tendermint_config(&home_dir, config.cometbft).await?;
+    let mut cometbft_config = config.cometbft;
+    config.sentry.apply(&mut cometbft_config);
+    update_tendermint_config(&home_dir, cometbft_config, &config.shell).await?;
     Ok((home_dir_string, tendermint_path))
 }
 
@@ -372,6 +374,7 @@ pub fn id_from_pk(pk: &common::PublicKey) -> TendermintNodeId {
 async fn update_tendermint_config(
     home_dir: impl AsRef<Path>,
     mut config: TendermintConfig,
+    shell_config: &config::Shell,
 ) -> Result<()> {
     let path = configuration(home_dir);
 
@@ -406,8 +409,10 @@ async fn update_tendermint_config(
         // parameter -> 50 * 6 MiB
         config.mempool.max_txs_bytes = 50 * 6 * 1024 * 1024;
 
-        // Hold up to 4k txs in the mempool
-        config.mempool.size = 4000;
+        // Hold up to 4k txs in the mempool, unless configured otherwise
+        config.mempool.size = shell_config
+            .cometbft_mempool_size
+            .unwrap_or(DEFAULT_MEMPOOL_SIZE);
     }
 
     // Bumped from the default `1_000_000`, because some WASMs can be
@@ -420,13 +425,44 @@ async fn update_tendermint_config(
         .open(path)
         .await
         .map_err(Error::OpenWriteConfig)?;
-    let config_str =
-        toml::to_string(&config).map_err(Error::ConfigSerializeToml)?;
+    let mempool_version = shell_config
+        .cometbft_mempool_version
+        .as_deref()
+        .unwrap_or(DEFAULT_MEMPOOL_VERSION);
+    let config_str = config_to_toml(&config, mempool_version)?;
     file.write_all(config_str.as_bytes())
         .await
         .map_err(Error::WriteConfig)
 }
 
+/// The default maximum number of txs in the mempool of CometBFT
+const DEFAULT_MEMPOOL_SIZE: usize = 4000;
+
+/// The default mempool version of CometBFT, which orders txs by the priority
+/// returned from CheckTx and evicts the lowest priority txs when it's full
+const DEFAULT_MEMPOOL_VERSION: &str = "v1";
+
+/// Serialize the CometBFT config with the given mempool version. The mempool
+/// version isn't part of [`TendermintConfig`], so it's set on the serialized
+/// config.
+fn config_to_toml(
+    config: &TendermintConfig,
+    mempool_version: &str,
+) -> Result<String> {
+    let mut config =
+        toml::Value::try_from(config).map_err(Error::ConfigSerializeToml)?;
+    if let Some(mempool) = config
+        .get_mut("mempool")
+        .and_then(toml::Value::as_table_mut)
+    {
+        mempool.insert(
+            "version".to_owned(),
+            toml::Value::String(mempool_version.to_owned()),
+        );
+    }
+    toml::to_string(&config).map_err(Error::ConfigSerializeToml)
+}
+
 async fn write_tm_genesis(
     home_dir: impl AsRef<Path>,
     chain_id: ChainId,
@@ -530,3 +566,24 @@ const STATE_FILE: &str = "private validator state file";
 const STATE_DIR: &str = "private validator state directory";
 
 const GENESIS_FILE: &str = "CometBFT genesis file";
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Test that the written CometBFT config uses the configured mempool
+    /// version
+    #[test]
+    fn test_config_mempool_version() {
+        let config =
+            TendermintConfig::parse_toml(config::DEFAULT_COMETBFT_CONFIG)
+                .expect("Test failed");
+        for version in [DEFAULT_MEMPOOL_VERSION, "v0"] {
+            let config_str =
+                config_to_toml(&config, version).expect("Test failed");
+            let config: toml::Value =
+                toml::from_str(&config_str).expect("Test failed");
+            assert_eq!(config["mempool"]["version"].as_str(), Some(version));
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! The wallet daemon, which serves the keys of an unlocked wallet session to
+//! the tools connected to its Unix socket. The protocol is defined in
+//! [`namada_sdk::wallet::daemon`].
+
+use std::collections::HashSet;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use namada::types::hash::Hash;
+use namada::types::io::Io;
+use namada::types::key::*;
+use namada_sdk::wallet::daemon::{
+    ConfirmPolicy, DaemonKey, DaemonRequest, DaemonResponse,
+};
+use namada_sdk::wallet::Wallet;
+use namada_sdk::{display_line, edisplay_line, prompt};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+
+use super::CliWalletUtils;
+
+/// An unlocked wallet session, shared by all the connections to the daemon
+struct Session<'io, IO> {
+    wallet: Wallet<CliWalletUtils>,
+    confirm: ConfirmPolicy,
+    /// The keys that were confirmed for the rest of the session with
+    /// [`ConfirmPolicy::Once`]
+    confirmed: HashSet<PublicKeyHash>,
+    io: &'io IO,
+}
+
+/// Serve the keys of the wallet on the given Unix socket until the daemon is
+/// interrupted. The encrypted keys are decrypted on the first request to sign
+/// with them and kept decrypted for the rest of the session.
+pub async fn run<IO: Io>(
+    wallet: Wallet<CliWalletUtils>,
+    socket_path: &Path,
+    confirm: ConfirmPolicy,
+    io: &IO,
+) -> std::io::Result<()> {
+    if socket_path.exists() {
+        if UnixStream::connect(socket_path).await.is_ok() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AddrInUse,
+                format!(
+                    "A wallet daemon is already listening on {}",
+                    socket_path.display()
+                ),
+            ));
+        }
+        // Remove the socket of a daemon that didn't shut down cleanly
+        fs::remove_file(socket_path)?;
+    }
+    let listener = UnixListener::bind(socket_path)?;
+    // Only the owner of the wallet may connect to the daemon
+    fs::set_permissions(socket_path, fs::Permissions::from_mode(0o600))?;
+    display_line!(
+        io,
+        "Wallet daemon listening on {} with the confirmation policy \"{}\".",
+        socket_path.display(),
+        confirm
+    );
+
+    let mut session = Session {
+        wallet,
+        confirm,
+        confirmed: HashSet::new(),
+        io,
+    };
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (stream, _) = accepted?;
+                // The connections are served one at a time, as a request may
+                // prompt for a password or a confirmation on the terminal
+                if let Err(err) = session.serve(stream).await {
+                    edisplay_line!(
+                        io,
+                        "Wallet daemon connection failed: {}",
+                        err
+                    );
+                }
+            }
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+    display_line!(io, "Shutting down the wallet daemon.");
+    fs::remove_file(socket_path)
+}
+
+impl<'io, IO: Io> Session<'io, IO> {
+    /// Respond to the requests of a connection until it's closed
+    async fn serve(&mut self, stream: UnixStream) -> std::io::Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+        while let Some(line) = lines.next_line().await? {
+            let response = match serde_json::from_str(&line) {
+                Ok(request) => self.handle(request).await,
+                Err(err) => DaemonResponse::Error {
+                    message: format!("Invalid request: {}", err),
+                },
+            };
+            let mut response = serde_json::to_string(&response)?;
+            response.push('\n');
+            writer.write_all(response.as_bytes()).await?;
+        }
+        Ok(())
+    }
+
+    async fn handle(&mut self, request: DaemonRequest) -> DaemonResponse {
+        match request {
+            DaemonRequest::ListKeys => DaemonResponse::Keys {
+                keys: self.list_keys(),
+            },
+            DaemonRequest::Sign { public_key, digest } => {
+                self.sign(public_key, digest).await
+            }
+        }
+    }
+
+    /// List the keys of the wallet that have a secret key, sorted by alias
+    fn list_keys(&self) -> Vec<DaemonKey> {
+        let mut keys: Vec<DaemonKey> = self
+            .wallet
+            .get_secret_keys()
+            .into_iter()
+            .filter_map(|(alias, (_, pkh))| {
+                let public_key =
+                    self.wallet.find_public_key_by_pkh(pkh?).ok()?;
+                Some(DaemonKey { alias, public_key })
+            })
+            .collect();
+        keys.sort_by(|a, b| a.alias.cmp(&b.alias));
+        keys
+    }
+
+    /// Sign the digest with the given key, if the signing is confirmed
+    /// according to the policy of the session
+    async fn sign(
+        &mut self,
+        public_key: common::PublicKey,
+        digest: Hash,
+    ) -> DaemonResponse {
+        let pkh = PublicKeyHash::from(&public_key);
+        let key_name = match self.wallet.store().find_alias_by_pkh(&pkh) {
+            Some(alias) => format!("\"{}\"", alias),
+            None => pkh.to_string(),
+        };
+        let needs_confirmation = match self.confirm {
+            ConfirmPolicy::Always => true,
+            ConfirmPolicy::Once => !self.confirmed.contains(&pkh),
+            ConfirmPolicy::Never => false,
+        };
+        if needs_confirmation {
+            let confirmation = prompt!(
+                self.io,
+                "Sign the digest {} with the key {}? [y/N]: ",
+                digest,
+                key_name
+            )
+            .await;
+            if !matches!(confirmation.trim(), "y" | "Y" | "yes") {
+                return DaemonResponse::Error {
+                    message: "The signing request was rejected".to_string(),
+                };
+            }
+        }
+        match self.wallet.find_key_by_pkh(&pkh, None) {
+            Ok(secret_key) => {
+                self.confirmed.insert(pkh);
+                DaemonResponse::Signature {
+                    signature: common::SigScheme::sign(&secret_key, digest),
+                }
+            }
+            Err(err) => DaemonResponse::Error {
+                message: format!(
+                    "Unable to load the key {}: {}",
+                    key_name, err
+                ),
+            },
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! Storage of the wallet decryption password in the keychain of the OS (the
+//! macOS Keychain, the Linux Secret Service or the Windows Credential
+//! Manager). Only available with the `keychain` feature.
+
+use zeroize::Zeroizing;
+
+/// The keychain service under which the password is stored
+#[cfg(feature = "keychain")]
+const SERVICE: &str = "namada-wallet";
+/// The keychain user under which the password is stored
+#[cfg(feature = "keychain")]
+const USER: &str = "decryption-password";
+
+/// Errors of the keychain
+#[derive(Debug, thiserror::Error)]
+pub enum KeychainError {
+    #[error("The keychain of the OS failed: {0}")]
+    Keychain(String),
+    #[error(
+        "The keychain is not supported, the client wasn't built with the \
+         `keychain` feature"
+    )]
+    Unsupported,
+}
+
+#[cfg(feature = "keychain")]
+fn entry() -> Result<keyring::Entry, KeychainError> {
+    keyring::Entry::new(SERVICE, USER)
+        .map_err(|err| KeychainError::Keychain(err.to_string()))
+}
+
+/// Save the wallet decryption password in the keychain, replacing any
+/// previously saved password
+#[cfg(feature = "keychain")]
+pub fn save_password(password: &str) -> Result<(), KeychainError> {
+    entry()?
+        .set_password(password)
+        .map_err(|err| KeychainError::Keychain(err.to_string()))
+}
+
+/// Save the wallet decryption password in the keychain, replacing any
+/// previously saved password
+#[cfg(not(feature = "keychain"))]
+pub fn save_password(_password: &str) -> Result<(), KeychainError> {
+    Err(KeychainError::Unsupported)
+}
+
+/// Read the wallet decryption password saved in the keychain, if any
+#[cfg(feature = "keychain")]
+pub fn read_password() -> Result<Option<Zeroizing<String>>, KeychainError> {
+    match entry()?.get_password() {
+        Ok(password) => Ok(Some(Zeroizing::new(password))),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(err) => Err(KeychainError::Keychain(err.to_string())),
+    }
+}
+
+/// Read the wallet decryption password saved in the keychain, if any
+#[cfg(not(feature = "keychain"))]
+pub fn read_password() -> Result<Option<Zeroizing<String>>, KeychainError> {
+    Ok(None)
+}
+
+/// Remove the wallet decryption password from the keychain. Returns `false`
+/// if no password was saved.
+#[cfg(feature = "keychain")]
+pub fn delete_password() -> Result<bool, KeychainError> {
+    match entry()?.delete_password() {
+        Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(err) => Err(KeychainError::Keychain(err.to_string())),
+    }
+}
+
+/// Remove the wallet decryption password from the keychain. Returns `false`
+/// if no password was saved.
+#[cfg(not(feature = "keychain"))]
+pub fn delete_password() -> Result<bool, KeychainError> {
+    Err(KeychainError::Unsupported)
+}
@@ -1,4 +1,7 @@
+#[cfg(unix)]
+pub mod daemon;
 pub mod defaults;
+pub mod keychain;
 pub mod pre_genesis;
 mod store;
 
@@ -61,12 +64,14 @@ impl WalletIo for CliWalletUtils {
                         },
                     )
                 }
-                Err(_) => {
-                    let prompt = "Enter your decryption password: ";
-                    rpassword::read_password_from_tty(Some(prompt))
-                        .map(Zeroizing::new)
-                        .expect("Failed reading password from tty.")
-                }
+                // Fall back on the password saved in the keychain of the OS
+                // before prompting for it
+                Err(_) => keychain::read_password()
+                    .unwrap_or_else(|err| {
+                        eprintln!("{err}");
+                        None
+                    })
+                    .unwrap_or_else(read_decryption_password_tty),
             },
         };
         if confirm && pwd.as_str().is_empty() {
@@ -164,6 +169,14 @@ where
     Ok(response)
 }
 
+/// Prompt for the wallet decryption password on the tty
+pub fn read_decryption_password_tty() -> Zeroizing<String> {
+    let prompt = "Enter your decryption password: ";
+    rpassword::read_password_from_tty(Some(prompt))
+        .map(Zeroizing::new)
+        .expect("Failed reading password from tty.")
+}
+
 pub fn read_and_confirm_passphrase_tty(
     prompt: &str,
 ) -> Result<Zeroizing<String>, std::io::Error> {
@@ -204,6 +217,7 @@ pub fn gen_validator_keys<U: WalletIo>(
         eth_bridge_keypair,
         protocol_keypair,
         protocol_key_scheme,
+        &mut OsRng,
     ))
 }
 
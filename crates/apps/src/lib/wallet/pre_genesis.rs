@@ -8,7 +8,7 @@ use namada_sdk::wallet::pre_genesis::{
     ReadError, ValidatorStore, ValidatorWallet,
 };
 use namada_sdk::wallet::{gen_key_to_store, WalletIo};
-use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 use zeroize::Zeroizing;
 
 use crate::wallet::store::gen_validator_keys;
@@ -22,15 +22,16 @@ pub fn validator_file_name(store_dir: impl AsRef<Path>) -> PathBuf {
     store_dir.as_ref().join(VALIDATOR_FILE_NAME)
 }
 
-/// Generate a new [`ValidatorWallet`] with required pre-genesis keys and
-/// store it as TOML at the given path.
+/// Generate a new [`ValidatorWallet`] with required pre-genesis keys from the
+/// given RNG and store it as TOML at the given path.
 pub fn gen_and_store(
     scheme: SchemeType,
     unsafe_dont_encrypt: bool,
     store_dir: &Path,
+    rng: &mut (impl CryptoRng + RngCore),
 ) -> std::io::Result<ValidatorWallet> {
     let password = read_and_confirm_encryption_password(unsafe_dont_encrypt);
-    let validator = gen(scheme, password);
+    let validator = gen(scheme, password, rng);
     let data = validator.store.encode();
     let wallet_path = validator_file_name(store_dir);
     // Make sure the dir exists
@@ -103,22 +104,23 @@ pub fn load(store_dir: &Path) -> Result<ValidatorWallet, ReadError> {
 fn gen(
     scheme: SchemeType,
     password: Option<Zeroizing<String>>,
+    rng: &mut (impl CryptoRng + RngCore),
 ) -> ValidatorWallet {
     let (consensus_key, consensus_sk) = gen_key_to_store(
         // Note that TM only allows ed25519 for consensus key
         SchemeType::Ed25519,
         password.clone(),
-        &mut OsRng,
+        rng,
     );
     let (eth_cold_key, eth_cold_sk) =
-        gen_key_to_store(SchemeType::Secp256k1, password.clone(), &mut OsRng);
+        gen_key_to_store(SchemeType::Secp256k1, password.clone(), rng);
     let (tendermint_node_key, tendermint_node_sk) = gen_key_to_store(
         // Note that TM only allows ed25519 for node IDs
         SchemeType::Ed25519,
         password,
-        &mut OsRng,
+        rng,
     );
-    let validator_keys = gen_validator_keys(None, None, scheme);
+    let validator_keys = gen_validator_keys(None, None, scheme, rng);
     let eth_hot_key = validator_keys.eth_bridge_keypair.clone();
     let store = ValidatorStore {
         consensus_key,
@@ -4,7 +4,7 @@ use namada::types::key::*;
 use namada_sdk::wallet::{
     gen_secret_key, LoadStoreError, Store, ValidatorKeys,
 };
-use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
 
 use crate::wallet::CliWalletUtils;
 
@@ -40,6 +40,7 @@ pub fn gen_validator_keys(
     eth_bridge_keypair: Option<common::SecretKey>,
     protocol_keypair: Option<common::SecretKey>,
     protocol_keypair_scheme: SchemeType,
+    rng: &mut (impl CryptoRng + RngCore),
 ) -> ValidatorKeys {
     let eth_bridge_keypair = eth_bridge_keypair
         .map(|k| {
@@ -48,9 +49,9 @@ pub fn gen_validator_keys(
             }
             k
         })
-        .unwrap_or_else(|| gen_secret_key(SchemeType::Secp256k1, &mut OsRng));
+        .unwrap_or_else(|| gen_secret_key(SchemeType::Secp256k1, rng));
     let protocol_keypair = protocol_keypair
-        .unwrap_or_else(|| gen_secret_key(protocol_keypair_scheme, &mut OsRng));
+        .unwrap_or_else(|| gen_secret_key(protocol_keypair_scheme, rng));
     ValidatorKeys {
         protocol_keypair,
         eth_bridge_keypair,
@@ -60,6 +61,7 @@ pub fn gen_validator_keys(
 #[cfg(test)]
 mod test_wallet {
     use namada::types::address::Address;
+    use rand::rngs::OsRng;
 
     use super::*;
 
@@ -67,7 +69,7 @@ mod test_wallet {
     fn test_toml_roundtrip_ed25519() {
         let mut store = Store::default();
         let validator_keys =
-            gen_validator_keys(None, None, SchemeType::Ed25519);
+            gen_validator_keys(None, None, SchemeType::Ed25519, &mut OsRng);
         store.add_validator_data(
             Address::decode("tnam1q99c37u38grkdcc2qze0hz4zjjd8zr3yucd3mzgz")
                 .unwrap(),
@@ -81,7 +83,7 @@ mod test_wallet {
     fn test_toml_roundtrip_secp256k1() {
         let mut store = Store::default();
         let validator_keys =
-            gen_validator_keys(None, None, SchemeType::Secp256k1);
+            gen_validator_keys(None, None, SchemeType::Secp256k1, &mut OsRng);
         store.add_validator_data(
             Address::decode("tnam1q99c37u38grkdcc2qze0hz4zjjd8zr3yucd3mzgz")
                 .unwrap(),
@@ -467,6 +467,72 @@ fn vp_multitoken(c: &mut Criterion) {
             })
         });
     }
+
+    // A block full of transfers to distinct targets, validated at once to
+    // measure the scaling with the number of changed balances
+    for num_transfers in [10, 100, 1000] {
+        let mut shell = BenchShell::default();
+        let transfers: Vec<Tx> = (0..num_transfers)
+            .map(|i| {
+                shell.generate_tx(
+                    TX_TRANSFER_WASM,
+                    Transfer {
+                        source: defaults::albert_address(),
+                        target: address::gen_deterministic_established_address(
+                            format!("transfer target {i}"),
+                        ),
+                        token: address::nam(),
+                        amount: Amount::native_whole(1).native_denominated(),
+                        key: None,
+                        shielded: None,
+                    },
+                    None,
+                    None,
+                    vec![&defaults::albert_keypair()],
+                )
+            })
+            .collect();
+        for transfer in &transfers {
+            shell.execute_tx(transfer);
+        }
+        let (verifiers, keys_changed) = shell
+            .wl_storage
+            .write_log
+            .verifiers_and_changed_keys(&BTreeSet::default());
+        let signed_tx = &transfers[0];
+
+        let multitoken = MultitokenVp {
+            ctx: Ctx::new(
+                &Address::Internal(InternalAddress::Multitoken),
+                &shell.wl_storage.storage,
+                &shell.wl_storage.write_log,
+                signed_tx,
+                &TxIndex(0),
+                VpGasMeter::new_from_tx_meter(&TxGasMeter::new_from_sub_limit(
+                    u64::MAX.into(),
+                )),
+                &keys_changed,
+                &verifiers,
+                shell.vp_wasm_cache.clone(),
+            ),
+        };
+
+        group.bench_function(format!("{num_transfers}_transfers"), |b| {
+            b.iter(|| {
+                assert!(
+                    multitoken
+                        .validate_tx(
+                            signed_tx,
+                            multitoken.ctx.keys_changed,
+                            multitoken.ctx.verifiers,
+                        )
+                        .unwrap()
+                )
+            })
+        });
+    }
+
+    group.finish();
 }
 
 // Generate and run masp transaction to be verified
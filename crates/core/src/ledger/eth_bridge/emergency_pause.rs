@@ -0,0 +1,94 @@
+//! Storage keys of the emergency pause of the Ethereum bridge.
+//!
+//! Consensus validators can vote to pause the transfers over the bridge in
+//! both directions. Votes are only counted in the epoch in which they were
+//! cast. Once the validators that voted in the current epoch hold more than
+//! 2/3 of the consensus voting power, the bridge is paused until it is
+//! resumed by a governance proposal.
+
+use super::ADDRESS;
+use crate::types::address::Address;
+use crate::types::storage::{DbKeySeg, Epoch, Key, KeySeg};
+
+/// Storage sub-key space of the emergency pause.
+pub const EMERGENCY_PAUSE_PREFIX_KEY_SEGMENT: &str = "emergency_pause";
+
+/// Key segment under which the pause status is stored.
+const PAUSED_KEY_SEGMENT: &str = "paused";
+
+/// Key segment under which the votes to pause are stored.
+const VOTES_KEY_SEGMENT: &str = "votes";
+
+/// The prefix of all the emergency pause keys.
+fn prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&EMERGENCY_PAUSE_PREFIX_KEY_SEGMENT.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Key under which a `bool` is stored that is `true` when the bridge is
+/// paused.
+pub fn paused_key() -> Key {
+    prefix()
+        .push(&PAUSED_KEY_SEGMENT.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given key is the [`paused_key`].
+pub fn is_paused_key(key: &Key) -> bool {
+    key == &paused_key()
+}
+
+/// The prefix of the votes to pause the bridge cast in the given epoch.
+pub fn votes_prefix(epoch: Epoch) -> Key {
+    prefix()
+        .push(&VOTES_KEY_SEGMENT.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(&epoch)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Key of the vote of a validator to pause the bridge, cast in the given
+/// epoch. A unit value is stored under it.
+pub fn vote_key(epoch: Epoch, validator: &Address) -> Key {
+    votes_prefix(epoch)
+        .push(validator)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given key is a vote to pause the bridge and if so, return
+/// its epoch and the voting validator.
+pub fn is_vote_key(key: &Key) -> Option<(Epoch, &Address)> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(votes), DbKeySeg::StringSeg(epoch), DbKeySeg::AddressSeg(validator)]
+            if addr == &ADDRESS
+                && prefix == EMERGENCY_PAUSE_PREFIX_KEY_SEGMENT
+                && votes == VOTES_KEY_SEGMENT =>
+        {
+            let epoch = Epoch::parse(epoch.clone()).ok()?;
+            Some((epoch, validator))
+        }
+        _ => None,
+    }
+}
+
+/// Check if the given key belongs to the emergency pause.
+pub fn is_emergency_pause_key(key: &Key) -> bool {
+    is_paused_key(key) || is_vote_key(key).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::address::testing::established_address_1;
+
+    #[test]
+    fn test_is_vote_key() {
+        let validator = established_address_1();
+        let key = vote_key(Epoch(3), &validator);
+        assert_eq!(is_vote_key(&key), Some((Epoch(3), &validator)));
+        assert!(is_emergency_pause_key(&key));
+        assert_eq!(is_vote_key(&paused_key()), None);
+        assert!(is_emergency_pause_key(&paused_key()));
+    }
+}
@@ -1,5 +1,8 @@
 //! Ethereum bridge account
 
+pub mod emergency_pause;
+pub mod nuts;
+
 use crate::types::address::{Address, InternalAddress};
 
 /// The [`InternalAddress`] of the Ethereum bridge account
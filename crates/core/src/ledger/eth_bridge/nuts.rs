@@ -0,0 +1,35 @@
+//! Conversion of Non-Usable Tokens (NUTs) into wrapped ERC20s.
+//!
+//! NUTs are minted instead of wrapped ERC20s for the ERC20s transferred to
+//! Namada that aren't whitelisted, or above the token cap of their asset. Once
+//! governance has whitelisted the asset or raised its cap, the holders of NUTs
+//! can convert them into the wrapped ERC20s of the same asset, as long as the
+//! supply of wrapped ERC20s stays within the cap.
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use crate::types::address::Address;
+use crate::types::ethereum_events::EthAddress;
+use crate::types::token::Amount;
+
+/// A tx data type to convert NUTs into the wrapped ERC20s of the same asset
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct ConvertNuts {
+    /// The owner of the NUTs, who receives the wrapped ERC20s
+    pub owner: Address,
+    /// The ERC20 asset of the NUTs
+    pub asset: EthAddress,
+    /// The amount of NUTs to convert
+    pub amount: Amount,
+}
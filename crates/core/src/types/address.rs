@@ -126,6 +126,9 @@ impl From<raw::Address<'_, raw::Validated>> for Address {
                 InternalAddress::IbcToken(IbcTokenHash(*raw_addr.data())),
             ),
             raw::Discriminant::Masp => Address::Internal(InternalAddress::Masp),
+            raw::Discriminant::Scheduler => {
+                Address::Internal(InternalAddress::Scheduler)
+            }
         }
     }
 }
@@ -220,6 +223,11 @@ impl<'addr> From<&'addr Address> for raw::Address<'addr, raw::Validated> {
                     .validate()
                     .expect("This raw address is valid")
             }
+            Address::Internal(InternalAddress::Scheduler) => {
+                raw::Address::from_discriminant(raw::Discriminant::Scheduler)
+                    .validate()
+                    .expect("This raw address is valid")
+            }
         }
     }
 }
@@ -545,6 +553,8 @@ pub enum InternalAddress {
     Pgf,
     /// Masp
     Masp,
+    /// Transactions scheduled for execution at a future epoch
+    Scheduler,
 }
 
 impl Display for InternalAddress {
@@ -566,6 +576,7 @@ impl Display for InternalAddress {
                 Self::Multitoken => "Multitoken".to_string(),
                 Self::Pgf => "PublicGoodFundings".to_string(),
                 Self::Masp => "MASP".to_string(),
+                Self::Scheduler => "Scheduler".to_string(),
             }
         )
     }
@@ -581,6 +592,7 @@ impl InternalAddress {
             "bridgepool" => Some(InternalAddress::EthBridgePool),
             "governance" => Some(InternalAddress::Governance),
             "masp" => Some(InternalAddress::Masp),
+            "scheduler" => Some(InternalAddress::Scheduler),
             _ => None,
         }
     }
@@ -855,6 +867,7 @@ pub mod testing {
             InternalAddress::Nut(_) => {}
             InternalAddress::Pgf => {}
             InternalAddress::Masp => {}
+            InternalAddress::Scheduler => {}
             InternalAddress::Multitoken => {} /* Add new addresses in the
                                                * `prop_oneof` below. */
         };
@@ -872,6 +885,7 @@ pub mod testing {
             Just(InternalAddress::Multitoken),
             Just(InternalAddress::Pgf),
             Just(InternalAddress::Masp),
+            Just(InternalAddress::Scheduler),
         ]
     }
 
@@ -16,7 +16,7 @@ use crate as namada_core; // This is needed for `StorageKeys` macro
 use crate::types::address::Address;
 use crate::types::eth_abi::Encode;
 use crate::types::ethereum_events::{
-    EthAddress, TransferToEthereum as TransferToEthereumEvent,
+    EthAddress, TransferToEthereum as TransferToEthereumEvent, Uint,
 };
 use crate::types::hash::Hash as HashDigest;
 use crate::types::storage::{DbKeySeg, Key};
@@ -33,6 +33,8 @@ pub struct Segments {
     pub signed_root: &'static str,
     /// Bridge pool nonce storage key
     pub bridge_pool_nonce: &'static str,
+    /// Transfer status storage key prefix
+    pub transfer_status: &'static str,
 }
 
 /// Check if a key is for a pending transfer
@@ -63,6 +65,90 @@ pub fn get_key_from_hash(hash: &KeccakHash) -> Key {
     }
 }
 
+/// Get the storage key for the status of a transfer to Ethereum, using
+/// the hash of the transfer
+pub fn get_transfer_status_key(hash: &KeccakHash) -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_POOL_ADDRESS),
+            DbKeySeg::StringSeg(Segments::VALUES.transfer_status.into()),
+            hash.to_db_key(),
+        ],
+    }
+}
+
+/// Check if a key is for the status of a transfer to Ethereum. Returns the
+/// hash of the transfer, if so.
+pub fn is_transfer_status_key(key: &storage::Key) -> Option<KeccakHash> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(hash)]
+            if addr == &BRIDGE_POOL_ADDRESS
+                && prefix == Segments::VALUES.transfer_status =>
+        {
+            KeccakHash::try_from(hash.as_str()).ok()
+        }
+        _ => None,
+    }
+}
+
+/// The status of a transfer to Ethereum, tracked through its lifecycle
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub enum TransferStatus {
+    /// The transfer is in the Bridge pool, waiting to be covered by a
+    /// signed Bridge pool root
+    Pending,
+    /// The transfer is covered by a Bridge pool root signed by a quorum
+    /// of validators, and can be relayed to Ethereum
+    Signed {
+        /// The nonce of the signed Bridge pool root
+        nonce: Uint,
+    },
+    /// The transfer has been relayed to Ethereum, and is waiting for
+    /// enough validators to observe its execution
+    Relayed {
+        /// The nonce of the relayed batch of transfers
+        nonce: Uint,
+    },
+    /// The execution of the transfer on Ethereum has been confirmed
+    /// by the Ethereum oracle of a quorum of validators
+    Confirmed {
+        /// The nonce of the confirmed batch of transfers
+        nonce: Uint,
+    },
+    /// The transfer timed out in the Bridge pool, and its escrowed
+    /// assets were refunded
+    Expired,
+}
+
+impl std::fmt::Display for TransferStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Pending => write!(f, "pending in the Bridge pool"),
+            Self::Signed { nonce } => {
+                write!(f, "signed in the batch with nonce {nonce}")
+            }
+            Self::Relayed { nonce } => {
+                write!(f, "relayed in the batch with nonce {nonce}")
+            }
+            Self::Confirmed { nonce } => {
+                write!(f, "confirmed on Ethereum with nonce {nonce}")
+            }
+            Self::Expired => write!(f, "expired and refunded"),
+        }
+    }
+}
+
 /// A version used in our Ethereuem smart contracts
 const VERSION: u8 = 1;
 
@@ -304,6 +304,16 @@ pub enum EthereumEvent {
         #[allow(dead_code)]
         governance_validator_hash: KeccakHash,
     },
+    /// The receipts root of an Ethereum block in which bridge events were
+    /// emitted, which commits to the logs of these events
+    ReceiptsRoot {
+        /// The height of the Ethereum block
+        block_height: Uint,
+        /// The root of the trie of the receipts of the block's transactions
+        receipts_root: KeccakHash,
+        /// The sorted hashes of the bridge events emitted in the block
+        events: Vec<Hash>,
+    },
 }
 
 impl EthereumEvent {
@@ -177,8 +177,9 @@ impl From<&H256> for Hash {
     }
 }
 
-/// The storage hasher used for the merkle tree.
-pub trait StorageHasher: Hasher + fmt::Debug + Default {
+/// The storage hasher used for the merkle tree. It's `Send` and `Sync` so
+/// that the sub-trees can be hashed in parallel.
+pub trait StorageHasher: Hasher + fmt::Debug + Default + Send + Sync {
     /// Hash the value to store
     fn hash(value: impl AsRef<[u8]>) -> H256;
 }
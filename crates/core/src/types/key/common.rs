@@ -287,6 +287,16 @@ impl FromStr for SecretKey {
     }
 }
 
+impl SecretKey {
+    /// Parse an Ethereum-style secp256k1 secret key, as exported by Ethereum
+    /// wallets. That is the hex encoding of the 32 raw bytes of the key, with
+    /// an optional "0x" prefix.
+    pub fn from_eth_str(str: &str) -> Result<Self, ParseSecretKeyError> {
+        let hex = str.strip_prefix("0x").unwrap_or(str).to_lowercase();
+        secp256k1::SecretKey::from_str(&hex).map(Self::Secp256k1)
+    }
+}
+
 /// Signature
 #[derive(
     Clone,
@@ -470,4 +480,30 @@ mod tests {
         println!("Public key: {}", public_key);
         println!("Secret key: {}", secret_key);
     }
+
+    /// Test that an Ethereum-style secret key is parsed into a secp256k1 key
+    /// controlling the same Ethereum address.
+    #[test]
+    fn test_secret_key_from_eth_str() {
+        const ETH_SK: &str =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+        let eth_address =
+            EthAddress::from_str("0x2c7536E3605D9C16a7a3D7b1898e529396a65c23")
+                .unwrap();
+
+        for sk in [
+            ETH_SK.to_string(),
+            format!("0x{ETH_SK}"),
+            format!("0x{}", ETH_SK.to_uppercase()),
+        ] {
+            let secret_key = SecretKey::from_eth_str(&sk).unwrap();
+            assert!(matches!(secret_key, SecretKey::Secp256k1(_)));
+            let public_key = secret_key.to_public();
+            assert_eq!(EthAddress::try_from(&public_key).unwrap(), eth_address);
+        }
+
+        // The key must have exactly 32 bytes
+        assert!(SecretKey::from_eth_str(&ETH_SK[2..]).is_err());
+        assert!(SecretKey::from_eth_str(&format!("{ETH_SK}00")).is_err());
+    }
 }
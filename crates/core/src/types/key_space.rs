@@ -0,0 +1,394 @@
+//! A registry of the storage key prefixes reserved by the protocol modules.
+//!
+//! Every module declares the prefixes it builds its storage keys under in a
+//! [`ModuleKeySpace`]. A [`KeySpace`] can only be built from modules whose
+//! prefixes don't overlap, so that no key can be written by two modules.
+//!
+//! The modules with a native VP also declare which of their prefixes the VP
+//! guards, so that the VP is always triggered by a tx that changes a key under
+//! any of them.
+
+use std::fmt::{self, Display};
+
+use thiserror::Error;
+
+use super::address::Address;
+use super::hash::HASH_LENGTH;
+use super::storage::{
+    DbKeySeg, Key, KeySeg, KEY_SEGMENT_SEPARATOR, RESERVED_VP_KEY,
+    WASM_KEY_PREFIX,
+};
+
+/// The length of a hex-encoded hash
+const HASH_HEX_LEN: usize = 2 * HASH_LENGTH;
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(
+        "The key prefix {prefix} of the module {module} overlaps with the key \
+         prefix {other_prefix} of the module {other_module}"
+    )]
+    Overlap {
+        module: &'static str,
+        prefix: String,
+        other_module: &'static str,
+        other_prefix: String,
+    },
+}
+
+/// A segment of a reserved key prefix
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PrefixSeg {
+    /// The given address
+    Address(Address),
+    /// Any address, e.g. the owner of an account
+    AnyAddress,
+    /// The given string
+    Str(&'static str),
+    /// Any string starting with the given prefix
+    StrPrefix(&'static str),
+    /// Any hex-encoded hash
+    Hash,
+    /// Any segment, e.g. a hash or an identifier
+    Any,
+}
+
+impl PrefixSeg {
+    /// Check if some key segment would be matched by both segments
+    fn overlaps(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Any, _) | (_, Self::Any) => true,
+            (Self::AnyAddress, Self::Address(_) | Self::AnyAddress)
+            | (Self::Address(_), Self::AnyAddress) => true,
+            (Self::Address(a), Self::Address(b)) => a == b,
+            (Self::Str(a), Self::Str(b)) => a == b,
+            (Self::Str(s), Self::StrPrefix(p))
+            | (Self::StrPrefix(p), Self::Str(s)) => s.starts_with(p),
+            (Self::StrPrefix(a), Self::StrPrefix(b)) => {
+                a.starts_with(b) || b.starts_with(a)
+            }
+            (Self::Hash, Self::Hash) => true,
+            (Self::Hash, Self::Str(s)) | (Self::Str(s), Self::Hash) => {
+                is_hash(s)
+            }
+            (Self::Hash, Self::StrPrefix(p))
+            | (Self::StrPrefix(p), Self::Hash) => {
+                p.len() <= HASH_HEX_LEN
+                    && p.chars().all(|c| c.is_ascii_hexdigit())
+            }
+            _ => false,
+        }
+    }
+
+    /// Check if the key segment is matched by this segment
+    fn matches(&self, seg: &DbKeySeg) -> bool {
+        match (self, seg) {
+            (Self::Any, _) | (Self::AnyAddress, DbKeySeg::AddressSeg(_)) => {
+                true
+            }
+            (Self::Address(a), DbKeySeg::AddressSeg(b)) => a == b,
+            (Self::Str(a), DbKeySeg::StringSeg(b)) => a == b,
+            (Self::StrPrefix(p), DbKeySeg::StringSeg(s)) => s.starts_with(p),
+            (Self::Hash, DbKeySeg::StringSeg(s)) => is_hash(s),
+            _ => false,
+        }
+    }
+}
+
+impl Display for PrefixSeg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Address(addr) => write!(f, "{}", addr.raw()),
+            Self::AnyAddress => write!(f, "<address>"),
+            Self::Str(s) => write!(f, "{s}"),
+            Self::StrPrefix(p) => write!(f, "{p}*"),
+            Self::Hash => write!(f, "<hash>"),
+            Self::Any => write!(f, "*"),
+        }
+    }
+}
+
+/// Check if the string is a hex-encoded hash
+fn is_hash(s: &str) -> bool {
+    s.len() == HASH_HEX_LEN && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// A key prefix reserved by a module
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReservedPrefix {
+    /// The segments of the prefix
+    pub segments: Vec<PrefixSeg>,
+    /// What the module stores under the prefix
+    pub description: &'static str,
+    /// The address of the native VP guarding the prefix, if any
+    pub vp: Option<Address>,
+}
+
+impl ReservedPrefix {
+    /// Check if some key could be under both prefixes
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.segments
+            .iter()
+            .zip(&other.segments)
+            .all(|(a, b)| a.overlaps(b))
+    }
+
+    /// Check if the key is under this prefix
+    pub fn matches(&self, key: &Key) -> bool {
+        key.segments.len() >= self.segments.len()
+            && self
+                .segments
+                .iter()
+                .zip(&key.segments)
+                .all(|(prefix_seg, seg)| prefix_seg.matches(seg))
+    }
+
+    /// Describe what's stored under the prefix and the VP guarding it
+    fn describe(&self) -> String {
+        match &self.vp {
+            Some(vp) => format!("{}, guarded by {vp}", self.description),
+            None => self.description.to_owned(),
+        }
+    }
+}
+
+impl Display for ReservedPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let segments: Vec<String> =
+            self.segments.iter().map(ToString::to_string).collect();
+        write!(f, "{}", segments.join(&KEY_SEGMENT_SEPARATOR.to_string()))
+    }
+}
+
+/// The key prefixes reserved by a module
+#[derive(Clone, Debug)]
+pub struct ModuleKeySpace {
+    /// The name of the module
+    pub module: &'static str,
+    /// The reserved prefixes
+    pub prefixes: Vec<ReservedPrefix>,
+    /// The native VP guarding the prefixes reserved next, if any
+    vp: Option<Address>,
+}
+
+impl ModuleKeySpace {
+    /// A module without reserved prefixes
+    pub fn new(module: &'static str) -> Self {
+        Self {
+            module,
+            prefixes: vec![],
+            vp: None,
+        }
+    }
+
+    /// Guard the prefixes reserved after this call with the native VP of the
+    /// given address
+    pub fn guarded_by(mut self, vp: Address) -> Self {
+        self.vp = Some(vp);
+        self
+    }
+
+    /// Reserve a key prefix
+    pub fn reserve(
+        mut self,
+        segments: impl IntoIterator<Item = PrefixSeg>,
+        description: &'static str,
+    ) -> Self {
+        self.prefixes.push(ReservedPrefix {
+            segments: segments.into_iter().collect(),
+            description,
+            vp: self.vp.clone(),
+        });
+        self
+    }
+
+    /// Reserve the given sub-keys of an address
+    pub fn reserve_sub_keys(
+        self,
+        address: &Address,
+        sub_keys: &[&'static str],
+        description: &'static str,
+    ) -> Self {
+        sub_keys.iter().fold(self, |module, sub_key| {
+            module.reserve(
+                [PrefixSeg::Address(address.clone()), PrefixSeg::Str(sub_key)],
+                description,
+            )
+        })
+    }
+}
+
+/// The key prefixes of all the modules, which don't overlap
+#[derive(Clone, Debug)]
+pub struct KeySpace {
+    modules: Vec<ModuleKeySpace>,
+}
+
+impl KeySpace {
+    /// Build the key space from the modules, checking that the prefixes of
+    /// different modules don't overlap
+    pub fn new(modules: Vec<ModuleKeySpace>) -> Result<Self, Error> {
+        for (ix, module) in modules.iter().enumerate() {
+            for other_module in &modules[ix + 1..] {
+                for prefix in &module.prefixes {
+                    if let Some(other_prefix) = other_module
+                        .prefixes
+                        .iter()
+                        .find(|other_prefix| prefix.overlaps(other_prefix))
+                    {
+                        return Err(Error::Overlap {
+                            module: module.module,
+                            prefix: prefix.to_string(),
+                            other_module: other_module.module,
+                            other_prefix: other_prefix.to_string(),
+                        });
+                    }
+                }
+            }
+        }
+        Ok(Self { modules })
+    }
+
+    /// The modules of the key space
+    pub fn modules(&self) -> &[ModuleKeySpace] {
+        &self.modules
+    }
+
+    /// Find the module that reserved a prefix of the key, if any
+    pub fn module_of(&self, key: &Key) -> Option<&'static str> {
+        self.modules
+            .iter()
+            .find(|module| {
+                module.prefixes.iter().any(|prefix| prefix.matches(key))
+            })
+            .map(|module| module.module)
+    }
+
+    /// Find the native VP guarding a prefix of the key, if any
+    pub fn vp_of(&self, key: &Key) -> Option<&Address> {
+        self.modules
+            .iter()
+            .flat_map(|module| &module.prefixes)
+            .find(|prefix| prefix.matches(key))
+            .and_then(|prefix| prefix.vp.as_ref())
+    }
+}
+
+impl Display for KeySpace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for module in &self.modules {
+            writeln!(f, "{}:", module.module)?;
+            for prefix in &module.prefixes {
+                writeln!(f, "  {prefix}  ({})", prefix.describe())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The key prefixes reserved by the storage itself
+pub fn storage_key_space() -> ModuleKeySpace {
+    ModuleKeySpace::new("storage")
+        .reserve(
+            [PrefixSeg::AnyAddress, PrefixSeg::Str(RESERVED_VP_KEY)],
+            "Validity predicates of accounts",
+        )
+        .reserve([PrefixSeg::Str(WASM_KEY_PREFIX)], "Wasm codes")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::address::{self, InternalAddress};
+    use crate::types::hash::Hash;
+
+    fn module_a() -> ModuleKeySpace {
+        ModuleKeySpace::new("a")
+            .reserve_sub_keys(
+                &Address::Internal(InternalAddress::PoS),
+                &["params", "bond"],
+                "Things",
+            )
+            .reserve(
+                [PrefixSeg::AnyAddress, PrefixSeg::Str("balance")],
+                "Balances",
+            )
+    }
+
+    #[test]
+    fn test_key_space_without_overlaps() {
+        let module_b = ModuleKeySpace::new("b")
+            .reserve(
+                [
+                    PrefixSeg::Address(Address::Internal(InternalAddress::PoS)),
+                    PrefixSeg::StrPrefix("pin-"),
+                ],
+                "Pins",
+            )
+            .reserve([PrefixSeg::AnyAddress, PrefixSeg::Hash], "Hashes")
+            .guarded_by(Address::Internal(InternalAddress::Parameters))
+            .reserve([PrefixSeg::Str("wasm"), PrefixSeg::Any], "Wasm");
+        let key_space = KeySpace::new(vec![module_a(), module_b]).unwrap();
+
+        let bond_key =
+            Key::from(Address::Internal(InternalAddress::PoS).to_db_key())
+                .push(&"bond".to_owned())
+                .unwrap()
+                .push(&address::testing::established_address_1())
+                .unwrap();
+        assert_eq!(key_space.module_of(&bond_key), Some("a"));
+        let pin_key =
+            Key::from(Address::Internal(InternalAddress::PoS).to_db_key())
+                .push(&"pin-abc".to_owned())
+                .unwrap();
+        assert_eq!(key_space.module_of(&pin_key), Some("b"));
+        let hash_key =
+            Key::from(address::testing::established_address_1().to_db_key())
+                .push(&Hash::sha256(b"hash"))
+                .unwrap();
+        assert_eq!(key_space.module_of(&hash_key), Some("b"));
+        let wasm_key = Key::parse("wasm").unwrap();
+        assert_eq!(key_space.module_of(&wasm_key), None);
+
+        // Only the prefixes reserved after `guarded_by` are guarded
+        assert_eq!(key_space.vp_of(&pin_key), None);
+        assert_eq!(key_space.vp_of(&hash_key), None);
+        let wasm_code_key = Key::parse("wasm/code").unwrap();
+        assert_eq!(
+            key_space.vp_of(&wasm_code_key),
+            Some(&Address::Internal(InternalAddress::Parameters))
+        );
+
+        let dump = key_space.to_string();
+        assert!(dump.contains("a:\n"));
+        assert!(dump.contains("<address>/balance  (Balances)"));
+        assert!(dump.contains("wasm/*  (Wasm, guarded by "));
+    }
+
+    #[test]
+    fn test_key_space_with_overlaps() {
+        // Any address includes the PoS address
+        let module_b = ModuleKeySpace::new("b").reserve(
+            [PrefixSeg::AnyAddress, PrefixSeg::StrPrefix("para")],
+            "Parameters",
+        );
+        let err = KeySpace::new(vec![module_a(), module_b]).unwrap_err();
+        let Error::Overlap {
+            module,
+            other_module,
+            other_prefix,
+            ..
+        } = err;
+        assert_eq!(module, "a");
+        assert_eq!(other_module, "b");
+        assert_eq!(other_prefix, "<address>/para*");
+
+        // A shorter prefix overlaps with all its extensions
+        let module_b = ModuleKeySpace::new("b").reserve(
+            [PrefixSeg::Address(Address::Internal(InternalAddress::PoS))],
+            "Everything",
+        );
+        assert!(KeySpace::new(vec![module_a(), module_b]).is_err());
+    }
+}
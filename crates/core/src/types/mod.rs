@@ -13,6 +13,7 @@ pub mod ibc;
 pub mod internal;
 pub mod keccak;
 pub mod key;
+pub mod key_space;
 pub mod masp;
 pub mod parameters;
 pub mod sign;
@@ -26,6 +26,12 @@ use crate::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 pub struct Parameters {
     /// Max payload size, in bytes, for a mempool tx.
     pub max_tx_bytes: u32,
+    /// Max size, in bytes, of the memo attached to a tx.
+    pub max_tx_memo_bytes: u32,
+    /// Max number of sections in a tx.
+    pub max_tx_sections: u32,
+    /// Max size, in bytes, of a WASM code embedded in a tx.
+    pub max_tx_code_bytes: u32,
     /// Epoch duration (read only)
     pub epoch_duration: EpochDuration,
     /// Maximum expected time per block (read only)
@@ -34,6 +40,8 @@ pub struct Parameters {
     pub max_proposal_bytes: ProposalBytes,
     /// Max gas for block
     pub max_block_gas: u64,
+    /// Max total gas limit of the txs scheduled to run at an epoch
+    pub max_scheduled_txs_gas: u64,
     /// Whitelisted validity predicate hashes (read only)
     pub vp_whitelist: Vec<String>,
     /// Whitelisted tx hashes (read only)
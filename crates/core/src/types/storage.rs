@@ -1917,6 +1917,13 @@ pub mod testing {
         }
     }
 
+    prop_compose! {
+        /// Generate an arbitrary block height
+        pub fn arb_block_height()(height: u64) -> BlockHeight {
+            BlockHeight(height)
+        }
+    }
+
     /// Generate an arbitrary [`Key`].
     pub fn arb_key() -> impl Strategy<Value = Key> {
         prop_oneof![
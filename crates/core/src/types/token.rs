@@ -1048,6 +1048,133 @@ pub struct Transfer {
     pub shielded: Option<Hash>,
 }
 
+/// A schedule of the release of a vesting account's locked balance. The whole
+/// `amount` is locked until the `start` epoch, after which it's released
+/// linearly until it's fully vested at the `end` epoch. When `start` and
+/// `end` are equal, the whole amount is released at once (a cliff).
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct VestingSchedule {
+    /// The amount of tokens locked by the schedule
+    pub amount: Amount,
+    /// The epoch at which the release of the tokens starts
+    pub start: Epoch,
+    /// The epoch at which the tokens are fully vested
+    pub end: Epoch,
+}
+
+impl VestingSchedule {
+    /// Get the amount of tokens that are still locked in the given epoch.
+    /// The released amount is rounded down, so that the locked amount
+    /// never drops faster than the schedule.
+    pub fn locked_amount(&self, epoch: Epoch) -> Amount {
+        if epoch < self.start {
+            self.amount
+        } else if epoch >= self.end {
+            Amount::zero()
+        } else {
+            let remaining = self.end.0 - epoch.0;
+            let duration = self.end.0 - self.start.0;
+            // amount * remaining / duration, rounded up without overflowing
+            let (locked, rem) = self.amount * (remaining, duration);
+            let rem = (rem.raw * remaining + Uint::from(duration - 1))
+                / Uint::from(duration);
+            locked + Amount { raw: rem }
+        }
+    }
+
+    /// Get the amount of tokens that have been released in the given epoch
+    pub fn vested_amount(&self, epoch: Epoch) -> Amount {
+        self.amount - self.locked_amount(epoch)
+    }
+}
+
+/// A token transfer whose amount is locked in the target's balance with a
+/// vesting schedule
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct VestingTransfer {
+    /// Source address will spend the tokens
+    pub source: Address,
+    /// Target address will receive the locked tokens
+    pub target: Address,
+    /// Token's address
+    pub token: Address,
+    /// The amount of tokens
+    pub amount: DenominatedAmount,
+    /// The epoch at which the release of the tokens starts
+    pub start: Epoch,
+    /// The epoch at which the tokens are fully vested
+    pub end: Epoch,
+}
+
+/// The cumulative flows of a token in an epoch, accounted by the protocol
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct TokenFlows {
+    /// The fees paid by the fee payers of wrapper txs
+    pub fees_collected: Amount,
+    /// The fees credited to the block proposers
+    pub fees_distributed: Amount,
+    /// The tokens burned by the protocol, e.g. the deposits of rejected
+    /// governance proposals
+    pub burned: Amount,
+    /// The tokens received by the PGF treasury
+    pub treasury_inflow: Amount,
+    /// The tokens paid out of the PGF treasury
+    pub treasury_outflow: Amount,
+}
+
+impl TokenFlows {
+    /// Add up the flows, returning `None` on overflow
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(Self {
+            fees_collected: self
+                .fees_collected
+                .checked_add(other.fees_collected)?,
+            fees_distributed: self
+                .fees_distributed
+                .checked_add(other.fees_distributed)?,
+            burned: self.burned.checked_add(other.burned)?,
+            treasury_inflow: self
+                .treasury_inflow
+                .checked_add(other.treasury_inflow)?,
+            treasury_outflow: self
+                .treasury_outflow
+                .checked_add(other.treasury_outflow)?,
+        })
+    }
+}
+
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
 pub enum TransferError {
@@ -1314,6 +1441,29 @@ mod tests {
         assert_eq!(three.mul_ceil(dec), two);
     }
 
+    #[test]
+    fn test_vesting_schedule() {
+        let schedule = VestingSchedule {
+            amount: Amount::from(100),
+            start: Epoch(10),
+            end: Epoch(13),
+        };
+        assert_eq!(schedule.locked_amount(Epoch(0)), Amount::from(100));
+        assert_eq!(schedule.locked_amount(Epoch(10)), Amount::from(100));
+        assert_eq!(schedule.locked_amount(Epoch(11)), Amount::from(67));
+        assert_eq!(schedule.locked_amount(Epoch(12)), Amount::from(34));
+        assert_eq!(schedule.locked_amount(Epoch(13)), Amount::zero());
+        assert_eq!(schedule.vested_amount(Epoch(12)), Amount::from(66));
+
+        // A cliff releases everything at once
+        let cliff = VestingSchedule {
+            end: Epoch(10),
+            ..schedule
+        };
+        assert_eq!(cliff.locked_amount(Epoch(9)), Amount::from(100));
+        assert_eq!(cliff.locked_amount(Epoch(10)), Amount::zero());
+    }
+
     #[test]
     fn test_denominateed_arithmetic() {
         let a = DenominatedAmount::new(10.into(), 3.into());
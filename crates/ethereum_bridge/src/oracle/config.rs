@@ -4,6 +4,8 @@ use std::num::NonZeroU64;
 use namada_core::types::ethereum_events::EthAddress;
 use namada_core::types::ethereum_structs;
 
+use crate::storage::parameters::{ContractVersion, UpgradeableContract};
+
 /// Configuration for an oracle.
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Config {
@@ -12,12 +14,32 @@ pub struct Config {
     pub min_confirmations: NonZeroU64,
     /// The Ethereum address of the current bridge contract.
     pub bridge_contract: EthAddress,
+    /// The version of the current bridge contract, which selects the ABI
+    /// its events are decoded with.
+    pub bridge_contract_version: ContractVersion,
+    /// The next bridge contract, during the migration window of a bridge
+    /// contract upgrade. Its events are decoded with the ABI of its own
+    /// version.
+    pub next_bridge_contract: Option<UpgradeableContract>,
     /// The earliest Ethereum block from which events may be processed.
     pub start_block: ethereum_structs::BlockHeight,
     /// The status of the Ethereum bridge (active / inactive)
     pub active: bool,
 }
 
+impl Config {
+    /// The bridge contracts whose events are relayed, with their versions
+    pub fn bridge_contracts(
+        &self,
+    ) -> impl Iterator<Item = UpgradeableContract> + '_ {
+        std::iter::once(UpgradeableContract {
+            address: self.bridge_contract,
+            version: self.bridge_contract_version,
+        })
+        .chain(self.next_bridge_contract)
+    }
+}
+
 // TODO: this production Default implementation is temporary, there should be no
 //  default config - initialization should always be from storage.
 impl std::default::Default for Config {
@@ -27,6 +49,8 @@ impl std::default::Default for Config {
             // value that is >= 1
             min_confirmations: unsafe { NonZeroU64::new_unchecked(100) },
             bridge_contract: EthAddress([0; 20]),
+            bridge_contract_version: ContractVersion::default(),
+            next_bridge_contract: None,
             start_block: 0.into(),
             active: true,
         }
@@ -0,0 +1,136 @@
+//! Tallying of the validators' emergency votes to pause the Ethereum bridge.
+
+use std::collections::BTreeSet;
+
+use eyre::Result;
+use namada_core::types::address::Address;
+use namada_core::types::token;
+use namada_core::types::voting_power::FractionalVotingPower;
+use namada_proof_of_stake::pos_queries::PosQueries;
+use namada_state::{DBIter, StorageHasher, WlStorage, DB};
+use namada_storage::{StorageRead, StorageWrite};
+
+use crate::protocol::transactions::ChangedKeys;
+use crate::storage::emergency_pause;
+use crate::storage::eth_bridge_queries::EthBridgeQueries;
+
+/// Tally the votes to pause the bridge cast in the current epoch. If the
+/// voters hold more than 2/3 of the consensus voting power, the bridge is
+/// paused and the votes are cleared, such that resuming the bridge requires
+/// new votes to pause it again. Returns the keys changed in storage.
+pub fn tally_votes<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+) -> Result<ChangedKeys>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    if wl_storage.ethbridge_queries().is_bridge_paused() {
+        return Ok(ChangedKeys::default());
+    }
+    let epoch = wl_storage.storage.get_current_epoch().0;
+    let votes_prefix = emergency_pause::votes_prefix(epoch);
+    let voters: BTreeSet<Address> =
+        namada_storage::iter_prefix_bytes(wl_storage, &votes_prefix)?
+            .filter_map(|entry| {
+                entry
+                    .map(|(key, _)| {
+                        emergency_pause::is_vote_key(&key)
+                            .map(|(_, validator)| validator.clone())
+                    })
+                    .transpose()
+            })
+            .collect::<namada_storage::Result<_>>()?;
+    if voters.is_empty() {
+        return Ok(ChangedKeys::default());
+    }
+
+    let voted_stake: token::Amount = wl_storage
+        .pos_queries()
+        .get_consensus_validators(Some(epoch))
+        .iter()
+        .filter(|validator| voters.contains(&validator.address))
+        .map(|validator| validator.bonded_stake)
+        .sum();
+    let total_stake =
+        wl_storage.pos_queries().get_total_voting_power(Some(epoch));
+    if voted_stake <= FractionalVotingPower::TWO_THIRDS * total_stake {
+        return Ok(ChangedKeys::default());
+    }
+
+    tracing::warn!(
+        %epoch,
+        voters = voters.len(),
+        "The validators voted to pause the Ethereum bridge"
+    );
+    let paused_key = emergency_pause::paused_key();
+    wl_storage.write(&paused_key, true)?;
+    let mut changed_keys = ChangedKeys::from([paused_key]);
+    for voter in voters {
+        let vote_key = emergency_pause::vote_key(epoch, &voter);
+        wl_storage.delete(&vote_key)?;
+        changed_keys.insert(vote_key);
+    }
+    Ok(changed_keys)
+}
+
+#[cfg(test)]
+mod test_emergency_pause {
+    use std::collections::HashMap;
+
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+    };
+
+    use super::*;
+    use crate::test_utils;
+
+    /// Test that the bridge is only paused once the voters hold more than 2/3
+    /// of the voting power.
+    #[test]
+    fn test_tally_votes() {
+        let (mut wl_storage, _) =
+            test_utils::setup_storage_with_validators(HashMap::from_iter([
+                (established_address_1(), token::Amount::native_whole(100)),
+                (established_address_2(), token::Amount::native_whole(100)),
+                (established_address_3(), token::Amount::native_whole(100)),
+            ]));
+        let epoch = wl_storage.storage.get_current_epoch().0;
+
+        assert!(tally_votes(&mut wl_storage).unwrap().is_empty());
+
+        for voter in [established_address_1(), established_address_2()] {
+            wl_storage
+                .write(&emergency_pause::vote_key(epoch, &voter), ())
+                .unwrap();
+        }
+        assert!(tally_votes(&mut wl_storage).unwrap().is_empty());
+        assert!(!wl_storage.ethbridge_queries().is_bridge_paused());
+
+        // A vote cast in another epoch is not counted
+        wl_storage
+            .write(
+                &emergency_pause::vote_key(
+                    epoch.next(),
+                    &established_address_3(),
+                ),
+                (),
+            )
+            .unwrap();
+        assert!(tally_votes(&mut wl_storage).unwrap().is_empty());
+
+        wl_storage
+            .write(
+                &emergency_pause::vote_key(epoch, &established_address_3()),
+                (),
+            )
+            .unwrap();
+        let changed_keys = tally_votes(&mut wl_storage).unwrap();
+        assert!(changed_keys.contains(&emergency_pause::paused_key()));
+        assert!(wl_storage.ethbridge_queries().is_bridge_paused());
+        // The votes are cleared
+        let vote_key =
+            emergency_pause::vote_key(epoch, &established_address_1());
+        assert!(!wl_storage.has_key(&vote_key).unwrap());
+    }
+}
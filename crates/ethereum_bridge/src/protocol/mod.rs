@@ -1,2 +1,3 @@
+pub mod emergency_pause;
 pub mod transactions;
 pub mod validation;
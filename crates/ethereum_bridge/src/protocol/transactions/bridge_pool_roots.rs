@@ -68,6 +68,11 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
+    let _span = tracing::info_span!(
+        "bridge_pool_roots_votes",
+        height = wl_storage.storage.block.height.0,
+    )
+    .entered();
     if vext.is_empty() {
         return Ok(TxResult::default());
     }
@@ -15,9 +15,11 @@ use namada_core::types::eth_bridge_pool::{
 };
 use namada_core::types::ethereum_events::{
     EthAddress, EthereumEvent, TransferToEthereum, TransferToNamada,
-    TransfersToNamada,
+    TransfersToNamada, Uint,
 };
 use namada_core::types::ethereum_structs::EthBridgeEvent;
+use namada_core::types::hash::Hash;
+use namada_core::types::keccak::KeccakHash;
 use namada_core::types::storage::{BlockHeight, Key, KeySeg};
 use namada_parameters::read_epoch_duration_parameter;
 use namada_state::{DBIter, StorageHasher, WlStorage, DB};
@@ -26,11 +28,12 @@ use namada_trans_token::storage_key::{balance_key, minted_balance_key};
 
 use crate::protocol::transactions::update;
 use crate::storage::bridge_pool::{
-    get_nonce_key, is_pending_transfer_key, BRIDGE_POOL_ADDRESS,
+    get_nonce_key, get_transfer_status_key, is_pending_transfer_key,
+    TransferStatus, BRIDGE_POOL_ADDRESS,
 };
 use crate::storage::eth_bridge_queries::{EthAssetMint, EthBridgeQueries};
 use crate::storage::parameters::read_native_erc20_address;
-use crate::storage::{self as bridge_storage};
+use crate::storage::{self as bridge_storage, vote_tallies};
 use crate::token;
 
 /// Updates storage based on the given confirmed `event`. For example, for a
@@ -54,8 +57,18 @@ where
         EthereumEvent::TransfersToEthereum {
             ref transfers,
             ref relayer,
-            ..
-        } => act_on_transfers_to_eth(wl_storage, transfers, relayer),
+            nonce,
+        } => act_on_transfers_to_eth(wl_storage, transfers, relayer, nonce),
+        EthereumEvent::ReceiptsRoot {
+            ref block_height,
+            receipts_root,
+            ref events,
+        } => act_on_receipts_root(
+            wl_storage,
+            block_height,
+            receipts_root,
+            events,
+        ),
         _ => {
             tracing::debug!(?event, "No actions taken for Ethereum event");
             Ok(Default::default())
@@ -301,10 +314,42 @@ where
     Ok((asset_count, changed_keys))
 }
 
+/// Store the receipts root of an Ethereum block, and the height of that block
+/// for each of the events that it emitted, against which the inclusion of
+/// these events in the block can be proven.
+fn act_on_receipts_root<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    block_height: &Uint,
+    receipts_root: KeccakHash,
+    events: &[Hash],
+) -> Result<(BTreeSet<Key>, BTreeSet<EthBridgeEvent>)>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    tracing::debug!(
+        %block_height,
+        ?receipts_root,
+        n_events = events.len(),
+        "Acting on the receipts root of an Ethereum block"
+    );
+    let mut changed_keys = BTreeSet::new();
+    let root_key = vote_tallies::eth_receipts_root_key(block_height);
+    wl_storage.write(&root_key, receipts_root)?;
+    changed_keys.insert(root_key);
+    for hash in events {
+        let eth_block_key = vote_tallies::eth_event_eth_block_key(hash);
+        wl_storage.write(&eth_block_key, block_height)?;
+        changed_keys.insert(eth_block_key);
+    }
+    Ok((changed_keys, BTreeSet::new()))
+}
+
 fn act_on_transfers_to_eth<D, H>(
     wl_storage: &mut WlStorage<D, H>,
     transfers: &[TransferToEthereum],
     relayer: &Address,
+    nonce: Uint,
 ) -> Result<(BTreeSet<Key>, BTreeSet<EthBridgeEvent>)>
 where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
@@ -364,8 +409,11 @@ where
             balance.spend(&pending_transfer.gas_fee.amount);
         })?;
         wl_storage.delete(&key)?;
+        let status_key = get_transfer_status_key(&pending_transfer.keccak256());
+        wl_storage.write(&status_key, TransferStatus::Confirmed { nonce })?;
         _ = pending_keys.remove(&key);
         _ = changed_keys.insert(key);
+        _ = changed_keys.insert(status_key);
         _ = changed_keys.insert(pool_balance_key);
         _ = changed_keys.insert(relayer_rewards_key);
         _ = tx_events.insert(EthBridgeEvent::new_bridge_pool_relayed(
@@ -441,6 +489,11 @@ where
     wl_storage.delete(&key)?;
     _ = changed_keys.insert(key);
 
+    // Mark the transfer as expired
+    let status_key = get_transfer_status_key(&transfer.keccak256());
+    wl_storage.write(&status_key, TransferStatus::Expired)?;
+    _ = changed_keys.insert(status_key);
+
     // Emit expiration event
     _ = tx_events.insert(EthBridgeEvent::new_bridge_pool_expired(
         transfer.keccak256(),
@@ -827,6 +880,46 @@ mod tests {
         }
     }
 
+    #[test]
+    /// Test that acting on the receipts root of an Ethereum block stores it,
+    /// along with the height of the block for each event that it emitted
+    fn test_act_on_receipts_root() {
+        let mut wl_storage = TestWlStorage::default();
+        test_utils::bootstrap_ethereum_bridge(&mut wl_storage);
+        let block_height = Uint::from(42);
+        let receipts_root = arbitrary_keccak_hash();
+        let events = vec![Hash::sha256(b"event 1"), Hash::sha256(b"event 2")];
+        let event = EthereumEvent::ReceiptsRoot {
+            block_height,
+            receipts_root: receipts_root.clone(),
+            events: events.clone(),
+        };
+
+        let (changed_keys, tx_events) =
+            act_on(&mut wl_storage, event).expect("Test failed");
+
+        let root_key = vote_tallies::eth_receipts_root_key(&block_height);
+        let mut expected_keys = BTreeSet::from([root_key.clone()]);
+        expected_keys
+            .extend(events.iter().map(vote_tallies::eth_event_eth_block_key));
+        assert_eq!(changed_keys, expected_keys);
+        assert!(tx_events.is_empty());
+        assert_eq!(
+            wl_storage
+                .read::<KeccakHash>(&root_key)
+                .expect("Test failed"),
+            Some(receipts_root)
+        );
+        for hash in &events {
+            assert_eq!(
+                wl_storage
+                    .read::<Uint>(&vote_tallies::eth_event_eth_block_key(hash))
+                    .expect("Test failed"),
+                Some(block_height)
+            );
+        }
+    }
+
     #[test]
     /// Test that wl_storage is indeed changed when we act on a non-empty
     /// TransfersToNamada batch
@@ -1021,8 +1114,9 @@ mod tests {
             .iter()
             .map(TransferToEthereum::from)
             .collect();
+        let nonce = arbitrary_nonce();
         let event = EthereumEvent::TransfersToEthereum {
-            nonce: arbitrary_nonce(),
+            nonce,
             transfers,
             relayer: relayer.clone(),
         };
@@ -1074,6 +1168,13 @@ mod tests {
         assert!(changed_keys.remove(&pool_nam_balance_key));
         assert!(changed_keys.remove(&pool_erc_balance_key));
         assert!(changed_keys.remove(&get_nonce_key()));
+        for transfer in &pending_transfers {
+            let status_key = get_transfer_status_key(&transfer.keccak256());
+            assert!(changed_keys.remove(&status_key));
+            let status: Option<TransferStatus> =
+                wl_storage.read(&status_key).expect("Test failed");
+            assert_eq!(status, Some(TransferStatus::Confirmed { nonce }));
+        }
         assert!(changed_keys.iter().all(|k| pending_keys.contains(k)));
 
         let prefix = BRIDGE_POOL_ADDRESS.to_db_key().into();
@@ -1083,7 +1184,8 @@ mod tests {
                 .expect("Test failed")
                 .count(),
             // NOTE: we should have one write -- the bridge pool nonce update
-            1
+            // -- besides the statuses of the confirmed transfers
+            1 + pending_transfers.len()
         );
         let relayer_nam_balance = Amount::try_from_slice(
             &wl_storage
@@ -1185,9 +1287,16 @@ mod tests {
                 .expect("Test failed")
                 .count(),
             // NOTE: we should have two writes -- one of them being
-            // the bridge pool nonce update
-            2
+            // the bridge pool nonce update -- besides the statuses of
+            // the expired transfers
+            2 + pending_transfers.len()
         );
+        for transfer in &pending_transfers {
+            let status_key = get_transfer_status_key(&transfer.keccak256());
+            let status: Option<TransferStatus> =
+                wl_storage.read(&status_key).expect("Test failed");
+            assert_eq!(status, Some(TransferStatus::Expired));
+        }
 
         // Check the gas fee
         let expected = pending_transfers
@@ -11,12 +11,14 @@ use eyre::Result;
 use namada_core::types::address::Address;
 use namada_core::types::ethereum_events::EthereumEvent;
 use namada_core::types::ethereum_structs::EthBridgeEvent;
+use namada_core::types::hash::Hash;
 use namada_core::types::key::common;
 use namada_core::types::storage::{BlockHeight, Epoch, Key};
 use namada_core::types::token::Amount;
 use namada_proof_of_stake::pos_queries::PosQueries;
 use namada_state::tx_queue::ExpiredTx;
 use namada_state::{DBIter, StorageHasher, WlStorage, DB};
+use namada_storage::{StorageRead, StorageWrite};
 use namada_tx::data::TxResult;
 use namada_vote_ext::ethereum_events::{MultiSignedEthEvent, SignedVext, Vext};
 
@@ -52,7 +54,11 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
-    if !wl_storage.ethbridge_queries().is_bridge_active() {
+    // While the bridge is paused, the events remain in the oracle's queue
+    // until it is resumed
+    if !wl_storage.ethbridge_queries().is_bridge_active()
+        || wl_storage.ethbridge_queries().is_bridge_paused()
+    {
         return None;
     }
 
@@ -88,7 +94,13 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
+    let _span = tracing::info_span!(
+        "ethereum_events_votes",
+        height = wl_storage.storage.block.height.0,
+    )
+    .entered();
     let mut changed_keys = timeout_events(wl_storage)?;
+    changed_keys.append(&mut prune_seen_events_in_blocks(wl_storage)?);
     if events.is_empty() {
         return Ok(TxResult {
             changed_keys,
@@ -163,6 +175,7 @@ where
         return Ok((changed_keys, tx_events));
     }
     tracing::debug!(n = confirmed.len(), "Events were newly confirmed",);
+    changed_keys.append(&mut record_seen_events(wl_storage, &confirmed)?);
 
     // Right now, the order in which events are acted on does not matter.
     // For `TransfersToNamada` events, they can happen in any order.
@@ -175,6 +188,75 @@ where
     Ok((changed_keys, tx_events))
 }
 
+/// Record the block height at which the given events were seen, and add their
+/// hashes to the events seen in the current block, so that their inclusion
+/// can later be proven from storage.
+fn record_seen_events<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    events: &[EthereumEvent],
+) -> Result<ChangedKeys>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let height = wl_storage.storage.block.height;
+    let mut changed = ChangedKeys::new();
+    let block_key = vote_tallies::eth_events_seen_in_block_key(height);
+    let mut seen_in_block: Vec<Hash> =
+        wl_storage.read(&block_key)?.unwrap_or_default();
+    for event in events {
+        let hash = event.hash()?;
+        let height_key = vote_tallies::eth_event_seen_height_key(&hash);
+        wl_storage.write(&height_key, height)?;
+        changed.insert(height_key);
+        seen_in_block.push(hash);
+    }
+    seen_in_block.sort();
+    seen_in_block.dedup();
+    wl_storage.write(&block_key, seen_in_block)?;
+    changed.insert(block_key);
+    Ok(changed)
+}
+
+/// Delete the hashes of the Ethereum events seen in the blocks of the epochs
+/// older than the unbonding length, like the votes on the events that time
+/// out. The height at which each event was seen is kept.
+fn prune_seen_events_in_blocks<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+) -> Result<ChangedKeys>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let unbonding_len = wl_storage.pos_queries().get_pos_params().unbonding_len;
+    let current_epoch = wl_storage.storage.last_epoch;
+    if current_epoch.0 <= unbonding_len {
+        return Ok(ChangedKeys::new());
+    }
+    let oldest_epoch = Epoch(current_epoch.0 - unbonding_len);
+
+    let prefix = vote_tallies::eth_events_seen_in_block_prefix();
+    let mut pruned = vec![];
+    for (key, _, _) in votes::storage::iter_prefix(wl_storage, &prefix)? {
+        let key = Key::parse(key).expect("The key should be parsable");
+        let is_old = vote_tallies::eth_events_seen_in_block_height(&key)
+            .and_then(|height| {
+                wl_storage.storage.block.pred_epochs.get_epoch(height)
+            })
+            .map(|epoch| epoch < oldest_epoch)
+            .unwrap_or_default();
+        if is_old {
+            pruned.push(key);
+        }
+    }
+    let mut changed = ChangedKeys::new();
+    for key in pruned {
+        wl_storage.delete(&key)?;
+        changed.insert(key);
+    }
+    Ok(changed)
+}
+
 /// Apply an [`EthMsgUpdate`] to storage. Returns any keys changed and whether
 /// the event was newly seen.
 ///
@@ -345,6 +427,7 @@ mod tests {
     use namada_core::types::ethereum_events::{
         EthereumEvent, TransferToNamada,
     };
+    use namada_core::types::keccak::KeccakHash;
     use namada_core::types::voting_power::FractionalVotingPower;
     use namada_state::testing::TestWlStorage;
     use namada_storage::mockdb::MockDBWriteBatch;
@@ -409,6 +492,8 @@ mod tests {
             apply_updates(&mut wl_storage, updates, voting_powers)?;
 
         let eth_msg_keys: vote_tallies::Keys<EthereumEvent> = (&body).into();
+        let body_hash = body.hash()?;
+        let height = wl_storage.storage.block.height;
         let wrapped_erc20_token = wrapped_erc20s::token(&asset);
         assert_eq!(
             BTreeSet::from_iter(vec![
@@ -417,6 +502,8 @@ mod tests {
                 eth_msg_keys.seen_by(),
                 eth_msg_keys.voting_power(),
                 eth_msg_keys.voting_started_epoch(),
+                vote_tallies::eth_event_seen_height_key(&body_hash),
+                vote_tallies::eth_events_seen_in_block_key(height),
                 balance_key(&wrapped_erc20_token, &receiver),
                 minted_balance_key(&wrapped_erc20_token),
             ]),
@@ -449,6 +536,20 @@ mod tests {
         let epoch_bytes = epoch_bytes.unwrap();
         assert_eq!(Epoch::try_from_slice(&epoch_bytes)?, Epoch(0));
 
+        let seen_at = wl_storage
+            .read::<BlockHeight>(&vote_tallies::eth_event_seen_height_key(
+                &body_hash,
+            ))?
+            .expect("Test failed");
+        assert_eq!(seen_at, height);
+
+        let seen_in_block = wl_storage
+            .read::<Vec<Hash>>(&vote_tallies::eth_events_seen_in_block_key(
+                height,
+            ))?
+            .expect("Test failed");
+        assert_eq!(seen_in_block, vec![body_hash]);
+
         let wrapped_erc20_balance_bytes = wl_storage
             .read_bytes(&balance_key(&wrapped_erc20_token, &receiver))?;
         let wrapped_erc20_balance_bytes = wrapped_erc20_balance_bytes.unwrap();
@@ -527,6 +628,10 @@ mod tests {
                 eth_msg_keys.seen_by(),
                 eth_msg_keys.voting_power(),
                 eth_msg_keys.voting_started_epoch(),
+                vote_tallies::eth_event_seen_height_key(&event.hash().unwrap()),
+                vote_tallies::eth_events_seen_in_block_key(
+                    wl_storage.storage.block.height
+                ),
                 balance_key(&dai_token, &receiver),
                 minted_balance_key(&dai_token),
             ])
@@ -798,6 +903,63 @@ mod tests {
         assert!(wl_storage.read_bytes(&new_keys.body()).unwrap().is_some());
     }
 
+    /// Test that the hashes of the events seen in a block are pruned once the
+    /// block is older than the unbonding length, while the height at which
+    /// each event was seen and the receipts roots are kept
+    #[test]
+    fn test_prune_seen_events_in_blocks() {
+        let sole_validator = address::testing::established_address_2();
+        let (mut wl_storage, _) =
+            test_utils::setup_storage_with_validators(HashMap::from_iter(
+                vec![(sole_validator.clone(), Amount::native_whole(100))],
+            ));
+        let event = EthereumEvent::ReceiptsRoot {
+            block_height: 42.into(),
+            receipts_root: KeccakHash([1; 32]),
+            events: vec![Hash::sha256(b"event")],
+        };
+        apply_derived_tx(
+            &mut wl_storage,
+            vec![MultiSignedEthEvent {
+                event: event.clone(),
+                signers: BTreeSet::from([(sole_validator, BlockHeight(100))]),
+            }],
+        )
+        .expect("Test failed");
+        let seen_height = wl_storage.storage.block.height;
+        let block_key = vote_tallies::eth_events_seen_in_block_key(seen_height);
+        let height_key =
+            vote_tallies::eth_event_seen_height_key(&event.hash().unwrap());
+        let root_key = vote_tallies::eth_receipts_root_key(&42.into());
+        assert!(wl_storage.read_bytes(&block_key).unwrap().is_some());
+        assert!(wl_storage.read_bytes(&root_key).unwrap().is_some());
+
+        // nothing is pruned within the unbonding length
+        wl_storage.storage.commit_block(MockDBWriteBatch).unwrap();
+        let unbonding_len =
+            namada_proof_of_stake::storage::read_pos_params(&wl_storage)
+                .expect("Test failed")
+                .unbonding_len;
+        wl_storage.storage.last_epoch =
+            wl_storage.storage.last_epoch + unbonding_len;
+        let tx_result =
+            apply_derived_tx(&mut wl_storage, vec![]).expect("Test failed");
+        assert!(tx_result.changed_keys.is_empty());
+        assert!(wl_storage.read_bytes(&block_key).unwrap().is_some());
+
+        // past it, the events seen in the block are pruned
+        wl_storage.storage.last_epoch = wl_storage.storage.last_epoch + 1_u64;
+        let tx_result =
+            apply_derived_tx(&mut wl_storage, vec![]).expect("Test failed");
+        assert_eq!(tx_result.changed_keys, BTreeSet::from([block_key.clone()]));
+        assert!(wl_storage.read_bytes(&block_key).unwrap().is_none());
+        assert_eq!(
+            wl_storage.read::<BlockHeight>(&height_key).unwrap(),
+            Some(seen_height)
+        );
+        assert!(wl_storage.read_bytes(&root_key).unwrap().is_some());
+    }
+
     /// Helper fn to [`test_timeout_events_before_state_upds`].
     fn check_event_keys<T, F>(
         keys: &Keys<T>,
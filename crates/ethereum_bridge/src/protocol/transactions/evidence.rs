@@ -0,0 +1,84 @@
+//! Slashing of the validators that signed conflicting vote extensions.
+
+use eyre::Result;
+use namada_core::types::address::Address;
+use namada_proof_of_stake::pos_queries::PosQueries;
+use namada_proof_of_stake::slashing::{record_slash_bounty, slash};
+use namada_proof_of_stake::types::SlashType;
+use namada_state::{
+    DBIter, StorageHasher, WlStorage, DB, EPOCH_SWITCH_BLOCKS_DELAY,
+};
+use namada_tx::data::TxResult;
+use namada_vote_ext::evidence::DuplicateVextEvidence;
+
+/// Slashes the validator that signed the conflicting vote extensions of the
+/// given evidence, and records its submitter to be paid a bounty from the
+/// slashed tokens once the slash is processed.
+///
+/// The evidence must have been validated beforehand.
+pub fn apply_derived_tx<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    evidence: DuplicateVextEvidence,
+    submitter: &Address,
+) -> Result<TxResult>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let (validator, _) = evidence.validators();
+    let (height, _) = evidence.block_heights();
+    let evidence_epoch =
+        wl_storage.pos_queries().get_epoch(height).ok_or_else(|| {
+            eyre::eyre!(
+                "The epoch of the evidence's height {height} is unknown"
+            )
+        })?;
+    let params = wl_storage.pos_queries().get_pos_params();
+    let current_epoch = wl_storage.storage.block.epoch;
+
+    // If we're waiting to switch to a new epoch, it's too late to update the
+    // validator sets of the next epoch
+    let validator_set_update_epoch =
+        match wl_storage.storage.update_epoch_blocks_delay {
+            Some(delay) if delay != EPOCH_SWITCH_BLOCKS_DELAY => {
+                current_epoch.next().next()
+            }
+            _ => current_epoch.next(),
+        };
+
+    tracing::info!(
+        %validator,
+        %submitter,
+        ?evidence_epoch,
+        ?height,
+        "Slashing a validator for signing duplicate vote extensions"
+    );
+    // the same evidence may have been included twice in a block
+    if !record_slash_bounty(
+        wl_storage,
+        &params,
+        evidence_epoch,
+        validator,
+        submitter,
+    )? {
+        eyre::bail!(
+            "Evidence of the misbehavior of validator {validator} in epoch \
+             {evidence_epoch} has already been submitted"
+        );
+    }
+    slash(
+        wl_storage,
+        &params,
+        current_epoch,
+        evidence_epoch,
+        height,
+        SlashType::DuplicateVoteExtension,
+        validator,
+        validator_set_update_epoch,
+    )?;
+
+    Ok(TxResult {
+        changed_keys: wl_storage.write_log.get_keys(),
+        ..Default::default()
+    })
+}
@@ -6,6 +6,7 @@
 //! transactions.
 pub mod bridge_pool_roots;
 pub mod ethereum_events;
+pub mod evidence;
 mod read;
 mod update;
 mod utils;
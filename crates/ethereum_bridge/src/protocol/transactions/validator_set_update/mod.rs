@@ -77,6 +77,12 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
+    let _span = tracing::info_span!(
+        "validator_set_update_votes",
+        height = wl_storage.storage.block.height.0,
+        %signing_epoch,
+    )
+    .entered();
     if ext.signatures.is_empty() {
         tracing::debug!("Ignoring empty validator set update");
         return Ok(Default::default());
@@ -11,12 +11,16 @@ use namada_core::types::token;
 use namada_core::types::voting_power::FractionalVotingPower;
 use namada_proof_of_stake::pos_queries::PosQueries;
 use namada_state::{DBIter, StorageHasher, WlStorage, DB};
+use namada_storage::{StorageRead, StorageWrite};
 
 use super::{read, ChangedKeys};
+use crate::storage::vote_tallies;
 
 pub(super) mod storage;
 pub(super) mod update;
 
+pub use storage::validate_tally_storage;
+
 /// The addresses of validators that voted for something, and the block
 /// heights at which they voted. We use a [`BTreeMap`] to enforce that a
 /// validator (as uniquely identified by an [`Address`]) may vote at most once,
@@ -104,9 +108,7 @@ impl EpochedVotingPowerExt for EpochedVotingPower {
     {
         self.keys()
             .copied()
-            .map(|epoch| {
-                wl_storage.pos_queries().get_total_voting_power(Some(epoch))
-            })
+            .map(|epoch| read_total_voting_power(wl_storage, epoch))
             .max()
     }
 
@@ -176,6 +178,44 @@ where
     })
 }
 
+/// Record a snapshot of the total voting power of the given epoch, against
+/// which the tallies of the votes cast in the epoch are evaluated. Must be
+/// called once the consensus validator set of the epoch is final, i.e. when
+/// the epoch starts.
+pub fn snapshot_total_voting_power<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    epoch: Epoch,
+) -> namada_storage::Result<()>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let total_voting_power =
+        wl_storage.pos_queries().get_total_voting_power(Some(epoch));
+    wl_storage.write(
+        &vote_tallies::voting_power_snapshot_key(epoch),
+        total_voting_power,
+    )
+}
+
+/// Read the total voting power of the given epoch from its snapshot, falling
+/// back to the PoS total consensus stake for epochs without a snapshot.
+pub fn read_total_voting_power<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    epoch: Epoch,
+) -> token::Amount
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    wl_storage
+        .read(&vote_tallies::voting_power_snapshot_key(epoch))
+        .expect("Reading the total voting power snapshot shouldn't fail")
+        .unwrap_or_else(|| {
+            wl_storage.pos_queries().get_total_voting_power(Some(epoch))
+        })
+}
+
 /// Deterministically constructs a [`Votes`] map from a set of validator
 /// addresses and the block heights they signed something at. We arbitrarily
 /// take the earliest block height for each validator address encountered.
@@ -382,4 +422,41 @@ mod tests {
             FractionalVotingPower::TWO_THIRDS
         );
     }
+
+    /// Test that the tallies are evaluated against the snapshot of the total
+    /// voting power of an epoch, rather than against the current stake.
+    #[test]
+    fn test_tally_uses_voting_power_snapshot() {
+        let (_, validator_stake) = test_utils::default_validator();
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        let epoch = Epoch::default();
+
+        snapshot_total_voting_power(&mut wl_storage, epoch)
+            .expect("Test failed");
+        assert_eq!(
+            read_total_voting_power(&wl_storage, epoch),
+            validator_stake
+        );
+
+        // A snapshot that differs from the current stake takes precedence
+        let snapshot = validator_stake + validator_stake;
+        wl_storage
+            .write(&vote_tallies::voting_power_snapshot_key(epoch), snapshot)
+            .expect("Test failed");
+        let aggregated = EpochedVotingPower::from([(epoch, validator_stake)]);
+        assert_eq!(
+            aggregated.fractional_stake(&wl_storage),
+            FractionalVotingPower::HALF
+        );
+        assert!(!aggregated.has_majority_quorum(&wl_storage));
+
+        // Epochs without a snapshot fall back to the PoS total stake
+        let next_epoch = epoch.next();
+        assert_eq!(
+            read_total_voting_power(&wl_storage, next_epoch),
+            wl_storage
+                .pos_queries()
+                .get_total_voting_power(Some(next_epoch)),
+        );
+    }
 }
@@ -1,7 +1,7 @@
-use eyre::{Result, WrapErr};
+use eyre::{eyre, Result, WrapErr};
 use namada_core::borsh::{BorshDeserialize, BorshSerialize, BorshSerializeExt};
 use namada_core::hints;
-use namada_core::types::storage::Key;
+use namada_core::types::storage::{Epoch, Key};
 use namada_core::types::voting_power::FractionalVotingPower;
 use namada_state::{DBIter, PrefixIter, StorageHasher, WlStorage, DB};
 use namada_storage::{StorageRead, StorageWrite};
@@ -54,13 +54,13 @@ where
 {
     let opt_body = {
         let voting_power: EpochedVotingPower =
-            super::read::value(wl_storage, &keys.voting_power())?;
+            read_value(wl_storage, &keys.voting_power())?;
 
         if hints::unlikely(
             voting_power.fractional_stake(wl_storage)
                 > FractionalVotingPower::ONE_THIRD,
         ) {
-            let body: T = super::read::value(wl_storage, &keys.body())?;
+            let body: T = read_value(wl_storage, &keys.body())?;
             Some(body)
         } else {
             None
@@ -82,10 +82,10 @@ where
     D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
     H: 'static + StorageHasher + Sync,
 {
-    let seen: bool = super::read::value(wl_storage, &keys.seen())?;
-    let seen_by: Votes = super::read::value(wl_storage, &keys.seen_by())?;
+    let seen: bool = read_value(wl_storage, &keys.seen())?;
+    let seen_by: Votes = read_value(wl_storage, &keys.seen_by())?;
     let voting_power: EpochedVotingPower =
-        super::read::value(wl_storage, &keys.voting_power())?;
+        read_value(wl_storage, &keys.voting_power())?;
 
     Ok(Tally {
         voting_power,
@@ -94,6 +94,40 @@ where
     })
 }
 
+/// Check that all the keys of a tally exist in storage and that their values
+/// decode to the expected types. The error lists every invalid key.
+pub fn validate_tally_storage<D, H, T>(
+    wl_storage: &WlStorage<D, H>,
+    keys: &vote_tallies::Keys<T>,
+) -> Result<()>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+    T: BorshDeserialize,
+{
+    let errors: Vec<String> = [
+        read_value::<_, _, T>(wl_storage, &keys.body()).err(),
+        read_value::<_, _, bool>(wl_storage, &keys.seen()).err(),
+        read_value::<_, _, Votes>(wl_storage, &keys.seen_by()).err(),
+        read_value::<_, _, EpochedVotingPower>(
+            wl_storage,
+            &keys.voting_power(),
+        )
+        .err(),
+        read_value::<_, _, Epoch>(wl_storage, &keys.voting_started_epoch())
+            .err(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|err| format!("{err:#}"))
+    .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(eyre!("The tally is invalid:\n{}", errors.join("\n")))
+    }
+}
+
 pub fn iter_prefix<'a, D, H>(
     wl_storage: &'a WlStorage<D, H>,
     prefix: &Key,
@@ -117,7 +151,7 @@ where
     H: 'static + StorageHasher + Sync,
     T: BorshDeserialize,
 {
-    super::read::value(wl_storage, &keys.body())
+    read_value(wl_storage, &keys.body())
 }
 
 #[inline]
@@ -130,7 +164,42 @@ where
     H: 'static + StorageHasher + Sync,
     T: BorshDeserialize,
 {
-    super::read::maybe_value(wl_storage, &keys.seen())
+    maybe_read_value(wl_storage, &keys.seen())
+}
+
+/// Read a value of a tally, erroring if it's not found, with the key and the
+/// expected type of the value in the context of the errors
+fn read_value<D, H, V>(wl_storage: &WlStorage<D, H>, key: &Key) -> Result<V>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+    V: BorshDeserialize,
+{
+    super::read::value(wl_storage, key).wrap_err_with(|| {
+        format!(
+            "Failed to read a value of type {} at {key}",
+            std::any::type_name::<V>()
+        )
+    })
+}
+
+/// Try to read a value of a tally, with the key and the expected type of the
+/// value in the context of the errors
+fn maybe_read_value<D, H, V>(
+    wl_storage: &WlStorage<D, H>,
+    key: &Key,
+) -> Result<Option<V>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+    V: BorshDeserialize,
+{
+    super::read::maybe_value(wl_storage, key).wrap_err_with(|| {
+        format!(
+            "Failed to read a value of type {} at {key}",
+            std::any::type_name::<V>()
+        )
+    })
 }
 
 #[cfg(test)]
@@ -266,4 +335,63 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), tally);
     }
+
+    #[test]
+    fn test_read_tally_error_context() {
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        let event = EthereumEvent::TransfersToNamada {
+            nonce: 0.into(),
+            transfers: vec![],
+        };
+        let keys = vote_tallies::Keys::from(&event);
+        wl_storage
+            .write_bytes(&keys.seen(), "not a bool".as_bytes())
+            .unwrap();
+
+        let err = read(&wl_storage, &keys).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            format!("Failed to read a value of type bool at {}", keys.seen())
+        );
+    }
+
+    #[test]
+    fn test_validate_tally_storage() {
+        let (mut wl_storage, _) = test_utils::setup_default_storage();
+        let (validator, validator_voting_power) =
+            test_utils::default_validator();
+        let event = EthereumEvent::TransfersToNamada {
+            nonce: 0.into(),
+            transfers: vec![],
+        };
+        let keys = vote_tallies::Keys::from(&event);
+        let tally = Tally {
+            voting_power: EpochedVotingPower::from([(
+                0.into(),
+                validator_voting_power,
+            )]),
+            seen_by: BTreeMap::from([(validator, 10.into())]),
+            seen: false,
+        };
+        write(&mut wl_storage, &keys, &event, &tally, false).unwrap();
+        assert!(validate_tally_storage(&wl_storage, &keys).is_ok());
+
+        // corrupt one of the values and remove another one
+        wl_storage
+            .write_bytes(&keys.seen_by(), "not votes".as_bytes())
+            .unwrap();
+        wl_storage.delete(&keys.voting_started_epoch()).unwrap();
+
+        let err = validate_tally_storage(&wl_storage, &keys)
+            .unwrap_err()
+            .to_string();
+        let invalid_keys: Vec<&str> = err.lines().skip(1).collect();
+        assert_eq!(invalid_keys.len(), 2);
+        assert!(invalid_keys[0].contains(&keys.seen_by().to_string()));
+        assert!(invalid_keys[1].contains(&format!(
+            "no value found at {}",
+            keys.voting_started_epoch()
+        )));
+    }
 }
@@ -2,6 +2,7 @@
 
 pub mod bridge_pool_roots;
 pub mod ethereum_events;
+pub mod evidence;
 pub mod validator_set_update;
 
 use thiserror::Error;
@@ -47,4 +48,21 @@ pub enum VoteExtensionError {
          not active"
     )]
     EthereumBridgeInactive,
+    #[error(
+        "Received an Ethereum events vote extension while the Ethereum bridge \
+         is paused"
+    )]
+    EthereumBridgePaused,
+    #[error(
+        "The vote extensions of the evidence were not issued by the same \
+         validator at the same block height over different data"
+    )]
+    NotConflicting,
+    #[error("The evidence is for a misbehavior that can no longer be slashed")]
+    OutdatedEvidence,
+    #[error(
+        "Evidence of the validator's misbehavior in the same epoch has \
+         already been submitted"
+    )]
+    EvidenceAlreadySubmitted,
 }
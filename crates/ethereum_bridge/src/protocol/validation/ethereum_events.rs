@@ -52,6 +52,13 @@ where
         );
         return Err(VoteExtensionError::EthereumBridgeInactive);
     }
+    if wl_storage.ethbridge_queries().is_bridge_paused() {
+        tracing::debug!(
+            "The Ethereum bridge is paused, so Ethereum events are not voted \
+             on",
+        );
+        return Err(VoteExtensionError::EthereumBridgePaused);
+    }
     if ext.data.block_height > last_height {
         tracing::debug!(
             ext_height = ?ext.data.block_height,
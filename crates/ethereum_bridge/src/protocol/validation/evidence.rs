@@ -0,0 +1,143 @@
+//! Validation of the evidence of validators that signed conflicting vote
+//! extensions.
+
+use namada_core::types::storage::BlockHeight;
+use namada_proof_of_stake::pos_queries::PosQueries;
+use namada_proof_of_stake::slashing::has_slash_bounty;
+use namada_state::{DBIter, StorageHasher, WlStorage, DB};
+use namada_vote_ext::evidence::DuplicateVextEvidence;
+
+use super::VoteExtensionError;
+
+/// Validates the evidence of a validator that signed two conflicting vote
+/// extensions.
+///
+/// Checks that:
+///  * Both vote extensions were issued by the same validator, at the same block
+///    height, and over different data.
+///  * The block height is not greater than the chain's last height.
+///  * The misbehavior can still be slashed, and no evidence of the validator's
+///    misbehavior in the same epoch has already been submitted.
+///  * The validator correctly signed both vote extensions.
+pub fn validate_duplicate_vext_evidence<D, H>(
+    wl_storage: &WlStorage<D, H>,
+    evidence: &DuplicateVextEvidence,
+    last_height: BlockHeight,
+) -> Result<(), VoteExtensionError>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    let (validator, other_validator) = evidence.validators();
+    if validator != other_validator {
+        tracing::debug!(
+            %validator,
+            %other_validator,
+            "The vote extensions of the evidence were issued by different \
+             validators"
+        );
+        return Err(VoteExtensionError::NotConflicting);
+    }
+    let (height, other_height) = evidence.block_heights();
+    if height != other_height {
+        tracing::debug!(
+            ?height,
+            ?other_height,
+            "The vote extensions of the evidence were issued at different \
+             block heights"
+        );
+        return Err(VoteExtensionError::NotConflicting);
+    }
+    if !evidence.is_conflicting() {
+        tracing::debug!(
+            %validator,
+            "The vote extensions of the evidence are not conflicting"
+        );
+        return Err(VoteExtensionError::NotConflicting);
+    }
+
+    if height > last_height {
+        tracing::debug!(
+            ?height,
+            ?last_height,
+            "The evidence contains vote extensions issued for a block height \
+             higher than the chain's last height"
+        );
+        return Err(VoteExtensionError::UnexpectedBlockHeight);
+    }
+    if height.0 == 0 {
+        tracing::debug!("Dropping evidence of vote extensions from genesis");
+        return Err(VoteExtensionError::UnexpectedBlockHeight);
+    }
+    let evidence_epoch = match wl_storage.pos_queries().get_epoch(height) {
+        Some(epoch) => epoch,
+        _ => {
+            tracing::debug!(
+                block_height = ?height,
+                "The epoch of the evidence's block height should always be \
+                 known",
+            );
+            return Err(VoteExtensionError::UnexpectedEpoch);
+        }
+    };
+
+    // reject the evidence of misbehaviors whose slashes should have already
+    // been processed at this time
+    let params = wl_storage.pos_queries().get_pos_params();
+    let current_epoch = wl_storage.storage.block.epoch;
+    if evidence_epoch + params.slash_processing_epoch_offset()
+        - params.cubic_slashing_window_length
+        <= current_epoch
+    {
+        tracing::debug!(
+            ?evidence_epoch,
+            ?current_epoch,
+            "The evidence of the duplicate vote extensions is outdated"
+        );
+        return Err(VoteExtensionError::OutdatedEvidence);
+    }
+    if has_slash_bounty(wl_storage, &params, evidence_epoch, validator)
+        .expect("Reading the slash bounties from storage should not fail")
+    {
+        tracing::debug!(
+            ?evidence_epoch,
+            %validator,
+            "Evidence of the validator's misbehavior in this epoch has \
+             already been submitted"
+        );
+        return Err(VoteExtensionError::EvidenceAlreadySubmitted);
+    }
+
+    // get the public key associated with this validator
+    let (_, pk) = wl_storage
+        .pos_queries()
+        .get_validator_from_address(validator, Some(evidence_epoch))
+        .map_err(|err| {
+            tracing::debug!(
+                ?err,
+                %validator,
+                "Could not get public key from Storage for some validator, \
+                 while validating the evidence of duplicate vote extensions"
+            );
+            VoteExtensionError::PubKeyNotInStorage
+        })?;
+    // verify the signatures of both vote extensions
+    let verified = match evidence {
+        DuplicateVextEvidence::EthereumEvents(first, second) => {
+            first.verify(&pk).and_then(|_| second.verify(&pk))
+        }
+        DuplicateVextEvidence::BridgePoolRoots(first, second) => {
+            first.verify(&pk).and_then(|_| second.verify(&pk))
+        }
+    };
+    verified.map_err(|err| {
+        tracing::debug!(
+            ?err,
+            ?pk,
+            %validator,
+            "Failed to verify the signatures of the duplicate vote extensions \
+             issued by some validator"
+        );
+        VoteExtensionError::VerifySigFailed
+    })
+}
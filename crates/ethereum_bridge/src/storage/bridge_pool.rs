@@ -3,7 +3,8 @@
 
 use namada_core::types::eth_bridge_pool::Segments;
 pub use namada_core::types::eth_bridge_pool::{
-    get_key_from_hash, get_pending_key, is_pending_transfer_key,
+    get_key_from_hash, get_pending_key, get_transfer_status_key,
+    is_pending_transfer_key, is_transfer_status_key, TransferStatus,
     BRIDGE_POOL_ADDRESS,
 };
 use namada_core::types::storage::{DbKeySeg, Key};
@@ -23,7 +23,9 @@ use namada_vote_ext::validator_set_update::{
 };
 
 use crate::storage::proof::BridgePoolRootProof;
-use crate::storage::{active_key, bridge_pool, vote_tallies, whitelist};
+use crate::storage::{
+    active_key, bridge_pool, emergency_pause, vote_tallies, whitelist,
+};
 
 /// This enum is used as a parameter to
 /// [`EthBridgeQueriesHook::must_send_valset_upd`].
@@ -178,6 +180,15 @@ where
         }
     }
 
+    /// Check if the transfers over the bridge have been paused by an
+    /// emergency vote of the validators.
+    pub fn is_bridge_paused(self) -> bool {
+        self.wl_storage
+            .read(&emergency_pause::paused_key())
+            .expect("Reading the Ethereum bridge paused key shouldn't fail.")
+            .unwrap_or(false)
+    }
+
     /// Get the nonce of the next transfers to Namada event to be processed.
     pub fn get_next_nam_transfers_nonce(self) -> Uint {
         self.wl_storage
@@ -9,14 +9,57 @@ pub mod vp;
 pub mod whitelist;
 pub mod wrapped_erc20s;
 
+pub use namada_core::ledger::eth_bridge::emergency_pause;
 use namada_core::ledger::eth_bridge::ADDRESS;
 use namada_core::types::address::Address;
+use namada_core::types::eth_bridge_pool::Segments as BridgePoolSegments;
+use namada_core::types::key_space::{ModuleKeySpace, PrefixSeg};
 use namada_core::types::storage::{DbKeySeg, Key, KeySeg};
 pub use namada_parameters::native_erc20_key;
 use namada_parameters::storage::*;
 use namada_parameters::ADDRESS as PARAM_ADDRESS;
 use namada_trans_token::storage_key::balance_key;
 
+/// The key prefixes reserved by the Ethereum bridge
+pub fn key_space() -> ModuleKeySpace {
+    ModuleKeySpace::new("ethereum_bridge")
+        .guarded_by(ADDRESS)
+        .reserve_sub_keys(
+            &ADDRESS,
+            &[
+                vote_tallies::ETH_MSGS_PREFIX_KEY_SEGMENT,
+                vote_tallies::BRIDGE_POOL_ROOT_PREFIX_KEY_SEGMENT,
+                vote_tallies::VALSET_UPDS_PREFIX_KEY_SEGMENT,
+                vote_tallies::VOTING_POWER_SNAPSHOTS_PREFIX_KEY_SEGMENT,
+                vote_tallies::SEEN_ETH_EVENTS_PREFIX_KEY_SEGMENT,
+            ],
+            "Votes on Ethereum events, bridge pool roots and validator sets",
+        )
+        .reserve_sub_keys(
+            &ADDRESS,
+            &[whitelist::segments::MAIN_SEGMENT],
+            "Whitelisted ERC20 tokens",
+        )
+        .reserve_sub_keys(
+            &ADDRESS,
+            &[emergency_pause::EMERGENCY_PAUSE_PREFIX_KEY_SEGMENT],
+            "Emergency pause of the bridge",
+        )
+        .guarded_by(bridge_pool::BRIDGE_POOL_ADDRESS)
+        .reserve_sub_keys(
+            &bridge_pool::BRIDGE_POOL_ADDRESS,
+            BridgePoolSegments::ALL,
+            "Signed root and nonce of the bridge pool",
+        )
+        .reserve(
+            [
+                PrefixSeg::Address(bridge_pool::BRIDGE_POOL_ADDRESS),
+                PrefixSeg::Hash,
+            ],
+            "Pending transfers in the bridge pool",
+        )
+}
+
 /// Key prefix for the storage subspace
 pub fn prefix() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -65,6 +108,13 @@ pub fn bridge_contract_key() -> Key {
     get_bridge_contract_address_key_at_addr(PARAM_ADDRESS)
 }
 
+/// Storage key for the Ethereum address of the next bridge contract. It is
+/// only present during the migration window of a bridge contract upgrade, in
+/// which the events of both contracts are relayed.
+pub fn next_bridge_contract_key() -> Key {
+    get_next_bridge_contract_address_key_at_addr(PARAM_ADDRESS)
+}
+
 #[cfg(test)]
 mod test {
     use namada_core::types::address;
@@ -4,7 +4,7 @@ use std::num::NonZeroU64;
 use eyre::{eyre, Result};
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::types::ethereum_events::EthAddress;
-use namada_core::types::storage::Key;
+use namada_core::types::storage::{Epoch, Key};
 use namada_core::types::token::{DenominatedAmount, NATIVE_MAX_DECIMAL_PLACES};
 use namada_core::types::{encode, ethereum_structs};
 use namada_state::{DBIter, StorageHasher, WlStorage, DB};
@@ -80,6 +80,9 @@ impl From<MinimumConfirmations> for NonZeroU64 {
     Copy,
     Eq,
     PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
     Debug,
     Deserialize,
     Serialize,
@@ -98,6 +101,18 @@ impl Default for ContractVersion {
     }
 }
 
+impl From<NonZeroU64> for ContractVersion {
+    fn from(value: NonZeroU64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ContractVersion> for u64 {
+    fn from(value: ContractVersion) -> Self {
+        value.0.get()
+    }
+}
+
 /// Represents an Ethereum contract that may be upgraded.
 #[derive(
     Copy,
@@ -105,6 +120,9 @@ impl Default for ContractVersion {
     Debug,
     Eq,
     PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
     Deserialize,
     Serialize,
     BorshSerialize,
@@ -260,6 +278,9 @@ pub struct EthereumOracleConfig {
     /// The addresses of the Ethereum contracts that need to be directly known
     /// by validators.
     pub contracts: Contracts,
+    /// The next bridge contract, during the migration window of a bridge
+    /// contract upgrade.
+    pub next_bridge: Option<UpgradeableContract>,
 }
 
 impl From<EthereumBridgeParams> for EthereumOracleConfig {
@@ -274,6 +295,7 @@ impl From<EthereumBridgeParams> for EthereumOracleConfig {
             eth_start_height,
             min_confirmations,
             contracts,
+            next_bridge: None,
         }
     }
 }
@@ -311,6 +333,16 @@ impl EthereumOracleConfig {
         let native_erc20 = must_read_key(wl_storage, &native_erc20_key);
         let bridge_contract = must_read_key(wl_storage, &bridge_contract_key);
         let eth_start_height = must_read_key(wl_storage, &eth_start_height_key);
+        // The next bridge contract is cleared at the end of a migration
+        // window by writing `None` to its key
+        let next_bridge = StorageRead::read::<Option<UpgradeableContract>>(
+            wl_storage,
+            &bridge_storage::next_bridge_contract_key(),
+        )
+        .unwrap_or_else(|err| {
+            panic!("Could not read the next bridge contract: {err:?}")
+        })
+        .flatten();
 
         Some(Self {
             eth_start_height,
@@ -319,10 +351,50 @@ impl EthereumOracleConfig {
                 native_erc20,
                 bridge: bridge_contract,
             },
+            next_bridge,
         })
     }
 }
 
+/// Schedule an upgrade of the bridge contract to `next`. The events of both
+/// the current and the `next` contract are relayed from `migration_start`
+/// until `migration_end`, at which point `next` becomes the bridge contract.
+pub fn schedule_bridge_contract_upgrade<S>(
+    storage: &mut S,
+    next: UpgradeableContract,
+    migration_start: Epoch,
+    migration_end: Epoch,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if migration_end <= migration_start {
+        return Err(namada_storage::Error::new_const(
+            "The migration window of a bridge contract upgrade must end after \
+             it starts",
+        ));
+    }
+    let next_bridge_contract_key = bridge_storage::next_bridge_contract_key();
+    namada_parameters::schedule_change(
+        storage,
+        migration_start,
+        next_bridge_contract_key.clone(),
+        encode(&Some(next)),
+    )?;
+    namada_parameters::schedule_change(
+        storage,
+        migration_end,
+        bridge_storage::bridge_contract_key(),
+        encode(&next),
+    )?;
+    namada_parameters::schedule_change(
+        storage,
+        migration_end,
+        next_bridge_contract_key,
+        encode(&Option::<UpgradeableContract>::None),
+    )
+}
+
 /// Get the Ethereum address for wNam from storage, if possible
 pub fn read_native_erc20_address<S>(storage: &S) -> Result<EthAddress>
 where
@@ -421,6 +493,74 @@ mod tests {
         assert_eq!(config, read);
     }
 
+    /// Test that a scheduled bridge contract upgrade exposes the next
+    /// contract during its migration window, and then replaces the current
+    /// bridge contract with it.
+    #[test]
+    fn test_schedule_bridge_contract_upgrade() {
+        let mut wl_storage = TestWlStorage::default();
+        let config = EthereumBridgeParams {
+            erc20_whitelist: vec![],
+            eth_start_height: Default::default(),
+            min_confirmations: MinimumConfirmations::default(),
+            contracts: Contracts {
+                native_erc20: EthAddress([42; 20]),
+                bridge: UpgradeableContract {
+                    address: EthAddress([23; 20]),
+                    version: ContractVersion::default(),
+                },
+            },
+        };
+        config.init_storage(&mut wl_storage);
+        let next = UpgradeableContract {
+            address: EthAddress([24; 20]),
+            version: ContractVersion::default(),
+        };
+        schedule_bridge_contract_upgrade(
+            &mut wl_storage,
+            next,
+            Epoch(2),
+            Epoch(4),
+        )
+        .unwrap();
+
+        namada_parameters::apply_scheduled_changes(&mut wl_storage, Epoch(1))
+            .unwrap();
+        let read = EthereumOracleConfig::read(&wl_storage).unwrap();
+        assert_eq!(read.contracts.bridge, config.contracts.bridge);
+        assert_eq!(read.next_bridge, None);
+
+        namada_parameters::apply_scheduled_changes(&mut wl_storage, Epoch(2))
+            .unwrap();
+        let read = EthereumOracleConfig::read(&wl_storage).unwrap();
+        assert_eq!(read.contracts.bridge, config.contracts.bridge);
+        assert_eq!(read.next_bridge, Some(next));
+
+        namada_parameters::apply_scheduled_changes(&mut wl_storage, Epoch(4))
+            .unwrap();
+        let read = EthereumOracleConfig::read(&wl_storage).unwrap();
+        assert_eq!(read.contracts.bridge, next);
+        assert_eq!(read.next_bridge, None);
+    }
+
+    #[test]
+    fn test_schedule_bridge_contract_upgrade_empty_window() {
+        let mut wl_storage = TestWlStorage::default();
+        let next = UpgradeableContract {
+            address: EthAddress([24; 20]),
+            version: ContractVersion::default(),
+        };
+        assert!(
+            schedule_bridge_contract_upgrade(
+                &mut wl_storage,
+                next,
+                Epoch(2),
+                Epoch(2),
+            )
+            .is_err()
+        );
+    }
+
     #[test]
     fn test_ethereum_bridge_config_uninitialized() {
         let wl_storage = TestWlStorage::default();
@@ -9,7 +9,7 @@ use namada_core::types::address::Address;
 use namada_core::types::ethereum_events::{EthereumEvent, Uint};
 use namada_core::types::hash::Hash;
 use namada_core::types::keccak::{keccak_hash, KeccakHash};
-use namada_core::types::storage::{BlockHeight, DbKeySeg, Epoch, Key};
+use namada_core::types::storage::{BlockHeight, DbKeySeg, Epoch, Key, KeySeg};
 use namada_macros::StorageKeys;
 use namada_vote_ext::validator_set_update::VotingPowersMap;
 
@@ -28,6 +28,22 @@ pub const BRIDGE_POOL_ROOT_PREFIX_KEY_SEGMENT: &str = "bp_root_and_nonce";
 /// voting power assigned to validator set updates.
 pub const VALSET_UPDS_PREFIX_KEY_SEGMENT: &str = "validator_set_updates";
 
+/// Storage sub-key space reserved to keeping snapshots of the total
+/// voting power of each epoch, against which the tallies are evaluated.
+pub const VOTING_POWER_SNAPSHOTS_PREFIX_KEY_SEGMENT: &str =
+    "voting_power_snapshots";
+
+/// Storage sub-key space reserved to keeping track of the block heights at
+/// which Ethereum events were seen, of the events seen at each height and of
+/// the receipts roots of the Ethereum blocks that emitted them.
+pub const SEEN_ETH_EVENTS_PREFIX_KEY_SEGMENT: &str = "seen_eth_events";
+
+/// Storage segments under [`SEEN_ETH_EVENTS_PREFIX_KEY_SEGMENT`].
+const SEEN_ETH_EVENT_HEIGHT_SEGMENT: &str = "event";
+const SEEN_ETH_EVENTS_IN_BLOCK_SEGMENT: &str = "block";
+const SEEN_ETH_EVENT_ETH_BLOCK_SEGMENT: &str = "eth_block";
+const ETH_RECEIPTS_ROOT_SEGMENT: &str = "receipts_root";
+
 /// Storage segments of [`Keys`].
 #[derive(StorageKeys)]
 pub struct KeysSegments {
@@ -128,6 +144,66 @@ pub fn eth_msgs_prefix() -> Key {
         .expect("should always be able to construct this key")
 }
 
+/// Get the key of the block height at which the Ethereum event with the given
+/// hash was seen - a [`BlockHeight`] is stored here.
+pub fn eth_event_seen_height_key(hash: &Hash) -> Key {
+    super::prefix()
+        .with_segment(SEEN_ETH_EVENTS_PREFIX_KEY_SEGMENT.to_owned())
+        .with_segment(SEEN_ETH_EVENT_HEIGHT_SEGMENT.to_owned())
+        .with_segment(hash.to_string())
+}
+
+/// Get the key of the hashes of the Ethereum events seen at the given block
+/// height, which commits to the events seen in the block - a sorted
+/// `Vec<Hash>` is stored here.
+pub fn eth_events_seen_in_block_key(height: BlockHeight) -> Key {
+    eth_events_seen_in_block_prefix().with_segment(height)
+}
+
+/// Get the key prefix of the hashes of the Ethereum events seen at each block
+/// height.
+pub fn eth_events_seen_in_block_prefix() -> Key {
+    super::prefix()
+        .with_segment(SEEN_ETH_EVENTS_PREFIX_KEY_SEGMENT.to_owned())
+        .with_segment(SEEN_ETH_EVENTS_IN_BLOCK_SEGMENT.to_owned())
+}
+
+/// Get the block height of a key of the Ethereum events seen at a block
+/// height. It returns None if the storage key isn't such a key.
+pub fn eth_events_seen_in_block_height(key: &Key) -> Option<BlockHeight> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(ADDRESS),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(segment),
+            DbKeySeg::StringSeg(height),
+        ] if prefix == SEEN_ETH_EVENTS_PREFIX_KEY_SEGMENT
+            && segment == SEEN_ETH_EVENTS_IN_BLOCK_SEGMENT =>
+        {
+            BlockHeight::parse(height.clone()).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Get the key of the height of the Ethereum block that emitted the Ethereum
+/// event with the given hash - a [`Uint`] is stored here.
+pub fn eth_event_eth_block_key(hash: &Hash) -> Key {
+    super::prefix()
+        .with_segment(SEEN_ETH_EVENTS_PREFIX_KEY_SEGMENT.to_owned())
+        .with_segment(SEEN_ETH_EVENT_ETH_BLOCK_SEGMENT.to_owned())
+        .with_segment(hash.to_string())
+}
+
+/// Get the key of the receipts root of the Ethereum block at the given
+/// height, as voted on by validators - a [`KeccakHash`] is stored here.
+pub fn eth_receipts_root_key(eth_height: &Uint) -> Key {
+    super::prefix()
+        .with_segment(SEEN_ETH_EVENTS_PREFIX_KEY_SEGMENT.to_owned())
+        .with_segment(ETH_RECEIPTS_ROOT_SEGMENT.to_owned())
+        .with_segment(eth_height.to_string())
+}
+
 /// Get the Keys from the storage key. It returns None if the storage key isn't
 /// for an Ethereum event.
 pub fn eth_event_keys(storage_key: &Key) -> Option<Keys<EthereumEvent>> {
@@ -239,6 +315,13 @@ pub fn valset_upds_prefix() -> Key {
         .expect("should always be able to construct this key")
 }
 
+/// Get the key of the snapshot of the total voting power of an epoch.
+pub fn voting_power_snapshot_key(epoch: Epoch) -> Key {
+    super::prefix()
+        .with_segment(VOTING_POWER_SNAPSHOTS_PREFIX_KEY_SEGMENT.to_owned())
+        .with_segment(epoch)
+}
+
 impl From<&Epoch> for Keys<EthereumProof<VotingPowersMap>> {
     fn from(epoch: &Epoch) -> Self {
         let prefix = valset_upds_prefix()
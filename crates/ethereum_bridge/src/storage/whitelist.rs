@@ -14,13 +14,13 @@ use namada_trans_token::storage_key::{denom_key, minted_balance_key};
 
 use super::prefix as ethbridge_key_prefix;
 
-mod segments {
+pub(super) mod segments {
     //! Storage key segments under the token whitelist.
     use namada_core::types::address::Address;
     use namada_macros::StorageKeys;
 
     /// The name of the main storage segment.
-    pub(super) const MAIN_SEGMENT: &str = "whitelist";
+    pub(in crate::storage) const MAIN_SEGMENT: &str = "whitelist";
 
     /// Storage key segments under the token whitelist.
     #[derive(StorageKeys)]
@@ -8,12 +8,13 @@ use serde::{Deserialize, Serialize};
 
 use super::validation::{
     is_valid_author_balance, is_valid_content, is_valid_default_proposal_data,
-    is_valid_end_epoch, is_valid_grace_epoch, is_valid_pgf_funding_data,
+    is_valid_end_epoch, is_valid_grace_epoch,
+    is_valid_ibc_client_recovery_data, is_valid_pgf_funding_data,
     is_valid_pgf_stewards_data, is_valid_proposal_period, is_valid_start_epoch,
     ProposalValidation,
 };
 use crate::parameters::GovernanceParameters;
-use crate::storage::proposal::PGFTarget;
+use crate::storage::proposal::{IbcClientRecovery, PGFTarget};
 
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
@@ -180,6 +181,72 @@ impl TryFrom<&[u8]> for PgfStewardProposal {
     }
 }
 
+/// Ibc client recovery proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IbcClientRecoveryProposal {
+    /// The proposal data
+    pub proposal: OnChainProposal,
+    /// The clients involved in the recovery
+    pub data: IbcClientRecovery,
+}
+
+impl IbcClientRecoveryProposal {
+    /// Validate an Ibc client recovery proposal
+    pub fn validate(
+        self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+        balance: token::Amount,
+        force: bool,
+    ) -> Result<Self, ProposalValidation> {
+        if force {
+            return Ok(self);
+        }
+        is_valid_start_epoch(
+            self.proposal.voting_start_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+        )?;
+        is_valid_end_epoch(
+            self.proposal.voting_start_epoch,
+            self.proposal.voting_end_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_grace_epoch(
+            self.proposal.grace_epoch,
+            self.proposal.voting_end_epoch,
+            governance_parameters.min_proposal_grace_epochs,
+        )?;
+        is_valid_proposal_period(
+            self.proposal.voting_start_epoch,
+            self.proposal.grace_epoch,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_author_balance(
+            balance,
+            governance_parameters.min_proposal_fund,
+        )?;
+        is_valid_content(
+            &self.proposal.content,
+            governance_parameters.max_proposal_content_size,
+        )?;
+        is_valid_ibc_client_recovery_data(&self.data)?;
+
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[u8]> for IbcClientRecoveryProposal {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value)
+    }
+}
+
 /// Pgf funding proposal
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
@@ -6,6 +6,7 @@ use namada_core::types::token;
 use thiserror::Error;
 
 use super::onchain::{PgfFunding, StewardsUpdate};
+use crate::storage::proposal::IbcClientRecovery;
 
 /// This enum raprresent a proposal data
 #[derive(Clone, Debug, PartialEq, Error)]
@@ -69,6 +70,12 @@ pub enum ProposalValidation {
     /// The pgf funding data is not valid
     #[error("invalid proposal extra data: cannot be empty.")]
     InvalidPgfFundingExtraData,
+    /// The ibc client recovery data is not valid
+    #[error(
+        "Invalid proposal extra data: the subject and the substitute clients \
+         must be different."
+    )]
+    InvalidIbcClientRecoveryExtraData,
 }
 
 pub fn is_valid_author_balance(
@@ -250,3 +257,13 @@ pub fn is_valid_pgf_funding_data(
         Err(ProposalValidation::InvalidPgfFundingExtraData)
     }
 }
+
+pub fn is_valid_ibc_client_recovery_data(
+    data: &IbcClientRecovery,
+) -> Result<(), ProposalValidation> {
+    if data.subject_client_id != data.substitute_client_id {
+        Ok(())
+    } else {
+        Err(ProposalValidation::InvalidIbcClientRecoveryExtraData)
+    }
+}
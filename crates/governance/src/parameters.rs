@@ -1,8 +1,134 @@
+use std::fmt::Display;
+
 use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::types::dec::Dec;
 use namada_core::types::token;
 use namada_state::{StorageRead, StorageResult, StorageWrite};
+use serde::{Deserialize, Serialize};
 
 use super::storage::keys as goverance_storage;
+use super::storage::proposal::ProposalType;
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+/// The parameters of the tally of a kind of proposals, as fractions of the
+/// voting power
+pub struct TallyParameters {
+    /// Fraction of the total voting power that must vote (yay, nay or
+    /// abstain) for the proposal to reach the quorum
+    pub quorum: Dec,
+    /// Fraction of the non-abstained voting power that must vote yay for the
+    /// proposal to pass. For the proposals tallied over the total voting
+    /// power, it's the fraction of the total voting power instead.
+    pub threshold: Dec,
+    /// Fraction of the total voting power that must vote and of the
+    /// non-abstained voting power that must vote nay for the proposal to be
+    /// vetoed
+    pub veto: Dec,
+}
+
+impl TallyParameters {
+    /// The tally parameters requiring `threshold` yay votes, with a quorum
+    /// of 1/3 and a veto of 2/3
+    fn with_threshold(threshold: Dec) -> Self {
+        Self {
+            quorum: Dec::one() / 3,
+            threshold,
+            veto: Dec::two() / 3,
+        }
+    }
+
+    /// Check that all the fractions are between 0 and 1
+    pub fn is_valid(&self) -> bool {
+        [self.quorum, self.threshold, self.veto]
+            .iter()
+            .all(|fraction| !fraction.is_negative() && *fraction <= Dec::one())
+    }
+}
+
+impl Display for TallyParameters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "quorum {}, threshold {}, veto {}",
+            self.quorum, self.threshold, self.veto
+        )
+    }
+}
+
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+/// The tally parameters of each type of proposal
+pub struct ProposalTallyParameters {
+    /// Tally parameters of the default proposals
+    pub default: TallyParameters,
+    /// Tally parameters of the PGF stewards proposals
+    pub pgf_steward: TallyParameters,
+    /// Tally parameters of the PGF funding proposals
+    pub pgf_payment: TallyParameters,
+    /// Tally parameters of the IBC client recovery proposals
+    pub ibc_client_recovery: TallyParameters,
+}
+
+impl Default for ProposalTallyParameters {
+    fn default() -> Self {
+        let one_half = Dec::one() / 2;
+        let two_thirds = Dec::two() / 3;
+        Self {
+            default: TallyParameters::with_threshold(two_thirds),
+            pgf_steward: TallyParameters::with_threshold(one_half),
+            pgf_payment: TallyParameters::with_threshold(one_half),
+            ibc_client_recovery: TallyParameters::with_threshold(two_thirds),
+        }
+    }
+}
+
+impl ProposalTallyParameters {
+    /// Select the tally parameters of the given type of proposal
+    pub fn get(&self, proposal_type: &ProposalType) -> TallyParameters {
+        match proposal_type {
+            ProposalType::Default(_) => self.default,
+            ProposalType::PGFSteward(_) => self.pgf_steward,
+            ProposalType::PGFPayment(_) => self.pgf_payment,
+            ProposalType::IbcClientRecovery(_) => self.ibc_client_recovery,
+        }
+    }
+
+    /// Check that the tally parameters of all the types are valid
+    pub fn is_valid(&self) -> bool {
+        [
+            self.default,
+            self.pgf_steward,
+            self.pgf_payment,
+            self.ibc_client_recovery,
+        ]
+        .iter()
+        .all(TallyParameters::is_valid)
+    }
+}
 
 #[derive(
     Clone,
@@ -29,6 +155,11 @@ pub struct GovernanceParameters {
     pub max_proposal_content_size: u64,
     /// Minimum epochs between end and grace epochs
     pub min_proposal_grace_epochs: u64,
+    /// Send the funds of the rejected proposals that didn't reach the quorum
+    /// to the PGF account instead of burning them
+    pub spam_proposal_fund_to_pgf: bool,
+    /// The tally parameters of each type of proposal
+    pub tally_params: ProposalTallyParameters,
 }
 
 impl Default for GovernanceParameters {
@@ -40,6 +171,8 @@ impl Default for GovernanceParameters {
             max_proposal_period: 27,
             max_proposal_content_size: 10_000,
             min_proposal_grace_epochs: 6,
+            spam_proposal_fund_to_pgf: false,
+            tally_params: ProposalTallyParameters::default(),
         }
     }
 }
@@ -57,6 +190,8 @@ impl GovernanceParameters {
             max_proposal_period,
             max_proposal_content_size,
             min_proposal_grace_epochs,
+            spam_proposal_fund_to_pgf,
+            tally_params,
         } = self;
 
         let min_proposal_fund_key =
@@ -88,6 +223,13 @@ impl GovernanceParameters {
         storage
             .write(&min_proposal_grace_epoch_key, min_proposal_grace_epochs)?;
 
+        let spam_fund_to_pgf_key =
+            goverance_storage::get_spam_fund_to_pgf_key();
+        storage.write(&spam_fund_to_pgf_key, spam_proposal_fund_to_pgf)?;
+
+        let tally_params_key = goverance_storage::get_tally_params_key();
+        storage.write(&tally_params_key, tally_params)?;
+
         let counter_key = goverance_storage::get_counter_key();
         storage.write(&counter_key, u64::MIN)
     }
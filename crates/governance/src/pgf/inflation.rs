@@ -7,8 +7,8 @@ use namada_parameters::storage as params_storage;
 use namada_state::{
     DBIter, StorageHasher, StorageRead, StorageResult, WlStorage, DB,
 };
-use namada_trans_token::credit_tokens;
 use namada_trans_token::storage_key::minted_balance_key;
+use namada_trans_token::{credit_tokens, record_epoch_flows};
 
 use crate::pgf::storage::{get_parameters, get_payments, get_stewards};
 use crate::storage::proposal::{PGFIbcTarget, PGFTarget};
@@ -49,6 +49,14 @@ where
         &super::ADDRESS,
         pgf_inflation_amount,
     )?;
+    record_epoch_flows(
+        storage,
+        &staking_token,
+        token::TokenFlows {
+            treasury_inflow: pgf_inflation_amount,
+            ..Default::default()
+        },
+    )?;
 
     tracing::info!(
         "Minting {} tokens for PGF rewards distribution into the PGF account.",
@@ -77,6 +85,14 @@ where
         };
         match result {
             Ok(()) => {
+                record_epoch_flows(
+                    storage,
+                    &staking_token,
+                    token::TokenFlows {
+                        treasury_outflow: funding.detail.amount(),
+                        ..Default::default()
+                    },
+                )?;
                 tracing::info!(
                     "Paying {} tokens for {} project.",
                     funding.detail.amount().to_string_native(),
@@ -1,4 +1,5 @@
 use namada_core::types::address::Address;
+use namada_core::types::key_space::ModuleKeySpace;
 use namada_core::types::storage::{DbKeySeg, Key, KeySeg};
 use namada_macros::StorageKeys;
 use namada_state::collections::{lazy_map, LazyCollection, LazyMap};
@@ -16,6 +17,17 @@ struct Keys {
     steward_inflation_rate: &'static str,
 }
 
+/// The key prefixes reserved by PGF
+pub fn key_space() -> ModuleKeySpace {
+    ModuleKeySpace::new("pgf")
+        .guarded_by(ADDRESS)
+        .reserve_sub_keys(
+            &ADDRESS,
+            Keys::ALL,
+            "Stewards, fundings and PGF parameters",
+        )
+}
+
 /// Obtain a storage key for stewards key
 pub fn stewards_key_prefix() -> Key {
     Key {
@@ -1,4 +1,5 @@
 use namada_core::types::address::Address;
+use namada_core::types::key_space::ModuleKeySpace;
 use namada_core::types::storage::{DbKeySeg, Key, KeySeg};
 use namada_macros::StorageKeys;
 
@@ -16,6 +17,7 @@ struct Keys {
     end_epoch: &'static str,
     grace_epoch: &'static str,
     funds: &'static str,
+    deposit_state: &'static str,
     proposal_code: &'static str,
     committing_epoch: &'static str,
     min_fund: &'static str,
@@ -24,9 +26,47 @@ struct Keys {
     max_period: &'static str,
     max_content: &'static str,
     min_grace_epoch: &'static str,
+    spam_fund_to_pgf: &'static str,
+    tally_params: &'static str,
     counter: &'static str,
     pending: &'static str,
     result: &'static str,
+    vote_proxy: &'static str,
+    proxy_delegators: &'static str,
+}
+
+/// The key prefixes reserved by governance
+pub fn key_space() -> ModuleKeySpace {
+    ModuleKeySpace::new("governance")
+        .guarded_by(ADDRESS)
+        .reserve_sub_keys(
+            &ADDRESS,
+            &[
+                Keys::VALUES.proposal,
+                Keys::VALUES.counter,
+                Keys::VALUES.pending,
+            ],
+            "Proposals",
+        )
+        .reserve_sub_keys(
+            &ADDRESS,
+            &[
+                Keys::VALUES.min_fund,
+                Keys::VALUES.max_code_size,
+                Keys::VALUES.min_period,
+                Keys::VALUES.max_period,
+                Keys::VALUES.max_content,
+                Keys::VALUES.min_grace_epoch,
+                Keys::VALUES.spam_fund_to_pgf,
+                Keys::VALUES.tally_params,
+            ],
+            "Governance parameters",
+        )
+        .reserve_sub_keys(
+            &ADDRESS,
+            &[Keys::VALUES.vote_proxy, Keys::VALUES.proxy_delegators],
+            "Vote proxies",
+        )
 }
 
 /// Check if key is inside governance address space
@@ -54,6 +94,37 @@ pub fn is_vote_key(key: &Key) -> bool {
     }
 }
 
+/// Check if a key is a vote proxy key of a delegator. Returns the address of
+/// the delegator.
+pub fn is_vote_proxy_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(delegator),
+        ] if addr == &ADDRESS && prefix == Keys::VALUES.vote_proxy => {
+            Some(delegator)
+        }
+        _ => None,
+    }
+}
+
+/// Check if a key is the key of a delegator of a vote proxy. Returns the
+/// addresses of the proxy and of the delegator.
+pub fn is_proxy_delegator_key(key: &Key) -> Option<(&Address, &Address)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(proxy),
+            DbKeySeg::AddressSeg(delegator),
+        ] if addr == &ADDRESS && prefix == Keys::VALUES.proxy_delegators => {
+            Some((proxy, delegator))
+        }
+        _ => None,
+    }
+}
+
 /// Check if key is author key
 pub fn is_author_key(key: &Key) -> bool {
     match &key.segments[..] {
@@ -270,6 +341,24 @@ pub fn is_min_grace_epoch_key(key: &Key) -> bool {
                     && min_grace_epoch_param == Keys::VALUES.min_grace_epoch)
 }
 
+/// Check if key is a spam proposal fund destination param key
+pub fn is_spam_fund_to_pgf_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(spam_fund_to_pgf_param),
+                ] if addr == &ADDRESS
+                    && spam_fund_to_pgf_param == Keys::VALUES.spam_fund_to_pgf)
+}
+
+/// Check if key is the tally parameters key
+pub fn is_tally_params_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+                    DbKeySeg::AddressSeg(addr),
+                    DbKeySeg::StringSeg(tally_params),
+                ] if addr == &ADDRESS
+                    && tally_params == Keys::VALUES.tally_params)
+}
+
 /// Check if key is parameter key
 pub fn is_parameter_key(key: &Key) -> bool {
     is_min_proposal_fund_key(key)
@@ -278,6 +367,8 @@ pub fn is_parameter_key(key: &Key) -> bool {
         || is_min_proposal_voting_period_key(key)
         || is_max_proposal_period_key(key)
         || is_min_grace_epoch_key(key)
+        || is_spam_fund_to_pgf_key(key)
+        || is_tally_params_key(key)
 }
 
 /// Check if key is start epoch or end epoch key
@@ -334,6 +425,20 @@ pub fn get_min_proposal_grace_epoch_key() -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get key for the spam proposal fund destination parameter
+pub fn get_spam_fund_to_pgf_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.spam_fund_to_pgf.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the tally parameters key
+pub fn get_tally_params_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.tally_params.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get key of proposal ids counter
 pub fn get_counter_key() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -395,6 +500,15 @@ pub fn get_funds_key(id: u64) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get key of the state of the proposal funds
+pub fn get_deposit_state_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.deposit_state.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get proposal grace epoch key
 pub fn get_grace_epoch_key(id: u64) -> Key {
     proposal_prefix()
@@ -451,6 +565,36 @@ pub fn get_vote_proposal_key(
         .expect("Cannot obtain a storage key")
 }
 
+/// Get the prefix of the vote proxy keys
+pub fn get_vote_proxy_prefix_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.vote_proxy.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the vote proxy of a delegator
+pub fn get_vote_proxy_key(delegator: &Address) -> Key {
+    get_vote_proxy_prefix_key()
+        .push(delegator)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the prefix of the keys of the delegators of a vote proxy
+pub fn get_proxy_delegators_prefix_key(proxy: &Address) -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.proxy_delegators.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(proxy)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of a delegator of a vote proxy
+pub fn get_proxy_delegator_key(proxy: &Address, delegator: &Address) -> Key {
+    get_proxy_delegators_prefix_key(proxy)
+        .push(delegator)
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get the proposal execution key
 pub fn get_proposal_execution_key(id: u64) -> Key {
     Key::from(ADDRESS.to_db_key())
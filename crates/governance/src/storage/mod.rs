@@ -7,20 +7,22 @@ pub mod proposal;
 /// Vote structures
 pub mod vote;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use namada_core::borsh::BorshDeserialize;
 use namada_core::types::address::Address;
 use namada_core::types::storage::Epoch;
 use namada_state::{
-    iter_prefix, StorageError, StorageRead, StorageResult, StorageWrite,
+    iter_prefix, iter_prefix_bytes, StorageError, StorageRead, StorageResult,
+    StorageWrite,
 };
 use namada_trans_token as token;
 
-use crate::parameters::GovernanceParameters;
+use crate::parameters::{GovernanceParameters, ProposalTallyParameters};
 use crate::storage::keys as governance_keys;
 use crate::storage::proposal::{
-    InitProposalData, ProposalType, StorageProposal, VoteProposalData,
+    DepositState, InitProposalData, ProposalDeposit, ProposalType,
+    StorageProposal, VoteProposalData, VoteProxyData,
 };
 use crate::storage::vote::ProposalVote;
 use crate::utils::{ProposalResult, Vote};
@@ -127,6 +129,86 @@ where
     Ok(())
 }
 
+/// A tx to set or remove the vote proxy of a delegator. The proxy votes with
+/// the bonded stake of the delegator on every proposal that the delegator
+/// doesn't vote on directly. The delegators of each proxy are also indexed by
+/// the proxy.
+pub fn update_vote_proxy<S>(
+    storage: &mut S,
+    data: VoteProxyData,
+) -> StorageResult<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if data.proxy.as_ref() == Some(&data.delegator) {
+        return Err(StorageError::new_const(
+            "A delegator cannot be its own vote proxy",
+        ));
+    }
+    let proxy_key = governance_keys::get_vote_proxy_key(&data.delegator);
+    if let Some(old_proxy) = storage.read::<Address>(&proxy_key)? {
+        storage.delete(&governance_keys::get_proxy_delegator_key(
+            &old_proxy,
+            &data.delegator,
+        ))?;
+    }
+    match data.proxy {
+        Some(proxy) => {
+            storage.write(
+                &governance_keys::get_proxy_delegator_key(
+                    &proxy,
+                    &data.delegator,
+                ),
+                (),
+            )?;
+            storage.write(&proxy_key, proxy)
+        }
+        None => storage.delete(&proxy_key),
+    }
+}
+
+/// Read the vote proxy of a delegator, if any
+pub fn get_vote_proxy<S>(
+    storage: &S,
+    delegator: &Address,
+) -> StorageResult<Option<Address>>
+where
+    S: StorageRead,
+{
+    let proxy_key = governance_keys::get_vote_proxy_key(delegator);
+    storage.read(&proxy_key)
+}
+
+/// Read the delegators that have set the given address as their vote proxy
+pub fn get_proxied_delegators<S>(
+    storage: &S,
+    proxy: &Address,
+) -> StorageResult<BTreeSet<Address>>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_proxy_delegators_prefix_key(proxy);
+    let mut delegators = BTreeSet::new();
+    for result in iter_prefix::<()>(storage, &prefix)? {
+        let (key, ()) = result?;
+        if let Some((_, delegator)) =
+            governance_keys::is_proxy_delegator_key(&key)
+        {
+            delegators.insert(delegator.clone());
+        }
+    }
+    Ok(delegators)
+}
+
+/// Check if any delegator has set the given address as their vote proxy
+pub fn is_vote_proxy<S>(storage: &S, proxy: &Address) -> StorageResult<bool>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_proxy_delegators_prefix_key(proxy);
+    Ok(iter_prefix_bytes(storage, &prefix)?.next().is_some())
+}
+
 /// Read a proposal by id from storage
 pub fn get_proposal_by_id<S>(
     storage: &S,
@@ -141,6 +223,8 @@ where
     let end_epoch_key = governance_keys::get_voting_end_epoch_key(id);
     let grace_epoch_key = governance_keys::get_grace_epoch_key(id);
     let proposal_type_key = governance_keys::get_proposal_type_key(id);
+    let funds_key = governance_keys::get_funds_key(id);
+    let deposit_state_key = governance_keys::get_deposit_state_key(id);
 
     let author: Option<Address> = storage.read(&author_key)?;
     let content: Option<BTreeMap<String, String>> = storage.read(&content)?;
@@ -149,6 +233,9 @@ where
     let grace_epoch: Option<Epoch> = storage.read(&grace_epoch_key)?;
     let proposal_type: Option<ProposalType> =
         storage.read(&proposal_type_key)?;
+    let funds: Option<token::Amount> = storage.read(&funds_key)?;
+    let deposit_state: Option<DepositState> =
+        storage.read(&deposit_state_key)?;
 
     let proposal = proposal_type.map(|proposal_type| StorageProposal {
         id,
@@ -158,6 +245,10 @@ where
         voting_start_epoch: voting_start_epoch.unwrap(),
         voting_end_epoch: voting_end_epoch.unwrap(),
         grace_epoch: grace_epoch.unwrap(),
+        deposit: ProposalDeposit {
+            amount: funds.unwrap_or_default(),
+            state: deposit_state.unwrap_or_default(),
+        },
     });
 
     Ok(proposal)
@@ -247,6 +338,16 @@ where
 
     let max_proposal_period: u64 = get_max_proposal_period(storage)?;
 
+    // Not defined in the storage of the chains started before it was added
+    let key = governance_keys::get_spam_fund_to_pgf_key();
+    let spam_proposal_fund_to_pgf: bool =
+        storage.read(&key)?.unwrap_or_default();
+
+    // Not defined in the storage of the chains started before it was added
+    let key = governance_keys::get_tally_params_key();
+    let tally_params: ProposalTallyParameters =
+        storage.read(&key)?.unwrap_or_default();
+
     Ok(GovernanceParameters {
         min_proposal_fund,
         max_proposal_code_size,
@@ -254,6 +355,8 @@ where
         max_proposal_period,
         max_proposal_content_size,
         min_proposal_grace_epochs,
+        spam_proposal_fund_to_pgf,
+        tally_params,
     })
 }
 
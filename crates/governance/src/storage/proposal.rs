@@ -2,7 +2,9 @@ use std::collections::{BTreeMap, HashSet};
 use std::fmt::Display;
 
 use borsh::{BorshDeserialize, BorshSerialize};
-use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
+use namada_core::ibc::core::host::types::identifiers::{
+    ChannelId, ClientId, PortId,
+};
 use namada_core::types::address::Address;
 use namada_core::types::hash::Hash;
 use namada_core::types::storage::Epoch;
@@ -12,8 +14,9 @@ use thiserror::Error;
 
 use super::vote::ProposalVote;
 use crate::cli::onchain::{
-    DefaultProposal, PgfAction, PgfContinuous, PgfFundingProposal, PgfRetro,
-    PgfSteward, PgfStewardProposal, StewardsUpdate,
+    DefaultProposal, IbcClientRecoveryProposal, PgfAction, PgfContinuous,
+    PgfFundingProposal, PgfRetro, PgfSteward, PgfStewardProposal,
+    StewardsUpdate,
 };
 use crate::utils::{ProposalStatus, TallyType};
 
@@ -82,6 +85,24 @@ pub struct VoteProposalData {
     pub delegations: Vec<Address>,
 }
 
+/// A tx data type to set or unset the vote proxy of a delegator
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct VoteProxyData {
+    /// The delegator whose bonded stake is voted with
+    pub delegator: Address,
+    /// The address voting on behalf of the delegator, or `None` to remove
+    /// the current proxy
+    pub proxy: Option<Address>,
+}
+
 impl TryFrom<DefaultProposal> for InitProposalData {
     type Error = ProposalError;
 
@@ -117,6 +138,22 @@ impl TryFrom<PgfStewardProposal> for InitProposalData {
     }
 }
 
+impl TryFrom<IbcClientRecoveryProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: IbcClientRecoveryProposal) -> Result<Self, Self::Error> {
+        Ok(InitProposalData {
+            id: value.proposal.id,
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::IbcClientRecovery(value.data),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            grace_epoch: value.proposal.grace_epoch,
+        })
+    }
+}
+
 impl TryFrom<PgfFundingProposal> for InitProposalData {
     type Error = ProposalError;
 
@@ -200,6 +237,8 @@ pub enum ProposalType {
     PGFSteward(HashSet<AddRemove<Address>>),
     /// PGF funding proposal
     PGFPayment(Vec<PGFAction>),
+    /// IBC client recovery proposal
+    IbcClientRecovery(IbcClientRecovery),
 }
 
 /// An add or remove action for PGF
@@ -360,6 +399,73 @@ impl borsh::BorshSchema for PGFIbcTarget {
     }
 }
 
+/// The IBC clients involved in the recovery of a frozen or expired client
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IbcClientRecovery {
+    /// The frozen or expired client to be recovered
+    pub subject_client_id: ClientId,
+    /// The active client whose state replaces the state of the subject
+    /// client
+    pub substitute_client_id: ClientId,
+}
+
+impl BorshSerialize for IbcClientRecovery {
+    fn serialize<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.subject_client_id.to_string(), writer)?;
+        BorshSerialize::serialize(
+            &self.substitute_client_id.to_string(),
+            writer,
+        )
+    }
+}
+
+impl borsh::BorshDeserialize for IbcClientRecovery {
+    fn deserialize_reader<R: std::io::Read>(
+        reader: &mut R,
+    ) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+        let mut client_id = || -> std::io::Result<ClientId> {
+            let client_id: String =
+                BorshDeserialize::deserialize_reader(reader)?;
+            client_id.parse().map_err(|err| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Error decoding client ID: {}", err),
+                )
+            })
+        };
+        let subject_client_id = client_id()?;
+        let substitute_client_id = client_id()?;
+        Ok(Self {
+            subject_client_id,
+            substitute_client_id,
+        })
+    }
+}
+
+impl borsh::BorshSchema for IbcClientRecovery {
+    fn add_definitions_recursively(
+        definitions: &mut BTreeMap<
+            borsh::schema::Declaration,
+            borsh::schema::Definition,
+        >,
+    ) {
+        let fields = borsh::schema::Fields::NamedFields(vec![
+            ("subject_client_id".into(), String::declaration()),
+            ("substitute_client_id".into(), String::declaration()),
+        ]);
+        let definition = borsh::schema::Definition::Struct { fields };
+        definitions.insert(Self::declaration(), definition);
+    }
+
+    fn declaration() -> borsh::schema::Declaration {
+        std::any::type_name::<Self>().into()
+    }
+}
+
 /// The actions that a PGF Steward can propose to execute
 #[derive(
     Debug,
@@ -390,6 +496,9 @@ impl Display for ProposalType {
             ProposalType::Default(_) => write!(f, "Default"),
             ProposalType::PGFSteward(_) => write!(f, "Pgf steward"),
             ProposalType::PGFPayment(_) => write!(f, "Pgf funding"),
+            ProposalType::IbcClientRecovery(_) => {
+                write!(f, "Ibc client recovery")
+            }
         }
     }
 }
@@ -464,6 +573,56 @@ pub struct StorageProposal {
     pub voting_end_epoch: Epoch,
     /// The epoch from which this changes are executed
     pub grace_epoch: Epoch,
+    /// The funds locked by the proposal
+    pub deposit: ProposalDeposit,
+}
+
+/// The funds locked by a proposal at its submission
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize,
+)]
+pub struct ProposalDeposit {
+    /// The locked amount
+    pub amount: Amount,
+    /// What happened to the locked amount
+    pub state: DepositState,
+}
+
+/// The state of the funds locked by a proposal. When the voting ends, the
+/// funds are refunded to the author if the proposal passed or reached the
+/// quorum, or else they're burned or sent to the PGF account, depending on
+/// the governance parameters.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize,
+)]
+pub enum DepositState {
+    /// Locked until the proposal is tallied
+    #[default]
+    Locked,
+    /// Refunded to the author of the proposal
+    Refunded,
+    /// Burned as the proposal was rejected without reaching the quorum
+    Burned,
+    /// Sent to the PGF account as the proposal was rejected without reaching
+    /// the quorum
+    SentToPgf,
+}
+
+impl Display for DepositState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DepositState::Locked => write!(f, "locked"),
+            DepositState::Refunded => write!(f, "refunded"),
+            DepositState::Burned => write!(f, "burned"),
+            DepositState::SentToPgf => write!(f, "sent to PGF"),
+        }
+    }
+}
+
+impl Display for ProposalDeposit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({})", self.amount.to_string_native(), self.state)
+    }
 }
 
 impl StorageProposal {
@@ -513,6 +672,7 @@ impl StorageProposal {
         {:2}End Epoch: {}
         {:2}Grace Epoch: {}
         {:2}Status: {}
+        {:2}Deposit: {}
         ",
             self.id,
             "",
@@ -528,7 +688,9 @@ impl StorageProposal {
             "",
             self.grace_epoch,
             "",
-            self.get_status(current_epoch)
+            self.get_status(current_epoch),
+            "",
+            self.deposit
         )
     }
 }
@@ -605,6 +767,24 @@ pub mod testing {
             .prop_union(arb_pgf_target().prop_map(PGFAction::Retro).boxed())
     }
 
+    prop_compose! {
+        /// Generate an arbitrary IBC client recovery
+        pub fn arb_ibc_client_recovery()(
+            subject in any::<u64>(),
+            substitute in any::<u64>(),
+        ) -> IbcClientRecovery {
+            let client_id = |counter: u64| {
+                format!("07-tendermint-{counter}")
+                    .parse()
+                    .expect("the client ID should be valid")
+            };
+            IbcClientRecovery {
+                subject_client_id: client_id(subject),
+                substitute_client_id: client_id(substitute),
+            }
+        }
+    }
+
     /// Generate an arbitrary proposal type
     pub fn arb_proposal_type() -> impl Strategy<Value = ProposalType> {
         option::of(arb_hash())
@@ -621,6 +801,9 @@ pub mod testing {
             .or(collection::vec(arb_pgf_action(), 0..10)
                 .prop_map(ProposalType::PGFPayment)
                 .boxed())
+            .or(arb_ibc_client_recovery()
+                .prop_map(ProposalType::IbcClientRecovery)
+                .boxed())
     }
 
     prop_compose! {
@@ -7,6 +7,7 @@ use namada_core::types::storage::Epoch;
 use namada_core::types::token;
 
 use super::cli::offline::OfflineVote;
+use super::parameters::TallyParameters;
 use super::storage::proposal::ProposalType;
 use super::storage::vote::ProposalVote;
 
@@ -58,17 +59,19 @@ impl Vote {
     }
 }
 
-/// Represent a tally type
+/// Represent a tally type. The fractions are the ones of the default
+/// [`TallyParameters`], the actual ones are selected by proposal type.
 #[derive(Copy, Clone, BorshSerialize, BorshDeserialize)]
 pub enum TallyType {
-    /// Represent a tally type for proposal requiring 2/3 of the total voting
-    /// power to be yay
+    /// Represent a tally type for proposal requiring 2/3 (the threshold) of
+    /// the total voting power to be yay
     TwoThirds,
-    /// Represent a tally type for proposal requiring 1/2 of yay votes over at
-    /// least 1/3 of the voting power
+    /// Represent a tally type for proposal requiring 1/2 (the threshold) of
+    /// yay votes over at least 1/3 (the quorum) of the voting power
     OneHalfOverOneThird,
-    /// Represent a tally type for proposal requiring less than 1/2 of nay
-    /// votes over at least 1/3 of the voting power
+    /// Represent a tally type for proposal requiring less than 1/2 (the
+    /// threshold) of nay votes over at least 1/3 (the quorum) of the voting
+    /// power
     LessOneHalfOverOneThirdNay,
 }
 
@@ -78,6 +81,7 @@ impl TallyType {
         match (proposal_type, is_steward) {
             (ProposalType::Default(_), _) => TallyType::TwoThirds,
             (ProposalType::PGFSteward(_), _) => TallyType::OneHalfOverOneThird,
+            (ProposalType::IbcClientRecovery(_), _) => TallyType::TwoThirds,
             (ProposalType::PGFPayment(_), true) => {
                 TallyType::LessOneHalfOverOneThirdNay
             }
@@ -110,38 +114,32 @@ impl TallyResult {
     /// Create a new tally result
     pub fn new(
         tally_type: &TallyType,
+        tally_params: &TallyParameters,
         yay_voting_power: VotePower,
         nay_voting_power: VotePower,
         abstain_voting_power: VotePower,
         total_voting_power: VotePower,
     ) -> Self {
+        let voted_power = Self::get_total_voted_power(
+            yay_voting_power,
+            nay_voting_power,
+            abstain_voting_power,
+        );
+        let reached_quorum =
+            voted_power >= tally_params.quorum * total_voting_power;
+        let yay_threshold =
+            tally_params.threshold * (yay_voting_power + nay_voting_power);
         let passed = match tally_type {
             TallyType::TwoThirds => {
-                yay_voting_power >= total_voting_power * 2 / 3
+                yay_voting_power >= tally_params.threshold * total_voting_power
             }
             TallyType::OneHalfOverOneThird => {
-                let at_least_one_third_voted = Self::get_total_voted_power(
-                    yay_voting_power,
-                    nay_voting_power,
-                    abstain_voting_power,
-                ) >= total_voting_power / 3;
-
-                // At least half of non-abstained votes are yay
-                let at_last_half_voted_yay =
-                    yay_voting_power >= nay_voting_power;
-                at_least_one_third_voted && at_last_half_voted_yay
+                // The threshold of non-abstained votes are yay
+                reached_quorum && yay_voting_power >= yay_threshold
             }
             TallyType::LessOneHalfOverOneThirdNay => {
-                let less_one_third_voted = Self::get_total_voted_power(
-                    yay_voting_power,
-                    nay_voting_power,
-                    abstain_voting_power,
-                ) < total_voting_power / 3;
-
-                // More than half of non-abstained votes are yay
-                let more_than_half_voted_yay =
-                    yay_voting_power > nay_voting_power;
-                less_one_third_voted || more_than_half_voted_yay
+                // More than the threshold of non-abstained votes are yay
+                !reached_quorum || yay_voting_power > yay_threshold
             }
         };
 
@@ -164,6 +162,8 @@ pub struct ProposalResult {
     pub result: TallyResult,
     /// The type of tally required for this proposal
     pub tally_type: TallyType,
+    /// The tally parameters of the type of the proposal
+    pub tally_params: TallyParameters,
     /// The total voting power during the proposal tally
     pub total_voting_power: VotePower,
     /// The total voting power from yay votes
@@ -175,28 +175,40 @@ pub struct ProposalResult {
 }
 
 impl ProposalResult {
-    /// Return true if at least 1/3 of the total voting power voted and at least
-    /// two third of the non-abstained voting power voted nay
-    pub fn two_thirds_nay_over_two_thirds_total(&self) -> bool {
-        let at_least_two_thirds_voted = self.total_yay_power
+    /// Return true if at least the quorum of the total voting power voted, in
+    /// which case the funds locked by the proposal are refunded even if it's
+    /// rejected
+    pub fn reached_quorum(&self) -> bool {
+        self.total_yay_power + self.total_nay_power + self.total_abstain_power
+            >= self.tally_params.quorum * self.total_voting_power
+    }
+
+    /// Return true if at least the veto fraction of the total voting power
+    /// voted and at least the veto fraction of the non-abstained voting power
+    /// voted nay
+    pub fn is_vetoed(&self) -> bool {
+        let veto = self.tally_params.veto;
+        let at_least_veto_voted = self.total_yay_power
             + self.total_nay_power
             + self.total_abstain_power
-            >= self.total_voting_power * 2 / 3;
+            >= veto * self.total_voting_power;
 
-        let at_least_two_thirds_nay = self.total_nay_power
-            >= (self.total_nay_power + self.total_yay_power) * 2 / 3;
+        let at_least_veto_nay = self.total_nay_power
+            >= veto * (self.total_nay_power + self.total_yay_power);
 
-        at_least_two_thirds_voted && at_least_two_thirds_nay
+        at_least_veto_voted && at_least_veto_nay
     }
 }
 
 impl Display for ProposalResult {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let threshold = match self.tally_type {
-            TallyType::TwoThirds => self.total_voting_power / 3 * 2,
+            TallyType::TwoThirds => {
+                self.tally_params.threshold * self.total_voting_power
+            }
             _ => {
-                let threshold_one_third = self.total_voting_power / 3;
-                threshold_one_third / 2
+                let quorum = self.tally_params.quorum * self.total_voting_power;
+                self.tally_params.threshold * quorum
             }
         };
 
@@ -341,6 +353,7 @@ pub fn compute_proposal_result(
     votes: ProposalVotes,
     total_voting_power: VotePower,
     tally_type: TallyType,
+    tally_params: TallyParameters,
 ) -> ProposalResult {
     let mut yay_voting_power = VotePower::default();
     let mut nay_voting_power = VotePower::default();
@@ -382,6 +395,7 @@ pub fn compute_proposal_result(
                             return ProposalResult {
                                 result: TallyResult::Rejected,
                                 tally_type,
+                                tally_params,
                                 total_voting_power: VotePower::default(),
                                 total_yay_power: VotePower::default(),
                                 total_nay_power: VotePower::default(),
@@ -425,6 +439,7 @@ pub fn compute_proposal_result(
 
     let tally_result = TallyResult::new(
         &tally_type,
+        &tally_params,
         yay_voting_power,
         nay_voting_power,
         abstain_voting_power,
@@ -434,6 +449,7 @@ pub fn compute_proposal_result(
     ProposalResult {
         result: tally_result,
         tally_type,
+        tally_params,
         total_voting_power,
         total_yay_power: yay_voting_power,
         total_nay_power: nay_voting_power,
@@ -6,7 +6,11 @@ use std::rc::Rc;
 use namada_core::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
 use namada_core::ibc::apps::transfer::types::packet::PacketData;
 use namada_core::ibc::apps::transfer::types::PrefixedCoin;
+use namada_core::ibc::clients::tendermint::client_state::ClientState as TmClientState;
+use namada_core::ibc::clients::tendermint::types::ClientState as TmClientStateType;
 use namada_core::ibc::core::channel::types::timeout::TimeoutHeight;
+use namada_core::ibc::core::client::context::consensus_state::ConsensusState;
+use namada_core::ibc::core::host::types::identifiers::ClientId;
 use namada_core::ibc::primitives::Msg;
 use namada_core::tendermint::Time as TmTime;
 use namada_core::types::address::{Address, InternalAddress};
@@ -26,6 +30,7 @@ use namada_state::{
 use namada_storage::StorageRead;
 use namada_trans_token as token;
 
+use crate::context::client::AnyClientState;
 use crate::{IbcActions, IbcCommonContext, IbcStorageContext};
 
 /// IBC protocol context
@@ -188,3 +193,121 @@ where
     let mut actions = IbcActions::new(Rc::new(RefCell::new(ctx)));
     actions.execute(&data).into_storage_result()
 }
+
+/// Recover a frozen or expired IBC client by substituting its state with the
+/// state of an active client tracking the same counterparty chain. The
+/// subject client keeps its ID, so that the connections and channels built
+/// on it, and the tokens escrowed through them, are usable again.
+pub fn recover_client<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    subject_client_id: &ClientId,
+    substitute_client_id: &ClientId,
+) -> StorageResult<()>
+where
+    D: DB + for<'iter> DBIter<'iter> + 'static,
+    H: StorageHasher + 'static,
+{
+    let mut ctx = IbcProtocolContext { wl_storage };
+
+    let subject = read_tm_client_state(&ctx, subject_client_id)?;
+    let substitute = read_tm_client_state(&ctx, substitute_client_id)?;
+
+    if !subject.is_frozen()
+        && !is_client_expired(&ctx, subject_client_id, &subject)?
+    {
+        return Err(StorageError::new(format!(
+            "The subject client {subject_client_id} is neither frozen nor \
+             expired"
+        )));
+    }
+    if substitute.is_frozen()
+        || is_client_expired(&ctx, substitute_client_id, &substitute)?
+    {
+        return Err(StorageError::new(format!(
+            "The substitute client {substitute_client_id} isn't active"
+        )));
+    }
+    if substitute.latest_height <= subject.latest_height {
+        return Err(StorageError::new(format!(
+            "The latest height of the substitute client \
+             {substitute_client_id} should be greater than the one of the \
+             subject client {subject_client_id}"
+        )));
+    }
+
+    // Only the chain ID, the trusting period and the heights are allowed to
+    // differ between the subject and the substitute
+    let mut recovered = subject;
+    recovered.chain_id = substitute.chain_id.clone();
+    recovered.trusting_period = substitute.trusting_period;
+    recovered.latest_height = substitute.latest_height;
+    recovered.frozen_height = None;
+    if recovered != substitute {
+        return Err(StorageError::new(format!(
+            "The parameters of the substitute client {substitute_client_id} \
+             don't match the ones of the subject client {subject_client_id}"
+        )));
+    }
+
+    let height = recovered.latest_height;
+    let consensus_state = ctx
+        .consensus_state(substitute_client_id, height)
+        .into_storage_result()?;
+    let update_time = ctx
+        .client_update_time(substitute_client_id)
+        .into_storage_result()?;
+    let update_height = ctx
+        .client_update_height(substitute_client_id)
+        .into_storage_result()?;
+
+    ctx.store_client_state(
+        subject_client_id,
+        TmClientState::from(recovered).into(),
+    )
+    .into_storage_result()?;
+    ctx.store_consensus_state(subject_client_id, height, consensus_state)
+        .into_storage_result()?;
+    ctx.store_update_time(subject_client_id, update_time)
+        .into_storage_result()?;
+    ctx.store_update_height(subject_client_id, update_height)
+        .into_storage_result()
+}
+
+/// Read a Tendermint client state
+fn read_tm_client_state<D, H>(
+    ctx: &IbcProtocolContext<'_, D, H>,
+    client_id: &ClientId,
+) -> StorageResult<TmClientStateType>
+where
+    D: DB + for<'iter> DBIter<'iter> + 'static,
+    H: StorageHasher + 'static,
+{
+    match ctx.client_state(client_id).into_storage_result()? {
+        AnyClientState::Tendermint(cs) => Ok(cs.inner().clone()),
+        #[cfg(feature = "testing")]
+        _ => Err(StorageError::new(format!(
+            "The client {client_id} isn't a Tendermint client"
+        ))),
+    }
+}
+
+/// Check if the client has expired, i.e. its latest consensus state is older
+/// than the trusting period
+fn is_client_expired<D, H>(
+    ctx: &IbcProtocolContext<'_, D, H>,
+    client_id: &ClientId,
+    client_state: &TmClientStateType,
+) -> StorageResult<bool>
+where
+    D: DB + for<'iter> DBIter<'iter> + 'static,
+    H: StorageHasher + 'static,
+{
+    let consensus_state = ctx
+        .consensus_state(client_id, client_state.latest_height)
+        .into_storage_result()?;
+    let now = ctx.host_timestamp().into_storage_result()?;
+    let elapsed = now
+        .duration_since(&consensus_state.timestamp())
+        .unwrap_or_default();
+    Ok(client_state.expired(elapsed))
+}
@@ -33,6 +33,7 @@ use sha2::Digest;
 
 use super::client::{AnyClientState, AnyConsensusState};
 use super::storage::IbcStorageContext;
+use crate::forward::PendingForward;
 use crate::storage;
 
 /// Result of IBC common function call
@@ -668,4 +669,60 @@ pub trait IbcCommonContext: IbcStorageContext {
         }
         Ok(())
     }
+
+    /// Write the IBC denom with its full trace path by the trace hash
+    fn store_ibc_trace(
+        &mut self,
+        trace_hash: impl AsRef<str>,
+        denom: impl AsRef<str>,
+    ) -> Result<()> {
+        let key = storage::ibc_trace_key(trace_hash);
+        let has_key = self.has_key(&key).map_err(|_| ChannelError::Other {
+            description: format!("Reading the IBC trace failed: Key {key}"),
+        })?;
+        if !has_key {
+            self.write(&key, denom.as_ref()).map_err(|_| {
+                ChannelError::Other {
+                    description: format!(
+                        "Writing the IBC trace failed: Key {key}",
+                    ),
+                }
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Get the pending packet sent by the forwarding
+    fn pending_forward(
+        &self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<Option<PendingForward>> {
+        let key = storage::forward_key(port_id, channel_id, sequence);
+        self.read(&key).map_err(ContextError::from)
+    }
+
+    /// Store the pending packet sent by the forwarding
+    fn store_pending_forward(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+        pending: PendingForward,
+    ) -> Result<()> {
+        let key = storage::forward_key(port_id, channel_id, sequence);
+        self.write(&key, pending).map_err(ContextError::from)
+    }
+
+    /// Delete the pending packet sent by the forwarding
+    fn delete_pending_forward(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        sequence: Sequence,
+    ) -> Result<()> {
+        let key = storage::forward_key(port_id, channel_id, sequence);
+        self.delete(&key).map_err(ContextError::from)
+    }
 }
@@ -16,8 +16,11 @@ use namada_core::ibc::apps::transfer::module::{
     on_timeout_packet_validate,
 };
 use namada_core::ibc::apps::transfer::types::error::TokenTransferError;
+use namada_core::ibc::apps::transfer::types::packet::PacketData;
 use namada_core::ibc::apps::transfer::types::MODULE_ID_STR;
-use namada_core::ibc::core::channel::types::acknowledgement::Acknowledgement;
+use namada_core::ibc::core::channel::types::acknowledgement::{
+    Acknowledgement, AcknowledgementStatus, StatusValue,
+};
 use namada_core::ibc::core::channel::types::channel::{Counterparty, Order};
 use namada_core::ibc::core::channel::types::error::{
     ChannelError, PacketError,
@@ -30,9 +33,11 @@ use namada_core::ibc::core::host::types::identifiers::{
 use namada_core::ibc::core::router::module::Module;
 use namada_core::ibc::core::router::types::module::{ModuleExtras, ModuleId};
 use namada_core::ibc::primitives::Signer;
+use namada_core::types::address::{Address, InternalAddress};
 
 use super::common::IbcCommonContext;
 use super::token_transfer::TokenTransferContext;
+use crate::forward::parse_forward;
 
 /// IBC module wrapper for getting the reference of the module
 pub trait ModuleWrapper: Module {
@@ -266,7 +271,18 @@ where
         packet: &Packet,
         _relayer: &Signer,
     ) -> (ModuleExtras, Acknowledgement) {
-        on_recv_packet_execute(&mut self.ctx, packet)
+        match forwarded_packet(packet) {
+            Ok(Some(packet)) => on_recv_packet_execute(&mut self.ctx, &packet),
+            Ok(None) => on_recv_packet_execute(&mut self.ctx, packet),
+            Err(e) => {
+                let status = StatusValue::new(e)
+                    .expect("The error message shouldn't be empty");
+                (
+                    ModuleExtras::empty(),
+                    AcknowledgementStatus::error(status).into(),
+                )
+            }
+        }
     }
 
     fn on_acknowledgement_packet_validate(
@@ -319,6 +335,25 @@ where
     }
 }
 
+/// Replace the receiver of a packet to be forwarded with the IBC escrow, which
+/// sends the received tokens to the next chain. Returns `None` if the packet
+/// isn't forwarded, or an error if its forwarding metadata is invalid.
+fn forwarded_packet(packet: &Packet) -> Result<Option<Packet>, String> {
+    let mut data: PacketData = match serde_json::from_slice(&packet.data) {
+        Ok(data) => data,
+        // The transfer module rejects the packet
+        Err(_) => return Ok(None),
+    };
+    if parse_forward(data.memo.as_ref())?.is_none() {
+        return Ok(None);
+    }
+    data.receiver = Address::Internal(InternalAddress::Ibc).to_string().into();
+    let mut packet = packet.clone();
+    packet.data = serde_json::to_vec(&data)
+        .expect("Encoding the packet data shouldn't fail");
+    Ok(Some(packet))
+}
+
 fn into_channel_error(error: TokenTransferError) -> ChannelError {
     ChannelError::AppModule {
         description: error.to_string(),
@@ -0,0 +1,243 @@
+//! Forwarding of the tokens received over IBC to another chain.
+//!
+//! A fungible token packet whose memo is a JSON object with a `forward` field
+//! is forwarded to the next chain in the same tx that receives it:
+//!
+//! ```json
+//! {
+//!   "forward": {
+//!     "receiver": "<the receiver on the next chain>",
+//!     "port": "transfer",
+//!     "channel": "<the channel on this chain to the next chain>",
+//!     "timeout": "10m",
+//!     "retries": 2,
+//!     "next": { "forward": { ... } }
+//!   }
+//! }
+//! ```
+//!
+//! The receiver of the packet on this chain is ignored. The tokens are
+//! credited to the IBC escrow and sent from there to the next chain, with
+//! `next` as the memo of the forwarded packet to continue a multi-hop
+//! transfer. The `timeout` is relative to the time of the block, either a
+//! duration like `"1h30m"` or a number of nanoseconds, and defaults to
+//! [`DEFAULT_TIMEOUT`].
+//!
+//! The acknowledgement of the received packet is written when it's received,
+//! so a failure of the forwarded packet can't be reported to the sender chain
+//! with an error acknowledgement. Instead, a forwarded packet that times out
+//! or is acknowledged with an error is sent again up to `retries` times, and
+//! the tokens are then refunded to the original sender with a transfer back
+//! over the channel they were received on. A refund that fails is sent again.
+
+use std::time::Duration;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
+use namada_core::ibc::primitives::Signer;
+use serde::Deserialize;
+use serde_json::Value;
+
+/// The timeout of the forwarded packets when not given in the metadata
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// The forwarding metadata of a received packet
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forward {
+    /// The receiver on the next chain
+    pub receiver: Signer,
+    /// The port on this chain to send the tokens from
+    pub port_id: PortId,
+    /// The channel on this chain to send the tokens over
+    pub channel_id: ChannelId,
+    /// The timeout of the forwarded packet, relative to the block time
+    pub timeout: Duration,
+    /// The number of times a failed forwarded packet is sent again
+    pub retries: u8,
+    /// The memo of the forwarded packet
+    pub next: Option<String>,
+}
+
+/// The state of a packet sent by the forwarding, stored until the packet is
+/// acknowledged or times out
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct PendingForward {
+    /// The port on this chain that the tokens were received on
+    pub port_id: String,
+    /// The channel on this chain that the tokens were received on
+    pub channel_id: String,
+    /// The sender of the received tokens on the previous chain
+    pub sender: String,
+    /// The number of times the packet can still be sent again
+    pub retries: u8,
+    /// The timeout of the sent packets in nanoseconds
+    pub timeout_nanos: u64,
+    /// Whether the packet refunds the sender
+    pub is_refund: bool,
+}
+
+#[derive(Deserialize)]
+struct RawForward {
+    receiver: String,
+    port: String,
+    channel: String,
+    #[serde(default)]
+    timeout: Option<RawTimeout>,
+    #[serde(default)]
+    retries: Option<u8>,
+    #[serde(default)]
+    next: Option<Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawTimeout {
+    Nanos(u64),
+    Duration(String),
+}
+
+/// Get the forwarding metadata from the memo of a packet. Returns `None` if
+/// the memo isn't a JSON object with a `forward` field, or an error if the
+/// metadata is invalid.
+pub fn parse_forward(memo: &str) -> Result<Option<Forward>, String> {
+    let memo: Value = match serde_json::from_str(memo) {
+        Ok(memo) => memo,
+        Err(_) => return Ok(None),
+    };
+    let forward = match memo.get("forward") {
+        Some(forward) => forward.clone(),
+        None => return Ok(None),
+    };
+    let raw: RawForward = serde_json::from_value(forward)
+        .map_err(|e| format!("Invalid forwarding metadata: {e}"))?;
+    if raw.receiver.is_empty() {
+        return Err("The forwarding receiver is empty".to_string());
+    }
+    let port_id: PortId = raw
+        .port
+        .parse()
+        .map_err(|e| format!("Invalid forwarding port: {e}"))?;
+    let channel_id: ChannelId = raw
+        .channel
+        .parse()
+        .map_err(|e| format!("Invalid forwarding channel: {e}"))?;
+    let timeout = match raw.timeout {
+        Some(RawTimeout::Nanos(nanos)) => Duration::from_nanos(nanos),
+        Some(RawTimeout::Duration(duration)) => parse_duration(&duration)
+            .ok_or_else(|| format!("Invalid forwarding timeout: {duration}"))?,
+        None => DEFAULT_TIMEOUT,
+    };
+    let next = raw.next.map(|next| match next {
+        Value::String(next) => next,
+        next => next.to_string(),
+    });
+    Ok(Some(Forward {
+        receiver: raw.receiver.into(),
+        port_id,
+        channel_id,
+        timeout,
+        retries: raw.retries.unwrap_or_default(),
+        next,
+    }))
+}
+
+/// Parse a duration like `"1h30m"`, with the units `h`, `m`, `s`, `ms`, `us`
+/// and `ns`
+fn parse_duration(duration: &str) -> Option<Duration> {
+    if duration.is_empty() {
+        return None;
+    }
+    let mut total = Duration::ZERO;
+    let mut rest = duration;
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let value: u64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let nanos_per_unit: u64 = match &rest[..unit] {
+            "h" => 3_600_000_000_000,
+            "m" => 60_000_000_000,
+            "s" => 1_000_000_000,
+            "ms" => 1_000_000,
+            "us" | "µs" => 1_000,
+            "ns" => 1,
+            _ => return None,
+        };
+        let nanos = value.checked_mul(nanos_per_unit)?;
+        total = total.checked_add(Duration::from_nanos(nanos))?;
+        rest = &rest[unit..];
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_forward() {
+        // Not forwarding
+        assert_eq!(parse_forward("memo"), Ok(None));
+        assert_eq!(parse_forward("{\"wasm\":{}}"), Ok(None));
+
+        let memo = r#"{
+            "forward": {
+                "receiver": "cosmos1receiver",
+                "port": "transfer",
+                "channel": "channel-1",
+                "next": {"forward": {"receiver": "osmo1receiver"}}
+            }
+        }"#;
+        let forward = parse_forward(memo).unwrap().unwrap();
+        assert_eq!(forward.receiver, "cosmos1receiver".to_string().into());
+        assert_eq!(forward.port_id, PortId::transfer());
+        assert_eq!(forward.channel_id, ChannelId::new(1));
+        assert_eq!(forward.timeout, DEFAULT_TIMEOUT);
+        assert_eq!(forward.retries, 0);
+        assert_eq!(
+            forward.next.as_deref(),
+            Some(r#"{"forward":{"receiver":"osmo1receiver"}}"#)
+        );
+
+        let memo = r#"{"forward": {"receiver": "a", "port": "transfer",
+            "channel": "channel-0", "timeout": "1m30s", "retries": 2}}"#;
+        let forward = parse_forward(memo).unwrap().unwrap();
+        assert_eq!(forward.timeout, Duration::from_secs(90));
+        assert_eq!(forward.retries, 2);
+        assert_eq!(forward.next, None);
+
+        let memo = r#"{"forward": {"receiver": "a", "port": "transfer",
+            "channel": "channel-0", "timeout": 1000}}"#;
+        let forward = parse_forward(memo).unwrap().unwrap();
+        assert_eq!(forward.timeout, Duration::from_nanos(1000));
+
+        // Invalid metadata
+        for memo in [
+            r#"{"forward": {}}"#,
+            r#"{"forward": {"receiver": "", "port": "transfer",
+                "channel": "channel-0"}}"#,
+            r#"{"forward": {"receiver": "a", "port": "transfer",
+                "channel": "invalid"}}"#,
+            r#"{"forward": {"receiver": "a", "port": "transfer",
+                "channel": "channel-0", "timeout": "10 minutes"}}"#,
+        ] {
+            assert!(parse_forward(memo).is_err(), "{memo}");
+        }
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("10m"), Some(Duration::from_secs(600)));
+        assert_eq!(
+            parse_duration("1h1s500ms"),
+            Some(Duration::from_millis(3_601_500))
+        );
+        assert_eq!(parse_duration("1ns"), Some(Duration::from_nanos(1)));
+        assert_eq!(parse_duration(""), None);
+        assert_eq!(parse_duration("10"), None);
+        assert_eq!(parse_duration("m"), None);
+        assert_eq!(parse_duration("1d"), None);
+    }
+}
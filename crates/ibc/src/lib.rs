@@ -2,14 +2,16 @@
 
 mod actions;
 pub mod context;
+pub mod forward;
 pub mod storage;
 
 use std::cell::RefCell;
 use std::fmt::Debug;
 use std::rc::Rc;
 use std::str::FromStr;
+use std::time::Duration;
 
-pub use actions::transfer_over_ibc;
+pub use actions::{recover_client, transfer_over_ibc};
 use borsh::BorshDeserialize;
 pub use context::common::IbcCommonContext;
 use context::router::IbcRouter;
@@ -18,15 +20,20 @@ pub use context::token_transfer::TokenTransferContext;
 pub use context::transfer_mod::{ModuleWrapper, TransferModule};
 use context::IbcContext;
 pub use context::ValidationParams;
+use forward::{parse_forward, PendingForward};
 use namada_core::ibc::apps::transfer::handler::{
     send_transfer_execute, send_transfer_validate,
 };
 use namada_core::ibc::apps::transfer::types::error::TokenTransferError;
 use namada_core::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
+use namada_core::ibc::apps::transfer::types::packet::PacketData;
 use namada_core::ibc::apps::transfer::types::{
-    is_receiver_chain_source, PrefixedDenom, TracePrefix,
+    is_receiver_chain_source, PrefixedCoin, PrefixedDenom, TracePrefix,
 };
+use namada_core::ibc::core::channel::types::acknowledgement::AcknowledgementStatus;
 use namada_core::ibc::core::channel::types::msgs::PacketMsg;
+use namada_core::ibc::core::channel::types::packet::Packet;
+use namada_core::ibc::core::channel::types::timeout::TimeoutHeight;
 use namada_core::ibc::core::entrypoint::{execute, validate};
 use namada_core::ibc::core::handler::types::error::ContextError;
 use namada_core::ibc::core::handler::types::msgs::MsgEnvelope;
@@ -36,7 +43,7 @@ use namada_core::ibc::core::router::types::error::RouterError;
 use namada_core::ibc::core::router::types::module::ModuleId;
 use namada_core::ibc::primitives::proto::Any;
 pub use namada_core::ibc::*;
-use namada_core::types::address::{Address, MASP};
+use namada_core::types::address::{Address, InternalAddress, MASP};
 use namada_core::types::ibc::{
     get_shielded_transfer, is_ibc_denom, MsgShieldedTransfer,
     EVENT_TYPE_DENOM_TRACE, EVENT_TYPE_PACKET,
@@ -62,6 +69,8 @@ pub enum Error {
     ChainId(IdentifierError),
     #[error("Handling MASP transaction error: {0}")]
     MaspTx(String),
+    #[error("Forwarding error: {0}")]
+    Forward(String),
 }
 
 /// IBC actions to handle IBC operations
@@ -131,6 +140,8 @@ where
                 // the current ibc-rs execution doesn't store the denom for the
                 // token hash when transfer with MsgRecvPacket
                 self.store_denom(envelope)?;
+                // For forwarding the received token to the next chain
+                self.handle_forward(envelope)?;
                 // For receiving the token to a shielded address
                 self.handle_masp_tx(message)
             }
@@ -158,6 +169,16 @@ where
                             e
                         ))
                     })?;
+                self.ctx
+                    .inner
+                    .borrow_mut()
+                    .store_ibc_trace(&trace_hash, &ibc_denom)
+                    .map_err(|e| {
+                        Error::Denom(format!(
+                            "Writing the IBC trace failed: {}",
+                            e
+                        ))
+                    })?;
                 if let Some((_, base_token)) = is_ibc_denom(&ibc_denom) {
                     self.ctx
                         .inner
@@ -228,6 +249,210 @@ where
         }))
     }
 
+    /// Forward the received token to the next chain, or handle the result of
+    /// a packet sent by the forwarding
+    fn handle_forward(&mut self, envelope: &MsgEnvelope) -> Result<(), Error> {
+        match envelope {
+            MsgEnvelope::Packet(PacketMsg::Recv(msg)) => {
+                self.forward_received_token(&msg.packet)
+            }
+            MsgEnvelope::Packet(PacketMsg::Ack(msg)) => {
+                let is_success =
+                    serde_json::from_slice::<AcknowledgementStatus>(
+                        msg.acknowledgement.as_bytes(),
+                    )
+                    .map(|ack| ack.is_successful())
+                    .unwrap_or_default();
+                self.handle_forwarded_packet(&msg.packet, is_success)
+            }
+            MsgEnvelope::Packet(PacketMsg::Timeout(msg)) => {
+                self.handle_forwarded_packet(&msg.packet, false)
+            }
+            MsgEnvelope::Packet(PacketMsg::TimeoutOnClose(msg)) => {
+                self.handle_forwarded_packet(&msg.packet, false)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Send the received token to the next chain if the packet has the
+    /// forwarding metadata
+    fn forward_received_token(&mut self, packet: &Packet) -> Result<(), Error> {
+        let data: PacketData = match serde_json::from_slice(&packet.data) {
+            Ok(data) => data,
+            Err(_) => return Ok(()),
+        };
+        let forward = match parse_forward(data.memo.as_ref()) {
+            Ok(Some(forward)) => forward,
+            // The packet with invalid metadata has been rejected with an error
+            // acknowledgement
+            _ => return Ok(()),
+        };
+        // Nothing to forward when the packet has been already received or
+        // the receiving failed
+        let events = self
+            .ctx
+            .inner
+            .borrow()
+            .get_ibc_events(EVENT_TYPE_PACKET)
+            .map_err(|_| {
+                Error::Forward("Reading the IBC event failed".to_string())
+            })?;
+        if !events.iter().any(|event| {
+            event.attributes.get("success").map(String::as_str) == Some("true")
+        }) {
+            return Ok(());
+        }
+
+        let denom = received_ibc_denom(
+            &data.token.denom,
+            &packet.port_id_on_a,
+            &packet.chan_id_on_a,
+            &packet.port_id_on_b,
+            &packet.chan_id_on_b,
+        );
+        let packet_data = PacketData {
+            token: PrefixedCoin {
+                denom,
+                amount: data.token.amount,
+            },
+            sender: Address::Internal(InternalAddress::Ibc).to_string().into(),
+            receiver: forward.receiver,
+            memo: forward.next.unwrap_or_default().into(),
+        };
+        let pending = PendingForward {
+            port_id: packet.port_id_on_b.to_string(),
+            channel_id: packet.chan_id_on_b.to_string(),
+            sender: data.sender.as_ref().to_string(),
+            retries: forward.retries,
+            timeout_nanos: u64::try_from(forward.timeout.as_nanos())
+                .unwrap_or(u64::MAX),
+            is_refund: false,
+        };
+        self.send_forward(
+            &forward.port_id,
+            &forward.channel_id,
+            packet_data,
+            pending,
+        )
+    }
+
+    /// Send a failed packet sent by the forwarding again, or refund the
+    /// original sender. The token has been already refunded to the IBC
+    /// account when the packet failed.
+    fn handle_forwarded_packet(
+        &mut self,
+        packet: &Packet,
+        is_success: bool,
+    ) -> Result<(), Error> {
+        let port_id = &packet.port_id_on_a;
+        let channel_id = &packet.chan_id_on_a;
+        let sequence = packet.seq_on_a;
+        let pending = self
+            .ctx
+            .inner
+            .borrow()
+            .pending_forward(port_id, channel_id, sequence)
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        // The packet wasn't sent by the forwarding or has been already handled
+        let pending = match pending {
+            Some(pending) => pending,
+            None => return Ok(()),
+        };
+        self.ctx
+            .inner
+            .borrow_mut()
+            .delete_pending_forward(port_id, channel_id, sequence)
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        if is_success {
+            return Ok(());
+        }
+
+        let data: PacketData =
+            serde_json::from_slice(&packet.data).map_err(|e| {
+                Error::Forward(format!(
+                    "Decoding the forwarded packet data failed: {e}"
+                ))
+            })?;
+        if !pending.is_refund && pending.retries > 0 {
+            let pending = PendingForward {
+                retries: pending.retries - 1,
+                ..pending
+            };
+            return self.send_forward(port_id, channel_id, data, pending);
+        }
+
+        // Refund the sender over the channel the token was received on
+        let refund_port_id: PortId = pending.port_id.parse().map_err(|e| {
+            Error::Forward(format!("Invalid port ID of the refund: {e}"))
+        })?;
+        let refund_channel_id: ChannelId =
+            pending.channel_id.parse().map_err(|e| {
+                Error::Forward(format!("Invalid channel ID of the refund: {e}"))
+            })?;
+        let refund_data = PacketData {
+            token: data.token,
+            sender: data.sender,
+            receiver: pending.sender.clone().into(),
+            memo: String::new().into(),
+        };
+        let pending = PendingForward {
+            is_refund: true,
+            ..pending
+        };
+        self.send_forward(
+            &refund_port_id,
+            &refund_channel_id,
+            refund_data,
+            pending,
+        )
+    }
+
+    /// Send the token from the IBC account and store the pending packet
+    fn send_forward(
+        &mut self,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+        packet_data: PacketData,
+        pending: PendingForward,
+    ) -> Result<(), Error> {
+        let host_timestamp = self
+            .ctx
+            .inner
+            .borrow()
+            .host_timestamp()
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        let timeout_timestamp_on_b = (host_timestamp
+            + Duration::from_nanos(pending.timeout_nanos))
+        .map_err(|e| Error::Forward(format!("Invalid timeout: {e}")))?;
+        let sequence = self
+            .ctx
+            .inner
+            .borrow()
+            .get_next_sequence_send(port_id, channel_id)
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        let msg = MsgTransfer {
+            port_id_on_a: port_id.clone(),
+            chan_id_on_a: channel_id.clone(),
+            packet_data,
+            timeout_height_on_b: TimeoutHeight::Never,
+            timeout_timestamp_on_b,
+        };
+
+        let mut token_transfer_ctx =
+            TokenTransferContext::new(self.ctx.inner.clone());
+        send_transfer_validate(&self.ctx, &token_transfer_ctx, msg.clone())
+            .map_err(Error::TokenTransfer)?;
+        send_transfer_execute(&mut self.ctx, &mut token_transfer_ctx, msg)
+            .map_err(Error::TokenTransfer)?;
+
+        self.ctx
+            .inner
+            .borrow_mut()
+            .store_pending_forward(port_id, channel_id, sequence, pending)
+            .map_err(|e| Error::Context(Box::new(e)))
+    }
+
     /// Validate according to the message in IBC VP
     pub fn validate(&self, tx_data: &[u8]) -> Result<(), Error> {
         let message = decode_message(tx_data)?;
@@ -334,6 +559,25 @@ pub fn received_ibc_token(
     dest_port_id: &PortId,
     dest_channel_id: &ChannelId,
 ) -> Result<Address, Error> {
+    let ibc_denom = received_ibc_denom(
+        ibc_denom,
+        src_port_id,
+        src_channel_id,
+        dest_port_id,
+        dest_channel_id,
+    );
+    Ok(storage::ibc_token(ibc_denom.to_string()))
+}
+
+/// Get the IBC denom on this chain of the token received from the
+/// source/destination ports and channels
+pub fn received_ibc_denom(
+    ibc_denom: &PrefixedDenom,
+    src_port_id: &PortId,
+    src_channel_id: &ChannelId,
+    dest_port_id: &PortId,
+    dest_channel_id: &ChannelId,
+) -> PrefixedDenom {
     let mut ibc_denom = ibc_denom.clone();
     if is_receiver_chain_source(
         src_port_id.clone(),
@@ -348,7 +592,7 @@ pub fn received_ibc_token(
             TracePrefix::new(dest_port_id.clone(), dest_channel_id.clone());
         ibc_denom.add_trace_prefix(prefix);
     }
-    Ok(storage::ibc_token(ibc_denom.to_string()))
+    ibc_denom
 }
 
 #[cfg(any(test, feature = "testing"))]
@@ -15,6 +15,7 @@ use namada_core::types::address::{
     Address, InternalAddress, HASH_LEN, SHA_HASH_LEN,
 };
 use namada_core::types::ibc::IbcTokenHash;
+use namada_core::types::key_space::ModuleKeySpace;
 use namada_core::types::storage::{DbKeySeg, Key, KeySeg};
 use sha2::{Digest, Sha256};
 use thiserror::Error;
@@ -23,6 +24,21 @@ const CLIENTS_COUNTER: &str = "clients/counter";
 const CONNECTIONS_COUNTER: &str = "connections/counter";
 const CHANNELS_COUNTER: &str = "channelEnds/counter";
 const DENOM: &str = "ibc_denom";
+const TRACE: &str = "ibc_trace";
+const FORWARD: &str = "forward";
+/// The first segments of the ICS-24 paths
+const ICS24_PREFIXES: &[&str] = &[
+    "clients",
+    "connections",
+    "channelEnds",
+    "ports",
+    "nextSequenceSend",
+    "nextSequenceRecv",
+    "nextSequenceAck",
+    "commitments",
+    "receipts",
+    "acks",
+];
 
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
@@ -42,6 +58,20 @@ pub enum Error {
 /// IBC storage functions result
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The key prefixes reserved by IBC
+pub fn key_space() -> ModuleKeySpace {
+    let addr = Address::Internal(InternalAddress::Ibc);
+    ModuleKeySpace::new("ibc")
+        .guarded_by(addr.clone())
+        .reserve_sub_keys(
+            &addr,
+            ICS24_PREFIXES,
+            "IBC clients, connections and channels",
+        )
+        .reserve_sub_keys(&addr, &[DENOM, TRACE], "Denominations of IBC tokens")
+        .reserve_sub_keys(&addr, &[FORWARD], "Packets pending forwarding")
+}
+
 /// Returns a key of the IBC-related data
 pub fn ibc_key(path: impl AsRef<str>) -> Result<Key> {
     let path = Key::parse(path).map_err(Error::StorageKey)?;
@@ -197,6 +227,18 @@ pub fn ack_key(
         .expect("Creating a key for the ack shouldn't fail")
 }
 
+/// Returns a key for the pending packet sent by the forwarding
+pub fn forward_key(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> Key {
+    let path = format!(
+        "{FORWARD}/ports/{port_id}/channels/{channel_id}/sequences/{sequence}"
+    );
+    ibc_key(path).expect("Creating a key for the forwarding shouldn't fail")
+}
+
 /// Returns a key for the timestamp for the client update
 pub fn client_update_timestamp_key(client_id: &ClientId) -> Key {
     let path = format!("clients/{}/update_timestamp", client_id);
@@ -396,6 +438,22 @@ pub fn ibc_denom_key(
         .expect("Cannot obtain a storage key")
 }
 
+/// The storage key prefix of the IBC denoms with their full trace path by the
+/// hashed IBC denom
+pub fn ibc_trace_key_prefix() -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&TRACE.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// The storage key to get the IBC denom with its full trace path by the hashed
+/// IBC denom, regardless of the owner of the token
+pub fn ibc_trace_key(token_hash: impl AsRef<str>) -> Key {
+    ibc_trace_key_prefix()
+        .push(&token_hash.as_ref().to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Hash the denom
 #[inline]
 pub fn calc_hash(denom: impl AsRef<str>) -> String {
@@ -449,3 +507,19 @@ pub fn is_ibc_denom_key(key: &Key) -> Option<(String, String)> {
         _ => None,
     }
 }
+
+/// Returns the token hash if the given key is the trace key
+pub fn is_ibc_trace_key(key: &Key) -> Option<String> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(hash),
+        ] if addr == &Address::Internal(InternalAddress::Ibc)
+            && prefix == TRACE =>
+        {
+            Some(hash.clone())
+        }
+        _ => None,
+    }
+}
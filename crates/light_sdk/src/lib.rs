@@ -14,6 +14,14 @@
 //!   currently supported by the protocol
 //! - reading: exposes queries to retrieve data from a Namada node
 //! - writing: TO BE DONE
+//!
+//! # Features
+//!
+//! The `blocking` feature, enabled by default, provides the blocking queries
+//! that connect to a node over HTTP. Without it, the SDK can be built for
+//! `wasm32-unknown-unknown` (e.g. for a web wallet) to construct and sign
+//! transactions and to query a node with the unblocking queries over any
+//! [`namada_sdk::queries::Client`].
 
 pub mod reading;
 pub mod transaction;
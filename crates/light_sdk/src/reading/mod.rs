@@ -1,53 +1,7 @@
-use std::str::FromStr;
+//! Queries of a Namada node. The blocking queries connect to the node over
+//! HTTP and run on their own runtime, while the unblocking ones can be awaited
+//! with any client, including on wasm targets.
 
-use namada_sdk::error::Error;
-use namada_sdk::queries::RPC;
-use namada_sdk::rpc;
-use namada_sdk::state::LastBlock;
-use namada_sdk::types::address::Address;
-use namada_sdk::types::storage::BlockResults;
-use namada_sdk::types::token;
-use tendermint_config::net::Address as TendermintAddress;
-use tendermint_rpc::HttpClient;
-use tokio::runtime::Runtime;
-
-pub mod account;
-pub mod governance;
-pub mod pgf;
-pub mod pos;
-pub mod tx;
-
-/// Query the address of the native token
-pub fn query_native_token(tendermint_addr: &str) -> Result<Address, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::query_native_token(&client))
-}
-
-/// Query the last committed block, if any.
-pub fn query_block(tendermint_addr: &str) -> Result<Option<LastBlock>, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::query_block(&client))
-}
-
-/// Query the results of the last committed block
-pub fn query_results(
-    tendermint_addr: &str,
-) -> Result<Vec<BlockResults>, Error> {
-    let client = HttpClient::new(
-        TendermintAddress::from_str(tendermint_addr)
-            .map_err(|e| Error::Other(e.to_string()))?,
-    )
-    .map_err(|e| Error::Other(e.to_string()))?;
-    let rt = Runtime::new().unwrap();
-    rt.block_on(rpc::query_results(&client))
-}
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod unblocking;
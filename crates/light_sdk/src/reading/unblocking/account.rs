@@ -0,0 +1,49 @@
+use namada_sdk::account::Account;
+use namada_sdk::types::address::Address;
+use namada_sdk::types::key::common;
+
+use super::*;
+
+/// Query token amount of owner.
+pub async fn get_token_balance<C: Client + Sync>(
+    client: &C,
+    token: &Address,
+    owner: &Address,
+) -> Result<token::Amount, Error> {
+    rpc::get_token_balance(client, token, owner).await
+}
+
+/// Check if the address exists on chain. Established address exists if it
+/// has a stored validity predicate. Implicit and internal addresses
+/// always return true.
+pub async fn known_address<C: Client + Sync>(
+    client: &C,
+    address: &Address,
+) -> Result<bool, Error> {
+    rpc::known_address(client, address).await
+}
+
+/// Query the accunt substorage space of an address
+pub async fn get_account_info<C: Client + Sync>(
+    client: &C,
+    owner: &Address,
+) -> Result<Option<Account>, Error> {
+    rpc::get_account_info(client, owner).await
+}
+
+/// Query if the public_key is revealed
+pub async fn is_public_key_revealed<C: Client + Sync>(
+    client: &C,
+    owner: &Address,
+) -> Result<bool, Error> {
+    rpc::is_public_key_revealed(client, owner).await
+}
+
+/// Query an account substorage at a specific index
+pub async fn get_public_key_at<C: Client + Sync>(
+    client: &C,
+    owner: &Address,
+    index: u8,
+) -> Result<Option<common::PublicKey>, Error> {
+    rpc::get_public_key_at(client, owner, index).await
+}
@@ -0,0 +1,28 @@
+use namada_sdk::governance::parameters::GovernanceParameters;
+use namada_sdk::governance::storage::proposal::StorageProposal;
+use namada_sdk::governance::utils::Vote;
+
+use super::*;
+
+/// Query proposal by Id
+pub async fn query_proposal_by_id<C: Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+) -> Result<Option<StorageProposal>, Error> {
+    rpc::query_proposal_by_id(client, proposal_id).await
+}
+
+/// Get the givernance parameters
+pub async fn query_governance_parameters<C: Client + Sync>(
+    client: &C,
+) -> Result<GovernanceParameters, Error> {
+    Ok(rpc::query_governance_parameters(client).await)
+}
+
+/// Get the givernance parameters
+pub async fn query_proposal_votes<C: Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+) -> Result<Vec<Vote>, Error> {
+    rpc::query_proposal_votes(client, proposal_id).await
+}
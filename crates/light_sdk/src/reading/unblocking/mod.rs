@@ -0,0 +1,34 @@
+use namada_sdk::error::Error;
+use namada_sdk::queries::{Client, RPC};
+use namada_sdk::rpc;
+use namada_sdk::state::LastBlock;
+use namada_sdk::types::address::Address;
+use namada_sdk::types::storage::BlockResults;
+use namada_sdk::types::token;
+
+pub mod account;
+pub mod governance;
+pub mod pgf;
+pub mod pos;
+pub mod tx;
+
+/// Query the address of the native token
+pub async fn query_native_token<C: Client + Sync>(
+    client: &C,
+) -> Result<Address, Error> {
+    rpc::query_native_token(client).await
+}
+
+/// Query the last committed block, if any.
+pub async fn query_block<C: Client + Sync>(
+    client: &C,
+) -> Result<Option<LastBlock>, Error> {
+    rpc::query_block(client).await
+}
+
+/// Query the results of the last committed block
+pub async fn query_results<C: Client + Sync>(
+    client: &C,
+) -> Result<Vec<BlockResults>, Error> {
+    rpc::query_results(client).await
+}
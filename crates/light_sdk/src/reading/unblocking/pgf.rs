@@ -0,0 +1,9 @@
+use super::*;
+
+/// Check if the given address is a pgf steward.
+pub async fn is_steward<C: Client + Sync>(
+    client: &C,
+    address: &Address,
+) -> Result<bool, Error> {
+    Ok(rpc::is_steward(client, address).await)
+}
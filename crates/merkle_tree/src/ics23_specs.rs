@@ -1,32 +1,19 @@
 //! A module that contains
 
-use arse_merkle_tree::H256;
-use ics23::{HashOp, LeafOp, LengthOp, ProofSpec};
+use ics23::{LeafOp, ProofSpec};
 use namada_core::types::hash::StorageHasher;
 
 /// Get the leaf spec for the base tree. The key is stored after hashing,
 /// but the stored value is the subtree's root without hashing.
 pub fn base_leaf_spec<H: StorageHasher>() -> LeafOp {
-    LeafOp {
-        hash: H::hash_op().into(),
-        prehash_key: H::hash_op().into(),
-        prehash_value: HashOp::NoHash.into(),
-        length: LengthOp::NoPrefix.into(),
-        prefix: H256::zero().as_slice().to_vec(),
-    }
+    namada_proofs::specs::base_leaf_spec(H::hash_op())
 }
 
 /// Get the leaf spec for the subtree. Non-hashed values are used for the
 /// verification with this spec because a subtree stores the key-value pairs
 /// after hashing.
 pub fn leaf_spec<H: StorageHasher>() -> LeafOp {
-    LeafOp {
-        hash: H::hash_op().into(),
-        prehash_key: H::hash_op().into(),
-        prehash_value: H::hash_op().into(),
-        length: LengthOp::NoPrefix.into(),
-        prefix: H256::zero().as_slice().to_vec(),
-    }
+    namada_proofs::specs::leaf_spec(H::hash_op())
 }
 
 /// Get the leaf spec for the ibc subtree. Non-hashed values are used for
@@ -34,13 +21,7 @@ pub fn leaf_spec<H: StorageHasher>() -> LeafOp {
 /// key-value pairs after hashing. However, keys are also not hashed in
 /// the backing store.
 pub fn ibc_leaf_spec<H: StorageHasher>() -> LeafOp {
-    LeafOp {
-        hash: H::hash_op().into(),
-        prehash_key: HashOp::NoHash.into(),
-        prehash_value: HashOp::NoHash.into(),
-        length: LengthOp::NoPrefix.into(),
-        prefix: H256::zero().as_slice().to_vec(),
-    }
+    namada_proofs::specs::ibc_leaf_spec(H::hash_op())
 }
 
 /// Get the proof specs for ibc
@@ -108,6 +108,10 @@ pub enum Error {
 /// Result for functions that may fail
 type Result<T> = std::result::Result<T, Error>;
 
+/// The minimum number of updates in a batch for the sub-trees to be hashed in
+/// parallel. Smaller batches are faster to hash serially.
+pub const PARALLEL_HASHING_THRESHOLD: usize = 512;
+
 /// Type alias for bytes to be put into the Merkle storage
 pub type StorageBytes<'a> = &'a [u8];
 
@@ -493,6 +497,91 @@ impl<H: StorageHasher + Default> MerkleTree<H> {
         self.update_tree(&store_type, &sub_key, value)
     }
 
+    /// Update the tree with a batch of keys and values, where `None` deletes
+    /// the key. The sub-trees are hashed in parallel when the batch has at
+    /// least [`PARALLEL_HASHING_THRESHOLD`] updates, with the same resulting
+    /// root as applying the updates one by one.
+    pub fn update_batch<'a>(
+        &mut self,
+        updates: Vec<(&'a Key, Option<StorageBytes<'a>>)>,
+    ) -> Result<()> {
+        #[cfg(feature = "multicore")]
+        if updates.len() >= PARALLEL_HASHING_THRESHOLD {
+            return self.update_batch_parallel(updates);
+        }
+        self.update_batch_serial(updates)
+    }
+
+    fn update_batch_serial(
+        &mut self,
+        updates: Vec<(&Key, Option<StorageBytes>)>,
+    ) -> Result<()> {
+        for (key, value) in updates {
+            match value {
+                Some(value) => self.update(key, value)?,
+                None => self.delete(key)?,
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "multicore")]
+    fn update_batch_parallel(
+        &mut self,
+        updates: Vec<(&Key, Option<StorageBytes>)>,
+    ) -> Result<()> {
+        use rayon::prelude::*;
+
+        // Group the updates by sub-tree, keeping their order
+        let mut account = vec![];
+        let mut ibc = vec![];
+        let mut pos = vec![];
+        let mut bridge_pool = vec![];
+        for (key, value) in updates {
+            let (store_type, sub_key) = StoreType::sub_key(key)?;
+            match store_type {
+                StoreType::Account => account.push((sub_key, value)),
+                StoreType::Ibc => ibc.push((sub_key, value)),
+                StoreType::PoS => pos.push((sub_key, value)),
+                StoreType::BridgePool => bridge_pool.push((sub_key, value)),
+                // The keys are never in the base tree
+                StoreType::Base => unreachable!(),
+            }
+        }
+
+        let sub_trees: Vec<(StoreType, Box<dyn SubTreeWrite + Send + '_>, _)> = vec![
+            (StoreType::Account, Box::new(&mut self.account), account),
+            (StoreType::Ibc, Box::new(&mut self.ibc), ibc),
+            (StoreType::PoS, Box::new(&mut self.pos), pos),
+            (
+                StoreType::BridgePool,
+                Box::new(&mut self.bridge_pool),
+                bridge_pool,
+            ),
+        ];
+        let sub_roots = sub_trees
+            .into_par_iter()
+            .filter(|(_, _, updates)| !updates.is_empty())
+            .map(|(store_type, mut tree, updates)| -> Result<_> {
+                let mut sub_root = Hash::zero();
+                for (sub_key, value) in updates {
+                    sub_root = match value {
+                        Some(value) => tree.subtree_update(&sub_key, value)?,
+                        None => tree.subtree_delete(&sub_key)?,
+                    };
+                }
+                Ok((store_type, sub_root))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Update the base tree with the updated sub roots
+        for (store_type, sub_root) in sub_roots {
+            let base_key = H::hash(store_type.to_string());
+            self.base.update(base_key.into(), sub_root)?;
+        }
+        Ok(())
+    }
+
     /// Delete the value corresponding to the given key
     pub fn delete(&mut self, key: &Key) -> Result<()> {
         let (store_type, sub_key) = StoreType::sub_key(key)?;
@@ -1095,6 +1184,128 @@ mod test {
         assert!(basetree_verification_res);
     }
 
+    /// A key in the account, IBC or PoS sub-tree
+    fn sub_tree_key(sub_tree: u8, ix: u64) -> Key {
+        let prefix: Key = match sub_tree {
+            0 => Address::Internal(InternalAddress::Ibc).to_db_key().into(),
+            1 => Address::Internal(InternalAddress::PoS).to_db_key().into(),
+            _ => Key::parse("account").unwrap(),
+        };
+        prefix.push(&ix).unwrap()
+    }
+
+    /// Apply the updates to the tree one by one
+    fn update_one_by_one(
+        tree: &mut MerkleTree<Sha256Hasher>,
+        updates: &[(Key, Option<Vec<u8>>)],
+    ) {
+        for (key, value) in updates {
+            match value {
+                Some(value) => tree.update(key, value).unwrap(),
+                None => tree.delete(key).unwrap(),
+            }
+        }
+    }
+
+    fn as_batch(
+        updates: &[(Key, Option<Vec<u8>>)],
+    ) -> Vec<(&Key, Option<StorageBytes>)> {
+        updates
+            .iter()
+            .map(|(key, value)| (key, value.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn test_update_batch() {
+        // Write keys in all the sub-trees ...
+        let writes: Vec<_> = (0..PARALLEL_HASHING_THRESHOLD as u64)
+            .map(|ix| {
+                let key = sub_tree_key((ix % 3) as u8, ix);
+                (key, Some(ix.to_le_bytes().to_vec()))
+            })
+            .collect();
+        // ... then delete or overwrite some of them
+        let changes: Vec<_> = (0..PARALLEL_HASHING_THRESHOLD as u64)
+            .step_by(5)
+            .map(|ix| {
+                let key = sub_tree_key((ix % 3) as u8, ix);
+                let value = (ix % 2 == 0).then(|| vec![1; 8]);
+                (key, value)
+            })
+            .collect();
+
+        let mut expected = MerkleTree::<Sha256Hasher>::default();
+        update_one_by_one(&mut expected, &writes);
+        update_one_by_one(&mut expected, &changes);
+
+        let mut tree = MerkleTree::<Sha256Hasher>::default();
+        tree.update_batch(as_batch(&writes)).unwrap();
+        tree.update_batch(as_batch(&changes)).unwrap();
+        assert_eq!(tree.root(), expected.root());
+        for key in writes.iter().map(|(key, _)| key) {
+            assert_eq!(
+                tree.has_key(key).unwrap(),
+                expected.has_key(key).unwrap()
+            );
+        }
+
+        #[cfg(feature = "multicore")]
+        {
+            let mut tree = MerkleTree::<Sha256Hasher>::default();
+            tree.update_batch_parallel(as_batch(&writes)).unwrap();
+            tree.update_batch_parallel(as_batch(&changes)).unwrap();
+            assert_eq!(tree.root(), expected.root());
+            for store_type in
+                [StoreType::Account, StoreType::Ibc, StoreType::PoS]
+            {
+                assert_eq!(
+                    tree.sub_root(&store_type),
+                    expected.sub_root(&store_type)
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "multicore")]
+    proptest::proptest! {
+        /// Check that the root of the tree is the same whether the sub-trees
+        /// are hashed in parallel or the updates are applied one by one
+        #[test]
+        fn test_parallel_hashing_determinism(
+            ops in proptest::collection::vec(
+                (
+                    0..3_u8,
+                    0..32_u64,
+                    proptest::option::of(proptest::prelude::any::<[u8; 8]>()),
+                ),
+                1..200,
+            ),
+        ) {
+            // Only delete the keys that were written before
+            let mut written = std::collections::HashSet::new();
+            let updates: Vec<_> = ops
+                .into_iter()
+                .filter_map(|(sub_tree, ix, value)| {
+                    let key = sub_tree_key(sub_tree, ix);
+                    match value {
+                        Some(value) => {
+                            written.insert(key.clone());
+                            Some((key, Some(value.to_vec())))
+                        }
+                        None => written.remove(&key).then_some((key, None)),
+                    }
+                })
+                .collect();
+
+            let mut expected = MerkleTree::<Sha256Hasher>::default();
+            update_one_by_one(&mut expected, &updates);
+            let mut tree = MerkleTree::<Sha256Hasher>::default();
+            tree.update_batch_parallel(as_batch(&updates)).unwrap();
+            proptest::prop_assert_eq!(tree.root(), expected.root());
+        }
+    }
+
     #[test]
     fn test_restore_tree() {
         let mut tree = MerkleTree::<Sha256Hasher>::default();
@@ -1313,4 +1524,79 @@ mod test {
             );
         assert!(basetree_verification_res);
     }
+
+    #[test]
+    fn test_proofs_crate_specs() {
+        let hash_op = Sha256Hasher::hash_op();
+        assert_eq!(
+            proof_specs::<Sha256Hasher>(),
+            namada_proofs::specs::proof_specs(hash_op)
+        );
+        assert_eq!(
+            ibc_proof_specs::<Sha256Hasher>(),
+            namada_proofs::specs::ibc_proof_specs(hash_op)
+        );
+    }
+
+    #[test]
+    fn test_verify_with_proofs_crate() {
+        use namada_proofs::{StorageProof, StoreType as ProofStore};
+
+        let mut tree = MerkleTree::<Sha256Hasher>::default();
+        let pos_key = sub_tree_key(1, 1);
+        let pos_val = vec![1u8; 8];
+        let ibc_key = sub_tree_key(0, 1);
+        let ibc_val = vec![2u8; 8];
+        let ibc_non_key = sub_tree_key(0, 2);
+        tree.update(&pos_key, &pos_val).unwrap();
+        tree.update(&ibc_key, &ibc_val).unwrap();
+        let root = tree.root().0;
+
+        // membership of a PoS key
+        let (_, sub_key) = StoreType::sub_key(&pos_key).unwrap();
+        let MembershipProof::ICS23(sub_proof) = tree
+            .get_sub_tree_existence_proof(
+                std::array::from_ref(&pos_key),
+                vec![pos_val.as_slice()],
+            )
+            .unwrap()
+        else {
+            unreachable!()
+        };
+        let proof = tree.get_sub_tree_proof(&pos_key, sub_proof).unwrap();
+        let proof = StorageProof {
+            sub_proof: proof.sub_proof,
+            base_proof: proof.base_proof,
+        };
+        let key = sub_key.to_string();
+        proof
+            .verify_membership(&root, ProofStore::PoS, key.as_bytes(), &pos_val)
+            .unwrap();
+        assert!(proof
+            .verify_membership(&root, ProofStore::PoS, key.as_bytes(), &[])
+            .is_err());
+        assert!(proof
+            .verify_membership(
+                &[0; 32],
+                ProofStore::PoS,
+                key.as_bytes(),
+                &pos_val
+            )
+            .is_err());
+
+        // non-membership of an IBC key
+        let (_, sub_key) = StoreType::sub_key(&ibc_non_key).unwrap();
+        let proof = tree.get_non_existence_proof(&ibc_non_key).unwrap();
+        let proof = StorageProof {
+            sub_proof: proof.sub_proof,
+            base_proof: proof.base_proof,
+        };
+        let key = sub_key.to_string();
+        proof
+            .verify_non_membership(&root, ProofStore::Ibc, key.as_bytes())
+            .unwrap();
+        assert!(proof
+            .verify_non_membership(&root, ProofStore::PoS, key.as_bytes())
+            .is_err());
+    }
 }
@@ -5,8 +5,11 @@ pub mod utils;
 use std::collections::BTreeSet;
 
 use borsh::BorshDeserialize;
+use namada_governance::parameters::ProposalTallyParameters;
 use namada_governance::storage::proposal::{AddRemove, ProposalType};
-use namada_governance::storage::{is_proposal_accepted, keys as gov_storage};
+use namada_governance::storage::{
+    self as gov_api, is_proposal_accepted, keys as gov_storage,
+};
 use namada_governance::utils::is_valid_validator_voting_period;
 use namada_proof_of_stake::is_validator;
 use namada_state::StorageRead;
@@ -85,6 +88,12 @@ where
                 (KeyType::VOTE, Some(proposal_id)) => {
                     self.is_valid_vote_key(proposal_id, key, verifiers)
                 }
+                (KeyType::VOTE_PROXY, _) => {
+                    self.is_valid_vote_proxy(key, verifiers)
+                }
+                (KeyType::PROXY_DELEGATOR, _) => {
+                    self.is_valid_proxy_delegator(key, verifiers)
+                }
                 (KeyType::CONTENT, Some(proposal_id)) => {
                     self.is_valid_content_key(proposal_id)
                 }
@@ -113,7 +122,9 @@ where
                 (KeyType::PROPOSAL_COMMIT, _) => {
                     self.is_valid_proposal_commit()
                 }
-                (KeyType::PARAMETER, _) => self.is_valid_parameter(tx_data),
+                (KeyType::PARAMETER, _) => {
+                    self.is_valid_parameter(key, tx_data)
+                }
                 (KeyType::BALANCE, _) => self.is_valid_balance(&native_token),
                 (KeyType::UNKNOWN_GOVERNANCE, _) => Ok(false),
                 (KeyType::UNKNOWN, _) => Ok(true),
@@ -254,7 +265,79 @@ where
                 delegation_address,
             )
             .unwrap_or(false);
-        Ok(is_delegator)
+        if is_delegator {
+            return Ok(true);
+        }
+
+        // An address without bonds can still vote on behalf of the
+        // delegators that made it their vote proxy
+        self.is_vote_proxy(verifiers, voter_address, delegation_address)
+    }
+
+    /// Validate a change of the vote proxy of a delegator
+    fn is_valid_vote_proxy(
+        &self,
+        key: &Key,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let delegator = match gov_storage::is_vote_proxy_key(key) {
+            Some(delegator) => delegator,
+            None => return Ok(false),
+        };
+        if !verifiers.contains(delegator) {
+            tracing::info!(
+                "The vote proxy of {delegator} can only be changed by \
+                 {delegator}."
+            );
+            return Ok(false);
+        }
+
+        let pre_proxy: Option<Address> = self.ctx.pre().read(key)?;
+        let proxy: Option<Address> = self.ctx.post().read(key)?;
+        if let Some(pre_proxy) = pre_proxy {
+            // The delegator must be removed from the index of its previous
+            // proxy
+            let index_key =
+                gov_storage::get_proxy_delegator_key(&pre_proxy, delegator);
+            if proxy.as_ref() != Some(&pre_proxy)
+                && self.ctx.has_key_post(&index_key)?
+            {
+                return Ok(false);
+            }
+        }
+        match proxy {
+            Some(proxy) if &proxy == delegator => {
+                tracing::info!("A delegator cannot be its own vote proxy.");
+                Ok(false)
+            }
+            Some(proxy) => {
+                // The delegator must be indexed by its new proxy
+                let index_key =
+                    gov_storage::get_proxy_delegator_key(&proxy, delegator);
+                Ok(self.ctx.has_key_post(&index_key)?)
+            }
+            None => Ok(true),
+        }
+    }
+
+    /// Validate a change of the index of the delegators of a vote proxy,
+    /// which must match the vote proxy of the delegator
+    fn is_valid_proxy_delegator(
+        &self,
+        key: &Key,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let Some((proxy, delegator)) = gov_storage::is_proxy_delegator_key(key)
+        else {
+            return Ok(false);
+        };
+        if !verifiers.contains(delegator) {
+            return Ok(false);
+        }
+        let proxy_key = gov_storage::get_vote_proxy_key(delegator);
+        let post_proxy: Option<Address> = self.ctx.post().read(&proxy_key)?;
+        let is_indexed = self.ctx.has_key_post(key)?;
+        Ok(is_indexed == (post_proxy.as_ref() == Some(proxy)))
     }
 
     /// Validate a content key
@@ -325,6 +408,9 @@ where
                     Ok(true)
                 }
             }
+            ProposalType::IbcClientRecovery(recovery) => {
+                Ok(recovery.subject_client_id != recovery.substitute_client_id)
+            }
             _ => Ok(true), // default proposal
         }
     }
@@ -587,7 +673,17 @@ where
     }
 
     /// Validate a governance parameter
-    pub fn is_valid_parameter(&self, tx: &Tx) -> Result<bool> {
+    pub fn is_valid_parameter(&self, key: &Key, tx: &Tx) -> Result<bool> {
+        if gov_storage::is_tally_params_key(key) {
+            let tally_params: ProposalTallyParameters =
+                self.force_read(key, ReadType::Post)?;
+            if !tally_params.is_valid() {
+                tracing::info!(
+                    "The tally parameters must be fractions between 0 and 1"
+                );
+                return Ok(false);
+            }
+        }
         match tx.data() {
             Some(data) => is_proposal_accepted(&self.ctx.pre(), data.as_ref())
                 .map_err(Error::NativeVpError),
@@ -617,6 +713,20 @@ where
         Ok(is_validator && verifiers.contains(address))
     }
 
+    /// Check if a vote is from the vote proxy of some delegator
+    pub fn is_vote_proxy(
+        &self,
+        verifiers: &BTreeSet<Address>,
+        address: &Address,
+        delegation_address: &Address,
+    ) -> Result<bool> {
+        if address == delegation_address || !verifiers.contains(address) {
+            return Ok(false);
+        }
+        let is_proxy = gov_api::is_vote_proxy(&self.ctx.pre(), address)?;
+        Ok(is_proxy && is_validator(&self.ctx.pre(), delegation_address)?)
+    }
+
     /// Private method to read from storage data that are 100% in storage.
     fn force_read<T>(&self, key: &Key, read_type: ReadType) -> Result<T>
     where
@@ -678,6 +788,10 @@ enum KeyType {
     #[allow(non_camel_case_types)]
     VOTE,
     #[allow(non_camel_case_types)]
+    VOTE_PROXY,
+    #[allow(non_camel_case_types)]
+    PROXY_DELEGATOR,
+    #[allow(non_camel_case_types)]
     CONTENT,
     #[allow(non_camel_case_types)]
     PROPOSAL_CODE,
@@ -709,6 +823,10 @@ impl KeyType {
     fn from_key(key: &Key, native_token: &Address) -> Self {
         if gov_storage::is_vote_key(key) {
             Self::VOTE
+        } else if gov_storage::is_vote_proxy_key(key).is_some() {
+            Self::VOTE_PROXY
+        } else if gov_storage::is_proxy_delegator_key(key).is_some() {
+            Self::PROXY_DELEGATOR
         } else if gov_storage::is_content_key(key) {
             KeyType::CONTENT
         } else if gov_storage::is_proposal_type_key(key) {
@@ -120,6 +120,20 @@ impl ProposalEvent {
         )
     }
 
+    /// Create a new proposal event for ibc client recovery proposal
+    pub fn ibc_client_recovery_proposal_event(
+        proposal_id: u64,
+        result: bool,
+    ) -> Self {
+        ProposalEvent::new(
+            EventType::Proposal.to_string(),
+            TallyResult::Passed,
+            proposal_id,
+            false,
+            result,
+        )
+    }
+
     /// Create a new proposal event for eth proposal
     pub fn eth_proposal_event(proposal_id: u64, result: bool) -> Self {
         ProposalEvent::new(
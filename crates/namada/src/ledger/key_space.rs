@@ -0,0 +1,111 @@
+//! The storage key space of the protocol modules
+
+use std::collections::BTreeSet;
+use std::sync::OnceLock;
+
+use namada_core::types::address::Address;
+pub use namada_core::types::key_space::*;
+use namada_core::types::storage::Key;
+
+/// Build the key space of all the protocol modules, checking that the key
+/// prefixes of different modules don't overlap
+pub fn key_space() -> Result<KeySpace, Error> {
+    KeySpace::new(vec![
+        storage_key_space(),
+        namada_account::key_space(),
+        namada_token::storage_key::key_space(),
+        namada_token::storage_key::masp_key_space(),
+        namada_parameters::storage::key_space(),
+        namada_proof_of_stake::storage_key::key_space(),
+        namada_governance::storage::keys::key_space(),
+        namada_governance::pgf::storage::keys::key_space(),
+        namada_ibc::storage::key_space(),
+        namada_ethereum_bridge::storage::key_space(),
+    ])
+}
+
+/// Get the native VPs guarding the given keys, which must be triggered by a
+/// tx that changes any of them
+pub fn guarding_vps<'a>(
+    keys: impl IntoIterator<Item = &'a Key>,
+) -> BTreeSet<Address> {
+    static KEY_SPACE: OnceLock<KeySpace> = OnceLock::new();
+    let key_space = KEY_SPACE.get_or_init(|| {
+        key_space().expect("The storage key space must be valid")
+    });
+    keys.into_iter()
+        .filter_map(|key| key_space.vp_of(key))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use namada_core::types::address::testing::established_address_1;
+    use namada_core::types::address::{nam, InternalAddress};
+    use namada_core::types::storage::Key;
+
+    use super::*;
+
+    #[test]
+    fn test_key_space_has_no_overlaps() {
+        let key_space = key_space().unwrap();
+
+        let owner = established_address_1();
+        let token = nam();
+        let keys: [(Key, &str); 5] = [
+            (Key::validity_predicate(&owner), "storage"),
+            (
+                namada_token::storage_key::balance_key(&token, &owner),
+                "trans_token",
+            ),
+            (
+                namada_proof_of_stake::storage_key::params_key(),
+                "proof_of_stake",
+            ),
+            (
+                namada_governance::storage::keys::get_counter_key(),
+                "governance",
+            ),
+            (namada_ibc::storage::client_counter_key(), "ibc"),
+        ];
+        for (key, module) in keys {
+            assert_eq!(key_space.module_of(&key), Some(module), "{key}");
+        }
+    }
+
+    #[test]
+    fn test_native_vps_guard_their_keys() {
+        let owner = established_address_1();
+        let keys: [(Key, Option<InternalAddress>); 6] = [
+            (Key::validity_predicate(&owner), None),
+            (
+                namada_token::storage_key::balance_key(&nam(), &owner),
+                Some(InternalAddress::Multitoken),
+            ),
+            (
+                namada_proof_of_stake::storage_key::params_key(),
+                Some(InternalAddress::PoS),
+            ),
+            (
+                namada_governance::storage::keys::get_counter_key(),
+                Some(InternalAddress::Governance),
+            ),
+            (
+                namada_ethereum_bridge::storage::bridge_pool::get_nonce_key(),
+                Some(InternalAddress::EthBridgePool),
+            ),
+            (
+                namada_ethereum_bridge::storage::emergency_pause::paused_key(),
+                Some(InternalAddress::EthBridge),
+            ),
+        ];
+        for (key, vp) in keys {
+            assert_eq!(
+                guarding_vps([&key]),
+                vp.into_iter().map(Address::Internal).collect(),
+                "{key}"
+            );
+        }
+    }
+}
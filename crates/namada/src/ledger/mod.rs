@@ -3,6 +3,7 @@
 pub use namada_sdk::{eth_bridge, events};
 pub mod governance;
 pub mod ibc;
+pub mod key_space;
 pub mod native_vp;
 pub mod pgf;
 pub mod pos;
@@ -22,7 +23,9 @@ pub use {
 
 #[cfg(feature = "wasm-runtime")]
 mod dry_run_tx {
-    use namada_sdk::queries::{EncodedResponseQuery, RequestCtx, RequestQuery};
+    use namada_sdk::queries::{
+        EncodedResponseQuery, GasEstimate, RequestCtx, RequestQuery, RPC,
+    };
     use namada_state::{DBIter, ResultExt, StorageHasher, DB};
     use namada_tx::data::GasLimit;
 
@@ -30,7 +33,9 @@ mod dry_run_tx {
     use crate::vm::wasm::{TxCache, VpCache};
     use crate::vm::WasmCacheAccess;
 
-    /// Dry run a transaction
+    /// Dry run a transaction. For the `estimate_gas` query, respond with the
+    /// gas estimate of the transaction instead of its result, or an error if
+    /// the transaction is not accepted.
     pub fn dry_run_tx<D, H, CA>(
         mut ctx: RequestCtx<'_, D, H, VpCache<CA>, TxCache<CA>>,
         request: &RequestQuery,
@@ -42,7 +47,6 @@ mod dry_run_tx {
     {
         use borsh_ext::BorshSerializeExt;
         use namada_gas::{Gas, GasMetering, TxGasMeter};
-        use namada_state::TempWlStorage;
         use namada_tx::data::{DecryptedTx, TxType};
         use namada_tx::Tx;
 
@@ -51,15 +55,24 @@ mod dry_run_tx {
 
         let mut tx = Tx::try_from(&request.data[..]).into_storage_result()?;
         tx.validate_tx().into_storage_result()?;
+        let estimate_gas = request.path == RPC.shell().estimate_gas_path();
 
-        let mut temp_wl_storage = TempWlStorage::new(&ctx.wl_storage.storage);
+        let mut temp_wl_storage = ctx.wl_storage.fork();
         let mut cumulated_gas = Gas::default();
 
         // Wrapper dry run to allow estimating the gas cost of a transaction
         let mut tx_gas_meter = match tx.header().tx_type {
             TxType::Wrapper(wrapper) => {
-                let mut tx_gas_meter =
-                    TxGasMeter::new(wrapper.gas_limit.to_owned());
+                // The gas limit of a wrapper whose gas is estimated is only
+                // provisional, so it's run with the max block gas instead
+                let gas_limit = if estimate_gas {
+                    GasLimit::from(namada_parameters::get_max_block_gas(
+                        ctx.wl_storage,
+                    )?)
+                } else {
+                    wrapper.gas_limit.to_owned()
+                };
+                let mut tx_gas_meter = TxGasMeter::new(gas_limit);
                 protocol::apply_wrapper_tx(
                     tx.clone(),
                     &wrapper,
@@ -122,7 +135,26 @@ mod dry_run_tx {
         data.gas_used = cumulated_gas;
         // NOTE: the keys changed by the wrapper transaction (if any) are not
         // returned from this function
-        let data = data.serialize_to_vec();
+        let data = if estimate_gas {
+            // The gas of a rejected tx is not a valid estimate
+            if !data.is_accepted() {
+                let rejected_vps = data
+                    .vps_result
+                    .rejected_vps
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(namada_state::StorageError::new(format!(
+                    "The gas cannot be estimated, the tx is rejected by the \
+                     VPs of {rejected_vps}"
+                )));
+            }
+            GasEstimate::new(cumulated_gas.get_whole_gas_units())
+                .serialize_to_vec()
+        } else {
+            data.serialize_to_vec()
+        };
         Ok(EncodedResponseQuery {
             data,
             proof: None,
@@ -137,13 +169,14 @@ mod test {
     use borsh_ext::BorshSerializeExt;
     use namada_core::types::address;
     use namada_core::types::hash::Hash;
-    use namada_core::types::storage::{BlockHeight, Key};
+    use namada_core::types::storage::{BlockHeight, DbKeySeg, Key};
     use namada_sdk::queries::{
         EncodedResponseQuery, RequestCtx, RequestQuery, Router, RPC,
     };
     use namada_sdk::tendermint_rpc::{self, Error as RpcError, Response};
     use namada_state::testing::TestWlStorage;
     use namada_state::StorageWrite;
+    use namada_test_utils::tx_data::TxWriteData;
     use namada_test_utils::TestWasms;
     use namada_tx::data::decrypted::DecryptedTx;
     use namada_tx::data::TxType;
@@ -151,6 +184,7 @@ mod test {
     use tempfile::TempDir;
 
     use crate::ledger::events::log::EventLog;
+    use crate::ledger::events::mempool::MempoolRejections;
     use crate::ledger::queries::Client;
     use crate::token;
     use crate::vm::wasm::{TxCache, VpCache};
@@ -167,6 +201,8 @@ mod test {
         pub wl_storage: TestWlStorage,
         /// event log
         pub event_log: EventLog,
+        /// mempool rejections log
+        pub mempool_rejections: MempoolRejections,
         /// VP wasm compilation cache
         pub vp_wasm_cache: VpCache<WasmCacheRoAccess>,
         /// tx wasm compilation cache
@@ -200,6 +236,7 @@ mod test {
                     "Max block gas parameter must be initialized in storage",
                 );
             let event_log = EventLog::default();
+            let mempool_rejections = MempoolRejections::default();
             let (vp_wasm_cache, vp_cache_dir) =
                 wasm::compilation_cache::common::testing::cache();
             let (tx_wasm_cache, tx_cache_dir) =
@@ -208,6 +245,7 @@ mod test {
                 rpc,
                 wl_storage,
                 event_log,
+                mempool_rejections,
                 vp_wasm_cache: vp_wasm_cache.read_only(),
                 tx_wasm_cache: tx_wasm_cache.read_only(),
                 vp_cache_dir,
@@ -244,13 +282,14 @@ mod test {
             let ctx = RequestCtx {
                 wl_storage: &self.wl_storage,
                 event_log: &self.event_log,
+                mempool_rejections: &self.mempool_rejections,
                 vp_wasm_cache: self.vp_wasm_cache.clone(),
                 tx_wasm_cache: self.tx_wasm_cache.clone(),
                 storage_read_past_height_limit: None,
             };
             // TODO: this is a hack to propagate errors to the caller, we should
             // really permit error types other than [`std::io::Error`]
-            if request.path == "/shell/dry_run_tx" {
+            if super::queries::is_dry_run_tx(&request) {
                 super::dry_run_tx(ctx, &request)
             } else {
                 self.rpc.handle(ctx, &request)
@@ -299,11 +338,70 @@ mod test {
         let tx_bytes = outer_tx.to_bytes();
         let result = RPC
             .shell()
-            .dry_run_tx(&client, Some(tx_bytes), None, false)
+            .dry_run_tx(&client, Some(tx_bytes.clone()), None, false)
             .await
             .unwrap();
         assert!(result.data.is_accepted());
 
+        // Request the gas estimate of the same tx
+        let estimate = RPC
+            .shell()
+            .estimate_gas(&client, Some(tx_bytes), None, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            estimate.data.gas_used,
+            result.data.gas_used.get_whole_gas_units()
+        );
+        assert!(u64::from(estimate.data.gas_limit) >= estimate.data.gas_used);
+
+        // A tx rejected by a VP can be dry run, but its gas can't be
+        // estimated
+        let tx_write = TestWasms::TxWriteStorageKey.read_bytes();
+        let tx_write_hash = Hash::sha256(&tx_write);
+        client
+            .wl_storage
+            .storage
+            .write(&Key::wasm_code(&tx_write_hash), &tx_write)
+            .unwrap();
+        client
+            .wl_storage
+            .storage
+            .write(
+                &Key::wasm_code_len(&tx_write_hash),
+                (tx_write.len() as u64).serialize_to_vec(),
+            )
+            .unwrap();
+        let param_key =
+            Key::from(DbKeySeg::AddressSeg(namada_parameters::ADDRESS))
+                .push(&"unknown".to_owned())
+                .unwrap();
+        let mut rejected_tx =
+            Tx::from_type(TxType::Decrypted(DecryptedTx::Decrypted));
+        rejected_tx.header.chain_id =
+            client.wl_storage.storage.chain_id.clone();
+        rejected_tx.set_code(Code::from_hash(tx_write_hash, None));
+        rejected_tx.set_data(Data::new(
+            TxWriteData {
+                key: param_key,
+                value: b"value".to_vec(),
+            }
+            .serialize_to_vec(),
+        ));
+        let tx_bytes = rejected_tx.to_bytes();
+        let result = RPC
+            .shell()
+            .dry_run_tx(&client, Some(tx_bytes.clone()), None, false)
+            .await
+            .unwrap();
+        assert!(!result.data.is_accepted());
+        assert!(
+            RPC.shell()
+                .estimate_gas(&client, Some(tx_bytes), None, false)
+                .await
+                .is_err()
+        );
+
         // Request storage value for a balance key ...
         let token_addr = address::testing::established_address_1();
         let owner = address::testing::established_address_2();
@@ -20,10 +20,11 @@ use eyre::eyre;
 use namada_core::hints;
 use namada_core::types::eth_bridge_pool::erc20_token_address;
 use namada_ethereum_bridge::storage::bridge_pool::{
-    get_pending_key, is_bridge_pool_key, BRIDGE_POOL_ADDRESS,
+    get_pending_key, get_transfer_status_key, is_bridge_pool_key,
+    TransferStatus, BRIDGE_POOL_ADDRESS,
 };
 use namada_ethereum_bridge::storage::parameters::read_native_erc20_address;
-use namada_ethereum_bridge::storage::whitelist;
+use namada_ethereum_bridge::storage::{emergency_pause, whitelist};
 use namada_ethereum_bridge::ADDRESS as BRIDGE_ADDRESS;
 use namada_state::{DBIter, StorageHasher, DB};
 use namada_tx::Tx;
@@ -537,6 +538,14 @@ where
             verifiers_len = _verifiers.len(),
             "Ethereum Bridge Pool VP triggered",
         );
+        let paused: Option<bool> =
+            (&self.ctx).read_pre_value(&emergency_pause::paused_key())?;
+        if paused.unwrap_or(false) {
+            tracing::debug!(
+                "Rejecting transaction as the Ethereum bridge is paused"
+            );
+            return Ok(false);
+        }
         let Some(tx_data) = tx.data() else {
             return Err(eyre!("No transaction data found").into());
         };
@@ -564,8 +573,9 @@ where
             }
             _ => {}
         }
+        let status_key = get_transfer_status_key(&transfer.keccak256());
         for key in keys_changed.iter().filter(|k| is_bridge_pool_key(k)) {
-            if *key != pending_key {
+            if *key != pending_key && *key != status_key {
                 tracing::debug!(
                     "Rejecting transaction as it is attempting to change an \
                      incorrect key in the Ethereum bridge pool: {}.\n \
@@ -576,6 +586,18 @@ where
                 return Ok(false);
             }
         }
+        if keys_changed.contains(&status_key) {
+            let status: Option<TransferStatus> =
+                (&self.ctx).read_post_value(&status_key)?;
+            if status != Some(TransferStatus::Pending) {
+                tracing::debug!(
+                    ?status,
+                    "Rejecting transaction as the status of the transfer was \
+                     not set to pending"
+                );
+                return Ok(false);
+            }
+        }
         let pending: PendingTransfer =
             (&self.ctx).read_post_value(&pending_key)?.ok_or(eyre!(
                 "Rejecting transaction as the transfer wasn't added to the \
@@ -1316,6 +1338,61 @@ mod test_bridge_pool_vp {
         );
     }
 
+    /// Test that the status of a transfer may be set to pending
+    /// when adding it to the pool.
+    #[test]
+    fn test_pending_transfer_status_accepted() {
+        assert_bridge_pool(
+            SignedAmount::Negative(GAS_FEE.into()),
+            SignedAmount::Positive(GAS_FEE.into()),
+            SignedAmount::Negative(TOKENS.into()),
+            SignedAmount::Positive(TOKENS.into()),
+            |transfer, log| {
+                let status_key = get_transfer_status_key(&transfer.keccak256());
+                log.write(
+                    &get_pending_key(transfer),
+                    transfer.serialize_to_vec(),
+                )
+                .unwrap();
+                log.write(
+                    &status_key,
+                    TransferStatus::Pending.serialize_to_vec(),
+                )
+                .unwrap();
+                BTreeSet::from([get_pending_key(transfer), status_key])
+            },
+            Expect::True,
+        );
+    }
+
+    /// Test that a tx adding a transfer to the pool may not set its
+    /// status to anything other than pending.
+    #[test]
+    fn test_non_pending_transfer_status_rejected() {
+        assert_bridge_pool(
+            SignedAmount::Negative(GAS_FEE.into()),
+            SignedAmount::Positive(GAS_FEE.into()),
+            SignedAmount::Negative(TOKENS.into()),
+            SignedAmount::Positive(TOKENS.into()),
+            |transfer, log| {
+                let status_key = get_transfer_status_key(&transfer.keccak256());
+                log.write(
+                    &get_pending_key(transfer),
+                    transfer.serialize_to_vec(),
+                )
+                .unwrap();
+                log.write(
+                    &status_key,
+                    TransferStatus::Confirmed { nonce: 0.into() }
+                        .serialize_to_vec(),
+                )
+                .unwrap();
+                BTreeSet::from([get_pending_key(transfer), status_key])
+            },
+            Expect::False,
+        );
+    }
+
     /// Test that adding a transfer to the pool
     /// that is already in the pool fails.
     #[test]
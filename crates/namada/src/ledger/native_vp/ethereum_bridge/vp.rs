@@ -2,16 +2,20 @@
 use std::collections::{BTreeSet, HashSet};
 
 use eyre::{eyre, Result};
-use namada_core::types::address::Address;
+use namada_core::types::address::{Address, InternalAddress};
+use namada_core::types::eth_bridge_pool::erc20_nut_address;
+use namada_core::types::ethereum_events::EthAddress;
 use namada_core::types::hash::StorageHasher;
 use namada_core::types::storage::Key;
-use namada_ethereum_bridge;
-use namada_ethereum_bridge::storage;
-use namada_ethereum_bridge::storage::escrow_key;
+use namada_ethereum_bridge::storage::{emergency_pause, escrow_key, whitelist};
+use namada_ethereum_bridge::{self, storage};
+use namada_state::StorageRead;
 use namada_tx::Tx;
 
 use crate::ledger::native_vp::{Ctx, NativeVp, StorageReader};
-use crate::token::storage_key::{balance_key, is_balance_key};
+use crate::token::storage_key::{
+    balance_key, is_any_minted_balance_key, is_balance_key, minted_balance_key,
+};
 use crate::token::Amount;
 use crate::vm::WasmCacheAccess;
 
@@ -83,6 +87,198 @@ where
             Ok(false)
         }
     }
+
+    /// Validate the changes to the emergency pause of the bridge. Consensus
+    /// validators may vote to pause the bridge in the current epoch, and the
+    /// pause status may only be changed by an accepted governance proposal.
+    /// The keys changed under this account must all belong to the emergency
+    /// pause.
+    fn check_emergency_pause(
+        &self,
+        tx: &Tx,
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let current_epoch = self.ctx.pre().get_block_epoch()?;
+        for key in keys_changed
+            .iter()
+            .filter(|key| storage::has_eth_addr_segment(key))
+        {
+            if emergency_pause::is_paused_key(key) {
+                if !self.is_proposal_accepted(tx) {
+                    tracing::debug!(
+                        "The Ethereum bridge pause status can only be changed \
+                         by governance"
+                    );
+                    return Ok(false);
+                }
+            } else if let Some((epoch, validator)) =
+                emergency_pause::is_vote_key(key)
+            {
+                if epoch != current_epoch
+                    || !verifiers.contains(validator)
+                    || !namada_proof_of_stake::is_validator(
+                        &self.ctx.pre(),
+                        validator,
+                    )?
+                    || !self.ctx.post().has_key(key)?
+                {
+                    tracing::debug!(
+                        %key,
+                        "Invalid vote to pause the Ethereum bridge"
+                    );
+                    return Ok(false);
+                }
+            } else {
+                tracing::debug!(
+                    %key,
+                    "Emergency pause changes cannot be mixed with other \
+                     changes to the Ethereum bridge"
+                );
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Validate the changes to the ERC20 whitelist, which may only be made by
+    /// an accepted governance proposal. This is how the token cap of an asset
+    /// is raised, so that its NUTs can be converted into wrapped ERC20s. The
+    /// keys changed under this account must all belong to the whitelist.
+    fn check_whitelist_update(
+        &self,
+        tx: &Tx,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool> {
+        for key in keys_changed
+            .iter()
+            .filter(|key| storage::has_eth_addr_segment(key))
+        {
+            if !whitelist::is_cap_or_whitelisted_key(key) {
+                tracing::debug!(
+                    %key,
+                    "ERC20 whitelist changes cannot be mixed with other \
+                     changes to the Ethereum bridge"
+                );
+                return Ok(false);
+            }
+        }
+        if !self.is_proposal_accepted(tx) {
+            tracing::debug!(
+                "The ERC20 whitelist can only be changed by governance"
+            );
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Validate the conversion of NUTs into wrapped ERC20s. For every asset
+    /// whose supplies changed, the supply of its NUTs must decrease by the
+    /// amount that the supply of its wrapped ERC20s increases, and the asset
+    /// must be whitelisted with a token cap that still covers the new supply
+    /// of wrapped ERC20s. The changes of the balances are validated by the
+    /// multitoken and NUT VPs.
+    fn check_nut_conversion(
+        &self,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool> {
+        let mut assets = BTreeSet::new();
+        for key in keys_changed {
+            if storage::has_eth_addr_segment(key) {
+                tracing::debug!(
+                    %key,
+                    "NUT conversions cannot be mixed with other changes to \
+                     the Ethereum bridge"
+                );
+                return Ok(false);
+            }
+            if let Some(Address::Internal(
+                InternalAddress::Erc20(asset) | InternalAddress::Nut(asset),
+            )) = is_any_minted_balance_key(key)
+            {
+                assets.insert(*asset);
+            }
+        }
+        for asset in assets {
+            if !self.is_valid_nut_conversion(&asset)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Check the conversion of the NUTs of the given asset into wrapped
+    /// ERC20s.
+    fn is_valid_nut_conversion(&self, asset: &EthAddress) -> Result<bool> {
+        let whitelist_key = |suffix| -> Key {
+            whitelist::Key {
+                asset: *asset,
+                suffix,
+            }
+            .into()
+        };
+        let nut_supply_key = minted_balance_key(&erc20_nut_address(asset));
+        let wrapped_supply_key =
+            whitelist_key(whitelist::KeyType::WrappedSupply);
+        let pre = self.ctx.pre();
+        let post = self.ctx.post();
+
+        let nut_supply_pre: Amount =
+            pre.read(&nut_supply_key)?.unwrap_or_default();
+        let nut_supply_post: Amount =
+            post.read(&nut_supply_key)?.unwrap_or_default();
+        let wrapped_supply_pre: Amount =
+            pre.read(&wrapped_supply_key)?.unwrap_or_default();
+        let wrapped_supply_post: Amount =
+            post.read(&wrapped_supply_key)?.unwrap_or_default();
+        let nuts_burned = nut_supply_pre.checked_sub(nut_supply_post);
+        let erc20s_minted = wrapped_supply_post.checked_sub(wrapped_supply_pre);
+        match (nuts_burned, erc20s_minted) {
+            (Some(burned), Some(minted))
+                if burned == minted && !minted.is_zero() => {}
+            _ => {
+                tracing::debug!(
+                    %asset,
+                    ?nuts_burned,
+                    ?erc20s_minted,
+                    "The wrapped ERC20s minted must match the NUTs burned"
+                );
+                return Ok(false);
+            }
+        }
+
+        let is_whitelisted: bool = post
+            .read(&whitelist_key(whitelist::KeyType::Whitelisted))?
+            .unwrap_or_default();
+        let cap: Amount = post
+            .read(&whitelist_key(whitelist::KeyType::Cap))?
+            .unwrap_or_default();
+        if !is_whitelisted || wrapped_supply_post > cap {
+            tracing::debug!(
+                %asset,
+                is_whitelisted,
+                ?cap,
+                ?wrapped_supply_post,
+                "NUTs can only be converted within the token cap of a \
+                 whitelisted asset"
+            );
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    /// Check if the tx is the execution of an accepted governance proposal
+    fn is_proposal_accepted(&self, tx: &Tx) -> bool {
+        tx.data()
+            .map(|data| {
+                namada_governance::storage::is_proposal_accepted(
+                    &self.ctx.pre(),
+                    &data,
+                )
+                .unwrap_or(false)
+            })
+            .unwrap_or(false)
+    }
 }
 
 impl<'a, DB, H, CA> NativeVp for EthBridge<'a, DB, H, CA>
@@ -103,9 +299,14 @@ where
     /// to happen natively i.e. bypassing this validity predicate. For example,
     /// changes to the `eth_msgs/...` keys. For those cases, we reject here as
     /// no wasm transactions should be able to modify those keys.
+    ///
+    /// The only other changes permitted are the validators' votes to pause
+    /// the bridge and its resumption by governance, the changes to the ERC20
+    /// whitelist by governance and the conversions of NUTs into wrapped
+    /// ERC20s.
     fn validate_tx(
         &self,
-        _: &Tx,
+        tx: &Tx,
         keys_changed: &BTreeSet<Key>,
         verifiers: &BTreeSet<Address>,
     ) -> Result<bool, Self::Error> {
@@ -115,6 +316,28 @@ where
             "Ethereum Bridge VP triggered",
         );
 
+        if keys_changed
+            .iter()
+            .any(emergency_pause::is_emergency_pause_key)
+        {
+            return Ok(self.check_emergency_pause(
+                tx,
+                keys_changed,
+                verifiers,
+            )?);
+        }
+
+        if keys_changed
+            .iter()
+            .any(whitelist::is_cap_or_whitelisted_key)
+        {
+            return Ok(self.check_whitelist_update(tx, keys_changed)?);
+        }
+
+        if keys_changed.iter().any(is_nut_conversion_key) {
+            return Ok(self.check_nut_conversion(keys_changed)?);
+        }
+
         if !validate_changed_keys(&self.ctx.storage.native_token, keys_changed)?
         {
             return Ok(false);
@@ -124,6 +347,17 @@ where
     }
 }
 
+/// Check if the given key is the supply of the NUTs or of the wrapped ERC20s
+/// of an asset, which a wasm tx may only change to convert NUTs.
+fn is_nut_conversion_key(key: &Key) -> bool {
+    matches!(
+        is_any_minted_balance_key(key),
+        Some(Address::Internal(
+            InternalAddress::Erc20(_) | InternalAddress::Nut(_)
+        ))
+    )
+}
+
 /// Checks if `keys_changed` represents a valid set of changed keys.
 ///
 /// This implies checking if two distinct keys were changed:
@@ -170,6 +404,7 @@ mod tests {
 
     use namada_core::borsh::BorshSerializeExt;
     use namada_gas::TxGasMeter;
+    use namada_proof_of_stake::storage::write_validator_max_commission_rate_change;
     use namada_state::StorageWrite;
     use namada_tx::data::TxType;
     use namada_tx::Tx;
@@ -352,6 +587,181 @@ mod tests {
         }
     }
 
+    /// Test that a vote to pause the bridge is only accepted from a
+    /// validator that is a verifier of the tx.
+    #[test]
+    fn test_emergency_pause_vote() {
+        let mut wl_storage = setup_storage();
+        let validator = established_address_1();
+        let vote_key = emergency_pause::vote_key(
+            wl_storage.storage.block.epoch,
+            &validator,
+        );
+        wl_storage
+            .write_log
+            .write(&vote_key, ().serialize_to_vec())
+            .expect("Test failed");
+        let keys_changed = BTreeSet::from([vote_key]);
+        let tx = Tx::from_type(TxType::Raw);
+
+        let validate = |wl_storage: &WlStorage<MockDB, Sha256Hasher>,
+                        verifiers: &BTreeSet<Address>| {
+            let vp = EthBridge {
+                ctx: setup_ctx(
+                    &tx,
+                    &wl_storage.storage,
+                    &wl_storage.write_log,
+                    &keys_changed,
+                    verifiers,
+                ),
+            };
+            vp.validate_tx(&tx, &keys_changed, verifiers)
+                .expect("Test failed")
+        };
+
+        // The voter must be a verifier
+        assert!(!validate(&wl_storage, &BTreeSet::new()));
+        // The voter must be a validator
+        let verifiers = BTreeSet::from([validator.clone()]);
+        assert!(!validate(&wl_storage, &verifiers));
+
+        let mut wl_storage = setup_storage();
+        write_validator_max_commission_rate_change(
+            &mut wl_storage,
+            &validator,
+            Default::default(),
+        )
+        .expect("Test failed");
+        wl_storage.commit_block().expect("Test failed");
+        for key in &keys_changed {
+            wl_storage
+                .write_log
+                .write(key, ().serialize_to_vec())
+                .expect("Test failed");
+        }
+        assert!(validate(&wl_storage, &verifiers));
+    }
+
+    /// Test that the NUTs of an asset can only be converted into wrapped
+    /// ERC20s one for one, within the token cap of the asset.
+    #[test]
+    fn test_nut_conversion() {
+        let mut wl_storage = setup_storage();
+        let asset = ethereum_events::testing::DAI_ERC20_ETH_ADDRESS;
+        let owner = established_address_1();
+        let nut = erc20_nut_address(&asset);
+        let erc20 = wrapped_erc20s::token(&asset);
+        let whitelist_key =
+            |suffix| -> Key { whitelist::Key { asset, suffix }.into() };
+        wl_storage
+            .write(&whitelist_key(whitelist::KeyType::Whitelisted), true)
+            .expect("Test failed");
+        wl_storage
+            .write(&whitelist_key(whitelist::KeyType::Cap), Amount::from(100))
+            .expect("Test failed");
+        for (token, amount) in [(&nut, 150), (&erc20, 50)] {
+            wl_storage
+                .write(&balance_key(token, &owner), Amount::from(amount))
+                .expect("Test failed");
+            wl_storage
+                .write(&minted_balance_key(token), Amount::from(amount))
+                .expect("Test failed");
+        }
+        wl_storage.commit_block().expect("Test failed");
+
+        let tx = Tx::from_type(TxType::Raw);
+        let verifiers = BTreeSet::new();
+        let validate = |nuts_burned: u64, erc20s_minted: u64| {
+            let mut write_log = WriteLog::default();
+            let mut keys_changed = BTreeSet::new();
+            for (token, amount) in
+                [(&nut, 150 - nuts_burned), (&erc20, 50 + erc20s_minted)]
+            {
+                for key in
+                    [balance_key(token, &owner), minted_balance_key(token)]
+                {
+                    write_log
+                        .write(&key, Amount::from(amount).serialize_to_vec())
+                        .expect("Test failed");
+                    keys_changed.insert(key);
+                }
+            }
+            let vp = EthBridge {
+                ctx: setup_ctx(
+                    &tx,
+                    &wl_storage.storage,
+                    &write_log,
+                    &keys_changed,
+                    &verifiers,
+                ),
+            };
+            vp.validate_tx(&tx, &keys_changed, &verifiers)
+                .expect("Test failed")
+        };
+
+        assert!(validate(50, 50));
+        // The new supply of wrapped ERC20s would exceed the token cap
+        assert!(!validate(60, 60));
+        // The wrapped ERC20s minted must match the NUTs burned
+        assert!(!validate(50, 40));
+        assert!(!validate(40, 50));
+        assert!(!validate(0, 0));
+    }
+
+    /// Test that the token cap of an asset may only be raised by an accepted
+    /// governance proposal.
+    #[test]
+    fn test_whitelist_update_by_governance() {
+        let mut wl_storage = setup_storage();
+        let proposal_id = 0_u64;
+        let cap_key: Key = whitelist::Key {
+            asset: ethereum_events::testing::DAI_ERC20_ETH_ADDRESS,
+            suffix: whitelist::KeyType::Cap,
+        }
+        .into();
+        wl_storage
+            .write_log
+            .write(&cap_key, Amount::from(1000).serialize_to_vec())
+            .expect("Test failed");
+        let keys_changed = BTreeSet::from([cap_key]);
+        let verifiers = BTreeSet::new();
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.add_data(proposal_id);
+
+        let validate = |wl_storage: &WlStorage<MockDB, Sha256Hasher>,
+                        tx: &Tx| {
+            let vp = EthBridge {
+                ctx: setup_ctx(
+                    tx,
+                    &wl_storage.storage,
+                    &wl_storage.write_log,
+                    &keys_changed,
+                    &verifiers,
+                ),
+            };
+            vp.validate_tx(tx, &keys_changed, &verifiers)
+                .expect("Test failed")
+        };
+
+        // The tx must execute a governance proposal
+        assert!(!validate(&wl_storage, &Tx::from_type(TxType::Raw)));
+        // The proposal must have been accepted
+        assert!(!validate(&wl_storage, &tx));
+
+        let write_log = std::mem::take(&mut wl_storage.write_log);
+        wl_storage
+            .write(
+                &namada_governance::storage::keys::get_proposal_execution_key(
+                    proposal_id,
+                ),
+                (),
+            )
+            .expect("Test failed");
+        wl_storage.commit_block().expect("Test failed");
+        wl_storage.write_log = write_log;
+        assert!(validate(&wl_storage, &tx));
+    }
+
     /// Test that escrowing Nam is accepted.
     #[test]
     fn test_escrow_nam_accepted() {
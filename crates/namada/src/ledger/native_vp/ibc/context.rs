@@ -204,13 +204,15 @@ where
         amount: DenominatedAmount,
     ) -> Result<()> {
         let amount = crate::token::denom_to_amount(amount, token, self)?;
+        if amount.is_zero() || src == dest {
+            return Ok(());
+        }
         let src_key = token::storage_key::balance_key(token, src);
         let dest_key = token::storage_key::balance_key(token, dest);
-        let src_bal: Option<Amount> = self.ctx.read(&src_key)?;
+        let src_bal: Option<Amount> = self.read(&src_key)?;
         let mut src_bal = src_bal.expect("The source has no balance");
         src_bal.spend(&amount);
-        let mut dest_bal: Amount =
-            self.ctx.read(&dest_key)?.unwrap_or_default();
+        let mut dest_bal: Amount = self.read(&dest_key)?.unwrap_or_default();
         dest_bal.receive(&amount);
 
         self.write(&src_key, src_bal.serialize_to_vec())?;
@@ -235,12 +237,12 @@ where
         let amount = crate::token::denom_to_amount(amount, token, self)?;
         let target_key = token::storage_key::balance_key(token, target);
         let mut target_bal: Amount =
-            self.ctx.read(&target_key)?.unwrap_or_default();
+            self.read(&target_key)?.unwrap_or_default();
         target_bal.receive(&amount);
 
         let minted_key = token::storage_key::minted_balance_key(token);
         let mut minted_bal: Amount =
-            self.ctx.read(&minted_key)?.unwrap_or_default();
+            self.read(&minted_key)?.unwrap_or_default();
         minted_bal.receive(&amount);
 
         self.write(&target_key, target_bal.serialize_to_vec())?;
@@ -262,12 +264,12 @@ where
         let amount = crate::token::denom_to_amount(amount, token, self)?;
         let target_key = token::storage_key::balance_key(token, target);
         let mut target_bal: Amount =
-            self.ctx.read(&target_key)?.unwrap_or_default();
+            self.read(&target_key)?.unwrap_or_default();
         target_bal.spend(&amount);
 
         let minted_key = token::storage_key::minted_balance_key(token);
         let mut minted_bal: Amount =
-            self.ctx.read(&minted_key)?.unwrap_or_default();
+            self.read(&minted_key)?.unwrap_or_default();
         minted_bal.spend(&amount);
 
         self.write(&target_key, target_bal.serialize_to_vec())?;
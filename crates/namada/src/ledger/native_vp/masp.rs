@@ -1,7 +1,7 @@
 //! MASP native VP
 
 use std::cmp::Ordering;
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 use borsh_ext::BorshSerializeExt;
 use masp_primitives::asset_type::AssetType;
@@ -11,8 +11,9 @@ use masp_primitives::transaction::components::I128Sum;
 use masp_primitives::transaction::Transaction;
 use namada_core::types::address::Address;
 use namada_core::types::address::InternalAddress::Masp;
+use namada_core::types::dec::Dec;
 use namada_core::types::masp::encode_asset_type;
-use namada_core::types::storage::{IndexedTx, Key};
+use namada_core::types::storage::{DbKeySeg, IndexedTx, Key};
 use namada_gas::MASP_VERIFY_SHIELDED_TX_GAS;
 use namada_sdk::masp::verify_shielded_tx;
 use namada_state::{OptionExt, ResultExt};
@@ -22,9 +23,13 @@ use ripemd::Digest as RipemdDigest;
 use sha2::Digest as Sha2Digest;
 use thiserror::Error;
 use token::storage_key::{
-    is_masp_allowed_key, is_masp_key, is_masp_nullifier_key,
+    is_any_shielded_rewards_key, is_masp_allowed_key, is_masp_key,
+    is_masp_nullifier_key, is_masp_reward_tokens_key,
     masp_commitment_anchor_key, masp_commitment_tree_key,
-    masp_convert_anchor_key, masp_nullifier_key, masp_pin_tx_key,
+    masp_convert_anchor_key, masp_kd_gain_key, masp_kp_gain_key,
+    masp_locked_ratio_target_key, masp_max_reward_rate_key, masp_nullifier_key,
+    masp_pin_tx_key, MASP_LOCKED_RATIO_TARGET_KEY, MASP_MAX_REWARD_RATE_KEY,
+    MASP_REWARD_PARAMS_KEYS,
 };
 use token::MaspDenom;
 
@@ -60,6 +65,114 @@ where
     H: 'static + namada_state::StorageHasher,
     CA: 'static + WasmCacheAccess,
 {
+    // Check that the shielded rewards parameters and the reward tokens are
+    // only changed by an accepted governance proposal, and to valid values
+    fn valid_rewards_update(
+        &self,
+        tx_data: &Tx,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool> {
+        let is_proposal_accepted = match tx_data.data() {
+            Some(data) => namada_governance::storage::is_proposal_accepted(
+                &self.ctx.pre(),
+                &data,
+            )?,
+            None => false,
+        };
+        if !is_proposal_accepted {
+            tracing::debug!(
+                "The shielded rewards can only be changed by governance"
+            );
+            return Ok(false);
+        }
+
+        for key in keys_changed {
+            if is_any_shielded_rewards_key(key).is_some() {
+                let param = match key.segments.last() {
+                    Some(DbKeySeg::StringSeg(param)) => param.as_str(),
+                    _ => return Ok(false),
+                };
+                // The state of the rewards controller is only updated by the
+                // protocol
+                if !MASP_REWARD_PARAMS_KEYS.contains(&param) {
+                    tracing::debug!(
+                        "The shielded rewards state {key} cannot be changed"
+                    );
+                    return Ok(false);
+                }
+                let Some(value) = self.ctx.read_post::<Dec>(key)? else {
+                    tracing::debug!(
+                        "The shielded rewards parameter {key} cannot be \
+                         removed"
+                    );
+                    return Ok(false);
+                };
+                // The max reward rate and the locked ratio target are
+                // fractions, the gains are only non-negative
+                let is_fraction = param == MASP_MAX_REWARD_RATE_KEY
+                    || param == MASP_LOCKED_RATIO_TARGET_KEY;
+                if value.is_negative() || (is_fraction && value > Dec::one()) {
+                    tracing::debug!(
+                        "Invalid shielded rewards parameter {key}: {value}"
+                    );
+                    return Ok(false);
+                }
+            } else if is_masp_reward_tokens_key(key) {
+                if !self.valid_reward_tokens(key)? {
+                    return Ok(false);
+                }
+            } else if is_masp_key(key) {
+                // The shielded pool cannot change together with its rewards
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    // Check that the reward tokens are only extended, with tokens that have a
+    // denomination and all the shielded rewards parameters
+    fn valid_reward_tokens(&self, key: &Key) -> Result<bool> {
+        let pre: BTreeMap<String, Address> =
+            self.ctx.read_pre(key)?.unwrap_or_default();
+        let Some(post) =
+            self.ctx.read_post::<BTreeMap<String, Address>>(key)?
+        else {
+            tracing::debug!("The shielded reward tokens cannot be removed");
+            return Ok(false);
+        };
+        if pre
+            .iter()
+            .any(|(alias, token)| post.get(alias) != Some(token))
+        {
+            tracing::debug!("A shielded reward token cannot be removed");
+            return Ok(false);
+        }
+        for token in post.values() {
+            if pre.values().any(|pre_token| pre_token == token) {
+                continue;
+            }
+            if token::read_denom(&self.ctx.post(), token)?.is_none() {
+                tracing::debug!("The reward token {token} has no denomination");
+                return Ok(false);
+            }
+            for param_key in [
+                masp_kp_gain_key(token),
+                masp_kd_gain_key(token),
+                masp_locked_ratio_target_key(token),
+                masp_max_reward_rate_key(token),
+            ] {
+                if !self.ctx.has_key_post(&param_key)? {
+                    tracing::debug!(
+                        "The reward token {token} is missing the shielded \
+                         rewards parameter {param_key}"
+                    );
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+
     // Check that the transaction correctly revealed the nullifiers
     fn valid_nullifiers_reveal(
         &self,
@@ -284,6 +397,15 @@ where
         keys_changed: &BTreeSet<Key>,
         _verifiers: &BTreeSet<Address>,
     ) -> Result<bool> {
+        // The shielded rewards are updated by governance proposals rather
+        // than by shielded actions
+        if keys_changed.iter().any(|key| {
+            is_any_shielded_rewards_key(key).is_some()
+                || is_masp_reward_tokens_key(key)
+        }) {
+            return self.valid_rewards_update(tx_data, keys_changed);
+        }
+
         let epoch = self.ctx.get_block_epoch()?;
         let conversion_state = self.ctx.storage.get_conversion_state();
         let (transfer, shielded_tx) = self.ctx.get_shielded_action(tx_data)?;
@@ -6,6 +6,7 @@ pub mod ibc;
 pub mod masp;
 pub mod multitoken;
 pub mod parameters;
+pub mod scheduler;
 
 use std::cell::RefCell;
 use std::collections::BTreeSet;
@@ -516,6 +517,23 @@ where
         .into_storage_result()
     }
 
+    fn get_changed_keys(
+        &self,
+        prefix: &Key,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<Key>, state::StorageError> {
+        vp_host_fns::get_changed_keys(
+            &mut self.gas_meter.borrow_mut(),
+            self.keys_changed,
+            prefix,
+            offset,
+            limit,
+            &mut self.sentinel.borrow_mut(),
+        )
+        .into_storage_result()
+    }
+
     fn eval(
         &self,
         vp_code_hash: Hash,
@@ -1,19 +1,20 @@
 //! Native VP for multitokens
 
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet};
 
 use namada_tx::Tx;
 use namada_vp_env::VpEnv;
 use thiserror::Error;
 
 use crate::ledger::native_vp::{self, Ctx, NativeVp};
+use crate::proof_of_stake::storage_key::is_bond_key;
 use crate::token::storage_key::{
-    is_any_minted_balance_key, is_any_minter_key, is_any_token_balance_key,
-    minter_key,
+    minter_key, vesting_key, BALANCE_STORAGE_KEY, MINTED_STORAGE_KEY,
+    MINTER_STORAGE_KEY, VESTING_STORAGE_KEY,
 };
-use crate::token::{Amount, Change};
+use crate::token::{Amount, Change, VestingSchedule};
 use crate::types::address::{Address, InternalAddress};
-use crate::types::storage::{Key, KeySeg};
+use crate::types::storage::{DbKeySeg, Key};
 use crate::vm::WasmCacheAccess;
 
 #[allow(missing_docs)]
@@ -51,61 +52,296 @@ where
         keys_changed: &BTreeSet<Key>,
         verifiers: &BTreeSet<Address>,
     ) -> Result<bool> {
-        let mut changes = HashMap::new();
-        let mut mints = HashMap::new();
+        // Collect the changes of each token in a single pass over the changed
+        // keys, skipping the keys outside of the multitoken sub-space
+        let mut tokens: BTreeMap<&Address, TokenChanges<'_>> = BTreeMap::new();
         for key in keys_changed {
-            if let Some([token, _]) = is_any_token_balance_key(key) {
-                let pre: Amount = self.ctx.read_pre(key)?.unwrap_or_default();
-                let post: Amount = self.ctx.read_post(key)?.unwrap_or_default();
-                let diff = post.change() - pre.change();
-                match changes.get_mut(token) {
-                    Some(change) => *change += diff,
-                    None => _ = changes.insert(token, diff),
+            let Some(multitoken_key) = MultitokenKey::parse(key) else {
+                continue;
+            };
+            match multitoken_key {
+                MultitokenKey::Balance { token, owner } => {
+                    let pre: Amount =
+                        self.ctx.read_pre(key)?.unwrap_or_default();
+                    let post: Amount =
+                        self.ctx.read_post(key)?.unwrap_or_default();
+                    let changes = tokens.entry(token).or_default();
+                    changes.balances_change += post.change() - pre.change();
+                    changes.balances.insert(owner, (pre, post));
                 }
-            } else if let Some(token) = is_any_minted_balance_key(key) {
-                let pre: Amount = self.ctx.read_pre(key)?.unwrap_or_default();
-                let post: Amount = self.ctx.read_post(key)?.unwrap_or_default();
-                let diff = post.change() - pre.change();
-                match mints.get_mut(token) {
-                    Some(mint) => *mint += diff,
-                    None => _ = mints.insert(token, diff),
+                MultitokenKey::Minted(token) => {
+                    let pre: Amount =
+                        self.ctx.read_pre(key)?.unwrap_or_default();
+                    let post: Amount =
+                        self.ctx.read_post(key)?.unwrap_or_default();
+                    let changes = tokens.entry(token).or_default();
+                    changes.minted_change += post.change() - pre.change();
+                    changes.minter_required = true;
                 }
-
-                // Check if the minter is set
-                if !self.is_valid_minter(token, verifiers)? {
-                    return Ok(false);
+                MultitokenKey::Minter(token) => {
+                    tokens.entry(token).or_default().minter_required = true;
                 }
-            } else if let Some(token) = is_any_minter_key(key) {
-                if !self.is_valid_minter(token, verifiers)? {
+                MultitokenKey::Vesting { token, owner } => {
+                    tokens.entry(token).or_default().vesting.push(owner);
+                }
+                MultitokenKey::Unknown => {
+                    // Reject when trying to update an unexpected key under
+                    // `#Multitoken/...`
+                    tracing::debug!(
+                        "Rejecting an update of an unexpected multitoken key \
+                         {}",
+                        key
+                    );
                     return Ok(false);
                 }
-            } else if key.segments.get(0)
-                == Some(
-                    &Address::Internal(InternalAddress::Multitoken).to_db_key(),
-                )
+            }
+        }
+
+        for (token, changes) in &tokens {
+            // Check if the minter is set
+            if changes.minter_required
+                && !self.is_valid_minter(token, verifiers)?
             {
-                // Reject when trying to update an unexpected key under
-                // `#Multitoken/...`
+                return Ok(false);
+            }
+            for owner in &changes.vesting {
+                let balance = changes.balances.get(owner);
+                if !self.is_valid_vesting_schedule(
+                    token, owner, balance, verifiers,
+                )? {
+                    return Ok(false);
+                }
+            }
+            if !self.is_valid_vested_spending(token, changes, keys_changed)? {
+                return Ok(false);
+            }
+            // The total change of the balances must match the change of the
+            // total supply
+            if changes.balances_change != changes.minted_change {
                 return Ok(false);
             }
         }
 
-        Ok(changes.iter().all(|(token, change)| {
-            let mint = match mints.get(token) {
-                Some(mint) => *mint,
-                None => Change::zero(),
-            };
-            *change == mint
-        }))
+        Ok(true)
     }
 }
 
+/// A key in the multitoken sub-space
+enum MultitokenKey<'k> {
+    /// The balance of an owner of a token
+    Balance {
+        token: &'k Address,
+        owner: &'k Address,
+    },
+    /// The total supply of a token
+    Minted(&'k Address),
+    /// The minter of a token
+    Minter(&'k Address),
+    /// The vesting schedule of an owner of a token
+    Vesting {
+        token: &'k Address,
+        owner: &'k Address,
+    },
+    /// Any other key, which cannot be updated
+    Unknown,
+}
+
+impl<'k> MultitokenKey<'k> {
+    /// Classify a key in a single match on its segments. Returns `None` for
+    /// the keys outside of the multitoken sub-space.
+    fn parse(key: &'k Key) -> Option<Self> {
+        let (DbKeySeg::AddressSeg(addr), rest) = key.segments.split_first()?
+        else {
+            return None;
+        };
+        if *addr != Address::Internal(InternalAddress::Multitoken) {
+            return None;
+        }
+        let parsed = match rest {
+            [
+                DbKeySeg::AddressSeg(token),
+                DbKeySeg::StringSeg(balance),
+                DbKeySeg::AddressSeg(owner),
+            ] if balance == BALANCE_STORAGE_KEY =>
+            {
+                Self::Balance { token, owner }
+            }
+            [
+                DbKeySeg::AddressSeg(token),
+                DbKeySeg::StringSeg(balance),
+                DbKeySeg::StringSeg(minted),
+            ] if balance == BALANCE_STORAGE_KEY
+                && minted == MINTED_STORAGE_KEY =>
+            {
+                Self::Minted(token)
+            }
+            [DbKeySeg::AddressSeg(token), DbKeySeg::StringSeg(minter)]
+                if minter == MINTER_STORAGE_KEY =>
+            {
+                Self::Minter(token)
+            }
+            [
+                DbKeySeg::AddressSeg(token),
+                DbKeySeg::StringSeg(vesting),
+                DbKeySeg::AddressSeg(owner),
+            ] if vesting == VESTING_STORAGE_KEY =>
+            {
+                Self::Vesting { token, owner }
+            }
+            _ => Self::Unknown,
+        };
+        Some(parsed)
+    }
+}
+
+/// The changes of a token in a tx
+#[derive(Default)]
+struct TokenChanges<'k> {
+    /// The sum of the changes of the balances
+    balances_change: Change,
+    /// The change of the total supply
+    minted_change: Change,
+    /// The balances changed in the tx, before and after it
+    balances: BTreeMap<&'k Address, (Amount, Amount)>,
+    /// The owners whose vesting schedule changed
+    vesting: Vec<&'k Address>,
+    /// Whether the total supply or the minter changed
+    minter_required: bool,
+}
+
 impl<'a, DB, H, CA> MultitokenVp<'a, DB, H, CA>
 where
     DB: 'static + namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
     H: 'static + namada_state::StorageHasher,
     CA: 'static + WasmCacheAccess,
 {
+    /// Get the amount of the owner's balance that is locked by a vesting
+    /// schedule in the current epoch
+    fn locked_amount(
+        &self,
+        token: &Address,
+        owner: &Address,
+    ) -> Result<Amount> {
+        let key = vesting_key(token, owner);
+        let locked = match self.ctx.read_post::<VestingSchedule>(&key)? {
+            Some(schedule) => {
+                schedule.locked_amount(self.ctx.get_block_epoch()?)
+            }
+            None => Amount::zero(),
+        };
+        Ok(locked)
+    }
+
+    /// Check that a vesting schedule only locks the tokens received in this
+    /// tx and that it doesn't replace a schedule with tokens still locked.
+    /// The owner's balance before and after the tx is given if it changed.
+    /// A new schedule must be accepted by its owner, whose VP checks the
+    /// signature of the tx.
+    fn is_valid_vesting_schedule(
+        &self,
+        token: &Address,
+        owner: &Address,
+        balance: Option<&(Amount, Amount)>,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let key = vesting_key(token, owner);
+        let epoch = self.ctx.get_block_epoch()?;
+        if let Some(pre) = self.ctx.read_pre::<VestingSchedule>(&key)? {
+            if !pre.locked_amount(epoch).is_zero() {
+                tracing::debug!(
+                    "A vesting schedule of {} tokens of {} with locked tokens \
+                     cannot be changed",
+                    token,
+                    owner
+                );
+                return Ok(false);
+            }
+        }
+        let Some(post) = self.ctx.read_post::<VestingSchedule>(&key)? else {
+            // A fully vested schedule may be removed
+            return Ok(true);
+        };
+        if post.start > post.end {
+            return Ok(false);
+        }
+        if !verifiers.contains(owner) {
+            tracing::debug!(
+                "A vesting schedule of {} tokens of {} must be accepted by \
+                 its owner",
+                token,
+                owner
+            );
+            return Ok(false);
+        }
+        let received = match balance {
+            Some((pre, post)) => post.checked_sub(*pre).unwrap_or_default(),
+            None => Amount::zero(),
+        };
+        Ok(received >= post.amount)
+    }
+
+    /// Check that the tokens locked by a vesting schedule only leave the
+    /// owner's balance to be bonded by the owner
+    fn is_valid_vested_spending(
+        &self,
+        token: &Address,
+        changes: &TokenChanges<'_>,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool> {
+        let pos = Address::Internal(InternalAddress::PoS);
+        for (owner, (pre, post)) in &changes.balances {
+            if post >= pre || **owner == pos {
+                continue;
+            }
+            let locked = self.locked_amount(token, owner)?;
+            let locked_spent = locked.min(*pre).checked_sub(*post);
+            if let Some(locked_spent) =
+                locked_spent.filter(|spent| !spent.is_zero())
+            {
+                let bonded = self.bonded_amount(token, owner, keys_changed)?;
+                if bonded < locked_spent {
+                    tracing::debug!(
+                        "Rejecting a transfer of {} locked {} tokens of {}",
+                        locked_spent.to_string_native(),
+                        token,
+                        owner
+                    );
+                    return Ok(false);
+                }
+            }
+        }
+        Ok(true)
+    }
+
+    /// Get the amount of the token that the owner bonded in this tx, from
+    /// the increase of the owner's own bonds
+    fn bonded_amount(
+        &self,
+        token: &Address,
+        owner: &Address,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<Amount> {
+        // Only the native token can be bonded
+        if *token != self.ctx.get_native_token()? {
+            return Ok(Amount::zero());
+        }
+        let mut bonded = Amount::zero();
+        for key in keys_changed {
+            let Some((bond_id, _start)) = is_bond_key(key) else {
+                continue;
+            };
+            if bond_id.source != *owner {
+                continue;
+            }
+            let pre: Amount = self.ctx.read_pre(key)?.unwrap_or_default();
+            let post: Amount = self.ctx.read_post(key)?.unwrap_or_default();
+            if let Some(increase) = post.checked_sub(pre) {
+                bonded += increase;
+            }
+        }
+        Ok(bonded)
+    }
+
     /// Return the minter if the minter is valid and the minter VP exists
     pub fn is_valid_minter(
         &self,
@@ -126,9 +362,16 @@ where
                     _ => Ok(false),
                 }
             }
+            Address::Internal(
+                InternalAddress::Erc20(_) | InternalAddress::Nut(_),
+            ) => {
+                // NUTs can only be converted into wrapped ERC20s, which is
+                // validated by the Ethereum bridge VP
+                Ok(verifiers
+                    .contains(&Address::Internal(InternalAddress::EthBridge)))
+            }
             _ => {
-                // ERC20 and other tokens should not be minted by a wasm
-                // transaction
+                // Other tokens should not be minted by a wasm transaction
                 Ok(false)
             }
         }
@@ -148,17 +391,22 @@ mod tests {
     use super::*;
     use crate::core::types::address::nam;
     use crate::core::types::address::testing::{
-        established_address_1, established_address_2,
+        established_address_1, established_address_2, established_address_3,
     };
     use crate::ledger::gas::VpGasMeter;
     use crate::ledger::ibc::storage::ibc_token;
+    use crate::ledger::pos::bond_handle;
     use crate::token::storage_key::{
-        balance_key, minted_balance_key, minter_key,
+        balance_key, minted_balance_key, minter_key, vesting_key,
     };
-    use crate::token::Amount;
+    use crate::token::{Amount, VestingSchedule};
     use crate::types::address::{Address, InternalAddress};
+    use crate::types::eth_bridge_pool::{
+        erc20_nut_address, erc20_token_address,
+    };
+    use crate::types::ethereum_events::EthAddress;
     use crate::types::key::testing::keypair_1;
-    use crate::types::storage::TxIndex;
+    use crate::types::storage::{Epoch, KeySeg, TxIndex};
     use crate::vm::wasm::compilation_cache::common::testing::cache as wasm_cache;
 
     const ADDRESS: Address = Address::Internal(InternalAddress::Multitoken);
@@ -569,6 +817,79 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_nut_conversion_minter() {
+        let mut wl_storage = TestWlStorage::default();
+        let mut keys_changed = BTreeSet::new();
+
+        let asset = EthAddress([1; 20]);
+        let nut = erc20_nut_address(&asset);
+        let erc20 = erc20_token_address(&asset);
+        let owner = established_address_1();
+        let amount = Amount::native_whole(100);
+
+        // the owner holds 100 NUTs
+        let nut_key = balance_key(&nut, &owner);
+        let nut_minted_key = minted_balance_key(&nut);
+        for key in [&nut_key, &nut_minted_key] {
+            wl_storage
+                .storage
+                .write(key, amount.serialize_to_vec())
+                .expect("write failed");
+        }
+
+        // convert them into 100 wrapped ERC20s
+        let erc20_key = balance_key(&erc20, &owner);
+        let erc20_minted_key = minted_balance_key(&erc20);
+        for (key, amount) in [
+            (nut_key, Amount::zero()),
+            (nut_minted_key, Amount::zero()),
+            (erc20_key, amount),
+            (erc20_minted_key, amount),
+        ] {
+            wl_storage
+                .write_log
+                .write(&key, amount.serialize_to_vec())
+                .expect("write failed");
+            keys_changed.insert(key);
+        }
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        // the conversion is only accepted when the Ethereum bridge VP is
+        // triggered to validate it
+        for (verifiers, expected) in [
+            (BTreeSet::new(), false),
+            (
+                BTreeSet::from([Address::Internal(InternalAddress::EthBridge)]),
+                true,
+            ),
+        ] {
+            let gas_meter = VpGasMeter::new_from_tx_meter(
+                &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+            );
+            let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+            let ctx = Ctx::new(
+                &ADDRESS,
+                &wl_storage.storage,
+                &wl_storage.write_log,
+                &tx,
+                &tx_index,
+                gas_meter,
+                &keys_changed,
+                &verifiers,
+                vp_wasm_cache,
+            );
+
+            let vp = MultitokenVp { ctx };
+            assert_eq!(
+                vp.validate_tx(&tx, &keys_changed, &verifiers)
+                    .expect("validation failed"),
+                expected
+            );
+        }
+    }
+
     #[test]
     fn test_invalid_key_update() {
         let mut wl_storage = TestWlStorage::default();
@@ -611,4 +932,304 @@ mod tests {
                 .expect("validation failed")
         );
     }
+
+    /// Write a vesting schedule locking the whole balance of 100 tokens of
+    /// the owner until epoch 10
+    fn init_vesting_account(wl_storage: &mut TestWlStorage, owner: &Address) {
+        let amount = Amount::native_whole(100);
+        wl_storage
+            .storage
+            .write(&balance_key(&nam(), owner), amount.serialize_to_vec())
+            .expect("write failed");
+        let schedule = VestingSchedule {
+            amount,
+            start: Epoch(10),
+            end: Epoch(20),
+        };
+        wl_storage
+            .storage
+            .write(&vesting_key(&nam(), owner), schedule.serialize_to_vec())
+            .expect("write failed");
+    }
+
+    /// Move 10 tokens from the owner to the target and validate the change.
+    /// When a bond source is given, its bond is increased by 10 tokens.
+    fn validate_vesting_transfer(
+        mut wl_storage: TestWlStorage,
+        owner: Address,
+        target: Address,
+        bond_source: Option<Address>,
+    ) -> bool {
+        let mut keys_changed = BTreeSet::new();
+        let owner_key = balance_key(&nam(), &owner);
+        let amount = Amount::native_whole(90);
+        wl_storage
+            .write_log
+            .write(&owner_key, amount.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(owner_key);
+        let target_key = balance_key(&nam(), &target);
+        let amount = Amount::native_whole(10);
+        wl_storage
+            .write_log
+            .write(&target_key, amount.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(target_key);
+        if let Some(source) = bond_source {
+            let bond_key = bond_handle(&source, &established_address_3())
+                .get_data_handler()
+                .get_data_key(&Epoch(0));
+            wl_storage
+                .write_log
+                .write(&bond_key, amount.serialize_to_vec())
+                .expect("write failed");
+            keys_changed.insert(bond_key);
+        }
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let mut verifiers = BTreeSet::new();
+        verifiers.insert(owner);
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+
+        let vp = MultitokenVp { ctx };
+        vp.validate_tx(&tx, &keys_changed, &verifiers)
+            .expect("validation failed")
+    }
+
+    #[test]
+    fn test_locked_transfer() {
+        let mut wl_storage = TestWlStorage::default();
+        let owner = established_address_1();
+        init_vesting_account(&mut wl_storage, &owner);
+
+        // The locked tokens cannot be transferred
+        assert!(!validate_vesting_transfer(
+            wl_storage,
+            owner,
+            established_address_2(),
+            None,
+        ));
+    }
+
+    #[test]
+    fn test_locked_bond() {
+        let mut wl_storage = TestWlStorage::default();
+        let owner = established_address_1();
+        init_vesting_account(&mut wl_storage, &owner);
+
+        // The locked tokens can be bonded by the owner
+        assert!(validate_vesting_transfer(
+            wl_storage,
+            owner.clone(),
+            Address::Internal(InternalAddress::PoS),
+            Some(owner),
+        ));
+    }
+
+    #[test]
+    fn test_locked_bond_by_other() {
+        let mut wl_storage = TestWlStorage::default();
+        let owner = established_address_1();
+        init_vesting_account(&mut wl_storage, &owner);
+
+        // The locked tokens cannot be spent while another account bonds
+        // the same amount
+        assert!(!validate_vesting_transfer(
+            wl_storage,
+            owner,
+            Address::Internal(InternalAddress::PoS),
+            Some(established_address_2()),
+        ));
+    }
+
+    #[test]
+    fn test_vested_transfer() {
+        let mut wl_storage = TestWlStorage::default();
+        let owner = established_address_1();
+        init_vesting_account(&mut wl_storage, &owner);
+        wl_storage.storage.block.epoch = Epoch(20);
+
+        // The vested tokens can be transferred
+        assert!(validate_vesting_transfer(
+            wl_storage,
+            owner,
+            established_address_2(),
+            None,
+        ));
+    }
+
+    #[test]
+    fn test_invalid_vesting_schedule() {
+        let mut wl_storage = TestWlStorage::default();
+        let mut keys_changed = BTreeSet::new();
+
+        // Lock tokens that the owner already had
+        let owner = established_address_1();
+        let amount = Amount::native_whole(100);
+        wl_storage
+            .storage
+            .write(&balance_key(&nam(), &owner), amount.serialize_to_vec())
+            .expect("write failed");
+        let key = vesting_key(&nam(), &owner);
+        let schedule = VestingSchedule {
+            amount,
+            start: Epoch(10),
+            end: Epoch(20),
+        };
+        wl_storage
+            .write_log
+            .write(&key, schedule.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(key);
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let verifiers = BTreeSet::new();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+
+        let vp = MultitokenVp { ctx };
+        assert!(
+            !vp.validate_tx(&tx, &keys_changed, &verifiers)
+                .expect("validation failed")
+        );
+    }
+
+    /// Transfer 100 tokens to the owner with a vesting schedule locking them
+    /// and validate the change
+    fn validate_vesting_schedule(owner_is_verifier: bool) -> bool {
+        let mut wl_storage = TestWlStorage::default();
+        let mut keys_changed = BTreeSet::new();
+
+        let source = established_address_1();
+        let owner = established_address_2();
+        let amount = Amount::native_whole(100);
+        wl_storage
+            .storage
+            .write(&balance_key(&nam(), &source), amount.serialize_to_vec())
+            .expect("write failed");
+        let source_key = balance_key(&nam(), &source);
+        wl_storage
+            .write_log
+            .write(&source_key, Amount::zero().serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(source_key);
+        let owner_key = balance_key(&nam(), &owner);
+        wl_storage
+            .write_log
+            .write(&owner_key, amount.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(owner_key);
+        let key = vesting_key(&nam(), &owner);
+        let schedule = VestingSchedule {
+            amount,
+            start: Epoch(10),
+            end: Epoch(20),
+        };
+        wl_storage
+            .write_log
+            .write(&key, schedule.serialize_to_vec())
+            .expect("write failed");
+        keys_changed.insert(key);
+
+        let tx_index = TxIndex::default();
+        let tx = dummy_tx(&wl_storage);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let mut verifiers = BTreeSet::new();
+        verifiers.insert(source);
+        if owner_is_verifier {
+            verifiers.insert(owner);
+        }
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            &keys_changed,
+            &verifiers,
+            vp_wasm_cache,
+        );
+
+        let vp = MultitokenVp { ctx };
+        vp.validate_tx(&tx, &keys_changed, &verifiers)
+            .expect("validation failed")
+    }
+
+    #[test]
+    fn test_vesting_schedule_accepted_by_owner() {
+        assert!(validate_vesting_schedule(true));
+    }
+
+    #[test]
+    fn test_vesting_schedule_not_accepted_by_owner() {
+        // Nobody else can lock the tokens of the owner
+        assert!(!validate_vesting_schedule(false));
+    }
+
+    #[test]
+    fn test_multitoken_key_parse() {
+        let token = nam();
+        let owner = established_address_1();
+        assert!(matches!(
+            MultitokenKey::parse(&balance_key(&token, &owner)),
+            Some(MultitokenKey::Balance { .. })
+        ));
+        assert!(matches!(
+            MultitokenKey::parse(&minted_balance_key(&token)),
+            Some(MultitokenKey::Minted(_))
+        ));
+        assert!(matches!(
+            MultitokenKey::parse(&minter_key(&token)),
+            Some(MultitokenKey::Minter(_))
+        ));
+        assert!(matches!(
+            MultitokenKey::parse(&vesting_key(&token, &owner)),
+            Some(MultitokenKey::Vesting { .. })
+        ));
+        let unknown = Key::from(ADDRESS.to_db_key())
+            .push(&"unknown".to_owned())
+            .expect("Cannot obtain a storage key");
+        assert!(matches!(
+            MultitokenKey::parse(&unknown),
+            Some(MultitokenKey::Unknown)
+        ));
+        // The keys outside of the multitoken sub-space are skipped
+        let other = Key::from(owner.to_db_key())
+            .push(&"balance".to_owned())
+            .expect("Cannot obtain a storage key");
+        assert!(MultitokenKey::parse(&other).is_none());
+    }
 }
@@ -3,8 +3,10 @@
 use std::collections::BTreeSet;
 
 use namada_core::types::address::Address;
-use namada_core::types::storage::Key;
+use namada_core::types::storage::{Epoch, Key};
+use namada_parameters::ScheduledChange;
 use namada_tx::Tx;
+use namada_vp_env::VpEnv;
 use thiserror::Error;
 
 use crate::ledger::native_vp::{self, Ctx, NativeVp};
@@ -60,6 +62,16 @@ where
                     )
                     .unwrap_or(false)
                 }
+                KeyType::PENDING_CHANGES(activation_epoch) => {
+                    namada_governance::storage::is_proposal_accepted(
+                        &self.ctx.pre(),
+                        &data,
+                    )
+                    .unwrap_or(false)
+                        && self
+                            .is_valid_schedule(key, activation_epoch)
+                            .unwrap_or(false)
+                }
                 KeyType::UNKNOWN_PARAMETER => false,
                 KeyType::UNKNOWN => true,
             }
@@ -68,6 +80,43 @@ where
     }
 }
 
+impl<'a, DB, H, CA> ParametersVp<'a, DB, H, CA>
+where
+    DB: 'static + namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: 'static + namada_state::StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    /// Check that scheduled parameter changes activate in a future epoch and
+    /// only target protocol parameters or PoS parameters.
+    fn is_valid_schedule(
+        &self,
+        key: &Key,
+        activation_epoch: Epoch,
+    ) -> Result<bool> {
+        // Removing the changes is only allowed once they have been applied
+        let Some(changes) = self.ctx.read_post::<Vec<ScheduledChange>>(key)?
+        else {
+            return Ok(false);
+        };
+        let current_epoch = self.ctx.get_block_epoch()?;
+        if activation_epoch <= current_epoch {
+            tracing::info!(
+                "Parameter changes must be scheduled for an epoch after the \
+                 current epoch {current_epoch}, got {activation_epoch}"
+            );
+            return Ok(false);
+        }
+        Ok(changes.iter().all(|change| is_schedulable_key(&change.key)))
+    }
+}
+
+/// Returns if the key is a parameter that can be changed with a scheduled
+/// activation.
+fn is_schedulable_key(key: &Key) -> bool {
+    namada_parameters::storage::is_protocol_parameter_key(key)
+        || namada_proof_of_stake::storage_key::is_params_key(key)
+}
+
 impl From<native_vp::Error> for Error {
     fn from(err: native_vp::Error) -> Self {
         Self::NativeVpError(err)
@@ -82,6 +131,9 @@ enum KeyType {
     #[allow(non_camel_case_types)]
     UNKNOWN_PARAMETER,
     #[allow(clippy::upper_case_acronyms)]
+    #[allow(non_camel_case_types)]
+    PENDING_CHANGES(Epoch),
+    #[allow(clippy::upper_case_acronyms)]
     UNKNOWN,
 }
 
@@ -89,6 +141,10 @@ impl From<&Key> for KeyType {
     fn from(value: &Key) -> Self {
         if namada_parameters::storage::is_protocol_parameter_key(value) {
             KeyType::PARAMETER
+        } else if let Some(epoch) =
+            namada_parameters::storage::is_pending_changes_key(value)
+        {
+            KeyType::PENDING_CHANGES(epoch)
         } else if namada_parameters::storage::is_parameter_key(value) {
             KeyType::UNKNOWN_PARAMETER
         } else {
@@ -0,0 +1,386 @@
+//! Native VP for the txs scheduled for execution at a future epoch
+
+use std::collections::BTreeSet;
+
+use namada_tx::data::scheduled::{self, ScheduledTx, ADDRESS};
+use namada_tx::data::TxType;
+use namada_tx::Tx;
+use namada_vp_env::VpEnv;
+use thiserror::Error;
+
+use crate::ledger::native_vp::{self, Ctx, NativeVp};
+use crate::token;
+use crate::types::address::Address;
+use crate::types::hash::Hash;
+use crate::types::storage::{DbKeySeg, Epoch, Key};
+use crate::vm::WasmCacheAccess;
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Native VP error: {0}")]
+    NativeVpError(#[from] native_vp::Error),
+}
+
+/// Scheduler functions result
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Scheduler VP
+pub struct SchedulerVp<'a, DB, H, CA>
+where
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: namada_state::StorageHasher,
+    CA: WasmCacheAccess,
+{
+    /// Context to interact with the host structures.
+    pub ctx: Ctx<'a, DB, H, CA>,
+}
+
+impl<'a, DB, H, CA> NativeVp for SchedulerVp<'a, DB, H, CA>
+where
+    DB: 'static + namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: 'static + namada_state::StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    type Error = Error;
+
+    fn validate_tx(
+        &self,
+        _tx_data: &Tx,
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let native_token = self.ctx.get_native_token()?;
+        let fees_key = token::storage_key::balance_key(&native_token, &ADDRESS);
+
+        // The sum of the fees prepaid by the newly scheduled txs
+        let mut prepaid = token::Amount::zero();
+        // The epochs at which new txs are scheduled
+        let mut epochs = BTreeSet::new();
+        for key in keys_changed {
+            if let Some((epoch, hash)) = scheduled::is_scheduled_tx_key(key) {
+                let Some(fee) =
+                    self.validate_scheduled_tx(key, epoch, hash, verifiers)?
+                else {
+                    return Ok(false);
+                };
+                let Some(sum) = prepaid.checked_add(fee) else {
+                    return Ok(false);
+                };
+                prepaid = sum;
+                epochs.insert(epoch);
+            } else if key == &fees_key {
+                // Checked against the prepaid fees below
+            } else if key.first() == Some(&DbKeySeg::AddressSeg(ADDRESS))
+                || token::storage_key::is_any_token_balance_key(key)
+                    .map(|[_, owner]| owner == &ADDRESS)
+                    .unwrap_or_default()
+            {
+                // Only the scheduled txs and their fees in the native token
+                // can be written
+                tracing::info!("Unexpected write to the scheduler key {key}");
+                return Ok(false);
+            }
+        }
+
+        for epoch in epochs {
+            if !self.is_within_epoch_gas_limit(epoch)? {
+                return Ok(false);
+            }
+        }
+
+        // The fees are only spent by the protocol, so the balance can only
+        // increase by the fees prepaid in this tx
+        let pre: token::Amount =
+            self.ctx.read_pre(&fees_key)?.unwrap_or_default();
+        let post: token::Amount =
+            self.ctx.read_post(&fees_key)?.unwrap_or_default();
+        Ok(post.checked_sub(pre) == Some(prepaid))
+    }
+}
+
+impl<'a, DB, H, CA> SchedulerVp<'a, DB, H, CA>
+where
+    DB: 'static + namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: 'static + namada_state::StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    /// Check a newly scheduled tx and return its prepaid fee if it's valid.
+    /// A scheduled tx can't be modified or removed by a tx, it's only removed
+    /// by the protocol once it's executed.
+    fn validate_scheduled_tx(
+        &self,
+        key: &Key,
+        epoch: Epoch,
+        hash: Hash,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<Option<token::Amount>> {
+        if self.ctx.has_key_pre(key)? {
+            tracing::info!("The scheduled tx {hash} cannot be modified");
+            return Ok(None);
+        }
+        let Some(scheduled) = self.ctx.read_post::<ScheduledTx>(key)? else {
+            return Ok(None);
+        };
+        if scheduled.epoch != epoch || scheduled.hash() != hash {
+            tracing::info!("The scheduled tx {hash} doesn't match its key");
+            return Ok(None);
+        }
+        let current_epoch = self.ctx.get_block_epoch()?;
+        if epoch <= current_epoch {
+            tracing::info!(
+                "Txs must be scheduled for an epoch after the current epoch \
+                 {current_epoch}, got {epoch}"
+            );
+            return Ok(None);
+        }
+        let header = scheduled.tx.header();
+        if !matches!(header.tx_type, TxType::Raw)
+            || header.chain_id.as_str() != self.ctx.get_chain_id()?
+        {
+            tracing::info!("The scheduled tx {hash} is not a valid raw tx");
+            return Ok(None);
+        }
+        if !verifiers.contains(&scheduled.payer) {
+            tracing::info!(
+                "The payer {} of the scheduled tx {hash} must authorize it",
+                scheduled.payer
+            );
+            return Ok(None);
+        }
+        // The fee must cover the gas limit at the minimum gas price
+        let native_token = self.ctx.get_native_token()?;
+        let Some(gas_price) =
+            namada_parameters::read_gas_cost(&self.ctx.pre(), &native_token)?
+        else {
+            return Ok(None);
+        };
+        let min_fee = gas_price.checked_mul(scheduled.gas_limit.into());
+        if min_fee.map_or(true, |min_fee| scheduled.fee < min_fee) {
+            tracing::info!(
+                "The fee {} of the scheduled tx {hash} doesn't cover its gas \
+                 limit",
+                scheduled.fee.to_string_native()
+            );
+            return Ok(None);
+        }
+        Ok(Some(scheduled.fee))
+    }
+
+    /// Check that the total gas limit of the txs scheduled for the epoch
+    /// doesn't exceed the `max_scheduled_txs_gas` parameter.
+    fn is_within_epoch_gas_limit(&self, epoch: Epoch) -> Result<bool> {
+        let max_gas =
+            namada_parameters::get_max_scheduled_txs_gas(&self.ctx.pre())?;
+        let prefix = scheduled::scheduled_txs_epoch_prefix(epoch);
+        let mut total_gas = 0_u64;
+        for entry in
+            namada_state::iter_prefix::<ScheduledTx>(&self.ctx.post(), &prefix)?
+        {
+            let (_, scheduled) = entry?;
+            total_gas =
+                match total_gas.checked_add(u64::from(scheduled.gas_limit)) {
+                    Some(total_gas) if total_gas <= max_gas => total_gas,
+                    _ => {
+                        tracing::info!(
+                            "The txs scheduled for epoch {epoch} exceed the \
+                             max gas of {max_gas}"
+                        );
+                        return Ok(false);
+                    }
+                };
+        }
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use borsh_ext::BorshSerializeExt;
+    use namada_gas::TxGasMeter;
+    use namada_parameters::storage::{
+        get_gas_cost_key, get_max_scheduled_txs_gas_key,
+    };
+    use namada_state::testing::TestWlStorage;
+    use namada_tx::{Code, Data};
+
+    use super::*;
+    use crate::core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use crate::ledger::gas::VpGasMeter;
+    use crate::types::storage::TxIndex;
+    use crate::vm::wasm::compilation_cache::common::testing::cache as wasm_cache;
+
+    const GAS_LIMIT: u64 = 10_000;
+
+    fn init_storage(max_scheduled_txs_gas: u64) -> TestWlStorage {
+        let mut wl_storage = TestWlStorage::default();
+        let native_token = wl_storage.storage.native_token.clone();
+        let gas_cost_table =
+            BTreeMap::from([(native_token, token::Amount::from_u64(1))]);
+        wl_storage
+            .storage
+            .write(&get_gas_cost_key(), gas_cost_table.serialize_to_vec())
+            .expect("write failed");
+        wl_storage
+            .storage
+            .write(
+                &get_max_scheduled_txs_gas_key(),
+                max_scheduled_txs_gas.serialize_to_vec(),
+            )
+            .expect("write failed");
+        wl_storage
+    }
+
+    fn raw_tx(wl_storage: &TestWlStorage, nonce: u8) -> Tx {
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.header.chain_id = wl_storage.storage.chain_id.clone();
+        tx.set_code(Code::new(vec![], None));
+        tx.set_data(Data::new(vec![nonce]));
+        tx
+    }
+
+    fn scheduled_tx(
+        wl_storage: &TestWlStorage,
+        payer: &Address,
+        epoch: Epoch,
+        nonce: u8,
+    ) -> (Key, ScheduledTx) {
+        let scheduled = ScheduledTx {
+            epoch,
+            payer: payer.clone(),
+            gas_limit: GAS_LIMIT.into(),
+            fee: token::Amount::from_u64(GAS_LIMIT),
+            tx: raw_tx(wl_storage, nonce),
+        };
+        (
+            scheduled::scheduled_tx_key(epoch, &scheduled.hash()),
+            scheduled,
+        )
+    }
+
+    /// Write the scheduled txs and their prepaid fees in the write log and
+    /// return the changed keys
+    fn schedule(
+        wl_storage: &mut TestWlStorage,
+        txs: impl IntoIterator<Item = (Key, ScheduledTx)>,
+    ) -> BTreeSet<Key> {
+        let native_token = wl_storage.storage.native_token.clone();
+        let fees_key = token::storage_key::balance_key(&native_token, &ADDRESS);
+        let mut fees = token::Amount::zero();
+        let mut keys_changed = BTreeSet::from([fees_key.clone()]);
+        for (key, scheduled) in txs {
+            fees = fees.checked_add(scheduled.fee).unwrap();
+            wl_storage
+                .write_log
+                .write(&key, scheduled.serialize_to_vec())
+                .expect("write failed");
+            keys_changed.insert(key);
+        }
+        wl_storage
+            .write_log
+            .write(&fees_key, fees.serialize_to_vec())
+            .expect("write failed");
+        keys_changed
+    }
+
+    fn validate(
+        wl_storage: &TestWlStorage,
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> bool {
+        let tx_index = TxIndex::default();
+        let tx = raw_tx(wl_storage, 0);
+        let gas_meter = VpGasMeter::new_from_tx_meter(
+            &TxGasMeter::new_from_sub_limit(u64::MAX.into()),
+        );
+        let (vp_wasm_cache, _vp_cache_dir) = wasm_cache();
+        let ctx = Ctx::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &tx_index,
+            gas_meter,
+            keys_changed,
+            verifiers,
+            vp_wasm_cache,
+        );
+        let vp = SchedulerVp { ctx };
+        vp.validate_tx(&tx, keys_changed, verifiers)
+            .expect("validation failed")
+    }
+
+    #[test]
+    fn test_schedule_tx() {
+        let mut wl_storage = init_storage(GAS_LIMIT);
+        let payer = established_address_1();
+        let tx = scheduled_tx(&wl_storage, &payer, Epoch(1), 0);
+        let keys_changed = schedule(&mut wl_storage, [tx]);
+
+        let verifiers = BTreeSet::from([payer]);
+        assert!(validate(&wl_storage, &keys_changed, &verifiers));
+    }
+
+    #[test]
+    fn test_schedule_tx_unauthorized() {
+        let mut wl_storage = init_storage(GAS_LIMIT);
+        let payer = established_address_1();
+        let tx = scheduled_tx(&wl_storage, &payer, Epoch(1), 0);
+        let keys_changed = schedule(&mut wl_storage, [tx]);
+
+        // The payer must authorize the scheduled tx
+        let verifiers = BTreeSet::from([established_address_2()]);
+        assert!(!validate(&wl_storage, &keys_changed, &verifiers));
+    }
+
+    #[test]
+    fn test_schedule_tx_at_current_epoch() {
+        let mut wl_storage = init_storage(GAS_LIMIT);
+        let payer = established_address_1();
+        let tx = scheduled_tx(&wl_storage, &payer, Epoch(0), 0);
+        let keys_changed = schedule(&mut wl_storage, [tx]);
+
+        let verifiers = BTreeSet::from([payer]);
+        assert!(!validate(&wl_storage, &keys_changed, &verifiers));
+    }
+
+    #[test]
+    fn test_schedule_tx_epoch_gas_limit() {
+        let mut wl_storage = init_storage(2 * GAS_LIMIT);
+        let payer = established_address_1();
+        let verifiers = BTreeSet::from([payer.clone()]);
+        // A tx already scheduled for epoch 1
+        let (key, scheduled) = scheduled_tx(&wl_storage, &payer, Epoch(1), 0);
+        wl_storage
+            .storage
+            .write(&key, scheduled.serialize_to_vec())
+            .expect("write failed");
+
+        // One more tx fits in epoch 1
+        let tx = scheduled_tx(&wl_storage, &payer, Epoch(1), 1);
+        let keys_changed = schedule(&mut wl_storage, [tx]);
+        assert!(validate(&wl_storage, &keys_changed, &verifiers));
+        wl_storage.write_log.drop_tx();
+
+        // Two more txs exceed the limit of epoch 1
+        let txs = [
+            scheduled_tx(&wl_storage, &payer, Epoch(1), 1),
+            scheduled_tx(&wl_storage, &payer, Epoch(1), 2),
+        ];
+        let keys_changed = schedule(&mut wl_storage, txs);
+        assert!(!validate(&wl_storage, &keys_changed, &verifiers));
+        wl_storage.write_log.drop_tx();
+
+        // But fit in epoch 2
+        let txs = [
+            scheduled_tx(&wl_storage, &payer, Epoch(2), 1),
+            scheduled_tx(&wl_storage, &payer, Epoch(2), 2),
+        ];
+        let keys_changed = schedule(&mut wl_storage, txs);
+        assert!(validate(&wl_storage, &keys_changed, &verifiers));
+    }
+}
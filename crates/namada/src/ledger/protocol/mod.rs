@@ -1,5 +1,6 @@
 //! The ledger's protocol
 use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use borsh_ext::BorshSerializeExt;
 use eyre::{eyre, WrapErr};
@@ -10,17 +11,18 @@ use namada_gas::TxGasMeter;
 use namada_sdk::tx::TX_TRANSFER_WASM;
 use namada_state::wl_storage::WriteLogAndStorage;
 use namada_state::StorageRead;
-use namada_tx::data::protocol::ProtocolTxType;
+use namada_tx::data::protocol::ProtocolTx;
 use namada_tx::data::{
     DecryptedTx, GasLimit, TxResult, TxType, VpsResult, WrapperTx,
 };
-use namada_tx::{Section, Tx};
+use namada_tx::{Section, Signer, Tx};
 use namada_vote_ext::EthereumTxData;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use thiserror::Error;
 
 use crate::ledger::gas::{GasMetering, VpGasMeter};
 use crate::ledger::governance::GovernanceVp;
+use crate::ledger::key_space;
 use crate::ledger::native_vp::ethereum_bridge::bridge_pool_vp::BridgePoolVp;
 use crate::ledger::native_vp::ethereum_bridge::nut::NonUsableTokens;
 use crate::ledger::native_vp::ethereum_bridge::vp::EthBridge;
@@ -28,13 +30,14 @@ use crate::ledger::native_vp::ibc::Ibc;
 use crate::ledger::native_vp::masp::MaspVp;
 use crate::ledger::native_vp::multitoken::MultitokenVp;
 use crate::ledger::native_vp::parameters::{self, ParametersVp};
+use crate::ledger::native_vp::scheduler::SchedulerVp;
 use crate::ledger::native_vp::{self, NativeVp};
 use crate::ledger::pgf::PgfVp;
 use crate::ledger::pos::{self, PosVP};
 use crate::state::write_log::WriteLog;
 use crate::state::{DBIter, State, StorageHasher, WlStorage, DB};
-use crate::token::Amount;
-use crate::types::address::{Address, InternalAddress};
+use crate::token::{Amount, TokenFlows};
+use crate::types::address::{Address, ImplicitAddress, InternalAddress};
 use crate::types::storage;
 use crate::types::storage::TxIndex;
 use crate::vm::wasm::{TxCache, VpCache};
@@ -91,6 +94,8 @@ pub enum Error {
     NutNativeVpError(native_vp::ethereum_bridge::nut::Error),
     #[error("MASP native VP error: {0}")]
     MaspNativeVpError(native_vp::masp::Error),
+    #[error("Scheduler native VP error: {0}")]
+    SchedulerNativeVpError(native_vp::scheduler::Error),
     #[error("Access to an internal address {0:?} is forbidden")]
     AccessForbidden(InternalAddress),
 }
@@ -176,7 +181,7 @@ where
             },
         ),
         TxType::Protocol(protocol_tx) => {
-            apply_protocol_tx(protocol_tx.tx, tx.data(), wl_storage)
+            apply_protocol_tx(protocol_tx, tx.data(), wl_storage)
         }
         TxType::Wrapper(ref wrapper) => {
             let fee_unshielding_transaction =
@@ -437,7 +442,8 @@ where
                     block_proposer,
                     fees,
                 )
-                .map_err(|e| Error::FeeError(e.to_string()))
+                .map_err(|e| Error::FeeError(e.to_string()))?;
+                record_fee_flows(wl_storage, &wrapper.fee.token, fees)
             } else {
                 // Balance was insufficient for fee payment, move all the
                 // available funds in the transparent balance of
@@ -457,6 +463,7 @@ where
                     balance,
                 )
                 .map_err(|e| Error::FeeError(e.to_string()))?;
+                record_fee_flows(wl_storage, &wrapper.fee.token, balance)?;
 
                 Err(Error::FeeError(
                     "Transparent balance of wrapper's signer was insufficient \
@@ -479,6 +486,40 @@ where
     }
 }
 
+/// Account the fees paid by the fee payer and credited to the block proposer
+/// in the flows of the fee token in the current epoch. Like
+/// [`token_transfer`], this function updates the tx write log.
+fn record_fee_flows<WLS>(
+    wl_storage: &mut WLS,
+    token: &Address,
+    fees: Amount,
+) -> Result<()>
+where
+    WLS: WriteLogAndStorage + StorageRead,
+{
+    let epoch = wl_storage
+        .get_block_epoch()
+        .map_err(|e| Error::FeeError(e.to_string()))?;
+    let flows = crate::token::read_epoch_flows(wl_storage, token, epoch)
+        .map_err(|e| Error::FeeError(e.to_string()))?
+        .checked_add(&TokenFlows {
+            fees_collected: fees,
+            fees_distributed: fees,
+            ..Default::default()
+        })
+        .ok_or_else(|| {
+            Error::FeeError("The fee flows would overflow".to_string())
+        })?;
+    wl_storage
+        .write_log_mut()
+        .write(
+            &crate::token::storage_key::epoch_flows_key(token, epoch),
+            flows.serialize_to_vec(),
+        )
+        .map_err(|e| Error::FeeError(e.to_string()))?;
+    Ok(())
+}
+
 /// Transfer `token` from `src` to `dest`. Returns an `Err` if `src` has
 /// insufficient balance or if the transfer the `dest` would overflow (This can
 /// only happen if the total supply doesn't fit in `token::Amount`). Contrary to
@@ -596,24 +637,39 @@ where
         return Err(Error::ReplayAttempt(tx_hash));
     }
 
-    let verifiers = execute_tx(
-        &tx,
-        tx_index,
-        storage,
-        tx_gas_meter,
-        write_log,
-        vp_wasm_cache,
-        tx_wasm_cache,
-    )?;
+    // Set once the VPs are done, so that the prefetch doesn't hold up the tx
+    let prefetch_done = AtomicBool::new(false);
+    let vps_result = rayon::in_place_scope(|scope| {
+        // Warm up the read cache for the VPs on the shared thread pool while
+        // the tx is executing
+        if storage.read_cache.is_enabled() {
+            let prefixes = vp_read_prefixes(&tx, &storage.native_token);
+            let prefetch_done = &prefetch_done;
+            scope.spawn(move |_| storage.prefetch(&prefixes, prefetch_done));
+        }
 
-    let vps_result = check_vps(CheckVps {
-        tx: &tx,
-        tx_index,
-        storage,
-        tx_gas_meter,
-        write_log,
-        verifiers_from_tx: &verifiers,
-        vp_wasm_cache,
+        let result = execute_tx(
+            &tx,
+            tx_index,
+            storage,
+            tx_gas_meter,
+            write_log,
+            vp_wasm_cache,
+            tx_wasm_cache,
+        )
+        .and_then(|verifiers| {
+            check_vps(CheckVps {
+                tx: &tx,
+                tx_index,
+                storage,
+                tx_gas_meter,
+                write_log,
+                verifiers_from_tx: &verifiers,
+                vp_wasm_cache,
+            })
+        });
+        prefetch_done.store(true, Ordering::Relaxed);
+        result
     })?;
 
     let gas_used = tx_gas_meter.get_tx_consumed_gas();
@@ -631,6 +687,38 @@ where
     })
 }
 
+/// The prefixes of the storage keys that the VPs of the given tx are likely to
+/// read: the subspaces and the native token balances of the accounts that
+/// signed the tx and the protocol parameters.
+fn vp_read_prefixes(tx: &Tx, native_token: &Address) -> Vec<storage::Key> {
+    let mut signers = BTreeSet::new();
+    for section in &tx.sections {
+        if let Section::Signature(signature) = section {
+            match &signature.signer {
+                Signer::Address(address) => {
+                    signers.insert(address.clone());
+                }
+                Signer::PubKeys(public_keys) => {
+                    signers.extend(public_keys.iter().map(|public_key| {
+                        Address::Implicit(ImplicitAddress(public_key.into()))
+                    }));
+                }
+            }
+        }
+    }
+    // The subspaces of internal addresses can be large
+    signers.retain(|address| !matches!(address, Address::Internal(_)));
+    let balances = signers.iter().map(|address| {
+        crate::token::storage_key::balance_key(native_token, address)
+    });
+    signers
+        .iter()
+        .chain(std::iter::once(&namada_parameters::ADDRESS))
+        .map(|address| storage::Key::from(address.to_db_key()))
+        .chain(balances)
+        .collect()
+}
+
 /// Apply a derived transaction to storage based on some protocol transaction.
 /// The logic here must be completely deterministic and will be executed by all
 /// full nodes every time a protocol transaction is included in a block. Storage
@@ -638,7 +726,7 @@ where
 /// need to be metered and validity predicates are bypassed. A [`TxResult`]
 /// containing changed keys and the like should be returned in the normal way.
 pub(crate) fn apply_protocol_tx<D, H>(
-    tx: ProtocolTxType,
+    ProtocolTx { pk, tx }: ProtocolTx,
     data: Option<Vec<u8>>,
     storage: &mut WlStorage<D, H>,
 ) -> Result<TxResult>
@@ -693,6 +781,14 @@ where
             )
             .map_err(Error::ProtocolTxError)
         }
+        EthereumTxData::DuplicateVextEvidence(evidence) => {
+            // anyone can submit the evidence, and be paid the bounty
+            let submitter = Address::from(&pk);
+            transactions::evidence::apply_derived_tx(
+                storage, evidence, &submitter,
+            )
+            .map_err(Error::ProtocolTxError)
+        }
         EthereumTxData::EthereumEvents(_)
         | EthereumTxData::BridgePool(_)
         | EthereumTxData::ValidatorSetUpdate(_) => {
@@ -771,8 +867,10 @@ where
     H: 'static + StorageHasher + Sync,
     CA: 'static + WasmCacheAccess + Sync,
 {
-    let (verifiers, keys_changed) =
+    let (mut verifiers, keys_changed) =
         write_log.verifiers_and_changed_keys(verifiers_from_tx);
+    // The native VPs must always validate the changes of the keys they guard
+    verifiers.extend(key_space::guarding_vps(&keys_changed));
 
     let vps_result = execute_vps(
         verifiers,
@@ -1025,6 +1123,17 @@ where
                                 gas_meter = masp.ctx.gas_meter.into_inner();
                                 (result, masp.ctx.sentinel.into_inner())
                             }
+                            InternalAddress::Scheduler => {
+                                let scheduler = SchedulerVp { ctx };
+                                let result = scheduler
+                                    .validate_tx(tx, &keys_changed, &verifiers)
+                                    .map_err(Error::SchedulerNativeVpError);
+                                // Take the gas meter and the sentinel back out
+                                // of the context
+                                gas_meter =
+                                    scheduler.ctx.gas_meter.into_inner();
+                                (result, scheduler.ctx.sentinel.into_inner())
+                            }
                         };
 
                     accepted.map_err(|err| {
@@ -1123,6 +1232,7 @@ mod tests {
         EthereumEvent, TransferToNamada,
     };
     use namada_core::types::keccak::keccak_hash;
+    use namada_core::types::key::RefTo;
     use namada_core::types::storage::BlockHeight;
     use namada_core::types::voting_power::FractionalVotingPower;
     use namada_core::types::{address, key};
@@ -1150,6 +1260,10 @@ mod tests {
         H: 'static + StorageHasher + Sync,
     {
         let (data, tx) = tx.serialize();
+        let tx = ProtocolTx {
+            pk: key::testing::keypair_1().ref_to(),
+            tx,
+        };
         let tx_result = apply_protocol_tx(tx, Some(data), wl_storage)?;
         Ok(tx_result)
     }
@@ -1,5 +1,6 @@
 //! Host functions for VPs used for both native and WASM VPs.
 
+use std::collections::BTreeSet;
 use std::num::TryFromIntError;
 
 use namada_core::types::address::{Address, ESTABLISHED_ADDRESS_BYTES_LEN};
@@ -11,7 +12,7 @@ use namada_core::types::storage::{
 use namada_core::types::validity_predicate::VpSentinel;
 use namada_gas::MEMORY_ACCESS_GAS_PER_BYTE;
 use namada_state::write_log::WriteLog;
-use namada_state::{write_log, State, StorageHasher};
+use namada_state::{write_log, State, StorageHasher, ValueChunk};
 use namada_tx::{Section, Tx};
 use thiserror::Error;
 
@@ -41,6 +42,8 @@ pub enum RuntimeError {
     ReadPermanentValueError,
     #[error("Invalid transaction code hash")]
     InvalidCodeHash,
+    #[error("Wasm execution exceeded its wall-clock deadline")]
+    ExecutionDeadline,
 }
 
 /// VP environment function result
@@ -144,6 +147,93 @@ where
     }
 }
 
+/// Storage read of a chunk of `len` bytes starting at `offset` of a value in
+/// prior state (before tx execution). It will try to read from the write log
+/// first and if no entry found then from the storage, without loading the
+/// whole value.
+pub fn read_chunk_pre<DB, H>(
+    gas_meter: &mut VpGasMeter,
+    storage: &State<DB, H>,
+    write_log: &WriteLog,
+    key: &Key,
+    offset: u64,
+    len: u64,
+    sentinel: &mut VpSentinel,
+) -> EnvResult<Option<ValueChunk>>
+where
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+{
+    let (log_val, gas) = write_log.read_pre(key);
+    add_gas(gas_meter, gas, sentinel)?;
+    read_chunk_from_log_or_storage(
+        gas_meter, storage, log_val, key, offset, len, sentinel,
+    )
+}
+
+/// Storage read of a chunk of `len` bytes starting at `offset` of a value in
+/// posterior state (after tx execution). It will try to read from the write
+/// log first and if no entry found then from the storage, without loading the
+/// whole value.
+pub fn read_chunk_post<DB, H>(
+    gas_meter: &mut VpGasMeter,
+    storage: &State<DB, H>,
+    write_log: &WriteLog,
+    key: &Key,
+    offset: u64,
+    len: u64,
+    sentinel: &mut VpSentinel,
+) -> EnvResult<Option<ValueChunk>>
+where
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+{
+    let (log_val, gas) = write_log.read(key);
+    add_gas(gas_meter, gas, sentinel)?;
+    read_chunk_from_log_or_storage(
+        gas_meter, storage, log_val, key, offset, len, sentinel,
+    )
+}
+
+fn read_chunk_from_log_or_storage<DB, H>(
+    gas_meter: &mut VpGasMeter,
+    storage: &State<DB, H>,
+    log_val: Option<&write_log::StorageModification>,
+    key: &Key,
+    offset: u64,
+    len: u64,
+    sentinel: &mut VpSentinel,
+) -> EnvResult<Option<ValueChunk>>
+where
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+{
+    match log_val {
+        Some(write_log::StorageModification::Write { value }) => {
+            Ok(Some(ValueChunk::new(value, offset, len)))
+        }
+        Some(write_log::StorageModification::Delete) => {
+            // Given key has been deleted
+            Ok(None)
+        }
+        Some(write_log::StorageModification::InitAccount { vp_code_hash }) => {
+            // Read the VP code hash of a new account
+            Ok(Some(ValueChunk::new(vp_code_hash.as_ref(), offset, len)))
+        }
+        Some(write_log::StorageModification::Temp { .. }) => {
+            Err(RuntimeError::ReadTemporaryValueError)
+        }
+        None => {
+            // When not found in write log, try to read from the storage
+            let (chunk, gas) = storage
+                .read_chunk(key, offset, len)
+                .map_err(RuntimeError::StorageError)?;
+            add_gas(gas_meter, gas, sentinel)?;
+            Ok(chunk)
+        }
+    }
+}
+
 /// Storage read temporary state (after tx execution). It will try to read from
 /// only the write log.
 pub fn read_temp(
@@ -399,6 +489,36 @@ pub fn get_ibc_events(
         .collect())
 }
 
+/// Get a page of the storage keys changed by the transaction under the given
+/// prefix, ordered by storage keys. The keys are skipped up to the `offset`
+/// and at most `limit` keys are returned.
+pub fn get_changed_keys(
+    gas_meter: &mut VpGasMeter,
+    keys_changed: &BTreeSet<Key>,
+    prefix: &Key,
+    offset: u64,
+    limit: u64,
+    sentinel: &mut VpSentinel,
+) -> EnvResult<Vec<Key>> {
+    let offset: usize = offset
+        .try_into()
+        .map_err(RuntimeError::NumConversionError)?;
+    let limit: usize =
+        limit.try_into().map_err(RuntimeError::NumConversionError)?;
+    // The keys under a prefix are contiguous in the set, starting from the
+    // prefix itself
+    let keys: Vec<Key> = keys_changed
+        .range(prefix.clone()..)
+        .take_while(|key| key.split_prefix(prefix).is_some())
+        .skip(offset)
+        .take(limit)
+        .cloned()
+        .collect();
+    let len: usize = keys.iter().map(Key::len).sum();
+    add_gas(gas_meter, len as u64 * MEMORY_ACCESS_GAS_PER_BYTE, sentinel)?;
+    Ok(keys)
+}
+
 /// Storage prefix iterator for prior state (before tx execution), ordered by
 /// storage keys. It will try to get an iterator from the storage.
 pub fn iter_prefix_pre<'a, DB, H>(
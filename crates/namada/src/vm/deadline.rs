@@ -0,0 +1,176 @@
+//! Wall-clock cap on the execution of tx and VP wasm.
+//!
+//! Gas is the only limit on the execution of wasm that validators agree on,
+//! so a bug in the gas costs of host functions could let a tx tie up a node.
+//! As a defense in depth layer, a node can cap the wall-clock time of the tx
+//! and VP wasm runs it does on its own behalf. The deadline is checked at the metering
+//! points of the host functions, at the first gas charge of a run and then
+//! every [`CHECK_INTERVAL`] charges, and a run that's past its deadline is
+//! stopped as if it ran out of gas.
+//!
+//! Unlike gas, the wall-clock time isn't deterministic, so the cap is only
+//! enforced on the wasm runs whose result isn't part of consensus, i.e. in
+//! CheckTx, dry-runs and queries. In block execution, a slow validator would
+//! otherwise reject a tx that the others accept and its app hash would
+//! diverge, so the wasm runs there are never capped.
+
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// The number of gas charges between two reads of the clock
+pub const CHECK_INTERVAL: u32 = 1_024;
+
+thread_local! {
+    /// The execution cap enforced on the wasm runs on the current thread
+    static ENFORCED_CAP: Cell<Option<Duration>> = Cell::new(None);
+    /// The deadline of the wasm run on the current thread
+    static DEADLINE: Cell<Option<Instant>> = Cell::new(None);
+    /// The gas charges since the last read of the clock
+    static CHARGES: Cell<u32> = Cell::new(0);
+}
+
+/// Guard of the execution cap enforced on the current thread. The previous
+/// cap is restored when it's dropped.
+#[derive(Debug)]
+pub struct EnforcementGuard {
+    previous: Option<Duration>,
+}
+
+impl Drop for EnforcementGuard {
+    fn drop(&mut self) {
+        ENFORCED_CAP.with(|cap| cap.set(self.previous));
+    }
+}
+
+/// Enforce the given wall-clock cap, if any, on the wasm runs on the current
+/// thread until the returned guard is dropped. This must only be used for
+/// execution whose result isn't part of consensus.
+pub fn enforce(cap: Option<Duration>) -> EnforcementGuard {
+    let previous = ENFORCED_CAP.with(|enforced| enforced.replace(cap));
+    EnforcementGuard { previous }
+}
+
+/// Get the wall-clock cap enforced on the wasm runs on the current thread,
+/// if any.
+pub fn enforced_cap() -> Option<Duration> {
+    ENFORCED_CAP.with(|cap| cap.get())
+}
+
+/// Guard of the deadline of a wasm run on the current thread. The previous
+/// deadline is restored when it's dropped.
+#[derive(Debug)]
+pub struct DeadlineGuard {
+    previous: Option<Instant>,
+}
+
+impl DeadlineGuard {
+    /// Arm the deadline of a wasm run with the given cap. A run nested in
+    /// another one, like a VP called with `eval`, is bound by the deadline
+    /// of the outer run.
+    pub fn new(cap: Option<Duration>) -> Self {
+        let previous = DEADLINE.with(|deadline| deadline.get());
+        let deadline = previous
+            .or_else(|| cap.and_then(|cap| Instant::now().checked_add(cap)));
+        DEADLINE.with(|cell| cell.set(deadline));
+        // the clock is read at the first charge
+        CHARGES.with(|charges| charges.set(CHECK_INTERVAL - 1));
+        Self { previous }
+    }
+}
+
+impl Drop for DeadlineGuard {
+    fn drop(&mut self) {
+        DEADLINE.with(|deadline| deadline.set(self.previous));
+    }
+}
+
+/// Arm the deadline of a wasm run with the execution cap enforced on the
+/// current thread. Without one, the run has no deadline.
+pub fn arm() -> DeadlineGuard {
+    DeadlineGuard::new(enforced_cap())
+}
+
+/// Check at a metering point if the wasm run on the current thread is past
+/// its deadline. The clock is only read at the first call and then every
+/// [`CHECK_INTERVAL`] calls.
+pub fn is_exceeded() -> bool {
+    let Some(deadline) = DEADLINE.with(|deadline| deadline.get()) else {
+        return false;
+    };
+    let charges = CHARGES.with(|charges| {
+        let count = charges.get() + 1;
+        charges.set(count % CHECK_INTERVAL);
+        count
+    });
+    charges == CHECK_INTERVAL && Instant::now() >= deadline
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that the deadline is checked at the first charge and then only
+    /// every `CHECK_INTERVAL` charges
+    #[test]
+    fn test_deadline_check_interval() {
+        assert!(!is_exceeded());
+
+        let _guard = DeadlineGuard::new(Some(Duration::ZERO));
+        assert!(is_exceeded());
+        for _ in 1..CHECK_INTERVAL {
+            assert!(!is_exceeded());
+        }
+        assert!(is_exceeded());
+        assert!(!is_exceeded());
+    }
+
+    /// Test that nested runs keep the deadline of the outer run and that the
+    /// deadline is disarmed when the outer run is over
+    #[test]
+    fn test_nested_deadline() {
+        let exceeded_within_interval =
+            || (0..CHECK_INTERVAL).any(|_| is_exceeded());
+        {
+            let _outer = DeadlineGuard::new(Some(Duration::ZERO));
+            {
+                let _inner = DeadlineGuard::new(Some(Duration::from_secs(60)));
+                assert!(exceeded_within_interval());
+            }
+            assert!(exceeded_within_interval());
+        }
+        assert!(!exceeded_within_interval());
+
+        {
+            let _outer = DeadlineGuard::new(None);
+            assert!(!exceeded_within_interval());
+        }
+    }
+
+    /// Test that a wasm run only has a deadline when a cap is enforced on its
+    /// thread
+    #[test]
+    fn test_enforced_deadline() {
+        let exceeded_within_interval =
+            || (0..CHECK_INTERVAL).any(|_| is_exceeded());
+        {
+            let _deadline = arm();
+            assert!(!exceeded_within_interval());
+        }
+        {
+            let _enforced = enforce(Some(Duration::ZERO));
+            {
+                let _deadline = arm();
+                assert!(exceeded_within_interval());
+            }
+            {
+                let _not_enforced = enforce(None);
+                let _deadline = arm();
+                assert!(!exceeded_within_interval());
+            }
+            assert_eq!(enforced_cap(), Some(Duration::ZERO));
+        }
+        assert_eq!(enforced_cap(), None);
+        let _deadline = arm();
+        assert!(!exceeded_within_interval());
+    }
+}
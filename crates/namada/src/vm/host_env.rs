@@ -16,7 +16,7 @@ use namada_gas::{
     MEMORY_ACCESS_GAS_PER_BYTE,
 };
 use namada_state::write_log::{self, WriteLog};
-use namada_state::{self, ResultExt, State, StorageHasher};
+use namada_state::{self, ResultExt, State, StorageHasher, ValueChunk};
 use namada_tx::data::TxSentinel;
 use namada_tx::Tx;
 use thiserror::Error;
@@ -38,7 +38,7 @@ use crate::types::internal::HostEnvResult;
 use crate::types::storage::{BlockHeight, Epoch, Key, TxIndex};
 use crate::vm::memory::VmMemory;
 use crate::vm::prefix_iter::{PrefixIteratorId, PrefixIterators};
-use crate::vm::{HostRef, MutHostRef};
+use crate::vm::{deadline, HostRef, MutHostRef};
 
 /// These runtime errors will abort tx WASM execution immediately
 #[allow(missing_docs)]
@@ -72,6 +72,8 @@ pub enum TxRuntimeError {
     MissingTxData,
     #[error("IBC: {0}")]
     Ibc(#[from] namada_ibc::Error),
+    #[error("Wasm execution exceeded its wall-clock deadline")]
+    ExecutionDeadline,
 }
 
 type TxResult<T> = std::result::Result<T, TxRuntimeError>;
@@ -235,6 +237,58 @@ where
     }
 }
 
+impl<'a, DB, H, CA> TxCtx<'a, DB, H, CA>
+where
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    /// Copy the context without sharing the references to the host
+    /// structures with its clones, so that it can be re-bound without
+    /// affecting them.
+    pub fn detach(&self) -> Self {
+        Self {
+            storage: self.storage.detach(),
+            write_log: self.write_log.detach(),
+            iterators: self.iterators.detach(),
+            gas_meter: self.gas_meter.detach(),
+            sentinel: self.sentinel.detach(),
+            tx: self.tx.detach(),
+            tx_index: self.tx_index.detach(),
+            verifiers: self.verifiers.detach(),
+            result_buffer: self.result_buffer.detach(),
+            #[cfg(feature = "wasm-runtime")]
+            vp_wasm_cache: self.vp_wasm_cache.detach(),
+            #[cfg(feature = "wasm-runtime")]
+            tx_wasm_cache: self.tx_wasm_cache.detach(),
+            #[cfg(not(feature = "wasm-runtime"))]
+            cache_access: std::marker::PhantomData,
+        }
+    }
+
+    /// Re-bind the context and all of its clones to the host structures of
+    /// the other context.
+    ///
+    /// # Safety
+    ///
+    /// The clones must not be used while they are being re-bound.
+    pub unsafe fn rebind(&self, other: &Self) {
+        self.storage.rebind(&other.storage);
+        self.write_log.rebind(&other.write_log);
+        self.iterators.rebind(&other.iterators);
+        self.gas_meter.rebind(&other.gas_meter);
+        self.sentinel.rebind(&other.sentinel);
+        self.tx.rebind(&other.tx);
+        self.tx_index.rebind(&other.tx_index);
+        self.verifiers.rebind(&other.verifiers);
+        self.result_buffer.rebind(&other.result_buffer);
+        #[cfg(feature = "wasm-runtime")]
+        self.vp_wasm_cache.rebind(&other.vp_wasm_cache);
+        #[cfg(feature = "wasm-runtime")]
+        self.tx_wasm_cache.rebind(&other.tx_wasm_cache);
+    }
+}
+
 /// A validity predicate's host environment
 pub struct VpVmEnv<'a, MEM, DB, H, EVAL, CA>
 where
@@ -479,6 +533,61 @@ where
     }
 }
 
+impl<'a, DB, H, EVAL, CA> VpCtx<'a, DB, H, EVAL, CA>
+where
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    /// Copy the context without sharing the references to the host
+    /// structures with its clones, so that it can be re-bound without
+    /// affecting them.
+    pub fn detach(&self) -> Self {
+        Self {
+            address: self.address.detach(),
+            storage: self.storage.detach(),
+            write_log: self.write_log.detach(),
+            iterators: self.iterators.detach(),
+            gas_meter: self.gas_meter.detach(),
+            sentinel: self.sentinel.detach(),
+            tx: self.tx.detach(),
+            tx_index: self.tx_index.detach(),
+            eval_runner: self.eval_runner.detach(),
+            result_buffer: self.result_buffer.detach(),
+            keys_changed: self.keys_changed.detach(),
+            verifiers: self.verifiers.detach(),
+            #[cfg(feature = "wasm-runtime")]
+            vp_wasm_cache: self.vp_wasm_cache.detach(),
+            #[cfg(not(feature = "wasm-runtime"))]
+            cache_access: std::marker::PhantomData,
+        }
+    }
+
+    /// Re-bind the context and all of its clones to the host structures of
+    /// the other context.
+    ///
+    /// # Safety
+    ///
+    /// The clones must not be used while they are being re-bound.
+    pub unsafe fn rebind(&self, other: &Self) {
+        self.address.rebind(&other.address);
+        self.storage.rebind(&other.storage);
+        self.write_log.rebind(&other.write_log);
+        self.iterators.rebind(&other.iterators);
+        self.gas_meter.rebind(&other.gas_meter);
+        self.sentinel.rebind(&other.sentinel);
+        self.tx.rebind(&other.tx);
+        self.tx_index.rebind(&other.tx_index);
+        self.eval_runner.rebind(&other.eval_runner);
+        self.result_buffer.rebind(&other.result_buffer);
+        self.keys_changed.rebind(&other.keys_changed);
+        self.verifiers.rebind(&other.verifiers);
+        #[cfg(feature = "wasm-runtime")]
+        self.vp_wasm_cache.rebind(&other.vp_wasm_cache);
+    }
+}
+
 /// Add a gas cost incured in a transaction
 pub fn tx_charge_gas<MEM, DB, H, CA>(
     env: &TxVmEnv<MEM, DB, H, CA>,
@@ -490,6 +599,15 @@ where
     H: StorageHasher,
     CA: WasmCacheAccess,
 {
+    if deadline::is_exceeded() {
+        let sentinel = unsafe { env.ctx.sentinel.get() };
+        sentinel.set_out_of_gas();
+        tracing::warn!(
+            "Stopping transaction execution because it exceeded its \
+             wall-clock deadline"
+        );
+        return Err(TxRuntimeError::ExecutionDeadline);
+    }
     let gas_meter = unsafe { env.ctx.gas_meter.get() };
     // if we run out of gas, we need to stop the execution
     gas_meter.consume(used_gas).map_err(|err| {
@@ -518,6 +636,13 @@ where
 {
     let gas_meter = unsafe { env.ctx.gas_meter.get() };
     let sentinel = unsafe { env.ctx.sentinel.get() };
+    if deadline::is_exceeded() {
+        sentinel.set_out_of_gas();
+        tracing::warn!(
+            "Stopping VP execution because it exceeded its wall-clock deadline"
+        );
+        return Err(vp_host_fns::RuntimeError::ExecutionDeadline);
+    }
     vp_host_fns::add_gas(gas_meter, used_gas, sentinel)
 }
 
@@ -661,6 +786,84 @@ where
     })
 }
 
+/// Storage read of a chunk of a value function exposed to the wasm VM Tx
+/// environment, to read large values with bounded memory. It will try to read
+/// from the write log first and if no entry found then from the storage.
+///
+/// Returns `-1` when the key is not present, or the length of the chunk of at
+/// most `len` bytes starting at `offset` when the key is present. The chunk
+/// is shorter than `len` (possibly `0`) when the end of the value is reached.
+pub fn tx_read_chunk<MEM, DB, H, CA>(
+    env: &TxVmEnv<MEM, DB, H, CA>,
+    key_ptr: u64,
+    key_len: u64,
+    offset: u64,
+    len: u64,
+) -> TxResult<i64>
+where
+    MEM: VmMemory,
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    let (key, gas) = env
+        .memory
+        .read_string(key_ptr, key_len as _)
+        .map_err(|e| TxRuntimeError::MemoryError(Box::new(e)))?;
+    tx_charge_gas(env, gas)?;
+
+    tracing::debug!(
+        "tx_read_chunk {}, key {}, offset {}, len {}",
+        key,
+        key_ptr,
+        offset,
+        len
+    );
+
+    let key = Key::parse(key).map_err(TxRuntimeError::StorageDataError)?;
+
+    // try to read from the write log first
+    let write_log = unsafe { env.ctx.write_log.get() };
+    let (log_val, gas) = write_log.read(&key);
+    tx_charge_gas(env, gas)?;
+    let chunk = match log_val {
+        Some(write_log::StorageModification::Write { value })
+        | Some(write_log::StorageModification::Temp { value }) => {
+            Some(ValueChunk::new(value, offset, len))
+        }
+        Some(write_log::StorageModification::Delete) => {
+            // fail, given key has been deleted
+            None
+        }
+        Some(write_log::StorageModification::InitAccount { vp_code_hash }) => {
+            // read the VP of a new account
+            Some(ValueChunk::new(vp_code_hash.as_ref(), offset, len))
+        }
+        None => {
+            // when not found in write log, try to read from the storage
+            let storage = unsafe { env.ctx.storage.get() };
+            let (chunk, gas) = storage
+                .read_chunk(&key, offset, len)
+                .map_err(TxRuntimeError::StorageError)?;
+            tx_charge_gas(env, gas)?;
+            chunk
+        }
+    };
+    Ok(match chunk {
+        Some(chunk) => {
+            let len: i64 = chunk
+                .bytes
+                .len()
+                .try_into()
+                .map_err(TxRuntimeError::NumConversionError)?;
+            let result_buffer = unsafe { env.ctx.result_buffer.get() };
+            result_buffer.replace(chunk.bytes);
+            len
+        }
+        None => HostEnvResult::Fail.to_i64(),
+    })
+}
+
 /// This function is a helper to handle the first step of reading var-len
 /// values from the host.
 ///
@@ -1131,6 +1334,129 @@ where
     })
 }
 
+/// Storage read of a chunk of a value in prior state (before tx execution)
+/// function exposed to the wasm VM VP environment, to read large values with
+/// bounded memory. It will try to read from the storage.
+///
+/// Returns `-1` when the key is not present, or the length of the chunk of at
+/// most `len` bytes starting at `offset` when the key is present. The chunk
+/// is shorter than `len` (possibly `0`) when the end of the value is reached.
+pub fn vp_read_chunk_pre<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    key_ptr: u64,
+    key_len: u64,
+    offset: u64,
+    len: u64,
+) -> vp_host_fns::EnvResult<i64>
+where
+    MEM: VmMemory,
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let (key, gas) = env
+        .memory
+        .read_string(key_ptr, key_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let sentinel = unsafe { env.ctx.sentinel.get() };
+    vp_host_fns::add_gas(gas_meter, gas, sentinel)?;
+
+    tracing::debug!(
+        "vp_read_chunk_pre {}, key {}, offset {}, len {}",
+        key,
+        key_ptr,
+        offset,
+        len
+    );
+
+    let key =
+        Key::parse(key).map_err(vp_host_fns::RuntimeError::StorageDataError)?;
+    let storage = unsafe { env.ctx.storage.get() };
+    let write_log = unsafe { env.ctx.write_log.get() };
+    let chunk = vp_host_fns::read_chunk_pre(
+        gas_meter, storage, write_log, &key, offset, len, sentinel,
+    )?;
+    vp_chunk_to_result_buffer(env, chunk)
+}
+
+/// Storage read of a chunk of a value in posterior state (after tx execution)
+/// function exposed to the wasm VM VP environment, to read large values with
+/// bounded memory. It will try to read from the write log first and if no
+/// entry found then from the storage.
+///
+/// Returns `-1` when the key is not present, or the length of the chunk of at
+/// most `len` bytes starting at `offset` when the key is present. The chunk
+/// is shorter than `len` (possibly `0`) when the end of the value is reached.
+pub fn vp_read_chunk_post<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    key_ptr: u64,
+    key_len: u64,
+    offset: u64,
+    len: u64,
+) -> vp_host_fns::EnvResult<i64>
+where
+    MEM: VmMemory,
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let (key, gas) = env
+        .memory
+        .read_string(key_ptr, key_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let sentinel = unsafe { env.ctx.sentinel.get() };
+    vp_host_fns::add_gas(gas_meter, gas, sentinel)?;
+
+    tracing::debug!(
+        "vp_read_chunk_post {}, key {}, offset {}, len {}",
+        key,
+        key_ptr,
+        offset,
+        len
+    );
+
+    let key =
+        Key::parse(key).map_err(vp_host_fns::RuntimeError::StorageDataError)?;
+    let storage = unsafe { env.ctx.storage.get() };
+    let write_log = unsafe { env.ctx.write_log.get() };
+    let chunk = vp_host_fns::read_chunk_post(
+        gas_meter, storage, write_log, &key, offset, len, sentinel,
+    )?;
+    vp_chunk_to_result_buffer(env, chunk)
+}
+
+/// Place the bytes of a chunk read by a VP in the result buffer and return
+/// their length, or `-1` if no value was found.
+fn vp_chunk_to_result_buffer<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    chunk: Option<ValueChunk>,
+) -> vp_host_fns::EnvResult<i64>
+where
+    MEM: VmMemory,
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    Ok(match chunk {
+        Some(chunk) => {
+            let len: i64 = chunk
+                .bytes
+                .len()
+                .try_into()
+                .map_err(vp_host_fns::RuntimeError::NumConversionError)?;
+            let result_buffer = unsafe { env.ctx.result_buffer.get() };
+            result_buffer.replace(chunk.bytes);
+            len
+        }
+        None => HostEnvResult::Fail.to_i64(),
+    })
+}
+
 /// Storage read temporary state (after tx execution) function exposed to the
 /// wasm VM VP environment. It will try to read from only the write log.
 ///
@@ -1394,6 +1720,63 @@ where
     Ok(HostEnvResult::Fail.to_i64())
 }
 
+/// Function exposed to the wasm VM VP environment to get a page of the storage
+/// keys changed by the transaction under the given prefix, ordered by storage
+/// keys.
+///
+/// Returns the length of the Borsh encoded keys placed in the result buffer.
+pub fn vp_get_changed_keys<MEM, DB, H, EVAL, CA>(
+    env: &VpVmEnv<MEM, DB, H, EVAL, CA>,
+    prefix_ptr: u64,
+    prefix_len: u64,
+    offset: u64,
+    limit: u64,
+) -> vp_host_fns::EnvResult<i64>
+where
+    MEM: VmMemory,
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator,
+    CA: WasmCacheAccess,
+{
+    let (prefix, gas) = env
+        .memory
+        .read_string(prefix_ptr, prefix_len as _)
+        .map_err(|e| vp_host_fns::RuntimeError::MemoryError(Box::new(e)))?;
+    let gas_meter = unsafe { env.ctx.gas_meter.get() };
+    let sentinel = unsafe { env.ctx.sentinel.get() };
+    vp_host_fns::add_gas(gas_meter, gas, sentinel)?;
+
+    tracing::debug!(
+        "vp_get_changed_keys {}, offset {}, limit {}",
+        prefix,
+        offset,
+        limit
+    );
+
+    let prefix = Key::parse(prefix)
+        .map_err(vp_host_fns::RuntimeError::StorageDataError)?;
+
+    let keys_changed = unsafe { env.ctx.keys_changed.get() };
+    let keys = vp_host_fns::get_changed_keys(
+        gas_meter,
+        keys_changed,
+        &prefix,
+        offset,
+        limit,
+        sentinel,
+    )?;
+    let keys = borsh::to_vec(&keys)
+        .map_err(vp_host_fns::RuntimeError::EncodingError)?;
+    let len: i64 = keys
+        .len()
+        .try_into()
+        .map_err(vp_host_fns::RuntimeError::NumConversionError)?;
+    let result_buffer = unsafe { env.ctx.result_buffer.get() };
+    result_buffer.replace(keys);
+    Ok(len)
+}
+
 /// Verifier insertion function exposed to the wasm VM Tx environment.
 pub fn tx_insert_verifier<MEM, DB, H, CA>(
     env: &TxVmEnv<MEM, DB, H, CA>,
@@ -3,9 +3,12 @@
 use std::ffi::c_void;
 use std::marker::PhantomData;
 use std::slice;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::Arc;
 
 use wasmparser::{Validator, WasmFeatures};
 
+pub mod deadline;
 pub mod host_env;
 pub mod memory;
 pub mod prefix_iter;
@@ -44,6 +47,8 @@ pub enum WasmValidationError {
          {UNTRUSTED_WASM_FEATURES:?}"
     )]
     ForbiddenWasmFeatures(wasmparser::BinaryReaderError),
+    #[error("Invalid WASM using floating-point types or operations: {0}")]
+    FloatingPoint(wasmparser::BinaryReaderError),
 }
 
 /// WASM Cache access level, used to limit dry-ran transactions to read-only
@@ -77,9 +82,12 @@ impl WasmCacheAccess for WasmCacheRoAccess {
 /// reference, so the access is thread-safe, but because of the unsafe
 /// reference conversion, care must be taken that while this reference is
 /// borrowed, no other process can modify it.
+///
+/// The clones of a reference share the pointer to the host structure, so that
+/// they can all be re-bound to another host structure at once.
 #[derive(Clone, Debug)]
 pub struct HostRef<'a, T: 'a> {
-    data: *const c_void,
+    data: Arc<AtomicPtr<c_void>>,
     phantom: PhantomData<&'a T>,
 }
 unsafe impl<T> Send for HostRef<'_, T> {}
@@ -93,8 +101,9 @@ impl<'a, T: 'a> HostRef<'a, &T> {
     /// Because this is unsafe, care must be taken that while this reference
     /// is borrowed, no other process can modify it.
     pub unsafe fn new(host_structure: &T) -> Self {
+        let data = host_structure as *const T as *mut c_void;
         Self {
-            data: host_structure as *const T as *const c_void,
+            data: Arc::new(AtomicPtr::new(data)),
             phantom: PhantomData,
         }
     }
@@ -106,7 +115,27 @@ impl<'a, T: 'a> HostRef<'a, &T> {
     /// Because this is unsafe, care must be taken that while this reference
     /// is borrowed, no other process can modify it.
     pub unsafe fn get(&self) -> &'a T {
-        &*(self.data as *const T)
+        &*(self.data.load(Ordering::Acquire) as *const T)
+    }
+
+    /// Copy the reference without sharing the pointer with its clones, so
+    /// that it can be re-bound without affecting them.
+    pub fn detach(&self) -> Self {
+        Self {
+            data: Arc::new(AtomicPtr::new(self.data.load(Ordering::Acquire))),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Re-bind the reference and all of its clones to the host structure of
+    /// the other reference.
+    ///
+    /// # Safety
+    ///
+    /// The clones must not be used while they are being re-bound.
+    pub unsafe fn rebind(&self, other: &Self) {
+        self.data
+            .store(other.data.load(Ordering::Acquire), Ordering::Release);
     }
 }
 
@@ -154,9 +183,12 @@ impl<'a, T: 'a> HostSlice<'a, &[T]> {
 /// which is used for implementing some host calls. Because it's mutable, it's
 /// not thread-safe. Also, care must be taken that while this reference is
 /// borrowed, no other process can read or modify it.
+///
+/// The clones of a reference share the pointer to the host structure, so that
+/// they can all be re-bound to another host structure at once.
 #[derive(Clone, Debug)]
 pub struct MutHostRef<'a, T: 'a> {
-    data: *mut c_void,
+    data: Arc<AtomicPtr<c_void>>,
     phantom: PhantomData<&'a T>,
 }
 unsafe impl<T> Send for MutHostRef<'_, T> {}
@@ -171,8 +203,9 @@ impl<'a, T: 'a> MutHostRef<'a, &T> {
     /// taken that while this reference is borrowed, no other process can read
     /// or modify it.
     pub unsafe fn new(host_structure: &mut T) -> Self {
+        let data = host_structure as *mut T as *mut c_void;
         Self {
-            data: host_structure as *mut T as *mut c_void,
+            data: Arc::new(AtomicPtr::new(data)),
             phantom: PhantomData,
         }
     }
@@ -185,7 +218,27 @@ impl<'a, T: 'a> MutHostRef<'a, &T> {
     /// taken that while this reference is borrowed, no other process can read
     /// or modify it.
     pub unsafe fn get(&self) -> &'a mut T {
-        &mut *(self.data as *mut T)
+        &mut *(self.data.load(Ordering::Acquire) as *mut T)
+    }
+
+    /// Copy the reference without sharing the pointer with its clones, so
+    /// that it can be re-bound without affecting them.
+    pub fn detach(&self) -> Self {
+        Self {
+            data: Arc::new(AtomicPtr::new(self.data.load(Ordering::Acquire))),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Re-bind the reference and all of its clones to the host structure of
+    /// the other reference.
+    ///
+    /// # Safety
+    ///
+    /// The clones must not be used while they are being re-bound.
+    pub unsafe fn rebind(&self, other: &Self) {
+        self.data
+            .store(other.data.load(Ordering::Acquire), Ordering::Release);
     }
 }
 
@@ -241,3 +294,19 @@ pub fn validate_untrusted_wasm(
         .map_err(WasmValidationError::ForbiddenWasmFeatures)?;
     Ok(())
 }
+
+/// Validate an untrusted wasm code like [`validate_untrusted_wasm`] and also
+/// reject any use of floating-point types or operations
+pub fn validate_untrusted_wasm_without_floats(
+    wasm_code: impl AsRef<[u8]>,
+) -> Result<(), WasmValidationError> {
+    validate_untrusted_wasm(wasm_code.as_ref())?;
+    let mut validator = Validator::new_with_features(WasmFeatures {
+        floats: false,
+        ..UNTRUSTED_WASM_FEATURES
+    });
+    let _types = validator
+        .validate_all(wasm_code.as_ref())
+        .map_err(WasmValidationError::FloatingPoint)?;
+    Ok(())
+}
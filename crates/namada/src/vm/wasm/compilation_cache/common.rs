@@ -20,6 +20,7 @@ use wasmer_cache::{FileSystemCache, Hash as CacheHash};
 
 use crate::core::types::hash::Hash;
 use crate::types::control_flow::time::{ExponentialBackoff, SleepStrategy};
+use crate::vm::wasm::instance_pool::InstancePool;
 use crate::vm::wasm::run::untrusted_wasm_store;
 use crate::vm::wasm::{self, memory};
 use crate::vm::{WasmCacheAccess, WasmCacheRoAccess};
@@ -37,6 +38,8 @@ pub struct Cache<N: CacheName, A: WasmCacheAccess> {
     name: PhantomData<N>,
     /// Cache access level
     access: PhantomData<A>,
+    /// Pool of instances of the compiled modules
+    instances: InstancePool,
 }
 
 /// This trait is used to give names to different caches
@@ -89,6 +92,7 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
             in_memory,
             name: Default::default(),
             access: Default::default(),
+            instances: Default::default(),
         }
     }
 
@@ -107,6 +111,11 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
         }
     }
 
+    /// Get the pool of instances of the compiled modules
+    pub fn instance_pool(&self) -> &InstancePool {
+        &self.instances
+    }
+
     /// Get the current number of items in the cache
     pub fn get_size(&self) -> usize {
         self.in_memory.read().unwrap().len()
@@ -462,6 +471,9 @@ impl<N: CacheName, A: WasmCacheAccess> Cache<N, A> {
             in_memory: self.in_memory.clone(),
             name: Default::default(),
             access: Default::default(),
+            // The instances are not shared with the read-only cache, which
+            // runs with a different host environment
+            instances: Default::default(),
         }
     }
 }
@@ -3,13 +3,17 @@
 //! Here, we expose the host functions into wasm's
 //! imports, so they can be called from inside the wasm.
 
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+
 use namada_core::types::hash::StorageHasher;
 use wasmer::{
-    Function, HostEnvInitError, ImportObject, Instance, Memory, Store,
+    Exports, Function, HostEnvInitError, ImportObject, Instance, Memory, Store,
     WasmerEnv,
 };
 
 use crate::vm::host_env::{TxVmEnv, VpEvaluator, VpVmEnv};
+use crate::vm::wasm::instance_pool::RebindEnv;
 use crate::vm::wasm::memory::WasmMemory;
 use crate::vm::{host_env, WasmCacheAccess};
 
@@ -42,63 +46,261 @@ where
     }
 }
 
+impl<DB, H, CA> RebindEnv for TxVmEnv<'static, WasmMemory, DB, H, CA>
+where
+    DB: 'static + namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: 'static + StorageHasher,
+    CA: 'static + WasmCacheAccess,
+{
+    fn detach(&self) -> Self {
+        Self {
+            memory: self.memory.clone(),
+            ctx: self.ctx.detach(),
+        }
+    }
+
+    unsafe fn rebind(&self, other: &Self) {
+        self.ctx.rebind(&other.ctx)
+    }
+}
+
+impl<DB, H, EVAL, CA> RebindEnv
+    for VpVmEnv<'static, WasmMemory, DB, H, EVAL, CA>
+where
+    DB: 'static + namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: 'static + StorageHasher,
+    EVAL: 'static + VpEvaluator,
+    CA: 'static + WasmCacheAccess,
+{
+    fn detach(&self) -> Self {
+        Self {
+            memory: self.memory.clone(),
+            ctx: self.ctx.detach(),
+        }
+    }
+
+    unsafe fn rebind(&self, other: &Self) {
+        self.ctx.rebind(&other.ctx)
+    }
+}
+
+/// The namespace of the host functions linked to the modules that don't
+/// request a version of the host functions API. It exposes the memory, the gas
+/// injection hook and the host functions of the API version 1.
+pub const LEGACY_NAMESPACE: &str = "env";
+/// The prefix of the versioned namespaces of the tx host functions, followed
+/// by the API version (e.g. `namada_tx_v1`)
+pub const TX_NAMESPACE_PREFIX: &str = "namada_tx_v";
+/// The prefix of the versioned namespaces of the VP host functions, followed
+/// by the API version (e.g. `namada_vp_v1`)
+pub const VP_NAMESPACE_PREFIX: &str = "namada_vp_v";
+/// The versions of the host functions API that the VM can link
+pub const SUPPORTED_API_VERSIONS: RangeInclusive<u32> = 1..=2;
+
+/// The names of the imports of the legacy namespace that are not host
+/// functions
+pub const LEGACY_NAMESPACE_EXTRA_IMPORTS: [&str; 2] = ["memory", "gas"];
+
+/// The tx host functions available since the API version 1
+const TX_HOST_FUNCTIONS_V1: &[&str] = &[
+    "namada_tx_charge_gas",
+    "namada_tx_read",
+    "namada_tx_result_buffer",
+    "namada_tx_has_key",
+    "namada_tx_write",
+    "namada_tx_write_temp",
+    "namada_tx_delete",
+    "namada_tx_iter_prefix",
+    "namada_tx_iter_next",
+    "namada_tx_insert_verifier",
+    "namada_tx_update_validity_predicate",
+    "namada_tx_init_account",
+    "namada_tx_emit_ibc_event",
+    "namada_tx_get_ibc_events",
+    "namada_tx_get_chain_id",
+    "namada_tx_get_tx_index",
+    "namada_tx_get_block_height",
+    "namada_tx_get_block_header",
+    "namada_tx_get_block_hash",
+    "namada_tx_get_block_epoch",
+    "namada_tx_get_pred_epochs",
+    "namada_tx_get_native_token",
+    "namada_tx_log_string",
+    "namada_tx_ibc_execute",
+    "namada_tx_set_commitment_sentinel",
+    "namada_tx_verify_tx_section_signature",
+    "namada_tx_update_masp_note_commitment_tree",
+];
+
+/// The tx host functions added in the API version 2
+const TX_HOST_FUNCTIONS_V2: &[&str] = &["namada_tx_read_chunk"];
+
+/// The VP host functions available since the API version 1
+const VP_HOST_FUNCTIONS_V1: &[&str] = &[
+    "namada_vp_charge_gas",
+    "namada_vp_read_pre",
+    "namada_vp_read_post",
+    "namada_vp_read_temp",
+    "namada_vp_result_buffer",
+    "namada_vp_has_key_pre",
+    "namada_vp_has_key_post",
+    "namada_vp_iter_prefix_pre",
+    "namada_vp_iter_prefix_post",
+    "namada_vp_iter_next",
+    "namada_vp_get_changed_keys",
+    "namada_vp_get_chain_id",
+    "namada_vp_get_tx_index",
+    "namada_vp_get_block_height",
+    "namada_vp_get_block_header",
+    "namada_vp_get_block_hash",
+    "namada_vp_get_tx_code_hash",
+    "namada_vp_get_block_epoch",
+    "namada_vp_get_pred_epochs",
+    "namada_vp_get_ibc_events",
+    "namada_vp_verify_tx_section_signature",
+    "namada_vp_eval",
+    "namada_vp_get_native_token",
+    "namada_vp_log_string",
+];
+
+/// The VP host functions added in the API version 2
+const VP_HOST_FUNCTIONS_V2: &[&str] =
+    &["namada_vp_read_chunk_pre", "namada_vp_read_chunk_post"];
+
+/// The names of the tx host functions of the given API version
+pub fn tx_host_function_names(
+    version: u32,
+) -> impl Iterator<Item = &'static str> {
+    let v2 = if version >= 2 {
+        TX_HOST_FUNCTIONS_V2
+    } else {
+        &[]
+    };
+    TX_HOST_FUNCTIONS_V1.iter().chain(v2).copied()
+}
+
+/// The names of the VP host functions of the given API version
+pub fn vp_host_function_names(
+    version: u32,
+) -> impl Iterator<Item = &'static str> {
+    let v2 = if version >= 2 {
+        VP_HOST_FUNCTIONS_V2
+    } else {
+        &[]
+    };
+    VP_HOST_FUNCTIONS_V1.iter().chain(v2).copied()
+}
+
 /// Prepare imports (memory and host functions) exposed to the vm guest running
-/// transaction code
+/// transaction code. The host functions are linked in the legacy namespace
+/// and in the namespaces of the given API versions.
 #[allow(clippy::too_many_arguments)]
 pub fn tx_imports<DB, H, CA>(
     wasm_store: &Store,
     initial_memory: Memory,
     env: TxVmEnv<'static, WasmMemory, DB, H, CA>,
+    api_versions: &BTreeSet<u32>,
 ) -> ImportObject
 where
     DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
     H: StorageHasher,
     CA: WasmCacheAccess,
 {
-    wasmer::imports! {
-        // default namespace
-        "env" => {
-            "memory" => initial_memory,
-            // Wasm middleware gas injection hook
-            "gas" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_charge_gas),
-            // Whitelisted gas exposed function, we need two different functions just because of colliding names in the vm_host_env macro to generate implementations
-            "namada_tx_charge_gas" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_charge_gas),
-            "namada_tx_read" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_read),
-            "namada_tx_result_buffer" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_result_buffer),
-            "namada_tx_has_key" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_has_key),
-            "namada_tx_write" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_write),
-            "namada_tx_write_temp" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_write_temp),
-            "namada_tx_delete" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_delete),
-            "namada_tx_iter_prefix" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_iter_prefix),
-            "namada_tx_iter_next" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_iter_next),
-            "namada_tx_insert_verifier" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_insert_verifier),
-            "namada_tx_update_validity_predicate" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_update_validity_predicate),
-            "namada_tx_init_account" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_init_account),
-            "namada_tx_emit_ibc_event" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_emit_ibc_event),
-            "namada_tx_get_ibc_events" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_ibc_events),
-            "namada_tx_get_chain_id" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_chain_id),
-            "namada_tx_get_tx_index" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_tx_index),
-            "namada_tx_get_block_height" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_block_height),
-            "namada_tx_get_block_header" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_block_header),
-            "namada_tx_get_block_hash" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_block_hash),
-            "namada_tx_get_block_epoch" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_block_epoch),
-            "namada_tx_get_pred_epochs" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_pred_epochs),
-            "namada_tx_get_native_token" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_native_token),
-            "namada_tx_log_string" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_log_string),
-            "namada_tx_ibc_execute" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_ibc_execute),
-            "namada_tx_set_commitment_sentinel" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_set_commitment_sentinel),
-            "namada_tx_verify_tx_section_signature" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_verify_tx_section_signature),
-            "namada_tx_update_masp_note_commitment_tree" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_update_masp_note_commitment_tree)
-        },
+    let mut legacy = tx_host_functions(wasm_store, &env, 1);
+    legacy.insert("memory", initial_memory);
+    // Wasm middleware gas injection hook
+    legacy.insert(
+        "gas",
+        Function::new_native_with_env(
+            wasm_store,
+            env.clone(),
+            host_env::tx_charge_gas,
+        ),
+    );
+    let mut imports = ImportObject::new();
+    imports.register(LEGACY_NAMESPACE, legacy);
+    for version in api_versions {
+        imports.register(
+            format!("{TX_NAMESPACE_PREFIX}{version}"),
+            tx_host_functions(wasm_store, &env, *version),
+        );
+    }
+    imports
+}
+
+/// The tx host functions of the given API version
+fn tx_host_functions<DB, H, CA>(
+    wasm_store: &Store,
+    env: &TxVmEnv<'static, WasmMemory, DB, H, CA>,
+    version: u32,
+) -> Exports
+where
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    CA: WasmCacheAccess,
+{
+    debug_assert!(SUPPORTED_API_VERSIONS.contains(&version));
+    // All of these are available since the API version 1. Functions added in
+    // a later version must only be inserted for that version and above.
+    let mut exports = wasmer::namespace! {
+        // Whitelisted gas exposed function, we need two different functions just because of colliding names in the vm_host_env macro to generate implementations
+        "namada_tx_charge_gas" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_charge_gas),
+        "namada_tx_read" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_read),
+        "namada_tx_result_buffer" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_result_buffer),
+        "namada_tx_has_key" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_has_key),
+        "namada_tx_write" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_write),
+        "namada_tx_write_temp" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_write_temp),
+        "namada_tx_delete" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_delete),
+        "namada_tx_iter_prefix" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_iter_prefix),
+        "namada_tx_iter_next" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_iter_next),
+        "namada_tx_insert_verifier" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_insert_verifier),
+        "namada_tx_update_validity_predicate" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_update_validity_predicate),
+        "namada_tx_init_account" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_init_account),
+        "namada_tx_emit_ibc_event" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_emit_ibc_event),
+        "namada_tx_get_ibc_events" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_ibc_events),
+        "namada_tx_get_chain_id" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_chain_id),
+        "namada_tx_get_tx_index" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_tx_index),
+        "namada_tx_get_block_height" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_block_height),
+        "namada_tx_get_block_header" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_block_header),
+        "namada_tx_get_block_hash" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_block_hash),
+        "namada_tx_get_block_epoch" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_block_epoch),
+        "namada_tx_get_pred_epochs" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_pred_epochs),
+        "namada_tx_get_native_token" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_get_native_token),
+        "namada_tx_log_string" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_log_string),
+        "namada_tx_ibc_execute" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_ibc_execute),
+        "namada_tx_set_commitment_sentinel" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_set_commitment_sentinel),
+        "namada_tx_verify_tx_section_signature" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_verify_tx_section_signature),
+        "namada_tx_update_masp_note_commitment_tree" => Function::new_native_with_env(wasm_store, env.clone(), host_env::tx_update_masp_note_commitment_tree),
+    };
+    if version >= 2 {
+        exports.insert(
+            "namada_tx_read_chunk",
+            Function::new_native_with_env(
+                wasm_store,
+                env.clone(),
+                host_env::tx_read_chunk,
+            ),
+        );
     }
+    debug_assert_eq!(
+        exports
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<BTreeSet<_>>(),
+        tx_host_function_names(version).collect(),
+        "The tx host functions must match their names"
+    );
+    exports
 }
 
 /// Prepare imports (memory and host functions) exposed to the vm guest running
-/// validity predicate code
+/// validity predicate code. The host functions are linked in the legacy
+/// namespace and in the namespaces of the given API versions.
 pub fn vp_imports<DB, H, EVAL, CA>(
     wasm_store: &Store,
     initial_memory: Memory,
     env: VpVmEnv<'static, WasmMemory, DB, H, EVAL, CA>,
+    api_versions: &BTreeSet<u32>,
 ) -> ImportObject
 where
     DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
@@ -106,36 +308,95 @@ where
     EVAL: VpEvaluator<Db = DB, H = H, Eval = EVAL, CA = CA>,
     CA: WasmCacheAccess,
 {
-    wasmer::imports! {
-        // default namespace
-        "env" => {
-            "memory" => initial_memory,
-            // Wasm middleware gas injection hook
-            "gas" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_charge_gas),
-            // Whitelisted gas exposed function, we need two different functions just because of colliding names in the vm_host_env macro to generate implementations
-            "namada_vp_charge_gas" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_charge_gas),
-            "namada_vp_read_pre" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_read_pre),
-            "namada_vp_read_post" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_read_post),
-            "namada_vp_read_temp" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_read_temp),
-            "namada_vp_result_buffer" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_result_buffer),
-            "namada_vp_has_key_pre" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_has_key_pre),
-            "namada_vp_has_key_post" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_has_key_post),
-            "namada_vp_iter_prefix_pre" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_iter_prefix_pre),
-            "namada_vp_iter_prefix_post" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_iter_prefix_pre),
-            "namada_vp_iter_next" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_iter_next),
-            "namada_vp_get_chain_id" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_chain_id),
-            "namada_vp_get_tx_index" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_tx_index),
-            "namada_vp_get_block_height" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_block_height),
-            "namada_vp_get_block_header" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_block_header),
-            "namada_vp_get_block_hash" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_block_hash),
-            "namada_vp_get_tx_code_hash" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_tx_code_hash),
-            "namada_vp_get_block_epoch" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_block_epoch),
-            "namada_vp_get_pred_epochs" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_pred_epochs),
-            "namada_vp_get_ibc_events" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_ibc_events),
-            "namada_vp_verify_tx_section_signature" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_verify_tx_section_signature),
-            "namada_vp_eval" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_eval),
-            "namada_vp_get_native_token" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_native_token),
-            "namada_vp_log_string" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_log_string),
-        },
+    let mut legacy = vp_host_functions(wasm_store, &env, 1);
+    legacy.insert("memory", initial_memory);
+    // Wasm middleware gas injection hook
+    legacy.insert(
+        "gas",
+        Function::new_native_with_env(
+            wasm_store,
+            env.clone(),
+            host_env::vp_charge_gas,
+        ),
+    );
+    let mut imports = ImportObject::new();
+    imports.register(LEGACY_NAMESPACE, legacy);
+    for version in api_versions {
+        imports.register(
+            format!("{VP_NAMESPACE_PREFIX}{version}"),
+            vp_host_functions(wasm_store, &env, *version),
+        );
+    }
+    imports
+}
+
+/// The VP host functions of the given API version
+fn vp_host_functions<DB, H, EVAL, CA>(
+    wasm_store: &Store,
+    env: &VpVmEnv<'static, WasmMemory, DB, H, EVAL, CA>,
+    version: u32,
+) -> Exports
+where
+    DB: namada_state::DB + for<'iter> namada_state::DBIter<'iter>,
+    H: StorageHasher,
+    EVAL: VpEvaluator<Db = DB, H = H, Eval = EVAL, CA = CA>,
+    CA: WasmCacheAccess,
+{
+    debug_assert!(SUPPORTED_API_VERSIONS.contains(&version));
+    // All of these are available since the API version 1. Functions added in
+    // a later version must only be inserted for that version and above.
+    let mut exports = wasmer::namespace! {
+        // Whitelisted gas exposed function, we need two different functions just because of colliding names in the vm_host_env macro to generate implementations
+        "namada_vp_charge_gas" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_charge_gas),
+        "namada_vp_read_pre" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_read_pre),
+        "namada_vp_read_post" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_read_post),
+        "namada_vp_read_temp" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_read_temp),
+        "namada_vp_result_buffer" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_result_buffer),
+        "namada_vp_has_key_pre" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_has_key_pre),
+        "namada_vp_has_key_post" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_has_key_post),
+        "namada_vp_iter_prefix_pre" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_iter_prefix_pre),
+        "namada_vp_iter_prefix_post" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_iter_prefix_pre),
+        "namada_vp_iter_next" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_iter_next),
+        "namada_vp_get_changed_keys" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_changed_keys),
+        "namada_vp_get_chain_id" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_chain_id),
+        "namada_vp_get_tx_index" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_tx_index),
+        "namada_vp_get_block_height" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_block_height),
+        "namada_vp_get_block_header" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_block_header),
+        "namada_vp_get_block_hash" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_block_hash),
+        "namada_vp_get_tx_code_hash" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_tx_code_hash),
+        "namada_vp_get_block_epoch" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_block_epoch),
+        "namada_vp_get_pred_epochs" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_pred_epochs),
+        "namada_vp_get_ibc_events" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_ibc_events),
+        "namada_vp_verify_tx_section_signature" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_verify_tx_section_signature),
+        "namada_vp_eval" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_eval),
+        "namada_vp_get_native_token" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_get_native_token),
+        "namada_vp_log_string" => Function::new_native_with_env(wasm_store, env.clone(), host_env::vp_log_string),
+    };
+    if version >= 2 {
+        exports.insert(
+            "namada_vp_read_chunk_pre",
+            Function::new_native_with_env(
+                wasm_store,
+                env.clone(),
+                host_env::vp_read_chunk_pre,
+            ),
+        );
+        exports.insert(
+            "namada_vp_read_chunk_post",
+            Function::new_native_with_env(
+                wasm_store,
+                env.clone(),
+                host_env::vp_read_chunk_post,
+            ),
+        );
     }
+    debug_assert_eq!(
+        exports
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<BTreeSet<_>>(),
+        vp_host_function_names(version).collect(),
+        "The VP host functions must match their names"
+    );
+    exports
 }
@@ -0,0 +1,454 @@
+//! Pool of wasm instances that can be reused across txs and VPs runs.
+//!
+//! Instantiating a wasm module for every tx and VP run is expensive, so the
+//! instances that ran successfully are put back into a pool keyed by the hash
+//! of their code and taken out for the next run of the same code.
+//!
+//! To make sure that no state can leak between runs, a snapshot of the memory
+//! and of the mutable globals of an instance is taken right after it's
+//! instantiated and it's restored before the instance is pooled. The wasm
+//! host environment of a pooled instance is re-bound to the host structures
+//! of the next run, which updates the environment of all the imported host
+//! functions at once.
+//!
+//! The mutable globals of a module are internal unless exported, so only the
+//! modules prepared with [`export_mutable_globals`] can be pooled. Instances
+//! whose memory has grown are not pooled, as the memory can't be shrunk.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use parity_wasm::elements;
+use wasmer::{Extern, Global, Instance, Memory, Mutability, Pages, Val};
+
+use crate::types::hash::Hash;
+
+/// The prefix of the names of the exported mutable globals
+pub const GLOBAL_EXPORT_PREFIX: &str = "__namada_global_";
+/// The export that marks a module whose mutable globals are all exported
+pub const POOLABLE_MARKER_EXPORT: &str = "__namada_poolable";
+/// The maximum number of pooled instances of the same code
+pub const MAX_INSTANCES_PER_CODE: usize = 4;
+/// The maximum number of pooled instances
+pub const MAX_POOLED_INSTANCES: usize = 32;
+
+/// A wasm host environment that can be re-bound to other host structures.
+pub trait RebindEnv: Clone + Send + 'static {
+    /// Copy the environment without sharing the references to the host
+    /// structures with its clones.
+    fn detach(&self) -> Self;
+
+    /// Re-bind the environment and all of its clones to the host structures
+    /// of the other environment.
+    ///
+    /// # Safety
+    ///
+    /// The clones must not be used while they are being re-bound.
+    unsafe fn rebind(&self, other: &Self);
+}
+
+/// Pool of wasm instances keyed by the hash of their code. Thread-safe.
+#[derive(Clone, Default)]
+pub struct InstancePool {
+    inner: Arc<Mutex<PoolInner>>,
+}
+
+#[derive(Default)]
+struct PoolInner {
+    /// The pooled instances by the hash of their code
+    instances: HashMap<Hash, Vec<Box<dyn Any + Send>>>,
+    /// The total number of pooled instances
+    len: usize,
+}
+
+impl std::fmt::Debug for InstancePool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InstancePool")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// A wasm instance with the snapshot of its initial state
+pub struct PooledInstance<ENV> {
+    /// The wasm instance
+    pub instance: Instance,
+    /// The host environment of the imported host functions
+    env: ENV,
+    /// The memory exported from the instance
+    memory: Memory,
+    /// The initial state of the instance
+    snapshot: Snapshot,
+}
+
+/// The initial state of an instance
+struct Snapshot {
+    /// The number of memory pages
+    pages: Pages,
+    /// The memory content, without the trailing zeros
+    memory: Vec<u8>,
+    /// The values of the mutable globals
+    globals: Vec<(Global, Val)>,
+}
+
+impl InstancePool {
+    /// Get the number of pooled instances
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().len
+    }
+
+    /// Check if there are no pooled instances
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Take a pooled instance of the given code, if any, with its host
+    /// environment re-bound to the given one.
+    pub fn take<ENV: RebindEnv>(
+        &self,
+        code_hash: &Hash,
+        env: &ENV,
+    ) -> Option<PooledInstance<ENV>> {
+        let pooled = {
+            let mut inner = self.inner.lock().unwrap();
+            let instances = inner.instances.get_mut(code_hash)?;
+            let pooled = instances.pop()?;
+            if instances.is_empty() {
+                inner.instances.remove(code_hash);
+            }
+            inner.len -= 1;
+            pooled
+        };
+        // An instance with a different environment type can't be reused
+        let pooled = pooled.downcast::<PooledInstance<ENV>>().ok()?;
+        // Safe because the instance is owned and not running
+        unsafe { pooled.env.rebind(env) };
+        Some(*pooled)
+    }
+
+    /// Reset the instance to its initial state and put it into the pool.
+    /// Returns `false` if the instance couldn't be reset or the pool is full.
+    pub fn put<ENV: RebindEnv>(
+        &self,
+        code_hash: Hash,
+        pooled: PooledInstance<ENV>,
+    ) -> bool {
+        if !pooled.reset() {
+            return false;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        if inner.len >= MAX_POOLED_INSTANCES {
+            return false;
+        }
+        let instances = inner.instances.entry(code_hash).or_default();
+        if instances.len() >= MAX_INSTANCES_PER_CODE {
+            return false;
+        }
+        instances.push(Box::new(pooled));
+        inner.len += 1;
+        true
+    }
+}
+
+impl<ENV: RebindEnv> PooledInstance<ENV> {
+    /// Take a snapshot of a new instance. Must be called before the instance
+    /// is used. Returns `None` if the module of the instance wasn't prepared
+    /// with [`export_mutable_globals`].
+    pub fn new(instance: Instance, env: ENV, memory: Memory) -> Option<Self> {
+        instance.exports.get_global(POOLABLE_MARKER_EXPORT).ok()?;
+        let globals = instance
+            .exports
+            .iter()
+            .filter_map(|(_name, export)| match export {
+                Extern::Global(global)
+                    if global.ty().mutability == Mutability::Var =>
+                {
+                    Some((global.clone(), global.get()))
+                }
+                _ => None,
+            })
+            .collect();
+        // Safe because the instance is owned and not running
+        let data = unsafe { memory.data_unchecked() };
+        let end = data
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map(|last| last + 1)
+            .unwrap_or_default();
+        let snapshot = Snapshot {
+            pages: memory.size(),
+            memory: data[..end].to_vec(),
+            globals,
+        };
+        Some(Self {
+            instance,
+            env,
+            memory,
+            snapshot,
+        })
+    }
+
+    /// Restore the initial state of the instance. Returns `false` if it
+    /// couldn't be restored.
+    fn reset(&self) -> bool {
+        if self.memory.size() != self.snapshot.pages {
+            return false;
+        }
+        // Safe because the instance is owned and not running
+        let data = unsafe { self.memory.data_unchecked_mut() };
+        let (init, rest) = data.split_at_mut(self.snapshot.memory.len());
+        init.copy_from_slice(&self.snapshot.memory);
+        rest.fill(0);
+        self.snapshot
+            .globals
+            .iter()
+            .all(|(global, value)| global.set(value.clone()).is_ok())
+    }
+}
+
+/// Export all the mutable globals of a module, which makes it possible to
+/// restore their initial values, and mark the module as poolable. A module
+/// without a globals section or an exports section is left as is.
+pub fn export_mutable_globals(
+    mut module: elements::Module,
+) -> elements::Module {
+    if module.export_section().is_none() {
+        return module;
+    }
+    let imported =
+        module.import_count(elements::ImportCountType::Global) as u32;
+    let (mutable, marker_idx) = match module.global_section_mut() {
+        Some(globals) => {
+            let mutable: Vec<u32> = globals
+                .entries()
+                .iter()
+                .enumerate()
+                .filter(|(_, global)| global.global_type().is_mutable())
+                .map(|(idx, _)| imported + idx as u32)
+                .collect();
+            let marker_idx = imported + globals.entries().len() as u32;
+            globals.entries_mut().push(elements::GlobalEntry::new(
+                elements::GlobalType::new(elements::ValueType::I32, false),
+                elements::InitExpr::new(vec![
+                    elements::Instruction::I32Const(0),
+                    elements::Instruction::End,
+                ]),
+            ));
+            (mutable, marker_idx)
+        }
+        None => return module,
+    };
+    if let Some(exports) = module.export_section_mut() {
+        let entries = exports.entries_mut();
+        for idx in mutable {
+            entries.push(elements::ExportEntry::new(
+                format!("{GLOBAL_EXPORT_PREFIX}{idx}"),
+                elements::Internal::Global(idx),
+            ));
+        }
+        entries.push(elements::ExportEntry::new(
+            POOLABLE_MARKER_EXPORT.to_string(),
+            elements::Internal::Global(marker_idx),
+        ));
+    }
+    module
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use wasmer::{Function, ImportObject, Module};
+
+    use super::*;
+    use crate::vm::wasm::memory;
+    use crate::vm::wasm::run::untrusted_wasm_store;
+
+    /// A host environment that records the calls of the host function
+    #[derive(Clone)]
+    struct TestEnv {
+        calls: Arc<Mutex<Arc<AtomicU64>>>,
+    }
+
+    impl TestEnv {
+        fn new(calls: &Arc<AtomicU64>) -> Self {
+            Self {
+                calls: Arc::new(Mutex::new(calls.clone())),
+            }
+        }
+    }
+
+    impl wasmer::WasmerEnv for TestEnv {}
+
+    impl RebindEnv for TestEnv {
+        fn detach(&self) -> Self {
+            Self {
+                calls: Arc::new(Mutex::new(self.calls.lock().unwrap().clone())),
+            }
+        }
+
+        unsafe fn rebind(&self, other: &Self) {
+            *self.calls.lock().unwrap() = other.calls.lock().unwrap().clone();
+        }
+    }
+
+    fn host_call(env: &TestEnv) {
+        env.calls.lock().unwrap().fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// A module that increments a global counter and writes it to memory,
+    /// with some initial data in memory
+    const COUNTER_WAT: &str = r#"
+        (module
+            (import "env" "host_call" (func $host_call))
+            (global $counter (mut i32) (i32.const 0))
+            (func (export "run") (result i32)
+                (call $host_call)
+                (global.set $counter (i32.add (global.get $counter) (i32.const 1)))
+                (i32.store (i32.const 1024) (global.get $counter))
+                (i32.store8 (i32.const 16) (i32.const 0))
+                (global.get $counter))
+            (func (export "grow") (result i32)
+                (memory.grow (i32.const 1)))
+            (memory 1 4)
+            (data (i32.const 16) "init")
+            (export "memory" (memory 0))
+        )
+    "#;
+
+    fn compile(wat: &str, poolable: bool) -> (Module, wasmer::Store) {
+        let code = wasmer::wat2wasm(wat.as_bytes()).unwrap().into_owned();
+        let code = if poolable {
+            let module: elements::Module =
+                elements::deserialize_buffer(&code).unwrap();
+            elements::serialize(export_mutable_globals(module)).unwrap()
+        } else {
+            code
+        };
+        let store = untrusted_wasm_store(memory::vp_limit());
+        let module = Module::new(&store, code).unwrap();
+        (module, store)
+    }
+
+    fn instantiate(
+        module: &Module,
+        store: &wasmer::Store,
+        env: &TestEnv,
+    ) -> Option<PooledInstance<TestEnv>> {
+        let imports: ImportObject = wasmer::imports! {
+            "env" => {
+                "host_call" => Function::new_native_with_env(store, env.clone(), host_call),
+            },
+        };
+        let instance = Instance::new(module, &imports).unwrap();
+        let memory = instance.exports.get_memory("memory").unwrap().clone();
+        PooledInstance::new(instance, env.clone(), memory)
+    }
+
+    fn run(pooled: &PooledInstance<TestEnv>) -> i32 {
+        pooled
+            .instance
+            .exports
+            .get_native_function::<(), i32>("run")
+            .unwrap()
+            .call()
+            .unwrap()
+    }
+
+    /// Test that a pooled instance is reset to its initial state, so that no
+    /// state can leak from one run to the next.
+    #[test]
+    fn test_reset_restores_initial_state() {
+        let (module, store) = compile(COUNTER_WAT, true);
+        let calls = Arc::new(AtomicU64::new(0));
+        let env = TestEnv::new(&calls);
+        let pool = InstancePool::default();
+        let code_hash = Hash::sha256(COUNTER_WAT);
+
+        let pooled = instantiate(&module, &store, &env.detach()).unwrap();
+        assert_eq!(run(&pooled), 1);
+        assert_eq!(run(&pooled), 2);
+        let memory = pooled.memory.clone();
+        assert!(pool.put(code_hash, pooled));
+        assert_eq!(pool.len(), 1);
+
+        // The memory and the globals are restored
+        let data = unsafe { memory.data_unchecked() };
+        assert_eq!(&data[16..20], b"init");
+        assert_eq!(&data[1024..1028], &[0; 4]);
+
+        let pooled = pool.take(&code_hash, &env).unwrap();
+        assert!(pool.is_empty());
+        assert_eq!(run(&pooled), 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        // Other code can't take the instance
+        assert!(pool.put(code_hash, pooled));
+        assert!(pool.take(&Hash::default(), &env).is_none());
+    }
+
+    /// Test that the instances of a module that wasn't prepared can't be
+    /// pooled.
+    #[test]
+    fn test_unprepared_module_is_not_poolable() {
+        let (module, store) = compile(COUNTER_WAT, false);
+        let env = TestEnv::new(&Arc::new(AtomicU64::new(0)));
+        assert!(instantiate(&module, &store, &env).is_none());
+    }
+
+    /// Test that an instance whose memory has grown is not pooled.
+    #[test]
+    fn test_grown_memory_is_not_pooled() {
+        let (module, store) = compile(COUNTER_WAT, true);
+        let env = TestEnv::new(&Arc::new(AtomicU64::new(0)));
+        let pool = InstancePool::default();
+
+        let pooled = instantiate(&module, &store, &env).unwrap();
+        let grow = pooled
+            .instance
+            .exports
+            .get_native_function::<(), i32>("grow")
+            .unwrap();
+        assert_eq!(grow.call().unwrap(), 1);
+        assert!(!pool.put(Hash::default(), pooled));
+        assert!(pool.is_empty());
+    }
+
+    /// Test that the host functions of a pooled instance use the host
+    /// environment of the run that takes it.
+    #[test]
+    fn test_take_rebinds_env() {
+        let (module, store) = compile(COUNTER_WAT, true);
+        let first_calls = Arc::new(AtomicU64::new(0));
+        let second_calls = Arc::new(AtomicU64::new(0));
+        let pool = InstancePool::default();
+
+        let first_env = TestEnv::new(&first_calls);
+        let pooled = instantiate(&module, &store, &first_env.detach()).unwrap();
+        run(&pooled);
+        assert!(pool.put(Hash::default(), pooled));
+
+        let second_env = TestEnv::new(&second_calls);
+        let pooled = pool.take(&Hash::default(), &second_env).unwrap();
+        run(&pooled);
+        assert_eq!(first_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(second_calls.load(Ordering::SeqCst), 1);
+    }
+
+    /// Test that the number of pooled instances is limited.
+    #[test]
+    fn test_pool_limits() {
+        let (module, store) = compile(COUNTER_WAT, true);
+        let env = TestEnv::new(&Arc::new(AtomicU64::new(0)));
+        let pool = InstancePool::default();
+
+        for _ in 0..MAX_INSTANCES_PER_CODE {
+            let pooled = instantiate(&module, &store, &env.detach()).unwrap();
+            assert!(pool.put(Hash::default(), pooled));
+        }
+        let pooled = instantiate(&module, &store, &env.detach()).unwrap();
+        assert!(!pool.put(Hash::default(), pooled));
+        assert_eq!(pool.len(), MAX_INSTANCES_PER_CODE);
+    }
+}
@@ -2,8 +2,10 @@
 
 pub mod compilation_cache;
 pub mod host_env;
+pub mod instance_pool;
 pub mod memory;
 pub mod run;
+pub mod validation;
 
 pub use compilation_cache::common::{Cache, CacheName};
 pub use compilation_cache::tx::TxCache;
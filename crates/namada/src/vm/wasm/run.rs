@@ -25,10 +25,16 @@ use crate::types::storage::{Key, TxIndex};
 use crate::vm::host_env::{TxVmEnv, VpCtx, VpEvaluator, VpVmEnv};
 use crate::vm::prefix_iter::PrefixIterators;
 use crate::vm::types::VpInput;
-use crate::vm::wasm::host_env::{tx_imports, vp_imports};
+use crate::vm::wasm::host_env::{
+    tx_imports, vp_imports, LEGACY_NAMESPACE, SUPPORTED_API_VERSIONS,
+    TX_NAMESPACE_PREFIX, VP_NAMESPACE_PREFIX,
+};
+use crate::vm::wasm::instance_pool::{
+    export_mutable_globals, InstancePool, PooledInstance, RebindEnv,
+};
 use crate::vm::wasm::{memory, Cache, CacheName, VpCache};
 use crate::vm::{
-    validate_untrusted_wasm, WasmCacheAccess, WasmValidationError,
+    deadline, validate_untrusted_wasm, WasmCacheAccess, WasmValidationError,
 };
 
 const TX_ENTRYPOINT: &str = "_apply_tx";
@@ -83,6 +89,15 @@ pub enum Error {
     ConversionError(String),
     #[error("Invalid transaction signature")]
     InvalidTxSignature,
+    #[error(
+        "The wasm module imports from the unsupported host functions \
+         namespace \"{namespace}\". Expected \"{LEGACY_NAMESPACE}\" or \
+         \"{prefix}<version>\" with a version in {SUPPORTED_API_VERSIONS:?}"
+    )]
+    UnsupportedHostApi {
+        namespace: String,
+        prefix: &'static str,
+    },
 }
 
 /// Result for functions that may fail
@@ -105,6 +120,13 @@ where
     H: 'static + StorageHasher,
     CA: 'static + WasmCacheAccess,
 {
+    let _span = tracing::info_span!(
+        "tx",
+        height = storage.block.height.0,
+        index = tx_index.0,
+        hash = %tx.header_hash(),
+    )
+    .entered();
     let tx_code = tx
         .get_section(tx.code_sechash())
         .and_then(|x| Section::code_sec(x.as_ref()))
@@ -152,6 +174,9 @@ where
         storage,
         gas_meter,
     )?;
+    let code_hash = tx_code.code.hash();
+    let api_versions = requested_api_versions(&module, TX_NAMESPACE_PREFIX)?;
+    let instance_pool = tx_wasm_cache.instance_pool().clone();
 
     let mut iterators: PrefixIterators<'_, DB> = PrefixIterators::default();
     let mut verifiers = BTreeSet::new();
@@ -173,13 +198,17 @@ where
         tx_wasm_cache,
     );
 
-    let initial_memory =
-        memory::prepare_tx_memory(&store).map_err(Error::MemoryError)?;
-    let imports = tx_imports(&store, initial_memory, env);
-
-    // Instantiate the wasm module
-    let instance = wasmer::Instance::new(&module, &imports)
-        .map_err(|e| Error::InstantiationError(Box::new(e)))?;
+    let (instance, pooled) = take_or_instantiate(
+        &instance_pool,
+        &code_hash,
+        &module,
+        &env,
+        |env| {
+            let initial_memory = memory::prepare_tx_memory(&store)
+                .map_err(Error::MemoryError)?;
+            Ok(tx_imports(&store, initial_memory, env, &api_versions))
+        },
+    )?;
 
     // We need to write the inputs in the memory exported from the wasm
     // module
@@ -201,6 +230,7 @@ where
             entrypoint: TX_ENTRYPOINT,
             error,
         })?;
+    let _deadline = deadline::arm();
     apply_tx.call(tx_data_ptr, tx_data_len).map_err(|err| {
         tracing::debug!("Tx WASM failed with {}", err);
         match sentinel {
@@ -212,6 +242,9 @@ where
         }
     })?;
 
+    if let Some(pooled) = pooled {
+        instance_pool.put(code_hash, pooled);
+    }
     Ok(verifiers)
 }
 
@@ -236,6 +269,13 @@ where
     H: 'static + StorageHasher,
     CA: 'static + WasmCacheAccess,
 {
+    let _span = tracing::info_span!(
+        "vp",
+        height = storage.block.height.0,
+        tx_hash = %tx.header_hash(),
+        %address,
+    )
+    .entered();
     // Compile the wasm module
     let (module, store) = fetch_or_compile(
         &mut vp_wasm_cache,
@@ -244,6 +284,8 @@ where
         storage,
         gas_meter,
     )?;
+    let api_versions = requested_api_versions(&module, VP_NAMESPACE_PREFIX)?;
+    let instance_pool = vp_wasm_cache.instance_pool().clone();
 
     let mut iterators: PrefixIterators<'_, DB> = PrefixIterators::default();
     let mut result_buffer: Option<Vec<u8>> = None;
@@ -271,20 +313,31 @@ where
         &mut vp_wasm_cache,
     );
 
-    let initial_memory =
-        memory::prepare_vp_memory(&store).map_err(Error::MemoryError)?;
-    let imports = vp_imports(&store, initial_memory, env);
+    let (instance, pooled) = take_or_instantiate(
+        &instance_pool,
+        &vp_code_hash,
+        &module,
+        &env,
+        |env| {
+            let initial_memory = memory::prepare_vp_memory(&store)
+                .map_err(Error::MemoryError)?;
+            Ok(vp_imports(&store, initial_memory, env, &api_versions))
+        },
+    )?;
 
-    match run_vp(
-        module,
-        imports,
+    let result = run_vp(
+        &instance,
         &vp_code_hash,
         tx,
         address,
         keys_changed,
         verifiers,
         gas_meter,
-    ) {
+    );
+    if let (Ok(_), Some(pooled)) = (&result, pooled) {
+        instance_pool.put(vp_code_hash, pooled);
+    }
+    match result {
         Ok(accept) => {
             if sentinel.is_invalid_signature() {
                 if accept {
@@ -316,8 +369,7 @@ where
 
 #[allow(clippy::too_many_arguments)]
 fn run_vp(
-    module: wasmer::Module,
-    vp_imports: wasmer::ImportObject,
+    instance: &wasmer::Instance,
     _vp_code_hash: &Hash,
     input_data: &Tx,
     address: &Address,
@@ -332,10 +384,6 @@ fn run_vp(
         verifiers,
     };
 
-    // Instantiate the wasm module
-    let instance = wasmer::Instance::new(&module, &vp_imports)
-        .map_err(|e| Error::InstantiationError(Box::new(e)))?;
-
     // We need to write the inputs in the memory exported from the wasm
     // module
     let memory = instance
@@ -363,6 +411,7 @@ fn run_vp(
             entrypoint: VP_ENTRYPOINT,
             error,
         })?;
+    let _deadline = deadline::arm();
     let is_valid = validate_tx
         .call(
             addr_ptr,
@@ -455,23 +504,99 @@ where
             storage,
             gas_meter,
         )?;
+        let api_versions =
+            requested_api_versions(&module, VP_NAMESPACE_PREFIX)?;
+        let instance_pool = vp_wasm_cache.instance_pool().clone();
+
+        // The context is shared with the instance of the parent VP, which
+        // is not affected by re-binding the instance of this VP
+        let (instance, pooled) = take_or_instantiate(
+            &instance_pool,
+            &vp_code_hash,
+            &module,
+            &env,
+            |env| {
+                let initial_memory = memory::prepare_vp_memory(&store)
+                    .map_err(Error::MemoryError)?;
+                Ok(vp_imports(&store, initial_memory, env, &api_versions))
+            },
+        )?;
 
-        let initial_memory =
-            memory::prepare_vp_memory(&store).map_err(Error::MemoryError)?;
-
-        let imports = vp_imports(&store, initial_memory, env);
-
-        run_vp(
-            module,
-            imports,
+        let result = run_vp(
+            &instance,
             &vp_code_hash,
             &input_data,
             address,
             keys_changed,
             verifiers,
             gas_meter,
-        )
+        );
+        if let (Ok(_), Some(pooled)) = (&result, pooled) {
+            instance_pool.put(vp_code_hash, pooled);
+        }
+        result
+    }
+}
+
+/// Take a pooled instance of the given code with its host environment
+/// re-bound to the given one, or instantiate the module with the imports
+/// built from a detached copy of the environment. Returns the instance and,
+/// if it can be pooled, the instance to put back into the pool after a
+/// successful run.
+fn take_or_instantiate<ENV: RebindEnv>(
+    instance_pool: &InstancePool,
+    code_hash: &Hash,
+    module: &Module,
+    env: &ENV,
+    imports: impl FnOnce(ENV) -> Result<wasmer::ImportObject>,
+) -> Result<(wasmer::Instance, Option<PooledInstance<ENV>>)> {
+    if let Some(pooled) = instance_pool.take(code_hash, env) {
+        return Ok((pooled.instance.clone(), Some(pooled)));
+    }
+    // The environment of a new instance must not be shared with any other
+    // instance, so that it can be re-bound on its own
+    let env = env.detach();
+    let imports = imports(env.clone())?;
+    let instance = wasmer::Instance::new(module, &imports)
+        .map_err(|e| Error::InstantiationError(Box::new(e)))?;
+    let memory = instance
+        .exports
+        .get_memory("memory")
+        .map_err(Error::MissingModuleMemory)?
+        .clone();
+    let pooled = PooledInstance::new(instance.clone(), env, memory);
+    Ok((instance, pooled))
+}
+
+/// Find the versions of the host functions API requested by the imports of
+/// the given module, from the namespaces with the given prefix. Fails if the
+/// module imports from any other namespace than the legacy one or if it
+/// requests an unsupported version.
+fn requested_api_versions(
+    module: &Module,
+    namespace_prefix: &'static str,
+) -> Result<BTreeSet<u32>> {
+    let mut versions = BTreeSet::new();
+    for import in module.imports() {
+        let namespace = import.module();
+        if namespace == LEGACY_NAMESPACE {
+            continue;
+        }
+        let version = namespace
+            .strip_prefix(namespace_prefix)
+            .and_then(|version| version.parse::<u32>().ok())
+            .filter(|version| {
+                // Reject non-canonical forms, e.g. with a sign or leading zeros
+                format!("{namespace_prefix}{version}") == namespace
+                    && SUPPORTED_API_VERSIONS.contains(version)
+            })
+            .ok_or_else(|| Error::UnsupportedHostApi {
+                namespace: namespace.to_string(),
+                prefix: namespace_prefix,
+            })?;
+        versions.insert(version);
     }
+    Ok(versions)
 }
 
 /// Prepare a wasm store for untrusted code.
@@ -484,7 +609,8 @@ pub fn untrusted_wasm_store(limit: Limit<BaseTunables>) -> wasmer::Store {
     )
 }
 
-/// Inject gas counter and stack-height limiter into the given wasm code
+/// Inject gas counter and stack-height limiter into the given wasm code and
+/// export its mutable globals to make its instances poolable
 pub fn prepare_wasm_code<T: AsRef<[u8]>>(code: T) -> Result<Vec<u8>> {
     let module: elements::Module = elements::deserialize_buffer(code.as_ref())
         .map_err(Error::DeserializationError)?;
@@ -499,6 +625,7 @@ pub fn prepare_wasm_code<T: AsRef<[u8]>>(code: T) -> Result<Vec<u8>> {
     let module =
         wasm_instrument::inject_stack_limiter(module, WASM_STACK_LIMIT)
             .map_err(|_original_module| Error::StackLimiterInjection)?;
+    let module = export_mutable_globals(module);
     elements::serialize(module).map_err(Error::SerializationError)
 }
 
@@ -685,6 +812,85 @@ mod tests {
         );
     }
 
+    /// Test that a tx can import the host functions from a versioned
+    /// namespace and that unsupported namespaces are rejected.
+    #[test]
+    fn test_tx_host_api_versions() {
+        let tx_code = |namespace: &str| {
+            wasmer::wat2wasm(
+                format!(
+                    r#"
+                    (module
+                        (import "{namespace}" "namada_tx_read" (func (param i64 i64) (result i64)))
+                        (func (param i64 i64))
+                        (memory 16)
+                        (export "memory" (memory 0))
+                        (export "_apply_tx" (func 1))
+                    )
+                    "#
+                )
+                .as_bytes(),
+            )
+            .expect("unexpected error converting wat2wasm")
+            .into_owned()
+        };
+
+        for namespace in ["env", "namada_tx_v1", "namada_tx_v2"] {
+            execute_tx_with_code(tx_code(namespace)).unwrap_or_else(|err| {
+                panic!("Importing from {namespace} should work: {err}")
+            });
+        }
+        for namespace in
+            ["namada_tx_v3", "namada_tx_v01", "namada_vp_v1", "wasi"]
+        {
+            let error = execute_tx_with_code(tx_code(namespace))
+                .expect_err("Unsupported namespaces must be rejected");
+            assert!(
+                matches!(
+                    &error,
+                    Error::UnsupportedHostApi { namespace: ns, .. }
+                        if ns == namespace
+                ),
+                "Unexpected error {error}"
+            );
+        }
+    }
+
+    /// Test that the host functions added in the API version 2 are only
+    /// linked in its namespace.
+    #[test]
+    fn test_tx_host_api_v2_functions() {
+        let tx_code = |namespace: &str| {
+            wasmer::wat2wasm(
+                format!(
+                    r#"
+                    (module
+                        (import "{namespace}" "namada_tx_read_chunk" (func (param i64 i64 i64 i64) (result i64)))
+                        (func (param i64 i64))
+                        (memory 16)
+                        (export "memory" (memory 0))
+                        (export "_apply_tx" (func 1))
+                    )
+                    "#
+                )
+                .as_bytes(),
+            )
+            .expect("unexpected error converting wat2wasm")
+            .into_owned()
+        };
+
+        execute_tx_with_code(tx_code("namada_tx_v2"))
+            .expect("Importing from the API version 2 should work");
+        for namespace in ["env", "namada_tx_v1"] {
+            let error = execute_tx_with_code(tx_code(namespace))
+                .expect_err("The function must not be linked in version 1");
+            assert!(
+                matches!(&error, Error::InstantiationError(_)),
+                "Unexpected error {error}"
+            );
+        }
+    }
+
     /// Extract a wasm runtime memory error from some [`Error`].
     fn assert_rt_mem_error<'err>(
         error: &'err Error,
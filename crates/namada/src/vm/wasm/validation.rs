@@ -0,0 +1,336 @@
+//! Checks of the untrusted wasm code submitted in txs, used to reject invalid
+//! code before it's included in a block.
+
+use thiserror::Error;
+use wasmparser::{MemoryType, Operator, Parser, Payload, TypeRef};
+
+use crate::vm::wasm::host_env::{
+    tx_host_function_names, vp_host_function_names, LEGACY_NAMESPACE,
+    LEGACY_NAMESPACE_EXTRA_IMPORTS, SUPPORTED_API_VERSIONS,
+    TX_NAMESPACE_PREFIX, VP_NAMESPACE_PREFIX,
+};
+use crate::vm::wasm::memory::{TX_MEMORY_MAX_PAGES, VP_MEMORY_MAX_PAGES};
+use crate::vm::{validate_untrusted_wasm_without_floats, WasmValidationError};
+
+/// The magic bytes at the start of any wasm module
+const WASM_MAGIC: &[u8] = b"\0asm";
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("{0}")]
+    Validation(WasmValidationError),
+    #[error("Failed to parse the wasm code: {0}")]
+    Parse(wasmparser::BinaryReaderError),
+    #[error(
+        "The wasm code imports \"{namespace}\".\"{name}\", which is not \
+         provided by the {kind} host functions API"
+    )]
+    UnknownImport {
+        kind: WasmCodeKind,
+        namespace: String,
+        name: String,
+    },
+    #[error(
+        "The wasm memory limits (initial {initial} pages, maximum {maximum:?} \
+         pages) exceed the allowed {max_pages} pages"
+    )]
+    MemoryLimits {
+        initial: u64,
+        maximum: Option<u64>,
+        max_pages: u32,
+    },
+    #[error(
+        "The wasm code uses the denied operator {operator} at offset {offset}"
+    )]
+    DeniedOperator { operator: String, offset: usize },
+}
+
+/// Result for functions that may fail
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The kind of a wasm code, which determines the host functions that it can
+/// import and its memory limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WasmCodeKind {
+    /// Transaction code
+    Tx,
+    /// Validity predicate code
+    Vp,
+}
+
+impl std::fmt::Display for WasmCodeKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WasmCodeKind::Tx => write!(f, "tx"),
+            WasmCodeKind::Vp => write!(f, "VP"),
+        }
+    }
+}
+
+/// Check if the given bytes look like a wasm module
+pub fn is_wasm(bytes: &[u8]) -> bool {
+    bytes.starts_with(WASM_MAGIC)
+}
+
+/// Check an untrusted wasm code submitted in a tx. On top of the validation
+/// done before running it, the code must only import the host functions of a
+/// supported API version, must not use floating-point types or operations,
+/// must declare memories within the VM's limits and must not use any of the
+/// `denied_operators`, named as the variants of [`Operator`] (e.g.
+/// `MemoryGrow`).
+pub fn check_submitted_wasm(
+    code: &[u8],
+    kind: WasmCodeKind,
+    denied_operators: &[String],
+) -> Result<()> {
+    validate_untrusted_wasm_without_floats(code).map_err(Error::Validation)?;
+
+    for payload in Parser::new(0).parse_all(code) {
+        match payload.map_err(Error::Parse)? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    let import = import.map_err(Error::Parse)?;
+                    check_import(kind, import.module, import.name)?;
+                    if let TypeRef::Memory(memory) = import.ty {
+                        check_memory(kind, &memory)?;
+                    }
+                }
+            }
+            Payload::MemorySection(reader) => {
+                for memory in reader {
+                    check_memory(kind, &memory.map_err(Error::Parse)?)?;
+                }
+            }
+            Payload::CodeSectionEntry(body) if !denied_operators.is_empty() => {
+                let mut reader =
+                    body.get_operators_reader().map_err(Error::Parse)?;
+                while !reader.eof() {
+                    let (operator, offset) =
+                        reader.read_with_offset().map_err(Error::Parse)?;
+                    let name = operator_name(&operator);
+                    if denied_operators.iter().any(|denied| denied == &name) {
+                        return Err(Error::DeniedOperator {
+                            operator: name,
+                            offset,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Check that an import is provided by the host functions API of the given
+/// kind of code
+fn check_import(kind: WasmCodeKind, namespace: &str, name: &str) -> Result<()> {
+    let namespace_prefix = match kind {
+        WasmCodeKind::Tx => TX_NAMESPACE_PREFIX,
+        WasmCodeKind::Vp => VP_NAMESPACE_PREFIX,
+    };
+    let host_function_names = |version| -> Vec<&str> {
+        match kind {
+            WasmCodeKind::Tx => tx_host_function_names(version).collect(),
+            WasmCodeKind::Vp => vp_host_function_names(version).collect(),
+        }
+    };
+    let is_provided = if namespace == LEGACY_NAMESPACE {
+        LEGACY_NAMESPACE_EXTRA_IMPORTS.contains(&name)
+            || host_function_names(1).contains(&name)
+    } else {
+        namespace
+            .strip_prefix(namespace_prefix)
+            .and_then(|version| version.parse::<u32>().ok())
+            .filter(|version| {
+                // Reject non-canonical forms, e.g. with a sign or leading zeros
+                format!("{namespace_prefix}{version}") == namespace
+                    && SUPPORTED_API_VERSIONS.contains(version)
+            })
+            .map(|version| host_function_names(version).contains(&name))
+            .unwrap_or_default()
+    };
+    if is_provided {
+        Ok(())
+    } else {
+        Err(Error::UnknownImport {
+            kind,
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        })
+    }
+}
+
+/// Check that a memory declared by the code is within the VM's limits
+fn check_memory(kind: WasmCodeKind, memory: &MemoryType) -> Result<()> {
+    let max_pages = match kind {
+        WasmCodeKind::Tx => TX_MEMORY_MAX_PAGES,
+        WasmCodeKind::Vp => VP_MEMORY_MAX_PAGES,
+    };
+    let within_limits = |pages: u64| pages <= u64::from(max_pages);
+    if within_limits(memory.initial)
+        && memory.maximum.map(within_limits).unwrap_or(true)
+    {
+        Ok(())
+    } else {
+        Err(Error::MemoryLimits {
+            initial: memory.initial,
+            maximum: memory.maximum,
+            max_pages,
+        })
+    }
+}
+
+/// The name of the variant of an operator, e.g. `MemoryGrow`
+fn operator_name(operator: &Operator<'_>) -> String {
+    let debug = format!("{operator:?}");
+    debug
+        .split(|c: char| !c.is_alphanumeric())
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Make a tx code with the given imports and `_apply_tx` function body
+    fn tx_code(imports: &str, body: &str) -> Vec<u8> {
+        let wat = format!(
+            r#"
+            (module
+                {imports}
+                (func $apply_tx (param i64 i64) (result i64)
+                    {body}
+                    i64.const 0
+                )
+                (export "_apply_tx" (func $apply_tx))
+            )
+            "#
+        );
+        wasmer::wat2wasm(wat.as_bytes())
+            .expect("unexpected error converting wat2wasm")
+            .into_owned()
+    }
+
+    /// Test that a tx code that only imports the provided host functions and
+    /// memory passes the checks
+    #[test]
+    fn test_check_valid_tx_code() {
+        let code = tx_code(
+            r#"
+            (import "env" "memory" (memory 16 200))
+            (import "env" "gas" (func (param i32)))
+            (import "env" "namada_tx_read" (func (param i64 i64) (result i64)))
+            (import "namada_tx_v2" "namada_tx_read_chunk"
+                (func (param i64 i64 i64 i64) (result i64)))
+            "#,
+            "",
+        );
+        assert!(is_wasm(&code));
+        check_submitted_wasm(&code, WasmCodeKind::Tx, &[]).unwrap();
+        assert!(!is_wasm(b"not a wasm"));
+    }
+
+    /// Test that imports not provided by the host functions API are rejected
+    #[test]
+    fn test_check_unknown_imports() {
+        for (namespace, name) in [
+            // Unknown function
+            ("env", "namada_tx_unknown"),
+            // VP function in a tx
+            ("env", "namada_vp_read_pre"),
+            // Function not available in the API version 1
+            ("env", "namada_tx_read_chunk"),
+            ("namada_tx_v1", "namada_tx_read_chunk"),
+            // Unsupported and non-canonical API versions
+            ("namada_tx_v3", "namada_tx_read"),
+            ("namada_tx_v02", "namada_tx_read"),
+            // Unknown namespace
+            ("wasi_snapshot_preview1", "fd_write"),
+        ] {
+            let code = tx_code(
+                &format!(r#"(import "{namespace}" "{name}" (func))"#),
+                "",
+            );
+            let err =
+                check_submitted_wasm(&code, WasmCodeKind::Tx, &[]).unwrap_err();
+            assert!(
+                matches!(err, Error::UnknownImport { .. }),
+                "Unexpected error for {namespace}.{name}: {err}"
+            );
+        }
+    }
+
+    /// Test that memories exceeding the VM's limits are rejected
+    #[test]
+    fn test_check_memory_limits() {
+        for memory in [
+            r#"(import "env" "memory" (memory 201))"#,
+            r#"(import "env" "memory" (memory 1 201))"#,
+            "(memory 1 1000)",
+        ] {
+            let code = tx_code(memory, "");
+            let err =
+                check_submitted_wasm(&code, WasmCodeKind::Tx, &[]).unwrap_err();
+            assert!(
+                matches!(err, Error::MemoryLimits { .. }),
+                "Unexpected error for {memory}: {err}"
+            );
+        }
+    }
+
+    /// Test that floating-point operations are rejected
+    #[test]
+    fn test_check_floats() {
+        let code = tx_code(
+            "",
+            "f64.const 1.5
+            f64.const 2.5
+            f64.add
+            drop",
+        );
+        let err =
+            check_submitted_wasm(&code, WasmCodeKind::Tx, &[]).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                Error::Validation(WasmValidationError::FloatingPoint(_))
+            ),
+            "Unexpected error: {err}"
+        );
+    }
+
+    /// Test that the configured denied operators are rejected
+    #[test]
+    fn test_check_denied_operators() {
+        let code = tx_code(
+            "(memory 1 2)",
+            "i32.const 1
+            memory.grow
+            drop",
+        );
+        check_submitted_wasm(&code, WasmCodeKind::Tx, &[]).unwrap();
+        check_submitted_wasm(
+            &code,
+            WasmCodeKind::Tx,
+            &["MemorySize".to_string()],
+        )
+        .unwrap();
+        let err = check_submitted_wasm(
+            &code,
+            WasmCodeKind::Tx,
+            &["MemoryGrow".to_string()],
+        )
+        .unwrap_err();
+        assert!(
+            matches!(
+                &err,
+                Error::DeniedOperator { operator, .. } if operator == "MemoryGrow"
+            ),
+            "Unexpected error: {err}"
+        );
+    }
+}
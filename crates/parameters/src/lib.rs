@@ -3,16 +3,17 @@ pub mod storage;
 
 use std::collections::BTreeMap;
 
+use borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::types::address::{Address, InternalAddress};
 use namada_core::types::chain::ProposalBytes;
 use namada_core::types::dec::Dec;
 use namada_core::types::hash::Hash;
 pub use namada_core::types::parameters::*;
-use namada_core::types::storage::Key;
-use namada_core::types::time::DurationSecs;
+use namada_core::types::storage::{Epoch, Key};
+use namada_core::types::time::{DateTimeUtc, DurationNanos, DurationSecs};
 use namada_core::types::token;
 use namada_storage::{self, ResultExt, StorageRead, StorageWrite};
-pub use storage::get_max_block_gas;
+pub use storage::{get_max_block_gas, get_max_scheduled_txs_gas};
 use thiserror::Error;
 
 /// The internal address for storage keys representing parameters than
@@ -49,10 +50,14 @@ where
 {
     let Parameters {
         max_tx_bytes,
+        max_tx_memo_bytes,
+        max_tx_sections,
+        max_tx_code_bytes,
         epoch_duration,
         max_expected_time_per_block,
         max_proposal_bytes,
         max_block_gas,
+        max_scheduled_txs_gas,
         vp_whitelist,
         tx_whitelist,
         implicit_vp_code_hash,
@@ -69,6 +74,18 @@ where
     let max_tx_bytes_key = storage::get_max_tx_bytes_key();
     storage.write(&max_tx_bytes_key, max_tx_bytes)?;
 
+    // write max tx memo bytes parameter
+    let max_tx_memo_bytes_key = storage::get_max_tx_memo_bytes_key();
+    storage.write(&max_tx_memo_bytes_key, max_tx_memo_bytes)?;
+
+    // write max tx sections parameter
+    let max_tx_sections_key = storage::get_max_tx_sections_key();
+    storage.write(&max_tx_sections_key, max_tx_sections)?;
+
+    // write max tx code bytes parameter
+    let max_tx_code_bytes_key = storage::get_max_tx_code_bytes_key();
+    storage.write(&max_tx_code_bytes_key, max_tx_code_bytes)?;
+
     // write max proposal bytes parameter
     let max_proposal_bytes_key = storage::get_max_proposal_bytes_key();
     storage.write(&max_proposal_bytes_key, max_proposal_bytes)?;
@@ -77,6 +94,10 @@ where
     let max_block_gas_key = storage::get_max_block_gas_key();
     storage.write(&max_block_gas_key, max_block_gas)?;
 
+    // write max scheduled txs gas parameter
+    let max_scheduled_txs_gas_key = storage::get_max_scheduled_txs_gas_key();
+    storage.write(&max_scheduled_txs_gas_key, max_scheduled_txs_gas)?;
+
     // write epoch parameters
     let epoch_key = storage::get_epoch_duration_storage_key();
     storage.write(&epoch_key, epoch_duration)?;
@@ -157,7 +178,7 @@ where
     S: StorageRead,
 {
     let key = storage::get_max_signatures_per_transaction_key();
-    storage.read(&key)
+    storage.read_cached(&key)
 }
 
 /// Update the max_expected_time_per_block parameter in storage. Returns the
@@ -291,6 +312,191 @@ where
     storage.write(&key, value)
 }
 
+/// A protocol parameter write scheduled to be applied at the start of an
+/// epoch.
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ScheduledChange {
+    /// The storage key of the parameter
+    pub key: Key,
+    /// The encoded value of the parameter
+    pub value: Vec<u8>,
+}
+
+/// Schedule a parameter write to be applied at the start of the given epoch.
+/// Changes scheduled for the same epoch are applied in the order in which
+/// they were scheduled.
+pub fn schedule_change<S>(
+    storage: &mut S,
+    activation_epoch: Epoch,
+    key: Key,
+    value: Vec<u8>,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let pending_key = storage::get_pending_changes_key(activation_epoch);
+    let mut changes: Vec<ScheduledChange> =
+        storage.read(&pending_key)?.unwrap_or_default();
+    changes.push(ScheduledChange { key, value });
+    storage.write(&pending_key, changes)
+}
+
+/// Read the parameter changes scheduled to be applied at the start of the
+/// given epoch.
+pub fn read_scheduled_changes<S>(
+    storage: &S,
+    epoch: Epoch,
+) -> namada_storage::Result<Vec<ScheduledChange>>
+where
+    S: StorageRead,
+{
+    let pending_key = storage::get_pending_changes_key(epoch);
+    Ok(storage.read(&pending_key)?.unwrap_or_default())
+}
+
+/// Apply the parameter changes scheduled for the current or any earlier
+/// epoch and remove them from the queue. Must be called on a new epoch.
+/// Returns the keys of the parameters that were written.
+pub fn apply_scheduled_changes<S>(
+    storage: &mut S,
+    current_epoch: Epoch,
+) -> namada_storage::Result<Vec<Key>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let prefix = storage::get_pending_changes_prefix();
+    let mut due = Vec::new();
+    for entry in
+        namada_storage::iter_prefix::<Vec<ScheduledChange>>(storage, &prefix)?
+    {
+        let (pending_key, changes) = entry?;
+        match storage::is_pending_changes_key(&pending_key) {
+            Some(epoch) if epoch <= current_epoch => {
+                due.push((pending_key, changes))
+            }
+            // The keys are ordered by epoch
+            Some(_) => break,
+            None => {}
+        }
+    }
+
+    let mut applied = Vec::new();
+    for (pending_key, changes) in due {
+        for ScheduledChange { key, value } in changes {
+            storage.write_bytes(&key, value)?;
+            applied.push(key);
+        }
+        storage.delete(&pending_key)?;
+    }
+    Ok(applied)
+}
+
+/// The number of the last blocks whose times are tracked for the
+/// [`BlockTimeStats`]
+pub const BLOCK_TIMES_WINDOW: usize = 11;
+
+/// Statistics of the times of the last blocks
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct BlockTimeStats {
+    /// The number of the last blocks whose times are tracked
+    pub num_blocks: u64,
+    /// The moving average of the time between the last blocks
+    pub average_block_time: DurationNanos,
+    /// The median of the time between the last blocks
+    pub median_block_time: DurationNanos,
+    /// The median of the times of the last blocks
+    pub median_time: DateTimeUtc,
+}
+
+impl BlockTimeStats {
+    /// Compute the statistics from the times of the last blocks, ordered by
+    /// their heights. Returns `None` if there are no times. The median of an
+    /// even number of values is the greater of the middle two.
+    pub fn new(times: &[DateTimeUtc]) -> Option<Self> {
+        let (first, last) = (times.first()?, times.last()?);
+        let mut sorted_times = times.to_vec();
+        sorted_times.sort();
+        let median_time = sorted_times[sorted_times.len() / 2];
+
+        // The times must be increasing, but a decreasing one is counted as no
+        // time between the blocks
+        let elapsed = |from: &DateTimeUtc, to: &DateTimeUtc| {
+            (to.0 - from.0).to_std().unwrap_or_default()
+        };
+        let mut block_times: Vec<std::time::Duration> = times
+            .windows(2)
+            .map(|pair| elapsed(&pair[0], &pair[1]))
+            .collect();
+        block_times.sort();
+        let median_block_time = block_times
+            .get(block_times.len() / 2)
+            .copied()
+            .unwrap_or_default();
+        let average_block_time = match u32::try_from(block_times.len()) {
+            Ok(num) if num > 0 => elapsed(first, last) / num,
+            _ => std::time::Duration::ZERO,
+        };
+
+        Some(Self {
+            num_blocks: times.len() as u64,
+            average_block_time: average_block_time.into(),
+            median_block_time: median_block_time.into(),
+            median_time,
+        })
+    }
+}
+
+/// Record the time of a new block, keeping the times of the last
+/// [`BLOCK_TIMES_WINDOW`] blocks.
+pub fn record_block_time<S>(
+    storage: &mut S,
+    time: DateTimeUtc,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = storage::get_block_times_key();
+    let mut times: Vec<DateTimeUtc> = storage.read(&key)?.unwrap_or_default();
+    times.push(time);
+    let excess = times.len().saturating_sub(BLOCK_TIMES_WINDOW);
+    times.drain(..excess);
+    storage.write(&key, times)
+}
+
+/// Read the statistics of the times of the last blocks, if any were recorded.
+pub fn read_block_time_stats<S>(
+    storage: &S,
+) -> namada_storage::Result<Option<BlockTimeStats>>
+where
+    S: StorageRead,
+{
+    let times: Vec<DateTimeUtc> = storage
+        .read(&storage::get_block_times_key())?
+        .unwrap_or_default();
+    Ok(BlockTimeStats::new(&times))
+}
+
+/// Record the gas used by the txs of the current block, in whole gas units.
+pub fn record_block_gas_used<S>(
+    storage: &mut S,
+    gas_used: u64,
+) -> namada_storage::Result<()>
+where
+    S: StorageWrite,
+{
+    storage.write(&storage::get_block_gas_used_key(), gas_used)
+}
+
+/// Read the gas used by the txs of the last block, in whole gas units.
+pub fn read_block_gas_used<S>(storage: &S) -> namada_storage::Result<u64>
+where
+    S: StorageRead,
+{
+    Ok(storage
+        .read(&storage::get_block_gas_used_key())?
+        .unwrap_or_default())
+}
+
 /// Read the the epoch duration parameter from store
 pub fn read_epoch_duration_parameter<S>(
     storage: &S,
@@ -300,7 +506,7 @@ where
 {
     // read epoch
     let epoch_key = storage::get_epoch_duration_storage_key();
-    let epoch_duration = storage.read(&epoch_key)?;
+    let epoch_duration = storage.read_cached(&epoch_key)?;
     epoch_duration
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()
@@ -315,7 +521,7 @@ where
     S: StorageRead,
 {
     let gas_cost_table: BTreeMap<Address, token::Amount> = storage
-        .read(&storage::get_gas_cost_key())?
+        .read_cached(&storage::get_gas_cost_key())?
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
     Ok(gas_cost_table.get(token).map(|amount| amount.to_owned()))
@@ -330,7 +536,7 @@ where
     // read max proposal bytes
     let max_proposal_bytes: ProposalBytes = {
         let key = storage::get_max_proposal_bytes_key();
-        let value = storage.read(&key)?;
+        let value = storage.read_cached(&key)?;
         value
             .ok_or(ReadError::ParametersMissing)
             .into_storage_result()?
@@ -339,7 +545,16 @@ where
     // read max block gas
     let max_block_gas: u64 = {
         let key = storage::get_max_block_gas_key();
-        let value = storage.read(&key)?;
+        let value = storage.read_cached(&key)?;
+        value
+            .ok_or(ReadError::ParametersMissing)
+            .into_storage_result()?
+    };
+
+    // read max scheduled txs gas
+    let max_scheduled_txs_gas: u64 = {
+        let key = storage::get_max_scheduled_txs_gas_key();
+        let value = storage.read_cached(&key)?;
         value
             .ok_or(ReadError::ParametersMissing)
             .into_storage_result()?
@@ -350,14 +565,14 @@ where
 
     // read vp whitelist
     let vp_whitelist_key = storage::get_vp_whitelist_storage_key();
-    let value = storage.read(&vp_whitelist_key)?;
+    let value = storage.read_cached(&vp_whitelist_key)?;
     let vp_whitelist: Vec<String> = value
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
 
     // read tx whitelist
     let tx_whitelist_key = storage::get_tx_whitelist_storage_key();
-    let value = storage.read(&tx_whitelist_key)?;
+    let value = storage.read_cached(&tx_whitelist_key)?;
     let tx_whitelist: Vec<String> = value
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
@@ -365,7 +580,7 @@ where
     // read max expected block time
     let max_expected_time_per_block_key =
         storage::get_max_expected_time_per_block_key();
-    let value = storage.read(&max_expected_time_per_block_key)?;
+    let value = storage.read_cached(&max_expected_time_per_block_key)?;
     let max_expected_time_per_block: DurationSecs = value
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
@@ -381,7 +596,7 @@ where
     // read fee unshielding gas limit
     let fee_unshielding_gas_limit_key =
         storage::get_fee_unshielding_gas_limit_key();
-    let value = storage.read(&fee_unshielding_gas_limit_key)?;
+    let value = storage.read_cached(&fee_unshielding_gas_limit_key)?;
     let fee_unshielding_gas_limit: u64 = value
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
@@ -389,14 +604,14 @@ where
     // read fee unshielding descriptions limit
     let fee_unshielding_descriptions_limit_key =
         storage::get_fee_unshielding_descriptions_limit_key();
-    let value = storage.read(&fee_unshielding_descriptions_limit_key)?;
+    let value = storage.read_cached(&fee_unshielding_descriptions_limit_key)?;
     let fee_unshielding_descriptions_limit: u64 = value
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
 
     // read epochs per year
     let epochs_per_year_key = storage::get_epochs_per_year_key();
-    let value = storage.read(&epochs_per_year_key)?;
+    let value = storage.read_cached(&epochs_per_year_key)?;
     let epochs_per_year: u64 = value
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
@@ -405,45 +620,70 @@ where
     let max_signatures_per_transaction_key =
         storage::get_max_signatures_per_transaction_key();
     let value: Option<u8> =
-        storage.read(&max_signatures_per_transaction_key)?;
+        storage.read_cached(&max_signatures_per_transaction_key)?;
     let max_signatures_per_transaction: u8 = value
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
 
     // read staked ratio
     let staked_ratio_key = storage::get_staked_ratio_key();
-    let value = storage.read(&staked_ratio_key)?;
+    let value = storage.read_cached(&staked_ratio_key)?;
     let staked_ratio = value
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
 
     // read PoS inflation rate
     let pos_inflation_key = storage::get_pos_inflation_amount_key();
-    let value = storage.read(&pos_inflation_key)?;
+    let value = storage.read_cached(&pos_inflation_key)?;
     let pos_inflation_amount = value
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
 
     // read gas cost
     let gas_cost_key = storage::get_gas_cost_key();
-    let value = storage.read(&gas_cost_key)?;
+    let value = storage.read_cached(&gas_cost_key)?;
     let minimum_gas_price: BTreeMap<Address, token::Amount> = value
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
 
     // read max tx bytes
     let max_tx_bytes_key = storage::get_max_tx_bytes_key();
-    let value = storage.read(&max_tx_bytes_key)?;
+    let value = storage.read_cached(&max_tx_bytes_key)?;
     let max_tx_bytes = value
         .ok_or(ReadError::ParametersMissing)
         .into_storage_result()?;
 
+    // read max tx memo bytes
+    let max_tx_memo_bytes_key = storage::get_max_tx_memo_bytes_key();
+    let value = storage.read_cached(&max_tx_memo_bytes_key)?;
+    let max_tx_memo_bytes = value
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()?;
+
+    // read max tx sections
+    let max_tx_sections_key = storage::get_max_tx_sections_key();
+    let value = storage.read_cached(&max_tx_sections_key)?;
+    let max_tx_sections = value
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()?;
+
+    // read max tx code bytes
+    let max_tx_code_bytes_key = storage::get_max_tx_code_bytes_key();
+    let value = storage.read_cached(&max_tx_code_bytes_key)?;
+    let max_tx_code_bytes = value
+        .ok_or(ReadError::ParametersMissing)
+        .into_storage_result()?;
+
     Ok(Parameters {
         max_tx_bytes,
+        max_tx_memo_bytes,
+        max_tx_sections,
+        max_tx_code_bytes,
         epoch_duration,
         max_expected_time_per_block,
         max_proposal_bytes,
         max_block_gas,
+        max_scheduled_txs_gas,
         vp_whitelist,
         tx_whitelist,
         implicit_vp_code_hash: Some(implicit_vp_code_hash),
@@ -466,11 +706,53 @@ where
     S: StorageRead,
 {
     let max_tx_bytes: u32 = storage
-        .read(&storage::get_max_tx_bytes_key())?
+        .read_cached(&storage::get_max_tx_bytes_key())?
         .expect("The max tx bytes param should be present in storage");
     Ok(tx_size <= max_tx_bytes as usize)
 }
 
+/// Validate the size of the memo attached to a tx.
+pub fn validate_tx_memo_bytes<S>(
+    storage: &S,
+    memo_size: usize,
+) -> namada_storage::Result<bool>
+where
+    S: StorageRead,
+{
+    let max_tx_memo_bytes: u32 = storage
+        .read_cached(&storage::get_max_tx_memo_bytes_key())?
+        .expect("The max tx memo bytes param should be present in storage");
+    Ok(memo_size <= max_tx_memo_bytes as usize)
+}
+
+/// Validate the number of sections of a tx.
+pub fn validate_tx_sections<S>(
+    storage: &S,
+    num_sections: usize,
+) -> namada_storage::Result<bool>
+where
+    S: StorageRead,
+{
+    let max_tx_sections: u32 = storage
+        .read_cached(&storage::get_max_tx_sections_key())?
+        .expect("The max tx sections param should be present in storage");
+    Ok(num_sections <= max_tx_sections as usize)
+}
+
+/// Validate the size of a WASM code embedded in a tx.
+pub fn validate_tx_code_bytes<S>(
+    storage: &S,
+    code_size: usize,
+) -> namada_storage::Result<bool>
+where
+    S: StorageRead,
+{
+    let max_tx_code_bytes: u32 = storage
+        .read_cached(&storage::get_max_tx_code_bytes_key())?
+        .expect("The max tx code bytes param should be present in storage");
+    Ok(code_size <= max_tx_code_bytes as usize)
+}
+
 /// Storage key for the Ethereum address of wNam.
 pub fn native_erc20_key() -> Key {
     storage::get_native_erc20_key_at_addr(ADDRESS)
@@ -1,7 +1,8 @@
 //! Parameters storage
 
 use namada_core::types::address::Address;
-use namada_core::types::storage::{DbKeySeg, Key};
+use namada_core::types::key_space::{ModuleKeySpace, PrefixSeg};
+use namada_core::types::storage::{DbKeySeg, Epoch, Key, KeySeg};
 use namada_macros::StorageKeys;
 use namada_storage::StorageRead;
 
@@ -24,6 +25,9 @@ struct Keys {
     native_erc20: &'static str,
     /// Sub-lkey for storing the Ethereum address of the bridge contract.
     bridge_contract_address: &'static str,
+    /// Sub-key for storing the Ethereum address and version of the next
+    /// bridge contract, during the migration window of an upgrade.
+    next_bridge_contract_address: &'static str,
     // ========================================
     // PoS parameters
     // ========================================
@@ -40,13 +44,49 @@ struct Keys {
     vp_whitelist: &'static str,
     max_proposal_bytes: &'static str,
     max_tx_bytes: &'static str,
+    max_tx_memo_bytes: &'static str,
+    max_tx_sections: &'static str,
+    max_tx_code_bytes: &'static str,
     max_block_gas: &'static str,
+    max_scheduled_txs_gas: &'static str,
     minimum_gas_price: &'static str,
     fee_unshielding_gas_limit: &'static str,
     fee_unshielding_descriptions_limit: &'static str,
     max_signatures_per_transaction: &'static str,
 }
 
+/// Sub-key for the parameter changes scheduled to activate at an epoch
+pub const PENDING_CHANGES_KEY: &str = "pending_changes";
+/// Sub-key for the times of the last blocks
+pub const BLOCK_TIMES_KEY: &str = "block_times";
+/// Sub-key for the gas used by the last block
+pub const BLOCK_GAS_USED_KEY: &str = "block_gas_used";
+
+/// The key prefixes reserved by the protocol parameters
+pub fn key_space() -> ModuleKeySpace {
+    ModuleKeySpace::new("parameters")
+        .guarded_by(ADDRESS)
+        .reserve_sub_keys(&ADDRESS, Keys::ALL, "Protocol parameters")
+        .reserve(
+            [
+                PrefixSeg::Address(ADDRESS),
+                PrefixSeg::Str(PENDING_CHANGES_KEY),
+            ],
+            "Scheduled parameter changes",
+        )
+        .reserve(
+            [PrefixSeg::Address(ADDRESS), PrefixSeg::Str(BLOCK_TIMES_KEY)],
+            "Times of the last blocks",
+        )
+        .reserve(
+            [
+                PrefixSeg::Address(ADDRESS),
+                PrefixSeg::Str(BLOCK_GAS_USED_KEY),
+            ],
+            "Gas used by the last block",
+        )
+}
+
 /// Returns if the key is a parameter key.
 pub fn is_parameter_key(key: &Key) -> bool {
     matches!(&key.segments[0], DbKeySeg::AddressSeg(addr) if addr == &ADDRESS)
@@ -65,6 +105,23 @@ pub fn is_protocol_parameter_key(key: &Key) -> bool {
     Keys::ALL.binary_search(&segment).is_ok()
 }
 
+/// Returns the activation epoch if the key is a scheduled parameter changes
+/// key.
+pub fn is_pending_changes_key(key: &Key) -> Option<Epoch> {
+    let [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), epoch] =
+        &key.segments[..]
+    else {
+        return None;
+    };
+    if addr != &ADDRESS || prefix != PENDING_CHANGES_KEY {
+        return None;
+    }
+    match epoch {
+        DbKeySeg::StringSeg(epoch) => Epoch::parse(epoch.clone()).ok(),
+        _ => None,
+    }
+}
+
 /// Returns if the key is an epoch storage key.
 pub fn is_epoch_duration_storage_key(key: &Key) -> bool {
     is_epoch_duration_key_at_addr(key, &ADDRESS)
@@ -115,6 +172,26 @@ pub fn is_max_tx_bytes_key(key: &Key) -> bool {
     is_max_tx_bytes_key_at_addr(key, &ADDRESS)
 }
 
+/// Returns if the key is the max tx memo bytes key.
+pub fn is_max_tx_memo_bytes_key(key: &Key) -> bool {
+    is_max_tx_memo_bytes_key_at_addr(key, &ADDRESS)
+}
+
+/// Returns if the key is the max tx sections key.
+pub fn is_max_tx_sections_key(key: &Key) -> bool {
+    is_max_tx_sections_key_at_addr(key, &ADDRESS)
+}
+
+/// Returns if the key is the max tx code bytes key.
+pub fn is_max_tx_code_bytes_key(key: &Key) -> bool {
+    is_max_tx_code_bytes_key_at_addr(key, &ADDRESS)
+}
+
+/// Returns if the key is the max scheduled txs gas key.
+pub fn is_max_scheduled_txs_gas_key(key: &Key) -> bool {
+    is_max_scheduled_txs_gas_key_at_addr(key, &ADDRESS)
+}
+
 /// Storage key used for epoch parameter.
 pub fn get_epoch_duration_storage_key() -> Key {
     get_epoch_duration_key_at_addr(ADDRESS)
@@ -175,11 +252,31 @@ pub fn get_max_tx_bytes_key() -> Key {
     get_max_tx_bytes_key_at_addr(ADDRESS)
 }
 
+/// Storage key used for the max tx memo bytes.
+pub fn get_max_tx_memo_bytes_key() -> Key {
+    get_max_tx_memo_bytes_key_at_addr(ADDRESS)
+}
+
+/// Storage key used for the max tx sections.
+pub fn get_max_tx_sections_key() -> Key {
+    get_max_tx_sections_key_at_addr(ADDRESS)
+}
+
+/// Storage key used for the max tx code bytes.
+pub fn get_max_tx_code_bytes_key() -> Key {
+    get_max_tx_code_bytes_key_at_addr(ADDRESS)
+}
+
 /// Storage key used for the max block gas.
 pub fn get_max_block_gas_key() -> Key {
     get_max_block_gas_key_at_addr(ADDRESS)
 }
 
+/// Storage key used for the max total gas of the txs scheduled at an epoch.
+pub fn get_max_scheduled_txs_gas_key() -> Key {
+    get_max_scheduled_txs_gas_key_at_addr(ADDRESS)
+}
+
 /// Storage key used for the gas cost table
 pub fn get_gas_cost_key() -> Key {
     get_minimum_gas_price_key_at_addr(ADDRESS)
@@ -190,14 +287,56 @@ pub fn get_max_signatures_per_transaction_key() -> Key {
     get_max_signatures_per_transaction_key_at_addr(ADDRESS)
 }
 
+/// Storage key prefix of the scheduled parameter changes.
+pub fn get_pending_changes_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&PENDING_CHANGES_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key of the parameter changes scheduled to activate at the given
+/// epoch.
+pub fn get_pending_changes_key(epoch: Epoch) -> Key {
+    get_pending_changes_prefix()
+        .push(&epoch)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key of the times of the last blocks.
+pub fn get_block_times_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&BLOCK_TIMES_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key of the gas used by the last block. The gas used by a past
+/// block is read from this key at the block's height.
+pub fn get_block_gas_used_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&BLOCK_GAS_USED_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Helper function to retrieve the `max_block_gas` protocol parameter from
 /// storage
 pub fn get_max_block_gas(
     storage: &impl StorageRead,
 ) -> std::result::Result<u64, namada_storage::Error> {
-    storage.read(&get_max_block_gas_key())?.ok_or(
+    storage.read_cached(&get_max_block_gas_key())?.ok_or(
         namada_storage::Error::SimpleMessage(
             "Missing max_block_gas parameter from storage",
         ),
     )
 }
+
+/// Helper function to retrieve the `max_scheduled_txs_gas` protocol parameter
+/// from storage
+pub fn get_max_scheduled_txs_gas(
+    storage: &impl StorageRead,
+) -> std::result::Result<u64, namada_storage::Error> {
+    storage
+        .read_cached(&get_max_scheduled_txs_gas_key())?
+        .ok_or(namada_storage::Error::SimpleMessage(
+            "Missing max_scheduled_txs_gas parameter from storage",
+        ))
+}
@@ -1039,6 +1039,84 @@ impl EpochOffset for OffsetMaxProposalPeriodOrSlashProcessingLenPlus {
     }
 }
 
+/// Offset at the stake history length, but at least the default num past
+/// epochs.
+#[derive(
+    Debug,
+    Clone,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub struct OffsetStakeHistoryLen;
+impl EpochOffset for OffsetStakeHistoryLen {
+    fn value(params: &PosParams) -> u64 {
+        params.stake_history_epoch_offset()
+    }
+
+    fn dyn_offset() -> DynEpochOffset {
+        DynEpochOffset::StakeHistoryLen
+    }
+}
+
+/// Offset at the larger of the max proposal period plus the default num past
+/// epochs and the stake history length.
+#[derive(
+    Debug,
+    Clone,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub struct OffsetMaxProposalPeriodPlusOrHistory;
+impl EpochOffset for OffsetMaxProposalPeriodPlusOrHistory {
+    fn value(params: &PosParams) -> u64 {
+        cmp::max(
+            OffsetMaxProposalPeriodPlus::value(params),
+            params.stake_history_epoch_offset(),
+        )
+    }
+
+    fn dyn_offset() -> DynEpochOffset {
+        DynEpochOffset::MaxProposalPeriodPlusOrHistory
+    }
+}
+
+/// Offset at the larger of the slash processing length and the max proposal
+/// period plus the default num past epochs, and the stake history length.
+#[derive(
+    Debug,
+    Clone,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+)]
+pub struct OffsetMaxProposalPeriodOrSlashProcessingLenPlusOrHistory;
+impl EpochOffset for OffsetMaxProposalPeriodOrSlashProcessingLenPlusOrHistory {
+    fn value(params: &PosParams) -> u64 {
+        cmp::max(
+            OffsetMaxProposalPeriodOrSlashProcessingLenPlus::value(params),
+            params.stake_history_epoch_offset(),
+        )
+    }
+
+    fn dyn_offset() -> DynEpochOffset {
+        DynEpochOffset::MaxProposalPeriodOrSlashProcessingLenPlusOrHistory
+    }
+}
+
 /// Offset length dynamic choice.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum DynEpochOffset {
@@ -1068,6 +1146,15 @@ pub enum DynEpochOffset {
     /// Offset at the larger of max proposal period or slash processing delay,
     /// plus the default num past epochs
     MaxProposalPeriodOrSlashProcessingLenPlus,
+    /// Offset at the stake history length, but at least the default num past
+    /// epochs
+    StakeHistoryLen,
+    /// Offset at the larger of the max proposal period plus the default num
+    /// past epochs and the stake history length
+    MaxProposalPeriodPlusOrHistory,
+    /// Offset at the larger of max proposal period or slash processing delay
+    /// plus the default num past epochs, and the stake history length
+    MaxProposalPeriodOrSlashProcessingLenPlusOrHistory,
     /// Offset of the max u64 value
     MaxU64,
 }
@@ -6,8 +6,8 @@ use namada_core::types::dec::Dec;
 use namada_core::types::storage::Epoch;
 use thiserror::Error;
 
-use crate::rewards;
 use crate::types::{BondId, ValidatorState};
+use crate::{rewards, token};
 
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
@@ -160,6 +160,11 @@ pub enum ReactivationError {
     NotInactive(Address, Epoch),
     #[error("No state found for validator {0} in epoch {1}")]
     NoStateFound(Address, Epoch),
+    #[error(
+        "The self-bond of validator {0} is below the minimum self-bond {}",
+        .1.to_string_native()
+    )]
+    SelfBondBelowMinimum(Address, token::Amount),
 }
 
 #[allow(missing_docs)]
@@ -49,14 +49,15 @@ use crate::slashing::{
 use crate::storage::{
     below_capacity_validator_set_handle, bond_handle,
     consensus_validator_set_handle, delegator_redelegated_bonds_handle,
-    delegator_redelegated_unbonds_handle, get_last_reward_claim_epoch,
-    liveness_missed_votes_handle, liveness_sum_missed_votes_handle,
+    delegator_redelegated_unbonds_handle, delete_validator_deactivation_reason,
+    get_last_reward_claim_epoch, liveness_missed_votes_handle,
+    liveness_sum_missed_votes_handle, read_all_validator_addresses,
     read_consensus_validator_set_addresses, read_non_pos_owned_params,
-    read_pos_params, read_validator_last_slash_epoch,
-    read_validator_max_commission_rate_change, read_validator_stake,
-    total_bonded_handle, total_consensus_stake_handle, total_unbonded_handle,
-    try_insert_consensus_key, unbond_handle, update_total_deltas,
-    update_validator_deltas, validator_addresses_handle,
+    read_pos_params, read_validator_deactivation_reason,
+    read_validator_last_slash_epoch, read_validator_max_commission_rate_change,
+    read_validator_stake, total_bonded_handle, total_consensus_stake_handle,
+    total_unbonded_handle, try_insert_consensus_key, unbond_handle,
+    update_total_deltas, update_validator_deltas, validator_addresses_handle,
     validator_commission_rate_handle, validator_consensus_key_handle,
     validator_deltas_handle, validator_eth_cold_key_handle,
     validator_eth_hot_key_handle, validator_incoming_redelegations_handle,
@@ -64,15 +65,17 @@ use crate::storage::{
     validator_rewards_products_handle, validator_set_positions_handle,
     validator_slashes_handle, validator_state_handle,
     validator_total_redelegated_bonded_handle,
-    validator_total_redelegated_unbonded_handle, write_last_reward_claim_epoch,
-    write_pos_params, write_validator_address_raw_hash, write_validator_avatar,
-    write_validator_description, write_validator_discord_handle,
-    write_validator_email, write_validator_max_commission_rate_change,
-    write_validator_metadata, write_validator_website,
+    validator_total_redelegated_unbonded_handle, write_auto_compound,
+    write_last_reward_claim_epoch, write_pos_params,
+    write_validator_address_raw_hash, write_validator_avatar,
+    write_validator_deactivation_reason, write_validator_description,
+    write_validator_discord_handle, write_validator_email,
+    write_validator_max_commission_rate_change, write_validator_metadata,
+    write_validator_website,
 };
 use crate::storage_key::{bonds_for_source_prefix, is_bond_key};
 use crate::types::{
-    BondId, ConsensusValidator, ConsensusValidatorSet,
+    BondId, ConsensusValidator, ConsensusValidatorSet, DeactivationReason,
     EagerRedelegatedBondsMap, RedelegatedBondsOrUnbonds, RedelegatedTokens,
     ResultSlashing, Slash, Unbonds, ValidatorMetaData, ValidatorSetUpdate,
     ValidatorState, VoteInfo,
@@ -2274,6 +2277,25 @@ where
         }
     }
 
+    // A validator whose self-bond is below the minimum would be deactivated
+    // again at the next epoch
+    let self_bond = bond_amount(
+        storage,
+        &BondId {
+            source: validator.clone(),
+            validator: validator.clone(),
+        },
+        pipeline_epoch,
+    )?;
+    if self_bond < params.min_self_bond {
+        return Err(ReactivationError::SelfBondBelowMinimum(
+            validator.clone(),
+            params.min_self_bond,
+        )
+        .into());
+    }
+    delete_validator_deactivation_reason(storage, validator)?;
+
     // Check to see if the validator should be jailed upon a reactivation. This
     // may occur if a validator is deactivated but then an infraction is
     // discovered later.
@@ -2309,6 +2331,105 @@ where
     Ok(())
 }
 
+/// Deactivate the validators whose self-bond at the pipeline epoch is below the
+/// `min_self_bond` parameter and reactivate the validators deactivated for it
+/// whose self-bond has been topped up. Must be called at the start of a new
+/// epoch, after the validator sets have been copied to the pipeline epoch.
+/// Returns the validators whose state has changed, with their new state at the
+/// pipeline epoch.
+pub fn update_validators_below_min_self_bond<S>(
+    storage: &mut S,
+    params: &PosParams,
+    current_epoch: Epoch,
+) -> namada_storage::Result<Vec<(Address, ValidatorState)>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    // Sorted for a deterministic order of the validator sets updates
+    let validators: BTreeSet<Address> =
+        read_all_validator_addresses(storage, pipeline_epoch)?
+            .into_iter()
+            .collect();
+
+    let mut updates = vec![];
+    for validator in validators {
+        let state = validator_state_handle(&validator).get(
+            storage,
+            pipeline_epoch,
+            params,
+        )?;
+        let self_bond = bond_amount(
+            storage,
+            &BondId {
+                source: validator.clone(),
+                validator: validator.clone(),
+            },
+            pipeline_epoch,
+        )?;
+        match state {
+            Some(
+                ValidatorState::Consensus
+                | ValidatorState::BelowCapacity
+                | ValidatorState::BelowThreshold,
+            ) if self_bond < params.min_self_bond => {
+                tracing::info!(
+                    "Deactivating validator {} starting in epoch {} for a \
+                     self-bond of {} below the minimum of {}",
+                    validator,
+                    pipeline_epoch,
+                    self_bond.to_string_native(),
+                    params.min_self_bond.to_string_native(),
+                );
+                deactivate_validator(storage, &validator, current_epoch)?;
+                write_validator_deactivation_reason(
+                    storage,
+                    &validator,
+                    DeactivationReason::SelfBondBelowMinimum,
+                )?;
+                updates.push((validator, ValidatorState::Inactive));
+            }
+            Some(ValidatorState::Inactive)
+                if self_bond >= params.min_self_bond =>
+            {
+                if read_validator_deactivation_reason(storage, &validator)?
+                    != Some(DeactivationReason::SelfBondBelowMinimum)
+                {
+                    continue;
+                }
+                // The validator can only be reactivated once it's inactive in
+                // every epoch up to the pipeline epoch
+                let mut is_inactive = true;
+                for epoch in
+                    Epoch::iter_bounds_inclusive(current_epoch, pipeline_epoch)
+                {
+                    is_inactive &= validator_state_handle(&validator)
+                        .get(storage, epoch, params)?
+                        == Some(ValidatorState::Inactive);
+                }
+                if !is_inactive {
+                    continue;
+                }
+                tracing::info!(
+                    "Reactivating validator {} starting in epoch {} for a \
+                     topped up self-bond of {}",
+                    validator,
+                    pipeline_epoch,
+                    self_bond.to_string_native(),
+                );
+                reactivate_validator(storage, &validator, current_epoch)?;
+                let state = validator_state_handle(&validator)
+                    .get(storage, pipeline_epoch, params)?
+                    .expect("Validator should have a state after reactivation");
+                updates.push((validator, state));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(updates)
+}
+
 /// Remove liveness data from storage for all validators that are not in the
 /// current consensus validator set.
 pub fn prune_liveness_data<S>(
@@ -2677,6 +2798,36 @@ where
     Ok(rewards_from_bonds + rewards_from_counter)
 }
 
+/// Enable or disable the automatic restaking of the rewards of a bond at each
+/// epoch boundary. The bond is a self-bond when `source` is `None` or equal to
+/// the `validator` address.
+pub fn set_auto_compound<S>(
+    storage: &mut S,
+    source: Option<&Address>,
+    validator: &Address,
+    enabled: bool,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if let Some(source) = source {
+        if source != validator && is_validator(storage, source)? {
+            return Err(
+                BondError::SourceMustNotBeAValidator(source.clone()).into()
+            );
+        }
+    }
+    if enabled && !is_validator(storage, validator)? {
+        return Err(BondError::NotAValidator(validator.clone()).into());
+    }
+    let source = source.unwrap_or(validator);
+    tracing::debug!(
+        "Setting auto-compounding of rewards of bond {source} --> {validator} \
+         to {enabled}"
+    );
+    write_auto_compound(storage, source, validator, enabled)
+}
+
 /// Jail a validator by removing it from and updating the validator sets and
 /// changing a its state to `Jailed`. Validators are jailed for liveness and for
 /// misbehaving.
@@ -1,5 +1,6 @@
 //! Proof-of-Stake system parameters
 
+use std::cmp;
 use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -10,6 +11,8 @@ use namada_core::types::uint::Uint;
 use namada_governance::parameters::GovernanceParameters;
 use thiserror::Error;
 
+use crate::epoched::DEFAULT_NUM_PAST_EPOCHS;
+
 /// Proof-of-Stake system parameters. This includes parameters that are used in
 /// PoS but are read from other accounts storage (governance).
 #[derive(Debug, Clone, BorshDeserialize, BorshSerialize)]
@@ -54,12 +57,23 @@ pub struct OwnedPosParams {
     /// Fraction of validator's stake that should be slashed on a light client
     /// attack.
     pub light_client_attack_min_slash_rate: Dec,
+    /// Fraction of the tokens slashed for a misbehavior that is paid to the
+    /// account that submitted its evidence.
+    pub slash_evidence_bounty_rate: Dec,
     /// Number of epochs above and below (separately) the current epoch to
     /// consider when doing cubic slashing
     pub cubic_slashing_window_length: u64,
     /// The minimum amount of bonded tokens that a validator needs to be in
     /// either the `consensus` or `below_capacity` validator sets
     pub validator_stake_threshold: token::Amount,
+    /// The minimum amount of tokens that a validator must have bonded to
+    /// itself to stay active. A validator whose self-bond falls below it is
+    /// deactivated at the next epoch until it tops it up.
+    pub min_self_bond: token::Amount,
+    /// The minimum number of past epochs for which the validator sets and
+    /// stakes are kept in storage, to allow to query their historical values.
+    /// The data may be kept for longer when the protocol needs it.
+    pub stake_history_len: u64,
     /// The length, in blocks, of the sliding window for consensus validators
     /// inactivity verification
     pub liveness_window_check: u64,
@@ -103,8 +117,14 @@ impl Default for OwnedPosParams {
             // slash 0.1%
             light_client_attack_min_slash_rate: Dec::new(1, 3)
                 .expect("Test failed"),
+            // pay 10% of the slashed tokens to the evidence submitter
+            slash_evidence_bounty_rate: Dec::new(1, 1).expect("Test failed"),
             cubic_slashing_window_length: 1,
             validator_stake_threshold: token::Amount::native_whole(1_u64),
+            // no minimum self-bond
+            min_self_bond: token::Amount::zero(),
+            // only keep the stake history needed by the protocol
+            stake_history_len: 0,
             liveness_window_check: 10_000,
             liveness_threshold: Dec::new(9, 1).expect("Test failed"),
             rewards_gain_p: Dec::from_str("0.25").expect("Test failed"),
@@ -123,6 +143,8 @@ pub enum ValidationError {
     TotalVotingPowerTooLarge(Uint),
     #[error("Votes per token cannot be greater than 1, got {0}")]
     VotesPerTokenGreaterThanOne(Dec),
+    #[error("Slash evidence bounty rate must be between 0 and 1, got {0}")]
+    InvalidSlashEvidenceBountyRate(Dec),
     #[error("Pipeline length must be >= 2, got {0}")]
     PipelineLenTooShort(u64),
     #[error(
@@ -187,6 +209,15 @@ impl OwnedPosParams {
             ))
         }
 
+        // Check that the bounty is a fraction of the slashed tokens
+        if self.slash_evidence_bounty_rate.is_negative()
+            || self.slash_evidence_bounty_rate > Dec::one()
+        {
+            errors.push(ValidationError::InvalidSlashEvidenceBountyRate(
+                self.slash_evidence_bounty_rate,
+            ))
+        }
+
         errors
     }
 
@@ -202,6 +233,12 @@ impl OwnedPosParams {
         self.unbonding_len + self.cubic_slashing_window_length + 1
     }
 
+    /// Get the number of past epochs for which the validator sets and stakes
+    /// can be queried
+    pub fn stake_history_epoch_offset(&self) -> u64 {
+        cmp::max(self.stake_history_len, DEFAULT_NUM_PAST_EPOCHS)
+    }
+
     /// Get the first and the last epoch of a cubic slash window.
     pub fn cubic_slash_epoch_window(
         &self,
@@ -161,6 +161,107 @@ where
     }
 }
 
+/// Collect the details of the bonds and unbonds that match the source and
+/// validator arguments as they were at the given past `epoch`. The bonds that
+/// were unbonded after the `epoch` are reported as bonds and only the slashes
+/// that have been processed by the `epoch` are applied. Note that the unbonds
+/// that have been withdrawn since are no longer in storage and cannot be
+/// reported.
+pub fn bonds_and_unbonds_at_epoch<S>(
+    storage: &S,
+    source: Option<Address>,
+    validator: Option<Address>,
+    epoch: Epoch,
+) -> namada_storage::Result<BondsAndUnbondsDetails>
+where
+    S: StorageRead,
+{
+    let params = read_pos_params(storage)?;
+    let mut slashes_cache = HashMap::<Address, Vec<Slash>>::new();
+    let mut applied_slashes = HashMap::<Address, Vec<Slash>>::new();
+
+    let mut details_at_epoch = HashMap::new();
+    for (bond_id, details) in bonds_and_unbonds(storage, source, validator)? {
+        if !slashes_cache.contains_key(&bond_id.validator) {
+            let slashes = find_validator_slashes(storage, &bond_id.validator)?
+                .into_iter()
+                .filter(|slash| {
+                    slash.epoch + params.slash_processing_epoch_offset()
+                        <= epoch
+                })
+                .collect();
+            slashes_cache.insert(bond_id.validator.clone(), slashes);
+        }
+        let slashes = slashes_cache
+            .get(&bond_id.validator)
+            .expect("We must have inserted it if it's not cached already");
+
+        let mut bonds = vec![];
+        let mut unbonds = vec![];
+        let bonds_at_epoch = details
+            .bonds
+            .into_iter()
+            .filter(|bond| bond.start <= epoch)
+            .map(|bond| (bond.start, bond.amount));
+        for (start, amount) in bonds_at_epoch {
+            bonds.push(make_bond_details(
+                &params,
+                &bond_id.validator,
+                amount,
+                start,
+                slashes,
+                &mut applied_slashes,
+            ));
+        }
+        for unbond in details.unbonds {
+            if unbond.start > epoch {
+                continue;
+            }
+            // The epoch in which the unbond stopped contributing to the stake
+            let end = unbond.withdraw.sub_or_default(Epoch(
+                params.unbonding_len + params.cubic_slashing_window_length,
+            ));
+            if end > epoch {
+                bonds.push(make_bond_details(
+                    &params,
+                    &bond_id.validator,
+                    unbond.amount,
+                    unbond.start,
+                    slashes,
+                    &mut applied_slashes,
+                ));
+            } else {
+                unbonds.push(make_unbond_details(
+                    &params,
+                    &bond_id.validator,
+                    unbond.amount,
+                    (unbond.start, unbond.withdraw),
+                    slashes,
+                    &mut applied_slashes,
+                ));
+            }
+        }
+        if !bonds.is_empty() || !unbonds.is_empty() {
+            details_at_epoch.insert(bond_id, (bonds, unbonds));
+        }
+    }
+
+    Ok(details_at_epoch
+        .into_iter()
+        .map(|(bond_id, (bonds, unbonds))| {
+            let details = BondsAndUnbondsDetail {
+                bonds,
+                unbonds,
+                slashes: applied_slashes
+                    .get(&bond_id.validator)
+                    .cloned()
+                    .unwrap_or_default(),
+            };
+            (bond_id, details)
+        })
+        .collect())
+}
+
 fn get_multiple_bonds_and_unbonds<S>(
     storage: &S,
     params: &PosParams,
@@ -15,15 +15,20 @@ use thiserror::Error;
 
 use crate::storage::{
     consensus_validator_set_handle, get_last_reward_claim_epoch,
-    read_pos_params, read_total_stake, read_validator_stake,
-    rewards_accumulator_handle, validator_commission_rate_handle,
-    validator_rewards_products_handle, validator_state_handle,
+    is_auto_compound_enabled, read_auto_compound_bonds,
+    read_last_auto_compound_bond, read_pos_params, read_total_stake,
+    read_validator_stake, rewards_accumulator_handle,
+    validator_commission_rate_handle, validator_rewards_products_handle,
+    validator_state_handle, write_last_auto_compound_bond,
 };
 use crate::token::credit_tokens;
 use crate::token::storage_key::minted_balance_key;
-use crate::types::{into_tm_voting_power, BondId, ValidatorState, VoteInfo};
+use crate::types::{
+    into_tm_voting_power, BondId, RewardsRate, ValidatorState, VoteInfo,
+};
 use crate::{
-    bond_amounts_for_rewards, get_total_consensus_stake, staking_token_address,
+    bond_amounts_for_rewards, bond_tokens, claim_reward_tokens,
+    get_total_consensus_stake, is_validator, staking_token_address,
     storage_key, InflationError, PosParams,
 };
 
@@ -547,3 +552,129 @@ where
     let key = storage_key::rewards_counter_key(source, validator);
     Ok(storage.read::<token::Amount>(&key)?.unwrap_or_default())
 }
+
+/// The maximum number of auto-compounding bonds whose rewards are restaked in
+/// a single epoch. When more bonds have opted in, the following epochs
+/// continue from where the last batch stopped.
+pub const MAX_AUTO_COMPOUND_BONDS_PER_EPOCH: usize = 500;
+
+/// Get the next batch of at most [`MAX_AUTO_COMPOUND_BONDS_PER_EPOCH`] bonds
+/// that have opted into auto-compounding, continuing after the last bond of
+/// the previous batch and wrapping around, and record the last bond of this
+/// batch. This must be called at the start of a new epoch, after the rewards
+/// products of the last epoch have been updated.
+pub fn next_auto_compound_bonds<S>(
+    storage: &mut S,
+) -> namada_storage::Result<Vec<BondId>>
+where
+    S: StorageRead + StorageWrite,
+{
+    let mut bonds = read_auto_compound_bonds(storage)?;
+    if bonds.is_empty() {
+        return Ok(bonds);
+    }
+    bonds.sort();
+    let start = match read_last_auto_compound_bond(storage)? {
+        Some(last) => bonds
+            .iter()
+            .position(|bond| bond > &last)
+            .unwrap_or_default(),
+        None => 0,
+    };
+    let len = bonds.len().min(MAX_AUTO_COMPOUND_BONDS_PER_EPOCH);
+    let batch: Vec<BondId> =
+        bonds.into_iter().cycle().skip(start).take(len).collect();
+    if let Some(last) = batch.last() {
+        write_last_auto_compound_bond(storage, last)?;
+    }
+    Ok(batch)
+}
+
+/// Restake the available rewards of a bond that has opted into
+/// auto-compounding.
+pub fn compound_bond_rewards<S>(
+    storage: &mut S,
+    bond_id: &BondId,
+    current_epoch: Epoch,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let BondId { source, validator } = bond_id;
+    // The source may have become a validator since it opted in, in which
+    // case it can no longer delegate
+    if source != validator && is_validator(storage, source)? {
+        tracing::debug!(
+            "Skipping auto-compounding of bond {source} --> {validator}, the \
+             source is a validator"
+        );
+        return Ok(());
+    }
+    let rewards =
+        claim_reward_tokens(storage, Some(source), validator, current_epoch)?;
+    if rewards.is_zero() {
+        return Ok(());
+    }
+    tracing::debug!(
+        "Restaking {} rewards of bond {source} --> {validator}",
+        rewards.to_string_native()
+    );
+    bond_tokens(
+        storage,
+        Some(source),
+        validator,
+        rewards,
+        current_epoch,
+        None,
+    )
+}
+
+/// Estimate the annual rewards rate of a bond from the rewards product of the
+/// validator in the last epoch. The APY assumes that the rewards are restaked
+/// at every epoch.
+pub fn estimate_rewards_rate<S>(
+    storage: &S,
+    source: Option<&Address>,
+    validator: &Address,
+    current_epoch: Epoch,
+) -> namada_storage::Result<RewardsRate>
+where
+    S: StorageRead,
+{
+    let source = source.unwrap_or(validator);
+    let auto_compound = is_auto_compound_enabled(storage, source, validator)?;
+    let epochs_per_year: u64 = storage
+        .read(&params_storage::get_epochs_per_year_key())?
+        .expect("Epochs per year should exist in storage");
+
+    let last_product = match current_epoch.checked_sub(1) {
+        Some(last_epoch) => validator_rewards_products_handle(validator)
+            .get(storage, &last_epoch)?
+            .unwrap_or_default(),
+        None => Dec::zero(),
+    };
+
+    let apr = last_product * epochs_per_year;
+    // (1 + product) ^ epochs_per_year - 1, by repeated squaring
+    let overflow =
+        || namada_storage::Error::new_const("Rewards rate APY overflow");
+    let mut apy = Dec::one();
+    let mut base = Dec::one() + last_product;
+    let mut exp = epochs_per_year;
+    while exp > 0 {
+        if exp % 2 == 1 {
+            apy = apy.checked_mul(&base).ok_or_else(overflow)?;
+        }
+        exp /= 2;
+        if exp > 0 {
+            base = base.checked_mul(&base).ok_or_else(overflow)?;
+        }
+    }
+    let apy = apy - Dec::one();
+
+    Ok(RewardsRate {
+        auto_compound,
+        apr,
+        apy,
+    })
+}
@@ -7,7 +7,6 @@ use borsh::BorshDeserialize;
 use namada_core::types::address::Address;
 use namada_core::types::dec::Dec;
 use namada_core::types::storage::Epoch;
-use namada_core::types::token;
 use namada_storage::collections::lazy_map::{
     Collectable, NestedMap, NestedSubKey, SubKey,
 };
@@ -16,8 +15,8 @@ use namada_storage::{StorageRead, StorageWrite};
 
 use crate::storage::{
     enqueued_slashes_handle, read_pos_params, read_validator_last_slash_epoch,
-    read_validator_stake, total_bonded_handle, total_unbonded_handle,
-    update_total_deltas, update_validator_deltas,
+    read_validator_stake, slash_bounties_handle, total_bonded_handle,
+    total_unbonded_handle, update_total_deltas, update_validator_deltas,
     validator_outgoing_redelegations_handle, validator_slashes_handle,
     validator_state_handle, validator_total_redelegated_bonded_handle,
     validator_total_redelegated_unbonded_handle,
@@ -30,8 +29,9 @@ use crate::types::{
 use crate::validator_set_update::update_validator_set;
 use crate::{
     fold_and_slash_redelegated_bonds, get_total_consensus_stake,
-    jail_validator, storage_key, EagerRedelegatedUnbonds,
-    FoldRedelegatedBondsResult, OwnedPosParams, PosParams,
+    jail_validator, staking_token_address, storage_key, token,
+    EagerRedelegatedUnbonds, FoldRedelegatedBondsResult, OwnedPosParams,
+    PosParams, ADDRESS,
 };
 
 /// Record a slash for a misbehavior that has been received from Tendermint and
@@ -93,6 +93,48 @@ where
     Ok(())
 }
 
+/// Record the submitter of the evidence of a validator's misbehavior, who is
+/// paid a bounty from the slashed tokens when the slash is processed. Returns
+/// `false` without recording anything if a submitter has already been
+/// recorded for a misbehavior of the validator in the same epoch.
+pub fn record_slash_bounty<S>(
+    storage: &mut S,
+    params: &PosParams,
+    evidence_epoch: Epoch,
+    validator: &Address,
+    submitter: &Address,
+) -> namada_storage::Result<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    let processing_epoch =
+        evidence_epoch + params.slash_processing_epoch_offset();
+    let bounties = slash_bounties_handle().at(&processing_epoch);
+    if bounties.contains(storage, validator)? {
+        return Ok(false);
+    }
+    bounties.insert(storage, validator.clone(), submitter.clone())?;
+    Ok(true)
+}
+
+/// Check if a submitter of the evidence of a validator's misbehavior in the
+/// given epoch has already been recorded.
+pub fn has_slash_bounty<S>(
+    storage: &S,
+    params: &PosParams,
+    evidence_epoch: Epoch,
+    validator: &Address,
+) -> namada_storage::Result<bool>
+where
+    S: StorageRead,
+{
+    let processing_epoch =
+        evidence_epoch + params.slash_processing_epoch_offset();
+    slash_bounties_handle()
+        .at(&processing_epoch)
+        .contains(storage, validator)
+}
+
 /// Process enqueued slashes that were discovered earlier. This function is
 /// called upon a new epoch. The final slash rate considering according to the
 /// cubic slashing rate is computed. Then, each slash is recorded in storage
@@ -185,6 +227,10 @@ where
     }
     tracing::debug!("Slashed amounts for validators: {map_validator_slash:#?}");
 
+    // Pay the bounties to the submitters of the evidence from the slashed
+    // tokens, which remain in the PoS account
+    pay_slash_bounties(storage, &params, current_epoch, &map_validator_slash)?;
+
     // Now update the remaining parts of storage
 
     // Write slashes themselves into storage
@@ -996,6 +1042,45 @@ where
 /// Adds any newly processed slash amount of any involved validator to
 /// `slashed_amounts_map`.
 // Quint `processSlash`
+/// Pay the bounties for the slashes processed in the current epoch to the
+/// submitters of their evidence and remove them from storage.
+fn pay_slash_bounties<S>(
+    storage: &mut S,
+    params: &PosParams,
+    current_epoch: Epoch,
+    map_validator_slash: &EagerRedelegatedBondsMap,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let bounties = slash_bounties_handle().at(&current_epoch);
+    let bounties: Vec<(Address, Address)> =
+        bounties.iter(storage)?.collect::<Result<_, _>>()?;
+    if bounties.is_empty() {
+        return Ok(());
+    }
+    let staking_token = staking_token_address(storage);
+    for (validator, submitter) in bounties {
+        // The slashed amounts are cumulative over the epochs
+        let slashed = map_validator_slash
+            .get(&validator)
+            .and_then(|amounts| amounts.values().max().copied())
+            .unwrap_or_default();
+        let bounty = params.slash_evidence_bounty_rate * slashed;
+        if bounty.is_zero() {
+            continue;
+        }
+        tracing::debug!(
+            "Paying a slash bounty of {} to {submitter} for the evidence \
+             against validator {validator}",
+            bounty.to_string_native()
+        );
+        token::transfer(storage, &staking_token, &ADDRESS, &submitter, bounty)?;
+    }
+    slash_bounties_handle().remove_all(storage, &current_epoch)?;
+    Ok(())
+}
+
 fn process_validator_slash<S>(
     storage: &mut S,
     params: &PosParams,
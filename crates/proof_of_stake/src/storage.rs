@@ -15,13 +15,15 @@ use namada_storage::collections::{LazyCollection, LazySet};
 use namada_storage::{Result, StorageRead, StorageWrite};
 use num_traits::CheckedAdd;
 
+use crate::epoched::{EpochOffset, OffsetMaxProposalPeriodPlusOrHistory};
 use crate::storage_key::consensus_keys_key;
 use crate::types::{
-    BelowCapacityValidatorSets, BondId, Bonds, CommissionRates,
-    ConsensusValidatorSets, DelegatorRedelegatedBonded,
-    DelegatorRedelegatedUnbonded, EpochedSlashes, IncomingRedelegations,
-    LivenessMissedVotes, LivenessSumMissedVotes, OutgoingRedelegations,
-    ReverseOrdTokenAmount, RewardsAccumulator, RewardsProducts, Slashes,
+    into_tm_voting_power, BelowCapacityValidatorSets, BondId, Bonds,
+    CommissionRates, ConsensusValidatorSets, DeactivationReason,
+    DelegatorRedelegatedBonded, DelegatorRedelegatedUnbonded, EpochedSlashes,
+    HistoricalValidator, IncomingRedelegations, LivenessMissedVotes,
+    LivenessSumMissedVotes, OutgoingRedelegations, ReverseOrdTokenAmount,
+    RewardsAccumulator, RewardsProducts, SlashBounties, Slashes,
     TotalConsensusStakes, TotalDeltas, TotalRedelegatedBonded,
     TotalRedelegatedUnbonded, Unbonds, ValidatorAddresses,
     ValidatorConsensusKeys, ValidatorDeltas, ValidatorEthColdKeys,
@@ -169,6 +171,12 @@ pub fn enqueued_slashes_handle() -> EpochedSlashes {
     EpochedSlashes::open(key)
 }
 
+/// Get the storage handle to the submitters of the evidence of misbehaviors
+pub fn slash_bounties_handle() -> SlashBounties {
+    let key = storage_key::slash_bounties_key();
+    SlashBounties::open(key)
+}
+
 /// Get the storage handle to the rewards accumulator for the consensus
 /// validators in a given epoch
 pub fn rewards_accumulator_handle() -> RewardsAccumulator {
@@ -252,7 +260,7 @@ where
     S: StorageRead,
 {
     let params = storage
-        .read(&storage_key::params_key())
+        .read_cached(&storage_key::params_key())
         .transpose()
         .expect("PosParams should always exist in storage after genesis")?;
     read_non_pos_owned_params(storage, params)
@@ -364,6 +372,45 @@ where
     storage.write(&key, epoch)
 }
 
+/// Read the reason for which a validator has been deactivated by the protocol,
+/// if any
+pub fn read_validator_deactivation_reason<S>(
+    storage: &S,
+    validator: &Address,
+) -> namada_storage::Result<Option<DeactivationReason>>
+where
+    S: StorageRead,
+{
+    let key = storage_key::validator_deactivation_reason_key(validator);
+    storage.read(&key)
+}
+
+/// Write the reason for which a validator has been deactivated by the protocol
+pub fn write_validator_deactivation_reason<S>(
+    storage: &mut S,
+    validator: &Address,
+    reason: DeactivationReason,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = storage_key::validator_deactivation_reason_key(validator);
+    storage.write(&key, reason)
+}
+
+/// Delete the reason for which a validator has been deactivated by the
+/// protocol, once it's reactivated
+pub fn delete_validator_deactivation_reason<S>(
+    storage: &mut S,
+    validator: &Address,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = storage_key::validator_deactivation_reason_key(validator);
+    storage.delete(&key)
+}
+
 /// Read last block proposer address.
 pub fn read_last_block_proposer_address<S>(
     storage: &S,
@@ -551,6 +598,98 @@ where
         .collect()
 }
 
+/// Record the consensus validator set of an epoch, with the voting powers and
+/// keys of its members, in the history of the validator sets. The history is
+/// committed to in the Merkle tree of the storage, so that the signatures made
+/// by past validator sets can be verified. It is pruned like the epoched
+/// consensus validator sets, from which it is built.
+pub fn write_validator_set_history<S>(
+    storage: &mut S,
+    params: &PosParams,
+    epoch: namada_core::types::storage::Epoch,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let consensus_set =
+        read_consensus_validator_set_addresses_with_stake(storage, epoch)?;
+    let mut validators = Vec::with_capacity(consensus_set.len());
+    for WeightedValidator {
+        bonded_stake,
+        address,
+    } in consensus_set.into_iter().rev()
+    {
+        let consensus_key = validator_consensus_key_handle(&address)
+            .get(storage, epoch, params)?
+            .ok_or_else(|| {
+                namada_storage::Error::new_const(
+                    "A consensus validator must have a consensus key",
+                )
+            })?;
+        let eth_hot_key = validator_eth_hot_key_handle(&address)
+            .get(storage, epoch, params)?;
+        let eth_cold_key = validator_eth_cold_key_handle(&address)
+            .get(storage, epoch, params)?;
+        validators.push(HistoricalValidator {
+            voting_power: into_tm_voting_power(
+                params.tm_votes_per_token,
+                bonded_stake,
+            ),
+            address,
+            bonded_stake,
+            consensus_key,
+            eth_hot_key,
+            eth_cold_key,
+        });
+    }
+    storage
+        .write(&storage_key::validator_set_history_key(epoch), validators)?;
+    prune_validator_set_history(storage, params, epoch)
+}
+
+/// Remove the validator sets of the epochs that are older than the past
+/// epochs kept for the epoched consensus validator sets from their history.
+pub fn prune_validator_set_history<S>(
+    storage: &mut S,
+    params: &PosParams,
+    current_epoch: Epoch,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let num_past_epochs = OffsetMaxProposalPeriodPlusOrHistory::value(params);
+    let Some(oldest_epoch) = current_epoch.checked_sub(num_past_epochs) else {
+        return Ok(());
+    };
+    let prefix = storage_key::validator_set_history_prefix();
+    let mut keys_to_prune = Vec::new();
+    for entry in namada_storage::iter_prefix_bytes(storage, &prefix)? {
+        let (key, _value) = entry?;
+        if matches!(
+            storage_key::is_validator_set_history_key(&key),
+            Some(epoch) if epoch < oldest_epoch
+        ) {
+            keys_to_prune.push(key);
+        }
+    }
+    for key in keys_to_prune {
+        storage.delete(&key)?;
+    }
+    Ok(())
+}
+
+/// Read the consensus validator set of an epoch from the history of the
+/// validator sets, sorted by descending stake.
+pub fn read_validator_set_history<S>(
+    storage: &S,
+    epoch: namada_core::types::storage::Epoch,
+) -> namada_storage::Result<Option<Vec<HistoricalValidator>>>
+where
+    S: StorageRead,
+{
+    storage.read(&storage_key::validator_set_history_key(epoch))
+}
+
 /// Count the number of consensus validators
 pub fn get_num_consensus_validators<S>(
     storage: &S,
@@ -837,6 +976,80 @@ where
     storage.write(&key, epoch)
 }
 
+/// Check if the rewards of the bond of the delegator-validator pair are
+/// restaked automatically
+pub fn is_auto_compound_enabled<S>(
+    storage: &S,
+    delegator: &Address,
+    validator: &Address,
+) -> namada_storage::Result<bool>
+where
+    S: StorageRead,
+{
+    let key = storage_key::auto_compound_key(delegator, validator);
+    storage.has_key(&key)
+}
+
+/// Enable or disable the automatic restaking of the rewards of the bond of the
+/// delegator-validator pair
+pub fn write_auto_compound<S>(
+    storage: &mut S,
+    delegator: &Address,
+    validator: &Address,
+    enabled: bool,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = storage_key::auto_compound_key(delegator, validator);
+    if enabled {
+        storage.write(&key, true)
+    } else {
+        storage.delete(&key)
+    }
+}
+
+/// Read the IDs of the bonds whose rewards are restaked automatically
+pub fn read_auto_compound_bonds<S>(
+    storage: &S,
+) -> namada_storage::Result<Vec<BondId>>
+where
+    S: StorageRead,
+{
+    let prefix = storage_key::auto_compound_prefix();
+    let mut bonds = Vec::new();
+    for entry in namada_storage::iter_prefix_bytes(storage, &prefix)? {
+        let (key, _value) = entry?;
+        if let Some(bond_id) = storage_key::is_auto_compound_key(&key) {
+            bonds.push(bond_id);
+        }
+    }
+    Ok(bonds)
+}
+
+/// Read the last bond whose rewards were restaked automatically
+pub fn read_last_auto_compound_bond<S>(
+    storage: &S,
+) -> namada_storage::Result<Option<BondId>>
+where
+    S: StorageRead,
+{
+    let key = storage_key::last_auto_compound_bond_key();
+    storage.read(&key)
+}
+
+/// Write the last bond whose rewards were restaked automatically
+pub fn write_last_auto_compound_bond<S>(
+    storage: &mut S,
+    bond_id: &BondId,
+) -> namada_storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = storage_key::last_auto_compound_bond_key();
+    storage.write(&key, bond_id)
+}
+
 /// Check if the given consensus key is already being used to ensure uniqueness.
 ///
 /// If it's not being used, it will be inserted into the set that's being used
@@ -1,6 +1,7 @@
 //! Proof-of-Stake storage keys and storage integration.
 
 use namada_core::types::address::Address;
+use namada_core::types::key_space::ModuleKeySpace;
 use namada_core::types::storage::{DbKeySeg, Epoch, Key, KeySeg};
 use namada_storage::collections::{lazy_map, lazy_vec};
 
@@ -27,6 +28,8 @@ const VALIDATOR_LAST_KNOWN_PRODUCT_EPOCH_KEY: &str =
 const SLASHES_PREFIX: &str = "slash";
 const ENQUEUED_SLASHES_KEY: &str = "enqueued_slashes";
 const VALIDATOR_LAST_SLASH_EPOCH: &str = "last_slash_epoch";
+const VALIDATOR_DEACTIVATION_REASON: &str = "deactivation_reason";
+const SLASH_BOUNTIES_KEY: &str = "slash_bounties";
 const BOND_STORAGE_KEY: &str = "bond";
 const UNBOND_STORAGE_KEY: &str = "unbond";
 const VALIDATOR_TOTAL_BONDED_STORAGE_KEY: &str = "total_bonded";
@@ -34,6 +37,7 @@ const VALIDATOR_TOTAL_UNBONDED_STORAGE_KEY: &str = "total_unbonded";
 const VALIDATOR_SETS_STORAGE_PREFIX: &str = "validator_sets";
 const CONSENSUS_VALIDATOR_SET_STORAGE_KEY: &str = "consensus";
 const BELOW_CAPACITY_VALIDATOR_SET_STORAGE_KEY: &str = "below_capacity";
+const VALIDATOR_SET_HISTORY_STORAGE_PREFIX: &str = "validator_set_history";
 const TOTAL_CONSENSUS_STAKE_STORAGE_KEY: &str = "total_consensus_stake";
 const TOTAL_DELTAS_STORAGE_KEY: &str = "total_deltas";
 const VALIDATOR_SET_POSITIONS_KEY: &str = "validator_set_positions";
@@ -43,6 +47,8 @@ const CONSENSUS_VALIDATOR_SET_ACCUMULATOR_STORAGE_KEY: &str =
     "validator_rewards_accumulator";
 const LAST_REWARD_CLAIM_EPOCH: &str = "last_reward_claim_epoch";
 const REWARDS_COUNTER_KEY: &str = "validator_rewards_commissions";
+const AUTO_COMPOUND_KEY: &str = "auto_compound";
+const LAST_AUTO_COMPOUND_BOND_KEY: &str = "last_auto_compound_bond";
 const VALIDATOR_INCOMING_REDELEGATIONS_KEY: &str = "incoming_redelegations";
 const VALIDATOR_OUTGOING_REDELEGATIONS_KEY: &str = "outgoing_redelegations";
 const VALIDATOR_TOTAL_REDELEGATED_BONDED_KEY: &str = "total_redelegated_bonded";
@@ -59,6 +65,42 @@ const LIVENESS_PREFIX: &str = "liveness";
 const LIVENESS_MISSED_VOTES: &str = "missed_votes";
 const LIVENESS_MISSED_VOTES_SUM: &str = "sum_missed_votes";
 
+/// The key prefixes reserved by PoS
+pub fn key_space() -> ModuleKeySpace {
+    ModuleKeySpace::new("proof_of_stake")
+        .guarded_by(ADDRESS)
+        .reserve_sub_keys(
+            &ADDRESS,
+            &[
+                PARAMS_STORAGE_KEY,
+                VALIDATOR_ADDRESSES_KEY,
+                VALIDATOR_STORAGE_PREFIX,
+                VALIDATOR_ADDRESS_RAW_HASH,
+                SLASHES_PREFIX,
+                ENQUEUED_SLASHES_KEY,
+                SLASH_BOUNTIES_KEY,
+                BOND_STORAGE_KEY,
+                UNBOND_STORAGE_KEY,
+                VALIDATOR_SETS_STORAGE_PREFIX,
+                VALIDATOR_SET_HISTORY_STORAGE_PREFIX,
+                TOTAL_CONSENSUS_STAKE_STORAGE_KEY,
+                TOTAL_DELTAS_STORAGE_KEY,
+                VALIDATOR_SET_POSITIONS_KEY,
+                CONSENSUS_KEYS,
+                LAST_BLOCK_PROPOSER_STORAGE_KEY,
+                CONSENSUS_VALIDATOR_SET_ACCUMULATOR_STORAGE_KEY,
+                LAST_REWARD_CLAIM_EPOCH,
+                REWARDS_COUNTER_KEY,
+                AUTO_COMPOUND_KEY,
+                LAST_AUTO_COMPOUND_BOND_KEY,
+                DELEGATOR_REDELEGATED_BONDS_KEY,
+                DELEGATOR_REDELEGATED_UNBONDS_KEY,
+                LIVENESS_PREFIX,
+            ],
+            "Validators, bonds, slashes and rewards",
+        )
+}
+
 /// Is the given key a PoS storage key?
 pub fn is_pos_key(key: &Key) -> bool {
     match &key.segments.get(0) {
@@ -338,6 +380,48 @@ pub fn is_rewards_counter_key(key: &Key) -> Option<BondId> {
     }
 }
 
+/// Storage prefix for the bonds whose rewards are restaked automatically.
+pub fn auto_compound_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&AUTO_COMPOUND_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the flag that enables the automatic restaking of the
+/// rewards of a bond.
+pub fn auto_compound_key(source: &Address, validator: &Address) -> Key {
+    auto_compound_prefix()
+        .push(&source.to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&validator.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is the storage key for the auto-compounding flag of a bond? Return the
+/// bond ID if so.
+pub fn is_auto_compound_key(key: &Key) -> Option<BondId> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(key),
+            DbKeySeg::AddressSeg(source),
+            DbKeySeg::AddressSeg(validator),
+        ] if addr == &ADDRESS && key == AUTO_COMPOUND_KEY => Some(BondId {
+            source: source.clone(),
+            validator: validator.clone(),
+        }),
+        _ => None,
+    }
+}
+
+/// Storage key for the last bond whose rewards were restaked automatically,
+/// from which the next epoch's batch continues.
+pub fn last_auto_compound_bond_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&LAST_AUTO_COMPOUND_BOND_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Storage key for a validator's incoming redelegations, where the prefixed
 /// validator is the destination validator.
 pub fn validator_incoming_redelegations_key(validator: &Address) -> Key {
@@ -596,6 +680,13 @@ pub fn enqueued_slashes_key() -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Storage key for the submitters of the evidence of misbehaviors.
+pub fn slash_bounties_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&SLASH_BOUNTIES_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Storage key for validator's slashes.
 pub fn validator_slashes_key(validator: &Address) -> Key {
     slashes_prefix()
@@ -634,6 +725,14 @@ pub fn validator_last_slash_key(validator: &Address) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Storage key for the reason for which a validator has been deactivated by
+/// the protocol
+pub fn validator_deactivation_reason_key(validator: &Address) -> Key {
+    validator_prefix(validator)
+        .push(&VALIDATOR_DEACTIVATION_REASON.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Storage key prefix for all bonds.
 pub fn bonds_prefix() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -841,6 +940,38 @@ pub fn is_below_capacity_validator_set_key(key: &Key) -> bool {
     matches!(&key.segments[..], [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key), DbKeySeg::StringSeg(set_type), DbKeySeg::StringSeg(lazy_map), DbKeySeg::StringSeg(data), DbKeySeg::StringSeg(_epoch), DbKeySeg::StringSeg(_), DbKeySeg::StringSeg(_amount), DbKeySeg::StringSeg(_), DbKeySeg::StringSeg(_position)] if addr == &ADDRESS && key == VALIDATOR_SETS_STORAGE_PREFIX && set_type == BELOW_CAPACITY_VALIDATOR_SET_STORAGE_KEY && lazy_map == epoched::LAZY_MAP_SUB_KEY && data == lazy_map::DATA_SUBKEY)
 }
 
+/// Storage prefix for the history of the consensus validator sets.
+pub fn validator_set_history_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&VALIDATOR_SET_HISTORY_STORAGE_PREFIX.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key for the consensus validator set of an epoch in the history of
+/// the validator sets.
+pub fn validator_set_history_key(epoch: Epoch) -> Key {
+    validator_set_history_prefix()
+        .push(&epoch)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Is storage key for the consensus validator set of an epoch in the history
+/// of the validator sets? Returns the epoch if so.
+pub fn is_validator_set_history_key(key: &Key) -> Option<Epoch> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(epoch),
+        ] if addr == &ADDRESS
+            && prefix == VALIDATOR_SET_HISTORY_STORAGE_PREFIX =>
+        {
+            Epoch::parse(epoch.clone()).ok()
+        }
+        _ => None,
+    }
+}
+
 /// Storage key for total consensus stake
 pub fn total_consensus_stake_key() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -1,7 +1,8 @@
 use std::collections::{BTreeMap, BTreeSet};
 
 use namada_core::types::address::testing::{
-    established_address_1, established_address_2, established_address_3,
+    address_from_simple_seed, established_address_1, established_address_2,
+    established_address_3,
 };
 use namada_core::types::dec::Dec;
 use namada_core::types::storage::{Epoch, Key};
@@ -10,6 +11,9 @@ use namada_state::testing::TestWlStorage;
 use namada_storage::collections::lazy_map::NestedMap;
 use namada_storage::collections::LazyCollection;
 
+use crate::rewards::{
+    next_auto_compound_bonds, MAX_AUTO_COMPOUND_BONDS_PER_EPOCH,
+};
 use crate::slashing::{
     apply_list_slashes, compute_amount_after_slashing_unbond,
     compute_amount_after_slashing_withdraw, compute_bond_at_epoch,
@@ -20,10 +24,11 @@ use crate::storage::{
     bond_handle, delegator_redelegated_bonds_handle, total_bonded_handle,
     total_unbonded_handle, validator_outgoing_redelegations_handle,
     validator_slashes_handle, validator_total_redelegated_bonded_handle,
-    validator_total_redelegated_unbonded_handle, write_pos_params,
+    validator_total_redelegated_unbonded_handle, write_auto_compound,
+    write_pos_params,
 };
 use crate::types::{
-    EagerRedelegatedBondsMap, RedelegatedTokens, Slash, SlashType,
+    BondId, EagerRedelegatedBondsMap, RedelegatedTokens, Slash, SlashType,
 };
 use crate::{
     compute_modified_redelegation, compute_new_redelegated_unbonds,
@@ -2036,3 +2041,43 @@ fn test_from_sm_case_1() {
 
     pretty_assertions::assert_eq!(mr, exp_mr);
 }
+
+#[test]
+fn test_next_auto_compound_bonds() {
+    let mut storage = TestWlStorage::default();
+    let validator = established_address_1();
+
+    // Opt more bonds into auto-compounding than can be restaked in an epoch
+    let num_bonds = MAX_AUTO_COMPOUND_BONDS_PER_EPOCH + 10;
+    let mut bonds: Vec<BondId> = (0..num_bonds as u64)
+        .map(|seed| BondId {
+            source: address_from_simple_seed(seed),
+            validator: validator.clone(),
+        })
+        .collect();
+    for BondId { source, validator } in &bonds {
+        write_auto_compound(&mut storage, source, validator, true).unwrap();
+    }
+    bonds.sort();
+
+    // The first batch is capped
+    let batch = next_auto_compound_bonds(&mut storage).unwrap();
+    assert_eq!(batch, bonds[..MAX_AUTO_COMPOUND_BONDS_PER_EPOCH]);
+
+    // The next batch continues after the last one and wraps around
+    let batch = next_auto_compound_bonds(&mut storage).unwrap();
+    let expected: Vec<BondId> = bonds[MAX_AUTO_COMPOUND_BONDS_PER_EPOCH..]
+        .iter()
+        .chain(&bonds[..MAX_AUTO_COMPOUND_BONDS_PER_EPOCH - 10])
+        .cloned()
+        .collect();
+    assert_eq!(batch, expected);
+
+    // Opting out the bond after the last restaked one doesn't reset the
+    // cursor
+    let next = &bonds[MAX_AUTO_COMPOUND_BONDS_PER_EPOCH - 10];
+    write_auto_compound(&mut storage, &next.source, &next.validator, false)
+        .unwrap();
+    let batch = next_auto_compound_bonds(&mut storage).unwrap();
+    assert_eq!(batch[0], bonds[MAX_AUTO_COMPOUND_BONDS_PER_EPOCH - 9]);
+}
@@ -19,19 +19,24 @@ use proptest::test_runner::Config;
 // `tracing` logs from tests
 use test_log::test;
 
+use crate::epoched::{
+    EpochOffset, OffsetMaxProposalPeriodOrSlashProcessingLenPlus,
+    OffsetMaxProposalPeriodPlusOrHistory,
+};
 use crate::parameters::testing::arb_pos_params;
 use crate::parameters::OwnedPosParams;
-use crate::queries::bonds_and_unbonds;
+use crate::queries::{bonds_and_unbonds, bonds_and_unbonds_at_epoch};
 use crate::rewards::{
     log_block_rewards, update_rewards_products_and_mint_inflation,
     PosRewardsCalculator,
 };
-use crate::slashing::{process_slashes, slash};
+use crate::slashing::{process_slashes, record_slash_bounty, slash};
 use crate::storage::{
     get_consensus_key_set, read_below_threshold_validator_set_addresses,
     read_consensus_validator_set_addresses_with_stake, read_total_stake,
-    read_validator_deltas_value, rewards_accumulator_handle,
-    total_deltas_handle,
+    read_validator_deactivation_reason, read_validator_deltas_value,
+    read_validator_set_history, rewards_accumulator_handle,
+    slash_bounties_handle, total_deltas_handle, write_validator_set_history,
 };
 use crate::test_utils::test_init_genesis;
 use crate::tests::helpers::{
@@ -40,16 +45,17 @@ use crate::tests::helpers::{
 use crate::token::{credit_tokens, read_balance};
 use crate::types::{
     into_tm_voting_power, BondDetails, BondId, BondsAndUnbondsDetails,
-    GenesisValidator, SlashType, UnbondDetails, ValidatorState, VoteInfo,
-    WeightedValidator,
+    DeactivationReason, GenesisValidator, SlashType, UnbondDetails,
+    ValidatorState, VoteInfo, WeightedValidator,
 };
 use crate::{
     below_capacity_validator_set_handle, bond_handle, bond_tokens,
     change_consensus_key, consensus_validator_set_handle, is_delegator,
-    is_validator, read_validator_stake, redelegate_tokens,
-    staking_token_address, token, unbond_handle, unbond_tokens,
-    unjail_validator, validator_consensus_key_handle,
-    validator_set_positions_handle, validator_state_handle, withdraw_tokens,
+    is_validator, reactivate_validator, read_validator_stake,
+    redelegate_tokens, staking_token_address, token, unbond_handle,
+    unbond_tokens, unjail_validator, update_validators_below_min_self_bond,
+    validator_consensus_key_handle, validator_set_positions_handle,
+    validator_state_handle, withdraw_tokens,
 };
 
 proptest! {
@@ -181,6 +187,71 @@ proptest! {
 }
 
 /// Test genesis initialization
+proptest! {
+    // Generate arb valid input for `test_slash_evidence_bounty_aux`
+    #![proptest_config(Config {
+        cases: 1,
+        .. Config::default()
+    })]
+    #[test]
+    fn test_slash_evidence_bounty(
+
+    genesis_validators in arb_genesis_validators(4..5, None),
+
+    ) {
+        test_slash_evidence_bounty_aux(genesis_validators)
+    }
+}
+
+proptest! {
+    // Generate arb valid input for `test_min_self_bond_aux`
+    #![proptest_config(Config {
+        cases: 5,
+        .. Config::default()
+    })]
+    #[test]
+    fn test_min_self_bond(
+
+    genesis_validators in arb_genesis_validators(2..5, None),
+
+    ) {
+        test_min_self_bond_aux(genesis_validators)
+    }
+}
+
+proptest! {
+    // Generate arb valid input for `test_stake_history_aux`
+    #![proptest_config(Config {
+        cases: 1,
+        .. Config::default()
+    })]
+    #[test]
+    fn test_stake_history(
+
+    genesis_validators in arb_genesis_validators(1..4, None),
+
+    ) {
+        test_stake_history_aux(genesis_validators)
+    }
+}
+
+proptest! {
+    // Generate arb valid input for `test_validator_set_history_aux`
+    #![proptest_config(Config {
+        cases: 10,
+        .. Config::default()
+    })]
+    #[test]
+    fn test_validator_set_history(
+
+    (pos_params, genesis_validators) in arb_params_and_genesis_validators(Some(5), 1..10),
+    start_epoch in (0_u64..1000).prop_map(Epoch),
+
+    ) {
+        test_validator_set_history_aux(pos_params, start_epoch, genesis_validators)
+    }
+}
+
 fn test_test_init_genesis_aux(
     params: OwnedPosParams,
     start_epoch: Epoch,
@@ -1652,3 +1723,457 @@ fn test_is_delegator_aux(mut validators: Vec<GenesisValidator>) {
         .unwrap()
     );
 }
+
+fn test_slash_evidence_bounty_aux(mut validators: Vec<GenesisValidator>) {
+    validators.sort_by(|a, b| b.tokens.cmp(&a.tokens));
+    let val_addr = validators[0].address.clone();
+
+    let mut storage = TestWlStorage::default();
+    let params = OwnedPosParams {
+        unbonding_len: 4,
+        ..Default::default()
+    };
+
+    // Genesis
+    let mut current_epoch = storage.storage.block.epoch;
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        validators.clone().into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    storage.commit_block().unwrap();
+    let staking_token = staking_token_address(&storage);
+
+    current_epoch = advance_epoch(&mut storage, &params);
+    process_slashes(&mut storage, current_epoch).unwrap();
+
+    // Slash the validator on the evidence submitted by some account
+    let submitter = address::testing::gen_implicit_address();
+    let evidence_epoch = current_epoch;
+    slash(
+        &mut storage,
+        &params,
+        current_epoch,
+        evidence_epoch,
+        BlockHeight(1),
+        SlashType::DuplicateVoteExtension,
+        &val_addr,
+        current_epoch.next(),
+    )
+    .unwrap();
+    assert!(
+        record_slash_bounty(
+            &mut storage,
+            &params,
+            evidence_epoch,
+            &val_addr,
+            &submitter,
+        )
+        .unwrap()
+    );
+    // The same misbehavior can't be paid twice
+    assert!(
+        !record_slash_bounty(
+            &mut storage,
+            &params,
+            evidence_epoch,
+            &val_addr,
+            &address::testing::gen_implicit_address(),
+        )
+        .unwrap()
+    );
+
+    // Advance to the epoch in which the slash is processed
+    let processing_epoch =
+        evidence_epoch + params.slash_processing_epoch_offset();
+    while current_epoch < processing_epoch {
+        current_epoch = advance_epoch(&mut storage, &params);
+        if current_epoch < processing_epoch {
+            process_slashes(&mut storage, current_epoch).unwrap();
+            assert!(
+                read_balance(&storage, &staking_token, &submitter)
+                    .unwrap()
+                    .is_zero()
+            );
+        }
+    }
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let stake_pre =
+        read_validator_stake(&storage, &params, &val_addr, pipeline_epoch)
+            .unwrap();
+    process_slashes(&mut storage, current_epoch).unwrap();
+    let stake_post =
+        read_validator_stake(&storage, &params, &val_addr, pipeline_epoch)
+            .unwrap();
+
+    // The submitter is paid a part of the slashed tokens
+    let slashed = stake_pre - stake_post;
+    assert!(!slashed.is_zero());
+    assert_eq!(
+        read_balance(&storage, &staking_token, &submitter).unwrap(),
+        params.slash_evidence_bounty_rate * slashed
+    );
+    assert!(
+        slash_bounties_handle()
+            .at(&processing_epoch)
+            .is_empty(&storage)
+            .unwrap()
+    );
+}
+
+fn test_min_self_bond_aux(mut validators: Vec<GenesisValidator>) {
+    validators.sort_by(|a, b| b.tokens.cmp(&a.tokens));
+    // The validator with the smallest self-bond is exactly at the minimum
+    let validator = validators.last().unwrap().address.clone();
+    let min_self_bond = validators.last().unwrap().tokens;
+
+    let mut storage = TestWlStorage::default();
+    let params = OwnedPosParams {
+        min_self_bond,
+        ..Default::default()
+    };
+
+    // Genesis
+    let mut current_epoch = storage.storage.block.epoch;
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        validators.clone().into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    storage.commit_block().unwrap();
+    let staking_token = staking_token_address(&storage);
+
+    current_epoch = advance_epoch(&mut storage, &params);
+    let updates = update_validators_below_min_self_bond(
+        &mut storage,
+        &params,
+        current_epoch,
+    )
+    .unwrap();
+    assert!(updates.is_empty());
+
+    // Unbond some of the self-bond below the minimum
+    let amount = token::Amount::from(1_u64);
+    unbond_tokens(&mut storage, None, &validator, amount, current_epoch, false)
+        .unwrap();
+
+    // The validator is deactivated at the pipeline epoch of the next epoch
+    current_epoch = advance_epoch(&mut storage, &params);
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let updates = update_validators_below_min_self_bond(
+        &mut storage,
+        &params,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(updates, vec![(validator.clone(), ValidatorState::Inactive)]);
+    assert_eq!(
+        validator_state_handle(&validator)
+            .get(&storage, pipeline_epoch, &params)
+            .unwrap(),
+        Some(ValidatorState::Inactive)
+    );
+    assert_eq!(
+        read_validator_deactivation_reason(&storage, &validator).unwrap(),
+        Some(DeactivationReason::SelfBondBelowMinimum)
+    );
+
+    // Top up the self-bond
+    credit_tokens(&mut storage, &staking_token, &validator, amount).unwrap();
+    bond_tokens(&mut storage, None, &validator, amount, current_epoch, None)
+        .unwrap();
+
+    // The validator can't be reactivated before the deactivation is effective
+    current_epoch = advance_epoch(&mut storage, &params);
+    let updates = update_validators_below_min_self_bond(
+        &mut storage,
+        &params,
+        current_epoch,
+    )
+    .unwrap();
+    assert!(updates.is_empty());
+
+    // The validator is reactivated once it's inactive up to the pipeline
+    // epoch
+    while validator_state_handle(&validator)
+        .get(&storage, current_epoch, &params)
+        .unwrap()
+        != Some(ValidatorState::Inactive)
+    {
+        current_epoch = advance_epoch(&mut storage, &params);
+    }
+    let pipeline_epoch = current_epoch + params.pipeline_len;
+    let updates = update_validators_below_min_self_bond(
+        &mut storage,
+        &params,
+        current_epoch,
+    )
+    .unwrap();
+    assert_eq!(updates.len(), 1);
+    assert_eq!(updates[0].0, validator);
+    assert_ne!(updates[0].1, ValidatorState::Inactive);
+    assert_eq!(
+        validator_state_handle(&validator)
+            .get(&storage, pipeline_epoch, &params)
+            .unwrap(),
+        Some(updates[0].1)
+    );
+    assert_eq!(
+        read_validator_deactivation_reason(&storage, &validator).unwrap(),
+        None
+    );
+
+    // A validator can't reactivate itself with a self-bond below the minimum
+    unbond_tokens(&mut storage, None, &validator, amount, current_epoch, false)
+        .unwrap();
+    current_epoch = advance_epoch(&mut storage, &params);
+    update_validators_below_min_self_bond(&mut storage, &params, current_epoch)
+        .unwrap();
+    while validator_state_handle(&validator)
+        .get(&storage, current_epoch, &params)
+        .unwrap()
+        != Some(ValidatorState::Inactive)
+    {
+        current_epoch = advance_epoch(&mut storage, &params);
+    }
+    assert!(
+        reactivate_validator(&mut storage, &validator, current_epoch).is_err()
+    );
+}
+
+fn test_stake_history_aux(validators: Vec<GenesisValidator>) {
+    let validator = validators[0].address.clone();
+
+    let mut storage = TestWlStorage::default();
+    let params = OwnedPosParams {
+        stake_history_len: 50,
+        ..Default::default()
+    };
+
+    // Genesis
+    let mut current_epoch = storage.storage.block.epoch;
+    let params = test_init_genesis(
+        &mut storage,
+        params,
+        validators.clone().into_iter(),
+        current_epoch,
+    )
+    .unwrap();
+    storage.commit_block().unwrap();
+    let staking_token = staking_token_address(&storage);
+
+    // Delegate to the validator
+    let delegator = address::testing::gen_implicit_address();
+    let amount = token::Amount::native_whole(100);
+    credit_tokens(&mut storage, &staking_token, &delegator, amount).unwrap();
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator,
+        amount,
+        current_epoch,
+        None,
+    )
+    .unwrap();
+    let bond_epoch = current_epoch + params.pipeline_len;
+    let stake_before_bond =
+        read_validator_stake(&storage, &params, &validator, current_epoch)
+            .unwrap();
+    let stake_at_bond =
+        read_validator_stake(&storage, &params, &validator, bond_epoch)
+            .unwrap();
+    let total_stake_at_bond =
+        read_total_stake(&storage, &params, bond_epoch).unwrap();
+
+    // Unbond some of the delegation once it's active
+    while current_epoch < bond_epoch {
+        current_epoch = advance_epoch(&mut storage, &params);
+    }
+    let unbond_amount = token::Amount::native_whole(40);
+    unbond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator,
+        unbond_amount,
+        current_epoch,
+        false,
+    )
+    .unwrap();
+    let unbond_epoch = current_epoch + params.pipeline_len;
+
+    // Advance beyond the epochs needed by the protocol and update the stake
+    // to trim the old data
+    while current_epoch < bond_epoch + params.stake_history_len - 1 {
+        current_epoch = advance_epoch(&mut storage, &params);
+    }
+    assert!(
+        current_epoch
+            > bond_epoch
+                + OffsetMaxProposalPeriodOrSlashProcessingLenPlus::value(
+                    &params
+                )
+    );
+    let top_up = token::Amount::native_whole(1);
+    credit_tokens(&mut storage, &staking_token, &delegator, top_up).unwrap();
+    bond_tokens(
+        &mut storage,
+        Some(&delegator),
+        &validator,
+        top_up,
+        current_epoch,
+        None,
+    )
+    .unwrap();
+
+    // The stake is still available at the past epochs
+    assert_eq!(
+        read_validator_stake(
+            &storage,
+            &params,
+            &validator,
+            bond_epoch.prev()
+        )
+        .unwrap(),
+        stake_before_bond
+    );
+    assert_eq!(
+        read_validator_stake(&storage, &params, &validator, bond_epoch)
+            .unwrap(),
+        stake_at_bond
+    );
+    assert_eq!(
+        read_total_stake(&storage, &params, bond_epoch).unwrap(),
+        total_stake_at_bond
+    );
+
+    // The bonds at the bond epoch include the tokens unbonded since
+    let bond_id = BondId {
+        source: delegator.clone(),
+        validator: validator.clone(),
+    };
+    let details = bonds_and_unbonds_at_epoch(
+        &storage,
+        Some(delegator.clone()),
+        Some(validator.clone()),
+        bond_epoch,
+    )
+    .unwrap();
+    let detail = &details[&bond_id];
+    assert_eq!(
+        detail
+            .bonds
+            .iter()
+            .map(|bond| bond.amount)
+            .sum::<token::Amount>(),
+        amount
+    );
+    assert!(detail.unbonds.is_empty());
+
+    // The unbond is reported from the epoch in which it stopped contributing
+    // to the stake
+    let details = bonds_and_unbonds_at_epoch(
+        &storage,
+        Some(delegator),
+        Some(validator),
+        unbond_epoch,
+    )
+    .unwrap();
+    let detail = &details[&bond_id];
+    assert_eq!(
+        detail
+            .bonds
+            .iter()
+            .map(|bond| bond.amount)
+            .sum::<token::Amount>(),
+        amount - unbond_amount
+    );
+    assert_eq!(
+        detail
+            .unbonds
+            .iter()
+            .map(|unbond| unbond.amount)
+            .sum::<token::Amount>(),
+        unbond_amount
+    );
+}
+
+fn test_validator_set_history_aux(
+    params: OwnedPosParams,
+    start_epoch: Epoch,
+    mut validators: Vec<GenesisValidator>,
+) {
+    let mut s = TestWlStorage::default();
+    s.storage.block.epoch = start_epoch;
+
+    validators.sort_by(|a, b| b.tokens.cmp(&a.tokens));
+    let params = test_init_genesis(
+        &mut s,
+        params,
+        validators.clone().into_iter(),
+        start_epoch,
+    )
+    .unwrap();
+
+    // The history of the validator sets records the consensus set
+    write_validator_set_history(&mut s, &params, start_epoch).unwrap();
+    let history = read_validator_set_history(&s, start_epoch)
+        .unwrap()
+        .unwrap();
+    let consensus_set =
+        read_consensus_validator_set_addresses_with_stake(&s, start_epoch)
+            .unwrap();
+    assert_eq!(history.len(), consensus_set.len());
+    assert!(
+        history
+            .windows(2)
+            .all(|pair| pair[0].bonded_stake >= pair[1].bonded_stake)
+    );
+    for validator in &history {
+        assert!(consensus_set.contains(&WeightedValidator {
+            bonded_stake: validator.bonded_stake,
+            address: validator.address.clone(),
+        }));
+        assert_eq!(
+            validator.voting_power,
+            into_tm_voting_power(
+                params.tm_votes_per_token,
+                validator.bonded_stake
+            )
+        );
+        assert_eq!(
+            Some(&validator.consensus_key),
+            validator_consensus_key_handle(&validator.address)
+                .get(&s, start_epoch, &params)
+                .unwrap()
+                .as_ref()
+        );
+    }
+
+    // The history is pruned past the epochs kept for the consensus
+    // validator sets
+    let num_past_epochs = OffsetMaxProposalPeriodPlusOrHistory::value(&params);
+    let mut current_epoch = start_epoch;
+    while current_epoch <= start_epoch + num_past_epochs {
+        current_epoch = advance_epoch(&mut s, &params);
+        write_validator_set_history(&mut s, &params, current_epoch).unwrap();
+        assert!(
+            read_validator_set_history(&s, current_epoch)
+                .unwrap()
+                .is_some()
+        );
+    }
+    assert!(
+        read_validator_set_history(&s, start_epoch)
+            .unwrap()
+            .is_none()
+    );
+    assert!(
+        read_validator_set_history(&s, start_epoch.next())
+            .unwrap()
+            .is_some()
+    );
+}
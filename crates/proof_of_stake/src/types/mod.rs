@@ -80,14 +80,14 @@ pub type BelowCapacityValidatorSet =
 pub type ConsensusValidatorSets = crate::epoched::NestedEpoched<
     ConsensusValidatorSet,
     crate::epoched::OffsetPipelineLen,
-    crate::epoched::OffsetMaxProposalPeriodPlus,
+    crate::epoched::OffsetMaxProposalPeriodPlusOrHistory,
 >;
 
 /// Epoched below-capacity validator sets.
 pub type BelowCapacityValidatorSets = crate::epoched::NestedEpoched<
     BelowCapacityValidatorSet,
     crate::epoched::OffsetPipelineLen,
-    crate::epoched::OffsetDefaultNumPastEpochs,
+    crate::epoched::OffsetStakeHistoryLen,
 >;
 
 /// Epoched total consensus validator set stake
@@ -101,14 +101,14 @@ pub type TotalConsensusStakes = crate::epoched::Epoched<
 pub type ValidatorDeltas = crate::epoched::EpochedDelta<
     token::Change,
     crate::epoched::OffsetPipelineLen,
-    crate::epoched::OffsetMaxProposalPeriodOrSlashProcessingLenPlus,
+    crate::epoched::OffsetMaxProposalPeriodOrSlashProcessingLenPlusOrHistory,
 >;
 
 /// Epoched total deltas.
 pub type TotalDeltas = crate::epoched::EpochedDelta<
     token::Change,
     crate::epoched::OffsetPipelineLen,
-    crate::epoched::OffsetMaxProposalPeriodOrSlashProcessingLenPlus,
+    crate::epoched::OffsetMaxProposalPeriodOrSlashProcessingLenPlusOrHistory,
 >;
 
 /// Epoched validator commission rate
@@ -146,6 +146,11 @@ pub type EpochedSlashes = crate::epoched::NestedEpoched<
     crate::epoched::OffsetSlashProcessingLenPlus,
 >;
 
+/// The submitters of the evidence of misbehaviors, who are paid a bounty from
+/// the slashed tokens. The outer epoch key is the epoch in which the slash is
+/// processed and the inner key is the slashed validator.
+pub type SlashBounties = NestedMap<Epoch, LazyMap<Address, Address>>;
+
 /// Epoched validator's unbonds
 ///
 /// The map keys from outside in are:
@@ -451,6 +456,26 @@ impl Display for WeightedValidator {
     }
 }
 
+/// A member of the consensus validator set of an epoch, as recorded in the
+/// history of the validator sets.
+#[derive(
+    Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize, BorshSchema,
+)]
+pub struct HistoricalValidator {
+    /// Validator's address
+    pub address: Address,
+    /// Validator's bonded stake in the epoch
+    pub bonded_stake: token::Amount,
+    /// Validator's voting power in CometBFT in the epoch
+    pub voting_power: i64,
+    /// Validator's consensus key in the epoch
+    pub consensus_key: common::PublicKey,
+    /// Validator's Ethereum hot key in the epoch
+    pub eth_hot_key: Option<common::PublicKey>,
+    /// Validator's Ethereum cold key in the epoch
+    pub eth_cold_key: Option<common::PublicKey>,
+}
+
 /// A position in a validator set
 #[derive(
     PartialEq,
@@ -532,6 +557,73 @@ pub enum ValidatorState {
     Jailed,
 }
 
+impl Display for ValidatorState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidatorState::Consensus => write!(f, "consensus"),
+            ValidatorState::BelowCapacity => write!(f, "below_capacity"),
+            ValidatorState::BelowThreshold => write!(f, "below_threshold"),
+            ValidatorState::Inactive => write!(f, "inactive"),
+            ValidatorState::Jailed => write!(f, "jailed"),
+        }
+    }
+}
+
+/// The reason for which a validator has been deactivated by the protocol
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+)]
+pub enum DeactivationReason {
+    /// The validator's self-bond is below the `min_self_bond` parameter. It's
+    /// reactivated once its self-bond is topped up.
+    SelfBondBelowMinimum,
+}
+
+impl Display for DeactivationReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeactivationReason::SelfBondBelowMinimum => {
+                write!(f, "self_bond_below_minimum")
+            }
+        }
+    }
+}
+
+/// The reason for which a validator deactivated by the protocol has been
+/// reactivated
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    BorshDeserialize,
+    BorshSerialize,
+    BorshSchema,
+    PartialEq,
+    Eq,
+)]
+pub enum ReactivationReason {
+    /// The validator's self-bond has been topped up to the `min_self_bond`
+    /// parameter.
+    SelfBondToppedUp,
+}
+
+impl Display for ReactivationReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReactivationReason::SelfBondToppedUp => {
+                write!(f, "self_bond_topped_up")
+            }
+        }
+    }
+}
+
 /// A slash applied to validator, to punish byzantine behavior by removing
 /// their staked tokens at and before the epoch of the slash.
 #[derive(
@@ -580,6 +672,8 @@ pub enum SlashType {
     DuplicateVote,
     /// Light client attack.
     LightClientAttack,
+    /// Conflicting vote extensions signed at the same block height.
+    DuplicateVoteExtension,
 }
 
 /// VoteInfo inspired from tendermint for validators whose signature was
@@ -649,6 +743,20 @@ pub struct UnbondDetails {
     pub slashed_amount: Option<token::Amount>,
 }
 
+/// Estimated rewards rate of a bond, based on the rewards product of the last
+/// epoch
+#[derive(
+    Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq,
+)]
+pub struct RewardsRate {
+    /// Whether the rewards of the bond are restaked automatically
+    pub auto_compound: bool,
+    /// Nominal annual rate, without compounding
+    pub apr: Dec,
+    /// Effective annual yield when the rewards are restaked at every epoch
+    pub apy: Dec,
+}
+
 impl Display for BondId {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -664,7 +772,9 @@ impl SlashType {
     /// parameters.
     pub fn get_slash_rate(&self, params: &PosParams) -> Dec {
         match self {
-            SlashType::DuplicateVote => params.duplicate_vote_min_slash_rate,
+            SlashType::DuplicateVote | SlashType::DuplicateVoteExtension => {
+                params.duplicate_vote_min_slash_rate
+            }
             SlashType::LightClientAttack => {
                 params.light_client_attack_min_slash_rate
             }
@@ -677,6 +787,9 @@ impl Display for SlashType {
         match self {
             SlashType::DuplicateVote => write!(f, "Duplicate vote"),
             SlashType::LightClientAttack => write!(f, "Light client attack"),
+            SlashType::DuplicateVoteExtension => {
+                write!(f, "Duplicate vote extension")
+            }
         }
     }
 }
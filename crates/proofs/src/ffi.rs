@@ -0,0 +1,120 @@
+//! C ABI of the proof verification, for light clients that are not written in
+//! Rust. The functions return `0` when the proof is valid, `1` when it is
+//! invalid and `-1` when the arguments are invalid.
+
+use core::slice;
+
+use crate::{StorageProof, StoreType};
+
+/// The store type argument of the [`StoreType::Account`] sub-tree
+pub const STORE_ACCOUNT: u8 = 0;
+/// The store type argument of the [`StoreType::Ibc`] sub-tree
+pub const STORE_IBC: u8 = 1;
+/// The store type argument of the [`StoreType::PoS`] sub-tree
+pub const STORE_POS: u8 = 2;
+
+fn store_type(store: u8) -> Option<StoreType> {
+    match store {
+        STORE_ACCOUNT => Some(StoreType::Account),
+        STORE_IBC => Some(StoreType::Ibc),
+        STORE_POS => Some(StoreType::PoS),
+        _ => None,
+    }
+}
+
+/// Build a slice from a pointer and a length, allowing a null pointer for an
+/// empty slice.
+///
+/// # Safety
+///
+/// A non-null `ptr` must be valid for reads of `len` bytes.
+unsafe fn bytes<'a>(ptr: *const u8, len: usize) -> Option<&'a [u8]> {
+    if ptr.is_null() {
+        (len == 0).then_some(&[][..])
+    } else {
+        Some(slice::from_raw_parts(ptr, len))
+    }
+}
+
+/// Verify that the key has the value in the storage committed to by the
+/// root. See [`StorageProof::verify_membership`].
+///
+/// # Safety
+///
+/// Every non-null pointer must be valid for reads of its length in bytes.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn namada_verify_membership(
+    sub_proof: *const u8,
+    sub_proof_len: usize,
+    base_proof: *const u8,
+    base_proof_len: usize,
+    root: *const u8,
+    root_len: usize,
+    store: u8,
+    key: *const u8,
+    key_len: usize,
+    value: *const u8,
+    value_len: usize,
+) -> i32 {
+    let args = (|| {
+        Some((
+            bytes(sub_proof, sub_proof_len)?,
+            bytes(base_proof, base_proof_len)?,
+            bytes(root, root_len)?,
+            store_type(store)?,
+            bytes(key, key_len)?,
+            bytes(value, value_len)?,
+        ))
+    })();
+    let Some((sub_proof, base_proof, root, store, key, value)) = args else {
+        return -1;
+    };
+    let Ok(proof) = StorageProof::decode(sub_proof, base_proof) else {
+        return -1;
+    };
+    match proof.verify_membership(root, store, key, value) {
+        Ok(()) => 0,
+        Err(_) => 1,
+    }
+}
+
+/// Verify that the key is not in the storage committed to by the root. See
+/// [`StorageProof::verify_non_membership`].
+///
+/// # Safety
+///
+/// Every non-null pointer must be valid for reads of its length in bytes.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn namada_verify_non_membership(
+    sub_proof: *const u8,
+    sub_proof_len: usize,
+    base_proof: *const u8,
+    base_proof_len: usize,
+    root: *const u8,
+    root_len: usize,
+    store: u8,
+    key: *const u8,
+    key_len: usize,
+) -> i32 {
+    let args = (|| {
+        Some((
+            bytes(sub_proof, sub_proof_len)?,
+            bytes(base_proof, base_proof_len)?,
+            bytes(root, root_len)?,
+            store_type(store)?,
+            bytes(key, key_len)?,
+        ))
+    })();
+    let Some((sub_proof, base_proof, root, store, key)) = args else {
+        return -1;
+    };
+    let Ok(proof) = StorageProof::decode(sub_proof, base_proof) else {
+        return -1;
+    };
+    match proof.verify_non_membership(root, store, key) {
+        Ok(()) => 0,
+        Err(_) => 1,
+    }
+}
@@ -0,0 +1,225 @@
+//! Verification of the storage proofs of Namada.
+//!
+//! The storage is committed to by a base merkle tree whose leaves are the
+//! roots of the storage sub-trees, and whose root is the app hash of a block.
+//! A proof of a storage key, as returned by the storage queries of the node,
+//! consists of two ICS23 commitment proofs, ordered from the leaf to the root:
+//! the proof of the key in its sub-tree and the proof of the sub-tree root in
+//! the base tree.
+//!
+//! This crate only depends on `ics23` and `prost` and supports `no_std`, so
+//! that light clients can verify the proofs without depending on the node.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod specs;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use ics23::commitment_proof::Proof as Ics23Proof;
+use ics23::{CommitmentProof, ExistenceProof, HostFunctionsManager, ProofSpec};
+use prost::Message;
+use specs::STORAGE_HASH_OP;
+
+#[allow(missing_docs)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// A commitment proof couldn't be decoded
+    Decode(prost::DecodeError),
+    /// A commitment proof is not of the expected kind
+    UnexpectedProof(&'static str),
+    /// The root of the sub-tree couldn't be computed from the proof
+    SubTreeRoot(String),
+    /// Only the keys of the IBC sub-tree have non-existence proofs
+    NonExistenceProof(StoreType),
+    /// The proof of the key in its sub-tree is invalid
+    InvalidSubTreeProof,
+    /// The proof of the sub-tree root in the base tree is invalid
+    InvalidBaseTreeProof,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Decode(err) => {
+                write!(f, "Failed to decode a commitment proof: {err}")
+            }
+            Error::UnexpectedProof(expected) => {
+                write!(f, "Expected {expected} proof")
+            }
+            Error::SubTreeRoot(err) => {
+                write!(f, "Failed to compute the sub-tree root: {err}")
+            }
+            Error::NonExistenceProof(store) => write!(
+                f,
+                "The {store} sub-tree doesn't support non-existence proofs"
+            ),
+            Error::InvalidSubTreeProof => {
+                write!(f, "The proof of the key in its sub-tree is invalid")
+            }
+            Error::InvalidBaseTreeProof => write!(
+                f,
+                "The proof of the sub-tree root in the base tree is invalid"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// Result of the proof verification
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// The storage sub-trees whose keys can be proven
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoreType {
+    /// For Account and other data. The keys are the full storage keys.
+    Account,
+    /// For IBC-related data. The keys are the storage keys without the
+    /// leading IBC address segment.
+    Ibc,
+    /// For PoS-related data. The keys are the storage keys without the
+    /// leading PoS address segment.
+    PoS,
+}
+
+impl StoreType {
+    /// The key of the sub-tree root in the base tree
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StoreType::Account => "account",
+            StoreType::Ibc => "ibc",
+            StoreType::PoS => "pos",
+        }
+    }
+
+    /// The ICS23 spec of the sub-tree
+    fn proof_spec(&self) -> ProofSpec {
+        let mut specs = match self {
+            StoreType::Ibc => specs::ibc_proof_specs(STORAGE_HASH_OP),
+            StoreType::Account | StoreType::PoS => {
+                specs::proof_specs(STORAGE_HASH_OP)
+            }
+        };
+        specs.swap_remove(0)
+    }
+}
+
+impl fmt::Display for StoreType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A proof of a storage key
+#[derive(Debug, Clone, PartialEq)]
+pub struct StorageProof {
+    /// The proof of the key in its sub-tree
+    pub sub_proof: CommitmentProof,
+    /// The proof of the sub-tree root in the base tree
+    pub base_proof: CommitmentProof,
+}
+
+impl StorageProof {
+    /// Decode a proof from the data of its two proof ops, i.e. the protobuf
+    /// encoded ICS23 commitment proofs.
+    pub fn decode(sub_proof: &[u8], base_proof: &[u8]) -> Result<Self> {
+        let sub_proof =
+            CommitmentProof::decode(sub_proof).map_err(Error::Decode)?;
+        let base_proof =
+            CommitmentProof::decode(base_proof).map_err(Error::Decode)?;
+        Ok(Self {
+            sub_proof,
+            base_proof,
+        })
+    }
+
+    /// Verify that the given key has the given value in the storage
+    /// committed to by the `root`, i.e. the app hash of a block.
+    pub fn verify_membership(
+        &self,
+        root: &[u8],
+        store: StoreType,
+        key: &[u8],
+        value: &[u8],
+    ) -> Result<()> {
+        let sub_root = match &self.sub_proof.proof {
+            Some(Ics23Proof::Exist(ep)) => existence_root(ep)?,
+            _ => return Err(Error::UnexpectedProof("an existence")),
+        };
+        if !ics23::verify_membership::<HostFunctionsManager>(
+            &self.sub_proof,
+            &store.proof_spec(),
+            &sub_root,
+            key,
+            value,
+        ) {
+            return Err(Error::InvalidSubTreeProof);
+        }
+        self.verify_sub_root(root, store, &sub_root)
+    }
+
+    /// Verify that the given key is not in the storage committed to by the
+    /// `root`, i.e. the app hash of a block.
+    pub fn verify_non_membership(
+        &self,
+        root: &[u8],
+        store: StoreType,
+        key: &[u8],
+    ) -> Result<()> {
+        if store != StoreType::Ibc {
+            return Err(Error::NonExistenceProof(store));
+        }
+        let sub_root = match &self.sub_proof.proof {
+            Some(Ics23Proof::Nonexist(nep)) => {
+                match nep.left.as_ref().or(nep.right.as_ref()) {
+                    Some(ep) => existence_root(ep)?,
+                    None => return Err(Error::UnexpectedProof("a neighbor")),
+                }
+            }
+            _ => return Err(Error::UnexpectedProof("a non-existence")),
+        };
+        if !ics23::verify_non_membership::<HostFunctionsManager>(
+            &self.sub_proof,
+            &store.proof_spec(),
+            &sub_root,
+            key,
+        ) {
+            return Err(Error::InvalidSubTreeProof);
+        }
+        self.verify_sub_root(root, store, &sub_root)
+    }
+
+    /// Verify that the sub-tree root is in the base tree
+    fn verify_sub_root(
+        &self,
+        root: &[u8],
+        store: StoreType,
+        sub_root: &[u8],
+    ) -> Result<()> {
+        let spec = specs::proof_specs(STORAGE_HASH_OP).swap_remove(1);
+        if !ics23::verify_membership::<HostFunctionsManager>(
+            &self.base_proof,
+            &spec,
+            &root.to_vec(),
+            store.as_str().as_bytes(),
+            sub_root,
+        ) {
+            return Err(Error::InvalidBaseTreeProof);
+        }
+        Ok(())
+    }
+}
+
+/// Compute the root of the tree from an existence proof
+fn existence_root(proof: &ExistenceProof) -> Result<Vec<u8>> {
+    ics23::calculate_existence_root::<HostFunctionsManager>(proof)
+        .map_err(|err| Error::SubTreeRoot(err.to_string()))
+}
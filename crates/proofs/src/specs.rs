@@ -0,0 +1,89 @@
+//! ICS23 specs of the storage merkle trees
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use ics23::{HashOp, InnerSpec, LeafOp, LengthOp, ProofSpec};
+
+/// The hash operation of the storage merkle trees
+pub const STORAGE_HASH_OP: HashOp = HashOp::Sha256;
+
+/// The length of the hashes in the sparse merkle trees
+const HASH_LENGTH: usize = 32;
+
+/// Get the leaf spec for the base tree. The key is stored after hashing,
+/// but the stored value is the subtree's root without hashing.
+pub fn base_leaf_spec(hash_op: HashOp) -> LeafOp {
+    LeafOp {
+        hash: hash_op.into(),
+        prehash_key: hash_op.into(),
+        prehash_value: HashOp::NoHash.into(),
+        length: LengthOp::NoPrefix.into(),
+        prefix: vec![0; HASH_LENGTH],
+    }
+}
+
+/// Get the leaf spec for the subtree. Non-hashed values are used for the
+/// verification with this spec because a subtree stores the key-value pairs
+/// after hashing.
+pub fn leaf_spec(hash_op: HashOp) -> LeafOp {
+    LeafOp {
+        hash: hash_op.into(),
+        prehash_key: hash_op.into(),
+        prehash_value: hash_op.into(),
+        length: LengthOp::NoPrefix.into(),
+        prefix: vec![0; HASH_LENGTH],
+    }
+}
+
+/// Get the leaf spec for the ibc subtree. Non-hashed values are used for
+/// the verification with this spec because a subtree stores the
+/// key-value pairs after hashing. However, keys are also not hashed in
+/// the backing store.
+pub fn ibc_leaf_spec(hash_op: HashOp) -> LeafOp {
+    LeafOp {
+        hash: hash_op.into(),
+        prehash_key: HashOp::NoHash.into(),
+        prehash_value: HashOp::NoHash.into(),
+        length: LengthOp::NoPrefix.into(),
+        prefix: vec![0; HASH_LENGTH],
+    }
+}
+
+/// Get the spec of the inner nodes of the sparse merkle trees
+pub fn inner_spec(hash_op: HashOp) -> InnerSpec {
+    InnerSpec {
+        child_order: vec![0, 1],
+        child_size: HASH_LENGTH as i32,
+        min_prefix_length: 1,
+        max_prefix_length: 1,
+        empty_child: vec![0; HASH_LENGTH],
+        hash: hash_op.into(),
+    }
+}
+
+/// Get the proof specs for ibc
+pub fn ibc_proof_specs(hash_op: HashOp) -> Vec<ProofSpec> {
+    vec![
+        proof_spec(ibc_leaf_spec(hash_op), hash_op),
+        proof_spec(base_leaf_spec(hash_op), hash_op),
+    ]
+}
+
+/// Get the proof specs
+pub fn proof_specs(hash_op: HashOp) -> Vec<ProofSpec> {
+    vec![
+        proof_spec(leaf_spec(hash_op), hash_op),
+        proof_spec(base_leaf_spec(hash_op), hash_op),
+    ]
+}
+
+fn proof_spec(leaf_spec: LeafOp, hash_op: HashOp) -> ProofSpec {
+    ProofSpec {
+        leaf_spec: Some(leaf_spec),
+        inner_spec: Some(inner_spec(hash_op)),
+        max_depth: 0,
+        min_depth: 0,
+        prehash_key_before_comparison: false,
+    }
+}
@@ -1,9 +1,11 @@
 //! Structures encapsulating SDK arguments
 
 use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::time::Duration as StdDuration;
 
+use namada_core::tendermint::Hash as TmHash;
 use namada_core::types::address::Address;
 use namada_core::types::chain::ChainId;
 use namada_core::types::dec::Dec;
@@ -11,11 +13,12 @@ use namada_core::types::ethereum_events::EthAddress;
 use namada_core::types::keccak::KeccakHash;
 use namada_core::types::key::{common, SchemeType};
 use namada_core::types::masp::PaymentAddress;
-use namada_core::types::storage::Epoch;
+use namada_core::types::storage::{BlockHeight, Epoch};
 use namada_core::types::time::DateTimeUtc;
 use namada_core::types::{storage, token};
 use namada_governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, IbcClientRecoveryProposal, PgfFundingProposal,
+    PgfStewardProposal,
 };
 use namada_tx::data::GasLimit;
 use namada_tx::Memo;
@@ -25,6 +28,8 @@ use zeroize::Zeroizing;
 use crate::eth_bridge::bridge_pool;
 use crate::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use crate::signing::SigningTxData;
+use crate::storage_value::StorageValueType;
+use crate::wallet::address_book::{AddressBookFormat, ConflictStrategy};
 use crate::{rpc, tx, Namada};
 
 /// [`Duration`](StdDuration) wrapper that provides a
@@ -184,6 +189,15 @@ impl TxCustom {
     }
 }
 
+/// Transaction builder arguments
+#[derive(Clone, Debug)]
+pub struct TxBuild<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Prompt for the contents of the transaction
+    pub interactive: bool,
+}
+
 /// An amount read in by the cli
 #[derive(Copy, Clone, Debug)]
 pub enum InputAmount {
@@ -210,6 +224,28 @@ impl From<token::DenominatedAmount> for InputAmount {
     }
 }
 
+/// The gas fees of a transfer to the Ethereum bridge pool, read in by
+/// the cli
+#[derive(Copy, Clone, Debug)]
+pub enum BridgePoolFee {
+    /// A given amount of fees
+    Amount(token::DenominatedAmount),
+    /// Fees estimated from recent relays and Ethereum gas prices
+    Auto,
+}
+
+impl std::str::FromStr for BridgePoolFee {
+    type Err = <token::DenominatedAmount as std::str::FromStr>::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "auto" {
+            Ok(BridgePoolFee::Auto)
+        } else {
+            token::DenominatedAmount::from_str(s).map(BridgePoolFee::Amount)
+        }
+    }
+}
+
 /// Transfer transaction arguments
 #[derive(Clone, Debug)]
 pub struct TxTransfer<C: NamadaTypes = SdkTypes> {
@@ -290,6 +326,169 @@ impl TxTransfer {
     }
 }
 
+/// Vesting transfer transaction arguments
+#[derive(Clone, Debug)]
+pub struct TxVestingTransfer<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Transfer source address
+    pub source: C::Address,
+    /// Transfer target address, whose received tokens are locked. The target
+    /// must also sign the tx to accept the vesting schedule.
+    pub target: C::Address,
+    /// Transferred token address
+    pub token: C::Address,
+    /// Transferred token amount
+    pub amount: InputAmount,
+    /// The epoch at which the release of the tokens starts
+    pub start: Epoch,
+    /// The epoch at which the tokens are fully vested
+    pub end: Epoch,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxVestingTransfer<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxVestingTransfer {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxVestingTransfer<C> {
+    /// Transfer source address
+    pub fn source(self, source: C::Address) -> Self {
+        Self { source, ..self }
+    }
+
+    /// Transfer target address
+    pub fn receiver(self, target: C::Address) -> Self {
+        Self { target, ..self }
+    }
+
+    /// Transferred token address
+    pub fn token(self, token: C::Address) -> Self {
+        Self { token, ..self }
+    }
+
+    /// Transferred token amount
+    pub fn amount(self, amount: InputAmount) -> Self {
+        Self { amount, ..self }
+    }
+
+    /// The epoch at which the release of the tokens starts
+    pub fn start(self, start: Epoch) -> Self {
+        Self { start, ..self }
+    }
+
+    /// The epoch at which the tokens are fully vested
+    pub fn end(self, end: Epoch) -> Self {
+        Self { end, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxVestingTransfer {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_vesting_transfer(context, self).await
+    }
+}
+
+/// Arguments to schedule a tx for execution at a future epoch
+#[derive(Clone, Debug)]
+pub struct TxSchedule<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The address authorizing the scheduled tx and paying its fee upfront
+    pub owner: C::Address,
+    /// The epoch at whose start the tx is executed
+    pub epoch: Epoch,
+    /// The gas limit of the scheduled tx
+    pub gas_limit: GasLimit,
+    /// Path to the scheduled tx WASM code file
+    pub code_path: PathBuf,
+    /// The data passed to the scheduled tx
+    pub data: Option<C::Data>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxSchedule<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxSchedule {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxSchedule<C> {
+    /// The address authorizing the scheduled tx and paying its fee upfront
+    pub fn owner(self, owner: C::Address) -> Self {
+        Self { owner, ..self }
+    }
+
+    /// The epoch at whose start the tx is executed
+    pub fn epoch(self, epoch: Epoch) -> Self {
+        Self { epoch, ..self }
+    }
+
+    /// The gas limit of the scheduled tx
+    pub fn gas_limit(self, gas_limit: GasLimit) -> Self {
+        Self { gas_limit, ..self }
+    }
+
+    /// Path to the scheduled tx WASM code file
+    pub fn code_path(self, code_path: PathBuf) -> Self {
+        Self { code_path, ..self }
+    }
+
+    /// The data passed to the scheduled tx
+    pub fn data(self, data: C::Data) -> Self {
+        Self {
+            data: Some(data),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxSchedule {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_schedule_tx(context, self).await
+    }
+}
+
 /// IBC transfer transaction arguments
 #[derive(Clone, Debug)]
 pub struct TxIbcTransfer<C: NamadaTypes = SdkTypes> {
@@ -419,6 +618,8 @@ pub struct InitProposal<C: NamadaTypes = SdkTypes> {
     pub is_pgf_stewards: bool,
     /// Flag if proposal is of type Pgf funding
     pub is_pgf_funding: bool,
+    /// Flag if proposal is of type Ibc client recovery
+    pub is_ibc_client_recovery: bool,
     /// Path to the tx WASM file
     pub tx_code_path: PathBuf,
 }
@@ -473,6 +674,14 @@ impl<C: NamadaTypes> InitProposal<C> {
         }
     }
 
+    /// Flag if proposal is of type Ibc client recovery
+    pub fn is_ibc_client_recovery(self, is_ibc_client_recovery: bool) -> Self {
+        Self {
+            is_ibc_client_recovery,
+            ..self
+        }
+    }
+
     /// Path to the tx WASM file
     pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
         Self {
@@ -535,6 +744,35 @@ impl InitProposal {
                 })?;
 
             tx::build_pgf_stewards_proposal(context, self, proposal).await
+        } else if self.is_ibc_client_recovery {
+            let proposal = IbcClientRecoveryProposal::try_from(
+                self.proposal_data.as_ref(),
+            )
+            .map_err(|e| {
+                crate::error::TxSubmitError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+            let nam_address = context.native_token();
+            let author_balance = rpc::get_token_balance(
+                context.client(),
+                &nam_address,
+                &proposal.proposal.author,
+            )
+            .await?;
+            let proposal = proposal
+                .validate(
+                    &governance_parameters,
+                    current_epoch,
+                    author_balance,
+                    self.tx.force,
+                )
+                .map_err(|e| {
+                    crate::error::TxSubmitError::InvalidProposal(e.to_string())
+                })?;
+
+            tx::build_ibc_client_recovery_proposal(context, self, proposal)
+                .await
         } else {
             let proposal = DefaultProposal::try_from(
                 self.proposal_data.as_ref(),
@@ -649,6 +887,65 @@ impl VoteProposal {
     }
 }
 
+/// Transaction to set or remove the vote proxy of a delegator
+#[derive(Clone, Debug)]
+pub struct UpdateVoteProxy<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The delegator whose bonded stake is voted with
+    pub delegator: C::Address,
+    /// The address voting on behalf of the delegator, or `None` to remove
+    /// the current proxy
+    pub proxy: Option<C::Address>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for UpdateVoteProxy<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        UpdateVoteProxy {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> UpdateVoteProxy<C> {
+    /// The delegator whose bonded stake is voted with
+    pub fn delegator(self, delegator: C::Address) -> Self {
+        Self { delegator, ..self }
+    }
+
+    /// The address voting on behalf of the delegator
+    pub fn proxy(self, proxy: C::Address) -> Self {
+        Self {
+            proxy: Some(proxy),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl UpdateVoteProxy {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_update_vote_proxy(context, self).await
+    }
+}
+
 /// Transaction to initialize a new account
 #[derive(Clone, Debug)]
 pub struct TxInitAccount<C: NamadaTypes = SdkTypes> {
@@ -878,6 +1175,74 @@ impl TxUpdateAccount {
     }
 }
 
+/// Transaction to replace the public keys and the threshold of an account
+#[derive(Clone, Debug)]
+pub struct TxUpdateAccountKeys<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+    /// Address of the account whose keys are to be updated
+    pub addr: C::Address,
+    /// The new public keys
+    pub public_keys: Vec<C::PublicKey>,
+    /// The new account threshold, may be omitted for a single key
+    pub threshold: Option<u8>,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxUpdateAccountKeys<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxUpdateAccountKeys {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxUpdateAccountKeys<C> {
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+
+    /// Address of the account whose keys are to be updated
+    pub fn addr(self, addr: C::Address) -> Self {
+        Self { addr, ..self }
+    }
+
+    /// The new public keys
+    pub fn public_keys(self, public_keys: Vec<C::PublicKey>) -> Self {
+        Self {
+            public_keys,
+            ..self
+        }
+    }
+
+    /// The new account threshold
+    pub fn threshold(self, threshold: u8) -> Self {
+        Self {
+            threshold: Some(threshold),
+            ..self
+        }
+    }
+}
+
+impl TxUpdateAccountKeys {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_update_account_keys(context, self).await
+    }
+}
+
 /// Bond arguments
 #[derive(Clone, Debug)]
 pub struct Bond<C: NamadaTypes = SdkTypes> {
@@ -1218,49 +1583,116 @@ impl<C: NamadaTypes> Withdraw<C> {
     }
 }
 
-impl Withdraw {
+impl Withdraw {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_withdraw(context, self).await
+    }
+}
+
+/// Claim arguments
+#[derive(Clone, Debug)]
+pub struct ClaimRewards<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Validator address
+    pub validator: C::Address,
+    /// Source address for claiming rewards due to bonds. For self-bonds, the
+    /// validator is also the source
+    pub source: Option<C::Address>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for ClaimRewards<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        ClaimRewards {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl ClaimRewards {
     /// Build a transaction from this builder
     pub async fn build(
         &self,
         context: &impl Namada,
     ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
-        tx::build_withdraw(context, self).await
+        tx::build_claim_rewards(context, self).await
     }
 }
 
-/// Claim arguments
+/// Auto-compounding arguments
 #[derive(Clone, Debug)]
-pub struct ClaimRewards<C: NamadaTypes = SdkTypes> {
+pub struct SetAutoCompound<C: NamadaTypes = SdkTypes> {
     /// Common tx arguments
     pub tx: Tx<C>,
     /// Validator address
     pub validator: C::Address,
-    /// Source address for claiming rewards due to bonds. For self-bonds, the
-    /// validator is also the source
+    /// Source address of the bond. For self-bonds, the validator is also the
+    /// source
     pub source: Option<C::Address>,
+    /// Whether the rewards should be restaked at every epoch
+    pub enabled: bool,
     /// Path to the TX WASM code file
     pub tx_code_path: PathBuf,
 }
 
-impl<C: NamadaTypes> TxBuilder<C> for ClaimRewards<C> {
+impl<C: NamadaTypes> TxBuilder<C> for SetAutoCompound<C> {
     fn tx<F>(self, func: F) -> Self
     where
         F: FnOnce(Tx<C>) -> Tx<C>,
     {
-        ClaimRewards {
+        SetAutoCompound {
             tx: func(self.tx),
             ..self
         }
     }
 }
 
-impl ClaimRewards {
+impl<C: NamadaTypes> SetAutoCompound<C> {
+    /// Validator address
+    pub fn validator(self, validator: C::Address) -> Self {
+        Self { validator, ..self }
+    }
+
+    /// Source address of the bond. For self-bonds, the validator is also the
+    /// source
+    pub fn source(self, source: C::Address) -> Self {
+        Self {
+            source: Some(source),
+            ..self
+        }
+    }
+
+    /// Whether the rewards should be restaked at every epoch
+    pub fn enabled(self, enabled: bool) -> Self {
+        Self { enabled, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl SetAutoCompound {
     /// Build a transaction from this builder
     pub async fn build(
         &self,
         context: &impl Namada,
     ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
-        tx::build_claim_rewards(context, self).await
+        tx::build_set_auto_compound(context, self).await
     }
 }
 
@@ -1297,6 +1729,37 @@ pub struct QueryBalance<C: NamadaTypes = SdkTypes> {
     pub no_conversions: bool,
 }
 
+/// Query the balances of many owners in many tokens at once
+#[derive(Clone, Debug)]
+pub struct QueryBalances<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Addresses of the owners
+    pub owners: Vec<C::Address>,
+    /// Addresses of the tokens
+    pub tokens: Vec<C::Address>,
+}
+
+/// Query the vesting schedule of a token balance
+#[derive(Clone, Debug)]
+pub struct QueryVesting<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Address of an owner
+    pub owner: C::Address,
+    /// Address of a token
+    pub token: C::Address,
+}
+
+/// Query the full trace path of an IBC denom
+#[derive(Clone, Debug)]
+pub struct QueryIbcDenom<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The hash of the IBC denom or the address of the IBC token
+    pub hash: String,
+}
+
 /// Query historical transfer(s)
 #[derive(Clone, Debug)]
 pub struct QueryTransfers<C: NamadaTypes = SdkTypes> {
@@ -1317,6 +1780,8 @@ pub struct QueryBonds<C: NamadaTypes = SdkTypes> {
     pub owner: Option<C::Address>,
     /// Address of a validator
     pub validator: Option<C::Address>,
+    /// Epoch in which to find the bonds
+    pub epoch: Option<Epoch>,
 }
 
 /// Query PoS bonded stake
@@ -1764,6 +2229,116 @@ impl TxDeactivateValidator {
     }
 }
 
+#[derive(Clone, Debug)]
+/// Vote to pause the Ethereum bridge args
+pub struct TxBridgePauseVote<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Address of the voting validator
+    pub validator: C::Address,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxBridgePauseVote<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxBridgePauseVote {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxBridgePauseVote<C> {
+    /// Address of the voting validator
+    pub fn validator(self, validator: C::Address) -> Self {
+        Self { validator, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxBridgePauseVote {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_bridge_pause_vote(context, self).await
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Convert NUTs into wrapped ERC20s args
+pub struct TxConvertNuts<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Address of the owner of the NUTs
+    pub owner: C::Address,
+    /// The ERC20 asset of the NUTs
+    pub asset: EthAddress,
+    /// The amount of NUTs to convert
+    pub amount: InputAmount,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for TxConvertNuts<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        TxConvertNuts {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> TxConvertNuts<C> {
+    /// Address of the owner of the NUTs
+    pub fn owner(self, owner: C::Address) -> Self {
+        Self { owner, ..self }
+    }
+
+    /// The ERC20 asset of the NUTs
+    pub fn asset(self, asset: EthAddress) -> Self {
+        Self { asset, ..self }
+    }
+
+    /// The amount of NUTs to convert
+    pub fn amount(self, amount: InputAmount) -> Self {
+        Self { amount, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl TxConvertNuts {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(namada_tx::Tx, SigningTxData)> {
+        tx::build_convert_nuts(context, self).await
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Re-activate a deactivated validator args
 pub struct TxReactivateValidator<C: NamadaTypes = SdkTypes> {
@@ -1823,6 +2398,15 @@ pub struct SignTx<C: NamadaTypes = SdkTypes> {
     pub owner: C::Address,
 }
 
+/// Broadcast a transaction signed offline
+#[derive(Clone, Debug)]
+pub struct BroadcastSignedTx<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The signed transaction
+    pub tx_data: C::Data,
+}
+
 /// Query PoS commission rate
 #[derive(Clone, Debug)]
 pub struct QueryCommissionRate<C: NamadaTypes = SdkTypes> {
@@ -1872,6 +2456,24 @@ pub struct QueryDelegations<C: NamadaTypes = SdkTypes> {
     pub owner: C::Address,
 }
 
+/// Query the PoS staking overview of an owner
+#[derive(Clone, Debug)]
+pub struct QueryStakingOverview<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// Address of an owner
+    pub owner: C::Address,
+}
+
+/// Query the fee, burn and treasury flows of the tokens in an epoch
+#[derive(Clone, Debug)]
+pub struct QueryFeeReport<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The epoch, the last committed one if not given
+    pub epoch: Option<Epoch>,
+}
+
 /// Query PoS to find a validator
 #[derive(Clone, Debug)]
 pub struct QueryFindValidator<C: NamadaTypes = SdkTypes> {
@@ -1883,6 +2485,38 @@ pub struct QueryFindValidator<C: NamadaTypes = SdkTypes> {
     pub validator_addr: Option<C::Address>,
 }
 
+/// Query the consensus validator set of an epoch from the history of the
+/// validator sets
+#[derive(Clone, Debug)]
+pub struct QueryValidatorSet<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The epoch of the validator set, defaults to the current epoch
+    pub epoch: Option<Epoch>,
+    /// Whether to also query a Merkle proof of the validator set
+    pub with_proof: bool,
+}
+
+/// Query a committed block
+#[derive(Clone, Debug)]
+pub struct QueryBlock<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The height of the block, defaults to the last committed block
+    pub height: Option<BlockHeight>,
+}
+
+/// Query the proposers of a range of block heights
+#[derive(Clone, Debug)]
+pub struct QueryProposer<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+    /// The first height to query, defaults to the next height
+    pub height: Option<BlockHeight>,
+    /// The number of consecutive heights to query
+    pub count: u64,
+}
+
 /// Query the raw bytes of given storage key
 #[derive(Clone, Debug)]
 pub struct QueryRawBytes<C: NamadaTypes = SdkTypes> {
@@ -1890,6 +2524,25 @@ pub struct QueryRawBytes<C: NamadaTypes = SdkTypes> {
     pub storage_key: storage::Key,
     /// Common query args
     pub query: Query<C>,
+    /// Accept the result from the node without verifying it with the light
+    /// client
+    pub trust_node: bool,
+    /// Height of a header to (re-)initialize the light client from
+    pub trusted_height: Option<BlockHeight>,
+    /// Hash of the header at `trusted_height`
+    pub trusted_hash: Option<TmHash>,
+}
+
+/// Query the value of given storage key and decode it
+#[derive(Clone, Debug)]
+pub struct QueryStorageValue<C: NamadaTypes = SdkTypes> {
+    /// The storage key to query
+    pub storage_key: storage::Key,
+    /// Common query args
+    pub query: Query<C>,
+    /// The type to decode the value as. When not given, the type is found
+    /// from the storage key.
+    pub value_type: Option<StorageValueType>,
 }
 
 /// Common transaction arguments
@@ -1901,6 +2554,9 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub dry_run_wrapper: bool,
     /// Dump the transaction bytes to file
     pub dump_tx: bool,
+    /// When dumping the transaction, dump it together with its signing data,
+    /// to be signed on an offline machine
+    pub offline_sign: bool,
     /// The output directory path to where serialize the data
     pub output_folder: Option<PathBuf>,
     /// Submit the transaction even if it doesn't pass client checks
@@ -1925,8 +2581,18 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub fee_unshield: Option<C::TransferSource>,
     /// The max amount of gas used to process tx
     pub gas_limit: GasLimit,
+    /// Replace the gas limit with the estimate of the node from a simulated
+    /// execution of the tx, which is signed a first time for the simulation
+    pub estimate_gas_limit: bool,
     /// The optional expiration of the transaction
     pub expiration: Option<DateTimeUtc>,
+    /// The optional last block height at which the transaction can be
+    /// included
+    pub expiration_height: Option<BlockHeight>,
+    /// The maximum number of times to sign again and resubmit the transaction
+    /// with a fresh timestamp and a higher fee, if it expires or is evicted
+    /// from the mempool before it's accepted
+    pub auto_resubmit: Option<u8>,
     /// Generate an ephimeral signing key to be used only once to sign a
     /// wrapper tx
     pub disposable_signing_key: bool,
@@ -1944,6 +2610,8 @@ pub struct Tx<C: NamadaTypes = SdkTypes> {
     pub memo: Option<Memo>,
     /// Use device to sign the transaction
     pub use_device: bool,
+    /// The socket of a wallet daemon to sign the transaction with
+    pub wallet_daemon: Option<PathBuf>,
 }
 
 /// Builder functions for Tx
@@ -1967,6 +2635,11 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
     fn dump_tx(self, dump_tx: bool) -> Self {
         self.tx(|x| Tx { dump_tx, ..x })
     }
+    /// Dump the unsigned transaction together with its signing data, to be
+    /// signed on an offline machine
+    fn offline_sign(self, offline_sign: bool) -> Self {
+        self.tx(|x| Tx { offline_sign, ..x })
+    }
     /// The output directory path to where serialize the data
     fn output_folder(self, output_folder: PathBuf) -> Self {
         self.tx(|x| Tx {
@@ -2040,6 +2713,14 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
     fn gas_limit(self, gas_limit: GasLimit) -> Self {
         self.tx(|x| Tx { gas_limit, ..x })
     }
+    /// Replace the gas limit with the estimate of the node from a simulated
+    /// execution of the tx
+    fn estimate_gas_limit(self, estimate_gas_limit: bool) -> Self {
+        self.tx(|x| Tx {
+            estimate_gas_limit,
+            ..x
+        })
+    }
     /// The optional expiration of the transaction
     fn expiration(self, expiration: DateTimeUtc) -> Self {
         self.tx(|x| Tx {
@@ -2047,6 +2728,23 @@ pub trait TxBuilder<C: NamadaTypes>: Sized {
             ..x
         })
     }
+    /// The optional last block height at which the transaction can be
+    /// included
+    fn expiration_height(self, expiration_height: BlockHeight) -> Self {
+        self.tx(|x| Tx {
+            expiration_height: Some(expiration_height),
+            ..x
+        })
+    }
+    /// The maximum number of times to sign again and resubmit the transaction
+    /// with a fresh timestamp and a higher fee, if it expires or is evicted
+    /// from the mempool before it's accepted
+    fn auto_resubmit(self, auto_resubmit: u8) -> Self {
+        self.tx(|x| Tx {
+            auto_resubmit: Some(auto_resubmit),
+            ..x
+        })
+    }
     /// Generate an ephimeral signing key to be used only once to sign a
     /// wrapper tx
     fn disposable_signing_key(self, disposable_signing_key: bool) -> Self {
@@ -2192,6 +2890,49 @@ pub struct KeyImport {
     pub unsafe_dont_encrypt: bool,
 }
 
+/// Wallet address book export arguments
+#[derive(Clone, Debug)]
+pub struct AddressBookExport {
+    /// The format of the exported file
+    pub format: AddressBookFormat,
+    /// The file to write to. Printed to stdout when not given
+    pub output: Option<PathBuf>,
+}
+
+/// Wallet vanity established address grinding arguments
+#[derive(Clone, Debug)]
+pub struct AddressGrind {
+    /// The pattern that the address must start with
+    pub prefix: String,
+    /// Scheme type of the generated key
+    pub scheme: SchemeType,
+    /// Key and address alias
+    pub alias: String,
+    /// Whether to force overwrite the alias
+    pub alias_force: bool,
+    /// Don't encrypt the keypair
+    pub unsafe_dont_encrypt: bool,
+    /// The validity predicate of the genesis established account
+    pub vp: String,
+    /// The maximum number of keys to try
+    pub max_iterations: Option<u64>,
+    /// The maximum time to grind for
+    pub time_limit: Option<StdDuration>,
+}
+
+/// Wallet address book import arguments
+#[derive(Clone, Debug)]
+pub struct AddressBookImport {
+    /// Path to the address book file
+    pub file_path: PathBuf,
+    /// The format of the address book file
+    pub format: AddressBookFormat,
+    /// How to resolve entries that conflict with the wallet
+    pub on_conflict: ConflictStrategy,
+    /// Only preview the changes without modifying the wallet
+    pub dry_run: bool,
+}
+
 /// Wallet key / address add arguments
 #[derive(Clone, Debug)]
 pub struct KeyAddressAdd {
@@ -2214,6 +2955,33 @@ pub struct KeyAddressRemove {
     pub do_it: bool,
 }
 
+/// Wallet offline tx signing arguments
+#[derive(Clone, Debug)]
+pub struct OfflineSignTx {
+    /// Path to the file with the unsigned tx and its signing data
+    pub tx_path: PathBuf,
+    /// The output directory path to where the signed tx is written
+    pub output_folder: Option<PathBuf>,
+}
+
+/// Wallet daemon arguments
+#[derive(Clone, Debug)]
+pub struct WalletDaemon {
+    /// Path of the Unix socket to listen on
+    pub socket_path: PathBuf,
+    /// When to ask for a confirmation of the signing requests
+    pub confirm: crate::wallet::daemon::ConfirmPolicy,
+}
+
+/// Wallet unlock arguments
+#[derive(Clone, Debug)]
+pub struct WalletUnlock {
+    /// Save the verified password in the keychain of the OS
+    pub save: bool,
+    /// Remove the password saved in the keychain of the OS
+    pub forget: bool,
+}
+
 /// Generate payment address arguments
 #[derive(Clone, Debug)]
 pub struct PayAddressGen<C: NamadaTypes = SdkTypes> {
@@ -2267,6 +3035,9 @@ pub struct EthereumBridgePool<C: NamadaTypes = SdkTypes> {
     pub fee_payer: Option<C::Address>,
     /// The token in which the gas is being paid
     pub fee_token: C::Address,
+    /// Estimate the amount of gas fees from recently relayed transfers
+    /// and Ethereum gas prices, overriding `fee_amount`.
+    pub fee_estimate: Option<BridgePoolFeeEstimate<C>>,
     /// Path to the tx WASM code file
     pub code_path: PathBuf,
 }
@@ -2332,6 +3103,14 @@ impl<C: NamadaTypes> EthereumBridgePool<C> {
         Self { fee_token, ..self }
     }
 
+    /// Estimate the amount of gas fees, overriding the fee amount
+    pub fn fee_estimate(self, fee_estimate: BridgePoolFeeEstimate<C>) -> Self {
+        Self {
+            fee_estimate: Some(fee_estimate),
+            ..self
+        }
+    }
+
     /// Path to the tx WASM code file
     pub fn code_path(self, code_path: PathBuf) -> Self {
         Self { code_path, ..self }
@@ -2348,6 +3127,49 @@ impl EthereumBridgePool {
     }
 }
 
+/// Parameters for estimating the gas fees of transfers in the
+/// Ethereum bridge pool.
+#[derive(Clone, Debug)]
+pub struct BridgePoolFeeEstimate<C: NamadaTypes = SdkTypes> {
+    /// The Ethereum RPC endpoint to query gas prices from.
+    pub eth_rpc_endpoint: C::EthereumAddress,
+    /// Conversion rate from the fee token to gwei.
+    pub conversion_rate: f64,
+    /// The number of Ethereum blocks within which the transfer
+    /// should be relayed.
+    pub blocks: u64,
+}
+
+/// Query a suggested gas fee for transfers in the Ethereum
+/// bridge pool.
+#[derive(Clone, Debug)]
+pub struct BridgePoolFees<C: NamadaTypes = SdkTypes> {
+    /// The query parameters.
+    pub query: Query<C>,
+    /// The token in which the gas fee is paid.
+    pub fee_token: C::Address,
+    /// The fee estimation parameters.
+    pub estimate: BridgePoolFeeEstimate<C>,
+}
+
+/// Query the status of a transfer to Ethereum.
+#[derive(Clone, Debug)]
+pub struct QueryTransferStatus<C: NamadaTypes = SdkTypes> {
+    /// The query parameters.
+    pub query: Query<C>,
+    /// The keccak hash of the transfer.
+    pub hash: KeccakHash,
+}
+
+/// Query the balances of the NUTs held by an owner.
+#[derive(Clone, Debug)]
+pub struct QueryNutBalances<C: NamadaTypes = SdkTypes> {
+    /// The query parameters.
+    pub query: Query<C>,
+    /// The owner of the NUTs.
+    pub owner: C::Address,
+}
+
 /// Bridge pool proof arguments.
 #[derive(Debug, Clone)]
 pub struct BridgePoolProof<C: NamadaTypes = SdkTypes> {
@@ -2457,6 +3279,9 @@ pub struct ValidatorSetUpdateRelay<C: NamadaTypes = SdkTypes> {
     /// Safe mode overrides keyboard interrupt signals, to ensure
     /// Ethereum transfers aren't canceled midway through.
     pub safe_mode: bool,
+    /// The local address of the REST API to serve in daemon mode, to
+    /// manage the relayer while it runs.
+    pub api_addr: Option<SocketAddr>,
 }
 
 /// IBC shielded transfer generation arguments
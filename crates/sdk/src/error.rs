@@ -42,6 +42,9 @@ pub enum Error {
     /// Ethereum bridge related errors
     #[error("{0}")]
     EthereumBridge(#[from] EthereumBridgeError),
+    /// Errors in loading keys from the wallet
+    #[error("{0}")]
+    Wallet(String),
     /// Any Other errors that are uncategorized
     #[error("{0}")]
     Other(String),
@@ -96,6 +99,12 @@ pub enum QueryError {
     /// synchronizing with the network.
     #[error("Node is still catching up with the network")]
     CatchingUp,
+    /// The query result could not be verified against a trusted header
+    #[error("Failed to verify the query result: {0}")]
+    Unverified(String),
+    /// The node couldn't be reached
+    #[error("Connection error: {0}")]
+    Connection(String),
 }
 
 /// Errors that deal with Decoding, Encoding, or Conversions
@@ -139,6 +148,16 @@ pub enum TxSubmitError {
     /// Error during broadcasting a transaction
     #[error("Encountered error while broadcasting transaction: {0}")]
     TxBroadcast(RpcError),
+    /// The transaction was rejected by the mempool
+    #[error("The transaction was rejected by the mempool: {0}")]
+    CheckTxRejected(String),
+    /// The transaction was included in a block, but it was rejected or it
+    /// failed on-chain
+    #[error(
+        "The transaction {0} was included in the block at height {1}, but it \
+         is invalid"
+    )]
+    InvalidOnChain(String, storage::BlockHeight),
     /// Invalid commission rate set
     #[error("Invalid new commission rate, received {0}")]
     InvalidCommissionRate(Dec),
@@ -162,6 +181,12 @@ pub enum TxSubmitError {
          be reactivated."
     )]
     ValidatorNotInactive(Address, Epoch),
+    /// Validator self-bond below the minimum
+    #[error(
+        "The self-bond of validator {0} is below the minimum self-bond {1} \
+         and so it cannot be reactivated."
+    )]
+    SelfBondBelowMinimum(Address, String),
     /// Validator still frozen and ineligible to be unjailed
     #[error(
         "The validator address {0} is currently frozen and ineligible to be \
@@ -234,6 +259,15 @@ pub enum TxSubmitError {
     /// No Balance found for token
     #[error("No balance found for the source {0} of token {1}")]
     NoBalanceForToken(Address, Address),
+    /// The vesting schedule is invalid
+    #[error("Invalid vesting schedule: {0}")]
+    InvalidVestingSchedule(String),
+    /// The epoch of a scheduled tx is not in the future
+    #[error(
+        "A tx must be scheduled for an epoch after the current epoch {0}, got \
+         {1}"
+    )]
+    InvalidScheduledEpoch(Epoch, Epoch),
     /// Negative balance after transfer
     #[error(
         "The balance of the source {0} is lower than the amount to be \
@@ -353,6 +387,12 @@ pub enum EthereumBridgeError {
     /// Error querying transfer to Ethereum progress.
     #[error("Failed to query transfer to Ethereum progress: {0}")]
     TransferToEthProgress(String),
+    /// Error querying the status of a transfer to Ethereum.
+    #[error("Failed to query the status of a transfer to Ethereum: {0}")]
+    TransferStatus(String),
+    /// Error querying the balances of NUTs.
+    #[error("Failed to query the balances of NUTs: {0}")]
+    NutBalances(String),
     /// Error querying Ethereum voting powers.
     #[error("Failed to query Ethereum voting powers: {0}")]
     QueryVotingPowers(String),
@@ -12,8 +12,8 @@ use futures::future::FutureExt;
 use namada_core::types::address::{Address, InternalAddress};
 use namada_core::types::eth_abi::Encode;
 use namada_core::types::eth_bridge_pool::{
-    erc20_token_address, GasFee, PendingTransfer, TransferToEthereum,
-    TransferToEthereumKind,
+    erc20_token_address, GasFee, PendingTransfer, TransferStatus,
+    TransferToEthereum, TransferToEthereumKind,
 };
 use namada_core::types::ethereum_events::EthAddress;
 use namada_core::types::keccak::KeccakHash;
@@ -39,7 +39,10 @@ use crate::queries::{
     Client, GenBridgePoolProofReq, GenBridgePoolProofRsp, TransferToErcArgs,
     TransferToEthereumStatus, RPC,
 };
-use crate::rpc::{query_storage_value, query_wasm_code_hash, validate_amount};
+use crate::rpc::{
+    format_denominated_amount, query_storage_value, query_wasm_code_hash,
+    validate_amount,
+};
 use crate::signing::aux_signing_data;
 use crate::tx::prepare_tx;
 use crate::{
@@ -60,6 +63,7 @@ pub async fn build_bridge_pool_tx(
         fee_amount,
         fee_payer,
         fee_token,
+        fee_estimate: _,
         code_path,
     }: args::EthereumBridgePool,
 ) -> Result<(Tx, SigningTxData), Error> {
@@ -416,6 +420,69 @@ pub async fn query_relay_progress(
     Ok(())
 }
 
+/// Query the status of a transfer to Ethereum.
+pub async fn query_transfer_status(
+    client: &(impl Client + Sync),
+    io: &impl Io,
+    args: args::QueryTransferStatus,
+) -> Result<Option<TransferStatus>, Error> {
+    let status = RPC
+        .shell()
+        .eth_bridge()
+        .read_transfer_status(client, &args.hash)
+        .await
+        .map_err(|e| {
+            Error::EthereumBridge(EthereumBridgeError::TransferStatus(
+                e.to_string(),
+            ))
+        })?;
+    match &status {
+        Some(status) => {
+            display_line!(io, "Transfer {}: {status}.", args.hash)
+        }
+        None => display_line!(
+            io,
+            "No transfer to Ethereum with hash {} was found.",
+            args.hash
+        ),
+    }
+    Ok(status)
+}
+
+/// Query the balances of the NUTs held by an owner, which may be converted
+/// into wrapped ERC20s once the token caps of their assets are raised.
+pub async fn query_nut_balances(
+    client: &(impl Client + Sync),
+    io: &impl Io,
+    args: args::QueryNutBalances,
+) -> Result<Vec<(EthAddress, Amount)>, Error> {
+    let balances = RPC
+        .shell()
+        .eth_bridge()
+        .read_nut_balances(client, &args.owner)
+        .await
+        .map_err(|e| {
+            Error::EthereumBridge(EthereumBridgeError::NutBalances(
+                e.to_string(),
+            ))
+        })?;
+    if balances.is_empty() {
+        display_line!(io, "No NUTs are held by {}.", args.owner);
+    }
+    for (asset, amount) in &balances {
+        // NUTs have the same denomination as the wrapped ERC20s
+        let amount = format_denominated_amount(
+            client,
+            io,
+            &erc20_token_address(asset),
+            *amount,
+        )
+        .await;
+        display_line!(io, "NUTs of {asset}: {amount}");
+    }
+    Ok(balances)
+}
+
 /// Internal method to construct a proof that a set of transfers are in the
 /// bridge pool.
 async fn construct_bridge_pool_proof(
@@ -744,6 +811,7 @@ mod recommendations {
 
     use super::*;
     use crate::edisplay_line;
+    use crate::eth_bridge::ethers::types::{BlockNumber, U256};
     use crate::eth_bridge::storage::bridge_pool::{
         get_nonce_key, get_signed_root_key,
     };
@@ -1209,6 +1277,258 @@ mod recommendations {
         *fees += Uint::from(amount);
     }
 
+    /// The number of recent Ethereum blocks whose gas prices are sampled
+    /// to estimate Bridge pool fees.
+    const FEE_HISTORY_BLOCKS: u64 = 100;
+
+    /// The probability with which a suggested fee should get a transfer
+    /// relayed within the requested number of Ethereum blocks.
+    const RELAY_CONFIDENCE: f64 = 0.9;
+
+    /// A suggested gas fee for transfers in the Bridge pool.
+    #[derive(Debug, Serialize)]
+    pub struct BridgePoolFeeSuggestion {
+        /// The token in which the fee is paid.
+        pub fee_token: Address,
+        /// The suggested fee.
+        pub fee: Amount,
+        /// The Ethereum gas price expected for the relay, in gwei.
+        pub gas_price: u64,
+        /// The Ethereum gas spent relaying a single transfer, including
+        /// its share of the validator set checks of its batch.
+        pub relay_gas: u64,
+        /// The number of transfers in recently relayed batches.
+        pub relayed_transfers: usize,
+        /// The lowest fee paid in `fee_token` by a recently relayed
+        /// transfer.
+        pub lowest_relayed_fee: Option<Amount>,
+    }
+
+    /// Suggest a gas fee, paid in `fee_token`, that should get a transfer
+    /// relayed to Ethereum within the requested number of Ethereum blocks.
+    ///
+    /// The fee covers the cost of relaying the transfer at the gas price
+    /// expected over those blocks, given the recent Ethereum gas prices,
+    /// and is never lower than the fees of recently relayed transfers.
+    pub async fn suggest_bridge_pool_fee<E>(
+        eth_client: Arc<E>,
+        client: &(impl Client + Sync),
+        fee_token: &Address,
+        estimate: &args::BridgePoolFeeEstimate,
+    ) -> Result<BridgePoolFeeSuggestion, Error>
+    where
+        E: Middleware,
+        E::Error: std::fmt::Debug + std::fmt::Display,
+    {
+        let gwei_per_fee_token = gwei_per_fee_token(estimate.conversion_rate)
+            .map_err(|err| {
+            Error::EthereumBridge(EthereumBridgeError::RelayCost(err))
+        })?;
+
+        // transfers that have been relayed to Ethereum, but whose
+        // events are still being voted on in Namada
+        let relayed: Vec<PendingTransfer> = RPC
+            .shell()
+            .eth_bridge()
+            .transfer_to_ethereum_progress(client)
+            .await
+            .map_err(|e| {
+                Error::EthereumBridge(
+                    EthereumBridgeError::TransferToEthProgress(e.to_string()),
+                )
+            })?
+            .into_keys()
+            .collect();
+        let lowest_relayed_fee = relayed
+            .iter()
+            .filter(|transfer| &transfer.gas_fee.token == fee_token)
+            .map(|transfer| transfer.gas_fee.amount)
+            .min();
+
+        // every batch checks a quorum of signatures of the
+        // current validator set
+        let epoch = crate::rpc::query_epoch(client).await?;
+        let voting_powers = RPC
+            .shell()
+            .eth_bridge()
+            .voting_powers_at_epoch(client, &epoch)
+            .await
+            .map_err(|e| {
+                Error::EthereumBridge(EthereumBridgeError::QueryVotingPowers(
+                    e.to_string(),
+                ))
+            })?;
+        let valset_size = Uint::from_u64(voting_powers.len() as u64);
+        let all_signed: HashMap<EthAddrBook, ()> = voting_powers
+            .keys()
+            .map(|addr| (addr.clone(), ()))
+            .collect();
+        let validator_gas = signature_fee()
+            * signature_checks(voting_powers, &all_signed)
+            + valset_fee() * valset_size;
+        let batch_size = Uint::from_u64(relayed.len().max(1) as u64);
+        let relay_gas = unsigned_transfer_fee()
+            + (validator_gas + batch_size - Uint::one()) / batch_size;
+        let relay_gas = relay_gas.as_u64();
+
+        let fee_history = eth_client
+            .fee_history(FEE_HISTORY_BLOCKS, BlockNumber::Latest, &[50.0])
+            .await
+            .map_err(|e| {
+                Error::EthereumBridge(EthereumBridgeError::Rpc(e.to_string()))
+            })?;
+        let mut gas_prices: Vec<u64> = fee_history
+            .base_fee_per_gas
+            .iter()
+            .zip(fee_history.reward.iter())
+            .map(|(base_fee, reward)| {
+                let tip = reward.first().copied().unwrap_or_default();
+                wei_to_gwei(base_fee.saturating_add(tip))
+            })
+            .collect();
+        if gas_prices.is_empty() {
+            let gas_price = eth_client.get_gas_price().await.map_err(|e| {
+                Error::EthereumBridge(EthereumBridgeError::Rpc(e.to_string()))
+            })?;
+            gas_prices.push(wei_to_gwei(gas_price));
+        }
+        let gas_price = gas_price_within_blocks(gas_prices, estimate.blocks);
+
+        let fee = fee_for_cost(
+            u128::from(gas_price) * u128::from(relay_gas),
+            gwei_per_fee_token,
+        );
+        let fee = lowest_relayed_fee.map_or(fee, |lowest| fee.max(lowest));
+
+        Ok(BridgePoolFeeSuggestion {
+            fee_token: fee_token.clone(),
+            fee,
+            gas_price,
+            relay_gas,
+            relayed_transfers: relayed.len(),
+            lowest_relayed_fee,
+        })
+    }
+
+    /// Query a suggested gas fee for transfers in the Bridge pool.
+    /// Prints out a json payload.
+    pub async fn query_bridge_pool_fees<E>(
+        eth_client: Arc<E>,
+        client: &(impl Client + Sync),
+        io: &impl Io,
+        args: args::BridgePoolFees,
+    ) -> Result<BridgePoolFeeSuggestion, Error>
+    where
+        E: Middleware,
+        E::Error: std::fmt::Debug + std::fmt::Display,
+    {
+        let suggestion = suggest_bridge_pool_fee(
+            eth_client,
+            client,
+            &args.fee_token,
+            &args.estimate,
+        )
+        .await?;
+        display_line!(
+            io,
+            "{}",
+            serde_json::to_string_pretty(&suggestion)
+                .map_err(|e| EncodingError::Serde(e.to_string()))?
+        );
+        Ok(suggestion)
+    }
+
+    /// Replace the gas fee of a transfer to the Bridge pool with a
+    /// suggested fee, if the transfer requested a fee estimate.
+    pub async fn estimate_bridge_pool_fee<E>(
+        eth_client: Arc<E>,
+        context: &impl Namada,
+        mut args: args::EthereumBridgePool,
+    ) -> Result<args::EthereumBridgePool, Error>
+    where
+        E: Middleware,
+        E::Error: std::fmt::Debug + std::fmt::Display,
+    {
+        let Some(estimate) = args.fee_estimate.take() else {
+            return Ok(args);
+        };
+        let suggestion = suggest_bridge_pool_fee(
+            eth_client,
+            context.client(),
+            &args.fee_token,
+            &estimate,
+        )
+        .await?;
+        let fee = crate::rpc::denominate_amount(
+            context.client(),
+            context.io(),
+            &args.fee_token,
+            suggestion.fee,
+        )
+        .await;
+        display_line!(
+            context.io(),
+            "Using an estimated Bridge pool gas fee of {fee} {}, at an \
+             Ethereum gas price of {} gwei.",
+            args.fee_token,
+            suggestion.gas_price,
+        );
+        args.fee_amount = args::InputAmount::Validated(fee);
+        Ok(args)
+    }
+
+    /// Convert a conversion rate from the fee token to gwei into the
+    /// amount of gwei a single unit of the fee token is worth, as
+    /// done when generating recommendations.
+    fn gwei_per_fee_token(conversion_rate: f64) -> Result<u64, String> {
+        if conversion_rate <= 0.0f64 || conversion_rate.is_nan() {
+            return Err(format!(
+                "Expected a positive conversion rate, got {conversion_rate}"
+            ));
+        }
+        if conversion_rate > 1e9 {
+            return Err(format!(
+                "Conversion rate is too high: {conversion_rate:.1} > 10^9"
+            ));
+        }
+        Ok((1e9 / conversion_rate).floor() as u64)
+    }
+
+    /// Convert an amount of wei to gwei, rounding up.
+    fn wei_to_gwei(wei: U256) -> u64 {
+        let gwei = wei
+            .saturating_add(U256::from(999_999_999_u64))
+            .checked_div(U256::exp10(9))
+            .unwrap_or_default();
+        gwei.min(U256::from(u64::MAX)).as_u64()
+    }
+
+    /// Pick a gas price that at least one of the next `blocks` Ethereum
+    /// blocks is expected to stay under with [`RELAY_CONFIDENCE`], treating
+    /// the given recent gas prices as a representative sample.
+    ///
+    /// The given gas prices must not be empty.
+    fn gas_price_within_blocks(mut gas_prices: Vec<u64>, blocks: u64) -> u64 {
+        gas_prices.sort_unstable();
+        // the probability that a single block is cheap enough, such that
+        // at least one of the blocks is cheap enough with the desired
+        // confidence
+        let quantile =
+            1.0 - (1.0 - RELAY_CONFIDENCE).powf(1.0 / blocks.max(1) as f64);
+        let index = (gas_prices.len() as f64 * quantile).ceil() as usize;
+        gas_prices[index.clamp(1, gas_prices.len()) - 1]
+    }
+
+    /// Compute the amount of fee tokens that pays for the given cost
+    /// in gwei, rounding up.
+    fn fee_for_cost(cost: u128, gwei_per_fee_token: u64) -> Amount {
+        let gwei_per_fee_token = u128::from(gwei_per_fee_token.max(1));
+        Amount::from_u128(
+            cost / gwei_per_fee_token
+                + u128::from(cost % gwei_per_fee_token != 0),
+        )
+    }
+
     #[cfg(test)]
     mod test_recommendations {
         use namada_core::types::address::Address;
@@ -1521,6 +1841,47 @@ mod recommendations {
             assert!(recommendation.is_none())
         }
 
+        /// Test that the suggested gas price decreases as more
+        /// Ethereum blocks are allowed for the relay.
+        #[test]
+        fn test_gas_price_within_blocks() {
+            let gas_prices: Vec<u64> = (1..=100).collect();
+            assert_eq!(gas_price_within_blocks(gas_prices.clone(), 1), 90);
+            assert_eq!(gas_price_within_blocks(gas_prices.clone(), 0), 90);
+            assert_eq!(gas_price_within_blocks(gas_prices.clone(), 10), 21);
+            assert_eq!(gas_price_within_blocks(gas_prices, 1000), 1);
+            assert_eq!(gas_price_within_blocks(vec![7], 5), 7);
+        }
+
+        /// Test converting relay costs in gwei into fee token amounts.
+        #[test]
+        fn test_fee_for_cost() {
+            // the fee token is worth 2 gwei
+            let gwei_per_fee_token = gwei_per_fee_token(5e8).unwrap();
+            assert_eq!(gwei_per_fee_token, 2);
+            assert_eq!(
+                fee_for_cost(37_500, gwei_per_fee_token),
+                Amount::from_u64(18_750)
+            );
+            // fees are rounded up
+            assert_eq!(
+                fee_for_cost(37_501, gwei_per_fee_token),
+                Amount::from_u64(18_751)
+            );
+            assert!(gwei_per_fee_token(0.0).is_err());
+            assert!(gwei_per_fee_token(-1.0).is_err());
+            assert!(gwei_per_fee_token(1e10).is_err());
+        }
+
+        /// Test converting gas prices from wei to gwei.
+        #[test]
+        fn test_wei_to_gwei() {
+            assert_eq!(wei_to_gwei(U256::zero()), 0);
+            assert_eq!(wei_to_gwei(U256::from(1_000_000_000_u64)), 1);
+            assert_eq!(wei_to_gwei(U256::from(1_000_000_001_u64)), 2);
+            assert_eq!(wei_to_gwei(U256::MAX), u64::MAX);
+        }
+
         /// Test the profit margin obtained from relaying two
         /// Bridge pool transfers with two distinct token types,
         /// whose relation is 1:2 in value.
@@ -1615,4 +1976,7 @@ mod recommendations {
     }
 }
 
-pub use recommendations::recommend_batch;
+pub use recommendations::{
+    estimate_bridge_pool_fee, query_bridge_pool_fees, recommend_batch,
+    suggest_bridge_pool_fee, BridgePoolFeeSuggestion,
+};
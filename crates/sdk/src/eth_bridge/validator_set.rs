@@ -3,6 +3,7 @@
 use std::cmp::Ordering;
 use std::future::Future;
 use std::pin::Pin;
+use std::sync::atomic::{self, AtomicBool};
 use std::sync::Arc;
 use std::task::Poll;
 
@@ -388,12 +389,40 @@ fn display_validator_set<IO: Io>(io: &IO, args: ValidatorSetArgs) {
     );
 }
 
+/// Controls of a validator set update relayer daemon, shared with the
+/// tasks that manage it while it runs.
+#[derive(Debug, Clone, Default)]
+pub struct RelayerControl {
+    paused: Arc<AtomicBool>,
+}
+
+impl RelayerControl {
+    /// Stop relaying validator set updates, until resumed.
+    pub fn pause(&self) {
+        self.paused.store(true, atomic::Ordering::SeqCst);
+    }
+
+    /// Resume relaying validator set updates.
+    pub fn resume(&self) {
+        self.paused.store(false, atomic::Ordering::SeqCst);
+    }
+
+    /// Check if the relays are paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(atomic::Ordering::SeqCst)
+    }
+}
+
 /// Relay a validator set update, signed off for a given epoch.
+///
+/// In daemon mode, the relays can be paused and resumed with the given
+/// control.
 pub async fn relay_validator_set_update<'a, E>(
     eth_client: Arc<E>,
     client: &(impl Client + Sync),
     io: &impl Io,
     args: args::ValidatorSetUpdateRelay,
+    control: &RelayerControl,
 ) -> Result<(), SdkError>
 where
     E: Middleware,
@@ -421,6 +450,7 @@ where
             eth_client,
             client,
             io,
+            control,
             &mut signal_receiver,
         )
         .await
@@ -481,6 +511,7 @@ async fn relay_validator_set_update_daemon<'a, E, F>(
     eth_client: Arc<E>,
     client: &(impl Client + Sync),
     io: &impl Io,
+    control: &RelayerControl,
     shutdown_receiver: &mut Option<F>,
 ) -> Result<(), Error>
 where
@@ -523,6 +554,11 @@ where
         tracing::debug!(?sleep_for, "Sleeping");
         time::sleep(sleep_for).await;
 
+        if control.is_paused() {
+            tracing::debug!("The relayer is paused");
+            continue;
+        }
+
         let is_synchronizing =
             eth_sync_or(&*eth_client, io, || ()).await.is_err();
         if is_synchronizing {
@@ -2,16 +2,23 @@
 //!
 //! The log can only hold `N` events at a time, where `N` is a configurable
 //! parameter. If the log is holding `N` events, and a new event is logged,
-//! old events are pruned.
+//! old events are pruned. The log can also be configured to only retain the
+//! events of the last blocks, or up to an approximate number of bytes.
 
+use std::collections::VecDeque;
 use std::default::Default;
 
-use circular_queue::CircularQueue;
+use borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::types::address::Address;
+use namada_core::types::storage::BlockHeight;
 
-use crate::events::Event;
+use crate::events::{Event, EventType};
 
 pub mod dumb_queries;
 
+/// The maximum number of events returned by a single page of the event log.
+pub const MAX_PAGE_LIMIT: u64 = 1000;
+
 /// Parameters to configure the pruning of the event log.
 #[derive(Debug, Copy, Clone)]
 pub struct Params {
@@ -20,6 +27,12 @@ pub struct Params {
     /// If the number of events in the log exceeds this value, the log
     /// will be pruned.
     pub max_log_events: usize,
+    /// The number of the last block heights whose events are retained, if
+    /// the retention is limited by blocks.
+    pub max_log_blocks: Option<u64>,
+    /// Soft limit on the approximate size of the logged events in bytes, if
+    /// the retention is limited by size.
+    pub max_log_bytes: Option<usize>,
 }
 
 impl Default for Params {
@@ -27,15 +40,99 @@ impl Default for Params {
         // TODO: tune the default params
         Self {
             max_log_events: 50000,
+            max_log_blocks: None,
+            max_log_bytes: None,
+        }
+    }
+}
+
+/// An event with its position in the event log.
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct LoggedEvent {
+    /// The sequence number of the event, increasing with every logged event.
+    /// Used as the cursor to paginate the log.
+    pub index: u64,
+    /// The height of the block that emitted the event
+    pub height: BlockHeight,
+    /// The event
+    pub event: Event,
+}
+
+/// A filter of the events of the log, used to fetch them by pages.
+#[derive(Clone, Debug, Default, BorshSerialize, BorshDeserialize)]
+pub struct EventLogQuery {
+    /// Only match the events emitted at this height or later
+    pub from_height: Option<BlockHeight>,
+    /// Only match the events emitted at this height or earlier
+    pub to_height: Option<BlockHeight>,
+    /// Only match the events of this type
+    pub event_type: Option<EventType>,
+    /// Only match the events with an attribute holding this address
+    pub account: Option<Address>,
+    /// Only match the events logged after the event with this index, i.e.
+    /// the `next` cursor of the previous page
+    pub after: Option<u64>,
+    /// The maximum number of events of the page, capped by
+    /// [`MAX_PAGE_LIMIT`]
+    pub limit: u64,
+}
+
+impl EventLogQuery {
+    /// Checks if the logged event matches this query, ignoring the cursor.
+    pub fn matches(&self, logged: &LoggedEvent) -> bool {
+        if matches!(self.from_height, Some(from) if logged.height < from)
+            || matches!(self.to_height, Some(to) if logged.height > to)
+        {
+            return false;
+        }
+        if matches!(
+            &self.event_type,
+            Some(event_type) if &logged.event.event_type != event_type
+        ) {
+            return false;
+        }
+        match &self.account {
+            Some(account) => {
+                let account = account.encode();
+                let mut values = logged.event.attributes.values();
+                values.any(|value| value == &account)
+            }
+            None => true,
         }
     }
 }
 
+/// A page of the events of the log, in the order they were logged.
+#[derive(Clone, Debug, Default, BorshSerialize, BorshDeserialize)]
+pub struct EventLogPage {
+    /// The matching events
+    pub events: Vec<LoggedEvent>,
+    /// The cursor to fetch the next page with, if there are more matching
+    /// events
+    pub next: Option<u64>,
+    /// The height of the oldest event still held by the log. The events of
+    /// earlier heights were pruned and can't be fetched anymore.
+    pub oldest_height: Option<BlockHeight>,
+}
+
+/// An event held by the log with its approximate size.
+#[derive(Debug)]
+struct Entry {
+    logged: LoggedEvent,
+    size: usize,
+}
+
 /// Represents a log of [`Event`] instances emitted by
 /// `FinalizeBlock` calls, in the ledger.
 #[derive(Debug)]
 pub struct EventLog {
-    queue: CircularQueue<Event>,
+    /// The events from the oldest to the newest.
+    queue: VecDeque<Entry>,
+    params: Params,
+    /// The index of the next logged event.
+    next_index: u64,
+    /// The total size of the events held by the log.
+    bytes: usize,
 }
 
 impl Default for EventLog {
@@ -48,27 +145,68 @@ impl EventLog {
     /// Return a new event log.
     pub fn new(params: Params) -> Self {
         Self {
-            queue: CircularQueue::with_capacity(params.max_log_events),
+            queue: VecDeque::new(),
+            params,
+            next_index: 0,
+            bytes: 0,
         }
     }
 
-    /// Log a new batch of events into the event log.
-    pub fn log_events<E>(&mut self, events: E)
+    /// Log a new batch of events emitted at the given height into the event
+    /// log.
+    pub fn log_events<E>(&mut self, height: BlockHeight, events: E)
     where
         E: IntoIterator<Item = Event>,
     {
         let mut num_entries = 0;
         for event in events.into_iter() {
-            self.queue.push(event);
+            let size = event_size(&event);
+            self.queue.push_back(Entry {
+                logged: LoggedEvent {
+                    index: self.next_index,
+                    height,
+                    event,
+                },
+                size,
+            });
+            self.next_index += 1;
+            self.bytes += size;
             num_entries += 1;
         }
+        self.prune();
         tracing::debug!(num_entries, "Added new entries to the event log");
     }
 
-    /// Returns a new iterator over this [`EventLog`].
+    /// Remove the oldest events until the log is within its retention
+    /// limits.
+    fn prune(&mut self) {
+        let Some(newest_height) = self.queue.back().map(|e| e.logged.height)
+        else {
+            return;
+        };
+        while let Some(oldest) = self.queue.front() {
+            let exceeds_events = self.queue.len() > self.params.max_log_events;
+            let exceeds_blocks = matches!(
+                self.params.max_log_blocks,
+                Some(max) if newest_height.0 - oldest.logged.height.0 >= max
+            );
+            let exceeds_bytes = matches!(
+                self.params.max_log_bytes,
+                Some(max) if self.bytes > max
+            );
+            if !(exceeds_events || exceeds_blocks || exceeds_bytes) {
+                break;
+            }
+            self.bytes -= oldest.size;
+            self.queue.pop_front();
+        }
+    }
+
+    /// Returns a new iterator over this [`EventLog`], from the newest to the
+    /// oldest event.
     #[inline]
     pub fn iter(&self) -> impl Iterator<Item = &Event> {
-        self.queue.iter()
+        self.queue.iter().rev().map(|entry| &entry.logged.event)
     }
 
     /// Returns a filtering iterator over this [`EventLog`].
@@ -77,18 +215,59 @@ impl EventLog {
         &self,
         matcher: dumb_queries::QueryMatcher,
     ) -> impl Iterator<Item = &Event> {
-        self.queue
-            .iter()
-            .filter(move |&event| matcher.matches(event))
+        self.iter().filter(move |&event| matcher.matches(event))
     }
+
+    /// Returns a page of the events matching the query, from the oldest to
+    /// the newest.
+    pub fn page(&self, query: &EventLogQuery) -> EventLogPage {
+        let limit = query.limit.min(MAX_PAGE_LIMIT) as usize;
+        // The indices are increasing, so the events after the cursor are
+        // found with a binary search
+        let start = match query.after {
+            Some(after) => self
+                .queue
+                .partition_point(|entry| entry.logged.index <= after),
+            None => 0,
+        };
+        let mut matching = self
+            .queue
+            .range(start..)
+            .map(|entry| &entry.logged)
+            .filter(|logged| query.matches(logged));
+        let events: Vec<LoggedEvent> =
+            matching.by_ref().take(limit).cloned().collect();
+        let next = match events.last() {
+            Some(last) if matching.next().is_some() => Some(last.index),
+            _ => None,
+        };
+        EventLogPage {
+            events,
+            next,
+            oldest_height: self.queue.front().map(|e| e.logged.height),
+        }
+    }
+}
+
+/// The approximate size of an event in bytes.
+fn event_size(event: &Event) -> usize {
+    event.event_type.to_string().len()
+        + event
+            .attributes
+            .iter()
+            .map(|(key, value)| key.len() + value.len())
+            .sum::<usize>()
 }
 
 #[cfg(test)]
 mod tests {
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
     use namada_core::types::hash::Hash;
 
     use super::*;
-    use crate::events::{EventLevel, EventType};
+    use crate::events::EventLevel;
 
     const HASH: &str =
         "DEADBEEFDEADBEEFDEADBEEFDEADBEEFDEADBEEFDEADBEEFDEADBEEFDEADBEEF";
@@ -134,8 +313,8 @@ mod tests {
         // add new events to the log
         let events = mock_tx_events(HASH);
 
-        for _ in 0..NUM_HEIGHTS {
-            log.log_events(events.clone());
+        for height in 0..NUM_HEIGHTS {
+            log.log_events(BlockHeight(height as u64), events.clone());
         }
 
         // inspect log
@@ -164,6 +343,7 @@ mod tests {
 
         let mut log = EventLog::new(Params {
             max_log_events: LOG_CAP,
+            ..Params::default()
         });
 
         // completely fill the log with events
@@ -173,8 +353,8 @@ mod tests {
         let events = mock_tx_events(HASH);
         assert_eq!(events.len(), 2);
 
-        for _ in 0..(LOG_CAP / 2) {
-            log.log_events(events.clone());
+        for height in 0..(LOG_CAP / 2) {
+            log.log_events(BlockHeight(height as u64), events.clone());
         }
 
         // inspect log - it should be full
@@ -189,7 +369,10 @@ mod tests {
 
         // add a new APPLIED event to the log,
         // pruning the first ACCEPTED event we added
-        log.log_events(Some(events[1].clone()));
+        log.log_events(
+            BlockHeight((LOG_CAP / 2) as u64),
+            Some(events[1].clone()),
+        );
 
         let events_in_log: Vec<_> =
             log.iter_with_matcher(accepted!(HASH)).cloned().collect();
@@ -201,4 +384,107 @@ mod tests {
             assert_eq!(events[0], event);
         }
     }
+
+    /// Test pruning the events of the heights beyond the retained blocks.
+    #[test]
+    fn test_log_prune_by_blocks() {
+        let mut log = EventLog::new(Params {
+            max_log_blocks: Some(2),
+            ..Params::default()
+        });
+        let events = mock_tx_events(HASH);
+        for height in 1..=3 {
+            log.log_events(BlockHeight(height), events.clone());
+        }
+
+        // only the events of the last 2 heights are retained
+        let page = log.page(&EventLogQuery {
+            limit: MAX_PAGE_LIMIT,
+            ..Default::default()
+        });
+        assert_eq!(page.oldest_height, Some(BlockHeight(2)));
+        assert_eq!(page.events.len(), 4);
+        assert!(page.events.iter().all(|e| e.height >= BlockHeight(2)));
+    }
+
+    /// Test pruning the oldest events when the log exceeds its size.
+    #[test]
+    fn test_log_prune_by_bytes() {
+        let events = mock_tx_events(HASH);
+        let size = event_size(&events[0]);
+        let mut log = EventLog::new(Params {
+            max_log_bytes: Some(3 * size),
+            ..Params::default()
+        });
+        for height in 0..4 {
+            log.log_events(BlockHeight(height), events.clone());
+        }
+
+        assert!(log.bytes <= 3 * size);
+        let indices: Vec<u64> = log
+            .page(&EventLogQuery {
+                limit: MAX_PAGE_LIMIT,
+                ..Default::default()
+            })
+            .events
+            .into_iter()
+            .map(|logged| logged.index)
+            .collect();
+        assert_eq!(indices.last(), Some(&7));
+        assert!(indices.len() <= 3);
+    }
+
+    /// Test fetching the filtered events of the log by pages.
+    #[test]
+    fn test_log_page() {
+        let account = established_address_1();
+        let mut log = EventLog::new(Params::default());
+        for height in 1..=5 {
+            let mut events = mock_tx_events(HASH);
+            events[1]
+                .attributes
+                .insert("owner".to_string(), account.encode());
+            log.log_events(BlockHeight(height), events);
+        }
+
+        // page through the applied events of the account from height 2
+        let mut query = EventLogQuery {
+            from_height: Some(BlockHeight(2)),
+            to_height: None,
+            event_type: Some(EventType::Applied),
+            account: Some(account),
+            after: None,
+            limit: 3,
+        };
+        let page = log.page(&query);
+        let heights: Vec<_> =
+            page.events.iter().map(|logged| logged.height.0).collect();
+        assert_eq!(heights, vec![2, 3, 4]);
+        assert_eq!(page.next, Some(page.events[2].index));
+        assert_eq!(page.oldest_height, Some(BlockHeight(1)));
+
+        query.after = page.next;
+        let page = log.page(&query);
+        let heights: Vec<_> =
+            page.events.iter().map(|logged| logged.height.0).collect();
+        assert_eq!(heights, vec![5]);
+        assert_eq!(page.next, None);
+
+        // the height range is inclusive
+        let page = log.page(&EventLogQuery {
+            from_height: Some(BlockHeight(3)),
+            to_height: Some(BlockHeight(3)),
+            limit: MAX_PAGE_LIMIT,
+            ..Default::default()
+        });
+        assert_eq!(page.events.len(), 2);
+
+        // no events hold another account
+        let page = log.page(&EventLogQuery {
+            account: Some(established_address_2()),
+            limit: MAX_PAGE_LIMIT,
+            ..Default::default()
+        });
+        assert!(page.events.is_empty());
+    }
 }
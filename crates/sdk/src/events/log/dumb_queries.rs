@@ -8,6 +8,7 @@
 
 use std::collections::HashMap;
 
+use data_encoding::HEXUPPER;
 use namada_core::types::hash::Hash;
 use namada_core::types::storage::BlockHeight;
 
@@ -61,6 +62,17 @@ impl QueryMatcher {
         }
     }
 
+    /// Returns a query matching the applied transactions with the given
+    /// memo.
+    pub fn applied_with_memo(memo: &[u8]) -> Self {
+        let mut attributes = HashMap::new();
+        attributes.insert("memo".to_string(), HEXUPPER.encode(memo));
+        Self {
+            event_type: EventType::Applied,
+            attributes,
+        }
+    }
+
     /// Returns a query matching the given IBC UpdateClient parameters
     pub fn ibc_update_client(
         client_id: ClientId,
@@ -168,4 +180,22 @@ mod tests {
             }
         }
     }
+
+    /// Test that applied txs are matched by their memo.
+    #[test]
+    fn test_memo_query_matching() {
+        let matcher = QueryMatcher::applied_with_memo(b"deposit-42");
+        let event = |memo: &[u8]| Event {
+            event_type: EventType::Applied,
+            level: EventLevel::Tx,
+            attributes: {
+                let mut attrs = std::collections::HashMap::new();
+                attrs.insert("memo".to_string(), HEXUPPER.encode(memo));
+                attrs
+            },
+        };
+
+        assert!(matcher.matches(&event(b"deposit-42")));
+        assert!(!matcher.matches(&event(b"deposit-43")));
+    }
 }
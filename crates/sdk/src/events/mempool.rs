@@ -0,0 +1,130 @@
+//! A log of the txs rejected by the mempool validation (`CheckTx` calls) in
+//! the ledger.
+//!
+//! Rejected txs never make it into a block, so they don't emit any events.
+//! The log keeps the last `N` rejections, where `N` is a configurable
+//! parameter, to let operators and integrators find out why a tx never
+//! landed.
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use circular_queue::CircularQueue;
+use namada_core::types::hash::Hash;
+use namada_core::types::storage::BlockHeight;
+use namada_core::types::time::DateTimeUtc;
+use serde::{Deserialize, Serialize};
+
+/// Parameters to configure the mempool rejections log.
+#[derive(Debug, Copy, Clone)]
+pub struct Params {
+    /// Maximum number of rejections the log can hold. When the log is full,
+    /// the oldest rejections are pruned.
+    pub max_rejections: usize,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            max_rejections: 1000,
+        }
+    }
+}
+
+/// A tx rejected by the mempool validation.
+///
+/// CometBFT doesn't forward the address of the peer or client that submitted
+/// the tx to the ABCI app, so the source of the tx is not recorded.
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct MempoolRejection {
+    /// The hash of the raw tx bytes, as reported by CometBFT
+    pub tx_hash: Hash,
+    /// The `ResultCode` of the rejection
+    pub code: u32,
+    /// The reason of the rejection
+    pub log: String,
+    /// Height of the last committed block when the tx was rejected
+    pub height: BlockHeight,
+    /// Time of the rejection
+    pub time: DateTimeUtc,
+}
+
+/// Represents a log of the last [`MempoolRejection`]s, in the ledger.
+#[derive(Debug)]
+pub struct MempoolRejections {
+    queue: CircularQueue<MempoolRejection>,
+}
+
+impl Default for MempoolRejections {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl MempoolRejections {
+    /// Return a new mempool rejections log.
+    pub fn new(params: Params) -> Self {
+        Self {
+            queue: CircularQueue::with_capacity(params.max_rejections),
+        }
+    }
+
+    /// Log a new rejection, pruning the oldest one if the log is full.
+    pub fn log_rejection(&mut self, rejection: MempoolRejection) {
+        tracing::debug!(
+            tx_hash = %rejection.tx_hash,
+            code = rejection.code,
+            log = %rejection.log,
+            "Tx rejected by the mempool validation"
+        );
+        self.queue.push(rejection);
+    }
+
+    /// Returns an iterator over the logged rejections, from the most recent
+    /// to the oldest.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &MempoolRejection> {
+        self.queue.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rejection(code: u32) -> MempoolRejection {
+        MempoolRejection {
+            tx_hash: Hash::sha256(code.to_le_bytes()),
+            code,
+            log: format!("Mempool validation failed: {code}"),
+            height: BlockHeight(1),
+            time: DateTimeUtc::now(),
+        }
+    }
+
+    /// Test that the log holds the most recent rejections first and that
+    /// the oldest rejections are pruned.
+    #[test]
+    fn test_log_rejections() {
+        const LOG_CAP: usize = 3;
+
+        let mut log = MempoolRejections::new(Params {
+            max_rejections: LOG_CAP,
+        });
+        assert_eq!(log.iter().count(), 0);
+
+        for code in 1..=LOG_CAP as u32 + 2 {
+            log.log_rejection(rejection(code));
+        }
+
+        let codes: Vec<u32> = log.iter().map(|r| r.code).collect();
+        assert_eq!(codes, vec![5, 4, 3]);
+    }
+}
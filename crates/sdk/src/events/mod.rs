@@ -1,5 +1,6 @@
 //! Logic to do with events emitted by the ledger.
 pub mod log;
+pub mod mempool;
 
 use std::collections::HashMap;
 use std::convert::TryFrom;
@@ -8,6 +9,7 @@ use std::ops::{Index, IndexMut};
 use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use data_encoding::HEXUPPER;
 use namada_core::types::ethereum_structs::{BpTransferStatus, EthBridgeEvent};
 use namada_core::types::ibc::IbcEvent;
 use namada_tx::data::TxType;
@@ -86,6 +88,8 @@ pub enum EventType {
     PgfPayment,
     /// Ethereum Bridge event
     EthereumBridge,
+    /// A validator's state changed by the protocol
+    ValidatorStateChange,
 }
 
 impl Display for EventType {
@@ -97,6 +101,9 @@ impl Display for EventType {
             EventType::Proposal => write!(f, "proposal"),
             EventType::PgfPayment => write!(f, "pgf_payment"),
             EventType::EthereumBridge => write!(f, "ethereum_bridge"),
+            EventType::ValidatorStateChange => {
+                write!(f, "validator_state_change")
+            }
         }?;
         Ok(())
     }
@@ -118,6 +125,7 @@ impl FromStr for EventType {
                 Ok(EventType::Ibc("write_acknowledgement".to_string()))
             }
             "ethereum_bridge" => Ok(EventType::EthereumBridge),
+            "validator_state_change" => Ok(EventType::ValidatorStateChange),
             _ => Err(EventError::InvalidEventType),
         }
     }
@@ -161,6 +169,9 @@ impl Event {
             }
             _ => unreachable!(),
         };
+        if let Some(memo) = tx.memo() {
+            event["memo"] = HEXUPPER.encode(&memo);
+        }
         event["height"] = height.to_string();
         event["log"] = "".to_string();
         event
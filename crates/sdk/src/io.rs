@@ -46,6 +46,17 @@ pub trait Io {
         eprintln!("{}", output.as_ref());
     }
 
+    /// Start reporting the progress of a long-running operation with the
+    /// given message and number of steps, if known. The report ends when the
+    /// returned handle is dropped. Nothing is reported by default.
+    fn progress(
+        &self,
+        _message: impl AsRef<str>,
+        _len: Option<u64>,
+    ) -> Box<dyn Progress> {
+        Box::new(())
+    }
+
     /// Read a string from input
     async fn read(&self) -> std::io::Result<String> {
         #[cfg(not(target_family = "wasm"))]
@@ -83,6 +94,19 @@ pub trait Io {
     }
 }
 
+/// A handle to report the progress of a long-running operation, obtained
+/// from [`Io::progress`].
+pub trait Progress: MaybeSend + MaybeSync {
+    /// Advance the progress by the given number of steps
+    fn inc(&self, _steps: u64) {}
+
+    /// Set the total number of steps, once it's known
+    fn set_length(&self, _len: u64) {}
+}
+
+/// Discards the progress reports.
+impl Progress for () {}
+
 /// Rust native I/O handling.
 #[derive(Default)]
 pub struct StdIo;
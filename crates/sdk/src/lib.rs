@@ -24,7 +24,10 @@ pub mod error;
 pub mod events;
 pub(crate) mod internal_macros;
 pub mod io;
+pub mod light_client;
+pub mod proposer;
 pub mod queries;
+pub mod storage_value;
 pub mod wallet;
 
 use std::collections::HashSet;
@@ -42,6 +45,7 @@ use namada_core::types::dec::Dec;
 use namada_core::types::ethereum_events::EthAddress;
 use namada_core::types::key::*;
 use namada_core::types::masp::{TransferSource, TransferTarget};
+use namada_core::types::storage::Epoch;
 use namada_core::types::token;
 use namada_tx::data::wrapper::GasLimit;
 use namada_tx::Tx;
@@ -56,14 +60,16 @@ use crate::signing::SigningTxData;
 use crate::token::{DenominatedAmount, NATIVE_MAX_DECIMAL_PLACES};
 use crate::tx::{
     ProcessTxResponse, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
-    TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
+    TX_BRIDGE_PAUSE_VOTE_WASM, TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
     TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
-    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
-    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
-    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
-    TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
-    VP_USER_WASM,
+    TX_CLAIM_REWARDS_WASM, TX_CONVERT_NUTS_WASM, TX_DEACTIVATE_VALIDATOR_WASM,
+    TX_IBC_WASM, TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL,
+    TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+    TX_REVEAL_PK, TX_SCHEDULE_WASM, TX_SET_AUTO_COMPOUND_WASM,
+    TX_TRANSFER_WASM, TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM,
+    TX_UPDATE_ACCOUNT_KEYS_WASM, TX_UPDATE_ACCOUNT_WASM,
+    TX_UPDATE_STEWARD_COMMISSION, TX_UPDATE_VOTE_PROXY,
+    TX_VESTING_TRANSFER_WASM, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM, VP_USER_WASM,
 };
 use crate::wallet::{Wallet, WalletIo, WalletStorage};
 
@@ -124,6 +130,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             dry_run: false,
             dry_run_wrapper: false,
             dump_tx: false,
+            offline_sign: false,
             output_folder: None,
             force: false,
             broadcast_only: false,
@@ -135,7 +142,10 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             fee_token: self.native_token(),
             fee_unshield: None,
             gas_limit: GasLimit::from(20_000),
+            estimate_gas_limit: false,
             expiration: None,
+            expiration_height: None,
+            auto_resubmit: None,
             disposable_signing_key: false,
             chain_id: None,
             signing_keys: vec![],
@@ -144,6 +154,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             password: None,
             memo: None,
             use_device: false,
+            wallet_daemon: None,
         }
     }
 
@@ -166,6 +177,48 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a TxVestingTransfer builder from the given minimum set of
+    /// arguments
+    fn new_vesting_transfer(
+        &self,
+        source: Address,
+        target: Address,
+        token: Address,
+        amount: InputAmount,
+        start: Epoch,
+        end: Epoch,
+    ) -> args::TxVestingTransfer {
+        args::TxVestingTransfer {
+            source,
+            target,
+            token,
+            amount,
+            start,
+            end,
+            tx_code_path: PathBuf::from(TX_VESTING_TRANSFER_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a TxSchedule builder from the given minimum set of arguments
+    fn new_schedule_tx(
+        &self,
+        owner: Address,
+        epoch: Epoch,
+        gas_limit: GasLimit,
+        code_path: PathBuf,
+    ) -> args::TxSchedule {
+        args::TxSchedule {
+            owner,
+            epoch,
+            gas_limit,
+            code_path,
+            data: None,
+            tx_code_path: PathBuf::from(TX_SCHEDULE_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a InitAccount builder from the given minimum set of arguments
     fn new_init_account(
         &self,
@@ -275,6 +328,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             is_offline: false,
             is_pgf_stewards: false,
             is_pgf_funding: false,
+            is_ibc_client_recovery: false,
             tx_code_path: PathBuf::from(TX_INIT_PROPOSAL),
             tx: self.tx_builder(),
         }
@@ -292,6 +346,23 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a TxUpdateAccountKeys builder from the given minimum set of
+    /// arguments
+    fn new_update_account_keys(
+        &self,
+        addr: Address,
+        public_keys: Vec<common::PublicKey>,
+        threshold: u8,
+    ) -> args::TxUpdateAccountKeys {
+        args::TxUpdateAccountKeys {
+            addr,
+            public_keys,
+            threshold: Some(threshold),
+            tx_code_path: PathBuf::from(TX_UPDATE_ACCOUNT_KEYS_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a VoteProposal builder from the given minimum set of arguments
     fn new_vote_prposal(
         &self,
@@ -309,6 +380,21 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make an UpdateVoteProxy builder from the given minimum set of
+    /// arguments
+    fn new_update_vote_proxy(
+        &self,
+        delegator: Address,
+        proxy: Option<Address>,
+    ) -> args::UpdateVoteProxy {
+        args::UpdateVoteProxy {
+            delegator,
+            proxy,
+            tx_code_path: PathBuf::from(TX_UPDATE_VOTE_PROXY),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a CommissionRateChange builder from the given minimum set of
     /// arguments
     fn new_change_commission_rate(
@@ -439,6 +525,35 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a TxBridgePauseVote builder from the given minimum set of
+    /// arguments
+    fn new_bridge_pause_vote(
+        &self,
+        validator: Address,
+    ) -> args::TxBridgePauseVote {
+        args::TxBridgePauseVote {
+            validator,
+            tx_code_path: PathBuf::from(TX_BRIDGE_PAUSE_VOTE_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a TxConvertNuts builder from the given minimum set of arguments
+    fn new_convert_nuts(
+        &self,
+        owner: Address,
+        asset: EthAddress,
+        amount: InputAmount,
+    ) -> args::TxConvertNuts {
+        args::TxConvertNuts {
+            owner,
+            asset,
+            amount,
+            tx_code_path: PathBuf::from(TX_CONVERT_NUTS_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a TxReactivateValidator builder from the given minimum set of
     /// arguments
     fn new_reactivate_validator(
@@ -472,6 +587,22 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make an auto-compounding builder from the given minimum set of
+    /// arguments
+    fn new_set_auto_compound(
+        &self,
+        validator: Address,
+        enabled: bool,
+    ) -> args::SetAutoCompound {
+        args::SetAutoCompound {
+            validator,
+            source: None,
+            enabled,
+            tx_code_path: PathBuf::from(TX_SET_AUTO_COMPOUND_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a Withdraw builder from the given minimum set of arguments
     fn new_add_erc20_transfer(
         &self,
@@ -493,6 +624,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             ),
             fee_payer: None,
             fee_token: self.native_token(),
+            fee_estimate: None,
             nut: false,
             code_path: PathBuf::from(TX_BRIDGE_POOL_WASM),
             tx: self.tx_builder(),
@@ -551,6 +683,17 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             + MaybeSync
             + std::future::Future<Output = crate::error::Result<Tx>>,
     {
+        if args.estimate_gas_limit {
+            signing::estimate_gas_limit(
+                self,
+                args,
+                tx,
+                signing_data.clone(),
+                &with,
+                user_data.clone(),
+            )
+            .await?;
+        }
         signing::sign_tx(
             self.wallet_lock(),
             args,
@@ -639,6 +782,7 @@ where
                 dry_run: false,
                 dry_run_wrapper: false,
                 dump_tx: false,
+                offline_sign: false,
                 output_folder: None,
                 force: false,
                 broadcast_only: false,
@@ -650,7 +794,10 @@ where
                 fee_token: native_token,
                 fee_unshield: None,
                 gas_limit: GasLimit::from(20_000),
+                estimate_gas_limit: false,
                 expiration: None,
+                expiration_height: None,
+                auto_resubmit: None,
                 disposable_signing_key: false,
                 chain_id: None,
                 signing_keys: vec![],
@@ -659,6 +806,7 @@ where
                 password: None,
                 memo: None,
                 use_device: false,
+                wallet_daemon: None,
             },
         }
     }
@@ -767,7 +915,7 @@ pub mod testing {
     };
     use namada_core::types::eth_bridge_pool::PendingTransfer;
     use namada_core::types::hash::testing::arb_hash;
-    use namada_core::types::storage::testing::arb_epoch;
+    use namada_core::types::storage::testing::{arb_block_height, arb_epoch};
     use namada_core::types::token::testing::{
         arb_denominated_amount, arb_transfer,
     };
@@ -937,6 +1085,7 @@ pub mod testing {
         pub fn arb_header()(
             chain_id in arb_chain_id(),
             expiration in option::of(arb_date_time_utc()),
+            expiration_height in option::of(arb_block_height()),
             timestamp in arb_date_time_utc(),
             code_hash in arb_hash(),
             data_hash in arb_hash(),
@@ -946,6 +1095,7 @@ pub mod testing {
             Header {
                 chain_id,
                 expiration,
+                expiration_height,
                 timestamp,
                 data_hash,
                 code_hash,
@@ -0,0 +1,368 @@
+//! In-process light client verification of query results.
+//!
+//! The light client keeps a trusted [`LightBlock`] and uses it to verify
+//! the headers served by a (possibly untrusted) full node. Storage values
+//! returned by `abci_query` are then checked against the app hash of a
+//! verified header with the Merkle proofs attached to the response.
+
+use ics23::commitment_proof::Proof as Ics23Proof;
+use ics23::{CommitmentProof, HostFunctionsManager};
+use namada_core::types::storage::{self, BlockHeight};
+use namada_state::ics23_specs::{ibc_proof_specs, proof_specs};
+use namada_state::merkle_tree::StoreType;
+use namada_state::Sha256Hasher;
+use prost::Message;
+use tendermint_light_client_verifier::options::Options;
+use tendermint_light_client_verifier::types::{
+    LightBlock, PeerId, TrustThreshold,
+};
+use tendermint_light_client_verifier::{ProdVerifier, Verdict, Verifier};
+
+use crate::error::{Error, QueryError};
+use crate::queries::{Client, RPC};
+use crate::tendermint::block::Height;
+use crate::tendermint::merkle::proof::ProofOps;
+use crate::tendermint::validator::Set as ValidatorSet;
+use crate::tendermint::{Hash, Time};
+
+/// The maximum number of validators requested per page from `/validators`
+const VALIDATORS_PER_PAGE: u8 = 100;
+
+/// The trusting period used when none is provided. Must be shorter than the
+/// unbonding period of the chain.
+pub const DEFAULT_TRUSTING_PERIOD: std::time::Duration =
+    std::time::Duration::from_secs(14 * 24 * 60 * 60);
+
+fn unverified(msg: impl Into<String>) -> Error {
+    Error::from(QueryError::Unverified(msg.into()))
+}
+
+/// Default light client verification options
+pub fn default_options() -> Options {
+    Options {
+        trust_threshold: TrustThreshold::ONE_THIRD,
+        trusting_period: DEFAULT_TRUSTING_PERIOD,
+        clock_drift: std::time::Duration::from_secs(10),
+    }
+}
+
+/// Fetch the full validator set at the given height.
+pub(crate) async fn fetch_validator_set<C: Client + Sync>(
+    client: &C,
+    height: Height,
+) -> Result<ValidatorSet, Error> {
+    let mut validators = vec![];
+    let mut page = 1;
+    loop {
+        let response = client
+            .validators(height, page, VALIDATORS_PER_PAGE)
+            .await
+            .map_err(|err| Error::from(QueryError::General(err.to_string())))?;
+        let total = response.total as usize;
+        validators.extend(response.validators);
+        if validators.len() >= total {
+            break;
+        }
+        page += 1;
+    }
+    Ok(ValidatorSet::without_proposer(validators))
+}
+
+/// Fetch an unverified light block at the given height from the node.
+pub async fn fetch_light_block<C: Client + Sync>(
+    client: &C,
+    height: Height,
+    provider: PeerId,
+) -> Result<LightBlock, Error> {
+    let signed_header = client
+        .commit(height)
+        .await
+        .map_err(|err| Error::from(QueryError::General(err.to_string())))?
+        .signed_header;
+    let validators = fetch_validator_set(client, height).await?;
+    let next_validators =
+        fetch_validator_set(client, height.increment()).await?;
+    Ok(LightBlock::new(
+        signed_header,
+        validators,
+        next_validators,
+        provider,
+    ))
+}
+
+/// Fetch the light block at the given height and check that its hash matches
+/// the one given by the user. The result can be used as the initial trusted
+/// state of the light client.
+pub async fn bootstrap<C: Client + Sync>(
+    client: &C,
+    height: BlockHeight,
+    hash: Hash,
+) -> Result<LightBlock, Error> {
+    let provider = client
+        .status()
+        .await
+        .map_err(|err| Error::from(QueryError::General(err.to_string())))?
+        .node_info
+        .id;
+    let height = Height::try_from(height.0)
+        .map_err(|err| Error::Other(err.to_string()))?;
+    let light_block = fetch_light_block(client, height, provider).await?;
+    let block_hash = light_block.signed_header.header.hash();
+    if block_hash != hash {
+        return Err(unverified(format!(
+            "The header at height {height} has hash {block_hash}, but the \
+             trusted hash is {hash}"
+        )));
+    }
+    Ok(light_block)
+}
+
+/// Verify the header at `target` height starting from the `trusted` light
+/// block, using skipping verification with bisection. On success, returns
+/// the verified light block at `target`.
+pub async fn verify_to_height<C: Client + Sync>(
+    client: &C,
+    trusted: &LightBlock,
+    target: Height,
+    options: &Options,
+) -> Result<LightBlock, Error> {
+    if target == trusted.height() {
+        return Ok(trusted.clone());
+    }
+    if target < trusted.height() {
+        return Err(unverified(format!(
+            "Cannot verify height {target} backwards from the trusted height \
+             {}",
+            trusted.height()
+        )));
+    }
+    let verifier = ProdVerifier::default();
+    let now = Time::now();
+    let mut trusted = trusted.clone();
+    // Heights that still have to be verified, the last one is tried first
+    let mut pending = vec![target];
+    while let Some(&height) = pending.last() {
+        let untrusted =
+            fetch_light_block(client, height, trusted.provider).await?;
+        match verifier.verify_update_header(
+            untrusted.as_untrusted_state(),
+            trusted.as_trusted_state(),
+            options,
+            now,
+        ) {
+            Verdict::Success => {
+                pending.pop();
+                trusted = untrusted;
+            }
+            Verdict::NotEnoughTrust(_) => {
+                let low = trusted.height().value();
+                let high = height.value();
+                let pivot = low + (high - low) / 2;
+                if pivot == low {
+                    return Err(unverified(format!(
+                        "Not enough trust to verify height {height}"
+                    )));
+                }
+                pending.push(
+                    Height::try_from(pivot)
+                        .map_err(|err| Error::Other(err.to_string()))?,
+                );
+            }
+            Verdict::Invalid(err) => {
+                return Err(unverified(format!(
+                    "Invalid header at height {height}: {err}"
+                )));
+            }
+        }
+    }
+    Ok(trusted)
+}
+
+/// Check the Merkle proof of a storage value (or of its absence, when `value`
+/// is `None`) against the given app hash.
+pub fn verify_storage_proof(
+    app_hash: &[u8],
+    key: &storage::Key,
+    value: Option<&[u8]>,
+    proof: &ProofOps,
+) -> Result<(), Error> {
+    let (store_type, sub_key) = StoreType::sub_key(key)
+        .map_err(|err| unverified(format!("Invalid key {key}: {err}")))?;
+    let specs = match store_type {
+        StoreType::Account | StoreType::PoS => proof_specs::<Sha256Hasher>(),
+        StoreType::Ibc => ibc_proof_specs::<Sha256Hasher>(),
+        StoreType::Base | StoreType::BridgePool => {
+            return Err(unverified(format!(
+                "Proofs of keys in the {store_type} sub-tree are not supported"
+            )));
+        }
+    };
+    let [sub_op, base_op] = &proof.ops[..] else {
+        return Err(unverified(format!(
+            "Expected a sub-tree and a base tree proof, got {} proof ops",
+            proof.ops.len()
+        )));
+    };
+    let decode = |data: &[u8]| {
+        CommitmentProof::decode(data)
+            .map_err(|err| unverified(format!("Invalid proof: {err}")))
+    };
+    let sub_proof = decode(&sub_op.data)?;
+    let base_proof = decode(&base_op.data)?;
+
+    let sub_root = match &sub_proof.proof {
+        Some(Ics23Proof::Exist(ep)) => {
+            ics23::calculate_existence_root::<HostFunctionsManager>(ep)
+        }
+        Some(Ics23Proof::Nonexist(nep)) => {
+            match nep.left.as_ref().or(nep.right.as_ref()) {
+                Some(ep) => {
+                    ics23::calculate_existence_root::<HostFunctionsManager>(ep)
+                }
+                None => {
+                    return Err(unverified("Empty non-existence proof"));
+                }
+            }
+        }
+        _ => return Err(unverified("Unexpected sub-tree proof type")),
+    }
+    .map_err(|err| unverified(format!("Invalid sub-tree proof: {err}")))?;
+
+    let sub_key = sub_key.to_string();
+    let sub_verified = match value {
+        Some(value) => ics23::verify_membership::<HostFunctionsManager>(
+            &sub_proof,
+            &specs[0],
+            &sub_root,
+            sub_key.as_bytes(),
+            value,
+        ),
+        None => ics23::verify_non_membership::<HostFunctionsManager>(
+            &sub_proof,
+            &specs[0],
+            &sub_root,
+            sub_key.as_bytes(),
+        ),
+    };
+    if !sub_verified {
+        return Err(unverified(format!(
+            "The sub-tree proof of key {key} is invalid"
+        )));
+    }
+
+    let base_verified = ics23::verify_membership::<HostFunctionsManager>(
+        &base_proof,
+        &specs[1],
+        &app_hash.to_vec(),
+        store_type.to_string().as_bytes(),
+        &sub_root,
+    );
+    if !base_verified {
+        return Err(unverified(format!(
+            "The base tree proof of key {key} doesn't match the app hash"
+        )));
+    }
+    Ok(())
+}
+
+/// Query a storage value without decoding and verify it against a header
+/// verified from the `trusted` light block. Returns the value together with
+/// the newly verified light block, which should replace the trusted state.
+pub async fn query_verified_storage_value_bytes<C: Client + Sync>(
+    client: &C,
+    trusted: &LightBlock,
+    key: &storage::Key,
+    options: &Options,
+) -> Result<(Option<Vec<u8>>, LightBlock), Error> {
+    let latest = client
+        .status()
+        .await
+        .map_err(|err| Error::from(QueryError::General(err.to_string())))?
+        .sync_info
+        .latest_block_height;
+    // The app hash of the state committed at height `h` is only included in
+    // the header at height `h + 1`, so query the value one block behind.
+    let query_height = BlockHeight(latest.value().saturating_sub(1));
+    if query_height.0 == 0 {
+        return Err(unverified("No verifiable block has been committed yet"));
+    }
+    let response = RPC
+        .shell()
+        .storage_value(client, None, Some(query_height), true, key)
+        .await
+        .map_err(|err| Error::from(QueryError::General(err.to_string())))?;
+    let proof = response.proof.ok_or_else(|| {
+        unverified(format!("The node returned no proof for key {key}"))
+    })?;
+    let value = if response.data.is_empty() {
+        None
+    } else {
+        Some(response.data)
+    };
+
+    let verified = verify_to_height(client, trusted, latest, options).await?;
+    verify_storage_proof(
+        verified.signed_header.header.app_hash.as_bytes(),
+        key,
+        value.as_deref(),
+        &proof,
+    )?;
+    Ok((value, verified))
+}
+
+#[cfg(test)]
+mod test {
+    use namada_core::types::address::{Address, InternalAddress};
+    use namada_core::types::storage::KeySeg;
+    use namada_state::{MembershipProof, MerkleTree};
+
+    use super::*;
+
+    #[test]
+    fn test_verify_storage_proof() {
+        let mut tree = MerkleTree::<Sha256Hasher>::default();
+        let key_prefix: storage::Key =
+            Address::Internal(InternalAddress::PoS).to_db_key().into();
+        let pos_key = key_prefix.push(&"test".to_string()).unwrap();
+        let pos_val = [2u8; 8].to_vec();
+        tree.update(&pos_key, pos_val.clone()).unwrap();
+
+        let proof = match tree
+            .get_sub_tree_existence_proof(
+                std::array::from_ref(&pos_key),
+                vec![&pos_val],
+            )
+            .unwrap()
+        {
+            MembershipProof::ICS23(proof) => proof,
+            _ => panic!("Test failed"),
+        };
+        let proof: ProofOps =
+            tree.get_sub_tree_proof(&pos_key, proof).unwrap().into();
+        let app_hash = tree.root().0;
+
+        assert!(verify_storage_proof(
+            &app_hash,
+            &pos_key,
+            Some(pos_val.as_slice()),
+            &proof
+        )
+        .is_ok());
+        // A different value must be rejected
+        assert!(verify_storage_proof(
+            &app_hash,
+            &pos_key,
+            Some(&[3u8; 8][..]),
+            &proof
+        )
+        .is_err());
+        // A different app hash must be rejected
+        assert!(verify_storage_proof(
+            &[0u8; 32],
+            &pos_key,
+            Some(pos_val.as_slice()),
+            &proof
+        )
+        .is_err());
+    }
+}
@@ -7,6 +7,7 @@ use std::fmt::Debug;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 
 // use async_std::io::prelude::WriteExt;
 // use async_std::io::{self};
@@ -378,6 +379,370 @@ pub fn get_params_dir() -> PathBuf {
     }
 }
 
+lazy_static! {
+    /// The MASP provers loaded by this process, by the folder of their
+    /// parameters, which are slow to load
+    static ref TX_PROVERS: Mutex<HashMap<Option<PathBuf>, Arc<LocalTxProver>>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Get a MASP transaction prover with the parameters from the folder in
+/// [`ENV_VAR_MASP_PARAMS_DIR`] env var or the default one. The parameters are
+/// only loaded once per process, the prover is shared between operations.
+pub fn load_local_tx_prover() -> Arc<LocalTxProver> {
+    let params_dir = env::var(ENV_VAR_MASP_PARAMS_DIR).ok().map(PathBuf::from);
+    let mut provers = TX_PROVERS.lock().unwrap();
+    let prover = provers.entry(params_dir).or_insert_with_key(|params_dir| {
+        let prover = match params_dir {
+            Some(params_dir) => {
+                let spend_path = params_dir.join(SPEND_NAME);
+                let convert_path = params_dir.join(CONVERT_NAME);
+                let output_path = params_dir.join(OUTPUT_NAME);
+                LocalTxProver::new(&spend_path, &output_path, &convert_path)
+            }
+            None => LocalTxProver::with_default_location()
+                .expect("unable to load MASP Parameters"),
+        };
+        Arc::new(prover)
+    });
+    Arc::clone(prover)
+}
+
+#[cfg(feature = "multicore")]
+lazy_static! {
+    /// The thread pool the MASP proofs are generated on, if configured. The
+    /// global rayon pool is used otherwise.
+    static ref PROVING_POOL: Mutex<Option<Arc<rayon::ThreadPool>>> =
+        Mutex::new(None);
+}
+
+/// Generate the MASP proofs on a pool of the given number of threads. The
+/// spend, convert and output proofs of a transaction are generated in
+/// parallel on the pool.
+#[cfg(feature = "multicore")]
+pub fn set_proving_threads(num_threads: usize) -> Result<(), Error> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .thread_name(|i| format!("masp-prover-{i}"))
+        .build()
+        .map_err(|e| {
+            Error::Other(format!("Failed to set the proving threads: {e}"))
+        })?;
+    *PROVING_POOL.lock().unwrap() = Some(Arc::new(pool));
+    Ok(())
+}
+
+/// Generation of the proofs of a MASP transaction in parallel
+#[cfg(feature = "multicore")]
+mod parallel_proofs {
+    use std::cell::RefCell;
+
+    use masp_primitives::constants::{
+        SPENDING_KEY_GENERATOR, VALUE_COMMITMENT_RANDOMNESS_GENERATOR,
+    };
+    use masp_primitives::sapling::prover::TxProver;
+    use masp_primitives::sapling::redjubjub::{PrivateKey, Signature};
+    use masp_primitives::sapling::{PaymentAddress, ProofGenerationKey, Rseed};
+    use masp_proofs::{bls12_381, jubjub};
+    use rayon::prelude::*;
+
+    use super::*;
+
+    /// The size of a Groth16 proof of the MASP circuits
+    const GROTH_PROOF_SIZE: usize = 48 + 96 + 48;
+
+    /// The arguments of a MASP proof requested by the transaction builder
+    enum ProofRequest {
+        Spend {
+            proof_generation_key: ProofGenerationKey,
+            diversifier: Diversifier,
+            rseed: Rseed,
+            ar: jubjub::Fr,
+            asset_type: AssetType,
+            value: u64,
+            anchor: bls12_381::Scalar,
+            merkle_path: MerklePath<Node>,
+            rcv: jubjub::Fr,
+        },
+        Convert {
+            allowed_conversion: AllowedConversion,
+            value: u64,
+            anchor: bls12_381::Scalar,
+            merkle_path: MerklePath<Node>,
+            rcv: jubjub::Fr,
+        },
+        Output {
+            esk: jubjub::Fr,
+            payment_address: PaymentAddress,
+            rcm: jubjub::Fr,
+            asset_type: AssetType,
+            value: u64,
+            rcv: jubjub::Fr,
+        },
+    }
+
+    impl ProofRequest {
+        /// Generate the proof with a fresh proving context, returning it with
+        /// the encoded value commitment of its description
+        fn prove(
+            self,
+            prover: &LocalTxProver,
+        ) -> Result<([u8; 32], [u8; GROTH_PROOF_SIZE]), Error> {
+            let mut ctx = prover.new_sapling_proving_context();
+            let (zkproof, cv) = match self {
+                Self::Spend {
+                    proof_generation_key,
+                    diversifier,
+                    rseed,
+                    ar,
+                    asset_type,
+                    value,
+                    anchor,
+                    merkle_path,
+                    rcv,
+                } => prover
+                    .spend_proof(
+                        &mut ctx,
+                        proof_generation_key,
+                        diversifier,
+                        rseed,
+                        ar,
+                        asset_type,
+                        value,
+                        anchor,
+                        merkle_path,
+                        rcv,
+                    )
+                    .map(|(zkproof, cv, _rk)| (zkproof, cv))
+                    .map_err(|()| {
+                        Error::Other("Failed to generate a spend proof".into())
+                    })?,
+                Self::Convert {
+                    allowed_conversion,
+                    value,
+                    anchor,
+                    merkle_path,
+                    rcv,
+                } => prover
+                    .convert_proof(
+                        &mut ctx,
+                        allowed_conversion,
+                        value,
+                        anchor,
+                        merkle_path,
+                        rcv,
+                    )
+                    .map_err(|()| {
+                        Error::Other(
+                            "Failed to generate a convert proof".into(),
+                        )
+                    })?,
+                Self::Output {
+                    esk,
+                    payment_address,
+                    rcm,
+                    asset_type,
+                    value,
+                    rcv,
+                } => prover.output_proof(
+                    &mut ctx,
+                    esk,
+                    payment_address,
+                    rcm,
+                    asset_type,
+                    value,
+                    rcv,
+                ),
+            };
+            Ok((cv.to_bytes(), zkproof))
+        }
+    }
+
+    /// A MASP prover that only records the proofs requested by the
+    /// transaction builder, to generate them in parallel once the
+    /// transaction is built. The value commitments, the randomized keys and
+    /// the binding signature are derived as with a [`LocalTxProver`], the
+    /// proofs are left empty. The proving context is the sum of the value
+    /// commitment randomness of the descriptions.
+    #[derive(Default)]
+    pub struct DeferredTxProver {
+        requests: RefCell<Vec<ProofRequest>>,
+    }
+
+    impl TxProver for DeferredTxProver {
+        type SaplingProvingContext = jubjub::Fr;
+
+        fn new_sapling_proving_context(&self) -> Self::SaplingProvingContext {
+            jubjub::Fr::zero()
+        }
+
+        fn spend_proof(
+            &self,
+            bsk: &mut Self::SaplingProvingContext,
+            proof_generation_key: ProofGenerationKey,
+            diversifier: Diversifier,
+            rseed: Rseed,
+            ar: jubjub::Fr,
+            asset_type: AssetType,
+            value: u64,
+            anchor: bls12_381::Scalar,
+            merkle_path: MerklePath<Node>,
+            rcv: jubjub::Fr,
+        ) -> Result<
+            ([u8; GROTH_PROOF_SIZE], jubjub::ExtendedPoint, PublicKey),
+            (),
+        > {
+            let cv =
+                asset_type.value_commitment(value, rcv).commitment().into();
+            let rk = PublicKey(proof_generation_key.ak.into())
+                .randomize(ar, SPENDING_KEY_GENERATOR);
+            // Spends add to the value balance
+            *bsk += rcv;
+            self.requests.borrow_mut().push(ProofRequest::Spend {
+                proof_generation_key,
+                diversifier,
+                rseed,
+                ar,
+                asset_type,
+                value,
+                anchor,
+                merkle_path,
+                rcv,
+            });
+            Ok(([0; GROTH_PROOF_SIZE], cv, rk))
+        }
+
+        fn convert_proof(
+            &self,
+            bsk: &mut Self::SaplingProvingContext,
+            allowed_conversion: AllowedConversion,
+            value: u64,
+            anchor: bls12_381::Scalar,
+            merkle_path: MerklePath<Node>,
+            rcv: jubjub::Fr,
+        ) -> Result<([u8; GROTH_PROOF_SIZE], jubjub::ExtendedPoint), ()>
+        {
+            let cv = allowed_conversion
+                .value_commitment(value, rcv)
+                .commitment()
+                .into();
+            // Converts add to the value balance
+            *bsk += rcv;
+            self.requests.borrow_mut().push(ProofRequest::Convert {
+                allowed_conversion,
+                value,
+                anchor,
+                merkle_path,
+                rcv,
+            });
+            Ok(([0; GROTH_PROOF_SIZE], cv))
+        }
+
+        fn output_proof(
+            &self,
+            bsk: &mut Self::SaplingProvingContext,
+            esk: jubjub::Fr,
+            payment_address: PaymentAddress,
+            rcm: jubjub::Fr,
+            asset_type: AssetType,
+            value: u64,
+            rcv: jubjub::Fr,
+        ) -> ([u8; GROTH_PROOF_SIZE], jubjub::ExtendedPoint) {
+            let cv =
+                asset_type.value_commitment(value, rcv).commitment().into();
+            // Outputs subtract from the value balance
+            *bsk -= rcv;
+            self.requests.borrow_mut().push(ProofRequest::Output {
+                esk,
+                payment_address,
+                rcm,
+                asset_type,
+                value,
+                rcv,
+            });
+            ([0; GROTH_PROOF_SIZE], cv)
+        }
+
+        fn binding_sig(
+            &self,
+            bsk: &mut Self::SaplingProvingContext,
+            _value_balance: &I128Sum,
+            sighash: &[u8; 32],
+        ) -> Result<Signature, ()> {
+            let bsk = PrivateKey(*bsk);
+            let bvk = PublicKey::from_private(
+                &bsk,
+                VALUE_COMMITMENT_RANDOMNESS_GENERATOR,
+            );
+            let mut data_to_be_signed = [0u8; 64];
+            data_to_be_signed[0..32].copy_from_slice(&bvk.0.to_bytes());
+            data_to_be_signed[32..64].copy_from_slice(&sighash[..]);
+            Ok(bsk.sign(
+                &data_to_be_signed,
+                &mut OsRng,
+                VALUE_COMMITMENT_RANDOMNESS_GENERATOR,
+            ))
+        }
+    }
+
+    impl DeferredTxProver {
+        /// Generate the recorded proofs in parallel on the proving thread
+        /// pool and set them in the descriptions of the given transaction,
+        /// which must have been built with this prover. The proofs are not
+        /// part of the signed data of a transaction, so its signatures remain
+        /// valid.
+        pub fn prove(
+            self,
+            masp_tx: Transaction,
+            prover: &LocalTxProver,
+        ) -> Result<Transaction, Error> {
+            let requests = self.requests.into_inner();
+            let prove_all = || {
+                requests
+                    .into_par_iter()
+                    .map(|request| request.prove(prover))
+                    .collect::<Result<HashMap<_, _>, _>>()
+            };
+            let pool = PROVING_POOL.lock().unwrap().clone();
+            let mut proofs = match pool {
+                Some(pool) => pool.install(prove_all),
+                None => prove_all(),
+            }?;
+            let mut take_proof = |cv: &jubjub::ExtendedPoint| {
+                proofs.remove(&cv.to_bytes()).ok_or_else(|| {
+                    Error::Other(
+                        "No proof was generated for a MASP description".into(),
+                    )
+                })
+            };
+
+            let Some(bundle) = masp_tx.sapling_bundle() else {
+                return Ok(masp_tx);
+            };
+            let mut bundle = bundle.clone();
+            for spend in &mut bundle.shielded_spends {
+                spend.zkproof = take_proof(&spend.cv)?;
+            }
+            for convert in &mut bundle.shielded_converts {
+                convert.zkproof = take_proof(&convert.cv)?;
+            }
+            for output in &mut bundle.shielded_outputs {
+                output.zkproof = take_proof(&output.cv)?;
+            }
+            TransactionData::from_parts(
+                masp_tx.version(),
+                masp_tx.consensus_branch_id(),
+                masp_tx.lock_time(),
+                masp_tx.expiry_height(),
+                masp_tx.transparent_bundle().cloned(),
+                Some(bundle),
+            )
+            .freeze()
+            .map_err(|e| Error::from(EncodingError::Conversion(e.to_string())))
+        }
+    }
+}
+
 /// Freeze a Builder into the format necessary for inclusion in a Tx. This is
 /// the format used by hardware wallets to validate a MASP Transaction.
 struct WalletMap;
@@ -422,7 +787,7 @@ pub trait ShieldedUtils:
     Sized + BorshDeserialize + BorshSerialize + Default + Clone
 {
     /// Get a MASP transaction prover
-    fn local_tx_prover(&self) -> LocalTxProver;
+    fn local_tx_prover(&self) -> Arc<LocalTxProver>;
 
     /// Load up the currently saved ShieldedContext
     async fn load<U: ShieldedUtils + MaybeSend>(
@@ -600,9 +965,10 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
 
     /// Fetch the current state of the multi-asset shielded pool into a
     /// ShieldedContext
-    pub async fn fetch<C: Client + Sync>(
+    pub async fn fetch<C: Client + Sync, IO: Io>(
         &mut self,
         client: &C,
+        io: &IO,
         sks: &[ExtendedSpendingKey],
         fvks: &[ViewingKey],
     ) -> Result<(), Error> {
@@ -627,7 +993,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         let (txs, mut tx_iter);
         if !unknown_keys.is_empty() {
             // Load all transactions accepted until this point
-            txs = Self::fetch_shielded_transfers(client, None).await?;
+            txs = Self::fetch_shielded_transfers(client, io, None).await?;
             tx_iter = txs.iter();
             // Do this by constructing a shielding context only for unknown keys
             let mut tx_ctx = Self {
@@ -650,7 +1016,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             self.merge(tx_ctx);
         } else {
             // Load only transactions accepted from last_txid until this point
-            txs = Self::fetch_shielded_transfers(client, self.last_indexed)
+            txs = Self::fetch_shielded_transfers(client, io, self.last_indexed)
                 .await?;
             tx_iter = txs.iter();
         }
@@ -664,8 +1030,9 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
 
     /// Obtain a chronologically-ordered list of all accepted shielded
     /// transactions from a node.
-    pub async fn fetch_shielded_transfers<C: Client + Sync>(
+    pub async fn fetch_shielded_transfers<C: Client + Sync, IO: Io>(
         client: &C,
+        io: &IO,
         last_indexed_tx: Option<IndexedTx>,
     ) -> Result<BTreeMap<IndexedTx, (Epoch, Transfer, Transaction)>, Error>
     {
@@ -680,7 +1047,14 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             last_indexed_tx.map_or_else(|| 1, |last| last.height.0);
         let first_idx_to_query =
             last_indexed_tx.map_or_else(|| 0, |last| last.index.0 + 1);
+        let progress = io.progress(
+            "Fetching the shielded transfers",
+            Some(
+                (last_block_height.0 + 1).saturating_sub(first_height_to_query),
+            ),
+        );
         for height in first_height_to_query..=last_block_height.0 {
+            progress.inc(1);
             // Get the valid masp transactions at the specified height
             let epoch = query_epoch_at_height(client, height.into())
                 .await?
@@ -1625,7 +1999,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             let mut shielded = context.shielded_mut().await;
             let _ = shielded.load().await;
             shielded
-                .fetch(context.client(), &spending_keys, &[])
+                .fetch(context.client(), context.io(), &spending_keys, &[])
                 .await?;
             // Save the update state so that future fetches can be
             // short-circuited
@@ -1959,12 +2333,21 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             Error::from(EncodingError::Conversion(e.to_string()))
         })?;
 
-        let build_transfer = |prover: LocalTxProver| -> Result<
+        let build_transfer = |prover: Arc<LocalTxProver>| -> Result<
             ShieldedTransfer,
-            builder::Error<std::convert::Infallible>,
+            TransferErr,
         > {
+            // Generate the proofs in parallel once the transaction is built
+            #[cfg(feature = "multicore")]
+            let (masp_tx, metadata) = {
+                let deferred = parallel_proofs::DeferredTxProver::default();
+                let (masp_tx, metadata) = builder
+                    .build(&deferred, &FeeRule::non_standard(U64Sum::zero()))?;
+                (deferred.prove(masp_tx, &prover)?, metadata)
+            };
+            #[cfg(not(feature = "multicore"))]
             let (masp_tx, metadata) = builder
-                .build(&prover, &FeeRule::non_standard(U64Sum::zero()))?;
+                .build(&*prover, &FeeRule::non_standard(U64Sum::zero()))?;
             Ok(ShieldedTransfer {
                 builder: builder_clone,
                 masp_tx,
@@ -2042,9 +2425,10 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
     /// transactions. If an owner is specified, then restrict the set to only
     /// transactions crediting/debiting the given owner. If token is specified,
     /// then restrict set to only transactions involving the given token.
-    pub async fn query_tx_deltas<C: Client + Sync>(
+    pub async fn query_tx_deltas<C: Client + Sync, IO: Io>(
         &mut self,
         client: &C,
+        io: &IO,
         query_owner: &Either<BalanceOwner, Vec<Address>>,
         query_token: &Option<Address>,
         viewing_keys: &HashMap<String, ExtendedViewingKey>,
@@ -2059,7 +2443,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             .values()
             .map(|fvk| ExtendedFullViewingKey::from(*fvk).fvk.vk)
             .collect();
-        self.fetch(client, &[], &fvks).await?;
+        self.fetch(client, io, &[], &fvks).await?;
         // Save the update state so that future fetches can be short-circuited
         let _ = self.save().await;
         // Required for filtering out rejected transactions from Tendermint
@@ -2525,17 +2909,8 @@ pub mod fs {
     #[cfg_attr(feature = "async-send", async_trait::async_trait)]
     #[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
     impl ShieldedUtils for FsShieldedUtils {
-        fn local_tx_prover(&self) -> LocalTxProver {
-            if let Ok(params_dir) = env::var(ENV_VAR_MASP_PARAMS_DIR) {
-                let params_dir = PathBuf::from(params_dir);
-                let spend_path = params_dir.join(SPEND_NAME);
-                let convert_path = params_dir.join(CONVERT_NAME);
-                let output_path = params_dir.join(OUTPUT_NAME);
-                LocalTxProver::new(&spend_path, &output_path, &convert_path)
-            } else {
-                LocalTxProver::with_default_location()
-                    .expect("unable to load MASP Parameters")
-            }
+        fn local_tx_prover(&self) -> Arc<LocalTxProver> {
+            load_local_tx_prover()
         }
 
         /// Try to load the last saved shielded context from the given context
@@ -0,0 +1,238 @@
+//! Introspection of the block proposer schedule.
+//!
+//! CometBFT selects the proposer of each height with a deterministic weighted
+//! round-robin over the proposer priorities of the validator set. Starting
+//! from the priorities of the validator set at the last committed height, the
+//! same algorithm yields the expected proposers of the following heights, as
+//! long as the validator set doesn't change and every block is decided in its
+//! first round.
+
+use namada_core::types::address::Address;
+use namada_core::types::storage::BlockHeight;
+
+use crate::error::{Error, QueryError};
+use crate::light_client::fetch_validator_set;
+use crate::queries::{Client, RPC};
+use crate::tendermint::account;
+use crate::tendermint::block::Height;
+use crate::tendermint::validator::Info as ValidatorInfo;
+
+/// The proposer of a block
+#[derive(Clone, Debug)]
+pub struct Proposer {
+    /// The height of the block
+    pub height: BlockHeight,
+    /// The Tendermint address of the proposer
+    pub tm_address: account::Id,
+    /// The validator address of the proposer, if it's known to PoS
+    pub address: Option<Address>,
+    /// Whether the proposer is predicted from the current validator set
+    /// rather than read from a committed block
+    pub predicted: bool,
+}
+
+/// A validator with its voting power and proposer priority
+#[derive(Clone, Debug)]
+struct Candidate {
+    address: account::Id,
+    power: i64,
+    priority: i64,
+}
+
+/// Find the Tendermint addresses of the proposers of the `count` heights that
+/// follow the height of the given validator set.
+pub fn simulate_proposers(
+    validators: &[ValidatorInfo],
+    count: usize,
+) -> Vec<account::Id> {
+    let mut candidates: Vec<Candidate> = validators
+        .iter()
+        .map(|val| Candidate {
+            address: val.address,
+            power: val.power.value() as i64,
+            priority: val.proposer_priority.value(),
+        })
+        .collect();
+    if candidates.is_empty() {
+        return vec![];
+    }
+    let total_power: i64 = candidates.iter().map(|val| val.power).sum();
+    (0..count)
+        .map(|_| increment_proposer_priority(&mut candidates, total_power))
+        .collect()
+}
+
+/// Advance the proposer priorities by one height and return the proposer
+/// that was selected, as in CometBFT's `IncrementProposerPriority(1)`
+fn increment_proposer_priority(
+    candidates: &mut [Candidate],
+    total_power: i64,
+) -> account::Id {
+    // Keep the spread of the priorities within twice the total power
+    let diff_max = 2 * total_power;
+    if diff_max > 0 {
+        let max = candidates.iter().map(|val| val.priority).max().unwrap();
+        let min = candidates.iter().map(|val| val.priority).min().unwrap();
+        let diff = max.saturating_sub(min);
+        if diff > diff_max {
+            let ratio = (diff + diff_max - 1) / diff_max;
+            for val in candidates.iter_mut() {
+                val.priority /= ratio;
+            }
+        }
+    }
+
+    // Center the priorities around zero. The average is rounded down, like
+    // the Euclidean division of Go's `big.Int`.
+    let sum: i128 = candidates.iter().map(|val| val.priority as i128).sum();
+    let avg = sum.div_euclid(candidates.len() as i128) as i64;
+    for val in candidates.iter_mut() {
+        val.priority = val.priority.saturating_sub(avg);
+    }
+
+    for val in candidates.iter_mut() {
+        val.priority = val.priority.saturating_add(val.power);
+    }
+    // The highest priority wins, with ties going to the lowest address
+    let proposer = candidates
+        .iter_mut()
+        .reduce(|best, val| {
+            if val.priority > best.priority
+                || (val.priority == best.priority
+                    && val.address.as_bytes() < best.address.as_bytes())
+            {
+                val
+            } else {
+                best
+            }
+        })
+        .unwrap();
+    proposer.priority = proposer.priority.saturating_sub(total_power);
+    proposer.address
+}
+
+/// Find the proposers of `count` consecutive heights starting from `height`,
+/// or from the height after the last committed block if it's not given. The
+/// proposers of committed blocks are read from their headers, the remaining
+/// ones are predicted from the validator set of the last committed height.
+pub async fn query_proposers<C: Client + Sync>(
+    client: &C,
+    height: Option<BlockHeight>,
+    count: u64,
+) -> Result<Vec<Proposer>, Error> {
+    let last_height = client
+        .status()
+        .await
+        .map_err(query_error)?
+        .sync_info
+        .latest_block_height;
+    let last_height = BlockHeight(last_height.value());
+
+    let mut proposers = vec![];
+    let mut next = height.unwrap_or_else(|| last_height.next_height());
+    while next <= last_height && proposers.len() < count as usize {
+        let tm_height = Height::try_from(next.0)
+            .map_err(|err| Error::Other(err.to_string()))?;
+        let tm_address = client
+            .block(tm_height)
+            .await
+            .map_err(query_error)?
+            .block
+            .header
+            .proposer_address;
+        proposers.push((next, tm_address, false));
+        next = next.next_height();
+    }
+
+    let remaining = count as usize - proposers.len();
+    if remaining > 0 {
+        let tm_height = Height::try_from(last_height.0)
+            .map_err(|err| Error::Other(err.to_string()))?;
+        let validators = fetch_validator_set(client, tm_height).await?;
+        // The schedule is simulated from the last committed height, skipping
+        // any heights before the requested one
+        let skip = (next.0 - last_height.0 - 1) as usize;
+        let predicted =
+            simulate_proposers(validators.validators(), skip + remaining);
+        for tm_address in predicted.into_iter().skip(skip) {
+            proposers.push((next, tm_address, true));
+            next = next.next_height();
+        }
+    }
+
+    let mut result = Vec::with_capacity(proposers.len());
+    for (height, tm_address, predicted) in proposers {
+        let address = RPC
+            .vp()
+            .pos()
+            .validator_by_tm_addr(client, &tm_address.to_string())
+            .await
+            .map_err(query_error)?;
+        result.push(Proposer {
+            height,
+            tm_address,
+            address,
+            predicted,
+        });
+    }
+    Ok(result)
+}
+
+fn query_error(err: impl ToString) -> Error {
+    Error::from(QueryError::General(err.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use namada_core::types::key::testing::keypair_1;
+    use namada_core::types::key::RefTo;
+
+    use super::*;
+    use crate::tendermint::vote::Power;
+
+    fn validator(name: &str, power: u32) -> ValidatorInfo {
+        let mut address = [0_u8; 20];
+        address[..name.len()].copy_from_slice(name.as_bytes());
+        let pub_key = keypair_1().ref_to().into();
+        let mut info = ValidatorInfo::new(pub_key, Power::from(power));
+        info.address = account::Id::new(address);
+        info
+    }
+
+    /// Test the proposer schedule against the sequence of CometBFT's
+    /// `TestProposerSelection1`
+    #[test]
+    fn test_simulate_proposers() {
+        let validators = [
+            validator("foo", 1000),
+            validator("bar", 300),
+            validator("baz", 330),
+        ];
+        let name = |id: account::Id| {
+            let bytes = id.as_bytes();
+            let len = bytes.iter().position(|b| *b == 0).unwrap();
+            String::from_utf8(bytes[..len].to_vec()).unwrap()
+        };
+        let proposers: Vec<String> = simulate_proposers(&validators, 20)
+            .into_iter()
+            .map(name)
+            .collect();
+        let expected = "foo baz foo bar foo foo baz foo bar foo foo baz foo \
+                        foo bar foo baz foo foo bar";
+        assert_eq!(proposers.join(" "), expected);
+    }
+
+    /// Test that each validator proposes in proportion to its voting power
+    #[test]
+    fn test_simulate_proposers_by_power() {
+        let validators =
+            [validator("a", 1), validator("b", 2), validator("c", 3)];
+        let proposers = simulate_proposers(&validators, 60);
+        for val in &validators {
+            let proposed =
+                proposers.iter().filter(|id| **id == val.address).count();
+            assert_eq!(proposed as u64, 10 * val.power.value());
+        }
+        assert!(simulate_proposers(&[], 5).is_empty());
+    }
+}
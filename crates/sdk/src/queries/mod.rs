@@ -4,8 +4,11 @@
 // Re-export to show in rustdoc!
 use namada_core::types::storage::BlockHeight;
 use namada_state::{DBIter, StorageHasher, DB};
-pub use shell::Shell;
 use shell::SHELL;
+pub use shell::{
+    ChainTiming, GasEstimate, Shell, TxEvents, GAS_ESTIMATE_MARGIN_PERCENT,
+    MAX_VALUE_CHUNK_LEN,
+};
 pub use types::{
     EncodedResponseQuery, Error, RequestCtx, RequestQuery, ResponseQuery,
     Router,
@@ -46,6 +49,38 @@ where
     RPC.handle(ctx, request)
 }
 
+/// Check if the request is for the `dry_run_tx` or `estimate_gas` handlers,
+/// which have to be invoked by the ledger instead of [`handle_path`], as they
+/// require the wasm runtime.
+pub fn is_dry_run_tx(request: &RequestQuery) -> bool {
+    request.path == RPC.shell().dry_run_tx_path()
+        || request.path == RPC.shell().estimate_gas_path()
+}
+
+/// The paths, or the prefixes of the paths, of the handlers that read the event
+/// log or the mempool rejections. These are held in the memory of the ledger's
+/// shell rather than in its storage.
+const SHELL_LOG_PATHS: &[&str] = &[
+    "/shell/accepted/",
+    "/shell/applied/",
+    "/shell/tx_events/",
+    "/shell/mempool_rejections",
+    "/shell/applied_with_memo/",
+    "/shell/event_log",
+    "/shell/ibc_client_update/",
+    "/shell/ibc_packet/",
+    "/shell/eth_bridge/pool/transfer_status",
+];
+
+/// Check if the request is for a handler that reads the event log or the
+/// mempool rejections, which can only be served by the ledger's shell and not
+/// from a snapshot of its storage.
+pub fn reads_shell_logs(request: &RequestQuery) -> bool {
+    SHELL_LOG_PATHS
+        .iter()
+        .any(|path| request.path.starts_with(path))
+}
+
 // Handler helpers:
 
 /// For queries that only support latest height, check that the given height is
@@ -103,6 +138,7 @@ mod testing {
 
     use super::*;
     use crate::events::log::EventLog;
+    use crate::events::mempool::MempoolRejections;
     use crate::tendermint_rpc::error::Error as RpcError;
 
     /// A test client that has direct access to the storage
@@ -116,6 +152,8 @@ mod testing {
         pub wl_storage: TestWlStorage,
         /// event log
         pub event_log: EventLog,
+        /// mempool rejections log
+        pub mempool_rejections: MempoolRejections,
     }
 
     impl<RPC> TestClient<RPC>
@@ -141,10 +179,12 @@ mod testing {
                     "Max block gas parameter must be initialized in storage",
                 );
             let event_log = EventLog::default();
+            let mempool_rejections = MempoolRejections::default();
             Self {
                 rpc,
                 wl_storage,
                 event_log,
+                mempool_rejections,
             }
         }
     }
@@ -181,6 +221,7 @@ mod testing {
             let ctx = RequestCtx {
                 wl_storage: &self.wl_storage,
                 event_log: &self.event_log,
+                mempool_rejections: &self.mempool_rejections,
                 vp_wasm_cache: (),
                 tx_wasm_cache: (),
                 storage_read_past_height_limit: None,
@@ -205,7 +246,7 @@ use std::fmt::{Debug, Display};
 
 use tendermint_rpc::endpoint::{
     abci_info, block, block_results, blockchain, commit, consensus_params,
-    consensus_state, health, net_info, status,
+    consensus_state, health, net_info, status, validators,
 };
 use tendermint_rpc::query::Query;
 use tendermint_rpc::{Error as RpcError, Order};
@@ -404,6 +445,24 @@ pub trait Client {
         self.perform(commit::Request::default()).await
     }
 
+    /// `/validators`: get a page of the validator set at a given height.
+    async fn validators<H>(
+        &self,
+        height: H,
+        page: usize,
+        per_page: u8,
+    ) -> Result<validators::Response, RpcError>
+    where
+        H: Into<Height> + Send,
+    {
+        self.perform(validators::Request::new(
+            Some(height.into()),
+            Some(page.into()),
+            Some(per_page.into()),
+        ))
+        .await
+    }
+
     /// `/health`: get node health.
     ///
     /// Returns empty result (200 OK) on success, no response in case of an
@@ -11,8 +11,25 @@ use thiserror::Error;
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
 pub enum Error {
-    #[error("Found no matching pattern for the given path {0}")]
-    WrongPath(String),
+    #[error(
+        "Found no matching pattern for the given path {path}{}",
+        did_you_mean(.closest)
+    )]
+    UnknownPath {
+        /// The requested path
+        path: String,
+        /// The path pattern of the router that is the closest to the
+        /// requested path, if any is close enough
+        closest: Option<String>,
+    },
+}
+
+/// Format the suggestion of the closest path, if any
+fn did_you_mean(closest: &Option<String>) -> String {
+    closest
+        .as_ref()
+        .map(|closest| format!(", did you mean {closest}?"))
+        .unwrap_or_default()
 }
 
 /// Find the index of a next forward slash after the given `start` index in the
@@ -30,6 +47,56 @@ pub fn find_next_slash_index(path: &str, start: usize) -> usize {
         .unwrap_or(path.len())
 }
 
+/// Find the path pattern that is the closest to the given path, if any is
+/// close enough to be a likely typo of it. The dynamic segments of the
+/// patterns (`{name}`) match any segment of the path.
+pub fn closest_path(path: &str, patterns: &[String]) -> Option<String> {
+    let path = path.trim_matches('/');
+    let segments: Vec<&str> = path.split('/').collect();
+    patterns
+        .iter()
+        .filter_map(|pattern| {
+            let filled = pattern
+                .trim_matches('/')
+                .split('/')
+                .enumerate()
+                .map(|(ix, segment)| {
+                    if segment.starts_with('{') {
+                        segments.get(ix).copied().unwrap_or(segment)
+                    } else {
+                        segment
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            let distance = edit_distance(path, &filled);
+            // Only suggest patterns that differ in at most a third of the
+            // path
+            (distance <= filled.len().max(path.len()) / 3)
+                .then_some((distance, pattern))
+        })
+        .min_by_key(|(distance, _pattern)| *distance)
+        .map(|(_distance, pattern)| pattern.clone())
+}
+
+/// The Levenshtein distance of the two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // The distances of the prefix of `a` to all the prefixes of `b`
+    let mut distances: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut prev_diagonal = distances[0];
+        distances[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = prev_diagonal + usize::from(a_char != *b_char);
+            prev_diagonal = distances[j + 1];
+            distances[j + 1] =
+                substitution.min(distances[j] + 1).min(distances[j + 1] + 1);
+        }
+    }
+    distances[b.len()]
+}
+
 /// Invoke the sub-handler or call the handler function with the matched
 /// arguments generated by `try_match_segments`.
 macro_rules! handle_match {
@@ -368,6 +435,56 @@ macro_rules! pattern_to_prefix {
     };
 }
 
+/// Turn a pattern into a path, in which the dynamic segments are shown as
+/// `{name}`
+macro_rules! pattern_to_path {
+    ( ( $( $segment:tt )/ * ) ) => {{
+        let mut path = String::new();
+        $(
+            path.push('/');
+            path.push_str(segment_to_path!($segment));
+        )*
+        path
+    }};
+}
+
+/// Turn a pattern segment into a path segment
+macro_rules! segment_to_path {
+    ($segment:literal) => {
+        $segment
+    };
+    ([$name:ident $(: $($_ty:tt)*)?]) => {
+        concat!("{", stringify!($name), "}")
+    };
+}
+
+/// Collect the paths of all the handlers of a pattern into `$paths`
+macro_rules! collect_paths {
+    // Nested router
+    ( $paths:ident, $prefix:expr, $pattern:tt = (sub $router:ident) ) => {{
+        let prefix = format!("{}{}", $prefix, pattern_to_path!($pattern));
+        for path in $crate::queries::Router::paths(&$router) {
+            $paths.push(format!("{prefix}{path}"));
+        }
+    }};
+    // Inlined sub-tree
+    (
+        $paths:ident, $prefix:expr,
+        $pattern:tt = {
+            $( $sub_pattern:tt $( -> $sub_return_ty:path )? = $handle:tt, )*
+        }
+    ) => {{
+        let prefix = format!("{}{}", $prefix, pattern_to_path!($pattern));
+        $(
+            collect_paths!($paths, prefix, $sub_pattern = $handle);
+        )*
+    }};
+    // Handler function
+    ( $paths:ident, $prefix:expr, $pattern:tt = $handle:tt ) => {{
+        $paths.push(format!("{}{}", $prefix, pattern_to_path!($pattern)));
+    }};
+}
+
 /// Turn patterns and their handlers into methods for the router, where each
 /// dynamic pattern is turned into a parameter for the method.
 macro_rules! pattern_and_handler_to_method {
@@ -718,6 +835,9 @@ macro_rules! router_type {
 /// methods (enabled with `feature = "async-client"`).
 ///
 /// The `router!` macro implements greedy matching algorithm.
+/// A request to a path that matches no pattern fails with
+/// [`Error::UnknownPath`], which suggests the closest path of the router, if
+/// any.
 ///
 /// ## Examples
 ///
@@ -815,10 +935,28 @@ macro_rules! router {
                     }
                 )*
 
+                // Suggest the closest path of this router, which is nested
+                // at the already matched part of the path
+                let (prefix, rest) = request.path.split_at(start);
+                let closest = $crate::queries::router::closest_path(
+                    rest,
+                    &$crate::queries::Router::paths(self),
+                ).map(|path| format!("{prefix}{path}"));
 				return Err(
-                    $crate::queries::router::Error::WrongPath(request.path.clone()))
+                    $crate::queries::router::Error::UnknownPath {
+                        path: request.path.clone(),
+                        closest,
+                    })
                     .into_storage_result();
 			}
+
+            fn paths(&self) -> Vec<String> {
+                let mut paths = vec![];
+                $(
+                    collect_paths!(paths, "", $pattern = $handle);
+                )*
+                paths
+            }
 		}
 
 		#[doc = "`" $name "` path router"]
@@ -1023,6 +1161,7 @@ mod test {
         };
         let ctx = RequestCtx {
             event_log: &client.event_log,
+            mempool_rejections: &client.mempool_rejections,
             wl_storage: &client.wl_storage,
             vp_wasm_cache: (),
             tx_wasm_cache: (),
@@ -1031,6 +1170,26 @@ mod test {
         let result = TEST_RPC.handle(ctx, &request);
         assert!(result.is_err());
 
+        // Test request with a path close to a valid one
+        let request = RequestQuery {
+            path: "/sub/yy/test123".to_owned(),
+            ..request
+        };
+        let ctx = RequestCtx {
+            event_log: &client.event_log,
+            mempool_rejections: &client.mempool_rejections,
+            wl_storage: &client.wl_storage,
+            vp_wasm_cache: (),
+            tx_wasm_cache: (),
+            storage_read_past_height_limit: None,
+        };
+        let err = TEST_RPC.handle(ctx, &request).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("did you mean /sub/y/{untyped_arg}?"),
+            "{err}"
+        );
+
         // Test requests to valid paths using the router's methods
 
         let result = TEST_RPC.a(&client).await.unwrap();
@@ -1115,4 +1274,32 @@ mod test {
 
         Ok(())
     }
+
+    /// Test the paths of the handlers in `TEST_RPC` router.
+    #[test]
+    fn test_router_paths() {
+        let paths = TEST_RPC.paths();
+        assert_eq!(paths.len(), 14);
+        for path in [
+            "/sub/x",
+            "/sub/y/{untyped_arg}",
+            "/a",
+            "/b/0/ii",
+            "/b/2/i/{balance}",
+            "/b/3/{a1}/{a2}/{a3}/iii",
+            "/b/3/{a1}/{a2}/iiii/{a3}/xyz/{a4}",
+            "/c",
+        ] {
+            assert!(paths.contains(&path.to_string()), "{path}");
+        }
+
+        let closest = |path| super::closest_path(path, &paths);
+        assert_eq!(closest("/b/0/iii"), Some("/b/0/ii".to_string()));
+        assert_eq!(closest("/sub/w"), Some("/sub/x".to_string()));
+        assert_eq!(
+            closest("/b/2/j/1000"),
+            Some("/b/2/i/{balance}".to_string())
+        );
+        assert_eq!(closest("/unknown/path"), None);
+    }
 }
@@ -2,8 +2,9 @@ use std::collections::BTreeMap;
 
 pub(super) mod eth_bridge;
 
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
 use borsh_ext::BorshSerializeExt;
+use data_encoding::HEXUPPER;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
@@ -14,14 +15,18 @@ use namada_core::types::hash::Hash;
 use namada_core::types::storage::{
     self, BlockHeight, BlockResults, Epoch, KeySeg, PrefixValue,
 };
+use namada_core::types::time::DateTimeUtc;
 use namada_core::types::token::MaspDenom;
-use namada_state::{DBIter, LastBlock, StorageHasher, DB};
+use namada_parameters::BlockTimeStats;
+use namada_state::{DBIter, LastBlock, StorageHasher, ValueChunk, DB};
 use namada_storage::{self, ResultExt, StorageRead};
+use namada_tx::data::GasLimit;
 #[cfg(any(test, feature = "async-client"))]
 use namada_tx::data::TxResult;
 
 use self::eth_bridge::{EthBridge, ETH_BRIDGE};
-use crate::events::log::dumb_queries;
+use crate::events::log::{dumb_queries, EventLogPage, EventLogQuery};
+use crate::events::mempool::MempoolRejection;
 use crate::events::{Event, EventType};
 use crate::ibc::core::host::types::identifiers::{
     ChannelId, ClientId, PortId, Sequence,
@@ -44,6 +49,57 @@ type Conversion = (
     MerklePath<Node>,
 );
 
+/// The events of a tx found by its hash
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct TxEvents {
+    /// Height of the block that included the tx
+    pub height: BlockHeight,
+    /// The events emitted for the tx, if they are still in the event log of
+    /// the node
+    pub events: Vec<Event>,
+}
+
+/// The safety margin added to the gas used by a simulated tx to recommend its
+/// gas limit, in percent
+pub const GAS_ESTIMATE_MARGIN_PERCENT: u64 = 20;
+
+/// The maximum length of a chunk of a storage value returned by a query, in
+/// bytes. Requests for longer chunks are truncated to this length.
+pub const MAX_VALUE_CHUNK_LEN: u64 = 1024 * 1024;
+
+/// The gas estimate of a tx from its simulated execution
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize,
+)]
+pub struct GasEstimate {
+    /// The gas used by the simulated execution, in whole units
+    pub gas_used: u64,
+    /// The recommended gas limit, with a safety margin on top of the gas used
+    pub gas_limit: GasLimit,
+}
+
+impl GasEstimate {
+    /// Recommend a gas limit for the gas used by a simulated execution
+    pub fn new(gas_used: u64) -> Self {
+        let margin = gas_used.saturating_mul(GAS_ESTIMATE_MARGIN_PERCENT) / 100;
+        Self {
+            gas_used,
+            gas_limit: GasLimit::from(gas_used.saturating_add(margin)),
+        }
+    }
+}
+
+/// The timing of the chain
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
+pub struct ChainTiming {
+    /// The statistics of the times of the last blocks, if any were recorded
+    pub block_times: Option<BlockTimeStats>,
+    /// The minimum height from which the next epoch can start
+    pub next_epoch_min_start_height: BlockHeight,
+    /// The minimum time from which the next epoch can start
+    pub next_epoch_min_start_time: DateTimeUtc,
+}
+
 router! {SHELL,
     // Shell provides storage read access, block metadata and can dry-run a tx
 
@@ -62,13 +118,25 @@ router! {SHELL,
     // Query the last committed block
     ( "last_block" ) -> Option<LastBlock> = last_block,
 
+    // The statistics of the last block times and the next epoch's minimum
+    // start
+    ( "chain_timing" ) -> ChainTiming = chain_timing,
+
     // Raw storage access - read value
     ( "value" / [storage_key: storage::Key] )
         -> Vec<u8> = (with_options storage_value),
 
+    // Raw storage access - read a chunk of the last committed value, to
+    // stream large values
+    ( "value_chunk" / [storage_key: storage::Key] / [offset: u64] / [len: u64] )
+        -> Option<ValueChunk> = storage_value_chunk,
+
     // Dry run a transaction
     ( "dry_run_tx" ) -> TxResult = (with_options dry_run_tx),
 
+    // Estimate the gas limit of a transaction by dry running it
+    ( "estimate_gas" ) -> GasEstimate = (with_options estimate_gas),
+
     // Raw storage access - prefix iterator
     ( "prefix" / [storage_key: storage::Key] )
         -> Vec<PrefixValue> = (with_options storage_prefix),
@@ -95,6 +163,20 @@ router! {SHELL,
     // was the transaction applied?
     ( "applied" / [tx_hash: Hash] ) -> Option<Event> = applied,
 
+    // Height and events of the tx with the given hash, wrapper or inner
+    ( "tx_events" / [tx_hash: Hash] ) -> Option<TxEvents> = tx_events,
+
+    // Txs recently rejected by the mempool validation of the node, from the
+    // most recent
+    ( "mempool_rejections" ) -> Vec<MempoolRejection> = mempool_rejections,
+
+    // applied transactions with the given hex encoded memo
+    ( "applied_with_memo" / [memo: String] ) -> Vec<Event> = applied_with_memo,
+
+    // A page of the events retained by the event log of the node, filtered
+    // by the borsh encoded `EventLogQuery` in the request data
+    ( "event_log" ) -> EventLogPage = (with_options event_log),
+
     // Query account subspace
     ( "account" / [owner: Address] ) -> Option<Account> = account,
 
@@ -121,6 +203,17 @@ where
     unimplemented!("Dry running tx requires \"wasm-runtime\" feature.")
 }
 
+fn estimate_gas<D, H, V, T>(
+    _ctx: RequestCtx<'_, D, H, V, T>,
+    _request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    unimplemented!("Estimating gas requires \"wasm-runtime\" feature.")
+}
+
 /// Query to read block results from storage
 pub fn read_results<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -242,6 +335,27 @@ where
     Ok(data)
 }
 
+fn chain_timing<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<ChainTiming>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let block_times = namada_parameters::read_block_time_stats(ctx.wl_storage)?;
+    Ok(ChainTiming {
+        block_times,
+        next_epoch_min_start_height: ctx
+            .wl_storage
+            .storage
+            .next_epoch_min_start_height,
+        next_epoch_min_start_time: ctx
+            .wl_storage
+            .storage
+            .next_epoch_min_start_time,
+    })
+}
+
 fn epoch_at_height<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     height: BlockHeight,
@@ -400,6 +514,24 @@ where
     })
 }
 
+fn storage_value_chunk<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    storage_key: storage::Key,
+    offset: u64,
+    len: u64,
+) -> namada_storage::Result<Option<ValueChunk>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let (chunk, _gas) = ctx
+        .wl_storage
+        .storage
+        .read_chunk(&storage_key, offset, len.min(MAX_VALUE_CHUNK_LEN))
+        .into_storage_result()?;
+    Ok(chunk)
+}
+
 fn storage_has_key<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     storage_key: storage::Key,
@@ -446,6 +578,78 @@ where
         .cloned())
 }
 
+fn tx_events<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    tx_hash: Hash,
+) -> namada_storage::Result<Option<TxEvents>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let Some(height) = ctx
+        .wl_storage
+        .storage
+        .read_tx_height(&tx_hash)
+        .into_storage_result()?
+    else {
+        return Ok(None);
+    };
+    let hash = tx_hash.to_string();
+    let height_str = height.to_string();
+    let events = ctx
+        .event_log
+        .iter()
+        .filter(|event| {
+            event.get("hash") == Some(&hash)
+                && event.get("height") == Some(&height_str)
+        })
+        .cloned()
+        .collect();
+    Ok(Some(TxEvents { height, events }))
+}
+
+fn mempool_rejections<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> namada_storage::Result<Vec<MempoolRejection>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    Ok(ctx.mempool_rejections.iter().cloned().collect())
+}
+
+fn applied_with_memo<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    memo: String,
+) -> namada_storage::Result<Vec<Event>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let memo = HEXUPPER
+        .decode(memo.to_uppercase().as_bytes())
+        .into_storage_result()?;
+    let matcher = dumb_queries::QueryMatcher::applied_with_memo(&memo);
+    Ok(ctx.event_log.iter_with_matcher(matcher).cloned().collect())
+}
+
+fn event_log<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let query =
+        EventLogQuery::try_from_slice(&request.data).into_storage_result()?;
+    let page = ctx.event_log.page(&query);
+    Ok(EncodedResponseQuery {
+        data: page.serialize_to_vec(),
+        ..Default::default()
+    })
+}
+
 fn ibc_client_update<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     client_id: ClientId,
@@ -535,10 +739,13 @@ where
 
 #[cfg(test)]
 mod test {
+    use namada_core::tendermint::block;
     use namada_core::types::address;
+    use namada_core::types::hash::Hash;
     use namada_token::storage_key::balance_key;
 
-    use crate::queries::RPC;
+    use super::GasEstimate;
+    use crate::queries::{reads_shell_logs, RequestQuery, RPC};
 
     #[test]
     fn test_shell_queries_router_paths() {
@@ -554,10 +761,62 @@ mod test {
         let path = RPC.shell().dry_run_tx_path();
         assert_eq!("/shell/dry_run_tx", path);
 
+        let path = RPC.shell().estimate_gas_path();
+        assert_eq!("/shell/estimate_gas", path);
+
         let path = RPC.shell().storage_prefix_path(&key);
         assert_eq!(format!("/shell/prefix/{}", key), path);
 
         let path = RPC.shell().storage_has_key_path(&key);
         assert_eq!(format!("/shell/has_key/{}", key), path);
     }
+
+    #[test]
+    fn test_shell_log_queries_paths() {
+        let query = |path: String| RequestQuery {
+            path,
+            data: Default::default(),
+            height: block::Height::from(0_u32),
+            prove: Default::default(),
+        };
+        let hash = Hash::zero();
+
+        // The queries that read the event log or the mempool rejections
+        for path in [
+            RPC.shell().accepted_path(&hash),
+            RPC.shell().applied_path(&hash),
+            RPC.shell().tx_events_path(&hash),
+            RPC.shell().mempool_rejections_path(),
+            RPC.shell().applied_with_memo_path(&"memo".to_string()),
+            RPC.shell().event_log_path(),
+            RPC.shell().eth_bridge().pending_eth_transfer_status_path(),
+        ] {
+            assert!(reads_shell_logs(&query(path.clone())), "{path}");
+        }
+
+        // The queries that only read the storage
+        let key = balance_key(
+            &address::testing::established_address_1(),
+            &address::testing::established_address_2(),
+        );
+        for path in [
+            RPC.shell().epoch_path(),
+            RPC.shell().storage_value_path(&key),
+            RPC.shell().dry_run_tx_path(),
+            RPC.shell()
+                .account_path(&address::testing::established_address_1()),
+        ] {
+            assert!(!reads_shell_logs(&query(path.clone())), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_gas_estimate_margin() {
+        let estimate = GasEstimate::new(10_000);
+        assert_eq!(estimate.gas_used, 10_000);
+        assert_eq!(u64::from(estimate.gas_limit), 12_000);
+
+        let estimate = GasEstimate::new(0);
+        assert_eq!(u64::from(estimate.gas_limit), 0);
+    }
 }
@@ -7,23 +7,26 @@ use std::str::FromStr;
 use borsh::{BorshDeserialize, BorshSerialize};
 use borsh_ext::BorshSerializeExt;
 use namada_core::hints;
-use namada_core::types::address::Address;
+use namada_core::types::address::{Address, InternalAddress, MULTITOKEN};
 use namada_core::types::eth_abi::{Encode, EncodeCell};
 use namada_core::types::eth_bridge_pool::{
-    PendingTransfer, PendingTransferAppendix,
+    PendingTransfer, PendingTransferAppendix, TransferStatus,
 };
 use namada_core::types::ethereum_events::{
-    EthAddress, EthereumEvent, TransferToEthereum,
+    EthAddress, EthereumEvent, TransferToEthereum, Uint,
 };
 use namada_core::types::ethereum_structs;
+use namada_core::types::hash::Hash;
 use namada_core::types::keccak::KeccakHash;
-use namada_core::types::storage::{BlockHeight, DbKeySeg, Epoch, Key};
+use namada_core::types::storage::{BlockHeight, DbKeySeg, Epoch, Key, KeySeg};
 use namada_core::types::token::Amount;
 use namada_core::types::voting_power::FractionalVotingPower;
 use namada_ethereum_bridge::protocol::transactions::votes::{
-    EpochedVotingPower, EpochedVotingPowerExt,
+    EpochedVotingPower, EpochedVotingPowerExt, Votes,
+};
+use namada_ethereum_bridge::storage::bridge_pool::{
+    get_key_from_hash, get_transfer_status_key,
 };
-use namada_ethereum_bridge::storage::bridge_pool::get_key_from_hash;
 use namada_ethereum_bridge::storage::eth_bridge_queries::EthBridgeQueries;
 use namada_ethereum_bridge::storage::parameters::UpgradeableContract;
 use namada_ethereum_bridge::storage::proof::{sort_sigs, EthereumProof};
@@ -35,6 +38,7 @@ use namada_proof_of_stake::pos_queries::PosQueries;
 use namada_state::MembershipProof::BridgePool;
 use namada_state::{DBIter, StorageHasher, StoreRef, StoreType, DB};
 use namada_storage::{self, CustomError, ResultExt, StorageRead};
+use namada_token::storage_key::is_any_token_balance_key;
 use namada_vote_ext::validator_set_update::{
     ValidatorSetArgs, VotingPowersMap,
 };
@@ -42,7 +46,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::eth_bridge::ethers::abi::AbiDecode;
 use crate::events::EventType;
-use crate::queries::{EncodedResponseQuery, RequestCtx, RequestQuery};
+use crate::queries::{
+    require_latest_height, EncodedResponseQuery, RequestCtx, RequestQuery,
+};
+use crate::tendermint::merkle::proof::ProofOps;
 
 /// Container for the status of queried transfers to Ethereum.
 #[derive(
@@ -142,6 +149,86 @@ impl GenBridgePoolProofRsp {
     }
 }
 
+/// The inclusion of an Ethereum event among the events seen by a quorum of
+/// validators.
+#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct EthEventInclusion {
+    /// The seen event.
+    pub event: EthereumEvent,
+    /// The block height at which the event was seen.
+    pub seen_at: BlockHeight,
+    /// The height of the Ethereum block that emitted the event and the
+    /// receipts root of that block, once it was seen by a quorum of
+    /// validators. The receipt of the event's transaction can be proven
+    /// against this root.
+    pub eth_block: Option<(Uint, KeccakHash)>,
+    /// The validators who voted for the event, with the block
+    /// heights of their votes. Not covered by the proof.
+    pub seen_by: Votes,
+    /// The fraction of the voting power behind the event. Not covered
+    /// by the proof.
+    pub voting_power: FractionalVotingPower,
+}
+
+impl EthEventInclusion {
+    /// The storage keys and values covered by the proof of this inclusion,
+    /// in the order of their proofs.
+    pub fn proven_values(&self) -> Vec<(Key, Vec<u8>)> {
+        let hash = self.event.hash().expect("Hashing an event can't fail");
+        let eth_msg_keys = Keys::<EthereumEvent>::from(&hash);
+        let mut values = vec![
+            (eth_msg_keys.body(), self.event.serialize_to_vec()),
+            (eth_msg_keys.seen(), true.serialize_to_vec()),
+            (
+                vote_tallies::eth_event_seen_height_key(&hash),
+                self.seen_at.serialize_to_vec(),
+            ),
+        ];
+        if let Some((eth_height, receipts_root)) = &self.eth_block {
+            values.push((
+                vote_tallies::eth_event_eth_block_key(&hash),
+                eth_height.serialize_to_vec(),
+            ));
+            values.push((
+                vote_tallies::eth_receipts_root_key(eth_height),
+                receipts_root.serialize_to_vec(),
+            ));
+        }
+        values
+    }
+
+    /// Verify the proof of this inclusion against the app hash of the block
+    /// at which it was queried. The proof is made of a sub-tree and a base
+    /// tree proof for each of the [`Self::proven_values`].
+    pub fn verify(
+        &self,
+        app_hash: &[u8],
+        proof: &ProofOps,
+    ) -> Result<(), crate::error::Error> {
+        let values = self.proven_values();
+        if proof.ops.len() != 2 * values.len() {
+            return Err(crate::error::Error::from(
+                crate::error::QueryError::Unverified(format!(
+                    "Expected {} proof ops for the inclusion of an Ethereum \
+                     event, got {}",
+                    2 * values.len(),
+                    proof.ops.len()
+                )),
+            ));
+        }
+        for ((key, value), ops) in values.iter().zip(proof.ops.chunks(2)) {
+            let proof = ProofOps { ops: ops.to_vec() };
+            crate::light_client::verify_storage_proof(
+                app_hash,
+                key,
+                Some(value),
+                &proof,
+            )?;
+        }
+        Ok(())
+    }
+}
+
 router! {ETH_BRIDGE,
     // Get the current contents of the Ethereum bridge pool
     ( "pool" / "contents" )
@@ -168,6 +255,11 @@ router! {ETH_BRIDGE,
     ( "pool" / "transfer_status" )
         -> TransferToEthereumStatus = (with_options pending_eth_transfer_status),
 
+    // Read the status of a transfer to Ethereum, given its keccak
+    // hash, as it moves through its lifecycle.
+    ( "pool" / "status" / [hash: KeccakHash] )
+        -> Option<TransferStatus> = read_transfer_status,
+
     // Request a proof of a validator set signed off for
     // the given epoch.
     //
@@ -212,6 +304,100 @@ router! {ETH_BRIDGE,
     // ERC20 token in Namada.
     ( "erc20" / "flow_control" / [asset: EthAddress] )
         -> Erc20FlowControl = get_erc20_flow_control,
+
+    // Read the balances of the NUTs held by the given owner, which
+    // may be converted into wrapped ERC20s once the token caps of
+    // their assets are raised.
+    ( "erc20" / "nut_balances" / [owner: Address] )
+        -> Vec<(EthAddress, Amount)> = read_nut_balances,
+
+    // Check if the Ethereum event with the given hash was seen by a
+    // quorum of validators. When a proof is requested, it can be
+    // checked with `EthEventInclusion::verify`.
+    ( "events" / "inclusion" / [hash: Hash] )
+        -> Option<EthEventInclusion> = (with_options eth_event_inclusion),
+}
+
+/// Check if the Ethereum event with the given hash was seen by a quorum
+/// of validators, along with the votes behind it.
+fn eth_event_inclusion<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+    hash: Hash,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    require_latest_height(&ctx, request)?;
+
+    let seen_height_key = vote_tallies::eth_event_seen_height_key(&hash);
+    let Some(seen_at) = ctx.wl_storage.read::<BlockHeight>(&seen_height_key)?
+    else {
+        return Ok(EncodedResponseQuery {
+            data: Option::<EthEventInclusion>::None.serialize_to_vec(),
+            ..Default::default()
+        });
+    };
+    let eth_msg_keys = Keys::<EthereumEvent>::from(&hash);
+    let event = ctx
+        .wl_storage
+        .read::<EthereumEvent>(&eth_msg_keys.body())?
+        .ok_or(namada_storage::Error::SimpleMessage(
+            "A seen Ethereum event must be present in storage",
+        ))?;
+    let eth_block = ctx
+        .wl_storage
+        .read::<Uint>(&vote_tallies::eth_event_eth_block_key(&hash))?
+        .map(|eth_height| {
+            ctx.wl_storage
+                .read::<KeccakHash>(&vote_tallies::eth_receipts_root_key(
+                    &eth_height,
+                ))?
+                .map(|receipts_root| (eth_height, receipts_root))
+                .ok_or(namada_storage::Error::SimpleMessage(
+                    "The receipts root of the Ethereum block of a seen event \
+                     must be present in storage",
+                ))
+        })
+        .transpose()?;
+    let seen_by = ctx
+        .wl_storage
+        .read::<Votes>(&eth_msg_keys.seen_by())?
+        .unwrap_or_default();
+    let voting_power = ctx
+        .wl_storage
+        .read::<EpochedVotingPower>(&eth_msg_keys.voting_power())?
+        .unwrap_or_default()
+        .fractional_stake(ctx.wl_storage);
+    let inclusion = EthEventInclusion {
+        event,
+        seen_at,
+        eth_block,
+        seen_by,
+        voting_power,
+    };
+
+    let proof = if request.prove {
+        let height = ctx.wl_storage.storage.get_last_block_height();
+        let mut ops = vec![];
+        for (key, value) in inclusion.proven_values() {
+            let mut proof = ctx
+                .wl_storage
+                .storage
+                .get_existence_proof(&key, &value, height)
+                .into_storage_result()?;
+            ops.append(&mut proof.ops);
+        }
+        Some(ProofOps { ops })
+    } else {
+        None
+    };
+    Ok(EncodedResponseQuery {
+        data: Some(inclusion).serialize_to_vec(),
+        proof,
+        ..Default::default()
+    })
 }
 
 /// Given a list of keccak hashes, check whether they have been
@@ -356,6 +542,42 @@ where
     })
 }
 
+/// Read the balances of the NUTs held by the given owner, along with
+/// the ERC20 assets they were minted for.
+fn read_nut_balances<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    owner: Address,
+) -> namada_storage::Result<Vec<(EthAddress, Amount)>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let prefix = Key::from(MULTITOKEN.to_db_key());
+    namada_storage::iter_prefix_with_filter::<Amount, _>(
+        ctx.wl_storage,
+        &prefix,
+        move |key| {
+            matches!(
+                is_any_token_balance_key(key),
+                Some([Address::Internal(InternalAddress::Nut(_)), holder])
+                    if *holder == owner
+            )
+        },
+    )?
+    .filter_map(|result| match result {
+        Ok((key, amount)) => match is_any_token_balance_key(&key) {
+            Some([Address::Internal(InternalAddress::Nut(asset)), _])
+                if !amount.is_zero() =>
+            {
+                Some(Ok((*asset, amount)))
+            }
+            _ => None,
+        },
+        Err(err) => Some(Err(err)),
+    })
+    .collect()
+}
+
 /// Helper function to read a smart contract from storage.
 fn read_contract<T, D, H, V, U>(
     key: &Key,
@@ -680,6 +902,77 @@ where
     Ok(pending_events)
 }
 
+/// Read the status of a transfer to Ethereum.
+///
+/// Only the pending, confirmed and expired statuses are written to
+/// storage. Whether a pending transfer has been signed or relayed is
+/// determined from the latest signed Bridge pool root and from the
+/// Ethereum events that have not been confirmed yet, respectively.
+fn read_transfer_status<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    hash: KeccakHash,
+) -> namada_storage::Result<Option<TransferStatus>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let status = ctx
+        .wl_storage
+        .read::<TransferStatus>(&get_transfer_status_key(&hash))?;
+    if !matches!(status, None | Some(TransferStatus::Pending)) {
+        return Ok(status);
+    }
+    if !ctx.wl_storage.has_key(&get_key_from_hash(&hash))? {
+        return Ok(status);
+    }
+
+    // check if the transfer has been relayed to Ethereum
+    for (key, value, _) in ctx.wl_storage.iter_prefix(&eth_msgs_prefix())? {
+        let key = Key::from_str(&key).expect(
+            "Iterating over keys from storage shouldn't not yield un-parsable \
+             keys.",
+        );
+        match key.segments.last() {
+            Some(DbKeySeg::StringSeg(ref seg))
+                if seg == Keys::segments().body => {}
+            _ => continue,
+        }
+        if let Ok(EthereumEvent::TransfersToEthereum {
+            transfers, nonce, ..
+        }) = EthereumEvent::try_from_slice(&value)
+        {
+            if transfers.iter().any(|t| t.keccak256() == hash) {
+                return Ok(Some(TransferStatus::Relayed { nonce }));
+            }
+        }
+    }
+
+    // check if the transfer is covered by the latest signed root
+    if let Some((proof, height)) = ctx
+        .wl_storage
+        .ethbridge_queries()
+        .get_signed_bridge_pool_root()
+    {
+        let merkle_tree = ctx
+            .wl_storage
+            .storage
+            .get_merkle_tree(height, Some(StoreType::BridgePool))
+            .expect("We should always be able to read the database");
+        let stores = merkle_tree.stores();
+        let store = match stores.store(&StoreType::BridgePool) {
+            StoreRef::BridgePool(store) => store,
+            _ => unreachable!(),
+        };
+        if store.contains_key(&hash) {
+            return Ok(Some(TransferStatus::Signed {
+                nonce: proof.data.1,
+            }));
+        }
+    }
+
+    Ok(Some(TransferStatus::Pending))
+}
+
 /// Read a validator set update proof from storage.
 ///
 /// This method may fail if a complete proof (i.e. with more than
@@ -834,20 +1127,22 @@ where
 
 #[cfg(test)]
 mod test_ethbridge_router {
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, BTreeSet};
 
     use assert_matches::assert_matches;
     use namada_core::types::address::nam;
     use namada_core::types::address::testing::established_address_1;
     use namada_core::types::eth_abi::Encode;
     use namada_core::types::eth_bridge_pool::{
-        GasFee, PendingTransfer, TransferToEthereum, TransferToEthereumKind,
+        erc20_nut_address, erc20_token_address, GasFee, PendingTransfer,
+        TransferToEthereum, TransferToEthereumKind,
     };
     use namada_core::types::ethereum_events::EthAddress;
     use namada_core::types::storage::BlockHeight;
     use namada_core::types::voting_power::{
         EthBridgeVotingPower, FractionalVotingPower,
     };
+    use namada_ethereum_bridge::protocol::transactions::ethereum_events::apply_derived_tx;
     use namada_ethereum_bridge::protocol::transactions::validator_set_update::aggregate_votes;
     use namada_ethereum_bridge::storage::bridge_pool::{
         get_pending_key, get_signed_root_key, BridgePoolTree,
@@ -857,6 +1152,8 @@ mod test_ethbridge_router {
     use namada_proof_of_stake::pos_queries::PosQueries;
     use namada_state::mockdb::MockDBWriteBatch;
     use namada_storage::StorageWrite;
+    use namada_token::storage_key::balance_key;
+    use namada_vote_ext::ethereum_events::MultiSignedEthEvent;
     use namada_vote_ext::validator_set_update;
     use namada_vote_ext::validator_set_update::{
         EthAddrBook, VotingPowersMapExt,
@@ -1447,6 +1744,115 @@ mod test_ethbridge_router {
         assert_eq!(resp, vec![transfer]);
     }
 
+    /// Test that the status of transfers to Ethereum is read
+    /// correctly throughout their lifecycle.
+    #[tokio::test]
+    async fn test_read_transfer_status() {
+        let mut client = TestClient::new(RPC);
+        let transfer = PendingTransfer {
+            transfer: TransferToEthereum {
+                kind: TransferToEthereumKind::Erc20,
+                asset: EthAddress([0; 20]),
+                recipient: EthAddress([0; 20]),
+                sender: bertha_address(),
+                amount: 0.into(),
+            },
+            gas_fee: GasFee {
+                token: nam(),
+                amount: 0.into(),
+                payer: bertha_address(),
+            },
+        };
+        // write validator to storage
+        test_utils::init_default_storage(&mut client.wl_storage);
+
+        // write a transfer into the bridge pool
+        client
+            .wl_storage
+            .write_bytes(
+                &get_pending_key(&transfer),
+                transfer.serialize_to_vec(),
+            )
+            .expect("Test failed");
+        client
+            .wl_storage
+            .write(
+                &get_transfer_status_key(&transfer.keccak256()),
+                TransferStatus::Pending,
+            )
+            .expect("Test failed");
+
+        // create a signed Merkle root for this pool
+        let signed_root = BridgePoolRootProof {
+            signatures: Default::default(),
+            data: (transfer.keccak256(), 3.into()),
+        };
+        let written_height = client.wl_storage.storage.block.height;
+
+        // commit the changes and increase block height
+        client.wl_storage.commit_block().expect("Test failed");
+        client.wl_storage.storage.block.height += 1;
+
+        // update the pool
+        let mut transfer2 = transfer.clone();
+        transfer2.transfer.amount = 1.into();
+        client
+            .wl_storage
+            .write_bytes(
+                &get_pending_key(&transfer2),
+                transfer2.serialize_to_vec(),
+            )
+            .expect("Test failed");
+
+        // add the signature for the pool at the previous block height
+        client
+            .wl_storage
+            .write_bytes(
+                &get_signed_root_key(),
+                (signed_root, written_height).serialize_to_vec(),
+            )
+            .expect("Test failed");
+
+        // an expired transfer, no longer in the pool
+        let mut transfer3 = transfer.clone();
+        transfer3.transfer.amount = 2.into();
+        client
+            .wl_storage
+            .write(
+                &get_transfer_status_key(&transfer3.keccak256()),
+                TransferStatus::Expired,
+            )
+            .expect("Test failed");
+
+        // commit the changes and increase block height
+        client.wl_storage.commit_block().expect("Test failed");
+        client.wl_storage.storage.block.height += 1;
+
+        let expected = [
+            (&transfer, Some(TransferStatus::Signed { nonce: 3.into() })),
+            (&transfer2, Some(TransferStatus::Pending)),
+            (&transfer3, Some(TransferStatus::Expired)),
+        ];
+        for (transfer, status) in expected {
+            let resp = RPC
+                .shell()
+                .eth_bridge()
+                .read_transfer_status(&client, &transfer.keccak256())
+                .await
+                .unwrap();
+            assert_eq!(resp, status);
+        }
+
+        // a transfer that was never added to the pool
+        let resp = RPC
+            .shell()
+            .eth_bridge()
+            .read_transfer_status(&client, &KeccakHash([1; 32]))
+            .await
+            .unwrap();
+        assert_eq!(resp, None);
+    }
+
     /// Test that we can get the backing voting power for
     /// each pending TransferToEthereum event.
     #[tokio::test]
@@ -1716,6 +2122,60 @@ mod test_ethbridge_router {
         );
     }
 
+    /// Test reading the balances of the NUTs held by an owner.
+    #[tokio::test]
+    async fn test_read_nut_balances() {
+        const ERC20_TOKEN: EthAddress = EthAddress([0; 20]);
+
+        let mut client = TestClient::new(RPC);
+        test_utils::init_default_storage(&mut client.wl_storage);
+        let owner = established_address_1();
+
+        // the owner holds no NUTs
+        let result = RPC
+            .shell()
+            .eth_bridge()
+            .read_nut_balances(&client, &owner)
+            .await;
+        assert_matches!(result, Ok(balances) if balances.is_empty());
+
+        // write the balances of some NUTs and of the wrapped ERC20
+        let nut_amount = Amount::from(123);
+        client
+            .wl_storage
+            .write(
+                &balance_key(&erc20_nut_address(&ERC20_TOKEN), &owner),
+                nut_amount,
+            )
+            .expect("Test failed");
+        client
+            .wl_storage
+            .write(
+                &balance_key(
+                    &erc20_nut_address(&ERC20_TOKEN),
+                    &bertha_address(),
+                ),
+                Amount::from(456),
+            )
+            .expect("Test failed");
+        client
+            .wl_storage
+            .write(
+                &balance_key(&erc20_token_address(&ERC20_TOKEN), &owner),
+                Amount::from(789),
+            )
+            .expect("Test failed");
+
+        // only the NUTs of the owner are returned
+        let result = RPC
+            .shell()
+            .eth_bridge()
+            .read_nut_balances(&client, &owner)
+            .await
+            .expect("Test failed");
+        assert_eq!(result, vec![(ERC20_TOKEN, nut_amount)]);
+    }
+
     /// Test that querying the status of the Bridge pool
     /// returns the expected keccak hashes.
     #[tokio::test]
@@ -1750,18 +2210,22 @@ mod test_ethbridge_router {
         transfer2.transfer.amount = 1.into();
         let mut transfer3 = transfer.clone();
         transfer3.transfer.amount = 2.into();
-        client.event_log.log_events(vec![
-            ethereum_structs::EthBridgeEvent::BridgePool {
-                tx_hash: transfer2.keccak256(),
-                status: ethereum_structs::BpTransferStatus::Expired,
-            }
-            .into(),
-            ethereum_structs::EthBridgeEvent::BridgePool {
-                tx_hash: transfer3.keccak256(),
-                status: ethereum_structs::BpTransferStatus::Relayed,
-            }
-            .into(),
-        ]);
+        let height = client.wl_storage.storage.get_last_block_height();
+        client.event_log.log_events(
+            height,
+            vec![
+                ethereum_structs::EthBridgeEvent::BridgePool {
+                    tx_hash: transfer2.keccak256(),
+                    status: ethereum_structs::BpTransferStatus::Expired,
+                }
+                .into(),
+                ethereum_structs::EthBridgeEvent::BridgePool {
+                    tx_hash: transfer3.keccak256(),
+                    status: ethereum_structs::BpTransferStatus::Relayed,
+                }
+                .into(),
+            ],
+        );
 
         // some arbitrary transfer - since it's neither in the
         // Bridge pool nor in the event log, it is assumed it has
@@ -1841,6 +2305,85 @@ mod test_ethbridge_router {
             "unexpected unrecognized transfers"
         );
     }
+
+    /// Test that the inclusion of a seen Ethereum event, along with the
+    /// receipts root of the Ethereum block that emitted it, can be verified
+    /// against the app hash.
+    #[tokio::test]
+    async fn test_eth_event_inclusion() {
+        let mut client = TestClient::new(RPC);
+        test_utils::init_default_storage(&mut client.wl_storage);
+        let (validator, _) = test_utils::default_validator();
+
+        let event = EthereumEvent::ValidatorSetUpdate {
+            nonce: 0.into(),
+            bridge_validator_hash: KeccakHash([1; 32]),
+            governance_validator_hash: KeccakHash([2; 32]),
+        };
+        let hash = event.hash().expect("Test failed");
+        let receipts_root = KeccakHash([3; 32]);
+        let root_event = EthereumEvent::ReceiptsRoot {
+            block_height: 42.into(),
+            receipts_root: receipts_root.clone(),
+            events: vec![hash],
+        };
+
+        // the event is unknown before it is seen
+        let inclusion = RPC
+            .shell()
+            .eth_bridge()
+            .eth_event_inclusion(&client, None, None, false, &hash)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(inclusion, None);
+
+        // a quorum of validators sees the event and the receipts root
+        client.wl_storage.storage.block.height = 1.into();
+        apply_derived_tx(
+            &mut client.wl_storage,
+            [event.clone(), root_event]
+                .into_iter()
+                .map(|event| MultiSignedEthEvent {
+                    event,
+                    signers: BTreeSet::from([(
+                        validator.clone(),
+                        BlockHeight(100),
+                    )]),
+                })
+                .collect(),
+        )
+        .expect("Test failed");
+        let seen_at = client.wl_storage.storage.block.height;
+        client.wl_storage.commit_block().expect("Test failed");
+        client.wl_storage.storage.block.height += 1;
+        let app_hash = client.wl_storage.storage.merkle_root().0;
+
+        let response = RPC
+            .shell()
+            .eth_bridge()
+            .eth_event_inclusion(&client, None, None, true, &hash)
+            .await
+            .unwrap();
+        let inclusion = response.data.expect("Test failed");
+        let proof = response.proof.expect("Test failed");
+        assert_eq!(inclusion.event, event);
+        assert_eq!(inclusion.seen_at, seen_at);
+        assert_eq!(
+            inclusion.eth_block,
+            Some((42.into(), receipts_root.clone()))
+        );
+        assert!(inclusion.verify(&app_hash, &proof).is_ok());
+
+        // a forged receipts root must be rejected
+        let forged = EthEventInclusion {
+            eth_block: Some((42.into(), KeccakHash([4; 32]))),
+            ..inclusion.clone()
+        };
+        assert!(forged.verify(&app_hash, &proof).is_err());
+        // as must a different app hash
+        assert!(inclusion.verify(&[0; 32], &proof).is_err());
+    }
 }
 
 #[cfg(any(feature = "testing", test))]
@@ -5,6 +5,7 @@ use namada_state::{DBIter, StorageHasher, WlStorage, DB};
 use thiserror::Error;
 
 use crate::events::log::EventLog;
+use crate::events::mempool::MempoolRejections;
 use crate::tendermint::merkle::proof::ProofOps;
 pub use crate::tendermint::v0_37::abci::request::Query as RequestQuery;
 /// A request context provides read-only access to storage and WASM compilation
@@ -19,6 +20,8 @@ where
     pub wl_storage: &'shell WlStorage<D, H>,
     /// Log of events emitted by `FinalizeBlock` ABCI calls.
     pub event_log: &'shell EventLog,
+    /// Log of txs rejected by `CheckTx` ABCI calls.
+    pub mempool_rejections: &'shell MempoolRejections,
     /// Cache of VP wasm compiled artifacts.
     pub vp_wasm_cache: VpCache,
     /// Cache of transaction wasm compiled artifacts.
@@ -61,6 +64,10 @@ pub trait Router {
     where
         D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
         H: 'static + StorageHasher + Sync;
+
+    /// The paths of all the handlers of the `Router`, relative to its prefix.
+    /// The dynamic segments are shown as `{name}`.
+    fn paths(&self) -> Vec<String>;
 }
 
 #[allow(missing_docs)]
@@ -1,5 +1,8 @@
 // cd namada && cargo expand ledger::queries::vp::governance
 
+use std::collections::BTreeSet;
+
+use namada_core::types::address::Address;
 use namada_governance::parameters::GovernanceParameters;
 use namada_governance::storage::proposal::StorageProposal;
 use namada_governance::utils::{ProposalResult, Vote};
@@ -13,6 +16,8 @@ router! {GOV,
     ( "proposal" / [id: u64 ] / "votes" ) -> Vec<Vote> = proposal_id_votes,
     ( "parameters" ) -> GovernanceParameters = parameters,
     ( "stored_proposal_result" / [id: u64] ) -> Option<ProposalResult> = proposal_result,
+    ( "vote_proxy" / [delegator: Address] ) -> Option<Address> = vote_proxy,
+    ( "proxied_delegators" / [proxy: Address] ) -> BTreeSet<Address> = proxied_delegators,
 }
 
 /// Query the provided proposal id
@@ -61,3 +66,27 @@ where
 {
     namada_governance::storage::get_proposal_result(ctx.wl_storage, id)
 }
+
+/// Get the vote proxy of a delegator
+fn vote_proxy<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    delegator: Address,
+) -> namada_storage::Result<Option<Address>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_vote_proxy(ctx.wl_storage, &delegator)
+}
+
+/// Get the delegators that have set the given address as their vote proxy
+fn proxied_delegators<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    proxy: Address,
+) -> namada_storage::Result<BTreeSet<Address>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_governance::storage::get_proxied_delegators(ctx.wl_storage, &proxy)
+}
@@ -13,7 +13,7 @@ use pgf::PGF;
 mod pgf;
 
 pub mod pos;
-mod token;
+pub mod token;
 
 // Validity predicate queries
 router! {VP,
@@ -18,7 +18,8 @@ use namada_proof_of_stake::storage::{
     bond_handle, read_all_validator_addresses,
     read_below_capacity_validator_set_addresses_with_stake,
     read_consensus_validator_set_addresses_with_stake, read_pos_params,
-    read_total_stake, read_validator_avatar, read_validator_description,
+    read_total_stake, read_validator_avatar,
+    read_validator_deactivation_reason, read_validator_description,
     read_validator_discord_handle, read_validator_email,
     read_validator_last_slash_epoch, read_validator_max_commission_rate_change,
     read_validator_stake, read_validator_website, unbond_handle,
@@ -27,7 +28,8 @@ use namada_proof_of_stake::storage::{
 };
 use namada_proof_of_stake::types::{
     BondId, BondsAndUnbondsDetail, BondsAndUnbondsDetails, CommissionPair,
-    Slash, ValidatorMetaData, ValidatorState, WeightedValidator,
+    DeactivationReason, RewardsRate, Slash, ValidatorMetaData, ValidatorState,
+    WeightedValidator,
 };
 use namada_proof_of_stake::{self, bond_amount, query_reward_tokens};
 use namada_state::{DBIter, StorageHasher, DB};
@@ -66,6 +68,9 @@ router! {POS,
 
         ( "last_infraction_epoch" / [validator: Address] )
             -> Option<Epoch> = validator_last_infraction_epoch,
+
+        ( "deactivation_reason" / [validator: Address] )
+            -> Option<DeactivationReason> = validator_deactivation_reason,
     },
 
     ( "validator_set" ) = {
@@ -98,6 +103,9 @@ router! {POS,
     ( "rewards" / [validator: Address] / [source: opt Address] )
         -> token::Amount = rewards,
 
+    ( "rewards_rate" / [validator: Address] / [source: opt Address] )
+        -> RewardsRate = rewards_rate,
+
     ( "bond_with_slashing" / [source: Address] / [validator: Address] / [epoch: opt Epoch] )
         -> token::Amount = bond_with_slashing,
 
@@ -113,6 +121,9 @@ router! {POS,
     ( "bonds_and_unbonds" / [source: opt Address] / [validator: opt Address] )
         -> BondsAndUnbondsDetails = bonds_and_unbonds,
 
+    ( "bonds_and_unbonds_at" / [epoch: Epoch] / [source: opt Address] / [validator: opt Address] )
+        -> BondsAndUnbondsDetails = bonds_and_unbonds_at,
+
     ( "enqueued_slashes" )
         -> HashMap<Address, BTreeMap<Epoch, Vec<Slash>>> = enqueued_slashes,
 
@@ -128,6 +139,9 @@ router! {POS,
     ( "has_bonds" / [source: Address] )
         -> bool = has_bonds,
 
+    ( "staking_overview" / [owner: Address] )
+        -> StakingOverview = staking_overview,
+
 }
 
 /// Enriched bonds data with extra information calculated from the data queried
@@ -170,6 +184,26 @@ impl<T> Enriched<T> {
     }
 }
 
+/// All the bonds, unbonds and pending rewards of an owner, with the slashes
+/// applied to them and the states of the validators they are bonded to, at
+/// the current epoch.
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema)]
+pub struct StakingOverview<B = BondsAndUnbondsDetails> {
+    /// The epoch of the overview
+    pub epoch: Epoch,
+    /// Bonds and unbonds with all details, grouped by their bond IDs
+    pub bonds_and_unbonds: B,
+    /// The rewards available to claim from each validator
+    pub rewards: HashMap<Address, token::Amount>,
+    /// The state of each validator, if any
+    pub validator_states: HashMap<Address, Option<ValidatorState>>,
+}
+
+/// The staking overview of an owner, with its bonds and unbonds enriched with
+/// extra information calculated from the data queried from the node.
+pub type EnrichedStakingOverview =
+    StakingOverview<EnrichedBondsAndUnbondsDetails>;
+
 // Handlers that implement the functions via `trait StorageRead`:
 
 /// Get the PoS parameters
@@ -331,6 +365,18 @@ where
     read_validator_last_slash_epoch(ctx.wl_storage, &validator)
 }
 
+/// Get the reason for which a validator has been deactivated by the protocol
+fn validator_deactivation_reason<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+) -> namada_storage::Result<Option<DeactivationReason>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_validator_deactivation_reason(ctx.wl_storage, &validator)
+}
+
 /// Get the total stake of a validator at the given epoch or current when
 /// `None`. The total stake is a sum of validator's self-bonds and delegations
 /// to their address.
@@ -562,6 +608,24 @@ where
     )
 }
 
+fn rewards_rate<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    validator: Address,
+    source: Option<Address>,
+) -> namada_storage::Result<RewardsRate>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let current_epoch = ctx.wl_storage.storage.last_epoch;
+    namada_proof_of_stake::rewards::estimate_rewards_rate(
+        ctx.wl_storage,
+        source.as_ref(),
+        &validator,
+        current_epoch,
+    )
+}
+
 fn bonds_and_unbonds<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     source: Option<Address>,
@@ -578,6 +642,24 @@ where
     )
 }
 
+fn bonds_and_unbonds_at<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    epoch: Epoch,
+    source: Option<Address>,
+    validator: Option<Address>,
+) -> namada_storage::Result<BondsAndUnbondsDetails>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    namada_proof_of_stake::queries::bonds_and_unbonds_at_epoch(
+        ctx.wl_storage,
+        source,
+        validator,
+        epoch,
+    )
+}
+
 /// Find all the validator addresses to whom the given `owner` address has
 /// some delegation in any epoch
 fn delegation_validators<D, H, V, T>(
@@ -680,6 +762,65 @@ where
     namada_proof_of_stake::queries::has_bonds(ctx.wl_storage, &source)
 }
 
+/// Get the staking overview of the given owner, i.e. the bonds, unbonds and
+/// rewards with every validator that the owner has delegated to or, for a
+/// validator, its self-bonds.
+fn staking_overview<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    owner: Address,
+) -> namada_storage::Result<StakingOverview>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let epoch = ctx.wl_storage.storage.last_epoch;
+    let params = read_pos_params(ctx.wl_storage)?;
+    let bonds_and_unbonds = namada_proof_of_stake::queries::bonds_and_unbonds(
+        ctx.wl_storage,
+        Some(owner.clone()),
+        None,
+    )?;
+
+    // The rewards may be left to claim from validators with no remaining
+    // bonds
+    let mut validators = find_delegation_validators(ctx.wl_storage, &owner)?;
+    validators.extend(
+        bonds_and_unbonds
+            .keys()
+            .map(|bond_id| bond_id.validator.clone()),
+    );
+    if namada_proof_of_stake::is_validator(ctx.wl_storage, &owner)? {
+        validators.insert(owner.clone());
+    }
+
+    let mut rewards = HashMap::new();
+    let mut validator_states = HashMap::new();
+    for validator in validators {
+        let reward = query_reward_tokens(
+            ctx.wl_storage,
+            Some(&owner),
+            &validator,
+            epoch,
+        )?;
+        if !reward.is_zero() {
+            rewards.insert(validator.clone(), reward);
+        }
+        let state = validator_state_handle(&validator).get(
+            ctx.wl_storage,
+            epoch,
+            &params,
+        )?;
+        validator_states.insert(validator, state);
+    }
+
+    Ok(StakingOverview {
+        epoch,
+        bonds_and_unbonds,
+        rewards,
+        validator_states,
+    })
+}
+
 /// Client-only methods for the router type are composed from router functions.
 #[cfg(any(test, feature = "async-client"))]
 pub mod client_only_methods {
@@ -707,6 +848,54 @@ pub mod client_only_methods {
                 .await?;
             Ok(enrich_bonds_and_unbonds(current_epoch, data))
         }
+
+        /// Get bonds and unbonds as they were at the given past `epoch`,
+        /// enriched with extra information calculated from the data as of
+        /// that epoch.
+        pub async fn enriched_bonds_and_unbonds_at<CLIENT>(
+            &self,
+            client: &CLIENT,
+            epoch: Epoch,
+            source: &Option<Address>,
+            validator: &Option<Address>,
+        ) -> Result<EnrichedBondsAndUnbondsDetails, <CLIENT as Client>::Error>
+        where
+            CLIENT: Client + Sync,
+        {
+            let data = RPC
+                .vp()
+                .pos()
+                .bonds_and_unbonds_at(client, &epoch, source, validator)
+                .await?;
+            Ok(enrich_bonds_and_unbonds(epoch, data))
+        }
+
+        /// Get the staking overview of the given owner, with the bonds and
+        /// unbonds enriched with extra information calculated from the data.
+        pub async fn enriched_staking_overview<CLIENT>(
+            &self,
+            client: &CLIENT,
+            owner: &Address,
+        ) -> Result<EnrichedStakingOverview, <CLIENT as Client>::Error>
+        where
+            CLIENT: Client + Sync,
+        {
+            let StakingOverview {
+                epoch,
+                bonds_and_unbonds,
+                rewards,
+                validator_states,
+            } = RPC.vp().pos().staking_overview(client, owner).await?;
+            Ok(StakingOverview {
+                epoch,
+                bonds_and_unbonds: enrich_bonds_and_unbonds(
+                    epoch,
+                    bonds_and_unbonds,
+                ),
+                rewards,
+                validator_states,
+            })
+        }
     }
 }
 
@@ -773,3 +962,61 @@ fn enrich_bonds_and_unbonds(
         total_withdrawable,
     }
 }
+
+#[cfg(test)]
+mod test_pos_queries {
+    use namada_core::types::address::testing::established_address_2;
+    use namada_ethereum_bridge::test_utils;
+
+    use super::*;
+    use crate::queries::testing::TestClient;
+    use crate::queries::RPC;
+
+    /// Test that the staking overview of a validator contains its self-bond
+    /// and its state, and that the overview of an owner without bonds is
+    /// empty.
+    #[tokio::test]
+    async fn test_staking_overview() {
+        let mut client = TestClient::new(RPC);
+        test_utils::init_default_storage(&mut client.wl_storage);
+        let (validator, stake) = test_utils::default_validator();
+
+        let overview = RPC
+            .vp()
+            .pos()
+            .staking_overview(&client, &validator)
+            .await
+            .unwrap();
+        assert_eq!(overview.epoch, Epoch(0));
+        assert_eq!(
+            overview.validator_states,
+            HashMap::from([(
+                validator.clone(),
+                Some(ValidatorState::Consensus)
+            )])
+        );
+        let bond_id = BondId {
+            source: validator.clone(),
+            validator: validator.clone(),
+        };
+        let details = overview
+            .bonds_and_unbonds
+            .get(&bond_id)
+            .expect("The self-bond should be in the overview");
+        let bonded: token::Amount =
+            details.bonds.iter().map(|bond| bond.amount).sum();
+        assert_eq!(bonded, stake);
+        assert!(details.unbonds.is_empty());
+        assert!(overview.rewards.is_empty());
+
+        let overview = RPC
+            .vp()
+            .pos()
+            .staking_overview(&client, &established_address_2())
+            .await
+            .unwrap();
+        assert!(overview.bonds_and_unbonds.is_empty());
+        assert!(overview.validator_states.is_empty());
+        assert!(overview.rewards.is_empty());
+    }
+}
@@ -1,15 +1,39 @@
 //! Token validity predicate queries
 
+use std::collections::{BTreeMap, BTreeSet};
+
+use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
 use namada_core::types::address::Address;
+use namada_core::types::storage::Epoch;
 use namada_core::types::token;
 use namada_state::{DBIter, StorageHasher, DB};
-use namada_token::{read_denom, read_total_supply};
+use namada_storage::ResultExt;
+use namada_token::storage_key::{
+    balance_prefix, epoch_flows_prefix, is_balance_key, is_epoch_flows_key,
+};
+use namada_token::{read_denom, read_total_supply, read_vesting_schedule};
+
+use crate::queries::types::RequestQuery;
+use crate::queries::{require_latest_height, EncodedResponseQuery, RequestCtx};
+
+/// The balances of owners in tokens, keyed by the token and the owner
+pub type Balances = BTreeMap<(Address, Address), token::Amount>;
 
-use crate::queries::RequestCtx;
+/// The flows of the tokens in an epoch, keyed by the token
+pub type EpochFlows = BTreeMap<Address, token::TokenFlows>;
 
 router! {TOKEN,
     ( "denomination" / [addr: Address] ) -> Option<token::Denomination> = denomination,
     ( "total_supply" / [addr: Address] ) -> Option<token::Amount> = total_supply,
+    ( "vesting" / [token: Address] / [owner: Address] ) -> Option<token::VestingSchedule> = vesting_schedule,
+
+    // Given the sets of tokens and owners, get the balance of every owner
+    // in every token
+    ( "balances" ) -> Balances = (with_options balances),
+
+    // The fee, burn and treasury flows of every token in an epoch
+    ( "epoch_flows" / [epoch: Epoch] ) -> EpochFlows = epoch_flows,
 }
 
 /// Get the number of decimal places (in base 10) for a
@@ -37,6 +61,92 @@ where
     read_total_supply(ctx.wl_storage, &addr)
 }
 
+/// Get the vesting schedule of the owner's balance of a token, if any
+fn vesting_schedule<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    token: Address,
+    owner: Address,
+) -> namada_storage::Result<Option<token::VestingSchedule>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    read_vesting_schedule(ctx.wl_storage, &token, &owner)
+}
+
+/// Get the balances of all the pairs of the tokens and the owners given in the
+/// request data as a borsh encoded `(BTreeSet<Address>, BTreeSet<Address>)`.
+/// The balances of every token are read in a single iteration over its
+/// balances prefix. The owners without a balance have a zero balance.
+fn balances<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    request: &RequestQuery,
+) -> namada_storage::Result<EncodedResponseQuery>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    require_latest_height(&ctx, request)?;
+
+    let (tokens, owners): (BTreeSet<Address>, BTreeSet<Address>) =
+        BorshDeserialize::try_from_slice(&request.data)
+            .into_storage_result()?;
+
+    let mut balances = Balances::new();
+    for token in tokens {
+        for owner in &owners {
+            balances
+                .insert((token.clone(), owner.clone()), token::Amount::zero());
+        }
+        if owners.is_empty() {
+            continue;
+        }
+        let iter = namada_storage::iter_prefix_bytes(
+            ctx.wl_storage,
+            &balance_prefix(&token),
+        )?;
+        for iter_result in iter {
+            let (key, value) = iter_result?;
+            let owner = match is_balance_key(&token, &key) {
+                Some(owner) if owners.contains(owner) => owner.clone(),
+                _ => continue,
+            };
+            let balance =
+                token::Amount::try_from_slice(&value).into_storage_result()?;
+            balances.insert((token.clone(), owner), balance);
+        }
+    }
+    Ok(EncodedResponseQuery {
+        data: balances.serialize_to_vec(),
+        ..Default::default()
+    })
+}
+
+/// Get the flows of all the tokens that had any in the given epoch, i.e. the
+/// fees collected and distributed to the block proposers, the tokens burned
+/// and the inflows and outflows of the PGF treasury.
+fn epoch_flows<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    epoch: Epoch,
+) -> namada_storage::Result<EpochFlows>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let iter = namada_storage::iter_prefix::<token::TokenFlows>(
+        ctx.wl_storage,
+        &epoch_flows_prefix(epoch),
+    )?;
+    let mut flows = EpochFlows::new();
+    for iter_result in iter {
+        let (key, token_flows) = iter_result?;
+        if let Some(token) = is_epoch_flows_key(epoch, &key) {
+            flows.insert(token.clone(), token_flows);
+        }
+    }
+    Ok(flows)
+}
+
 #[cfg(any(test, feature = "async-client"))]
 pub mod client_only_methods {
     use borsh::BorshDeserialize;
@@ -74,3 +184,58 @@ pub mod client_only_methods {
         }
     }
 }
+
+#[cfg(test)]
+mod test_token_queries {
+    use namada_core::types::address::nam;
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+    };
+    use namada_storage::StorageWrite;
+    use namada_token::storage_key::{balance_key, minted_balance_key};
+
+    use super::*;
+    use crate::queries::testing::TestClient;
+    use crate::queries::RPC;
+
+    #[tokio::test]
+    async fn test_balances() {
+        let mut client = TestClient::new(RPC);
+        let token = nam();
+        let alice = established_address_1();
+        let bob = established_address_2();
+        let carol = established_address_3();
+        for (owner, amount) in [(&alice, 10_u64), (&bob, 20), (&carol, 30)] {
+            client
+                .wl_storage
+                .write(&balance_key(&token, owner), token::Amount::from(amount))
+                .unwrap();
+        }
+        client
+            .wl_storage
+            .write(&minted_balance_key(&token), token::Amount::from(60_u64))
+            .unwrap();
+
+        let tokens = BTreeSet::from([token.clone(), carol.clone()]);
+        let owners = BTreeSet::from([alice.clone(), bob.clone()]);
+        let balances: Balances = RPC
+            .vp()
+            .token()
+            .balances(
+                &client,
+                Some((tokens, owners).serialize_to_vec()),
+                None,
+                false,
+            )
+            .await
+            .unwrap()
+            .data;
+        let expected = Balances::from([
+            ((token.clone(), alice.clone()), token::Amount::from(10_u64)),
+            ((token, bob.clone()), token::Amount::from(20_u64)),
+            ((carol.clone(), alice), token::Amount::zero()),
+            ((carol, bob), token::Amount::zero()),
+        ]);
+        assert_eq!(balances, expected);
+    }
+}
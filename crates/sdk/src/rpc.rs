@@ -6,16 +6,19 @@ use std::ops::ControlFlow;
 use std::str::FromStr;
 
 use borsh::BorshDeserialize;
+use borsh_ext::BorshSerializeExt;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
 use namada_account::Account;
 use namada_core::types::address::{Address, InternalAddress};
 use namada_core::types::hash::Hash;
+use namada_core::types::ibc::IbcEvent;
 use namada_core::types::key::common;
 use namada_core::types::storage::{
     BlockHeight, BlockResults, Epoch, Key, PrefixValue,
 };
+use namada_core::types::time::DateTimeUtc;
 use namada_core::types::token::{
     Amount, DenominatedAmount, Denomination, MaspDenom,
 };
@@ -28,24 +31,29 @@ use namada_governance::utils::{
     compute_proposal_result, ProposalResult, ProposalVotes, Vote,
 };
 use namada_ibc::storage::{
-    ibc_denom_key, ibc_denom_key_prefix, is_ibc_denom_key,
+    ibc_denom_key, ibc_denom_key_prefix, ibc_trace_key, is_ibc_denom_key,
 };
 use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::types::{
     BondsAndUnbondsDetails, CommissionPair, ValidatorMetaData, ValidatorState,
 };
-use namada_state::LastBlock;
+use namada_state::{LastBlock, ValueChunk};
 use namada_tx::data::{ResultCode, TxResult};
 use serde::Serialize;
 
 use crate::args::InputAmount;
 use crate::control_flow::time;
 use crate::error::{EncodingError, Error, QueryError, TxSubmitError};
-use crate::events::Event;
+use crate::events::log::{EventLogPage, EventLogQuery};
+use crate::events::mempool::MempoolRejection;
+use crate::events::{Event, EventLevel, EventType};
 use crate::internal_macros::echo_error;
 use crate::io::Io;
-use crate::queries::vp::pos::EnrichedBondsAndUnbondsDetails;
-use crate::queries::{Client, RPC};
+use crate::queries::vp::pos::{
+    EnrichedBondsAndUnbondsDetails, EnrichedStakingOverview,
+};
+use crate::queries::vp::token::{Balances, EpochFlows};
+use crate::queries::{ChainTiming, Client, GasEstimate, TxEvents, RPC};
 use crate::tendermint::block::Height;
 use crate::tendermint::merkle::proof::ProofOps;
 use crate::tendermint_rpc::error::Error as TError;
@@ -62,6 +70,10 @@ pub async fn query_tx_status(
     status: TxEventQuery<'_>,
     deadline: time::Instant,
 ) -> Result<Event, Error> {
+    let _progress = context.io().progress(
+        format!("Waiting for the transaction to be {}", status.event_type()),
+        None,
+    );
     time::Sleep {
         strategy: time::LinearBackoff {
             delta: time::Duration::from_secs(1),
@@ -125,6 +137,14 @@ pub async fn query_native_token<C: crate::queries::Client + Sync>(
     convert_response::<C, _>(RPC.shell().native_token(client).await)
 }
 
+/// Query the statistics of the last block times and the minimum start of the
+/// next epoch
+pub async fn query_chain_timing<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<ChainTiming, error::Error> {
+    convert_response::<C, _>(RPC.shell().chain_timing(client).await)
+}
+
 /// Query the epoch of the given block height, if it exists.
 /// Will return none if the input block height is greater than
 /// the latest committed block height.
@@ -144,6 +164,64 @@ pub async fn query_block<C: crate::queries::Client + Sync>(
     convert_response::<C, _>(RPC.shell().last_block(client).await)
 }
 
+/// Query the block committed at the given height. Returns `None` if the
+/// height is past the last committed block.
+pub async fn query_block_at_height<C: crate::queries::Client + Sync>(
+    client: &C,
+    height: BlockHeight,
+) -> Result<Option<LastBlock>, error::Error> {
+    match query_block(client).await? {
+        Some(last_block) if height <= last_block.height => {}
+        _ => return Ok(None),
+    }
+    let tm_height = Height::try_from(height.0)
+        .map_err(|err| Error::Other(err.to_string()))?;
+    let response = client
+        .block(tm_height)
+        .await
+        .map_err(|err| Error::from(QueryError::General(err.to_string())))?;
+    let hash = storage::BlockHash::try_from(response.block_id.hash.as_bytes())
+        .map_err(|err| Error::Other(err.to_string()))?;
+    let time = DateTimeUtc::try_from(response.block.header.time)
+        .map_err(|err| Error::Other(err.to_string()))?;
+    Ok(Some(LastBlock { height, hash, time }))
+}
+
+/// The gas used by a block and the gas limit of the blocks
+#[derive(Clone, Copy, Debug)]
+pub struct BlockGas {
+    /// The gas used by the transactions of the block
+    pub gas_used: u64,
+    /// The maximum gas that the transactions of a block may use
+    pub max_block_gas: u64,
+}
+
+/// Query the gas used by the block committed at the given height, or by the
+/// last committed block
+pub async fn query_block_gas<C: crate::queries::Client + Sync>(
+    client: &C,
+    height: Option<BlockHeight>,
+) -> Result<BlockGas, error::Error> {
+    let gas_used_key = namada_parameters::storage::get_block_gas_used_key();
+    let gas_used =
+        match query_storage_value_bytes(client, &gas_used_key, height, false)
+            .await?
+        {
+            (Some(bytes), _) => u64::try_from_slice(&bytes).map_err(|err| {
+                Error::from(EncodingError::Decoding(err.to_string()))
+            })?,
+            // No gas is recorded before the first block with transactions
+            (None, _) => 0,
+        };
+    let max_block_gas_key = namada_parameters::storage::get_max_block_gas_key();
+    let max_block_gas =
+        query_storage_value::<C, u64>(client, &max_block_gas_key).await?;
+    Ok(BlockGas {
+        gas_used,
+        max_block_gas,
+    })
+}
+
 /// A helper to unwrap client's response. Will shut down process on error.
 fn unwrap_client_response<C: crate::queries::Client, T>(
     response: Result<T, C::Error>,
@@ -180,6 +258,41 @@ pub async fn get_token_balance<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Query the balances of all the owners in all the tokens in a single query.
+/// The owners without a balance have a zero balance.
+pub async fn get_token_balances<C: crate::queries::Client + Sync>(
+    client: &C,
+    tokens: BTreeSet<Address>,
+    owners: BTreeSet<Address>,
+) -> Result<Balances, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .token()
+            .balances(
+                client,
+                Some((tokens, owners).serialize_to_vec()),
+                None,
+                false,
+            )
+            .await,
+    )
+    .map(|response| response.data)
+}
+
+/// Query the vesting schedule of the owner's balance of a token, if any
+pub async fn get_vesting_schedule<C: crate::queries::Client + Sync>(
+    client: &C,
+    token: &Address,
+    owner: &Address,
+) -> Result<Option<token::VestingSchedule>, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .token()
+            .vesting_schedule(client, token, owner)
+            .await,
+    )
+}
+
 /// Query token total supply;
 pub async fn get_token_total_supply<C: crate::queries::Client + Sync>(
     client: &C,
@@ -444,6 +557,23 @@ where
     })
 }
 
+/// Query a chunk of at most `len` bytes starting at `offset` of the last
+/// committed value under the given storage key, to read large values piece by
+/// piece. The chunk length is capped by the node at
+/// [`crate::queries::MAX_VALUE_CHUNK_LEN`].
+pub async fn query_storage_value_chunk<C: crate::queries::Client + Sync>(
+    client: &C,
+    key: &storage::Key,
+    offset: u64,
+    len: u64,
+) -> Result<Option<ValueChunk>, Error> {
+    convert_response::<C, _>(
+        RPC.shell()
+            .storage_value_chunk(client, key, &offset, &len)
+            .await,
+    )
+}
+
 /// Query to check if the given storage key exists.
 pub async fn query_has_storage_key<C: crate::queries::Client + Sync>(
     client: &C,
@@ -513,6 +643,39 @@ pub async fn query_tx_events<C: crate::queries::Client + Sync>(
     }
 }
 
+/// Query a page of the events retained by the event log of the node. The
+/// events are returned from the oldest, and the following pages can be
+/// fetched by setting [`EventLogQuery::after`] to the `next` cursor of the
+/// page.
+pub async fn query_event_log<C: crate::queries::Client + Sync>(
+    client: &C,
+    query: &EventLogQuery,
+) -> Result<EventLogPage, Error> {
+    let (data, height, prove) = (Some(query.serialize_to_vec()), None, false);
+    convert_response::<C, _>(
+        RPC.shell().event_log(client, data, height, prove).await,
+    )
+    .map(|response| response.data)
+}
+
+/// Query the applied transactions with the given memo that are still in the
+/// event log of the node.
+pub async fn query_applied_txs_with_memo<C: crate::queries::Client + Sync>(
+    client: &C,
+    memo: &[u8],
+) -> Result<Vec<Event>, Error> {
+    let memo = data_encoding::HEXUPPER.encode(memo);
+    convert_response::<C, _>(RPC.shell().applied_with_memo(client, &memo).await)
+}
+
+/// Query the txs recently rejected by the mempool validation of the node,
+/// from the most recent.
+pub async fn query_mempool_rejections<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<Vec<MempoolRejection>, Error> {
+    convert_response::<C, _>(RPC.shell().mempool_rejections(client).await)
+}
+
 /// Dry run a transaction
 pub async fn dry_run_tx<N: Namada>(
     context: &N,
@@ -542,6 +705,18 @@ pub async fn dry_run_tx<N: Namada>(
     Ok(result)
 }
 
+/// Estimate the gas limit of a transaction from its simulated execution
+pub async fn estimate_gas<C: crate::queries::Client + Sync>(
+    client: &C,
+    tx_bytes: Vec<u8>,
+) -> Result<GasEstimate, Error> {
+    let (data, height, prove) = (Some(tx_bytes), None, false);
+    convert_response::<C, _>(
+        RPC.shell().estimate_gas(client, data, height, prove).await,
+    )
+    .map(|response| response.data)
+}
+
 /// Data needed for broadcasting a tx and
 /// monitoring its progress on chain
 ///
@@ -578,8 +753,25 @@ pub struct TxResponse {
     pub hash: String,
     /// Response code
     pub code: ResultCode,
-    /// Gas used. If there's an `inner_tx`, its gas is equal to this value.
-    pub gas_used: String,
+    /// Gas used, in whole units. If there's an `inner_tx`, its gas is equal
+    /// to this value.
+    pub gas_used: u64,
+}
+
+/// Errors of decoding a [`TxResponse`] from the attributes of a tx event
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum TxResponseError {
+    /// A required attribute is not present in the event
+    #[error("Field \"{0}\" not present in event")]
+    MissingField(&'static str),
+    /// An attribute of the event couldn't be parsed
+    #[error("Invalid field \"{field}\" in event: {error}")]
+    InvalidField {
+        /// The name of the attribute
+        field: &'static str,
+        /// The parsing error
+        error: String,
+    },
 }
 
 /// Determines a result of an inner tx from [`TxResponse::inner_tx_result`].
@@ -593,56 +785,49 @@ pub enum InnerTxResult<'a> {
 }
 
 impl TryFrom<Event> for TxResponse {
-    type Error = String;
+    type Error = TxResponseError;
 
     fn try_from(event: Event) -> Result<Self, Self::Error> {
-        fn missing_field_err(field: &str) -> String {
-            format!("Field \"{field}\" not present in event")
-        }
+        Self::from_attributes(|key| event.get(key).map(String::as_str))
+    }
+}
 
-        let inner_tx = event
-            .get("inner_tx")
-            .map(|s| TxResult::from_str(s).unwrap());
-        let hash = event
-            .get("hash")
-            .ok_or_else(|| missing_field_err("hash"))?
-            .clone();
-        let info = event
-            .get("info")
-            .ok_or_else(|| missing_field_err("info"))?
-            .clone();
-        let log = event
-            .get("log")
-            .ok_or_else(|| missing_field_err("log"))?
-            .clone();
-        let height = BlockHeight::from_str(
-            event
-                .get("height")
-                .ok_or_else(|| missing_field_err("height"))?,
-        )
-        .map_err(|e| e.to_string())?;
-        let code = ResultCode::from_str(
-            event.get("code").ok_or_else(|| missing_field_err("code"))?,
-        )
-        .map_err(|e| e.to_string())?;
-        let gas_used = event
-            .get("gas_used")
-            .ok_or_else(|| missing_field_err("gas_used"))?
-            .clone();
+impl TxResponse {
+    /// Decode a [`TxResponse`] from the attributes of a tx event, looked up
+    /// with the given function
+    pub fn from_attributes<'a>(
+        get: impl Fn(&str) -> Option<&'a str>,
+    ) -> Result<Self, TxResponseError> {
+        let field = |field: &'static str| {
+            get(field).ok_or(TxResponseError::MissingField(field))
+        };
+        fn parse<T: FromStr>(
+            field: &'static str,
+            value: &str,
+        ) -> Result<T, TxResponseError>
+        where
+            T::Err: std::fmt::Display,
+        {
+            T::from_str(value).map_err(|err| TxResponseError::InvalidField {
+                field,
+                error: err.to_string(),
+            })
+        }
 
+        let inner_tx = get("inner_tx")
+            .map(|inner_tx| parse::<TxResult>("inner_tx", inner_tx))
+            .transpose()?;
         Ok(TxResponse {
             inner_tx,
-            info,
-            hash,
-            log,
-            height,
-            code,
-            gas_used,
+            info: field("info")?.to_string(),
+            log: field("log")?.to_string(),
+            height: parse("height", field("height")?)?,
+            hash: field("hash")?.to_string(),
+            code: parse("code", field("code")?)?,
+            gas_used: parse("gas_used", field("gas_used")?)?,
         })
     }
-}
 
-impl TxResponse {
     /// Convert an [`Event`] to a [`TxResponse`], or error out.
     pub fn from_event(event: Event) -> Self {
         event.try_into().unwrap_or_else(|err| {
@@ -650,6 +835,21 @@ impl TxResponse {
         })
     }
 
+    /// The established addresses initialized by the inner tx, if any
+    pub fn initialized_accounts(&self) -> &[Address] {
+        self.inner_tx
+            .as_ref()
+            .map(|inner| inner.initialized_accounts.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// The IBC events emitted by the inner tx, if any
+    pub fn ibc_events(&self) -> impl Iterator<Item = &IbcEvent> {
+        self.inner_tx
+            .iter()
+            .flat_map(|inner| inner.ibc_events.iter())
+    }
+
     /// Check the result of the inner tx. This should not be used with wrapper
     /// txs.
     pub fn inner_tx_result(&self) -> InnerTxResult<'_> {
@@ -665,6 +865,131 @@ impl TxResponse {
     }
 }
 
+impl std::fmt::Display for TxResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Transaction {} at height {} with result code {} ({:?}). Used {} \
+             gas.",
+            self.hash, self.height, self.code, self.code, self.gas_used
+        )?;
+        if let Some(inner) = &self.inner_tx {
+            if !inner.is_accepted() {
+                let rejected_vps: Vec<_> = inner
+                    .vps_result
+                    .rejected_vps
+                    .iter()
+                    .map(Address::encode)
+                    .collect();
+                write!(f, "\nRejected by VPs: {}.", rejected_vps.join(", "))?;
+            }
+            let accounts = self.initialized_accounts();
+            if !accounts.is_empty() {
+                let accounts: Vec<_> =
+                    accounts.iter().map(Address::encode).collect();
+                write!(f, "\nInitialized accounts: {}.", accounts.join(", "))?;
+            }
+            let ibc_events = self.ibc_events().count();
+            if ibc_events != 0 {
+                write!(f, "\nEmitted {ibc_events} IBC events.")?;
+            }
+        }
+        if !self.info.is_empty() {
+            write!(f, "\nInfo: {}", self.info)?;
+        }
+        if !self.log.is_empty() {
+            write!(f, "\nLog: {}", self.log)?;
+        }
+        Ok(())
+    }
+}
+
+/// Whether a tx found by its hash is a wrapper or an inner tx
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum TxHashKind {
+    /// The hash of a wrapper tx
+    Wrapper,
+    /// The hash of an inner (decrypted) or a protocol tx
+    Inner,
+}
+
+/// The result and the events of a tx found by its hash
+#[derive(Debug)]
+pub struct IndexedTxEvents {
+    /// Whether the hash is of a wrapper or of an inner tx
+    pub kind: TxHashKind,
+    /// The decoded result of the tx
+    pub response: TxResponse,
+    /// All the events emitted for the tx
+    pub events: Vec<Event>,
+}
+
+/// Query the result and the events of the tx with the given hash, using the
+/// node's index of tx hashes to block heights. Returns `None` if the tx
+/// hasn't been included in a block.
+pub async fn query_indexed_tx_events<C: crate::queries::Client + Sync>(
+    client: &C,
+    tx_hash: &Hash,
+) -> Result<Option<IndexedTxEvents>, Error> {
+    let Some(TxEvents { height, mut events }) =
+        convert_response::<C, _>(RPC.shell().tx_events(client, tx_hash).await)?
+    else {
+        return Ok(None);
+    };
+    if events.is_empty() {
+        // The events are no longer in the event log of the node, so get them
+        // from the results of the block that included the tx
+        let hash = tx_hash.to_string();
+        events = client
+            .block_results(height.0 as u32)
+            .await
+            .map_err(|e| Error::from(QueryError::General(e.to_string())))?
+            .end_block_events
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|event| {
+                event
+                    .attributes
+                    .iter()
+                    .any(|attr| attr.key == "hash" && attr.value == hash)
+            })
+            .map(|event| Event {
+                event_type: EventType::from_str(&event.kind)
+                    .unwrap_or(EventType::Ibc(event.kind)),
+                level: EventLevel::Tx,
+                attributes: event
+                    .attributes
+                    .into_iter()
+                    .map(|attr| (attr.key, attr.value))
+                    .collect(),
+            })
+            .collect();
+    }
+    let tx_event = events
+        .iter()
+        .find(|event| {
+            matches!(event.event_type, EventType::Accepted | EventType::Applied)
+        })
+        .ok_or_else(|| {
+            Error::from(QueryError::General(format!(
+                "Unable to find the events of the tx {tx_hash} at height \
+                 {height}"
+            )))
+        })?;
+    let kind = if tx_event.event_type == EventType::Accepted {
+        TxHashKind::Wrapper
+    } else {
+        TxHashKind::Inner
+    };
+    let response = TxResponse::try_from(tx_event.clone())
+        .map_err(|e| Error::from(EncodingError::Decoding(e)))?;
+    Ok(Some(IndexedTxEvents {
+        kind,
+        response,
+        events,
+    }))
+}
+
 /// Lookup the full response accompanying the specified transaction event
 // TODO: maybe remove this in favor of `query_tx_status`
 pub async fn query_tx_response<C: crate::queries::Client + Sync>(
@@ -721,28 +1046,8 @@ pub async fn query_tx_response<C: crate::queries::Client + Sync>(
         .map(|tag| (tag.key.as_ref(), tag.value.as_ref()))
         .collect();
     // Summarize the transaction results that we were searching for
-    let inner_tx = event_map
-        .get("inner_tx")
-        .map(|s| {
-            TxResult::from_str(s).map_err(|_| {
-                TError::parse("Error parsing TxResult".to_string())
-            })
-        })
-        .transpose()?;
-    let code = ResultCode::from_str(event_map["code"])
-        .map_err(|_| TError::parse("Error parsing ResultCode".to_string()))?;
-    let height = BlockHeight::from_str(event_map["height"])
-        .map_err(|_| TError::parse("Error parsing BlockHeight".to_string()))?;
-    let result = TxResponse {
-        inner_tx,
-        info: event_map["info"].to_string(),
-        log: event_map["log"].to_string(),
-        height,
-        hash: event_map["hash"].to_string(),
-        code,
-        gas_used: event_map["gas_used"].to_string(),
-    };
-    Ok(result)
+    TxResponse::from_attributes(|key| event_map.get(key).copied())
+        .map_err(|err| TError::parse(err.to_string()))
 }
 
 /// Get the PoS parameters
@@ -971,6 +1276,10 @@ pub async fn query_proposal_result<C: crate::queries::Client + Sync>(
                 .await
                 .unwrap_or_default();
             let tally_type = proposal.get_tally_type(is_author_pgf_steward);
+            let tally_params = query_governance_parameters(client)
+                .await
+                .tally_params
+                .get(&proposal.r#type);
             let total_staked_token =
                 get_total_staked_tokens(client, tally_epoch)
                     .await
@@ -1018,6 +1327,7 @@ pub async fn query_proposal_result<C: crate::queries::Client + Sync>(
                 proposal_votes,
                 total_staked_token,
                 tally_type,
+                tally_params,
             )
         }
     };
@@ -1118,6 +1428,26 @@ pub async fn query_proposal_votes<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Get the vote proxy of a delegator, if any
+pub async fn query_vote_proxy<C: crate::queries::Client + Sync>(
+    client: &C,
+    delegator: &Address,
+) -> Result<Option<Address>, error::Error> {
+    convert_response::<C, Option<Address>>(
+        RPC.vp().gov().vote_proxy(client, delegator).await,
+    )
+}
+
+/// Get the delegators that have set the given address as their vote proxy
+pub async fn query_proxied_delegators<C: crate::queries::Client + Sync>(
+    client: &C,
+    proxy: &Address,
+) -> Result<BTreeSet<Address>, error::Error> {
+    convert_response::<C, BTreeSet<Address>>(
+        RPC.vp().gov().proxied_delegators(client, proxy).await,
+    )
+}
+
 /// Get the bond amount at the given epoch
 pub async fn get_bond_amount_at<C: crate::queries::Client + Sync>(
     client: &C,
@@ -1171,6 +1501,44 @@ pub async fn enriched_bonds_and_unbonds<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Get bonds and unbonds as they were at the given past `epoch`, enriched with
+/// extra information calculated from the data as of that epoch. The unbonds
+/// withdrawn since are not included.
+pub async fn enriched_bonds_and_unbonds_at<C: crate::queries::Client + Sync>(
+    client: &C,
+    epoch: Epoch,
+    source: &Option<Address>,
+    validator: &Option<Address>,
+) -> Result<EnrichedBondsAndUnbondsDetails, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp()
+            .pos()
+            .enriched_bonds_and_unbonds_at(client, epoch, source, validator)
+            .await,
+    )
+}
+
+/// Get the staking overview of the given owner in a single query, i.e. its
+/// bonds, unbonds and pending rewards with the slashes applied to them and the
+/// states of the validators, enriched with extra information calculated from
+/// the data.
+pub async fn query_staking_overview<C: crate::queries::Client + Sync>(
+    client: &C,
+    owner: &Address,
+) -> Result<EnrichedStakingOverview, error::Error> {
+    convert_response::<C, _>(
+        RPC.vp().pos().enriched_staking_overview(client, owner).await,
+    )
+}
+
+/// Get the fee, burn and treasury flows of all the tokens in the given epoch
+pub async fn query_epoch_flows<C: crate::queries::Client + Sync>(
+    client: &C,
+    epoch: Epoch,
+) -> Result<EpochFlows, error::Error> {
+    convert_response::<C, _>(RPC.vp().token().epoch_flows(client, &epoch).await)
+}
+
 /// Get the correct representation of the amount given the token type.
 pub async fn validate_amount<N: Namada>(
     context: &N,
@@ -1272,7 +1640,7 @@ pub async fn wait_until_node_is_synched(
                 ControlFlow::Continue(())
             }
             Err(e) => ControlFlow::Break(Err(Error::Query(
-                QueryError::General(echo_error!(
+                QueryError::Connection(echo_error!(
                     io,
                     "Failed to query node status with error: {e}"
                 )),
@@ -1329,6 +1697,23 @@ pub async fn format_denominated_amount(
         .to_string()
 }
 
+/// Look up the IBC denom with its full trace path by the hash of the denom.
+pub async fn query_ibc_trace<C: crate::queries::Client + Sync>(
+    client: &C,
+    token_hash: impl AsRef<str>,
+) -> Result<Option<String>, error::Error> {
+    let key = ibc_trace_key(token_hash);
+    let (value, _) =
+        query_storage_value_bytes(client, &key, None, false).await?;
+    value
+        .map(|bytes| {
+            String::try_from_slice(&bytes).map_err(|err| {
+                Error::from(EncodingError::Decoding(err.to_string()))
+            })
+        })
+        .transpose()
+}
+
 /// Look up the IBC denomination from a IbcToken.
 pub async fn query_ibc_denom<N: Namada>(
     context: &N,
@@ -1342,6 +1727,11 @@ pub async fn query_ibc_denom<N: Namada>(
         _ => return token.as_ref().to_string(),
     };
 
+    if let Ok(Some(ibc_denom)) = query_ibc_trace(context.client(), &hash).await
+    {
+        return ibc_denom;
+    }
+
     if let Some(owner) = owner {
         let ibc_denom_key = ibc_denom_key(owner.to_string(), &hash);
         if let Ok(ibc_denom) =
@@ -1368,3 +1758,56 @@ pub async fn query_ibc_denom<N: Namada>(
 
     token.as_ref().to_string()
 }
+
+#[cfg(test)]
+mod test {
+    use namada_core::types::address::testing::established_address_1;
+
+    use super::*;
+
+    /// Test decoding a tx response from the attributes of an event
+    #[test]
+    fn test_tx_response_from_event() {
+        let inner_tx = TxResult {
+            gas_used: 10_000.into(),
+            initialized_accounts: vec![established_address_1()],
+            ..Default::default()
+        };
+        let mut event = Event {
+            event_type: EventType::Applied,
+            level: EventLevel::Tx,
+            attributes: HashMap::from_iter(
+                [
+                    ("hash", "DEADBEEF".to_string()),
+                    ("info", "".to_string()),
+                    ("log", "".to_string()),
+                    ("height", "10".to_string()),
+                    ("code", "0".to_string()),
+                    ("gas_used", "10".to_string()),
+                    ("inner_tx", serde_json::to_string(&inner_tx).unwrap()),
+                ]
+                .map(|(key, value)| (key.to_string(), value)),
+            ),
+        };
+        let resp = TxResponse::try_from(event.clone()).unwrap();
+        assert_eq!(resp.code, ResultCode::Ok);
+        assert_eq!(resp.height, BlockHeight(10));
+        assert_eq!(resp.gas_used, 10);
+        assert_eq!(resp.initialized_accounts(), &[established_address_1()]);
+        assert!(matches!(resp.inner_tx_result(), InnerTxResult::Success(_)));
+
+        event["gas_used"] = "ten".to_string();
+        assert!(matches!(
+            TxResponse::try_from(event.clone()),
+            Err(TxResponseError::InvalidField {
+                field: "gas_used",
+                ..
+            })
+        ));
+        event.attributes.remove("height");
+        assert_eq!(
+            TxResponse::try_from(event).unwrap_err(),
+            TxResponseError::MissingField("height")
+        );
+    }
+}
@@ -10,7 +10,10 @@ use masp_primitives::asset_type::AssetType;
 use masp_primitives::transaction::components::sapling::fees::{
     InputView, OutputView,
 };
-use namada_account::{AccountPublicKeysMap, InitAccount, UpdateAccount};
+use namada_account::{
+    AccountPublicKeysMap, InitAccount, UpdateAccount, UpdateAccountKeys,
+};
+use namada_core::ledger::eth_bridge::nuts::ConvertNuts;
 use namada_core::types::address::{
     Address, ImplicitAddress, InternalAddress, MASP,
 };
@@ -25,20 +28,22 @@ use namada_core::types::token::Transfer;
 // use namada_core::types::storage::Key;
 use namada_core::types::token::{Amount, DenominatedAmount, MaspDenom};
 use namada_governance::storage::proposal::{
-    InitProposalData, ProposalType, VoteProposalData,
+    InitProposalData, ProposalType, VoteProposalData, VoteProxyData,
 };
 use namada_governance::storage::vote::ProposalVote;
 use namada_parameters::storage as parameter_storage;
 use namada_token::storage_key::balance_key;
 use namada_tx::data::pgf::UpdateStewardCommission;
 use namada_tx::data::pos::BecomeValidator;
-use namada_tx::data::{pos, Fee};
+use namada_tx::data::scheduled::ScheduledTx;
+use namada_tx::data::{pos, Fee, TxType, WrapperTx};
 use namada_tx::{MaspBuilder, Section, Tx};
 use prost::Message;
 use rand::rngs::OsRng;
 use serde::{Deserialize, Serialize};
 use sha2::Digest;
 use tokio::sync::RwLock;
+use zeroize::Zeroizing;
 
 use super::masp::{ShieldedContext, ShieldedTransfer};
 use crate::args::SdkTypes;
@@ -48,23 +53,25 @@ use crate::ibc::primitives::proto::Any;
 use crate::io::*;
 use crate::rpc::validate_amount;
 use crate::tx::{
-    TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
-    TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-    TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
-    TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
-    TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-    TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
-    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
-    TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
-    VP_USER_WASM,
+    TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_PAUSE_VOTE_WASM,
+    TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
+    TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+    TX_CLAIM_REWARDS_WASM, TX_CONVERT_NUTS_WASM, TX_DEACTIVATE_VALIDATOR_WASM,
+    TX_IBC_WASM, TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL,
+    TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+    TX_REVEAL_PK, TX_SCHEDULE_WASM, TX_SET_AUTO_COMPOUND_WASM,
+    TX_TRANSFER_WASM, TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM,
+    TX_UPDATE_ACCOUNT_KEYS_WASM, TX_UPDATE_ACCOUNT_WASM,
+    TX_UPDATE_STEWARD_COMMISSION, TX_UPDATE_VOTE_PROXY,
+    TX_VESTING_TRANSFER_WASM, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM, VP_USER_WASM,
 };
 use crate::types::eth_bridge_pool::PendingTransfer;
 pub use crate::wallet::store::AddressVpType;
-use crate::wallet::{Wallet, WalletIo};
+use crate::wallet::{FindKeyError, Wallet, WalletIo};
 use crate::{args, display_line, rpc, MaybeSend, Namada};
 
 /// A structure holding the signing data to craft a transaction
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct SigningTxData {
     /// The address owning the transaction
     pub owner: Option<Address>,
@@ -78,6 +85,83 @@ pub struct SigningTxData {
     pub fee_payer: common::PublicKey,
 }
 
+/// An unsigned transaction together with the data needed to sign it on a
+/// machine that has no access to a node
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OfflineTx {
+    /// The transaction to be signed
+    pub tx: Tx,
+    /// The keys that still have to sign the transaction
+    pub signing_data: SigningTxData,
+}
+
+impl OfflineTx {
+    /// Sign the inner transaction with the keys of the signing data found in
+    /// the wallet and, if the fee payer's key is found too, the wrapper. The
+    /// keys that signed are removed from the signing data, so that a
+    /// partially signed transaction can be passed on to the holders of the
+    /// other keys. The wrapper is only signed once all the inner signatures
+    /// have been added, as it commits to them. Returns `true` iff the wrapper
+    /// was signed, at which point the transaction is ready to be broadcast.
+    pub fn sign<U: WalletIo>(
+        &mut self,
+        wallet: &mut Wallet<U>,
+        password: Option<Zeroizing<String>>,
+    ) -> Result<bool, Error> {
+        let mut find_key = |public_key: &common::PublicKey| {
+            match wallet.find_key_by_pk(public_key, password.clone()) {
+                Ok(secret_key) => Ok(Some(secret_key)),
+                // Watch-only keys can't sign
+                Err(FindKeyError::KeyNotFound(_)) => Ok(None),
+                Err(err) => Err(Error::Wallet(format!(
+                    "Unable to load the keypair from the wallet for public \
+                     key {}. Failed with: {}",
+                    public_key, err
+                ))),
+            }
+        };
+
+        if let Some(account_public_keys_map) =
+            &self.signing_data.account_public_keys_map
+        {
+            let mut secret_keys = vec![];
+            let mut remaining_keys = vec![];
+            for public_key in &self.signing_data.public_keys {
+                match find_key(public_key)? {
+                    Some(secret_key) => secret_keys.push(secret_key),
+                    None => remaining_keys.push(public_key.clone()),
+                }
+            }
+            if !secret_keys.is_empty() {
+                self.tx.sign_raw(
+                    secret_keys,
+                    account_public_keys_map.clone(),
+                    self.signing_data.owner.clone(),
+                );
+            }
+            self.signing_data.public_keys = remaining_keys;
+        }
+        // The wrapper signature must cover all the inner signatures
+        if self.has_pending_signatures() {
+            return Ok(false);
+        }
+
+        match find_key(&self.signing_data.fee_payer)? {
+            Some(fee_payer_key) => {
+                self.tx.sign_wrapper(fee_payer_key);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Check if some keys still have to sign the inner transaction
+    pub fn has_pending_signatures(&self) -> bool {
+        self.signing_data.account_public_keys_map.is_some()
+            && !self.signing_data.public_keys.is_empty()
+    }
+}
+
 /// Find the public key for the given address and try to load the keypair
 /// for it from the wallet. If the keypair is encrypted but a password is not
 /// supplied, then it is interactively prompted. Errors if the key cannot be
@@ -105,7 +189,7 @@ pub async fn find_pk(
             .await
             .find_public_key_by_pkh(pkh)
             .map_err(|err| {
-                Error::Other(format!(
+                Error::Wallet(format!(
                     "Unable to load the keypair from the wallet for the \
                      implicit address {}. Failed with: {}",
                     addr.encode(),
@@ -130,7 +214,7 @@ pub fn find_key_by_pk<U: WalletIo>(
     wallet
         .find_key_by_pk(public_key, args.password.clone())
         .map_err(|err| {
-            Error::Other(format!(
+            Error::Wallet(format!(
                 "Unable to load the keypair from the wallet for public key \
                  {}. Failed with: {}",
                 public_key, err
@@ -299,6 +383,50 @@ where
     Ok(())
 }
 
+/// Replace the gas limit of a wrapper tx with the estimate of the node from a
+/// simulated execution of a signed copy of the tx. The estimate covers the
+/// fee payment and the signatures checks, and fails if the tx is rejected.
+pub async fn estimate_gas_limit<D, F>(
+    context: &impl Namada,
+    args: &args::Tx,
+    tx: &mut Tx,
+    signing_data: SigningTxData,
+    sign: impl Fn(Tx, common::PublicKey, HashSet<Signable>, D) -> F,
+    user_data: D,
+) -> Result<(), Error>
+where
+    D: Clone + MaybeSend,
+    F: std::future::Future<Output = Result<Tx, Error>>,
+{
+    let Some(wrapper) = tx.header().wrapper() else {
+        // Only a wrapper tx has a gas limit
+        return Ok(());
+    };
+    let mut signed_tx = tx.clone();
+    sign_tx(
+        context.wallet_lock(),
+        args,
+        &mut signed_tx,
+        signing_data,
+        sign,
+        user_data,
+    )
+    .await?;
+    let estimate =
+        rpc::estimate_gas(context.client(), signed_tx.to_bytes()).await?;
+    display_line!(
+        context.io(),
+        "Estimated gas: {}, using a gas limit of {}",
+        estimate.gas_used,
+        u64::from(estimate.gas_limit)
+    );
+    tx.update_header(TxType::Wrapper(Box::new(WrapperTx {
+        gas_limit: estimate.gas_limit,
+        ..wrapper
+    })));
+    Ok(())
+}
+
 /// Return the necessary data regarding an account to be able to generate a
 /// multisignature section
 pub async fn aux_signing_data(
@@ -910,6 +1038,9 @@ impl<'a> Display for LedgerProposalType<'a> {
             }
             ProposalType::PGFSteward(_) => write!(f, "PGF Steward"),
             ProposalType::PGFPayment(_) => write!(f, "PGF Payment"),
+            ProposalType::IbcClientRecovery(_) => {
+                write!(f, "IBC Client Recovery")
+            }
         }
     }
 }
@@ -1139,6 +1270,29 @@ pub async fn to_ledger_vector(
             tv.output_expert
                 .push(format!("Delegation : {}", delegation));
         }
+    } else if code_sec.tag == Some(TX_UPDATE_VOTE_PROXY.to_string()) {
+        let vote_proxy = VoteProxyData::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Update_Vote_Proxy_0".to_string();
+
+        tv.output.push("Type : Update Vote Proxy".to_string());
+        tv.output
+            .push(format!("Delegator : {}", vote_proxy.delegator));
+        if let Some(proxy) = vote_proxy.proxy.as_ref() {
+            tv.output.push(format!("Proxy : {}", proxy));
+        }
+
+        tv.output_expert
+            .push(format!("Delegator : {}", vote_proxy.delegator));
+        if let Some(proxy) = vote_proxy.proxy.as_ref() {
+            tv.output_expert.push(format!("Proxy : {}", proxy));
+        }
     } else if code_sec.tag == Some(TX_REVEAL_PK.to_string()) {
         let public_key = common::PublicKey::try_from_slice(
             &tx.data()
@@ -1222,6 +1376,39 @@ pub async fn to_ledger_vector(
                 HEXLOWER.encode(&extra_code_hash.0)
             )]);
         }
+    } else if code_sec.tag == Some(TX_UPDATE_ACCOUNT_KEYS_WASM.to_string()) {
+        let update_keys = UpdateAccountKeys::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Update_Account_Keys_0".to_string();
+        tv.output.extend(vec![
+            format!("Type : Update Account Keys"),
+            format!("Address : {}", update_keys.addr),
+        ]);
+        tv.output.extend(
+            update_keys
+                .public_keys
+                .iter()
+                .map(|k| format!("Public key : {}", k)),
+        );
+        tv.output
+            .push(format!("Threshold : {}", update_keys.threshold));
+
+        tv.output_expert
+            .extend(vec![format!("Address : {}", update_keys.addr)]);
+        tv.output_expert.extend(
+            update_keys
+                .public_keys
+                .iter()
+                .map(|k| format!("Public key : {}", k)),
+        );
+        tv.output_expert
+            .push(format!("Threshold : {}", update_keys.threshold));
     } else if code_sec.tag == Some(TX_TRANSFER_WASM.to_string()) {
         let transfer = Transfer::try_from_slice(
             &tx.data()
@@ -1276,6 +1463,82 @@ pub async fn to_ledger_vector(
             &asset_types,
         )
         .await;
+    } else if code_sec.tag == Some(TX_VESTING_TRANSFER_WASM.to_string()) {
+        let transfer = token::VestingTransfer::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Vesting_Transfer_0".to_string();
+
+        tv.output.extend(vec![
+            format!("Type : Vesting Transfer"),
+            format!("Sender : {}", transfer.source),
+            format!("Destination : {}", transfer.target),
+        ]);
+        make_ledger_amount_addr(
+            &tokens,
+            &mut tv.output,
+            transfer.amount,
+            &transfer.token,
+            "",
+        );
+        tv.output.extend(vec![
+            format!("Start epoch : {}", transfer.start),
+            format!("End epoch : {}", transfer.end),
+        ]);
+
+        tv.output_expert.extend(vec![
+            format!("Sender : {}", transfer.source),
+            format!("Destination : {}", transfer.target),
+        ]);
+        make_ledger_amount_addr(
+            &tokens,
+            &mut tv.output_expert,
+            transfer.amount,
+            &transfer.token,
+            "",
+        );
+        tv.output_expert.extend(vec![
+            format!("Start epoch : {}", transfer.start),
+            format!("End epoch : {}", transfer.end),
+        ]);
+    } else if code_sec.tag == Some(TX_SCHEDULE_WASM.to_string()) {
+        let scheduled = ScheduledTx::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Schedule_Tx_0".to_string();
+
+        tv.output.extend(vec![
+            format!("Type : Schedule Tx"),
+            format!("Payer : {}", scheduled.payer),
+            format!("Epoch : {}", scheduled.epoch),
+            format!("Scheduled tx : {}", scheduled.hash()),
+            format!("Gas limit : {}", u64::from(scheduled.gas_limit)),
+            format!(
+                "Fee : {}",
+                to_ledger_decimal(&scheduled.fee.to_string_native())
+            ),
+        ]);
+
+        tv.output_expert.extend(vec![
+            format!("Payer : {}", scheduled.payer),
+            format!("Epoch : {}", scheduled.epoch),
+            format!("Scheduled tx : {}", scheduled.hash()),
+            format!("Gas limit : {}", u64::from(scheduled.gas_limit)),
+            format!(
+                "Fee : {}",
+                to_ledger_decimal(&scheduled.fee.to_string_native())
+            ),
+        ]);
     } else if code_sec.tag == Some(TX_IBC_WASM.to_string()) {
         let any_msg = Any::decode(
             tx.data()
@@ -1452,6 +1715,33 @@ pub async fn to_ledger_vector(
         }
         tv.output_expert
             .push(format!("Validator : {}", claim.validator));
+    } else if code_sec.tag == Some(TX_SET_AUTO_COMPOUND_WASM.to_string()) {
+        let auto_compound = pos::SetAutoCompound::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Set_Auto_Compound_0".to_string();
+
+        tv.output.push("Type : Set Auto Compound".to_string());
+        if let Some(source) = auto_compound.source.as_ref() {
+            tv.output.push(format!("Source : {}", source));
+        }
+        tv.output
+            .push(format!("Validator : {}", auto_compound.validator));
+        tv.output
+            .push(format!("Enabled : {}", auto_compound.enabled));
+
+        if let Some(source) = auto_compound.source.as_ref() {
+            tv.output_expert.push(format!("Source : {}", source));
+        }
+        tv.output_expert
+            .push(format!("Validator : {}", auto_compound.validator));
+        tv.output_expert
+            .push(format!("Enabled : {}", auto_compound.enabled));
     } else if code_sec.tag == Some(TX_CHANGE_COMMISSION_WASM.to_string()) {
         let commission_change = pos::CommissionChange::try_from_slice(
             &tx.data()
@@ -1575,6 +1865,23 @@ pub async fn to_ledger_vector(
             format!("Validator : {}", address),
         ]);
 
+        tv.output_expert.push(format!("Validator : {}", address));
+    } else if code_sec.tag == Some(TX_BRIDGE_PAUSE_VOTE_WASM.to_string()) {
+        let address = Address::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Bridge_Pause_Vote_0".to_string();
+
+        tv.output.extend(vec![
+            format!("Type : Bridge Pause Vote"),
+            format!("Validator : {}", address),
+        ]);
+
         tv.output_expert.push(format!("Validator : {}", address));
     } else if code_sec.tag == Some(TX_REACTIVATE_VALIDATOR_WASM.to_string()) {
         let address = Address::try_from_slice(
@@ -1698,6 +2005,29 @@ pub async fn to_ledger_vector(
             format!("Gas Token : {}", transfer.gas_fee.token),
             format!("Gas Amount : {}", transfer.gas_fee.amount),
         ]);
+    } else if code_sec.tag == Some(TX_CONVERT_NUTS_WASM.to_string()) {
+        let conversion = ConvertNuts::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Convert_Nuts_0".to_string();
+
+        tv.output.extend(vec![
+            format!("Type : Convert NUTs"),
+            format!("Owner : {}", conversion.owner),
+            format!("Asset : {}", conversion.asset),
+            format!("Amount : {}", conversion.amount),
+        ]);
+
+        tv.output_expert.extend(vec![
+            format!("Owner : {}", conversion.owner),
+            format!("Asset : {}", conversion.asset),
+            format!("Amount : {}", conversion.amount),
+        ]);
     } else {
         tv.name = "Custom_0".to_string();
         tv.output.push("Type : Custom".to_string());
@@ -0,0 +1,357 @@
+//! Decoding of raw storage values into JSON.
+//!
+//! The values under a number of well-known storage keys (token balances,
+//! bonds, governance votes and results, protocol parameters, ...) are
+//! recognized by their key and decoded to their type. Other values can be
+//! decoded by explicitly giving a [`StorageValueType`].
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use borsh::BorshDeserialize;
+use namada_core::types::address::Address;
+use namada_core::types::chain::ProposalBytes;
+use namada_core::types::dec::Dec;
+use namada_core::types::hash::Hash;
+use namada_core::types::parameters::EpochDuration;
+use namada_core::types::storage::Key;
+use namada_core::types::time::DurationSecs;
+use namada_core::types::token;
+use namada_governance::storage::keys as governance_keys;
+use namada_governance::utils::ProposalResult;
+use namada_governance::ProposalVote;
+use namada_parameters::storage as parameter_keys;
+use namada_proof_of_stake::storage_key as pos_keys;
+use namada_token::storage_key as token_keys;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::error::EncodingError;
+
+/// The type of a storage value that can be decoded into JSON
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageValueType {
+    /// A token amount
+    Amount,
+    /// A governance proposal vote
+    ProposalVote,
+    /// The tally of a governance proposal
+    ProposalResult,
+    /// The epoch duration parameter
+    EpochDuration,
+    /// A duration in seconds
+    DurationSecs,
+    /// The max proposal bytes parameter
+    ProposalBytes,
+    /// The minimum gas price of each token
+    GasPrices,
+    /// A list of strings
+    StringList,
+    /// A decimal number
+    Dec,
+    /// A hash
+    Hash,
+    /// An address
+    Address,
+    /// An 8-bit unsigned integer
+    U8,
+    /// A 32-bit unsigned integer
+    U32,
+    /// A 64-bit unsigned integer
+    U64,
+}
+
+impl StorageValueType {
+    /// All the storage value types
+    pub const ALL: [Self; 14] = [
+        Self::Amount,
+        Self::ProposalVote,
+        Self::ProposalResult,
+        Self::EpochDuration,
+        Self::DurationSecs,
+        Self::ProposalBytes,
+        Self::GasPrices,
+        Self::StringList,
+        Self::Dec,
+        Self::Hash,
+        Self::Address,
+        Self::U8,
+        Self::U32,
+        Self::U64,
+    ];
+
+    /// The name of the type
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Amount => "amount",
+            Self::ProposalVote => "proposal-vote",
+            Self::ProposalResult => "proposal-result",
+            Self::EpochDuration => "epoch-duration",
+            Self::DurationSecs => "duration-secs",
+            Self::ProposalBytes => "proposal-bytes",
+            Self::GasPrices => "gas-prices",
+            Self::StringList => "string-list",
+            Self::Dec => "dec",
+            Self::Hash => "hash",
+            Self::Address => "address",
+            Self::U8 => "u8",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+        }
+    }
+
+    /// Decode the given value bytes of this type into JSON
+    pub fn decode(&self, bytes: &[u8]) -> Result<Value, EncodingError> {
+        match self {
+            Self::Amount => to_json::<token::Amount>(bytes),
+            Self::ProposalVote => to_json::<ProposalVote>(bytes),
+            Self::ProposalResult => {
+                let result = decode::<ProposalResult>(bytes)?;
+                Ok(json!({
+                    "result": result.result.to_string(),
+                    "total_voting_power": result.total_voting_power,
+                    "total_yay_power": result.total_yay_power,
+                    "total_nay_power": result.total_nay_power,
+                    "total_abstain_power": result.total_abstain_power,
+                }))
+            }
+            Self::EpochDuration => {
+                let duration = decode::<EpochDuration>(bytes)?;
+                Ok(json!({
+                    "min_num_of_blocks": duration.min_num_of_blocks,
+                    "min_duration": duration.min_duration,
+                }))
+            }
+            Self::DurationSecs => to_json::<DurationSecs>(bytes),
+            Self::ProposalBytes => to_json::<ProposalBytes>(bytes),
+            Self::GasPrices => {
+                to_json::<BTreeMap<Address, token::Amount>>(bytes)
+            }
+            Self::StringList => to_json::<Vec<String>>(bytes),
+            Self::Dec => to_json::<Dec>(bytes),
+            Self::Hash => to_json::<Hash>(bytes),
+            Self::Address => to_json::<Address>(bytes),
+            Self::U8 => to_json::<u8>(bytes),
+            Self::U32 => to_json::<u32>(bytes),
+            Self::U64 => to_json::<u64>(bytes),
+        }
+    }
+}
+
+impl Display for StorageValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for StorageValueType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|ty| ty.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<_> =
+                    Self::ALL.iter().map(|ty| ty.name()).collect();
+                format!(
+                    "Unknown storage value type {s}, expected one of: {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+fn decode<T: BorshDeserialize>(bytes: &[u8]) -> Result<T, EncodingError> {
+    T::try_from_slice(bytes)
+        .map_err(|err| EncodingError::Decoding(err.to_string()))
+}
+
+fn to_json<T: BorshDeserialize + Serialize>(
+    bytes: &[u8],
+) -> Result<Value, EncodingError> {
+    serde_json::to_value(decode::<T>(bytes)?)
+        .map_err(|err| EncodingError::Serde(err.to_string()))
+}
+
+/// The registry of known storage key patterns and the types of their values.
+/// The first matching pattern is used.
+const KEY_PATTERNS: &[(fn(&Key) -> bool, StorageValueType)] = &[
+    (is_token_balance_key, StorageValueType::Amount),
+    (is_minted_balance_key, StorageValueType::Amount),
+    (is_bond_key, StorageValueType::Amount),
+    (is_unbond_key, StorageValueType::Amount),
+    (governance_keys::is_vote_key, StorageValueType::ProposalVote),
+    (is_proposal_result_key, StorageValueType::ProposalResult),
+    (governance_keys::is_counter_key, StorageValueType::U64),
+    (
+        governance_keys::is_start_or_end_epoch_key,
+        StorageValueType::U64,
+    ),
+    (governance_keys::is_grace_epoch_key, StorageValueType::U64),
+    (governance_keys::is_author_key, StorageValueType::Address),
+    (
+        parameter_keys::is_epoch_duration_storage_key,
+        StorageValueType::EpochDuration,
+    ),
+    (
+        parameter_keys::is_max_expected_time_per_block_key,
+        StorageValueType::DurationSecs,
+    ),
+    (
+        parameter_keys::is_tx_whitelist_key,
+        StorageValueType::StringList,
+    ),
+    (
+        parameter_keys::is_vp_whitelist_key,
+        StorageValueType::StringList,
+    ),
+    (parameter_keys::is_implicit_vp_key, StorageValueType::Hash),
+    (
+        parameter_keys::is_epochs_per_year_key,
+        StorageValueType::U64,
+    ),
+    (parameter_keys::is_staked_ratio_key, StorageValueType::Dec),
+    (
+        parameter_keys::is_pos_inflation_amount_key,
+        StorageValueType::Amount,
+    ),
+    (
+        parameter_keys::is_max_proposal_bytes_key,
+        StorageValueType::ProposalBytes,
+    ),
+    (parameter_keys::is_max_tx_bytes_key, StorageValueType::U32),
+    (
+        parameter_keys::is_max_tx_memo_bytes_key,
+        StorageValueType::U32,
+    ),
+    (
+        parameter_keys::is_max_tx_sections_key,
+        StorageValueType::U32,
+    ),
+    (
+        parameter_keys::is_max_tx_code_bytes_key,
+        StorageValueType::U32,
+    ),
+    (is_max_block_gas_key, StorageValueType::U64),
+    (
+        parameter_keys::is_max_scheduled_txs_gas_key,
+        StorageValueType::U64,
+    ),
+    (is_gas_cost_key, StorageValueType::GasPrices),
+    (is_fee_unshielding_limit_key, StorageValueType::U64),
+    (is_max_signatures_key, StorageValueType::U8),
+    (is_validity_predicate_key, StorageValueType::Hash),
+];
+
+fn is_token_balance_key(key: &Key) -> bool {
+    token_keys::is_any_token_balance_key(key).is_some()
+}
+
+fn is_minted_balance_key(key: &Key) -> bool {
+    token_keys::is_any_minted_balance_key(key).is_some()
+}
+
+fn is_bond_key(key: &Key) -> bool {
+    pos_keys::is_bond_key(key).is_some()
+}
+
+fn is_unbond_key(key: &Key) -> bool {
+    pos_keys::is_unbond_key(key).is_some()
+}
+
+fn is_proposal_result_key(key: &Key) -> bool {
+    governance_keys::get_proposal_id(key)
+        .map(|id| key == &governance_keys::get_proposal_result_key(id))
+        .unwrap_or_default()
+}
+
+fn is_max_block_gas_key(key: &Key) -> bool {
+    key == &parameter_keys::get_max_block_gas_key()
+}
+
+fn is_gas_cost_key(key: &Key) -> bool {
+    key == &parameter_keys::get_gas_cost_key()
+}
+
+fn is_fee_unshielding_limit_key(key: &Key) -> bool {
+    key == &parameter_keys::get_fee_unshielding_gas_limit_key()
+        || key == &parameter_keys::get_fee_unshielding_descriptions_limit_key()
+}
+
+fn is_max_signatures_key(key: &Key) -> bool {
+    key == &parameter_keys::get_max_signatures_per_transaction_key()
+}
+
+fn is_validity_predicate_key(key: &Key) -> bool {
+    key.is_validity_predicate().is_some()
+}
+
+/// Find the type of the value stored under the given key, if it matches any
+/// of the known key patterns.
+pub fn value_type_of(key: &Key) -> Option<StorageValueType> {
+    KEY_PATTERNS
+        .iter()
+        .find(|(matches, _)| matches(key))
+        .map(|(_, ty)| *ty)
+}
+
+#[cfg(test)]
+mod test {
+    use borsh_ext::BorshSerializeExt;
+    use namada_core::types::address::nam;
+    use namada_core::types::address::testing::established_address_1;
+
+    use super::*;
+
+    #[test]
+    fn test_value_type_of() {
+        let balance_key =
+            token_keys::balance_key(&nam(), &established_address_1());
+        assert_eq!(value_type_of(&balance_key), Some(StorageValueType::Amount));
+        assert_eq!(
+            value_type_of(&parameter_keys::get_epoch_duration_storage_key()),
+            Some(StorageValueType::EpochDuration)
+        );
+        assert_eq!(
+            value_type_of(&parameter_keys::get_max_tx_bytes_key()),
+            Some(StorageValueType::U32)
+        );
+        assert_eq!(
+            value_type_of(&governance_keys::get_proposal_result_key(0)),
+            Some(StorageValueType::ProposalResult)
+        );
+        assert_eq!(value_type_of(&Key::parse("unknown").unwrap()), None);
+    }
+
+    #[test]
+    fn test_decode_storage_value() {
+        let amount = token::Amount::native_whole(5);
+        assert_eq!(
+            StorageValueType::Amount
+                .decode(&amount.serialize_to_vec())
+                .unwrap(),
+            serde_json::to_value(amount).unwrap()
+        );
+        let duration = EpochDuration {
+            min_num_of_blocks: 10,
+            min_duration: DurationSecs(60),
+        };
+        assert_eq!(
+            StorageValueType::EpochDuration
+                .decode(&duration.serialize_to_vec())
+                .unwrap(),
+            json!({"min_num_of_blocks": 10, "min_duration": 60})
+        );
+        assert!(StorageValueType::U64.decode(&[1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_storage_value_type_names() {
+        for ty in StorageValueType::ALL {
+            assert_eq!(ty.name().parse::<StorageValueType>(), Ok(ty));
+        }
+        assert!("unknown".parse::<StorageValueType>().is_err());
+    }
+}
@@ -1,8 +1,9 @@
 //! SDK functions to construct different types of transactions
 
 use std::borrow::Cow;
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs::File;
+use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
@@ -18,7 +19,7 @@ use masp_primitives::transaction::components::transparent::fees::{
     InputView as TransparentInputView, OutputView as TransparentOutputView,
 };
 use masp_primitives::transaction::components::I128Sum;
-use namada_account::{InitAccount, UpdateAccount};
+use namada_account::{InitAccount, UpdateAccount, UpdateAccountKeys};
 use namada_core::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
 use namada_core::ibc::apps::transfer::types::packet::PacketData;
 use namada_core::ibc::apps::transfer::types::PrefixedCoin;
@@ -26,8 +27,12 @@ use namada_core::ibc::core::channel::types::timeout::TimeoutHeight;
 use namada_core::ibc::core::client::types::Height as IbcHeight;
 use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use namada_core::ibc::primitives::{Msg, Timestamp as IbcTimestamp};
+use namada_core::ledger::eth_bridge::nuts::ConvertNuts;
 use namada_core::types::address::{Address, InternalAddress, MASP};
 use namada_core::types::dec::Dec;
+use namada_core::types::eth_bridge_pool::{
+    erc20_nut_address, erc20_token_address,
+};
 use namada_core::types::hash::Hash;
 use namada_core::types::ibc::{IbcShieldedTransfer, MsgShieldedTransfer};
 use namada_core::types::key::*;
@@ -37,33 +42,39 @@ use namada_core::types::time::DateTimeUtc;
 use namada_core::types::token::MaspDenom;
 use namada_core::types::{storage, token};
 use namada_governance::cli::onchain::{
-    DefaultProposal, OnChainProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, IbcClientRecoveryProposal, OnChainProposal,
+    PgfFundingProposal, PgfStewardProposal,
 };
 use namada_governance::pgf::cli::steward::Commission;
 use namada_governance::storage::proposal::{
-    InitProposalData, ProposalType, VoteProposalData,
+    InitProposalData, ProposalType, VoteProposalData, VoteProxyData,
 };
 use namada_governance::storage::vote::ProposalVote;
 use namada_ibc::storage::channel_key;
+use namada_parameters::storage as parameter_storage;
 use namada_proof_of_stake::parameters::PosParams;
 use namada_proof_of_stake::types::{CommissionPair, ValidatorState};
 use namada_token::storage_key::balance_key;
 use namada_tx::data::pgf::UpdateStewardCommission;
-use namada_tx::data::{pos, ResultCode, TxResult};
+use namada_tx::data::scheduled::ScheduledTx;
+use namada_tx::data::{pos, ResultCode, TxResult, TxType};
 pub use namada_tx::{Signature, *};
 
 use crate::args::{self, InputAmount};
 use crate::control_flow::time;
-use crate::error::{EncodingError, Error, QueryError, Result, TxSubmitError};
+use crate::error::{
+    EncodingError, Error, EthereumBridgeError, QueryError, Result,
+    TxSubmitError,
+};
 use crate::io::Io;
 use crate::masp::TransferErr::Build;
 use crate::masp::{ShieldedContext, ShieldedTransfer};
-use crate::queries::Client;
+use crate::queries::{Client, RPC};
 use crate::rpc::{
     self, query_wasm_code_hash, validate_amount, InnerTxResult,
     TxBroadcastData, TxResponse,
 };
-use crate::signing::{self, SigningTxData, TxSourcePostBalance};
+use crate::signing::{self, OfflineTx, SigningTxData, TxSourcePostBalance};
 use crate::tendermint_rpc::endpoint::broadcast::tx_sync::Response;
 use crate::tendermint_rpc::error::Error as RpcError;
 use crate::wallet::WalletIo;
@@ -83,12 +94,20 @@ pub const TX_REACTIVATE_VALIDATOR_WASM: &str = "tx_reactivate_validator.wasm";
 pub const TX_INIT_PROPOSAL: &str = "tx_init_proposal.wasm";
 /// Vote transaction WASM path
 pub const TX_VOTE_PROPOSAL: &str = "tx_vote_proposal.wasm";
+/// Update vote proxy WASM path
+pub const TX_UPDATE_VOTE_PROXY: &str = "tx_update_vote_proxy.wasm";
 /// Reveal public key transaction WASM path
 pub const TX_REVEAL_PK: &str = "tx_reveal_pk.wasm";
+/// Schedule a tx at a future epoch WASM path
+pub const TX_SCHEDULE_WASM: &str = "tx_schedule.wasm";
 /// Update validity predicate WASM path
 pub const TX_UPDATE_ACCOUNT_WASM: &str = "tx_update_account.wasm";
+/// Update account keys and threshold WASM path
+pub const TX_UPDATE_ACCOUNT_KEYS_WASM: &str = "tx_update_account_keys.wasm";
 /// Transfer transaction WASM path
 pub const TX_TRANSFER_WASM: &str = "tx_transfer.wasm";
+/// Vesting transfer transaction WASM path
+pub const TX_VESTING_TRANSFER_WASM: &str = "tx_vesting_transfer.wasm";
 /// IBC transaction WASM path
 pub const TX_IBC_WASM: &str = "tx_ibc.wasm";
 /// User validity predicate WASM path
@@ -101,8 +120,14 @@ pub const TX_UNBOND_WASM: &str = "tx_unbond.wasm";
 pub const TX_WITHDRAW_WASM: &str = "tx_withdraw.wasm";
 /// Claim-rewards WASM path
 pub const TX_CLAIM_REWARDS_WASM: &str = "tx_claim_rewards.wasm";
+/// Set auto-compounding of rewards WASM path
+pub const TX_SET_AUTO_COMPOUND_WASM: &str = "tx_set_auto_compound.wasm";
 /// Bridge pool WASM path
 pub const TX_BRIDGE_POOL_WASM: &str = "tx_bridge_pool.wasm";
+/// Vote to pause the Ethereum bridge WASM path
+pub const TX_BRIDGE_PAUSE_VOTE_WASM: &str = "tx_bridge_pause_vote.wasm";
+/// Convert NUTs into wrapped ERC20s WASM path
+pub const TX_CONVERT_NUTS_WASM: &str = "tx_convert_nuts.wasm";
 /// Change commission WASM path
 pub const TX_CHANGE_COMMISSION_WASM: &str =
     "tx_change_validator_commission.wasm";
@@ -122,6 +147,14 @@ pub const TX_REDELEGATE_WASM: &str = "tx_redelegate.wasm";
 /// and `/applied` ABCI query endpoints.
 const DEFAULT_NAMADA_EVENTS_MAX_WAIT_TIME_SECONDS: u64 = 60;
 
+/// The interval in seconds between the queries of the status of a tx that
+/// is awaited to expire
+const TX_EXPIRATION_POLL_INTERVAL_SECONDS: u64 = 5;
+
+/// The percentage by which the fee per gas unit of an expired tx is raised
+/// when it's renewed
+pub const RESUBMISSION_FEE_INCREASE_PERCENT: i128 = 10;
+
 /// Capture the result of running a transaction
 #[derive(Debug)]
 pub enum ProcessTxResponse {
@@ -153,6 +186,20 @@ impl ProcessTxResponse {
             }
         }
     }
+
+    /// Returns an error if the transaction was applied, but it was rejected
+    /// or it failed on-chain. Otherwise, returns the response itself.
+    pub fn into_valid(self) -> Result<Self> {
+        if let ProcessTxResponse::Applied(resp) = &self {
+            if self.is_applied_and_valid().is_none() {
+                return Err(Error::from(TxSubmitError::InvalidOnChain(
+                    resp.hash.clone(),
+                    resp.height,
+                )));
+            }
+        }
+        Ok(self)
+    }
 }
 
 /// Build and dump a transaction either to file or to screen
@@ -179,6 +226,41 @@ pub fn dump_tx<IO: Io>(io: &IO, args: &args::Tx, tx: Tx) {
     }
 }
 
+/// Dump an unsigned transaction together with its signing data either to file
+/// or to screen, to be signed on an offline machine
+pub fn dump_offline_tx<IO: Io>(
+    io: &IO,
+    args: &args::Tx,
+    tx: Tx,
+    signing_data: SigningTxData,
+) {
+    let tx_id = tx.header_hash();
+    let offline_tx = OfflineTx { tx, signing_data };
+    match args.output_folder.to_owned() {
+        Some(path) => {
+            let tx_filename = format!("{}.offline.tx", tx_id);
+            let tx_path = path.join(tx_filename);
+            let out = File::create(&tx_path).unwrap();
+            serde_json::to_writer_pretty(out, &offline_tx)
+                .expect("Should be able to write to file.");
+            display_line!(
+                io,
+                "Unsigned transaction serialized to {}.",
+                tx_path.to_string_lossy()
+            );
+        }
+        None => {
+            display_line!(io, "Below the unsigned transaction: \n");
+            display_line!(
+                io,
+                "{}",
+                serde_json::to_string_pretty(&offline_tx)
+                    .expect("Should be able to serialize the transaction.")
+            )
+        }
+    }
+}
+
 /// Prepare a transaction for signing and submission by adding a wrapper header
 /// to it.
 #[allow(clippy::too_many_arguments)]
@@ -189,9 +271,22 @@ pub async fn prepare_tx(
     fee_payer: common::PublicKey,
     tx_source_balance: Option<TxSourcePostBalance>,
 ) -> Result<()> {
+    if let Some(expiration_height) = args.expiration_height {
+        tx.header.expiration_height = Some(expiration_height);
+    }
     if !args.dry_run {
         let epoch = rpc::query_epoch(context.client()).await?;
 
+        // The fee unshielding is built for the gas limit of the wrapper, so
+        // the gas limit can't be replaced with an estimate when signing
+        if args.estimate_gas_limit && args.fee_unshield.is_some() {
+            return Err(Error::Other(
+                "The gas limit cannot be estimated for a tx paying its fee \
+                 with unshielded tokens"
+                    .to_string(),
+            ));
+        }
+
         signing::wrap_tx(context, tx, args, tx_source_balance, epoch, fee_payer)
             .await
     } else {
@@ -349,11 +444,11 @@ pub async fn broadcast_tx(
         }
         Ok(response)
     } else {
-        Err(Error::from(TxSubmitError::TxBroadcast(RpcError::server(
+        Err(Error::from(TxSubmitError::CheckTxRejected(
             serde_json::to_string(&response).map_err(|err| {
                 Error::from(EncodingError::Serde(err.to_string()))
             })?,
-        ))))
+        )))
     }
 }
 
@@ -383,47 +478,169 @@ pub async fn submit_tx(
     // Broadcast the supplied transaction
     broadcast_tx(context, &to_broadcast).await?;
 
+    wait_for_tx_result(context, wrapper_hash, decrypted_hash).await
+}
+
+/// Wait for the results of a broadcasted wrapper tx and of its inner tx,
+/// identified by their hashes.
+pub async fn wait_for_tx_result(
+    context: &impl Namada,
+    wrapper_hash: &str,
+    decrypted_hash: &str,
+) -> Result<TxResponse> {
     let deadline = time::Instant::now()
         + time::Duration::from_secs(
             DEFAULT_NAMADA_EVENTS_MAX_WAIT_TIME_SECONDS,
         );
 
-    tracing::debug!(
-        transaction = ?to_broadcast,
-        ?deadline,
-        "Awaiting transaction approval",
-    );
+    tracing::debug!(wrapper_hash, ?deadline, "Awaiting transaction approval");
 
-    let response = {
-        let wrapper_query = rpc::TxEventQuery::Accepted(wrapper_hash.as_str());
+    let wrapper_query = rpc::TxEventQuery::Accepted(wrapper_hash);
+    let event = rpc::query_tx_status(context, wrapper_query, deadline).await?;
+    let wrapper_resp = TxResponse::from_event(event);
+
+    if display_wrapper_resp_and_get_result(context, &wrapper_resp) {
+        display_line!(context.io(), "Waiting for inner transaction result...");
+        // The transaction is now on chain. We wait for it to be decrypted
+        // and applied
+        // We also listen to the event emitted when the encrypted
+        // payload makes its way onto the blockchain
+        let decrypted_query = rpc::TxEventQuery::Applied(decrypted_hash);
         let event =
-            rpc::query_tx_status(context, wrapper_query, deadline).await?;
-        let wrapper_resp = TxResponse::from_event(event);
+            rpc::query_tx_status(context, decrypted_query, deadline).await?;
+        let inner_resp = TxResponse::from_event(event);
 
-        if display_wrapper_resp_and_get_result(context, &wrapper_resp) {
-            display_line!(
-                context.io(),
-                "Waiting for inner transaction result..."
-            );
-            // The transaction is now on chain. We wait for it to be decrypted
-            // and applied
-            // We also listen to the event emitted when the encrypted
-            // payload makes its way onto the blockchain
-            let decrypted_query =
-                rpc::TxEventQuery::Applied(decrypted_hash.as_str());
-            let event =
-                rpc::query_tx_status(context, decrypted_query, deadline)
-                    .await?;
-            let inner_resp = TxResponse::from_event(event);
-
-            display_inner_resp(context, &inner_resp);
-            Ok(inner_resp)
+        display_inner_resp(context, &inner_resp);
+        Ok(inner_resp)
+    } else {
+        Ok(wrapper_resp)
+    }
+}
+
+/// What became of a broadcasted wrapper tx that wasn't accepted in time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PendingTxOutcome {
+    /// The tx was eventually accepted in a block
+    Accepted,
+    /// The tx expired, so it can no longer be included in a block
+    Expired,
+    /// The tx was evicted from the mempool of the node, because it was
+    /// rejected when it got validated again after a block, for the given
+    /// reason
+    Evicted(String),
+}
+
+/// Poll the hash of a broadcasted wrapper tx, that wasn't accepted in time,
+/// until it's either accepted, it expires or it's evicted from the mempool of
+/// the node.
+///
+/// The eviction of the tx is found in the node's log of mempool rejections,
+/// which also records the txs rejected when the mempool is validated again
+/// after each block. Since this validation only depends on the state of the
+/// chain, the other nodes evict the tx as well.
+///
+/// A tx without an expiration may stay in the mempool indefinitely, in which
+/// case this only returns once it's accepted or evicted.
+pub async fn wait_for_pending_tx(
+    context: &impl Namada,
+    tx: &Tx,
+) -> Result<PendingTxOutcome> {
+    let wrapper_hash = tx.header_hash().to_string();
+    let tx_bytes_hash = Hash::sha256(tx.to_bytes());
+    time::Sleep {
+        strategy: time::Constant(time::Duration::from_secs(
+            TX_EXPIRATION_POLL_INTERVAL_SECONDS,
+        )),
+    }
+    .run(|| async {
+        let accepted = rpc::query_tx_events(
+            context.client(),
+            rpc::TxEventQuery::Accepted(&wrapper_hash),
+        )
+        .await
+        .map_err(|err| Error::from(QueryError::General(err.to_string())));
+        match accepted {
+            Ok(Some(_)) => {
+                return ControlFlow::Break(Ok(PendingTxOutcome::Accepted));
+            }
+            Ok(None) => {}
+            Err(err) => return ControlFlow::Break(Err(err)),
+        }
+        match rpc::query_mempool_rejections(context.client()).await {
+            Ok(rejections) => {
+                if let Some(rejection) = rejections
+                    .into_iter()
+                    .find(|rejection| rejection.tx_hash == tx_bytes_hash)
+                {
+                    return ControlFlow::Break(Ok(PendingTxOutcome::Evicted(
+                        rejection.log,
+                    )));
+                }
+            }
+            Err(err) => return ControlFlow::Break(Err(err)),
+        }
+        let last_block = match rpc::query_block(context.client()).await {
+            Ok(Some(last_block)) => last_block,
+            Ok(None) => return ControlFlow::Continue(()),
+            Err(err) => return ControlFlow::Break(Err(err)),
+        };
+        let expired_in_time = matches!(
+            tx.header.expiration,
+            Some(expiration) if last_block.time > expiration
+        );
+        if expired_in_time
+            || tx
+                .header
+                .is_expired_at_height(last_block.height.next_height())
+        {
+            ControlFlow::Break(Ok(PendingTxOutcome::Expired))
         } else {
-            Ok(wrapper_resp)
+            ControlFlow::Continue(())
         }
-    };
+    })
+    .await
+}
+
+/// Renew an expired or evicted wrapper tx, so that it can be signed and
+/// submitted again.
+/// The tx gets a fresh timestamp, an expiration time with the same lifetime
+/// as before and its fee per gas unit is raised by
+/// [`RESUBMISSION_FEE_INCREASE_PERCENT`]. The signatures are removed, as they
+/// no longer match the renewed header.
+pub fn renew_expired_tx(
+    tx: &mut Tx,
+    expiration_height: Option<storage::BlockHeight>,
+) -> Result<()> {
+    let mut wrapper = tx.header.wrapper().ok_or_else(|| {
+        Error::Other("Only a wrapper tx can be renewed".to_string())
+    })?;
+    if wrapper.unshield_section_hash.is_some() {
+        return Err(Error::Other(
+            "A tx that unshields its fee can't be renewed".to_string(),
+        ));
+    }
+    wrapper.fee.amount_per_gas_unit =
+        raised_fee(wrapper.fee.amount_per_gas_unit);
+
+    let timestamp = DateTimeUtc::now();
+    if let Some(expiration) = tx.header.expiration {
+        let lifetime = expiration.0 - tx.header.timestamp.0;
+        tx.header.expiration = Some(DateTimeUtc(timestamp.0 + lifetime));
+    }
+    tx.header.timestamp = timestamp;
+    tx.header.expiration_height = expiration_height;
+    tx.header.tx_type = TxType::Wrapper(Box::new(wrapper));
+    tx.sections
+        .retain(|section| !matches!(section, Section::Signature(_)));
+    Ok(())
+}
 
-    response
+/// Raise a fee per gas unit by [`RESUBMISSION_FEE_INCREASE_PERCENT`], rounded
+/// up
+fn raised_fee(fee: token::DenominatedAmount) -> token::DenominatedAmount {
+    let factor = Dec::new(100 + RESUBMISSION_FEE_INCREASE_PERCENT, 2)
+        .expect("The fee increase factor must be valid");
+    token::DenominatedAmount::new(fee.amount().mul_ceil(factor), fee.denom())
 }
 
 /// Display a result of a wrapper tx.
@@ -435,9 +652,8 @@ pub fn display_wrapper_resp_and_get_result(
     let result = if resp.code != ResultCode::Ok {
         display_line!(
             context.io(),
-            "Wrapper transaction failed with error code {}. Used {} gas.",
-            resp.code,
-            resp.gas_used,
+            "Wrapper transaction failed.\n{}",
+            resp
         );
         false
     } else {
@@ -487,7 +703,7 @@ pub fn display_inner_resp(context: &impl Namada, resp: &TxResponse) {
             edisplay_line!(
                 context.io(),
                 "Transaction failed.\nDetails: {}",
-                serde_json::to_string_pretty(&resp).unwrap()
+                resp
             );
         }
     }
@@ -1081,6 +1297,135 @@ pub async fn build_deactivate_validator(
     .map(|tx| (tx, signing_data))
 }
 
+/// Submit transaction for a validator to vote to pause the Ethereum bridge
+pub async fn build_bridge_pause_vote(
+    context: &impl Namada,
+    args::TxBridgePauseVote {
+        tx: tx_args,
+        validator,
+        tx_code_path,
+    }: &args::TxBridgePauseVote,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(validator.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(validator.clone()),
+        default_signer,
+    )
+    .await?;
+
+    // Check if the validator address is actually a validator
+    if !rpc::is_validator(context.client(), validator).await? {
+        edisplay_line!(
+            context.io(),
+            "The given address {} is not a validator.",
+            &validator
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidValidatorAddress(
+                validator.clone(),
+            )));
+        }
+    }
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        validator.clone(),
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Submit transaction to convert NUTs into wrapped ERC20s
+pub async fn build_convert_nuts(
+    context: &impl Namada,
+    args::TxConvertNuts {
+        tx: tx_args,
+        owner,
+        asset,
+        amount,
+        tx_code_path,
+    }: &args::TxConvertNuts,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(owner.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(owner.clone()),
+        default_signer,
+    )
+    .await?;
+
+    // NUTs have the same denomination as the wrapped ERC20s
+    let amount = validate_amount(
+        context,
+        *amount,
+        &erc20_token_address(asset),
+        tx_args.force,
+    )
+    .await?
+    .amount();
+    let nut = erc20_nut_address(asset);
+    check_balance_too_low_err(
+        &nut,
+        owner,
+        amount,
+        balance_key(&nut, owner),
+        tx_args.force,
+        context,
+    )
+    .await?;
+
+    // Check that the token cap of the asset covers the conversion
+    let flow_control = RPC
+        .shell()
+        .eth_bridge()
+        .get_erc20_flow_control(context.client(), asset)
+        .await
+        .map_err(|e| {
+            Error::Query(QueryError::General(format!(
+                "Failed to read the ERC20 flow control data: {e}"
+            )))
+        })?;
+    let error = if !flow_control.whitelisted {
+        Some(EthereumBridgeError::Erc20NotWhitelisted(*asset))
+    } else if flow_control.exceeds_token_caps(amount) {
+        Some(EthereumBridgeError::Erc20TokenCapsExceeded(*asset))
+    } else {
+        None
+    };
+    if let Some(error) = error {
+        edisplay_line!(context.io(), "{error}");
+        if !tx_args.force {
+            return Err(Error::EthereumBridge(error));
+        }
+    }
+
+    let data = ConvertNuts {
+        owner: owner.clone(),
+        asset: *asset,
+        amount,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit transaction to deactivate a validator
 pub async fn build_reactivate_validator(
     context: &impl Namada,
@@ -1138,6 +1483,30 @@ pub async fn build_reactivate_validator(
         }
     }
 
+    let self_bond = rpc::get_bond_amount_at(
+        context.client(),
+        validator,
+        validator,
+        pipeline_epoch,
+    )
+    .await?;
+    if self_bond < params.min_self_bond {
+        edisplay_line!(
+            context.io(),
+            "The self-bond of validator {} at the pipeline epoch is {}, below \
+             the minimum self-bond {}.",
+            &validator,
+            self_bond.to_string_native(),
+            params.min_self_bond.to_string_native()
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::SelfBondBelowMinimum(
+                validator.clone(),
+                params.min_self_bond.to_string_native(),
+            )));
+        }
+    }
+
     build(
         context,
         tx_args,
@@ -1466,6 +1835,59 @@ pub async fn build_claim_rewards(
     .map(|tx| (tx, signing_data))
 }
 
+/// Submit transaction to enable or disable auto-compounding of bond rewards
+pub async fn build_set_auto_compound(
+    context: &impl Namada,
+    args::SetAutoCompound {
+        tx: tx_args,
+        validator,
+        source,
+        enabled,
+        tx_code_path,
+    }: &args::SetAutoCompound,
+) -> Result<(Tx, SigningTxData)> {
+    let default_address = source.clone().unwrap_or(validator.clone());
+    let default_signer = Some(default_address.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(default_address),
+        default_signer,
+    )
+    .await?;
+
+    // Check that the validator address is actually a validator
+    let validator =
+        known_validator_or_err(validator.clone(), tx_args.force, context)
+            .await?;
+
+    // Check that the source address exists on chain
+    let source = match source.clone() {
+        Some(source) => source_exists_or_err(source, tx_args.force, context)
+            .await
+            .map(Some),
+        None => Ok(source.clone()),
+    }?;
+
+    let data = pos::SetAutoCompound {
+        validator,
+        source,
+        enabled: *enabled,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit a transaction to unbond
 pub async fn build_unbond(
     context: &impl Namada,
@@ -1763,6 +2185,7 @@ pub async fn build_default_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_ibc_client_recovery: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: DefaultProposal,
@@ -1857,7 +2280,7 @@ pub async fn build_vote_proposal(
         }
     }
 
-    let delegations = rpc::get_delegators_delegation_at(
+    let mut delegations = rpc::get_delegators_delegation_at(
         context.client(),
         voter,
         proposal.voting_start_epoch,
@@ -1867,9 +2290,31 @@ pub async fn build_vote_proposal(
     .cloned()
     .collect::<Vec<Address>>();
 
+    // A vote proxy without bonds votes under the validators of the
+    // delegators it represents
+    if delegations.is_empty() {
+        let mut validators = BTreeSet::new();
+        for delegator in
+            rpc::query_proxied_delegators(context.client(), voter).await?
+        {
+            validators.extend(
+                rpc::get_delegators_delegation_at(
+                    context.client(),
+                    &delegator,
+                    proposal.voting_start_epoch,
+                )
+                .await?
+                .into_keys(),
+            );
+        }
+        delegations = validators.into_iter().collect();
+    }
+
     if delegations.is_empty() {
         return Err(Error::Other(
-            "Voter address must have delegations".to_string(),
+            "Voter address must have delegations or be the vote proxy of a \
+             delegator"
+                .to_string(),
         ));
     }
 
@@ -1893,18 +2338,81 @@ pub async fn build_vote_proposal(
     .map(|tx| (tx, signing_data))
 }
 
-/// Build a pgf funding proposal governance
-pub async fn build_pgf_funding_proposal(
+/// Submit a transaction to set or remove the vote proxy of a delegator
+pub async fn build_update_vote_proxy(
     context: &impl Namada,
-    args::InitProposal {
-        tx,
-        proposal_data: _,
-        native_token: _,
-        is_offline: _,
-        is_pgf_stewards: _,
-        is_pgf_funding: _,
+    args::UpdateVoteProxy {
+        tx: tx_args,
+        delegator,
+        proxy,
         tx_code_path,
-    }: &args::InitProposal,
+    }: &args::UpdateVoteProxy,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(delegator.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(delegator.clone()),
+        default_signer,
+    )
+    .await?;
+
+    if proxy.as_ref() == Some(delegator) {
+        return Err(Error::Other(
+            "A delegator cannot be its own vote proxy".to_string(),
+        ));
+    }
+
+    let has_delegations =
+        !rpc::get_delegators_delegation(context.client(), delegator)
+            .await?
+            .is_empty();
+    if !has_delegations {
+        if tx_args.force {
+            edisplay_line!(
+                context.io(),
+                "The address {} has no delegations.",
+                delegator
+            );
+        } else {
+            return Err(Error::Other(format!(
+                "The address {} has no delegations.",
+                delegator
+            )));
+        }
+    }
+
+    let data = VoteProxyData {
+        delegator: delegator.clone(),
+        proxy: proxy.clone(),
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build a pgf funding proposal governance
+pub async fn build_pgf_funding_proposal(
+    context: &impl Namada,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        native_token: _,
+        is_offline: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_ibc_client_recovery: _,
+        tx_code_path,
+    }: &args::InitProposal,
     proposal: PgfFundingProposal,
 ) -> Result<(Tx, SigningTxData)> {
     let default_signer = Some(proposal.proposal.author.clone());
@@ -1948,6 +2456,7 @@ pub async fn build_pgf_stewards_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_ibc_client_recovery: _,
         tx_code_path,
     }: &args::InitProposal,
     proposal: PgfStewardProposal,
@@ -1984,6 +2493,53 @@ pub async fn build_pgf_stewards_proposal(
     .map(|tx| (tx, signing_data))
 }
 
+/// Build an ibc client recovery proposal governance
+pub async fn build_ibc_client_recovery_proposal(
+    context: &impl Namada,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        native_token: _,
+        is_offline: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_ibc_client_recovery: _,
+        tx_code_path,
+    }: &args::InitProposal,
+    proposal: IbcClientRecoveryProposal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(proposal.proposal.author.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(proposal.proposal.author.clone()),
+        default_signer,
+    )
+    .await?;
+
+    let init_proposal_data = InitProposalData::try_from(proposal.clone())
+        .map_err(|e| TxSubmitError::InvalidProposal(e.to_string()))?;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?, None);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build(
+        context,
+        tx,
+        tx_code_path.clone(),
+        init_proposal_data,
+        add_section,
+        &signing_data.fee_payer,
+        None, // TODO: need to pay the fee to submit a proposal
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit an IBC transfer
 pub async fn build_ibc_transfer(
     context: &impl Namada,
@@ -2292,6 +2848,229 @@ async fn used_asset_types<P, R, K, N>(
     Ok(asset_types)
 }
 
+/// Submit a transfer whose amount is locked in the target's balance with a
+/// vesting schedule
+pub async fn build_vesting_transfer(
+    context: &impl Namada,
+    args::TxVestingTransfer {
+        tx: tx_args,
+        source,
+        target,
+        token,
+        amount,
+        start,
+        end,
+        tx_code_path,
+    }: &args::TxVestingTransfer,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(source.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(source.clone()),
+        default_signer,
+    )
+    .await?;
+
+    if start > end {
+        edisplay_line!(
+            context.io(),
+            "The start epoch {} of the vesting schedule is after its end \
+             epoch {}.",
+            start,
+            end
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidVestingSchedule(
+                format!("start epoch {start} is after end epoch {end}"),
+            )));
+        }
+    }
+
+    // Check that the source and target addresses exist on chain
+    source_exists_or_err(source.clone(), tx_args.force, context).await?;
+    target_exists_or_err(target.clone(), tx_args.force, context).await?;
+
+    // A schedule with tokens still locked cannot be replaced
+    if let Some(schedule) =
+        rpc::get_vesting_schedule(context.client(), token, target).await?
+    {
+        let epoch = rpc::query_epoch(context.client()).await?;
+        let locked = schedule.locked_amount(epoch);
+        if !locked.is_zero() {
+            edisplay_line!(
+                context.io(),
+                "The target {} still has {} tokens locked by a previous \
+                 vesting schedule.",
+                target,
+                locked.to_string_native()
+            );
+            if !tx_args.force {
+                return Err(Error::from(
+                    TxSubmitError::InvalidVestingSchedule(format!(
+                        "the target {target} still has locked tokens"
+                    )),
+                ));
+            }
+        }
+    }
+
+    // validate the amount given
+    let validated_amount =
+        validate_amount(context, *amount, token, tx_args.force).await?;
+    check_balance_too_low_err(
+        token,
+        source,
+        validated_amount.amount(),
+        balance_key(token, source),
+        tx_args.force,
+        context,
+    )
+    .await?;
+
+    let data = token::VestingTransfer {
+        source: source.clone(),
+        target: target.clone(),
+        token: token.clone(),
+        amount: validated_amount,
+        start: *start,
+        end: *end,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Schedule a tx for execution at the start of a future epoch. The scheduled
+/// tx is signed with the keys of its owner from the software wallet and its
+/// fee, the gas limit at the minimum gas price of the native token, is paid
+/// upfront by the owner.
+pub async fn build_schedule_tx(
+    context: &impl Namada,
+    args::TxSchedule {
+        tx: tx_args,
+        owner,
+        epoch,
+        gas_limit,
+        code_path,
+        data,
+        tx_code_path,
+    }: &args::TxSchedule,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(owner.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(owner.clone()),
+        default_signer,
+    )
+    .await?;
+
+    let current_epoch = rpc::query_epoch(context.client()).await?;
+    if *epoch <= current_epoch {
+        edisplay_line!(
+            context.io(),
+            "A tx must be scheduled for an epoch after the current epoch {}.",
+            current_epoch
+        );
+        if !tx_args.force {
+            return Err(Error::from(TxSubmitError::InvalidScheduledEpoch(
+                current_epoch,
+                *epoch,
+            )));
+        }
+    }
+
+    // The fee covers the gas limit at the minimum gas price
+    let native_token = context.native_token();
+    let gas_cost_key = parameter_storage::get_gas_cost_key();
+    let gas_price = rpc::query_storage_value::<
+        _,
+        BTreeMap<Address, token::Amount>,
+    >(context.client(), &gas_cost_key)
+    .await?
+    .get(&native_token)
+    .copied()
+    .ok_or_else(|| {
+        Error::Other(format!(
+            "Could not retrieve from storage the gas cost for token {}",
+            native_token
+        ))
+    })?;
+    let fee = gas_price
+        .checked_mul(token::Amount::from(*gas_limit))
+        .ok_or_else(|| {
+            Error::Other("The fee of the scheduled tx overflows".to_string())
+        })?;
+    check_balance_too_low_err(
+        &native_token,
+        owner,
+        fee,
+        balance_key(&native_token, owner),
+        tx_args.force,
+        context,
+    )
+    .await?;
+
+    // Build the scheduled tx and sign its raw header
+    let chain_id = tx_args.chain_id.clone().unwrap();
+    let mut scheduled_tx = Tx::new(chain_id, None);
+    let code_hash = query_wasm_code_hash_buf(context, code_path).await?;
+    scheduled_tx.add_code_from_hash(
+        code_hash,
+        Some(code_path.to_string_lossy().into_owned()),
+    );
+    if let Some(data) = data {
+        scheduled_tx.add_serialized_data(data.clone());
+    }
+    if let Some(account_public_keys_map) =
+        signing_data.account_public_keys_map.clone()
+    {
+        let mut wallet = context.wallet_mut().await;
+        let keypairs = signing_data
+            .public_keys
+            .iter()
+            .map(|public_key| {
+                signing::find_key_by_pk(&mut wallet, tx_args, public_key)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        scheduled_tx.sign_raw(
+            keypairs,
+            account_public_keys_map,
+            signing_data.owner.clone(),
+        );
+    }
+
+    let data = ScheduledTx {
+        epoch: *epoch,
+        payer: owner.clone(),
+        gas_limit: *gas_limit,
+        fee,
+        tx: scheduled_tx,
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit an ordinary transfer
 pub async fn build_transfer<N: Namada>(
     context: &N,
@@ -2608,6 +3387,77 @@ pub async fn build_update_account(
     .map(|tx| (tx, signing_data))
 }
 
+/// Submit a transaction to replace the public keys and the threshold of an
+/// established account
+pub async fn build_update_account_keys(
+    context: &impl Namada,
+    args::TxUpdateAccountKeys {
+        tx: tx_args,
+        tx_code_path,
+        addr,
+        public_keys,
+        threshold,
+    }: &args::TxUpdateAccountKeys,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(addr.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(addr.clone()),
+        default_signer,
+    )
+    .await?;
+
+    match addr {
+        Address::Established(_) => {}
+        Address::Implicit(_) => {
+            return Err(Error::from(TxSubmitError::ImplicitUpdate));
+        }
+        Address::Internal(_) => {
+            return Err(Error::from(TxSubmitError::ImplicitInternalError));
+        }
+    }
+    if rpc::get_account_info(context.client(), addr)
+        .await?
+        .is_none()
+    {
+        if tx_args.force {
+            edisplay_line!(context.io(), "The account {} doesn't exist.", addr);
+        } else {
+            return Err(Error::from(TxSubmitError::LocationDoesNotExist(
+                addr.clone(),
+            )));
+        }
+    }
+
+    let threshold = match threshold {
+        Some(threshold) => *threshold,
+        None if public_keys.len() == 1 => 1u8,
+        None => {
+            return Err(Error::from(TxSubmitError::MissingAccountThreshold));
+        }
+    };
+    let data = UpdateAccountKeys {
+        addr: addr.clone(),
+        public_keys: public_keys.clone(),
+        threshold,
+    };
+    data.validate()
+        .map_err(|err| Error::Other(err.to_string()))?;
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Submit a custom transaction
 pub async fn build_custom(
     context: &impl Namada,
@@ -2970,3 +3820,139 @@ fn proposal_to_vec(proposal: OnChainProposal) -> Result<Vec<u8>> {
     borsh::to_vec(&proposal.content)
         .map_err(|e| Error::from(EncodingError::Conversion(e.to_string())))
 }
+
+#[cfg(test)]
+mod test_renew_expired_tx {
+    use namada_core::types::address::nam;
+    use namada_core::types::chain::ChainId;
+    use namada_core::types::key::testing::keypair_1;
+    use namada_core::types::time::Duration as TimeDuration;
+    use namada_tx::data::{Fee, GasLimit};
+
+    use super::*;
+
+    /// A wrapper tx with the given fee per gas unit, signed by its fee payer
+    fn signed_wrapper_tx(
+        amount_per_gas_unit: token::DenominatedAmount,
+        expiration: Option<DateTimeUtc>,
+    ) -> Tx {
+        let keypair = keypair_1();
+        let mut tx = Tx::new(ChainId::default(), expiration);
+        tx.header.timestamp = DateTimeUtc::unix_epoch();
+        tx.add_code(vec![], None).add_data(0_u8);
+        tx.add_wrapper(
+            Fee {
+                amount_per_gas_unit,
+                token: nam(),
+            },
+            keypair.ref_to(),
+            Epoch(0),
+            GasLimit::from(20_000),
+            None,
+        );
+        tx.sign_wrapper(keypair);
+        tx
+    }
+
+    /// Test that the fee per gas unit is raised by 10%, rounded up to the
+    /// smallest unit of its denomination.
+    #[test]
+    fn test_raised_fee() {
+        let fee = token::DenominatedAmount::native(token::Amount::from(100));
+        assert_eq!(raised_fee(fee).amount(), token::Amount::from(110));
+
+        // a fraction of the smallest unit is rounded up
+        let fee = token::DenominatedAmount::native(token::Amount::from(15));
+        assert_eq!(raised_fee(fee).amount(), token::Amount::from(17));
+        let fee = token::DenominatedAmount::native(token::Amount::from(1));
+        assert_eq!(raised_fee(fee).amount(), token::Amount::from(2));
+
+        // the denomination is kept
+        let fee = token::DenominatedAmount::new(
+            token::Amount::from(3),
+            token::Denomination(0),
+        );
+        let raised = raised_fee(fee);
+        assert_eq!(raised.amount(), token::Amount::from(4));
+        assert_eq!(raised.denom(), token::Denomination(0));
+
+        // a zero fee stays zero
+        let fee = token::DenominatedAmount::native(token::Amount::zero());
+        assert!(raised_fee(fee).is_zero());
+    }
+
+    /// Test that a renewed tx gets a fresh timestamp, an expiration with the
+    /// same lifetime, the given expiration height and a raised fee, and that
+    /// its signatures are removed.
+    #[test]
+    fn test_renew_expired_tx() {
+        let lifetime = TimeDuration::minutes(10);
+        let expiration = DateTimeUtc(DateTimeUtc::unix_epoch().0 + lifetime);
+        let mut tx = signed_wrapper_tx(
+            token::DenominatedAmount::native(token::Amount::from(100)),
+            Some(expiration),
+        );
+        assert!(
+            tx.sections
+                .iter()
+                .any(|section| matches!(section, Section::Signature(_)))
+        );
+        let sections_len = tx.sections.len();
+        let header_hash = tx.header_hash();
+
+        let before_renewal = DateTimeUtc::now();
+        let expiration_height = Some(storage::BlockHeight(42));
+        renew_expired_tx(&mut tx, expiration_height).expect("Test failed");
+
+        assert!(tx.header.timestamp >= before_renewal);
+        assert_eq!(
+            tx.header.expiration,
+            Some(DateTimeUtc(tx.header.timestamp.0 + lifetime))
+        );
+        assert_eq!(tx.header.expiration_height, expiration_height);
+        let wrapper = tx.header.wrapper().expect("Test failed");
+        assert_eq!(
+            wrapper.fee.amount_per_gas_unit.amount(),
+            token::Amount::from(110)
+        );
+        assert!(
+            !tx.sections
+                .iter()
+                .any(|section| matches!(section, Section::Signature(_)))
+        );
+        assert_eq!(tx.sections.len(), sections_len - 1);
+        assert_ne!(tx.header_hash(), header_hash);
+    }
+
+    /// Test that a tx without an expiration time is renewed without one, and
+    /// that the expiration height is cleared if none is given.
+    #[test]
+    fn test_renew_expired_tx_without_expiration() {
+        let mut tx = signed_wrapper_tx(
+            token::DenominatedAmount::native(token::Amount::from(100)),
+            None,
+        );
+        tx.header.expiration_height = Some(storage::BlockHeight(10));
+        renew_expired_tx(&mut tx, None).expect("Test failed");
+        assert_eq!(tx.header.expiration, None);
+        assert_eq!(tx.header.expiration_height, None);
+    }
+
+    /// Test that only wrapper txs that don't unshield their fee are renewed.
+    #[test]
+    fn test_renew_expired_tx_rejects_invalid_txs() {
+        let mut raw_tx = Tx::new(ChainId::default(), None);
+        assert!(renew_expired_tx(&mut raw_tx, None).is_err());
+
+        let mut tx = signed_wrapper_tx(
+            token::DenominatedAmount::native(token::Amount::from(100)),
+            None,
+        );
+        let mut wrapper = tx.header.wrapper().expect("Test failed");
+        wrapper.unshield_section_hash = Some(Hash::default());
+        tx.header.tx_type = TxType::Wrapper(Box::new(wrapper));
+        let header_hash = tx.header_hash();
+        assert!(renew_expired_tx(&mut tx, None).is_err());
+        assert_eq!(tx.header_hash(), header_hash);
+    }
+}
@@ -0,0 +1,491 @@
+//! Import and export of the wallet's transparent address book in formats
+//! understood by other wallets and tools.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use namada_core::types::address::Address;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::wallet::alias::Alias;
+use crate::wallet::Store;
+
+/// The file format of an exported address book
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AddressBookFormat {
+    /// A JSON object of aliases to addresses
+    #[default]
+    Json,
+    /// Comma-separated values with an `alias,address` header line
+    Csv,
+    /// A JSON array of Keplr address book entries
+    Keplr,
+}
+
+impl FromStr for AddressBookFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "keplr" => Ok(Self::Keplr),
+            _ => Err(format!("Unknown address book format \"{s}\"")),
+        }
+    }
+}
+
+/// How to resolve an imported entry whose alias or address is already in the
+/// wallet
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// Keep the wallet's entry
+    #[default]
+    Skip,
+    /// Replace the wallet's entry with the imported one
+    Overwrite,
+    /// Import the entry under a fresh alias
+    Rename,
+}
+
+impl FromStr for ConflictStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "skip" => Ok(Self::Skip),
+            "overwrite" => Ok(Self::Overwrite),
+            "rename" => Ok(Self::Rename),
+            _ => Err(format!("Unknown conflict strategy \"{s}\"")),
+        }
+    }
+}
+
+/// Errors of address book parsing
+#[derive(Error, Debug)]
+pub enum AddressBookError {
+    /// Invalid JSON
+    #[error("Invalid JSON address book: {0}")]
+    Json(#[from] serde_json::Error),
+    /// Invalid CSV line
+    #[error("Invalid CSV address book at line {line}: {msg}")]
+    Csv {
+        /// The 1-based line number
+        line: usize,
+        /// What's wrong with the line
+        msg: String,
+    },
+    /// An entry with an invalid address
+    #[error("Invalid address \"{address}\" of alias \"{alias}\"")]
+    Address {
+        /// The alias of the entry
+        alias: String,
+        /// The address that failed to decode
+        address: String,
+    },
+}
+
+/// An alias with its address
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressBookEntry {
+    /// The alias of the address
+    pub alias: String,
+    /// The address
+    pub address: Address,
+}
+
+/// A Keplr address book entry
+#[derive(Serialize, Deserialize)]
+struct KeplrEntry {
+    name: String,
+    address: String,
+    #[serde(default)]
+    memo: String,
+}
+
+/// Read the transparent addresses of the wallet store, sorted by alias
+pub fn read_entries(store: &Store) -> Vec<AddressBookEntry> {
+    let mut entries: Vec<_> = store
+        .get_addresses()
+        .iter()
+        .map(|(alias, address)| AddressBookEntry {
+            alias: alias.normalize(),
+            address: address.clone(),
+        })
+        .collect();
+    entries.sort_by(|a, b| a.alias.cmp(&b.alias));
+    entries
+}
+
+/// Encode address book entries in the given format
+pub fn encode(
+    entries: &[AddressBookEntry],
+    format: AddressBookFormat,
+) -> String {
+    match format {
+        AddressBookFormat::Json => {
+            let book: BTreeMap<&str, String> = entries
+                .iter()
+                .map(|entry| (entry.alias.as_str(), entry.address.encode()))
+                .collect();
+            serde_json::to_string_pretty(&book)
+                .expect("Serializing the address book shouldn't fail")
+        }
+        AddressBookFormat::Csv => {
+            let mut csv = String::from("alias,address\n");
+            for entry in entries {
+                csv.push_str(&csv_field(&entry.alias));
+                csv.push(',');
+                csv.push_str(&entry.address.encode());
+                csv.push('\n');
+            }
+            csv
+        }
+        AddressBookFormat::Keplr => {
+            let book: Vec<KeplrEntry> = entries
+                .iter()
+                .map(|entry| KeplrEntry {
+                    name: entry.alias.clone(),
+                    address: entry.address.encode(),
+                    memo: String::new(),
+                })
+                .collect();
+            serde_json::to_string_pretty(&book)
+                .expect("Serializing the address book shouldn't fail")
+        }
+    }
+}
+
+/// Decode address book entries in the given format
+pub fn decode(
+    data: &str,
+    format: AddressBookFormat,
+) -> Result<Vec<AddressBookEntry>, AddressBookError> {
+    let raw: Vec<(String, String)> = match format {
+        AddressBookFormat::Json => {
+            let book: BTreeMap<String, String> = serde_json::from_str(data)?;
+            book.into_iter().collect()
+        }
+        AddressBookFormat::Csv => decode_csv(data)?,
+        AddressBookFormat::Keplr => {
+            let book: Vec<KeplrEntry> = serde_json::from_str(data)?;
+            book.into_iter()
+                .map(|entry| (entry.name, entry.address))
+                .collect()
+        }
+    };
+    raw.into_iter()
+        .map(|(alias, address)| match Address::decode(address.trim()) {
+            Ok(address) => Ok(AddressBookEntry {
+                alias: alias.trim().to_lowercase(),
+                address,
+            }),
+            Err(_) => Err(AddressBookError::Address { alias, address }),
+        })
+        .collect()
+}
+
+/// Quote a CSV field if needed
+fn csv_field(field: &str) -> String {
+    if field.contains(|c| matches!(c, ',' | '"' | '\n')) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Decode `alias,address` CSV lines. A header line is optional.
+fn decode_csv(data: &str) -> Result<Vec<(String, String)>, AddressBookError> {
+    let mut rows = vec![];
+    for (ix, line) in data.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let csv_err = |msg: &str| AddressBookError::Csv {
+            line: ix + 1,
+            msg: msg.to_string(),
+        };
+        // The address never needs quoting, so the alias ends at the last
+        // comma
+        let (alias, address) = line
+            .rsplit_once(',')
+            .ok_or_else(|| csv_err("expected \"alias,address\""))?;
+        if ix == 0 && alias == "alias" && address == "address" {
+            continue;
+        }
+        let alias = match alias.strip_prefix('"') {
+            Some(quoted) => quoted
+                .strip_suffix('"')
+                .ok_or_else(|| csv_err("unterminated quoted alias"))?
+                .replace("\"\"", "\""),
+            None => alias.to_string(),
+        };
+        rows.push((alias, address.to_string()));
+    }
+    Ok(rows)
+}
+
+/// What importing an entry does to the wallet
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImportAction {
+    /// Add the entry under its alias
+    Add(AddressBookEntry),
+    /// Leave the wallet unchanged
+    Skip {
+        /// The skipped entry
+        entry: AddressBookEntry,
+        /// Why the entry is skipped
+        reason: SkipReason,
+    },
+    /// Replace the wallet's entry under the same alias or for the same
+    /// address
+    Overwrite(AddressBookEntry),
+    /// Add the entry under a fresh alias
+    Rename {
+        /// The renamed entry
+        entry: AddressBookEntry,
+        /// The alias the entry is added under
+        new_alias: String,
+    },
+}
+
+/// Why an imported entry is skipped
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    /// The wallet already has the same alias for the same address
+    AlreadyKnown,
+    /// The alias is used by another address or key
+    AliasTaken,
+    /// The address is known under another alias
+    AddressKnownAs(String),
+    /// The alias is reserved for an internal address
+    ReservedAlias,
+}
+
+impl Display for ImportAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Add(AddressBookEntry { alias, address }) => {
+                write!(f, "add \"{alias}\": {address}")
+            }
+            Self::Skip {
+                entry: AddressBookEntry { alias, address },
+                reason,
+            } => {
+                let reason = match reason {
+                    SkipReason::AlreadyKnown => "already in the wallet".into(),
+                    SkipReason::AliasTaken => "alias already in use".into(),
+                    SkipReason::AddressKnownAs(other) => {
+                        format!("address already known as \"{other}\"")
+                    }
+                    SkipReason::ReservedAlias => "alias is reserved".into(),
+                };
+                write!(f, "skip \"{alias}\": {address} ({reason})")
+            }
+            Self::Overwrite(AddressBookEntry { alias, address }) => {
+                write!(f, "overwrite \"{alias}\": {address}")
+            }
+            Self::Rename {
+                entry: AddressBookEntry { alias, address },
+                new_alias,
+            } => write!(f, "rename \"{alias}\" to \"{new_alias}\": {address}"),
+        }
+    }
+}
+
+/// Resolve the imported entries against the wallet store with the given
+/// conflict strategy. The store is not modified, so this doubles as a dry-run
+/// preview of the import.
+pub fn plan_import(
+    store: &Store,
+    entries: Vec<AddressBookEntry>,
+    strategy: ConflictStrategy,
+) -> Vec<ImportAction> {
+    // Aliases taken by the entries planned so far
+    let mut planned: HashSet<String> = HashSet::new();
+    let is_taken = |planned: &HashSet<String>, alias: &str| {
+        planned.contains(alias) || store.contains_alias(&Alias::from(alias))
+    };
+
+    let mut actions = vec![];
+    for entry in entries {
+        if Alias::is_reserved(&entry.alias).is_some() {
+            actions.push(ImportAction::Skip {
+                entry,
+                reason: SkipReason::ReservedAlias,
+            });
+            continue;
+        }
+        let known_alias =
+            store.find_alias(&entry.address).map(Alias::normalize);
+        let conflict = match known_alias {
+            Some(known) if known == entry.alias => {
+                actions.push(ImportAction::Skip {
+                    entry,
+                    reason: SkipReason::AlreadyKnown,
+                });
+                continue;
+            }
+            Some(known) => Some(SkipReason::AddressKnownAs(known)),
+            None if is_taken(&planned, &entry.alias) => {
+                Some(SkipReason::AliasTaken)
+            }
+            None => None,
+        };
+        let action = match (conflict, strategy) {
+            (None, _) => ImportAction::Add(entry),
+            (Some(_), ConflictStrategy::Overwrite) => {
+                ImportAction::Overwrite(entry)
+            }
+            (Some(SkipReason::AliasTaken), ConflictStrategy::Rename) => {
+                let new_alias = (2..)
+                    .map(|n| format!("{}-{n}", entry.alias))
+                    .find(|alias| !is_taken(&planned, alias))
+                    .expect("There must be a free alias");
+                planned.insert(new_alias.clone());
+                actions.push(ImportAction::Rename { entry, new_alias });
+                continue;
+            }
+            // Renaming cannot resolve an address that is already known
+            (Some(reason), _) => ImportAction::Skip { entry, reason },
+        };
+        if let ImportAction::Add(entry) | ImportAction::Overwrite(entry) =
+            &action
+        {
+            planned.insert(entry.alias.clone());
+        }
+        actions.push(action);
+    }
+    actions
+}
+
+#[cfg(test)]
+mod test {
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+    };
+
+    use super::*;
+    use crate::wallet::fs::FsWalletUtils;
+
+    fn store_with(entries: &[(&str, &Address)]) -> Store {
+        let mut store = Store::default();
+        for (alias, address) in entries {
+            store
+                .insert_address::<FsWalletUtils>(
+                    Alias::from(alias),
+                    (*address).clone(),
+                    false,
+                )
+                .unwrap();
+        }
+        store
+    }
+
+    fn entry(alias: &str, address: &Address) -> AddressBookEntry {
+        AddressBookEntry {
+            alias: alias.to_string(),
+            address: address.clone(),
+        }
+    }
+
+    /// Test that every format round-trips the entries
+    #[test]
+    fn test_address_book_round_trip() {
+        let entries = vec![
+            entry("alice", &established_address_1()),
+            entry("bob, the builder", &established_address_2()),
+        ];
+        for format in [
+            AddressBookFormat::Json,
+            AddressBookFormat::Csv,
+            AddressBookFormat::Keplr,
+        ] {
+            let encoded = encode(&entries, format);
+            assert_eq!(decode(&encoded, format).unwrap(), entries);
+        }
+
+        // The CSV header is optional
+        let csv = format!("carol,{}\n", established_address_3().encode());
+        assert_eq!(
+            decode(&csv, AddressBookFormat::Csv).unwrap(),
+            vec![entry("carol", &established_address_3())]
+        );
+        assert!(decode("carol,not-an-address", AddressBookFormat::Csv).is_err());
+    }
+
+    /// Test the resolution of conflicting entries with each strategy
+    #[test]
+    fn test_address_book_import_conflicts() {
+        let (addr1, addr2, addr3) = (
+            established_address_1(),
+            established_address_2(),
+            established_address_3(),
+        );
+        let store = store_with(&[("alice", &addr1), ("bob", &addr2)]);
+        let entries = vec![
+            // Already known
+            entry("alice", &addr1),
+            // Known address under another alias
+            entry("robert", &addr2),
+            // Taken alias
+            entry("bob", &addr3),
+            // Reserved alias
+            entry("pos", &addr3),
+        ];
+
+        let skipped =
+            plan_import(&store, entries.clone(), ConflictStrategy::Skip);
+        assert_eq!(
+            skipped,
+            vec![
+                ImportAction::Skip {
+                    entry: entry("alice", &addr1),
+                    reason: SkipReason::AlreadyKnown,
+                },
+                ImportAction::Skip {
+                    entry: entry("robert", &addr2),
+                    reason: SkipReason::AddressKnownAs("bob".to_string()),
+                },
+                ImportAction::Skip {
+                    entry: entry("bob", &addr3),
+                    reason: SkipReason::AliasTaken,
+                },
+                ImportAction::Skip {
+                    entry: entry("pos", &addr3),
+                    reason: SkipReason::ReservedAlias,
+                },
+            ]
+        );
+
+        let overwritten =
+            plan_import(&store, entries.clone(), ConflictStrategy::Overwrite);
+        assert_eq!(
+            overwritten[1],
+            ImportAction::Overwrite(entry("robert", &addr2))
+        );
+        assert_eq!(
+            overwritten[2],
+            ImportAction::Overwrite(entry("bob", &addr3))
+        );
+
+        let renamed = plan_import(&store, entries, ConflictStrategy::Rename);
+        assert_eq!(
+            renamed[1],
+            ImportAction::Skip {
+                entry: entry("robert", &addr2),
+                reason: SkipReason::AddressKnownAs("bob".to_string()),
+            }
+        );
+        assert_eq!(
+            renamed[2],
+            ImportAction::Rename {
+                entry: entry("bob", &addr3),
+                new_alias: "bob-2".to_string(),
+            }
+        );
+    }
+}
@@ -0,0 +1,250 @@
+//! The protocol of the wallet daemon, which shares a single unlocked wallet
+//! session with the tools connected to its Unix socket. Every request and
+//! every response is a JSON object on its own line.
+
+use std::fmt;
+use std::str::FromStr;
+
+use namada_core::types::hash::Hash;
+use namada_core::types::key::common;
+use serde::{Deserialize, Serialize};
+
+/// A request to the wallet daemon
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "request", rename_all = "snake_case")]
+pub enum DaemonRequest {
+    /// List the keys that the daemon can sign with
+    ListKeys,
+    /// Sign a digest with the secret key of the given public key
+    Sign {
+        /// The public key of the signing key
+        public_key: common::PublicKey,
+        /// The digest to sign
+        digest: Hash,
+    },
+}
+
+/// A response of the wallet daemon
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "response", rename_all = "snake_case")]
+pub enum DaemonResponse {
+    /// The keys that the daemon can sign with
+    Keys {
+        /// The keys with their aliases
+        keys: Vec<DaemonKey>,
+    },
+    /// The signature of the requested digest
+    Signature {
+        /// The signature
+        signature: common::Signature,
+    },
+    /// The request failed or was rejected
+    Error {
+        /// The reason of the failure
+        message: String,
+    },
+}
+
+/// A key that the wallet daemon can sign with
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DaemonKey {
+    /// The alias of the key in the wallet
+    pub alias: String,
+    /// The public key
+    pub public_key: common::PublicKey,
+}
+
+/// When the wallet daemon asks its user to confirm a signing request
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConfirmPolicy {
+    /// Confirm every signing request
+    #[default]
+    Always,
+    /// Confirm the first signing request with each key of the session
+    Once,
+    /// Sign without asking for a confirmation
+    Never,
+}
+
+impl FromStr for ConfirmPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "always" => Ok(Self::Always),
+            "once" => Ok(Self::Once),
+            "never" => Ok(Self::Never),
+            _ => Err(format!(
+                "Unknown confirmation policy {s}, expected one of: always, \
+                 once, never"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ConfirmPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Always => write!(f, "always"),
+            Self::Once => write!(f, "once"),
+            Self::Never => write!(f, "never"),
+        }
+    }
+}
+
+/// Send a request to the wallet daemon listening on the given socket and
+/// wait for its response. The daemon may wait for its user to confirm the
+/// request before responding.
+#[cfg(unix)]
+pub async fn request(
+    socket_path: &std::path::Path,
+    request: &DaemonRequest,
+) -> std::io::Result<DaemonResponse> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::UnixStream;
+
+    let mut stream = UnixStream::connect(socket_path).await?;
+    let mut line = serde_json::to_string(request)?;
+    line.push('\n');
+    stream.write_all(line.as_bytes()).await?;
+    let mut response = String::new();
+    BufReader::new(stream).read_line(&mut response).await?;
+    Ok(serde_json::from_str(&response)?)
+}
+
+/// Ask the wallet daemon listening on the given socket to sign the digest
+/// with the secret key of the given public key
+#[cfg(unix)]
+pub async fn sign(
+    socket_path: &std::path::Path,
+    public_key: &common::PublicKey,
+    digest: Hash,
+) -> Result<common::Signature, crate::error::Error> {
+    let sign_request = DaemonRequest::Sign {
+        public_key: public_key.clone(),
+        digest,
+    };
+    match request(socket_path, &sign_request).await {
+        Ok(DaemonResponse::Signature { signature }) => Ok(signature),
+        Ok(DaemonResponse::Error { message }) => {
+            Err(crate::error::Error::Other(format!(
+                "The wallet daemon didn't sign with {}: {}",
+                public_key, message
+            )))
+        }
+        Ok(response) => Err(crate::error::Error::Other(format!(
+            "Unexpected response of the wallet daemon: {:?}",
+            response
+        ))),
+        Err(err) => Err(crate::error::Error::Other(format!(
+            "Unable to reach the wallet daemon at {}: {}",
+            socket_path.display(),
+            err
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use namada_core::types::key::testing::keypair_1;
+    use namada_core::types::key::{RefTo, SigScheme};
+
+    use super::*;
+
+    /// Test that the requests and responses are encoded on a single line.
+    #[test]
+    fn test_daemon_messages_roundtrip() {
+        let secret_key = keypair_1();
+        let public_key = secret_key.ref_to();
+        let digest = Hash::sha256(b"digest");
+        let messages = [
+            serde_json::to_string(&DaemonRequest::ListKeys).unwrap(),
+            serde_json::to_string(&DaemonRequest::Sign {
+                public_key: public_key.clone(),
+                digest,
+            })
+            .unwrap(),
+            serde_json::to_string(&DaemonResponse::Keys {
+                keys: vec![DaemonKey {
+                    alias: "albert".to_string(),
+                    public_key: public_key.clone(),
+                }],
+            })
+            .unwrap(),
+            serde_json::to_string(&DaemonResponse::Signature {
+                signature: common::SigScheme::sign(&secret_key, digest),
+            })
+            .unwrap(),
+        ];
+        for message in &messages {
+            assert!(!message.contains('\n'));
+        }
+        let request: DaemonRequest =
+            serde_json::from_str(&messages[1]).expect("Test failed");
+        assert_eq!(request, DaemonRequest::Sign { public_key, digest });
+        assert_eq!(
+            serde_json::from_str::<DaemonRequest>(r#"{"request":"list_keys"}"#)
+                .expect("Test failed"),
+            DaemonRequest::ListKeys
+        );
+    }
+
+    /// Test signing with a daemon that responds on a Unix socket, without
+    /// blocking the runtime while waiting for its response.
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_daemon_sign() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+        use tokio::net::UnixListener;
+
+        let secret_key = keypair_1();
+        let public_key = secret_key.ref_to();
+        let digest = Hash::sha256(b"digest");
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("daemon.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+
+        // The daemon runs on the same thread as the client, so it can only
+        // respond if the client yields while waiting
+        let daemon = async {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut lines = BufReader::new(reader).lines();
+            let line = lines.next_line().await.unwrap().unwrap();
+            let response = match serde_json::from_str(&line).unwrap() {
+                DaemonRequest::Sign { digest, .. } => {
+                    DaemonResponse::Signature {
+                        signature: common::SigScheme::sign(&secret_key, digest),
+                    }
+                }
+                DaemonRequest::ListKeys => panic!("Test failed"),
+            };
+            let mut response = serde_json::to_string(&response).unwrap();
+            response.push('\n');
+            writer.write_all(response.as_bytes()).await.unwrap();
+        };
+        let (signature, ()) =
+            tokio::join!(sign(&socket_path, &public_key, digest), daemon);
+        let signature = signature.expect("Test failed");
+        common::SigScheme::verify_signature(&public_key, &digest, &signature)
+            .expect("Test failed");
+
+        // Signing fails when no daemon is listening
+        drop(listener);
+        std::fs::remove_file(&socket_path).unwrap();
+        assert!(sign(&socket_path, &public_key, digest).await.is_err());
+    }
+
+    /// Test the parsing of the confirmation policies.
+    #[test]
+    fn test_confirm_policy_from_str() {
+        for policy in [
+            ConfirmPolicy::Always,
+            ConfirmPolicy::Once,
+            ConfirmPolicy::Never,
+        ] {
+            assert_eq!(policy.to_string().parse(), Ok(policy));
+        }
+        assert!("sometimes".parse::<ConfirmPolicy>().is_err());
+    }
+}
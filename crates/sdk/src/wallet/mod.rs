@@ -1,5 +1,7 @@
 //! Provides functionality for managing keys and addresses for a user
+pub mod address_book;
 pub mod alias;
+pub mod daemon;
 mod derivation_path;
 mod keys;
 pub mod pre_genesis;
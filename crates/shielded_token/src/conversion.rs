@@ -198,9 +198,40 @@ where
     };
     use rayon::prelude::ParallelSlice;
 
+    use crate::storage_key::masp_reward_tokens_key;
+
     // The derived conversions will be placed in MASP address space
     let masp_addr = MASP;
 
+    // Add the tokens that governance made eligible for shielded rewards
+    let reward_tokens: BTreeMap<String, Address> = wl_storage
+        .read(&masp_reward_tokens_key())?
+        .unwrap_or_default();
+    for (alias, token) in reward_tokens {
+        if wl_storage
+            .storage
+            .conversion_state
+            .tokens
+            .values()
+            .any(|addr| *addr == token)
+        {
+            continue;
+        }
+        tracing::info!("Adding {token} to the shielded reward tokens");
+        // The rewards controller of a new reward token starts from zero
+        if !wl_storage.has_key(&masp_last_inflation_key(&token))? {
+            wl_storage
+                .write(&masp_last_inflation_key(&token), Amount::zero())?;
+            wl_storage
+                .write(&masp_last_locked_ratio_key(&token), Dec::zero())?;
+        }
+        wl_storage
+            .storage
+            .conversion_state
+            .tokens
+            .insert(alias, token);
+    }
+
     let mut masp_reward_keys: Vec<_> = wl_storage
         .storage
         .conversion_state
@@ -511,9 +542,65 @@ mod tests {
     ) {
         const ROUNDS: usize = 10;
 
+        let mut s = init_storage(initial_balance, masp_locked_ratio);
+
+        for i in 0..ROUNDS {
+            println!("Round {i}");
+            update_allowed_conversions(&mut s).unwrap();
+            println!();
+            println!();
+        }
+    }
+
+    /// Test that a token added to the reward tokens by governance gets
+    /// conversions from the next epoch
+    #[test]
+    fn test_reward_token_added_by_governance() {
+        let mut s = init_storage(
+            Amount::native_whole(1_000),
+            Dec::from_str("0.5").unwrap(),
+        );
+        let new_token = address::kartoffel();
+
+        // The new token has parameters, but no rewards controller state yet
+        let reward_tokens = s.storage.conversion_state.tokens.clone();
+        s.storage
+            .conversion_state
+            .tokens
+            .retain(|_, token| *token != new_token);
+        s.delete(&masp_last_inflation_key(&new_token)).unwrap();
+        s.delete(&masp_last_locked_ratio_key(&new_token)).unwrap();
+        s.write(
+            &crate::storage_key::masp_reward_tokens_key(),
+            reward_tokens.clone(),
+        )
+        .unwrap();
+
+        update_allowed_conversions(&mut s).unwrap();
+
+        assert_eq!(s.storage.conversion_state.tokens, reward_tokens);
+        assert!(s.has_key(&masp_last_inflation_key(&new_token)).unwrap());
+        assert!(s.has_key(&masp_last_locked_ratio_key(&new_token)).unwrap());
+        assert!(s
+            .storage
+            .conversion_state
+            .assets
+            .values()
+            .any(|((token, _), epoch, _, _)| *token == new_token
+                && *epoch == s.storage.block.epoch));
+    }
+
+    /// Initialize the parameters and all the test tokens, as reward tokens
+    fn init_storage(
+        initial_balance: Amount,
+        masp_locked_ratio: Dec,
+    ) -> TestWlStorage {
         let mut s = TestWlStorage::default();
         let params = Parameters {
             max_tx_bytes: 1024 * 1024,
+            max_tx_memo_bytes: 256,
+            max_tx_sections: 64,
+            max_tx_code_bytes: 600_000,
             epoch_duration: EpochDuration {
                 min_num_of_blocks: 1,
                 min_duration: DurationSecs(3600),
@@ -521,6 +608,7 @@ mod tests {
             max_expected_time_per_block: DurationSecs(3600),
             max_proposal_bytes: Default::default(),
             max_block_gas: 100,
+            max_scheduled_txs_gas: 100,
             vp_whitelist: vec![],
             tx_whitelist: vec![],
             implicit_vp_code_hash: Default::default(),
@@ -577,13 +665,7 @@ mod tests {
                     .insert(alias.to_string(), token_addr.clone());
             }
         }
-
-        for i in 0..ROUNDS {
-            println!("Round {i}");
-            update_allowed_conversions(&mut s).unwrap();
-            println!();
-            println!();
-        }
+        s
     }
 
     pub fn tokens() -> HashMap<Address, (&'static str, Denomination)> {
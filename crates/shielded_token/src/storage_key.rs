@@ -4,8 +4,14 @@ use masp_primitives::bls12_381::Scalar;
 use masp_primitives::sapling::Nullifier;
 use namada_core::types::address::{self, Address};
 use namada_core::types::hash::Hash;
+use namada_core::types::key_space::{ModuleKeySpace, PrefixSeg};
 use namada_core::types::storage::{self, DbKeySeg, KeySeg};
 use namada_trans_token::storage_key::key_of_token;
+pub use namada_trans_token::storage_key::{
+    MASP_KD_GAIN_KEY, MASP_KP_GAIN_KEY, MASP_LAST_INFLATION_KEY,
+    MASP_LAST_LOCKED_RATIO_KEY, MASP_LOCKED_RATIO_TARGET_KEY,
+    MASP_MAX_REWARD_RATE_KEY, MASP_REWARD_PARAMS_KEYS, MASP_REWARD_STATE_KEYS,
+};
 
 /// Key segment prefix for pinned shielded transactions
 pub const PIN_KEY_PREFIX: &str = "pin-";
@@ -17,19 +23,41 @@ pub const MASP_NOTE_COMMITMENT_TREE_KEY: &str = "commitment_tree";
 pub const MASP_NOTE_COMMITMENT_ANCHOR_PREFIX: &str = "note_commitment_anchor";
 /// Key segment prefix for the convert anchor
 pub const MASP_CONVERT_ANCHOR_KEY: &str = "convert_anchor";
-/// Last calculated inflation value handed out
-pub const MASP_LAST_INFLATION_KEY: &str = "last_inflation";
-/// The last locked ratio
-pub const MASP_LAST_LOCKED_RATIO_KEY: &str = "last_locked_ratio";
-/// The key for the nominal proportional gain of a shielded pool for a given
-/// asset
-pub const MASP_KP_GAIN_KEY: &str = "proportional_gain";
-/// The key for the nominal derivative gain of a shielded pool for a given asset
-pub const MASP_KD_GAIN_KEY: &str = "derivative_gain";
-/// The key for the locked ratio target for a given asset
-pub const MASP_LOCKED_RATIO_TARGET_KEY: &str = "locked_ratio_target";
-/// The key for the max reward rate for a given asset
-pub const MASP_MAX_REWARD_RATE_KEY: &str = "max_reward_rate";
+/// Key segment for the set of tokens that receive shielded rewards
+pub const MASP_REWARD_TOKENS_KEY: &str = "reward_tokens";
+
+/// The key prefixes reserved by shielded tokens
+pub fn masp_key_space() -> ModuleKeySpace {
+    let token_keys = MASP_REWARD_PARAMS_KEYS
+        .into_iter()
+        .chain(MASP_REWARD_STATE_KEYS);
+    let key_space = ModuleKeySpace::new("shielded_token")
+        .guarded_by(address::MASP)
+        .reserve_sub_keys(
+            &address::MASP,
+            &[
+                MASP_NULLIFIERS_KEY,
+                MASP_NOTE_COMMITMENT_TREE_KEY,
+                MASP_NOTE_COMMITMENT_ANCHOR_PREFIX,
+                MASP_CONVERT_ANCHOR_KEY,
+                MASP_REWARD_TOKENS_KEY,
+            ],
+            "Shielded pool state",
+        )
+        .reserve(
+            [
+                PrefixSeg::Address(address::MASP),
+                PrefixSeg::StrPrefix(PIN_KEY_PREFIX),
+            ],
+            "Pinned shielded transactions",
+        );
+    token_keys.into_iter().fold(key_space, |key_space, key| {
+        key_space.reserve(
+            [PrefixSeg::AnyAddress, PrefixSeg::Str(key)],
+            "Shielded rewards of tokens",
+        )
+    })
+}
 
 /// Obtain the nominal proportional key for the given token
 pub fn masp_kp_gain_key(token_addr: &Address) -> storage::Key {
@@ -154,3 +182,18 @@ pub fn masp_convert_anchor_key() -> storage::Key {
         .push(&MASP_CONVERT_ANCHOR_KEY.to_owned())
         .expect("Cannot obtain a storage key")
 }
+
+/// Get the key for the set of tokens that receive shielded rewards
+pub fn masp_reward_tokens_key() -> storage::Key {
+    storage::Key::from(address::MASP.to_db_key())
+        .push(&MASP_REWARD_TOKENS_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given storage key is the key for the set of tokens that
+/// receive shielded rewards
+pub fn is_masp_reward_tokens_key(key: &storage::Key) -> bool {
+    matches!(&key.segments[..],
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(key)]
+            if *addr == address::MASP && key == MASP_REWARD_TOKENS_KEY)
+}
@@ -0,0 +1,72 @@
+//! A cache of decoded storage values.
+//!
+//! Some values, like the protocol and PoS parameters or the total supplies of
+//! tokens, are read and decoded from Borsh many times in every block, while
+//! they rarely change. The values read with [`StorageRead::read_cached`] are
+//! kept decoded in this cache, so that they are only decoded again after they
+//! have changed. Only committed values are cached: the values modified in the
+//! write log are always decoded from it, and a cached value is dropped
+//! whenever its key is written to the DB.
+//!
+//! [`StorageRead::read_cached`]: namada_storage::StorageRead::read_cached
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, RwLock};
+
+use namada_core::types::storage::Key;
+
+/// The max number of values kept in the cache. Values aren't cached anymore
+/// once it's full, until the cache is cleared.
+pub const MAX_DECODED_VALUES: usize = 10_000;
+
+/// A cache of the decoded committed values of storage keys
+#[derive(Default)]
+pub struct DecodedCache {
+    values: RwLock<HashMap<Key, Arc<dyn Any + Send + Sync>>>,
+}
+
+impl DecodedCache {
+    /// Get the cached value of a key, if it's cached with the given type
+    pub fn get<T>(&self, key: &Key) -> Option<T>
+    where
+        T: Clone + 'static,
+    {
+        self.values
+            .read()
+            .unwrap()
+            .get(key)?
+            .downcast_ref::<T>()
+            .cloned()
+    }
+
+    /// Cache the decoded committed value of a key, unless the cache is full
+    pub fn insert<T>(&self, key: Key, value: T)
+    where
+        T: Send + Sync + 'static,
+    {
+        let mut values = self.values.write().unwrap();
+        if values.len() < MAX_DECODED_VALUES || values.contains_key(&key) {
+            values.insert(key, Arc::new(value));
+        }
+    }
+
+    /// Drop the cached value of a key whose value in the DB has changed
+    pub fn invalidate(&mut self, key: &Key) {
+        self.values.get_mut().unwrap().remove(key);
+    }
+
+    /// Drop all the cached values
+    pub fn clear(&mut self) {
+        self.values.get_mut().unwrap().clear();
+    }
+}
+
+impl Debug for DecodedCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DecodedCache")
+            .field("len", &self.values.read().unwrap().len())
+            .finish()
+    }
+}
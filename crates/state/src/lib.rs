@@ -1,12 +1,16 @@
 //! Ledger's state storage with key-value backed store and a merkle tree
 
+pub mod decoded_cache;
+pub mod read_cache;
 pub mod wl_storage;
 pub mod write_log;
 
 use core::fmt::Debug;
 use std::cmp::Ordering;
 use std::format;
+use std::sync::atomic::{self, AtomicBool};
 
+pub use decoded_cache::DecodedCache;
 use namada_core::borsh::{BorshDeserialize, BorshSerialize, BorshSerializeExt};
 use namada_core::tendermint::merkle::proof::ProofOps;
 use namada_core::types::address::{
@@ -35,6 +39,7 @@ pub use namada_merkle_tree::{
 use namada_merkle_tree::{Error as MerkleTreeError, MerkleRoot};
 use namada_parameters::{self, EpochDuration, Parameters};
 pub use namada_storage::{Error as StorageError, Result as StorageResult, *};
+pub use read_cache::ReadCache;
 use thiserror::Error;
 use tx_queue::{ExpiredTxsQueue, TxQueue};
 pub use wl_storage::{
@@ -104,6 +109,10 @@ where
     pub eth_events_queue: EthEventsQueue,
     /// How many block heights in the past can the storage be queried
     pub storage_read_past_height_limit: Option<u64>,
+    /// Values of the subspace prefetched from the DB
+    pub read_cache: ReadCache,
+    /// Decoded values of the subspace that are read often
+    pub decoded_cache: DecodedCache,
 }
 
 /// Last committed block
@@ -179,6 +188,21 @@ where
         native_token: Address,
         cache: Option<&D::Cache>,
         storage_read_past_height_limit: Option<u64>,
+    ) -> Self {
+        Self::with_db(
+            D::open(db_path, cache),
+            chain_id,
+            native_token,
+            storage_read_past_height_limit,
+        )
+    }
+
+    /// Create a new instance of the storage with an already opened DB
+    pub fn with_db(
+        db: D,
+        chain_id: ChainId,
+        native_token: Address,
+        storage_read_past_height_limit: Option<u64>,
     ) -> Self {
         let block = BlockStorage {
             tree: MerkleTree::default(),
@@ -189,7 +213,7 @@ where
             results: BlockResults::default(),
         };
         State::<D, H> {
-            db: D::open(db_path, cache),
+            db,
             chain_id,
             block,
             header: None,
@@ -209,6 +233,8 @@ where
             ethereum_height: None,
             eth_events_queue: EthEventsQueue::default(),
             storage_read_past_height_limit,
+            read_cache: ReadCache::default(),
+            decoded_cache: DecodedCache::default(),
         }
     }
 
@@ -326,6 +352,7 @@ where
             self.prune_merkle_tree_stores(&mut batch)?;
         }
         self.db.exec_batch(batch)?;
+        self.read_cache.clear();
         Ok(())
     }
 
@@ -351,7 +378,11 @@ where
             return Ok((None, gas));
         }
 
-        match self.db.read_subspace_val(key)? {
+        let value = match self.read_cache.get(key) {
+            Some(value) => value,
+            None => self.db.read_subspace_val(key)?,
+        };
+        match value {
             Some(v) => {
                 let gas =
                     (key.len() + v.len()) as u64 * STORAGE_ACCESS_GAS_PER_BYTE;
@@ -361,6 +392,59 @@ where
         }
     }
 
+    /// Returns a chunk of `len` bytes starting at `offset` of a value from
+    /// the specified subspace and the gas cost. Only the bytes of the chunk
+    /// are charged for.
+    pub fn read_chunk(
+        &self,
+        key: &Key,
+        offset: u64,
+        len: u64,
+    ) -> Result<(Option<ValueChunk>, u64)> {
+        tracing::debug!("storage read chunk key {}", key);
+        let (present, gas) = self.has_key(key)?;
+        if !present {
+            return Ok((None, gas));
+        }
+
+        let chunk = match self.read_cache.get(key) {
+            Some(value) => {
+                value.map(|value| ValueChunk::new(&value, offset, len))
+            }
+            None => self.db.read_subspace_val_chunk(key, offset, len)?,
+        };
+        match chunk {
+            Some(chunk) => {
+                let gas = (key.len() + chunk.bytes.len()) as u64
+                    * STORAGE_ACCESS_GAS_PER_BYTE;
+                Ok((Some(chunk), gas))
+            }
+            None => Ok((None, key.len() as u64 * STORAGE_ACCESS_GAS_PER_BYTE)),
+        }
+    }
+
+    /// Load the committed values under the given key prefixes into the read
+    /// cache, if it's enabled. This is meant to be run in the background
+    /// ahead of the reads of the values and it stops early once `done` is
+    /// set.
+    pub fn prefetch(&self, prefixes: &[Key], done: &AtomicBool) {
+        if !self.read_cache.is_enabled() {
+            return;
+        }
+        for prefix in prefixes {
+            for (key, value, _gas) in self.db.iter_prefix(Some(prefix)) {
+                if done.load(atomic::Ordering::Relaxed)
+                    || !self.read_cache.has_capacity()
+                {
+                    return;
+                }
+                if let Ok(key) = Key::parse(key) {
+                    self.read_cache.insert(key, Some(value));
+                }
+            }
+        }
+    }
+
     /// Returns a value from the specified subspace at the given height or the
     /// last committed height when 0 and the gas cost.
     pub fn read_with_height(
@@ -434,6 +518,8 @@ where
         let gas = (key.len() + len) as u64 * STORAGE_WRITE_GAS_PER_BYTE;
         let size_diff =
             self.db.write_subspace_val(self.block.height, key, value)?;
+        self.read_cache.invalidate(key);
+        self.decoded_cache.invalidate(key);
         Ok((gas, size_diff))
     }
 
@@ -447,6 +533,8 @@ where
             self.block.tree.delete(key)?;
             deleted_bytes_len =
                 self.db.delete_subspace_val(self.block.height, key)?;
+            self.read_cache.invalidate(key);
+            self.decoded_cache.invalidate(key);
         }
         let gas = (key.len() + deleted_bytes_len as usize) as u64
             * STORAGE_WRITE_GAS_PER_BYTE;
@@ -855,7 +943,10 @@ where
 
     /// Execute write batch.
     pub fn exec_batch(&mut self, batch: D::WriteBatch) -> Result<()> {
-        Ok(self.db.exec_batch(batch)?)
+        self.db.exec_batch(batch)?;
+        self.read_cache.clear();
+        self.decoded_cache.clear();
+        Ok(())
     }
 
     /// Batch write the value with the given height and account subspace key to
@@ -877,6 +968,7 @@ where
             // Update the merkle tree
             self.block.tree.update(key, value)?;
         }
+        self.decoded_cache.invalidate(key);
         Ok(self.db.batch_write_subspace_val(
             batch,
             self.block.height,
@@ -895,11 +987,55 @@ where
     ) -> Result<i64> {
         // Update the merkle tree
         self.block.tree.delete(key)?;
+        self.decoded_cache.invalidate(key);
         Ok(self
             .db
             .batch_delete_subspace_val(batch, self.block.height, key)?)
     }
 
+    /// Batch write or delete (for `None` values) the given account subspace
+    /// keys in the DB at the current height. The Merkle tree is updated with
+    /// the whole batch at once, so that its sub-trees can be hashed in
+    /// parallel. Returns the sum of the size differences.
+    pub fn batch_update_subspace_vals(
+        &mut self,
+        batch: &mut D::WriteBatch,
+        updates: Vec<(&Key, Option<&[u8]>)>,
+    ) -> Result<i64> {
+        // The tree of the bridge pool stores the current height for the
+        // pending transfer
+        let height = self.block.height.serialize_to_vec();
+        let tree_updates = updates
+            .iter()
+            .map(|(key, value)| match value {
+                Some(_) if is_pending_transfer_key(key) => {
+                    (*key, Some(height.as_slice()))
+                }
+                _ => (*key, *value),
+            })
+            .collect();
+        self.block.tree.update_batch(tree_updates)?;
+
+        let mut size_diff = 0;
+        for (key, value) in updates {
+            self.decoded_cache.invalidate(key);
+            size_diff += match value {
+                Some(value) => self.db.batch_write_subspace_val(
+                    batch,
+                    self.block.height,
+                    key,
+                    value,
+                )?,
+                None => self.db.batch_delete_subspace_val(
+                    batch,
+                    self.block.height,
+                    key,
+                )?,
+            };
+        }
+        Ok(size_diff)
+    }
+
     // Prune merkle tree stores. Use after updating self.block.height in the
     // commit.
     fn prune_merkle_tree_stores(
@@ -1044,6 +1180,21 @@ where
             raw_key.parse().expect("Failed hash conversion")
         }))
     }
+
+    /// Read the height of the block that included the tx with the given hash
+    pub fn read_tx_height(&self, hash: &Hash) -> Result<Option<BlockHeight>> {
+        Ok(self.db.read_tx_height(hash)?)
+    }
+
+    /// Index the current block height for the tx with the given hash
+    pub fn write_tx_height(
+        &mut self,
+        batch: &mut D::WriteBatch,
+        hash: &Hash,
+    ) -> Result<()> {
+        self.db.write_tx_height(batch, hash, self.block.height)?;
+        Ok(())
+    }
 }
 
 impl From<MerkleTreeError> for Error {
@@ -1104,6 +1255,8 @@ pub mod testing {
                 ethereum_height: None,
                 eth_events_queue: EthEventsQueue::default(),
                 storage_read_past_height_limit: Some(1000),
+                read_cache: ReadCache::default(),
+                decoded_cache: DecodedCache::default(),
             }
         }
     }
@@ -1204,8 +1357,12 @@ mod tests {
             };
             let mut parameters = Parameters {
                 max_tx_bytes: 1024 * 1024,
+                max_tx_memo_bytes: 256,
+                max_tx_sections: 64,
+                max_tx_code_bytes: 600_000,
                 max_proposal_bytes: Default::default(),
                 max_block_gas: 20_000_000,
+                max_scheduled_txs_gas: 2_000_000,
                 epoch_duration: epoch_duration.clone(),
                 max_expected_time_per_block: Duration::seconds(max_expected_time_per_block).into(),
                 vp_whitelist: vec![],
@@ -1342,4 +1499,72 @@ mod tests {
             assert_eq!(wl_storage.storage.block.epoch, epoch_before.next());
         }
     }
+
+    /// Test that the expected time of the blocks until an epoch switch is
+    /// deducted from the minimum duration of the next epoch.
+    #[test]
+    fn update_epoch_deducts_switch_delay() {
+        let mut wl_storage = TestWlStorage::default();
+        let parameters = Parameters {
+            max_tx_bytes: 1024 * 1024,
+            max_tx_memo_bytes: 256,
+            max_tx_sections: 64,
+            max_tx_code_bytes: 600_000,
+            max_proposal_bytes: Default::default(),
+            max_block_gas: 20_000_000,
+            max_scheduled_txs_gas: 2_000_000,
+            epoch_duration: EpochDuration {
+                min_num_of_blocks: 1,
+                min_duration: Duration::seconds(60).into(),
+            },
+            max_expected_time_per_block: Duration::seconds(30).into(),
+            vp_whitelist: vec![],
+            tx_whitelist: vec![],
+            implicit_vp_code_hash: Some(Hash::zero()),
+            epochs_per_year: 100,
+            max_signatures_per_transaction: 15,
+            staked_ratio: Dec::new(1, 1).expect("Cannot fail"),
+            pos_inflation_amount: token::Amount::zero(),
+            fee_unshielding_gas_limit: 20_000,
+            fee_unshielding_descriptions_limit: 15,
+            minimum_gas_price: BTreeMap::default(),
+        };
+        namada_parameters::init_storage(&parameters, &mut wl_storage).unwrap();
+
+        // Blocks with 4s, 5s and 7s between them
+        let start: DateTimeUtc = Utc
+            .timestamp_opt(1000, 0)
+            .single()
+            .expect("expected valid timestamp")
+            .into();
+        for secs in [0, 4, 9, 16] {
+            namada_parameters::record_block_time(
+                &mut wl_storage,
+                start + Duration::seconds(secs),
+            )
+            .unwrap();
+        }
+        let stats = namada_parameters::read_block_time_stats(&wl_storage)
+            .unwrap()
+            .unwrap();
+        assert_eq!(stats.num_blocks, 4);
+        assert_eq!(
+            std::time::Duration::from(stats.average_block_time),
+            std::time::Duration::from_secs(16) / 3
+        );
+        assert_eq!(
+            std::time::Duration::from(stats.median_block_time),
+            std::time::Duration::from_secs(5)
+        );
+        assert_eq!(stats.median_time, start + Duration::seconds(9));
+
+        // Begin a new epoch
+        wl_storage.storage.update_epoch_blocks_delay = Some(1);
+        let time = start + Duration::seconds(16);
+        assert!(wl_storage.update_epoch(BlockHeight(4), time).unwrap());
+        assert_eq!(
+            wl_storage.storage.next_epoch_min_start_time,
+            time + Duration::seconds(60 - 2 * 5)
+        );
+    }
 }
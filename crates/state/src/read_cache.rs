@@ -0,0 +1,106 @@
+//! A cache of storage values prefetched from the DB.
+//!
+//! The keys that the VPs of a tx are likely to read (the subspaces and the
+//! balances of the accounts that signed it and the protocol parameters) are
+//! known before the tx is executed. Their values can therefore be loaded from
+//! the DB on the shared thread pool while the tx is running, so that the VPs
+//! don't wait on cold disk reads. Only committed values are cached, which is
+//! the same data that the DB would return, so the cache doesn't affect the
+//! results or the gas of the reads. It is cleared whenever the DB is written
+//! to.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use namada_core::types::storage::Key;
+
+/// The max number of values kept in the cache. Prefetching stops once it's
+/// full until the cache is cleared on the next commit.
+pub const MAX_CACHED_VALUES: usize = 100_000;
+
+/// A cache of the committed values of storage keys
+#[derive(Debug, Default)]
+pub struct ReadCache {
+    /// Whether the values are prefetched
+    enabled: bool,
+    /// The cached values, `None` for keys without a value in the DB
+    values: RwLock<HashMap<Key, Option<Vec<u8>>>>,
+}
+
+impl ReadCache {
+    /// Enable or disable the prefetching of values. Disabling also clears
+    /// the cache.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.clear();
+        }
+    }
+
+    /// Check if the prefetching of values is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Get the cached value of a key. Returns `None` if the key isn't cached.
+    pub fn get(&self, key: &Key) -> Option<Option<Vec<u8>>> {
+        self.values.read().unwrap().get(key).cloned()
+    }
+
+    /// Check if there's still space in the cache
+    pub fn has_capacity(&self) -> bool {
+        self.values.read().unwrap().len() < MAX_CACHED_VALUES
+    }
+
+    /// Cache the committed value of a key, unless the cache is full
+    pub fn insert(&self, key: Key, value: Option<Vec<u8>>) {
+        let mut values = self.values.write().unwrap();
+        if values.len() < MAX_CACHED_VALUES {
+            values.insert(key, value);
+        }
+    }
+
+    /// Drop the cached value of a key whose value in the DB has changed
+    pub fn invalidate(&mut self, key: &Key) {
+        self.values.get_mut().unwrap().remove(key);
+    }
+
+    /// Drop all the cached values
+    pub fn clear(&mut self) {
+        self.values.get_mut().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+
+    use namada_core::types::address::testing::established_address_1;
+
+    use crate::testing::TestStorage;
+    use crate::{Key, KeySeg};
+
+    #[test]
+    fn test_prefetch_and_invalidate() {
+        let mut storage = TestStorage::default();
+        let owner = established_address_1();
+        let prefix = Key::from(owner.to_db_key());
+        let key = prefix.push(&"threshold".to_owned()).unwrap();
+        storage.write(&key, [1]).unwrap();
+
+        // Nothing is prefetched unless enabled
+        let done = AtomicBool::new(false);
+        storage.prefetch(&[prefix.clone()], &done);
+        assert_eq!(storage.read_cache.get(&key), None);
+
+        storage.read_cache.set_enabled(true);
+        storage.prefetch(&[prefix.clone()], &done);
+        assert_eq!(storage.read_cache.get(&key), Some(Some(vec![1])));
+        assert_eq!(storage.read(&key).unwrap().0, Some(vec![1]));
+
+        // Writing the key drops its cached value
+        storage.write(&key, [2]).unwrap();
+        assert_eq!(storage.read_cache.get(&key), None);
+        assert_eq!(storage.read(&key).unwrap().0, Some(vec![2]));
+    }
+}
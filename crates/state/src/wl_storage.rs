@@ -5,7 +5,7 @@ use std::iter::Peekable;
 use namada_core::types::address::Address;
 use namada_core::types::hash::{Hash, StorageHasher};
 use namada_core::types::storage::{self, BlockHeight, Epochs};
-use namada_core::types::time::DateTimeUtc;
+use namada_core::types::time::{DateTimeUtc, DurationSecs};
 use namada_parameters::EpochDuration;
 use namada_storage::{ResultExt, StorageRead, StorageWrite};
 
@@ -31,6 +31,10 @@ where
 /// to the DB. This is useful for the shell `PrepareProposal` and
 /// `ProcessProposal` handlers that should not change state, but need to apply
 /// storage changes for replay protection to validate the proposal.
+///
+/// It's also the in-memory overlay returned by [`WlStorage::fork`] for
+/// speculative execution, whose changes can be either merged back into the
+/// forked storage or discarded.
 #[derive(Debug)]
 pub struct TempWlStorage<'a, D, H>
 where
@@ -77,6 +81,13 @@ where
     ) -> Result<bool, super::Error> {
         self.storage.has_replay_protection_entry(hash)
     }
+
+    /// Take the write log with the speculative changes, to merge them back
+    /// into the forked storage with [`WlStorage::merge`]. To discard the
+    /// changes instead, simply drop the overlay.
+    pub fn into_write_log(self) -> WriteLog {
+        self.write_log
+    }
 }
 
 /// Common trait for [`WlStorage`] and [`TempWlStorage`], used to implement
@@ -173,6 +184,26 @@ where
         Self { write_log, storage }
     }
 
+    /// Fork an in-memory overlay of the storage for speculative execution.
+    /// The overlay shares the committed state in the DB and the pending
+    /// changes of the block in the write log, which are only copied if the
+    /// overlay commits a tx of its own. Its changes can be merged back with
+    /// [`WlStorage::merge`], or discarded by dropping it.
+    pub fn fork(&self) -> TempWlStorage<'_, D, H> {
+        TempWlStorage {
+            write_log: self.write_log.clone(),
+            storage: &self.storage,
+        }
+    }
+
+    /// Merge the changes of an overlay created with [`WlStorage::fork`],
+    /// taken with [`TempWlStorage::into_write_log`]. The overlay started from
+    /// the pending changes of this storage, so its write log replaces the
+    /// current one.
+    pub fn merge(&mut self, write_log: WriteLog) {
+        self.write_log = write_log;
+    }
+
     /// Commit the current transaction's write log to the block when it's
     /// accepted by all the triggered validity predicates. Starts a new
     /// transaction write log.
@@ -247,7 +278,21 @@ where
             } = parameters.epoch_duration;
             self.storage.next_epoch_min_start_height =
                 height + min_num_of_blocks;
-            self.storage.next_epoch_min_start_time = time + min_duration;
+            // An epoch begins a few blocks after its minimum duration has
+            // passed. To keep the epochs' lengths stable under variable block
+            // times, the expected time of these blocks is deducted from the
+            // minimum duration of the next epoch.
+            let switch_delay = namada_parameters::read_block_time_stats(self)
+                .expect("Couldn't read the block time statistics")
+                .map(|stats| {
+                    std::time::Duration::from(stats.median_block_time)
+                        * EPOCH_SWITCH_BLOCKS_DELAY
+                })
+                .unwrap_or_default();
+            let min_duration = std::time::Duration::from(min_duration)
+                .saturating_sub(switch_delay);
+            self.storage.next_epoch_min_start_time =
+                time + DurationSecs::from(min_duration);
 
             self.storage.block.pred_epochs.new_epoch(height);
             tracing::info!("Began a new epoch {}", self.storage.block.epoch);
@@ -466,6 +511,33 @@ macro_rules! impl_storage_traits {
                 }
             }
 
+            fn read_cached<T>(
+                &self,
+                key: &storage::Key,
+            ) -> namada_storage::Result<Option<T>>
+            where
+                T: namada_core::borsh::BorshDeserialize
+                    + Clone
+                    + Send
+                    + Sync
+                    + 'static,
+            {
+                // only the committed values are cached, the values modified
+                // in the write log are decoded from it
+                if self.write_log().read(key).0.is_some() {
+                    return self.read(key);
+                }
+                let cache = &self.storage().decoded_cache;
+                if let Some(value) = cache.get::<T>(key) {
+                    return Ok(Some(value));
+                }
+                let value: Option<T> = self.read(key)?;
+                if let Some(value) = value.as_ref() {
+                    cache.insert(key.clone(), value.clone());
+                }
+                Ok(value)
+            }
+
             fn has_key(&self, key: &storage::Key) -> namada_storage::Result<bool> {
                 // try to read from the write log first
                 let (log_val, _gas) = self.write_log().read(key);
@@ -612,6 +684,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_fork_merge_and_discard() {
+        let mut s = TestWlStorage::default();
+        let key = storage::Key::parse("key").unwrap();
+        let other_key = storage::Key::parse("other_key").unwrap();
+        s.write(&key, 1_u64).unwrap();
+
+        // A fork sees the pending changes, but its own changes don't leak
+        // into the forked storage
+        let mut fork = s.fork();
+        assert_eq!(fork.read::<u64>(&key).unwrap(), Some(1));
+        fork.write(&key, 2_u64).unwrap();
+        fork.write(&other_key, 3_u64).unwrap();
+        assert_eq!(fork.read::<u64>(&key).unwrap(), Some(2));
+
+        // Discarding the fork leaves the storage untouched
+        drop(fork);
+        assert_eq!(s.read::<u64>(&key).unwrap(), Some(1));
+        assert_eq!(s.read::<u64>(&other_key).unwrap(), None);
+
+        // Merging the fork adopts its changes
+        let mut fork = s.fork();
+        fork.write(&key, 2_u64).unwrap();
+        fork.write(&other_key, 3_u64).unwrap();
+        let write_log = fork.into_write_log();
+        s.merge(write_log);
+        assert_eq!(s.read::<u64>(&key).unwrap(), Some(2));
+        assert_eq!(s.read::<u64>(&other_key).unwrap(), Some(3));
+    }
+
+    #[test]
+    fn test_read_cached() {
+        let mut s = TestWlStorage::default();
+        let key = storage::Key::parse("key").unwrap();
+        s.storage.write(&key, 1_u64.serialize_to_vec()).unwrap();
+
+        // Committed values are cached once read
+        assert_eq!(s.storage.decoded_cache.get::<u64>(&key), None);
+        assert_eq!(s.read_cached::<u64>(&key).unwrap(), Some(1));
+        assert_eq!(s.storage.decoded_cache.get::<u64>(&key), Some(1));
+
+        // Values modified in the write log are decoded from it
+        s.write_log.write(&key, 2_u64.serialize_to_vec()).unwrap();
+        assert_eq!(s.read_cached::<u64>(&key).unwrap(), Some(2));
+        assert_eq!(s.storage.decoded_cache.get::<u64>(&key), Some(1));
+        s.write_log.drop_tx();
+        assert_eq!(s.read_cached::<u64>(&key).unwrap(), Some(1));
+
+        // Writing the committed value drops the cached one
+        s.storage.write(&key, 3_u64.serialize_to_vec()).unwrap();
+        assert_eq!(s.storage.decoded_cache.get::<u64>(&key), None);
+        assert_eq!(s.read_cached::<u64>(&key).unwrap(), Some(3));
+    }
+
     /// Check the `prefix_iter_pre` and `prefix_iter_post` return expected
     /// values, generated in the input to this function
     fn test_prefix_iters_aux(kvs: Vec<KeyVal<i8>>) {
@@ -2,6 +2,7 @@
 //! before they are committed to the ledger's storage.
 
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 
 use itertools::Itertools;
 use namada_core::ledger::replay_protection;
@@ -13,7 +14,8 @@ use namada_core::types::ibc::IbcEvent;
 use namada_core::types::storage;
 use namada_gas::{MEMORY_ACCESS_GAS_PER_BYTE, STORAGE_WRITE_GAS_PER_BYTE};
 use namada_trans_token::storage_key::{
-    is_any_minted_balance_key, is_any_minter_key, is_any_token_balance_key,
+    is_any_minted_balance_key, is_any_minter_key, is_any_shielded_rewards_key,
+    is_any_token_balance_key,
 };
 use thiserror::Error;
 
@@ -78,14 +80,69 @@ enum ReProtStorageModification {
     Finalize,
 }
 
+/// The storage modifications of a single transaction, moved out of a
+/// [`WriteLog`]
+#[derive(Debug, Clone, Default)]
+pub struct TxWriteLog {
+    precommit: HashMap<storage::Key, StorageModification>,
+    tx: HashMap<storage::Key, StorageModification>,
+}
+
+impl TxWriteLog {
+    /// Iterate the keys modified by the transaction
+    pub fn keys(&self) -> impl Iterator<Item = &storage::Key> {
+        self.precommit.keys().chain(self.tx.keys())
+    }
+
+    /// Check if the transaction initialized any account, whose address
+    /// depends on the address generator of the write log it was applied on
+    pub fn has_initialized_accounts(&self) -> bool {
+        self.precommit
+            .values()
+            .chain(self.tx.values())
+            .any(|modification| {
+                matches!(modification, StorageModification::InitAccount { .. })
+            })
+    }
+}
+
+/// The keys read through a [`WriteLog`], recorded to check if a transaction
+/// applied on a copy of the write log depends on the writes of another one
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReadSet {
+    /// The keys that were read
+    pub keys: BTreeSet<storage::Key>,
+    /// The prefixes of the keys that were iterated
+    pub prefixes: BTreeSet<storage::Key>,
+}
+
+impl ReadSet {
+    /// Check if the given key was read, either directly or by iterating a
+    /// prefix. The prefixes are compared as strings, as the storage iterates
+    /// them.
+    pub fn contains(&self, key: &storage::Key) -> bool {
+        self.keys.contains(key)
+            || (!self.prefixes.is_empty() && {
+                let key = key.to_string();
+                self.prefixes
+                    .iter()
+                    .any(|prefix| key.starts_with(&prefix.to_string()))
+            })
+    }
+}
+
 /// The write log storage
+///
+/// The modifications of the block are shared between the clones of a write
+/// log until one of them changes them, so that a clone of the write log used
+/// as an overlay for speculative execution doesn't copy the whole block.
 #[derive(Debug, Clone)]
 pub struct WriteLog {
     /// The generator of established addresses
     address_gen: Option<EstablishedAddressGen>,
     /// All the storage modification accepted by validity predicates are stored
     /// in block write-log, before being committed to the storage
-    block_write_log: HashMap<storage::Key, StorageModification>,
+    block_write_log: Arc<HashMap<storage::Key, StorageModification>>,
     /// The storage modifications for the current transaction
     tx_write_log: HashMap<storage::Key, StorageModification>,
     /// A precommit bucket for the `tx_write_log`. This is useful for
@@ -101,7 +158,12 @@ pub struct WriteLog {
     ibc_events: BTreeSet<IbcEvent>,
     /// Storage modifications for the replay protection storage, always
     /// committed regardless of the result of the transaction
-    replay_protection: HashMap<Hash, ReProtStorageModification>,
+    replay_protection: Arc<HashMap<Hash, ReProtStorageModification>>,
+    /// Hashes of the txs included in the current block, to be indexed by the
+    /// block height on commit
+    tx_heights: Vec<Hash>,
+    /// When recording, the keys read through this write log and its clones
+    read_set: Option<Arc<Mutex<ReadSet>>>,
 }
 
 /// Write log prefix iterator
@@ -124,11 +186,13 @@ impl Default for WriteLog {
     fn default() -> Self {
         Self {
             address_gen: None,
-            block_write_log: HashMap::with_capacity(100_000),
+            block_write_log: Arc::new(HashMap::with_capacity(100_000)),
             tx_write_log: HashMap::with_capacity(100),
             tx_precommit_write_log: HashMap::with_capacity(100),
             ibc_events: BTreeSet::new(),
-            replay_protection: HashMap::with_capacity(1_000),
+            replay_protection: Arc::new(HashMap::with_capacity(1_000)),
+            tx_heights: Vec::with_capacity(1_000),
+            read_set: None,
         }
     }
 }
@@ -140,6 +204,7 @@ impl WriteLog {
         &self,
         key: &storage::Key,
     ) -> (Option<&StorageModification>, u64) {
+        self.record_read(key);
         // try to read from tx write log first
         match self
             .tx_write_log
@@ -178,6 +243,7 @@ impl WriteLog {
         &self,
         key: &storage::Key,
     ) -> (Option<&StorageModification>, u64) {
+        self.record_read(key);
         match self.block_write_log.get(key) {
             Some(v) => {
                 let gas = match v {
@@ -243,8 +309,7 @@ impl WriteLog {
         key: &storage::Key,
         value: Vec<u8>,
     ) -> Result<()> {
-        if let Some(prev) = self
-            .block_write_log
+        if let Some(prev) = Arc::make_mut(&mut self.block_write_log)
             .insert(key.clone(), StorageModification::Write { value })
         {
             match prev {
@@ -335,8 +400,7 @@ impl WriteLog {
         if key.is_validity_predicate().is_some() {
             return Err(Error::DeleteVp);
         }
-        if let Some(prev) = self
-            .block_write_log
+        if let Some(prev) = Arc::make_mut(&mut self.block_write_log)
             .insert(key.clone(), StorageModification::Delete)
         {
             match prev {
@@ -472,7 +536,7 @@ impl WriteLog {
             HashMap::with_capacity(100),
         );
 
-        self.block_write_log.extend(tx_precommit_write_log);
+        Arc::make_mut(&mut self.block_write_log).extend(tx_precommit_write_log);
         self.take_ibc_events();
     }
 
@@ -484,6 +548,54 @@ impl WriteLog {
         self.tx_write_log.clear();
     }
 
+    /// Take the current transaction's write log and precommit, leaving both
+    /// empty. Used to move the modifications of a transaction applied on a
+    /// copy of this write log back into the original with
+    /// [`WriteLog::restore_tx`].
+    pub fn take_tx(&mut self) -> TxWriteLog {
+        TxWriteLog {
+            precommit: std::mem::take(&mut self.tx_precommit_write_log),
+            tx: std::mem::take(&mut self.tx_write_log),
+        }
+    }
+
+    /// Add the modifications of a transaction previously taken with
+    /// [`WriteLog::take_tx`] to the current transaction's write log and
+    /// precommit. The transaction can then be committed or dropped as if it
+    /// had been applied directly on this write log.
+    pub fn restore_tx(&mut self, tx: TxWriteLog) {
+        self.tx_precommit_write_log.extend(tx.precommit);
+        self.tx_write_log.extend(tx.tx);
+    }
+
+    /// Start recording the keys read through this write log and its clones,
+    /// either directly or by iterating a prefix. A previous recording is
+    /// discarded.
+    pub fn record_reads(&mut self) {
+        self.read_set = Some(Default::default());
+    }
+
+    /// Stop recording the reads and return the keys read since
+    /// [`WriteLog::record_reads`] was called
+    pub fn take_read_set(&mut self) -> ReadSet {
+        self.read_set
+            .take()
+            .map(|read_set| std::mem::take(&mut *read_set.lock().unwrap()))
+            .unwrap_or_default()
+    }
+
+    fn record_read(&self, key: &storage::Key) {
+        if let Some(read_set) = &self.read_set {
+            read_set.lock().unwrap().keys.insert(key.clone());
+        }
+    }
+
+    fn record_prefix_read(&self, prefix: &storage::Key) {
+        if let Some(read_set) = &self.read_set {
+            read_set.lock().unwrap().prefixes.insert(prefix.clone());
+        }
+    }
+
     /// Drop the current transaction's write log but keep the precommit one.
     /// This is useful only when a part of a transaction failed but it can still
     /// be valid and we want to keep the changes applied before the failed
@@ -503,27 +615,24 @@ impl WriteLog {
         D: 'static + DB + for<'iter> DBIter<'iter>,
         H: StorageHasher,
     {
-        for (key, entry) in self.block_write_log.iter() {
-            match entry {
+        let updates = self
+            .block_write_log
+            .iter()
+            .filter_map(|(key, entry)| match entry {
                 StorageModification::Write { value } => {
-                    storage
-                        .batch_write_subspace_val(batch, key, value.clone())
-                        .map_err(Error::StorageError)?;
-                }
-                StorageModification::Delete => {
-                    storage
-                        .batch_delete_subspace_val(batch, key)
-                        .map_err(Error::StorageError)?;
+                    Some((key, Some(value.as_slice())))
                 }
+                StorageModification::Delete => Some((key, None)),
                 StorageModification::InitAccount { vp_code_hash } => {
-                    storage
-                        .batch_write_subspace_val(batch, key, *vp_code_hash)
-                        .map_err(Error::StorageError)?;
+                    Some((key, Some(vp_code_hash.as_ref())))
                 }
                 // temporary value isn't persisted
-                StorageModification::Temp { .. } => {}
-            }
-        }
+                StorageModification::Temp { .. } => None,
+            })
+            .collect();
+        storage
+            .batch_update_subspace_vals(batch, updates)
+            .map_err(Error::StorageError)?;
 
         for (hash, entry) in self.replay_protection.iter() {
             match entry {
@@ -560,11 +669,17 @@ impl WriteLog {
             }
         }
 
+        for hash in self.tx_heights.drain(..) {
+            storage
+                .write_tx_height(batch, &hash)
+                .map_err(Error::StorageError)?;
+        }
+
         if let Some(address_gen) = self.address_gen.take() {
             storage.address_gen = address_gen
         }
-        self.block_write_log.clear();
-        self.replay_protection.clear();
+        Arc::make_mut(&mut self.block_write_log).clear();
+        Arc::make_mut(&mut self.replay_protection).clear();
         Ok(())
     }
 
@@ -602,6 +717,9 @@ impl WriteLog {
             {
                 verifiers
                     .insert(Address::Internal(InternalAddress::Multitoken));
+            } else if is_any_shielded_rewards_key(key).is_some() {
+                // The shielded rewards of tokens are validated by the MASP VP
+                verifiers.insert(Address::Internal(InternalAddress::Masp));
             } else {
                 for addr in key.iter_addresses() {
                     if verifiers_from_tx.contains(addr)
@@ -627,6 +745,7 @@ impl WriteLog {
     /// Iterate modifications prior to the current transaction, whose storage
     /// key matches the given prefix, sorted by their storage key.
     pub fn iter_prefix_pre(&self, prefix: &storage::Key) -> PrefixIter {
+        self.record_prefix_read(prefix);
         let mut matches = BTreeMap::new();
 
         for (key, modification) in &self.block_write_log {
@@ -642,6 +761,7 @@ impl WriteLog {
     /// Iterate modifications posterior of the current tx, whose storage key
     /// matches the given prefix, sorted by their storage key.
     pub fn iter_prefix_post(&self, prefix: &storage::Key) -> PrefixIter {
+        self.record_prefix_read(prefix);
         let mut matches = BTreeMap::new();
 
         for (key, modification) in &self.block_write_log {
@@ -669,8 +789,7 @@ impl WriteLog {
 
     /// Write the transaction hash
     pub(crate) fn write_tx_hash(&mut self, hash: Hash) -> Result<()> {
-        if self
-            .replay_protection
+        if Arc::make_mut(&mut self.replay_protection)
             .insert(hash, ReProtStorageModification::Write)
             .is_some()
         {
@@ -684,10 +803,15 @@ impl WriteLog {
         Ok(())
     }
 
+    /// Index the height of the current block for the tx with the given hash,
+    /// to be able to find its events later on
+    pub fn index_tx_height(&mut self, hash: Hash) {
+        self.tx_heights.push(hash);
+    }
+
     /// Remove the transaction hash
     pub(crate) fn delete_tx_hash(&mut self, hash: Hash) -> Result<()> {
-        match self
-            .replay_protection
+        match Arc::make_mut(&mut self.replay_protection)
             .insert(hash, ReProtStorageModification::Delete)
         {
             None => Ok(()),
@@ -709,8 +833,7 @@ impl WriteLog {
     /// blocks. This functions should be called at the beginning of the block
     /// processing, before any other replay protection operation is done
     pub fn finalize_tx_hash(&mut self, hash: Hash) -> Result<()> {
-        if self
-            .replay_protection
+        if Arc::make_mut(&mut self.replay_protection)
             .insert(hash, ReProtStorageModification::Finalize)
             .is_some()
         {
@@ -892,6 +1015,103 @@ mod tests {
         assert_matches!(result, Error::DeleteVp);
     }
 
+    #[test]
+    fn test_take_and_restore_tx() {
+        let mut write_log = WriteLog::default();
+        let key1 =
+            storage::Key::parse("key1").expect("cannot parse the key string");
+        let key2 =
+            storage::Key::parse("key2").expect("cannot parse the key string");
+        let val = "val".as_bytes().to_vec();
+
+        // apply a tx on a copy of the write log
+        let mut copy = write_log.clone();
+        copy.write(&key1, val.clone()).unwrap();
+        copy.precommit_tx();
+        copy.write(&key2, val.clone()).unwrap();
+        let tx = copy.take_tx();
+        assert!(copy.get_keys_with_precommit().is_empty());
+
+        // move its modifications into the original
+        write_log.restore_tx(tx);
+        assert_eq!(
+            write_log.get_keys_with_precommit(),
+            BTreeSet::from([key1.clone(), key2.clone()])
+        );
+        write_log.commit_tx();
+        match write_log.read(&key1).0.expect("no read value") {
+            StorageModification::Write { value } => assert_eq!(*value, val),
+            _ => panic!("unexpected read result"),
+        }
+        assert!(write_log.read(&key2).0.is_some());
+    }
+
+    #[test]
+    fn test_record_reads() {
+        let mut write_log = WriteLog::default();
+        let key1 =
+            storage::Key::parse("key1").expect("cannot parse the key string");
+        let key2 =
+            storage::Key::parse("key2").expect("cannot parse the key string");
+        let prefix =
+            storage::Key::parse("prefix").expect("cannot parse the key string");
+        let sub_key = prefix
+            .push(&"sub".to_string())
+            .expect("cannot push the key segment");
+
+        // nothing is recorded unless enabled
+        let _ = write_log.read(&key1);
+        assert_eq!(write_log.take_read_set(), ReadSet::default());
+
+        // the reads of a copy of the write log are recorded
+        let mut copy = write_log.clone();
+        copy.record_reads();
+        let _ = copy.read(&key1);
+        let _ = copy.iter_prefix_post(&prefix);
+        copy.write(&key2, vec![]).unwrap();
+        let read_set = copy.take_read_set();
+        assert_eq!(read_set.keys, BTreeSet::from([key1.clone()]));
+        assert!(read_set.contains(&key1));
+        assert!(read_set.contains(&sub_key));
+        assert!(!read_set.contains(&key2));
+
+        // the recording is stopped
+        let _ = copy.read_pre(&key2);
+        assert_eq!(copy.take_read_set(), ReadSet::default());
+    }
+
+    #[test]
+    fn test_clone_shares_block_write_log() {
+        let mut write_log = WriteLog::default();
+        let key1 =
+            storage::Key::parse("key1").expect("cannot parse the key string");
+        let key2 =
+            storage::Key::parse("key2").expect("cannot parse the key string");
+        let val = "val".as_bytes().to_vec();
+        write_log.write(&key1, val.clone()).unwrap();
+        write_log.commit_tx();
+
+        // a copy shares the block modifications until it changes them
+        let mut copy = write_log.clone();
+        assert!(Arc::ptr_eq(
+            &write_log.block_write_log,
+            &copy.block_write_log
+        ));
+        copy.write(&key2, val).unwrap();
+        assert!(Arc::ptr_eq(
+            &write_log.block_write_log,
+            &copy.block_write_log
+        ));
+        copy.commit_tx();
+        assert!(!Arc::ptr_eq(
+            &write_log.block_write_log,
+            &copy.block_write_log
+        ));
+        assert!(copy.read(&key2).0.is_some());
+        assert!(write_log.read(&key2).0.is_none());
+        assert!(write_log.read(&key1).0.is_some());
+    }
+
     #[test]
     fn test_commit() {
         let mut storage = crate::testing::TestStorage::default();
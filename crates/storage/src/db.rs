@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
 use namada_core::types::address::EstablishedAddressGen;
 use namada_core::types::hash::{Error as HashError, Hash};
 use namada_core::types::storage::{
@@ -43,6 +44,36 @@ pub enum Error {
 /// A result of a function that may fail
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// A chunk of a storage value, used to read large values (e.g. WASM blobs)
+/// piece by piece with bounded memory
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct ValueChunk {
+    /// The bytes of the value in the requested range. Shorter than requested
+    /// when the range goes past the end of the value.
+    pub bytes: Vec<u8>,
+    /// The length of the whole value
+    pub value_len: u64,
+}
+
+impl ValueChunk {
+    /// Copy the chunk of `len` bytes starting at `offset` out of the given
+    /// value
+    pub fn new(value: &[u8], offset: u64, len: u64) -> Self {
+        let value_len = value.len() as u64;
+        let start = offset.min(value_len);
+        let end = offset.saturating_add(len).min(value_len);
+        Self {
+            bytes: value[start as usize..end as usize].to_vec(),
+            value_len,
+        }
+    }
+
+    /// Check if this is the last chunk of the value
+    pub fn is_last(&self, offset: u64) -> bool {
+        offset.saturating_add(self.bytes.len() as u64) >= self.value_len
+    }
+}
+
 /// The block's state as stored in the database.
 pub struct BlockStateRead {
     /// Merkle tree stores
@@ -159,9 +190,27 @@ pub trait DB: Debug {
     /// Check if the given replay protection entry exists
     fn has_replay_protection_entry(&self, hash: &Hash) -> Result<bool>;
 
+    /// Read the height of the block that included the tx with the given hash
+    fn read_tx_height(&self, hash: &Hash) -> Result<Option<BlockHeight>>;
+
     /// Read the latest value for account subspace key from the DB
     fn read_subspace_val(&self, key: &Key) -> Result<Option<Vec<u8>>>;
 
+    /// Read a chunk of `len` bytes starting at `offset` of the latest value
+    /// for account subspace key from the DB. The default implementation
+    /// reads the whole value, implementations should override it to only
+    /// copy the requested chunk.
+    fn read_subspace_val_chunk(
+        &self,
+        key: &Key,
+        offset: u64,
+        len: u64,
+    ) -> Result<Option<ValueChunk>> {
+        Ok(self
+            .read_subspace_val(key)?
+            .map(|value| ValueChunk::new(&value, offset, len)))
+    }
+
     /// Read the value for account subspace key at the given height from the DB.
     /// In our `PersistentStorage` (rocksdb), to find a value from arbitrary
     /// height requires looking for diffs from the given `height`, possibly
@@ -247,6 +296,14 @@ pub trait DB: Debug {
         batch: &mut Self::WriteBatch,
         key: &Key,
     ) -> Result<()>;
+
+    /// Index the height of the block that included the tx with the given hash
+    fn write_tx_height(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        hash: &Hash,
+        height: BlockHeight,
+    ) -> Result<()>;
 }
 
 /// A database prefix iterator.
@@ -53,6 +53,16 @@ pub trait StorageRead {
         }
     }
 
+    /// Storage read Borsh encoded value of a key that is read often but
+    /// rarely changes, like a parameter. Behaves like [`StorageRead::read`],
+    /// but implementations may keep the decoded value in a cache.
+    fn read_cached<T>(&self, key: &storage::Key) -> Result<Option<T>>
+    where
+        T: BorshDeserialize + Clone + Send + Sync + 'static,
+    {
+        self.read(key)
+    }
+
     /// Storage read raw bytes. It will try to read from the storage.
     fn read_bytes(&self, key: &storage::Key) -> Result<Option<Vec<u8>>>;
 
@@ -23,7 +23,8 @@ use namada_merkle_tree::{
 };
 
 use crate::db::{
-    BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, Error, Result, DB,
+    BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, Error, Result,
+    ValueChunk, DB,
 };
 use crate::tx_queue::TxQueue;
 use crate::types::{KVBytes, PrefixIterator};
@@ -459,11 +460,34 @@ impl DB for MockDB {
         Ok(false)
     }
 
+    fn read_tx_height(&self, hash: &Hash) -> Result<Option<BlockHeight>> {
+        match self.0.borrow().get(&format!("tx_heights/{hash}")) {
+            Some(bytes) => {
+                Ok(Some(types::decode(bytes).map_err(Error::CodingError)?))
+            }
+            None => Ok(None),
+        }
+    }
+
     fn read_subspace_val(&self, key: &Key) -> Result<Option<Vec<u8>>> {
         let key = Key::parse("subspace").map_err(Error::KeyError)?.join(key);
         Ok(self.0.borrow().get(&key.to_string()).cloned())
     }
 
+    fn read_subspace_val_chunk(
+        &self,
+        key: &Key,
+        offset: u64,
+        len: u64,
+    ) -> Result<Option<ValueChunk>> {
+        let key = Key::parse("subspace").map_err(Error::KeyError)?.join(key);
+        Ok(self
+            .0
+            .borrow()
+            .get(&key.to_string())
+            .map(|value| ValueChunk::new(value, offset, len)))
+    }
+
     fn read_subspace_val_with_height(
         &self,
         key: &Key,
@@ -625,6 +649,18 @@ impl DB for MockDB {
 
         Ok(())
     }
+
+    fn write_tx_height(
+        &mut self,
+        _batch: &mut Self::WriteBatch,
+        hash: &Hash,
+        height: BlockHeight,
+    ) -> Result<()> {
+        self.0
+            .borrow_mut()
+            .insert(format!("tx_heights/{hash}"), types::encode(&height));
+        Ok(())
+    }
 }
 
 impl<'iter> DBIter<'iter> for MockDB {
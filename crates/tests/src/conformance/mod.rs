@@ -0,0 +1,49 @@
+//! Conformance tests of the storage keys against checked-in golden files.
+//!
+//! The storage keys are part of the consensus, so any change of the keys
+//! produced by the key constructors of the modules breaks the compatibility
+//! with the existing chains. Each test derives the keys from fixed inputs and
+//! compares their string form with a golden file in the `golden` directory,
+//! which has a `label = key` line per key.
+//!
+//! When a change of the keys is intended, run the tests with the
+//! `NAMADA_UPDATE_GOLDEN` env var set to regenerate the golden files and
+//! commit them together with the change.
+
+mod storage_keys;
+
+use std::path::PathBuf;
+
+use namada::types::storage::Key;
+
+/// When set, the golden files are written instead of checked
+const UPDATE_GOLDEN_ENV_VAR: &str = "NAMADA_UPDATE_GOLDEN";
+
+/// Check that the given labelled keys match the golden file with the given
+/// name, or write the file when [`UPDATE_GOLDEN_ENV_VAR`] is set.
+fn assert_golden(name: &str, keys: &[(&str, Key)]) {
+    let actual: String = keys
+        .iter()
+        .map(|(label, key)| format!("{label} = {key}\n"))
+        .collect();
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/conformance/golden")
+        .join(format!("{name}.txt"));
+    if std::env::var_os(UPDATE_GOLDEN_ENV_VAR).is_some() {
+        std::fs::write(&path, actual).unwrap_or_else(|err| {
+            panic!("Failed to write the golden file {}: {err}", path.display())
+        });
+        return;
+    }
+    let expected = std::fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!("Failed to read the golden file {}: {err}", path.display())
+    });
+    pretty_assertions::assert_eq!(
+        expected,
+        actual,
+        "The keys don't match the golden file {}. If the change of the keys \
+         is intended, re-run the test with {UPDATE_GOLDEN_ENV_VAR}=1 to \
+         update it.",
+        path.display()
+    );
+}
@@ -0,0 +1,198 @@
+use namada::eth_bridge::storage::vote_tallies;
+use namada::governance::storage::keys as gov_keys;
+use namada::proof_of_stake::storage_key as pos_keys;
+use namada::proof_of_stake::types::BondId;
+use namada::token::storage_key as token_keys;
+use namada::types::address::nam;
+use namada::types::address::testing::{
+    established_address_1, established_address_2,
+};
+use namada::types::ethereum_events::EthereumEvent;
+use namada::types::hash::Hash;
+use namada::types::storage::{BlockHeight, Epoch};
+
+use super::assert_golden;
+
+#[test]
+fn test_token_keys() {
+    let token = nam();
+    let owner = established_address_1();
+    let epoch = Epoch(5);
+
+    let flows_key = token_keys::epoch_flows_key(&token, epoch);
+    assert_eq!(
+        token_keys::is_epoch_flows_key(epoch, &flows_key),
+        Some(&token)
+    );
+
+    assert_golden(
+        "token",
+        &[
+            ("balance", token_keys::balance_key(&token, &owner)),
+            ("balance_prefix", token_keys::balance_prefix(&token)),
+            ("minted_balance", token_keys::minted_balance_key(&token)),
+            ("minter", token_keys::minter_key(&token)),
+            ("vesting", token_keys::vesting_key(&token, &owner)),
+            ("denom", token_keys::denom_key(&token)),
+            ("epoch_flows_prefix", token_keys::epoch_flows_prefix(epoch)),
+            ("epoch_flows", flows_key),
+        ],
+    );
+}
+
+#[test]
+fn test_pos_keys() {
+    let bond_id = BondId {
+        source: established_address_1(),
+        validator: established_address_2(),
+    };
+    let validator = &bond_id.validator;
+
+    assert_golden(
+        "pos",
+        &[
+            ("params", pos_keys::params_key()),
+            ("bond", pos_keys::bond_key(&bond_id)),
+            ("unbond", pos_keys::unbond_key(&bond_id)),
+            ("validator_state", pos_keys::validator_state_key(validator)),
+            (
+                "validator_deltas",
+                pos_keys::validator_deltas_key(validator),
+            ),
+            (
+                "validator_consensus_key",
+                pos_keys::validator_consensus_key_key(validator),
+            ),
+            (
+                "validator_commission_rate",
+                pos_keys::validator_commission_rate_key(validator),
+            ),
+            (
+                "validator_total_bonded",
+                pos_keys::validator_total_bonded_key(validator),
+            ),
+            ("total_deltas", pos_keys::total_deltas_key()),
+            (
+                "consensus_validator_set",
+                pos_keys::consensus_validator_set_key(),
+            ),
+            (
+                "below_capacity_validator_set",
+                pos_keys::below_capacity_validator_set_key(),
+            ),
+            (
+                "rewards_counter",
+                pos_keys::rewards_counter_key(
+                    &bond_id.source,
+                    &bond_id.validator,
+                ),
+            ),
+            (
+                "auto_compound",
+                pos_keys::auto_compound_key(
+                    &bond_id.source,
+                    &bond_id.validator,
+                ),
+            ),
+        ],
+    );
+}
+
+#[test]
+fn test_vote_tallies_keys() {
+    let event = EthereumEvent::TransfersToNamada {
+        nonce: 0.into(),
+        transfers: vec![],
+    };
+    let hash: Hash = event.hash().unwrap();
+    let event_keys = vote_tallies::Keys::from(&event);
+    assert!(event_keys == vote_tallies::Keys::from(&hash));
+    let valset_keys = vote_tallies::Keys::from(&Epoch(5));
+
+    assert_golden(
+        "vote_tallies",
+        &[
+            ("eth_msgs_prefix", vote_tallies::eth_msgs_prefix()),
+            ("eth_msg_body", event_keys.body()),
+            ("eth_msg_seen", event_keys.seen()),
+            ("eth_msg_seen_by", event_keys.seen_by()),
+            ("eth_msg_voting_power", event_keys.voting_power()),
+            (
+                "eth_msg_voting_started_epoch",
+                event_keys.voting_started_epoch(),
+            ),
+            ("valset_upds_prefix", vote_tallies::valset_upds_prefix()),
+            ("valset_upd_body", valset_keys.body()),
+            ("valset_upd_seen", valset_keys.seen()),
+            ("valset_upd_seen_by", valset_keys.seen_by()),
+            ("valset_upd_voting_power", valset_keys.voting_power()),
+            (
+                "valset_upd_voting_started_epoch",
+                valset_keys.voting_started_epoch(),
+            ),
+            (
+                "voting_power_snapshot",
+                vote_tallies::voting_power_snapshot_key(Epoch(5)),
+            ),
+            (
+                "eth_event_seen_height",
+                vote_tallies::eth_event_seen_height_key(&hash),
+            ),
+            (
+                "eth_events_seen_in_block",
+                vote_tallies::eth_events_seen_in_block_key(BlockHeight(10)),
+            ),
+        ],
+    );
+}
+
+#[test]
+fn test_governance_keys() {
+    let id = 3;
+    let voter = established_address_1();
+    let delegation = established_address_2();
+
+    assert_golden(
+        "governance",
+        &[
+            ("counter", gov_keys::get_counter_key()),
+            ("proposal_prefix", gov_keys::proposal_prefix()),
+            ("content", gov_keys::get_content_key(id)),
+            ("author", gov_keys::get_author_key(id)),
+            ("proposal_type", gov_keys::get_proposal_type_key(id)),
+            ("start_epoch", gov_keys::get_voting_start_epoch_key(id)),
+            ("end_epoch", gov_keys::get_voting_end_epoch_key(id)),
+            ("grace_epoch", gov_keys::get_grace_epoch_key(id)),
+            ("funds", gov_keys::get_funds_key(id)),
+            ("deposit_state", gov_keys::get_deposit_state_key(id)),
+            ("proposal_code", gov_keys::get_proposal_code_key(id)),
+            ("result", gov_keys::get_proposal_result_key(id)),
+            ("vote_prefix", gov_keys::get_proposal_vote_prefix_key(id)),
+            (
+                "vote",
+                gov_keys::get_vote_proposal_key(id, voter.clone(), delegation),
+            ),
+            (
+                "committing_proposal",
+                gov_keys::get_committing_proposals_key(id, 7),
+            ),
+            ("vote_proxy", gov_keys::get_vote_proxy_key(&voter)),
+            (
+                "proxy_delegator",
+                gov_keys::get_proxy_delegator_key(&delegation, &voter),
+            ),
+            ("execution", gov_keys::get_proposal_execution_key(id)),
+            ("min_fund", gov_keys::get_min_proposal_fund_key()),
+            ("max_code_size", gov_keys::get_max_proposal_code_size_key()),
+            ("min_period", gov_keys::get_min_proposal_voting_period_key()),
+            ("max_period", gov_keys::get_max_proposal_period_key()),
+            ("max_content", gov_keys::get_max_proposal_content_key()),
+            (
+                "min_grace_epoch",
+                gov_keys::get_min_proposal_grace_epoch_key(),
+            ),
+            ("spam_fund_to_pgf", gov_keys::get_spam_fund_to_pgf_key()),
+            ("tally_params", gov_keys::get_tally_params_key()),
+        ],
+    );
+}
@@ -0,0 +1,299 @@
+//! Fault injection helpers for the E2E tests, to test the liveness and the
+//! recovery of the nodes:
+//!
+//! - [`pause`] stops a node's processes with `SIGSTOP` until the returned
+//!   [`Paused`] guard is resumed or dropped.
+//! - [`delay_p2p`] routes a node's P2P connections through local TCP proxies
+//!   that delay all the traffic by an adjustable latency.
+//! - [`corrupt_db`] overwrites parts of the DB files of a stopped node with
+//!   random bytes.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use color_eyre::eyre::Result;
+use eyre::eyre;
+use namada_apps::config::utils::{
+    convert_tm_addr_to_socket_addr, set_ip, set_port,
+};
+use namada_apps::config::Config;
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+use super::setup::{update_actor_config, NamadaCmd, Test, Who};
+
+/// The number of bytes overwritten in every corrupted DB file
+const CORRUPTED_BYTES: usize = 4096;
+/// How often the proxies check if they're stopped when idle
+const PROXY_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A node whose processes are stopped. They're resumed on drop.
+pub struct Paused {
+    pid: String,
+    resumed: bool,
+}
+
+impl Paused {
+    /// Resume the processes of the node
+    pub fn resume(mut self) -> Result<()> {
+        self.resumed = true;
+        signal_group(&self.pid, "CONT")
+    }
+}
+
+impl Drop for Paused {
+    fn drop(&mut self) {
+        if !self.resumed {
+            let _ = signal_group(&self.pid, "CONT");
+        }
+    }
+}
+
+/// Pause a running node with `SIGSTOP`, as if its machine froze. The signal
+/// is sent to the whole process group of the command, which includes the
+/// CometBFT process started by a ledger node.
+pub fn pause(cmd: &NamadaCmd) -> Result<Paused> {
+    let pid = cmd.session.get_process().pid().to_string();
+    signal_group(&pid, "STOP")?;
+    Ok(Paused {
+        pid,
+        resumed: false,
+    })
+}
+
+/// Send a signal to the process group led by the given process
+fn signal_group(pid: &str, signal: &str) -> Result<()> {
+    let status = Command::new("kill")
+        .args([&format!("-{signal}"), "--", &format!("-{pid}")])
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(eyre!("Failed to send SIG{signal} to process group {pid}"))
+    }
+}
+
+/// A TCP proxy that forwards the traffic in both directions after a delay.
+/// It's stopped on drop, closing the open connections.
+pub struct LatencyProxy {
+    addr: SocketAddr,
+    latency_ms: Arc<AtomicU64>,
+    stopped: Arc<AtomicBool>,
+    connections: Arc<Mutex<Vec<TcpStream>>>,
+}
+
+impl LatencyProxy {
+    /// Start a proxy listening on the given address and forwarding to the
+    /// target address
+    pub fn start(
+        listen: SocketAddr,
+        target: SocketAddr,
+        latency: Duration,
+    ) -> Result<Self> {
+        let listener = TcpListener::bind(listen)?;
+        listener.set_nonblocking(true)?;
+        let proxy = Self {
+            addr: listener.local_addr()?,
+            latency_ms: Arc::new(AtomicU64::new(latency.as_millis() as u64)),
+            stopped: Arc::new(AtomicBool::new(false)),
+            connections: Default::default(),
+        };
+
+        let latency_ms = proxy.latency_ms.clone();
+        let stopped = proxy.stopped.clone();
+        let connections = proxy.connections.clone();
+        thread::spawn(move || {
+            while !stopped.load(Ordering::Relaxed) {
+                let inbound = match listener.accept() {
+                    Ok((inbound, _)) => inbound,
+                    Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                        thread::sleep(PROXY_POLL_INTERVAL);
+                        continue;
+                    }
+                    Err(_) => return,
+                };
+                let outbound = match TcpStream::connect(target) {
+                    Ok(outbound) => outbound,
+                    Err(_) => continue,
+                };
+                let streams = inbound.set_nonblocking(false).and_then(|()| {
+                    Ok((
+                        inbound.try_clone()?,
+                        outbound.try_clone()?,
+                        inbound.try_clone()?,
+                        outbound.try_clone()?,
+                    ))
+                });
+                if let Ok((in_read, out_read, in_write, out_write)) = streams {
+                    forward(in_read, out_write, latency_ms.clone());
+                    forward(out_read, in_write, latency_ms.clone());
+                    let mut connections = connections.lock().unwrap();
+                    connections.push(inbound);
+                    connections.push(outbound);
+                }
+            }
+        });
+        Ok(proxy)
+    }
+
+    /// The address the proxy listens on
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Change the latency of the traffic forwarded from now on
+    pub fn set_latency(&self, latency: Duration) {
+        self.latency_ms
+            .store(latency.as_millis() as u64, Ordering::Relaxed);
+    }
+}
+
+impl Drop for LatencyProxy {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        for stream in self.connections.lock().unwrap().drain(..) {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+/// Forward the data read from one stream to the other after the latency.
+/// The reads are timestamped so that the delay doesn't limit the throughput.
+fn forward(mut from: TcpStream, mut to: TcpStream, latency_ms: Arc<AtomicU64>) {
+    let (send, recv) = mpsc::channel::<(Instant, Vec<u8>)>();
+    thread::spawn(move || {
+        let mut buf = [0; 16 * 1024];
+        loop {
+            match from.read(&mut buf) {
+                Ok(0) | Err(_) => return,
+                Ok(len) => {
+                    let latency = Duration::from_millis(
+                        latency_ms.load(Ordering::Relaxed),
+                    );
+                    let chunk = (Instant::now() + latency, buf[..len].to_vec());
+                    if send.send(chunk).is_err() {
+                        return;
+                    }
+                }
+            }
+        }
+    });
+    thread::spawn(move || {
+        for (deadline, chunk) in recv {
+            thread::sleep(deadline.saturating_duration_since(Instant::now()));
+            if to.write_all(&chunk).is_err() {
+                break;
+            }
+        }
+        let _ = to.shutdown(Shutdown::Write);
+    });
+}
+
+/// The proxies delaying the P2P traffic of a node. The node must not run
+/// after they're dropped, as it's only reachable through them.
+pub struct P2pLatency {
+    proxies: Vec<LatencyProxy>,
+}
+
+impl P2pLatency {
+    /// Change the latency of the P2P traffic of the node
+    pub fn set_latency(&self, latency: Duration) {
+        for proxy in &self.proxies {
+            proxy.set_latency(latency);
+        }
+    }
+}
+
+/// Delay all the P2P traffic of a node by the given latency. This must be
+/// called before the node is started.
+///
+/// The node's P2P listen address is moved to a free port and a proxy takes
+/// its place, which delays the connections opened by the peers. The node's
+/// persistent peers are replaced with proxies to them too, which delays the
+/// connections opened by the node.
+pub fn delay_p2p(
+    test: &Test,
+    who: Who,
+    latency: Duration,
+) -> Result<P2pLatency> {
+    let config = Config::load(test.get_base_dir(who), &test.net.chain_id, None);
+    let p2p = &config.ledger.cometbft.p2p;
+    let mut proxies = vec![];
+
+    let listen = convert_tm_addr_to_socket_addr(&p2p.laddr);
+    let node_port = free_port()?;
+    let node_addr = SocketAddr::new([127, 0, 0, 1].into(), node_port);
+    proxies.push(LatencyProxy::start(listen, node_addr, latency)?);
+
+    let mut persistent_peers = Vec::with_capacity(p2p.persistent_peers.len());
+    for peer in &p2p.persistent_peers {
+        let target = convert_tm_addr_to_socket_addr(peer);
+        let proxy = LatencyProxy::start(
+            SocketAddr::new([127, 0, 0, 1].into(), 0),
+            target,
+            latency,
+        )?;
+        let mut peer = peer.clone();
+        set_ip(&mut peer, "127.0.0.1");
+        set_port(&mut peer, proxy.addr().port());
+        persistent_peers.push(peer);
+        proxies.push(proxy);
+    }
+
+    update_actor_config(test, &test.net.chain_id, who, |config| {
+        set_port(&mut config.ledger.cometbft.p2p.laddr, node_port);
+        config.ledger.cometbft.p2p.persistent_peers = persistent_peers;
+        // All the connections come from the proxies
+        config.ledger.cometbft.p2p.allow_duplicate_ip = true;
+    });
+    Ok(P2pLatency { proxies })
+}
+
+/// Find a free local port
+fn free_port() -> Result<u16> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// Corrupt the DB of a stopped node by overwriting the middle of its table
+/// files (or of its write-ahead logs, if nothing was flushed to tables yet)
+/// with random bytes. Returns the corrupted files.
+pub fn corrupt_db(test: &Test, who: Who) -> Result<Vec<PathBuf>> {
+    let config = Config::load(test.get_base_dir(who), &test.net.chain_id, None);
+    let db_dir = config.ledger.db_dir();
+    let files_with_ext = |ext: &str| -> Result<Vec<PathBuf>> {
+        let mut files = vec![];
+        for entry in fs::read_dir(&db_dir)? {
+            let path = entry?.path();
+            if path.extension().map_or(false, |path_ext| path_ext == ext) {
+                files.push(path);
+            }
+        }
+        Ok(files)
+    };
+    let mut files = files_with_ext("sst")?;
+    if files.is_empty() {
+        files = files_with_ext("log")?;
+    }
+    if files.is_empty() {
+        return Err(eyre!("No DB files found in {}", db_dir.display()));
+    }
+
+    for path in &files {
+        let mut file = OpenOptions::new().write(true).open(path)?;
+        let len = file.metadata()?.len();
+        let mut garbage = vec![0; CORRUPTED_BYTES.min(len as usize)];
+        OsRng.fill_bytes(&mut garbage);
+        file.seek(SeekFrom::Start((len - garbage.len() as u64) / 2))?;
+        file.write_all(&garbage)?;
+        file.sync_all()?;
+    }
+    Ok(files)
+}
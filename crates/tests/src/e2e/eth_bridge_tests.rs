@@ -19,6 +19,7 @@ use namada::types::ethereum_events::testing::DAI_ERC20_ETH_ADDRESS;
 use namada::types::ethereum_events::EthAddress;
 use namada::types::storage::{self, Epoch};
 use namada::types::{address, token};
+use namada_apps::cli::exit::FailureClass;
 use namada_apps::config::ethereum_bridge;
 use namada_core::ledger::eth_bridge::ADDRESS as BRIDGE_ADDRESS;
 use namada_core::types::address::Address;
@@ -763,7 +764,7 @@ async fn test_wdai_transfer_implicit_unauthorized() -> Result<()> {
         &token::DenominatedAmount::new(token::Amount::from(10_000), 0u8.into()),
     )?;
     cmd.exp_string(TX_REJECTED)?;
-    cmd.assert_success();
+    cmd.assert_failure_class(FailureClass::Invalid);
 
     // check balances are unchanged after an unsuccessful transfer
     let albert_wdai_balance = find_wrapped_erc20_balance(
@@ -830,7 +831,7 @@ async fn test_wdai_transfer_established_unauthorized() -> Result<()> {
     )?;
     cmd.exp_string(TX_ACCEPTED)?;
     cmd.exp_string(TX_REJECTED)?;
-    cmd.assert_success();
+    cmd.assert_failure_class(FailureClass::Invalid);
 
     // check balances are unchanged after an unsuccessful transfer
     let albert_wdai_balance = find_wrapped_erc20_balance(
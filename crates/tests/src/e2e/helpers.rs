@@ -27,6 +27,7 @@ use namada_apps::config::genesis::templates;
 use namada_apps::config::utils::convert_tm_addr_to_socket_addr;
 use namada_apps::config::{Config, TendermintMode};
 use namada_core::types::token::NATIVE_MAX_DECIMAL_PLACES;
+use namada_sdk::rpc::{self, TxResponse};
 use namada_sdk::wallet::fs::FsWalletUtils;
 use namada_sdk::wallet::Wallet;
 use toml::Value;
@@ -240,12 +241,13 @@ pub fn get_established_addr_from_pregenesis<P: AsRef<Path>>(
     Some(acct.derive_address())
 }
 
-/// Find the address of an account by its alias from the wallet
+/// Find a keypair by its alias from the wallet. The secret key is returned as
+/// the requested type, e.g. `common::SecretKey` or `ed25519::SecretKey`.
 #[allow(dead_code)]
-pub fn find_keypair(
+pub fn find_keypair<SK: SecretKey>(
     test: &Test,
     alias: impl AsRef<str>,
-) -> Result<common::SecretKey> {
+) -> Result<SK> {
     let mut find = run!(
         test,
         Bin::Wallet,
@@ -272,11 +274,18 @@ pub fn find_keypair(
         .unwrap()
         .1;
     let key = format!("{}{}", sk, pk);
-    common::SecretKey::from_str(&key).map_err(|e| {
+    let sk = common::SecretKey::from_str(&key).map_err(|e| {
         eyre!(format!(
             "Key: {} parsed from {}, Error: {}\n\nOutput: {}",
             key, matched, e, unread
         ))
+    })?;
+    sk.try_to_sk().map_err(|e| {
+        eyre!(format!(
+            "Key with alias {} has an unexpected scheme: {}",
+            alias.as_ref(),
+            e
+        ))
     })
 }
 
@@ -371,6 +380,38 @@ pub fn get_height(test: &Test, ledger_address: &str) -> Result<u64> {
     })
 }
 
+/// Wait for the given client command to exit and get the hash of the last
+/// inner tx that it submitted, as a tx may be preceded by a tx revealing the
+/// public key of its fee payer.
+pub fn exp_last_inner_tx_hash(client: &mut NamadaCmd) -> Result<String> {
+    let output = client.exp_eof()?;
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("Inner transaction hash: "))
+        .last()
+        .map(str::to_string)
+        .ok_or_else(|| eyre!("No inner tx hash in the output:\n{output}"))
+}
+
+/// Query the typed response of the applied inner tx with the given hash, to
+/// assert on its result instead of on the output of the client.
+pub fn query_applied_tx(
+    test: &Test,
+    ledger_address: &str,
+    hash: &str,
+) -> Result<TxResponse> {
+    let client =
+        HttpClient::new(ledger_address).expect("Invalid ledger address");
+    test.async_runtime()
+        .block_on(rpc::query_tx_response(
+            &client,
+            rpc::TxEventQuery::Applied(hash),
+        ))
+        .map_err(|err| {
+            eyre!("Failed to query the response of the tx {hash}: {err}")
+        })
+}
+
 /// Sleep until the given height is reached or panic when time out is reached
 /// before the height
 pub fn wait_for_block_height(
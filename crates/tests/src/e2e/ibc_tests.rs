@@ -18,7 +18,9 @@ use std::path::{Path, PathBuf};
 use color_eyre::eyre::Result;
 use eyre::eyre;
 use namada::governance::cli::onchain::PgfFunding;
-use namada::governance::storage::proposal::{PGFIbcTarget, PGFTarget};
+use namada::governance::storage::proposal::{
+    IbcClientRecovery, PGFIbcTarget, PGFTarget,
+};
 use namada::ibc::apps::transfer::types::VERSION as ICS20_VERSION;
 use namada::ibc::clients::tendermint::client_state::ClientState as TmClientState;
 use namada::ibc::clients::tendermint::consensus_state::ConsensusState as TmConsensusState;
@@ -282,6 +284,90 @@ fn run_ledger_ibc_with_hermes() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn ibc_packet_forward_with_hermes() -> Result<()> {
+    let update_genesis =
+        |mut genesis: templates::All<templates::Unvalidated>, base_dir: &_| {
+            genesis.parameters.parameters.epochs_per_year = 31536;
+            setup::set_validators(1, genesis, base_dir, |_| 0)
+        };
+    let (ledger_a, ledger_b, test_a, test_b) = run_two_nets(update_genesis)?;
+    let _bg_ledger_a = ledger_a.background();
+    let _bg_ledger_b = ledger_b.background();
+
+    setup_hermes(&test_a, &test_b)?;
+    let port_id_a = "transfer".parse().unwrap();
+    let port_id_b = "transfer".parse().unwrap();
+    let (channel_id_a, channel_id_b) =
+        create_channel_with_hermes(&test_a, &test_b)?;
+
+    // Start relaying
+    let hermes = run_hermes(&test_a)?;
+    let _bg_hermes = hermes.background();
+
+    // Transfer 100000 from Chain A to Chain B, which forwards it back to the
+    // sender on Chain A
+    std::env::set_var(ENV_VAR_CHAIN_ID, test_a.net.chain_id.to_string());
+    let forward_receiver = find_address(&test_a, ALBERT)?;
+    std::env::set_var(ENV_VAR_CHAIN_ID, test_b.net.chain_id.to_string());
+    let receiver = find_address(&test_b, BERTHA)?;
+    let memo_path = write_forward_memo(
+        &test_a,
+        &forward_receiver,
+        &port_id_b,
+        &channel_id_b,
+        None,
+    )?;
+    transfer(
+        &test_a,
+        ALBERT,
+        receiver.to_string(),
+        NAM,
+        "100000",
+        ALBERT_KEY,
+        &port_id_a,
+        &channel_id_a,
+        Some(&memo_path.to_string_lossy()),
+        None,
+        None,
+        false,
+    )?;
+    wait_for_packet_relay(&port_id_a, &channel_id_a, &test_a)?;
+    wait_for_packet_relay(&port_id_b, &channel_id_b, &test_b)?;
+    check_balances_after_forward(&test_a)?;
+
+    // The forwarded packet will time out and the token will be refunded to
+    // the sender on Chain A
+    let memo_path = write_forward_memo(
+        &test_a,
+        &forward_receiver,
+        &port_id_b,
+        &channel_id_b,
+        Some("1ns"),
+    )?;
+    transfer(
+        &test_a,
+        ALBERT,
+        receiver.to_string(),
+        NAM,
+        "100000",
+        ALBERT_KEY,
+        &port_id_a,
+        &channel_id_a,
+        Some(&memo_path.to_string_lossy()),
+        None,
+        None,
+        false,
+    )?;
+    wait_for_packet_relay(&port_id_a, &channel_id_a, &test_a)?;
+    // wait for the timeout and the refund
+    wait_for_packet_relay(&port_id_b, &channel_id_b, &test_b)?;
+    wait_for_packet_relay(&port_id_a, &channel_id_a, &test_a)?;
+    check_balances_after_forward(&test_a)?;
+
+    Ok(())
+}
+
 #[test]
 fn pgf_over_ibc_with_hermes() -> Result<()> {
     let update_genesis =
@@ -358,6 +444,81 @@ fn pgf_over_ibc_with_hermes() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn ibc_client_recovery() -> Result<()> {
+    let update_genesis =
+        |mut genesis: templates::All<templates::Unvalidated>, base_dir: &_| {
+            genesis.parameters.parameters.epochs_per_year =
+                epochs_per_year_from_min_duration(10);
+            // for the trusting period of the substitute IBC client
+            genesis.parameters.pos_params.pipeline_len = 40;
+            genesis.parameters.parameters.max_proposal_bytes =
+                Default::default();
+            setup::set_validators(1, genesis, base_dir, |_| 0)
+        };
+    let (ledger_a, ledger_b, test_a, test_b) = run_two_nets(update_genesis)?;
+    let _bg_ledger_a = ledger_a.background();
+    let _bg_ledger_b = ledger_b.background();
+
+    // The client of Chain B on Chain A which will expire soon
+    let trusting_period = Duration::from_secs(10);
+    let subject_client_id =
+        create_client_on_a(&test_a, &test_b, Some(trusting_period))?;
+    sleep(trusting_period.as_secs() + 5);
+    // The client can't be updated anymore
+    let height = query_height(&test_b)?;
+    let result =
+        update_client_with_height(&test_b, &test_a, &subject_client_id, height);
+    assert!(result.is_err());
+
+    // The active client to substitute the expired one
+    let substitute_client_id = create_client_on_a(&test_a, &test_b, None)?;
+
+    // Proposal on Chain A
+    let start_epoch = propose_client_recovery(
+        &test_a,
+        &subject_client_id,
+        &substitute_client_id,
+    )?;
+    let rpc_a = get_actor_rpc(&test_a, Who::Validator(0));
+    let mut epoch = get_epoch(&test_a, &rpc_a).unwrap();
+    // Vote
+    while epoch <= start_epoch {
+        sleep(5);
+        epoch = get_epoch(&test_a, &rpc_a).unwrap();
+    }
+    submit_validator_vote(&test_a)?;
+
+    // Keep the substitute client active until the proposal is executed
+    let grace_epoch = start_epoch + 12u64 + 6u64;
+    while epoch < grace_epoch {
+        sleep(5);
+        epoch = get_epoch(&test_a, &rpc_a).unwrap();
+    }
+    let height = query_height(&test_b)?;
+    update_client_with_height(&test_b, &test_a, &substitute_client_id, height)?;
+    // wait for the grace
+    while epoch <= grace_epoch {
+        sleep(5);
+        epoch = get_epoch(&test_a, &rpc_a).unwrap();
+    }
+    sleep(5);
+
+    // The subject client has taken over the state of the substitute client
+    let subject_state = query_client_state(&test_a, &subject_client_id)?;
+    let substitute_state = query_client_state(&test_a, &substitute_client_id)?;
+    assert_eq!(
+        subject_state.latest_height(),
+        substitute_state.latest_height()
+    );
+
+    // The recovered client can be updated again
+    let height = query_height(&test_b)?;
+    update_client_with_height(&test_b, &test_a, &subject_client_id, height)?;
+
+    Ok(())
+}
+
 fn run_two_nets(
     update_genesis: impl FnMut(
         templates::All<templates::Unvalidated>,
@@ -592,7 +753,37 @@ fn create_client(test_a: &Test, test_b: &Test) -> Result<(ClientId, ClientId)> {
     Ok((client_id_a, client_id_b))
 }
 
+/// Create a client of Chain B on Chain A, optionally with the given trusting
+/// period
+fn create_client_on_a(
+    test_a: &Test,
+    test_b: &Test,
+    trusting_period: Option<Duration>,
+) -> Result<ClientId> {
+    let height = query_height(test_b)?;
+    let client_state =
+        make_client_state_with_trusting_period(test_b, height, trusting_period);
+    let height = client_state.latest_height();
+    let message = MsgCreateClient {
+        client_state: client_state.into(),
+        consensus_state: make_consensus_state(test_b, height)?.into(),
+        signer: signer(),
+    };
+    let height_a = submit_ibc_tx(test_a, message, ALBERT, ALBERT_KEY, false)?;
+
+    let events = get_events(test_a, height_a)?;
+    get_client_id_from_events(&events).ok_or(eyre!(TX_FAILED))
+}
+
 fn make_client_state(test: &Test, height: Height) -> TmClientState {
+    make_client_state_with_trusting_period(test, height, None)
+}
+
+fn make_client_state_with_trusting_period(
+    test: &Test,
+    height: Height,
+    trusting_period: Option<Duration>,
+) -> TmClientState {
     let rpc = get_actor_rpc(test, Who::Validator(0));
     let ledger_address = TendermintAddress::from_str(&rpc).unwrap();
     let client = HttpClient::new(ledger_address).unwrap();
@@ -610,14 +801,15 @@ fn make_client_state(test: &Test, height: Height) -> TmClientState {
         .unwrap();
     let unbonding_period = pipeline_len * epoch_duration.min_duration.0;
 
-    let trusting_period = 2 * unbonding_period / 3;
+    let trusting_period = trusting_period
+        .unwrap_or_else(|| Duration::from_secs(2 * unbonding_period / 3));
     let max_clock_drift = Duration::new(60, 0);
     let chain_id = ChainId::from_str(test.net.chain_id.as_str()).unwrap();
 
     TmClientStateType::new(
         chain_id,
         TrustThreshold::default(),
-        Duration::from_secs(trusting_period),
+        trusting_period,
         Duration::from_secs(unbonding_period),
         max_clock_drift,
         height,
@@ -943,6 +1135,26 @@ fn get_channel_proof(
 
 // get the client state, the proof of the client state, and the proof of the
 // consensus state
+fn query_client_state(
+    test: &Test,
+    client_id: &ClientId,
+) -> Result<TmClientState> {
+    let key = client_state_key(client_id);
+    let (value, _) = query_value_with_proof(test, &key, None)?;
+    let cs = match value {
+        Some(v) => Any::decode(&v[..])
+            .map_err(|e| eyre!("Decoding the client state failed: {}", e))?,
+        None => {
+            return Err(eyre!(
+                "The client state doesn't exist: client ID {}",
+                client_id
+            ));
+        }
+    };
+    TmClientState::try_from(cs)
+        .map_err(|e| eyre!("The state should be a TmClientState: {}", e))
+}
+
 fn get_client_states(
     test: &Test,
     client_id: &ClientId,
@@ -1524,6 +1736,28 @@ fn transfer(
     }
 }
 
+/// Write the memo to forward the received token to the receiver
+fn write_forward_memo(
+    test: &Test,
+    receiver: &Address,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    timeout: Option<&str>,
+) -> Result<PathBuf> {
+    let mut forward = serde_json::json!({
+        "receiver": receiver.to_string(),
+        "port": port_id.to_string(),
+        "channel": channel_id.to_string(),
+    });
+    if let Some(timeout) = timeout {
+        forward["timeout"] = timeout.into();
+    }
+    let memo = serde_json::json!({ "forward": forward });
+    let path = test.test_dir.path().join("forward_memo.json");
+    std::fs::write(&path, memo.to_string())?;
+    Ok(path)
+}
+
 fn delegate_token(test: &Test) -> Result<()> {
     std::env::set_var(ENV_VAR_CHAIN_ID, test.net.chain_id.to_string());
     let rpc = get_actor_rpc(test, Who::Validator(0));
@@ -1593,6 +1827,67 @@ fn propose_funding(
     Ok(start_epoch.into())
 }
 
+fn propose_client_recovery(
+    test: &Test,
+    subject_client_id: &ClientId,
+    substitute_client_id: &ClientId,
+) -> Result<Epoch> {
+    std::env::set_var(ENV_VAR_CHAIN_ID, test.net.chain_id.to_string());
+    let recovery = IbcClientRecovery {
+        subject_client_id: subject_client_id.clone(),
+        substitute_client_id: substitute_client_id.clone(),
+    };
+
+    let albert = find_address(test, ALBERT)?;
+    let rpc = get_actor_rpc(test, Who::Validator(0));
+    let epoch = get_epoch(test, &rpc)?;
+    let start_epoch = (epoch.0 + 3) / 3 * 3;
+    let proposal_json_path =
+        prepare_proposal_data(test, 0, albert, recovery, start_epoch);
+
+    let submit_proposal_args = vec![
+        "init-proposal",
+        "--ibc-client-recovery",
+        "--data-path",
+        proposal_json_path.to_str().unwrap(),
+        "--node",
+        &rpc,
+    ];
+    let mut client = run!(test, Bin::Client, submit_proposal_args, Some(40))?;
+    client.exp_string(TX_ACCEPTED)?;
+    client.exp_string(TX_APPLIED_SUCCESS)?;
+    client.assert_success();
+    Ok(start_epoch.into())
+}
+
+fn submit_validator_vote(test: &Test) -> Result<()> {
+    std::env::set_var(ENV_VAR_CHAIN_ID, test.net.chain_id.to_string());
+    let rpc = get_actor_rpc(test, Who::Validator(0));
+
+    let submit_proposal_vote = vec![
+        "vote-proposal",
+        "--proposal-id",
+        "0",
+        "--vote",
+        "yay",
+        "--address",
+        "validator-0",
+        "--node",
+        &rpc,
+    ];
+    let mut client = run_as!(
+        test,
+        Who::Validator(0),
+        Bin::Client,
+        submit_proposal_vote,
+        Some(40)
+    )?;
+    client.exp_string(TX_ACCEPTED)?;
+    client.exp_string(TX_APPLIED_SUCCESS)?;
+    client.assert_success();
+    Ok(())
+}
+
 fn submit_votes(test: &Test) -> Result<()> {
     std::env::set_var(ENV_VAR_CHAIN_ID, test.net.chain_id.to_string());
     let rpc = get_actor_rpc(test, Who::Validator(0));
@@ -1886,6 +2181,27 @@ fn check_balances_after_back(
     Ok(())
 }
 
+/// Check balances after the token has come back to the sender on Chain A
+fn check_balances_after_forward(test_a: &Test) -> Result<()> {
+    std::env::set_var(ENV_VAR_CHAIN_ID, test_a.net.chain_id.to_string());
+    let rpc_a = get_actor_rpc(test_a, Who::Validator(0));
+    // Check the escrowed balance
+    let escrow = Address::Internal(InternalAddress::Ibc).to_string();
+    let query_args = vec![
+        "balance", "--owner", &escrow, "--token", NAM, "--node", &rpc_a,
+    ];
+    let mut client = run!(test_a, Bin::Client, query_args, Some(40))?;
+    client.exp_string("nam: 0")?;
+    // Check the sender balance
+    let query_args = vec![
+        "balance", "--owner", ALBERT, "--token", NAM, "--node", &rpc_a,
+    ];
+    let mut client = run!(test_a, Bin::Client, query_args, Some(40))?;
+    client.exp_string("nam: 2000000")?;
+    client.assert_success();
+    Ok(())
+}
+
 /// Check balances after IBC shielded transfer
 fn check_shielded_balances(
     dest_port_id: &PortId,
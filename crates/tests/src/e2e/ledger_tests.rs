@@ -13,6 +13,7 @@
 use std::path::PathBuf;
 use std::process::Command;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -25,11 +26,13 @@ use namada::governance::storage::proposal::{PGFInternalTarget, PGFTarget};
 use namada::token;
 use namada::types::address::Address;
 use namada::types::storage::Epoch;
+use namada_apps::cli::exit::FailureClass;
 use namada_apps::config::ethereum_bridge;
 use namada_apps::config::utils::convert_tm_addr_to_socket_addr;
 use namada_apps::facade::tendermint_config::net::Address as TendermintAddress;
 use namada_core::types::token::NATIVE_MAX_DECIMAL_PLACES;
 use namada_sdk::masp::fs::FsShieldedUtils;
+use namada_sdk::rpc::InnerTxResult;
 use namada_test_utils::TestWasms;
 use namada_vp_prelude::BTreeSet;
 use serde_json::json;
@@ -37,9 +40,9 @@ use setup::constants::*;
 use setup::Test;
 
 use super::helpers::{
-    epochs_per_year_from_min_duration, get_established_addr_from_pregenesis,
-    get_height, get_pregenesis_wallet, wait_for_block_height,
-    wait_for_wasm_pre_compile,
+    epochs_per_year_from_min_duration, exp_last_inner_tx_hash,
+    get_established_addr_from_pregenesis, get_height, get_pregenesis_wallet,
+    query_applied_tx, wait_for_block_height, wait_for_wasm_pre_compile,
 };
 use super::setup::{get_all_wasms_hashes, set_ethereum_bridge_mode, NamadaCmd};
 use crate::e2e::helpers::{
@@ -47,12 +50,12 @@ use crate::e2e::helpers::{
     is_debug_mode, parse_reached_epoch,
 };
 use crate::e2e::setup::{
-    self, allow_duplicate_ips, default_port_offset, set_validators, sleep, Bin,
-    Who,
+    self, allow_duplicate_ips, default_port_offset, set_validators, sleep,
+    update_actor_config, Bin, Who,
 };
 use crate::strings::{
     LEDGER_SHUTDOWN, LEDGER_STARTED, NON_VALIDATOR_NODE, TX_ACCEPTED,
-    TX_APPLIED_SUCCESS, TX_FAILED, TX_REJECTED, VALIDATOR_NODE,
+    TX_APPLIED_SUCCESS, VALIDATOR_NODE, VALSET_UPDATE_APPLIED,
 };
 use crate::{run, run_as};
 
@@ -453,6 +456,7 @@ fn ledger_txs_and_queries() -> Result<()> {
 
     let multisig_account =
         format!("{},{},{}", BERTHA_KEY, ALBERT_KEY, CHRISTEL_KEY);
+    let multisig_signers = format!("{},{}", BERTHA_KEY, ALBERT_KEY);
 
     let txs_args = vec![
         // 2. Submit a token transfer tx (from an established account)
@@ -561,6 +565,21 @@ fn ledger_txs_and_queries() -> Result<()> {
             "--node",
             &validator_one_rpc,
         ],
+        // 6. Submit a tx to raise the threshold of the multisig account,
+        // signed by enough keys to meet its current threshold
+        vec![
+            "update-account-keys",
+            "--address",
+            "Test-Account-2",
+            "--public-keys",
+            &multisig_account,
+            "--threshold",
+            "3",
+            "--signing-keys",
+            &multisig_signers,
+            "--node",
+            &validator_one_rpc,
+        ],
     ];
 
     for tx_args in &txs_args {
@@ -574,11 +593,18 @@ fn ledger_txs_and_queries() -> Result<()> {
             };
             let mut client = run!(test, Bin::Client, tx_args, Some(40))?;
 
-            if !dry_run {
-                client.exp_string(TX_ACCEPTED)?;
+            if dry_run {
+                client.exp_string(TX_APPLIED_SUCCESS)?;
+                client.assert_success();
+            } else {
+                let hash = exp_last_inner_tx_hash(&mut client)?;
+                client.assert_exit_code(0);
+                let resp = query_applied_tx(&test, &validator_one_rpc, &hash)?;
+                assert!(
+                    matches!(resp.inner_tx_result(), InnerTxResult::Success(_)),
+                    "{resp}"
+                );
             }
-            client.exp_string(TX_APPLIED_SUCCESS)?;
-            client.assert_success();
         }
     }
 
@@ -617,12 +643,12 @@ fn ledger_txs_and_queries() -> Result<()> {
         (
             vec![
                 "query-account",
-                "--owner",
+                "--address",
                 "Test-Account-2",
                 "--node",
                 &validator_one_rpc,
             ],
-            vec!["Threshold: 2"],
+            vec!["Threshold: 3 of 3 keys"],
             // check also as validator node
             false,
         ),
@@ -829,10 +855,16 @@ fn invalid_transactions() -> Result<()> {
     ];
 
     let mut client = run!(test, Bin::Client, tx_args, Some(40))?;
-    client.exp_string(TX_ACCEPTED)?;
-    client.exp_string(TX_REJECTED)?;
-
-    client.assert_success();
+    let hash = exp_last_inner_tx_hash(&mut client)?;
+    client.assert_exit_code(FailureClass::Invalid.exit_code());
+    let resp = query_applied_tx(&test, &validator_one_rpc, &hash)?;
+    let bertha = find_address(&test, BERTHA)?;
+    match resp.inner_tx_result() {
+        InnerTxResult::VpsRejected(inner) => {
+            assert!(inner.vps_result.rejected_vps.contains(&bertha))
+        }
+        _ => panic!("Expected the tx to be rejected by VPs: {resp}"),
+    }
     let mut ledger = bg_ledger.foreground();
     ledger.exp_string("rejected inner txs: 1")?;
 
@@ -881,9 +913,13 @@ fn invalid_transactions() -> Result<()> {
     ];
 
     let mut client = run!(test, Bin::Client, tx_args, Some(40))?;
-    client.exp_string(TX_ACCEPTED)?;
-    client.exp_string(TX_FAILED)?;
-    client.assert_success();
+    let hash = exp_last_inner_tx_hash(&mut client)?;
+    client.assert_exit_code(FailureClass::Invalid.exit_code());
+    let resp = query_applied_tx(&test, &validator_one_rpc, &hash)?;
+    assert!(
+        matches!(resp.inner_tx_result(), InnerTxResult::OtherFailure),
+        "{resp}"
+    );
     Ok(())
 }
 
@@ -1746,6 +1782,118 @@ fn ledger_many_txs_in_a_block() -> Result<()> {
     Ok(())
 }
 
+/// Test that a protocol tx gets into a block when the mempool is saturated
+/// with wrapper txs.
+///
+/// In this test we:
+/// 1. Run the ledger node with a mempool of 2 txs and 5s consensus timeout
+/// 2. Spawn threads that keep submitting token transfer txs, each filling a
+///    whole block, until the mempool rejects them as full
+/// 3. Check that the validator set update protocol tx of the next epoch is
+///    still applied
+#[test]
+fn protocol_txs_in_saturated_mempool() -> Result<()> {
+    const MEMPOOL_SIZE: usize = 2;
+    const NUM_SUBMITTERS: usize = 4;
+
+    let test = Arc::new(setup::network(
+        |genesis, base_dir: &_| {
+            setup::set_validators(1, genesis, base_dir, |_| 0)
+        },
+        // Set 5s consensus timeout for the txs to pile up in the mempool
+        Some("5s"),
+    )?);
+
+    set_ethereum_bridge_mode(
+        &test,
+        &test.net.chain_id,
+        Who::Validator(0),
+        ethereum_bridge::ledger::Mode::Off,
+        None,
+    );
+    update_actor_config(
+        &test,
+        &test.net.chain_id,
+        Who::Validator(0),
+        |config| {
+            config.ledger.shell.cometbft_mempool_size = Some(MEMPOOL_SIZE);
+        },
+    );
+
+    // 1. Run the ledger node
+    let bg_ledger =
+        start_namada_ledger_node_wait_wasm(&test, Some(0), Some(120))?
+            .background();
+
+    let validator_one_rpc = Arc::new(get_actor_rpc(&test, Who::Validator(0)));
+
+    // A token transfer tx args, whose gas limit takes the whole block
+    let tx_args = Arc::new(vec![
+        "transfer",
+        "--source",
+        BERTHA,
+        "--target",
+        ALBERT,
+        "--token",
+        NAM,
+        "--amount",
+        "1.01",
+        "--signing-keys",
+        BERTHA_KEY,
+        "--gas-limit",
+        "20000000",
+        "--broadcast-only",
+        "--node",
+    ]);
+
+    // 2. Spawn threads that keep submitting token transfer txs
+    let stop = Arc::new(AtomicBool::new(false));
+    let rejected_as_full = Arc::new(AtomicUsize::new(0));
+    // We collect to run the threads in parallel.
+    #[allow(clippy::needless_collect)]
+    let tasks: Vec<std::thread::JoinHandle<_>> = (0..NUM_SUBMITTERS)
+        .map(|_| {
+            let test = Arc::clone(&test);
+            let validator_one_rpc = Arc::clone(&validator_one_rpc);
+            let tx_args = Arc::clone(&tx_args);
+            let stop = Arc::clone(&stop);
+            let rejected_as_full = Arc::clone(&rejected_as_full);
+            std::thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let mut args = (*tx_args).clone();
+                    args.push(&*validator_one_rpc);
+                    let mut client = run!(*test, Bin::Client, args, Some(80))?;
+                    if client.exp_eof()?.contains("mempool is full") {
+                        rejected_as_full.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+                let res: Result<()> = Ok(());
+                res
+            })
+        })
+        .collect();
+
+    // Wait for the mempool to be saturated
+    let saturated = (0..60).any(|_| {
+        sleep(1);
+        rejected_as_full.load(Ordering::Relaxed) > 0
+    });
+
+    // 3. Check that the validator set update protocol tx of the next epoch
+    // is still applied
+    let mut ledger = bg_ledger.foreground();
+    let res = ledger.exp_string(VALSET_UPDATE_APPLIED);
+
+    stop.store(true, Ordering::Relaxed);
+    for task in tasks.into_iter() {
+        task.join().unwrap()?;
+    }
+    assert!(saturated, "The mempool was never saturated");
+    res?;
+
+    Ok(())
+}
+
 /// In this test we:
 /// 1. Run the ledger node
 /// 2. Submit a valid proposal
@@ -3397,6 +3545,18 @@ fn test_invalid_validator_txs() -> Result<()> {
     let validator_0_rpc = get_actor_rpc(&test, Who::Validator(0));
     let validator_1_rpc = get_actor_rpc(&test, Who::Validator(1));
 
+    // Check that the tx submitted by the given client was rejected by VPs
+    let exp_vps_rejected = |client: &mut NamadaCmd| -> Result<()> {
+        let hash = exp_last_inner_tx_hash(client)?;
+        client.assert_exit_code(FailureClass::Invalid.exit_code());
+        let resp = query_applied_tx(&test, &validator_0_rpc, &hash)?;
+        assert!(
+            matches!(resp.inner_tx_result(), InnerTxResult::VpsRejected(_)),
+            "{resp}"
+        );
+        Ok(())
+    };
+
     // Try to change validator-1 commission rate as validator-0
     let tx_args = vec![
         "change-commission-rate",
@@ -3411,8 +3571,7 @@ fn test_invalid_validator_txs() -> Result<()> {
     ];
     let mut client =
         run_as!(test, Who::Validator(0), Bin::Client, tx_args, Some(40))?;
-    client.exp_string(TX_REJECTED)?;
-    client.assert_success();
+    exp_vps_rejected(&mut client)?;
 
     // Try to deactivate validator-1 as validator-0
     let tx_args = vec![
@@ -3426,8 +3585,7 @@ fn test_invalid_validator_txs() -> Result<()> {
     ];
     let mut client =
         run_as!(test, Who::Validator(0), Bin::Client, tx_args, Some(40))?;
-    client.exp_string(TX_REJECTED)?;
-    client.assert_success();
+    exp_vps_rejected(&mut client)?;
 
     // Try to change the validator-1 website as validator-0
     let tx_args = vec![
@@ -3443,8 +3601,7 @@ fn test_invalid_validator_txs() -> Result<()> {
     ];
     let mut client =
         run_as!(test, Who::Validator(0), Bin::Client, tx_args, Some(40))?;
-    client.exp_string(TX_REJECTED)?;
-    client.assert_success();
+    exp_vps_rejected(&mut client)?;
 
     // Deactivate validator-1
     let tx_args = vec![
@@ -3501,8 +3658,7 @@ fn test_invalid_validator_txs() -> Result<()> {
     ];
     let mut client =
         run_as!(test, Who::Validator(0), Bin::Client, tx_args, Some(40))?;
-    client.exp_string(TX_REJECTED)?;
-    client.assert_success();
+    exp_vps_rejected(&mut client)?;
 
     Ok(())
 }
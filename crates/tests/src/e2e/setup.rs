@@ -20,22 +20,25 @@ use eyre::eyre;
 use itertools::{Either, Itertools};
 use namada::types::chain::ChainId;
 use namada_apps::cli::context::ENV_VAR_CHAIN_ID;
+use namada_apps::cli::exit::FailureClass;
 use namada_apps::client::utils::{
     self, validator_pre_genesis_dir, validator_pre_genesis_txs_file,
 };
 use namada_apps::config::genesis::utils::read_toml;
 use namada_apps::config::genesis::{templates, transactions, GenesisAddress};
 use namada_apps::config::{ethereum_bridge, genesis, Config};
+use namada_apps::wallet::pre_genesis;
 use namada_apps::{config, wallet};
 use namada_core::types::address::Address;
+use namada_core::types::dec::Dec;
 use namada_core::types::key::{RefTo, SchemeType};
 use namada_core::types::string_encoding::StringEncoded;
 use namada_core::types::token::NATIVE_MAX_DECIMAL_PLACES;
 use namada_sdk::wallet::alias::Alias;
 use namada_tx_prelude::token;
 use once_cell::sync::Lazy;
-use rand::rngs::OsRng;
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use serde_json;
 use tempfile::{tempdir, tempdir_in, TempDir};
 
@@ -61,6 +64,25 @@ const ENV_VAR_TEMP_PATH: &str = "NAMADA_E2E_TEMP_PATH";
 pub const ENV_VAR_USE_PREBUILT_BINARIES: &str =
     "NAMADA_E2E_USE_PREBUILT_BINARIES";
 
+/// Env. var holding the path to a folder with the binaries of a previous
+/// version of Namada (e.g. an extracted release artifact), to test upgrading
+/// a network from that version to the current build. When it's not set, the
+/// artifact of the previous release is downloaded.
+pub const ENV_VAR_UPGRADE_FROM_BINARIES: &str =
+    "NAMADA_E2E_UPGRADE_FROM_BINARIES";
+
+/// Env. var for generating the validator wallet fixtures. Without it, a missing
+/// fixture is an error.
+const ENV_VAR_REGEN_WALLET_FIXTURES: &str = "NAMADA_E2E_REGEN_WALLETS";
+
+/// The directory with the fixtures of the genesis validators' pre-genesis
+/// wallets and transactions, relative to the root of the repository
+pub const WALLET_FIXTURES_DIR: &str = "test_fixtures/e2e_wallets";
+
+/// Seed of the RNG used to generate the genesis validators' balance and
+/// validator keys. It's offset by the index of the validator.
+const WALLET_FIXTURES_SEED: u64 = 1337;
+
 /// The E2E tests genesis config source.
 /// This file must contain a single validator with alias "validator-0".
 /// To add more validators, use the [`set_validators`] function in the call to
@@ -159,7 +181,10 @@ where
             );
         });
         let pre_genesis_tx_path_str = pre_genesis_tx_path.to_string_lossy();
-        // generate a balance key
+        // generate a balance key from a seeded RNG, so that it matches the
+        // validator's wallet fixture
+        let mut rng =
+            StdRng::seed_from_u64(WALLET_FIXTURES_SEED + u64::from(val));
         let mut wallet = wallet::load(&wallet_path)
             .expect("Could not locate pre-genesis wallet used for e2e tests.");
         let alias = format!("validator-{val}-balance-key");
@@ -169,7 +194,7 @@ where
                 Some(alias),
                 true,
                 None,
-                &mut OsRng,
+                &mut rng,
             )
             .unwrap_or_else(|_| {
                 panic!("Could not generate new key for validator-{}", val)
@@ -195,26 +220,6 @@ where
             true,
         );
         wallet::save(&wallet).unwrap();
-        // invoke `init-genesis-established-account` to generate a new
-        // established account with the generated balance key
-        let args = vec![
-            "utils",
-            "init-genesis-established-account",
-            "--aliases",
-            &alias,
-            "--path",
-            &pre_genesis_tx_path_str,
-        ];
-        let mut init_established_account = run_cmd(
-            Bin::Client,
-            args,
-            Some(5),
-            &working_dir(),
-            base_dir,
-            format!("{}:{}", std::file!(), std::line!()),
-        )
-        .unwrap();
-        init_established_account.assert_success();
         // assign balance to the implicit addr (i.e. pubkey) + established acc
         let nam_balances = genesis
             .balances
@@ -237,63 +242,107 @@ where
                 NATIVE_MAX_DECIMAL_PLACES.into(),
             ),
         );
-        // invoke `init-genesis-validator` to promote the generated established
-        // account to a validator account
-        let net_addr = format!("127.0.0.1:{}", 27656 + port_offset(val));
-        let validator_address_str = validator_address.to_string();
-        let args = vec![
-            "utils",
-            "init-genesis-validator",
-            "--alias",
-            &validator_alias,
-            "--address",
-            &validator_address_str,
-            "--path",
-            &pre_genesis_tx_path_str,
-            "--net-address",
-            &net_addr,
-            "--commission-rate",
-            "0.05",
-            "--max-commission-rate-change",
-            "0.01",
-            "--email",
-            "null@null.net",
-            "--self-bond-amount",
-            "100000",
-            "--unsafe-dont-encrypt",
-        ];
-        let mut init_genesis_validator = run_cmd(
-            Bin::Client,
-            args,
-            Some(5),
-            &working_dir(),
-            base_dir,
-            format!("{}:{}", std::file!(), std::line!()),
-        )
-        .unwrap();
-        init_genesis_validator.assert_success();
-        // invoke `sign-genesis-txs` to sign the validator txs with
-        // the generated balance key
-        let args = vec![
-            "utils",
-            "sign-genesis-txs",
-            "--alias",
-            &validator_alias,
-            "--path",
-            &pre_genesis_tx_path_str,
-            "--output",
-            &pre_genesis_tx_path_str,
-        ];
-        let mut sign_pre_genesis_txs = run_cmd(
-            Bin::Client,
-            args,
-            Some(5),
-            &working_dir(),
-            base_dir,
-            format!("{}:{}", std::file!(), std::line!()),
-        )
-        .unwrap();
-        sign_pre_genesis_txs.assert_success();
+        // the validator's wallet and signed genesis txs are taken from its
+        // checked-in fixture, which is only generated on demand
+        let net_port = 27656 + port_offset(val);
+        let net_addr = format!("127.0.0.1:{net_port}");
+        let fixture_dir = working_dir()
+            .join(WALLET_FIXTURES_DIR)
+            .join(format!("{validator_alias}-{net_port}"));
+        if regen_wallet_fixtures() {
+            // invoke `init-genesis-established-account` to generate a new
+            // established account with the generated balance key
+            let args = vec![
+                "utils",
+                "init-genesis-established-account",
+                "--aliases",
+                &alias,
+                "--path",
+                &pre_genesis_tx_path_str,
+            ];
+            let mut init_established_account = run_cmd(
+                Bin::Client,
+                args,
+                Some(5),
+                &working_dir(),
+                base_dir,
+                format!("{}:{}", std::file!(), std::line!()),
+            )
+            .unwrap();
+            init_established_account.assert_success();
+            // generate the validator keys from the seeded RNG and promote the
+            // generated established account to a validator account, like
+            // `init-genesis-validator` does
+            let validator_wallet = pre_genesis::gen_and_store(
+                SchemeType::Ed25519,
+                true,
+                &pre_genesis_path,
+                &mut rng,
+            )
+            .unwrap();
+            let established_account_txs: transactions::UnsignedTransactions =
+                read_toml(&pre_genesis_tx_path, "transactions.toml").unwrap();
+            let (_, mut validator_txs) = transactions::init_validator(
+                transactions::GenesisValidatorData {
+                    address: validator_address.clone(),
+                    commission_rate: Dec::new(5, 2).unwrap(),
+                    max_commission_rate_change: Dec::new(1, 2).unwrap(),
+                    net_address: net_addr.parse().unwrap(),
+                    self_bond_amount: token::DenominatedAmount::new(
+                        token::Amount::from_uint(
+                            100000,
+                            NATIVE_MAX_DECIMAL_PLACES,
+                        )
+                        .unwrap(),
+                        NATIVE_MAX_DECIMAL_PLACES.into(),
+                    ),
+                    email: "null@null.net".to_string(),
+                    description: None,
+                    website: None,
+                    discord_handle: None,
+                    avatar: None,
+                },
+                &validator_wallet,
+            );
+            validator_txs.established_account =
+                established_account_txs.established_account;
+            fs::write(
+                &pre_genesis_tx_path,
+                toml::to_string(&validator_txs).unwrap(),
+            )
+            .unwrap();
+            // invoke `sign-genesis-txs` to sign the validator txs with
+            // the generated balance key
+            let args = vec![
+                "utils",
+                "sign-genesis-txs",
+                "--alias",
+                &validator_alias,
+                "--path",
+                &pre_genesis_tx_path_str,
+                "--output",
+                &pre_genesis_tx_path_str,
+            ];
+            let mut sign_pre_genesis_txs = run_cmd(
+                Bin::Client,
+                args,
+                Some(5),
+                &working_dir(),
+                base_dir,
+                format!("{}:{}", std::file!(), std::line!()),
+            )
+            .unwrap();
+            sign_pre_genesis_txs.assert_success();
+            store_wallet_fixture(&pre_genesis_path, &fixture_dir);
+        } else {
+            assert!(
+                fixture_dir.exists(),
+                "The wallet fixture {} is missing. Set {}=true to generate it.",
+                fixture_dir.to_string_lossy(),
+                ENV_VAR_REGEN_WALLET_FIXTURES,
+            );
+            copy_dir_files(&fixture_dir, &pre_genesis_path);
+        }
         // initialize the validator
         // add generated txs to genesis
         let pre_genesis_txs =
@@ -318,6 +367,46 @@ where
     genesis
 }
 
+/// Whether the validator wallet fixtures should be (re)generated.
+fn regen_wallet_fixtures() -> bool {
+    match env::var(ENV_VAR_REGEN_WALLET_FIXTURES) {
+        Ok(val) => val.to_ascii_lowercase() != "false",
+        _ => false,
+    }
+}
+
+/// Store a copy of a validator's pre-genesis directory as its wallet fixture.
+/// The copy is moved into place once it's complete, so that tests running
+/// concurrently never load a partial fixture.
+fn store_wallet_fixture(pre_genesis_path: &Path, fixture_dir: &Path) {
+    let parent = fixture_dir.parent().unwrap();
+    fs::create_dir_all(parent).unwrap();
+    let staging = tempdir_in(parent).unwrap().into_path();
+    copy_dir_files(pre_genesis_path, &staging);
+    if fixture_dir.exists() {
+        fs::remove_dir_all(fixture_dir).unwrap();
+    }
+    if fs::rename(&staging, fixture_dir).is_err() {
+        fs::remove_dir_all(&staging).unwrap();
+        // Another test may have stored the same fixture in the meantime
+        assert!(
+            fixture_dir.exists(),
+            "Failed to store the wallet fixture {}",
+            fixture_dir.to_string_lossy()
+        );
+    }
+}
+
+/// Copy the files (non-recursively) from one directory into another.
+fn copy_dir_files(src: &Path, dst: &Path) {
+    for entry in fs::read_dir(src).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_file() {
+            fs::copy(&path, dst.join(path.file_name().unwrap())).unwrap();
+        }
+    }
+}
+
 /// Setup a network with a single genesis validator node.
 pub fn single_node_net() -> Result<Test> {
     network(
@@ -702,6 +791,38 @@ mod macros {
             $test.run_cmd_as($who, $bin, $args, $timeout_sec, loc)
         }};
     }
+
+    /// Get an [`NamadaCmd`] to run a Namada binary from the given folder,
+    /// instead of the current build, e.g. the binaries of a previous
+    /// version.
+    ///
+    /// Arguments:
+    /// - the test [`super::Test`]
+    /// - who to run this command as [`super::Who`]
+    /// - the folder with the binaries `&Path`
+    /// - which binary to run [`super::Bin`]
+    /// - arguments, which implement `IntoIterator<item = &str>`, e.g.
+    ///   `&["cmd"]`
+    /// - optional timeout in seconds `Option<u64>`
+    ///
+    /// This is a helper macro that adds file and line location to the
+    /// [`super::run_cmd_from`] function call.
+    #[macro_export]
+    macro_rules! run_as_from {
+        (
+            $test:expr,
+            $who:expr,
+            $bin_dir:expr,
+            $bin:expr,
+            $args:expr,
+            $timeout_sec:expr $(,)?
+        ) => {{
+            // The file and line will expand to the location that invoked
+            // `run_as_from!`
+            let loc = format!("{}:{}", std::file!(), std::line!());
+            $test.run_cmd_from($who, $bin_dir, $bin, $args, $timeout_sec, loc)
+        }};
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -755,6 +876,35 @@ impl Test {
         run_cmd(bin, args, timeout_sec, &self.working_dir, base_dir, loc)
     }
 
+    /// Use the `run_as_from!` macro instead of calling this method directly
+    /// to get automatic source location reporting.
+    ///
+    /// Get an [`NamadaCmd`] to run a Namada binary from the given folder.
+    pub fn run_cmd_from<I, S>(
+        &self,
+        who: Who,
+        bin_dir: impl AsRef<Path>,
+        bin: Bin,
+        args: I,
+        timeout_sec: Option<u64>,
+        loc: String,
+    ) -> Result<NamadaCmd>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let base_dir = self.get_base_dir(who);
+        run_cmd_from(
+            bin,
+            Some(bin_dir.as_ref()),
+            args,
+            timeout_sec,
+            &self.working_dir,
+            base_dir,
+            loc,
+        )
+    }
+
     pub fn get_base_dir(&self, who: Who) -> PathBuf {
         match who {
             Who::NonValidator => self.test_dir.path().to_owned(),
@@ -864,9 +1014,24 @@ impl NamadaCmd {
         // Make sure that there is no unread output first
         let _ = self.exp_eof().unwrap();
 
+        self.assert_exit_code(0);
+    }
+
+    /// Assert that the process exited with the exit code of the given failure
+    /// class
+    pub fn assert_failure_class(&mut self, class: FailureClass) {
+        // Make sure that there is no unread output first
+        let _ = self.exp_eof().unwrap();
+
+        self.assert_exit_code(class.exit_code());
+    }
+
+    /// Assert that the process exited with the given exit code. All of its
+    /// output must have been read, e.g. with [`NamadaCmd::exp_eof`].
+    pub fn assert_exit_code(&mut self, code: i32) {
         let process = self.session.get_process();
         let status = process.wait().unwrap();
-        assert_eq!(WaitStatus::Exited(process.pid(), 0), status);
+        assert_eq!(WaitStatus::Exited(process.pid(), code), status);
     }
 
     /// Assert that the process exited with failure
@@ -1033,6 +1198,24 @@ pub fn run_cmd<I, S>(
     base_dir: impl AsRef<Path>,
     loc: String,
 ) -> Result<NamadaCmd>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    run_cmd_from(bin, None, args, timeout_sec, working_dir, base_dir, loc)
+}
+
+/// Get a [`Command`] to run an Namada binary from the given folder, or from
+/// the current build if none is given.
+pub fn run_cmd_from<I, S>(
+    bin: Bin,
+    bin_dir: Option<&Path>,
+    args: I,
+    timeout_sec: Option<u64>,
+    working_dir: impl AsRef<Path>,
+    base_dir: impl AsRef<Path>,
+    loc: String,
+) -> Result<NamadaCmd>
 where
     I: IntoIterator<Item = S>,
     S: AsRef<OsStr>,
@@ -1045,10 +1228,13 @@ where
         Bin::Relayer => ("namadar", "info"),
     };
 
-    let mut run_cmd = generate_bin_command(
-        bin_name,
-        &working_dir.as_ref().join("Cargo.toml"),
-    );
+    let mut run_cmd = match bin_dir {
+        Some(bin_dir) => Command::new(bin_dir.join(bin_name)),
+        None => generate_bin_command(
+            bin_name,
+            &working_dir.as_ref().join("Cargo.toml"),
+        ),
+    };
 
     run_cmd
         .env("NAMADA_LOG", log_level)
@@ -0,0 +1,208 @@
+//! Tests of upgrading a network from a previous version of Namada to the
+//! current build.
+//!
+//! The binaries of the previous version are downloaded from the artifact of
+//! its release, unless the `NAMADA_E2E_UPGRADE_FROM_BINARIES` env var points
+//! to a folder with them. The previous version must accept the genesis files
+//! produced by the current build. The tests are ignored by default, as they
+//! need network access, run them with `--ignored`.
+
+use std::env;
+use std::path::PathBuf;
+use std::process::Command;
+
+use color_eyre::eyre::Result;
+use eyre::eyre;
+use namada_apps::config::ethereum_bridge;
+use setup::constants::*;
+
+use super::helpers::get_actor_rpc;
+use super::setup::{self, set_ethereum_bridge_mode, Bin, Test, Who};
+use crate::strings::{LEDGER_STARTED, TX_APPLIED_SUCCESS, VALIDATOR_NODE};
+use crate::{run_as, run_as_from};
+
+/// The release of Namada that the network is upgraded from
+const PREVIOUS_RELEASE: &str = "v0.30.0";
+
+/// The height at which the network is halted to upgrade it
+const UPGRADE_HEIGHT: u64 = 30;
+
+/// Get the folder with the binaries of the previous version. Unless it is
+/// configured, the artifact of the previous release is downloaded and
+/// extracted into the test dir.
+fn previous_version_binaries(test: &Test) -> Result<PathBuf> {
+    if let Some(bin_dir) = env::var_os(setup::ENV_VAR_UPGRADE_FROM_BINARIES) {
+        return Ok(PathBuf::from(bin_dir));
+    }
+    // The platform in the name of the release artifacts, as in
+    // `scripts/make-package.sh`
+    let os = match env::consts::OS {
+        "linux" => "Linux",
+        "macos" => "Darwin",
+        os => return Err(eyre!("No release artifact for the OS {os}")),
+    };
+    let arch = match (os, env::consts::ARCH) {
+        ("Darwin", "aarch64") => "arm64",
+        (_, arch) => arch,
+    };
+    let package = format!("namada-{PREVIOUS_RELEASE}-{os}-{arch}");
+    let url = format!(
+        "https://github.com/anoma/namada/releases/download/{PREVIOUS_RELEASE}/\
+         {package}.tar.gz"
+    );
+    let download_dir = test.test_dir.as_ref().join("previous-release");
+    std::fs::create_dir_all(&download_dir)?;
+    let archive = download_dir.join(format!("{package}.tar.gz"));
+
+    println!("Downloading the release artifact {url}");
+    let status = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location"])
+        .arg("--output")
+        .arg(&archive)
+        .arg(&url)
+        .status()?;
+    if !status.success() {
+        return Err(eyre!("Failed to download {url}: {status}"));
+    }
+    let status = Command::new("tar")
+        .arg("-xzf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(&download_dir)
+        .status()?;
+    if !status.success() {
+        return Err(eyre!("Failed to extract {}: {status}", archive.display()));
+    }
+    Ok(download_dir.join(package))
+}
+
+/// In this test we:
+/// 0. Download the binaries of the previous release
+/// 1. Run the ledger node of the previous version until the upgrade height, at
+///    which it halts
+/// 2. Submit a transfer and query the balance with the previous client
+/// 3. Wait for the node to halt at the upgrade height
+/// 4. Restart the node with the current build, which must load the state of the
+///    previous version. There are no state migrations between the versions to
+///    run yet, so the node resumes from the halted state as is.
+/// 5. Check that the balance is the same with the current client
+/// 6. Submit a transfer with the current client
+#[test]
+#[ignore]
+fn upgrade_network_from_previous_version() -> Result<()> {
+    let test = setup::single_node_net()?;
+    // 0. Download the binaries of the previous release
+    let bin_dir = previous_version_binaries(&test)?;
+    set_ethereum_bridge_mode(
+        &test,
+        &test.net.chain_id,
+        Who::Validator(0),
+        ethereum_bridge::ledger::Mode::Off,
+        None,
+    );
+    let upgrade_height = UPGRADE_HEIGHT.to_string();
+
+    // 1. Run the ledger node of the previous version until the upgrade height
+    let mut ledger = run_as_from!(
+        test,
+        Who::Validator(0),
+        &bin_dir,
+        Bin::Node,
+        &[
+            "ledger",
+            "run-until",
+            "--block-height",
+            &upgrade_height,
+            "--halt"
+        ],
+        Some(80)
+    )?;
+    ledger.exp_string(LEDGER_STARTED)?;
+    ledger.exp_string(VALIDATOR_NODE)?;
+    ledger.exp_regex(r"Committed block hash.*, height: [0-9]+")?;
+    let bg_ledger = ledger.background();
+
+    // 2. Submit a transfer and query the balance with the previous client
+    let validator_one_rpc = get_actor_rpc(&test, Who::Validator(0));
+    let tx_args = [
+        "transfer",
+        "--source",
+        BERTHA,
+        "--target",
+        ALBERT,
+        "--token",
+        NAM,
+        "--amount",
+        "10.1",
+        "--signing-keys",
+        BERTHA_KEY,
+        "--node",
+        &validator_one_rpc,
+    ];
+    let mut client = run_as_from!(
+        test,
+        Who::NonValidator,
+        &bin_dir,
+        Bin::Client,
+        tx_args,
+        Some(40)
+    )?;
+    client.exp_string(TX_APPLIED_SUCCESS)?;
+    client.assert_success();
+
+    let query_balance_args = [
+        "balance",
+        "--owner",
+        ALBERT,
+        "--token",
+        NAM,
+        "--node",
+        &validator_one_rpc,
+    ];
+    let mut client = run_as_from!(
+        test,
+        Who::NonValidator,
+        &bin_dir,
+        Bin::Client,
+        query_balance_args,
+        Some(40)
+    )?;
+    let (_, balance) = client.exp_regex(r"nam: [0-9.]+")?;
+    client.assert_success();
+
+    // 3. Wait for the node to halt at the upgrade height
+    let mut ledger = bg_ledger.foreground();
+    ledger.exp_string(&format!(
+        "Reached block height {upgrade_height}, halting the chain."
+    ))?;
+    ledger.exp_eof()?;
+    drop(ledger);
+
+    // 4. Restart the node with the current build
+    let mut ledger =
+        run_as!(test, Who::Validator(0), Bin::Node, &["ledger"], Some(80))?;
+    ledger.exp_string(LEDGER_STARTED)?;
+    // The state of the previous version must be loaded
+    ledger.exp_string("Last state root hash:")?;
+    ledger.exp_regex(r"Committed block hash.*, height: [0-9]+")?;
+    let _bg_ledger = ledger.background();
+
+    // 5. Check that the balance is the same with the current client
+    let mut client = run_as!(
+        test,
+        Who::NonValidator,
+        Bin::Client,
+        query_balance_args,
+        Some(40)
+    )?;
+    client.exp_string(&balance)?;
+    client.assert_success();
+
+    // 6. Submit a transfer with the current client
+    let mut client =
+        run_as!(test, Who::NonValidator, Bin::Client, tx_args, Some(40))?;
+    client.exp_string(TX_APPLIED_SUCCESS)?;
+    client.assert_success();
+
+    Ok(())
+}
@@ -14,7 +14,7 @@ use std::env;
 use color_eyre::eyre::Result;
 
 use super::setup;
-use crate::e2e::setup::Bin;
+use crate::e2e::setup::{Bin, NamadaCmd};
 use crate::run;
 
 /// Test wallet key commands with an encrypted key:
@@ -221,3 +221,248 @@ fn wallet_address_cmds() -> Result<()> {
 
     Ok(())
 }
+
+/// Test the interactive prompts for the encryption password of a new key:
+/// 1. key gen with a mismatched password confirmation is cancelled
+/// 2. key find doesn't find the key
+/// 3. key gen with an empty password is cancelled
+#[test]
+fn wallet_encryption_password_prompts() -> Result<()> {
+    let test = setup::single_node_net()?;
+    let key_alias = "test_key_1";
+
+    // 1. key gen with a mismatched password confirmation
+    let mut cmd =
+        run!(test, Bin::Wallet, &["gen", "--alias", key_alias], Some(20),)?;
+    enter_new_password(&mut cmd, "VeRySeCuR3", "NoTsOsEcUr3")?;
+    cmd.exp_string("Passphrases did not match")?;
+    cmd.exp_string("Action cancelled, no changes persisted.")?;
+    cmd.assert_failure();
+
+    // 2. key find
+    let mut cmd = run!(
+        test,
+        Bin::Wallet,
+        &["find", "--keys", "--alias", key_alias],
+        Some(20),
+    )?;
+    cmd.exp_string(&format!("Alias \"{}\" not found.", key_alias))?;
+
+    // 3. key gen with an empty password
+    let mut cmd =
+        run!(test, Bin::Wallet, &["gen", "--alias", key_alias], Some(20),)?;
+    cmd.exp_string("Enter your encryption password:")?;
+    cmd.send_line("")?;
+    cmd.exp_string("Password cannot be empty")?;
+    cmd.exp_string("Action cancelled, no changes persisted.")?;
+    cmd.assert_failure();
+
+    Ok(())
+}
+
+/// Test the interactive prompt for the decryption password of a key:
+/// 1. key gen
+/// 2. key find with a wrong password fails to decrypt the key
+/// 3. key find with the right password decrypts the key
+#[test]
+fn wallet_decryption_password_prompt() -> Result<()> {
+    let test = setup::single_node_net()?;
+    let key_alias = "test_key_1";
+    let password = "VeRySeCuR3";
+
+    // 1. key gen
+    let mut cmd =
+        run!(test, Bin::Wallet, &["gen", "--alias", key_alias], Some(20),)?;
+    enter_new_password(&mut cmd, password, password)?;
+    skip_bip39_passphrase(&mut cmd)?;
+    cmd.exp_string(&format!(
+        "Successfully added a key and an address with alias: \"{}\"",
+        key_alias
+    ))?;
+
+    // 2. key find with a wrong password
+    let mut cmd = run!(
+        test,
+        Bin::Wallet,
+        &[
+            "find",
+            "--keys",
+            "--alias",
+            key_alias,
+            "--decrypt",
+            "--unsafe-show-secret"
+        ],
+        Some(20),
+    )?;
+    cmd.exp_string(&format!("  Alias \"{}\" (encrypted):", key_alias))?;
+    enter_password(&mut cmd, "NoTsOsEcUr3")?;
+    cmd.exp_string("Unable to decrypt the keypair. Is the password correct?")?;
+
+    // 3. key find with the right password
+    let mut cmd = run!(
+        test,
+        Bin::Wallet,
+        &[
+            "find",
+            "--keys",
+            "--alias",
+            key_alias,
+            "--decrypt",
+            "--unsafe-show-secret"
+        ],
+        Some(20),
+    )?;
+    cmd.exp_string(&format!("  Alias \"{}\" (encrypted):", key_alias))?;
+    enter_password(&mut cmd, password)?;
+    cmd.exp_string("    Secret key:")?;
+
+    Ok(())
+}
+
+/// Test the interactive prompt for a conflicting alias of a new key:
+/// 1. key gen
+/// 2. key gen with the same alias, skipped after an invalid answer
+/// 3. key gen with the same alias, reselecting another alias
+/// 4. key gen with the same alias, replacing the existing key
+/// 5. key list
+#[test]
+fn wallet_key_alias_conflict() -> Result<()> {
+    let test = setup::single_node_net()?;
+    let key_alias = "test_key_1";
+    let other_alias = "test_key_2";
+    let gen_args = ["gen", "--alias", key_alias, "--unsafe-dont-encrypt"];
+
+    // 1. key gen
+    let mut cmd = run!(test, Bin::Wallet, &gen_args, Some(20),)?;
+    cmd.exp_string(&format!(
+        "Successfully added a key and an address with alias: \"{}\"",
+        key_alias
+    ))?;
+
+    // 2. key gen with the same alias, skipped after an invalid answer
+    let mut cmd = run!(test, Bin::Wallet, &gen_args, Some(20),)?;
+    answer_alias_conflict(&mut cmd, key_alias, "a key", "x")?;
+    cmd.exp_string("Invalid option, try again.")?;
+    answer_alias_conflict(&mut cmd, key_alias, "a key", "k")?;
+    cmd.exp_string("No changes are persisted. Exiting.")?;
+    cmd.assert_success();
+
+    // 3. key gen with the same alias, reselecting another alias
+    let mut cmd = run!(test, Bin::Wallet, &gen_args, Some(20),)?;
+    answer_alias_conflict(&mut cmd, key_alias, "a key", "s")?;
+    cmd.exp_string("Please enter a different alias:")?;
+    cmd.send_line(other_alias)?;
+    cmd.exp_string(&format!(
+        "Successfully added a key and an address with alias: \"{}\"",
+        other_alias
+    ))?;
+
+    // 4. key gen with the same alias, replacing the existing key
+    let mut cmd = run!(test, Bin::Wallet, &gen_args, Some(20),)?;
+    answer_alias_conflict(&mut cmd, key_alias, "a key", "p")?;
+    cmd.exp_string(&format!(
+        "Successfully added a key and an address with alias: \"{}\"",
+        key_alias
+    ))?;
+
+    // 5. key list
+    let mut cmd = run!(test, Bin::Wallet, &["list", "--keys"], Some(20))?;
+    cmd.exp_string(&format!("  Alias \"{}\" (not encrypted):", key_alias))?;
+    cmd.exp_string(&format!("  Alias \"{}\" (not encrypted):", other_alias))?;
+
+    Ok(())
+}
+
+/// Test the interactive prompt for a conflicting alias of a new address:
+/// 1. key gen
+/// 2. address add with the same alias, skipped
+/// 3. address add with the same alias, replacing the existing address
+/// 4. address find
+#[test]
+fn wallet_address_alias_conflict() -> Result<()> {
+    let test = setup::single_node_net()?;
+    let alias = "test_address_1";
+    let address = "tnam1q82t25z5f9gmnv5sztyr8ht9tqhrw4u875qjhy56";
+    let add_args = ["add", "--value", address, "--alias", alias];
+
+    // 1. key gen
+    let mut cmd = run!(
+        test,
+        Bin::Wallet,
+        &["gen", "--alias", alias, "--unsafe-dont-encrypt"],
+        Some(20),
+    )?;
+    cmd.exp_string(&format!(
+        "Successfully added a key and an address with alias: \"{}\"",
+        alias
+    ))?;
+
+    // 2. address add with the same alias, skipped
+    let mut cmd = run!(test, Bin::Wallet, &add_args, Some(20),)?;
+    answer_alias_conflict(&mut cmd, alias, "an address", "k")?;
+    cmd.exp_string("Address not added")?;
+    cmd.assert_failure();
+
+    // 3. address add with the same alias, replacing the existing address
+    let mut cmd = run!(test, Bin::Wallet, &add_args, Some(20),)?;
+    answer_alias_conflict(&mut cmd, alias, "an address", "p")?;
+    cmd.exp_string(&format!(
+        "Successfully added an address with alias: \"{}\"",
+        alias
+    ))?;
+
+    // 4. address find
+    let mut cmd = run!(
+        test,
+        Bin::Wallet,
+        &["find", "--addr", "--alias", alias],
+        Some(20),
+    )?;
+    cmd.exp_string("Found transparent address:")?;
+    cmd.exp_string(&format!("  \"{}\": {}", alias, address))?;
+
+    Ok(())
+}
+
+/// Answer the prompts for the encryption password of a new key
+fn enter_new_password(
+    cmd: &mut NamadaCmd,
+    password: &str,
+    confirmation: &str,
+) -> Result<()> {
+    cmd.exp_string("Enter your encryption password:")?;
+    cmd.send_line(password)?;
+    cmd.exp_string("Enter same passphrase again:")?;
+    cmd.send_line(confirmation)
+}
+
+/// Answer the prompt for the decryption password of a key
+fn enter_password(cmd: &mut NamadaCmd, password: &str) -> Result<()> {
+    cmd.exp_string("Enter your decryption password:")?;
+    cmd.send_line(password)
+}
+
+/// Answer the prompt for the BIP39 passphrase of a new key with none
+fn skip_bip39_passphrase(cmd: &mut NamadaCmd) -> Result<()> {
+    cmd.exp_string("Enter BIP39 passphrase (empty for none):")?;
+    cmd.send_line("")
+}
+
+/// Answer the prompt for an alias that already exists for a key or an address
+/// with one of: s(k)ip/re(p)lace/re(s)elect
+fn answer_alias_conflict(
+    cmd: &mut NamadaCmd,
+    alias: &str,
+    alias_for: &str,
+    answer: &str,
+) -> Result<()> {
+    cmd.exp_string(&format!(
+        "You're trying to create an alias \"{}\" that already exists for {} \
+         in your store.",
+        alias, alias_for
+    ))?;
+    cmd.exp_string(
+        "Would you like to replace it? s(k)ip/re(p)lace/re(s)elect:",
+    )?;
+    cmd.send_line(answer)
+}
@@ -1,2 +1,3 @@
+mod fixtures;
 mod masp;
 mod setup;
@@ -0,0 +1,87 @@
+//! The network states snapshotted as integration test fixtures. Each fixture
+//! is built from genesis by its builder the first time it's requested with
+//! [`setup_from_fixture`](super::setup::setup_from_fixture) and loaded from
+//! its snapshot afterwards.
+
+use color_eyre::eyre::{eyre, Result};
+use namada::proof_of_stake::storage::read_pos_params;
+use namada_apps::node::ledger::shell::testing::client::run;
+use namada_apps::node::ledger::shell::testing::node::MockNode;
+use namada_apps::node::ledger::shell::testing::utils::{Bin, CapturedOutput};
+use test_log::test;
+
+use super::setup;
+use crate::e2e::setup::constants::{ALBERT, ALBERT_KEY, BERTHA, BERTHA_KEY};
+
+/// A network with delegations from Albert and Bertha to the genesis
+/// validator, both of which are active in the current epoch
+pub const POST_GENESIS_WITH_BONDS: &str = "post_genesis_with_bonds";
+
+/// A function that advances a network from genesis to the state of a fixture
+pub type FixtureBuilder = fn(&mut MockNode) -> Result<()>;
+
+/// Find the builder of the fixture with the given name
+pub fn builder(name: &str) -> Option<FixtureBuilder> {
+    match name {
+        POST_GENESIS_WITH_BONDS => Some(post_genesis_with_bonds),
+        _ => None,
+    }
+}
+
+/// Delegate to the genesis validator from Albert and Bertha, then advance the
+/// chain until both bonds are active.
+fn post_genesis_with_bonds(node: &mut MockNode) -> Result<()> {
+    // This address doesn't matter for tests. But an argument is required.
+    let validator_one_rpc = "127.0.0.1:26567";
+    for (source, signing_key) in [(ALBERT, ALBERT_KEY), (BERTHA, BERTHA_KEY)] {
+        run(
+            node,
+            Bin::Client,
+            vec![
+                "bond",
+                "--validator",
+                "validator-0",
+                "--source",
+                source,
+                "--amount",
+                "10000.0",
+                "--signing-keys",
+                signing_key,
+                "--node",
+                validator_one_rpc,
+            ],
+        )?;
+        node.assert_success();
+    }
+
+    let pipeline_len = {
+        let locked = node.shell.lock().unwrap();
+        read_pos_params(&locked.wl_storage)
+            .map_err(|e| eyre!("Failed to read PoS params: {e}"))?
+            .pipeline_len
+    };
+    for _ in 0..pipeline_len {
+        node.next_epoch();
+    }
+    Ok(())
+}
+
+/// Test that the bonds of the `post_genesis_with_bonds` fixture are active
+/// once it's loaded.
+#[test]
+fn test_post_genesis_with_bonds_fixture() -> Result<()> {
+    let validator_one_rpc = "127.0.0.1:26567";
+    let (node, _services) = setup::setup_from_fixture(POST_GENESIS_WITH_BONDS)?;
+
+    let captured = CapturedOutput::of(|| {
+        run(
+            &node,
+            Bin::Client,
+            vec!["bonds", "--owner", BERTHA, "--node", validator_one_rpc],
+        )
+    });
+    assert!(captured.result.is_ok());
+    assert!(captured.contains("Delegations from"));
+    assert!(captured.contains("Bonds total: 10000.000000"));
+    Ok(())
+}
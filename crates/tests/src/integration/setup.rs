@@ -1,16 +1,19 @@
 use std::collections::HashMap;
+use std::fs;
 use std::mem::ManuallyDrop;
 use std::path::Path;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
 use color_eyre::eyre::{eyre, Result};
+use namada::state::DB;
 use namada_apps::cli::args;
 use namada_apps::client::utils::PRE_GENESIS_DIR;
 use namada_apps::config;
 use namada_apps::config::genesis::chain::Finalized;
 use namada_apps::config::genesis::templates;
 use namada_apps::config::genesis::templates::load_and_validate;
+use namada_apps::config::global::GlobalConfig;
 use namada_apps::config::TendermintMode;
 use namada_apps::facade::tendermint::Timeout;
 use namada_apps::facade::tendermint_proto::google::protobuf::Timestamp;
@@ -24,23 +27,161 @@ use namada_apps::wallet::pre_genesis;
 use namada_core::types::chain::ChainIdPrefix;
 use namada_sdk::wallet::alias::Alias;
 
+use super::fixtures;
 use crate::e2e::setup::{copy_wasm_to_chain_dir, SINGLE_NODE_NET_GENESIS};
 
 /// Env. var for keeping temporary files created by the integration tests
 const ENV_VAR_KEEP_TEMP: &str = "NAMADA_INT_KEEP_TEMP";
 
+/// Env. var for rebuilding the integration test fixtures from genesis, even
+/// if their snapshots already exist
+const ENV_VAR_REGEN_FIXTURES: &str = "NAMADA_INT_REGEN_FIXTURES";
+
+/// The directory with the snapshots of the integration test fixtures,
+/// relative to the root of the repository
+pub const FIXTURES_DIR: &str = "test_fixtures/integration";
+
 /// Setup a network with a single genesis validator node.
 pub fn setup() -> Result<(MockNode, MockServicesController)> {
     initialize_genesis()
 }
 
-/// Setup folders with genesis, configs, wasm, etc.
-pub fn initialize_genesis() -> Result<(MockNode, MockServicesController)> {
-    let working_dir = std::fs::canonicalize("../..").unwrap();
-    let keep_temp = match std::env::var(ENV_VAR_KEEP_TEMP) {
+/// Setup a network from the state snapshot of the fixture with the given
+/// name. If the snapshot doesn't exist yet, or if the
+/// `NAMADA_INT_REGEN_FIXTURES` env var is set, the fixture is first built
+/// from genesis and its snapshot stored for the following runs.
+///
+/// Note that the node loaded from a snapshot doesn't have the responses of
+/// the blocks committed before the snapshot was taken.
+pub fn setup_from_fixture(
+    name: &str,
+) -> Result<(MockNode, MockServicesController)> {
+    let working_dir = fs::canonicalize("../..").unwrap();
+    let fixture_dir = working_dir.join(FIXTURES_DIR).join(name);
+    let regen = match std::env::var(ENV_VAR_REGEN_FIXTURES) {
         Ok(val) => val.to_ascii_lowercase() != "false",
         _ => false,
     };
+    if regen || !fixture_dir.exists() {
+        let build = fixtures::builder(name).ok_or_else(|| {
+            eyre!("Unknown integration test fixture \"{name}\"")
+        })?;
+        let (mut node, _services) = setup()?;
+        build(&mut node)?;
+        dump_fixture(&node, &fixture_dir)?;
+    }
+    load_fixture(&fixture_dir)
+}
+
+/// Dump the state of a mock ledger node into a snapshot at the given path,
+/// which can be loaded back with [`setup_from_fixture`]. The compiled wasms
+/// are left out of the snapshot, they are copied in again when it's loaded.
+pub fn dump_fixture(node: &MockNode, target: &Path) -> Result<()> {
+    // Hold the lock so that no block gets committed while copying the DB
+    let locked = node.shell.lock().unwrap();
+    locked
+        .wl_storage
+        .storage
+        .db
+        .flush(true)
+        .map_err(|e| eyre!("Failed to flush the DB: {e}"))?;
+    let parent = target
+        .parent()
+        .ok_or_else(|| eyre!("Invalid fixture path {}", target.display()))?;
+    fs::create_dir_all(parent)?;
+    // Write the snapshot next to its target and move it into place once it's
+    // complete, so that tests running concurrently never load a partial one
+    let staging = tempfile::tempdir_in(parent)?.into_path();
+    copy_state(node.test_dir.path(), &staging)?;
+    drop(locked);
+    if target.exists() {
+        fs::remove_dir_all(target)?;
+    }
+    if let Err(err) = fs::rename(&staging, target) {
+        fs::remove_dir_all(&staging)?;
+        // Another test may have stored the same fixture in the meantime
+        if !target.exists() {
+            return Err(err.into());
+        }
+    }
+    Ok(())
+}
+
+/// Load a mock ledger node from the state snapshot at the given path.
+fn load_fixture(
+    fixture_dir: &Path,
+) -> Result<(MockNode, MockServicesController)> {
+    let test_dir = TestDir::new();
+    copy_state(fixture_dir, test_dir.path())?;
+    let chain_id = GlobalConfig::read(test_dir.path())
+        .map_err(|e| eyre!("Failed to read the fixture's global config: {e}"))?
+        .default_chain_id
+        .ok_or_else(|| eyre!("Missing the chain ID of the fixture"))?;
+    let chain_dir = test_dir.path().join(chain_id.as_str());
+    let genesis = Finalized::read_toml_files(&chain_dir)?;
+    let global_args = args::Global {
+        is_pre_genesis: false,
+        chain_id: Some(chain_id),
+        base_dir: test_dir.path().to_path_buf(),
+        wasm_dir: Some(chain_dir.join("wasm")),
+        no_progress: true,
+        profile: None,
+        proving_threads: None,
+    };
+    create_node(test_dir, global_args, keep_temp(), services_cfg(&genesis))
+}
+
+/// Recursively copy the state of a node between directories, skipping the
+/// compiled wasms.
+fn copy_state(src: &Path, dst: &Path) -> Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let path = entry.path();
+        let target = dst.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_state(&path, &target)?;
+        } else if path.extension().and_then(|ext| ext.to_str()) != Some("wasm")
+        {
+            fs::copy(&path, &target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether to keep the temporary files created by the integration tests
+fn keep_temp() -> bool {
+    match std::env::var(ENV_VAR_KEEP_TEMP) {
+        Ok(val) => val.to_ascii_lowercase() != "false",
+        _ => false,
+    }
+}
+
+/// Configure the mock services for the given genesis.
+fn services_cfg(genesis: &Finalized) -> MockServicesCfg {
+    let eth_bridge_params = genesis.get_eth_bridge_params();
+    let auto_drive_services = {
+        // NB: for now, the only condition that
+        // dictates whether mock services should
+        // be enabled is if the Ethereum bridge
+        // is enabled at genesis
+        eth_bridge_params.is_some()
+    };
+    let enable_eth_oracle = {
+        // NB: we only enable the oracle if the
+        // Ethereum bridge is enabled at genesis
+        eth_bridge_params.is_some()
+    };
+    MockServicesCfg {
+        auto_drive_services,
+        enable_eth_oracle,
+    }
+}
+
+/// Setup folders with genesis, configs, wasm, etc.
+pub fn initialize_genesis() -> Result<(MockNode, MockServicesController)> {
+    let working_dir = std::fs::canonicalize("../..").unwrap();
+    let keep_temp = keep_temp();
     let test_dir = TestDir::new();
     let template_dir = working_dir.join(SINGLE_NODE_NET_GENESIS);
 
@@ -75,6 +216,9 @@ pub fn initialize_genesis() -> Result<(MockNode, MockServicesController)> {
         chain_id: Some(chain_id.clone()),
         base_dir: test_dir.path().to_path_buf(),
         wasm_dir: Some(test_dir.path().join(chain_id.as_str()).join("wasm")),
+        no_progress: true,
+        profile: None,
+        proving_threads: None,
     };
     // setup genesis file
     namada_apps::client::utils::init_network(
@@ -90,25 +234,12 @@ pub fn initialize_genesis() -> Result<(MockNode, MockServicesController)> {
         },
     );
 
-    let eth_bridge_params = genesis.get_eth_bridge_params();
-    let auto_drive_services = {
-        // NB: for now, the only condition that
-        // dictates whether mock services should
-        // be enabled is if the Ethereum bridge
-        // is enabled at genesis
-        eth_bridge_params.is_some()
-    };
-    let enable_eth_oracle = {
-        // NB: we only enable the oracle if the
-        // Ethereum bridge is enabled at genesis
-        eth_bridge_params.is_some()
-    };
-    let services_cfg = MockServicesCfg {
-        auto_drive_services,
-        enable_eth_oracle,
-    };
+    let services_cfg = services_cfg(&genesis);
     finalize_wallet(&template_dir, &global_args, genesis);
-    create_node(test_dir, global_args, keep_temp, services_cfg)
+    let (node, controller) =
+        create_node(test_dir, global_args, keep_temp, services_cfg)?;
+    init_chain(&node)?;
+    Ok((node, controller))
 }
 
 /// Add the address from the finalized genesis to the wallet.
@@ -194,6 +325,12 @@ fn create_node(
         blocks: Arc::new(Mutex::new(HashMap::new())),
         auto_drive_services,
     };
+    Ok((node, controller))
+}
+
+/// Initialize the chain of a fresh mock ledger node from its genesis.
+fn init_chain(node: &MockNode) -> Result<()> {
+    let chain_id = node.shell.lock().unwrap().chain_id.clone();
     let init_req =
         namada_apps::facade::tendermint::v0_37::abci::request::InitChain {
             time: Timestamp {
@@ -237,5 +374,5 @@ fn create_node(
         locked.commit();
     }
 
-    Ok((node, controller))
+    Ok(())
 }
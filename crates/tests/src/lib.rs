@@ -7,6 +7,8 @@
 
 pub use namada;
 
+#[cfg(test)]
+mod conformance;
 mod vm_host_env;
 pub use vm_host_env::{ibc, tx, vp};
 #[cfg(test)]
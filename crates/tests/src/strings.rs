@@ -23,3 +23,7 @@ pub const TX_FAILED: &str = "Transaction failed";
 
 /// Wrapper transaction accepted.
 pub const TX_ACCEPTED: &str = "Wrapper transaction accepted";
+
+/// The votes of a validator set update protocol tx were applied.
+pub const VALSET_UPDATE_APPLIED: &str =
+    "Aggregating new votes for validator set update";
@@ -80,7 +80,7 @@ use namada::types::hash::Hash;
 use namada::types::storage::{
     self, BlockHash, BlockHeight, Epoch, Key, TxIndex,
 };
-use namada::types::time::DurationSecs;
+use namada::types::time::{DateTimeUtc, DurationSecs};
 use namada::vm::{wasm, WasmCacheRwAccess};
 use namada_test_utils::TestWasms;
 use namada_tx_prelude::BorshSerializeExt;
@@ -766,6 +766,53 @@ pub fn packet_from_message(
     }
 }
 
+/// Replace the memo of a packet
+pub fn set_packet_memo(packet: &mut Packet, memo: &str) {
+    let mut data: PacketData =
+        serde_json::from_slice(&packet.data).expect("invalid packet data");
+    data.memo = memo.to_string().into();
+    packet.data = serde_json::to_vec(&data).unwrap();
+}
+
+/// Make the packet sending the token of the received packet back over the
+/// channel it was received on, as the forwarding at the given time
+pub fn forwarded_packet(
+    received: &Packet,
+    sequence: Sequence,
+    receiver: &str,
+    forwarded_at: DateTimeUtc,
+) -> Packet {
+    let received_data: PacketData =
+        serde_json::from_slice(&received.data).expect("invalid packet data");
+    let denom = format!(
+        "{}/{}/{}",
+        received.port_id_on_b, received.chan_id_on_b, received_data.token.denom
+    );
+    let data = PacketData {
+        token: PrefixedCoin {
+            denom: denom.parse().expect("invalid denom"),
+            amount: received_data.token.amount,
+        },
+        sender: ADDRESS.to_string().into(),
+        receiver: receiver.to_string().into(),
+        memo: String::new().into(),
+    };
+    let forwarded_at: Timestamp =
+        TmTime::try_from(forwarded_at).expect("invalid time").into();
+    Packet {
+        seq_on_a: sequence,
+        port_id_on_a: received.port_id_on_b.clone(),
+        chan_id_on_a: received.chan_id_on_b.clone(),
+        port_id_on_b: received.port_id_on_a.clone(),
+        chan_id_on_b: received.chan_id_on_a.clone(),
+        data: serde_json::to_vec(&data).unwrap(),
+        timeout_height_on_b: TimeoutHeight::Never,
+        timeout_timestamp_on_b: (forwarded_at
+            + namada::ibc::forward::DEFAULT_TIMEOUT)
+            .unwrap(),
+    }
+}
+
 pub fn balance_key_with_ibc_prefix(denom: String, owner: &Address) -> Key {
     let ibc_token = ibc_token(denom);
     token::storage_key::balance_key(&ibc_token, owner)
@@ -86,6 +86,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_tx_read_chunk() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let key = storage::Key::parse("key").unwrap();
+        assert_eq!(tx::ctx().read_bytes_chunk(&key, 0, 10).unwrap(), None);
+
+        let value: Vec<u8> = (0..1000).map(|i| (i % 256) as u8).collect();
+        let read_in_chunks = || {
+            let mut read = vec![];
+            let mut offset = 0;
+            loop {
+                let chunk = tx::ctx()
+                    .read_bytes_chunk(&key, offset, 300)
+                    .unwrap()
+                    .expect("The value should be found");
+                assert!(chunk.len() <= 300);
+                offset += chunk.len() as u64;
+                read.extend(chunk);
+                if offset >= 1000 {
+                    break read;
+                }
+            }
+        };
+
+        // Read the chunks of a value from the write log
+        tx::ctx().write_bytes(&key, &value).unwrap();
+        assert_eq!(read_in_chunks(), value);
+
+        // Read the chunks of a value from the storage
+        tx_host_env::commit_tx_and_block();
+        assert_eq!(read_in_chunks(), value);
+
+        // A chunk past the end of the value is empty
+        assert_eq!(
+            tx::ctx().read_bytes_chunk(&key, 2000, 10).unwrap(),
+            Some(vec![])
+        );
+    }
+
     #[test]
     fn test_tx_has_key() {
         // The environment must be initialized first
@@ -444,6 +485,55 @@ mod tests {
         itertools::assert_equal(iter_post, expected_post);
     }
 
+    #[test]
+    fn test_vp_get_changed_keys() {
+        let tx_env = TestTxEnv::default();
+
+        let addr = address::testing::established_address_1();
+        let addr_key = storage::Key::from(addr.to_db_key());
+        let prefix = addr_key.join(&Key::parse("prefix").unwrap());
+        let other_key = addr_key.join(&Key::parse("other").unwrap());
+        let sub_keys = [3_u64, 1, 4, 0, 2];
+
+        // Initialize the VP environment via a transaction
+        vp_host_env::init_from_tx(addr, tx_env, |_addr| {
+            for i in sub_keys.iter() {
+                tx::ctx().write(&prefix.push(i).unwrap(), i).unwrap();
+            }
+            // A key outside of the prefix
+            tx::ctx().write(&other_key, 0_u64).unwrap();
+        });
+
+        let expected: Vec<Key> = sub_keys
+            .iter()
+            .sorted()
+            .map(|i| prefix.push(i).unwrap())
+            .collect();
+
+        // Get the changed keys by pages
+        let page = vp::CTX.get_changed_keys(&prefix, 1, 2).unwrap();
+        assert_eq!(page, expected[1..3]);
+        let page = vp::CTX.get_changed_keys(&prefix, 4, 2).unwrap();
+        assert_eq!(page, expected[4..]);
+        let page = vp::CTX.get_changed_keys(&prefix, 5, 2).unwrap();
+        assert!(page.is_empty());
+
+        // Iterate all the changed keys with pages smaller than their count
+        let keys: Vec<Key> = vp::CTX
+            .iter_changed_keys(&prefix, 2)
+            .map(|key| key.unwrap())
+            .collect();
+        assert_eq!(keys, expected);
+
+        // The keys under the account's prefix include the other key
+        let keys: Vec<Key> = vp::CTX
+            .iter_changed_keys(&addr_key, 10)
+            .map(|key| key.unwrap())
+            .collect();
+        assert_eq!(keys.len(), sub_keys.len() + 1);
+        assert!(keys.contains(&other_key));
+    }
+
     #[test]
     fn test_vp_verify_tx_signature() {
         let mut env = TestVpEnv::default();
@@ -1368,7 +1458,9 @@ mod tests {
         assert!(result.expect("token validation failed unexpectedly"));
         // Check the balance
         tx_host_env::set(env);
-        let key = ibc::balance_key_with_ibc_prefix(denom, &receiver);
+        let trace_key =
+            ibc_storage::ibc_trace_key(ibc_storage::calc_hash(&denom));
+        let key = ibc::balance_key_with_ibc_prefix(denom.clone(), &receiver);
         let balance: Option<Amount> = tx_host_env::with(|env| {
             env.wl_storage.read(&key).expect("read error")
         });
@@ -1377,6 +1469,11 @@ mod tests {
             env.wl_storage.read(&minted_key).expect("read error")
         });
         assert_eq!(minted, Some(Amount::from_u64(100)));
+        // Check the denom trace registered for the hash
+        let trace: Option<String> = tx_host_env::with(|env| {
+            env.wl_storage.read(&trace_key).expect("read error")
+        });
+        assert_eq!(trace, Some(denom));
     }
 
     #[test]
@@ -1464,6 +1561,155 @@ mod tests {
         assert_eq!(changed_keys, expected_changed_keys);
     }
 
+    #[test]
+    fn test_ibc_forward_received_token() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Set the initial state before starting transactions
+        let (token, receiver) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        let (port_id, channel_id, channel_writes) =
+            ibc::prepare_opened_channel(&conn_id, false);
+        writes.extend(channel_writes);
+
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.wl_storage
+                    .storage
+                    .write(&key, &val)
+                    .expect("write error");
+            });
+        });
+
+        // packet forwarded back over the same channel
+        let mut packet = ibc::received_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            token.to_string(),
+            &receiver,
+        );
+        let next_receiver = "cosmos1receiver";
+        let memo = format!(
+            r#"{{"forward":{{"receiver":"{next_receiver}","port":"{port_id}","channel":"{channel_id}"}}}}"#
+        );
+        ibc::set_packet_memo(&mut packet, &memo);
+
+        // Start a transaction to receive a packet
+        let msg = ibc::msg_packet_recv(packet.clone());
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs.clone(), pks_map.clone(), None)
+            .sign_wrapper(keypair.clone());
+        // receive a packet with the message
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("receiving and forwarding the token failed");
+
+        // Check
+        let mut env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(result.expect("validation failed unexpectedly"));
+        // The token was minted to the escrow and burned to be sent back
+        let denom = format!("{}/{}/{}", port_id, channel_id, token);
+        let ibc_token = ibc::ibc_token(&denom);
+        let minted_key = token::storage_key::minted_balance_key(&ibc_token);
+        let result =
+            ibc::validate_multitoken_vp_from_tx(&env, &tx, &minted_key);
+        assert!(result.expect("token validation failed unexpectedly"));
+        let minted: Option<Amount> =
+            env.wl_storage.read(&minted_key).expect("read error");
+        assert_eq!(minted, Some(Amount::zero()));
+        let key = ibc::balance_key_with_ibc_prefix(denom.clone(), &receiver);
+        let balance: Option<Amount> =
+            env.wl_storage.read(&key).expect("read error");
+        assert_eq!(balance, None);
+        // The forwarded packet was sent
+        let sequence = ibc::Sequence::from(1);
+        let commitment_key =
+            ibc_storage::commitment_key(&port_id, &channel_id, sequence);
+        assert!(env.wl_storage.has_key(&commitment_key).unwrap());
+        let forward_key =
+            ibc_storage::forward_key(&port_id, &channel_id, sequence);
+        assert!(env.wl_storage.has_key(&forward_key).unwrap());
+        let forwarded_at = env
+            .wl_storage
+            .storage
+            .header
+            .as_ref()
+            .expect("no header")
+            .time;
+
+        // Commit
+        env.commit_tx_and_block();
+        // for the next block
+        env.wl_storage
+            .storage
+            .begin_block(BlockHash::default(), BlockHeight(2))
+            .unwrap();
+        env.wl_storage
+            .storage
+            .set_header(tm_dummy_header())
+            .unwrap();
+        tx_host_env::set(env);
+
+        // Start the next transaction for receiving an error ack of the
+        // forwarded packet
+        let forwarded_packet = ibc::forwarded_packet(
+            &packet,
+            sequence,
+            next_receiver,
+            forwarded_at,
+        );
+        let mut msg = ibc::msg_packet_ack(forwarded_packet);
+        msg.acknowledgement = ibc::transfer_ack_with_error().into();
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        // ack the packet with the message
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("ack failed");
+
+        // Check
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(result.expect("validation failed unexpectedly"));
+        // The token was refunded to the escrow and burned again to be sent
+        // back to the original sender
+        let minted: Option<Amount> =
+            env.wl_storage.read(&minted_key).expect("read error");
+        assert_eq!(minted, Some(Amount::zero()));
+        assert!(!env.wl_storage.has_key(&forward_key).unwrap());
+        let refund_sequence = ibc::Sequence::from(2);
+        let commitment_key =
+            ibc_storage::commitment_key(&port_id, &channel_id, refund_sequence);
+        assert!(env.wl_storage.has_key(&commitment_key).unwrap());
+        let forward_key =
+            ibc_storage::forward_key(&port_id, &channel_id, refund_sequence);
+        let pending: Option<namada::ibc::forward::PendingForward> =
+            env.wl_storage.read(&forward_key).expect("read error");
+        assert!(pending.expect("no pending refund").is_refund);
+    }
+
     #[test]
     fn test_ibc_unescrow_token() {
         // The environment must be initialized first
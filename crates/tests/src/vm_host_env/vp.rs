@@ -347,12 +347,30 @@ mod native_vp_host_env {
     native_host_fn!(vp_read_pre(key_ptr: u64, key_len: u64) -> i64);
     native_host_fn!(vp_read_post(key_ptr: u64, key_len: u64) -> i64);
     native_host_fn!(vp_read_temp(key_ptr: u64, key_len: u64) -> i64);
+    native_host_fn!(vp_read_chunk_pre(
+            key_ptr: u64,
+            key_len: u64,
+            offset: u64,
+            len: u64,
+        ) -> i64);
+    native_host_fn!(vp_read_chunk_post(
+            key_ptr: u64,
+            key_len: u64,
+            offset: u64,
+            len: u64,
+        ) -> i64);
     native_host_fn!(vp_result_buffer(result_ptr: u64));
     native_host_fn!(vp_has_key_pre(key_ptr: u64, key_len: u64) -> i64);
     native_host_fn!(vp_has_key_post(key_ptr: u64, key_len: u64) -> i64);
     native_host_fn!(vp_iter_prefix_pre(prefix_ptr: u64, prefix_len: u64) -> u64);
     native_host_fn!(vp_iter_prefix_post(prefix_ptr: u64, prefix_len: u64) -> u64);
     native_host_fn!(vp_iter_next(iter_id: u64) -> i64);
+    native_host_fn!(vp_get_changed_keys(
+            prefix_ptr: u64,
+            prefix_len: u64,
+            offset: u64,
+            limit: u64,
+        ) -> i64);
     native_host_fn!(vp_get_chain_id(result_ptr: u64));
     native_host_fn!(vp_get_block_height() -> u64);
     native_host_fn!(vp_get_tx_index() -> u32);
@@ -1,5 +1,6 @@
 use namada_core::hints;
 use namada_core::types::address::{Address, InternalAddress};
+use namada_core::types::storage::Epoch;
 use namada_core::types::token::{self, Amount, DenominatedAmount};
 use namada_storage as storage;
 use namada_storage::{StorageRead, StorageWrite};
@@ -33,6 +34,49 @@ where
     Ok(balance)
 }
 
+/// Read the vesting schedule of the owner's balance of a given token, if any.
+pub fn read_vesting_schedule<S>(
+    storage: &S,
+    token: &Address,
+    owner: &Address,
+) -> storage::Result<Option<token::VestingSchedule>>
+where
+    S: StorageRead,
+{
+    storage.read(&vesting_key(token, owner))
+}
+
+/// Read the part of the owner's balance of a given token that is still
+/// locked by a vesting schedule in the current epoch. The locked tokens can
+/// be bonded, but not transferred.
+pub fn read_locked_balance<S>(
+    storage: &S,
+    token: &Address,
+    owner: &Address,
+) -> storage::Result<token::Amount>
+where
+    S: StorageRead,
+{
+    let locked = match read_vesting_schedule(storage, token, owner)? {
+        Some(schedule) => schedule.locked_amount(storage.get_block_epoch()?),
+        None => token::Amount::zero(),
+    };
+    Ok(locked)
+}
+
+/// Write the vesting schedule of the owner's balance of a given token.
+pub fn write_vesting_schedule<S>(
+    storage: &mut S,
+    token: &Address,
+    owner: &Address,
+    schedule: token::VestingSchedule,
+) -> storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    storage.write(&vesting_key(token, owner), schedule)
+}
+
 /// Read the total network supply of a given token.
 pub fn read_total_supply<S>(
     storage: &S,
@@ -42,7 +86,9 @@ where
     S: StorageRead,
 {
     let key = minted_balance_key(token);
-    let balance = storage.read::<token::Amount>(&key)?.unwrap_or_default();
+    let balance = storage
+        .read_cached::<token::Amount>(&key)?
+        .unwrap_or_default();
     Ok(balance)
 }
 
@@ -69,7 +115,7 @@ where
         }
         token => (denom_key(token), false),
     };
-    storage.read(&key).map(|opt_denom| {
+    storage.read_cached(&key).map(|opt_denom| {
         Some(opt_denom.unwrap_or_else(|| {
             if is_default_zero {
                 0u8.into()
@@ -195,6 +241,35 @@ where
     storage.write(&total_supply_key, new_total_supply)
 }
 
+/// Read the flows of a token in the given epoch.
+pub fn read_epoch_flows<S>(
+    storage: &S,
+    token: &Address,
+    epoch: Epoch,
+) -> storage::Result<token::TokenFlows>
+where
+    S: StorageRead,
+{
+    let flows = storage.read(&epoch_flows_key(token, epoch))?;
+    Ok(flows.unwrap_or_default())
+}
+
+/// Add the given flows of a token to its flows in the current epoch.
+pub fn record_epoch_flows<S>(
+    storage: &mut S,
+    token: &Address,
+    flows: token::TokenFlows,
+) -> storage::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let epoch = storage.get_block_epoch()?;
+    let total = read_epoch_flows(storage, token, epoch)?
+        .checked_add(&flows)
+        .ok_or_else(|| storage::Error::new_const("Token flows overflow"))?;
+    storage.write(&epoch_flows_key(token, epoch), total)
+}
+
 /// Add denomination info if it exists in storage.
 pub fn denominated(
     amount: token::Amount,
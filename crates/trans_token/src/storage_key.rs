@@ -1,6 +1,7 @@
 //! Transparent token storage keys
 
 use namada_core::types::address::{Address, InternalAddress};
+use namada_core::types::key_space::{ModuleKeySpace, PrefixSeg};
 use namada_core::types::storage::{self, DbKeySeg, KeySeg};
 
 /// Key segment for a balance key
@@ -11,6 +12,74 @@ pub const DENOM_STORAGE_KEY: &str = "denomination";
 pub const MINTER_STORAGE_KEY: &str = "minter";
 /// Key segment for minted balance
 pub const MINTED_STORAGE_KEY: &str = "minted";
+/// Key segment for a vesting schedule
+pub const VESTING_STORAGE_KEY: &str = "vesting";
+/// Key segment for the flows of tokens accounted per epoch
+pub const EPOCH_FLOWS_STORAGE_KEY: &str = "epoch_flows";
+/// Last calculated inflation value handed out
+pub const MASP_LAST_INFLATION_KEY: &str = "last_inflation";
+/// The last locked ratio
+pub const MASP_LAST_LOCKED_RATIO_KEY: &str = "last_locked_ratio";
+/// The key for the nominal proportional gain of a shielded pool for a given
+/// asset
+pub const MASP_KP_GAIN_KEY: &str = "proportional_gain";
+/// The key for the nominal derivative gain of a shielded pool for a given asset
+pub const MASP_KD_GAIN_KEY: &str = "derivative_gain";
+/// The key for the locked ratio target for a given asset
+pub const MASP_LOCKED_RATIO_TARGET_KEY: &str = "locked_ratio_target";
+/// The key for the max reward rate for a given asset
+pub const MASP_MAX_REWARD_RATE_KEY: &str = "max_reward_rate";
+/// The shielded rewards parameters of a token that can be set by governance
+pub const MASP_REWARD_PARAMS_KEYS: [&str; 4] = [
+    MASP_KP_GAIN_KEY,
+    MASP_KD_GAIN_KEY,
+    MASP_LOCKED_RATIO_TARGET_KEY,
+    MASP_MAX_REWARD_RATE_KEY,
+];
+/// The state of the shielded rewards controller of a token, only written by
+/// the protocol
+pub const MASP_REWARD_STATE_KEYS: [&str; 2] =
+    [MASP_LAST_INFLATION_KEY, MASP_LAST_LOCKED_RATIO_KEY];
+
+/// The key prefixes reserved by transparent tokens
+pub fn key_space() -> ModuleKeySpace {
+    let multitoken =
+        || PrefixSeg::Address(Address::Internal(InternalAddress::Multitoken));
+    ModuleKeySpace::new("trans_token")
+        .guarded_by(Address::Internal(InternalAddress::Multitoken))
+        .reserve(
+            [
+                multitoken(),
+                PrefixSeg::AnyAddress,
+                PrefixSeg::Str(BALANCE_STORAGE_KEY),
+            ],
+            "Balances and minted supplies of tokens",
+        )
+        .reserve(
+            [
+                multitoken(),
+                PrefixSeg::AnyAddress,
+                PrefixSeg::Str(MINTER_STORAGE_KEY),
+            ],
+            "Minters of tokens",
+        )
+        .reserve(
+            [
+                multitoken(),
+                PrefixSeg::AnyAddress,
+                PrefixSeg::Str(VESTING_STORAGE_KEY),
+            ],
+            "Vesting schedules of balances",
+        )
+        .reserve(
+            [multitoken(), PrefixSeg::Str(EPOCH_FLOWS_STORAGE_KEY)],
+            "Fee, burn and treasury flows of tokens per epoch",
+        )
+        .reserve(
+            [PrefixSeg::AnyAddress, PrefixSeg::Str(DENOM_STORAGE_KEY)],
+            "Denominations of tokens",
+        )
+}
 
 /// Gets the key for the given token address, error with the given
 /// message to expect if the key is not in the address
@@ -60,6 +129,62 @@ pub fn minted_balance_key(token_addr: &Address) -> storage::Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Obtain a storage key for the vesting schedule of the owner's balance.
+pub fn vesting_key(token_addr: &Address, owner: &Address) -> storage::Key {
+    storage::Key::from(
+        Address::Internal(InternalAddress::Multitoken).to_db_key(),
+    )
+    .push(&token_addr.to_db_key())
+    .expect("Cannot obtain a storage key")
+    .push(&VESTING_STORAGE_KEY.to_owned())
+    .expect("Cannot obtain a storage key")
+    .push(&owner.to_db_key())
+    .expect("Cannot obtain a storage key")
+}
+
+/// Obtain a storage key prefix for the flows of all the tokens in an epoch.
+pub fn epoch_flows_prefix(epoch: storage::Epoch) -> storage::Key {
+    storage::Key::from(
+        Address::Internal(InternalAddress::Multitoken).to_db_key(),
+    )
+    .push(&EPOCH_FLOWS_STORAGE_KEY.to_owned())
+    .expect("Cannot obtain a storage key")
+    .push(&epoch)
+    .expect("Cannot obtain a storage key")
+}
+
+/// Obtain a storage key for the flows of a token in an epoch.
+pub fn epoch_flows_key(
+    token_addr: &Address,
+    epoch: storage::Epoch,
+) -> storage::Key {
+    epoch_flows_prefix(epoch)
+        .push(&token_addr.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if the given storage key is for the flows of a token in the given
+/// epoch. If it is, returns the token.
+pub fn is_epoch_flows_key(
+    epoch: storage::Epoch,
+    key: &storage::Key,
+) -> Option<&Address> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(flows),
+            DbKeySeg::StringSeg(key_epoch),
+            DbKeySeg::AddressSeg(token),
+        ] if *addr == Address::Internal(InternalAddress::Multitoken)
+            && flows == EPOCH_FLOWS_STORAGE_KEY
+            && *key_epoch == epoch.raw() =>
+        {
+            Some(token)
+        }
+        _ => None,
+    }
+}
+
 /// Check if the given storage key is balance key for the given token. If it is,
 /// returns the owner. For minted balances, use [`is_any_minted_balance_key()`].
 pub fn is_balance_key<'a>(
@@ -100,6 +225,39 @@ pub fn is_any_token_balance_key(key: &storage::Key) -> Option<[&Address; 2]> {
     }
 }
 
+/// Check if the given storage key is a vesting schedule key for unspecified
+/// token. If it is, returns the token and owner address.
+pub fn is_any_vesting_key(key: &storage::Key) -> Option<[&Address; 2]> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::AddressSeg(token),
+            DbKeySeg::StringSeg(vesting),
+            DbKeySeg::AddressSeg(owner),
+        ] if *addr == Address::Internal(InternalAddress::Multitoken)
+            && vesting == VESTING_STORAGE_KEY =>
+        {
+            Some([token, owner])
+        }
+        _ => None,
+    }
+}
+
+/// Check if the given storage key is for the shielded rewards parameters or
+/// state of an unspecified token. If it is, returns the token. These keys are
+/// validated by the MASP VP rather than the VP of the token.
+pub fn is_any_shielded_rewards_key(key: &storage::Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(token), DbKeySeg::StringSeg(key)]
+            if MASP_REWARD_PARAMS_KEYS.contains(&key.as_str())
+                || MASP_REWARD_STATE_KEYS.contains(&key.as_str()) =>
+        {
+            Some(token)
+        }
+        _ => None,
+    }
+}
+
 /// Obtain a storage key denomination of a token.
 pub fn denom_key(token_addr: &Address) -> storage::Key {
     storage::Key::from(token_addr.to_db_key())
@@ -10,6 +10,8 @@ pub mod pgf;
 pub mod pos;
 /// transaction protocols made by validators
 pub mod protocol;
+/// txs scheduled for execution at a future epoch
+pub mod scheduled;
 /// wrapper txs with encrypted payloads
 pub mod wrapper;
 
@@ -84,6 +86,10 @@ pub enum ResultCode {
     TooLarge = 14,
     /// Decrypted tx is expired
     ExpiredDecryptedTx = 15,
+    /// Tx has too many sections
+    TooManySections = 16,
+    /// Wasm code embedded in the tx is too large
+    CodeTooLarge = 17,
     // =========================================================================
     // WARN: These codes shouldn't be changed between version!
 }
@@ -100,7 +106,7 @@ impl ResultCode {
             InvalidTx | InvalidSig | InvalidOrder | ExtraTxs
             | Undecryptable | AllocationError | ReplayTx | InvalidChainId
             | ExpiredTx | TxGasLimit | FeeError | InvalidVoteExtension
-            | TooLarge => false,
+            | TooLarge | TooManySections | CodeTooLarge => false,
         }
     }
 
@@ -117,6 +117,29 @@ pub struct ClaimRewards {
     pub source: Option<Address>,
 }
 
+/// Enable or disable the automatic restaking of the rewards of a bond.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Hash,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub struct SetAutoCompound {
+    /// Validator address
+    pub validator: Address,
+    /// Source address of the bond. For self-bonds, the validator is also the
+    /// source
+    pub source: Option<Address>,
+    /// Whether the rewards should be restaked at every epoch
+    pub enabled: bool,
+}
+
 /// A redelegation of bonded tokens from one validator to another.
 #[derive(
     Debug,
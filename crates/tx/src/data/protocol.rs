@@ -76,6 +76,9 @@ pub enum ProtocolTxType {
     BridgePoolVext,
     /// Validator set update signed by some validator
     ValSetUpdateVext,
+    /// Evidence of a validator that signed conflicting vote extensions,
+    /// submitted by any account
+    DuplicateVextEvidence,
 }
 
 impl ProtocolTxType {
@@ -0,0 +1,101 @@
+//! Transactions scheduled for execution by the protocol at a future epoch
+
+use namada_core::borsh::{BorshDeserialize, BorshSerialize};
+use namada_core::types::address::{Address, InternalAddress};
+use namada_core::types::hash::Hash;
+use namada_core::types::storage::{DbKeySeg, Epoch, Key, KeySeg};
+use namada_core::types::token;
+use serde::{Deserialize, Serialize};
+
+use crate::data::GasLimit;
+use crate::Tx;
+
+/// The internal address holding the scheduled txs and their prepaid fees
+pub const ADDRESS: Address = Address::Internal(InternalAddress::Scheduler);
+
+const SCHEDULED_TXS_KEY: &str = "scheduled";
+
+/// A tx data type to schedule a tx for execution at the start of a future
+/// epoch
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ScheduledTx {
+    /// The epoch at whose start the tx is executed
+    pub epoch: Epoch,
+    /// The address paying the execution fee upfront
+    pub payer: Address,
+    /// The gas limit of the execution
+    pub gas_limit: GasLimit,
+    /// The fee paid upfront in the native token, at least the gas limit
+    /// times the minimum gas price of the native token
+    pub fee: token::Amount,
+    /// The tx to execute, with the signatures that authorize it
+    pub tx: Tx,
+}
+
+impl ScheduledTx {
+    /// The hash identifying the scheduled tx, which is the hash of its raw
+    /// header, used for replay protection
+    pub fn hash(&self) -> Hash {
+        self.tx.raw_header_hash()
+    }
+}
+
+/// Storage key prefix of all the scheduled txs
+pub fn scheduled_txs_prefix() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&SCHEDULED_TXS_KEY.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key prefix of the txs scheduled for execution at the given epoch
+pub fn scheduled_txs_epoch_prefix(epoch: Epoch) -> Key {
+    scheduled_txs_prefix()
+        .push(&epoch)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Storage key of a tx scheduled for execution at the given epoch
+pub fn scheduled_tx_key(epoch: Epoch, hash: &Hash) -> Key {
+    scheduled_txs_epoch_prefix(epoch)
+        .push(hash)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns the epoch and the hash of a scheduled tx if the key is a scheduled
+/// tx key
+pub fn is_scheduled_tx_key(key: &Key) -> Option<(Epoch, Hash)> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(epoch), DbKeySeg::StringSeg(hash)]
+            if addr == &ADDRESS && prefix == SCHEDULED_TXS_KEY =>
+        {
+            let epoch = Epoch::parse(epoch.clone()).ok()?;
+            let hash = Hash::parse(hash.clone()).ok()?;
+            Some((epoch, hash))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheduled_tx_key() {
+        let hash = Hash::sha256(b"scheduled tx");
+        let key = scheduled_tx_key(Epoch(5), &hash);
+        assert!(key.split_prefix(&scheduled_txs_prefix()).is_some());
+        assert!(
+            key.split_prefix(&scheduled_txs_epoch_prefix(Epoch(5)))
+                .is_some()
+        );
+        assert!(
+            key.split_prefix(&scheduled_txs_epoch_prefix(Epoch(6)))
+                .is_none()
+        );
+        assert_eq!(is_scheduled_tx_key(&key), Some((Epoch(5), hash)));
+        assert_eq!(is_scheduled_tx_key(&scheduled_txs_prefix()), None);
+    }
+}
@@ -19,7 +19,7 @@ use namada_core::types::address::Address;
 use namada_core::types::chain::ChainId;
 use namada_core::types::key::*;
 use namada_core::types::sign::SignatureIndex;
-use namada_core::types::storage::Epoch;
+use namada_core::types::storage::{BlockHeight, Epoch};
 use namada_core::types::time::DateTimeUtc;
 use namada_core::types::token::MaspDenom;
 use serde::de::Error as SerdeError;
@@ -861,6 +861,8 @@ pub struct Header {
     pub chain_id: ChainId,
     /// The time at which this transaction expires
     pub expiration: Option<DateTimeUtc>,
+    /// The last block height at which this transaction can be included
+    pub expiration_height: Option<BlockHeight>,
     /// A transaction timestamp
     pub timestamp: DateTimeUtc,
     /// The SHA-256 hash of the transaction's code section
@@ -883,6 +885,7 @@ impl Header {
             tx_type,
             chain_id: ChainId::default(),
             expiration: None,
+            expiration_height: None,
             timestamp: DateTimeUtc::now(),
             code_hash: namada_core::types::hash::Hash::default(),
             data_hash: namada_core::types::hash::Hash::default(),
@@ -896,6 +899,12 @@ impl Header {
         hasher
     }
 
+    /// Check if the transaction can't be included in a block at the given
+    /// height anymore
+    pub fn is_expired_at_height(&self, height: BlockHeight) -> bool {
+        matches!(self.expiration_height, Some(exp) if height > exp)
+    }
+
     /// Get the wrapper header if it is present
     pub fn wrapper(&self) -> Option<WrapperTx> {
         if let TxType::Wrapper(wrapper) = &self.tx_type {
@@ -11,6 +11,7 @@ pub mod ibc;
 pub mod key;
 pub mod pgf;
 pub mod proof_of_stake;
+pub mod scheduled;
 pub mod token;
 
 use core::slice;
@@ -103,6 +104,23 @@ impl Ctx {
     pub const unsafe fn new() -> Self {
         Self(())
     }
+
+    /// Read a chunk of at most `len` bytes starting at `offset` of the value
+    /// under the given key, to process large values with bounded memory.
+    /// The chunk is shorter than `len` when the end of the value is reached.
+    /// Returns `None` if the key is not present.
+    pub fn read_bytes_chunk(
+        &self,
+        key: &storage::Key,
+        offset: u64,
+        len: u64,
+    ) -> EnvResult<Option<Vec<u8>>> {
+        let key = key.to_string();
+        let read_result = unsafe {
+            namada_tx_read_chunk(key.as_ptr() as _, key.len() as _, offset, len)
+        };
+        Ok(read_from_buffer(read_result, namada_tx_result_buffer))
+    }
 }
 
 /// Result of `TxEnv`, `namada_storage::StorageRead` or
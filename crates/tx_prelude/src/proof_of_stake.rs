@@ -10,7 +10,8 @@ use namada_proof_of_stake::{
     become_validator, bond_tokens, change_consensus_key,
     change_validator_commission_rate, change_validator_metadata,
     claim_reward_tokens, deactivate_validator, reactivate_validator,
-    redelegate_tokens, unbond_tokens, unjail_validator, withdraw_tokens,
+    redelegate_tokens, set_auto_compound, unbond_tokens, unjail_validator,
+    withdraw_tokens,
 };
 pub use namada_proof_of_stake::{parameters, types};
 use namada_tx::data::pos::BecomeValidator;
@@ -111,6 +112,16 @@ impl Ctx {
         claim_reward_tokens(self, source, validator, current_epoch)
     }
 
+    /// Enable or disable the automatic restaking of the rewards of a bond
+    pub fn set_auto_compound(
+        &mut self,
+        source: Option<&Address>,
+        validator: &Address,
+        enabled: bool,
+    ) -> TxResult {
+        set_auto_compound(self, source, validator, enabled)
+    }
+
     /// Attempt to initialize a validator account. On success, returns the
     /// initialized validator account's address.
     pub fn become_validator(
@@ -0,0 +1,22 @@
+//! Txs scheduled for execution by the protocol at a future epoch
+
+use namada_tx::data::scheduled::{scheduled_tx_key, ScheduledTx, ADDRESS};
+
+use super::*;
+
+/// Schedule a tx for execution at the start of its epoch. The execution fee
+/// is paid upfront from the payer's balance of the native token.
+pub fn schedule_tx(ctx: &mut Ctx, scheduled: ScheduledTx) -> TxResult {
+    let native_token = ctx.get_native_token()?;
+    token::undenominated_transfer(
+        ctx,
+        &scheduled.payer,
+        &ADDRESS,
+        &native_token,
+        scheduled.fee,
+    )?;
+    ctx.insert_verifier(&scheduled.payer)?;
+    let key = scheduled_tx_key(scheduled.epoch, &scheduled.hash());
+    ctx.write(&key, scheduled)?;
+    Ok(())
+}
@@ -4,7 +4,7 @@ use namada_proof_of_stake::token::storage_key::{
 };
 pub use namada_token::*;
 
-use crate::{log_string, Ctx, StorageRead, StorageWrite, TxResult};
+use crate::{log_string, Ctx, StorageRead, StorageWrite, TxEnv, TxResult};
 
 #[allow(clippy::too_many_arguments)]
 /// A token transfer that can be used in a transaction.
@@ -58,6 +58,29 @@ pub fn undenominated_transfer(
     Ok(())
 }
 
+/// A token transfer whose amount is locked in the target's balance with a
+/// vesting schedule. The target must not have any tokens that are still
+/// locked by a previous schedule and it must accept the schedule by signing
+/// the tx.
+pub fn vesting_transfer(ctx: &mut Ctx, transfer: &VestingTransfer) -> TxResult {
+    let amount = denom_to_amount(transfer.amount, &transfer.token, ctx)?;
+    undenominated_transfer(
+        ctx,
+        &transfer.source,
+        &transfer.target,
+        &transfer.token,
+        amount,
+    )?;
+    let schedule = VestingSchedule {
+        amount,
+        start: transfer.start,
+        end: transfer.end,
+    };
+    write_vesting_schedule(ctx, &transfer.token, &transfer.target, schedule)?;
+    ctx.insert_verifier(&transfer.target)?;
+    Ok(())
+}
+
 /// Mint that can be used in a transaction.
 pub fn mint(
     ctx: &mut Ctx,
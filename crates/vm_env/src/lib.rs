@@ -142,6 +142,21 @@ pub mod tx {
             transaction_len: u64,
         ) -> i64;
     }
+
+    // These host functions are available since the API version 2.
+    #[link(wasm_import_module = "namada_tx_v2")]
+    extern "C" {
+        // Read a chunk of at most `len` bytes starting at `offset` of a
+        // variable-length value, returns the size of the chunk (can be 0 when
+        // the end of the value is reached), or -1 if the key is not present.
+        // If a value is found, the chunk will be placed in the result buffer.
+        pub fn namada_tx_read_chunk(
+            key_ptr: u64,
+            key_len: u64,
+            offset: u64,
+            len: u64,
+        ) -> i64;
+    }
 }
 
 /// Validity predicate environment imports
@@ -200,6 +215,16 @@ pub mod vp {
         // buffer for it before we know its size.
         pub fn namada_vp_iter_next(iter_id: u64) -> i64;
 
+        // Get a page of the keys changed by the tx under the given prefix,
+        // ordered by storage keys. Returns the size of the Borsh encoded keys,
+        // which are placed in the result buffer.
+        pub fn namada_vp_get_changed_keys(
+            prefix_ptr: u64,
+            prefix_len: u64,
+            offset: u64,
+            limit: u64,
+        ) -> i64;
+
         // Get the chain ID
         pub fn namada_vp_get_chain_id(result_ptr: u64);
 
@@ -259,6 +284,34 @@ pub mod vp {
         /// Charge the provided amount of gas for the current vp
         pub fn namada_vp_charge_gas(used_gas: u64);
     }
+
+    // These host functions are available since the API version 2.
+    #[link(wasm_import_module = "namada_vp_v2")]
+    extern "C" {
+        // Read a chunk of at most `len` bytes starting at `offset` of a
+        // variable-length prior state, returns the size of the chunk (can be
+        // 0 when the end of the value is reached), or -1 if the key is not
+        // present. If a value is found, the chunk will be placed in the
+        // result buffer.
+        pub fn namada_vp_read_chunk_pre(
+            key_ptr: u64,
+            key_len: u64,
+            offset: u64,
+            len: u64,
+        ) -> i64;
+
+        // Read a chunk of at most `len` bytes starting at `offset` of a
+        // variable-length posterior state, returns the size of the chunk (can
+        // be 0 when the end of the value is reached), or -1 if the key is not
+        // present. If a value is found, the chunk will be placed in the
+        // result buffer.
+        pub fn namada_vp_read_chunk_post(
+            key_ptr: u64,
+            key_len: u64,
+            offset: u64,
+            len: u64,
+        ) -> i64;
+    }
 }
 
 /// This function is a helper to handle the second step of reading var-len
@@ -0,0 +1,55 @@
+//! Evidence of misbehaviors of validators in their vote extensions, that can
+//! be submitted by anyone to have the validators slashed.
+
+use namada_core::borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use namada_core::types::address::Address;
+use namada_core::types::storage::BlockHeight;
+
+use crate::{bridge_pool_roots, ethereum_events};
+
+/// Evidence of a validator that signed two different vote extensions of the
+/// same kind at the same block height.
+#[derive(Clone, Debug, BorshSerialize, BorshDeserialize, BorshSchema)]
+pub enum DuplicateVextEvidence {
+    /// Two different Ethereum events vote extensions
+    EthereumEvents(ethereum_events::SignedVext, ethereum_events::SignedVext),
+    /// Two different Bridge pool root vote extensions
+    BridgePoolRoots(
+        bridge_pool_roots::SignedVext,
+        bridge_pool_roots::SignedVext,
+    ),
+}
+
+impl DuplicateVextEvidence {
+    /// The validators that signed the first and the second vote extensions
+    pub fn validators(&self) -> (&Address, &Address) {
+        match self {
+            Self::EthereumEvents(first, second) => {
+                (&first.data.validator_addr, &second.data.validator_addr)
+            }
+            Self::BridgePoolRoots(first, second) => {
+                (&first.data.validator_addr, &second.data.validator_addr)
+            }
+        }
+    }
+
+    /// The block heights of the first and the second vote extensions
+    pub fn block_heights(&self) -> (BlockHeight, BlockHeight) {
+        match self {
+            Self::EthereumEvents(first, second) => {
+                (first.data.block_height, second.data.block_height)
+            }
+            Self::BridgePoolRoots(first, second) => {
+                (first.data.block_height, second.data.block_height)
+            }
+        }
+    }
+
+    /// Check if the signed data of the two vote extensions differ
+    pub fn is_conflicting(&self) -> bool {
+        match self {
+            Self::EthereumEvents(first, second) => first.data != second.data,
+            Self::BridgePoolRoots(first, second) => first.data != second.data,
+        }
+    }
+}
@@ -2,6 +2,7 @@
 
 pub mod bridge_pool_roots;
 pub mod ethereum_events;
+pub mod evidence;
 pub mod validator_set_update;
 
 use namada_core::borsh::{
@@ -105,6 +106,8 @@ ethereum_tx_data_declare! {
         BridgePoolVext(bridge_pool_roots::SignedVext),
         /// Validator set update signed by some validator
         ValSetUpdateVext(validator_set_update::SignedVext),
+        /// Evidence of a validator that signed conflicting vote extensions
+        DuplicateVextEvidence(evidence::DuplicateVextEvidence),
     }
 }
 
@@ -166,6 +169,7 @@ impl EthereumTxData {
             EthEventsVext,
             BridgePoolVext,
             ValSetUpdateVext,
+            DuplicateVextEvidence,
         }
     }
 
@@ -199,6 +203,10 @@ impl EthereumTxData {
                 BorshDeserialize::try_from_slice(data)
                     .map(EthereumTxData::ValSetUpdateVext)
             },
+            ProtocolTxType::DuplicateVextEvidence => |data| {
+                BorshDeserialize::try_from_slice(data)
+                    .map(EthereumTxData::DuplicateVextEvidence)
+            },
         };
         deserialize(data)
             .map_err(|err| TxError::Deserialization(err.to_string()))
@@ -98,6 +98,16 @@ where
         prefix: &Key,
     ) -> Result<Self::PrefixIter<'iter>, namada_storage::Error>;
 
+    /// Get a page of the storage keys changed by the transaction under the
+    /// given prefix, ordered by storage keys. The keys are skipped up to the
+    /// `offset` and at most `limit` keys are returned.
+    fn get_changed_keys(
+        &self,
+        prefix: &Key,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<Key>, namada_storage::Error>;
+
     /// Evaluate a validity predicate with given data. The address, changed
     /// storage keys and verifiers will have the same values as the input to
     /// caller's validity predicate.
@@ -216,4 +226,70 @@ where
     ) -> Result<bool, namada_storage::Error> {
         self.post().has_key(key)
     }
+
+    /// Iterate the storage keys changed by the transaction under the given
+    /// prefix, ordered by storage keys. The keys are fetched in pages of
+    /// `page_size` keys with [`VpEnv::get_changed_keys`].
+    fn iter_changed_keys(
+        &'view self,
+        prefix: &Key,
+        page_size: u64,
+    ) -> ChangedKeys<'view, Self>
+    where
+        Self: Sized,
+    {
+        ChangedKeys {
+            ctx: self,
+            prefix: prefix.clone(),
+            page_size,
+            offset: 0,
+            page: Vec::new().into_iter(),
+            done: false,
+        }
+    }
+}
+
+/// Iterator over the storage keys changed by the transaction under a prefix,
+/// created with [`VpEnv::iter_changed_keys`]
+#[derive(Debug)]
+pub struct ChangedKeys<'view, CTX> {
+    ctx: &'view CTX,
+    prefix: Key,
+    page_size: u64,
+    offset: u64,
+    page: std::vec::IntoIter<Key>,
+    done: bool,
+}
+
+impl<'view, CTX> Iterator for ChangedKeys<'view, CTX>
+where
+    CTX: VpEnv<'view>,
+{
+    type Item = Result<Key, namada_storage::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(key) = self.page.next() {
+            return Some(Ok(key));
+        }
+        if self.done {
+            return None;
+        }
+        match self.ctx.get_changed_keys(
+            &self.prefix,
+            self.offset,
+            self.page_size,
+        ) {
+            Ok(page) => {
+                // A partial page is the last one
+                self.done = (page.len() as u64) < self.page_size;
+                self.offset += page.len() as u64;
+                self.page = page.into_iter();
+                self.page.next().map(Ok)
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
 }
@@ -196,6 +196,50 @@ impl Ctx {
     pub fn post(&self) -> CtxPostStorageRead<'_> {
         CtxPostStorageRead { _ctx: self }
     }
+
+    /// Read a chunk of at most `len` bytes starting at `offset` of the value
+    /// under the given key in the prior storage, to process large values
+    /// with bounded memory. The chunk is shorter than `len` when the end of
+    /// the value is reached. Returns `None` if the key is not present.
+    pub fn read_bytes_chunk_pre(
+        &self,
+        key: &storage::Key,
+        offset: u64,
+        len: u64,
+    ) -> EnvResult<Option<Vec<u8>>> {
+        let key = key.to_string();
+        let read_result = unsafe {
+            namada_vp_read_chunk_pre(
+                key.as_ptr() as _,
+                key.len() as _,
+                offset,
+                len,
+            )
+        };
+        Ok(read_from_buffer(read_result, namada_vp_result_buffer))
+    }
+
+    /// Read a chunk of at most `len` bytes starting at `offset` of the value
+    /// under the given key in the posterior storage, to process large values
+    /// with bounded memory. The chunk is shorter than `len` when the end of
+    /// the value is reached. Returns `None` if the key is not present.
+    pub fn read_bytes_chunk_post(
+        &self,
+        key: &storage::Key,
+        offset: u64,
+        len: u64,
+    ) -> EnvResult<Option<Vec<u8>>> {
+        let key = key.to_string();
+        let read_result = unsafe {
+            namada_vp_read_chunk_post(
+                key.as_ptr() as _,
+                key.len() as _,
+                offset,
+                len,
+            )
+        };
+        Ok(read_from_buffer(read_result, namada_vp_result_buffer))
+    }
 }
 
 /// Read access to the prior storage (state before tx execution) via
@@ -331,6 +375,28 @@ impl<'view> VpEnv<'view> for Ctx {
         iter_prefix_pre_impl(prefix)
     }
 
+    fn get_changed_keys(
+        &self,
+        prefix: &storage::Key,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<storage::Key>, Error> {
+        let prefix = prefix.to_string();
+        let read_result = unsafe {
+            namada_vp_get_changed_keys(
+                prefix.as_ptr() as _,
+                prefix.len() as _,
+                offset,
+                limit,
+            )
+        };
+        match read_from_buffer(read_result, namada_vp_result_buffer) {
+            Some(value) => Ok(Vec::<storage::Key>::try_from_slice(&value[..])
+                .expect("The conversion shouldn't fail")),
+            None => Ok(Vec::new()),
+        }
+    }
+
     fn eval(&self, vp_code_hash: Hash, input_data: Tx) -> Result<bool, Error> {
         let input_data_bytes = borsh::to_vec(&input_data).unwrap();
         let result = unsafe {
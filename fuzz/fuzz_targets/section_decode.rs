@@ -0,0 +1,19 @@
+//! Fuzz the decoding of tx sections. Decoding must never panic, decoding a
+//! re-encoded section must give back the same section and its hash, which
+//! the signatures are made over, must not change.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use namada_core::borsh::{BorshDeserialize, BorshSerializeExt};
+use namada_tx::Section;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(section) = Section::try_from_slice(data) {
+        let bytes = section.serialize_to_vec();
+        let decoded = Section::try_from_slice(&bytes)
+            .expect("A re-encoded section must be decodable");
+        assert_eq!(decoded.serialize_to_vec(), bytes);
+        assert_eq!(decoded.get_hash(), section.get_hash());
+    }
+});
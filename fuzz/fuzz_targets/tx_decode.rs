@@ -0,0 +1,37 @@
+//! Fuzz the decoding of txs from the protobuf bytes received by the ledger
+//! and from their Borsh encoding. Decoding must never panic and decoding a
+//! re-encoded tx must give back the same tx in both encodings.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use namada_core::borsh::{BorshDeserialize, BorshSerializeExt};
+use namada_tx::Tx;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(tx) = Tx::try_from(data) {
+        let proto_bytes = tx.to_bytes();
+        let decoded = Tx::try_from(proto_bytes.as_slice())
+            .expect("A re-encoded tx must be decodable");
+        assert_eq!(decoded.to_bytes(), proto_bytes);
+        check_borsh_round_trip(&tx);
+        assert_eq!(decoded.sechashes(), tx.sechashes());
+        assert_eq!(decoded.raw_header_hash(), tx.raw_header_hash());
+    }
+
+    if let Ok(tx) = Tx::try_from_slice(data) {
+        check_borsh_round_trip(&tx);
+        // The protobuf encoding wraps the Borsh encoding
+        let decoded = Tx::try_from(tx.to_bytes().as_slice())
+            .expect("A tx wrapped in protobuf must be decodable");
+        assert_eq!(decoded.serialize_to_vec(), tx.serialize_to_vec());
+    }
+});
+
+/// Check that decoding the Borsh encoding of a tx is stable
+fn check_borsh_round_trip(tx: &Tx) {
+    let bytes = tx.serialize_to_vec();
+    let decoded =
+        Tx::try_from_slice(&bytes).expect("A re-encoded tx must be decodable");
+    assert_eq!(decoded.serialize_to_vec(), bytes);
+}
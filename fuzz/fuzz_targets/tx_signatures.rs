@@ -0,0 +1,45 @@
+//! Fuzz the verification of the signatures of txs. The verification must
+//! never panic and its result must not change when the tx is re-encoded, as
+//! the signatures are made over the hashes of the encoded sections.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use namada_core::borsh::{BorshDeserialize, BorshSerializeExt};
+use namada_core::types::account::AccountPublicKeysMap;
+use namada_tx::{Section, Signer, Tx};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(tx) = Tx::try_from_slice(data) else {
+        return;
+    };
+    let decoded = Tx::try_from_slice(&tx.serialize_to_vec())
+        .expect("A re-encoded tx must be decodable");
+
+    // Verifies the signature of wrapper and protocol txs
+    assert_eq!(tx.validate_tx().is_ok(), decoded.validate_tx().is_ok());
+
+    for section in &tx.sections {
+        let Section::Signature(signature) = section else {
+            continue;
+        };
+        let (public_keys, signer) = match &signature.signer {
+            Signer::PubKeys(public_keys) => (public_keys.clone(), None),
+            Signer::Address(address) => (vec![], Some(address.clone())),
+        };
+        let threshold =
+            u8::try_from(public_keys.len()).unwrap_or(u8::MAX).max(1);
+        let verify = |tx: &Tx| {
+            tx.verify_signatures(
+                &signature.targets,
+                AccountPublicKeysMap::from_iter(public_keys.clone()),
+                &signer,
+                threshold,
+                None,
+                || Ok(()),
+            )
+            .is_ok()
+        };
+        assert_eq!(verify(&tx), verify(&decoded));
+    }
+});
@@ -2,6 +2,8 @@
 pub mod tx_become_validator;
 #[cfg(feature = "tx_bond")]
 pub mod tx_bond;
+#[cfg(feature = "tx_bridge_pause_vote")]
+pub mod tx_bridge_pause_vote;
 #[cfg(feature = "tx_bridge_pool")]
 pub mod tx_bridge_pool;
 #[cfg(feature = "tx_change_consensus_key")]
@@ -12,6 +14,8 @@ pub mod tx_change_validator_commission;
 pub mod tx_change_validator_metadata;
 #[cfg(feature = "tx_claim_rewards")]
 pub mod tx_claim_rewards;
+#[cfg(feature = "tx_convert_nuts")]
+pub mod tx_convert_nuts;
 #[cfg(feature = "tx_deactivate_validator")]
 pub mod tx_deactivate_validator;
 #[cfg(feature = "tx_ibc")]
@@ -28,6 +32,10 @@ pub mod tx_redelegate;
 pub mod tx_resign_steward;
 #[cfg(feature = "tx_reveal_pk")]
 pub mod tx_reveal_pk;
+#[cfg(feature = "tx_schedule")]
+pub mod tx_schedule;
+#[cfg(feature = "tx_set_auto_compound")]
+pub mod tx_set_auto_compound;
 #[cfg(feature = "tx_transfer")]
 pub mod tx_transfer;
 #[cfg(feature = "tx_unbond")]
@@ -36,8 +44,14 @@ pub mod tx_unbond;
 pub mod tx_unjail_validator;
 #[cfg(feature = "tx_update_account")]
 pub mod tx_update_account;
+#[cfg(feature = "tx_update_account_keys")]
+pub mod tx_update_account_keys;
 #[cfg(feature = "tx_update_steward_commission")]
 pub mod tx_update_steward_commission;
+#[cfg(feature = "tx_update_vote_proxy")]
+pub mod tx_update_vote_proxy;
+#[cfg(feature = "tx_vesting_transfer")]
+pub mod tx_vesting_transfer;
 #[cfg(feature = "tx_vote_proposal")]
 pub mod tx_vote_proposal;
 #[cfg(feature = "tx_withdraw")]
@@ -0,0 +1,18 @@
+//! A tx for a validator to vote to pause the Ethereum bridge.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 340000)]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let validator = Address::try_from_slice(&data[..])
+        .wrap_err("failed to decode an Address")?;
+    let current_epoch = ctx.get_block_epoch()?;
+    // The validator's VP must authorize the vote
+    ctx.insert_verifier(&validator)?;
+    ctx.write(
+        &eth_bridge::emergency_pause::vote_key(current_epoch, &validator),
+        (),
+    )
+}
@@ -2,7 +2,8 @@
 //! into the bridge pool.
 use eth_bridge_pool::{GasFee, PendingTransfer, TransferToEthereum};
 use namada_tx_prelude::eth_bridge_pool::{
-    get_pending_key, BRIDGE_POOL_ADDRESS,
+    get_pending_key, get_transfer_status_key, TransferStatus,
+    BRIDGE_POOL_ADDRESS,
 };
 use namada_tx_prelude::parameters::native_erc20_key;
 use namada_tx_prelude::*;
@@ -62,6 +63,10 @@ fn apply_tx(ctx: &mut Ctx, signed: Tx) -> TxResult {
     let pending_key = get_pending_key(&transfer);
     ctx.write_bytes(&pending_key, transfer.serialize_to_vec())
         .wrap_err("Could not write transfer to bridge pool")?;
+    // track the status of the transfer
+    let status_key = get_transfer_status_key(&transfer.keccak256());
+    ctx.write(&status_key, TransferStatus::Pending)
+        .wrap_err("Could not write the status of the transfer")?;
     Ok(())
 }
 
@@ -0,0 +1,32 @@
+//! A tx to convert the Non-Usable Tokens (NUTs) of an ERC20 asset into the
+//! wrapped ERC20s of the same asset. The conversion is validated against the
+//! token cap of the asset by the Ethereum bridge VP.
+
+use eth_bridge::nuts::ConvertNuts;
+use eth_bridge_pool::{erc20_nut_address, erc20_token_address};
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1038546)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let ConvertNuts {
+        owner,
+        asset,
+        amount,
+    } = ConvertNuts::try_from_slice(&data[..])
+        .wrap_err("failed to decode ConvertNuts")?;
+
+    debug_log!("convert the NUTs of {asset} of {owner}");
+
+    // The owner's VP must authorize spending its NUTs
+    ctx.insert_verifier(&owner)?;
+    // The Ethereum bridge VP must authorize the minting of wrapped ERC20s
+    ctx.insert_verifier(&eth_bridge::ADDRESS)?;
+
+    token::burn(ctx, &owner, &erc20_nut_address(&asset), amount)?;
+    token::credit_tokens(ctx, &erc20_token_address(&asset), &owner, amount)
+}
@@ -0,0 +1,25 @@
+//! A tx to schedule another tx for execution by the protocol at the start of
+//! a future epoch, paying its fee upfront.
+//! This tx uses `transaction::scheduled::ScheduledTx` as its input as
+//! declared in `namada_tx` crate.
+
+use namada_tx_prelude::transaction::scheduled::ScheduledTx;
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1119469)]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let scheduled = ScheduledTx::try_from_slice(&data[..])
+        .wrap_err("failed to decode a ScheduledTx")?;
+    debug_log!(
+        "apply_tx called to schedule tx {} at epoch {}",
+        scheduled.hash(),
+        scheduled.epoch
+    );
+
+    scheduled::schedule_tx(ctx, scheduled)
+}
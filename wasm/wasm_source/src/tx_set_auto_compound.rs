@@ -0,0 +1,18 @@
+//! A tx for a delegator or a validator to enable or disable the automatic
+//! restaking of the PoS rewards of a bond at every epoch.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 260000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data")?;
+    let transaction::pos::SetAutoCompound {
+        validator,
+        source,
+        enabled,
+    } = transaction::pos::SetAutoCompound::try_from_slice(&data[..])
+        .wrap_err("failed to decode SetAutoCompound")?;
+
+    ctx.set_auto_compound(source.as_ref(), &validator, enabled)
+}
@@ -0,0 +1,21 @@
+//! A tx to replace the public keys and the signature threshold of an
+//! established account, without updating its validity predicate. The change
+//! is authorized by the VP of the account with its current keys and
+//! threshold.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 260000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let tx_data = account::UpdateAccountKeys::try_from_slice(&data[..])
+        .wrap_err("failed to decode UpdateAccountKeys")?;
+
+    debug_log!("update the keys of: {:#?}", tx_data.addr);
+
+    account::update_account_keys(ctx, &tx_data)
+}
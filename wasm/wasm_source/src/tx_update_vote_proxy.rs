@@ -0,0 +1,19 @@
+//! A tx for a delegator to set or remove the address that votes on
+//! governance proposals with their bonded stake.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 260000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let tx_data = governance::VoteProxyData::try_from_slice(&data[..])
+        .wrap_err("failed to decode VoteProxyData")?;
+
+    debug_log!("apply_tx called to update a governance vote proxy");
+
+    governance::update_vote_proxy(ctx, tx_data)
+}
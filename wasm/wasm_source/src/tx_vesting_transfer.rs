@@ -0,0 +1,20 @@
+//! A tx for a token transfer whose amount is locked in the target's balance
+//! with a vesting schedule.
+//! This tx uses `token::VestingTransfer` wrapped inside `SignedTxData`
+//! as its input as declared in `namada` crate.
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1703358)]
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let transfer = token::VestingTransfer::try_from_slice(&data[..])
+        .wrap_err("failed to decode token::VestingTransfer")?;
+    debug_log!("apply_tx called with vesting transfer: {:#?}", transfer);
+
+    token::vesting_transfer(ctx, &transfer)
+}
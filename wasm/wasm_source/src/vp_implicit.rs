@@ -28,6 +28,7 @@ enum KeyType<'a> {
     Masp,
     PgfSteward(&'a Address),
     GovernanceVote(&'a Address),
+    GovernanceVoteProxy(&'a Address),
     Ibc,
     Unknown,
 }
@@ -56,6 +57,14 @@ impl<'a> From<&'a storage::Key> for KeyType<'a> {
             } else {
                 Self::Unknown
             }
+        } else if let Some(delegator) =
+            gov_storage::keys::is_vote_proxy_key(key)
+        {
+            Self::GovernanceVoteProxy(delegator)
+        } else if let Some((_proxy, delegator)) =
+            gov_storage::keys::is_proxy_delegator_key(key)
+        {
+            Self::GovernanceVoteProxy(delegator)
         } else if token::storage_key::is_masp_key(key) {
             Self::Masp
         } else if ibc::is_ibc_key(key) {
@@ -154,6 +163,9 @@ fn validate_tx(
             KeyType::PoS => validate_pos_changes(ctx, &addr, key, &valid_sig)?,
             KeyType::PgfSteward(address) => address != &addr || *valid_sig,
             KeyType::GovernanceVote(voter) => voter != &addr || *valid_sig,
+            KeyType::GovernanceVoteProxy(delegator) => {
+                delegator != &addr || *valid_sig
+            }
             KeyType::Masp | KeyType::Ibc => true,
             KeyType::Unknown => {
                 // Unknown changes require a valid signature
@@ -258,6 +270,14 @@ fn validate_pos_changes(
         false
     };
 
+    let is_valid_auto_compound_change = || {
+        if let Some(bond_id) = storage_key::is_auto_compound_key(key) {
+            // Auto-compounding of this address' bonds must be signed
+            return &bond_id.source != owner || **valid_sig;
+        }
+        false
+    };
+
     let is_valid_redelegation = || {
         if storage_key::is_validator_redelegations_key(key) {
             return true;
@@ -283,6 +303,7 @@ fn validate_pos_changes(
         || storage_key::is_total_consensus_stake_key(key)
         || is_valid_state_change()?
         || is_valid_reward_claim()
+        || is_valid_auto_compound_change()
         || is_valid_redelegation()
         || **valid_sig)
 }
@@ -17,17 +17,18 @@ use namada_vp_prelude::*;
 use once_cell::unsync::Lazy;
 use proof_of_stake::storage::{read_pos_params, validator_state_handle};
 use proof_of_stake::storage_key::{
-    is_below_capacity_validator_set_key, is_bond_epoched_meta_key, is_bond_key,
-    is_consensus_keys_key, is_consensus_validator_set_key,
-    is_delegator_redelegations_key, is_last_pos_reward_claim_epoch_key,
-    is_pos_key, is_rewards_counter_key, is_total_consensus_stake_key,
-    is_total_deltas_key, is_unbond_key, is_validator_address_raw_hash_key,
-    is_validator_addresses_key, is_validator_commission_rate_key,
-    is_validator_deltas_key, is_validator_eth_cold_key_key,
-    is_validator_eth_hot_key_key, is_validator_max_commission_rate_change_key,
-    is_validator_metadata_key, is_validator_redelegations_key,
-    is_validator_set_positions_key, is_validator_state_epoched_meta_key,
-    is_validator_state_key, is_validator_total_bond_or_unbond_key,
+    is_auto_compound_key, is_below_capacity_validator_set_key,
+    is_bond_epoched_meta_key, is_bond_key, is_consensus_keys_key,
+    is_consensus_validator_set_key, is_delegator_redelegations_key,
+    is_last_pos_reward_claim_epoch_key, is_pos_key, is_rewards_counter_key,
+    is_total_consensus_stake_key, is_total_deltas_key, is_unbond_key,
+    is_validator_address_raw_hash_key, is_validator_addresses_key,
+    is_validator_commission_rate_key, is_validator_deltas_key,
+    is_validator_eth_cold_key_key, is_validator_eth_hot_key_key,
+    is_validator_max_commission_rate_change_key, is_validator_metadata_key,
+    is_validator_redelegations_key, is_validator_set_positions_key,
+    is_validator_state_epoched_meta_key, is_validator_state_key,
+    is_validator_total_bond_or_unbond_key,
 };
 
 enum KeyType<'a> {
@@ -39,6 +40,7 @@ enum KeyType<'a> {
     Masp,
     PgfSteward(&'a Address),
     GovernanceVote(&'a Address),
+    GovernanceVoteProxy(&'a Address),
     Ibc,
     Unknown,
 }
@@ -63,6 +65,14 @@ impl<'a> From<&'a storage::Key> for KeyType<'a> {
             } else {
                 Self::Unknown
             }
+        } else if let Some(delegator) =
+            gov_storage::keys::is_vote_proxy_key(key)
+        {
+            Self::GovernanceVoteProxy(delegator)
+        } else if let Some((_proxy, delegator)) =
+            gov_storage::keys::is_proxy_delegator_key(key)
+        {
+            Self::GovernanceVoteProxy(delegator)
         } else if let Some(address) = pgf_storage::keys::is_stewards_key(key) {
             Self::PgfSteward(address)
         } else if let Some(address) = key.is_validity_predicate() {
@@ -138,6 +148,9 @@ fn validate_tx(
             KeyType::PoS => validate_pos_changes(ctx, &addr, key, &valid_sig)?,
             KeyType::PgfSteward(address) => address != &addr || *valid_sig,
             KeyType::GovernanceVote(voter) => voter != &addr || *valid_sig,
+            KeyType::GovernanceVoteProxy(delegator) => {
+                delegator != &addr || *valid_sig
+            }
             KeyType::Vp(owner) => {
                 let has_post: bool = ctx.has_key_post(key)?;
                 if owner == &addr {
@@ -296,6 +309,14 @@ fn validate_pos_changes(
         false
     };
 
+    let is_valid_auto_compound_change = || {
+        if let Some(bond_id) = is_auto_compound_key(key) {
+            // Auto-compounding of this address' bonds must be signed
+            return &bond_id.source != owner || **valid_sig;
+        }
+        false
+    };
+
     let is_valid_redelegation = || {
         if is_validator_redelegations_key(key) {
             return true;
@@ -333,6 +354,7 @@ fn validate_pos_changes(
         || is_total_consensus_stake_key(key)
         || is_valid_state_change()?
         || is_valid_reward_claim()
+        || is_valid_auto_compound_change()
         || is_valid_redelegation()
         || is_valid_commission_rate_change()
         || is_valid_metadata_change()